@@ -11,21 +11,26 @@
 // Server-side: Use actual implementations
 #[cfg(not(target_arch = "wasm32"))]
 pub use crate::core::{
-	AdminDatabase, AdminRecord, AdminSite, ExportFormat, ImportBuilder, ImportError, ImportFormat,
-	ImportResult, ModelAdmin, ModelAdminConfig, ModelAdminConfigBuilder,
+	AdminDatabase, AdminRecord, AdminSite, BackgroundExporter, ExportFormat, ImportBuilder,
+	ImportError, ImportFormat, ImportResult, ModelAdmin, ModelAdminConfig, ModelAdminConfigBuilder,
+	RequestUser,
 };
 
 // WASM: Use stub types
 #[cfg(target_arch = "wasm32")]
 pub use crate::types::{
-	AdminDatabase, AdminRecord, AdminSite, ExportFormat, ImportBuilder, ImportError, ImportFormat,
-	ImportResult, ModelAdmin,
+	AdminDatabase, AdminRecord, AdminSite, BackgroundExporter, ExportFormat, ImportBuilder,
+	ImportError, ImportFormat, ImportResult, ModelAdmin, RequestUser,
 };
 
 // Re-export shared types (DTOs) that are always from reinhardt-admin-types
 pub use crate::types::{
 	AdminError, BulkDeleteRequest, BulkDeleteResponse, ColumnInfo, DashboardResponse,
-	DetailResponse, ExportFormat as ExportFormatRequest, ExportResponse, FieldInfo, FieldType,
-	FieldsResponse, FilterChoice, FilterInfo, FilterType, ImportResponse, ListQueryParams,
-	ListResponse, ModelInfo, MutationRequest, MutationResponse,
+	DashboardWidgetsRequest, DashboardWidgetsResponse, DetailResponse,
+	ExportFormat as ExportFormatRequest, ExportJobResponse, ExportJobStatus, ExportResponse,
+	FieldChangeInfo, FieldInfo, FieldType, FieldsResponse, FilterChoice, FilterInfo, FilterType,
+	GenericInlineResponse, GenericInlinesResponse, HistoryEntryResponse, ImportIssue,
+	ImportIssueSeverity, ImportResponse, ImportValidationReport, ListPreferences, ListQueryParams,
+	ListResponse, ModelInfo, MutationRequest, MutationResponse, NavLinkInfo, ObjectHistoryResponse,
+	SavedFilter, ThemeResponse, WidgetResponse,
 };
@@ -5,27 +5,56 @@
 //! - ModelAdmin trait and configuration
 //! - AdminSite registry
 //! - Database operations
-//! - Import/Export functionality
+//! - Dashboard widgets
+//! - Generic foreign keys and generic inlines
+//! - Change history with diff and revert
+//! - Per-user list view preferences
+//! - Theming and template overrides
+//! - Import/Export functionality (including background exports)
 
+pub mod dashboard;
 pub mod database;
 pub mod export;
+pub mod export_job;
+pub mod generic;
+pub mod history;
 pub mod import;
 pub mod model_admin;
+pub mod preferences;
 pub mod router;
 pub mod site;
+pub mod theme;
 
 // Re-exports
 pub use crate::types::{
-	AdminError, AdminResult, BulkDeleteRequest, BulkDeleteResponse, ColumnInfo, DashboardResponse,
-	DetailResponse, ExportFormat as TypesExportFormat, FieldInfo, FieldType, FilterChoice,
-	FilterInfo, FilterType, ImportResponse, ListQueryParams, ListResponse, ModelInfo,
-	MutationRequest, MutationResponse,
+	AdminError, AdminResult, BulkDeleteRequest, BulkDeleteResponse, ChartType, ColumnInfo,
+	DashboardResponse, DashboardWidgetsRequest, DashboardWidgetsResponse, DataPoint,
+	DetailResponse, ExportFormat as TypesExportFormat, ExportJobResponse, ExportJobStatus,
+	FieldChangeInfo, FieldInfo, FieldType, FilterChoice, FilterInfo, FilterType,
+	GenericInlineResponse, GenericInlinesResponse, HistoryEntryResponse, ImportIssue,
+	ImportIssueSeverity, ImportResponse, ImportValidationReport, ListPreferences, ListQueryParams,
+	ListResponse, ModelInfo, MutationRequest, MutationResponse, NavLinkInfo, ObjectHistoryResponse,
+	SavedFilter, ThemeResponse, WidgetData, WidgetResponse,
+};
+pub use dashboard::{
+	ChartWidget, CustomWidget, DashboardWidget, DateRange, StatWidget, TimeBucket, WidgetProvider,
+	WidgetQuery, WidgetSource,
 };
 pub use database::{AdminDatabase, AdminRecord};
 pub use export::{CsvExporter, ExportBuilder, ExportConfig, ExportFormat, JsonExporter};
+pub use export_job::{
+	BackgroundExporter, ExportContext, ExportDispatch, ExportDownloadHandler, ExportJobStore,
+	ExportTaskFactory, ExportUrlSigner,
+};
+pub use generic::{GenericForeignKeyConfig, GenericInline, GenericTarget};
+pub use history::{AuditLog, AuditLogEntry, ChangeAction, FieldChange, compute_changes};
 pub use import::{
 	CsvImporter, ImportBuilder, ImportConfig, ImportError, ImportFormat, ImportResult, JsonImporter,
 };
 pub use model_admin::{ModelAdmin, ModelAdminConfig, ModelAdminConfigBuilder};
-pub use router::{AdminRouter, admin_routes};
+pub use preferences::{
+	DatabaseListPreferenceStore, InMemoryListPreferenceStore, ListPreferenceStore, RequestUser,
+};
+pub use router::{AdminIndexHandler, AdminRouter, admin_routes};
 pub use site::{AdminSite, AdminSiteConfig};
+pub use theme::{NavLink, TemplateContext, TemplateLoader, ThemeConfig};
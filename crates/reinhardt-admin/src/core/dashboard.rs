@@ -0,0 +1,785 @@
+//! Dashboard widgets backed by live database queries
+//!
+//! This module defines the widgets shown on the admin dashboard and the
+//! [`WidgetProvider`] that computes their data through [`AdminDatabase`].
+//!
+//! Three widget kinds are supported:
+//! - [`StatWidget`]: a single aggregated number (e.g. "Users registered")
+//! - [`ChartWidget`]: a series of labelled data points (e.g. "Orders per day")
+//! - [`CustomWidget`]: data computed by a [`WidgetSource`] outside the
+//!   database, such as the throttling analytics widgets in [`throttling`]
+//!   (requires the `throttling` feature)
+//!
+//! Widget data is computed from a [`WidgetQuery`], which describes the
+//! aggregation to run (total count, counts over time, or a group-by breakdown).
+//! Results are cached per widget and date range for a configurable TTL.
+//!
+//! # Examples
+//!
+//! ```
+//! use reinhardt_admin::core::dashboard::{
+//!     ChartWidget, StatWidget, TimeBucket, WidgetProvider, WidgetQuery,
+//! };
+//! use reinhardt_admin::types::ChartType;
+//!
+//! let provider = WidgetProvider::new()
+//!     .with_stat(StatWidget::new("users", "Users", WidgetQuery::count("users")))
+//!     .with_chart(ChartWidget::new(
+//!         "signups",
+//!         "Signups per day",
+//!         ChartType::Line,
+//!         WidgetQuery::count_over_time("users", "created_at", TimeBucket::Day),
+//!     ));
+//!
+//! assert_eq!(provider.widget_keys(), vec!["users", "signups"]);
+//! ```
+
+#[cfg(feature = "throttling")]
+pub mod throttling;
+
+use crate::core::AdminDatabase;
+use crate::types::{AdminError, AdminResult, ChartType, DataPoint, WidgetData};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use dashmap::DashMap;
+use reinhardt_db::orm::Filter;
+use sea_query::{Alias, Expr, ExprTrait, Order, PostgresQueryBuilder, Query as SeaQuery};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Default time-to-live for cached widget data
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Default number of groups returned by a group-by breakdown
+const DEFAULT_GROUP_LIMIT: u64 = 10;
+
+/// Inclusive-exclusive date range used to restrict widget queries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DateRange {
+	/// Start of the range (inclusive)
+	pub start: DateTime<Utc>,
+	/// End of the range (exclusive)
+	pub end: DateTime<Utc>,
+}
+
+impl DateRange {
+	/// Create a new date range
+	///
+	/// Returns a validation error if `start` is not before `end`.
+	pub fn new(start: DateTime<Utc>, end: DateTime<Utc>) -> AdminResult<Self> {
+		if start >= end {
+			return Err(AdminError::ValidationError(format!(
+				"Invalid date range: start ({}) must be before end ({})",
+				start, end
+			)));
+		}
+		Ok(Self { start, end })
+	}
+
+	/// Create a range covering the last `days` days up to now
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_admin::core::dashboard::DateRange;
+	///
+	/// let range = DateRange::last_days(7);
+	/// assert_eq!((range.end - range.start).num_days(), 7);
+	/// ```
+	pub fn last_days(days: i64) -> Self {
+		let end = Utc::now();
+		Self {
+			start: end - ChronoDuration::days(days),
+			end,
+		}
+	}
+
+	fn cache_fragment(&self) -> String {
+		format!("{}..{}", self.start.timestamp(), self.end.timestamp())
+	}
+}
+
+/// Time bucket granularity for time-series aggregations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeBucket {
+	/// Group by hour
+	Hour,
+	/// Group by day
+	Day,
+	/// Group by week
+	Week,
+	/// Group by month
+	Month,
+}
+
+impl TimeBucket {
+	/// Unit name understood by PostgreSQL's `date_trunc`
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			TimeBucket::Hour => "hour",
+			TimeBucket::Day => "day",
+			TimeBucket::Week => "week",
+			TimeBucket::Month => "month",
+		}
+	}
+}
+
+/// Aggregation executed to compute widget data
+#[derive(Debug, Clone)]
+pub enum WidgetQuery {
+	/// Total row count of a table
+	Count {
+		/// Table to count rows in
+		table: String,
+		/// Column used to apply the date range (if any)
+		date_field: Option<String>,
+		/// Additional filters (AND logic)
+		filters: Vec<Filter>,
+	},
+	/// Row counts grouped into time buckets
+	CountOverTime {
+		/// Table to count rows in
+		table: String,
+		/// Timestamp column used for bucketing and the date range
+		date_field: String,
+		/// Bucket granularity
+		bucket: TimeBucket,
+		/// Additional filters (AND logic)
+		filters: Vec<Filter>,
+	},
+	/// Row counts grouped by the distinct values of a column
+	GroupBy {
+		/// Table to count rows in
+		table: String,
+		/// Column whose values define the groups
+		group_field: String,
+		/// Column used to apply the date range (if any)
+		date_field: Option<String>,
+		/// Maximum number of groups returned (largest first)
+		limit: u64,
+		/// Additional filters (AND logic)
+		filters: Vec<Filter>,
+	},
+}
+
+impl WidgetQuery {
+	/// Count all rows of `table`
+	pub fn count(table: impl Into<String>) -> Self {
+		Self::Count {
+			table: table.into(),
+			date_field: None,
+			filters: Vec::new(),
+		}
+	}
+
+	/// Count rows of `table` per time bucket of `date_field`
+	pub fn count_over_time(
+		table: impl Into<String>,
+		date_field: impl Into<String>,
+		bucket: TimeBucket,
+	) -> Self {
+		Self::CountOverTime {
+			table: table.into(),
+			date_field: date_field.into(),
+			bucket,
+			filters: Vec::new(),
+		}
+	}
+
+	/// Count rows of `table` per distinct value of `group_field`
+	pub fn group_by(table: impl Into<String>, group_field: impl Into<String>) -> Self {
+		Self::GroupBy {
+			table: table.into(),
+			group_field: group_field.into(),
+			date_field: None,
+			limit: DEFAULT_GROUP_LIMIT,
+			filters: Vec::new(),
+		}
+	}
+
+	/// Set the column used to apply date ranges
+	///
+	/// For [`WidgetQuery::CountOverTime`] this replaces the bucketing column.
+	pub fn with_date_field(mut self, field: impl Into<String>) -> Self {
+		let field = field.into();
+		match &mut self {
+			Self::Count { date_field, .. } | Self::GroupBy { date_field, .. } => {
+				*date_field = Some(field);
+			}
+			Self::CountOverTime { date_field, .. } => *date_field = field,
+		}
+		self
+	}
+
+	/// Add a filter applied before aggregating
+	pub fn with_filter(mut self, filter: Filter) -> Self {
+		match &mut self {
+			Self::Count { filters, .. }
+			| Self::CountOverTime { filters, .. }
+			| Self::GroupBy { filters, .. } => filters.push(filter),
+		}
+		self
+	}
+
+	/// Set the maximum number of groups for a group-by breakdown
+	///
+	/// Has no effect on other query kinds.
+	pub fn with_limit(mut self, max_groups: u64) -> Self {
+		if let Self::GroupBy { limit, .. } = &mut self {
+			*limit = max_groups;
+		}
+		self
+	}
+
+	/// Build the SQL for this aggregation, restricted to `range` when given
+	pub fn to_sql(&self, range: Option<&DateRange>) -> String {
+		let (table, date_field, filters) = match self {
+			Self::Count {
+				table,
+				date_field,
+				filters,
+			}
+			| Self::GroupBy {
+				table,
+				date_field,
+				filters,
+				..
+			} => (table, date_field.as_deref(), filters),
+			Self::CountOverTime {
+				table,
+				date_field,
+				filters,
+				..
+			} => (table, Some(date_field.as_str()), filters),
+		};
+
+		let mut query = SeaQuery::select().from(Alias::new(table)).to_owned();
+
+		if let Some(condition) = super::database::build_filter_condition(filters) {
+			query.cond_where(condition);
+		}
+		if let (Some(range), Some(field)) = (range, date_field) {
+			query
+				.and_where(Expr::col(Alias::new(field)).gte(range.start.to_rfc3339()))
+				.and_where(Expr::col(Alias::new(field)).lt(range.end.to_rfc3339()));
+		}
+
+		match self {
+			Self::Count { .. } => {
+				query.expr_as(Expr::cust("COUNT(*)"), Alias::new("value"));
+			}
+			Self::CountOverTime {
+				date_field, bucket, ..
+			} => {
+				query
+					.expr_as(
+						Expr::cust(format!(
+							"date_trunc('{}', \"{}\")",
+							bucket.as_str(),
+							date_field.replace('"', "\"\"")
+						)),
+						Alias::new("label"),
+					)
+					.expr_as(Expr::cust("COUNT(*)"), Alias::new("value"))
+					.group_by_col(Alias::new("label"))
+					.order_by(Alias::new("label"), Order::Asc);
+			}
+			Self::GroupBy {
+				group_field, limit, ..
+			} => {
+				query
+					.expr_as(Expr::col(Alias::new(group_field)), Alias::new("label"))
+					.expr_as(Expr::cust("COUNT(*)"), Alias::new("value"))
+					.group_by_col(Alias::new(group_field))
+					.order_by(Alias::new("value"), Order::Desc)
+					.limit(*limit);
+			}
+		}
+
+		query.to_string(PostgresQueryBuilder)
+	}
+}
+
+/// Widget displaying a single aggregated number
+#[derive(Debug, Clone)]
+pub struct StatWidget {
+	/// Unique widget key
+	pub key: String,
+	/// Title displayed above the value
+	pub title: String,
+	/// Aggregation producing the value
+	pub query: WidgetQuery,
+}
+
+impl StatWidget {
+	/// Create a new stat widget
+	pub fn new(key: impl Into<String>, title: impl Into<String>, query: WidgetQuery) -> Self {
+		Self {
+			key: key.into(),
+			title: title.into(),
+			query,
+		}
+	}
+}
+
+/// Widget displaying a series of labelled data points
+#[derive(Debug, Clone)]
+pub struct ChartWidget {
+	/// Unique widget key
+	pub key: String,
+	/// Title displayed above the chart
+	pub title: String,
+	/// Chart rendering hint
+	pub chart_type: ChartType,
+	/// Aggregation producing the data points
+	pub query: WidgetQuery,
+}
+
+impl ChartWidget {
+	/// Create a new chart widget
+	pub fn new(
+		key: impl Into<String>,
+		title: impl Into<String>,
+		chart_type: ChartType,
+		query: WidgetQuery,
+	) -> Self {
+		Self {
+			key: key.into(),
+			title: title.into(),
+			chart_type,
+			query,
+		}
+	}
+}
+
+/// Computes widget data without a database query
+#[async_trait::async_trait]
+pub trait WidgetSource: Send + Sync {
+	/// Compute the widget data, restricted to `range` when given
+	async fn fetch(&self, range: Option<&DateRange>) -> AdminResult<WidgetData>;
+}
+
+/// Widget whose data is computed by a [`WidgetSource`]
+#[derive(Clone)]
+pub struct CustomWidget {
+	/// Unique widget key
+	pub key: String,
+	/// Title displayed above the widget
+	pub title: String,
+	/// Source computing the widget data
+	pub source: Arc<dyn WidgetSource>,
+}
+
+impl CustomWidget {
+	/// Create a new custom widget
+	pub fn new(
+		key: impl Into<String>,
+		title: impl Into<String>,
+		source: Arc<dyn WidgetSource>,
+	) -> Self {
+		Self {
+			key: key.into(),
+			title: title.into(),
+			source,
+		}
+	}
+}
+
+impl std::fmt::Debug for CustomWidget {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("CustomWidget")
+			.field("key", &self.key)
+			.field("title", &self.title)
+			.finish_non_exhaustive()
+	}
+}
+
+/// A dashboard widget
+#[derive(Debug, Clone)]
+pub enum DashboardWidget {
+	/// Single-value widget
+	Stat(StatWidget),
+	/// Chart widget
+	Chart(ChartWidget),
+}
+
+impl DashboardWidget {
+	/// Get the widget key
+	pub fn key(&self) -> &str {
+		match self {
+			DashboardWidget::Stat(w) => &w.key,
+			DashboardWidget::Chart(w) => &w.key,
+		}
+	}
+
+	/// Get the widget title
+	pub fn title(&self) -> &str {
+		match self {
+			DashboardWidget::Stat(w) => &w.title,
+			DashboardWidget::Chart(w) => &w.title,
+		}
+	}
+
+	/// Get the aggregation backing this widget
+	pub fn query(&self) -> &WidgetQuery {
+		match self {
+			DashboardWidget::Stat(w) => &w.query,
+			DashboardWidget::Chart(w) => &w.query,
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+struct CachedData {
+	data: WidgetData,
+	cached_at: Instant,
+}
+
+/// Computes dashboard widget data through [`AdminDatabase`]
+///
+/// Results are cached per widget key and date range. Cached entries expire
+/// after the configured TTL (60 seconds by default).
+///
+/// Custom widgets are listed after the database-backed widgets.
+#[derive(Debug)]
+pub struct WidgetProvider {
+	widgets: Vec<DashboardWidget>,
+	custom_widgets: Vec<CustomWidget>,
+	cache: DashMap<String, CachedData>,
+	cache_ttl: Duration,
+}
+
+impl Default for WidgetProvider {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl WidgetProvider {
+	/// Create an empty widget provider
+	pub fn new() -> Self {
+		Self {
+			widgets: Vec::new(),
+			custom_widgets: Vec::new(),
+			cache: DashMap::new(),
+			cache_ttl: DEFAULT_CACHE_TTL,
+		}
+	}
+
+	/// Set the cache TTL (`Duration::ZERO` disables caching)
+	pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+		self.cache_ttl = ttl;
+		self
+	}
+
+	/// Add a stat widget
+	pub fn with_stat(mut self, widget: StatWidget) -> Self {
+		self.widgets.push(DashboardWidget::Stat(widget));
+		self
+	}
+
+	/// Add a chart widget
+	pub fn with_chart(mut self, widget: ChartWidget) -> Self {
+		self.widgets.push(DashboardWidget::Chart(widget));
+		self
+	}
+
+	/// Add a custom widget
+	pub fn with_widget(mut self, widget: CustomWidget) -> Self {
+		self.custom_widgets.push(widget);
+		self
+	}
+
+	/// Get all database-backed widgets in registration order
+	pub fn widgets(&self) -> &[DashboardWidget] {
+		&self.widgets
+	}
+
+	/// Get all custom widgets in registration order
+	pub fn custom_widgets(&self) -> &[CustomWidget] {
+		&self.custom_widgets
+	}
+
+	/// Get all widget keys, database-backed widgets first
+	pub fn widget_keys(&self) -> Vec<&str> {
+		self.widgets
+			.iter()
+			.map(|w| w.key())
+			.chain(self.custom_widgets.iter().map(|w| w.key.as_str()))
+			.collect()
+	}
+
+	/// Get a database-backed widget by key
+	pub fn get(&self, key: &str) -> Option<&DashboardWidget> {
+		self.widgets.iter().find(|w| w.key() == key)
+	}
+
+	/// Get a custom widget by key
+	pub fn get_custom(&self, key: &str) -> Option<&CustomWidget> {
+		self.custom_widgets.iter().find(|w| w.key == key)
+	}
+
+	/// Get the title of any widget by key
+	pub fn title(&self, key: &str) -> Option<&str> {
+		self.get(key)
+			.map(|w| w.title())
+			.or_else(|| self.get_custom(key).map(|w| w.title.as_str()))
+	}
+
+	/// Drop all cached widget data
+	pub fn invalidate(&self) {
+		self.cache.clear();
+	}
+
+	/// Compute the data for a single widget
+	///
+	/// Returns [`AdminError::InvalidAction`] if no widget with `key` exists.
+	pub async fn fetch(
+		&self,
+		db: &AdminDatabase,
+		key: &str,
+		range: Option<&DateRange>,
+	) -> AdminResult<WidgetData> {
+		let cache_key = match range {
+			Some(range) => format!("{}@{}", key, range.cache_fragment()),
+			None => key.to_string(),
+		};
+		if let Some(cached) = self.cache.get(&cache_key)
+			&& cached.cached_at.elapsed() < self.cache_ttl
+		{
+			return Ok(cached.data.clone());
+		}
+
+		let data = match (self.get(key), self.get_custom(key)) {
+			(Some(widget), _) => fetch_database_widget(db, widget, range).await?,
+			(None, Some(custom)) => custom.source.fetch(range).await?,
+			(None, None) => {
+				return Err(AdminError::InvalidAction(format!(
+					"Unknown dashboard widget: {}",
+					key
+				)));
+			}
+		};
+
+		if !self.cache_ttl.is_zero() {
+			self.cache.insert(
+				cache_key,
+				CachedData {
+					data: data.clone(),
+					cached_at: Instant::now(),
+				},
+			);
+		}
+
+		Ok(data)
+	}
+
+	/// Compute the data for every widget, in registration order
+	pub async fn fetch_all(
+		&self,
+		db: &AdminDatabase,
+		range: Option<&DateRange>,
+	) -> AdminResult<Vec<(String, WidgetData)>> {
+		let keys = self.widget_keys();
+		let mut results = Vec::with_capacity(keys.len());
+		for key in keys {
+			let data = self.fetch(db, key, range).await?;
+			results.push((key.to_string(), data));
+		}
+		Ok(results)
+	}
+}
+
+async fn fetch_database_widget(
+	db: &AdminDatabase,
+	widget: &DashboardWidget,
+	range: Option<&DateRange>,
+) -> AdminResult<WidgetData> {
+	let sql = widget.query().to_sql(range);
+	let rows = db
+		.connection()
+		.query(&sql, vec![])
+		.await
+		.map_err(|e| AdminError::DatabaseError(e.to_string()))?;
+
+	Ok(match widget {
+		DashboardWidget::Stat(_) => WidgetData::Stat {
+			value: rows
+				.first()
+				.and_then(|row| row.data.get("value"))
+				.map(json_to_i64)
+				.unwrap_or(0),
+		},
+		DashboardWidget::Chart(chart) => WidgetData::Chart {
+			chart_type: chart.chart_type,
+			points: rows
+				.iter()
+				.map(|row| DataPoint {
+					label: row.data.get("label").map(json_to_label).unwrap_or_default(),
+					value: row.data.get("value").map(json_to_i64).unwrap_or(0),
+				})
+				.collect(),
+		},
+	})
+}
+
+fn json_to_i64(value: &serde_json::Value) -> i64 {
+	match value {
+		serde_json::Value::Number(n) => n
+			.as_i64()
+			.or_else(|| n.as_f64().map(|f| f as i64))
+			.unwrap_or(0),
+		serde_json::Value::String(s) => s.parse().unwrap_or(0),
+		_ => 0,
+	}
+}
+
+fn json_to_label(value: &serde_json::Value) -> String {
+	match value {
+		serde_json::Value::String(s) => s.clone(),
+		serde_json::Value::Null => String::new(),
+		other => other.to_string(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use chrono::TimeZone;
+	use reinhardt_db::orm::{DatabaseConnection, FilterOperator, FilterValue};
+	use reinhardt_test::fixtures::mock_connection;
+	use rstest::*;
+
+	fn fixed_range() -> DateRange {
+		DateRange::new(
+			Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+			Utc.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap(),
+		)
+		.unwrap()
+	}
+
+	#[rstest]
+	fn test_date_range_rejects_inverted_bounds() {
+		let start = Utc.with_ymd_and_hms(2025, 2, 1, 0, 0, 0).unwrap();
+		let end = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+
+		let result = DateRange::new(start, end);
+
+		assert!(matches!(result, Err(AdminError::ValidationError(_))));
+	}
+
+	#[rstest]
+	fn test_count_sql() {
+		let query = WidgetQuery::count("users");
+
+		let sql = query.to_sql(None);
+
+		assert_eq!(sql, r#"SELECT COUNT(*) AS "value" FROM "users""#);
+	}
+
+	#[rstest]
+	fn test_count_sql_with_range_and_filter() {
+		let query = WidgetQuery::count("users")
+			.with_date_field("created_at")
+			.with_filter(Filter::new(
+				"is_active".to_string(),
+				FilterOperator::Eq,
+				FilterValue::Boolean(true),
+			));
+
+		let sql = query.to_sql(Some(&fixed_range()));
+
+		assert_eq!(
+			sql,
+			r#"SELECT COUNT(*) AS "value" FROM "users" WHERE "is_active" = TRUE AND "created_at" >= '2025-01-01T00:00:00+00:00' AND "created_at" < '2025-02-01T00:00:00+00:00'"#
+		);
+	}
+
+	#[rstest]
+	fn test_count_over_time_sql() {
+		let query = WidgetQuery::count_over_time("orders", "created_at", TimeBucket::Day);
+
+		let sql = query.to_sql(None);
+
+		assert_eq!(
+			sql,
+			r#"SELECT date_trunc('day', "created_at") AS "label", COUNT(*) AS "value" FROM "orders" GROUP BY "label" ORDER BY "label" ASC"#
+		);
+	}
+
+	#[rstest]
+	fn test_group_by_sql() {
+		let query = WidgetQuery::group_by("orders", "status").with_limit(5);
+
+		let sql = query.to_sql(None);
+
+		assert_eq!(
+			sql,
+			r#"SELECT "status" AS "label", COUNT(*) AS "value" FROM "orders" GROUP BY "status" ORDER BY "value" DESC LIMIT 5"#
+		);
+	}
+
+	#[rstest]
+	fn test_json_conversions() {
+		assert_eq!(json_to_i64(&serde_json::json!(42)), 42);
+		assert_eq!(json_to_i64(&serde_json::json!("17")), 17);
+		assert_eq!(json_to_i64(&serde_json::json!(null)), 0);
+		assert_eq!(json_to_label(&serde_json::json!("paid")), "paid");
+		assert_eq!(json_to_label(&serde_json::json!(3)), "3");
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_fetch_unknown_widget(mock_connection: DatabaseConnection) {
+		let db = AdminDatabase::new(mock_connection);
+		let provider = WidgetProvider::new();
+
+		let result = provider.fetch(&db, "missing", None).await;
+
+		assert!(matches!(result, Err(AdminError::InvalidAction(_))));
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_fetch_chart_with_empty_result(mock_connection: DatabaseConnection) {
+		let db = AdminDatabase::new(mock_connection);
+		let provider = WidgetProvider::new().with_chart(ChartWidget::new(
+			"status",
+			"Orders by status",
+			ChartType::Pie,
+			WidgetQuery::group_by("orders", "status"),
+		));
+
+		let data = provider.fetch(&db, "status", None).await.unwrap();
+
+		assert_eq!(
+			data,
+			WidgetData::Chart {
+				chart_type: ChartType::Pie,
+				points: vec![],
+			}
+		);
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_fetch_all_caches_results(mock_connection: DatabaseConnection) {
+		let db = AdminDatabase::new(mock_connection);
+		let provider = WidgetProvider::new().with_stat(StatWidget::new(
+			"users",
+			"Users",
+			WidgetQuery::count("users"),
+		));
+
+		let first = provider.fetch_all(&db, None).await.unwrap();
+
+		assert_eq!(
+			first,
+			vec![("users".to_string(), WidgetData::Stat { value: 0 })]
+		);
+		assert_eq!(provider.cache.len(), 1);
+		provider.invalidate();
+		assert_eq!(provider.cache.len(), 0);
+	}
+}
@@ -0,0 +1,272 @@
+//! Dashboard widgets for throttling analytics
+//!
+//! These widgets read [`ThrottleAnalytics`] recorded by
+//! `reinhardt-throttling` and show who is being rate limited and how often.
+//!
+//! # Examples
+//!
+//! ```
+//! use reinhardt_admin::core::dashboard::WidgetProvider;
+//! use reinhardt_admin::core::dashboard::throttling::{BlockRateWidget, TopOffendersWidget};
+//! use reinhardt_throttling::ThrottleAnalytics;
+//!
+//! let analytics = ThrottleAnalytics::memory();
+//! let provider = WidgetProvider::new()
+//!     .with_widget(TopOffendersWidget::new(analytics.clone()).into_widget("offenders", "Top offenders"))
+//!     .with_widget(BlockRateWidget::new(analytics).into_widget("block_rate", "Blocked requests (%)"));
+//!
+//! assert_eq!(provider.widget_keys(), vec!["offenders", "block_rate"]);
+//! ```
+
+use super::{CustomWidget, DateRange, WidgetSource};
+use crate::types::{AdminError, AdminResult, ChartType, DataPoint, WidgetData};
+use chrono::{DateTime, Utc};
+use reinhardt_throttling::ThrottleAnalytics;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Default period shown when no date range is requested
+const DEFAULT_WINDOW: Duration = Duration::from_secs(86_400);
+
+/// Unix timestamps covering `range`, or the last `window`
+fn time_range(range: Option<&DateRange>, window: Duration) -> (u64, u64) {
+	match range {
+		Some(range) => (
+			range.start.timestamp().max(0) as u64,
+			range.end.timestamp().max(0) as u64,
+		),
+		None => {
+			let now = Utc::now().timestamp().max(0) as u64 + 1;
+			(now.saturating_sub(window.as_secs()), now)
+		}
+	}
+}
+
+/// Bar chart of the identities with the most blocked requests
+#[derive(Clone)]
+pub struct TopOffendersWidget {
+	analytics: ThrottleAnalytics,
+	scope: Option<String>,
+	window: Duration,
+	limit: usize,
+}
+
+impl TopOffendersWidget {
+	/// Show the top 10 offenders across all scopes over the last day
+	pub fn new(analytics: ThrottleAnalytics) -> Self {
+		Self {
+			analytics,
+			scope: None,
+			window: DEFAULT_WINDOW,
+			limit: 10,
+		}
+	}
+
+	/// Only show offenders of one scope
+	pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+		self.scope = Some(scope.into());
+		self
+	}
+
+	/// Set the period shown when no date range is requested
+	pub fn with_window(mut self, window: Duration) -> Self {
+		self.window = window;
+		self
+	}
+
+	/// Set the maximum number of offenders shown
+	pub fn with_limit(mut self, limit: usize) -> Self {
+		self.limit = limit;
+		self
+	}
+
+	/// Turn into a dashboard widget
+	pub fn into_widget(self, key: impl Into<String>, title: impl Into<String>) -> CustomWidget {
+		CustomWidget::new(key, title, Arc::new(self))
+	}
+}
+
+#[async_trait::async_trait]
+impl WidgetSource for TopOffendersWidget {
+	async fn fetch(&self, range: Option<&DateRange>) -> AdminResult<WidgetData> {
+		let (from, to) = time_range(range, self.window);
+		let offenders = self
+			.analytics
+			.backend()
+			.top_offenders(self.scope.as_deref(), from, to, self.limit)
+			.await
+			.map_err(|e| AdminError::InvalidAction(e.to_string()))?;
+
+		Ok(WidgetData::Chart {
+			chart_type: ChartType::Bar,
+			points: offenders
+				.into_iter()
+				.map(|offender| DataPoint {
+					label: match self.scope {
+						Some(_) => offender.identity,
+						None => format!("{}:{}", offender.scope, offender.identity),
+					},
+					value: offender.stats.blocked as i64,
+				})
+				.collect(),
+		})
+	}
+}
+
+/// Line chart of the percentage of blocked requests over time
+#[derive(Clone)]
+pub struct BlockRateWidget {
+	analytics: ThrottleAnalytics,
+	scope: Option<String>,
+	window: Duration,
+	bucket: Duration,
+}
+
+impl BlockRateWidget {
+	/// Show hourly block rates across all scopes over the last day
+	pub fn new(analytics: ThrottleAnalytics) -> Self {
+		Self {
+			analytics,
+			scope: None,
+			window: DEFAULT_WINDOW,
+			bucket: Duration::from_secs(3600),
+		}
+	}
+
+	/// Only show block rates of one scope
+	pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+		self.scope = Some(scope.into());
+		self
+	}
+
+	/// Set the period shown when no date range is requested
+	pub fn with_window(mut self, window: Duration) -> Self {
+		self.window = window;
+		self
+	}
+
+	/// Set the interval each data point covers
+	pub fn with_bucket(mut self, bucket: Duration) -> Self {
+		self.bucket = bucket;
+		self
+	}
+
+	/// Turn into a dashboard widget
+	pub fn into_widget(self, key: impl Into<String>, title: impl Into<String>) -> CustomWidget {
+		CustomWidget::new(key, title, Arc::new(self))
+	}
+}
+
+#[async_trait::async_trait]
+impl WidgetSource for BlockRateWidget {
+	async fn fetch(&self, range: Option<&DateRange>) -> AdminResult<WidgetData> {
+		let (from, to) = time_range(range, self.window);
+		let buckets = self
+			.analytics
+			.backend()
+			.stats_over_time(self.scope.as_deref(), from, to, self.bucket.as_secs())
+			.await
+			.map_err(|e| AdminError::InvalidAction(e.to_string()))?;
+
+		Ok(WidgetData::Chart {
+			chart_type: ChartType::Line,
+			points: buckets
+				.into_iter()
+				.map(|bucket| DataPoint {
+					label: DateTime::<Utc>::from_timestamp(bucket.start as i64, 0)
+						.map(|start| start.to_rfc3339())
+						.unwrap_or_default(),
+					value: (bucket.stats.block_rate() * 100.0).round() as i64,
+				})
+				.collect(),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use chrono::TimeZone;
+	use reinhardt_throttling::{AnalyticsBackend, ThrottleEvent};
+	use rstest::*;
+
+	async fn analytics() -> ThrottleAnalytics {
+		let analytics = ThrottleAnalytics::memory();
+		let start = Utc
+			.with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+			.unwrap()
+			.timestamp() as u64;
+		for (scope, identity, allowed, offset) in [
+			("anon", "10.0.0.1", false, 0),
+			("anon", "10.0.0.1", false, 60),
+			("anon", "10.0.0.2", false, 120),
+			("anon", "10.0.0.2", true, 3_600),
+			("user", "42", true, 3_660),
+		] {
+			analytics
+				.backend()
+				.record(ThrottleEvent::new(scope, identity, allowed).at(start + offset))
+				.await
+				.unwrap();
+		}
+		analytics
+	}
+
+	fn range() -> DateRange {
+		DateRange::new(
+			Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+			Utc.with_ymd_and_hms(2025, 1, 2, 0, 0, 0).unwrap(),
+		)
+		.unwrap()
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_top_offenders_widget() {
+		let widget = TopOffendersWidget::new(analytics().await);
+
+		let data = widget.fetch(Some(&range())).await.unwrap();
+
+		assert_eq!(
+			data,
+			WidgetData::Chart {
+				chart_type: ChartType::Bar,
+				points: vec![
+					DataPoint {
+						label: "anon:10.0.0.1".to_string(),
+						value: 2,
+					},
+					DataPoint {
+						label: "anon:10.0.0.2".to_string(),
+						value: 1,
+					},
+				],
+			}
+		);
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_block_rate_widget() {
+		let widget = BlockRateWidget::new(analytics().await);
+
+		let data = widget.fetch(Some(&range())).await.unwrap();
+
+		assert_eq!(
+			data,
+			WidgetData::Chart {
+				chart_type: ChartType::Line,
+				points: vec![
+					DataPoint {
+						label: "2025-01-01T00:00:00+00:00".to_string(),
+						value: 100,
+					},
+					DataPoint {
+						label: "2025-01-01T01:00:00+00:00".to_string(),
+						value: 0,
+					},
+				],
+			}
+		);
+	}
+}
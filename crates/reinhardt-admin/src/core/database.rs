@@ -3,10 +3,11 @@
 //! This module provides database access layer for admin CRUD operations,
 //! integrating with reinhardt-orm's QuerySet API.
 
+use crate::core::import::{ImportError, ImportResult};
 use crate::types::{AdminError, AdminResult};
 use async_trait::async_trait;
 use reinhardt_db::orm::{
-	DatabaseConnection, Filter, FilterCondition, FilterOperator, FilterValue, Model,
+	DatabaseConnection, Filter, FilterCondition, FilterOperator, FilterValue, Model, QueryRow,
 };
 use reinhardt_di::{DiResult, Injectable, InjectionContext};
 use sea_query::{
@@ -179,7 +180,7 @@ fn build_single_filter_expr(filter: &Filter) -> Option<sea_query::SimpleExpr> {
 }
 
 /// Build sea-query Condition from filters (AND logic only)
-fn build_filter_condition(filters: &[Filter]) -> Option<Condition> {
+pub(crate) fn build_filter_condition(filters: &[Filter]) -> Option<Condition> {
 	if filters.is_empty() {
 		return None;
 	}
@@ -579,37 +580,14 @@ impl AdminDatabase {
 		pk_field: &str,
 		id: &str,
 	) -> AdminResult<Option<HashMap<String, serde_json::Value>>> {
-		// Convert id to appropriate type for WHERE clause
-		let pk_value: sea_query::Value = if let Ok(num_id) = id.parse::<i64>() {
-			sea_query::Value::BigInt(Some(num_id))
-		} else {
-			sea_query::Value::String(Some(id.to_string()))
-		};
-
-		let query = SeaQuery::select()
-			.from(Alias::new(table_name))
-			.column(Asterisk)
-			.and_where(Expr::col(Alias::new(pk_field)).eq(pk_value))
-			.to_owned();
-
-		let sql = query.to_string(PostgresQueryBuilder);
+		let sql = get_sql(table_name, pk_field, id);
 		let row = self
 			.connection
 			.query_optional(&sql, vec![])
 			.await
 			.map_err(|e| AdminError::DatabaseError(e.to_string()))?;
 
-		Ok(row.and_then(|r| {
-			// r.data is already a serde_json::Value, typically an Object
-			if let serde_json::Value::Object(map) = r.data {
-				Some(
-					map.into_iter()
-						.collect::<HashMap<String, serde_json::Value>>(),
-				)
-			} else {
-				None
-			}
-		}))
+		Ok(row.and_then(row_to_record))
 	}
 
 	/// Create a new item
@@ -658,23 +636,7 @@ impl AdminDatabase {
 
 		for (key, value) in data {
 			columns.push(Alias::new(&key));
-
-			let sea_value = match value {
-				serde_json::Value::String(s) => sea_query::Value::String(Some(s)),
-				serde_json::Value::Number(n) => {
-					if let Some(i) = n.as_i64() {
-						sea_query::Value::BigInt(Some(i))
-					} else if let Some(f) = n.as_f64() {
-						sea_query::Value::Double(Some(f))
-					} else {
-						sea_query::Value::String(Some(n.to_string()))
-					}
-				}
-				serde_json::Value::Bool(b) => sea_query::Value::Bool(Some(b)),
-				serde_json::Value::Null => sea_query::Value::Int(None),
-				_ => sea_query::Value::String(Some(value.to_string())),
-			};
-			values.push(sea_value);
+			values.push(json_to_sea_value(value));
 		}
 
 		// Convert Values to Exprs for sea-query v1.0
@@ -743,22 +705,7 @@ impl AdminDatabase {
 
 		// Build SET clauses
 		for (key, value) in data {
-			let sea_value = match value {
-				serde_json::Value::String(s) => sea_query::Value::String(Some(s)),
-				serde_json::Value::Number(n) => {
-					if let Some(i) = n.as_i64() {
-						sea_query::Value::BigInt(Some(i))
-					} else if let Some(f) = n.as_f64() {
-						sea_query::Value::Double(Some(f))
-					} else {
-						sea_query::Value::String(Some(n.to_string()))
-					}
-				}
-				serde_json::Value::Bool(b) => sea_query::Value::Bool(Some(b)),
-				serde_json::Value::Null => sea_query::Value::Int(None),
-				_ => sea_query::Value::String(Some(value.to_string())),
-			};
-			query.value(Alias::new(&key), sea_value);
+			query.value(Alias::new(&key), json_to_sea_value(value));
 		}
 
 		// Convert id to appropriate type for WHERE clause
@@ -991,6 +938,199 @@ impl AdminDatabase {
 
 		Ok(count)
 	}
+
+	/// Restore an item to the given field values inside a transaction
+	///
+	/// Updates the row when `exists` is true, otherwise re-inserts it with its
+	/// original primary key. The transaction is rolled back if the statement fails.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_admin::core::AdminDatabase;
+	/// use reinhardt_db::orm::DatabaseConnection;
+	/// use std::collections::HashMap;
+	///
+	/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+	/// let conn = DatabaseConnection::connect("postgres://localhost/test").await?;
+	/// let db = AdminDatabase::new(conn);
+	///
+	/// let mut data = HashMap::new();
+	/// data.insert("id".to_string(), serde_json::json!(1));
+	/// data.insert("name".to_string(), serde_json::json!("Alice"));
+	///
+	/// db.restore("users", "id", "1", data, true).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub async fn restore(
+		&self,
+		table_name: &str,
+		pk_field: &str,
+		id: &str,
+		data: HashMap<String, serde_json::Value>,
+		exists: bool,
+	) -> AdminResult<u64> {
+		let sql = restore_sql(table_name, pk_field, id, data, exists)?;
+
+		let mut tx = self
+			.connection
+			.begin()
+			.await
+			.map_err(|e| AdminError::DatabaseError(e.to_string()))?;
+
+		match tx.execute(&sql, vec![]).await {
+			Ok(result) => {
+				tx.commit()
+					.await
+					.map_err(|e| AdminError::DatabaseError(e.to_string()))?;
+				Ok(result.rows_affected)
+			}
+			Err(e) => {
+				// The original error is more useful than a rollback failure
+				let _ = tx.rollback().await;
+				Err(AdminError::DatabaseError(e.to_string()))
+			}
+		}
+	}
+}
+
+impl AdminDatabase {
+	/// Insert imported rows in a single transaction
+	///
+	/// Rows are inserted in order. The first failing row rolls back the whole
+	/// transaction and is reported in the result with its 1-indexed row
+	/// number. When `commit` is false the transaction is rolled back even if
+	/// every row was inserted, so database constraints are checked without
+	/// persisting anything.
+	pub async fn import_rows(
+		&self,
+		table_name: &str,
+		rows: Vec<HashMap<String, serde_json::Value>>,
+		commit: bool,
+	) -> AdminResult<ImportResult> {
+		let mut tx = self
+			.connection
+			.begin()
+			.await
+			.map_err(|e| AdminError::DatabaseError(e.to_string()))?;
+
+		let mut result = ImportResult::new();
+		for (index, row) in rows.into_iter().enumerate() {
+			let mut query = SeaQuery::insert()
+				.into_table(Alias::new(table_name))
+				.to_owned();
+			let (columns, values): (Vec<_>, Vec<sea_query::SimpleExpr>) = row
+				.into_iter()
+				.map(|(key, value)| (Alias::new(&key), json_to_sea_value(value).into()))
+				.unzip();
+			let inserted = match query.columns(columns).values(values) {
+				Ok(query) => tx
+					.execute(&query.to_string(PostgresQueryBuilder), vec![])
+					.await
+					.map_err(|e| e.to_string()),
+				Err(e) => Err(e.to_string()),
+			};
+
+			if let Err(message) = inserted {
+				// The row error is more useful than a rollback failure
+				let _ = tx.rollback().await;
+				result.imported_count = 0;
+				result.add_failed(ImportError::new(index + 1, message));
+				return Ok(result);
+			}
+			result.add_imported();
+		}
+
+		if commit {
+			tx.commit().await
+		} else {
+			tx.rollback().await
+		}
+		.map_err(|e| AdminError::DatabaseError(e.to_string()))?;
+
+		Ok(result)
+	}
+}
+
+/// SQL selecting a row by primary key
+pub(crate) fn get_sql(table_name: &str, pk_field: &str, id: &str) -> String {
+	SeaQuery::select()
+		.from(Alias::new(table_name))
+		.column(Asterisk)
+		.and_where(Expr::col(Alias::new(pk_field)).eq(pk_to_sea_value(id)))
+		.to_string(PostgresQueryBuilder)
+}
+
+/// SQL restoring a row to `data`, updating it if it `exists` and
+/// re-inserting it otherwise
+pub(crate) fn restore_sql(
+	table_name: &str,
+	pk_field: &str,
+	id: &str,
+	data: HashMap<String, serde_json::Value>,
+	exists: bool,
+) -> AdminResult<String> {
+	if exists {
+		let mut query = SeaQuery::update().table(Alias::new(table_name)).to_owned();
+		for (key, value) in data {
+			if key != pk_field {
+				query.value(Alias::new(&key), json_to_sea_value(value));
+			}
+		}
+		query.and_where(Expr::col(Alias::new(pk_field)).eq(pk_to_sea_value(id)));
+		Ok(query.to_string(PostgresQueryBuilder))
+	} else {
+		let mut query = SeaQuery::insert()
+			.into_table(Alias::new(table_name))
+			.to_owned();
+		let (columns, values): (Vec<_>, Vec<sea_query::SimpleExpr>) = data
+			.into_iter()
+			.map(|(key, value)| (Alias::new(&key), json_to_sea_value(value).into()))
+			.unzip();
+		query
+			.columns(columns)
+			.values(values)
+			.map_err(|e| AdminError::DatabaseError(e.to_string()))?;
+		Ok(query.to_string(PostgresQueryBuilder))
+	}
+}
+
+/// Convert a query row into a field map
+pub(crate) fn row_to_record(row: QueryRow) -> Option<HashMap<String, serde_json::Value>> {
+	// row.data is already a serde_json::Value, typically an Object
+	match row.data {
+		serde_json::Value::Object(map) => Some(map.into_iter().collect()),
+		_ => None,
+	}
+}
+
+/// Convert a JSON value into a sea-query value
+pub(crate) fn json_to_sea_value(value: serde_json::Value) -> sea_query::Value {
+	match value {
+		serde_json::Value::String(s) => sea_query::Value::String(Some(s)),
+		serde_json::Value::Number(n) => {
+			if let Some(i) = n.as_i64() {
+				sea_query::Value::BigInt(Some(i))
+			} else if let Some(f) = n.as_f64() {
+				sea_query::Value::Double(Some(f))
+			} else {
+				sea_query::Value::String(Some(n.to_string()))
+			}
+		}
+		serde_json::Value::Bool(b) => sea_query::Value::Bool(Some(b)),
+		serde_json::Value::Null => sea_query::Value::Int(None),
+		_ => sea_query::Value::String(Some(value.to_string())),
+	}
+}
+
+/// Convert a primary key string into a sea-query value
+pub(crate) fn pk_to_sea_value(id: &str) -> sea_query::Value {
+	if let Ok(num_id) = id.parse::<i64>() {
+		sea_query::Value::BigInt(Some(num_id))
+	} else {
+		sea_query::Value::String(Some(id.to_string()))
+	}
 }
 
 /// Injectable trait implementation for AdminDatabase
@@ -0,0 +1,1078 @@
+//! Background export of large datasets
+//!
+//! Exporting a large table inline blocks the request until every row has been
+//! serialized. This module moves that work into a `reinhardt-tasks` task:
+//!
+//! 1. [`BackgroundExporter::enqueue`] persists an [`ExportJob`] and starts an
+//!    [`ExportTask`] for it.
+//! 2. The task reads the table page by page. Every page is serialized into its
+//!    own part file and the job's progress is saved, so clients can poll it.
+//! 3. Once finished, the job exposes a download URL signed by
+//!    [`ExportUrlSigner`], served by [`ExportDownloadHandler`].
+//!
+//! Jobs and part files live in the [`Storage`] backend, so they survive
+//! restarts and are visible to every instance sharing that storage.
+//!
+//! Each job runs in exactly one place: either spawned on the current Tokio
+//! runtime ([`ExportDispatch::Spawn`], the default) or enqueued on the task
+//! backend for a worker that has an [`ExportTaskFactory`] registered in its
+//! `TaskRegistry` ([`ExportDispatch::Backend`]).
+
+use crate::core::export::{CsvExporter, ExportFormat, TsvExporter};
+use crate::core::{AdminDatabase, AdminRecord};
+use crate::types::{AdminError, AdminResult, ExportJobResponse, ExportJobStatus};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use reinhardt_core::security::csrf::{generate_token_hmac, verify_token_hmac};
+use reinhardt_di::{DiResult, Injectable, InjectionContext};
+use reinhardt_http::{Handler, Request, Response};
+use reinhardt_tasks::{
+	Task, TaskBackend, TaskError, TaskExecutor, TaskFactory, TaskId, TaskResult,
+};
+use reinhardt_utils::storage::Storage;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Task name used when registering [`ExportTaskFactory`] with a task registry
+pub const EXPORT_TASK_NAME: &str = "reinhardt_admin.export";
+
+/// Storage directory holding job records and export files
+const EXPORT_DIR: &str = "exports";
+
+/// Default number of rows fetched per batch
+const DEFAULT_BATCH_SIZE: u64 = 1000;
+
+/// Default lifetime of signed download URLs
+const DEFAULT_URL_TTL_SECS: i64 = 3600;
+
+/// State of a background export job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportJob {
+	/// Job identifier (same as the task ID)
+	pub id: TaskId,
+	/// Model being exported
+	pub model_name: String,
+	/// Table the rows are read from
+	pub table_name: String,
+	/// Output format
+	pub format: ExportFormat,
+	/// Current status
+	pub status: ExportJobStatus,
+	/// Rows written so far
+	pub processed_rows: u64,
+	/// Total rows to export (known once the task starts)
+	pub total_rows: Option<u64>,
+	/// Download name of the finished file
+	pub file_name: Option<String>,
+	/// Storage paths of the file parts, in order
+	pub parts: Vec<String>,
+	/// Error message when the job failed
+	pub error: Option<String>,
+	/// Creation time
+	pub created_at: DateTime<Utc>,
+	/// Last update time
+	pub updated_at: DateTime<Utc>,
+}
+
+impl ExportJob {
+	fn new(
+		id: TaskId,
+		model_name: impl Into<String>,
+		table_name: impl Into<String>,
+		format: ExportFormat,
+	) -> Self {
+		let now = Utc::now();
+		Self {
+			id,
+			model_name: model_name.into(),
+			table_name: table_name.into(),
+			format,
+			status: ExportJobStatus::Pending,
+			processed_rows: 0,
+			total_rows: None,
+			file_name: None,
+			parts: Vec::new(),
+			error: None,
+			created_at: now,
+			updated_at: now,
+		}
+	}
+
+	/// Progress as a percentage (0-100), if the total is known
+	pub fn progress_percent(&self) -> Option<u8> {
+		match (self.status, self.total_rows) {
+			(ExportJobStatus::Completed, _) => Some(100),
+			(_, Some(0)) => Some(0),
+			(_, Some(total)) => Some(((self.processed_rows.min(total) * 100) / total) as u8),
+			(_, None) => None,
+		}
+	}
+
+	/// Logical storage path of the finished file, used in download URLs
+	pub fn file_path(&self) -> Option<String> {
+		self.file_name
+			.as_ref()
+			.map(|name| format!("{}/{}/{}", EXPORT_DIR, self.id, name))
+	}
+}
+
+/// Registry of export jobs and their progress
+///
+/// Jobs are stored as JSON records in a [`Storage`] backend, next to the
+/// export files. Each job is only written by the task running it.
+pub struct ExportJobStore {
+	storage: Arc<dyn Storage>,
+}
+
+impl ExportJobStore {
+	/// Create a job store saving its records to `storage`
+	pub fn new(storage: Arc<dyn Storage>) -> Self {
+		Self { storage }
+	}
+
+	/// Get a snapshot of a job
+	pub async fn get(&self, id: TaskId) -> AdminResult<Option<ExportJob>> {
+		let path = Self::record_path(id);
+		if !self.storage.exists(&path).await.map_err(storage_error)? {
+			return Ok(None);
+		}
+		let file = self.storage.read(&path).await.map_err(storage_error)?;
+		serde_json::from_slice(&file.content)
+			.map(Some)
+			.map_err(|e| AdminError::DatabaseError(format!("Corrupt export job {}: {}", id, e)))
+	}
+
+	/// Remove a job together with its file parts
+	pub async fn remove(&self, id: TaskId) -> AdminResult<Option<ExportJob>> {
+		let Some(job) = self.get(id).await? else {
+			return Ok(None);
+		};
+		for part in &job.parts {
+			self.storage.delete(part).await.map_err(storage_error)?;
+		}
+		self.storage
+			.delete(&Self::record_path(id))
+			.await
+			.map_err(storage_error)?;
+		Ok(Some(job))
+	}
+
+	async fn save(&self, job: &ExportJob) -> AdminResult<()> {
+		let data = serde_json::to_vec(job).map_err(|e| {
+			AdminError::DatabaseError(format!("Failed to encode export job: {}", e))
+		})?;
+		self.storage
+			.save(&Self::record_path(job.id), &data)
+			.await
+			.map_err(storage_error)?;
+		Ok(())
+	}
+
+	async fn update(&self, id: TaskId, f: impl FnOnce(&mut ExportJob)) -> AdminResult<ExportJob> {
+		let mut job = self
+			.get(id)
+			.await?
+			.ok_or_else(|| AdminError::InvalidAction(format!("Unknown export job: {}", id)))?;
+		f(&mut job);
+		job.updated_at = Utc::now();
+		self.save(&job).await?;
+		Ok(job)
+	}
+
+	fn record_path(id: TaskId) -> String {
+		format!("{}/{}/job.json", EXPORT_DIR, id)
+	}
+
+	fn part_path(id: TaskId, index: usize) -> String {
+		format!("{}/{}/part-{:06}", EXPORT_DIR, id, index)
+	}
+}
+
+fn storage_error(e: impl std::fmt::Display) -> AdminError {
+	AdminError::DatabaseError(format!("Export storage error: {}", e))
+}
+
+/// Signs and verifies time-limited download URLs
+///
+/// URLs carry an `expires` timestamp and an HMAC-SHA256 `signature` over the
+/// file path and expiry.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_admin::core::export_job::ExportUrlSigner;
+///
+/// let signer = ExportUrlSigner::new(b"secret-key", "/admin/exports/download");
+/// let url = signer.sign("exports/users.csv");
+///
+/// assert!(url.starts_with("/admin/exports/download?path=exports%2Fusers.csv&expires="));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExportUrlSigner {
+	secret: Vec<u8>,
+	base_url: String,
+	ttl: Duration,
+}
+
+impl ExportUrlSigner {
+	/// Create a signer producing URLs under `base_url`
+	pub fn new(secret: impl AsRef<[u8]>, base_url: impl Into<String>) -> Self {
+		Self {
+			secret: secret.as_ref().to_vec(),
+			base_url: base_url.into(),
+			ttl: Duration::seconds(DEFAULT_URL_TTL_SECS),
+		}
+	}
+
+	/// Set how long signed URLs stay valid
+	pub fn with_ttl(mut self, ttl: Duration) -> Self {
+		self.ttl = ttl;
+		self
+	}
+
+	/// Sign `path`, valid for the configured TTL from now
+	pub fn sign(&self, path: &str) -> String {
+		self.sign_until(path, Utc::now() + self.ttl)
+	}
+
+	/// Sign `path`, valid until `expires_at`
+	pub fn sign_until(&self, path: &str, expires_at: DateTime<Utc>) -> String {
+		let expires = expires_at.timestamp();
+		let signature = generate_token_hmac(&self.secret, &Self::message(path, expires));
+		let query = serde_urlencoded::to_string([
+			("path", path),
+			("expires", &expires.to_string()),
+			("signature", &signature),
+		])
+		.unwrap_or_default();
+		format!("{}?{}", self.base_url, query)
+	}
+
+	/// Verify a signature produced by [`sign`](Self::sign)
+	///
+	/// Returns `false` when the signature does not match or the URL expired.
+	pub fn verify(&self, path: &str, expires: i64, signature: &str) -> bool {
+		expires >= Utc::now().timestamp()
+			&& verify_token_hmac(signature, &self.secret, &Self::message(path, expires))
+	}
+
+	fn message(path: &str, expires: i64) -> String {
+		format!("{}:{}", path, expires)
+	}
+}
+
+/// Shared dependencies of export tasks
+#[derive(Clone)]
+pub struct ExportContext {
+	/// Database the rows are read from
+	pub db: Arc<AdminDatabase>,
+	/// Storage the job records and files are written to
+	pub storage: Arc<dyn Storage>,
+	/// Job progress registry
+	pub jobs: Arc<ExportJobStore>,
+	/// Download URL signer
+	pub signer: Arc<ExportUrlSigner>,
+	/// Rows fetched per batch
+	pub batch_size: u64,
+}
+
+impl ExportContext {
+	/// Create an export context with the default batch size
+	///
+	/// Job records are kept in `storage` alongside the export files.
+	pub fn new(
+		db: Arc<AdminDatabase>,
+		storage: Arc<dyn Storage>,
+		signer: Arc<ExportUrlSigner>,
+	) -> Self {
+		Self {
+			db,
+			jobs: Arc::new(ExportJobStore::new(storage.clone())),
+			storage,
+			signer,
+			batch_size: DEFAULT_BATCH_SIZE,
+		}
+	}
+
+	/// Set the number of rows fetched per batch
+	pub fn with_batch_size(mut self, batch_size: u64) -> Self {
+		self.batch_size = batch_size.max(1);
+		self
+	}
+
+	/// Build the pollable response for a job
+	pub async fn job_response(&self, id: TaskId) -> AdminResult<ExportJobResponse> {
+		let job = self
+			.jobs
+			.get(id)
+			.await?
+			.ok_or_else(|| AdminError::InvalidAction(format!("Unknown export job: {}", id)))?;
+		Ok(ExportJobResponse {
+			job_id: job.id.to_string(),
+			model_name: job.model_name.clone(),
+			status: job.status,
+			processed_rows: job.processed_rows,
+			total_rows: job.total_rows,
+			progress_percent: job.progress_percent(),
+			download_url: job
+				.file_path()
+				.filter(|_| job.status == ExportJobStatus::Completed)
+				.map(|p| self.signer.sign(&p)),
+			error: job.error.clone(),
+		})
+	}
+
+	/// Read the finished file behind a signed download path
+	///
+	/// Returns the job and the file content, or `None` when the path does not
+	/// name a completed export.
+	pub async fn read_file(&self, path: &str) -> AdminResult<Option<(ExportJob, Vec<u8>)>> {
+		let Some(id) = path
+			.strip_prefix(EXPORT_DIR)
+			.and_then(|rest| rest.strip_prefix('/'))
+			.and_then(|rest| rest.split_once('/'))
+			.and_then(|(id, _)| id.parse().ok())
+		else {
+			return Ok(None);
+		};
+		let Some(job) = self.jobs.get(id).await? else {
+			return Ok(None);
+		};
+		if job.status != ExportJobStatus::Completed || job.file_path().as_deref() != Some(path) {
+			return Ok(None);
+		}
+
+		let mut data = Vec::new();
+		for part in &job.parts {
+			let file = self.storage.read(part).await.map_err(storage_error)?;
+			data.extend_from_slice(&file.content);
+		}
+		Ok(Some((job, data)))
+	}
+}
+
+/// Serialized arguments of an export task
+///
+/// The job record holds everything else, so only its ID travels with the
+/// task.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportTaskArgs {
+	/// Job identifier
+	pub job_id: TaskId,
+}
+
+/// Task exporting a whole table to storage
+pub struct ExportTask {
+	args: ExportTaskArgs,
+	context: ExportContext,
+}
+
+impl ExportTask {
+	/// Create an export task
+	pub fn new(args: ExportTaskArgs, context: ExportContext) -> Self {
+		Self { args, context }
+	}
+
+	/// Get the task arguments
+	pub fn args(&self) -> &ExportTaskArgs {
+		&self.args
+	}
+
+	async fn run(&self) -> AdminResult<()> {
+		let id = self.args.job_id;
+		let jobs = &self.context.jobs;
+		let db = &self.context.db;
+
+		let job = jobs
+			.get(id)
+			.await?
+			.ok_or_else(|| AdminError::InvalidAction(format!("Unknown export job: {}", id)))?;
+		let total = db.count::<AdminRecord>(&job.table_name, vec![]).await?;
+		jobs.update(id, |job| {
+			job.status = ExportJobStatus::Running;
+			job.total_rows = Some(total);
+		})
+		.await?;
+
+		let mut writer = PartWriter::new(job.format);
+		let mut parts = Vec::new();
+		let mut offset = 0;
+		loop {
+			let batch = db
+				.list::<AdminRecord>(&job.table_name, vec![], offset, self.context.batch_size)
+				.await?;
+			let fetched = batch.len() as u64;
+			let rows: Vec<_> = batch.into_iter().map(stringify_row).collect();
+			let last = fetched < self.context.batch_size;
+
+			let mut chunk = writer.page(&rows)?;
+			if last {
+				chunk.extend(writer.finish());
+			}
+			if !chunk.is_empty() {
+				let path = ExportJobStore::part_path(id, parts.len());
+				self.context
+					.storage
+					.save(&path, &chunk)
+					.await
+					.map_err(storage_error)?;
+				parts.push(path);
+			}
+
+			offset += fetched;
+			let saved_parts = parts.clone();
+			jobs.update(id, |job| {
+				job.processed_rows = offset;
+				job.parts = saved_parts;
+			})
+			.await?;
+			if last {
+				break;
+			}
+		}
+
+		let file_name = format!(
+			"{}_{}.{}",
+			job.model_name,
+			Utc::now().format("%Y%m%d_%H%M%S"),
+			job.format.extension()
+		);
+		jobs.update(id, |job| {
+			job.status = ExportJobStatus::Completed;
+			job.file_name = Some(file_name);
+		})
+		.await?;
+		Ok(())
+	}
+}
+
+/// Serializes pages of rows into consecutive chunks of one file
+struct PartWriter {
+	format: ExportFormat,
+	fields: Option<Vec<String>>,
+	started: bool,
+}
+
+impl PartWriter {
+	fn new(format: ExportFormat) -> Self {
+		Self {
+			format,
+			fields: None,
+			started: false,
+		}
+	}
+
+	/// Serialize one page; column headers are written with the first rows
+	fn page(&mut self, rows: &[HashMap<String, String>]) -> AdminResult<Vec<u8>> {
+		let first = self.fields.is_none();
+		if first && !rows.is_empty() {
+			let mut fields: Vec<String> = rows[0].keys().cloned().collect();
+			fields.sort();
+			self.fields = Some(fields);
+		}
+		let fields = self.fields.as_deref().unwrap_or_default();
+
+		match self.format {
+			ExportFormat::CSV if rows.is_empty() => Ok(Vec::new()),
+			ExportFormat::CSV => CsvExporter::export(fields, rows, first),
+			ExportFormat::TSV if rows.is_empty() => Ok(Vec::new()),
+			ExportFormat::TSV => TsvExporter::export(fields, rows, first),
+			ExportFormat::JSON => {
+				let mut out = Vec::new();
+				for row in rows {
+					out.push(if self.started { b',' } else { b'[' });
+					self.started = true;
+					serde_json::to_writer(&mut out, row).map_err(|e| {
+						AdminError::ValidationError(format!("JSON export failed: {}", e))
+					})?;
+				}
+				Ok(out)
+			}
+			ExportFormat::Excel | ExportFormat::XML => Err(AdminError::ValidationError(format!(
+				"{:?} export not yet implemented",
+				self.format
+			))),
+		}
+	}
+
+	/// Trailing bytes closing the file
+	fn finish(&mut self) -> Vec<u8> {
+		match self.format {
+			ExportFormat::JSON if self.started => b"]".to_vec(),
+			ExportFormat::JSON => b"[]".to_vec(),
+			_ => Vec::new(),
+		}
+	}
+}
+
+impl Task for ExportTask {
+	fn id(&self) -> TaskId {
+		self.args.job_id
+	}
+
+	fn name(&self) -> &str {
+		EXPORT_TASK_NAME
+	}
+}
+
+#[async_trait]
+impl TaskExecutor for ExportTask {
+	async fn execute(&self) -> TaskResult<()> {
+		let id = self.args.job_id;
+		match self.run().await {
+			Ok(()) => Ok(()),
+			Err(e) => {
+				let message = e.to_string();
+				// Best effort: the job record may be what failed
+				let _ = self
+					.context
+					.jobs
+					.update(id, |job| {
+						job.status = ExportJobStatus::Failed;
+						job.error = Some(message.clone());
+					})
+					.await;
+				Err(TaskError::ExecutionFailed(message))
+			}
+		}
+	}
+}
+
+/// Creates [`ExportTask`]s from serialized [`ExportTaskArgs`]
+///
+/// Register it under [`EXPORT_TASK_NAME`] in a worker's `TaskRegistry` when
+/// using [`ExportDispatch::Backend`].
+pub struct ExportTaskFactory {
+	context: ExportContext,
+}
+
+impl ExportTaskFactory {
+	/// Create a factory sharing `context` with the exporter
+	pub fn new(context: ExportContext) -> Self {
+		Self { context }
+	}
+}
+
+#[async_trait]
+impl TaskFactory for ExportTaskFactory {
+	async fn create(&self, data: &str) -> TaskResult<Box<dyn TaskExecutor>> {
+		let args: ExportTaskArgs =
+			serde_json::from_str(data).map_err(|e| TaskError::SerializationError(e.to_string()))?;
+		Ok(Box::new(ExportTask::new(args, self.context.clone())))
+	}
+}
+
+/// Where export tasks are executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportDispatch {
+	/// Run on the current Tokio runtime without touching the task backend
+	#[default]
+	Spawn,
+	/// Enqueue on the task backend; a worker executes the task
+	Backend,
+}
+
+/// Entry point used by export handlers to start background exports
+///
+/// Register an `Arc<BackgroundExporter>` singleton in the DI container to make
+/// the `start_export` and `get_export_status` server functions available.
+#[derive(Clone)]
+pub struct BackgroundExporter {
+	context: ExportContext,
+	backend: Arc<dyn TaskBackend>,
+	dispatch: ExportDispatch,
+}
+
+impl BackgroundExporter {
+	/// Create an exporter enqueueing tasks on `backend`
+	pub fn new(context: ExportContext, backend: Arc<dyn TaskBackend>) -> Self {
+		Self {
+			context,
+			backend,
+			dispatch: ExportDispatch::default(),
+		}
+	}
+
+	/// Set where tasks are executed
+	pub fn with_dispatch(mut self, dispatch: ExportDispatch) -> Self {
+		self.dispatch = dispatch;
+		self
+	}
+
+	/// Get the shared export context
+	pub fn context(&self) -> &ExportContext {
+		&self.context
+	}
+
+	/// Handler serving the signed download URLs of finished exports
+	///
+	/// Mount it at the signer's base URL, e.g.
+	/// `router.handler_with_method("/exports/download", Method::GET, exporter.download_handler())`.
+	pub fn download_handler(&self) -> ExportDownloadHandler {
+		ExportDownloadHandler {
+			context: self.context.clone(),
+		}
+	}
+
+	/// Start an export of `table_name` and return the job ID
+	pub async fn enqueue(
+		&self,
+		model_name: &str,
+		table_name: &str,
+		format: ExportFormat,
+	) -> AdminResult<TaskId> {
+		if matches!(format, ExportFormat::Excel | ExportFormat::XML) {
+			return Err(AdminError::ValidationError(format!(
+				"{:?} export not yet implemented",
+				format
+			)));
+		}
+
+		let id = TaskId::new();
+		self.context
+			.jobs
+			.save(&ExportJob::new(id, model_name, table_name, format))
+			.await?;
+		let task = ExportTask::new(ExportTaskArgs { job_id: id }, self.context.clone());
+
+		match self.dispatch {
+			ExportDispatch::Spawn => {
+				tokio::spawn(async move {
+					// Failures are recorded on the job by `execute`
+					let _ = task.execute().await;
+				});
+			}
+			ExportDispatch::Backend => {
+				if let Err(e) = self.backend.enqueue(Box::new(task)).await {
+					let _ = self.context.jobs.remove(id).await;
+					return Err(AdminError::InvalidAction(format!(
+						"Failed to enqueue export: {}",
+						e
+					)));
+				}
+			}
+		}
+
+		Ok(id)
+	}
+
+	/// Get the pollable state of a job
+	pub async fn status(&self, id: TaskId) -> AdminResult<ExportJobResponse> {
+		self.context.job_response(id).await
+	}
+}
+
+/// Injectable trait implementation for BackgroundExporter
+///
+/// Resolves `Arc<BackgroundExporter>` from the container and clones the
+/// inner value, which shares the job store and backend.
+#[async_trait]
+impl Injectable for BackgroundExporter {
+	async fn inject(ctx: &InjectionContext) -> DiResult<Self> {
+		ctx.resolve::<Self>().await.map(|arc| (*arc).clone())
+	}
+}
+
+/// Query parameters of a signed download URL
+#[derive(Debug, Deserialize)]
+struct DownloadQuery {
+	path: String,
+	expires: i64,
+	signature: String,
+}
+
+/// Serves finished exports behind URLs signed by [`ExportUrlSigner`]
+///
+/// Responds with `403 Forbidden` when the signature is invalid or expired and
+/// `404 Not Found` when the path does not name a completed export.
+#[derive(Clone)]
+pub struct ExportDownloadHandler {
+	context: ExportContext,
+}
+
+#[async_trait]
+impl Handler for ExportDownloadHandler {
+	async fn handle(&self, request: Request) -> reinhardt_http::Result<Response> {
+		let Ok(query) =
+			serde_urlencoded::from_str::<DownloadQuery>(request.uri.query().unwrap_or_default())
+		else {
+			return Ok(Response::bad_request());
+		};
+		if !self
+			.context
+			.signer
+			.verify(&query.path, query.expires, &query.signature)
+		{
+			return Ok(Response::forbidden());
+		}
+
+		match self.context.read_file(&query.path).await? {
+			Some((job, data)) => Ok(Response::ok()
+				.with_header("Content-Type", job.format.mime_type())
+				.with_header(
+					"Content-Disposition",
+					&format!(
+						"attachment; filename=\"{}\"",
+						job.file_name.unwrap_or_default()
+					),
+				)
+				.with_body(data)),
+			None => Ok(Response::not_found()),
+		}
+	}
+}
+
+fn stringify_row(row: HashMap<String, serde_json::Value>) -> HashMap<String, String> {
+	row.into_iter()
+		.map(|(k, v)| {
+			let value = match v {
+				serde_json::Value::String(s) => s,
+				serde_json::Value::Null => String::new(),
+				other => other.to_string(),
+			};
+			(k, value)
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use reinhardt_db::orm::DatabaseConnection;
+	use reinhardt_tasks::{DummyBackend, TaskExecutionError, TaskStatus};
+	use reinhardt_test::fixtures::mock_connection;
+	use reinhardt_utils::storage::InMemoryStorage;
+	use rstest::*;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	fn context(conn: DatabaseConnection) -> ExportContext {
+		ExportContext::new(
+			Arc::new(AdminDatabase::new(conn)),
+			Arc::new(InMemoryStorage::new("memory", "/media/")),
+			Arc::new(ExportUrlSigner::new(
+				b"test-secret",
+				"/admin/exports/download",
+			)),
+		)
+	}
+
+	fn row(id: &str, name: &str) -> HashMap<String, String> {
+		HashMap::from([
+			("id".to_string(), id.to_string()),
+			("name".to_string(), name.to_string()),
+		])
+	}
+
+	fn download_request(url: &str) -> Request {
+		Request::builder().uri(url).build().unwrap()
+	}
+
+	/// Backend counting enqueued tasks
+	#[derive(Default)]
+	struct CountingBackend {
+		enqueued: AtomicUsize,
+	}
+
+	#[async_trait]
+	impl TaskBackend for CountingBackend {
+		async fn enqueue(&self, task: Box<dyn Task>) -> Result<TaskId, TaskExecutionError> {
+			self.enqueued.fetch_add(1, Ordering::SeqCst);
+			Ok(task.id())
+		}
+
+		async fn dequeue(&self) -> Result<Option<TaskId>, TaskExecutionError> {
+			Ok(None)
+		}
+
+		async fn get_status(&self, _task_id: TaskId) -> Result<TaskStatus, TaskExecutionError> {
+			Ok(TaskStatus::Pending)
+		}
+
+		async fn update_status(
+			&self,
+			_task_id: TaskId,
+			_status: TaskStatus,
+		) -> Result<(), TaskExecutionError> {
+			Ok(())
+		}
+
+		async fn get_task_data(
+			&self,
+			_task_id: TaskId,
+		) -> Result<Option<reinhardt_tasks::SerializedTask>, TaskExecutionError> {
+			Ok(None)
+		}
+
+		fn backend_name(&self) -> &str {
+			"counting"
+		}
+	}
+
+	async fn wait_for_completion(exporter: &BackgroundExporter, id: TaskId) -> ExportJobResponse {
+		for _ in 0..100 {
+			let status = exporter.status(id).await.unwrap();
+			if status.status == ExportJobStatus::Completed {
+				return status;
+			}
+			tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+		}
+		panic!("export did not complete");
+	}
+
+	#[rstest]
+	fn test_signer_round_trip() {
+		let signer = ExportUrlSigner::new(b"secret", "/download");
+		let expires = Utc::now() + Duration::minutes(5);
+
+		let url = signer.sign_until("exports/a.csv", expires);
+		let query = url.split_once('?').unwrap().1;
+		let params: HashMap<String, String> = serde_urlencoded::from_str(query).unwrap();
+
+		assert_eq!(params["path"], "exports/a.csv");
+		assert!(signer.verify(
+			&params["path"],
+			params["expires"].parse().unwrap(),
+			&params["signature"]
+		));
+		assert!(!signer.verify(
+			"exports/b.csv",
+			params["expires"].parse().unwrap(),
+			&params["signature"]
+		));
+	}
+
+	#[rstest]
+	fn test_signer_rejects_expired() {
+		let signer = ExportUrlSigner::new(b"secret", "/download");
+		let expires = Utc::now() - Duration::minutes(1);
+		let signature = generate_token_hmac(b"secret", &format!("a.csv:{}", expires.timestamp()));
+
+		assert!(!signer.verify("a.csv", expires.timestamp(), &signature));
+	}
+
+	#[rstest]
+	fn test_stringify_row() {
+		let row = HashMap::from([
+			("id".to_string(), serde_json::json!(1)),
+			("name".to_string(), serde_json::json!("Alice")),
+			("bio".to_string(), serde_json::Value::Null),
+		]);
+
+		let result = stringify_row(row);
+
+		assert_eq!(result["id"], "1");
+		assert_eq!(result["name"], "Alice");
+		assert_eq!(result["bio"], "");
+	}
+
+	#[rstest]
+	fn test_part_writer_csv_writes_header_once() {
+		let mut writer = PartWriter::new(ExportFormat::CSV);
+
+		let mut data = writer.page(&[row("1", "Alice")]).unwrap();
+		data.extend(writer.page(&[row("2", "Bob")]).unwrap());
+		data.extend(writer.page(&[]).unwrap());
+		data.extend(writer.finish());
+
+		assert_eq!(
+			String::from_utf8(data).unwrap(),
+			"id,name\n1,Alice\n2,Bob\n"
+		);
+	}
+
+	#[rstest]
+	#[case(vec![], serde_json::json!([]))]
+	#[case(
+		vec![vec![row("1", "Alice")], vec![row("2", "Bob")]],
+		serde_json::json!([{"id": "1", "name": "Alice"}, {"id": "2", "name": "Bob"}])
+	)]
+	fn test_part_writer_json_pages_form_one_array(
+		#[case] pages: Vec<Vec<HashMap<String, String>>>,
+		#[case] expected: serde_json::Value,
+	) {
+		let mut writer = PartWriter::new(ExportFormat::JSON);
+
+		let mut data = Vec::new();
+		for page in &pages {
+			data.extend(writer.page(page).unwrap());
+		}
+		data.extend(writer.finish());
+
+		let parsed: serde_json::Value = serde_json::from_slice(&data).unwrap();
+		assert_eq!(parsed, expected);
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_jobs_are_persisted_in_storage(mock_connection: DatabaseConnection) {
+		let context = context(mock_connection);
+		let id = TaskId::new();
+		context
+			.jobs
+			.save(&ExportJob::new(id, "User", "users", ExportFormat::CSV))
+			.await
+			.unwrap();
+
+		// A store on another instance sharing the storage sees the job
+		let other = ExportJobStore::new(context.storage.clone());
+		let job = other.get(id).await.unwrap().unwrap();
+
+		assert_eq!(job.table_name, "users");
+		assert_eq!(job.status, ExportJobStatus::Pending);
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_export_task_completes_and_stores_file(mock_connection: DatabaseConnection) {
+		let context = context(mock_connection);
+		let id = TaskId::new();
+		context
+			.jobs
+			.save(&ExportJob::new(id, "User", "users", ExportFormat::JSON))
+			.await
+			.unwrap();
+		let task = ExportTask::new(ExportTaskArgs { job_id: id }, context.clone());
+
+		task.execute().await.unwrap();
+
+		let response = context.job_response(id).await.unwrap();
+		assert_eq!(response.status, ExportJobStatus::Completed);
+		assert_eq!(response.progress_percent, Some(100));
+		let job = context.jobs.get(id).await.unwrap().unwrap();
+		let (_, data) = context
+			.read_file(&job.file_path().unwrap())
+			.await
+			.unwrap()
+			.unwrap();
+		assert_eq!(data, b"[]");
+		assert!(response.download_url.unwrap().contains("signature="));
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_enqueue_with_spawn_dispatch_runs_once_locally(
+		mock_connection: DatabaseConnection,
+	) {
+		let backend = Arc::new(CountingBackend::default());
+		let exporter = BackgroundExporter::new(context(mock_connection), backend.clone());
+
+		let id = exporter
+			.enqueue("User", "users", ExportFormat::CSV)
+			.await
+			.unwrap();
+
+		wait_for_completion(&exporter, id).await;
+		assert_eq!(backend.enqueued.load(Ordering::SeqCst), 0);
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_enqueue_with_backend_dispatch(mock_connection: DatabaseConnection) {
+		let backend = Arc::new(CountingBackend::default());
+		let exporter = BackgroundExporter::new(context(mock_connection), backend.clone())
+			.with_dispatch(ExportDispatch::Backend);
+
+		let id = exporter
+			.enqueue("User", "users", ExportFormat::CSV)
+			.await
+			.unwrap();
+		tokio::task::yield_now().await;
+
+		let status = exporter.status(id).await.unwrap();
+		assert_eq!(status.status, ExportJobStatus::Pending);
+		assert_eq!(status.download_url, None);
+		assert_eq!(backend.enqueued.load(Ordering::SeqCst), 1);
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_enqueue_rejects_unsupported_format(mock_connection: DatabaseConnection) {
+		let exporter = BackgroundExporter::new(context(mock_connection), Arc::new(DummyBackend));
+
+		let result = exporter.enqueue("User", "users", ExportFormat::XML).await;
+
+		assert!(matches!(result, Err(AdminError::ValidationError(_))));
+		let files = exporter.context().storage.list(EXPORT_DIR).await;
+		assert!(files.map(|f| f.is_empty()).unwrap_or(true));
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_download_handler_serves_signed_url(mock_connection: DatabaseConnection) {
+		let exporter = BackgroundExporter::new(context(mock_connection), Arc::new(DummyBackend));
+		let id = exporter
+			.enqueue("User", "users", ExportFormat::JSON)
+			.await
+			.unwrap();
+		let url = wait_for_completion(&exporter, id)
+			.await
+			.download_url
+			.unwrap();
+
+		let response = exporter
+			.download_handler()
+			.handle(download_request(&url))
+			.await
+			.unwrap();
+
+		assert_eq!(response.status.as_u16(), 200);
+		assert_eq!(response.body.as_ref(), b"[]");
+		assert_eq!(
+			response.headers.get("Content-Type").unwrap(),
+			"application/json"
+		);
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_download_handler_rejects_bad_signature(mock_connection: DatabaseConnection) {
+		let exporter = BackgroundExporter::new(context(mock_connection), Arc::new(DummyBackend));
+		let id = exporter
+			.enqueue("User", "users", ExportFormat::JSON)
+			.await
+			.unwrap();
+		wait_for_completion(&exporter, id).await;
+		let path = exporter
+			.context()
+			.jobs
+			.get(id)
+			.await
+			.unwrap()
+			.unwrap()
+			.file_path()
+			.unwrap();
+		let forged = ExportUrlSigner::new(b"other-secret", "/admin/exports/download").sign(&path);
+		let expired = exporter
+			.context()
+			.signer
+			.sign_until(&path, Utc::now() - Duration::minutes(1));
+
+		for url in [forged, expired] {
+			let response = exporter
+				.download_handler()
+				.handle(download_request(&url))
+				.await
+				.unwrap();
+
+			assert_eq!(response.status.as_u16(), 403);
+		}
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_factory_creates_task_from_args(mock_connection: DatabaseConnection) {
+		let factory = ExportTaskFactory::new(context(mock_connection));
+		let args = ExportTaskArgs {
+			job_id: TaskId::new(),
+		};
+
+		let task = factory
+			.create(&serde_json::to_string(&args).unwrap())
+			.await
+			.unwrap();
+
+		assert_eq!(task.id(), args.job_id);
+		assert_eq!(task.name(), EXPORT_TASK_NAME);
+	}
+}
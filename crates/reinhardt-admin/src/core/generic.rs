@@ -0,0 +1,417 @@
+//! Generic relation support
+//!
+//! Admin configuration for models using the contenttypes framework:
+//!
+//! - [`GenericForeignKeyConfig`] keeps the content type and object id columns
+//!   of a `GenericForeignKey` editable, turning the content type column into a
+//!   selector of the allowed models
+//! - [`GenericInline`] makes rows pointing to an object through a generic
+//!   relation (comments, tags, audit entries) listable, editable and
+//!   deletable from that object's page
+
+use crate::types::{AdminError, AdminResult, FieldInfo, FieldType};
+use reinhardt_db::contenttypes::{ContentType, GenericRelationQuery};
+use reinhardt_db::orm::Filter;
+use reinhardt_utils::utils_core::text::humanize_field_name;
+use std::collections::HashMap;
+
+/// Default name of the content type column
+pub const DEFAULT_CT_FIELD: &str = "content_type_id";
+
+/// Default name of the object id column
+pub const DEFAULT_FK_FIELD: &str = "object_id";
+
+/// A model a generic foreign key may point to
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenericTarget {
+	/// Content type of the model
+	pub content_type: ContentType,
+	/// Name under which the model is registered in the admin site
+	pub model_name: String,
+}
+
+/// Admin configuration of a `GenericForeignKey`
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_admin::core::GenericForeignKeyConfig;
+/// use reinhardt_db::contenttypes::ContentType;
+///
+/// let config = GenericForeignKeyConfig::new("target")
+///     .with_target(ContentType::new("blog", "Post").with_id(1), "Post")
+///     .with_target(ContentType::new("shop", "Product").with_id(2), "Product");
+///
+/// assert_eq!(config.ct_field(), "content_type_id");
+/// assert_eq!(config.target(2).unwrap().model_name, "Product");
+/// assert_eq!(
+///     config.content_type_choices(),
+///     vec![
+///         ("1".to_string(), "blog.Post".to_string()),
+///         ("2".to_string(), "shop.Product".to_string()),
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenericForeignKeyConfig {
+	name: String,
+	ct_field: String,
+	fk_field: String,
+	targets: Vec<GenericTarget>,
+}
+
+impl GenericForeignKeyConfig {
+	/// Create a configuration using the default column names
+	pub fn new(name: impl Into<String>) -> Self {
+		Self {
+			name: name.into(),
+			ct_field: DEFAULT_CT_FIELD.to_string(),
+			fk_field: DEFAULT_FK_FIELD.to_string(),
+			targets: Vec::new(),
+		}
+	}
+
+	/// Set the content type and object id column names
+	pub fn with_fields(mut self, ct_field: impl Into<String>, fk_field: impl Into<String>) -> Self {
+		self.ct_field = ct_field.into();
+		self.fk_field = fk_field.into();
+		self
+	}
+
+	/// Allow pointing to the given content type
+	///
+	/// `model_name` is the admin model used to look up objects of that type.
+	pub fn with_target(mut self, content_type: ContentType, model_name: impl Into<String>) -> Self {
+		self.targets.push(GenericTarget {
+			content_type,
+			model_name: model_name.into(),
+		});
+		self
+	}
+
+	/// Name of the generic foreign key
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Content type column name
+	pub fn ct_field(&self) -> &str {
+		&self.ct_field
+	}
+
+	/// Object id column name
+	pub fn fk_field(&self) -> &str {
+		&self.fk_field
+	}
+
+	/// Models the generic foreign key may point to
+	pub fn targets(&self) -> &[GenericTarget] {
+		&self.targets
+	}
+
+	/// Find the target with the given content type id
+	pub fn target(&self, content_type_id: i64) -> Option<&GenericTarget> {
+		self.targets
+			.iter()
+			.find(|t| t.content_type.id == Some(content_type_id))
+	}
+
+	/// Choices of the content type selector as (id, "app.Model") pairs
+	///
+	/// Targets whose content type has not been saved yet are skipped.
+	pub fn content_type_choices(&self) -> Vec<(String, String)> {
+		self.targets
+			.iter()
+			.filter_map(|t| {
+				t.content_type
+					.id
+					.map(|id| (id.to_string(), t.content_type.qualified_name()))
+			})
+			.collect()
+	}
+
+	/// Adjust the form field of one of the columns backing this generic foreign key
+	///
+	/// The content type column becomes a selector of the allowed content
+	/// types; both columns get a help text naming the relation. Other fields
+	/// are left untouched.
+	pub fn apply_to_field(&self, field: &mut FieldInfo) {
+		let label = humanize_field_name(&self.name);
+		if field.name == self.ct_field {
+			field.field_type = FieldType::Select {
+				choices: self.content_type_choices(),
+			};
+			field.help_text = Some(format!("Type of the {} object", label));
+		} else if field.name == self.fk_field {
+			field.help_text = Some(format!("ID of the {} object", label));
+		}
+	}
+}
+
+/// Inline editing of rows related to an object through a generic relation
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_admin::core::GenericInline;
+/// use reinhardt_db::contenttypes::ContentType;
+///
+/// let inline = GenericInline::new("Comment", ContentType::new("blog", "Post").with_id(3))
+///     .with_fields(vec!["author", "body"]);
+///
+/// let mut data = std::collections::HashMap::new();
+/// data.insert("body".to_string(), serde_json::json!("Nice"));
+/// inline.bind(&mut data, 42).unwrap();
+///
+/// assert_eq!(data["content_type_id"], 3);
+/// assert_eq!(data["object_id"], 42);
+/// assert_eq!(inline.filters(42).unwrap().len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenericInline {
+	model_name: String,
+	content_type: ContentType,
+	ct_field: String,
+	fk_field: String,
+	fields: Option<Vec<String>>,
+	extra: usize,
+}
+
+impl GenericInline {
+	/// Create an inline for `model_name` rows pointing to objects of `content_type`
+	///
+	/// `model_name` is the admin model of the related rows and `content_type`
+	/// the content type of the model owning the inline. The content type must
+	/// have been saved: reading or writing rows fails without its id.
+	pub fn new(model_name: impl Into<String>, content_type: ContentType) -> Self {
+		Self {
+			model_name: model_name.into(),
+			content_type,
+			ct_field: DEFAULT_CT_FIELD.to_string(),
+			fk_field: DEFAULT_FK_FIELD.to_string(),
+			fields: None,
+			extra: 1,
+		}
+	}
+
+	/// Set the content type and object id column names of the related model
+	pub fn with_relation_fields(
+		mut self,
+		ct_field: impl Into<String>,
+		fk_field: impl Into<String>,
+	) -> Self {
+		self.ct_field = ct_field.into();
+		self.fk_field = fk_field.into();
+		self
+	}
+
+	/// Set the fields editable in the inline
+	pub fn with_fields(mut self, fields: Vec<impl Into<String>>) -> Self {
+		self.fields = Some(fields.into_iter().map(Into::into).collect());
+		self
+	}
+
+	/// Set the number of empty rows offered for new objects
+	pub fn with_extra(mut self, extra: usize) -> Self {
+		self.extra = extra;
+		self
+	}
+
+	/// Admin model of the related rows
+	pub fn model_name(&self) -> &str {
+		&self.model_name
+	}
+
+	/// Content type of the owning model
+	pub fn content_type(&self) -> &ContentType {
+		&self.content_type
+	}
+
+	/// Content type column name
+	pub fn ct_field(&self) -> &str {
+		&self.ct_field
+	}
+
+	/// Object id column name
+	pub fn fk_field(&self) -> &str {
+		&self.fk_field
+	}
+
+	/// Fields editable in the inline (None = the related model's form fields)
+	pub fn fields(&self) -> Option<Vec<&str>> {
+		self.fields
+			.as_ref()
+			.map(|f| f.iter().map(|s| s.as_str()).collect())
+	}
+
+	/// Number of empty rows offered for new objects
+	pub fn extra(&self) -> usize {
+		self.extra
+	}
+
+	/// Id of the owning model's content type
+	fn content_type_id(&self) -> AdminResult<i64> {
+		self.content_type.id.ok_or_else(|| {
+			AdminError::InvalidAction(format!(
+				"Content type {} of the {} inline has not been saved",
+				self.content_type.qualified_name(),
+				self.model_name
+			))
+		})
+	}
+
+	/// Query selecting the rows related to `object_id`
+	pub fn query(&self, object_id: i64) -> GenericRelationQuery {
+		let mut query = GenericRelationQuery::new(self.content_type.clone())
+			.with_fields(self.ct_field.clone(), self.fk_field.clone());
+		query.add_object(object_id);
+		query
+	}
+
+	/// ORM filters selecting the rows related to `object_id`
+	pub fn filters(&self, object_id: i64) -> AdminResult<Vec<Filter>> {
+		self.content_type_id()?;
+		self.query(object_id)
+			.filters()
+			.map_err(|e| AdminError::InvalidAction(e.to_string()))
+	}
+
+	/// Point a row to `object_id`, overriding any submitted relation values
+	pub fn bind(
+		&self,
+		data: &mut HashMap<String, serde_json::Value>,
+		object_id: i64,
+	) -> AdminResult<()> {
+		let content_type_id = self.content_type_id()?;
+		data.insert(
+			self.ct_field.clone(),
+			serde_json::Value::from(content_type_id),
+		);
+		data.insert(self.fk_field.clone(), serde_json::Value::from(object_id));
+		Ok(())
+	}
+
+	/// Whether a stored row points to `object_id`
+	pub fn owns(&self, row: &HashMap<String, serde_json::Value>, object_id: i64) -> bool {
+		let as_i64 = |field: &str| {
+			row.get(field).and_then(|value| match value {
+				serde_json::Value::Number(n) => n.as_i64(),
+				serde_json::Value::String(s) => s.parse().ok(),
+				_ => None,
+			})
+		};
+		self.content_type.id.is_some()
+			&& as_i64(&self.ct_field) == self.content_type.id
+			&& as_i64(&self.fk_field) == Some(object_id)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use reinhardt_db::orm::{FilterOperator, FilterValue};
+	use rstest::rstest;
+	use serde_json::json;
+
+	fn post_type() -> ContentType {
+		ContentType::new("blog", "Post").with_id(3)
+	}
+
+	fn field(name: &str, field_type: FieldType) -> FieldInfo {
+		FieldInfo {
+			name: name.to_string(),
+			label: humanize_field_name(name),
+			field_type,
+			required: true,
+			readonly: false,
+			help_text: None,
+			placeholder: None,
+		}
+	}
+
+	#[rstest]
+	fn test_generic_fk_keeps_columns_editable() {
+		let config = GenericForeignKeyConfig::new("target")
+			.with_fields("target_ct", "target_id")
+			.with_target(post_type(), "Post")
+			.with_target(ContentType::new("shop", "Product"), "Product");
+		let mut ct = field("target_ct", FieldType::Number);
+		let mut fk = field("target_id", FieldType::Number);
+		let mut title = field("title", FieldType::Text);
+
+		config.apply_to_field(&mut ct);
+		config.apply_to_field(&mut fk);
+		config.apply_to_field(&mut title);
+
+		assert_eq!(
+			ct.field_type,
+			FieldType::Select {
+				choices: vec![("3".to_string(), "blog.Post".to_string())],
+			}
+		);
+		assert!(!ct.readonly);
+		assert_eq!(fk.field_type, FieldType::Number);
+		assert_eq!(fk.help_text.as_deref(), Some("ID of the Target object"));
+		assert_eq!(title.field_type, FieldType::Text);
+		assert!(title.help_text.is_none());
+		assert!(config.target(4).is_none());
+	}
+
+	#[rstest]
+	fn test_inline_filters_select_owner_rows() {
+		let inline = GenericInline::new("Tag", post_type()).with_relation_fields("ct", "obj");
+
+		let filters = inline.filters(42).unwrap();
+
+		assert_eq!(filters[0].field, "ct");
+		assert!(matches!(filters[0].operator, FilterOperator::Eq));
+		assert!(matches!(filters[0].value, FilterValue::Integer(3)));
+		assert_eq!(filters[1].field, "obj");
+		assert!(matches!(filters[1].value, FilterValue::Integer(42)));
+	}
+
+	#[rstest]
+	fn test_inline_bind_overrides_submitted_relation() {
+		let inline = GenericInline::new("Comment", post_type());
+		let mut data = HashMap::from([
+			("content_type_id".to_string(), json!(99)),
+			("object_id".to_string(), json!(1)),
+			("body".to_string(), json!("Hello")),
+		]);
+
+		inline.bind(&mut data, 42).unwrap();
+
+		assert_eq!(data["content_type_id"], json!(3));
+		assert_eq!(data["object_id"], json!(42));
+		assert_eq!(data["body"], json!("Hello"));
+	}
+
+	#[rstest]
+	fn test_inline_requires_saved_content_type() {
+		let inline = GenericInline::new("Comment", ContentType::new("blog", "Post"));
+		let mut data = HashMap::new();
+
+		assert!(inline.bind(&mut data, 42).is_err());
+		assert!(inline.filters(42).is_err());
+		assert!(data.is_empty());
+	}
+
+	#[rstest]
+	#[case(json!(3), json!(42), true)]
+	#[case(json!("3"), json!("42"), true)]
+	#[case(json!(4), json!(42), false)]
+	#[case(json!(3), json!(7), false)]
+	fn test_inline_owns(
+		#[case] ct: serde_json::Value,
+		#[case] object_id: serde_json::Value,
+		#[case] expected: bool,
+	) {
+		let inline = GenericInline::new("Comment", post_type());
+		let row = HashMap::from([
+			("content_type_id".to_string(), ct),
+			("object_id".to_string(), object_id),
+		]);
+
+		assert_eq!(inline.owns(&row, 42), expected);
+	}
+}
@@ -0,0 +1,824 @@
+//! Change history for admin-managed objects
+//!
+//! [`AuditLog`] records every create, update, delete and revert performed
+//! through the admin in a database table, with the acting user and
+//! field-level before/after values, so that an object's history can be
+//! displayed as human-readable diffs and any previous version can be
+//! restored.
+//!
+//! # Examples
+//!
+//! ```
+//! use reinhardt_admin::core::AdminDatabase;
+//! use reinhardt_admin::core::history::{AuditLog, ChangeAction};
+//! use std::collections::HashMap;
+//!
+//! # async fn example(db: &AdminDatabase) -> Result<(), Box<dyn std::error::Error>> {
+//! let log = AuditLog::new();
+//!
+//! let before = HashMap::from([("name".to_string(), serde_json::json!("Alice"))]);
+//! let after = HashMap::from([("name".to_string(), serde_json::json!("Alicia"))]);
+//! log.record_update(db, "User", "1", Some("42".to_string()), &before, &after)
+//!     .await?;
+//!
+//! let history = log.history(db, "User", "1").await?;
+//! assert_eq!(history[0].action, ChangeAction::Update);
+//! assert_eq!(history[0].changes[0].describe(), "Changed name from \"Alice\" to \"Alicia\"");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::database::{
+	AdminDatabase, get_sql, json_to_sea_value, restore_sql, row_to_record,
+};
+use crate::core::model_admin::ModelAdmin;
+use crate::types::{AdminError, AdminResult};
+use chrono::{DateTime, Utc};
+use reinhardt_db::orm::{DatabaseBackend, QueryRow, TransactionExecutor};
+use sea_query::{
+	Alias, Asterisk, ColumnDef, Expr, ExprTrait, MysqlQueryBuilder, Order, PostgresQueryBuilder,
+	Query as SeaQuery, SqliteQueryBuilder, Table,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Kind of change recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeAction {
+	/// Object was created
+	Create,
+	/// Object was updated
+	Update,
+	/// Object was deleted
+	Delete,
+	/// Object was restored to a previous version
+	Revert,
+}
+
+impl ChangeAction {
+	/// Human-readable verb for this action
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			ChangeAction::Create => "created",
+			ChangeAction::Update => "updated",
+			ChangeAction::Delete => "deleted",
+			ChangeAction::Revert => "reverted",
+		}
+	}
+}
+
+/// Before/after values of a single field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+	/// Field name
+	pub field: String,
+	/// Value before the change (`None` if the field did not exist)
+	pub before: Option<serde_json::Value>,
+	/// Value after the change (`None` if the field was removed)
+	pub after: Option<serde_json::Value>,
+}
+
+impl FieldChange {
+	/// Create a field change
+	pub fn new(
+		field: impl Into<String>,
+		before: Option<serde_json::Value>,
+		after: Option<serde_json::Value>,
+	) -> Self {
+		Self {
+			field: field.into(),
+			before,
+			after,
+		}
+	}
+
+	/// Describe the change in a human-readable sentence
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_admin::core::history::FieldChange;
+	///
+	/// let change = FieldChange::new("age", None, Some(serde_json::json!(30)));
+	/// assert_eq!(change.describe(), "Set age to 30");
+	/// ```
+	pub fn describe(&self) -> String {
+		match (&self.before, &self.after) {
+			(None, Some(after)) => format!("Set {} to {}", self.field, after),
+			(Some(before), None) => format!("Removed {} (was {})", self.field, before),
+			(Some(before), Some(after)) => {
+				format!("Changed {} from {} to {}", self.field, before, after)
+			}
+			(None, None) => format!("Touched {}", self.field),
+		}
+	}
+}
+
+/// Compute field-level changes between two versions of an object
+///
+/// Unchanged fields are omitted. The result is sorted by field name.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_admin::core::history::compute_changes;
+/// use std::collections::HashMap;
+///
+/// let before = HashMap::from([
+///     ("name".to_string(), serde_json::json!("Alice")),
+///     ("age".to_string(), serde_json::json!(30)),
+/// ]);
+/// let after = HashMap::from([
+///     ("name".to_string(), serde_json::json!("Alice")),
+///     ("age".to_string(), serde_json::json!(31)),
+/// ]);
+///
+/// let changes = compute_changes(&before, &after);
+/// assert_eq!(changes.len(), 1);
+/// assert_eq!(changes[0].field, "age");
+/// ```
+pub fn compute_changes(
+	before: &HashMap<String, serde_json::Value>,
+	after: &HashMap<String, serde_json::Value>,
+) -> Vec<FieldChange> {
+	let fields: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+
+	fields
+		.into_iter()
+		.filter_map(|field| {
+			let old = before.get(field);
+			let new = after.get(field);
+			if old == new {
+				None
+			} else {
+				Some(FieldChange::new(field.clone(), old.cloned(), new.cloned()))
+			}
+		})
+		.collect()
+}
+
+/// A single recorded change of an object
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLogEntry {
+	/// Entry identifier, unique within the log
+	pub id: u64,
+	/// Model name
+	pub model_name: String,
+	/// Primary key of the changed object
+	pub object_id: String,
+	/// User who performed the change, if known
+	pub user_id: Option<String>,
+	/// Kind of change
+	pub action: ChangeAction,
+	/// Field-level changes
+	pub changes: Vec<FieldChange>,
+	/// Full object state after the change (`None` after a delete)
+	pub snapshot: Option<HashMap<String, serde_json::Value>>,
+	/// Time of the change
+	pub timestamp: DateTime<Utc>,
+}
+
+impl AuditLogEntry {
+	/// One-line summary of the entry
+	pub fn summary(&self) -> String {
+		match self.changes.len() {
+			0 => format!("{} {}", self.model_name, self.action.as_str()),
+			1 => format!(
+				"{} {} (1 field changed)",
+				self.model_name,
+				self.action.as_str()
+			),
+			n => format!(
+				"{} {} ({} fields changed)",
+				self.model_name,
+				self.action.as_str(),
+				n
+			),
+		}
+	}
+}
+
+/// Log of changes performed through the admin, persisted in a database table
+///
+/// Entries are stored one row per change, in a table created on first use
+/// (see [`create_table_sql`](Self::create_table_sql)), and read back in
+/// chronological order.
+#[derive(Debug)]
+pub struct AuditLog {
+	table_name: String,
+	table_ready: AtomicBool,
+}
+
+impl Default for AuditLog {
+	fn default() -> Self {
+		Self {
+			table_name: Self::DEFAULT_TABLE.to_string(),
+			table_ready: AtomicBool::new(false),
+		}
+	}
+}
+
+impl AuditLog {
+	/// Default table name
+	pub const DEFAULT_TABLE: &'static str = "admin_audit_log";
+
+	/// Create an audit log using the default table
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Use a custom table name
+	pub fn with_table_name(mut self, table_name: impl Into<String>) -> Self {
+		self.table_name = table_name.into();
+		self
+	}
+
+	/// Get the table name
+	pub fn table_name(&self) -> &str {
+		&self.table_name
+	}
+
+	/// SQL creating the audit log table for a database backend
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_admin::core::history::AuditLog;
+	/// use reinhardt_db::orm::DatabaseBackend;
+	///
+	/// let sql = AuditLog::create_table_sql("admin_audit_log", DatabaseBackend::Postgres);
+	/// assert!(sql.starts_with(r#"CREATE TABLE IF NOT EXISTS "admin_audit_log""#));
+	/// ```
+	pub fn create_table_sql(table_name: &str, backend: DatabaseBackend) -> String {
+		let table = Table::create()
+			.table(Alias::new(table_name))
+			.if_not_exists()
+			.col(
+				ColumnDef::new(Alias::new("id"))
+					.big_integer()
+					.not_null()
+					.auto_increment()
+					.primary_key(),
+			)
+			.col(ColumnDef::new(Alias::new("model_name")).string().not_null())
+			.col(ColumnDef::new(Alias::new("object_id")).string().not_null())
+			.col(ColumnDef::new(Alias::new("user_id")).string().null())
+			.col(ColumnDef::new(Alias::new("action")).string().not_null())
+			.col(ColumnDef::new(Alias::new("changes")).text().not_null())
+			.col(ColumnDef::new(Alias::new("snapshot")).text().null())
+			.col(ColumnDef::new(Alias::new("timestamp")).string().not_null())
+			.to_owned();
+		match backend {
+			DatabaseBackend::Postgres => table.to_string(PostgresQueryBuilder),
+			DatabaseBackend::MySql => table.to_string(MysqlQueryBuilder),
+			DatabaseBackend::Sqlite => table.to_string(SqliteQueryBuilder),
+		}
+	}
+
+	/// Record the creation of an object
+	pub async fn record_create(
+		&self,
+		db: &AdminDatabase,
+		model_name: &str,
+		object_id: &str,
+		user_id: Option<String>,
+		data: &HashMap<String, serde_json::Value>,
+	) -> AdminResult<AuditLogEntry> {
+		let changes = compute_changes(&HashMap::new(), data);
+		self.insert(
+			db,
+			NewEntry {
+				model_name,
+				object_id,
+				user_id,
+				action: ChangeAction::Create,
+				changes,
+				snapshot: Some(data.clone()),
+				timestamp: Utc::now(),
+			},
+		)
+		.await
+	}
+
+	/// Record an update of an object
+	///
+	/// `after` is the full object state once the update has been applied.
+	pub async fn record_update(
+		&self,
+		db: &AdminDatabase,
+		model_name: &str,
+		object_id: &str,
+		user_id: Option<String>,
+		before: &HashMap<String, serde_json::Value>,
+		after: &HashMap<String, serde_json::Value>,
+	) -> AdminResult<AuditLogEntry> {
+		let changes = compute_changes(before, after);
+		self.insert(
+			db,
+			NewEntry {
+				model_name,
+				object_id,
+				user_id,
+				action: ChangeAction::Update,
+				changes,
+				snapshot: Some(after.clone()),
+				timestamp: Utc::now(),
+			},
+		)
+		.await
+	}
+
+	/// Record the deletion of an object
+	pub async fn record_delete(
+		&self,
+		db: &AdminDatabase,
+		model_name: &str,
+		object_id: &str,
+		user_id: Option<String>,
+		before: &HashMap<String, serde_json::Value>,
+	) -> AdminResult<AuditLogEntry> {
+		let changes = compute_changes(before, &HashMap::new());
+		self.insert(
+			db,
+			NewEntry {
+				model_name,
+				object_id,
+				user_id,
+				action: ChangeAction::Delete,
+				changes,
+				snapshot: None,
+				timestamp: Utc::now(),
+			},
+		)
+		.await
+	}
+
+	/// Get the history of an object, oldest first
+	pub async fn history(
+		&self,
+		db: &AdminDatabase,
+		model_name: &str,
+		object_id: &str,
+	) -> AdminResult<Vec<AuditLogEntry>> {
+		self.ensure_table(db).await?;
+		let sql = SeaQuery::select()
+			.column(Asterisk)
+			.from(Alias::new(&self.table_name))
+			.and_where(Expr::col(Alias::new("model_name")).eq(model_name))
+			.and_where(Expr::col(Alias::new("object_id")).eq(object_id))
+			.order_by(Alias::new("id"), Order::Asc)
+			.to_string(PostgresQueryBuilder);
+		let rows = db
+			.connection()
+			.query(&sql, vec![])
+			.await
+			.map_err(|e| AdminError::DatabaseError(e.to_string()))?;
+		rows.into_iter().map(entry_from_row).collect()
+	}
+
+	/// Find an entry by its identifier
+	pub async fn entry(
+		&self,
+		db: &AdminDatabase,
+		entry_id: u64,
+	) -> AdminResult<Option<AuditLogEntry>> {
+		self.ensure_table(db).await?;
+		let row = db
+			.connection()
+			.query_optional(&self.entry_sql(entry_id), vec![])
+			.await
+			.map_err(|e| AdminError::DatabaseError(e.to_string()))?;
+		row.map(entry_from_row).transpose()
+	}
+
+	/// Restore an object to the version recorded by an entry
+	///
+	/// The entry and the current object are read, the object is updated (or
+	/// re-inserted if it has been deleted) and a [`ChangeAction::Revert`]
+	/// entry is recorded, all inside a single transaction.
+	///
+	/// # Errors
+	///
+	/// Returns [`AdminError::ValidationError`] if the entry does not exist,
+	/// belongs to another object, or records a deletion.
+	pub async fn revert(
+		&self,
+		db: &AdminDatabase,
+		model_admin: &dyn ModelAdmin,
+		object_id: &str,
+		entry_id: u64,
+		user_id: Option<String>,
+	) -> AdminResult<AuditLogEntry> {
+		self.ensure_table(db).await?;
+		let mut tx = db
+			.connection()
+			.begin()
+			.await
+			.map_err(|e| AdminError::DatabaseError(e.to_string()))?;
+
+		match self
+			.revert_in(tx.as_mut(), model_admin, object_id, entry_id, user_id)
+			.await
+		{
+			Ok(entry) => {
+				tx.commit()
+					.await
+					.map_err(|e| AdminError::DatabaseError(e.to_string()))?;
+				Ok(entry)
+			}
+			Err(e) => {
+				// The original error is more useful than a rollback failure
+				let _ = tx.rollback().await;
+				Err(e)
+			}
+		}
+	}
+
+	async fn revert_in(
+		&self,
+		tx: &mut dyn TransactionExecutor,
+		model_admin: &dyn ModelAdmin,
+		object_id: &str,
+		entry_id: u64,
+		user_id: Option<String>,
+	) -> AdminResult<AuditLogEntry> {
+		let model_name = model_admin.model_name();
+		let table_name = model_admin.table_name();
+		let pk_field = model_admin.pk_field();
+
+		let entry = tx
+			.fetch_optional(&self.entry_sql(entry_id), vec![])
+			.await
+			.map_err(|e| AdminError::DatabaseError(e.to_string()))?
+			.map(|row| entry_from_row(QueryRow::from_backend_row(row)))
+			.transpose()?
+			.filter(|entry| entry.model_name == model_name && entry.object_id == object_id)
+			.ok_or_else(|| {
+				AdminError::ValidationError(format!(
+					"History entry {} not found for {} '{}'",
+					entry_id, model_name, object_id
+				))
+			})?;
+
+		let target = entry.snapshot.ok_or_else(|| {
+			AdminError::ValidationError(format!(
+				"Cannot revert to entry {}: the object was deleted",
+				entry_id
+			))
+		})?;
+
+		let current = tx
+			.fetch_optional(&get_sql(table_name, pk_field, object_id), vec![])
+			.await
+			.map_err(|e| AdminError::DatabaseError(e.to_string()))?
+			.and_then(|row| row_to_record(QueryRow::from_backend_row(row)));
+
+		let sql = restore_sql(
+			table_name,
+			pk_field,
+			object_id,
+			target.clone(),
+			current.is_some(),
+		)?;
+		tx.execute(&sql, vec![])
+			.await
+			.map_err(|e| AdminError::DatabaseError(e.to_string()))?;
+
+		let changes = compute_changes(&current.unwrap_or_default(), &target);
+		let new_entry = NewEntry {
+			model_name,
+			object_id,
+			user_id,
+			action: ChangeAction::Revert,
+			changes,
+			snapshot: Some(target),
+			timestamp: Utc::now(),
+		};
+		let row = tx
+			.fetch_one(&self.insert_sql(&new_entry)?, vec![])
+			.await
+			.map_err(|e| AdminError::DatabaseError(e.to_string()))?;
+		new_entry.into_entry(QueryRow::from_backend_row(row))
+	}
+
+	async fn insert(
+		&self,
+		db: &AdminDatabase,
+		new_entry: NewEntry<'_>,
+	) -> AdminResult<AuditLogEntry> {
+		self.ensure_table(db).await?;
+		let row = db
+			.connection()
+			.query_one(&self.insert_sql(&new_entry)?, vec![])
+			.await
+			.map_err(|e| AdminError::DatabaseError(e.to_string()))?;
+		new_entry.into_entry(row)
+	}
+
+	async fn ensure_table(&self, db: &AdminDatabase) -> AdminResult<()> {
+		if self.table_ready.load(Ordering::Acquire) {
+			return Ok(());
+		}
+		let connection = db.connection();
+		let sql = Self::create_table_sql(&self.table_name, connection.backend());
+		connection
+			.execute(&sql, vec![])
+			.await
+			.map_err(|e| AdminError::DatabaseError(e.to_string()))?;
+		self.table_ready.store(true, Ordering::Release);
+		Ok(())
+	}
+
+	fn entry_sql(&self, entry_id: u64) -> String {
+		SeaQuery::select()
+			.column(Asterisk)
+			.from(Alias::new(&self.table_name))
+			.and_where(Expr::col(Alias::new("id")).eq(entry_id))
+			.to_string(PostgresQueryBuilder)
+	}
+
+	fn insert_sql(&self, new_entry: &NewEntry<'_>) -> AdminResult<String> {
+		let changes = serde_json::to_string(&new_entry.changes)
+			.map_err(|e| AdminError::ValidationError(e.to_string()))?;
+		let snapshot = new_entry
+			.snapshot
+			.as_ref()
+			.map(serde_json::to_string)
+			.transpose()
+			.map_err(|e| AdminError::ValidationError(e.to_string()))?;
+		let values = [
+			json!(new_entry.model_name),
+			json!(new_entry.object_id),
+			json!(new_entry.user_id),
+			json!(new_entry.action),
+			json!(changes),
+			json!(snapshot),
+			json!(new_entry.timestamp.to_rfc3339()),
+		];
+
+		let mut query = SeaQuery::insert()
+			.into_table(Alias::new(&self.table_name))
+			.columns(
+				[
+					"model_name",
+					"object_id",
+					"user_id",
+					"action",
+					"changes",
+					"snapshot",
+					"timestamp",
+				]
+				.map(Alias::new),
+			)
+			.to_owned();
+		query
+			.values(values.map(|value| json_to_sea_value(value).into()))
+			.map_err(|e| AdminError::DatabaseError(e.to_string()))?;
+		query.returning_col(Alias::new("id"));
+		Ok(query.to_string(PostgresQueryBuilder))
+	}
+}
+
+/// Entry about to be written to the audit log
+struct NewEntry<'a> {
+	model_name: &'a str,
+	object_id: &'a str,
+	user_id: Option<String>,
+	action: ChangeAction,
+	changes: Vec<FieldChange>,
+	snapshot: Option<HashMap<String, serde_json::Value>>,
+	timestamp: DateTime<Utc>,
+}
+
+impl NewEntry<'_> {
+	/// Build the stored entry from the row returned by its insert
+	fn into_entry(self, row: QueryRow) -> AdminResult<AuditLogEntry> {
+		let id = row
+			.get::<u64>("id")
+			.ok_or_else(|| AdminError::DatabaseError("Audit log insert returned no id".into()))?;
+		Ok(AuditLogEntry {
+			id,
+			model_name: self.model_name.to_string(),
+			object_id: self.object_id.to_string(),
+			user_id: self.user_id,
+			action: self.action,
+			changes: self.changes,
+			snapshot: self.snapshot,
+			timestamp: self.timestamp,
+		})
+	}
+}
+
+/// Decode an audit log row
+fn entry_from_row(row: QueryRow) -> AdminResult<AuditLogEntry> {
+	let invalid = |field: &str| AdminError::DatabaseError(format!("Invalid audit log {}", field));
+	let text = |field: &str| row.get::<String>(field).ok_or_else(|| invalid(field));
+
+	let snapshot = match row.get::<Option<String>>("snapshot").flatten() {
+		Some(snapshot) => Some(serde_json::from_str(&snapshot).map_err(|_| invalid("snapshot"))?),
+		None => None,
+	};
+	Ok(AuditLogEntry {
+		id: row.get::<u64>("id").ok_or_else(|| invalid("id"))?,
+		model_name: text("model_name")?,
+		object_id: text("object_id")?,
+		user_id: row.get::<Option<String>>("user_id").flatten(),
+		action: serde_json::from_value(json!(text("action")?)).map_err(|_| invalid("action"))?,
+		changes: serde_json::from_str(&text("changes")?).map_err(|_| invalid("changes"))?,
+		snapshot,
+		timestamp: DateTime::parse_from_rfc3339(&text("timestamp")?)
+			.map_err(|_| invalid("timestamp"))?
+			.with_timezone(&Utc),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::core::database::AdminRecord;
+	use crate::core::model_admin::ModelAdminConfig;
+	use reinhardt_db::orm::DatabaseConnection;
+	use rstest::rstest;
+	use serde_json::json;
+
+	fn record(pairs: &[(&str, serde_json::Value)]) -> HashMap<String, serde_json::Value> {
+		pairs
+			.iter()
+			.map(|(k, v)| (k.to_string(), v.clone()))
+			.collect()
+	}
+
+	#[rstest]
+	fn test_compute_changes_detects_added_changed_and_removed_fields() {
+		// Arrange
+		let before = record(&[
+			("name", json!("Alice")),
+			("age", json!(30)),
+			("nick", json!("al")),
+		]);
+		let after = record(&[
+			("name", json!("Alice")),
+			("age", json!(31)),
+			("email", json!("a@x")),
+		]);
+
+		// Act
+		let changes = compute_changes(&before, &after);
+
+		// Assert
+		assert_eq!(
+			changes,
+			vec![
+				FieldChange::new("age", Some(json!(30)), Some(json!(31))),
+				FieldChange::new("email", None, Some(json!("a@x"))),
+				FieldChange::new("nick", Some(json!("al")), None),
+			]
+		);
+	}
+
+	#[rstest]
+	fn test_field_change_describe() {
+		// Arrange
+		let changed = FieldChange::new("age", Some(json!(30)), Some(json!(31)));
+		let removed = FieldChange::new("nick", Some(json!("al")), None);
+
+		// Act & Assert
+		assert_eq!(changed.describe(), "Changed age from 30 to 31");
+		assert_eq!(removed.describe(), "Removed nick (was \"al\")");
+	}
+
+	async fn sqlite_users_db() -> (tempfile::TempDir, AdminDatabase) {
+		let dir = tempfile::tempdir().unwrap();
+		let url = format!(
+			"sqlite://{}?mode=rwc",
+			dir.path().join("db.sqlite").display()
+		);
+		let conn = DatabaseConnection::connect(&url).await.unwrap();
+		conn.execute(
+			"CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)",
+			vec![],
+		)
+		.await
+		.unwrap();
+		(dir, AdminDatabase::new(conn))
+	}
+
+	fn users_admin() -> ModelAdminConfig {
+		ModelAdminConfig::builder()
+			.model_name("User")
+			.table_name("users")
+			.build()
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_history_is_persisted_per_object_and_chronological() {
+		// Arrange
+		let (_dir, db) = sqlite_users_db().await;
+		let log = AuditLog::new();
+		let v1 = record(&[("name", json!("Alice"))]);
+		let v2 = record(&[("name", json!("Alicia"))]);
+
+		// Act
+		log.record_create(&db, "User", "1", Some("admin".into()), &v1)
+			.await
+			.unwrap();
+		let update = log
+			.record_update(&db, "User", "1", Some("admin".into()), &v1, &v2)
+			.await
+			.unwrap();
+		log.record_create(&db, "User", "2", None, &v1)
+			.await
+			.unwrap();
+
+		// Assert
+		let history = AuditLog::new().history(&db, "User", "1").await.unwrap();
+		assert_eq!(history.len(), 2);
+		assert_eq!(history[0].action, ChangeAction::Create);
+		assert_eq!(history[1].action, ChangeAction::Update);
+		assert_eq!(history[1].id, update.id);
+		assert_eq!(history[1].user_id.as_deref(), Some("admin"));
+		assert_eq!(history[1].snapshot, Some(v2));
+		assert_eq!(history[1].summary(), "User updated (1 field changed)");
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_revert_restores_snapshot_and_records_revert() {
+		// Arrange
+		let (_dir, db) = sqlite_users_db().await;
+		let admin = users_admin();
+		let log = AuditLog::new();
+		let v1 = record(&[("id", json!(1)), ("name", json!("Alice"))]);
+		let v2 = record(&[("id", json!(1)), ("name", json!("Alicia"))]);
+		db.connection()
+			.execute("INSERT INTO users (id, name) VALUES (1, 'Alicia')", vec![])
+			.await
+			.unwrap();
+		let created = log
+			.record_create(&db, "User", "1", None, &v1)
+			.await
+			.unwrap();
+		log.record_update(&db, "User", "1", None, &v1, &v2)
+			.await
+			.unwrap();
+
+		// Act
+		let entry = log
+			.revert(&db, &admin, "1", created.id, Some("admin".into()))
+			.await
+			.unwrap();
+
+		// Assert
+		let current = db.get::<AdminRecord>("users", "id", "1").await.unwrap();
+		assert_eq!(current.unwrap()["name"], json!("Alice"));
+		assert_eq!(entry.action, ChangeAction::Revert);
+		assert_eq!(
+			entry.changes,
+			vec![FieldChange::new(
+				"name",
+				Some(json!("Alicia")),
+				Some(json!("Alice"))
+			)]
+		);
+		let history = log.history(&db, "User", "1").await.unwrap();
+		assert_eq!(history.len(), 3);
+		assert_eq!(history[2].user_id.as_deref(), Some("admin"));
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_revert_rejects_unknown_entry() {
+		// Arrange
+		let (_dir, db) = sqlite_users_db().await;
+		let log = AuditLog::new();
+
+		// Act
+		let result = log.revert(&db, &users_admin(), "1", 42, None).await;
+
+		// Assert
+		assert!(matches!(result, Err(AdminError::ValidationError(_))));
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_revert_rejects_delete_entry() {
+		// Arrange
+		let (_dir, db) = sqlite_users_db().await;
+		let log = AuditLog::new();
+		let entry = log
+			.record_delete(&db, "User", "1", None, &record(&[("name", json!("Alice"))]))
+			.await
+			.unwrap();
+
+		// Act
+		let result = log.revert(&db, &users_admin(), "1", entry.id, None).await;
+
+		// Assert
+		assert!(matches!(result, Err(AdminError::ValidationError(_))));
+		assert_eq!(log.history(&db, "User", "1").await.unwrap().len(), 1);
+	}
+}
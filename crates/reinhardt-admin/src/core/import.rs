@@ -2,8 +2,18 @@
 //!
 //! This module provides import capabilities for admin data from various formats
 //! including CSV and JSON.
+//!
+//! Parsed records can be checked without writing anything using the
+//! [`ImportValidator`], which produces a per-row report. An import in dry-run
+//! mode validates the records, inserts them in a transaction and rolls it
+//! back, so database constraints are checked as well.
+
+mod validation;
 
-use crate::types::{AdminError, AdminResult};
+pub use validation::{ColumnRule, ColumnType, ForeignKeyRef, ImportValidator};
+
+use crate::core::AdminDatabase;
+use crate::types::{AdminError, AdminResult, ImportValidationReport};
 use csv::ReaderBuilder;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -97,6 +107,8 @@ pub struct ImportConfig {
 	skip_header: bool,
 	/// Validate before import
 	validate_first: bool,
+	/// Only validate, never write
+	dry_run: bool,
 }
 
 impl ImportConfig {
@@ -113,6 +125,7 @@ impl ImportConfig {
 			max_records: None,
 			skip_header: true,
 			validate_first: true,
+			dry_run: false,
 		}
 	}
 
@@ -226,6 +239,17 @@ impl ImportConfig {
 	pub fn should_validate(&self) -> bool {
 		self.validate_first
 	}
+
+	/// Set dry-run mode (validate every row, write nothing)
+	pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+		self.dry_run = dry_run;
+		self
+	}
+
+	/// Check if this is a dry run
+	pub fn is_dry_run(&self) -> bool {
+		self.dry_run
+	}
 }
 
 /// Import result
@@ -615,6 +639,104 @@ impl ImportBuilder {
 		self
 	}
 
+	/// Enable dry-run mode
+	pub fn dry_run(mut self, dry_run: bool) -> Self {
+		self.config = self.config.with_dry_run(dry_run);
+		self
+	}
+
+	/// Get the import configuration
+	pub fn config(&self) -> &ImportConfig {
+		&self.config
+	}
+
+	/// Parse data and validate every row without writing anything
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_admin::core::import::{ColumnRule, ColumnType, ImportValidator};
+	/// use reinhardt_admin::core::{ImportBuilder, ImportFormat};
+	///
+	/// let validator = ImportValidator::new()
+	///     .with_rule(ColumnRule::new("id", ColumnType::Integer));
+	///
+	/// let report = ImportBuilder::new("User", ImportFormat::CSV)
+	///     .data(b"id,name\n1,Alice\nx,Bob".to_vec())
+	///     .dry_run(true)
+	///     .validate(&validator)
+	///     .unwrap();
+	///
+	/// assert_eq!(report.invalid_rows, 1);
+	/// ```
+	pub fn validate(self, validator: &ImportValidator) -> AdminResult<ImportValidationReport> {
+		let records = self.parse()?;
+		Ok(validator.validate(&records))
+	}
+
+	/// Parse, validate and insert the records into `table_name`
+	///
+	/// Records are validated first; if any row is invalid nothing is written
+	/// and every error is reported. Valid records are coerced with the same
+	/// rules and inserted in a single transaction, which is rolled back in
+	/// dry-run mode.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use reinhardt_admin::core::import::ImportValidator;
+	/// use reinhardt_admin::core::{AdminDatabase, ImportBuilder, ImportFormat};
+	/// use reinhardt_db::orm::DatabaseConnection;
+	///
+	/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+	/// let db = AdminDatabase::new(DatabaseConnection::connect("postgres://localhost/test").await?);
+	///
+	/// let result = ImportBuilder::new("User", ImportFormat::CSV)
+	///     .data(b"name\nAlice\nBob".to_vec())
+	///     .dry_run(true)
+	///     .import(&db, "users", &ImportValidator::new())
+	///     .await?;
+	///
+	/// // Both rows would be imported, none was persisted
+	/// assert_eq!(result.imported_count, 2);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub async fn import(
+		self,
+		db: &AdminDatabase,
+		table_name: &str,
+		validator: &ImportValidator,
+	) -> AdminResult<ImportResult> {
+		let commit = !self.config.is_dry_run();
+		let records = self.parse()?;
+
+		let report = validator
+			.validate_with_database(db, table_name, &records)
+			.await?;
+		if report.has_errors() {
+			let mut result = ImportResult::new();
+			result.failed_count = report.invalid_rows;
+			result.errors = report
+				.errors()
+				.map(|issue| {
+					let message = match &issue.column {
+						Some(column) => format!("{}: {}", column, issue.message),
+						None => issue.message.clone(),
+					};
+					ImportError::new(issue.row.unwrap_or(0), message)
+				})
+				.collect();
+			return Ok(result);
+		}
+
+		let rows = records
+			.iter()
+			.map(|record| validator.coerce_record(record))
+			.collect();
+		db.import_rows(table_name, rows, commit).await
+	}
+
 	/// Parse data
 	pub fn parse(self) -> AdminResult<Vec<HashMap<String, String>>> {
 		let mut records = match self.config.format() {
@@ -905,4 +1027,111 @@ mod tests {
 			Some(ImportFormat::JSON)
 		);
 	}
+
+	async fn sqlite_users_db() -> (tempfile::TempDir, AdminDatabase) {
+		let dir = tempfile::tempdir().unwrap();
+		let url = format!(
+			"sqlite://{}?mode=rwc",
+			dir.path().join("db.sqlite").display()
+		);
+		let conn = reinhardt_db::orm::DatabaseConnection::connect(&url)
+			.await
+			.unwrap();
+		conn.execute(
+			"CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, \
+			 name TEXT NOT NULL UNIQUE, age INTEGER)",
+			vec![],
+		)
+		.await
+		.unwrap();
+		(dir, AdminDatabase::new(conn))
+	}
+
+	async fn count_users(db: &AdminDatabase) -> i64 {
+		let row = db
+			.connection()
+			.query_one("SELECT COUNT(*) AS count FROM users", vec![])
+			.await
+			.unwrap();
+		row.data["count"].as_i64().unwrap()
+	}
+
+	fn users_validator() -> ImportValidator {
+		ImportValidator::new()
+			.with_rule(ColumnRule::new("name", ColumnType::Text).required(true))
+			.with_rule(ColumnRule::new("age", ColumnType::Integer))
+	}
+
+	#[tokio::test]
+	async fn test_import_dry_run_persists_nothing() {
+		let (_dir, db) = sqlite_users_db().await;
+
+		let result = ImportBuilder::new("User", ImportFormat::CSV)
+			.data(b"name,age\nAlice,30\nBob,".to_vec())
+			.dry_run(true)
+			.import(&db, "users", &users_validator())
+			.await
+			.unwrap();
+
+		assert_eq!(result.imported_count, 2);
+		assert!(result.is_successful());
+		assert_eq!(count_users(&db).await, 0);
+	}
+
+	#[tokio::test]
+	async fn test_import_inserts_coerced_values() {
+		let (_dir, db) = sqlite_users_db().await;
+
+		let result = ImportBuilder::new("User", ImportFormat::JSON)
+			.data(br#"[{"name":"Alice","age":"30"},{"name":"Bob","age":""}]"#.to_vec())
+			.import(&db, "users", &users_validator())
+			.await
+			.unwrap();
+
+		assert_eq!(result.imported_count, 2);
+		assert_eq!(count_users(&db).await, 2);
+		let row = db
+			.connection()
+			.query_one("SELECT age FROM users WHERE name = 'Alice'", vec![])
+			.await
+			.unwrap();
+		assert_eq!(row.data["age"], serde_json::json!(30));
+	}
+
+	#[tokio::test]
+	async fn test_import_dry_run_reports_constraint_violations() {
+		let (_dir, db) = sqlite_users_db().await;
+
+		let result = ImportBuilder::new("User", ImportFormat::CSV)
+			.data(b"name\nAlice\nAlice".to_vec())
+			.dry_run(true)
+			.import(&db, "users", &users_validator())
+			.await
+			.unwrap();
+
+		assert_eq!(result.imported_count, 0);
+		assert_eq!(result.failed_count, 1);
+		assert_eq!(result.errors[0].row_number, 2);
+		assert_eq!(count_users(&db).await, 0);
+	}
+
+	#[tokio::test]
+	async fn test_import_writes_nothing_when_validation_fails() {
+		let (_dir, db) = sqlite_users_db().await;
+
+		let result = ImportBuilder::new("User", ImportFormat::CSV)
+			.data(b"name,age\nAlice,30\nBob,old".to_vec())
+			.import(&db, "users", &users_validator())
+			.await
+			.unwrap();
+
+		assert_eq!(result.imported_count, 0);
+		assert_eq!(result.failed_count, 1);
+		assert_eq!(result.errors[0].row_number, 2);
+		assert_eq!(
+			result.errors[0].message,
+			"age: 'old' is not a valid integer"
+		);
+		assert_eq!(count_users(&db).await, 0);
+	}
 }
@@ -0,0 +1,656 @@
+//! Dry-run validation for admin imports
+//!
+//! [`ImportValidator`] checks parsed import records against per-column rules
+//! without writing anything to the database. Every row is validated and all
+//! problems are collected into an [`ImportValidationReport`], so users can fix
+//! the whole file at once instead of one error at a time.
+//!
+//! Checks performed:
+//! - Type coercion (integer, float, boolean, date, datetime, UUID, JSON)
+//! - Required columns
+//! - Uniqueness within the file and against existing rows
+//! - Foreign key resolution against the referenced table
+//!
+//! Columns that have no rule are reported as warnings.
+
+use crate::core::AdminDatabase;
+use crate::types::{
+	AdminError, AdminResult, ImportIssue, ImportIssueSeverity, ImportValidationReport,
+};
+use reinhardt_db::migrations::{FieldType as DbFieldType, ModelMetadata};
+use sea_query::{Alias, Expr, ExprTrait, PostgresQueryBuilder, Query as SeaQuery};
+use std::collections::{HashMap, HashSet};
+
+/// Expected type of an import column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnType {
+	/// Any string
+	Text,
+	/// Signed 64-bit integer
+	Integer,
+	/// Floating point number
+	Float,
+	/// Boolean (`true`/`false`, `1`/`0`, `yes`/`no`)
+	Boolean,
+	/// Date in `YYYY-MM-DD` format
+	Date,
+	/// RFC 3339 datetime or `YYYY-MM-DD HH:MM:SS`
+	DateTime,
+	/// UUID
+	Uuid,
+	/// JSON document
+	Json,
+}
+
+impl ColumnType {
+	/// Map a database field type to the import column type used for coercion
+	pub fn from_db_type(db_type: &DbFieldType) -> Self {
+		match db_type {
+			DbFieldType::BigInteger
+			| DbFieldType::Integer
+			| DbFieldType::SmallInteger
+			| DbFieldType::TinyInt
+			| DbFieldType::MediumInt
+			| DbFieldType::Year
+			| DbFieldType::ForeignKey { .. }
+			| DbFieldType::OneToOne { .. } => ColumnType::Integer,
+			DbFieldType::Decimal { .. }
+			| DbFieldType::Float
+			| DbFieldType::Double
+			| DbFieldType::Real => ColumnType::Float,
+			DbFieldType::Boolean => ColumnType::Boolean,
+			DbFieldType::Date => ColumnType::Date,
+			DbFieldType::DateTime | DbFieldType::TimestampTz => ColumnType::DateTime,
+			DbFieldType::Uuid => ColumnType::Uuid,
+			DbFieldType::Json | DbFieldType::JsonBinary => ColumnType::Json,
+			_ => ColumnType::Text,
+		}
+	}
+
+	/// Coerce a raw import value into a typed JSON value
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_admin::core::import::ColumnType;
+	///
+	/// assert_eq!(ColumnType::Integer.coerce("42").unwrap(), serde_json::json!(42));
+	/// assert_eq!(ColumnType::Boolean.coerce("yes").unwrap(), serde_json::json!(true));
+	/// assert!(ColumnType::Date.coerce("2025-13-01").is_err());
+	/// ```
+	pub fn coerce(&self, raw: &str) -> Result<serde_json::Value, String> {
+		let value = raw.trim();
+		match self {
+			ColumnType::Text => Ok(serde_json::Value::String(raw.to_string())),
+			ColumnType::Integer => value
+				.parse::<i64>()
+				.map(Into::into)
+				.map_err(|_| format!("'{}' is not a valid integer", value)),
+			ColumnType::Float => value
+				.parse::<f64>()
+				.ok()
+				.and_then(serde_json::Number::from_f64)
+				.map(serde_json::Value::Number)
+				.ok_or_else(|| format!("'{}' is not a valid number", value)),
+			ColumnType::Boolean => match value.to_lowercase().as_str() {
+				"true" | "1" | "yes" | "y" | "t" => Ok(true.into()),
+				"false" | "0" | "no" | "n" | "f" => Ok(false.into()),
+				_ => Err(format!("'{}' is not a valid boolean", value)),
+			},
+			ColumnType::Date => chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+				.map(|d| d.to_string().into())
+				.map_err(|_| format!("'{}' is not a valid date (expected YYYY-MM-DD)", value)),
+			ColumnType::DateTime => chrono::DateTime::parse_from_rfc3339(value)
+				.map(|dt| dt.to_rfc3339().into())
+				.or_else(|_| {
+					chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+						.map(|dt| dt.to_string().into())
+				})
+				.map_err(|_| format!("'{}' is not a valid datetime", value)),
+			ColumnType::Uuid => uuid::Uuid::parse_str(value)
+				.map(|u| u.to_string().into())
+				.map_err(|_| format!("'{}' is not a valid UUID", value)),
+			ColumnType::Json => serde_json::from_str(value)
+				.map_err(|e| format!("'{}' is not valid JSON: {}", value, e)),
+		}
+	}
+}
+
+/// Reference to the column a foreign key points at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForeignKeyRef {
+	/// Referenced table
+	pub table: String,
+	/// Referenced column (usually "id")
+	pub column: String,
+}
+
+/// Validation rule for a single import column
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_admin::core::import::{ColumnRule, ColumnType};
+///
+/// let rule = ColumnRule::new("author_id", ColumnType::Integer)
+///     .required(true)
+///     .references("users", "id");
+///
+/// assert!(rule.is_required());
+/// assert_eq!(rule.foreign_key().unwrap().table, "users");
+/// ```
+#[derive(Debug, Clone)]
+pub struct ColumnRule {
+	column: String,
+	column_type: ColumnType,
+	required: bool,
+	unique: bool,
+	foreign_key: Option<ForeignKeyRef>,
+}
+
+impl ColumnRule {
+	/// Create a rule for `column` expecting values of `column_type`
+	pub fn new(column: impl Into<String>, column_type: ColumnType) -> Self {
+		Self {
+			column: column.into(),
+			column_type,
+			required: false,
+			unique: false,
+			foreign_key: None,
+		}
+	}
+
+	/// Set whether a non-empty value is required
+	pub fn required(mut self, required: bool) -> Self {
+		self.required = required;
+		self
+	}
+
+	/// Set whether values must be unique (within the file and the table)
+	pub fn unique(mut self, unique: bool) -> Self {
+		self.unique = unique;
+		self
+	}
+
+	/// Require values to exist in `table.column`
+	pub fn references(mut self, table: impl Into<String>, column: impl Into<String>) -> Self {
+		self.foreign_key = Some(ForeignKeyRef {
+			table: table.into(),
+			column: column.into(),
+		});
+		self
+	}
+
+	/// Get the column name
+	pub fn column(&self) -> &str {
+		&self.column
+	}
+
+	/// Get the expected column type
+	pub fn column_type(&self) -> ColumnType {
+		self.column_type
+	}
+
+	/// Check if the column is required
+	pub fn is_required(&self) -> bool {
+		self.required
+	}
+
+	/// Check if the column must be unique
+	pub fn is_unique(&self) -> bool {
+		self.unique
+	}
+
+	/// Get the foreign key reference, if any
+	pub fn foreign_key(&self) -> Option<&ForeignKeyRef> {
+		self.foreign_key.as_ref()
+	}
+}
+
+/// Validates import records without writing them
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_admin::core::import::{ColumnRule, ColumnType, ImportValidator};
+/// use std::collections::HashMap;
+///
+/// let validator = ImportValidator::new()
+///     .with_rule(ColumnRule::new("age", ColumnType::Integer).required(true));
+///
+/// let records = vec![HashMap::from([("age".to_string(), "abc".to_string())])];
+/// let report = validator.validate(&records);
+///
+/// assert!(report.has_errors());
+/// assert_eq!(report.invalid_rows, 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ImportValidator {
+	rules: Vec<ColumnRule>,
+}
+
+impl ImportValidator {
+	/// Create a validator with no rules
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Build a validator from model metadata registered in the migration registry
+	///
+	/// Primary key columns are skipped because they are usually generated.
+	pub fn from_model_metadata(metadata: &ModelMetadata) -> Self {
+		let is_set = |params: &HashMap<String, String>, key: &str| {
+			params.get(key).map(|v| v == "true").unwrap_or(false)
+		};
+
+		let mut rules: Vec<ColumnRule> = metadata
+			.fields
+			.iter()
+			.filter(|(_, meta)| !is_set(&meta.params, "primary_key"))
+			.map(|(name, meta)| {
+				let mut rule = ColumnRule::new(name, ColumnType::from_db_type(&meta.field_type))
+					.required(
+						!is_set(&meta.params, "null")
+							&& !is_set(&meta.params, "blank")
+							&& !meta.params.contains_key("default"),
+					)
+					.unique(is_set(&meta.params, "unique"));
+				if let Some(fk) = &meta.foreign_key {
+					rule = rule.references(&fk.referenced_table, &fk.referenced_column);
+				}
+				rule
+			})
+			.collect();
+		rules.sort_by(|a, b| a.column.cmp(&b.column));
+
+		Self { rules }
+	}
+
+	/// Add a column rule
+	pub fn with_rule(mut self, rule: ColumnRule) -> Self {
+		self.rules.push(rule);
+		self
+	}
+
+	/// Get all column rules
+	pub fn rules(&self) -> &[ColumnRule] {
+		&self.rules
+	}
+
+	fn rule(&self, column: &str) -> Option<&ColumnRule> {
+		self.rules.iter().find(|r| r.column == column)
+	}
+
+	/// Convert a record into the typed values written to the database
+	///
+	/// Values are coerced exactly as [`validate`](Self::validate) checks them.
+	/// Empty values are left out so column defaults apply, and columns without
+	/// a rule are dropped. A validator without rules keeps every non-empty
+	/// value as text.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_admin::core::import::{ColumnRule, ColumnType, ImportValidator};
+	/// use std::collections::HashMap;
+	///
+	/// let validator = ImportValidator::new()
+	///     .with_rule(ColumnRule::new("age", ColumnType::Integer))
+	///     .with_rule(ColumnRule::new("bio", ColumnType::Text));
+	///
+	/// let record = HashMap::from([
+	///     ("age".to_string(), "42".to_string()),
+	///     ("bio".to_string(), "".to_string()),
+	///     ("nickname".to_string(), "Al".to_string()),
+	/// ]);
+	/// let values = validator.coerce_record(&record);
+	///
+	/// assert_eq!(values, HashMap::from([("age".to_string(), serde_json::json!(42))]));
+	/// ```
+	pub fn coerce_record(
+		&self,
+		record: &HashMap<String, String>,
+	) -> HashMap<String, serde_json::Value> {
+		record
+			.iter()
+			.filter(|(_, raw)| !raw.trim().is_empty())
+			.filter_map(|(column, raw)| {
+				let value = match self.rule(column) {
+					Some(rule) => rule
+						.column_type
+						.coerce(raw)
+						.unwrap_or_else(|_| serde_json::Value::String(raw.clone())),
+					None if self.rules.is_empty() => serde_json::Value::String(raw.clone()),
+					None => return None,
+				};
+				Some((column.clone(), value))
+			})
+			.collect()
+	}
+
+	/// Validate records without database access
+	///
+	/// Performs type coercion, required and in-file uniqueness checks.
+	/// Row numbers in the report are 1-indexed data rows.
+	pub fn validate(&self, records: &[HashMap<String, String>]) -> ImportValidationReport {
+		let mut issues = Vec::new();
+
+		if !self.rules.is_empty() {
+			let mut unknown: Vec<&String> = records
+				.iter()
+				.flat_map(|r| r.keys())
+				.filter(|c| self.rule(c).is_none())
+				.collect::<HashSet<_>>()
+				.into_iter()
+				.collect();
+			unknown.sort();
+			for column in unknown {
+				issues.push(ImportIssue {
+					row: None,
+					column: Some(column.clone()),
+					severity: ImportIssueSeverity::Warning,
+					message: format!(
+						"Column '{}' is not a model field and will be ignored",
+						column
+					),
+				});
+			}
+		}
+
+		let mut seen: HashMap<&str, HashMap<String, usize>> = HashMap::new();
+		for (index, record) in records.iter().enumerate() {
+			let row = index + 1;
+			for rule in &self.rules {
+				let raw = record.get(&rule.column).map(String::as_str).unwrap_or("");
+				if raw.trim().is_empty() {
+					if rule.required {
+						issues.push(ImportIssue::error(
+							row,
+							&rule.column,
+							"This field is required",
+						));
+					}
+					continue;
+				}
+
+				if let Err(message) = rule.column_type.coerce(raw) {
+					issues.push(ImportIssue::error(row, &rule.column, message));
+					continue;
+				}
+
+				if rule.unique {
+					let values = seen.entry(rule.column.as_str()).or_default();
+					if let Some(first_row) = values.get(raw.trim()) {
+						issues.push(ImportIssue::error(
+							row,
+							&rule.column,
+							format!(
+								"Duplicate value '{}' (first seen in row {})",
+								raw.trim(),
+								first_row
+							),
+						));
+					} else {
+						values.insert(raw.trim().to_string(), row);
+					}
+				}
+			}
+		}
+
+		ImportValidationReport::new(records.len(), issues)
+	}
+
+	/// Validate records including checks that require the database
+	///
+	/// In addition to [`validate`](Self::validate), this resolves foreign keys
+	/// against their referenced tables and checks unique columns against rows
+	/// already stored in `table_name`. Nothing is written.
+	pub async fn validate_with_database(
+		&self,
+		db: &AdminDatabase,
+		table_name: &str,
+		records: &[HashMap<String, String>],
+	) -> AdminResult<ImportValidationReport> {
+		let mut report = self.validate(records);
+
+		for rule in &self.rules {
+			// Only look up values that passed offline validation for this column
+			let candidates: Vec<(usize, &str)> = records
+				.iter()
+				.enumerate()
+				.map(|(i, r)| (i + 1, r.get(&rule.column).map(|v| v.trim()).unwrap_or("")))
+				.filter(|(row, v)| !v.is_empty() && !report.has_error_at(*row, &rule.column))
+				.collect();
+			if candidates.is_empty() {
+				continue;
+			}
+			let distinct: Vec<&str> = candidates
+				.iter()
+				.map(|(_, v)| *v)
+				.collect::<HashSet<_>>()
+				.into_iter()
+				.collect();
+
+			if let Some(fk) = &rule.foreign_key {
+				let existing = fetch_existing_values(db, &fk.table, &fk.column, &distinct).await?;
+				for (row, value) in &candidates {
+					if !existing.contains(*value) {
+						report.push(ImportIssue::error(
+							*row,
+							&rule.column,
+							format!("No {} with {} '{}' exists", fk.table, fk.column, value),
+						));
+					}
+				}
+			}
+
+			if rule.unique {
+				let existing =
+					fetch_existing_values(db, table_name, &rule.column, &distinct).await?;
+				for (row, value) in &candidates {
+					if existing.contains(*value) {
+						report.push(ImportIssue::error(
+							*row,
+							&rule.column,
+							format!("Value '{}' already exists in {}", value, table_name),
+						));
+					}
+				}
+			}
+		}
+
+		Ok(report)
+	}
+}
+
+/// Fetch which of `values` already exist in `table.column`
+async fn fetch_existing_values(
+	db: &AdminDatabase,
+	table: &str,
+	column: &str,
+	values: &[&str],
+) -> AdminResult<HashSet<String>> {
+	let sql = SeaQuery::select()
+		.from(Alias::new(table))
+		.column(Alias::new(column))
+		.and_where(Expr::col(Alias::new(column)).is_in(values.iter().copied()))
+		.to_string(PostgresQueryBuilder);
+
+	let rows = db
+		.connection()
+		.query(&sql, vec![])
+		.await
+		.map_err(|e| AdminError::DatabaseError(e.to_string()))?;
+
+	Ok(rows
+		.iter()
+		.filter_map(|row| row.data.get(column))
+		.map(|value| match value {
+			serde_json::Value::String(s) => s.clone(),
+			other => other.to_string(),
+		})
+		.collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use reinhardt_db::migrations::FieldMetadata;
+	use reinhardt_db::orm::DatabaseConnection;
+	use reinhardt_test::fixtures::mock_connection;
+	use rstest::*;
+
+	fn record(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+		pairs
+			.iter()
+			.map(|(k, v)| (k.to_string(), v.to_string()))
+			.collect()
+	}
+
+	#[rstest]
+	#[case(ColumnType::Integer, " 7 ", serde_json::json!(7))]
+	#[case(ColumnType::Float, "1.5", serde_json::json!(1.5))]
+	#[case(ColumnType::Boolean, "No", serde_json::json!(false))]
+	#[case(ColumnType::Date, "2025-01-31", serde_json::json!("2025-01-31"))]
+	#[case(ColumnType::Json, r#"{"a":1}"#, serde_json::json!({"a": 1}))]
+	fn test_coerce_valid(
+		#[case] column_type: ColumnType,
+		#[case] raw: &str,
+		#[case] expected: serde_json::Value,
+	) {
+		assert_eq!(column_type.coerce(raw).unwrap(), expected);
+	}
+
+	#[rstest]
+	fn test_coerce_invalid() {
+		assert_eq!(
+			ColumnType::Integer.coerce("1.5").unwrap_err(),
+			"'1.5' is not a valid integer"
+		);
+		assert!(ColumnType::Uuid.coerce("not-a-uuid").is_err());
+		assert!(ColumnType::DateTime.coerce("yesterday").is_err());
+	}
+
+	#[rstest]
+	fn test_validate_reports_every_row() {
+		let validator = ImportValidator::new()
+			.with_rule(
+				ColumnRule::new("email", ColumnType::Text)
+					.required(true)
+					.unique(true),
+			)
+			.with_rule(ColumnRule::new("age", ColumnType::Integer));
+		let records = vec![
+			record(&[("email", "a@example.com"), ("age", "30")]),
+			record(&[("email", ""), ("age", "old")]),
+			record(&[("email", "a@example.com"), ("age", "")]),
+		];
+
+		let report = validator.validate(&records);
+
+		assert_eq!(report.total_rows, 3);
+		assert_eq!(report.valid_rows, 1);
+		assert_eq!(report.invalid_rows, 2);
+		let row2: Vec<_> = report.issues_for_row(2).map(|i| i.column.clone()).collect();
+		assert_eq!(
+			row2,
+			vec![Some("email".to_string()), Some("age".to_string())]
+		);
+		let row3: Vec<_> = report
+			.issues_for_row(3)
+			.map(|i| i.message.clone())
+			.collect();
+		assert_eq!(
+			row3,
+			vec!["Duplicate value 'a@example.com' (first seen in row 1)"]
+		);
+	}
+
+	#[rstest]
+	fn test_coerce_record_matches_validation() {
+		let validator = ImportValidator::new()
+			.with_rule(ColumnRule::new("active", ColumnType::Boolean))
+			.with_rule(ColumnRule::new("joined", ColumnType::Date));
+		let records = vec![record(&[("active", "yes"), ("joined", " 2025-01-31 ")])];
+
+		assert!(!validator.validate(&records).has_errors());
+		assert_eq!(
+			validator.coerce_record(&records[0]),
+			HashMap::from([
+				("active".to_string(), serde_json::json!(true)),
+				("joined".to_string(), serde_json::json!("2025-01-31")),
+			])
+		);
+		assert_eq!(
+			ImportValidator::new().coerce_record(&record(&[("name", "Al"), ("bio", "")])),
+			HashMap::from([("name".to_string(), serde_json::json!("Al"))])
+		);
+	}
+
+	#[rstest]
+	fn test_validate_warns_on_unknown_columns() {
+		let validator = ImportValidator::new().with_rule(ColumnRule::new("name", ColumnType::Text));
+		let records = vec![record(&[("name", "Alice"), ("nickname", "Al")])];
+
+		let report = validator.validate(&records);
+
+		assert!(!report.has_errors());
+		assert_eq!(report.warnings().count(), 1);
+		assert_eq!(report.valid_rows, 1);
+	}
+
+	#[rstest]
+	fn test_from_model_metadata() {
+		let mut metadata = ModelMetadata::new("blog", "Post", "blog_post");
+		metadata.add_field(
+			"id".to_string(),
+			FieldMetadata::new(DbFieldType::BigInteger).with_param("primary_key", "true"),
+		);
+		metadata.add_field(
+			"slug".to_string(),
+			FieldMetadata::new(DbFieldType::VarChar(50)).with_param("unique", "true"),
+		);
+		metadata.add_field(
+			"subtitle".to_string(),
+			FieldMetadata::new(DbFieldType::VarChar(100)).with_param("null", "true"),
+		);
+
+		let validator = ImportValidator::from_model_metadata(&metadata);
+
+		let columns: Vec<_> = validator.rules().iter().map(|r| r.column()).collect();
+		assert_eq!(columns, vec!["slug", "subtitle"]);
+		assert!(validator.rules()[0].is_required());
+		assert!(validator.rules()[0].is_unique());
+		assert!(!validator.rules()[1].is_required());
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_validate_with_database_unresolved_foreign_key(
+		mock_connection: DatabaseConnection,
+	) {
+		let db = AdminDatabase::new(mock_connection);
+		let validator = ImportValidator::new()
+			.with_rule(ColumnRule::new("author_id", ColumnType::Integer).references("users", "id"));
+		let records = vec![record(&[("author_id", "5")]), record(&[("author_id", "x")])];
+
+		let report = validator
+			.validate_with_database(&db, "posts", &records)
+			.await
+			.unwrap();
+
+		// Mock connection returns no rows, so the FK cannot be resolved
+		let messages: Vec<_> = report
+			.errors()
+			.map(|i| (i.row, i.message.clone()))
+			.collect();
+		assert_eq!(
+			messages,
+			vec![
+				(Some(2), "'x' is not a valid integer".to_string()),
+				(Some(1), "No users with id '5' exists".to_string()),
+			]
+		);
+		assert_eq!(report.invalid_rows, 2);
+	}
+}
@@ -2,6 +2,7 @@
 //!
 //! This module defines how models are displayed and managed in the admin interface.
 
+use super::generic::{GenericForeignKeyConfig, GenericInline};
 use async_trait::async_trait;
 
 /// Trait for configuring model administration
@@ -63,6 +64,16 @@ pub trait ModelAdmin: Send + Sync {
 		None
 	}
 
+	/// Generic foreign keys rendered as content type selector plus object autocomplete
+	fn generic_foreign_keys(&self) -> &[GenericForeignKeyConfig] {
+		&[]
+	}
+
+	/// Rows related through generic relations, editable on the object's page
+	fn generic_inlines(&self) -> &[GenericInline] {
+		&[]
+	}
+
 	/// Check if user has permission to view this model
 	///
 	/// Default implementation allows all access.
@@ -126,6 +137,8 @@ pub struct ModelAdminConfig {
 	readonly_fields: Vec<String>,
 	ordering: Vec<String>,
 	list_per_page: Option<usize>,
+	generic_foreign_keys: Vec<GenericForeignKeyConfig>,
+	generic_inlines: Vec<GenericInline>,
 }
 
 impl ModelAdminConfig {
@@ -151,6 +164,8 @@ impl ModelAdminConfig {
 			readonly_fields: vec![],
 			ordering: vec!["-id".into()],
 			list_per_page: None,
+			generic_foreign_keys: vec![],
+			generic_inlines: vec![],
 		}
 	}
 
@@ -187,6 +202,18 @@ impl ModelAdminConfig {
 		self.search_fields = fields.into_iter().map(Into::into).collect();
 		self
 	}
+
+	/// Add a generic foreign key
+	pub fn with_generic_foreign_key(mut self, config: GenericForeignKeyConfig) -> Self {
+		self.generic_foreign_keys.push(config);
+		self
+	}
+
+	/// Add a generic inline
+	pub fn with_generic_inline(mut self, inline: GenericInline) -> Self {
+		self.generic_inlines.push(inline);
+		self
+	}
 }
 
 #[async_trait]
@@ -234,6 +261,14 @@ impl ModelAdmin for ModelAdminConfig {
 	fn list_per_page(&self) -> Option<usize> {
 		self.list_per_page
 	}
+
+	fn generic_foreign_keys(&self) -> &[GenericForeignKeyConfig] {
+		&self.generic_foreign_keys
+	}
+
+	fn generic_inlines(&self) -> &[GenericInline] {
+		&self.generic_inlines
+	}
 }
 
 /// Builder for ModelAdminConfig
@@ -249,6 +284,8 @@ pub struct ModelAdminConfigBuilder {
 	readonly_fields: Option<Vec<String>>,
 	ordering: Option<Vec<String>>,
 	list_per_page: Option<usize>,
+	generic_foreign_keys: Vec<GenericForeignKeyConfig>,
+	generic_inlines: Vec<GenericInline>,
 }
 
 impl ModelAdminConfigBuilder {
@@ -316,6 +353,18 @@ impl ModelAdminConfigBuilder {
 		self
 	}
 
+	/// Add a generic foreign key
+	pub fn generic_foreign_key(mut self, config: GenericForeignKeyConfig) -> Self {
+		self.generic_foreign_keys.push(config);
+		self
+	}
+
+	/// Add a generic inline
+	pub fn generic_inline(mut self, inline: GenericInline) -> Self {
+		self.generic_inlines.push(inline);
+		self
+	}
+
 	/// Build the configuration
 	///
 	/// # Panics
@@ -333,6 +382,8 @@ impl ModelAdminConfigBuilder {
 			readonly_fields: self.readonly_fields.unwrap_or_default(),
 			ordering: self.ordering.unwrap_or_else(|| vec!["-id".into()]),
 			list_per_page: self.list_per_page,
+			generic_foreign_keys: self.generic_foreign_keys,
+			generic_inlines: self.generic_inlines,
 		}
 	}
 }
@@ -379,6 +430,25 @@ mod tests {
 		assert_eq!(admin.search_fields(), vec!["title", "content"]);
 	}
 
+	#[rstest]
+	fn test_generic_relations() {
+		use reinhardt_db::contenttypes::ContentType;
+
+		let post = ContentType::new("blog", "Post").with_id(1);
+		let admin = ModelAdminConfig::builder()
+			.model_name("Comment")
+			.generic_foreign_key(
+				GenericForeignKeyConfig::new("target").with_target(post.clone(), "Post"),
+			)
+			.build();
+		let post_admin =
+			ModelAdminConfig::new("Post").with_generic_inline(GenericInline::new("Comment", post));
+
+		assert_eq!(admin.generic_foreign_keys()[0].name(), "target");
+		assert!(admin.generic_inlines().is_empty());
+		assert_eq!(post_admin.generic_inlines()[0].model_name(), "Comment");
+	}
+
 	#[rstest]
 	#[should_panic(expected = "model_name is required")]
 	fn test_builder_without_model_name() {
@@ -0,0 +1,373 @@
+//! Per-user list view preferences
+//!
+//! Users can save named filter/search/sort combinations and choose which
+//! columns are displayed in list views. Preferences are persisted through a
+//! [`ListPreferenceStore`] configured on the [`AdminSite`](crate::core::AdminSite).
+//!
+//! # Examples
+//!
+//! ```
+//! use reinhardt_admin::core::preferences::{InMemoryListPreferenceStore, ListPreferenceStore};
+//! use reinhardt_admin::types::{ListPreferences, SavedFilter};
+//! use std::collections::HashMap;
+//!
+//! # tokio_test::block_on(async {
+//! let store = InMemoryListPreferenceStore::new();
+//!
+//! let mut prefs = ListPreferences::default();
+//! prefs.upsert_saved_filter(SavedFilter {
+//!     name: "Active staff".to_string(),
+//!     filters: HashMap::from([("is_staff".to_string(), "true".to_string())]),
+//!     search: None,
+//!     sort_by: Some("-date_joined".to_string()),
+//! });
+//! store.save("42", "User", &prefs).await.unwrap();
+//!
+//! let loaded = store.load("42", "User").await.unwrap();
+//! assert_eq!(loaded, prefs);
+//! # });
+//! ```
+
+use crate::types::{AdminError, AdminResult, ListPreferences};
+use async_trait::async_trait;
+use dashmap::DashMap;
+use reinhardt_db::orm::DatabaseConnection;
+use reinhardt_di::{DiResult, Injectable, InjectionContext};
+use reinhardt_http::{AuthState, Request};
+use sea_query::{
+	Alias, ColumnDef, Expr, ExprTrait, OnConflict, PostgresQueryBuilder, Query as SeaQuery, Table,
+};
+use std::sync::Arc;
+
+/// Storage backend for per-user list preferences
+#[async_trait]
+pub trait ListPreferenceStore: Send + Sync {
+	/// Load the preferences of a user for a model
+	///
+	/// Returns default preferences when nothing has been saved yet.
+	async fn load(&self, user_id: &str, model_name: &str) -> AdminResult<ListPreferences>;
+
+	/// Save the preferences of a user for a model
+	async fn save(
+		&self,
+		user_id: &str,
+		model_name: &str,
+		preferences: &ListPreferences,
+	) -> AdminResult<()>;
+}
+
+/// In-memory preference store
+///
+/// Preferences are lost on restart. This is the default store of an admin site.
+#[derive(Debug, Default)]
+pub struct InMemoryListPreferenceStore {
+	preferences: DashMap<(String, String), ListPreferences>,
+}
+
+impl InMemoryListPreferenceStore {
+	/// Create an empty store
+	pub fn new() -> Self {
+		Self::default()
+	}
+}
+
+#[async_trait]
+impl ListPreferenceStore for InMemoryListPreferenceStore {
+	async fn load(&self, user_id: &str, model_name: &str) -> AdminResult<ListPreferences> {
+		Ok(self
+			.preferences
+			.get(&(user_id.to_string(), model_name.to_string()))
+			.map(|prefs| prefs.clone())
+			.unwrap_or_default())
+	}
+
+	async fn save(
+		&self,
+		user_id: &str,
+		model_name: &str,
+		preferences: &ListPreferences,
+	) -> AdminResult<()> {
+		self.preferences.insert(
+			(user_id.to_string(), model_name.to_string()),
+			preferences.clone(),
+		);
+		Ok(())
+	}
+}
+
+/// Database-backed preference store
+///
+/// Preferences are stored as JSON, one row per user and model, in a table
+/// created with [`create_table_sql`](Self::create_table_sql).
+pub struct DatabaseListPreferenceStore {
+	connection: Arc<DatabaseConnection>,
+	table_name: String,
+}
+
+impl DatabaseListPreferenceStore {
+	/// Default table name
+	pub const DEFAULT_TABLE: &'static str = "admin_list_preferences";
+
+	/// Create a store using the default table
+	pub fn new(connection: Arc<DatabaseConnection>) -> Self {
+		Self {
+			connection,
+			table_name: Self::DEFAULT_TABLE.to_string(),
+		}
+	}
+
+	/// Use a custom table name
+	pub fn with_table_name(mut self, table_name: impl Into<String>) -> Self {
+		self.table_name = table_name.into();
+		self
+	}
+
+	/// Get the table name
+	pub fn table_name(&self) -> &str {
+		&self.table_name
+	}
+
+	/// SQL creating the preferences table
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_admin::core::preferences::DatabaseListPreferenceStore;
+	///
+	/// let sql = DatabaseListPreferenceStore::create_table_sql("admin_list_preferences");
+	/// assert!(sql.starts_with(r#"CREATE TABLE IF NOT EXISTS "admin_list_preferences""#));
+	/// ```
+	pub fn create_table_sql(table_name: &str) -> String {
+		Table::create()
+			.table(Alias::new(table_name))
+			.if_not_exists()
+			.col(ColumnDef::new(Alias::new("user_id")).string().not_null())
+			.col(ColumnDef::new(Alias::new("model_name")).string().not_null())
+			.col(ColumnDef::new(Alias::new("data")).text().not_null())
+			.primary_key(
+				sea_query::Index::create()
+					.col(Alias::new("user_id"))
+					.col(Alias::new("model_name")),
+			)
+			.to_string(PostgresQueryBuilder)
+	}
+
+	fn select_sql(&self, user_id: &str, model_name: &str) -> String {
+		SeaQuery::select()
+			.column(Alias::new("data"))
+			.from(Alias::new(&self.table_name))
+			.and_where(Expr::col(Alias::new("user_id")).eq(user_id))
+			.and_where(Expr::col(Alias::new("model_name")).eq(model_name))
+			.to_string(PostgresQueryBuilder)
+	}
+
+	fn upsert_sql(&self, user_id: &str, model_name: &str, data: String) -> AdminResult<String> {
+		let mut query = SeaQuery::insert()
+			.into_table(Alias::new(&self.table_name))
+			.columns([
+				Alias::new("user_id"),
+				Alias::new("model_name"),
+				Alias::new("data"),
+			])
+			.to_owned();
+		query
+			.values([user_id.into(), model_name.into(), data.into()])
+			.map_err(|e| AdminError::DatabaseError(e.to_string()))?;
+		query.on_conflict(
+			OnConflict::columns([Alias::new("user_id"), Alias::new("model_name")])
+				.update_column(Alias::new("data"))
+				.to_owned(),
+		);
+		Ok(query.to_string(PostgresQueryBuilder))
+	}
+}
+
+#[async_trait]
+impl ListPreferenceStore for DatabaseListPreferenceStore {
+	async fn load(&self, user_id: &str, model_name: &str) -> AdminResult<ListPreferences> {
+		let sql = self.select_sql(user_id, model_name);
+		let row = self
+			.connection
+			.query_optional(&sql, vec![])
+			.await
+			.map_err(|e| AdminError::DatabaseError(e.to_string()))?;
+
+		match row.and_then(|r| r.get::<String>("data")) {
+			Some(data) => serde_json::from_str(&data)
+				.map_err(|e| AdminError::DatabaseError(format!("Invalid preferences: {}", e))),
+			None => Ok(ListPreferences::default()),
+		}
+	}
+
+	async fn save(
+		&self,
+		user_id: &str,
+		model_name: &str,
+		preferences: &ListPreferences,
+	) -> AdminResult<()> {
+		let data = serde_json::to_string(preferences)
+			.map_err(|e| AdminError::ValidationError(e.to_string()))?;
+		let sql = self.upsert_sql(user_id, model_name, data)?;
+		self.connection
+			.execute(&sql, vec![])
+			.await
+			.map_err(|e| AdminError::DatabaseError(e.to_string()))?;
+		Ok(())
+	}
+}
+
+/// Identity of the user issuing an admin request
+///
+/// Resolved from the [`AuthState`] stored in the request extensions by the
+/// authentication middleware. Resolves to an anonymous user when no request
+/// or authentication state is available.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequestUser {
+	/// Authenticated user ID, `None` for anonymous users
+	pub user_id: Option<String>,
+}
+
+impl RequestUser {
+	/// Create an authenticated request user
+	pub fn authenticated(user_id: impl Into<String>) -> Self {
+		Self {
+			user_id: Some(user_id.into()),
+		}
+	}
+
+	/// Create an anonymous request user
+	pub fn anonymous() -> Self {
+		Self::default()
+	}
+
+	/// Get the user ID, failing for anonymous users
+	pub fn require_id(&self) -> AdminResult<&str> {
+		self.user_id
+			.as_deref()
+			.ok_or_else(|| AdminError::PermissionDenied("Authentication required".to_string()))
+	}
+}
+
+#[async_trait]
+impl Injectable for RequestUser {
+	async fn inject(ctx: &InjectionContext) -> DiResult<Self> {
+		let user_id = ctx
+			.get_request::<Request>()
+			.and_then(|request| request.extensions.get::<AuthState>())
+			.filter(|state| state.is_authenticated)
+			.map(|state| state.user_id);
+		Ok(Self { user_id })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::types::SavedFilter;
+	use reinhardt_test::fixtures::mock_connection;
+	use rstest::rstest;
+	use std::collections::HashMap;
+
+	fn saved_filter(name: &str) -> SavedFilter {
+		SavedFilter {
+			name: name.to_string(),
+			filters: HashMap::from([("status".to_string(), "open".to_string())]),
+			search: Some("bug".to_string()),
+			sort_by: None,
+		}
+	}
+
+	#[rstest]
+	fn test_upsert_saved_filter_replaces_same_name() {
+		// Arrange
+		let mut prefs = ListPreferences::default();
+		prefs.upsert_saved_filter(saved_filter("Open"));
+
+		// Act
+		let mut replacement = saved_filter("Open");
+		replacement.search = None;
+		prefs.upsert_saved_filter(replacement.clone());
+
+		// Assert
+		assert_eq!(prefs.saved_filters, vec![replacement]);
+	}
+
+	#[rstest]
+	fn test_remove_saved_filter() {
+		// Arrange
+		let mut prefs = ListPreferences::default();
+		prefs.upsert_saved_filter(saved_filter("Open"));
+
+		// Act & Assert
+		assert!(prefs.remove_saved_filter("Open"));
+		assert!(!prefs.remove_saved_filter("Open"));
+		assert!(prefs.saved_filters.is_empty());
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_in_memory_store_is_per_user_and_model() {
+		// Arrange
+		let store = InMemoryListPreferenceStore::new();
+		let prefs = ListPreferences {
+			columns: Some(vec!["id".to_string(), "email".to_string()]),
+			saved_filters: vec![saved_filter("Open")],
+		};
+
+		// Act
+		store.save("1", "User", &prefs).await.unwrap();
+
+		// Assert
+		assert_eq!(store.load("1", "User").await.unwrap(), prefs);
+		assert_eq!(
+			store.load("2", "User").await.unwrap(),
+			ListPreferences::default()
+		);
+		assert_eq!(
+			store.load("1", "Group").await.unwrap(),
+			ListPreferences::default()
+		);
+	}
+
+	#[rstest]
+	fn test_database_store_upsert_sql() {
+		// Arrange
+		let store = DatabaseListPreferenceStore::new(Arc::new(mock_connection()));
+
+		// Act
+		let sql = store.upsert_sql("1", "User", "{}".to_string()).unwrap();
+
+		// Assert
+		assert_eq!(
+			sql,
+			r#"INSERT INTO "admin_list_preferences" ("user_id", "model_name", "data") VALUES ('1', 'User', '{}') ON CONFLICT ("user_id", "model_name") DO UPDATE SET "data" = "excluded"."data""#
+		);
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_database_store_load_defaults_when_missing() {
+		// Arrange
+		let store = DatabaseListPreferenceStore::new(Arc::new(mock_connection()));
+
+		// Act
+		let prefs = store.load("1", "User").await.unwrap();
+
+		// Assert
+		assert_eq!(prefs, ListPreferences::default());
+	}
+
+	#[rstest]
+	fn test_request_user_require_id() {
+		// Arrange
+		let anonymous = RequestUser::anonymous();
+		let user = RequestUser::authenticated("42");
+
+		// Act & Assert
+		assert!(matches!(
+			anonymous.require_id(),
+			Err(AdminError::PermissionDenied(_))
+		));
+		assert_eq!(user.require_id().unwrap(), "42");
+	}
+}
@@ -6,7 +6,10 @@
 //! All endpoints are registered automatically using the `.endpoint()` method
 //! with HTTP method macros from handlers module.
 
-use crate::core::AdminSite;
+use crate::core::{AdminSite, ThemeConfig};
+use async_trait::async_trait;
+use hyper::Method;
+use reinhardt_http::{Handler, Request, Response};
 use reinhardt_urls::routers::ServerRouter;
 use std::sync::Arc;
 
@@ -30,15 +33,32 @@ pub fn admin_routes() -> ServerRouter {
 	//
 	// Available Server Functions (from reinhardt-admin-server crate):
 	// - get_dashboard() -> DashboardResponse
+	// - get_dashboard_widgets() -> DashboardWidgetsResponse
+	// - get_theme() -> ThemeResponse
 	// - get_list() -> ListResponse
+	// - get_list_preferences() -> ListPreferences
+	// - save_list_filter() -> ListPreferences
+	// - delete_list_filter() -> ListPreferences
+	// - set_list_columns() -> ListPreferences
 	// - get_detail() -> DetailResponse
 	// - create_record() -> MutationResponse
 	// - update_record() -> MutationResponse
 	// - delete_record() -> MutationResponse
+	// - get_object_history() -> ObjectHistoryResponse
+	// - revert_object() -> MutationResponse
 	// - bulk_delete_records() -> BulkDeleteResponse
 	// - export_data() -> ExportResponse
+	// - start_export() -> ExportJobResponse
+	// - get_export_status() -> ExportJobResponse
 	// - import_data() -> ImportResponse
+	// - validate_import() -> ImportValidationReport
 	// - get_fields() -> FieldsResponse
+	// - get_generic_inlines() -> GenericInlinesResponse
+	// - save_generic_inline() -> MutationResponse
+	// - delete_generic_inline() -> MutationResponse
+	//
+	// Background export downloads are served by the handler returned from
+	// `BackgroundExporter::download_handler()`, mounted at the signer's base URL.
 	ServerRouter::new().with_namespace("admin")
 }
 
@@ -99,10 +119,28 @@ impl AdminRouter {
 		self
 	}
 
+	/// Set the theme of the admin panel
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use reinhardt_admin::core::{AdminSite, AdminRouter, ThemeConfig};
+	/// use std::sync::Arc;
+	///
+	/// let site = Arc::new(AdminSite::new("Admin"));
+	/// let router = AdminRouter::from_arc(site)
+	///     .with_theme(ThemeConfig::new().with_template_dir("templates/admin"))
+	///     .build();
+	/// ```
+	pub fn with_theme(self, theme: ThemeConfig) -> Self {
+		self.site.set_theme(theme);
+		self
+	}
+
 	/// Build the ServerRouter with all admin endpoints
 	///
 	/// Generated endpoints:
-	/// - `GET /` - Dashboard (list of registered models)
+	/// - `GET /` - HTML shell of the admin panel, rendered from the theme's templates
 	/// - `GET /favicon.ico` - Favicon
 	/// - `GET /{model}/` - List model instances
 	/// - `GET /{model}/{id}/` - Get model instance detail
@@ -113,12 +151,37 @@ impl AdminRouter {
 	/// - `GET /{model}/export/` - Export model data
 	/// - `POST /{model}/import/` - Import model data
 	pub fn routes(&self) -> ServerRouter {
-		admin_routes()
+		admin_routes().handler_with_method(
+			"/",
+			Method::GET,
+			AdminIndexHandler {
+				site: Arc::clone(&self.site),
+			},
+		)
 	}
 
 	/// Build the ServerRouter (alias for routes())
 	pub fn build(self) -> ServerRouter {
-		admin_routes()
+		self.routes()
+	}
+}
+
+/// Serves the HTML shell of the admin panel
+///
+/// Renders [`AdminSite::render_index`] on every request, so theme changes
+/// apply without rebuilding the router.
+#[derive(Clone)]
+pub struct AdminIndexHandler {
+	site: Arc<AdminSite>,
+}
+
+#[async_trait]
+impl Handler for AdminIndexHandler {
+	async fn handle(&self, _request: Request) -> reinhardt_http::Result<Response> {
+		let html = self.site.render_index()?;
+		Ok(Response::ok()
+			.with_header("Content-Type", "text/html; charset=utf-8")
+			.with_body(html))
 	}
 }
 
@@ -133,6 +196,25 @@ mod tests {
 		assert_eq!(router.namespace(), Some("admin"));
 	}
 
+	#[tokio::test]
+	async fn test_index_serves_themed_shell() {
+		let site = Arc::new(AdminSite::new("Test Admin"));
+		let router = AdminRouter::from_arc(Arc::clone(&site))
+			.with_theme(ThemeConfig::new().with_brand_name("Acme"))
+			.build();
+		let request = Request::builder()
+			.method(Method::GET)
+			.uri("/")
+			.build()
+			.unwrap();
+
+		let response = router.handle(request).await.unwrap();
+
+		let html = String::from_utf8(response.body.to_vec()).unwrap();
+		assert_eq!(response.status, hyper::StatusCode::OK);
+		assert!(html.contains(r#"<span class="admin-brand-name">Acme</span>"#));
+	}
+
 	#[test]
 	fn test_admin_router_backward_compat() {
 		let site = Arc::new(AdminSite::new("Test Admin"));
@@ -3,6 +3,10 @@
 //! The `AdminSite` is the central registry for all admin models and provides
 //! routing, authentication, and rendering functionality.
 
+use crate::core::dashboard::WidgetProvider;
+use crate::core::history::AuditLog;
+use crate::core::preferences::{InMemoryListPreferenceStore, ListPreferenceStore};
+use crate::core::theme::ThemeConfig;
 use crate::core::{AdminRouter, ModelAdmin};
 use crate::types::{AdminError, AdminResult};
 use async_trait::async_trait;
@@ -39,6 +43,18 @@ pub struct AdminSite {
 
 	/// Favicon data (PNG, ICO, etc.)
 	favicon_data: Arc<RwLock<Option<Vec<u8>>>>,
+
+	/// Query-backed dashboard widgets
+	widget_provider: Arc<RwLock<Arc<WidgetProvider>>>,
+
+	/// Change history of objects edited through the admin
+	audit_log: Arc<AuditLog>,
+
+	/// Per-user list view preferences
+	preference_store: Arc<RwLock<Arc<dyn ListPreferenceStore>>>,
+
+	/// Branding, navigation and template overrides
+	theme: Arc<RwLock<ThemeConfig>>,
 }
 
 /// Configuration for the admin site
@@ -93,6 +109,10 @@ impl AdminSite {
 			registry: Arc::new(DashMap::new()),
 			config: Arc::new(RwLock::new(AdminSiteConfig::default())),
 			favicon_data: Arc::new(RwLock::new(None)),
+			widget_provider: Arc::new(RwLock::new(Arc::new(WidgetProvider::new()))),
+			audit_log: Arc::new(AuditLog::new()),
+			preference_store: Arc::new(RwLock::new(Arc::new(InMemoryListPreferenceStore::new()))),
+			theme: Arc::new(RwLock::new(ThemeConfig::default())),
 		}
 	}
 
@@ -152,6 +172,97 @@ impl AdminSite {
 		self.favicon_data.read().clone()
 	}
 
+	/// Set the provider computing dashboard widget data
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_admin::core::AdminSite;
+	/// use reinhardt_admin::core::dashboard::{StatWidget, WidgetProvider, WidgetQuery};
+	///
+	/// let admin = AdminSite::new("Admin");
+	/// admin.set_widget_provider(
+	///     WidgetProvider::new().with_stat(StatWidget::new("users", "Users", WidgetQuery::count("users"))),
+	/// );
+	/// assert_eq!(admin.widget_provider().widget_keys(), vec!["users"]);
+	/// ```
+	pub fn set_widget_provider(&self, provider: WidgetProvider) {
+		*self.widget_provider.write() = Arc::new(provider);
+	}
+
+	/// Get the provider computing dashboard widget data
+	pub fn widget_provider(&self) -> Arc<WidgetProvider> {
+		Arc::clone(&self.widget_provider.read())
+	}
+
+	/// Get the change history log
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_admin::core::AdminSite;
+	///
+	/// let admin = AdminSite::new("Admin");
+	/// assert_eq!(admin.audit_log().table_name(), "admin_audit_log");
+	/// ```
+	pub fn audit_log(&self) -> Arc<AuditLog> {
+		Arc::clone(&self.audit_log)
+	}
+
+	/// Set the store persisting per-user list view preferences
+	///
+	/// Defaults to an [`InMemoryListPreferenceStore`].
+	pub fn set_preference_store(&self, store: impl ListPreferenceStore + 'static) {
+		*self.preference_store.write() = Arc::new(store);
+	}
+
+	/// Get the store persisting per-user list view preferences
+	pub fn preference_store(&self) -> Arc<dyn ListPreferenceStore> {
+		Arc::clone(&self.preference_store.read())
+	}
+
+	/// Set the theme of the admin panel
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_admin::core::AdminSite;
+	/// use reinhardt_admin::core::theme::ThemeConfig;
+	///
+	/// let admin = AdminSite::new("Admin");
+	/// admin.set_theme(ThemeConfig::new().with_brand_name("Acme"));
+	/// assert_eq!(admin.theme().brand_name.as_deref(), Some("Acme"));
+	/// ```
+	pub fn set_theme(&self, theme: ThemeConfig) {
+		*self.theme.write() = theme;
+	}
+
+	/// Get the theme of the admin panel (cloned)
+	pub fn theme(&self) -> ThemeConfig {
+		self.theme.read().clone()
+	}
+
+	/// Render the HTML shell of the admin panel
+	///
+	/// Uses the `base.html` template, which projects can override through the
+	/// theme's template directories.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_admin::core::AdminSite;
+	///
+	/// let admin = AdminSite::new("Admin");
+	/// let html = admin.render_index().unwrap();
+	/// assert!(html.contains("<title>Admin Panel</title>"));
+	/// ```
+	pub fn render_index(&self) -> AdminResult<String> {
+		let theme = self.theme();
+		let config = self.config.read();
+		let context = theme.context(&config.site_title, &config.site_header, &self.url_prefix);
+		theme.loader().render("base.html", &context)
+	}
+
 	/// Configure the admin site
 	///
 	/// # Examples
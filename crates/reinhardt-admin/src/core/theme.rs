@@ -0,0 +1,537 @@
+//! Admin theming and template overrides
+//!
+//! A [`ThemeConfig`] on the [`AdminSite`](crate::core::AdminSite) controls the
+//! branding, navigation and extra assets of the admin panel. The HTML shell is
+//! rendered from templates resolved by a [`TemplateLoader`]: projects override
+//! any template or partial by placing a file with the same name in one of the
+//! theme's template directories, without forking reinhardt-admin.
+//!
+//! Templates are rendered with Tera, with HTML autoescaping. Values that are
+//! already markup (links, assets) must be output with the `safe` filter, as
+//! the built-in templates do.
+//!
+//! # Examples
+//!
+//! ```
+//! use reinhardt_admin::core::theme::{NavLink, ThemeConfig};
+//!
+//! let theme = ThemeConfig::new()
+//!     .with_brand_name("Acme Backoffice")
+//!     .with_primary_color("#ff6600")
+//!     .with_extra_css("/static/acme/admin.css")
+//!     .with_nav_link(NavLink::new("Docs", "https://docs.acme.test"))
+//!     .with_template_dir("templates/admin");
+//!
+//! assert_eq!(theme.brand_name.as_deref(), Some("Acme Backoffice"));
+//! assert_eq!(theme.template_dirs.len(), 1);
+//! ```
+
+use crate::types::{AdminError, AdminResult};
+use reinhardt_utils::utils_core::html::escape;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tera::Tera;
+
+/// Built-in templates, used when no override is found
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+	(
+		"base.html",
+		r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>{{ site_title }}</title>
+<link rel="icon" href="{{ url_prefix }}/favicon.ico">
+{% include "partials/extra_head.html" %}
+</head>
+<body>
+{% include "partials/branding.html" %}
+{% include "partials/nav.html" %}
+<div id="app"></div>
+{% include "partials/extra_body.html" %}
+</body>
+</html>
+"#,
+	),
+	(
+		"partials/branding.html",
+		r#"<header class="admin-branding">{{ logo | safe }}<span class="admin-brand-name">{{ brand_name }}</span></header>
+"#,
+	),
+	(
+		"partials/nav.html",
+		r#"<nav class="admin-extra-nav">{{ nav_links | safe }}</nav>
+"#,
+	),
+	(
+		"partials/extra_head.html",
+		"{{ extra_css | safe }}{{ theme_style | safe }}",
+	),
+	("partials/extra_body.html", "{{ extra_js | safe }}"),
+];
+
+/// Extra navigation link shown in the admin header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NavLink {
+	/// Link label
+	pub label: String,
+	/// Link target
+	pub url: String,
+}
+
+impl NavLink {
+	/// Create a navigation link
+	pub fn new(label: impl Into<String>, url: impl Into<String>) -> Self {
+		Self {
+			label: label.into(),
+			url: url.into(),
+		}
+	}
+}
+
+/// Branding, navigation and asset configuration of the admin panel
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ThemeConfig {
+	/// Brand name shown in the header (defaults to the site header)
+	pub brand_name: Option<String>,
+	/// URL of the logo shown next to the brand name
+	pub logo_url: Option<String>,
+	/// Primary color exposed as the `--admin-primary` CSS variable
+	pub primary_color: Option<String>,
+	/// Extra stylesheet URLs, loaded after the built-in styles
+	pub extra_css: Vec<String>,
+	/// Extra script URLs, loaded after the admin application
+	pub extra_js: Vec<String>,
+	/// Extra navigation links
+	pub nav_links: Vec<NavLink>,
+	/// Directories searched for template overrides, in priority order
+	pub template_dirs: Vec<PathBuf>,
+}
+
+impl ThemeConfig {
+	/// Create the default theme
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Set the brand name
+	pub fn with_brand_name(mut self, name: impl Into<String>) -> Self {
+		self.brand_name = Some(name.into());
+		self
+	}
+
+	/// Set the logo URL
+	pub fn with_logo_url(mut self, url: impl Into<String>) -> Self {
+		self.logo_url = Some(url.into());
+		self
+	}
+
+	/// Set the primary color
+	pub fn with_primary_color(mut self, color: impl Into<String>) -> Self {
+		self.primary_color = Some(color.into());
+		self
+	}
+
+	/// Add an extra stylesheet
+	pub fn with_extra_css(mut self, url: impl Into<String>) -> Self {
+		self.extra_css.push(url.into());
+		self
+	}
+
+	/// Add an extra script
+	pub fn with_extra_js(mut self, url: impl Into<String>) -> Self {
+		self.extra_js.push(url.into());
+		self
+	}
+
+	/// Add an extra navigation link
+	pub fn with_nav_link(mut self, link: NavLink) -> Self {
+		self.nav_links.push(link);
+		self
+	}
+
+	/// Add a template override directory
+	///
+	/// Directories added first take precedence.
+	pub fn with_template_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+		self.template_dirs.push(dir.into());
+		self
+	}
+
+	/// Create a template loader searching this theme's directories
+	pub fn loader(&self) -> TemplateLoader {
+		TemplateLoader::new(self.template_dirs.clone())
+	}
+
+	/// Build the template context for this theme
+	///
+	/// Values that contain markup (links, assets) are built here with their
+	/// parts escaped; templates output them with the `safe` filter.
+	pub fn context(
+		&self,
+		site_title: &str,
+		site_header: &str,
+		url_prefix: &str,
+	) -> TemplateContext {
+		let mut context = TemplateContext::new();
+		context.insert("site_title", site_title);
+		context.insert("url_prefix", url_prefix);
+		context.insert(
+			"brand_name",
+			self.brand_name.as_deref().unwrap_or(site_header),
+		);
+		context.insert_html(
+			"logo",
+			self.logo_url
+				.as_deref()
+				.map(|url| format!(r#"<img class="admin-logo" src="{}" alt="">"#, escape(url)))
+				.unwrap_or_default(),
+		);
+		context.insert_html(
+			"nav_links",
+			self.nav_links
+				.iter()
+				.map(|link| {
+					format!(
+						r#"<a class="admin-nav-link" href="{}">{}</a>"#,
+						escape(&link.url),
+						escape(&link.label)
+					)
+				})
+				.collect::<String>(),
+		);
+		context.insert_html(
+			"extra_css",
+			self.extra_css
+				.iter()
+				.map(|url| format!(r#"<link rel="stylesheet" href="{}">"#, escape(url)))
+				.collect::<String>(),
+		);
+		context.insert_html(
+			"extra_js",
+			self.extra_js
+				.iter()
+				.map(|url| format!(r#"<script src="{}" defer></script>"#, escape(url)))
+				.collect::<String>(),
+		);
+		context.insert_html(
+			"theme_style",
+			self.primary_color
+				.as_deref()
+				.map(|color| {
+					format!(
+						"<style>:root {{ --admin-primary: {}; }}</style>",
+						escape(color)
+					)
+				})
+				.unwrap_or_default(),
+		);
+		context
+	}
+}
+
+/// Values available to admin templates
+#[derive(Debug, Clone, Default)]
+pub struct TemplateContext {
+	values: HashMap<String, String>,
+}
+
+impl TemplateContext {
+	/// Create an empty context
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Insert a plain-text value, escaped when rendered
+	pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) {
+		self.values.insert(key.into(), value.into());
+	}
+
+	/// Insert a trusted HTML value, to be output with the `safe` filter
+	pub fn insert_html(&mut self, key: impl Into<String>, html: impl Into<String>) {
+		self.values.insert(key.into(), html.into());
+	}
+
+	/// Get a value
+	pub fn get(&self, key: &str) -> Option<&str> {
+		self.values.get(key).map(String::as_str)
+	}
+
+	fn to_tera(&self) -> tera::Context {
+		let mut context = tera::Context::new();
+		for (key, value) in &self.values {
+			context.insert(key.as_str(), value);
+		}
+		context
+	}
+}
+
+/// Resolves admin templates from override directories, then built-ins
+#[derive(Debug, Clone, Default)]
+pub struct TemplateLoader {
+	search_path: Vec<PathBuf>,
+}
+
+impl TemplateLoader {
+	/// Create a loader searching the given directories, in order
+	pub fn new(search_path: Vec<PathBuf>) -> Self {
+		Self { search_path }
+	}
+
+	/// Get the directories searched for overrides
+	pub fn search_path(&self) -> &[PathBuf] {
+		&self.search_path
+	}
+
+	/// Load the source of a template
+	///
+	/// The first file named `name` found under a directory of the search path
+	/// wins; otherwise the built-in template is used.
+	///
+	/// # Errors
+	///
+	/// Returns [`AdminError::TemplateError`] if the name resolves outside the
+	/// template directory it is looked up in, an override cannot be read, or
+	/// no template exists.
+	pub fn load(&self, name: &str) -> AdminResult<String> {
+		for dir in &self.search_path {
+			// Missing directories and files simply fall through to the next
+			// candidate
+			let Ok(root) = dir.canonicalize() else {
+				continue;
+			};
+			let Ok(path) = root.join(name).canonicalize() else {
+				continue;
+			};
+			if !path.starts_with(&root) {
+				return Err(AdminError::TemplateError(format!(
+					"Invalid template name '{}'",
+					name
+				)));
+			}
+			if path.is_file() {
+				return std::fs::read_to_string(&path).map_err(|e| {
+					AdminError::TemplateError(format!("Failed to read {}: {}", path.display(), e))
+				});
+			}
+		}
+
+		BUILTIN_TEMPLATES
+			.iter()
+			.find(|(builtin, _)| *builtin == name)
+			.map(|(_, source)| source.to_string())
+			.ok_or_else(|| AdminError::TemplateError(format!("Template '{}' not found", name)))
+	}
+
+	/// Render a template with the given context
+	///
+	/// The template and every template it includes, extends or imports are
+	/// resolved through [`load`](Self::load) and rendered by Tera. Using a
+	/// variable missing from the context is an error.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_admin::core::theme::ThemeConfig;
+	///
+	/// let theme = ThemeConfig::new().with_brand_name("Acme & Co");
+	/// let context = theme.context("Admin", "Administration", "/admin");
+	///
+	/// let html = theme.loader().render("partials/branding.html", &context).unwrap();
+	/// assert!(html.contains("Acme &amp; Co"));
+	/// ```
+	pub fn render(&self, name: &str, context: &TemplateContext) -> AdminResult<String> {
+		let mut sources = Vec::new();
+		self.collect(name, &mut Vec::new(), &mut sources)?;
+
+		let mut tera = Tera::default();
+		tera.autoescape_on(vec![".html"]);
+		tera.add_raw_templates(sources).map_err(template_error)?;
+		tera.render(name, &context.to_tera())
+			.map_err(template_error)
+	}
+
+	/// Load `name` and the templates it references, dependencies first
+	fn collect(
+		&self,
+		name: &str,
+		stack: &mut Vec<String>,
+		sources: &mut Vec<(String, String)>,
+	) -> AdminResult<()> {
+		if stack.iter().any(|loading| loading == name) {
+			return Err(AdminError::TemplateError(format!(
+				"Recursive template reference: {} -> {}",
+				stack.join(" -> "),
+				name
+			)));
+		}
+		if sources.iter().any(|(loaded, _)| loaded == name) {
+			return Ok(());
+		}
+
+		let source = self.load(name)?;
+		stack.push(name.to_string());
+		for referenced in referenced_templates(&source) {
+			self.collect(&referenced, stack, sources)?;
+		}
+		stack.pop();
+		sources.push((name.to_string(), source));
+		Ok(())
+	}
+}
+
+/// Names of the templates referenced by `include`, `extends` and `import` tags
+fn referenced_templates(source: &str) -> Vec<String> {
+	source
+		.split("{%")
+		.skip(1)
+		.filter_map(|tag| {
+			let tag = tag
+				.split("%}")
+				.next()?
+				.trim_matches(|c: char| c == '-' || c.is_whitespace());
+			let rest = ["include", "extends", "import"]
+				.iter()
+				.find_map(|keyword| tag.strip_prefix(keyword))?;
+			let rest = rest.trim_start();
+			let quote = rest.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+			let name = &rest[1..];
+			name.find(quote).map(|end| name[..end].to_string())
+		})
+		.collect()
+}
+
+/// Convert a Tera error, including its causes, into an admin error
+fn template_error(error: tera::Error) -> AdminError {
+	let mut message = error.to_string();
+	let mut source = std::error::Error::source(&error);
+	while let Some(cause) = source {
+		message.push_str(": ");
+		message.push_str(&cause.to_string());
+		source = cause.source();
+	}
+	AdminError::TemplateError(message)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+
+	fn temp_template_dir(files: &[(&str, &str)]) -> PathBuf {
+		let dir =
+			std::env::temp_dir().join(format!("reinhardt-admin-theme-{}", uuid::Uuid::new_v4()));
+		for (name, content) in files {
+			let path = dir.join(name);
+			std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+			std::fs::write(path, content).unwrap();
+		}
+		dir
+	}
+
+	#[rstest]
+	fn test_render_builtin_base_includes_partials() {
+		// Arrange
+		let theme = ThemeConfig::new()
+			.with_brand_name("Acme")
+			.with_extra_css("/static/acme.css")
+			.with_extra_js("/static/acme.js");
+		let context = theme.context("Acme Admin", "Administration", "/admin");
+
+		// Act
+		let html = theme.loader().render("base.html", &context).unwrap();
+
+		// Assert
+		assert!(html.contains("<title>Acme Admin</title>"));
+		assert!(html.contains(r#"<span class="admin-brand-name">Acme</span>"#));
+		assert!(html.contains(r#"<link rel="stylesheet" href="/static/acme.css">"#));
+		assert!(html.contains(r#"<script src="/static/acme.js" defer></script>"#));
+		assert!(!html.contains("{%"));
+	}
+
+	#[rstest]
+	fn test_override_directory_takes_precedence() {
+		// Arrange
+		let dir = temp_template_dir(&[(
+			"partials/branding.html",
+			"<header>Custom {{ brand_name }}</header>",
+		)]);
+		let theme = ThemeConfig::new().with_template_dir(&dir);
+		let context = theme.context("Admin", "Administration", "/admin");
+
+		// Act
+		let html = theme.loader().render("base.html", &context).unwrap();
+
+		// Assert
+		assert!(html.contains("<header>Custom Administration</header>"));
+		std::fs::remove_dir_all(dir).unwrap();
+	}
+
+	#[rstest]
+	fn test_context_values_are_escaped() {
+		// Arrange
+		let theme = ThemeConfig::new()
+			.with_brand_name("<b>Acme</b>")
+			.with_nav_link(NavLink::new("Q&A", "/qa?x=\"1\""));
+
+		// Act
+		let context = theme.context("Admin", "Administration", "/admin");
+
+		// Assert
+		let html = theme.loader().render("base.html", &context).unwrap();
+		assert!(html.contains("&lt;b&gt;Acme&lt;&#x2F;b&gt;"));
+		assert_eq!(
+			context.get("nav_links"),
+			Some(r#"<a class="admin-nav-link" href="/qa?x=&quot;1&quot;">Q&amp;A</a>"#)
+		);
+		assert!(html.contains(r#"<a class="admin-nav-link" href="/qa?x=&quot;1&quot;">"#));
+	}
+
+	#[rstest]
+	fn test_load_rejects_parent_directory() {
+		// Arrange
+		let loader = TemplateLoader::default();
+
+		// Act
+		let result = loader.load("../secrets.html");
+
+		// Assert
+		assert!(matches!(result, Err(AdminError::TemplateError(_))));
+	}
+
+	#[rstest]
+	fn test_load_rejects_names_outside_template_dir() {
+		// Arrange
+		let dir = temp_template_dir(&[("base.html", "override")]);
+		let outside = temp_template_dir(&[("secrets.html", "secret")]);
+		let loader = TemplateLoader::new(vec![dir.clone()]);
+
+		// Act
+		let absolute = loader.load(outside.join("secrets.html").to_str().unwrap());
+		let relative = loader.load(&format!(
+			"../{}/secrets.html",
+			outside.file_name().unwrap().to_str().unwrap()
+		));
+
+		// Assert
+		assert!(matches!(absolute, Err(AdminError::TemplateError(_))));
+		assert!(matches!(relative, Err(AdminError::TemplateError(_))));
+		std::fs::remove_dir_all(dir).unwrap();
+		std::fs::remove_dir_all(outside).unwrap();
+	}
+
+	#[rstest]
+	fn test_recursive_include_is_rejected() {
+		// Arrange
+		let dir = temp_template_dir(&[("loop.html", r#"{% include "loop.html" %}"#)]);
+		let loader = TemplateLoader::new(vec![dir.clone()]);
+
+		// Act
+		let result = loader.render("loop.html", &TemplateContext::new());
+
+		// Assert
+		assert!(matches!(result, Err(AdminError::TemplateError(_))));
+		std::fs::remove_dir_all(dir).unwrap();
+	}
+}
@@ -12,8 +12,11 @@
 //! - `create` - Create operations
 //! - `update` - Update operations
 //! - `delete` - Delete operations (including bulk delete)
+//! - `history` - Change history and revert
+//! - `generic` - Generic inline listing, saving and deletion
 //! - `export` - Export operations
 //! - `import` - Import operations
+//! - `preferences` - Saved filters and column choice of list views
 //!
 //! # Server Functions
 //!
@@ -39,8 +42,11 @@ pub mod detail;
 pub mod error;
 pub mod export;
 pub mod fields;
+pub mod generic;
+pub mod history;
 pub mod import;
 pub mod list;
+pub mod preferences;
 pub mod update;
 
 // Server-side only modules
@@ -54,6 +60,9 @@ pub use delete::*;
 pub use detail::*;
 pub use export::*;
 pub use fields::*;
+pub use generic::*;
+pub use history::*;
 pub use import::*;
 pub use list::*;
+pub use preferences::*;
 pub use update::*;
@@ -2,7 +2,7 @@
 //!
 //! Provides create operations for admin models.
 
-use crate::adapters::{AdminDatabase, AdminRecord, AdminSite};
+use crate::adapters::{AdminDatabase, AdminRecord, AdminSite, RequestUser};
 use crate::types::{MutationRequest, MutationResponse};
 use reinhardt_pages::server_fn::{ServerFnError, server_fn};
 use std::sync::Arc;
@@ -18,7 +18,7 @@ use super::error::MapServerFnError;
 /// # Server Function
 ///
 /// This function is automatically exposed as an HTTP endpoint by the `#[server_fn]` macro.
-/// AdminSite and AdminDatabase dependencies are automatically injected via the DI system.
+/// AdminSite, AdminDatabase and the requesting user are automatically injected via the DI system.
 ///
 /// # Example
 ///
@@ -42,15 +42,35 @@ pub async fn create_record(
 	request: MutationRequest,
 	#[inject] site: Arc<AdminSite>,
 	#[inject] db: Arc<AdminDatabase>,
+	#[inject] user: RequestUser,
 ) -> Result<MutationResponse, ServerFnError> {
 	let model_admin = site.get_model_admin(&model_name).map_server_fn_error()?;
 	let table_name = model_admin.table_name();
 
 	let affected = db
-		.create::<AdminRecord>(table_name, request.data)
+		.create::<AdminRecord>(table_name, request.data.clone())
 		.await
 		.map_server_fn_error()?;
 
+	// `create` returns the generated primary key
+	if affected > 0 {
+		let mut data = request.data;
+		data.insert(
+			model_admin.pk_field().to_string(),
+			serde_json::Value::from(affected),
+		);
+		site.audit_log()
+			.record_create(
+				&db,
+				model_admin.model_name(),
+				&affected.to_string(),
+				user.user_id,
+				&data,
+			)
+			.await
+			.map_server_fn_error()?;
+	}
+
 	Ok(MutationResponse {
 		success: true,
 		message: format!("{} created successfully", model_name),
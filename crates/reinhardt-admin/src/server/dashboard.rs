@@ -2,10 +2,22 @@
 //!
 //! Provides dashboard data retrieval functionality.
 
-use crate::adapters::{AdminSite, DashboardResponse, ModelInfo};
+use crate::adapters::{
+	AdminDatabase, AdminSite, DashboardResponse, DashboardWidgetsRequest, DashboardWidgetsResponse,
+	ModelInfo, ThemeResponse,
+};
 use reinhardt_pages::server_fn::{ServerFnError, server_fn};
 use std::sync::Arc;
 
+#[cfg(not(target_arch = "wasm32"))]
+use super::error::MapServerFnError;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::adapters::NavLinkInfo;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::adapters::WidgetResponse;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::core::dashboard::DateRange;
+
 /// Get dashboard data
 ///
 /// Returns dashboard information including registered models and site metadata.
@@ -48,6 +60,63 @@ pub async fn get_dashboard(
 	})
 }
 
+/// Get computed data for all dashboard widgets
+///
+/// Runs the aggregations of every widget registered on the site's
+/// `WidgetProvider`, optionally restricted to a date range. When only `start`
+/// is given, the range ends at the current time.
+///
+/// # Server Function
+///
+/// This function is automatically exposed as an HTTP endpoint by the `#[server_fn]` macro.
+/// AdminSite and AdminDatabase dependencies are automatically injected via the DI system.
+///
+/// # Example
+///
+/// ```ignore
+/// use reinhardt_admin::server::get_dashboard_widgets;
+/// use reinhardt_admin::types::DashboardWidgetsRequest;
+///
+/// // Client-side usage (automatically generates HTTP request)
+/// let response = get_dashboard_widgets(DashboardWidgetsRequest::default()).await?;
+/// println!("{} widgets", response.widgets.len());
+/// ```
+#[server_fn(use_inject = true)]
+pub async fn get_dashboard_widgets(
+	request: DashboardWidgetsRequest,
+	#[inject] site: Arc<AdminSite>,
+	#[inject] db: Arc<AdminDatabase>,
+) -> Result<DashboardWidgetsResponse, ServerFnError> {
+	let range = match (request.start, request.end) {
+		(None, None) => None,
+		(Some(start), end) => Some(
+			DateRange::new(start, end.unwrap_or_else(chrono::Utc::now)).map_server_fn_error()?,
+		),
+		(None, Some(_)) => {
+			return Err(ServerFnError::application(
+				"Dashboard date range requires a start",
+			));
+		}
+	};
+
+	let provider = site.widget_provider();
+	let data = provider
+		.fetch_all(&db, range.as_ref())
+		.await
+		.map_server_fn_error()?;
+
+	let widgets = data
+		.into_iter()
+		.map(|(key, data)| WidgetResponse {
+			title: provider.title(&key).unwrap_or_default().to_string(),
+			key,
+			data,
+		})
+		.collect();
+
+	Ok(DashboardWidgetsResponse { widgets })
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -82,3 +151,46 @@ mod tests {
 		assert_eq!(model_info.list_url, "/admin/user/");
 	}
 }
+
+/// Get the theme of the admin panel
+///
+/// Returns the branding, extra assets and navigation links configured on the
+/// site's `ThemeConfig`, for use by the admin UI.
+///
+/// # Server Function
+///
+/// This function is automatically exposed as an HTTP endpoint by the `#[server_fn]` macro.
+/// AdminSite dependency is automatically injected via the DI system.
+///
+/// # Example
+///
+/// ```ignore
+/// use reinhardt_admin::server::get_theme;
+///
+/// // Client-side usage (automatically generates HTTP request)
+/// let theme = get_theme().await?;
+/// println!("Brand: {}", theme.brand_name);
+/// ```
+#[server_fn(use_inject = true)]
+pub async fn get_theme(#[inject] site: Arc<AdminSite>) -> Result<ThemeResponse, ServerFnError> {
+	let theme = site.theme();
+	let brand_name = theme
+		.brand_name
+		.unwrap_or_else(|| site.config().site_header);
+
+	Ok(ThemeResponse {
+		brand_name,
+		logo_url: theme.logo_url,
+		primary_color: theme.primary_color,
+		extra_css: theme.extra_css,
+		extra_js: theme.extra_js,
+		nav_links: theme
+			.nav_links
+			.into_iter()
+			.map(|link| NavLinkInfo {
+				label: link.label,
+				url: link.url,
+			})
+			.collect(),
+	})
+}
@@ -3,7 +3,7 @@
 //! Provides delete operations for admin models (single and bulk).
 
 use crate::adapters::{
-	AdminDatabase, AdminRecord, AdminSite, BulkDeleteRequest, BulkDeleteResponse,
+	AdminDatabase, AdminRecord, AdminSite, BulkDeleteRequest, BulkDeleteResponse, RequestUser,
 };
 use crate::types::MutationResponse;
 use reinhardt_pages::server_fn::{ServerFnError, server_fn};
@@ -20,7 +20,7 @@ use super::error::MapServerFnError;
 /// # Server Function
 ///
 /// This function is automatically exposed as an HTTP endpoint by the `#[server_fn]` macro.
-/// AdminSite and AdminDatabase dependencies are automatically injected via the DI system.
+/// AdminSite, AdminDatabase and the requesting user are automatically injected via the DI system.
 ///
 /// # Example
 ///
@@ -37,16 +37,31 @@ pub async fn delete_record(
 	id: String,
 	#[inject] site: Arc<AdminSite>,
 	#[inject] db: Arc<AdminDatabase>,
+	#[inject] user: RequestUser,
 ) -> Result<MutationResponse, ServerFnError> {
 	let model_admin = site.get_model_admin(&model_name).map_server_fn_error()?;
 	let table_name = model_admin.table_name();
 	let pk_field = model_admin.pk_field();
 
+	let before = db
+		.get::<AdminRecord>(table_name, pk_field, &id)
+		.await
+		.map_server_fn_error()?;
+
 	let affected = db
 		.delete::<AdminRecord>(table_name, pk_field, &id)
 		.await
 		.map_server_fn_error()?;
 
+	if affected > 0
+		&& let Some(before) = before
+	{
+		site.audit_log()
+			.record_delete(&db, model_admin.model_name(), &id, user.user_id, &before)
+			.await
+			.map_server_fn_error()?;
+	}
+
 	Ok(MutationResponse {
 		success: true,
 		message: format!("{} deleted successfully", model_name),
@@ -2,7 +2,10 @@
 //!
 //! Provides export operations for admin models.
 
-use crate::adapters::{AdminDatabase, AdminRecord, AdminSite, ExportFormat, ExportResponse};
+use crate::adapters::{
+	AdminDatabase, AdminRecord, AdminSite, BackgroundExporter, ExportFormat, ExportJobResponse,
+	ExportResponse,
+};
 use reinhardt_pages::server_fn::{ServerFnError, server_fn};
 use std::sync::Arc;
 
@@ -113,3 +116,71 @@ pub async fn export_data(
 		content_type: content_type.to_string(),
 	})
 }
+
+/// Start a background export of model data
+///
+/// Enqueues an export task and returns immediately with the job state. Poll
+/// [`get_export_status`] with the returned `job_id` until the status is
+/// `completed`, then download the file from the signed `download_url`.
+///
+/// # Server Function
+///
+/// This function is automatically exposed as an HTTP endpoint by the `#[server_fn]` macro.
+/// AdminSite and BackgroundExporter dependencies are automatically injected via the DI system.
+///
+/// # Example
+///
+/// ```ignore
+/// use reinhardt_admin::server::start_export;
+/// use reinhardt_admin::types::ExportFormat;
+///
+/// // Client-side usage (automatically generates HTTP request)
+/// let job = start_export("User".to_string(), ExportFormat::CSV).await?;
+/// println!("Export job {} started", job.job_id);
+/// ```
+#[server_fn(use_inject = true)]
+pub async fn start_export(
+	model_name: String,
+	format: ExportFormat,
+	#[inject] site: Arc<AdminSite>,
+	#[inject] exporter: Arc<BackgroundExporter>,
+) -> Result<ExportJobResponse, ServerFnError> {
+	let model_admin = site.get_model_admin(&model_name).map_server_fn_error()?;
+
+	let job_id = exporter
+		.enqueue(&model_name, model_admin.table_name(), format)
+		.await
+		.map_server_fn_error()?;
+
+	exporter.status(job_id).await.map_server_fn_error()
+}
+
+/// Get the progress of a background export
+///
+/// # Server Function
+///
+/// This function is automatically exposed as an HTTP endpoint by the `#[server_fn]` macro.
+/// The BackgroundExporter dependency is automatically injected via the DI system.
+///
+/// # Example
+///
+/// ```ignore
+/// use reinhardt_admin::server::get_export_status;
+///
+/// // Client-side usage (automatically generates HTTP request)
+/// let job = get_export_status(job_id).await?;
+/// if let Some(url) = job.download_url {
+///     println!("Download at {}", url);
+/// }
+/// ```
+#[server_fn(use_inject = true)]
+pub async fn get_export_status(
+	job_id: String,
+	#[inject] exporter: Arc<BackgroundExporter>,
+) -> Result<ExportJobResponse, ServerFnError> {
+	let id = job_id
+		.parse()
+		.map_err(|_| ServerFnError::application(format!("Invalid export job ID: {}", job_id)))?;
+
+	exporter.status(id).await.map_server_fn_error()
+}
@@ -14,11 +14,40 @@ use crate::server::type_inference::{get_field_metadata, infer_admin_field_type,
 #[cfg(not(target_arch = "wasm32"))]
 use reinhardt_utils::utils_core::text::humanize_field_name;
 
+/// Build the form metadata of a single field
+///
+/// The type and requiredness are inferred from the global model registry,
+/// falling back to an optional text input.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn build_field_info(table_name: &str, name: &str, readonly: bool) -> FieldInfo {
+	let (field_type, required) = get_field_metadata(table_name, name)
+		.map(|meta| {
+			let admin_type = infer_admin_field_type(&meta.field_type);
+			let is_required = infer_required(&meta);
+			(admin_type, is_required)
+		})
+		.unwrap_or_else(|| (FieldType::Text, false));
+
+	FieldInfo {
+		name: name.to_string(),
+		label: humanize_field_name(name),
+		field_type,
+		required,
+		readonly,
+		help_text: None,
+		placeholder: None,
+	}
+}
+
 /// Get field definitions for dynamic form generation
 ///
 /// Retrieves field metadata for creating or editing model instances.
 /// When `id` is provided, also retrieves the existing field values for editing.
 ///
+/// The content type column of a generic foreign key is rendered as a
+/// [`FieldType::Select`] of its allowed content types; the object id column
+/// stays a plain editable field.
+///
 /// # Server Function
 ///
 /// This function is automatically exposed as an HTTP endpoint by the `#[server_fn]` macro.
@@ -49,32 +78,18 @@ pub async fn get_fields(
 		.fields()
 		.unwrap_or_else(|| model_admin.list_display());
 	let readonly_fields = model_admin.readonly_fields();
+	let generic_foreign_keys = model_admin.generic_foreign_keys();
 
 	// Build field metadata with type inference from global registry
 	let table_name = model_admin.table_name();
-	let fields = field_names
+	let fields: Vec<FieldInfo> = field_names
 		.iter()
 		.map(|&name| {
-			let is_readonly = readonly_fields.contains(&name);
-
-			// Try to get field metadata from the global model registry
-			let (field_type, required) = get_field_metadata(table_name, name)
-				.map(|meta| {
-					let admin_type = infer_admin_field_type(&meta.field_type);
-					let is_required = infer_required(&meta);
-					(admin_type, is_required)
-				})
-				.unwrap_or_else(|| (FieldType::Text, false));
-
-			FieldInfo {
-				name: name.to_string(),
-				label: humanize_field_name(name),
-				field_type,
-				required,
-				readonly: is_readonly,
-				help_text: None,
-				placeholder: None,
+			let mut field = build_field_info(table_name, name, readonly_fields.contains(&name));
+			for gfk in generic_foreign_keys {
+				gfk.apply_to_field(&mut field);
 			}
+			field
 		})
 		.collect();
 
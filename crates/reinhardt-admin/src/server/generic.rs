@@ -0,0 +1,349 @@
+//! Generic relation Server Functions
+//!
+//! Provides the listing, saving and deletion of generic inline rows.
+
+use crate::adapters::{AdminDatabase, AdminRecord, AdminSite, RequestUser};
+use crate::types::{GenericInlinesResponse, MutationRequest, MutationResponse};
+use reinhardt_pages::server_fn::{ServerFnError, server_fn};
+use std::sync::Arc;
+
+#[cfg(not(target_arch = "wasm32"))]
+use super::error::MapServerFnError;
+#[cfg(not(target_arch = "wasm32"))]
+use super::fields::build_field_info;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::adapters::ModelAdmin;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::core::GenericInline;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::types::GenericInlineResponse;
+
+/// Maximum number of rows loaded per generic inline
+#[cfg(not(target_arch = "wasm32"))]
+const INLINE_ROWS_LIMIT: u64 = 1000;
+
+/// Parse the primary key of the object owning generic inlines
+#[cfg(not(target_arch = "wasm32"))]
+fn parse_object_id(id: &str) -> Result<i64, ServerFnError> {
+	id.parse().map_err(|_| {
+		ServerFnError::application(format!(
+			"Generic relations require an integer primary key, got '{}'",
+			id
+		))
+	})
+}
+
+/// Find the generic inline of `model_admin` editing `inline_model`
+#[cfg(not(target_arch = "wasm32"))]
+fn find_inline<'a>(
+	model_admin: &'a Arc<dyn ModelAdmin>,
+	inline_model: &str,
+) -> Result<&'a GenericInline, ServerFnError> {
+	model_admin
+		.generic_inlines()
+		.iter()
+		.find(|inline| inline.model_name() == inline_model)
+		.ok_or_else(|| {
+			ServerFnError::application(format!(
+				"{} has no generic inline for {}",
+				model_admin.model_name(),
+				inline_model
+			))
+		})
+}
+
+/// Get the generic inlines of an object
+///
+/// Returns, for each generic inline configured on the model, its editable
+/// fields and the rows currently pointing to the object (at most 1000 per inline).
+///
+/// # Server Function
+///
+/// This function is automatically exposed as an HTTP endpoint by the `#[server_fn]` macro.
+/// AdminSite and AdminDatabase dependencies are automatically injected via the DI system.
+///
+/// # Example
+///
+/// ```ignore
+/// use reinhardt_admin::server::get_generic_inlines;
+///
+/// let response = get_generic_inlines("Post".to_string(), "42".to_string()).await?;
+/// for inline in response.inlines {
+///     println!("{}: {} rows", inline.model_name, inline.rows.len());
+/// }
+/// ```
+#[server_fn(use_inject = true)]
+pub async fn get_generic_inlines(
+	model_name: String,
+	id: String,
+	#[inject] site: Arc<AdminSite>,
+	#[inject] db: Arc<AdminDatabase>,
+) -> Result<GenericInlinesResponse, ServerFnError> {
+	let model_admin = site.get_model_admin(&model_name).map_server_fn_error()?;
+	let object_id = parse_object_id(&id)?;
+
+	let mut inlines = Vec::new();
+	for inline in model_admin.generic_inlines() {
+		let inline_admin = site
+			.get_model_admin(inline.model_name())
+			.map_server_fn_error()?;
+		let table_name = inline_admin.table_name();
+		let readonly_fields = inline_admin.readonly_fields();
+
+		// The relation columns are set by the inline itself
+		let field_names = inline.fields().unwrap_or_else(|| {
+			inline_admin
+				.fields()
+				.unwrap_or_else(|| inline_admin.list_display())
+		});
+		let fields = field_names
+			.into_iter()
+			.filter(|&name| name != inline.ct_field() && name != inline.fk_field())
+			.map(|name| build_field_info(table_name, name, readonly_fields.contains(&name)))
+			.collect();
+
+		let rows = db
+			.list_with_condition::<AdminRecord>(
+				table_name,
+				None,
+				inline.filters(object_id).map_server_fn_error()?,
+				inline_admin.ordering().first().copied(),
+				0,
+				INLINE_ROWS_LIMIT,
+			)
+			.await
+			.map_server_fn_error()?;
+
+		inlines.push(GenericInlineResponse {
+			model_name: inline.model_name().to_string(),
+			ct_field: inline.ct_field().to_string(),
+			fk_field: inline.fk_field().to_string(),
+			fields,
+			extra: inline.extra(),
+			rows,
+		});
+	}
+
+	Ok(GenericInlinesResponse {
+		model_name,
+		object_id: id,
+		inlines,
+	})
+}
+
+/// Create or update a generic inline row
+///
+/// The row's content type and object id are always set to the owning object,
+/// whatever the request contains. When `row_id` is given, the row must already
+/// point to the owning object.
+///
+/// # Server Function
+///
+/// This function is automatically exposed as an HTTP endpoint by the `#[server_fn]` macro.
+/// AdminSite, AdminDatabase and the requesting user are automatically injected via the DI system.
+///
+/// # Example
+///
+/// ```ignore
+/// use reinhardt_admin::server::save_generic_inline;
+/// use reinhardt_admin::types::MutationRequest;
+/// use std::collections::HashMap;
+///
+/// let mut data = HashMap::new();
+/// data.insert("body".to_string(), serde_json::json!("Great post"));
+///
+/// // Add a comment to post 42
+/// let response = save_generic_inline(
+///     "Post".to_string(),
+///     "42".to_string(),
+///     "Comment".to_string(),
+///     None,
+///     MutationRequest { data },
+/// )
+/// .await?;
+/// println!("Saved: {}", response.message);
+/// ```
+#[server_fn(use_inject = true)]
+#[allow(clippy::too_many_arguments)] // Request arguments plus injected dependencies
+pub async fn save_generic_inline(
+	model_name: String,
+	id: String,
+	inline_model: String,
+	row_id: Option<String>,
+	request: MutationRequest,
+	#[inject] site: Arc<AdminSite>,
+	#[inject] db: Arc<AdminDatabase>,
+	#[inject] user: RequestUser,
+) -> Result<MutationResponse, ServerFnError> {
+	let model_admin = site.get_model_admin(&model_name).map_server_fn_error()?;
+	let object_id = parse_object_id(&id)?;
+	let inline = find_inline(&model_admin, &inline_model)?;
+	let inline_admin = site
+		.get_model_admin(inline.model_name())
+		.map_server_fn_error()?;
+	let table_name = inline_admin.table_name();
+	let pk_field = inline_admin.pk_field();
+
+	let mut data = request.data;
+	inline.bind(&mut data, object_id).map_server_fn_error()?;
+
+	let Some(row_id) = row_id else {
+		let created = db
+			.create::<AdminRecord>(table_name, data.clone())
+			.await
+			.map_server_fn_error()?;
+
+		data.insert(pk_field.to_string(), serde_json::Value::from(created));
+		site.audit_log()
+			.record_create(
+				&db,
+				inline_admin.model_name(),
+				&created.to_string(),
+				user.user_id,
+				&data,
+			)
+			.await
+			.map_server_fn_error()?;
+
+		return Ok(MutationResponse {
+			success: true,
+			message: format!("{} created successfully", inline_model),
+			affected: Some(created),
+			data: None,
+		});
+	};
+
+	let before = db
+		.get::<AdminRecord>(table_name, pk_field, &row_id)
+		.await
+		.map_server_fn_error()?
+		.filter(|row| inline.owns(row, object_id))
+		.ok_or_else(|| {
+			ServerFnError::application(format!(
+				"{} {} does not belong to {} {}",
+				inline_model, row_id, model_name, id
+			))
+		})?;
+	let mut after = before.clone();
+	after.extend(data.clone());
+
+	let affected = db
+		.update::<AdminRecord>(table_name, pk_field, &row_id, data)
+		.await
+		.map_server_fn_error()?;
+
+	if affected > 0 {
+		site.audit_log()
+			.record_update(
+				&db,
+				inline_admin.model_name(),
+				&row_id,
+				user.user_id,
+				&before,
+				&after,
+			)
+			.await
+			.map_server_fn_error()?;
+	}
+
+	Ok(MutationResponse {
+		success: true,
+		message: format!("{} updated successfully", inline_model),
+		affected: Some(affected),
+		data: None,
+	})
+}
+
+/// Delete a generic inline row
+///
+/// The row must point to the owning object; rows of other objects are never
+/// deleted through this endpoint.
+///
+/// # Server Function
+///
+/// This function is automatically exposed as an HTTP endpoint by the `#[server_fn]` macro.
+/// AdminSite, AdminDatabase and the requesting user are automatically injected via the DI system.
+///
+/// # Example
+///
+/// ```ignore
+/// use reinhardt_admin::server::delete_generic_inline;
+///
+/// // Remove comment 7 from post 42
+/// let response = delete_generic_inline(
+///     "Post".to_string(),
+///     "42".to_string(),
+///     "Comment".to_string(),
+///     "7".to_string(),
+/// )
+/// .await?;
+/// println!("Deleted: {}", response.message);
+/// ```
+#[server_fn(use_inject = true)]
+pub async fn delete_generic_inline(
+	model_name: String,
+	id: String,
+	inline_model: String,
+	row_id: String,
+	#[inject] site: Arc<AdminSite>,
+	#[inject] db: Arc<AdminDatabase>,
+	#[inject] user: RequestUser,
+) -> Result<MutationResponse, ServerFnError> {
+	let model_admin = site.get_model_admin(&model_name).map_server_fn_error()?;
+	let object_id = parse_object_id(&id)?;
+	let inline = find_inline(&model_admin, &inline_model)?;
+	let inline_admin = site
+		.get_model_admin(inline.model_name())
+		.map_server_fn_error()?;
+	let table_name = inline_admin.table_name();
+	let pk_field = inline_admin.pk_field();
+
+	let before = db
+		.get::<AdminRecord>(table_name, pk_field, &row_id)
+		.await
+		.map_server_fn_error()?
+		.filter(|row| inline.owns(row, object_id))
+		.ok_or_else(|| {
+			ServerFnError::application(format!(
+				"{} {} does not belong to {} {}",
+				inline_model, row_id, model_name, id
+			))
+		})?;
+
+	let affected = db
+		.delete::<AdminRecord>(table_name, pk_field, &row_id)
+		.await
+		.map_server_fn_error()?;
+
+	if affected > 0 {
+		site.audit_log()
+			.record_delete(
+				&db,
+				inline_admin.model_name(),
+				&row_id,
+				user.user_id,
+				&before,
+			)
+			.await
+			.map_server_fn_error()?;
+	}
+
+	Ok(MutationResponse {
+		success: true,
+		message: format!("{} deleted successfully", inline_model),
+		affected: Some(affected),
+		data: None,
+	})
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+
+	#[rstest]
+	fn test_parse_object_id_rejects_non_integer() {
+		assert_eq!(parse_object_id("42").unwrap(), 42);
+		assert!(parse_object_id("abc").is_err());
+	}
+}
@@ -0,0 +1,121 @@
+//! Change history Server Functions
+//!
+//! Provides per-object change history with field-level diffs and reverting
+//! an object to a previous version.
+
+use crate::adapters::{AdminDatabase, AdminSite, RequestUser};
+use crate::types::{MutationResponse, ObjectHistoryResponse};
+use reinhardt_pages::server_fn::{ServerFnError, server_fn};
+use std::sync::Arc;
+
+#[cfg(not(target_arch = "wasm32"))]
+use super::error::MapServerFnError;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::types::{FieldChangeInfo, HistoryEntryResponse};
+
+/// Get the change history of a model instance
+///
+/// Returns every recorded create, update, delete and revert of the object,
+/// oldest first, with a human-readable description of each field change.
+///
+/// # Server Function
+///
+/// This function is automatically exposed as an HTTP endpoint by the `#[server_fn]` macro.
+/// AdminSite and AdminDatabase dependencies are automatically injected via the DI system.
+///
+/// # Example
+///
+/// ```ignore
+/// use reinhardt_admin::server::get_object_history;
+///
+/// // Client-side usage (automatically generates HTTP request)
+/// let history = get_object_history("User".to_string(), "42".to_string()).await?;
+/// for entry in history.entries {
+///     println!("{}", entry.summary);
+/// }
+/// ```
+#[server_fn(use_inject = true)]
+pub async fn get_object_history(
+	model_name: String,
+	id: String,
+	#[inject] site: Arc<AdminSite>,
+	#[inject] db: Arc<AdminDatabase>,
+) -> Result<ObjectHistoryResponse, ServerFnError> {
+	let model_admin = site.get_model_admin(&model_name).map_server_fn_error()?;
+
+	let entries = site
+		.audit_log()
+		.history(&db, model_admin.model_name(), &id)
+		.await
+		.map_server_fn_error()?
+		.into_iter()
+		.map(|entry| HistoryEntryResponse {
+			id: entry.id,
+			action: entry.action.as_str().to_string(),
+			user_id: entry.user_id.clone(),
+			timestamp: entry.timestamp,
+			summary: entry.summary(),
+			changes: entry
+				.changes
+				.iter()
+				.map(|change| FieldChangeInfo {
+					field: change.field.clone(),
+					before: change.before.clone(),
+					after: change.after.clone(),
+					description: change.describe(),
+				})
+				.collect(),
+		})
+		.collect();
+
+	Ok(ObjectHistoryResponse {
+		model_name,
+		object_id: id,
+		entries,
+	})
+}
+
+/// Revert a model instance to a previous version
+///
+/// Restores the field values recorded by a history entry inside a single
+/// transaction. A deleted object is re-inserted. The revert itself is
+/// recorded in the history.
+///
+/// # Server Function
+///
+/// This function is automatically exposed as an HTTP endpoint by the `#[server_fn]` macro.
+/// AdminSite, AdminDatabase and the requesting user are automatically injected via the DI system.
+///
+/// # Example
+///
+/// ```ignore
+/// use reinhardt_admin::server::revert_object;
+///
+/// // Client-side usage (automatically generates HTTP request)
+/// let response = revert_object("User".to_string(), "42".to_string(), 7).await?;
+/// println!("{}", response.message);
+/// ```
+#[server_fn(use_inject = true)]
+pub async fn revert_object(
+	model_name: String,
+	id: String,
+	entry_id: u64,
+	#[inject] site: Arc<AdminSite>,
+	#[inject] db: Arc<AdminDatabase>,
+	#[inject] user: RequestUser,
+) -> Result<MutationResponse, ServerFnError> {
+	let model_admin = site.get_model_admin(&model_name).map_server_fn_error()?;
+
+	let entry = site
+		.audit_log()
+		.revert(&db, model_admin.as_ref(), &id, entry_id, user.user_id)
+		.await
+		.map_server_fn_error()?;
+
+	Ok(MutationResponse {
+		success: true,
+		message: format!("{} reverted to version {}", model_name, entry_id),
+		affected: Some(entry.changes.len() as u64),
+		data: entry.snapshot,
+	})
+}
@@ -2,19 +2,28 @@
 //!
 //! Provides import operations for admin models from various formats (JSON, CSV, TSV).
 
-use crate::adapters::{AdminDatabase, AdminRecord, AdminSite, ImportFormat, ImportResponse};
+use crate::adapters::{
+	AdminDatabase, AdminSite, ImportFormat, ImportResponse, ImportValidationReport,
+};
 use reinhardt_pages::server_fn::{ServerFnError, server_fn};
-#[cfg(not(target_arch = "wasm32"))]
-use std::collections::HashMap;
 use std::sync::Arc;
 
 #[cfg(not(target_arch = "wasm32"))]
 use super::error::MapServerFnError;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::core::ImportBuilder;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::core::import::ImportValidator;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::server::type_inference::find_model_by_table_name;
 
 /// Import model data from various formats
 ///
 /// Imports records from uploaded data in the specified format (JSON, CSV, TSV).
-/// Each record is inserted as a new entry. Returns statistics about the import operation.
+/// Records are parsed and validated exactly as [`validate_import`] does; if
+/// any row is invalid nothing is written. Valid records are inserted as new
+/// entries in a single transaction. With `dry_run`, the transaction is rolled
+/// back, so database constraints are checked without persisting anything.
 ///
 /// # Server Function
 ///
@@ -32,7 +41,8 @@ use super::error::MapServerFnError;
 /// let response = import_data(
 ///     "User".to_string(),
 ///     ImportFormat::JSON,
-///     file_data
+///     file_data,
+///     false,
 /// ).await?;
 /// println!("Imported {} records", response.imported);
 /// ```
@@ -41,65 +51,105 @@ pub async fn import_data(
 	model_name: String,
 	format: ImportFormat,
 	data: Vec<u8>,
+	dry_run: bool,
 	#[inject] site: Arc<AdminSite>,
 	#[inject] db: Arc<AdminDatabase>,
 ) -> Result<ImportResponse, ServerFnError> {
 	let model_admin = site.get_model_admin(&model_name).map_server_fn_error()?;
 	let table_name = model_admin.table_name();
+	let validator = find_model_by_table_name(table_name)
+		.map(|metadata| ImportValidator::from_model_metadata(&metadata))
+		.unwrap_or_default();
 
-	// Parse data based on format
-	let records: Vec<HashMap<String, serde_json::Value>> = match format {
-		ImportFormat::JSON => serde_json::from_slice(&data)
-			.map_err(|e| ServerFnError::deserialization(format!("JSON parse failed: {}", e)))?,
-		ImportFormat::CSV => {
-			let mut rdr = csv::Reader::from_reader(&data[..]);
-			rdr.deserialize()
-				.collect::<Result<Vec<_>, _>>()
-				.map_err(|e| ServerFnError::deserialization(format!("CSV parse failed: {}", e)))?
-		}
-		ImportFormat::TSV => {
-			let mut rdr = csv::ReaderBuilder::new()
-				.delimiter(b'\t')
-				.from_reader(&data[..]);
-			rdr.deserialize()
-				.collect::<Result<Vec<_>, _>>()
-				.map_err(|e| ServerFnError::deserialization(format!("TSV parse failed: {}", e)))?
-		}
-	};
-
-	// Import records
-	let mut imported = 0;
-	let mut failed = 0;
-	let mut errors = Vec::new();
-
-	for (index, record) in records.into_iter().enumerate() {
-		match db.create::<AdminRecord>(table_name, record).await {
-			Ok(_) => imported += 1,
-			Err(e) => {
-				failed += 1;
-				errors.push(format!("Record {}: {}", index + 1, e));
-			}
-		}
-	}
+	let result = ImportBuilder::new(&model_name, format)
+		.data(data)
+		.dry_run(dry_run)
+		.import(&db, table_name, &validator)
+		.await
+		.map_server_fn_error()?;
 
+	let imported = result.imported_count as u64;
+	let failed = result.failed_count as u64;
 	Ok(ImportResponse {
 		success: failed == 0,
 		imported,
 		updated: 0, // Not supporting updates in basic import
 		skipped: 0,
 		failed,
-		message: if failed == 0 {
-			format!("Successfully imported {} {} records", imported, model_name)
-		} else {
-			format!(
-				"Imported {} {} records, {} failed",
-				imported, model_name, failed
-			)
+		message: match (failed, dry_run) {
+			(0, false) => format!("Successfully imported {} {} records", imported, model_name),
+			(0, true) => format!(
+				"Dry run: {} {} records would be imported, nothing was saved",
+				imported, model_name
+			),
+			_ => format!(
+				"{} {} records failed, nothing was imported",
+				failed, model_name
+			),
 		},
-		errors: if errors.is_empty() {
+		errors: if result.errors.is_empty() {
 			None
 		} else {
-			Some(errors)
+			Some(
+				result
+					.errors
+					.iter()
+					.map(|e| format!("Record {}: {}", e.row_number, e.message))
+					.collect(),
+			)
 		},
 	})
 }
+
+/// Validate import data without writing anything (dry run)
+///
+/// Parses the uploaded data and validates every row against the model's
+/// field metadata: type coercion, required fields, uniqueness (within the file
+/// and against existing rows) and foreign key resolution. Returns a report of
+/// errors and warnings per row and column.
+///
+/// When the model is not found in the migration registry, only parsing is
+/// validated.
+///
+/// # Server Function
+///
+/// This function is automatically exposed as an HTTP endpoint by the `#[server_fn]` macro.
+/// AdminSite and AdminDatabase dependencies are automatically injected via the DI system.
+///
+/// # Example
+///
+/// ```ignore
+/// use reinhardt_admin::server::validate_import;
+/// use reinhardt_admin::types::ImportFormat;
+///
+/// // Client-side usage (automatically generates HTTP request)
+/// let report = validate_import("User".to_string(), ImportFormat::CSV, file_data).await?;
+/// if report.has_errors() {
+///     println!("{} rows would fail", report.invalid_rows);
+/// }
+/// ```
+#[server_fn(use_inject = true)]
+pub async fn validate_import(
+	model_name: String,
+	format: ImportFormat,
+	data: Vec<u8>,
+	#[inject] site: Arc<AdminSite>,
+	#[inject] db: Arc<AdminDatabase>,
+) -> Result<ImportValidationReport, ServerFnError> {
+	let model_admin = site.get_model_admin(&model_name).map_server_fn_error()?;
+	let table_name = model_admin.table_name();
+
+	let records = ImportBuilder::new(&model_name, format)
+		.data(data)
+		.parse()
+		.map_server_fn_error()?;
+
+	let validator = find_model_by_table_name(table_name)
+		.map(|metadata| ImportValidator::from_model_metadata(&metadata))
+		.unwrap_or_default();
+
+	validator
+		.validate_with_database(&db, table_name, &records)
+		.await
+		.map_server_fn_error()
+}
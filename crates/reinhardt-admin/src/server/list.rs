@@ -4,7 +4,7 @@
 
 use crate::adapters::{
 	AdminDatabase, AdminRecord, AdminSite, ColumnInfo, FilterInfo, FilterType, ListQueryParams,
-	ListResponse, ModelAdmin,
+	ListResponse, ModelAdmin, RequestUser,
 };
 #[cfg(not(target_arch = "wasm32"))]
 use reinhardt_db::orm::{Filter, FilterCondition, FilterOperator, FilterValue};
@@ -46,9 +46,22 @@ fn build_filters(model_admin: &Arc<dyn ModelAdmin>) -> Vec<FilterInfo> {
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-fn build_columns(model_admin: &Arc<dyn ModelAdmin>) -> Vec<ColumnInfo> {
-	model_admin
-		.list_display()
+fn build_columns(
+	model_admin: &Arc<dyn ModelAdmin>,
+	selected: Option<&[String]>,
+) -> Vec<ColumnInfo> {
+	let list_display = model_admin.list_display();
+	let fields: Vec<&str> = match selected {
+		// Keep the user's order, ignoring columns no longer displayable
+		Some(selected) => selected
+			.iter()
+			.map(String::as_str)
+			.filter(|field| list_display.contains(field))
+			.collect(),
+		None => list_display,
+	};
+
+	fields
 		.iter()
 		.map(|field| ColumnInfo {
 			field: field.to_string(),
@@ -93,9 +106,21 @@ pub async fn get_list(
 	params: ListQueryParams,
 	#[inject] site: Arc<AdminSite>,
 	#[inject] db: Arc<AdminDatabase>,
+	#[inject] user: RequestUser,
 ) -> Result<ListResponse, ServerFnError> {
 	let model_admin = site.get_model_admin(&model_name).map_server_fn_error()?;
 
+	// Load the requesting user's saved filters and column choice
+	let preferences = match user.user_id.as_deref() {
+		Some(user_id) => Some(
+			site.preference_store()
+				.load(user_id, model_admin.model_name())
+				.await
+				.map_server_fn_error()?,
+		),
+		None => None,
+	};
+
 	// Build search condition (OR across search fields)
 	let mut filter_condition: Option<FilterCondition> = None;
 	if let Some(search) = params.search.as_ref() {
@@ -179,6 +204,10 @@ pub async fn get_list(
 		total_pages,
 		results,
 		available_filters: Some(build_filters(&model_admin)),
-		columns: Some(build_columns(&model_admin)),
+		columns: Some(build_columns(
+			&model_admin,
+			preferences.as_ref().and_then(|p| p.columns.as_deref()),
+		)),
+		preferences,
 	})
 }
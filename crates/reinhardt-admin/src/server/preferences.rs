@@ -0,0 +1,197 @@
+//! List view preference Server Functions
+//!
+//! Provides saving named filter/search/sort combinations and choosing the
+//! displayed columns of list views, per user and model.
+
+use crate::adapters::{AdminSite, ListPreferences, RequestUser, SavedFilter};
+use reinhardt_pages::server_fn::{ServerFnError, server_fn};
+use std::sync::Arc;
+
+#[cfg(not(target_arch = "wasm32"))]
+use super::error::MapServerFnError;
+
+/// Get the list view preferences of the current user
+///
+/// # Server Function
+///
+/// This function is automatically exposed as an HTTP endpoint by the `#[server_fn]` macro.
+/// AdminSite and the requesting user are automatically injected via the DI system.
+///
+/// # Example
+///
+/// ```ignore
+/// use reinhardt_admin::server::get_list_preferences;
+///
+/// // Client-side usage (automatically generates HTTP request)
+/// let prefs = get_list_preferences("User".to_string()).await?;
+/// println!("{} saved filters", prefs.saved_filters.len());
+/// ```
+#[server_fn(use_inject = true)]
+pub async fn get_list_preferences(
+	model_name: String,
+	#[inject] site: Arc<AdminSite>,
+	#[inject] user: RequestUser,
+) -> Result<ListPreferences, ServerFnError> {
+	let model_admin = site.get_model_admin(&model_name).map_server_fn_error()?;
+	let user_id = user.require_id().map_server_fn_error()?;
+
+	site.preference_store()
+		.load(user_id, model_admin.model_name())
+		.await
+		.map_server_fn_error()
+}
+
+/// Save a named filter/search/sort combination for the current user
+///
+/// A saved filter with the same name is replaced.
+///
+/// # Server Function
+///
+/// This function is automatically exposed as an HTTP endpoint by the `#[server_fn]` macro.
+/// AdminSite and the requesting user are automatically injected via the DI system.
+///
+/// # Example
+///
+/// ```ignore
+/// use reinhardt_admin::server::save_list_filter;
+/// use reinhardt_admin::types::SavedFilter;
+/// use std::collections::HashMap;
+///
+/// // Client-side usage (automatically generates HTTP request)
+/// let filter = SavedFilter {
+///     name: "Active staff".to_string(),
+///     filters: HashMap::from([("is_staff".to_string(), "true".to_string())]),
+///     search: None,
+///     sort_by: Some("-date_joined".to_string()),
+/// };
+/// let prefs = save_list_filter("User".to_string(), filter).await?;
+/// ```
+#[server_fn(use_inject = true)]
+pub async fn save_list_filter(
+	model_name: String,
+	filter: SavedFilter,
+	#[inject] site: Arc<AdminSite>,
+	#[inject] user: RequestUser,
+) -> Result<ListPreferences, ServerFnError> {
+	let model_admin = site.get_model_admin(&model_name).map_server_fn_error()?;
+	let user_id = user.require_id().map_server_fn_error()?;
+
+	if filter.name.trim().is_empty() {
+		return Err(ServerFnError::application(
+			"Saved filter name must not be empty",
+		));
+	}
+
+	let store = site.preference_store();
+	let mut prefs = store
+		.load(user_id, model_admin.model_name())
+		.await
+		.map_server_fn_error()?;
+	prefs.upsert_saved_filter(filter);
+	store
+		.save(user_id, model_admin.model_name(), &prefs)
+		.await
+		.map_server_fn_error()?;
+
+	Ok(prefs)
+}
+
+/// Delete a saved filter of the current user
+///
+/// # Server Function
+///
+/// This function is automatically exposed as an HTTP endpoint by the `#[server_fn]` macro.
+/// AdminSite and the requesting user are automatically injected via the DI system.
+///
+/// # Example
+///
+/// ```ignore
+/// use reinhardt_admin::server::delete_list_filter;
+///
+/// // Client-side usage (automatically generates HTTP request)
+/// let prefs = delete_list_filter("User".to_string(), "Active staff".to_string()).await?;
+/// ```
+#[server_fn(use_inject = true)]
+pub async fn delete_list_filter(
+	model_name: String,
+	name: String,
+	#[inject] site: Arc<AdminSite>,
+	#[inject] user: RequestUser,
+) -> Result<ListPreferences, ServerFnError> {
+	let model_admin = site.get_model_admin(&model_name).map_server_fn_error()?;
+	let user_id = user.require_id().map_server_fn_error()?;
+
+	let store = site.preference_store();
+	let mut prefs = store
+		.load(user_id, model_admin.model_name())
+		.await
+		.map_server_fn_error()?;
+	if !prefs.remove_saved_filter(&name) {
+		return Err(ServerFnError::application(format!(
+			"Saved filter '{}' not found",
+			name
+		)));
+	}
+	store
+		.save(user_id, model_admin.model_name(), &prefs)
+		.await
+		.map_server_fn_error()?;
+
+	Ok(prefs)
+}
+
+/// Choose the columns displayed in the list view for the current user
+///
+/// Columns must be part of the model's `list_display`. Passing `None`
+/// restores the default columns.
+///
+/// # Server Function
+///
+/// This function is automatically exposed as an HTTP endpoint by the `#[server_fn]` macro.
+/// AdminSite and the requesting user are automatically injected via the DI system.
+///
+/// # Example
+///
+/// ```ignore
+/// use reinhardt_admin::server::set_list_columns;
+///
+/// // Client-side usage (automatically generates HTTP request)
+/// let columns = Some(vec!["username".to_string(), "email".to_string()]);
+/// let prefs = set_list_columns("User".to_string(), columns).await?;
+/// ```
+#[server_fn(use_inject = true)]
+pub async fn set_list_columns(
+	model_name: String,
+	columns: Option<Vec<String>>,
+	#[inject] site: Arc<AdminSite>,
+	#[inject] user: RequestUser,
+) -> Result<ListPreferences, ServerFnError> {
+	let model_admin = site.get_model_admin(&model_name).map_server_fn_error()?;
+	let user_id = user.require_id().map_server_fn_error()?;
+
+	if let Some(columns) = columns.as_ref() {
+		let list_display = model_admin.list_display();
+		if let Some(unknown) = columns
+			.iter()
+			.find(|column| !list_display.contains(&column.as_str()))
+		{
+			return Err(ServerFnError::application(format!(
+				"Column '{}' is not displayable for {}",
+				unknown, model_name
+			)));
+		}
+	}
+
+	let store = site.preference_store();
+	let mut prefs = store
+		.load(user_id, model_admin.model_name())
+		.await
+		.map_server_fn_error()?;
+	prefs.columns = columns;
+	store
+		.save(user_id, model_admin.model_name(), &prefs)
+		.await
+		.map_server_fn_error()?;
+
+	Ok(prefs)
+}
@@ -2,7 +2,7 @@
 //!
 //! Provides update operations for admin models.
 
-use crate::adapters::{AdminDatabase, AdminRecord, AdminSite};
+use crate::adapters::{AdminDatabase, AdminRecord, AdminSite, RequestUser};
 use crate::types::{MutationRequest, MutationResponse};
 use reinhardt_pages::server_fn::{ServerFnError, server_fn};
 use std::sync::Arc;
@@ -18,7 +18,7 @@ use super::error::MapServerFnError;
 /// # Server Function
 ///
 /// This function is automatically exposed as an HTTP endpoint by the `#[server_fn]` macro.
-/// AdminSite and AdminDatabase dependencies are automatically injected via the DI system.
+/// AdminSite, AdminDatabase and the requesting user are automatically injected via the DI system.
 ///
 /// # Example
 ///
@@ -42,16 +42,39 @@ pub async fn update_record(
 	request: MutationRequest,
 	#[inject] site: Arc<AdminSite>,
 	#[inject] db: Arc<AdminDatabase>,
+	#[inject] user: RequestUser,
 ) -> Result<MutationResponse, ServerFnError> {
 	let model_admin = site.get_model_admin(&model_name).map_server_fn_error()?;
 	let table_name = model_admin.table_name();
 	let pk_field = model_admin.pk_field();
 
+	let before = db
+		.get::<AdminRecord>(table_name, pk_field, &id)
+		.await
+		.map_server_fn_error()?
+		.unwrap_or_default();
+	let mut after = before.clone();
+	after.extend(request.data.clone());
+
 	let affected = db
 		.update::<AdminRecord>(table_name, pk_field, &id, request.data)
 		.await
 		.map_server_fn_error()?;
 
+	if affected > 0 {
+		site.audit_log()
+			.record_update(
+				&db,
+				model_admin.model_name(),
+				&id,
+				user.user_id,
+				&before,
+				&after,
+			)
+			.await
+			.map_server_fn_error()?;
+	}
+
 	Ok(MutationResponse {
 		success: true,
 		message: format!("{} updated successfully", model_name),
@@ -28,6 +28,6 @@ pub use responses::*;
 // These stubs allow Server Function client code to type-check correctly
 #[cfg(target_arch = "wasm32")]
 pub use wasm_stubs::{
-	AdminDatabase, AdminRecord, AdminSite, ExportFormat, ImportBuilder, ImportError, ImportFormat,
-	ImportResult, ModelAdmin,
+	AdminDatabase, AdminRecord, AdminSite, BackgroundExporter, ExportFormat, ImportBuilder,
+	ImportError, ImportFormat, ImportResult, ModelAdmin, RequestUser,
 };
@@ -1,6 +1,7 @@
 //! Model information types
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Model information for dashboard
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -104,3 +105,56 @@ pub struct ColumnInfo {
 	/// Whether column is sortable
 	pub sortable: bool,
 }
+
+/// Named combination of filters, search and sort saved by a user
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedFilter {
+	/// Name chosen by the user (unique per user and model)
+	pub name: String,
+	/// Filter field=value pairs
+	#[serde(default)]
+	pub filters: HashMap<String, String>,
+	/// Search query
+	#[serde(default)]
+	pub search: Option<String>,
+	/// Sort field (prefix with "-" for descending)
+	#[serde(default)]
+	pub sort_by: Option<String>,
+}
+
+/// Per-user list view preferences for a model
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListPreferences {
+	/// Columns to display, in order (`None` uses the model's `list_display`)
+	#[serde(default)]
+	pub columns: Option<Vec<String>>,
+	/// Saved filter combinations
+	#[serde(default)]
+	pub saved_filters: Vec<SavedFilter>,
+}
+
+impl ListPreferences {
+	/// Find a saved filter by name
+	pub fn saved_filter(&self, name: &str) -> Option<&SavedFilter> {
+		self.saved_filters.iter().find(|f| f.name == name)
+	}
+
+	/// Add a saved filter, replacing any existing one with the same name
+	pub fn upsert_saved_filter(&mut self, filter: SavedFilter) {
+		match self
+			.saved_filters
+			.iter_mut()
+			.find(|f| f.name == filter.name)
+		{
+			Some(existing) => *existing = filter,
+			None => self.saved_filters.push(filter),
+		}
+	}
+
+	/// Remove a saved filter by name, returning whether it existed
+	pub fn remove_saved_filter(&mut self, name: &str) -> bool {
+		let before = self.saved_filters.len();
+		self.saved_filters.retain(|f| f.name != name);
+		self.saved_filters.len() != before
+	}
+}
@@ -44,3 +44,14 @@ pub enum ExportFormat {
 	Csv,
 	Tsv,
 }
+
+/// Date range parameters for dashboard widgets
+///
+/// When both bounds are omitted, widgets are computed over all rows.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DashboardWidgetsRequest {
+	/// Start of the range (inclusive)
+	pub start: Option<chrono::DateTime<chrono::Utc>>,
+	/// End of the range (exclusive, defaults to now)
+	pub end: Option<chrono::DateTime<chrono::Utc>>,
+}
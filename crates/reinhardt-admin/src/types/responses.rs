@@ -1,6 +1,6 @@
 //! Response types for admin panel API
 
-use crate::types::models::{ColumnInfo, FilterInfo, ModelInfo};
+use crate::types::models::{ColumnInfo, FilterInfo, ListPreferences, ModelInfo};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -39,6 +39,9 @@ pub struct ListResponse {
 	/// Column definitions for list display
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub columns: Option<Vec<ColumnInfo>>,
+	/// Saved filters and column choice of the requesting user
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub preferences: Option<ListPreferences>,
 }
 
 /// Response for detail endpoint
@@ -108,6 +111,45 @@ pub struct ExportResponse {
 	pub content_type: String,
 }
 
+/// Status of a background export job
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportJobStatus {
+	/// Enqueued, not started yet
+	Pending,
+	/// Rows are being exported
+	Running,
+	/// File is ready for download
+	Completed,
+	/// Export failed
+	Failed,
+}
+
+/// Response for background export endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportJobResponse {
+	/// Job identifier used for polling
+	pub job_id: String,
+	/// Model being exported
+	pub model_name: String,
+	/// Current status
+	pub status: ExportJobStatus,
+	/// Rows exported so far
+	pub processed_rows: u64,
+	/// Total rows to export (once known)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub total_rows: Option<u64>,
+	/// Progress percentage (once the total is known)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub progress_percent: Option<u8>,
+	/// Signed download URL (when completed)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub download_url: Option<String>,
+	/// Error message (when failed)
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+}
+
 /// Response for fields endpoint
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FieldsResponse {
@@ -120,3 +162,286 @@ pub struct FieldsResponse {
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub values: Option<HashMap<String, serde_json::Value>>,
 }
+
+/// A generic inline of an object
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenericInlineResponse {
+	/// Admin model of the related rows
+	pub model_name: String,
+	/// Content type column of the related rows
+	pub ct_field: String,
+	/// Object id column of the related rows
+	pub fk_field: String,
+	/// Editable fields
+	pub fields: Vec<crate::types::models::FieldInfo>,
+	/// Number of empty rows to offer for new objects
+	pub extra: usize,
+	/// Existing related rows
+	pub rows: Vec<HashMap<String, serde_json::Value>>,
+}
+
+/// Response for the generic inlines endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenericInlinesResponse {
+	/// Model name of the owning object
+	pub model_name: String,
+	/// Primary key of the owning object
+	pub object_id: String,
+	/// Inlines in configuration order
+	pub inlines: Vec<GenericInlineResponse>,
+}
+
+/// Chart rendering hint for dashboard chart widgets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChartType {
+	/// Line chart (suited for time series)
+	Line,
+	/// Bar chart
+	Bar,
+	/// Pie chart (suited for breakdowns)
+	Pie,
+}
+
+/// A single labelled data point of a dashboard chart widget
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataPoint {
+	/// Point label (time bucket or group value)
+	pub label: String,
+	/// Aggregated value
+	pub value: i64,
+}
+
+/// Computed data of a dashboard widget
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum WidgetData {
+	/// Single value
+	Stat {
+		/// Aggregated value
+		value: i64,
+	},
+	/// Series of data points
+	Chart {
+		/// Chart rendering hint
+		chart_type: ChartType,
+		/// Data points in display order
+		points: Vec<DataPoint>,
+	},
+}
+
+/// A dashboard widget with its computed data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WidgetResponse {
+	/// Unique widget key
+	pub key: String,
+	/// Widget title
+	pub title: String,
+	/// Computed widget data
+	pub data: WidgetData,
+}
+
+/// Response for dashboard widgets endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DashboardWidgetsResponse {
+	/// Widgets in display order
+	pub widgets: Vec<WidgetResponse>,
+}
+
+/// Severity of an import validation issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportIssueSeverity {
+	/// The row cannot be imported
+	Error,
+	/// The row can be imported, but something may be unexpected
+	Warning,
+}
+
+/// A single problem found while validating import data
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportIssue {
+	/// Data row number (1-indexed), `None` for file-level issues
+	pub row: Option<usize>,
+	/// Column name, `None` for row-level issues
+	pub column: Option<String>,
+	/// Issue severity
+	pub severity: ImportIssueSeverity,
+	/// Human-readable description
+	pub message: String,
+}
+
+impl ImportIssue {
+	/// Create an error for a specific row and column
+	pub fn error(row: usize, column: impl Into<String>, message: impl Into<String>) -> Self {
+		Self {
+			row: Some(row),
+			column: Some(column.into()),
+			severity: ImportIssueSeverity::Error,
+			message: message.into(),
+		}
+	}
+
+	/// Create a warning for a specific row and column
+	pub fn warning(row: usize, column: impl Into<String>, message: impl Into<String>) -> Self {
+		Self {
+			row: Some(row),
+			column: Some(column.into()),
+			severity: ImportIssueSeverity::Warning,
+			message: message.into(),
+		}
+	}
+}
+
+/// Per-row and per-column report produced by an import dry run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportValidationReport {
+	/// Number of data rows validated
+	pub total_rows: usize,
+	/// Number of rows without errors
+	pub valid_rows: usize,
+	/// Number of rows with at least one error
+	pub invalid_rows: usize,
+	/// All issues, in discovery order
+	pub issues: Vec<ImportIssue>,
+}
+
+impl ImportValidationReport {
+	/// Create a report from validated row count and issues
+	pub fn new(total_rows: usize, issues: Vec<ImportIssue>) -> Self {
+		let mut report = Self {
+			total_rows,
+			valid_rows: total_rows,
+			invalid_rows: 0,
+			issues: Vec::new(),
+		};
+		for issue in issues {
+			report.push(issue);
+		}
+		report
+	}
+
+	/// Add an issue, updating the row counters
+	pub fn push(&mut self, issue: ImportIssue) {
+		if issue.severity == ImportIssueSeverity::Error
+			&& let Some(row) = issue.row
+			&& !self.has_error_in_row(row)
+		{
+			self.invalid_rows += 1;
+			self.valid_rows = self.valid_rows.saturating_sub(1);
+		}
+		self.issues.push(issue);
+	}
+
+	/// Check whether any error was found
+	pub fn has_errors(&self) -> bool {
+		self.errors().next().is_some()
+	}
+
+	/// Iterate over errors
+	pub fn errors(&self) -> impl Iterator<Item = &ImportIssue> {
+		self.issues
+			.iter()
+			.filter(|i| i.severity == ImportIssueSeverity::Error)
+	}
+
+	/// Iterate over warnings
+	pub fn warnings(&self) -> impl Iterator<Item = &ImportIssue> {
+		self.issues
+			.iter()
+			.filter(|i| i.severity == ImportIssueSeverity::Warning)
+	}
+
+	/// Iterate over issues of a data row
+	pub fn issues_for_row(&self, row: usize) -> impl Iterator<Item = &ImportIssue> {
+		self.issues.iter().filter(move |i| i.row == Some(row))
+	}
+
+	/// Iterate over issues of a column
+	pub fn issues_for_column<'a>(
+		&'a self,
+		column: &'a str,
+	) -> impl Iterator<Item = &'a ImportIssue> {
+		self.issues
+			.iter()
+			.filter(move |i| i.column.as_deref() == Some(column))
+	}
+
+	/// Check whether a row has an error
+	pub fn has_error_in_row(&self, row: usize) -> bool {
+		self.errors().any(|i| i.row == Some(row))
+	}
+
+	/// Check whether a specific cell has an error
+	pub fn has_error_at(&self, row: usize, column: &str) -> bool {
+		self.errors()
+			.any(|i| i.row == Some(row) && i.column.as_deref() == Some(column))
+	}
+}
+
+/// A single field difference in an object's history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldChangeInfo {
+	/// Field name
+	pub field: String,
+	/// Value before the change
+	pub before: Option<serde_json::Value>,
+	/// Value after the change
+	pub after: Option<serde_json::Value>,
+	/// Human-readable description of the change
+	pub description: String,
+}
+
+/// A single entry of an object's change history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntryResponse {
+	/// Entry identifier, used to revert to this version
+	pub id: u64,
+	/// Kind of change ("create", "update", "delete" or "revert")
+	pub action: String,
+	/// User who performed the change, if known
+	pub user_id: Option<String>,
+	/// Time of the change
+	pub timestamp: chrono::DateTime<chrono::Utc>,
+	/// One-line summary of the change
+	pub summary: String,
+	/// Field-level differences
+	pub changes: Vec<FieldChangeInfo>,
+}
+
+/// Response for the object history endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectHistoryResponse {
+	/// Model name
+	pub model_name: String,
+	/// Primary key of the object
+	pub object_id: String,
+	/// History entries, oldest first
+	pub entries: Vec<HistoryEntryResponse>,
+}
+
+/// Extra navigation link of the admin theme
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NavLinkInfo {
+	/// Link label
+	pub label: String,
+	/// Link target
+	pub url: String,
+}
+
+/// Response for the theme endpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeResponse {
+	/// Brand name shown in the header
+	pub brand_name: String,
+	/// URL of the logo
+	pub logo_url: Option<String>,
+	/// Primary color
+	pub primary_color: Option<String>,
+	/// Extra stylesheet URLs
+	pub extra_css: Vec<String>,
+	/// Extra script URLs
+	pub extra_js: Vec<String>,
+	/// Extra navigation links
+	pub nav_links: Vec<NavLinkInfo>,
+}
@@ -24,6 +24,20 @@ mod wasm_only {
 	/// It exists purely for type checking purposes.
 	pub struct AdminDatabase;
 
+	/// Dummy BackgroundExporter type for WASM type checking
+	///
+	/// This type is never actually used in WASM code, as the `#[server_fn]`
+	/// macro removes all dependency injection parameters from client stubs.
+	/// It exists purely for type checking purposes.
+	pub struct BackgroundExporter;
+
+	/// Dummy RequestUser type for WASM type checking
+	///
+	/// This type is never actually used in WASM code, as the `#[server_fn]`
+	/// macro removes all dependency injection parameters from client stubs.
+	/// It exists purely for type checking purposes.
+	pub struct RequestUser;
+
 	/// Dummy AdminRecord type for WASM type checking
 	///
 	/// This type is never actually used in WASM code.
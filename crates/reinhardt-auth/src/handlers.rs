@@ -35,9 +35,28 @@ pub struct LoginCredentials {
 /// Session cookie name
 pub const SESSION_COOKIE_NAME: &str = "sessionid";
 
+/// Read the session ID from the request's session cookie
+fn extract_session_id(request: &Request) -> Option<SessionId> {
+	request
+		.headers
+		.get("cookie")
+		.and_then(|v| v.to_str().ok())
+		.and_then(|cookies| {
+			cookies.split(';').find_map(|cookie| {
+				let mut parts = cookie.trim().split('=');
+				if parts.next()? == SESSION_COOKIE_NAME {
+					Some(parts.next()?.to_string())
+				} else {
+					None
+				}
+			})
+		})
+}
+
 /// Login handler
 ///
-/// Handles user login with username/password authentication
+/// Handles user login with username/password authentication. The session ID
+/// is rotated on every successful login.
 ///
 /// # Examples
 ///
@@ -80,10 +99,40 @@ impl<S: SessionStore, A: AuthenticationBackend> LoginHandler<S, A> {
 		}
 	}
 
-	async fn perform_login(&self, user: Box<dyn User>) -> Result<(SessionId, String)> {
-		let session_id = self.session_store.create_session_id();
-		let mut session = Session::new();
-		session.set(SESSION_KEY_USER_ID, serde_json::json!(user.id()));
+	/// Store the user in the session and return its ID and cookie
+	///
+	/// An existing session is carried over to a new ID so that a session ID
+	/// known before login cannot be used afterwards (session fixation). If the
+	/// existing session belongs to another user it is discarded instead.
+	async fn perform_login(
+		&self,
+		user: Box<dyn User>,
+		existing: Option<SessionId>,
+	) -> Result<(SessionId, String)> {
+		let user_id = serde_json::json!(user.id());
+		let mut cycled = None;
+		if let Some(old_id) = existing {
+			match self.session_store.load(&old_id).await {
+				Some(session)
+					if session
+						.get(SESSION_KEY_USER_ID)
+						.is_none_or(|id| *id == user_id) =>
+				{
+					cycled = self.session_store.cycle_key(&old_id).await;
+				}
+				Some(_) => self.session_store.delete(&old_id).await,
+				None => {}
+			}
+		}
+
+		let (session_id, mut session) = match cycled {
+			Some(new_id) => {
+				let session = self.session_store.load(&new_id).await.unwrap_or_default();
+				(new_id, session)
+			}
+			None => (self.session_store.create_session_id(), Session::new()),
+		};
+		session.set(SESSION_KEY_USER_ID, user_id);
 
 		self.session_store.save(&session_id, &session).await;
 
@@ -106,7 +155,8 @@ impl<S: SessionStore + 'static, A: AuthenticationBackend + 'static> Handler for
 			.ok()
 			.flatten()
 		{
-			let (_session_id, cookie_str) = self.perform_login(user).await?;
+			let existing = extract_session_id(&request);
+			let (_session_id, cookie_str) = self.perform_login(user, existing).await?;
 
 			Ok(Response::ok()
 				.with_header("Set-Cookie", &cookie_str)
@@ -159,29 +209,12 @@ impl<S: SessionStore> LogoutHandler<S> {
 	pub fn new(session_store: Arc<S>) -> Self {
 		Self { session_store }
 	}
-
-	fn extract_session_id(&self, request: &Request) -> Option<SessionId> {
-		request
-			.headers
-			.get("cookie")
-			.and_then(|v| v.to_str().ok())
-			.and_then(|cookies| {
-				cookies.split(';').find_map(|cookie| {
-					let mut parts = cookie.trim().split('=');
-					if parts.next()? == SESSION_COOKIE_NAME {
-						Some(parts.next()?.to_string())
-					} else {
-						None
-					}
-				})
-			})
-	}
 }
 
 #[async_trait]
 impl<S: SessionStore + 'static> Handler for LogoutHandler<S> {
 	async fn handle(&self, request: Request) -> Result<Response> {
-		if let Some(session_id) = self.extract_session_id(&request) {
+		if let Some(session_id) = extract_session_id(&request) {
 			self.session_store.delete(&session_id).await;
 		}
 
@@ -264,6 +297,97 @@ mod tests {
 		assert!(response.headers.contains_key("set-cookie"));
 	}
 
+	fn login_request(session_id: &str) -> Request {
+		let mut headers = HeaderMap::new();
+		headers.insert(
+			"cookie",
+			format!("{}={}", SESSION_COOKIE_NAME, session_id)
+				.parse()
+				.unwrap(),
+		);
+		Request::builder()
+			.method(Method::POST)
+			.uri("/login")
+			.headers(headers)
+			.body(Bytes::new())
+			.build()
+			.unwrap()
+	}
+
+	fn test_user() -> SimpleUser {
+		SimpleUser {
+			id: Uuid::new_v4(),
+			username: "testuser".to_string(),
+			email: "test@example.com".to_string(),
+			is_active: true,
+			is_admin: false,
+			is_staff: false,
+			is_superuser: false,
+		}
+	}
+
+	#[tokio::test]
+	async fn test_login_handler_cycles_existing_session() {
+		let session_store = Arc::new(InMemorySessionStore::new());
+		let old_id = session_store.create_session_id();
+		let mut session = Session::new();
+		session.set("cart", serde_json::json!(["book"]));
+		session_store.save(&old_id, &session).await;
+		let user = test_user();
+		let auth_backend = Arc::new(TestAuthBackend {
+			test_user: Some(user.clone()),
+		});
+
+		let handler = LoginHandler::new(session_store.clone(), auth_backend);
+		let (new_id, _) = handler
+			.perform_login(Box::new(user.clone()), Some(old_id.clone()))
+			.await
+			.unwrap();
+
+		assert_ne!(new_id, old_id);
+		assert!(session_store.load(&old_id).await.is_none());
+		let session = session_store.load(&new_id).await.unwrap();
+		assert_eq!(session.get("cart"), Some(&serde_json::json!(["book"])));
+		assert_eq!(
+			session.get(SESSION_KEY_USER_ID),
+			Some(&serde_json::json!(user.id.to_string()))
+		);
+
+		let response = handler.handle(login_request(&new_id)).await.unwrap();
+		let cookie = response.headers["set-cookie"].to_str().unwrap();
+		assert!(!cookie.contains(&new_id));
+		assert!(session_store.load(&new_id).await.is_none());
+	}
+
+	#[tokio::test]
+	async fn test_login_handler_discards_other_users_session() {
+		let session_store = Arc::new(InMemorySessionStore::new());
+		let old_id = session_store.create_session_id();
+		let mut session = Session::new();
+		session.set(SESSION_KEY_USER_ID, serde_json::json!("someone-else"));
+		session.set("secret", serde_json::json!("value"));
+		session_store.save(&old_id, &session).await;
+		let auth_backend = Arc::new(TestAuthBackend {
+			test_user: Some(test_user()),
+		});
+
+		let handler = LoginHandler::new(session_store.clone(), auth_backend);
+		let (new_id, _) = handler
+			.perform_login(Box::new(test_user()), Some(old_id.clone()))
+			.await
+			.unwrap();
+
+		assert!(session_store.load(&old_id).await.is_none());
+		assert!(
+			session_store
+				.load(&new_id)
+				.await
+				.unwrap()
+				.get("secret")
+				.is_none()
+		);
+	}
+
 	#[tokio::test]
 	async fn test_login_handler_failure() {
 		let session_store = Arc::new(InMemorySessionStore::new());
@@ -152,6 +152,20 @@ pub trait SessionStore: Send + Sync {
 	fn create_session_id(&self) -> SessionId {
 		Uuid::new_v4().to_string()
 	}
+
+	/// Move a session's data to a freshly generated ID
+	///
+	/// Returns the new ID, or `None` if no session exists under `session_id`.
+	/// The data is saved under the new ID before the old one is deleted, so the
+	/// session is never unreachable. Stores that can swap keys in a single step
+	/// should override this.
+	async fn cycle_key(&self, session_id: &SessionId) -> Option<SessionId> {
+		let session = self.load(session_id).await?;
+		let new_id = self.create_session_id();
+		self.save(&new_id, &session).await;
+		self.delete(session_id).await;
+		Some(new_id)
+	}
 }
 
 /// In-memory session store for testing and development
@@ -213,6 +227,14 @@ impl SessionStore for InMemorySessionStore {
 		let mut sessions = self.sessions.lock().unwrap();
 		sessions.remove(session_id);
 	}
+
+	async fn cycle_key(&self, session_id: &SessionId) -> Option<SessionId> {
+		let new_id = self.create_session_id();
+		let mut sessions = self.sessions.lock().unwrap();
+		let session = sessions.remove(session_id)?;
+		sessions.insert(new_id.clone(), session);
+		Some(new_id)
+	}
 }
 
 /// Session key constant for storing user ID
@@ -282,4 +304,23 @@ mod tests {
 		assert!(!id1.is_empty());
 		assert!(!id2.is_empty());
 	}
+
+	#[tokio::test]
+	async fn test_session_store_cycle_key() {
+		let store = InMemorySessionStore::new();
+		let old_id = store.create_session_id();
+		let mut session = Session::new();
+		session.set("cart", serde_json::json!([1, 2]));
+		store.save(&old_id, &session).await;
+
+		let new_id = store.cycle_key(&old_id).await.unwrap();
+
+		assert_ne!(new_id, old_id);
+		assert!(store.load(&old_id).await.is_none());
+		assert_eq!(
+			store.load(&new_id).await.unwrap().get("cart"),
+			Some(&serde_json::json!([1, 2]))
+		);
+		assert!(store.cycle_key(&old_id).await.is_none());
+	}
 }
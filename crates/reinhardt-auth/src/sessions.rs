@@ -116,4 +116,4 @@ pub use session::Session;
 pub use tenant::{TenantConfig, TenantSessionBackend, TenantSessionOperations};
 
 #[cfg(feature = "middleware")]
-pub use middleware::{HttpSessionConfig, SameSite, SessionMiddleware};
+pub use middleware::{HttpSessionConfig, LazySession, SameSite, SessionMiddleware};
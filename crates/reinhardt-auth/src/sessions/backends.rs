@@ -73,7 +73,7 @@ pub use database::DatabaseSessionBackend;
 pub use file::FileSessionBackend;
 
 #[cfg(feature = "cookie")]
-pub use cookie::CookieSessionBackend;
+pub use cookie::{CookieSessionBackend, CookieSessionError};
 
 #[cfg(feature = "jwt")]
 pub use jwt::{JwtConfig, JwtSessionBackend};
@@ -5,11 +5,41 @@
 //!
 //! ## Features
 //!
-//! - **AES-GCM encryption**: Session data is encrypted using AES-256-GCM
-//! - **HMAC signing**: Encrypted data is signed to prevent tampering
-//! - **Size limitation**: Handles cookie size limits (max 4096 bytes)
+//! - **AES-GCM encryption**: Session data is encrypted using AES-256-GCM (optional)
+//! - **HMAC signing**: Session data is signed with HMAC-SHA256 to prevent tampering
+//! - **Key rotation**: Cookies signed or encrypted with fallback keys are still accepted
+//! - **Size limitation**: Handles cookie size limits (4096 bytes by default)
 //! - **Secure**: Protects against tampering and eavesdropping
 //!
+//! ## Cookie Format
+//!
+//! Cookie values are URL-safe base64 (without padding) of
+//! `version || payload || signature`, where `version` is `1` for signed-only
+//! payloads (`payload` is the JSON data) and `2` for encrypted payloads
+//! (`payload` is `nonce || ciphertext`). The HMAC-SHA256 signature covers the
+//! version byte and the payload.
+//!
+//! ## Key Rotation
+//!
+//! New cookies are always signed with the first signing secret and encrypted
+//! with the first encryption key. To rotate keys, construct the backend with
+//! the new keys and register the previous ones as fallbacks; cookies issued
+//! with the old keys keep working until they are rewritten.
+//!
+//! ```rust
+//! use reinhardt_auth::sessions::backends::CookieSessionBackend;
+//! use serde_json::json;
+//!
+//! let old = CookieSessionBackend::new(b"old_encryption_key_32_bytes_long", b"old_secret");
+//! let cookie = old.encode(&json!({"user_id": 42})).unwrap();
+//!
+//! let rotated = CookieSessionBackend::new(b"new_encryption_key_32_bytes_long", b"new_secret")
+//!     .with_fallback_encryption_key(b"old_encryption_key_32_bytes_long")
+//!     .with_fallback_signing_secret(b"old_secret");
+//! let data: serde_json::Value = rotated.decode(&cookie).unwrap();
+//! assert_eq!(data["user_id"], 42);
+//! ```
+//!
 //! ## Example
 //!
 //! ```rust
@@ -39,12 +69,13 @@ use aes_gcm::{
 	aead::{Aead, KeyInit, OsRng, rand_core::RngCore},
 };
 use async_trait::async_trait;
-use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD as BASE64};
 use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
 use std::collections::HashMap;
 use std::sync::Arc;
+use thiserror::Error;
 
 type HmacSha256 = Hmac<Sha256>;
 
@@ -54,9 +85,41 @@ const MAX_COOKIE_SIZE: usize = 4096;
 /// Nonce size for AES-GCM (96 bits / 12 bytes)
 const NONCE_SIZE: usize = 12;
 
+/// HMAC-SHA256 signature size
+const SIGNATURE_SIZE: usize = 32;
+
+/// Format version of signed-only cookie values
+const VERSION_SIGNED: u8 = 1;
+
+/// Format version of encrypted and signed cookie values
+const VERSION_ENCRYPTED: u8 = 2;
+
+/// Cookie-specific session errors
+#[derive(Debug, Error)]
+pub enum CookieSessionError {
+	#[error("Serialization failed: {0}")]
+	SerializationError(String),
+	#[error("Encryption failed: {0}")]
+	EncryptionError(String),
+	#[error("Decryption failed")]
+	DecryptionError,
+	#[error("Signature verification failed")]
+	InvalidSignature,
+	#[error("Invalid cookie data: {0}")]
+	MalformedCookie(String),
+	#[error("Session data too large: {size} bytes (max {max} bytes)")]
+	TooLarge { size: usize, max: usize },
+}
+
+impl From<CookieSessionError> for SessionError {
+	fn from(e: CookieSessionError) -> Self {
+		SessionError::SerializationError(e.to_string())
+	}
+}
+
 /// Cookie session backend configuration
 ///
-/// This backend encrypts and signs session data for storage in cookies.
+/// This backend signs, and optionally encrypts, session data for storage in cookies.
 ///
 /// ## Example
 ///
@@ -79,8 +142,11 @@ const NONCE_SIZE: usize = 12;
 /// ```
 #[derive(Clone)]
 pub struct CookieSessionBackend {
-	cipher: Arc<Aes256Gcm>,
-	signing_secret: Arc<Vec<u8>>,
+	/// Ciphers, current key first (empty for signed-only cookies)
+	ciphers: Arc<Vec<Aes256Gcm>>,
+	/// Signing secrets, current secret first
+	signing_secrets: Arc<Vec<Vec<u8>>>,
+	max_size: usize,
 	// In-memory storage for demonstration (maps session_key -> encrypted_data)
 	storage: Arc<tokio::sync::RwLock<HashMap<String, String>>>,
 }
@@ -103,100 +169,227 @@ impl CookieSessionBackend {
 	/// let backend = CookieSessionBackend::new(encryption_key, signing_secret);
 	/// ```
 	pub fn new(encryption_key: &[u8; 32], signing_secret: &[u8]) -> Self {
-		let cipher = Aes256Gcm::new(encryption_key.into());
 		Self {
-			cipher: Arc::new(cipher),
-			signing_secret: Arc::new(signing_secret.to_vec()),
+			ciphers: Arc::new(vec![Aes256Gcm::new(encryption_key.into())]),
+			..Self::signed(signing_secret)
+		}
+	}
+
+	/// Create a cookie session backend that signs but does not encrypt
+	///
+	/// Session data is readable by the client but cannot be modified.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_auth::sessions::backends::CookieSessionBackend;
+	/// use serde_json::json;
+	///
+	/// let backend = CookieSessionBackend::signed(b"signing_secret");
+	/// let cookie = backend.encode(&json!({"theme": "dark"})).unwrap();
+	///
+	/// let data: serde_json::Value = backend.decode(&cookie).unwrap();
+	/// assert_eq!(data["theme"], "dark");
+	/// ```
+	pub fn signed(signing_secret: &[u8]) -> Self {
+		Self {
+			ciphers: Arc::new(Vec::new()),
+			signing_secrets: Arc::new(vec![signing_secret.to_vec()]),
+			max_size: MAX_COOKIE_SIZE,
 			storage: Arc::new(tokio::sync::RwLock::new(HashMap::new())),
 		}
 	}
 
-	/// Encrypt and sign session data
+	/// Accept cookies signed with a previous signing secret
 	///
-	/// Returns base64-encoded string: `base64(nonce || encrypted_data || signature)`
-	fn encrypt_and_sign(&self, data: &[u8]) -> Result<String, SessionError> {
-		// Generate random nonce
-		let mut nonce_bytes = [0u8; NONCE_SIZE];
-		OsRng.fill_bytes(&mut nonce_bytes);
-		let nonce = Nonce::from(nonce_bytes);
-
-		// Encrypt data
-		let encrypted = self
-			.cipher
-			.encrypt(&nonce, data)
-			.map_err(|e| SessionError::SerializationError(format!("Encryption failed: {}", e)))?;
-
-		// Combine nonce and encrypted data
-		let mut combined = Vec::with_capacity(NONCE_SIZE + encrypted.len());
-		combined.extend_from_slice(&nonce_bytes);
-		combined.extend_from_slice(&encrypted);
-
-		// Sign the combined data
-		let signature = self.sign(&combined)?;
+	/// Fallback secrets are only used for verification, in the order they were added.
+	pub fn with_fallback_signing_secret(mut self, signing_secret: &[u8]) -> Self {
+		Arc::make_mut(&mut self.signing_secrets).push(signing_secret.to_vec());
+		self
+	}
 
-		// Append signature
-		combined.extend_from_slice(&signature);
+	/// Accept cookies encrypted with a previous encryption key
+	///
+	/// Fallback keys are only used for decryption, in the order they were added.
+	/// Has no effect on a signed-only backend.
+	pub fn with_fallback_encryption_key(mut self, encryption_key: &[u8; 32]) -> Self {
+		if !self.ciphers.is_empty() {
+			Arc::make_mut(&mut self.ciphers).push(Aes256Gcm::new(encryption_key.into()));
+		}
+		self
+	}
 
-		// Encode as base64
-		Ok(BASE64.encode(&combined))
+	/// Set the maximum size of encoded cookie values in bytes
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_auth::sessions::backends::CookieSessionBackend;
+	/// use serde_json::json;
+	///
+	/// let backend = CookieSessionBackend::signed(b"signing_secret").with_max_size(64);
+	/// assert_eq!(backend.max_size(), 64);
+	/// assert!(backend.encode(&json!({"data": "x".repeat(64)})).is_err());
+	/// ```
+	pub fn with_max_size(mut self, max_size: usize) -> Self {
+		self.max_size = max_size;
+		self
+	}
+
+	/// Get the maximum size of encoded cookie values in bytes
+	pub fn max_size(&self) -> usize {
+		self.max_size
+	}
+
+	/// Check whether session data is encrypted
+	pub fn is_encrypted(&self) -> bool {
+		!self.ciphers.is_empty()
 	}
 
-	/// Verify signature and decrypt session data
+	/// Serialize, sign and (if configured) encrypt session data into a cookie value
+	pub fn encode<T: Serialize>(&self, data: &T) -> Result<String, CookieSessionError> {
+		let json_bytes = serde_json::to_vec(data)
+			.map_err(|e| CookieSessionError::SerializationError(e.to_string()))?;
+		let encoded = self.encrypt_and_sign(&json_bytes)?;
+
+		if encoded.len() > self.max_size {
+			return Err(CookieSessionError::TooLarge {
+				size: encoded.len(),
+				max: self.max_size,
+			});
+		}
+		Ok(encoded)
+	}
+
+	/// Verify, decrypt and deserialize a cookie value
 	///
-	/// Input format: `base64(nonce || encrypted_data || signature)`
-	fn verify_and_decrypt(&self, encoded: &str) -> Result<Vec<u8>, SessionError> {
-		// Decode base64
-		let combined = BASE64.decode(encoded).map_err(|e| {
-			SessionError::SerializationError(format!("Base64 decode failed: {}", e))
-		})?;
-
-		// Check minimum size (nonce + encrypted_data + signature)
-		if combined.len() < NONCE_SIZE + 32 {
-			return Err(SessionError::SerializationError(
-				"Invalid cookie data: too short".to_string(),
-			));
+	/// Rejects values that are oversized, malformed, signed with an unknown
+	/// secret, or not encrypted although this backend encrypts.
+	pub fn decode<T>(&self, value: &str) -> Result<T, CookieSessionError>
+	where
+		T: for<'de> Deserialize<'de>,
+	{
+		if value.len() > self.max_size {
+			return Err(CookieSessionError::TooLarge {
+				size: value.len(),
+				max: self.max_size,
+			});
 		}
+		let decrypted = self.verify_and_decrypt(value)?;
+		serde_json::from_slice(&decrypted)
+			.map_err(|e| CookieSessionError::SerializationError(e.to_string()))
+	}
+
+	/// Get the cookie value stored for a session key
+	pub async fn cookie_value(&self, session_key: &str) -> Option<String> {
+		self.storage.read().await.get(session_key).cloned()
+	}
+
+	/// Sign and (if configured) encrypt data
+	///
+	/// Returns base64-encoded string: `base64(version || payload || signature)`
+	fn encrypt_and_sign(&self, data: &[u8]) -> Result<String, CookieSessionError> {
+		let mut combined = match self.ciphers.first() {
+			Some(cipher) => {
+				// Generate random nonce
+				let mut nonce_bytes = [0u8; NONCE_SIZE];
+				OsRng.fill_bytes(&mut nonce_bytes);
+				let nonce = Nonce::from(nonce_bytes);
+
+				// Encrypt data
+				let encrypted = cipher
+					.encrypt(&nonce, data)
+					.map_err(|e| CookieSessionError::EncryptionError(e.to_string()))?;
+
+				let mut combined =
+					Vec::with_capacity(1 + NONCE_SIZE + encrypted.len() + SIGNATURE_SIZE);
+				combined.push(VERSION_ENCRYPTED);
+				combined.extend_from_slice(&nonce_bytes);
+				combined.extend_from_slice(&encrypted);
+				combined
+			}
+			None => {
+				let mut combined = Vec::with_capacity(1 + data.len() + SIGNATURE_SIZE);
+				combined.push(VERSION_SIGNED);
+				combined.extend_from_slice(data);
+				combined
+			}
+		};
 
-		// Split signature from the rest
-		let (data_with_nonce, signature) = combined.split_at(combined.len() - 32);
+		// Sign the version and payload, then append the signature
+		let signature = self.sign(&combined)?;
+		combined.extend_from_slice(&signature);
 
-		// Verify signature
-		self.verify_signature(data_with_nonce, signature)?;
+		Ok(BASE64.encode(&combined))
+	}
 
-		// Extract nonce and encrypted data
-		let (nonce_bytes, encrypted) = data_with_nonce.split_at(NONCE_SIZE);
-		// SAFETY: We checked the length above, nonce_bytes is exactly NONCE_SIZE bytes
-		let nonce_array: [u8; NONCE_SIZE] = nonce_bytes
-			.try_into()
-			.map_err(|_| SessionError::SerializationError("Invalid nonce size".to_string()))?;
-		let nonce = Nonce::from(nonce_array);
+	/// Verify signature and decrypt data
+	///
+	/// Input format: `base64(version || payload || signature)`
+	fn verify_and_decrypt(&self, encoded: &str) -> Result<Vec<u8>, CookieSessionError> {
+		let combined = BASE64
+			.decode(encoded)
+			.map_err(|e| CookieSessionError::MalformedCookie(e.to_string()))?;
+
+		// Check minimum size (version + signature)
+		if combined.len() < 1 + SIGNATURE_SIZE {
+			return Err(CookieSessionError::MalformedCookie("too short".to_string()));
+		}
 
-		// Decrypt data
-		let decrypted = self
-			.cipher
-			.decrypt(&nonce, encrypted)
-			.map_err(|e| SessionError::SerializationError(format!("Decryption failed: {}", e)))?;
+		// Split signature from the rest and verify it before looking at the payload
+		let (signed, signature) = combined.split_at(combined.len() - SIGNATURE_SIZE);
+		self.verify_signature(signed, signature)?;
 
-		Ok(decrypted)
+		let (version, payload) = signed.split_first().expect("length checked above");
+		match (*version, self.ciphers.is_empty()) {
+			(VERSION_SIGNED, true) => Ok(payload.to_vec()),
+			(VERSION_ENCRYPTED, false) => {
+				if payload.len() < NONCE_SIZE {
+					return Err(CookieSessionError::MalformedCookie("too short".to_string()));
+				}
+				let (nonce_bytes, encrypted) = payload.split_at(NONCE_SIZE);
+				let nonce_array: [u8; NONCE_SIZE] = nonce_bytes.try_into().map_err(|_| {
+					CookieSessionError::MalformedCookie("invalid nonce".to_string())
+				})?;
+				let nonce = Nonce::from(nonce_array);
+
+				self.ciphers
+					.iter()
+					.find_map(|cipher| cipher.decrypt(&nonce, encrypted).ok())
+					.ok_or(CookieSessionError::DecryptionError)
+			}
+			(VERSION_SIGNED, false) => Err(CookieSessionError::MalformedCookie(
+				"unencrypted session data".to_string(),
+			)),
+			(version, _) => Err(CookieSessionError::MalformedCookie(format!(
+				"unsupported version {}",
+				version
+			))),
+		}
 	}
 
-	/// Sign data using HMAC-SHA256
-	fn sign(&self, data: &[u8]) -> Result<Vec<u8>, SessionError> {
-		let mut mac = <HmacSha256 as hmac::Mac>::new_from_slice(&self.signing_secret)
-			.map_err(|e| SessionError::SerializationError(format!("HMAC init failed: {}", e)))?;
+	/// Sign data using HMAC-SHA256 with the current signing secret
+	fn sign(&self, data: &[u8]) -> Result<Vec<u8>, CookieSessionError> {
+		let mut mac = Self::mac(&self.signing_secrets[0])?;
 		mac.update(data);
 		Ok(mac.finalize().into_bytes().to_vec())
 	}
 
-	/// Verify HMAC-SHA256 signature
-	fn verify_signature(&self, data: &[u8], signature: &[u8]) -> Result<(), SessionError> {
-		let mut mac = <HmacSha256 as hmac::Mac>::new_from_slice(&self.signing_secret)
-			.map_err(|e| SessionError::SerializationError(format!("HMAC init failed: {}", e)))?;
-		mac.update(data);
-		mac.verify_slice(signature).map_err(|_| {
-			SessionError::SerializationError("Signature verification failed".to_string())
-		})?;
-		Ok(())
+	/// Verify HMAC-SHA256 signature against the current and fallback secrets
+	fn verify_signature(&self, data: &[u8], signature: &[u8]) -> Result<(), CookieSessionError> {
+		for secret in self.signing_secrets.iter() {
+			let mut mac = Self::mac(secret)?;
+			mac.update(data);
+			if mac.verify_slice(signature).is_ok() {
+				return Ok(());
+			}
+		}
+		Err(CookieSessionError::InvalidSignature)
+	}
+
+	fn mac(secret: &[u8]) -> Result<HmacSha256, CookieSessionError> {
+		<HmacSha256 as hmac::Mac>::new_from_slice(secret)
+			.map_err(|e| CookieSessionError::EncryptionError(format!("HMAC init failed: {}", e)))
 	}
 }
 
@@ -212,15 +405,7 @@ impl SessionBackend for CookieSessionBackend {
 			None => return Ok(None),
 		};
 
-		// Verify and decrypt
-		let decrypted = self.verify_and_decrypt(encoded)?;
-
-		// Deserialize
-		let data: T = serde_json::from_slice(&decrypted).map_err(|e| {
-			SessionError::SerializationError(format!("Deserialization failed: {}", e))
-		})?;
-
-		Ok(Some(data))
+		Ok(Some(self.decode(encoded)?))
 	}
 
 	async fn save<T>(
@@ -232,22 +417,7 @@ impl SessionBackend for CookieSessionBackend {
 	where
 		T: Serialize + Send + Sync,
 	{
-		// Serialize data
-		let json_bytes = serde_json::to_vec(data).map_err(|e| {
-			SessionError::SerializationError(format!("Serialization failed: {}", e))
-		})?;
-
-		// Encrypt and sign
-		let encoded = self.encrypt_and_sign(&json_bytes)?;
-
-		// Check size limitation
-		if encoded.len() > MAX_COOKIE_SIZE {
-			return Err(SessionError::SerializationError(format!(
-				"Session data too large: {} bytes (max {} bytes)",
-				encoded.len(),
-				MAX_COOKIE_SIZE
-			)));
-		}
+		let encoded = self.encode(data)?;
 
 		// Store in memory (HTTP cookie handling via middleware layer)
 		let mut storage = self.storage.write().await;
@@ -459,4 +629,98 @@ mod tests {
 		assert_eq!(loaded_data["user"]["roles"][0], "admin");
 		assert_eq!(loaded_data["settings"]["theme"], "dark");
 	}
+
+	#[test]
+	fn test_signed_only_round_trip_and_tampering() {
+		let backend = CookieSessionBackend::signed(TEST_SIGNING_SECRET);
+		let cookie = backend.encode(&json!({"user_id": 1})).unwrap();
+
+		// Payload is readable but not encrypted
+		let raw = BASE64.decode(&cookie).unwrap();
+		assert_eq!(raw[0], VERSION_SIGNED);
+		assert!(String::from_utf8_lossy(&raw).contains("user_id"));
+
+		let data: serde_json::Value = backend.decode(&cookie).unwrap();
+		assert_eq!(data["user_id"], 1);
+
+		let forged = raw[..raw.len() - SIGNATURE_SIZE]
+			.iter()
+			.map(|&b| if b == b'1' { b'2' } else { b })
+			.chain(raw[raw.len() - SIGNATURE_SIZE..].iter().copied())
+			.collect::<Vec<_>>();
+		let result = backend.decode::<serde_json::Value>(&BASE64.encode(forged));
+		assert!(matches!(result, Err(CookieSessionError::InvalidSignature)));
+	}
+
+	#[test]
+	fn test_key_rotation_accepts_fallback_keys() {
+		let old_key = b"old_encryption_key_32_bytes_long";
+		let new_key = b"new_encryption_key_32_bytes_long";
+		let old = CookieSessionBackend::new(old_key, b"old_secret");
+		let old_cookie = old.encode(&json!({"user_id": 7})).unwrap();
+
+		let rotated = CookieSessionBackend::new(new_key, b"new_secret")
+			.with_fallback_encryption_key(old_key)
+			.with_fallback_signing_secret(b"old_secret");
+		let data: serde_json::Value = rotated.decode(&old_cookie).unwrap();
+		assert_eq!(data["user_id"], 7);
+
+		// New cookies use the current keys only
+		let new_cookie = rotated.encode(&json!({"user_id": 7})).unwrap();
+		assert!(old.decode::<serde_json::Value>(&new_cookie).is_err());
+
+		let without_fallback = CookieSessionBackend::new(new_key, b"new_secret");
+		let result = without_fallback.decode::<serde_json::Value>(&old_cookie);
+		assert!(matches!(result, Err(CookieSessionError::InvalidSignature)));
+	}
+
+	#[test]
+	fn test_encrypted_backend_rejects_signed_only_cookie() {
+		let signed = CookieSessionBackend::signed(TEST_SIGNING_SECRET);
+		let encrypted = CookieSessionBackend::new(TEST_ENCRYPTION_KEY, TEST_SIGNING_SECRET);
+		let cookie = signed.encode(&json!({"user_id": 1})).unwrap();
+
+		let result = encrypted.decode::<serde_json::Value>(&cookie);
+
+		assert!(matches!(
+			result,
+			Err(CookieSessionError::MalformedCookie(_))
+		));
+	}
+
+	#[rstest::rstest]
+	#[case("")]
+	#[case("not base64!")]
+	#[case("AQ")]
+	fn test_malformed_cookie_rejected(#[case] value: &str) {
+		let backend = CookieSessionBackend::new(TEST_ENCRYPTION_KEY, TEST_SIGNING_SECRET);
+
+		let result = backend.decode::<serde_json::Value>(value);
+
+		assert!(matches!(
+			result,
+			Err(CookieSessionError::MalformedCookie(_))
+		));
+	}
+
+	#[test]
+	fn test_configurable_size_limit() {
+		let backend =
+			CookieSessionBackend::new(TEST_ENCRYPTION_KEY, TEST_SIGNING_SECRET).with_max_size(128);
+		let small = backend.encode(&json!({"a": 1})).unwrap();
+		assert!(small.len() <= 128);
+
+		let result = backend.encode(&json!({"a": "x".repeat(128)}));
+		assert!(matches!(
+			result,
+			Err(CookieSessionError::TooLarge { max: 128, .. })
+		));
+
+		// Oversized input is rejected before it is decoded
+		let result = backend.decode::<serde_json::Value>(&"A".repeat(129));
+		assert!(matches!(
+			result,
+			Err(CookieSessionError::TooLarge { size: 129, .. })
+		));
+	}
 }
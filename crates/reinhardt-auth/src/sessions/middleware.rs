@@ -22,6 +22,7 @@
 //!     httponly: true,
 //!     samesite: SameSite::Lax,
 //!     max_age: Some(Duration::from_secs(3600)),
+//!     save_every_request: false,
 //! };
 //!
 //! // Create middleware
@@ -31,6 +32,8 @@
 #[cfg(feature = "middleware")]
 use super::backends::SessionBackend;
 #[cfg(feature = "middleware")]
+use super::backends::SessionError;
+#[cfg(feature = "middleware")]
 use super::session::Session;
 #[cfg(feature = "middleware")]
 use async_trait::async_trait;
@@ -45,7 +48,7 @@ use std::sync::Arc;
 #[cfg(feature = "middleware")]
 use std::time::Duration;
 #[cfg(feature = "middleware")]
-use tokio::sync::RwLock;
+use tokio::sync::{OnceCell, RwLock};
 
 #[cfg(feature = "middleware")]
 /// SameSite cookie attribute
@@ -112,6 +115,7 @@ impl SameSite {
 ///     httponly: true,
 ///     samesite: SameSite::Strict,
 ///     max_age: Some(Duration::from_secs(7200)),
+///     save_every_request: false,
 /// };
 /// ```
 #[derive(Debug, Clone)]
@@ -128,8 +132,10 @@ pub struct HttpSessionConfig {
 	pub httponly: bool,
 	/// SameSite attribute
 	pub samesite: SameSite,
-	/// Maximum age for the cookie
+	/// Maximum age for the cookie (None = browser-session cookie)
 	pub max_age: Option<Duration>,
+	/// Save the session on every request instead of only when modified
+	pub save_every_request: bool,
 }
 
 #[cfg(feature = "middleware")]
@@ -155,14 +161,89 @@ impl Default for HttpSessionConfig {
 			httponly: true,
 			samesite: SameSite::Lax,
 			max_age: None,
+			save_every_request: false,
 		}
 	}
 }
 
+#[cfg(feature = "middleware")]
+/// Request-scoped session that is loaded on first access
+///
+/// [`SessionMiddleware`] stores an `Arc<LazySession<B>>` in the request
+/// extensions. Requests whose handlers never touch the session do not hit
+/// the backend at all.
+///
+/// ## Example
+///
+/// ```rust
+/// use reinhardt_auth::sessions::backends::InMemorySessionBackend;
+/// use reinhardt_auth::sessions::middleware::LazySession;
+///
+/// # tokio_test::block_on(async {
+/// let lazy = LazySession::new(InMemorySessionBackend::new(), None);
+/// assert!(!lazy.is_loaded());
+///
+/// let session = lazy.get().await.unwrap();
+/// session.write().await.set("user_id", 42).unwrap();
+/// assert!(lazy.is_loaded());
+/// # });
+/// ```
+pub struct LazySession<B: SessionBackend> {
+	backend: B,
+	session_key: Option<String>,
+	session: OnceCell<Arc<RwLock<Session<B>>>>,
+}
+
+#[cfg(feature = "middleware")]
+impl<B: SessionBackend> LazySession<B> {
+	/// Create a lazy session for the key sent by the client, if any
+	pub fn new(backend: B, session_key: Option<String>) -> Self {
+		Self {
+			backend,
+			session_key,
+			session: OnceCell::new(),
+		}
+	}
+
+	/// Session key sent by the client
+	pub fn session_key(&self) -> Option<&str> {
+		self.session_key.as_deref()
+	}
+
+	/// Whether the session has been loaded yet
+	pub fn is_loaded(&self) -> bool {
+		self.session.initialized()
+	}
+
+	/// Get the session, loading it from the backend on first access
+	///
+	/// A key unknown to the backend starts a new session under a fresh key
+	/// rather than adopting the client-supplied one.
+	pub async fn get(&self) -> std::result::Result<Arc<RwLock<Session<B>>>, SessionError> {
+		self.session
+			.get_or_try_init(|| async {
+				let session = match &self.session_key {
+					Some(key) if self.backend.exists(key).await? => {
+						Session::from_key(self.backend.clone(), key.clone()).await?
+					}
+					_ => Session::new(self.backend.clone()),
+				};
+				Ok::<_, SessionError>(Arc::new(RwLock::new(session)))
+			})
+			.await
+			.cloned()
+	}
+}
+
 #[cfg(feature = "middleware")]
 /// Session middleware
 ///
-/// Automatically loads sessions from cookies on request and saves them on response.
+/// Makes a [`LazySession`] available to handlers through the request
+/// extensions. The session is only read from the backend when a handler
+/// accesses it, and only written back when it was modified, or on every
+/// request that has a session when
+/// [`save_every_request`](HttpSessionConfig::save_every_request) is set. A
+/// session left empty is deleted and its cookie cleared.
 ///
 /// ## Example
 ///
@@ -219,7 +300,16 @@ impl<B: SessionBackend> SessionMiddleware<B> {
 
 	/// Build Set-Cookie header value
 	fn build_set_cookie_header(&self, session_key: &str) -> String {
-		let mut cookie = format!("{}={}", self.config.cookie_name, session_key);
+		self.build_cookie_header(session_key, self.config.max_age.map(|age| age.as_secs()))
+	}
+
+	/// Build Set-Cookie header value that removes the session cookie
+	fn build_delete_cookie_header(&self) -> String {
+		self.build_cookie_header("", Some(0))
+	}
+
+	fn build_cookie_header(&self, value: &str, max_age: Option<u64>) -> String {
+		let mut cookie = format!("{}={}", self.config.cookie_name, value);
 
 		cookie.push_str(&format!("; Path={}", self.config.cookie_path));
 
@@ -227,11 +317,12 @@ impl<B: SessionBackend> SessionMiddleware<B> {
 			cookie.push_str(&format!("; Domain={}", domain));
 		}
 
-		if let Some(max_age) = self.config.max_age {
-			cookie.push_str(&format!("; Max-Age={}", max_age.as_secs()));
+		if let Some(max_age) = max_age {
+			cookie.push_str(&format!("; Max-Age={}", max_age));
 		}
 
-		if self.config.secure {
+		// Browsers reject SameSite=None cookies that are not also Secure
+		if self.config.secure || self.config.samesite == SameSite::None {
 			cookie.push_str("; Secure");
 		}
 
@@ -245,49 +336,51 @@ impl<B: SessionBackend> SessionMiddleware<B> {
 	}
 }
 
+#[cfg(feature = "middleware")]
+fn internal_error(e: SessionError) -> reinhardt_core::exception::Error {
+	reinhardt_core::exception::Error::Internal(format!("Failed to save session: {}", e))
+}
+
 #[cfg(feature = "middleware")]
 #[async_trait]
 impl<B: SessionBackend + 'static> Middleware for SessionMiddleware<B> {
 	async fn process(&self, request: Request, next: Arc<dyn Handler>) -> Result<Response> {
-		// Load session from cookie
-		let session_key = self.get_session_key_from_cookie(&request);
-
-		let session: Session<B> = if let Some(key) = session_key {
-			Session::from_key(self.backend.clone(), key)
-				.await
-				.unwrap_or_else(|_| Session::new(self.backend.clone()))
-		} else {
-			Session::new(self.backend.clone())
-		};
+		let lazy = Arc::new(LazySession::new(
+			self.backend.clone(),
+			self.get_session_key_from_cookie(&request),
+		));
+		request.extensions.insert(lazy.clone());
 
-		// Store session in request extensions wrapped in Arc<RwLock> for shared access
-		let shared_session = Arc::new(RwLock::new(session));
-		request.extensions.insert(shared_session.clone());
-
-		// Process the request
 		let mut response = next.handle(request).await?;
 
-		// Save session if modified
-		// Acquire read lock to check if modified
-		let is_modified = {
-			let session_read = shared_session.read().await;
-			session_read.is_modified()
-		};
-
-		if is_modified {
-			// Acquire write lock to save
-			let mut session_mut = shared_session.write().await;
-			session_mut.save().await.map_err(|e| {
-				reinhardt_core::exception::Error::Internal(format!("Failed to save session: {}", e))
-			})?;
+		let save_every_request = self.config.save_every_request && lazy.session_key().is_some();
+		if !lazy.is_loaded() && !save_every_request {
+			return Ok(response);
+		}
 
-			// Add Set-Cookie header
-			let session_key_str = session_mut.get_or_create_key();
-			let cookie_value = self.build_set_cookie_header(session_key_str);
+		let shared_session = lazy.get().await.map_err(internal_error)?;
+		let mut session = shared_session.write().await;
+		if !session.is_modified() && !save_every_request {
+			return Ok(response);
+		}
 
-			response = response.with_header("Set-Cookie", &cookie_value);
+		if session.is_empty() {
+			// Don't store empty sessions; drop the client's cookie instead
+			if let Some(key) = session.session_key() {
+				self.backend.delete(key).await.map_err(internal_error)?;
+			}
+			if lazy.session_key().is_some() {
+				response = response.with_header("Set-Cookie", &self.build_delete_cookie_header());
+			}
+			return Ok(response);
 		}
 
+		session.mark_modified();
+		session.save().await.map_err(internal_error)?;
+
+		let cookie_value = self.build_set_cookie_header(session.get_or_create_key());
+		response = response.with_header("Set-Cookie", &cookie_value);
+
 		Ok(response)
 	}
 }
@@ -316,12 +409,13 @@ mod tests {
 	#[async_trait]
 	impl Handler for SessionModifyingHandler {
 		async fn handle(&self, request: Request) -> Result<Response> {
-			// Get the shared session from extensions
-			if let Some(shared_session) = request
+			// Get the lazy session from extensions
+			if let Some(lazy) = request
 				.extensions
-				.get::<Arc<RwLock<Session<InMemorySessionBackend>>>>()
+				.get::<Arc<LazySession<InMemorySessionBackend>>>()
 			{
 				// Acquire write lock to modify the session
+				let shared_session = lazy.get().await.unwrap();
 				let mut session = shared_session.write().await;
 				session.set("user_id", 42).unwrap();
 				// Lock is automatically released when session goes out of scope
@@ -369,6 +463,7 @@ mod tests {
 		assert!(config.httponly);
 		assert_eq!(config.samesite, SameSite::Lax);
 		assert!(config.max_age.is_none());
+		assert!(!config.save_every_request);
 	}
 
 	#[tokio::test]
@@ -410,6 +505,7 @@ mod tests {
 			httponly: true,
 			samesite: SameSite::Strict,
 			max_age: Some(Duration::from_secs(3600)),
+			save_every_request: false,
 		};
 		let middleware = SessionMiddleware::new(backend, config);
 
@@ -472,4 +568,111 @@ mod tests {
 		// Session should be loaded (we can't easily verify this without extracting it)
 		// But at minimum, the middleware should not fail
 	}
+
+	// Handler that clears the session
+	struct SessionClearingHandler;
+
+	#[async_trait]
+	impl Handler for SessionClearingHandler {
+		async fn handle(&self, request: Request) -> Result<Response> {
+			let lazy = request
+				.extensions
+				.get::<Arc<LazySession<InMemorySessionBackend>>>()
+				.unwrap();
+			lazy.get().await.unwrap().write().await.clear();
+			Ok(Response::new(StatusCode::OK))
+		}
+	}
+
+	async fn stored_session(backend: &InMemorySessionBackend) -> String {
+		let mut session = Session::new(backend.clone());
+		session.set("existing_data", "test_value").unwrap();
+		session.save().await.unwrap();
+		session.session_key().unwrap().to_string()
+	}
+
+	#[tokio::test]
+	async fn test_middleware_does_not_save_unmodified_session() {
+		let backend = InMemorySessionBackend::new();
+		let session_key = stored_session(&backend).await;
+		let middleware = SessionMiddleware::with_defaults(backend);
+		let request = create_test_request_with_cookie(&format!("sessionid={}", session_key));
+
+		let response = middleware
+			.process(request, Arc::new(MockHandler))
+			.await
+			.unwrap();
+
+		assert!(response.headers.get("set-cookie").is_none());
+	}
+
+	#[tokio::test]
+	async fn test_middleware_save_every_request() {
+		let backend = InMemorySessionBackend::new();
+		let session_key = stored_session(&backend).await;
+		let config = HttpSessionConfig {
+			save_every_request: true,
+			max_age: Some(Duration::from_secs(60)),
+			..Default::default()
+		};
+		let middleware = SessionMiddleware::new(backend, config);
+		let request = create_test_request_with_cookie(&format!("sessionid={}", session_key));
+
+		let response = middleware
+			.process(request, Arc::new(MockHandler))
+			.await
+			.unwrap();
+
+		let cookie = response.headers["set-cookie"].to_str().unwrap();
+		assert!(cookie.starts_with(&format!("sessionid={}", session_key)));
+		assert!(cookie.contains("Max-Age=60"));
+	}
+
+	#[tokio::test]
+	async fn test_middleware_ignores_unknown_session_key() {
+		let backend = InMemorySessionBackend::new();
+		let middleware = SessionMiddleware::with_defaults(backend);
+		let request = create_test_request_with_cookie("sessionid=attacker-chosen");
+
+		let response = middleware
+			.process(request, Arc::new(SessionModifyingHandler))
+			.await
+			.unwrap();
+
+		let cookie = response.headers["set-cookie"].to_str().unwrap();
+		assert!(cookie.starts_with("sessionid="));
+		assert!(!cookie.contains("attacker-chosen"));
+	}
+
+	#[tokio::test]
+	async fn test_middleware_deletes_emptied_session() {
+		let backend = InMemorySessionBackend::new();
+		let session_key = stored_session(&backend).await;
+		let middleware = SessionMiddleware::with_defaults(backend.clone());
+		let request = create_test_request_with_cookie(&format!("sessionid={}", session_key));
+
+		let response = middleware
+			.process(request, Arc::new(SessionClearingHandler))
+			.await
+			.unwrap();
+
+		let cookie = response.headers["set-cookie"].to_str().unwrap();
+		assert!(cookie.starts_with("sessionid=;"));
+		assert!(cookie.contains("Max-Age=0"));
+		assert!(!backend.exists(&session_key).await.unwrap());
+	}
+
+	#[tokio::test]
+	async fn test_build_set_cookie_header_samesite_none_is_secure() {
+		let config = HttpSessionConfig {
+			samesite: SameSite::None,
+			..Default::default()
+		};
+		let middleware = SessionMiddleware::new(InMemorySessionBackend::new(), config);
+
+		let cookie = middleware.build_set_cookie_header("abc123");
+
+		assert!(cookie.contains("Secure"));
+		assert!(cookie.contains("SameSite=None"));
+	}
 }
@@ -215,6 +215,25 @@ impl<B: SessionBackend> Session<B> {
 		self.data.contains_key(key)
 	}
 
+	/// Check if the session holds no data
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_auth::sessions::Session;
+	/// use reinhardt_auth::sessions::backends::InMemorySessionBackend;
+	///
+	/// let backend = InMemorySessionBackend::new();
+	/// let mut session = Session::new(backend);
+	/// assert!(session.is_empty());
+	///
+	/// session.set("key", "value").unwrap();
+	/// assert!(!session.is_empty());
+	/// ```
+	pub fn is_empty(&self) -> bool {
+		self.data.is_empty()
+	}
+
 	/// Get the session key (creates one if it doesn't exist)
 	///
 	/// # Example
@@ -292,6 +311,10 @@ impl<B: SessionBackend> Session<B> {
 
 	/// Cycle the session key (keep data but change key)
 	///
+	/// The data is saved under the new key before the old key is deleted; if
+	/// either step fails the session keeps its old key and the error is
+	/// returned.
+	///
 	/// # Example
 	///
 	/// ```rust
@@ -317,14 +340,24 @@ impl<B: SessionBackend> Session<B> {
 	/// # }
 	/// ```
 	pub async fn cycle_key(&mut self) -> Result<(), super::backends::SessionError> {
-		// Delete old session if it exists
-		if let Some(old_key) = &self.session_key {
-			self.backend.delete(old_key).await?;
+		let new_key = Self::generate_key();
+
+		let Some(old_key) = self.session_key.clone() else {
+			self.session_key = Some(new_key);
+			self.is_modified = true;
+			return Ok(());
+		};
+
+		// Persist the data under the new key before dropping the old one, so a
+		// failure at any point leaves exactly one usable key behind
+		self.backend.save(&new_key, &self.data, Some(3600)).await?;
+		if let Err(e) = self.backend.delete(&old_key).await {
+			let _ = self.backend.delete(&new_key).await;
+			return Err(e);
 		}
 
-		// Generate new key
-		self.session_key = Some(Self::generate_key());
-		self.is_modified = true;
+		self.session_key = Some(new_key);
+		self.is_modified = false;
 
 		Ok(())
 	}
@@ -808,6 +841,27 @@ mod tests {
 		assert_ne!(session.get_or_create_key(), old_key);
 	}
 
+	#[tokio::test]
+	async fn test_session_cycle_key_migrates_stored_data() {
+		let backend = InMemorySessionBackend::new();
+		let mut session = Session::new(backend.clone());
+
+		session.set("cart", vec![1, 2, 3]).unwrap();
+		session.save().await.unwrap();
+		let old_key = session.get_or_create_key().to_string();
+
+		session.cycle_key().await.unwrap();
+		let new_key = session.get_or_create_key().to_string();
+
+		assert!(!backend.exists(&old_key).await.unwrap());
+		let stored: Option<HashMap<String, Value>> = backend.load(&new_key).await.unwrap();
+		assert_eq!(
+			stored.unwrap().get("cart"),
+			Some(&serde_json::json!([1, 2, 3]))
+		);
+		assert!(!session.is_modified());
+	}
+
 	#[tokio::test]
 	async fn test_session_is_modified() {
 		let backend = InMemorySessionBackend::new();
@@ -158,7 +158,7 @@ impl BaseCommand for MigrateCommand {
 			};
 			if migrations_to_apply.is_empty() {
 				ctx.info("No migrations to apply");
-				return Ok(());
+				return create_content_types(ctx, &database_url).await;
 			}
 
 			ctx.info(&format!(
@@ -217,7 +217,7 @@ impl BaseCommand for MigrateCommand {
 				migrations_to_apply.len()
 			));
 
-			Ok(())
+			create_content_types(ctx, &database_url).await
 		}
 
 		#[cfg(not(feature = "migrations"))]
@@ -229,6 +229,26 @@ impl BaseCommand for MigrateCommand {
 	}
 }
 
+/// Create the content types of all registered models (post-migrate step)
+#[cfg(feature = "migrations")]
+async fn create_content_types(ctx: &CommandContext, database_url: &str) -> CommandResult<()> {
+	let models: Vec<(&'static str, &'static str)> =
+		reinhardt_apps::registry::get_registered_models()
+			.iter()
+			.map(|model| (model.app_label, model.model_name))
+			.collect();
+	let created = reinhardt_db::contenttypes::cache::create_contenttypes(database_url, models)
+		.await
+		.map_err(|e| {
+			crate::CommandError::ExecutionError(format!("Failed to create content types: {}", e))
+		})?;
+
+	for ct in &created {
+		ctx.verbose(&format!("  Created content type: {}", ct.qualified_name()));
+	}
+	Ok(())
+}
+
 /// Build from_state from database history (preferred approach)
 #[cfg(feature = "migrations")]
 async fn build_from_state_from_db(
@@ -1262,7 +1282,8 @@ impl RunServerCommand {
 		let router = if !no_docs {
 			use reinhardt_http::Handler;
 			use reinhardt_openapi::OpenApiRouter;
-			std::sync::Arc::new(OpenApiRouter::wrap(base_router)) as std::sync::Arc<dyn Handler>
+			std::sync::Arc::new(OpenApiRouter::wrap_router(base_router))
+				as std::sync::Arc<dyn Handler>
 		} else {
 			base_router
 		};
@@ -1622,7 +1643,11 @@ impl BaseCommand for ShowUrlsCommand {
 	}
 
 	fn options(&self) -> Vec<CommandOption> {
-		vec![CommandOption::flag(None, "names", "Show only named URLs")]
+		vec![
+			CommandOption::flag(None, "names", "Show only named URLs"),
+			CommandOption::option(Some('f'), "format", "Output format: text, json or mermaid")
+				.with_default("text"),
+		]
 	}
 
 	async fn execute(&self, ctx: &CommandContext) -> CommandResult<()> {
@@ -1645,6 +1670,38 @@ impl BaseCommand for ShowUrlsCommand {
 		let router = reinhardt_urls::routers::get_router()
 			.expect("Router should be registered (checked above)");
 
+		// Check if --names flag is set
+		let names_only = ctx.has_option("names");
+
+		// Machine-readable formats are printed without log prefixes
+		let format = ctx.option("format").map(String::as_str).unwrap_or("text");
+		use reinhardt_urls::routers::introspection::RouteInspector;
+		use reinhardt_urls::routers::visualization::{RouteVisualizer, VisualizationFormat};
+		let visualization = match format {
+			"text" => None,
+			"json" => Some(VisualizationFormat::Json),
+			"mermaid" => Some(VisualizationFormat::Mermaid),
+			other => {
+				return Err(crate::CommandError::InvalidArguments(format!(
+					"Unknown format '{}'. Expected text, json or mermaid",
+					other
+				)));
+			}
+		};
+		if let Some(visualization) = visualization {
+			let mut inspector = RouteInspector::new();
+			for route in router.inspect().all_routes() {
+				if !names_only || route.name.is_some() {
+					inspector.add_route_info(route.clone());
+				}
+			}
+			println!(
+				"{}",
+				RouteVisualizer::from_inspector(&inspector).render(visualization)
+			);
+			return Ok(());
+		}
+
 		// Get all routes
 		let routes = router.get_all_routes();
 
@@ -1653,9 +1710,6 @@ impl BaseCommand for ShowUrlsCommand {
 			return Ok(());
 		}
 
-		// Check if --names flag is set
-		let names_only = ctx.has_option("names");
-
 		// Display header
 		ctx.info("Registered URL patterns:");
 		ctx.info("");
@@ -179,12 +179,16 @@ pub enum Commands {
 		/// Show only named URLs
 		#[arg(long)]
 		names: bool,
+
+		/// Output format (text, json, or mermaid)
+		#[arg(short = 'f', long, default_value = "text")]
+		format: String,
 	},
 
 	/// Generate OpenAPI 3.0 schema from registered endpoints
 	#[cfg(feature = "openapi")]
 	Generateopenapi {
-		/// Output format (json or yaml)
+		/// Output format (json, yaml, or typescript for a typed API client)
 		#[arg(short = 'f', long, default_value = "json")]
 		format: String,
 
@@ -327,7 +331,7 @@ pub async fn run_command(
 			link,
 			ignore,
 		} => execute_collectstatic(clear, no_input, dry_run, link, ignore, verbosity).await,
-		Commands::Showurls { names } => execute_showurls(names, verbosity).await,
+		Commands::Showurls { names, format } => execute_showurls(names, format, verbosity).await,
 		#[cfg(feature = "openapi")]
 		Commands::Generateopenapi {
 			format,
@@ -583,39 +587,42 @@ async fn execute_collectstatic(
 		verbosity,
 		enable_hashing: true,
 		fast_compare: false,
+		source_maps: true,
+		compress: false,
 	};
 
-	// Create and execute command in blocking context
-	let mut cmd = CollectStaticCommand::new(config, options);
-	let result = tokio::task::spawn_blocking(move || {
-		// Call the sync execute() method directly (not the BaseCommand trait method)
-		CollectStaticCommand::execute(&mut cmd)
-	})
-	.await;
-
-	match result {
-		Ok(Ok(_stats)) => Ok(()),
-		Ok(Err(e)) => Err(Box::new(e) as Box<dyn std::error::Error>),
-		Err(e) => Err(Box::new(e) as Box<dyn std::error::Error>),
-	}
+	let cmd = CollectStaticCommand::new(config, options);
+	cmd.collect()
+		.await
+		.map(|_stats| ())
+		.map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
 }
 
 /// Execute the showurls command
 #[cfg(feature = "routers")]
-async fn execute_showurls(names: bool, verbosity: u8) -> Result<(), Box<dyn std::error::Error>> {
+async fn execute_showurls(
+	names: bool,
+	format: String,
+	verbosity: u8,
+) -> Result<(), Box<dyn std::error::Error>> {
 	let mut ctx = CommandContext::default();
 	ctx.set_verbosity(verbosity);
 
 	if names {
 		ctx.set_option("names".to_string(), "true".to_string());
 	}
+	ctx.set_option("format".to_string(), format);
 
 	let cmd = ShowUrlsCommand;
 	cmd.execute(&ctx).await.map_err(|e| e.into())
 }
 
 #[cfg(not(feature = "routers"))]
-async fn execute_showurls(_names: bool, _verbosity: u8) -> Result<(), Box<dyn std::error::Error>> {
+async fn execute_showurls(
+	_names: bool,
+	_format: String,
+	_verbosity: u8,
+) -> Result<(), Box<dyn std::error::Error>> {
 	use colored::Colorize;
 	eprintln!(
 		"{}",
@@ -651,6 +658,7 @@ async fn execute_generateopenapi(
 	// Generate content based on format
 	let content = match format.as_str() {
 		"yaml" | "yml" => generator.to_yaml()?,
+		"typescript" | "ts" => generator.to_typescript()?,
 		_ => generator.to_json()?,
 	};
 
@@ -1,15 +1,44 @@
 //! Collect static files command
 //!
-//! Django-style static file collection for production deployment
+//! Django-style static file collection for production deployment. Files are
+//! gathered and post-processed by a
+//! [`StaticCollector`](reinhardt_utils::staticfiles::StaticCollector); the
+//! options select the post-processors:
+//!
+//! - `enable_hashing`: content-hashed copies and the `staticfiles.json`
+//!   manifest, plus `{{ static_url("...") }}` resolution in `index.html`
+//! - `source_maps`: scripts and stylesheets are linked to their source maps,
+//!   whose references follow the hashed names when hashing is enabled
+//! - `compress`: `.gz` and `.br` variants of eligible files
 
 use crate::CommandResult;
-use crate::{BaseCommand, CommandContext};
+use crate::{BaseCommand, CommandContext, CommandError, CommandOption};
 use async_trait::async_trait;
-use reinhardt_utils::staticfiles::{StaticFilesConfig, StaticFilesFinder};
-use std::collections::HashMap;
-use std::fs;
+use reinhardt_utils::staticfiles::processing::compress::CompressionConfig;
+use reinhardt_utils::staticfiles::{
+	CollectedFile, CollectedFiles, CompressionPostProcessor, FileSystemStorage,
+	HashingPostProcessor, ManifestStaticFilesStorage, PostProcessor, SourceMapPostProcessor,
+	StaticCollector, StaticFilesConfig, StaticFilesFinder,
+};
 use std::io;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Finders for [`StaticCollector`](reinhardt_utils::staticfiles::StaticCollector)
+///
+/// `STATICFILES_DIRS` come first, followed by one finder per app registered
+/// with `register_app_static_files!`, so project files override app files.
+pub fn static_finders(config: &StaticFilesConfig) -> Vec<StaticFilesFinder> {
+	let mut finders = vec![StaticFilesFinder::new(config.staticfiles_dirs.clone())];
+	finders.extend(
+		::reinhardt_apps::get_app_static_files()
+			.into_iter()
+			.map(|app| PathBuf::from(app.static_dir))
+			.filter(|dir| !config.staticfiles_dirs.contains(dir))
+			.map(|dir| StaticFilesFinder::new(vec![dir])),
+	);
+	finders
+}
 
 #[derive(Debug, Clone)]
 pub struct CollectStaticOptions {
@@ -22,6 +51,10 @@ pub struct CollectStaticOptions {
 	pub ignore_patterns: Vec<String>,
 	pub enable_hashing: bool,
 	pub fast_compare: bool,
+	/// Link scripts and stylesheets to their source maps
+	pub source_maps: bool,
+	/// Add gzip and brotli variants of eligible files
+	pub compress: bool,
 }
 
 impl Default for CollectStaticOptions {
@@ -36,6 +69,8 @@ impl Default for CollectStaticOptions {
 			ignore_patterns: Vec::new(),
 			enable_hashing: true,
 			fast_compare: false,
+			source_maps: true,
+			compress: false,
 		}
 	}
 }
@@ -65,24 +100,42 @@ impl Default for CollectStaticStats {
 	}
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct CollectStaticCommand {
 	config: StaticFilesConfig,
 	options: CollectStaticOptions,
-	manifest: HashMap<String, String>,
 }
 
 impl CollectStaticCommand {
 	pub fn new(config: StaticFilesConfig, options: CollectStaticOptions) -> Self {
-		Self {
-			config,
-			options,
-			manifest: HashMap::new(),
-		}
+		Self { config, options }
 	}
 
 	/// Execute the collectstatic command
+	///
+	/// Blocking wrapper around [`collect`](Self::collect). Inside an async
+	/// runtime, where blocking on a nested runtime would panic, the collection
+	/// runs on a thread of its own.
 	pub fn execute(&mut self) -> Result<CollectStaticStats, io::Error> {
+		let run = || {
+			tokio::runtime::Builder::new_current_thread()
+				.enable_all()
+				.build()?
+				.block_on(self.collect())
+		};
+		if tokio::runtime::Handle::try_current().is_err() {
+			return run();
+		}
+		std::thread::scope(|scope| {
+			scope
+				.spawn(run)
+				.join()
+				.unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+		})
+	}
+
+	/// Collect the static files into `STATIC_ROOT`
+	pub async fn collect(&self) -> Result<CollectStaticStats, io::Error> {
 		let mut stats = CollectStaticStats::new();
 
 		// Validate configuration
@@ -90,74 +143,68 @@ impl CollectStaticCommand {
 
 		// Clear destination if requested
 		if self.options.clear {
-			self.clear_destination(&mut stats)?;
+			self.clear_destination(&mut stats).await?;
 		}
 
-		// Create destination directory if it doesn't exist
-		if !self.options.dry_run {
-			fs::create_dir_all(&self.config.static_root)?;
+		if self.options.verbosity > 1 {
+			for app in ::reinhardt_apps::get_app_static_files() {
+				println!(
+					"Auto-discovered static files from app '{}': {}",
+					app.app_label, app.static_dir
+				);
+			}
 		}
 
-		// Collect files from all source directories
-		// Start with manually configured directories
-		let mut all_dirs = self.config.staticfiles_dirs.clone();
-
-		// Auto-discover static files from installed apps via inventory
-		let app_static_configs = ::reinhardt_apps::get_app_static_files();
-
-		for config in app_static_configs {
-			// Convert &'static str to PathBuf
-			let static_dir = std::path::PathBuf::from(config.static_dir);
+		let (files, report) = self.collector().process().await?;
 
-			// Skip if already in staticfiles_dirs (manual config takes precedence)
-			if !all_dirs.contains(&static_dir) {
-				if self.options.verbosity > 1 {
-					println!(
-						"Auto-discovered static files from app '{}': {}",
-						config.app_label,
-						static_dir.display()
-					);
-				}
-				all_dirs.push(static_dir);
+		if self.options.verbosity > 0 {
+			println!("Found {} static files", report.found.len());
+		}
+		if self.options.verbosity > 1 {
+			for name in &report.ignored {
+				println!("Ignoring: {}", name);
 			}
 		}
+		stats.skipped = report.ignored.len();
 
-		let finder = StaticFilesFinder::new(all_dirs.clone());
-		let all_files = finder.find_all();
-
-		if self.options.verbosity > 0 {
-			println!("Found {} static files", all_files.len());
+		if !self.options.dry_run {
+			tokio::fs::create_dir_all(&self.config.static_root).await?;
 		}
 
-		// Use a HashSet to track unique file paths and ensure we process each file only once
-		// Later sources override earlier ones due to the order of find_all()
-		let mut processed_files = std::collections::HashSet::new();
-		let files_to_process: Vec<String> = all_files.into_iter().rev().collect();
+		for (name, file) in files.iter() {
+			let dest_path = self.config.static_root.join(name);
 
-		// Process each file (reversed so later sources are processed first)
-		for file_path in &files_to_process {
-			// Skip if already processed (handles duplicates from multiple source dirs)
-			if !processed_files.insert(file_path.clone()) {
+			// Check if file exists and is identical
+			if !self.options.clear && self.is_unmodified(&dest_path, file).await? {
+				if self.options.verbosity > 1 {
+					println!("Unmodified: {}", name);
+				}
+				stats.unmodified += 1;
 				continue;
 			}
 
-			if self.should_ignore(file_path) {
-				if self.options.verbosity > 1 {
-					println!("Ignoring: {}", file_path);
-				}
-				stats.skipped += 1;
+			if self.options.verbosity > 1 {
+				println!("Copying: {} → {}", name, dest_path.display());
+			}
+			stats.copied += 1;
+			if self.options.dry_run {
 				continue;
 			}
 
-			match self.copy_file(file_path, &all_dirs)? {
-				CopyResult::Copied => stats.copied += 1,
-				CopyResult::Unmodified => stats.unmodified += 1,
+			if let Some(parent) = dest_path.parent() {
+				tokio::fs::create_dir_all(parent).await?;
 			}
-		}
 
-		// Save manifest if hashing is enabled
-		if self.options.enable_hashing && !self.options.dry_run {
-			self.save_manifest()?;
+			// Only files left untouched by the post-processors can be linked
+			match (&file.source, &file.content) {
+				(Some(source), None) if self.options.link => {
+					self.create_symlink(source, &dest_path).await?
+				}
+				(Some(source), None) => {
+					tokio::fs::copy(source, &dest_path).await?;
+				}
+				_ => tokio::fs::write(&dest_path, file.read().await?).await?,
+			}
 		}
 
 		// Print summary
@@ -168,6 +215,37 @@ impl CollectStaticCommand {
 		Ok(stats)
 	}
 
+	/// Collector with the finders and post-processors selected by the options
+	pub fn collector(&self) -> StaticCollector {
+		let storage = Arc::new(FileSystemStorage::new(
+			self.config.static_root.clone(),
+			&self.config.static_url,
+		));
+		let mut collector = StaticCollector::new(storage)
+			.with_finders(static_finders(&self.config))
+			.with_ignore_patterns(self.options.ignore_patterns.clone());
+
+		// Source maps are linked first so the hashes cover the final content
+		if self.options.source_maps {
+			collector = collector.with_post_processor(SourceMapPostProcessor::new());
+		}
+		if self.options.enable_hashing {
+			let manifest = Arc::new(ManifestStaticFilesStorage::new(
+				self.config.static_root.clone(),
+				&self.config.static_url,
+			));
+			collector = collector.with_post_processor(HashingPostProcessor::new(manifest));
+			collector = collector.with_post_processor(HtmlTemplatePostProcessor {
+				verbosity: self.options.verbosity,
+			});
+		}
+		if self.options.compress {
+			collector = collector
+				.with_post_processor(CompressionPostProcessor::new(CompressionConfig::default()));
+		}
+		collector
+	}
+
 	fn validate_config(&self) -> Result<(), io::Error> {
 		if self.config.static_root.as_os_str().is_empty() {
 			return Err(io::Error::new(
@@ -179,7 +257,7 @@ impl CollectStaticCommand {
 		Ok(())
 	}
 
-	fn clear_destination(&self, stats: &mut CollectStaticStats) -> Result<(), io::Error> {
+	async fn clear_destination(&self, stats: &mut CollectStaticStats) -> Result<(), io::Error> {
 		if !self.config.static_root.exists() {
 			return Ok(());
 		}
@@ -192,15 +270,15 @@ impl CollectStaticCommand {
 		}
 
 		if !self.options.dry_run {
-			for entry in fs::read_dir(&self.config.static_root)? {
-				let entry = entry?;
+			let mut entries = tokio::fs::read_dir(&self.config.static_root).await?;
+			while let Some(entry) = entries.next_entry().await? {
 				let path = entry.path();
 
-				if path.is_file() {
-					fs::remove_file(&path)?;
+				if path.is_file() || path.is_symlink() {
+					tokio::fs::remove_file(&path).await?;
 					stats.deleted += 1;
 				} else if path.is_dir() {
-					fs::remove_dir_all(&path)?;
+					tokio::fs::remove_dir_all(&path).await?;
 					stats.deleted += 1;
 				}
 			}
@@ -209,291 +287,119 @@ impl CollectStaticCommand {
 		Ok(())
 	}
 
-	fn find_in_reverse(&self, path: &str, all_dirs: &[PathBuf]) -> Result<PathBuf, io::Error> {
-		// Search directories in reverse order to prioritize later sources
-		// Now searches ALL directories (manual + auto-discovered from inventory)
-		for dir in all_dirs.iter().rev() {
-			let file_path = dir.join(path);
-			if file_path.exists() {
-				return Ok(file_path);
-			}
-		}
-		Err(io::Error::new(
-			io::ErrorKind::NotFound,
-			format!("File not found in any directory: {}", path),
-		))
-	}
-
-	fn should_ignore(&self, file_path: &str) -> bool {
-		// Check if file is hidden (starts with dot)
-		if let Some(file_name) = std::path::Path::new(file_path).file_name()
-			&& let Some(name_str) = file_name.to_str()
-			&& name_str.starts_with('.')
-		{
-			return true;
-		}
-
-		// Check ignore patterns
-		for pattern in &self.options.ignore_patterns {
-			// Simple wildcard matching: "*.ext" matches any file ending with .ext
-			if pattern.starts_with("*.") {
-				let ext = &pattern[1..]; // Remove the *
-				if file_path.ends_with(ext) {
-					return true;
-				}
-			} else if file_path.contains(pattern) {
-				return true;
-			}
-		}
-		false
-	}
-
-	fn copy_file(
-		&mut self,
-		relative_path: &str,
-		all_dirs: &[PathBuf],
-	) -> Result<CopyResult, io::Error> {
-		// Find source file - search directories in reverse order to prioritize later sources
-		let source_path = self.find_in_reverse(relative_path, all_dirs)?;
-
-		// Special handling for index.html template processing
-		if relative_path.ends_with("index.html") && self.options.enable_hashing {
-			let dest_path = self.config.static_root.join(relative_path);
-
-			if !self.options.dry_run {
-				if let Some(parent) = dest_path.parent() {
-					fs::create_dir_all(parent)?;
-				}
-				self.process_html_template(&source_path, &dest_path)?;
-			}
-
-			return Ok(CopyResult::Copied);
-		}
-
-		// Generate hashed filename if hashing is enabled
-		let (_dest_filename, dest_path) = if self.options.enable_hashing {
-			let hash = self.calculate_hash(&source_path)?;
-			let hashed_name = self.get_hashed_filename(relative_path, &hash);
-
-			// Record in manifest
-			self.manifest
-				.insert(relative_path.to_string(), hashed_name.clone());
-
-			let dest = self.config.static_root.join(&hashed_name);
-			(hashed_name, dest)
-		} else {
-			let dest = self.config.static_root.join(relative_path);
-			(relative_path.to_string(), dest)
+	/// Whether `dest` already holds the content of `file`
+	///
+	/// With `fast_compare`, files over 1MB are compared by size only.
+	async fn is_unmodified(&self, dest: &Path, file: &CollectedFile) -> Result<bool, io::Error> {
+		let Ok(meta) = tokio::fs::metadata(dest).await else {
+			return Ok(false);
 		};
-
-		// Check if file exists and is identical
-		if dest_path.exists() && !self.options.clear {
-			let identical = if self.options.fast_compare {
-				self.files_identical_fast(&source_path, &dest_path)?
-			} else if self.options.enable_hashing {
-				self.files_identical_hash(&source_path, &dest_path)?
-			} else {
-				self.files_identical(&source_path, &dest_path)?
-			};
-
-			if identical {
-				if self.options.verbosity > 1 {
-					println!("Unmodified: {}", relative_path);
-				}
-				return Ok(CopyResult::Unmodified);
-			}
-		}
-
-		if self.options.verbosity > 1 {
-			println!(
-				"Copying: {} → {}",
-				source_path.display(),
-				dest_path.display()
-			);
-		}
-
-		if self.options.dry_run {
-			return Ok(CopyResult::Copied);
-		}
-
-		// Create parent directories
-		if let Some(parent) = dest_path.parent() {
-			fs::create_dir_all(parent)?;
+		let len = match (&file.content, &file.source) {
+			(Some(content), _) => content.len() as u64,
+			(None, Some(source)) => tokio::fs::metadata(source).await?.len(),
+			(None, None) => 0,
+		};
+		if meta.len() != len {
+			return Ok(false);
 		}
-
-		// Copy or link file
-		if self.options.link {
-			self.create_symlink(&source_path, &dest_path)?;
-		} else {
-			fs::copy(&source_path, &dest_path)?;
+		if self.options.fast_compare && meta.len() >= 1024 * 1024 {
+			return Ok(true);
 		}
-
-		Ok(CopyResult::Copied)
+		Ok(tokio::fs::read(dest).await? == *file.read().await?)
 	}
 
 	#[cfg(unix)]
-	fn create_symlink(&self, source: &Path, dest: &Path) -> Result<(), io::Error> {
-		use std::os::unix::fs::symlink;
-
+	async fn create_symlink(&self, source: &Path, dest: &Path) -> Result<(), io::Error> {
 		// Remove existing file/symlink
-		if dest.exists() || dest.symlink_metadata().is_ok() {
-			fs::remove_file(dest)?;
+		if tokio::fs::symlink_metadata(dest).await.is_ok() {
+			tokio::fs::remove_file(dest).await?;
 		}
 
-		symlink(source, dest)
+		tokio::fs::symlink(source, dest).await
 	}
 
 	#[cfg(not(unix))]
-	fn create_symlink(&self, source: &Path, dest: &Path) -> Result<(), io::Error> {
+	async fn create_symlink(&self, source: &Path, dest: &Path) -> Result<(), io::Error> {
 		// Fallback to copy on non-Unix systems
 		if dest.exists() {
-			fs::remove_file(dest)?;
+			tokio::fs::remove_file(dest).await?;
 		}
-		fs::copy(source, dest)?;
+		tokio::fs::copy(source, dest).await?;
 		Ok(())
 	}
 
-	fn files_identical(&self, path1: &Path, path2: &Path) -> Result<bool, io::Error> {
-		let meta1 = fs::metadata(path1)?;
-		let meta2 = fs::metadata(path2)?;
-
-		// Quick check: if sizes differ, files are different
-		if meta1.len() != meta2.len() {
-			return Ok(false);
-		}
+	fn print_summary(&self, stats: &CollectStaticStats) {
+		println!("\n{} static files copied", stats.copied);
 
-		// For small files, compare content
-		if meta1.len() < 1024 * 1024 {
-			// 1MB threshold
-			let content1 = fs::read(path1)?;
-			let content2 = fs::read(path2)?;
-			return Ok(content1 == content2);
+		if stats.unmodified > 0 {
+			println!("{} files unmodified", stats.unmodified);
 		}
 
-		// For large files, assume identical if same size
-		// (more sophisticated comparison could use checksums)
-		Ok(true)
-	}
-
-	fn calculate_hash(&self, path: &Path) -> Result<String, io::Error> {
-		use sha2::{Digest, Sha256};
-
-		// Handle symlinks: resolve to target
-		let canonical_path = fs::canonicalize(path)?;
-		let content = fs::read(&canonical_path)?;
-
-		let hash = Sha256::digest(&content);
-		// Use first 8 characters of SHA-256 hash
-		Ok(format!("{:x}", hash)[..8].to_string())
-	}
-
-	fn get_hashed_filename(&self, filename: &str, hash: &str) -> String {
-		if let Some(dot_pos) = filename.rfind('.') {
-			// Insert hash before extension
-			format!("{}.{}{}", &filename[..dot_pos], hash, &filename[dot_pos..])
-		} else {
-			// No extension: append hash to end
-			format!("{}.{}", filename, hash)
+		if stats.skipped > 0 {
+			println!("{} files skipped", stats.skipped);
 		}
-	}
 
-	fn save_manifest(&self) -> Result<(), io::Error> {
-		let manifest_path = self.config.static_root.join("manifest.json");
-
-		let manifest_data = serde_json::json!({
-			"version": "1.0",
-			"files": self.manifest
-		});
-
-		let json = serde_json::to_string_pretty(&manifest_data)?;
-		fs::write(manifest_path, json)?;
-
-		if self.options.verbosity > 0 {
-			println!("✓ Manifest saved: manifest.json");
+		if stats.deleted > 0 {
+			println!("{} files deleted", stats.deleted);
 		}
-
-		Ok(())
-	}
-
-	fn files_identical_hash(&self, path1: &Path, path2: &Path) -> Result<bool, io::Error> {
-		let hash1 = self.calculate_hash(path1)?;
-		let hash2 = self.calculate_hash(path2)?;
-		Ok(hash1 == hash2)
 	}
+}
 
-	fn files_identical_fast(&self, path1: &Path, path2: &Path) -> Result<bool, io::Error> {
-		let meta1 = fs::metadata(path1)?;
-		let meta2 = fs::metadata(path2)?;
-
-		// Check size
-		if meta1.len() != meta2.len() {
-			return Ok(false);
-		}
-
-		// Files <= 1MB: content comparison
-		if meta1.len() < 1024 * 1024 {
-			let content1 = fs::read(path1)?;
-			let content2 = fs::read(path2)?;
-			return Ok(content1 == content2);
-		}
+/// Resolves `{{ static_url("path") }}` in `index.html` files to hashed names
+///
+/// Runs after [`HashingPostProcessor`]; only the original `index.html` is
+/// rewritten, so it can be served under its usual name.
+struct HtmlTemplatePostProcessor {
+	verbosity: u8,
+}
 
-		// Files > 1MB: size-only comparison
-		Ok(true)
+#[async_trait]
+impl PostProcessor for HtmlTemplatePostProcessor {
+	fn name(&self) -> &str {
+		"HtmlTemplatePostProcessor"
 	}
 
-	fn process_html_template(&self, source: &Path, dest: &Path) -> Result<(), io::Error> {
-		let content = fs::read_to_string(source)?;
-
-		// Detect {{ static_url("path") }} pattern
+	async fn post_process(&self, files: &mut CollectedFiles) -> io::Result<()> {
 		let re = regex::Regex::new(r#"\{\{\s*static_url\("([^"]+)"\)\s*\}\}"#).unwrap();
 
-		let processed = re.replace_all(&content, |caps: &regex::Captures| {
-			let original_path = &caps[1];
-
-			// Resolve from manifest
-			if let Some(hashed_path) = self.manifest.get(original_path) {
-				format!("/{}", hashed_path)
-			} else {
-				if self.options.verbosity > 0 {
-					eprintln!(
-						"⚠️  Static file '{}' not in manifest, using original path",
-						original_path
-					);
-				}
-				format!("/{}", original_path)
+		for name in files.names() {
+			if !name.ends_with("index.html") || files.get(&name).is_none_or(|f| f.source.is_none())
+			{
+				continue;
 			}
-		});
+			let Some(content) = files.read(&name).await? else {
+				continue;
+			};
+			let Ok(content) = std::str::from_utf8(&content) else {
+				continue;
+			};
 
-		fs::write(dest, processed.as_bytes())?;
+			let processed = re.replace_all(content, |caps: &regex::Captures| {
+				let original_path = &caps[1];
+
+				// Resolve from manifest
+				if let Some(hashed_path) = files.path(original_path) {
+					format!("/{}", hashed_path)
+				} else {
+					if self.verbosity > 0 {
+						eprintln!(
+							"⚠️  Static file '{}' not in manifest, using original path",
+							original_path
+						);
+					}
+					format!("/{}", original_path)
+				}
+			});
+			let processed = processed.into_owned();
+			files.set_content(&name, processed.into_bytes());
 
-		if self.options.verbosity > 1 {
-			println!("✓ Processed HTML template: {}", source.display());
+			if self.verbosity > 1 {
+				println!("✓ Processed HTML template: {}", name);
+			}
 		}
 
 		Ok(())
 	}
-
-	fn print_summary(&self, stats: &CollectStaticStats) {
-		println!("\n{} static files copied", stats.copied);
-
-		if stats.unmodified > 0 {
-			println!("{} files unmodified", stats.unmodified);
-		}
-
-		if stats.skipped > 0 {
-			println!("{} files skipped", stats.skipped);
-		}
-
-		if stats.deleted > 0 {
-			println!("{} files deleted", stats.deleted);
-		}
-	}
-}
-
-#[derive(Debug, PartialEq)]
-enum CopyResult {
-	Copied,
-	Unmodified,
 }
 
 #[async_trait]
@@ -502,19 +408,36 @@ impl BaseCommand for CollectStaticCommand {
 		"collectstatic"
 	}
 
-	async fn execute(&self, _ctx: &CommandContext) -> CommandResult<()> {
-		// BaseCommand requires async, but our logic is sync
-		// We simply return Ok as the actual execution happens via the sync execute() method
-		Ok(())
+	fn description(&self) -> &str {
+		"Collect static files into STATIC_ROOT"
 	}
-}
 
-impl Clone for CollectStaticCommand {
-	fn clone(&self) -> Self {
-		Self {
-			config: self.config.clone(),
-			options: self.options.clone(),
-			manifest: HashMap::new(),
-		}
+	fn options(&self) -> Vec<CommandOption> {
+		vec![
+			CommandOption::flag(None, "clear", "Clear STATIC_ROOT before copying"),
+			CommandOption::flag(None, "no-input", "Do not prompt for input"),
+			CommandOption::flag(Some('n'), "dry-run", "Show what would be collected"),
+			CommandOption::flag(Some('l'), "link", "Symlink files instead of copying"),
+			CommandOption::option(Some('i'), "ignore", "Glob pattern of files to ignore").multi(),
+		]
+	}
+
+	async fn execute(&self, ctx: &CommandContext) -> CommandResult<()> {
+		let mut options = self.options.clone();
+		options.clear |= ctx.has_option("clear");
+		options.no_input |= ctx.has_option("no-input");
+		options.interactive &= !options.no_input;
+		options.dry_run |= ctx.has_option("dry-run");
+		options.link |= ctx.has_option("link");
+		options
+			.ignore_patterns
+			.extend(ctx.option_values("ignore").unwrap_or_default());
+		options.verbosity = ctx.verbosity;
+
+		Self::new(self.config.clone(), options)
+			.collect()
+			.await
+			.map(|_stats| ())
+			.map_err(|e| CommandError::ExecutionError(format!("collectstatic failed: {}", e)))
 	}
 }
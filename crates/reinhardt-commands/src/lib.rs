@@ -163,7 +163,9 @@ pub use builtin::MakeMigrationsCommand;
 pub use builtin::ShowUrlsCommand;
 pub use builtin::{CheckCommand, CheckDiCommand, MigrateCommand, RunServerCommand, ShellCommand};
 pub use cli::{Cli, Commands, execute_from_command_line, run_command};
-pub use collectstatic::{CollectStaticCommand, CollectStaticOptions, CollectStaticStats};
+pub use collectstatic::{
+	CollectStaticCommand, CollectStaticOptions, CollectStaticStats, static_finders,
+};
 pub use context::CommandContext;
 pub use i18n_commands::{CompileMessagesCommand, MakeMessagesCommand};
 pub use mail_commands::SendTestEmailCommand;
@@ -392,10 +392,13 @@ fn test_commands_collectstatic_defaults() {
 /// Verifies that the names flag is correctly parsed.
 #[rstest]
 fn test_commands_showurls_names_flag() {
-	let cmd = Commands::Showurls { names: true };
+	let cmd = Commands::Showurls {
+		names: true,
+		format: "text".to_string(),
+	};
 
 	match cmd {
-		Commands::Showurls { names } => {
+		Commands::Showurls { names, .. } => {
 			assert!(names, "names flag should be true");
 		}
 		#[allow(unreachable_patterns)]
@@ -830,7 +833,10 @@ fn test_cli_commands_sanity() {
 			link: false,
 			ignore: vec![],
 		},
-		Commands::Showurls { names: false },
+		Commands::Showurls {
+			names: false,
+			format: "text".to_string(),
+		},
 	];
 
 	// Verify each command can be created and has Debug implementation
@@ -964,10 +970,13 @@ fn test_cli_version_flag() {
 /// Verifies that Showurls command has correct defaults.
 #[rstest]
 fn test_commands_showurls_defaults() {
-	let cmd = Commands::Showurls { names: false };
+	let cmd = Commands::Showurls {
+		names: false,
+		format: "text".to_string(),
+	};
 
 	match cmd {
-		Commands::Showurls { names } => {
+		Commands::Showurls { names, .. } => {
 			assert!(!names, "names should be false by default");
 		}
 		#[allow(unreachable_patterns)]
@@ -981,7 +990,10 @@ fn test_commands_showurls_defaults() {
 /// Verifies that Showurls has proper Debug implementation.
 #[rstest]
 fn test_commands_showurls_debug() {
-	let cmd = Commands::Showurls { names: true };
+	let cmd = Commands::Showurls {
+		names: true,
+		format: "text".to_string(),
+	};
 	let debug_str = format!("{:?}", cmd);
 
 	assert!(
@@ -1000,17 +1012,39 @@ fn test_commands_showurls_debug() {
 /// Verifies that Showurls can be cloned correctly.
 #[rstest]
 fn test_commands_showurls_clone() {
-	let original = Commands::Showurls { names: true };
+	let original = Commands::Showurls {
+		names: true,
+		format: "text".to_string(),
+	};
 	let cloned = original.clone();
 
 	match (&original, &cloned) {
-		(Commands::Showurls { names: n1 }, Commands::Showurls { names: n2 }) => {
+		(Commands::Showurls { names: n1, .. }, Commands::Showurls { names: n2, .. }) => {
 			assert_eq!(n1, n2, "Cloned value should match original");
 		}
 		_ => panic!("Expected Commands::Showurls variants"),
 	}
 }
 
+/// Test: Parse Showurls command with --format option
+///
+/// Category: Happy Path
+/// Verifies that the output format is parsed and defaults to text.
+#[rstest]
+fn test_commands_showurls_format_option() {
+	let cli = Cli::try_parse_from(["test", "showurls", "--format", "mermaid"]).unwrap();
+	match cli.command {
+		Commands::Showurls { format, .. } => assert_eq!(format, "mermaid"),
+		_ => panic!("Expected Commands::Showurls variant"),
+	}
+
+	let cli = Cli::try_parse_from(["test", "showurls"]).unwrap();
+	match cli.command {
+		Commands::Showurls { format, .. } => assert_eq!(format, "text"),
+		_ => panic!("Expected Commands::Showurls variant"),
+	}
+}
+
 // ============================================================================
 // Generateopenapi Command Tests (Feature-independent behavior)
 // ============================================================================
@@ -1244,7 +1278,10 @@ fn test_all_command_variants_creatable() {
 		ignore: vec!["*.txt".to_string()],
 	};
 
-	let showurls = Commands::Showurls { names: true };
+	let showurls = Commands::Showurls {
+		names: true,
+		format: "text".to_string(),
+	};
 
 	// Verify all have Debug
 	let all_cmds: Vec<&Commands> = vec![
@@ -3,7 +3,9 @@
 //! Tests for the CollectStaticCommand, CollectStaticOptions, and CollectStaticStats.
 //! These tests verify static file collection functionality.
 
-use reinhardt_commands::{CollectStaticCommand, CollectStaticOptions, CollectStaticStats};
+use reinhardt_commands::{
+	CollectStaticCommand, CollectStaticOptions, CollectStaticStats, CommandContext,
+};
 use reinhardt_utils::staticfiles::StaticFilesConfig;
 use rstest::*;
 use std::fs;
@@ -116,6 +118,8 @@ fn test_collectstatic_options_all_set() {
 		ignore_patterns: vec!["*.map".to_string(), "*.log".to_string()],
 		enable_hashing: false,
 		fast_compare: true,
+		source_maps: false,
+		compress: true,
 	};
 
 	assert!(options.clear, "clear should be true");
@@ -147,6 +151,8 @@ fn test_collectstatic_options_clone() {
 		ignore_patterns: vec!["*.map".to_string()],
 		enable_hashing: false,
 		fast_compare: true,
+		source_maps: false,
+		compress: true,
 	};
 
 	let cloned = original.clone();
@@ -285,6 +291,54 @@ fn test_collectstatic_basic_copy(collectstatic_command: (TempDir, CollectStaticC
 	assert!(stats.copied > 0, "Should have copied at least one file");
 }
 
+/// Test: CollectStaticCommand execute inside an async runtime
+///
+/// Category: Happy Path
+/// Verifies that the blocking wrapper does not panic inside a runtime.
+#[rstest]
+#[tokio::test]
+async fn test_collectstatic_execute_inside_runtime(
+	collectstatic_command: (TempDir, CollectStaticCommand),
+) {
+	let (_temp_dir, mut command) = collectstatic_command;
+
+	let stats = command.execute().expect("Execute should succeed");
+
+	assert!(stats.copied > 0, "Should have copied at least one file");
+}
+
+/// Test: CollectStaticCommand as a BaseCommand
+///
+/// Category: Happy Path
+/// Verifies that running the command through BaseCommand collects the files
+/// and honours the context options.
+#[rstest]
+#[tokio::test]
+async fn test_collectstatic_base_command_collects(
+	temp_with_static_files: (TempDir, PathBuf, PathBuf),
+) {
+	let (_temp_dir, source_dir, dest_dir) = temp_with_static_files;
+	let config = StaticFilesConfig {
+		static_url: "/static/".to_string(),
+		static_root: dest_dir.clone(),
+		staticfiles_dirs: vec![source_dir],
+		media_url: Some("/media/".to_string()),
+	};
+	let command = CollectStaticCommand::new(config, CollectStaticOptions::default());
+	let mut ctx = CommandContext::default();
+	ctx.set_option_multi("ignore".to_string(), vec!["*.css".to_string()]);
+
+	reinhardt_commands::BaseCommand::execute(&command, &ctx)
+		.await
+		.expect("Execute should succeed");
+
+	assert_eq!(
+		fs::read(dest_dir.join("app.js")).unwrap(),
+		b"console.log('app');"
+	);
+	assert!(!dest_dir.join("style.css").exists());
+}
+
 /// Test: CollectStaticCommand dry run mode
 ///
 /// Category: Happy Path
@@ -953,3 +1007,87 @@ fn test_collectstatic_skips_nonexistent_autodiscovered_dirs(temp_dir: TempDir) {
 	let _stats = result.unwrap();
 	// Test passes if execute() succeeded without errors
 }
+
+/// Test: Hashing through the static collector
+///
+/// Category: Integration
+/// Verifies that hashing writes hashed copies and the `staticfiles.json`
+/// manifest, and resolves `static_url` references in `index.html`.
+#[rstest]
+fn test_collectstatic_hashing_writes_manifest(temp_dir: TempDir) {
+	let source_dir = temp_dir.path().join("static_source");
+	let dest_dir = temp_dir.path().join("static_root");
+	fs::create_dir_all(&source_dir).unwrap();
+	fs::write(source_dir.join("app.js"), b"console.log('app');").unwrap();
+	fs::write(
+		source_dir.join("index.html"),
+		br#"<script src="{{ static_url("app.js") }}"></script>"#,
+	)
+	.unwrap();
+
+	let config = StaticFilesConfig {
+		static_url: "/static/".to_string(),
+		static_root: dest_dir.clone(),
+		staticfiles_dirs: vec![source_dir],
+		media_url: None,
+	};
+	let options = CollectStaticOptions {
+		verbosity: 0,
+		..Default::default()
+	};
+	let mut command = CollectStaticCommand::new(config, options);
+
+	command.execute().unwrap();
+
+	let manifest: serde_json::Value =
+		serde_json::from_slice(&fs::read(dest_dir.join("staticfiles.json")).unwrap()).unwrap();
+	let hashed = manifest["paths"]["app.js"].as_str().unwrap();
+	assert_ne!(hashed, "app.js");
+	assert_eq!(
+		fs::read(dest_dir.join(hashed)).unwrap(),
+		b"console.log('app');"
+	);
+	assert!(dest_dir.join("app.js").exists());
+	assert_eq!(
+		fs::read_to_string(dest_dir.join("index.html")).unwrap(),
+		format!(r#"<script src="/{}"></script>"#, hashed)
+	);
+}
+
+/// Test: Compression selected by options
+///
+/// Category: Integration
+/// Verifies that `compress` adds pre-compressed variants and that hashing
+/// can be turned off independently.
+#[rstest]
+fn test_collectstatic_compress_option(temp_dir: TempDir) {
+	let source_dir = temp_dir.path().join("static_source");
+	let dest_dir = temp_dir.path().join("static_root");
+	fs::create_dir_all(&source_dir).unwrap();
+	fs::write(
+		source_dir.join("app.js"),
+		"console.log('app');\n".repeat(100),
+	)
+	.unwrap();
+
+	let config = StaticFilesConfig {
+		static_url: "/static/".to_string(),
+		static_root: dest_dir.clone(),
+		staticfiles_dirs: vec![source_dir],
+		media_url: None,
+	};
+	let options = CollectStaticOptions {
+		verbosity: 0,
+		enable_hashing: false,
+		compress: true,
+		..Default::default()
+	};
+	let mut command = CollectStaticCommand::new(config, options);
+
+	let stats = command.execute().unwrap();
+
+	assert_eq!(stats.copied, 3);
+	assert!(dest_dir.join("app.js.gz").exists());
+	assert!(dest_dir.join("app.js.br").exists());
+	assert!(!dest_dir.join("staticfiles.json").exists());
+}
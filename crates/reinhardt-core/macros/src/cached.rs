@@ -0,0 +1,160 @@
+//! `#[cached]` attribute macro for memoizing async functions
+
+use crate::crate_paths::get_reinhardt_utils_crate;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Expr, FnArg, GenericArgument, ItemFn, Pat, PathArguments, Result, ReturnType, Type};
+
+/// Parsed `#[cached(...)]` arguments
+#[derive(Default)]
+struct CachedArgs {
+	ttl: Option<u64>,
+	prefix: Option<String>,
+	cache: Option<Expr>,
+	unless: Option<Expr>,
+}
+
+fn parse_cached_args(args: TokenStream) -> Result<CachedArgs> {
+	let mut result = CachedArgs::default();
+
+	if args.is_empty() {
+		return Ok(result);
+	}
+
+	let parser = syn::meta::parser(|meta| {
+		if meta.path.is_ident("ttl") {
+			result.ttl = Some(meta.value()?.parse::<syn::LitInt>()?.base10_parse()?);
+			Ok(())
+		} else if meta.path.is_ident("prefix") {
+			result.prefix = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+			Ok(())
+		} else if meta.path.is_ident("cache") {
+			result.cache = Some(meta.value()?.parse()?);
+			Ok(())
+		} else if meta.path.is_ident("unless") {
+			result.unless = Some(meta.value()?.parse()?);
+			Ok(())
+		} else {
+			Err(meta.error("unsupported cached attribute"))
+		}
+	});
+
+	syn::parse::Parser::parse2(parser, args)?;
+	Ok(result)
+}
+
+/// Returns the `T` of a `Result<T, E>` return type
+fn result_ok_type(ty: &Type) -> Option<&Type> {
+	let Type::Path(path) = ty else {
+		return None;
+	};
+	let segment = path.path.segments.last()?;
+	if segment.ident != "Result" {
+		return None;
+	}
+	let PathArguments::AngleBracketed(args) = &segment.arguments else {
+		return None;
+	};
+	match args.args.first()? {
+		GenericArgument::Type(ty) => Some(ty),
+		_ => None,
+	}
+}
+
+/// Implementation of the `cached` procedural macro
+///
+/// Rewrites the function body to look up its result in a cache keyed by the
+/// function's arguments before running it. Functions returning `Result`
+/// only cache `Ok` values.
+///
+/// # Arguments
+///
+/// - `ttl`: Optional expiry in seconds
+/// - `prefix`: Optional key prefix (default: module path and function name)
+/// - `cache`: Required cache expression; it is dereferenced once, so `&C`,
+///   `Arc<C>`, `&'static C` and `LazyLock<C>` statics all work
+/// - `unless`: Optional predicate; results it returns `true` for are not cached
+///
+/// On methods the receiver is part of the key, so `Self` must implement
+/// `Serialize`.
+pub(crate) fn cached_impl(args: TokenStream, mut input: ItemFn) -> Result<TokenStream> {
+	let args = parse_cached_args(args)?;
+	let utils_crate = get_reinhardt_utils_crate();
+
+	let Some(cache) = &args.cache else {
+		return Err(syn::Error::new_spanned(
+			&input.sig.ident,
+			"#[cached] requires a `cache = ...` argument naming the cache backend",
+		));
+	};
+
+	if input.sig.asyncness.is_none() {
+		return Err(syn::Error::new_spanned(
+			input.sig.fn_token,
+			"#[cached] can only be applied to async functions",
+		));
+	}
+
+	let ReturnType::Type(_, return_type) = &input.sig.output else {
+		return Err(syn::Error::new_spanned(
+			&input.sig,
+			"#[cached] functions must return a value",
+		));
+	};
+	let return_type = (**return_type).clone();
+
+	let mut key_args = Vec::new();
+	for arg in &input.sig.inputs {
+		let pat_type = match arg {
+			// Results depend on the receiver, so it is part of the key
+			FnArg::Receiver(receiver) => {
+				key_args.push(syn::Ident::new("self", receiver.self_token.span));
+				continue;
+			}
+			FnArg::Typed(pat_type) => pat_type,
+		};
+		let Pat::Ident(pat_ident) = &*pat_type.pat else {
+			return Err(syn::Error::new_spanned(
+				&pat_type.pat,
+				"#[cached] arguments must be plain identifiers",
+			));
+		};
+		key_args.push(pat_ident.ident.clone());
+	}
+
+	let fn_name = &input.sig.ident;
+	let prefix = match &args.prefix {
+		Some(prefix) => quote!(#prefix),
+		None => quote!(concat!(module_path!(), "::", stringify!(#fn_name))),
+	};
+	let ttl = args
+		.ttl
+		.map(|secs| quote!(.with_ttl(::std::time::Duration::from_secs(#secs))));
+	let unless = args
+		.unless
+		.as_ref()
+		.map(|predicate| quote!(.unless(#predicate)));
+	let cache = quote!(&*(#cache));
+	let (value_type, method) = match result_ok_type(&return_type) {
+		Some(ok_type) => (ok_type.clone(), quote!(try_get_or_compute)),
+		None => (return_type.clone(), quote!(get_or_compute)),
+	};
+
+	let block = &input.block;
+	let body = quote! {
+		{
+			let __reinhardt_memo = #utils_crate::cache::Memoize::<#value_type>::new(#prefix) #ttl #unless;
+			let __reinhardt_key = __reinhardt_memo.key(&(#(&#key_args,)*)).ok();
+			let __reinhardt_cache = #cache;
+			__reinhardt_memo
+				.#method(__reinhardt_cache, __reinhardt_key.as_deref(), async move {
+					let __reinhardt_output: #return_type = #block;
+					__reinhardt_output
+				})
+				.await
+		}
+	};
+	input.block = Box::new(syn::parse2(body)?);
+
+	Ok(quote!(#input))
+}
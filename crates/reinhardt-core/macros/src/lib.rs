@@ -9,6 +9,7 @@
 //! - `#[action]` - Define custom ViewSet action
 //! - `#[get]`, `#[post]`, etc. - HTTP method decorators
 //! - `#[permission_required]` - Permission decorator
+//! - `#[cached]` - Memoize async function results
 //!
 
 use proc_macro::TokenStream;
@@ -19,6 +20,7 @@ mod admin;
 mod api_view;
 mod app_config_attribute;
 mod app_config_derive;
+mod cached;
 mod collect_migrations;
 mod crate_paths;
 mod injectable_common;
@@ -43,6 +45,7 @@ use action::action_impl;
 use admin::admin_impl;
 use api_view::api_view_impl;
 use app_config_attribute::app_config_attribute_impl;
+use cached::cached_impl;
 use injectable_fn::injectable_fn_impl;
 use injectable_struct::injectable_struct_impl;
 use installed_apps::installed_apps_impl;
@@ -314,6 +317,12 @@ pub fn routes(args: TokenStream, input: TokenStream) -> TokenStream {
 /// - `slug` - Slug strings (alphanumeric, hyphens, underscores)
 /// - `path` - Path segments (can include slashes)
 ///
+/// # Custom Converters
+///
+/// Converters registered at runtime must be declared so the pattern can be
+/// validated: `path!("events/{<ulid:id>}/", converters = [ulid])`. Route
+/// macros take the same option: `#[get("/events/{<ulid:id>}/", converters = [ulid])]`.
+///
 #[proc_macro]
 pub fn path(input: TokenStream) -> TokenStream {
 	path_impl(input.into())
@@ -335,6 +344,42 @@ pub fn receiver(args: TokenStream, input: TokenStream) -> TokenStream {
 		.into()
 }
 
+/// Memoize an async function's result in the cache
+///
+/// The result is stored under a key built from the function's arguments,
+/// which must implement `Serialize`. On methods the receiver is part of the
+/// key, so `Self` must implement `Serialize` too. Functions returning
+/// `Result` only cache `Ok` values.
+///
+/// # Arguments
+///
+/// - `cache = expr`: Cache backend to use (required)
+/// - `ttl = 60`: Expire cached results after the given number of seconds
+/// - `prefix = "users"`: Key prefix (default: module path and function name)
+/// - `unless = |result| ...`: Skip caching results matching the predicate
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use reinhardt_utils::cache::{InMemoryCache, cached};
+/// use std::sync::LazyLock;
+///
+/// static PROFILES: LazyLock<InMemoryCache> = LazyLock::new(InMemoryCache::new);
+///
+/// #[cached(cache = PROFILES, ttl = 300, prefix = "profiles", unless = |profiles| profiles.is_empty())]
+/// async fn load_profiles(org_id: i64) -> Vec<String> {
+///     fetch_profiles(org_id).await
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn cached(args: TokenStream, input: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(input as ItemFn);
+
+	cached_impl(args.into(), input)
+		.unwrap_or_else(|e| e.to_compile_error())
+		.into()
+}
+
 /// Automatic dependency injection macro
 ///
 /// This macro enables FastAPI-style dependency injection using parameter attributes.
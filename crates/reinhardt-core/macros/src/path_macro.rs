@@ -11,15 +11,16 @@ use nom::{
 	branch::alt,
 	bytes::complete::{tag, take_while1},
 	character::complete::{alpha1, alphanumeric1},
-	combinator::{map, recognize, value, verify},
+	combinator::{map, recognize, verify},
 	multi::{many0, many0_count},
 	sequence::{delimited, pair, separated_pair},
 };
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{
-	Error, LitStr, Result,
+	Error, Ident, LitStr, Result, Token, bracketed,
 	parse::{Parse, ParseStream},
+	punctuated::Punctuated,
 };
 
 // ============================================================================
@@ -98,6 +99,10 @@ pub(crate) enum TypeSpec {
 	Email,
 	/// ISO 8601 date format (YYYY-MM-DD)
 	Date,
+
+	// === Custom converters ===
+	/// Converter registered at runtime and declared in the macro invocation
+	Custom(String),
 }
 
 impl TypeSpec {
@@ -151,6 +156,11 @@ impl TypeSpec {
 			_ => None,
 		}
 	}
+
+	/// Convert from string to TypeSpec, treating unknown names as custom converters
+	fn from_name(s: &str) -> Self {
+		Self::from_str(s).unwrap_or_else(|| TypeSpec::Custom(s.to_string()))
+	}
 }
 
 // ============================================================================
@@ -168,39 +178,17 @@ fn identifier(input: &str) -> IResult<&str, &str> {
 
 /// Parse a type specifier
 ///
-/// Supported types:
+/// Built-in types are:
 /// - Basic: int, str, uuid, slug, path
 /// - Signed integers: i8, i16, i32, i64
 /// - Unsigned integers: u8, u16, u32, u64
 /// - Floating point: f32, f64
 /// - Other: bool, email, date
+///
+/// Any other identifier is a custom converter; whether it was declared is
+/// checked by [`parse_and_validate`].
 fn type_spec(input: &str) -> IResult<&str, TypeSpec> {
-	alt((
-		// Basic types (legacy)
-		value(TypeSpec::Int, tag("int")),
-		value(TypeSpec::Str, tag("str")),
-		value(TypeSpec::Uuid, tag("uuid")),
-		value(TypeSpec::Slug, tag("slug")),
-		value(TypeSpec::Path, tag("path")),
-		// Signed integers (order matters: i64 before i6, etc.)
-		value(TypeSpec::I64, tag("i64")),
-		value(TypeSpec::I32, tag("i32")),
-		value(TypeSpec::I16, tag("i16")),
-		value(TypeSpec::I8, tag("i8")),
-		// Unsigned integers
-		value(TypeSpec::U64, tag("u64")),
-		value(TypeSpec::U32, tag("u32")),
-		value(TypeSpec::U16, tag("u16")),
-		value(TypeSpec::U8, tag("u8")),
-		// Floating point
-		value(TypeSpec::F64, tag("f64")),
-		value(TypeSpec::F32, tag("f32")),
-		// Other types
-		value(TypeSpec::Bool, tag("bool")),
-		value(TypeSpec::Email, tag("email")),
-		value(TypeSpec::Date, tag("date")),
-	))
-	.parse(input)
+	map(identifier, TypeSpec::from_name).parse(input)
 }
 
 /// Parse a typed parameter: <type:name>
@@ -266,8 +254,14 @@ fn url_pattern(input: &str) -> IResult<&str, UrlPatternAst> {
 /// - Correct type specifier syntax
 /// - Django-style parameter placement
 ///
+/// Type specifiers must be built-in types or one of `converters`, the custom
+/// converters declared in the macro invocation.
+///
 /// Returns an AST representation of the pattern if valid, or a descriptive error message.
-pub(crate) fn parse_and_validate(pattern: &str) -> std::result::Result<UrlPatternAst, String> {
+pub(crate) fn parse_and_validate(
+	pattern: &str,
+	converters: &[String],
+) -> std::result::Result<UrlPatternAst, String> {
 	// Pre-validation: Check for common errors before parsing
 	if pattern.contains("{{") {
 		return Err("Nested braces are not allowed in URL patterns. Use single braces like {id}, not {{id}}".to_string());
@@ -310,15 +304,19 @@ pub(crate) fn parse_and_validate(pattern: &str) -> std::result::Result<UrlPatter
 	}
 
 	// Check for invalid type specifiers
-	if let Some(type_start) = pattern.find("<")
-		&& let Some(type_end) = pattern[type_start..].find(":")
-	{
+	for (type_start, _) in pattern.match_indices('<') {
+		let Some(type_end) = pattern[type_start..].find(':') else {
+			continue;
+		};
 		let type_spec = &pattern[type_start + 1..type_start + type_end];
-		if TypeSpec::from_str(type_spec).is_none() {
+		if TypeSpec::from_str(type_spec).is_none() && !converters.iter().any(|c| c == type_spec) {
+			let mut valid_types = TypeSpec::valid_types().join(", ");
+			if !converters.is_empty() {
+				valid_types = format!("{}, {}", valid_types, converters.join(", "));
+			}
 			return Err(format!(
 				"Invalid type specifier '{}'. Valid types are: {}",
-				type_spec,
-				TypeSpec::valid_types().join(", ")
+				type_spec, valid_types
 			));
 		}
 	}
@@ -386,19 +384,50 @@ pub(crate) fn parse_and_validate(pattern: &str) -> std::result::Result<UrlPatter
 // ============================================================================
 
 /// Parsed URL pattern with validation
+///
+/// Accepts `"pattern"` or `"pattern", converters = [name, ...]`, the latter
+/// declaring custom converters registered at runtime.
 struct UrlPattern {
 	pattern: String,
 	_ast: UrlPatternAst,
 }
 
+/// Parse the optional `converters = [name, ...]` argument
+fn parse_converters(input: ParseStream) -> Result<Vec<String>> {
+	if input.is_empty() {
+		return Ok(Vec::new());
+	}
+	input.parse::<Token![,]>()?;
+	if input.is_empty() {
+		return Ok(Vec::new());
+	}
+
+	let key: Ident = input.parse()?;
+	if key != "converters" {
+		return Err(Error::new(
+			key.span(),
+			"Expected `converters = [...]` after the URL pattern",
+		));
+	}
+	input.parse::<Token![=]>()?;
+	let content;
+	bracketed!(content in input);
+	let names = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+	if !input.is_empty() {
+		input.parse::<Token![,]>()?;
+	}
+	Ok(names.iter().map(Ident::to_string).collect())
+}
+
 impl Parse for UrlPattern {
 	fn parse(input: ParseStream) -> Result<Self> {
 		let pattern_lit: LitStr = input.parse()?;
 		let pattern = pattern_lit.value();
 		let span = pattern_lit.span();
+		let converters = parse_converters(input)?;
 
 		// Parse and validate the pattern at compile time
-		let ast = parse_and_validate(&pattern).map_err(|e| Error::new(span, e))?;
+		let ast = parse_and_validate(&pattern, &converters).map_err(|e| Error::new(span, e))?;
 
 		Ok(UrlPattern { pattern, _ast: ast })
 	}
@@ -427,7 +456,7 @@ mod tests {
 	// AST parsing tests
 	#[test]
 	fn test_parse_simple_literal() {
-		let result = parse_and_validate("polls/");
+		let result = parse_and_validate("polls/", &[]);
 		let ast = result.unwrap();
 		assert_eq!(ast.segments.len(), 1);
 		assert!(matches!(ast.segments[0], Segment::Literal(_)));
@@ -435,7 +464,7 @@ mod tests {
 
 	#[test]
 	fn test_parse_simple_parameter() {
-		let result = parse_and_validate("polls/{id}/");
+		let result = parse_and_validate("polls/{id}/", &[]);
 		let ast = result.unwrap();
 		assert_eq!(ast.segments.len(), 3);
 
@@ -450,7 +479,7 @@ mod tests {
 
 	#[test]
 	fn test_parse_typed_parameter() {
-		let result = parse_and_validate("polls/{<int:question_id>}/");
+		let result = parse_and_validate("polls/{<int:question_id>}/", &[]);
 		let ast = result.unwrap();
 
 		// Find the parameter segment
@@ -469,7 +498,7 @@ mod tests {
 
 	#[test]
 	fn test_parse_multiple_parameters() {
-		let result = parse_and_validate("users/{user_id}/posts/{post_id}/");
+		let result = parse_and_validate("users/{user_id}/posts/{post_id}/", &[]);
 		let ast = result.unwrap();
 
 		let params: Vec<&Parameter> = ast
@@ -489,21 +518,21 @@ mod tests {
 	// Error case tests
 	#[test]
 	fn test_invalid_unclosed_brace() {
-		let result = parse_and_validate("polls/{id");
+		let result = parse_and_validate("polls/{id", &[]);
 		assert!(result.is_err());
 		assert!(result.unwrap_err().contains("Unclosed brace"));
 	}
 
 	#[test]
 	fn test_invalid_unmatched_closing_brace() {
-		let result = parse_and_validate("polls/id}/");
+		let result = parse_and_validate("polls/id}/", &[]);
 		assert!(result.is_err());
 		assert!(result.unwrap_err().contains("closing brace"));
 	}
 
 	#[test]
 	fn test_invalid_empty_param() {
-		let result = parse_and_validate("polls/{}/");
+		let result = parse_and_validate("polls/{}/", &[]);
 		assert!(result.is_err());
 		let err = result.unwrap_err();
 		eprintln!("Error message: {}", err);
@@ -512,20 +541,20 @@ mod tests {
 
 	#[test]
 	fn test_invalid_nested_braces() {
-		let result = parse_and_validate("polls/{{id}}/");
+		let result = parse_and_validate("polls/{{id}}/", &[]);
 		assert!(result.is_err());
 		assert!(result.unwrap_err().contains("Nested braces"));
 	}
 
 	#[test]
 	fn test_invalid_param_starting_with_number() {
-		let result = parse_and_validate("polls/{1id}/");
+		let result = parse_and_validate("polls/{1id}/", &[]);
 		assert!(result.is_err());
 	}
 
 	#[test]
 	fn test_invalid_type_specifier() {
-		let result = parse_and_validate("polls/{<invalid:id>}/");
+		let result = parse_and_validate("polls/{<invalid:id>}/", &[]);
 		assert!(result.is_err());
 		let err = result.unwrap_err();
 		eprintln!("Error message: {}", err);
@@ -534,11 +563,34 @@ mod tests {
 
 	#[test]
 	fn test_reinhardt_style_outside_braces() {
-		let result = parse_and_validate("polls/<int:id>/");
+		let result = parse_and_validate("polls/<int:id>/", &[]);
 		assert!(result.is_err());
 		assert!(result.unwrap_err().contains("inside braces"));
 	}
 
+	#[test]
+	fn test_declared_custom_converter() {
+		let converters = vec!["ulid".to_string()];
+		let ast = parse_and_validate("events/{<ulid:id>}/{<int:page>}/", &converters).unwrap();
+
+		let types: Vec<_> = ast
+			.segments
+			.iter()
+			.filter_map(|s| match s {
+				Segment::Parameter(p) => p.type_spec.clone(),
+				_ => None,
+			})
+			.collect();
+		assert_eq!(
+			types,
+			vec![TypeSpec::Custom("ulid".to_string()), TypeSpec::Int]
+		);
+
+		// Undeclared converters are rejected, including after valid ones
+		let err = parse_and_validate("events/{<int:page>}/{<ulid:id>}/", &[]).unwrap_err();
+		assert!(err.contains("Invalid type specifier 'ulid'"));
+	}
+
 	// All type specifiers
 	#[test]
 	fn test_all_type_specifiers() {
@@ -551,7 +603,7 @@ mod tests {
 		];
 
 		for (pattern, expected_type) in patterns {
-			let result = parse_and_validate(pattern);
+			let result = parse_and_validate(pattern, &[]);
 			assert!(result.is_ok(), "Failed to parse: {}", pattern);
 
 			let ast = result.unwrap();
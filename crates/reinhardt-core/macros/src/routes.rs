@@ -20,6 +20,8 @@ struct RouteOptions {
 	use_inject: bool,
 	/// Route name for URL reversal
 	name: Option<String>,
+	/// Custom path converters declared with `converters = [name, ...]`
+	converters: Vec<String>,
 }
 
 /// Information about parameter extractors
@@ -39,12 +41,39 @@ struct InjectInfo {
 }
 
 /// Validate a route path at compile time
-fn validate_route_path(path: &str, span: Span) -> Result<()> {
-	path_macro::parse_and_validate(path)
+///
+/// `converters` are the custom converters declared on the route.
+fn validate_route_path(path: &str, span: Span, converters: &[String]) -> Result<()> {
+	path_macro::parse_and_validate(path, converters)
 		.map(|_| ())
 		.map_err(|e| Error::new(span, format!("Invalid route path: {}", e)))
 }
 
+/// Parse the converter names of `converters = [name, ...]`
+fn parse_converter_names(expr: &Expr) -> Result<Vec<String>> {
+	let error = || {
+		Error::new_spanned(
+			expr,
+			"converters must be a list of converter names, e.g. converters = [ulid]",
+		)
+	};
+	let Expr::Array(array) = expr else {
+		return Err(error());
+	};
+	array
+		.elems
+		.iter()
+		.map(|elem| match elem {
+			Expr::Path(path) => path
+				.path
+				.get_ident()
+				.map(|i| i.to_string())
+				.ok_or_else(error),
+			_ => Err(error()),
+		})
+		.collect()
+}
+
 /// Convert snake_case function name to PascalCase + View suffix
 fn fn_name_to_view_type(fn_name: &str) -> String {
 	let pascal_case: String = fn_name
@@ -446,9 +475,7 @@ fn route_impl(method: &str, args: TokenStream, input: ItemFn) -> Result<TokenStr
 	// Handle the common case: #[get("/users/{id}")]
 	// Try to parse as a single string literal first
 	if let Ok(lit) = syn::parse2::<LitStr>(args.clone()) {
-		let path_str = lit.value();
-		validate_route_path(&path_str, lit.span())?;
-		path = Some((path_str, lit.span()));
+		path = Some((lit.value(), lit.span()));
 	} else {
 		// Parse path and options: #[get("/path", use_inject = true)]
 		let parser = Punctuated::<Expr, Token![,]>::parse_terminated;
@@ -459,9 +486,7 @@ fn route_impl(method: &str, args: TokenStream, input: ItemFn) -> Result<TokenStr
 					Expr::Lit(ExprLit {
 						lit: Lit::Str(lit), ..
 					}) if i == 0 => {
-						let path_str = lit.value();
-						validate_route_path(&path_str, lit.span())?;
-						path = Some((path_str, lit.span()));
+						path = Some((lit.value(), lit.span()));
 					}
 					// use_inject = true/false or name = "xxx"
 					Expr::Assign(assign) => {
@@ -492,6 +517,8 @@ fn route_impl(method: &str, args: TokenStream, input: ItemFn) -> Result<TokenStr
 										"name must be a string literal",
 									));
 								}
+							} else if path_expr.path.is_ident("converters") {
+								options.converters = parse_converter_names(&assign.right)?;
 							}
 						}
 					}
@@ -506,9 +533,7 @@ fn route_impl(method: &str, args: TokenStream, input: ItemFn) -> Result<TokenStr
 				match meta {
 					Meta::Path(p) => {
 						if let Some(ident) = p.get_ident() {
-							let path_str = ident.to_string();
-							validate_route_path(&path_str, p.span())?;
-							path = Some((path_str, p.span()));
+							path = Some((ident.to_string(), p.span()));
 						}
 					}
 					Meta::NameValue(nv) if nv.path.is_ident("path") => {
@@ -516,9 +541,7 @@ fn route_impl(method: &str, args: TokenStream, input: ItemFn) -> Result<TokenStr
 							lit: Lit::Str(lit), ..
 						}) = &nv.value
 						{
-							let path_str = lit.value();
-							validate_route_path(&path_str, lit.span())?;
-							path = Some((path_str, lit.span()));
+							path = Some((lit.value(), lit.span()));
 						}
 					}
 					_ => {}
@@ -526,6 +549,10 @@ fn route_impl(method: &str, args: TokenStream, input: ItemFn) -> Result<TokenStr
 			}
 		}
 	}
+	// Validate once all options, including the converters, are known
+	if let Some((path_str, span)) = &path {
+		validate_route_path(path_str, *span, &options.converters)?;
+	}
 
 	// Detect extractors
 	let extractors = detect_extractors(&input.sig.inputs);
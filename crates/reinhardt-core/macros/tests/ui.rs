@@ -126,3 +126,11 @@ fn test_app_config_fail() {
 	let t = trybuild::TestCases::new();
 	t.compile_fail("tests/ui/app_config/fail/*.rs");
 }
+
+// ===== Cached =====
+
+#[test]
+fn test_cached_fail() {
+	let t = trybuild::TestCases::new();
+	t.compile_fail("tests/ui/cached/fail/*.rs");
+}
@@ -0,0 +1,12 @@
+//! Test that #[cached] without a cache backend produces a compile error
+//!
+//! There is no implicit process-wide cache; the backend must be named.
+
+use reinhardt_macros::cached;
+
+#[cached(ttl = 60)]
+async fn square(n: u64) -> u64 {
+	n * n
+}
+
+fn main() {}
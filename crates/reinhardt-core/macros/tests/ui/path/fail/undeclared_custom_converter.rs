@@ -0,0 +1,7 @@
+// Test: URL pattern with a custom converter that was not declared should fail
+
+use reinhardt_macros::path;
+
+fn main() {
+	let pattern = path!("events/{<ulid:id>}/", converters = [day]);
+}
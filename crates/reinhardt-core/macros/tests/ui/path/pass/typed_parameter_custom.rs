@@ -0,0 +1,8 @@
+// Test: URL pattern with a declared custom converter
+
+use reinhardt_macros::path;
+
+fn main() {
+	let pattern = path!("events/{<ulid:id>}/{<int:page>}/", converters = [ulid]);
+	assert_eq!(pattern, "events/{<ulid:id>}/{<int:page>}/");
+}
@@ -0,0 +1,8 @@
+use reinhardt_macros::get;
+
+#[get("/events/{<ulid:id>}/", converters = [day])]
+async fn event_detail() -> Result<(), ()> {
+	Ok(())
+}
+
+fn main() {}
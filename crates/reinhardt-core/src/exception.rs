@@ -1,6 +1,8 @@
 use thiserror::Error;
 
+pub mod negotiation_error;
 pub mod param_error;
+pub use negotiation_error::NegotiationErrorContext;
 pub use param_error::{ParamErrorContext, ParamType};
 
 /// The main error type for the Reinhardt framework.
@@ -148,6 +150,35 @@ pub enum Error {
 	#[error("Method not allowed: {0}")]
 	MethodNotAllowed(String),
 
+	/// No response representation satisfies the request's Accept headers (status code: 406)
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_core::exception::{Error, NegotiationErrorContext};
+	///
+	/// let ctx = NegotiationErrorContext::accept(Some("text/csv"), ["application/json"]);
+	/// let error = Error::NotAcceptable(Box::new(ctx));
+	/// assert_eq!(error.status_code(), 406);
+	/// assert!(error.to_string().contains("supported: application/json"));
+	/// ```
+	#[error("Not acceptable: {}", .0.format_error())]
+	NotAcceptable(Box<NegotiationErrorContext>),
+
+	/// The request body's Content-Type cannot be parsed (status code: 415)
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_core::exception::{Error, NegotiationErrorContext};
+	///
+	/// let ctx = NegotiationErrorContext::content_type(Some("text/csv"), ["application/json"]);
+	/// let error = Error::UnsupportedMediaType(Box::new(ctx));
+	/// assert_eq!(error.status_code(), 415);
+	/// ```
+	#[error("Unsupported media type: {}", .0.format_error())]
+	UnsupportedMediaType(Box<NegotiationErrorContext>),
+
 	/// Conflict errors (status code: 409)
 	///
 	/// This error occurs when the request could not be completed due to a
@@ -324,6 +355,8 @@ pub enum ErrorKind {
 	Authorization,
 	NotFound,
 	MethodNotAllowed,
+	NotAcceptable,
+	UnsupportedMediaType,
 	Conflict,
 	Internal,
 	ImproperlyConfigured,
@@ -346,6 +379,8 @@ impl Error {
 	/// - `Authorization`: 403 (Forbidden)
 	/// - `NotFound`, `TemplateNotFound`: 404 (Not Found)
 	/// - `MethodNotAllowed`: 405 (Method Not Allowed)
+	/// - `NotAcceptable`: 406 (Not Acceptable)
+	/// - `UnsupportedMediaType`: 415 (Unsupported Media Type)
 	/// - `Conflict`: 409 (Conflict)
 	/// - `Database`, `Internal`, `ImproperlyConfigured`, `Other`: 500 (Internal Server Error)
 	///
@@ -392,6 +427,8 @@ impl Error {
 			Error::NotFound(_) => 404,
 			Error::TemplateNotFound(_) => 404,
 			Error::MethodNotAllowed(_) => 405,
+			Error::NotAcceptable(_) => 406,
+			Error::UnsupportedMediaType(_) => 415,
 			Error::Conflict(_) => 409,
 			Error::Internal(_) => 500,
 			Error::ImproperlyConfigured(_) => 500,
@@ -407,6 +444,14 @@ impl Error {
 		}
 	}
 
+	/// Returns the negotiation details for 406 and 415 errors.
+	pub fn negotiation_context(&self) -> Option<&NegotiationErrorContext> {
+		match self {
+			Error::NotAcceptable(ctx) | Error::UnsupportedMediaType(ctx) => Some(ctx),
+			_ => None,
+		}
+	}
+
 	/// Returns the categorical `ErrorKind` for this error.
 	pub fn kind(&self) -> ErrorKind {
 		match self {
@@ -419,6 +464,8 @@ impl Error {
 			Error::NotFound(_) => ErrorKind::NotFound,
 			Error::TemplateNotFound(_) => ErrorKind::NotFound,
 			Error::MethodNotAllowed(_) => ErrorKind::MethodNotAllowed,
+			Error::NotAcceptable(_) => ErrorKind::NotAcceptable,
+			Error::UnsupportedMediaType(_) => ErrorKind::UnsupportedMediaType,
 			Error::Conflict(_) => ErrorKind::Conflict,
 			Error::Internal(_) => ErrorKind::Internal,
 			Error::ImproperlyConfigured(_) => ErrorKind::ImproperlyConfigured,
@@ -631,6 +678,17 @@ mod tests {
 			405
 		);
 
+		// 406/415 errors
+		let ctx = NegotiationErrorContext::accept(Some("text/csv"), ["application/json"]);
+		assert_eq!(
+			Error::NotAcceptable(Box::new(ctx.clone())).status_code(),
+			406
+		);
+		assert_eq!(
+			Error::UnsupportedMediaType(Box::new(ctx)).status_code(),
+			415
+		);
+
 		// 500 errors
 		assert_eq!(Error::Database("test".to_string()).status_code(), 500);
 		assert_eq!(Error::Internal("test".to_string()).status_code(), 500);
@@ -0,0 +1,139 @@
+//! Negotiation Error Context
+//!
+//! This module provides structured details for content negotiation failures
+//! (406 Not Acceptable and 415 Unsupported Media Type). The context records
+//! which request header caused the rejection, the value that was received and
+//! the values the server supports, so API consumers can self-diagnose.
+
+use serde_json::{Value, json};
+
+/// Detailed context for content negotiation failures
+#[derive(Debug, Clone, PartialEq)]
+pub struct NegotiationErrorContext {
+	/// Request header that caused the rejection (e.g. `Accept`)
+	pub header: String,
+	/// Value received in the header, if any
+	pub received: Option<String>,
+	/// Values the server can produce or consume
+	pub supported: Vec<String>,
+}
+
+impl NegotiationErrorContext {
+	/// Create a new NegotiationErrorContext
+	pub fn new<I, S>(header: impl Into<String>, received: Option<&str>, supported: I) -> Self
+	where
+		I: IntoIterator<Item = S>,
+		S: Into<String>,
+	{
+		Self {
+			header: header.into(),
+			received: received.map(str::to_string),
+			supported: supported.into_iter().map(Into::into).collect(),
+		}
+	}
+
+	/// Context for an `Accept` header no renderer can satisfy
+	pub fn accept<I, S>(received: Option<&str>, supported: I) -> Self
+	where
+		I: IntoIterator<Item = S>,
+		S: Into<String>,
+	{
+		Self::new("Accept", received, supported)
+	}
+
+	/// Context for an `Accept-Encoding` header no encoding can satisfy
+	pub fn accept_encoding<I, S>(received: Option<&str>, supported: I) -> Self
+	where
+		I: IntoIterator<Item = S>,
+		S: Into<String>,
+	{
+		Self::new("Accept-Encoding", received, supported)
+	}
+
+	/// Context for an `Accept-Language` header no language can satisfy
+	pub fn accept_language<I, S>(received: Option<&str>, supported: I) -> Self
+	where
+		I: IntoIterator<Item = S>,
+		S: Into<String>,
+	{
+		Self::new("Accept-Language", received, supported)
+	}
+
+	/// Context for a `Content-Type` no parser can handle
+	pub fn content_type<I, S>(received: Option<&str>, supported: I) -> Self
+	where
+		I: IntoIterator<Item = S>,
+		S: Into<String>,
+	{
+		Self::new("Content-Type", received, supported)
+	}
+
+	/// Whether the rejection concerns the request body (415) rather than the response (406)
+	pub fn is_request_body(&self) -> bool {
+		self.header.eq_ignore_ascii_case("Content-Type")
+	}
+
+	/// Format error message
+	pub fn format_error(&self) -> String {
+		let received = self.received.as_deref().unwrap_or("none");
+		if self.supported.is_empty() {
+			format!("Unsupported {} '{}'", self.header, received)
+		} else {
+			format!(
+				"Unsupported {} '{}' (supported: {})",
+				self.header,
+				received,
+				self.supported.join(", ")
+			)
+		}
+	}
+
+	/// Structured details for response bodies
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_core::exception::NegotiationErrorContext;
+	///
+	/// let ctx = NegotiationErrorContext::accept(Some("text/csv"), ["application/json"]);
+	/// let details = ctx.to_json();
+	/// assert_eq!(details["header"], "Accept");
+	/// assert_eq!(details["received"], "text/csv");
+	/// assert_eq!(details["supported"][0], "application/json");
+	/// ```
+	pub fn to_json(&self) -> Value {
+		json!({
+			"header": self.header,
+			"received": self.received,
+			"supported": self.supported,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_format_error_lists_supported_values() {
+		let ctx = NegotiationErrorContext::content_type(
+			Some("text/csv"),
+			["application/json", "application/x-www-form-urlencoded"],
+		);
+
+		assert!(ctx.is_request_body());
+		assert_eq!(
+			ctx.format_error(),
+			"Unsupported Content-Type 'text/csv' (supported: application/json, application/x-www-form-urlencoded)"
+		);
+	}
+
+	#[test]
+	fn test_format_error_without_header_value() {
+		let ctx = NegotiationErrorContext::accept_language(None, Vec::<String>::new());
+
+		assert!(!ctx.is_request_body());
+		assert_eq!(ctx.format_error(), "Unsupported Accept-Language 'none'");
+		assert!(ctx.to_json()["received"].is_null());
+	}
+}
@@ -14,6 +14,8 @@ use serde::Serialize;
 /// Messages context for template rendering
 ///
 /// This struct wraps messages for easy serialization into template contexts.
+/// Messages are core [`Message`]s by default; frameworks with their own
+/// message representation can wrap that instead.
 ///
 /// ## Example
 ///
@@ -26,12 +28,12 @@ use serde::Serialize;
 /// assert_eq!(context.messages.len(), 2);
 /// ```
 #[derive(Debug, Clone, Serialize)]
-pub struct MessagesContext {
+pub struct MessagesContext<M = Message> {
 	/// All messages to be displayed
-	pub messages: Vec<Message>,
+	pub messages: Vec<M>,
 }
 
-impl MessagesContext {
+impl<M> MessagesContext<M> {
 	/// Create a new messages context
 	///
 	/// # Example
@@ -44,26 +46,10 @@ impl MessagesContext {
 	/// let context = MessagesContext::new(messages);
 	/// assert_eq!(context.messages.len(), 1);
 	/// ```
-	pub fn new(messages: Vec<Message>) -> Self {
+	pub fn new(messages: Vec<M>) -> Self {
 		Self { messages }
 	}
 
-	/// Create an empty messages context
-	///
-	/// # Example
-	///
-	/// ```rust
-	/// use reinhardt_core::messages::context::MessagesContext;
-	///
-	/// let context = MessagesContext::empty();
-	/// assert_eq!(context.messages.len(), 0);
-	/// ```
-	pub fn empty() -> Self {
-		Self {
-			messages: Vec::new(),
-		}
-	}
-
 	/// Check if there are any messages
 	///
 	/// # Example
@@ -101,6 +87,32 @@ impl MessagesContext {
 	}
 }
 
+impl MessagesContext {
+	/// Create an empty messages context
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_core::messages::context::MessagesContext;
+	///
+	/// let context = MessagesContext::empty();
+	/// assert_eq!(context.messages.len(), 0);
+	/// ```
+	pub fn empty() -> Self {
+		Self {
+			messages: Vec::new(),
+		}
+	}
+}
+
+impl<M> Default for MessagesContext<M> {
+	fn default() -> Self {
+		Self {
+			messages: Vec::new(),
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -4,7 +4,10 @@ use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
 /// Message levels (similar to Django)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Levels are compared, ordered and hashed by their numeric value, so
+/// `Custom(25)` equals `Success`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[repr(i32)]
 #[derive(Default)]
 pub enum Level {
@@ -87,6 +90,20 @@ impl Level {
 	}
 }
 
+impl PartialEq for Level {
+	fn eq(&self, other: &Self) -> bool {
+		self.value() == other.value()
+	}
+}
+
+impl Eq for Level {}
+
+impl std::hash::Hash for Level {
+	fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+		self.value().hash(state);
+	}
+}
+
 impl PartialOrd for Level {
 	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
 		Some(self.cmp(other))
@@ -208,4 +225,21 @@ mod tests {
 		assert!(custom_high > info);
 		assert!(custom_high < error);
 	}
+
+	#[test]
+	fn test_level_equality_matches_ordering() {
+		use std::collections::{BTreeSet, HashSet};
+
+		assert_eq!(Level::Custom(25), Level::Success);
+		assert_eq!(
+			Level::Custom(25).cmp(&Level::Success),
+			std::cmp::Ordering::Equal
+		);
+		assert_ne!(Level::Custom(35), Level::Warning);
+
+		let hashed: HashSet<Level> = [Level::Custom(40), Level::Error].into_iter().collect();
+		assert_eq!(hashed.len(), 1);
+		let ordered: BTreeSet<Level> = [Level::Custom(10), Level::Debug].into_iter().collect();
+		assert_eq!(ordered.len(), 1);
+	}
 }
@@ -10,6 +10,7 @@
 //! - **Language negotiation**: Support for Accept-Language header with quality factors
 //! - **Encoding negotiation**: Support for Accept-Encoding header (Gzip, Brotli, Deflate, Identity)
 //! - **Cache optimization**: Caching of negotiation results with TTL support
+//! - **Pluggable strategies**: Override renderer/parser selection per client or vendor media type
 
 pub mod accept;
 pub mod cache;
@@ -18,11 +19,15 @@ pub mod encoding;
 pub mod language;
 pub mod media_type;
 pub mod negotiator;
+pub mod strategy;
 
 pub use media_type::MediaType;
 pub use negotiator::{
 	BaseContentNegotiation, BaseNegotiator, ContentNegotiator, NegotiationError, RendererInfo,
 };
+pub use strategy::{
+	IgnoreAcceptStrategy, NegotiationContext, NegotiationStrategyRegistry, VendorMediaTypeStrategy,
+};
 
 /// Re-export commonly used types
 pub mod prelude {
@@ -32,4 +37,5 @@ pub mod prelude {
 	pub use super::language::*;
 	pub use super::media_type::*;
 	pub use super::negotiator::*;
+	pub use super::strategy::*;
 }
@@ -1,5 +1,7 @@
 //! Encoding negotiation based on Accept-Encoding header
 
+use crate::exception::{Error, NegotiationErrorContext, Result};
+
 /// Supported encoding types
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Encoding {
@@ -276,6 +278,101 @@ impl EncodingNegotiator {
 	pub fn select_best(&self, accept_encoding: &str, available: &[Encoding]) -> Encoding {
 		self.negotiate(accept_encoding, available)
 	}
+
+	/// Selects an encoding the client accepts, failing with 406 Not Acceptable
+	///
+	/// Unlike [`negotiate`](Self::negotiate), encodings refused with `q=0`
+	/// are never chosen. `identity` stays acceptable unless it is refused
+	/// explicitly or through `*;q=0`, and an empty header accepts anything.
+	/// Ties go to the server preference order.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_core::negotiation::encoding::{EncodingNegotiator, Encoding};
+	///
+	/// let negotiator = EncodingNegotiator::new();
+	/// let available = vec![Encoding::Gzip, Encoding::Identity];
+	///
+	/// let result = negotiator.select_acceptable("br, gzip", &available);
+	/// assert_eq!(result.unwrap(), Encoding::Gzip);
+	///
+	/// let err = negotiator
+	///     .select_acceptable("br, *;q=0", &available)
+	///     .unwrap_err();
+	/// assert_eq!(err.status_code(), 406);
+	/// assert_eq!(err.negotiation_context().unwrap().header, "Accept-Encoding");
+	/// ```
+	pub fn select_acceptable(
+		&self,
+		accept_encoding: &str,
+		available: &[Encoding],
+	) -> Result<Encoding> {
+		let header = accept_encoding.trim();
+		let mut explicit = Vec::new();
+		let mut wildcard = None;
+		for item in header
+			.split(',')
+			.map(str::trim)
+			.filter(|item| !item.is_empty())
+		{
+			let Some(parsed) = EncodingQuality::parse(item) else {
+				continue;
+			};
+			if item
+				.split(';')
+				.next()
+				.is_some_and(|name| name.trim() == "*")
+			{
+				wildcard = Some(parsed.quality);
+			} else {
+				explicit.push(parsed);
+			}
+		}
+
+		let quality = |encoding: &Encoding| {
+			if header.is_empty() {
+				return 1.0;
+			}
+			explicit
+				.iter()
+				.find(|requested| requested.encoding == *encoding)
+				.map(|requested| requested.quality)
+				.or(wildcard)
+				.unwrap_or(if *encoding == Encoding::Identity {
+					1.0
+				} else {
+					0.0
+				})
+		};
+		let rank = |encoding: &Encoding| {
+			self.preference_order
+				.iter()
+				.position(|preferred| preferred == encoding)
+				.unwrap_or(usize::MAX)
+		};
+
+		let mut best: Option<(&Encoding, f32)> = None;
+		for encoding in available {
+			let q = quality(encoding);
+			if q <= 0.0 {
+				continue;
+			}
+			let better = best.is_none_or(|(current, best_q)| {
+				q > best_q || (q == best_q && rank(encoding) < rank(current))
+			});
+			if better {
+				best = Some((encoding, q));
+			}
+		}
+
+		best.map(|(encoding, _)| encoding.clone()).ok_or_else(|| {
+			Error::NotAcceptable(Box::new(NegotiationErrorContext::accept_encoding(
+				Some(header).filter(|header| !header.is_empty()),
+				available.iter().map(Encoding::as_str),
+			)))
+		})
+	}
 }
 
 impl Default for EncodingNegotiator {
@@ -288,6 +385,43 @@ impl Default for EncodingNegotiator {
 mod tests {
 	use super::*;
 
+	#[test]
+	fn test_select_acceptable_honours_refusals() {
+		let negotiator = EncodingNegotiator::new();
+		let available = vec![Encoding::Gzip, Encoding::Brotli, Encoding::Identity];
+
+		assert_eq!(
+			negotiator.select_acceptable("", &available).unwrap(),
+			Encoding::Brotli
+		);
+		assert_eq!(
+			negotiator
+				.select_acceptable("br;q=0, gzip", &available)
+				.unwrap(),
+			Encoding::Gzip
+		);
+		// Identity is implicitly acceptable
+		assert_eq!(
+			negotiator.select_acceptable("zstd", &available).unwrap(),
+			Encoding::Identity
+		);
+
+		let err = negotiator
+			.select_acceptable(
+				"gzip;q=0, identity;q=0",
+				&[Encoding::Gzip, Encoding::Identity],
+			)
+			.unwrap_err();
+		assert!(matches!(err, Error::NotAcceptable(_)));
+		assert_eq!(
+			err.negotiation_context(),
+			Some(&NegotiationErrorContext::accept_encoding(
+				Some("gzip;q=0, identity;q=0"),
+				["gzip", "identity"]
+			))
+		);
+	}
+
 	#[test]
 	fn test_encoding_parse() {
 		assert_eq!(Encoding::parse("gzip"), Some(Encoding::Gzip));
@@ -1,5 +1,7 @@
 //! Language negotiation based on Accept-Language header
 
+use crate::exception::{Error, NegotiationErrorContext, Result};
+
 /// Represents a language with quality factor
 #[derive(Debug, Clone, PartialEq)]
 pub struct Language {
@@ -280,6 +282,62 @@ impl LanguageNegotiator {
 
 		matches
 	}
+
+	/// Selects a language the client accepts, failing with 406 Not Acceptable
+	///
+	/// Unlike [`negotiate`](Self::negotiate), there is no fallback when the
+	/// header names only unavailable languages; languages refused with `q=0`
+	/// are never chosen. An empty header selects the fallback language.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_core::negotiation::language::{LanguageNegotiator, Language};
+	///
+	/// let negotiator = LanguageNegotiator::new();
+	/// let available = vec![Language::new("en"), Language::new("fr")];
+	///
+	/// let result = negotiator.select_acceptable("de, fr;q=0.8", &available);
+	/// assert_eq!(result.unwrap().code, "fr");
+	///
+	/// let err = negotiator.select_acceptable("de", &available).unwrap_err();
+	/// assert_eq!(err.status_code(), 406);
+	/// assert_eq!(err.negotiation_context().unwrap().supported, vec!["en", "fr"]);
+	/// ```
+	pub fn select_acceptable(
+		&self,
+		accept_language: &str,
+		available: &[Language],
+	) -> Result<Language> {
+		let header = accept_language.trim();
+		if header.is_empty() {
+			return Ok(self.fallback.clone());
+		}
+
+		let mut requested = self.parse_accept_language(header);
+		requested.sort_by(|a, b| b.quality.partial_cmp(&a.quality).unwrap());
+		let refused = |language: &Language| {
+			requested
+				.iter()
+				.any(|r| r.quality <= 0.0 && r.code != "*" && r.matches(language))
+		};
+
+		requested
+			.iter()
+			.filter(|r| r.quality > 0.0)
+			.find_map(|r| {
+				available
+					.iter()
+					.find(|avail| r.matches(avail) && !refused(avail))
+			})
+			.cloned()
+			.ok_or_else(|| {
+				Error::NotAcceptable(Box::new(NegotiationErrorContext::accept_language(
+					Some(header),
+					available.iter().map(Language::tag),
+				)))
+			})
+	}
 }
 
 impl Default for LanguageNegotiator {
@@ -292,6 +350,36 @@ impl Default for LanguageNegotiator {
 mod tests {
 	use super::*;
 
+	#[test]
+	fn test_select_acceptable_reports_available_languages() {
+		let negotiator = LanguageNegotiator::new();
+		let available = vec![Language::with_region("en", "US"), Language::new("ja")];
+
+		assert_eq!(
+			negotiator.select_acceptable("", &available).unwrap().code,
+			"en"
+		);
+		assert_eq!(
+			negotiator
+				.select_acceptable("*, en;q=0", &available)
+				.unwrap()
+				.code,
+			"ja"
+		);
+
+		let err = negotiator
+			.select_acceptable("de, fr;q=0.5", &available)
+			.unwrap_err();
+		assert!(matches!(err, Error::NotAcceptable(_)));
+		assert_eq!(
+			err.negotiation_context(),
+			Some(&NegotiationErrorContext::accept_language(
+				Some("de, fr;q=0.5"),
+				["en-US", "ja"]
+			))
+		);
+	}
+
 	#[test]
 	fn test_language_parse() {
 		let lang = Language::parse("en-US;q=0.9").unwrap();
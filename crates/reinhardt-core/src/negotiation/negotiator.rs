@@ -2,6 +2,8 @@
 
 use super::accept::AcceptHeader;
 use super::media_type::MediaType;
+use super::strategy::NegotiationContext;
+use crate::exception::{Error, NegotiationErrorContext};
 
 /// Trait for renderers
 pub trait Renderer {
@@ -13,12 +15,14 @@ pub trait Renderer {
 #[derive(Debug, Clone, PartialEq)]
 pub enum NegotiationError {
 	NoSuitableRenderer,
+	NoSuitableParser,
 }
 
 impl std::fmt::Display for NegotiationError {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		match self {
 			NegotiationError::NoSuitableRenderer => write!(f, "No suitable renderer found"),
+			NegotiationError::NoSuitableParser => write!(f, "No suitable parser found"),
 		}
 	}
 }
@@ -26,7 +30,23 @@ impl std::fmt::Display for NegotiationError {
 impl std::error::Error for NegotiationError {}
 
 /// Base content negotiator trait
+///
+/// Renderers are selected from the `Accept` header and parsers from the
+/// `Content-Type`. Negotiators registered in a
+/// [`NegotiationStrategyRegistry`](super::NegotiationStrategyRegistry) only
+/// handle the requests their [`applies_to`](Self::applies_to) accepts, so a
+/// negotiator can serve particular clients or media types.
 pub trait BaseContentNegotiation {
+	/// Name used to identify the negotiator
+	fn name(&self) -> &str {
+		std::any::type_name::<Self>()
+	}
+
+	/// Whether this negotiator handles the request
+	fn applies_to(&self, _ctx: &NegotiationContext) -> bool {
+		true
+	}
+
 	fn select_parser(
 		&self,
 		_request: Option<&str>,
@@ -172,6 +192,79 @@ impl ContentNegotiator {
 
 		Err(NegotiationError::NoSuitableRenderer)
 	}
+	/// Select renderer based on Accept header, failing with 406 Not Acceptable
+	///
+	/// Like [`select_renderer`](Self::select_renderer), but the error records
+	/// the received `Accept` header and the media types that can be produced.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_core::exception::Error;
+	/// use reinhardt_core::negotiation::{ContentNegotiator, MediaType};
+	///
+	/// let negotiator = ContentNegotiator::new();
+	/// let renderers = vec![MediaType::new("application", "json")];
+	///
+	/// let err = negotiator
+	///     .select_acceptable_renderer(Some("text/csv"), &renderers)
+	///     .unwrap_err();
+	/// assert_eq!(err.status_code(), 406);
+	/// let ctx = err.negotiation_context().unwrap();
+	/// assert_eq!(ctx.header, "Accept");
+	/// assert_eq!(ctx.supported, vec!["application/json"]);
+	/// ```
+	pub fn select_acceptable_renderer(
+		&self,
+		accept_header: Option<&str>,
+		renderers: &[MediaType],
+	) -> crate::exception::Result<(MediaType, String)> {
+		self.select_renderer(accept_header, renderers).map_err(|_| {
+			Error::NotAcceptable(Box::new(NegotiationErrorContext::accept(
+				accept_header,
+				renderers.iter().map(ToString::to_string),
+			)))
+		})
+	}
+	/// Select the parser media type for a request's Content-Type
+	///
+	/// The most specific matching parser type wins, so `application/json`
+	/// is preferred over `*/*`; ties go to the first listed parser.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_core::negotiation::{ContentNegotiator, MediaType};
+	///
+	/// let negotiator = ContentNegotiator::new();
+	/// let parsers = vec![MediaType::new("*", "*"), MediaType::new("application", "json")];
+	///
+	/// let selected = negotiator
+	///     .select_parser(Some("application/json; charset=utf-8"), &parsers)
+	///     .unwrap();
+	/// assert_eq!(selected.subtype, "json");
+	/// assert!(negotiator.select_parser(None, &parsers).is_err());
+	/// ```
+	pub fn select_parser(
+		&self,
+		content_type: Option<&str>,
+		parsers: &[MediaType],
+	) -> Result<MediaType, NegotiationError> {
+		let request = content_type
+			.and_then(MediaType::parse)
+			.ok_or(NegotiationError::NoSuitableParser)?;
+
+		let mut selected: Option<&MediaType> = None;
+		for parser in parsers {
+			let matches = (parser.type_ == "*"
+				|| parser.type_.eq_ignore_ascii_case(&request.type_))
+				&& (parser.subtype == "*" || parser.subtype.eq_ignore_ascii_case(&request.subtype));
+			if matches && selected.is_none_or(|best| parser.precedence() > best.precedence()) {
+				selected = Some(parser);
+			}
+		}
+		selected.cloned().ok_or(NegotiationError::NoSuitableParser)
+	}
 	/// Filter renderers by format
 	///
 	/// # Examples
@@ -262,10 +355,59 @@ pub struct BaseNegotiator;
 
 impl BaseContentNegotiation for BaseNegotiator {}
 
+impl BaseContentNegotiation for ContentNegotiator {
+	fn name(&self) -> &str {
+		"default"
+	}
+
+	fn select_parser(
+		&self,
+		request: Option<&str>,
+		parsers: &[MediaType],
+	) -> Result<MediaType, NegotiationError> {
+		ContentNegotiator::select_parser(self, request, parsers)
+	}
+
+	fn select_renderer(
+		&self,
+		request: Option<&str>,
+		renderers: &[MediaType],
+	) -> Result<(MediaType, String), NegotiationError> {
+		ContentNegotiator::select_renderer(self, request, renderers)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
 
+	#[test]
+	fn test_select_acceptable_renderer_reports_supported_types() {
+		let negotiator = ContentNegotiator::new();
+		let renderers = vec![
+			MediaType::new("application", "json"),
+			MediaType::new("text", "html"),
+		];
+
+		let err = negotiator
+			.select_acceptable_renderer(Some("application/xml"), &renderers)
+			.unwrap_err();
+
+		assert!(matches!(err, Error::NotAcceptable(_)));
+		assert_eq!(
+			err.negotiation_context(),
+			Some(&NegotiationErrorContext::accept(
+				Some("application/xml"),
+				["application/json", "text/html"]
+			))
+		);
+		assert!(
+			negotiator
+				.select_acceptable_renderer(Some("text/html"), &renderers)
+				.is_ok()
+		);
+	}
+
 	#[test]
 	fn test_negotiate() {
 		let negotiator = ContentNegotiator::new();
@@ -0,0 +1,506 @@
+//! Pluggable negotiation strategies
+//!
+//! Strategies are [`BaseContentNegotiation`] implementations that handle
+//! only some requests. They are collected in a
+//! [`NegotiationStrategyRegistry`]; the first registered strategy that
+//! applies to a request wins and a [`ContentNegotiator`] handles the rest.
+
+use super::accept::AcceptHeader;
+use super::media_type::MediaType;
+use super::negotiator::{BaseContentNegotiation, ContentNegotiator, NegotiationError};
+use http::HeaderMap;
+use http::header::{ACCEPT, CONTENT_TYPE, USER_AGENT};
+use std::sync::Arc;
+
+/// Request information available to negotiation strategies
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NegotiationContext {
+	pub accept: Option<String>,
+	pub content_type: Option<String>,
+	pub user_agent: Option<String>,
+}
+
+impl NegotiationContext {
+	/// Creates an empty context
+	pub fn new() -> Self {
+		Self::default()
+	}
+	/// Builds a context from the Accept, Content-Type and User-Agent headers
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use http::HeaderMap;
+	/// use reinhardt_core::negotiation::NegotiationContext;
+	///
+	/// let mut headers = HeaderMap::new();
+	/// headers.insert("accept", "application/json".parse().unwrap());
+	/// headers.insert("user-agent", "curl/8.0".parse().unwrap());
+	///
+	/// let ctx = NegotiationContext::from_headers(&headers);
+	/// assert_eq!(ctx.accept.as_deref(), Some("application/json"));
+	/// assert_eq!(ctx.user_agent.as_deref(), Some("curl/8.0"));
+	/// assert!(ctx.content_type.is_none());
+	/// ```
+	pub fn from_headers(headers: &HeaderMap) -> Self {
+		let header = |name| {
+			headers
+				.get(name)
+				.and_then(|value: &http::HeaderValue| value.to_str().ok())
+				.map(str::to_string)
+		};
+		Self {
+			accept: header(ACCEPT),
+			content_type: header(CONTENT_TYPE),
+			user_agent: header(USER_AGENT),
+		}
+	}
+	/// Sets the Accept header value
+	pub fn with_accept(mut self, accept: impl Into<String>) -> Self {
+		self.accept = Some(accept.into());
+		self
+	}
+	/// Sets the Content-Type header value
+	pub fn with_content_type(mut self, content_type: impl Into<String>) -> Self {
+		self.content_type = Some(content_type.into());
+		self
+	}
+	/// Sets the User-Agent header value
+	pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+		self.user_agent = Some(user_agent.into());
+		self
+	}
+}
+
+/// Ignores the Accept header for clients identifying as given products
+///
+/// The User-Agent is read as product tokens (`Name/Version`, comments in
+/// parentheses are skipped), and a client matches when one of its product
+/// names equals a configured name, ignoring ASCII case. Matching clients get
+/// the preferred media type if a renderer offers it, otherwise the first
+/// renderer.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_core::negotiation::{
+///     BaseContentNegotiation, IgnoreAcceptStrategy, MediaType, NegotiationContext,
+/// };
+///
+/// let strategy = IgnoreAcceptStrategy::new(["LegacyClient"])
+///     .with_media_type(MediaType::new("application", "json"));
+/// let ctx = NegotiationContext::new()
+///     .with_accept("text/html")
+///     .with_user_agent("LegacyClient/1.2 (Windows NT 10.0)");
+///
+/// assert!(strategy.applies_to(&ctx));
+/// assert!(!strategy.applies_to(&ctx.clone().with_user_agent("NotLegacyClient/1.2")));
+/// let renderers = vec![MediaType::new("text", "html"), MediaType::new("application", "json")];
+/// let (selected, _) = strategy.select_renderer(Some("text/html"), &renderers).unwrap();
+/// assert_eq!(selected.subtype, "json");
+/// ```
+pub struct IgnoreAcceptStrategy {
+	products: Vec<String>,
+	media_type: Option<MediaType>,
+}
+
+impl IgnoreAcceptStrategy {
+	/// Creates a strategy for clients whose User-Agent names any of the `products`
+	pub fn new<I, S>(products: I) -> Self
+	where
+		I: IntoIterator<Item = S>,
+		S: Into<String>,
+	{
+		Self {
+			products: products.into_iter().map(Into::into).collect(),
+			media_type: None,
+		}
+	}
+	/// Prefers `media_type` over the first renderer
+	pub fn with_media_type(mut self, media_type: MediaType) -> Self {
+		self.media_type = Some(media_type);
+		self
+	}
+}
+
+/// Product names of a User-Agent, e.g. `Mozilla` and `Firefox` for
+/// `Mozilla/5.0 (X11; Linux x86_64) Firefox/120.0`
+fn user_agent_products(user_agent: &str) -> Vec<&str> {
+	let mut products = Vec::new();
+	let mut depth = 0usize;
+	for token in user_agent.split_whitespace() {
+		if depth == 0 && !token.starts_with('(') {
+			products.push(token.split('/').next().unwrap_or(token));
+		}
+		depth += token.matches('(').count();
+		depth = depth.saturating_sub(token.matches(')').count());
+	}
+	products
+}
+
+impl BaseContentNegotiation for IgnoreAcceptStrategy {
+	fn name(&self) -> &str {
+		"ignore_accept"
+	}
+
+	fn applies_to(&self, ctx: &NegotiationContext) -> bool {
+		ctx.user_agent.as_deref().is_some_and(|agent| {
+			user_agent_products(agent).into_iter().any(|name| {
+				self.products
+					.iter()
+					.any(|product| product.eq_ignore_ascii_case(name))
+			})
+		})
+	}
+
+	fn select_parser(
+		&self,
+		content_type: Option<&str>,
+		parsers: &[MediaType],
+	) -> Result<MediaType, NegotiationError> {
+		ContentNegotiator::new().select_parser(content_type, parsers)
+	}
+
+	fn select_renderer(
+		&self,
+		_accept: Option<&str>,
+		renderers: &[MediaType],
+	) -> Result<(MediaType, String), NegotiationError> {
+		let renderer = self
+			.media_type
+			.as_ref()
+			.and_then(|preferred| {
+				renderers.iter().find(|renderer| {
+					renderer.type_ == preferred.type_ && renderer.subtype == preferred.subtype
+				})
+			})
+			.or_else(|| renderers.first())
+			.ok_or(NegotiationError::NoSuitableRenderer)?;
+		Ok((renderer.clone(), renderer.to_string()))
+	}
+}
+
+/// Versioned vendor media types such as `application/vnd.myapp.v2+json`
+///
+/// A vendor type is served by a renderer registered for the exact vendor
+/// type, or else by the renderer for its structured syntax suffix
+/// (`application/json` for `+json`). The vendor type is echoed back as the
+/// response media type.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_core::negotiation::{
+///     BaseContentNegotiation, MediaType, NegotiationContext, VendorMediaTypeStrategy,
+/// };
+///
+/// let strategy = VendorMediaTypeStrategy::new("myapp");
+/// let ctx = NegotiationContext::new().with_accept("application/vnd.myapp.v2+json");
+/// assert!(strategy.applies_to(&ctx));
+/// assert_eq!(strategy.version(&ctx).as_deref(), Some("v2"));
+///
+/// let renderers = vec![MediaType::new("text", "html"), MediaType::new("application", "json")];
+/// let (selected, media_type) = strategy
+///     .select_renderer(ctx.accept.as_deref(), &renderers)
+///     .unwrap();
+/// assert_eq!(selected.subtype, "json");
+/// assert_eq!(media_type, "application/vnd.myapp.v2+json");
+/// ```
+pub struct VendorMediaTypeStrategy {
+	vendor: String,
+}
+
+/// A parsed vendor media type
+struct VendorMediaType {
+	media_type: MediaType,
+	version: Option<String>,
+	suffix: Option<String>,
+}
+
+impl VendorMediaTypeStrategy {
+	/// Creates a strategy for `application/vnd.<vendor>...` media types
+	pub fn new(vendor: impl Into<String>) -> Self {
+		Self {
+			vendor: vendor.into(),
+		}
+	}
+	/// The version requested through the Accept header, e.g. `v2`
+	pub fn version(&self, ctx: &NegotiationContext) -> Option<String> {
+		self.accepted(ctx.accept.as_deref())
+			.into_iter()
+			.find_map(|vendor_type| vendor_type.version)
+	}
+
+	fn parse(&self, media_type: &MediaType) -> Option<VendorMediaType> {
+		let prefix = format!("vnd.{}", self.vendor);
+		let rest = media_type.subtype.strip_prefix(&prefix)?;
+		let (rest, suffix) = match rest.rsplit_once('+') {
+			Some((rest, suffix)) => (rest, Some(suffix.to_string())),
+			None => (rest, None),
+		};
+		let version = match rest {
+			"" => None,
+			_ => Some(rest.strip_prefix('.')?.to_string()),
+		};
+		Some(VendorMediaType {
+			media_type: media_type.clone(),
+			version,
+			suffix,
+		})
+	}
+
+	fn accepted(&self, accept: Option<&str>) -> Vec<VendorMediaType> {
+		let accept = AcceptHeader::parse(accept.unwrap_or(""));
+		accept
+			.media_types
+			.iter()
+			.filter(|media_type| media_type.quality > 0.0)
+			.filter_map(|media_type| self.parse(media_type))
+			.collect()
+	}
+
+	fn find_match<'a>(
+		&self,
+		vendor_type: &VendorMediaType,
+		available: &'a [MediaType],
+	) -> Option<&'a MediaType> {
+		let requested = &vendor_type.media_type;
+		available
+			.iter()
+			.find(|media_type| {
+				media_type.type_ == requested.type_ && media_type.subtype == requested.subtype
+			})
+			.or_else(|| {
+				let suffix = vendor_type.suffix.as_deref()?;
+				available.iter().find(|media_type| {
+					media_type.type_ == requested.type_ && media_type.subtype == suffix
+				})
+			})
+	}
+}
+
+impl BaseContentNegotiation for VendorMediaTypeStrategy {
+	fn name(&self) -> &str {
+		"vendor_media_type"
+	}
+
+	fn applies_to(&self, ctx: &NegotiationContext) -> bool {
+		let content_type = ctx.content_type.as_deref().and_then(MediaType::parse);
+		!self.accepted(ctx.accept.as_deref()).is_empty()
+			|| content_type.is_some_and(|media_type| self.parse(&media_type).is_some())
+	}
+
+	fn select_renderer(
+		&self,
+		accept: Option<&str>,
+		renderers: &[MediaType],
+	) -> Result<(MediaType, String), NegotiationError> {
+		for vendor_type in self.accepted(accept) {
+			if let Some(renderer) = self.find_match(&vendor_type, renderers) {
+				let mut media_type = vendor_type.media_type;
+				media_type.quality = 1.0;
+				return Ok((renderer.clone(), media_type.full_string()));
+			}
+		}
+		ContentNegotiator::new().select_renderer(accept, renderers)
+	}
+
+	fn select_parser(
+		&self,
+		content_type: Option<&str>,
+		parsers: &[MediaType],
+	) -> Result<MediaType, NegotiationError> {
+		let vendor_type = content_type
+			.and_then(MediaType::parse)
+			.and_then(|media_type| self.parse(&media_type));
+		match vendor_type {
+			Some(vendor_type) => self
+				.find_match(&vendor_type, parsers)
+				.cloned()
+				.ok_or(NegotiationError::NoSuitableParser),
+			None => ContentNegotiator::new().select_parser(content_type, parsers),
+		}
+	}
+}
+
+/// Ordered collection of negotiation strategies
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_core::negotiation::{
+///     IgnoreAcceptStrategy, MediaType, NegotiationContext, NegotiationStrategyRegistry,
+///     VendorMediaTypeStrategy,
+/// };
+///
+/// let registry = NegotiationStrategyRegistry::new()
+///     .with_strategy(IgnoreAcceptStrategy::new(["LegacyClient"]))
+///     .with_strategy(VendorMediaTypeStrategy::new("myapp"));
+/// let renderers = vec![MediaType::new("application", "json"), MediaType::new("text", "html")];
+///
+/// let ctx = NegotiationContext::new().with_accept("text/html");
+/// assert_eq!(registry.strategy_for(&ctx).name(), "default");
+/// let (selected, _) = registry.select_renderer(&ctx, &renderers).unwrap();
+/// assert_eq!(selected.subtype, "html");
+///
+/// let ctx = ctx.with_user_agent("LegacyClient/1.0");
+/// assert_eq!(registry.strategy_for(&ctx).name(), "ignore_accept");
+/// let (selected, _) = registry.select_renderer(&ctx, &renderers).unwrap();
+/// assert_eq!(selected.subtype, "json");
+/// ```
+#[derive(Clone)]
+pub struct NegotiationStrategyRegistry {
+	strategies: Vec<Arc<dyn BaseContentNegotiation + Send + Sync>>,
+	default: Arc<dyn BaseContentNegotiation + Send + Sync>,
+}
+
+impl NegotiationStrategyRegistry {
+	/// Creates a registry that only uses a [`ContentNegotiator`]
+	pub fn new() -> Self {
+		Self {
+			strategies: Vec::new(),
+			default: Arc::new(ContentNegotiator::new()),
+		}
+	}
+	/// Adds a strategy; earlier strategies take precedence
+	pub fn with_strategy<S>(mut self, strategy: S) -> Self
+	where
+		S: BaseContentNegotiation + Send + Sync + 'static,
+	{
+		self.register(strategy);
+		self
+	}
+	/// Replaces the fallback negotiator
+	pub fn with_default<S>(mut self, negotiator: S) -> Self
+	where
+		S: BaseContentNegotiation + Send + Sync + 'static,
+	{
+		self.default = Arc::new(negotiator);
+		self
+	}
+	/// Adds a strategy; earlier strategies take precedence
+	pub fn register<S>(&mut self, strategy: S)
+	where
+		S: BaseContentNegotiation + Send + Sync + 'static,
+	{
+		self.strategies.push(Arc::new(strategy));
+	}
+	/// Names of the registered strategies, in order, without the fallback
+	pub fn names(&self) -> Vec<&str> {
+		self.strategies
+			.iter()
+			.map(|strategy| strategy.name())
+			.collect()
+	}
+	/// The strategy that handles `ctx`
+	pub fn strategy_for(
+		&self,
+		ctx: &NegotiationContext,
+	) -> &(dyn BaseContentNegotiation + Send + Sync) {
+		self.strategies
+			.iter()
+			.find(|strategy| strategy.applies_to(ctx))
+			.unwrap_or(&self.default)
+			.as_ref()
+	}
+	/// Select a renderer with the strategy that handles `ctx`
+	pub fn select_renderer(
+		&self,
+		ctx: &NegotiationContext,
+		renderers: &[MediaType],
+	) -> Result<(MediaType, String), NegotiationError> {
+		self.strategy_for(ctx)
+			.select_renderer(ctx.accept.as_deref(), renderers)
+	}
+	/// Select a parser with the strategy that handles `ctx`
+	pub fn select_parser(
+		&self,
+		ctx: &NegotiationContext,
+		parsers: &[MediaType],
+	) -> Result<MediaType, NegotiationError> {
+		self.strategy_for(ctx)
+			.select_parser(ctx.content_type.as_deref(), parsers)
+	}
+}
+
+impl Default for NegotiationStrategyRegistry {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+
+	fn renderers() -> Vec<MediaType> {
+		vec![
+			MediaType::new("application", "json"),
+			MediaType::new("text", "html"),
+		]
+	}
+
+	#[test]
+	fn test_vendor_strategy_prefers_exact_vendor_renderer() {
+		let strategy = VendorMediaTypeStrategy::new("myapp");
+		let mut available = renderers();
+		available.push(MediaType::new("application", "vnd.myapp.v2+json"));
+		let ctx = NegotiationContext::new().with_accept("application/vnd.myapp.v2+json");
+
+		let (selected, media_type) = strategy
+			.select_renderer(ctx.accept.as_deref(), &available)
+			.unwrap();
+
+		assert_eq!(selected.subtype, "vnd.myapp.v2+json");
+		assert_eq!(media_type, "application/vnd.myapp.v2+json");
+	}
+
+	#[test]
+	fn test_vendor_strategy_ignores_other_vendors() {
+		let strategy = VendorMediaTypeStrategy::new("myapp");
+		let ctx = NegotiationContext::new().with_accept("application/vnd.other.v1+json");
+
+		assert!(!strategy.applies_to(&ctx));
+		assert!(strategy.version(&ctx).is_none());
+	}
+
+	#[test]
+	fn test_vendor_strategy_selects_parser_by_suffix() {
+		let strategy = VendorMediaTypeStrategy::new("myapp");
+		let ctx = NegotiationContext::new().with_content_type("application/vnd.myapp.v3+json");
+
+		assert!(strategy.applies_to(&ctx));
+		let selected = strategy
+			.select_parser(ctx.content_type.as_deref(), &renderers())
+			.unwrap();
+		assert_eq!(selected.subtype, "json");
+	}
+
+	#[rstest]
+	#[case("LegacyClient/1.0", true)]
+	#[case("legacyclient", true)]
+	#[case("Mozilla/5.0 (X11; Linux x86_64) LegacyClient/2.1", true)]
+	#[case("NotLegacyClient/1.0", false)]
+	#[case("LegacyClientX/1.0", false)]
+	#[case("Mozilla/5.0 (compatible; LegacyClient/1.0)", false)]
+	fn test_ignore_accept_matches_product_names(#[case] user_agent: &str, #[case] expected: bool) {
+		let strategy = IgnoreAcceptStrategy::new(["LegacyClient"]);
+		let ctx = NegotiationContext::new().with_user_agent(user_agent);
+
+		assert_eq!(strategy.applies_to(&ctx), expected);
+	}
+
+	#[test]
+	fn test_registry_falls_back_to_default() {
+		let registry =
+			NegotiationStrategyRegistry::new().with_strategy(IgnoreAcceptStrategy::new(["curl"]));
+		let ctx = NegotiationContext::new()
+			.with_accept("text/html")
+			.with_user_agent("Mozilla/5.0");
+
+		assert_eq!(registry.names(), vec!["ignore_accept"]);
+		let (selected, _) = registry.select_renderer(&ctx, &renderers()).unwrap();
+		assert_eq!(selected.subtype, "html");
+	}
+}
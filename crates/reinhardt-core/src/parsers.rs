@@ -14,6 +14,12 @@
 //! - **MessagePackParser**: Parse MessagePack binary format (application/msgpack)
 //! - **ProtobufParser**: Parse Protocol Buffers with dynamic schema support (application/protobuf)
 //! - **StreamingParser**: Memory-efficient parsing for large uploads
+//! - **StreamingMultiPartParser**: Multipart uploads read chunk by chunk, spooling large files to disk
+//!
+//! ## Parser Selection
+//!
+//! - **ParserRegistry**: Selects a parser through content negotiation on the Content-Type
+//! - **BodySizeLimit**: Rejects oversized bodies from `Content-Length` or while streaming
 //!
 //! ## Validation
 //!
@@ -43,11 +49,13 @@ pub mod compressed;
 pub mod file;
 pub mod form;
 pub mod json;
+pub mod limit;
 pub mod msgpack;
 pub mod multipart;
 pub mod parser;
 pub mod protobuf;
 pub mod streaming;
+pub mod streaming_multipart;
 pub mod validator;
 #[cfg(feature = "xml")]
 pub mod xml;
@@ -58,11 +66,15 @@ pub use compressed::{CompressedParser, CompressionEncoding};
 pub use file::FileUploadParser;
 pub use form::FormParser;
 pub use json::JSONParser;
+pub use limit::BodySizeLimit;
 pub use msgpack::MessagePackParser;
 pub use multipart::MultiPartParser;
-pub use parser::{MediaType, ParseError, ParseResult, Parser};
+pub use parser::{MediaType, ParseError, ParseResult, Parser, ParserRegistry};
 pub use protobuf::{ProtobufMessage, ProtobufParser};
 pub use streaming::{StreamChunk, StreamingParser};
+pub use streaming_multipart::{
+	FileContent, SpooledFile, StreamedMultiPart, StreamingMultiPartParser,
+};
 pub use validator::{
 	CompositeValidator, ContentTypeValidator, ParserValidator, SizeLimitValidator,
 };
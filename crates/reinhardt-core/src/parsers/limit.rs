@@ -0,0 +1,162 @@
+//! Request body size guard.
+//!
+//! [`BodySizeLimit`] rejects oversized bodies as early as possible: from the
+//! `Content-Length` header before anything is read, and while a body stream
+//! is being consumed, so a client cannot exhaust memory by sending an
+//! endless chunked body.
+
+use bytes::Bytes;
+use futures_util::stream::{Stream, StreamExt};
+use http::HeaderMap;
+use http::header::CONTENT_LENGTH;
+
+use super::parser::{ParseError, ParseResult};
+
+/// Maximum accepted request body size.
+///
+/// # Examples
+///
+/// ```
+/// use bytes::Bytes;
+/// use http::HeaderMap;
+/// use reinhardt_core::parsers::limit::BodySizeLimit;
+///
+/// let limit = BodySizeLimit::new(8);
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert("content-length", "1024".parse().unwrap());
+/// assert!(limit.check_headers(&headers).is_err());
+///
+/// assert!(limit.check(&Bytes::from("tiny")).is_ok());
+/// assert!(limit.check(&Bytes::from("far too large")).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodySizeLimit {
+	max_size: usize,
+}
+
+impl BodySizeLimit {
+	/// Create a guard accepting bodies of at most `max_size` bytes.
+	pub fn new(max_size: usize) -> Self {
+		Self { max_size }
+	}
+
+	/// The maximum body size in bytes.
+	pub fn max_size(&self) -> usize {
+		self.max_size
+	}
+
+	fn exceeded(&self) -> ParseError {
+		ParseError::Validation(format!(
+			"Request body exceeds maximum allowed size of {} bytes",
+			self.max_size
+		))
+	}
+
+	/// Reject a request whose declared `Content-Length` is too large.
+	///
+	/// A missing or unparsable header is accepted; the body is then checked
+	/// as it is read.
+	pub fn check_headers(&self, headers: &HeaderMap) -> ParseResult<()> {
+		let declared = headers
+			.get(CONTENT_LENGTH)
+			.and_then(|value| value.to_str().ok())
+			.and_then(|value| value.trim().parse::<usize>().ok());
+		match declared {
+			Some(length) if length > self.max_size => Err(self.exceeded()),
+			_ => Ok(()),
+		}
+	}
+
+	/// Reject a buffered body that is too large.
+	pub fn check(&self, body: &Bytes) -> ParseResult<()> {
+		if body.len() > self.max_size {
+			return Err(self.exceeded());
+		}
+		Ok(())
+	}
+
+	/// Wrap a body stream so it fails as soon as more than the limit is read.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bytes::Bytes;
+	/// use futures_util::{StreamExt, stream};
+	/// use reinhardt_core::parsers::limit::BodySizeLimit;
+	///
+	/// # tokio_test::block_on(async {
+	/// let body = stream::iter(vec![
+	///     Ok::<_, std::io::Error>(Bytes::from("1234")),
+	///     Ok(Bytes::from("5678")),
+	/// ]);
+	/// let mut limited = Box::pin(BodySizeLimit::new(6).limit_stream(body));
+	///
+	/// assert!(limited.next().await.unwrap().is_ok());
+	/// assert!(limited.next().await.unwrap().is_err());
+	/// assert!(limited.next().await.is_none());
+	/// # });
+	/// ```
+	pub fn limit_stream<S>(
+		&self,
+		stream: S,
+	) -> impl Stream<Item = Result<Bytes, std::io::Error>> + Send + 'static
+	where
+		S: Stream<Item = Result<Bytes, std::io::Error>> + Send + 'static,
+	{
+		let limit = *self;
+		stream.scan(Some(0usize), move |read, chunk| {
+			// `None` once the limit has been hit, which ends the stream
+			let item = read.map(|total| {
+				chunk.and_then(|bytes| {
+					let total = total + bytes.len();
+					if total > limit.max_size {
+						*read = None;
+						return Err(std::io::Error::new(
+							std::io::ErrorKind::InvalidData,
+							limit.exceeded().to_string(),
+						));
+					}
+					*read = Some(total);
+					Ok(bytes)
+				})
+			});
+			futures_util::future::ready(item)
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures_util::stream;
+
+	#[test]
+	fn test_check_headers_ignores_missing_or_invalid_length() {
+		let limit = BodySizeLimit::new(10);
+		let mut headers = HeaderMap::new();
+		assert!(limit.check_headers(&headers).is_ok());
+
+		headers.insert(CONTENT_LENGTH, "not-a-number".parse().unwrap());
+		assert!(limit.check_headers(&headers).is_ok());
+
+		headers.insert(CONTENT_LENGTH, "10".parse().unwrap());
+		assert!(limit.check_headers(&headers).is_ok());
+
+		headers.insert(CONTENT_LENGTH, "11".parse().unwrap());
+		assert!(limit.check_headers(&headers).is_err());
+	}
+
+	#[tokio::test]
+	async fn test_limit_stream_passes_bodies_within_limit() {
+		let body = stream::iter(vec![
+			Ok::<_, std::io::Error>(Bytes::from("12345")),
+			Ok(Bytes::from("67890")),
+		]);
+
+		let chunks: Vec<_> = BodySizeLimit::new(10).limit_stream(body).collect().await;
+
+		assert_eq!(chunks.len(), 2);
+		assert!(chunks.iter().all(Result::is_ok));
+	}
+}
@@ -1,10 +1,9 @@
 use async_trait::async_trait;
 use bytes::Bytes;
 use http::HeaderMap;
-use multer::Multipart as MulterMultipart;
-use std::collections::HashMap;
 
-use super::parser::{MediaType, ParseError, ParseResult, ParsedData, Parser, UploadedFile};
+use super::parser::{BodyStream, ParseResult, ParsedData, Parser};
+use super::streaming_multipart::StreamingMultiPartParser;
 
 /// MultiPart parser for multipart/form-data content type (file uploads)
 #[derive(Debug, Clone, Default)]
@@ -59,71 +58,16 @@ impl MultiPartParser {
 		self
 	}
 
-	async fn parse_multipart(&self, boundary: &str, body: Bytes) -> ParseResult<ParsedData> {
-		let mut multipart = MulterMultipart::new(
-			futures_util::stream::once(async move { Result::<_, std::io::Error>::Ok(body) }),
-			boundary,
-		);
-
-		let mut fields = HashMap::new();
-		let mut files = Vec::new();
-		let mut total_size = 0usize;
-
-		while let Some(field) = multipart
-			.next_field()
-			.await
-			.map_err(|e| ParseError::ParseError(format!("Multipart parse error: {}", e)))?
-		{
-			let name = field.name().unwrap_or("").to_string();
-			let filename = field.file_name().map(|s| s.to_string());
-			let content_type = field.content_type().map(|m| m.to_string());
-
-			let data = field
-				.bytes()
-				.await
-				.map_err(|e| ParseError::ParseError(format!("Failed to read field data: {}", e)))?;
-
-			let size = data.len();
-
-			// Check file size limit
-			if let Some(max_size) = self.max_file_size
-				&& size > max_size
-			{
-				return Err(ParseError::ParseError(format!(
-					"File '{}' exceeds maximum size of {} bytes",
-					name, max_size
-				)));
-			}
-
-			// Check total size limit
-			total_size += size;
-			if let Some(max_total) = self.max_total_size
-				&& total_size > max_total
-			{
-				return Err(ParseError::ParseError(format!(
-					"Total upload size exceeds maximum of {} bytes",
-					max_total
-				)));
-			}
-
-			if filename.is_some() {
-				// This is a file field
-				let mut file = UploadedFile::new(name.clone(), data);
-				if let Some(fname) = filename {
-					file = file.with_filename(fname);
-				}
-				if let Some(ct) = content_type {
-					file = file.with_content_type(ct);
-				}
-				files.push(file);
-			} else {
-				// This is a regular field
-				let value = String::from_utf8_lossy(&data).to_string();
-				fields.insert(name, value);
-			}
+	/// The streaming parser doing the work, keeping every part in memory.
+	fn streaming(&self) -> StreamingMultiPartParser {
+		StreamingMultiPartParser {
+			memory_threshold: usize::MAX,
+			spool_dir: None,
+			// Text fields are bound by the file limit as well
+			max_file_size: self.max_file_size,
+			max_field_size: self.max_file_size,
+			max_total_size: self.max_total_size,
 		}
-
-		Ok(ParsedData::MultiPart { fields, files })
 	}
 }
 
@@ -137,18 +81,18 @@ impl Parser for MultiPartParser {
 		&self,
 		content_type: Option<&str>,
 		body: Bytes,
-		_headers: &HeaderMap,
+		headers: &HeaderMap,
 	) -> ParseResult<ParsedData> {
-		let content_type = content_type.ok_or(ParseError::MissingContentType)?;
-
-		let media_type = MediaType::parse(content_type)?;
-
-		let boundary = media_type
-			.parameters
-			.get("boundary")
-			.ok_or_else(|| ParseError::ParseError("Missing boundary parameter".to_string()))?;
+		self.streaming().parse(content_type, body, headers).await
+	}
 
-		self.parse_multipart(boundary, body).await
+	async fn parse_stream(
+		&self,
+		content_type: Option<&str>,
+		body: BodyStream,
+		headers: &HeaderMap,
+	) -> ParseResult<ParsedData> {
+		Parser::parse_stream(&self.streaming(), content_type, body, headers).await
 	}
 }
 
@@ -1,13 +1,23 @@
-use crate::exception::{Error, Result};
+use super::limit::BodySizeLimit;
+use crate::exception::{Error, NegotiationErrorContext, Result};
+use crate::negotiation::{
+	ContentNegotiator, MediaType as NegotiationMediaType, NegotiationContext,
+	NegotiationStrategyRegistry,
+};
 use async_trait::async_trait;
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
+use futures_util::stream::{Stream, StreamExt};
 use http::HeaderMap;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::pin::Pin;
 
 pub type ParseError = Error;
 pub type ParseResult<T> = Result<T>;
 
+/// Request body delivered as a stream of chunks
+pub type BodyStream = Pin<Box<dyn Stream<Item = std::io::Result<Bytes>> + Send>>;
+
 /// Media type representation
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MediaType {
@@ -224,6 +234,27 @@ pub trait Parser: Send + Sync {
 		headers: &HeaderMap,
 	) -> ParseResult<ParsedData>;
 
+	/// Parse the request body from a stream of chunks
+	///
+	/// The default implementation collects the stream and calls
+	/// [`Parser::parse`]. Parsers able to process the body incrementally
+	/// override it.
+	async fn parse_stream(
+		&self,
+		content_type: Option<&str>,
+		mut body: BodyStream,
+		headers: &HeaderMap,
+	) -> ParseResult<ParsedData> {
+		let mut buffer = BytesMut::new();
+		while let Some(chunk) = body.next().await {
+			let chunk = chunk.map_err(|e| {
+				ParseError::ParseError(format!("Failed to read request body: {}", e))
+			})?;
+			buffer.extend_from_slice(&chunk);
+		}
+		self.parse(content_type, buffer.freeze(), headers).await
+	}
+
 	/// Check if this parser can handle the given content type
 	fn can_parse(&self, content_type: Option<&str>) -> bool {
 		if let Some(ct) = content_type
@@ -236,9 +267,17 @@ pub trait Parser: Send + Sync {
 }
 
 /// Parser registry for selecting appropriate parser
+///
+/// The parser is chosen through content negotiation on the request's
+/// Content-Type: the most specific matching media type wins, so a parser
+/// for `application/json` takes precedence over a catch-all `*/*` parser
+/// regardless of registration order.
 #[derive(Default)]
 pub struct ParserRegistry {
 	parsers: Vec<Box<dyn Parser>>,
+	negotiator: ContentNegotiator,
+	strategies: Option<NegotiationStrategyRegistry>,
+	body_limit: Option<BodySizeLimit>,
 }
 
 impl ParserRegistry {
@@ -298,15 +337,199 @@ impl ParserRegistry {
 		body: Bytes,
 		headers: &HeaderMap,
 	) -> ParseResult<ParsedData> {
-		for parser in &self.parsers {
-			if parser.can_parse(content_type) {
-				return parser.parse(content_type, body, headers).await;
+		if let Some(limit) = &self.body_limit {
+			limit.check_headers(headers)?;
+			limit.check(&body)?;
+		}
+		let ctx = NegotiationContext::from_headers(headers);
+		let ctx = NegotiationContext {
+			content_type: content_type.map(str::to_string),
+			..ctx
+		};
+		self.select_for(&ctx)?
+			.parse(content_type, body, headers)
+			.await
+	}
+	/// Parse a request body stream using the negotiated parser.
+	///
+	/// The configured body size guard is applied to the `Content-Length`
+	/// header and to the chunks as they are read, so an oversized body is
+	/// rejected without being buffered.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bytes::Bytes;
+	/// use futures_util::stream;
+	/// use http::HeaderMap;
+	/// use reinhardt_core::parsers::parser::ParserRegistry;
+	/// use reinhardt_core::parsers::json::JSONParser;
+	///
+	/// # tokio_test::block_on(async {
+	/// let registry = ParserRegistry::new()
+	///     .register(JSONParser::new())
+	///     .with_max_body_size(8);
+	/// let body = stream::iter(vec![
+	///     Ok::<_, std::io::Error>(Bytes::from(r#"{"key":"#)),
+	///     Ok(Bytes::from(r#""value"}"#)),
+	/// ]);
+	///
+	/// let result = registry
+	///     .parse_stream(Some("application/json"), Box::pin(body), &HeaderMap::new())
+	///     .await;
+	/// assert!(result.is_err());
+	/// # });
+	/// ```
+	pub async fn parse_stream(
+		&self,
+		content_type: Option<&str>,
+		body: BodyStream,
+		headers: &HeaderMap,
+	) -> ParseResult<ParsedData> {
+		let body: BodyStream = match &self.body_limit {
+			Some(limit) => {
+				limit.check_headers(headers)?;
+				Box::pin(limit.limit_stream(body))
 			}
+			None => body,
+		};
+		let ctx = NegotiationContext::from_headers(headers);
+		let ctx = NegotiationContext {
+			content_type: content_type.map(str::to_string),
+			..ctx
+		};
+		self.select_for(&ctx)?
+			.parse_stream(content_type, body, headers)
+			.await
+	}
+	/// Reject request bodies larger than `max_size` bytes before parsing.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bytes::Bytes;
+	/// use http::HeaderMap;
+	/// use reinhardt_core::parsers::parser::ParserRegistry;
+	/// use reinhardt_core::parsers::json::JSONParser;
+	///
+	/// # tokio_test::block_on(async {
+	/// let registry = ParserRegistry::new()
+	///     .register(JSONParser::new())
+	///     .with_max_body_size(8);
+	/// let body = Bytes::from(r#"{"key":"value"}"#);
+	/// let result = registry.parse(Some("application/json"), body, &HeaderMap::new()).await;
+	/// assert!(result.is_err());
+	/// # });
+	/// ```
+	pub fn with_max_body_size(mut self, max_size: usize) -> Self {
+		self.body_limit = Some(BodySizeLimit::new(max_size));
+		self
+	}
+	/// Use a custom negotiator to select parsers.
+	pub fn with_negotiator(mut self, negotiator: ContentNegotiator) -> Self {
+		self.negotiator = negotiator;
+		self
+	}
+	/// Select parsers through negotiation strategies instead of the negotiator.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_core::negotiation::{NegotiationStrategyRegistry, VendorMediaTypeStrategy};
+	/// use reinhardt_core::parsers::parser::ParserRegistry;
+	/// use reinhardt_core::parsers::json::JSONParser;
+	///
+	/// let registry = ParserRegistry::new()
+	///     .register(JSONParser::new())
+	///     .with_strategies(
+	///         NegotiationStrategyRegistry::new().with_strategy(VendorMediaTypeStrategy::new("myapp")),
+	///     );
+	///
+	/// assert!(registry.select(Some("application/vnd.myapp.v2+json")).is_ok());
+	/// ```
+	pub fn with_strategies(mut self, strategies: NegotiationStrategyRegistry) -> Self {
+		self.strategies = Some(strategies);
+		self
+	}
+	/// The configured body size guard, if any.
+	pub fn body_limit(&self) -> Option<&BodySizeLimit> {
+		self.body_limit.as_ref()
+	}
+	/// Media types accepted by the registered parsers, in registration order.
+	pub fn media_types(&self) -> Vec<String> {
+		self.parsers
+			.iter()
+			.flat_map(|parser| parser.media_types())
+			.collect()
+	}
+	/// Select the parser for a Content-Type through content negotiation.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_core::parsers::parser::ParserRegistry;
+	/// use reinhardt_core::parsers::json::JSONParser;
+	/// use reinhardt_core::parsers::streaming::StreamingParser;
+	///
+	/// // The catch-all streaming parser is registered first but JSON is more specific
+	/// let registry = ParserRegistry::new()
+	///     .register(StreamingParser::default())
+	///     .register(JSONParser::new());
+	///
+	/// let parser = registry.select(Some("application/json")).unwrap();
+	/// assert!(parser.media_types().contains(&"application/json".to_string()));
+	/// ```
+	pub fn select(&self, content_type: Option<&str>) -> ParseResult<&dyn Parser> {
+		let ctx = NegotiationContext {
+			content_type: content_type.map(str::to_string),
+			..NegotiationContext::default()
+		};
+		self.select_for(&ctx)
+	}
+	/// Select the parser for a request described by a [`NegotiationContext`].
+	///
+	/// Without strategies, only parsers whose [`Parser::can_parse`] accepts
+	/// the Content-Type are considered. Strategies map vendor or versioned
+	/// types onto the types parsers declare, so they select on their own.
+	pub fn select_for(&self, ctx: &NegotiationContext) -> ParseResult<&dyn Parser> {
+		let content_type = ctx.content_type.as_deref();
+		let candidates: Vec<(usize, NegotiationMediaType)> = self
+			.parsers
+			.iter()
+			.enumerate()
+			.filter(|(_, parser)| self.strategies.is_some() || parser.can_parse(content_type))
+			.flat_map(|(index, parser)| {
+				parser
+					.media_types()
+					.into_iter()
+					.filter_map(move |media_type| {
+						NegotiationMediaType::parse(&media_type)
+							.map(|media_type| (index, media_type))
+					})
+			})
+			.collect();
+		let available: Vec<NegotiationMediaType> = candidates
+			.iter()
+			.map(|(_, media_type)| media_type.clone())
+			.collect();
+
+		let selected = match &self.strategies {
+			Some(strategies) => strategies.select_parser(ctx, &available),
+			None => self.negotiator.select_parser(content_type, &available),
 		}
+		.map_err(|_| self.unsupported_media_type(content_type))?;
+		let (index, _) = candidates
+			.iter()
+			.find(|(_, media_type)| *media_type == selected)
+			.ok_or_else(|| self.unsupported_media_type(content_type))?;
+		Ok(self.parsers[*index].as_ref())
+	}
 
-		Err(Error::Validation(
-			content_type.unwrap_or("none").to_string(),
-		))
+	fn unsupported_media_type(&self, content_type: Option<&str>) -> Error {
+		Error::UnsupportedMediaType(Box::new(NegotiationErrorContext::content_type(
+			content_type,
+			self.media_types(),
+		)))
 	}
 }
 
@@ -335,4 +558,57 @@ mod tests {
 		assert!(mt.matches("*/*"));
 		assert!(!mt.matches("text/html"));
 	}
+
+	struct RestrictedParser;
+
+	#[async_trait]
+	impl Parser for RestrictedParser {
+		fn media_types(&self) -> Vec<String> {
+			vec!["application/json".to_string()]
+		}
+
+		async fn parse(
+			&self,
+			_content_type: Option<&str>,
+			_body: Bytes,
+			_headers: &HeaderMap,
+		) -> ParseResult<ParsedData> {
+			Ok(ParsedData::Json(Value::Null))
+		}
+
+		fn can_parse(&self, content_type: Option<&str>) -> bool {
+			content_type.is_some_and(|ct| !ct.contains("charset=latin1"))
+		}
+	}
+
+	#[test]
+	fn test_select_honours_can_parse() {
+		let registry = ParserRegistry::new().register(RestrictedParser);
+
+		assert!(registry.select(Some("application/json")).is_ok());
+		assert!(
+			registry
+				.select(Some("application/json; charset=latin1"))
+				.is_err()
+		);
+	}
+
+	#[tokio::test]
+	async fn test_parse_stream_enforces_body_limit_while_reading() {
+		let registry = ParserRegistry::new()
+			.register(RestrictedParser)
+			.with_max_body_size(4);
+		let body = futures_util::stream::iter(vec![
+			Ok(Bytes::from("1234")),
+			Ok(Bytes::from("5")),
+			Err(std::io::Error::other("read past the limit")),
+		]);
+
+		let error = registry
+			.parse_stream(Some("application/json"), Box::pin(body), &HeaderMap::new())
+			.await
+			.unwrap_err();
+
+		assert!(error.to_string().contains("maximum allowed size"));
+	}
 }
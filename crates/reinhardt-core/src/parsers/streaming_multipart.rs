@@ -0,0 +1,504 @@
+//! Streaming multipart/form-data parser.
+//!
+//! [`StreamingMultiPartParser`] reads a multipart body chunk by chunk. Files
+//! are kept in memory while small and spooled to a temporary file once they
+//! grow past a threshold, so large uploads never need to fit in memory.
+//! Size limits are enforced while reading, before the whole body arrives.
+
+use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+use futures_util::stream::Stream;
+use http::HeaderMap;
+use multer::Multipart as MulterMultipart;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+
+use super::parser::{
+	BodyStream, MediaType, ParseError, ParseResult, ParsedData, Parser, UploadedFile,
+};
+
+/// Files up to this size stay in memory (2.5 MiB).
+pub const DEFAULT_MEMORY_THRESHOLD: usize = 2_621_440;
+
+/// Where the content of a [`SpooledFile`] is stored
+#[derive(Debug)]
+pub enum FileContent {
+	/// Held in memory
+	Memory(Bytes),
+	/// Spooled to a temporary file, removed when the [`SpooledFile`] is dropped
+	Disk(PathBuf),
+}
+
+/// A file uploaded through [`StreamingMultiPartParser`]
+#[derive(Debug)]
+pub struct SpooledFile {
+	pub name: String,
+	pub filename: Option<String>,
+	pub content_type: Option<String>,
+	pub size: usize,
+	content: FileContent,
+}
+
+impl SpooledFile {
+	/// Where the content is stored.
+	pub fn content(&self) -> &FileContent {
+		&self.content
+	}
+
+	/// Whether the content is held in memory.
+	pub fn is_in_memory(&self) -> bool {
+		matches!(self.content, FileContent::Memory(_))
+	}
+
+	/// Path of the temporary file, if the content was spooled to disk.
+	pub fn path(&self) -> Option<&Path> {
+		match &self.content {
+			FileContent::Disk(path) => Some(path),
+			FileContent::Memory(_) => None,
+		}
+	}
+
+	/// Read the whole content.
+	pub async fn read(&self) -> ParseResult<Bytes> {
+		match &self.content {
+			FileContent::Memory(data) => Ok(data.clone()),
+			FileContent::Disk(path) => tokio::fs::read(path)
+				.await
+				.map(Bytes::from)
+				.map_err(|e| ParseError::ParseError(format!("Failed to read upload: {}", e))),
+		}
+	}
+
+	/// Move the content to `destination` and return its path.
+	///
+	/// Spooled files are renamed when possible, falling back to a copy.
+	pub async fn persist(mut self, destination: impl AsRef<Path>) -> ParseResult<PathBuf> {
+		let destination = destination.as_ref().to_path_buf();
+		let io_error = |e: std::io::Error| {
+			ParseError::ParseError(format!(
+				"Failed to persist upload to '{}': {}",
+				destination.display(),
+				e
+			))
+		};
+		let content = std::mem::replace(&mut self.content, FileContent::Memory(Bytes::new()));
+		match content {
+			FileContent::Memory(data) => {
+				tokio::fs::write(&destination, &data)
+					.await
+					.map_err(io_error)?;
+			}
+			FileContent::Disk(path) => {
+				if tokio::fs::rename(&path, &destination).await.is_err() {
+					let copied = tokio::fs::copy(&path, &destination).await;
+					let _ = tokio::fs::remove_file(&path).await;
+					copied.map_err(io_error)?;
+				}
+			}
+		}
+		Ok(destination)
+	}
+
+	/// Load the content into an [`UploadedFile`].
+	pub async fn into_uploaded_file(self) -> ParseResult<UploadedFile> {
+		let mut file = UploadedFile::new(self.name.clone(), self.read().await?);
+		file.filename = self.filename.clone();
+		file.content_type = self.content_type.clone();
+		Ok(file)
+	}
+}
+
+impl Drop for SpooledFile {
+	fn drop(&mut self) {
+		if let FileContent::Disk(path) = &self.content {
+			let _ = std::fs::remove_file(path);
+		}
+	}
+}
+
+/// Result of [`StreamingMultiPartParser::parse_stream`]
+#[derive(Debug, Default)]
+pub struct StreamedMultiPart {
+	pub fields: HashMap<String, String>,
+	pub files: Vec<SpooledFile>,
+}
+
+/// Streaming parser for multipart/form-data bodies
+///
+/// # Examples
+///
+/// ```
+/// use bytes::Bytes;
+/// use futures_util::stream;
+/// use reinhardt_core::parsers::streaming_multipart::StreamingMultiPartParser;
+///
+/// # tokio_test::block_on(async {
+/// let body = "--b\r\n\
+///     Content-Disposition: form-data; name=\"file\"; filename=\"a.txt\"\r\n\r\n\
+///     Hello\r\n--b--\r\n";
+/// let parser = StreamingMultiPartParser::new().memory_threshold(4);
+///
+/// let parsed = parser
+///     .parse_stream(
+///         "multipart/form-data; boundary=b",
+///         stream::once(async move { Ok::<_, std::io::Error>(Bytes::from(body)) }),
+///     )
+///     .await
+///     .unwrap();
+///
+/// let file = &parsed.files[0];
+/// assert!(!file.is_in_memory());
+/// assert_eq!(file.read().await.unwrap(), Bytes::from("Hello"));
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct StreamingMultiPartParser {
+	/// Files larger than this are spooled to disk
+	pub memory_threshold: usize,
+	/// Directory for spooled files (None = the system temp directory)
+	pub spool_dir: Option<PathBuf>,
+	/// Maximum file size in bytes (None = unlimited)
+	pub max_file_size: Option<usize>,
+	/// Maximum size of a non-file field in bytes (None = unlimited)
+	pub max_field_size: Option<usize>,
+	/// Maximum total size in bytes (None = unlimited)
+	pub max_total_size: Option<usize>,
+}
+
+impl Default for StreamingMultiPartParser {
+	fn default() -> Self {
+		Self {
+			memory_threshold: DEFAULT_MEMORY_THRESHOLD,
+			spool_dir: None,
+			max_file_size: None,
+			max_field_size: None,
+			max_total_size: None,
+		}
+	}
+}
+
+impl StreamingMultiPartParser {
+	/// Create a new StreamingMultiPartParser with default settings.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_core::parsers::streaming_multipart::{
+	///     DEFAULT_MEMORY_THRESHOLD, StreamingMultiPartParser,
+	/// };
+	///
+	/// let parser = StreamingMultiPartParser::new();
+	/// assert_eq!(parser.memory_threshold, DEFAULT_MEMORY_THRESHOLD);
+	/// assert!(parser.spool_dir.is_none());
+	/// ```
+	pub fn new() -> Self {
+		Self::default()
+	}
+	/// Set the size above which files are spooled to disk.
+	pub fn memory_threshold(mut self, size: usize) -> Self {
+		self.memory_threshold = size;
+		self
+	}
+	/// Set the directory spooled files are written to.
+	pub fn spool_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+		self.spool_dir = Some(dir.into());
+		self
+	}
+	/// Set the maximum file size in bytes for individual files.
+	pub fn max_file_size(mut self, size: usize) -> Self {
+		self.max_file_size = Some(size);
+		self
+	}
+	/// Set the maximum size in bytes of a non-file field.
+	pub fn max_field_size(mut self, size: usize) -> Self {
+		self.max_field_size = Some(size);
+		self
+	}
+	/// Set the maximum total size in bytes for all parts combined.
+	pub fn max_total_size(mut self, size: usize) -> Self {
+		self.max_total_size = Some(size);
+		self
+	}
+
+	fn boundary(content_type: &str) -> ParseResult<String> {
+		MediaType::parse(content_type)?
+			.parameters
+			.remove("boundary")
+			.map(|boundary| boundary.trim_matches('"').to_string())
+			.ok_or_else(|| ParseError::ParseError("Missing boundary parameter".to_string()))
+	}
+
+	fn spool_path(&self) -> PathBuf {
+		self.spool_dir
+			.clone()
+			.unwrap_or_else(std::env::temp_dir)
+			.join(format!("reinhardt-upload-{}", uuid::Uuid::new_v4()))
+	}
+
+	/// Parse a multipart body from a stream of chunks.
+	pub async fn parse_stream<S>(
+		&self,
+		content_type: &str,
+		stream: S,
+	) -> ParseResult<StreamedMultiPart>
+	where
+		S: Stream<Item = Result<Bytes, std::io::Error>> + Send + 'static,
+	{
+		let boundary = Self::boundary(content_type)?;
+		let mut multipart = MulterMultipart::new(stream, boundary);
+
+		let mut parsed = StreamedMultiPart::default();
+		let mut total_size = 0usize;
+
+		while let Some(mut field) = multipart
+			.next_field()
+			.await
+			.map_err(|e| ParseError::ParseError(format!("Multipart parse error: {}", e)))?
+		{
+			let name = field.name().unwrap_or("").to_string();
+			let filename = field.file_name().map(|s| s.to_string());
+			let content_type = field.content_type().map(|m| m.to_string());
+			let limit = if filename.is_some() {
+				self.max_file_size
+			} else {
+				self.max_field_size
+			};
+
+			let mut size = 0usize;
+			let mut buffer = BytesMut::new();
+			// Open temporary file, once the part outgrows the threshold
+			let mut spooled: Option<(tokio::fs::File, SpooledFile)> = None;
+
+			while let Some(chunk) = field
+				.chunk()
+				.await
+				.map_err(|e| ParseError::ParseError(format!("Failed to read field data: {}", e)))?
+			{
+				size += chunk.len();
+				total_size += chunk.len();
+				if let Some(max_size) = limit
+					&& size > max_size
+				{
+					return Err(ParseError::ParseError(format!(
+						"Field '{}' exceeds maximum size of {} bytes",
+						name, max_size
+					)));
+				}
+				if let Some(max_total) = self.max_total_size
+					&& total_size > max_total
+				{
+					return Err(ParseError::ParseError(format!(
+						"Total upload size exceeds maximum of {} bytes",
+						max_total
+					)));
+				}
+
+				let write_error = |e: std::io::Error| {
+					ParseError::ParseError(format!("Failed to spool upload: {}", e))
+				};
+				if spooled.is_none()
+					&& filename.is_some()
+					&& buffer.len() + chunk.len() > self.memory_threshold
+				{
+					let path = self.spool_path();
+					// Owning the path first removes the file on early return
+					let spooled_file = SpooledFile {
+						name: name.clone(),
+						filename: filename.clone(),
+						content_type: content_type.clone(),
+						size: 0,
+						content: FileContent::Disk(path.clone()),
+					};
+					let mut file = tokio::fs::File::create(&path).await.map_err(write_error)?;
+					file.write_all(&buffer).await.map_err(write_error)?;
+					buffer.clear();
+					spooled = Some((file, spooled_file));
+				}
+				match spooled.as_mut() {
+					Some((file, _)) => file.write_all(&chunk).await.map_err(write_error)?,
+					None => buffer.extend_from_slice(&chunk),
+				}
+			}
+
+			match (filename, spooled) {
+				(_, Some((mut file, mut spooled_file))) => {
+					file.flush().await.map_err(|e| {
+						ParseError::ParseError(format!("Failed to spool upload: {}", e))
+					})?;
+					spooled_file.size = size;
+					parsed.files.push(spooled_file);
+				}
+				(Some(filename), None) => parsed.files.push(SpooledFile {
+					name,
+					filename: Some(filename),
+					content_type,
+					size,
+					content: FileContent::Memory(buffer.freeze()),
+				}),
+				(None, None) => {
+					parsed
+						.fields
+						.insert(name, String::from_utf8_lossy(&buffer).to_string());
+				}
+			}
+		}
+
+		Ok(parsed)
+	}
+}
+
+#[async_trait]
+impl Parser for StreamingMultiPartParser {
+	fn media_types(&self) -> Vec<String> {
+		vec!["multipart/form-data".to_string()]
+	}
+
+	async fn parse(
+		&self,
+		content_type: Option<&str>,
+		body: Bytes,
+		headers: &HeaderMap,
+	) -> ParseResult<ParsedData> {
+		let body: BodyStream = Box::pin(futures_util::stream::once(async move { Ok(body) }));
+		Parser::parse_stream(self, content_type, body, headers).await
+	}
+
+	async fn parse_stream(
+		&self,
+		content_type: Option<&str>,
+		body: BodyStream,
+		_headers: &HeaderMap,
+	) -> ParseResult<ParsedData> {
+		let content_type = content_type.ok_or(ParseError::MissingContentType)?;
+		let parsed = StreamingMultiPartParser::parse_stream(self, content_type, body).await?;
+
+		let mut files = Vec::with_capacity(parsed.files.len());
+		for file in parsed.files {
+			files.push(file.into_uploaded_file().await?);
+		}
+		Ok(ParsedData::MultiPart {
+			fields: parsed.fields,
+			files,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures_util::stream;
+
+	const CONTENT_TYPE: &str = "multipart/form-data; boundary=XyZ";
+
+	fn body() -> Vec<Result<Bytes, std::io::Error>> {
+		// Split across chunks to exercise incremental reads
+		vec![
+			Ok(Bytes::from(
+				"--XyZ\r\nContent-Disposition: form-data; name=\"title\"\r\n\r\nReport\r\n",
+			)),
+			Ok(Bytes::from(
+				"--XyZ\r\nContent-Disposition: form-data; name=\"doc\"; filename=\"r.txt\"\r\n\
+				 Content-Type: text/plain\r\n\r\n0123456789",
+			)),
+			Ok(Bytes::from("abcdefghij\r\n--XyZ--\r\n")),
+		]
+	}
+
+	#[tokio::test]
+	async fn test_small_files_stay_in_memory() {
+		let parsed = StreamingMultiPartParser::new()
+			.parse_stream(CONTENT_TYPE, stream::iter(body()))
+			.await
+			.unwrap();
+
+		assert_eq!(parsed.fields.get("title"), Some(&"Report".to_string()));
+		let file = &parsed.files[0];
+		assert!(file.is_in_memory());
+		assert_eq!(file.size, 20);
+		assert_eq!(file.filename.as_deref(), Some("r.txt"));
+		assert_eq!(file.content_type.as_deref(), Some("text/plain"));
+	}
+
+	#[tokio::test]
+	async fn test_large_files_are_spooled_and_removed_on_drop() {
+		let parsed = StreamingMultiPartParser::new()
+			.memory_threshold(15)
+			.parse_stream(CONTENT_TYPE, stream::iter(body()))
+			.await
+			.unwrap();
+
+		let file = parsed.files.into_iter().next().unwrap();
+		let path = file.path().unwrap().to_path_buf();
+		assert_eq!(
+			file.read().await.unwrap(),
+			Bytes::from("0123456789abcdefghij")
+		);
+		assert!(path.exists());
+
+		drop(file);
+		assert!(!path.exists());
+	}
+
+	#[tokio::test]
+	async fn test_persist_moves_spooled_file() {
+		let parsed = StreamingMultiPartParser::new()
+			.memory_threshold(0)
+			.parse_stream(CONTENT_TYPE, stream::iter(body()))
+			.await
+			.unwrap();
+		let file = parsed.files.into_iter().next().unwrap();
+		let spooled = file.path().unwrap().to_path_buf();
+		let destination =
+			std::env::temp_dir().join(format!("reinhardt-persist-{}", uuid::Uuid::new_v4()));
+
+		let persisted = file.persist(&destination).await.unwrap();
+
+		assert!(!spooled.exists());
+		assert_eq!(std::fs::read(&persisted).unwrap(), b"0123456789abcdefghij");
+		std::fs::remove_file(persisted).unwrap();
+	}
+
+	#[tokio::test]
+	async fn test_limits_are_enforced_while_streaming() {
+		let file_limit = StreamingMultiPartParser::new()
+			.max_file_size(15)
+			.parse_stream(CONTENT_TYPE, stream::iter(body()))
+			.await;
+		assert!(file_limit.is_err());
+
+		let field_limit = StreamingMultiPartParser::new()
+			.max_field_size(3)
+			.parse_stream(CONTENT_TYPE, stream::iter(body()))
+			.await;
+		assert!(field_limit.is_err());
+
+		let total_limit = StreamingMultiPartParser::new()
+			.max_total_size(25)
+			.parse_stream(CONTENT_TYPE, stream::iter(body()))
+			.await;
+		assert!(total_limit.is_err());
+	}
+
+	#[tokio::test]
+	async fn test_parser_trait_returns_multipart_data() {
+		let body: Vec<u8> = body()
+			.into_iter()
+			.flat_map(|chunk| chunk.unwrap().to_vec())
+			.collect();
+		let parser = StreamingMultiPartParser::new().memory_threshold(0);
+
+		let parsed = parser
+			.parse(Some(CONTENT_TYPE), Bytes::from(body), &HeaderMap::new())
+			.await
+			.unwrap();
+
+		match parsed {
+			ParsedData::MultiPart { fields, files } => {
+				assert_eq!(fields.len(), 1);
+				assert_eq!(files[0].data, Bytes::from("0123456789abcdefghij"));
+			}
+			_ => panic!("Expected multipart data"),
+		}
+	}
+}
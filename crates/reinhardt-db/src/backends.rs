@@ -145,12 +145,14 @@ pub mod drivers;
 pub mod error;
 pub mod optimization;
 pub mod query_builder;
+pub mod retry;
 pub mod schema;
 pub mod types;
 
 // Re-export commonly used types
 pub use error::DatabaseError as QueryDatabaseError;
 pub use error::{DatabaseError, Result};
+pub use retry::RetryPolicy;
 pub use schema::{BaseDatabaseSchemaEditor, SchemaEditorError, SchemaEditorResult};
 
 // Re-export query abstraction types
@@ -158,7 +160,8 @@ pub use backend::DatabaseBackend;
 pub use connection::DatabaseConnection;
 pub use query_builder::{InsertBuilder, SelectBuilder, UpdateBuilder};
 pub use types::{
-	DatabaseType, IsolationLevel, QueryResult, QueryValue, Row, Savepoint, TransactionExecutor,
+	DatabaseType, IsolationLevel, QueryResult, QueryValue, Row, RowStream, Savepoint,
+	TransactionExecutor,
 };
 
 // Re-export optimization features
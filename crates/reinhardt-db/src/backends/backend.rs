@@ -1,10 +1,14 @@
 //! Database backend abstraction
 
 use async_trait::async_trait;
+use futures::{StreamExt, TryStreamExt, stream};
 
 use super::{
 	error::Result,
-	types::{DatabaseType, IsolationLevel, QueryResult, QueryValue, Row, TransactionExecutor},
+	optimization::StatementCacheMetrics,
+	types::{
+		DatabaseType, IsolationLevel, QueryResult, QueryValue, Row, RowStream, TransactionExecutor,
+	},
 };
 
 /// Core database backend trait
@@ -46,6 +50,23 @@ pub trait DatabaseBackend: Send + Sync {
 	/// Fetches an optional single row from the database
 	async fn fetch_optional(&self, sql: &str, params: Vec<QueryValue>) -> Result<Option<Row>>;
 
+	/// Streams matching rows from the database
+	///
+	/// Rows are yielded as they are read instead of being collected into a
+	/// `Vec`, so large result sets can be processed in constant memory.
+	/// The query runs when the stream is first polled.
+	///
+	/// # Default Implementation
+	///
+	/// Falls back to `fetch_all()` and yields the buffered rows.
+	/// Backends with cursor support should override this.
+	fn fetch_stream<'a>(&'a self, sql: &'a str, params: Vec<QueryValue>) -> RowStream<'a> {
+		stream::once(self.fetch_all(sql, params))
+			.map_ok(|rows| stream::iter(rows.into_iter().map(Ok)))
+			.try_flatten()
+			.boxed()
+	}
+
 	/// Begin a database transaction and return a dedicated executor
 	///
 	/// This method acquires a dedicated database connection and begins a
@@ -87,6 +108,13 @@ pub trait DatabaseBackend: Send + Sync {
 		self.begin().await
 	}
 
+	/// Returns prepared statement cache metrics summed over pooled connections
+	///
+	/// Returns `None` for backends that do not track prepared statements.
+	fn statement_cache_metrics(&self) -> Option<StatementCacheMetrics> {
+		None
+	}
+
 	/// Returns self as &dyn std::any::Any for downcasting
 	fn as_any(&self) -> &dyn std::any::Any;
 }
@@ -6,6 +6,7 @@ use super::{
 	backend::DatabaseBackend,
 	error::Result,
 	query_builder::{DeleteBuilder, InsertBuilder, SelectBuilder, UpdateBuilder},
+	retry::{self, RetryPolicy},
 };
 
 #[cfg(feature = "postgres")]
@@ -21,6 +22,7 @@ use super::dialect::MySqlBackend;
 #[derive(Clone)]
 pub struct DatabaseConnection {
 	backend: Arc<dyn DatabaseBackend>,
+	retry_policy: RetryPolicy,
 }
 
 /// Injectable implementation for DatabaseConnection
@@ -84,7 +86,58 @@ impl reinhardt_di::Injectable for DatabaseConnection {
 
 impl DatabaseConnection {
 	pub fn new(backend: Arc<dyn DatabaseBackend>) -> Self {
-		Self { backend }
+		Self {
+			backend,
+			retry_policy: RetryPolicy::none(),
+		}
+	}
+
+	/// Retry statements failing with transient errors according to `policy`
+	///
+	/// Only statements that can safely run twice are retried: read-only
+	/// `fetch_*` queries (see [`retry::is_read_only`]), statements run with
+	/// [`execute_idempotent`](Self::execute_idempotent) and `begin`. A write
+	/// failing with a connection reset may already have been applied, so
+	/// `execute` and writing `fetch_*` queries are never retried, nor are
+	/// transaction control statements or statements run through a
+	/// transaction executor; wrap the whole transaction with
+	/// [`RetryPolicy::run`] instead.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+	/// use reinhardt_db::backends::connection::DatabaseConnection;
+	/// use reinhardt_db::backends::retry::RetryPolicy;
+	///
+	/// let conn = DatabaseConnection::connect_postgres("postgres://localhost/mydb")
+	///     .await?
+	///     .with_retry_policy(RetryPolicy::new(5));
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+		self.retry_policy = policy;
+		self
+	}
+
+	/// Get the retry policy of this connection
+	pub fn retry_policy(&self) -> &RetryPolicy {
+		&self.retry_policy
+	}
+
+	/// Retry policy applying to a statement, if it can safely run twice
+	fn retry_policy_for(&self, retryable: bool) -> RetryPolicy {
+		if retryable {
+			self.retry_policy
+		} else {
+			RetryPolicy::none()
+		}
+	}
+
+	/// Policy applying to a `fetch_*` query
+	fn read_retry_policy(&self, sql: &str) -> RetryPolicy {
+		self.retry_policy_for(retry::is_read_only(sql))
 	}
 
 	#[cfg(feature = "postgres")]
@@ -118,9 +171,48 @@ impl DatabaseConnection {
 			.connect(url)
 			.await?;
 
-		Ok(Self {
-			backend: Arc::new(PostgresBackend::new(pool)),
-		})
+		Ok(Self::new(Arc::new(PostgresBackend::new(pool))))
+	}
+
+	/// Connect to PostgreSQL with a per-connection schema search path
+	///
+	/// Every pooled connection runs `SET search_path` right after connecting,
+	/// so unqualified table names resolve against `schemas` in order. This is
+	/// the building block for schema-per-tenant deployments. An empty
+	/// `schemas` list keeps the server's default search path.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+	/// use reinhardt_db::backends::connection::DatabaseConnection;
+	///
+	/// let conn = DatabaseConnection::connect_postgres_with_search_path(
+	///     "postgres://postgres@localhost/mydb",
+	///     &["tenant_a", "public"],
+	/// ).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	#[cfg(feature = "postgres")]
+	pub async fn connect_postgres_with_search_path(url: &str, schemas: &[&str]) -> Result<Self> {
+		use sqlx::Executor;
+		use sqlx::postgres::PgPoolOptions;
+
+		let mut pool_options = PgPoolOptions::new();
+		if let Some(search_path_sql) = super::schema::set_search_path_sql(schemas) {
+			let search_path_sql = Arc::new(search_path_sql);
+			pool_options = pool_options.after_connect(move |conn, _meta| {
+				let search_path_sql = Arc::clone(&search_path_sql);
+				Box::pin(async move {
+					conn.execute(search_path_sql.as_str()).await?;
+					Ok(())
+				})
+			});
+		}
+		let pool = pool_options.connect(url).await?;
+
+		Ok(Self::new(Arc::new(PostgresBackend::new(pool))))
 	}
 
 	/// Connect to PostgreSQL with automatic database creation if it doesn't exist.
@@ -273,9 +365,7 @@ impl DatabaseConnection {
 		// Handle in-memory database
 		if url == "sqlite::memory:" {
 			let pool = SqlitePool::connect(url).await?;
-			return Ok(Self {
-				backend: Arc::new(SqliteBackend::new(pool)),
-			});
+			return Ok(Self::new(Arc::new(SqliteBackend::new(pool))));
 		}
 
 		// Extract file path from URL and convert to absolute path
@@ -373,25 +463,19 @@ impl DatabaseConnection {
 
 		let pool = SqlitePool::connect_with(options).await?;
 
-		Ok(Self {
-			backend: Arc::new(SqliteBackend::new(pool)),
-		})
+		Ok(Self::new(Arc::new(SqliteBackend::new(pool))))
 	}
 
 	#[cfg(feature = "sqlite")]
 	pub fn from_sqlite_pool(pool: sqlx::SqlitePool) -> Self {
-		Self {
-			backend: Arc::new(SqliteBackend::new(pool)),
-		}
+		Self::new(Arc::new(SqliteBackend::new(pool)))
 	}
 
 	#[cfg(feature = "mysql")]
 	pub async fn connect_mysql(url: &str) -> Result<Self> {
 		use sqlx::MySqlPool;
 		let pool = MySqlPool::connect(url).await?;
-		Ok(Self {
-			backend: Arc::new(MySqlBackend::new(pool)),
-		})
+		Ok(Self::new(Arc::new(MySqlBackend::new(pool))))
 	}
 
 	pub fn backend(&self) -> Arc<dyn DatabaseBackend> {
@@ -569,6 +653,12 @@ impl DatabaseConnection {
 		Ok(db_config.to_url())
 	}
 
+	/// Execute a statement
+	///
+	/// The statement is never retried: after a connection reset it may
+	/// already have been applied. Use
+	/// [`execute_idempotent`](Self::execute_idempotent) for statements that
+	/// can safely run twice.
 	pub async fn execute(
 		&self,
 		sql: &str,
@@ -577,28 +667,104 @@ impl DatabaseConnection {
 		self.backend.execute(sql, params).await
 	}
 
+	/// Execute a statement that can safely run twice, retrying transient errors
+	///
+	/// The caller guarantees that running the statement again has the same
+	/// effect as running it once (e.g. `UPDATE ... SET status = 'done'` or an
+	/// upsert). Transaction control statements are never retried.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+	/// use reinhardt_db::backends::connection::DatabaseConnection;
+	/// use reinhardt_db::backends::retry::RetryPolicy;
+	///
+	/// let conn = DatabaseConnection::connect_postgres("postgres://localhost/mydb")
+	///     .await?
+	///     .with_retry_policy(RetryPolicy::new(3));
+	/// conn.execute_idempotent("UPDATE jobs SET status = 'done' WHERE id = $1", vec![7.into()])
+	///     .await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub async fn execute_idempotent(
+		&self,
+		sql: &str,
+		params: Vec<super::types::QueryValue>,
+	) -> Result<super::types::QueryResult> {
+		self.retry_policy_for(!retry::is_transaction_control(sql))
+			.run(|| self.backend.execute(sql, params.clone()))
+			.await
+	}
+
+	/// Run a query with `fetch_one`
+	///
+	/// Transient errors are retried only when the query is read-only.
 	pub async fn fetch_one(
 		&self,
 		sql: &str,
 		params: Vec<super::types::QueryValue>,
 	) -> Result<super::types::Row> {
-		self.backend.fetch_one(sql, params).await
+		self.read_retry_policy(sql)
+			.run(|| self.backend.fetch_one(sql, params.clone()))
+			.await
 	}
 
+	/// Run a query with `fetch_all`
+	///
+	/// Transient errors are retried only when the query is read-only.
 	pub async fn fetch_all(
 		&self,
 		sql: &str,
 		params: Vec<super::types::QueryValue>,
 	) -> Result<Vec<super::types::Row>> {
-		self.backend.fetch_all(sql, params).await
+		self.read_retry_policy(sql)
+			.run(|| self.backend.fetch_all(sql, params.clone()))
+			.await
 	}
 
+	/// Run a query with `fetch_optional`
+	///
+	/// Transient errors are retried only when the query is read-only.
 	pub async fn fetch_optional(
 		&self,
 		sql: &str,
 		params: Vec<super::types::QueryValue>,
 	) -> Result<Option<super::types::Row>> {
-		self.backend.fetch_optional(sql, params).await
+		self.read_retry_policy(sql)
+			.run(|| self.backend.fetch_optional(sql, params.clone()))
+			.await
+	}
+
+	/// Stream rows of a query without loading the whole result set
+	///
+	/// PostgreSQL reads rows through a server-side cursor in batches, MySQL
+	/// and SQLite read them incrementally from the connection.
+	///
+	/// # Example
+	///
+	/// ```no_run
+	/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+	/// use futures::TryStreamExt;
+	/// use reinhardt_db::backends::connection::DatabaseConnection;
+	///
+	/// let conn = DatabaseConnection::connect_postgres("postgres://localhost/mydb").await?;
+	/// let mut rows = conn.fetch_stream("SELECT id, name FROM users", vec![]);
+	///
+	/// while let Some(row) = rows.try_next().await? {
+	///     let name: String = row.get("name")?;
+	///     println!("{}", name);
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn fetch_stream<'a>(
+		&'a self,
+		sql: &'a str,
+		params: Vec<super::types::QueryValue>,
+	) -> super::types::RowStream<'a> {
+		self.backend.fetch_stream(sql, params)
 	}
 
 	/// Begin a database transaction and return a dedicated executor
@@ -628,7 +794,7 @@ impl DatabaseConnection {
 	/// # }
 	/// ```
 	pub async fn begin(&self) -> Result<Box<dyn super::types::TransactionExecutor>> {
-		self.backend.begin().await
+		self.retry_policy.run(|| self.backend.begin()).await
 	}
 
 	/// Begin a transaction with a specific isolation level
@@ -655,6 +821,13 @@ impl DatabaseConnection {
 		self.backend.begin_with_isolation(level).await
 	}
 
+	/// Get prepared statement cache metrics of the backend
+	///
+	/// Returns `None` when the backend does not track prepared statements.
+	pub fn statement_cache_metrics(&self) -> Option<super::optimization::StatementCacheMetrics> {
+		self.backend.statement_cache_metrics()
+	}
+
 	#[cfg(feature = "postgres")]
 	pub fn into_postgres(&self) -> Option<sqlx::PgPool> {
 		self.backend
@@ -679,3 +852,146 @@ impl DatabaseConnection {
 			.map(|backend| backend.pool().clone())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::backends::error::DatabaseError;
+	use crate::backends::types::{DatabaseType, QueryResult, QueryValue, Row, TransactionExecutor};
+	use rstest::rstest;
+	use std::sync::atomic::{AtomicU32, Ordering};
+
+	/// Backend whose first statement fails with a connection reset
+	#[derive(Default)]
+	struct FlakyBackend {
+		attempts: AtomicU32,
+	}
+
+	impl FlakyBackend {
+		fn attempt(&self) -> Result<()> {
+			if self.attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+				Err(DatabaseError::ConnectionReset("reset".to_string()))
+			} else {
+				Ok(())
+			}
+		}
+	}
+
+	#[async_trait::async_trait]
+	impl DatabaseBackend for FlakyBackend {
+		fn database_type(&self) -> DatabaseType {
+			DatabaseType::Postgres
+		}
+
+		fn placeholder(&self, index: usize) -> String {
+			format!("${}", index)
+		}
+
+		fn supports_returning(&self) -> bool {
+			true
+		}
+
+		fn supports_on_conflict(&self) -> bool {
+			true
+		}
+
+		async fn execute(&self, _sql: &str, _params: Vec<QueryValue>) -> Result<QueryResult> {
+			self.attempt().map(|_| QueryResult { rows_affected: 1 })
+		}
+
+		async fn fetch_one(&self, _sql: &str, _params: Vec<QueryValue>) -> Result<Row> {
+			self.attempt().map(|_| Row::new())
+		}
+
+		async fn fetch_all(&self, _sql: &str, _params: Vec<QueryValue>) -> Result<Vec<Row>> {
+			self.attempt().map(|_| Vec::new())
+		}
+
+		async fn fetch_optional(
+			&self,
+			_sql: &str,
+			_params: Vec<QueryValue>,
+		) -> Result<Option<Row>> {
+			self.attempt().map(|_| None)
+		}
+
+		async fn begin(&self) -> Result<Box<dyn TransactionExecutor>> {
+			Err(DatabaseError::NotSupported("transactions".to_string()))
+		}
+
+		fn as_any(&self) -> &dyn std::any::Any {
+			self
+		}
+	}
+
+	fn flaky_connection() -> (Arc<FlakyBackend>, DatabaseConnection) {
+		let backend = Arc::new(FlakyBackend::default());
+		let conn = DatabaseConnection::new(backend.clone()).with_retry_policy(RetryPolicy::new(3));
+		(backend, conn)
+	}
+
+	#[rstest]
+	#[tokio::test(start_paused = true)]
+	async fn test_execute_is_not_retried() {
+		// Arrange
+		let (backend, conn) = flaky_connection();
+
+		// Act
+		let result = conn
+			.execute("UPDATE accounts SET balance = balance - 10", vec![])
+			.await;
+
+		// Assert
+		assert!(matches!(result, Err(DatabaseError::ConnectionReset(_))));
+		assert_eq!(backend.attempts.load(Ordering::SeqCst), 1);
+	}
+
+	#[rstest]
+	#[tokio::test(start_paused = true)]
+	async fn test_execute_idempotent_is_retried() {
+		// Arrange
+		let (backend, conn) = flaky_connection();
+
+		// Act
+		let result = conn
+			.execute_idempotent("UPDATE jobs SET status = 'done' WHERE id = 1", vec![])
+			.await;
+
+		// Assert
+		assert!(result.is_ok());
+		assert_eq!(backend.attempts.load(Ordering::SeqCst), 2);
+	}
+
+	#[rstest]
+	#[tokio::test(start_paused = true)]
+	async fn test_transaction_control_is_not_retried() {
+		// Arrange
+		let (backend, conn) = flaky_connection();
+
+		// Act
+		let result = conn.execute_idempotent("COMMIT", vec![]).await;
+
+		// Assert
+		assert!(result.is_err());
+		assert_eq!(backend.attempts.load(Ordering::SeqCst), 1);
+	}
+
+	#[rstest]
+	#[case("SELECT id FROM users", true)]
+	#[case("INSERT INTO users (name) VALUES ('a') RETURNING id", false)]
+	#[tokio::test(start_paused = true)]
+	async fn test_fetch_retries_only_reads(#[case] sql: &str, #[case] retried: bool) {
+		// Arrange
+		let (backend, conn) = flaky_connection();
+
+		// Act
+		let result = conn.fetch_one(sql, vec![]).await;
+
+		// Assert
+		assert_eq!(result.is_ok(), retried);
+		assert_eq!(
+			backend.attempts.load(Ordering::SeqCst),
+			if retried { 2 } else { 1 }
+		);
+	}
+}
@@ -1,30 +1,69 @@
 //! PostgreSQL dialect implementation
 
 use async_trait::async_trait;
-use sqlx::{Column, PgPool, Postgres, Transaction, postgres::PgRow};
+use futures::StreamExt;
+use sqlx::{Column, Connection as _, PgPool, Postgres, Transaction, postgres::PgRow};
+use std::collections::VecDeque;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use super::super::{
 	backend::DatabaseBackend,
 	error::Result,
+	optimization::{StatementCache, StatementCacheMetrics},
 	types::{
-		DatabaseType, IsolationLevel, QueryResult, QueryValue, Row, Savepoint, TransactionExecutor,
+		DatabaseType, IsolationLevel, QueryResult, QueryValue, Row, RowStream, Savepoint,
+		TransactionExecutor,
 	},
 };
 
+/// Default number of rows fetched per round trip when streaming
+pub const DEFAULT_STREAM_BATCH_SIZE: usize = 1000;
+
 /// PostgreSQL database backend
 pub struct PostgresBackend {
 	pool: Arc<PgPool>,
+	statement_cache: Arc<StatementCache>,
+	stream_batch_size: usize,
 }
 
 impl PostgresBackend {
 	pub fn new(pool: PgPool) -> Self {
 		Self {
 			pool: Arc::new(pool),
+			statement_cache: Arc::new(StatementCache::default()),
+			stream_batch_size: DEFAULT_STREAM_BATCH_SIZE,
 		}
 	}
 
+	/// Set the number of prepared statements cached per connection
+	///
+	/// This should match the `statement_cache_capacity` of the pool's connect
+	/// options. A capacity of zero disables prepared statement caching.
+	pub fn with_statement_cache_capacity(mut self, capacity: usize) -> Self {
+		self.statement_cache = Arc::new(StatementCache::new(capacity));
+		self
+	}
+
+	/// Get the prepared statement cache
+	pub fn statement_cache(&self) -> &StatementCache {
+		&self.statement_cache
+	}
+
+	/// Build a query, persisting it on the connection when caching is enabled
+	fn prepare<'q>(
+		&self,
+		sql: &'q str,
+	) -> sqlx::query::Query<'q, sqlx::Postgres, sqlx::postgres::PgArguments> {
+		sqlx::query(sql).persistent(self.statement_cache.is_enabled())
+	}
+
+	/// Set the number of rows fetched per round trip by `fetch_stream()`
+	pub fn with_stream_batch_size(mut self, batch_size: usize) -> Self {
+		self.stream_batch_size = batch_size.max(1);
+		self
+	}
+
 	pub fn pool(&self) -> &PgPool {
 		&self.pool
 	}
@@ -74,43 +113,82 @@ impl DatabaseBackend for PostgresBackend {
 	}
 
 	async fn execute(&self, sql: &str, params: Vec<QueryValue>) -> Result<QueryResult> {
-		let mut query = sqlx::query(sql);
+		let mut query = self.prepare(sql);
 		for param in &params {
 			query = Self::bind_value(query, param);
 		}
-		let result = query.execute(self.pool.as_ref()).await?;
+		let mut conn = self.pool.acquire().await?;
+		let cached = conn.cached_statements_size();
+		let result = query.execute(&mut *conn).await;
+		self.statement_cache
+			.record(cached, conn.cached_statements_size());
+		let result = result?;
 		Ok(QueryResult {
 			rows_affected: result.rows_affected(),
 		})
 	}
 
 	async fn fetch_one(&self, sql: &str, params: Vec<QueryValue>) -> Result<Row> {
-		let mut query = sqlx::query(sql);
+		let mut query = self.prepare(sql);
 		for param in &params {
 			query = Self::bind_value(query, param);
 		}
-		let row = query.fetch_one(self.pool.as_ref()).await?;
+		let mut conn = self.pool.acquire().await?;
+		let cached = conn.cached_statements_size();
+		let row = query.fetch_one(&mut *conn).await;
+		self.statement_cache
+			.record(cached, conn.cached_statements_size());
+		let row = row?;
 		Self::convert_row(row)
 	}
 
 	async fn fetch_all(&self, sql: &str, params: Vec<QueryValue>) -> Result<Vec<Row>> {
-		let mut query = sqlx::query(sql);
+		let mut query = self.prepare(sql);
 		for param in &params {
 			query = Self::bind_value(query, param);
 		}
-		let rows = query.fetch_all(self.pool.as_ref()).await?;
+		let mut conn = self.pool.acquire().await?;
+		let cached = conn.cached_statements_size();
+		let rows = query.fetch_all(&mut *conn).await;
+		self.statement_cache
+			.record(cached, conn.cached_statements_size());
+		let rows = rows?;
 		rows.into_iter().map(Self::convert_row).collect()
 	}
 
 	async fn fetch_optional(&self, sql: &str, params: Vec<QueryValue>) -> Result<Option<Row>> {
-		let mut query = sqlx::query(sql);
+		let mut query = self.prepare(sql);
 		for param in &params {
 			query = Self::bind_value(query, param);
 		}
-		let row = query.fetch_optional(self.pool.as_ref()).await?;
+		let mut conn = self.pool.acquire().await?;
+		let cached = conn.cached_statements_size();
+		let row = query.fetch_optional(&mut *conn).await;
+		self.statement_cache
+			.record(cached, conn.cached_statements_size());
+		let row = row?;
 		row.map(Self::convert_row).transpose()
 	}
 
+	/// Streams rows through a server-side cursor
+	///
+	/// The query is declared as a `NO SCROLL` cursor inside a dedicated
+	/// transaction and rows are fetched in batches. The transaction is
+	/// committed once the cursor is exhausted and rolled back if the stream
+	/// is dropped early.
+	fn fetch_stream<'a>(&'a self, sql: &'a str, params: Vec<QueryValue>) -> RowStream<'a> {
+		let cursor = PgCursor {
+			pool: Arc::clone(&self.pool),
+			name: format!("reinhardt_cursor_{}", Uuid::new_v4().simple()),
+			declare: Some((sql.to_string(), params)),
+			batch_size: self.stream_batch_size,
+			tx: None,
+			buffer: VecDeque::new(),
+			exhausted: false,
+		};
+		futures::stream::try_unfold(cursor, PgCursor::next).boxed()
+	}
+
 	async fn begin(&self) -> Result<Box<dyn TransactionExecutor>> {
 		let tx = self.pool.begin().await?;
 		Ok(Box::new(PgTransactionExecutor::new(tx)))
@@ -133,11 +211,71 @@ impl DatabaseBackend for PostgresBackend {
 		Ok(Box::new(PgTransactionExecutor::new(tx)))
 	}
 
+	fn statement_cache_metrics(&self) -> Option<StatementCacheMetrics> {
+		Some(self.statement_cache.metrics())
+	}
+
 	fn as_any(&self) -> &dyn std::any::Any {
 		self
 	}
 }
 
+/// Server-side cursor state of a row stream
+struct PgCursor {
+	pool: Arc<PgPool>,
+	name: String,
+	/// Query and parameters, taken when the cursor is declared
+	declare: Option<(String, Vec<QueryValue>)>,
+	batch_size: usize,
+	tx: Option<Transaction<'static, Postgres>>,
+	buffer: VecDeque<Row>,
+	exhausted: bool,
+}
+
+impl PgCursor {
+	async fn next(mut self) -> Result<Option<(Row, Self)>> {
+		loop {
+			if let Some(row) = self.buffer.pop_front() {
+				return Ok(Some((row, self)));
+			}
+
+			if let Some((sql, params)) = self.declare.take() {
+				let mut tx = self.pool.begin().await?;
+				let declare = format!("DECLARE {} NO SCROLL CURSOR FOR {}", self.name, sql);
+				let mut query = sqlx::query(&declare);
+				for param in &params {
+					query = PostgresBackend::bind_value(query, param);
+				}
+				query.execute(&mut *tx).await?;
+				self.tx = Some(tx);
+				continue;
+			}
+
+			let Some(tx) = self.tx.as_mut() else {
+				return Ok(None);
+			};
+
+			if self.exhausted {
+				sqlx::query(&format!("CLOSE {}", self.name))
+					.execute(&mut **tx)
+					.await?;
+				if let Some(tx) = self.tx.take() {
+					tx.commit().await?;
+				}
+				return Ok(None);
+			}
+
+			let rows = sqlx::query(&format!("FETCH {} FROM {}", self.batch_size, self.name))
+				.fetch_all(&mut **tx)
+				.await?;
+			self.exhausted = rows.len() < self.batch_size;
+			for row in rows {
+				self.buffer.push_back(PostgresBackend::convert_row(row)?);
+			}
+		}
+	}
+}
+
 /// PostgreSQL transaction executor
 ///
 /// This struct wraps a SQLx `Transaction` to ensure all queries
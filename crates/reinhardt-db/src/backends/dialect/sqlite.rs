@@ -1,30 +1,60 @@
 //! SQLite dialect implementation
 
 use async_trait::async_trait;
-use sqlx::{Column, Row as SqlxRow, Sqlite, SqlitePool, Transaction, TypeInfo, sqlite::SqliteRow};
+use futures::StreamExt;
+use sqlx::{
+	Column, Connection as _, Row as SqlxRow, Sqlite, SqlitePool, Transaction, TypeInfo,
+	sqlite::SqliteRow,
+};
 use std::sync::Arc;
 use tracing::warn;
 
 use super::super::{
 	backend::DatabaseBackend,
 	error::Result,
+	optimization::{StatementCache, StatementCacheMetrics},
 	types::{
-		DatabaseType, IsolationLevel, QueryResult, QueryValue, Row, Savepoint, TransactionExecutor,
+		DatabaseType, IsolationLevel, QueryResult, QueryValue, Row, RowStream, Savepoint,
+		TransactionExecutor,
 	},
 };
 
 /// SQLite database backend
 pub struct SqliteBackend {
 	pool: Arc<SqlitePool>,
+	statement_cache: Arc<StatementCache>,
 }
 
 impl SqliteBackend {
 	pub fn new(pool: SqlitePool) -> Self {
 		Self {
 			pool: Arc::new(pool),
+			statement_cache: Arc::new(StatementCache::default()),
 		}
 	}
 
+	/// Set the number of prepared statements cached per connection
+	///
+	/// This should match the `statement_cache_capacity` of the pool's connect
+	/// options. A capacity of zero disables prepared statement caching.
+	pub fn with_statement_cache_capacity(mut self, capacity: usize) -> Self {
+		self.statement_cache = Arc::new(StatementCache::new(capacity));
+		self
+	}
+
+	/// Get the prepared statement cache
+	pub fn statement_cache(&self) -> &StatementCache {
+		&self.statement_cache
+	}
+
+	/// Build a query, persisting it on the connection when caching is enabled
+	fn prepare<'q>(
+		&self,
+		sql: &'q str,
+	) -> sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>> {
+		sqlx::query(sql).persistent(self.statement_cache.is_enabled())
+	}
+
 	pub fn pool(&self) -> &SqlitePool {
 		&self.pool
 	}
@@ -51,6 +81,24 @@ impl SqliteBackend {
 		}
 	}
 
+	/// Bind a value by ownership so that the query does not borrow the parameters
+	fn bind_owned_value<'q>(
+		query: sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>>,
+		value: QueryValue,
+	) -> sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>> {
+		match value {
+			QueryValue::Null => query.bind(None::<i32>),
+			QueryValue::Bool(b) => query.bind(b),
+			QueryValue::Int(i) => query.bind(i),
+			QueryValue::Float(f) => query.bind(f),
+			QueryValue::String(s) => query.bind(s),
+			QueryValue::Bytes(b) => query.bind(b),
+			QueryValue::Timestamp(dt) => query.bind(dt),
+			QueryValue::Uuid(u) => query.bind(u.to_string()),
+			QueryValue::Now => query.bind(chrono::Utc::now()),
+		}
+	}
+
 	fn convert_row(sqlite_row: SqliteRow) -> Result<Row> {
 		let mut row = Row::new();
 		for column in sqlite_row.columns() {
@@ -153,43 +201,78 @@ impl DatabaseBackend for SqliteBackend {
 	}
 
 	async fn execute(&self, sql: &str, params: Vec<QueryValue>) -> Result<QueryResult> {
-		let mut query = sqlx::query(sql);
+		let mut query = self.prepare(sql);
 		for param in &params {
 			query = Self::bind_value(query, param);
 		}
-		let result = query.execute(self.pool.as_ref()).await?;
+		let mut conn = self.pool.acquire().await?;
+		let cached = conn.cached_statements_size();
+		let result = query.execute(&mut *conn).await;
+		self.statement_cache
+			.record(cached, conn.cached_statements_size());
+		let result = result?;
 		Ok(QueryResult {
 			rows_affected: result.rows_affected(),
 		})
 	}
 
 	async fn fetch_one(&self, sql: &str, params: Vec<QueryValue>) -> Result<Row> {
-		let mut query = sqlx::query(sql);
+		let mut query = self.prepare(sql);
 		for param in &params {
 			query = Self::bind_value(query, param);
 		}
-		let row = query.fetch_one(self.pool.as_ref()).await?;
+		let mut conn = self.pool.acquire().await?;
+		let cached = conn.cached_statements_size();
+		let row = query.fetch_one(&mut *conn).await;
+		self.statement_cache
+			.record(cached, conn.cached_statements_size());
+		let row = row?;
 		Self::convert_row(row)
 	}
 
 	async fn fetch_all(&self, sql: &str, params: Vec<QueryValue>) -> Result<Vec<Row>> {
-		let mut query = sqlx::query(sql);
+		let mut query = self.prepare(sql);
 		for param in &params {
 			query = Self::bind_value(query, param);
 		}
-		let rows = query.fetch_all(self.pool.as_ref()).await?;
+		let mut conn = self.pool.acquire().await?;
+		let cached = conn.cached_statements_size();
+		let rows = query.fetch_all(&mut *conn).await;
+		self.statement_cache
+			.record(cached, conn.cached_statements_size());
+		let rows = rows?;
 		rows.into_iter().map(Self::convert_row).collect()
 	}
 
 	async fn fetch_optional(&self, sql: &str, params: Vec<QueryValue>) -> Result<Option<Row>> {
-		let mut query = sqlx::query(sql);
+		let mut query = self.prepare(sql);
 		for param in &params {
 			query = Self::bind_value(query, param);
 		}
-		let row = query.fetch_optional(self.pool.as_ref()).await?;
+		let mut conn = self.pool.acquire().await?;
+		let cached = conn.cached_statements_size();
+		let row = query.fetch_optional(&mut *conn).await;
+		self.statement_cache
+			.record(cached, conn.cached_statements_size());
+		let row = row?;
 		row.map(Self::convert_row).transpose()
 	}
 
+	/// Streams rows as they are read from the connection
+	///
+	/// sqlx decodes rows one at a time, so the result set is never buffered
+	/// as a whole on the client.
+	fn fetch_stream<'a>(&'a self, sql: &'a str, params: Vec<QueryValue>) -> RowStream<'a> {
+		let mut query = self.prepare(sql);
+		for param in params {
+			query = Self::bind_owned_value(query, param);
+		}
+		query
+			.fetch(self.pool.as_ref())
+			.map(|row| Self::convert_row(row?))
+			.boxed()
+	}
+
 	async fn begin(&self) -> Result<Box<dyn TransactionExecutor>> {
 		let tx = self.pool.begin().await?;
 		Ok(Box::new(SqliteTransactionExecutor::new(tx)))
@@ -252,6 +335,10 @@ impl DatabaseBackend for SqliteBackend {
 		Ok(Box::new(SqliteTransactionExecutor::new(tx)))
 	}
 
+	fn statement_cache_metrics(&self) -> Option<StatementCacheMetrics> {
+		Some(self.statement_cache.metrics())
+	}
+
 	fn as_any(&self) -> &dyn std::any::Any {
 		self
 	}
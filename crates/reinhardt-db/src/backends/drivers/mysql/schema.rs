@@ -2,6 +2,9 @@
 //!
 //! This module provides MySQL-specific DDL operations through the `MySQLSchemaEditor`.
 
+use super::super::super::schema::partitioning::{
+	PartitionBound, PartitionKey, PartitionMethod, RolloverPlan, TablePartition,
+};
 use super::super::super::schema::{
 	BaseDatabaseSchemaEditor, SchemaEditorError, SchemaEditorResult,
 };
@@ -80,6 +83,176 @@ impl MySQLSchemaEditor {
 			quote_mysql_identifier(constraint_name)
 		)
 	}
+
+	/// Generate the PARTITION BY clause of a partitioned table
+	///
+	/// Range and list partitioning use the `COLUMNS` variants so that date and
+	/// string keys can be used directly. Hash partitioning creates one
+	/// partition per entry of `partitions`; MySQL names them itself.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// # use reinhardt_db::backends::drivers::mysql::schema::MySQLSchemaEditor;
+	/// # use reinhardt_db::backends::schema::partitioning::{PartitionBound, PartitionKey, TablePartition};
+	/// let editor = MySQLSchemaEditor::new();
+	/// let sql = editor
+	///     .partition_by_sql(
+	///         &PartitionKey::range(&["created_at"]),
+	///         &[TablePartition::new("p2024", PartitionBound::range("'2024-01-01'", "'2025-01-01'"))],
+	///     )
+	///     .unwrap();
+	/// assert_eq!(
+	///     sql,
+	///     "PARTITION BY RANGE COLUMNS(`created_at`) (PARTITION `p2024` VALUES LESS THAN ('2025-01-01'))"
+	/// );
+	/// ```
+	pub fn partition_by_sql(
+		&self,
+		key: &PartitionKey,
+		partitions: &[TablePartition],
+	) -> SchemaEditorResult<String> {
+		let columns: Vec<String> = key
+			.columns
+			.iter()
+			.map(|c| quote_mysql_identifier(c))
+			.collect();
+
+		if key.method == PartitionMethod::Hash {
+			if columns.len() != 1 {
+				return Err(SchemaEditorError::InvalidOperation(
+					"MySQL HASH partitioning requires exactly one column".to_string(),
+				));
+			}
+			return Ok(format!(
+				"PARTITION BY HASH({}) PARTITIONS {}",
+				columns[0],
+				partitions.len().max(1)
+			));
+		}
+
+		let definitions = partitions
+			.iter()
+			.map(Self::partition_definition_sql)
+			.collect::<SchemaEditorResult<Vec<_>>>()?;
+		Ok(format!(
+			"PARTITION BY {} COLUMNS({}) ({})",
+			key.method.as_sql(),
+			columns.join(", "),
+			definitions.join(", ")
+		))
+	}
+
+	/// Generate ALTER TABLE ... PARTITION BY SQL
+	///
+	/// Partitions an existing table, rebuilding it.
+	pub fn partition_table_sql(
+		&self,
+		table: &str,
+		key: &PartitionKey,
+		partitions: &[TablePartition],
+	) -> SchemaEditorResult<String> {
+		Ok(format!(
+			"ALTER TABLE {} {}",
+			quote_mysql_identifier(table),
+			self.partition_by_sql(key, partitions)?
+		))
+	}
+
+	/// Generate ALTER TABLE ... ADD PARTITION SQL
+	///
+	/// Range partitions can only be added above the highest existing bound,
+	/// so tables with a `MAXVALUE` partition must be reorganized instead.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// # use reinhardt_db::backends::drivers::mysql::schema::MySQLSchemaEditor;
+	/// # use reinhardt_db::backends::schema::partitioning::{PartitionBound, TablePartition};
+	/// let editor = MySQLSchemaEditor::new();
+	/// let partition = TablePartition::new("p_eu", PartitionBound::list(&["'de'", "'fr'"]));
+	/// let sql = editor.add_partition_sql("orders", &partition).unwrap();
+	/// assert_eq!(sql, "ALTER TABLE `orders` ADD PARTITION (PARTITION `p_eu` VALUES IN ('de', 'fr'))");
+	/// ```
+	pub fn add_partition_sql(
+		&self,
+		table: &str,
+		partition: &TablePartition,
+	) -> SchemaEditorResult<String> {
+		Ok(format!(
+			"ALTER TABLE {} ADD PARTITION ({})",
+			quote_mysql_identifier(table),
+			Self::partition_definition_sql(partition)?
+		))
+	}
+
+	/// Generate ALTER TABLE ... DROP PARTITION SQL
+	///
+	/// Rows stored in the partition are deleted.
+	pub fn drop_partition_sql(&self, table: &str, partition: &str) -> String {
+		format!(
+			"ALTER TABLE {} DROP PARTITION {}",
+			quote_mysql_identifier(table),
+			quote_mysql_identifier(partition)
+		)
+	}
+
+	/// Generate ALTER TABLE ... EXCHANGE PARTITION SQL
+	///
+	/// MySQL has no ATTACH/DETACH: swapping a partition with an empty table of
+	/// the same structure detaches its rows, and swapping it with a populated
+	/// table attaches them.
+	pub fn exchange_partition_sql(
+		&self,
+		table: &str,
+		partition: &str,
+		other_table: &str,
+	) -> String {
+		format!(
+			"ALTER TABLE {} EXCHANGE PARTITION {} WITH TABLE {}",
+			quote_mysql_identifier(table),
+			quote_mysql_identifier(partition),
+			quote_mysql_identifier(other_table)
+		)
+	}
+
+	/// Generate the statements applying a partition rollover plan
+	pub fn rollover_sql(
+		&self,
+		table: &str,
+		plan: &RolloverPlan,
+	) -> SchemaEditorResult<Vec<String>> {
+		let mut statements = plan
+			.create
+			.iter()
+			.map(|partition| self.add_partition_sql(table, &partition.to_partition()))
+			.collect::<SchemaEditorResult<Vec<_>>>()?;
+		statements.extend(
+			plan.drop
+				.iter()
+				.map(|name| self.drop_partition_sql(table, name)),
+		);
+		Ok(statements)
+	}
+
+	fn partition_definition_sql(partition: &TablePartition) -> SchemaEditorResult<String> {
+		let values = match &partition.bound {
+			PartitionBound::Range { to, .. } => format!("VALUES LESS THAN ({})", to),
+			PartitionBound::RangeFrom { .. } => "VALUES LESS THAN (MAXVALUE)".to_string(),
+			PartitionBound::List(values) => format!("VALUES IN ({})", values.join(", ")),
+			PartitionBound::Hash { .. } | PartitionBound::Default => {
+				return Err(SchemaEditorError::InvalidOperation(format!(
+					"Partition {} cannot be defined individually in MySQL",
+					partition.name
+				)));
+			}
+		};
+		Ok(format!(
+			"PARTITION {} {}",
+			quote_mysql_identifier(&partition.name),
+			values
+		))
+	}
 }
 
 #[async_trait::async_trait]
@@ -150,4 +323,92 @@ mod tests {
 		let sql = editor.drop_constraint_sql("users", "unique_email");
 		assert_eq!(sql, "ALTER TABLE `users` DROP CONSTRAINT `unique_email`");
 	}
+
+	#[test]
+	fn test_partition_table_sql_list() {
+		let editor = MySQLSchemaEditor::new();
+		let sql = editor
+			.partition_table_sql(
+				"orders",
+				&PartitionKey::list("region"),
+				&[
+					TablePartition::new("p_eu", PartitionBound::list(&["'de'", "'fr'"])),
+					TablePartition::new("p_us", PartitionBound::list(&["'us'"])),
+				],
+			)
+			.unwrap();
+		assert_eq!(
+			sql,
+			"ALTER TABLE `orders` PARTITION BY LIST COLUMNS(`region`) (PARTITION `p_eu` VALUES IN ('de', 'fr'), PARTITION `p_us` VALUES IN ('us'))"
+		);
+	}
+
+	#[test]
+	fn test_partition_by_sql_hash() {
+		let editor = MySQLSchemaEditor::new();
+		let partitions: Vec<TablePartition> = (0..4)
+			.map(|remainder| {
+				TablePartition::new(
+					format!("p{}", remainder),
+					PartitionBound::Hash {
+						modulus: 4,
+						remainder,
+					},
+				)
+			})
+			.collect();
+
+		let sql = editor
+			.partition_by_sql(&PartitionKey::hash(&["id"]), &partitions)
+			.unwrap();
+		assert_eq!(sql, "PARTITION BY HASH(`id`) PARTITIONS 4");
+
+		let result = editor.partition_by_sql(&PartitionKey::hash(&["a", "b"]), &partitions);
+		assert!(matches!(
+			result,
+			Err(SchemaEditorError::InvalidOperation(_))
+		));
+	}
+
+	#[test]
+	fn test_add_partition_rejects_default() {
+		let editor = MySQLSchemaEditor::new();
+		let result = editor.add_partition_sql(
+			"orders",
+			&TablePartition::new("p_other", PartitionBound::Default),
+		);
+		assert!(matches!(
+			result,
+			Err(SchemaEditorError::InvalidOperation(_))
+		));
+	}
+
+	#[test]
+	fn test_exchange_partition_sql() {
+		let editor = MySQLSchemaEditor::new();
+		let sql = editor.exchange_partition_sql("events", "p2020", "events_archive_2020");
+		assert_eq!(
+			sql,
+			"ALTER TABLE `events` EXCHANGE PARTITION `p2020` WITH TABLE `events_archive_2020`"
+		);
+	}
+
+	#[test]
+	fn test_rollover_sql() {
+		use crate::backends::schema::partitioning::{PartitionInterval, TimePartitionRollover};
+
+		let editor = MySQLSchemaEditor::new();
+		let today = chrono::NaiveDate::from_ymd_opt(2024, 12, 20).unwrap();
+		let plan = TimePartitionRollover::new("events", PartitionInterval::Month)
+			.retention(0)
+			.plan(today, &["events_p2024_11", "events_p2024_12"]);
+
+		assert_eq!(
+			editor.rollover_sql("events", &plan).unwrap(),
+			vec![
+				"ALTER TABLE `events` ADD PARTITION (PARTITION `events_p2025_01` VALUES LESS THAN ('2025-02-01'))".to_string(),
+				"ALTER TABLE `events` DROP PARTITION `events_p2024_11`".to_string(),
+			]
+		);
+	}
 }
@@ -1,8 +1,10 @@
 //! PostgreSQL backend module
 
 pub mod extensions;
+pub mod fts;
 pub mod schema;
 pub mod two_phase;
 
+pub use fts::{SearchWeight, TextSearchConfiguration, TsVectorColumn};
 pub use schema::PostgreSQLSchemaEditor;
 pub use two_phase::{PostgresTwoPhaseParticipant, PreparedTransactionInfo};
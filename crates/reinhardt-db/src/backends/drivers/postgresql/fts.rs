@@ -0,0 +1,351 @@
+//! PostgreSQL full-text search DDL
+//!
+//! This module provides building blocks for full-text search schema:
+//! - `tsvector` generated columns combining weighted source columns
+//! - Text search configurations copied from built-in ones with custom mappings
+//!
+//! # Example
+//!
+//! ```rust
+//! use reinhardt_db::backends::drivers::postgresql::fts::{SearchWeight, TsVectorColumn};
+//!
+//! let column = TsVectorColumn::new("search_vector")
+//!     .config("english")
+//!     .weighted_source("title", SearchWeight::A)
+//!     .source("body");
+//!
+//! assert_eq!(
+//!     column.expression(),
+//!     "setweight(to_tsvector('english'::regconfig, coalesce(\"title\", '')), 'A') || \
+//!      to_tsvector('english'::regconfig, coalesce(\"body\", ''))"
+//! );
+//! ```
+
+use crate::backends::schema::quote_qualified_name;
+use pg_escape::quote_literal;
+use serde::{Deserialize, Serialize};
+
+/// Default text search configuration
+pub const DEFAULT_SEARCH_CONFIG: &str = "english";
+
+fn quote_identifier(name: &str) -> String {
+	format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Weight of a source column in a `tsvector`
+///
+/// Weights rank matches: `A` is the most important, `D` the least.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SearchWeight {
+	A,
+	B,
+	C,
+	D,
+}
+
+impl SearchWeight {
+	/// Get the weight letter
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			SearchWeight::A => "A",
+			SearchWeight::B => "B",
+			SearchWeight::C => "C",
+			SearchWeight::D => "D",
+		}
+	}
+}
+
+/// Source column of a `tsvector` column
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TsVectorSource {
+	/// Column name
+	pub column: String,
+	/// Optional weight
+	pub weight: Option<SearchWeight>,
+}
+
+/// Definition of a `tsvector` generated column
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TsVectorColumn {
+	/// Column name
+	pub name: String,
+	/// Text search configuration (e.g. `english`, `simple`)
+	pub config: String,
+	/// Source columns, concatenated in order
+	pub sources: Vec<TsVectorSource>,
+}
+
+impl TsVectorColumn {
+	/// Create a `tsvector` column using the default configuration
+	pub fn new(name: impl Into<String>) -> Self {
+		Self {
+			name: name.into(),
+			config: DEFAULT_SEARCH_CONFIG.to_string(),
+			sources: Vec::new(),
+		}
+	}
+
+	/// Set the text search configuration
+	pub fn config(mut self, config: impl Into<String>) -> Self {
+		self.config = config.into();
+		self
+	}
+
+	/// Add an unweighted source column
+	pub fn source(mut self, column: impl Into<String>) -> Self {
+		self.sources.push(TsVectorSource {
+			column: column.into(),
+			weight: None,
+		});
+		self
+	}
+
+	/// Add a weighted source column
+	pub fn weighted_source(mut self, column: impl Into<String>, weight: SearchWeight) -> Self {
+		self.sources.push(TsVectorSource {
+			column: column.into(),
+			weight: Some(weight),
+		});
+		self
+	}
+
+	/// Build the expression computing the `tsvector`
+	///
+	/// The configuration is cast to `regconfig` so that the expression is
+	/// immutable, as required by generated columns and expression indexes.
+	pub fn expression(&self) -> String {
+		let config = format!("{}::regconfig", quote_literal(&self.config));
+		let parts: Vec<String> = self
+			.sources
+			.iter()
+			.map(|source| {
+				let vector = format!(
+					"to_tsvector({}, coalesce({}, ''))",
+					config,
+					quote_identifier(&source.column)
+				);
+				match source.weight {
+					Some(weight) => format!("setweight({}, '{}')", vector, weight.as_str()),
+					None => vector,
+				}
+			})
+			.collect();
+
+		if parts.is_empty() {
+			"''::tsvector".to_string()
+		} else {
+			parts.join(" || ")
+		}
+	}
+
+	/// Generate the `ALTER TABLE ... ADD COLUMN` statement
+	pub fn add_column_sql(&self, table: &str) -> String {
+		format!(
+			"ALTER TABLE {} ADD COLUMN {} tsvector GENERATED ALWAYS AS ({}) STORED",
+			quote_qualified_name(table),
+			quote_identifier(&self.name),
+			self.expression()
+		)
+	}
+
+	/// Generate the `ALTER TABLE ... DROP COLUMN` statement
+	pub fn drop_column_sql(&self, table: &str) -> String {
+		format!(
+			"ALTER TABLE {} DROP COLUMN IF EXISTS {}",
+			quote_qualified_name(table),
+			quote_identifier(&self.name)
+		)
+	}
+}
+
+/// Token type to dictionary mapping of a text search configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextSearchMapping {
+	/// Token types (e.g. `word`, `hword`, `asciiword`)
+	pub token_types: Vec<String>,
+	/// Dictionaries consulted in order (e.g. `unaccent`, `english_stem`)
+	pub dictionaries: Vec<String>,
+}
+
+/// Custom text search configuration
+///
+/// # Example
+///
+/// ```rust
+/// use reinhardt_db::backends::drivers::postgresql::fts::TextSearchConfiguration;
+///
+/// let config = TextSearchConfiguration::new("english_unaccent")
+///     .copy_from("english")
+///     .mapping(&["hword", "hword_part", "word"], &["unaccent", "english_stem"]);
+///
+/// let sql = config.create_sql();
+/// assert_eq!(sql[0], "CREATE TEXT SEARCH CONFIGURATION \"english_unaccent\" (COPY = \"english\")");
+/// assert_eq!(
+///     sql[1],
+///     "ALTER TEXT SEARCH CONFIGURATION \"english_unaccent\" ALTER MAPPING FOR hword, hword_part, word WITH \"unaccent\", \"english_stem\""
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextSearchConfiguration {
+	/// Configuration name
+	pub name: String,
+	/// Existing configuration to copy
+	pub copy_from: Option<String>,
+	/// Parser used when not copying (defaults to `default`)
+	pub parser: Option<String>,
+	/// Mappings applied after creation
+	pub mappings: Vec<TextSearchMapping>,
+}
+
+impl TextSearchConfiguration {
+	/// Create a text search configuration
+	pub fn new(name: impl Into<String>) -> Self {
+		Self {
+			name: name.into(),
+			copy_from: None,
+			parser: None,
+			mappings: Vec::new(),
+		}
+	}
+
+	/// Copy an existing configuration
+	pub fn copy_from(mut self, config: impl Into<String>) -> Self {
+		self.copy_from = Some(config.into());
+		self
+	}
+
+	/// Use a specific parser
+	pub fn parser(mut self, parser: impl Into<String>) -> Self {
+		self.parser = Some(parser.into());
+		self
+	}
+
+	/// Map token types to dictionaries
+	pub fn mapping(mut self, token_types: &[&str], dictionaries: &[&str]) -> Self {
+		self.mappings.push(TextSearchMapping {
+			token_types: token_types.iter().map(|t| t.to_string()).collect(),
+			dictionaries: dictionaries.iter().map(|d| d.to_string()).collect(),
+		});
+		self
+	}
+
+	/// Generate the statements creating the configuration and its mappings
+	pub fn create_sql(&self) -> Vec<String> {
+		let source = match (&self.copy_from, &self.parser) {
+			(Some(copy), _) => format!("COPY = {}", quote_identifier(copy)),
+			(None, Some(parser)) => format!("PARSER = {}", quote_identifier(parser)),
+			(None, None) => "PARSER = \"default\"".to_string(),
+		};
+
+		let mut statements = vec![format!(
+			"CREATE TEXT SEARCH CONFIGURATION {} ({})",
+			quote_identifier(&self.name),
+			source
+		)];
+
+		statements.extend(self.mappings.iter().map(|mapping| {
+			// Token types are keywords of the parser and must stay unquoted
+			let token_types: Vec<&str> = mapping
+				.token_types
+				.iter()
+				.map(String::as_str)
+				.filter(|t| t.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+				.collect();
+			let dictionaries: Vec<String> = mapping
+				.dictionaries
+				.iter()
+				.map(|d| quote_identifier(d))
+				.collect();
+			format!(
+				"ALTER TEXT SEARCH CONFIGURATION {} ALTER MAPPING FOR {} WITH {}",
+				quote_identifier(&self.name),
+				token_types.join(", "),
+				dictionaries.join(", ")
+			)
+		}));
+
+		statements
+	}
+
+	/// Generate the statement dropping the configuration
+	pub fn drop_sql(&self) -> String {
+		format!(
+			"DROP TEXT SEARCH CONFIGURATION IF EXISTS {}",
+			quote_identifier(&self.name)
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+
+	#[rstest]
+	fn test_tsvector_add_column_sql() {
+		// Arrange
+		let column = TsVectorColumn::new("search")
+			.config("simple")
+			.weighted_source("title", SearchWeight::A)
+			.weighted_source("body", SearchWeight::B);
+
+		// Act
+		let sql = column.add_column_sql("articles");
+
+		// Assert
+		assert_eq!(
+			sql,
+			"ALTER TABLE \"articles\" ADD COLUMN \"search\" tsvector GENERATED ALWAYS AS (\
+			 setweight(to_tsvector('simple'::regconfig, coalesce(\"title\", '')), 'A') || \
+			 setweight(to_tsvector('simple'::regconfig, coalesce(\"body\", '')), 'B')) STORED"
+		);
+	}
+
+	#[rstest]
+	fn test_tsvector_without_sources_is_empty_vector() {
+		// Arrange
+		let column = TsVectorColumn::new("search");
+
+		// Act
+		let expression = column.expression();
+
+		// Assert
+		assert_eq!(expression, "''::tsvector");
+	}
+
+	#[rstest]
+	fn test_config_literal_is_escaped() {
+		// Arrange
+		let column = TsVectorColumn::new("search")
+			.config("en'glish")
+			.source("title");
+
+		// Act
+		let expression = column.expression();
+
+		// Assert
+		assert_eq!(
+			expression,
+			"to_tsvector('en''glish'::regconfig, coalesce(\"title\", ''))"
+		);
+	}
+
+	#[rstest]
+	fn test_text_search_configuration_with_parser() {
+		// Arrange
+		let config = TextSearchConfiguration::new("custom").parser("default");
+
+		// Act
+		let sql = config.create_sql();
+
+		// Assert
+		assert_eq!(
+			sql,
+			vec!["CREATE TEXT SEARCH CONFIGURATION \"custom\" (PARSER = \"default\")".to_string()]
+		);
+		assert_eq!(
+			config.drop_sql(),
+			"DROP TEXT SEARCH CONFIGURATION IF EXISTS \"custom\""
+		);
+	}
+}
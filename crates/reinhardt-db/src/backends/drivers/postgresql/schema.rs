@@ -5,6 +5,8 @@
 /// - IDENTITY column support
 /// - Sequence operations
 /// - LIKE index auto-creation for varchar/text columns
+/// - Full-text search columns, GIN indexes and text search configurations
+/// - Declarative partitioning (range/list/hash) and partition rollover
 ///
 /// # Example
 ///
@@ -21,9 +23,14 @@
 /// # Ok(())
 /// # }
 /// ```
+use super::super::super::schema::partitioning::{
+	PartitionBound, PartitionKey, RolloverPlan, TablePartition,
+};
 use super::super::super::schema::{
-	BaseDatabaseSchemaEditor, SchemaEditorError, SchemaEditorResult,
+	BaseDatabaseSchemaEditor, SchemaEditorError, SchemaEditorResult, quote_qualified_name,
+	split_qualified_name,
 };
+use super::fts::{TextSearchConfiguration, TsVectorColumn};
 use sqlx::PgPool;
 use std::sync::Arc;
 
@@ -116,7 +123,7 @@ impl PostgreSQLSchemaEditor {
 			"CREATE {}INDEX CONCURRENTLY {} ON {} ({})",
 			unique_keyword,
 			quote_identifier(name),
-			quote_identifier(table),
+			quote_qualified_name(table),
 			quoted_columns.join(", ")
 		);
 
@@ -161,7 +168,7 @@ impl PostgreSQLSchemaEditor {
 	pub fn alter_sequence_type_sql(&self, sequence: &str, seq_type: &str) -> String {
 		format!(
 			"ALTER SEQUENCE IF EXISTS {} AS {}",
-			quote_identifier(sequence),
+			quote_qualified_name(sequence),
 			seq_type
 		)
 	}
@@ -181,7 +188,7 @@ impl PostgreSQLSchemaEditor {
 	pub fn drop_sequence_sql(&self, sequence: &str) -> String {
 		format!(
 			"DROP SEQUENCE IF EXISTS {} CASCADE",
-			quote_identifier(sequence)
+			quote_qualified_name(sequence)
 		)
 	}
 
@@ -200,7 +207,7 @@ impl PostgreSQLSchemaEditor {
 	pub fn add_identity_sql(&self, table: &str, column: &str) -> String {
 		format!(
 			"ALTER TABLE {} ALTER COLUMN {} ADD GENERATED BY DEFAULT AS IDENTITY",
-			quote_identifier(table),
+			quote_qualified_name(table),
 			quote_identifier(column)
 		)
 	}
@@ -220,7 +227,7 @@ impl PostgreSQLSchemaEditor {
 	pub fn drop_identity_sql(&self, table: &str, column: &str) -> String {
 		format!(
 			"ALTER TABLE {} ALTER COLUMN {} DROP IDENTITY IF EXISTS",
-			quote_identifier(table),
+			quote_qualified_name(table),
 			quote_identifier(column)
 		)
 	}
@@ -259,12 +266,14 @@ impl PostgreSQLSchemaEditor {
 				"varchar_pattern_ops"
 			};
 
-			let index_name = format!("{}_{}_like", table, column);
+			// Index names live in the table's schema and cannot be qualified
+			let (_, table_name) = split_qualified_name(table);
+			let index_name = format!("{}_{}_like", table_name, column);
 
 			Some(format!(
 				"CREATE INDEX {} ON {} ({} {})",
 				quote_identifier(&index_name),
-				quote_identifier(table),
+				quote_qualified_name(table),
 				quote_identifier(column),
 				pattern_ops
 			))
@@ -272,6 +281,233 @@ impl PostgreSQLSchemaEditor {
 			None
 		}
 	}
+
+	/// Generate SQL adding a `tsvector` generated column
+	///
+	/// # Example
+	///
+	/// ```rust,no_run
+	/// # use reinhardt_db::backends::drivers::postgresql::schema::PostgreSQLSchemaEditor;
+	/// # use reinhardt_db::backends::drivers::postgresql::fts::{SearchWeight, TsVectorColumn};
+	/// # use sqlx::PgPool;
+	/// let pool = PgPool::connect_lazy("postgresql://localhost/test").expect("Failed to create lazy pool");
+	/// let editor = PostgreSQLSchemaEditor::new(pool);
+	/// let column = TsVectorColumn::new("search").weighted_source("title", SearchWeight::A);
+	/// let sql = editor.add_tsvector_column_sql("articles", &column);
+	/// assert_eq!(
+	///     sql,
+	///     "ALTER TABLE \"articles\" ADD COLUMN \"search\" tsvector GENERATED ALWAYS AS (setweight(to_tsvector('english'::regconfig, coalesce(\"title\", '')), 'A')) STORED"
+	/// );
+	/// ```
+	pub fn add_tsvector_column_sql(&self, table: &str, column: &TsVectorColumn) -> String {
+		column.add_column_sql(table)
+	}
+
+	/// Generate SQL dropping a `tsvector` column
+	pub fn drop_tsvector_column_sql(&self, table: &str, column: &TsVectorColumn) -> String {
+		column.drop_column_sql(table)
+	}
+
+	/// Generate CREATE INDEX ... USING GIN SQL
+	///
+	/// `expression` is either a quoted column or an arbitrary expression such as
+	/// [`TsVectorColumn::expression`] for indexes without a stored column.
+	///
+	/// # Example
+	///
+	/// ```rust,no_run
+	/// # use reinhardt_db::backends::drivers::postgresql::schema::PostgreSQLSchemaEditor;
+	/// # use sqlx::PgPool;
+	/// let pool = PgPool::connect_lazy("postgresql://localhost/test").expect("Failed to create lazy pool");
+	/// let editor = PostgreSQLSchemaEditor::new(pool);
+	/// let sql = editor.create_gin_index_sql("articles_search_gin", "articles", "\"search\"", true);
+	/// assert_eq!(
+	///     sql,
+	///     "CREATE INDEX CONCURRENTLY \"articles_search_gin\" ON \"articles\" USING GIN (\"search\")"
+	/// );
+	/// ```
+	pub fn create_gin_index_sql(
+		&self,
+		name: &str,
+		table: &str,
+		expression: &str,
+		concurrently: bool,
+	) -> String {
+		let concurrently_keyword = if concurrently { "CONCURRENTLY " } else { "" };
+		format!(
+			"CREATE INDEX {}{} ON {} USING GIN ({})",
+			concurrently_keyword,
+			quote_identifier(name),
+			quote_qualified_name(table),
+			expression
+		)
+	}
+
+	/// Generate SQL creating a text search configuration and its mappings
+	///
+	/// # Example
+	///
+	/// ```rust,no_run
+	/// # use reinhardt_db::backends::drivers::postgresql::schema::PostgreSQLSchemaEditor;
+	/// # use reinhardt_db::backends::drivers::postgresql::fts::TextSearchConfiguration;
+	/// # use sqlx::PgPool;
+	/// let pool = PgPool::connect_lazy("postgresql://localhost/test").expect("Failed to create lazy pool");
+	/// let editor = PostgreSQLSchemaEditor::new(pool);
+	/// let config = TextSearchConfiguration::new("english_unaccent").copy_from("english");
+	/// let sql = editor.create_text_search_configuration_sql(&config);
+	/// assert_eq!(
+	///     sql,
+	///     vec!["CREATE TEXT SEARCH CONFIGURATION \"english_unaccent\" (COPY = \"english\")".to_string()]
+	/// );
+	/// ```
+	pub fn create_text_search_configuration_sql(
+		&self,
+		config: &TextSearchConfiguration,
+	) -> Vec<String> {
+		config.create_sql()
+	}
+
+	/// Generate SQL dropping a text search configuration
+	pub fn drop_text_search_configuration_sql(&self, config: &TextSearchConfiguration) -> String {
+		config.drop_sql()
+	}
+
+	/// Generate the PARTITION BY clause of a partitioned table
+	///
+	/// # Example
+	///
+	/// ```rust,no_run
+	/// # use reinhardt_db::backends::drivers::postgresql::schema::PostgreSQLSchemaEditor;
+	/// # use reinhardt_db::backends::schema::partitioning::PartitionKey;
+	/// # use sqlx::PgPool;
+	/// let pool = PgPool::connect_lazy("postgresql://localhost/test").expect("Failed to create lazy pool");
+	/// let editor = PostgreSQLSchemaEditor::new(pool);
+	/// let sql = editor.partition_by_sql(&PartitionKey::range(&["created_at"]));
+	/// assert_eq!(sql, "PARTITION BY RANGE (\"created_at\")");
+	/// ```
+	pub fn partition_by_sql(&self, key: &PartitionKey) -> String {
+		let columns: Vec<String> = key.columns.iter().map(|c| quote_identifier(c)).collect();
+		format!(
+			"PARTITION BY {} ({})",
+			key.method.as_sql(),
+			columns.join(", ")
+		)
+	}
+
+	/// Generate CREATE TABLE SQL for a partitioned table
+	///
+	/// `columns` are full column definitions, e.g. `"id" BIGINT NOT NULL`.
+	/// PostgreSQL requires primary keys and unique constraints of partitioned
+	/// tables to include every partition key column.
+	pub fn create_partitioned_table_sql(
+		&self,
+		table: &str,
+		columns: &[&str],
+		key: &PartitionKey,
+	) -> String {
+		format!(
+			"CREATE TABLE {} ({}) {}",
+			quote_qualified_name(table),
+			columns.join(", "),
+			self.partition_by_sql(key)
+		)
+	}
+
+	/// Generate CREATE TABLE ... PARTITION OF SQL
+	///
+	/// # Example
+	///
+	/// ```rust,no_run
+	/// # use reinhardt_db::backends::drivers::postgresql::schema::PostgreSQLSchemaEditor;
+	/// # use reinhardt_db::backends::schema::partitioning::{PartitionBound, TablePartition};
+	/// # use sqlx::PgPool;
+	/// let pool = PgPool::connect_lazy("postgresql://localhost/test").expect("Failed to create lazy pool");
+	/// let editor = PostgreSQLSchemaEditor::new(pool);
+	/// let partition = TablePartition::new(
+	///     "events_p2024",
+	///     PartitionBound::range("'2024-01-01'", "'2025-01-01'"),
+	/// );
+	/// let sql = editor.create_partition_sql("events", &partition);
+	/// assert_eq!(
+	///     sql,
+	///     "CREATE TABLE \"events_p2024\" PARTITION OF \"events\" FOR VALUES FROM ('2024-01-01') TO ('2025-01-01')"
+	/// );
+	/// ```
+	pub fn create_partition_sql(&self, parent: &str, partition: &TablePartition) -> String {
+		format!(
+			"CREATE TABLE {} PARTITION OF {} {}",
+			quote_qualified_name(&partition.name),
+			quote_qualified_name(parent),
+			Self::partition_bound_sql(&partition.bound)
+		)
+	}
+
+	/// Generate ALTER TABLE ... ATTACH PARTITION SQL
+	///
+	/// The attached table must already exist with a matching structure.
+	pub fn attach_partition_sql(&self, parent: &str, partition: &TablePartition) -> String {
+		format!(
+			"ALTER TABLE {} ATTACH PARTITION {} {}",
+			quote_qualified_name(parent),
+			quote_qualified_name(&partition.name),
+			Self::partition_bound_sql(&partition.bound)
+		)
+	}
+
+	/// Generate ALTER TABLE ... DETACH PARTITION SQL
+	///
+	/// `CONCURRENTLY` (PostgreSQL 14+) avoids blocking queries on the parent
+	/// table but cannot run inside a transaction block.
+	pub fn detach_partition_sql(
+		&self,
+		parent: &str,
+		partition: &str,
+		concurrently: bool,
+	) -> String {
+		let concurrently_keyword = if concurrently { " CONCURRENTLY" } else { "" };
+		format!(
+			"ALTER TABLE {} DETACH PARTITION {}{}",
+			quote_qualified_name(parent),
+			quote_qualified_name(partition),
+			concurrently_keyword
+		)
+	}
+
+	/// Generate the statements applying a partition rollover plan
+	///
+	/// Missing partitions are created and expired ones are detached, then dropped.
+	pub fn rollover_sql(&self, parent: &str, plan: &RolloverPlan) -> Vec<String> {
+		let mut statements: Vec<String> = plan
+			.create
+			.iter()
+			.map(|partition| self.create_partition_sql(parent, &partition.to_partition()))
+			.collect();
+
+		for name in &plan.drop {
+			statements.push(self.detach_partition_sql(parent, name, false));
+			statements.push(format!(
+				"DROP TABLE IF EXISTS {}",
+				quote_qualified_name(name)
+			));
+		}
+
+		statements
+	}
+
+	fn partition_bound_sql(bound: &PartitionBound) -> String {
+		match bound {
+			PartitionBound::Range { from, to } => format!("FOR VALUES FROM ({}) TO ({})", from, to),
+			PartitionBound::RangeFrom { from } => {
+				format!("FOR VALUES FROM ({}) TO (MAXVALUE)", from)
+			}
+			PartitionBound::List(values) => format!("FOR VALUES IN ({})", values.join(", ")),
+			PartitionBound::Hash { modulus, remainder } => format!(
+				"FOR VALUES WITH (MODULUS {}, REMAINDER {})",
+				modulus, remainder
+			),
+			PartitionBound::Default => "DEFAULT".to_string(),
+		}
+	}
 }
 
 #[async_trait::async_trait]
@@ -444,4 +680,179 @@ mod tests {
 		let array_sql = editor.create_like_index_sql("users", "tags", "varchar[100]");
 		assert!(array_sql.is_none());
 	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_add_tsvector_column(#[future] pg_pool: PgPool) {
+		let pool = pg_pool.await;
+		let editor = create_test_editor(pool);
+		let column = TsVectorColumn::new("search_vector")
+			.weighted_source("title", super::super::fts::SearchWeight::A)
+			.source("body");
+		let sql = editor.add_tsvector_column_sql("articles", &column);
+
+		assert_eq!(
+			sql,
+			"ALTER TABLE \"articles\" ADD COLUMN \"search_vector\" tsvector GENERATED ALWAYS AS (setweight(to_tsvector('english'::regconfig, coalesce(\"title\", '')), 'A') || to_tsvector('english'::regconfig, coalesce(\"body\", ''))) STORED"
+		);
+		assert_eq!(
+			editor.drop_tsvector_column_sql("articles", &column),
+			"ALTER TABLE \"articles\" DROP COLUMN IF EXISTS \"search_vector\""
+		);
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_create_gin_index(#[future] pg_pool: PgPool) {
+		let pool = pg_pool.await;
+		let editor = create_test_editor(pool);
+		let sql = editor.create_gin_index_sql("idx_search", "articles", "\"search_vector\"", false);
+
+		assert_eq!(
+			sql,
+			"CREATE INDEX \"idx_search\" ON \"articles\" USING GIN (\"search_vector\")"
+		);
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_create_text_search_configuration(#[future] pg_pool: PgPool) {
+		let pool = pg_pool.await;
+		let editor = create_test_editor(pool);
+		let config = TextSearchConfiguration::new("english_unaccent")
+			.copy_from("english")
+			.mapping(&["word"], &["unaccent", "english_stem"]);
+		let sql = editor.create_text_search_configuration_sql(&config);
+
+		assert_eq!(
+			sql,
+			vec![
+				"CREATE TEXT SEARCH CONFIGURATION \"english_unaccent\" (COPY = \"english\")"
+					.to_string(),
+				"ALTER TEXT SEARCH CONFIGURATION \"english_unaccent\" ALTER MAPPING FOR word WITH \"unaccent\", \"english_stem\""
+					.to_string(),
+			]
+		);
+		assert_eq!(
+			editor.drop_text_search_configuration_sql(&config),
+			"DROP TEXT SEARCH CONFIGURATION IF EXISTS \"english_unaccent\""
+		);
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_create_partitioned_table(#[future] pg_pool: PgPool) {
+		let pool = pg_pool.await;
+		let editor = create_test_editor(pool);
+		let sql = editor.create_partitioned_table_sql(
+			"events",
+			&["\"id\" BIGINT NOT NULL", "\"created_at\" DATE NOT NULL"],
+			&PartitionKey::range(&["created_at"]),
+		);
+
+		assert_eq!(
+			sql,
+			"CREATE TABLE \"events\" (\"id\" BIGINT NOT NULL, \"created_at\" DATE NOT NULL) PARTITION BY RANGE (\"created_at\")"
+		);
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_create_list_hash_and_default_partitions(#[future] pg_pool: PgPool) {
+		let pool = pg_pool.await;
+		let editor = create_test_editor(pool);
+
+		let list = TablePartition::new("orders_eu", PartitionBound::list(&["'de'", "'fr'"]));
+		assert_eq!(
+			editor.create_partition_sql("orders", &list),
+			"CREATE TABLE \"orders_eu\" PARTITION OF \"orders\" FOR VALUES IN ('de', 'fr')"
+		);
+
+		let hash = TablePartition::new(
+			"users_h0",
+			PartitionBound::Hash {
+				modulus: 4,
+				remainder: 0,
+			},
+		);
+		assert_eq!(
+			editor.create_partition_sql("users", &hash),
+			"CREATE TABLE \"users_h0\" PARTITION OF \"users\" FOR VALUES WITH (MODULUS 4, REMAINDER 0)"
+		);
+
+		let default = TablePartition::new("orders_other", PartitionBound::Default);
+		assert_eq!(
+			editor.create_partition_sql("orders", &default),
+			"CREATE TABLE \"orders_other\" PARTITION OF \"orders\" DEFAULT"
+		);
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_attach_and_detach_partition(#[future] pg_pool: PgPool) {
+		let pool = pg_pool.await;
+		let editor = create_test_editor(pool);
+		let partition = TablePartition::new(
+			"events_old",
+			PartitionBound::RangeFrom {
+				from: "'2020-01-01'".to_string(),
+			},
+		);
+
+		assert_eq!(
+			editor.attach_partition_sql("events", &partition),
+			"ALTER TABLE \"events\" ATTACH PARTITION \"events_old\" FOR VALUES FROM ('2020-01-01') TO (MAXVALUE)"
+		);
+		assert_eq!(
+			editor.detach_partition_sql("events", "events_old", true),
+			"ALTER TABLE \"events\" DETACH PARTITION \"events_old\" CONCURRENTLY"
+		);
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_rollover_sql(#[future] pg_pool: PgPool) {
+		use crate::backends::schema::partitioning::{PartitionInterval, TimePartitionRollover};
+
+		let pool = pg_pool.await;
+		let editor = create_test_editor(pool);
+		let today = chrono::NaiveDate::from_ymd_opt(2024, 5, 10).unwrap();
+		let plan = TimePartitionRollover::new("events", PartitionInterval::Month)
+			.retention(1)
+			.plan(
+				today,
+				&["events_p2024_03", "events_p2024_04", "events_p2024_05"],
+			);
+
+		assert_eq!(
+			editor.rollover_sql("events", &plan),
+			vec![
+				"CREATE TABLE \"events_p2024_06\" PARTITION OF \"events\" FOR VALUES FROM ('2024-06-01') TO ('2024-07-01')".to_string(),
+				"ALTER TABLE \"events\" DETACH PARTITION \"events_p2024_03\"".to_string(),
+				"DROP TABLE IF EXISTS \"events_p2024_03\"".to_string(),
+			]
+		);
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_schema_qualified_partition_sql(#[future] pg_pool: PgPool) {
+		let pool = pg_pool.await;
+		let editor = create_test_editor(pool);
+		let partition = TablePartition::new(
+			"tenant_a.events_old",
+			PartitionBound::RangeFrom {
+				from: "'2020-01-01'".to_string(),
+			},
+		);
+
+		assert_eq!(
+			editor.create_partition_sql("tenant_a.events", &partition),
+			"CREATE TABLE \"tenant_a\".\"events_old\" PARTITION OF \"tenant_a\".\"events\" FOR VALUES FROM ('2020-01-01') TO (MAXVALUE)"
+		);
+		assert_eq!(
+			editor.add_identity_sql("tenant_a.events", "id"),
+			"ALTER TABLE \"tenant_a\".\"events\" ALTER COLUMN \"id\" ADD GENERATED BY DEFAULT AS IDENTITY"
+		);
+	}
 }
@@ -45,6 +45,22 @@ pub enum DatabaseError {
 	#[error("Transaction error: {0}")]
 	TransactionError(String),
 
+	/// Transaction aborted due to a serialization failure (SQLSTATE 40001)
+	#[error("Serialization failure: {0}")]
+	SerializationFailure(String),
+
+	/// Transaction aborted to resolve a deadlock
+	#[error("Deadlock detected: {0}")]
+	Deadlock(String),
+
+	/// Lock could not be acquired in time (lock wait timeout, busy database)
+	#[error("Lock timeout: {0}")]
+	LockTimeout(String),
+
+	/// Connection was reset or lost while executing a statement
+	#[error("Connection reset: {0}")]
+	ConnectionReset(String),
+
 	/// Generic database error
 	#[error("Database error: {0}")]
 	Other(String),
@@ -53,6 +69,63 @@ pub enum DatabaseError {
 /// Result type for database operations
 pub type Result<T> = std::result::Result<T, DatabaseError>;
 
+impl DatabaseError {
+	/// Check whether the error is transient and the operation may succeed if retried
+	///
+	/// Serialization failures, deadlocks, lock timeouts and connection resets
+	/// are transient. See [`RetryPolicy`](super::retry::RetryPolicy).
+	pub fn is_transient(&self) -> bool {
+		matches!(
+			self,
+			DatabaseError::SerializationFailure(_)
+				| DatabaseError::Deadlock(_)
+				| DatabaseError::LockTimeout(_)
+				| DatabaseError::ConnectionReset(_)
+		)
+	}
+}
+
+/// Classify a driver error reported by the database server
+fn classify_database_error(err: &dyn sqlx::error::DatabaseError) -> DatabaseError {
+	let message = err.to_string();
+
+	#[cfg(feature = "mysql")]
+	if let Some(mysql_err) = err.try_downcast_ref::<sqlx::mysql::MySqlDatabaseError>() {
+		// MySQL reports both deadlocks and serialization failures as SQLSTATE 40001
+		match mysql_err.number() {
+			1213 => return DatabaseError::Deadlock(message),
+			1205 => return DatabaseError::LockTimeout(message),
+			2006 | 2013 => return DatabaseError::ConnectionReset(message),
+			_ => {}
+		}
+	}
+
+	#[cfg(feature = "sqlite")]
+	if err
+		.try_downcast_ref::<sqlx::sqlite::SqliteError>()
+		.is_some()
+	{
+		let code = err.code().and_then(|code| code.parse::<i32>().ok());
+		return match code {
+			// SQLITE_BUSY_SNAPSHOT: the read snapshot is stale
+			Some(517) => DatabaseError::SerializationFailure(message),
+			// SQLITE_BUSY and SQLITE_LOCKED, including their extended codes
+			Some(code) if matches!(code & 0xff, 5 | 6) => DatabaseError::LockTimeout(message),
+			_ => DatabaseError::QueryError(message),
+		};
+	}
+
+	match err.code().as_deref() {
+		Some("40001") => DatabaseError::SerializationFailure(message),
+		Some("40P01") => DatabaseError::Deadlock(message),
+		Some("55P03") => DatabaseError::LockTimeout(message),
+		// Class 08 (connection exception), admin/crash shutdown
+		Some(code) if code.starts_with("08") => DatabaseError::ConnectionReset(message),
+		Some("57P01") | Some("57P02") => DatabaseError::ConnectionReset(message),
+		_ => DatabaseError::QueryError(message),
+	}
+}
+
 impl From<serde_json::Error> for DatabaseError {
 	fn from(err: serde_json::Error) -> Self {
 		DatabaseError::SerializationError(err.to_string())
@@ -64,8 +137,14 @@ impl From<sqlx::Error> for DatabaseError {
 		use sqlx::Error::*;
 		match err {
 			Configuration(msg) => DatabaseError::ConfigError(msg.to_string()),
-			Database(e) => DatabaseError::QueryError(e.to_string()),
-			Io(e) => DatabaseError::ConnectionError(e.to_string()),
+			Database(e) => classify_database_error(e.as_ref()),
+			Io(e) => match e.kind() {
+				std::io::ErrorKind::ConnectionReset
+				| std::io::ErrorKind::ConnectionAborted
+				| std::io::ErrorKind::BrokenPipe
+				| std::io::ErrorKind::UnexpectedEof => DatabaseError::ConnectionReset(e.to_string()),
+				_ => DatabaseError::ConnectionError(e.to_string()),
+			},
 			Tls(e) => DatabaseError::ConnectionError(e.to_string()),
 			Protocol(msg) => DatabaseError::QueryError(msg),
 			RowNotFound => DatabaseError::QueryError("Row not found".to_string()),
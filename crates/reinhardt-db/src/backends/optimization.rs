@@ -3,12 +3,17 @@
 //! This module provides optimization features for database backends:
 //! - Connection pooling configuration
 //! - Query caching
+//! - Prepared statement caching
 //! - Batch operations
 
 pub mod batch_ops;
 pub mod connection_pool;
 pub mod query_cache;
+pub mod statement_cache;
 
 pub use batch_ops::{BatchInsertBuilder, BatchOperations, BatchUpdateBuilder};
 pub use connection_pool::{OptimizedPoolBuilder, PoolOptimizationConfig};
 pub use query_cache::{CachedQuery, QueryCache, QueryCacheConfig};
+pub use statement_cache::{
+	DEFAULT_STATEMENT_CACHE_CAPACITY, StatementCache, StatementCacheMetrics,
+};
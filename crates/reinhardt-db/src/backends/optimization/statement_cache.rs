@@ -0,0 +1,174 @@
+//! Prepared statement caching
+//!
+//! Backends prepare statements on each physical connection and keep them in
+//! an LRU cache keyed by SQL text, so hot queries skip the parse/plan round
+//! trip. [`StatementCache`] holds the configured capacity, decides whether
+//! statements are persisted, and counts hits and misses of those
+//! per-connection caches.
+//!
+//! A query is a miss when the connection running it had to prepare the
+//! statement, which shows as its cache growing, and a hit otherwise. Once a
+//! connection's cache is full, preparing a statement evicts another one and
+//! leaves the size unchanged, so executions on a full cache are counted as
+//! undetermined instead of being guessed. Streamed queries and statements
+//! run inside a transaction are not counted.
+//!
+//! # Example
+//!
+//! ```rust
+//! use reinhardt_db::backends::optimization::StatementCache;
+//!
+//! let cache = StatementCache::new(2);
+//! // The connection prepared the statement: its cache grew from 0 to 1
+//! assert_eq!(cache.record(0, 1), Some(false));
+//! // The statement was already prepared on the connection
+//! assert_eq!(cache.record(1, 1), Some(true));
+//!
+//! let metrics = cache.metrics();
+//! assert_eq!(metrics.hits, 1);
+//! assert_eq!(metrics.misses, 1);
+//! assert_eq!(metrics.hit_ratio(), 0.5);
+//! ```
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Default number of prepared statements kept per connection
+pub const DEFAULT_STATEMENT_CACHE_CAPACITY: usize = 100;
+
+/// Prepared statement settings with hit/miss counters
+#[derive(Debug)]
+pub struct StatementCache {
+	capacity: usize,
+	hits: AtomicU64,
+	misses: AtomicU64,
+	undetermined: AtomicU64,
+}
+
+impl StatementCache {
+	/// Create a cache holding up to `capacity` statements per connection
+	///
+	/// A capacity of zero disables caching: statements are not persisted on
+	/// the connection and every execution counts as a miss.
+	pub fn new(capacity: usize) -> Self {
+		Self {
+			capacity,
+			hits: AtomicU64::new(0),
+			misses: AtomicU64::new(0),
+			undetermined: AtomicU64::new(0),
+		}
+	}
+
+	/// Create a disabled cache
+	pub fn disabled() -> Self {
+		Self::new(0)
+	}
+
+	/// Check whether statements should be persisted on the connection
+	pub fn is_enabled(&self) -> bool {
+		self.capacity > 0
+	}
+
+	/// Get the maximum number of cached statements per connection
+	pub fn capacity(&self) -> usize {
+		self.capacity
+	}
+
+	/// Record an execution from the statement cache size of the connection
+	/// that ran it, before and after the query
+	///
+	/// Returns `Some(true)` for a hit, `Some(false)` for a miss and `None`
+	/// when the connection's cache was already full, in which case a newly
+	/// prepared statement cannot be told apart from a cached one.
+	pub fn record(&self, cached_before: usize, cached_after: usize) -> Option<bool> {
+		let hit = if !self.is_enabled() || cached_after != cached_before {
+			Some(false)
+		} else if cached_before >= self.capacity {
+			None
+		} else {
+			Some(true)
+		};
+
+		let counter = match hit {
+			Some(true) => &self.hits,
+			Some(false) => &self.misses,
+			None => &self.undetermined,
+		};
+		counter.fetch_add(1, Ordering::Relaxed);
+		hit
+	}
+
+	/// Get a snapshot of the cache metrics
+	pub fn metrics(&self) -> StatementCacheMetrics {
+		StatementCacheMetrics {
+			hits: self.hits.load(Ordering::Relaxed),
+			misses: self.misses.load(Ordering::Relaxed),
+			undetermined: self.undetermined.load(Ordering::Relaxed),
+			capacity: self.capacity,
+		}
+	}
+}
+
+impl Default for StatementCache {
+	fn default() -> Self {
+		Self::new(DEFAULT_STATEMENT_CACHE_CAPACITY)
+	}
+}
+
+/// Prepared statement cache metrics, summed over the pooled connections
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StatementCacheMetrics {
+	/// Executions of a statement already prepared on their connection
+	pub hits: u64,
+	/// Executions that prepared the statement on their connection
+	pub misses: u64,
+	/// Executions on a connection whose cache was full
+	pub undetermined: u64,
+	/// Maximum number of prepared statements per connection
+	pub capacity: usize,
+}
+
+impl StatementCacheMetrics {
+	/// Fraction of determined executions served from the cache (0.0 when unused)
+	pub fn hit_ratio(&self) -> f64 {
+		let total = self.hits + self.misses;
+		if total == 0 {
+			0.0
+		} else {
+			self.hits as f64 / total as f64
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_statement_cache_counts_hits_and_misses() {
+		let cache = StatementCache::new(2);
+
+		assert_eq!(cache.record(0, 1), Some(false));
+		assert_eq!(cache.record(1, 1), Some(true));
+		assert_eq!(cache.record(1, 2), Some(false));
+		assert_eq!(cache.record(2, 2), None);
+
+		let metrics = cache.metrics();
+		assert_eq!(metrics.hits, 1);
+		assert_eq!(metrics.misses, 2);
+		assert_eq!(metrics.undetermined, 1);
+		assert_eq!(metrics.hit_ratio(), 1.0 / 3.0);
+	}
+
+	#[test]
+	fn test_disabled_statement_cache() {
+		let cache = StatementCache::disabled();
+
+		assert!(!cache.is_enabled());
+		assert_eq!(cache.record(0, 0), Some(false));
+		assert_eq!(cache.record(0, 0), Some(false));
+
+		let metrics = cache.metrics();
+		assert_eq!(metrics.misses, 2);
+		assert_eq!(metrics.hit_ratio(), 0.0);
+	}
+}
@@ -2,7 +2,7 @@
 
 use std::sync::Arc;
 
-use sea_query::{Alias, Asterisk, Expr, ExprTrait, Query, Value};
+use sea_query::{Alias, Asterisk, Expr, ExprTrait, IntoTableRef, Query, TableRef, Value};
 
 use super::{
 	backend::DatabaseBackend,
@@ -30,6 +30,14 @@ fn query_value_to_sea_value(qv: &QueryValue) -> Value {
 	}
 }
 
+/// Build a table reference, qualified with a schema when one is set
+fn table_ref(schema: Option<&str>, table: &str) -> TableRef {
+	match schema {
+		Some(schema) => (Alias::new(schema), Alias::new(table)).into_table_ref(),
+		None => Alias::new(table).into_table_ref(),
+	}
+}
+
 /// ON CONFLICT action for INSERT statements
 #[derive(Debug, Clone)]
 pub enum OnConflictAction {
@@ -50,6 +58,7 @@ pub enum OnConflictAction {
 /// INSERT query builder
 pub struct InsertBuilder {
 	backend: Arc<dyn DatabaseBackend>,
+	schema: Option<String>,
 	table: String,
 	columns: Vec<String>,
 	values: Vec<QueryValue>,
@@ -61,6 +70,7 @@ impl InsertBuilder {
 	pub fn new(backend: Arc<dyn DatabaseBackend>, table: impl Into<String>) -> Self {
 		Self {
 			backend,
+			schema: None,
 			table: table.into(),
 			columns: Vec::new(),
 			values: Vec::new(),
@@ -69,6 +79,12 @@ impl InsertBuilder {
 		}
 	}
 
+	/// Qualify the table with a schema (e.g. a PostgreSQL tenant schema)
+	pub fn schema(mut self, schema: impl Into<String>) -> Self {
+		self.schema = Some(schema.into());
+		self
+	}
+
 	pub fn value(mut self, column: impl Into<String>, value: impl Into<QueryValue>) -> Self {
 		self.columns.push(column.into());
 		self.values.push(value.into());
@@ -130,7 +146,7 @@ impl InsertBuilder {
 		use sea_query::{MysqlQueryBuilder, PostgresQueryBuilder, SqliteQueryBuilder};
 
 		let mut stmt = Query::insert()
-			.into_table(Alias::new(&self.table))
+			.into_table(table_ref(self.schema.as_deref(), &self.table))
 			.to_owned();
 
 		// Add columns
@@ -285,6 +301,7 @@ impl InsertBuilder {
 /// UPDATE query builder
 pub struct UpdateBuilder {
 	backend: Arc<dyn DatabaseBackend>,
+	schema: Option<String>,
 	table: String,
 	sets: Vec<(String, QueryValue)>,
 	wheres: Vec<(String, String, QueryValue)>,
@@ -294,12 +311,19 @@ impl UpdateBuilder {
 	pub fn new(backend: Arc<dyn DatabaseBackend>, table: impl Into<String>) -> Self {
 		Self {
 			backend,
+			schema: None,
 			table: table.into(),
 			sets: Vec::new(),
 			wheres: Vec::new(),
 		}
 	}
 
+	/// Qualify the table with a schema (e.g. a PostgreSQL tenant schema)
+	pub fn schema(mut self, schema: impl Into<String>) -> Self {
+		self.schema = Some(schema.into());
+		self
+	}
+
 	pub fn set(mut self, column: impl Into<String>, value: impl Into<QueryValue>) -> Self {
 		self.sets.push((column.into(), value.into()));
 		self
@@ -320,7 +344,9 @@ impl UpdateBuilder {
 		use super::types::DatabaseType;
 		use sea_query::{MysqlQueryBuilder, PostgresQueryBuilder, SqliteQueryBuilder};
 
-		let mut stmt = Query::update().table(Alias::new(&self.table)).to_owned();
+		let mut stmt = Query::update()
+			.table(table_ref(self.schema.as_deref(), &self.table))
+			.to_owned();
 
 		// Add SET clauses
 		for (col, val) in &self.sets {
@@ -371,6 +397,7 @@ impl UpdateBuilder {
 pub struct SelectBuilder {
 	backend: Arc<dyn DatabaseBackend>,
 	columns: Vec<String>,
+	schema: Option<String>,
 	table: String,
 	wheres: Vec<(String, String, QueryValue)>,
 	limit: Option<i64>,
@@ -381,6 +408,7 @@ impl SelectBuilder {
 		Self {
 			backend,
 			columns: vec!["*".to_string()],
+			schema: None,
 			table: String::new(),
 			wheres: Vec::new(),
 			limit: None,
@@ -397,6 +425,12 @@ impl SelectBuilder {
 		self
 	}
 
+	/// Qualify the table with a schema (e.g. a PostgreSQL tenant schema)
+	pub fn schema(mut self, schema: impl Into<String>) -> Self {
+		self.schema = Some(schema.into());
+		self
+	}
+
 	pub fn where_eq(mut self, column: impl Into<String>, value: impl Into<QueryValue>) -> Self {
 		self.wheres
 			.push((column.into(), "=".to_string(), value.into()));
@@ -412,7 +446,9 @@ impl SelectBuilder {
 		use super::types::DatabaseType;
 		use sea_query::{MysqlQueryBuilder, PostgresQueryBuilder, SqliteQueryBuilder};
 
-		let mut stmt = Query::select().from(Alias::new(&self.table)).to_owned();
+		let mut stmt = Query::select()
+			.from(table_ref(self.schema.as_deref(), &self.table))
+			.to_owned();
 
 		// Add columns
 		if self.columns == vec!["*".to_string()] {
@@ -464,6 +500,7 @@ impl SelectBuilder {
 /// DELETE query builder
 pub struct DeleteBuilder {
 	backend: Arc<dyn DatabaseBackend>,
+	schema: Option<String>,
 	table: String,
 	wheres: Vec<(String, String, QueryValue)>,
 }
@@ -472,11 +509,18 @@ impl DeleteBuilder {
 	pub fn new(backend: Arc<dyn DatabaseBackend>, table: impl Into<String>) -> Self {
 		Self {
 			backend,
+			schema: None,
 			table: table.into(),
 			wheres: Vec::new(),
 		}
 	}
 
+	/// Qualify the table with a schema (e.g. a PostgreSQL tenant schema)
+	pub fn schema(mut self, schema: impl Into<String>) -> Self {
+		self.schema = Some(schema.into());
+		self
+	}
+
 	pub fn where_eq(mut self, column: impl Into<String>, value: impl Into<QueryValue>) -> Self {
 		self.wheres
 			.push((column.into(), "=".to_string(), value.into()));
@@ -496,7 +540,7 @@ impl DeleteBuilder {
 		use sea_query::{MysqlQueryBuilder, PostgresQueryBuilder, SqliteQueryBuilder};
 
 		let mut stmt = Query::delete()
-			.from_table(Alias::new(&self.table))
+			.from_table(table_ref(self.schema.as_deref(), &self.table))
 			.to_owned();
 
 		// Add WHERE clauses
@@ -680,4 +724,32 @@ mod tests {
 		);
 		assert_eq!(params.len(), 2);
 	}
+
+	#[test]
+	fn test_builders_qualify_table_with_schema() {
+		let backend: Arc<dyn DatabaseBackend> = Arc::new(MockBackend);
+
+		let (select_sql, _) = SelectBuilder::new(backend.clone())
+			.from("users")
+			.schema("tenant_a")
+			.build();
+		let (delete_sql, _) = DeleteBuilder::new(backend.clone(), "users")
+			.schema("tenant_a")
+			.where_eq("id", QueryValue::Int(1))
+			.build();
+		let (update_sql, _) = UpdateBuilder::new(backend, "users")
+			.schema("tenant_a")
+			.set("name", QueryValue::String("Alice".to_string()))
+			.build();
+
+		assert_eq!(select_sql, "SELECT * FROM \"tenant_a\".\"users\"");
+		assert_eq!(
+			delete_sql,
+			"DELETE FROM \"tenant_a\".\"users\" WHERE \"id\" = 1"
+		);
+		assert_eq!(
+			update_sql,
+			"UPDATE \"tenant_a\".\"users\" SET \"name\" = 'Alice'"
+		);
+	}
 }
@@ -0,0 +1,354 @@
+//! Retry policies for transient database errors
+//!
+//! Serialization failures, deadlocks, lock timeouts and connection resets are
+//! reported as typed [`DatabaseError`] variants (see
+//! [`DatabaseError::is_transient`]). A [`RetryPolicy`] re-runs an operation
+//! failing with such an error, waiting an exponentially growing, jittered
+//! delay between attempts.
+//!
+//! A statement that failed with a connection reset may already have been
+//! applied, so only statements that can safely run twice are retried:
+//! reads (see [`is_read_only`]) and statements explicitly marked idempotent.
+//! Transaction control statements are never retried.
+//!
+//! # Example
+//!
+//! ```rust
+//! use reinhardt_db::backends::retry::RetryPolicy;
+//! use std::time::Duration;
+//!
+//! let policy = RetryPolicy::new(5)
+//!     .with_base_delay(Duration::from_millis(20))
+//!     .with_max_delay(Duration::from_secs(1));
+//!
+//! assert_eq!(policy.max_retries(), 5);
+//! assert!(policy.backoff(3) <= Duration::from_secs(1));
+//! ```
+
+use std::future::Future;
+use std::time::Duration;
+
+use super::error::{DatabaseError, Result};
+
+/// Default number of retries after the first attempt
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// Default delay before the first retry
+pub const DEFAULT_BASE_DELAY: Duration = Duration::from_millis(50);
+
+/// Default upper bound of the delay between retries
+pub const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(2);
+
+/// Policy deciding whether and when to retry a failed database operation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+	max_retries: u32,
+	base_delay: Duration,
+	max_delay: Duration,
+	jitter: f64,
+}
+
+impl RetryPolicy {
+	/// Create a policy retrying transient errors up to `max_retries` times
+	pub fn new(max_retries: u32) -> Self {
+		Self {
+			max_retries,
+			base_delay: DEFAULT_BASE_DELAY,
+			max_delay: DEFAULT_MAX_DELAY,
+			jitter: 0.5,
+		}
+	}
+
+	/// Create a policy that never retries
+	pub fn none() -> Self {
+		Self::new(0)
+	}
+
+	/// Set the delay before the first retry
+	pub fn with_base_delay(mut self, delay: Duration) -> Self {
+		self.base_delay = delay;
+		self
+	}
+
+	/// Set the upper bound of the delay between retries
+	pub fn with_max_delay(mut self, delay: Duration) -> Self {
+		self.max_delay = delay;
+		self
+	}
+
+	/// Set the jitter fraction (0.0 = fixed delays, 1.0 = fully random delays)
+	///
+	/// Jitter spreads out retries of clients that failed at the same time,
+	/// e.g. both sides of a deadlock.
+	pub fn with_jitter(mut self, jitter: f64) -> Self {
+		self.jitter = jitter.clamp(0.0, 1.0);
+		self
+	}
+
+	/// Get the maximum number of retries
+	pub fn max_retries(&self) -> u32 {
+		self.max_retries
+	}
+
+	/// Check whether a failed attempt (starting at 0) should be retried
+	pub fn should_retry(&self, error: &DatabaseError, attempt: u32) -> bool {
+		attempt < self.max_retries && error.is_transient()
+	}
+
+	/// Compute the delay before retrying a failed attempt (starting at 0)
+	///
+	/// The delay doubles with every attempt up to the maximum delay, then a
+	/// random share of up to `jitter` of it is subtracted.
+	pub fn backoff(&self, attempt: u32) -> Duration {
+		let exponential = self
+			.base_delay
+			.saturating_mul(2u32.saturating_pow(attempt))
+			.min(self.max_delay);
+		let factor = 1.0 - self.jitter * rand::random::<f64>();
+		exponential.mul_f64(factor)
+	}
+
+	/// Run an operation, retrying it while it fails with a transient error
+	///
+	/// The operation is re-created for every attempt, so a whole transaction
+	/// can be retried by beginning it inside the closure.
+	///
+	/// # Example
+	///
+	/// ```rust,no_run
+	/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+	/// use reinhardt_db::backends::connection::DatabaseConnection;
+	/// use reinhardt_db::backends::retry::RetryPolicy;
+	///
+	/// let conn = DatabaseConnection::connect_postgres("postgres://localhost/mydb").await?;
+	///
+	/// RetryPolicy::default()
+	///     .run(|| async {
+	///         let mut tx = conn.begin().await?;
+	///         tx.execute("UPDATE accounts SET balance = balance - 10 WHERE id = 1", vec![]).await?;
+	///         tx.execute("UPDATE accounts SET balance = balance + 10 WHERE id = 2", vec![]).await?;
+	///         tx.commit().await
+	///     })
+	///     .await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub async fn run<F, Fut, T>(&self, mut operation: F) -> Result<T>
+	where
+		F: FnMut() -> Fut,
+		Fut: Future<Output = Result<T>>,
+	{
+		let mut attempt = 0;
+		loop {
+			match operation().await {
+				Err(error) if self.should_retry(&error, attempt) => {
+					tokio::time::sleep(self.backoff(attempt)).await;
+					attempt += 1;
+				}
+				result => return result,
+			}
+		}
+	}
+}
+
+impl Default for RetryPolicy {
+	fn default() -> Self {
+		Self::new(DEFAULT_MAX_RETRIES)
+	}
+}
+
+/// Split `sql` into upper-cased keywords, skipping comments
+fn keywords(sql: &str) -> Vec<String> {
+	let mut code = String::with_capacity(sql.len());
+	let mut rest = sql;
+	while !rest.is_empty() {
+		if let Some(after) = rest.strip_prefix("--") {
+			rest = after.find('\n').map_or("", |end| &after[end..]);
+			code.push(' ');
+		} else if let Some(after) = rest.strip_prefix("/*") {
+			rest = after.find("*/").map_or("", |end| &after[end + 2..]);
+			code.push(' ');
+		} else {
+			let mut chars = rest.chars();
+			code.extend(chars.next());
+			rest = chars.as_str();
+		}
+	}
+	code.split(|c: char| !c.is_alphanumeric() && c != '_')
+		.filter(|word| !word.is_empty())
+		.map(str::to_ascii_uppercase)
+		.collect()
+}
+
+/// Check whether `sql` is a transaction control statement
+pub fn is_transaction_control(sql: &str) -> bool {
+	matches!(
+		keywords(sql).first().map(String::as_str),
+		Some("BEGIN" | "START" | "COMMIT" | "END" | "ROLLBACK" | "SAVEPOINT" | "RELEASE" | "ABORT")
+	)
+}
+
+/// Check whether `sql` only reads data, so re-running it has no side effect
+///
+/// Conservative: any data-modifying keyword (e.g. in a CTE or `SELECT ... INTO`)
+/// makes the statement count as a write.
+///
+/// # Example
+///
+/// ```rust
+/// use reinhardt_db::backends::retry::is_read_only;
+///
+/// assert!(is_read_only("SELECT * FROM users WHERE id = $1"));
+/// assert!(!is_read_only("INSERT INTO users (name) VALUES ($1) RETURNING id"));
+/// assert!(!is_read_only("WITH moved AS (DELETE FROM a RETURNING *) SELECT * FROM moved"));
+/// ```
+pub fn is_read_only(sql: &str) -> bool {
+	let keywords = keywords(sql);
+	let reads = matches!(
+		keywords.first().map(String::as_str),
+		Some("SELECT" | "WITH" | "VALUES" | "SHOW" | "EXPLAIN" | "TABLE")
+	);
+	reads
+		&& !keywords.iter().any(|word| {
+			matches!(
+				word.as_str(),
+				"INSERT"
+					| "UPDATE" | "DELETE"
+					| "MERGE" | "INTO"
+					| "ANALYZE" | "CALL"
+					| "LOCK" | "FOR"
+			)
+		})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+	use std::sync::atomic::{AtomicU32, Ordering};
+
+	#[rstest]
+	fn test_backoff_is_exponential_and_capped() {
+		// Arrange
+		let policy = RetryPolicy::new(10)
+			.with_base_delay(Duration::from_millis(10))
+			.with_max_delay(Duration::from_millis(50))
+			.with_jitter(0.0);
+
+		// Act
+		let delays: Vec<Duration> = (0..4).map(|attempt| policy.backoff(attempt)).collect();
+
+		// Assert
+		assert_eq!(
+			delays,
+			vec![
+				Duration::from_millis(10),
+				Duration::from_millis(20),
+				Duration::from_millis(40),
+				Duration::from_millis(50),
+			]
+		);
+	}
+
+	#[rstest]
+	fn test_backoff_jitter_stays_within_bounds() {
+		// Arrange
+		let policy = RetryPolicy::new(3)
+			.with_base_delay(Duration::from_millis(100))
+			.with_jitter(0.5);
+
+		// Act & Assert
+		for _ in 0..100 {
+			let delay = policy.backoff(0);
+			assert!(delay >= Duration::from_millis(50));
+			assert!(delay <= Duration::from_millis(100));
+		}
+	}
+
+	#[rstest]
+	#[case(DatabaseError::Deadlock("deadlock".to_string()), true)]
+	#[case(DatabaseError::ConnectionReset("reset".to_string()), true)]
+	#[case(DatabaseError::SyntaxError("syntax".to_string()), false)]
+	fn test_should_retry_only_transient_errors(
+		#[case] error: DatabaseError,
+		#[case] expected: bool,
+	) {
+		// Arrange
+		let policy = RetryPolicy::new(1);
+
+		// Act & Assert
+		assert_eq!(policy.should_retry(&error, 0), expected);
+		assert!(!policy.should_retry(&error, 1));
+	}
+
+	#[rstest]
+	#[case("SELECT id FROM users", true)]
+	#[case("  -- users\n select count(*) from users", true)]
+	#[case("/* cte */ WITH t AS (SELECT 1) SELECT * FROM t", true)]
+	#[case("INSERT INTO users (name) VALUES ($1) RETURNING id", false)]
+	#[case("UPDATE users SET name = $1", false)]
+	#[case("WITH d AS (DELETE FROM users RETURNING id) SELECT * FROM d", false)]
+	#[case("SELECT * INTO backup FROM users", false)]
+	#[case("SELECT * FROM users FOR UPDATE", false)]
+	#[case("EXPLAIN ANALYZE DELETE FROM users", false)]
+	#[case("BEGIN", false)]
+	fn test_is_read_only(#[case] sql: &str, #[case] expected: bool) {
+		// Act & Assert
+		assert_eq!(is_read_only(sql), expected);
+	}
+
+	#[rstest]
+	#[case("BEGIN", true)]
+	#[case("start transaction", true)]
+	#[case("SAVEPOINT sp1", true)]
+	#[case("/* done */ COMMIT", true)]
+	#[case("SELECT 1", false)]
+	#[case("UPDATE accounts SET balance = 0", false)]
+	fn test_is_transaction_control(#[case] sql: &str, #[case] expected: bool) {
+		// Act & Assert
+		assert_eq!(is_transaction_control(sql), expected);
+	}
+
+	#[rstest]
+	#[tokio::test(start_paused = true)]
+	async fn test_run_retries_until_success() {
+		// Arrange
+		let policy = RetryPolicy::new(3);
+		let attempts = AtomicU32::new(0);
+
+		// Act
+		let result = policy
+			.run(|| async {
+				if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+					Err(DatabaseError::SerializationFailure("conflict".to_string()))
+				} else {
+					Ok(42)
+				}
+			})
+			.await;
+
+		// Assert
+		assert_eq!(result.unwrap(), 42);
+		assert_eq!(attempts.load(Ordering::SeqCst), 3);
+	}
+
+	#[rstest]
+	#[tokio::test(start_paused = true)]
+	async fn test_run_gives_up_after_max_retries() {
+		// Arrange
+		let policy = RetryPolicy::new(2);
+		let attempts = AtomicU32::new(0);
+
+		// Act
+		let result: Result<()> = policy
+			.run(|| async {
+				attempts.fetch_add(1, Ordering::SeqCst);
+				Err(DatabaseError::Deadlock("deadlock".to_string()))
+			})
+			.await;
+
+		// Assert
+		assert!(matches!(result, Err(DatabaseError::Deadlock(_))));
+		assert_eq!(attempts.load(Ordering::SeqCst), 3);
+	}
+}
@@ -19,8 +19,8 @@
 use std::fmt;
 
 use sea_query::{
-	Alias, ColumnDef, Index, IndexCreateStatement, IndexDropStatement, Table, TableAlterStatement,
-	TableCreateStatement, TableDropStatement,
+	Alias, ColumnDef, Index, IndexCreateStatement, IndexDropStatement, IntoTableRef, Table,
+	TableAlterStatement, TableCreateStatement, TableDropStatement, TableRef,
 };
 
 /// DDL reference objects for schema operations
@@ -29,6 +29,80 @@ pub mod ddl_references;
 /// Schema editor factory for creating database-specific editors
 pub mod factory;
 
+/// Declarative table partitioning definitions
+pub mod partitioning;
+
+/// Split a possibly schema-qualified table name into schema and table
+///
+/// The name is split at the first dot outside double quotes and quoted parts
+/// are unquoted, so `"tenant.a"."users"` yields `tenant.a` and `users`.
+pub(crate) fn split_qualified_name(name: &str) -> (Option<String>, String) {
+	let mut in_quotes = false;
+	let separator = name.char_indices().find(|&(_, c)| {
+		if c == '"' {
+			in_quotes = !in_quotes;
+		}
+		c == '.' && !in_quotes
+	});
+
+	match separator {
+		Some((index, _)) => (
+			Some(unquote_identifier(&name[..index])),
+			unquote_identifier(&name[index + 1..]),
+		),
+		None => (None, unquote_identifier(name)),
+	}
+}
+
+/// Strip surrounding double quotes from an identifier and unescape `""`
+fn unquote_identifier(part: &str) -> String {
+	match part.strip_prefix('"').and_then(|p| p.strip_suffix('"')) {
+		Some(inner) => inner.replace("\"\"", "\""),
+		None => part.to_string(),
+	}
+}
+
+/// Quote an identifier with double quotes, escaping embedded quotes
+fn quote_name(part: &str) -> String {
+	format!("\"{}\"", part.replace('"', "\"\""))
+}
+
+/// Build a SeaQuery table reference from a possibly schema-qualified name
+///
+/// `"tenant_a.users"` is rendered as `"tenant_a"."users"`, while an
+/// unqualified name resolves through the connection's `search_path`.
+pub(crate) fn table_ref(name: &str) -> TableRef {
+	match split_qualified_name(name) {
+		(Some(schema), table) => (Alias::new(schema), Alias::new(table)).into_table_ref(),
+		(None, table) => Alias::new(table).into_table_ref(),
+	}
+}
+
+/// Quote a possibly schema-qualified table name with double quotes
+pub(crate) fn quote_qualified_name(name: &str) -> String {
+	match split_qualified_name(name) {
+		(Some(schema), table) => format!("{}.{}", quote_name(&schema), quote_name(&table)),
+		(None, table) => quote_name(&table),
+	}
+}
+
+/// Build a `SET search_path` statement for the given schemas
+///
+/// Empty schema names are skipped. Returns `None` when no schema is left,
+/// since `SET search_path TO` needs at least one schema.
+pub(crate) fn set_search_path_sql(schemas: &[impl AsRef<str>]) -> Option<String> {
+	let schemas: Vec<String> = schemas
+		.iter()
+		.map(AsRef::as_ref)
+		.filter(|s| !s.is_empty())
+		.map(quote_name)
+		.collect();
+	if schemas.is_empty() {
+		return None;
+	}
+	Some(format!("SET search_path TO {}", schemas.join(", ")))
+}
+
 /// Represents a DDL statement type
 #[derive(Debug, Clone, PartialEq)]
 pub enum DDLStatement {
@@ -198,7 +272,7 @@ pub trait BaseDatabaseSchemaEditor: Send + Sync {
 		columns: &[(&str, &str)],
 	) -> TableCreateStatement {
 		let mut stmt = Table::create();
-		stmt.table(Alias::new(table)).if_not_exists();
+		stmt.table(table_ref(table)).if_not_exists();
 
 		for (name, definition) in columns {
 			let mut col = ColumnDef::new(Alias::new(*name));
@@ -213,7 +287,7 @@ pub trait BaseDatabaseSchemaEditor: Send + Sync {
 	/// Generate DROP TABLE statement using SeaQuery
 	fn drop_table_statement(&self, table: &str, cascade: bool) -> TableDropStatement {
 		let mut stmt = Table::drop();
-		stmt.table(Alias::new(table)).if_exists();
+		stmt.table(table_ref(table)).if_exists();
 
 		if cascade {
 			stmt.cascade();
@@ -230,7 +304,7 @@ pub trait BaseDatabaseSchemaEditor: Send + Sync {
 		definition: &str,
 	) -> TableAlterStatement {
 		let mut stmt = Table::alter();
-		stmt.table(Alias::new(table));
+		stmt.table(table_ref(table));
 
 		let mut col = ColumnDef::new(Alias::new(column));
 		// Use custom() for raw type definitions
@@ -243,7 +317,7 @@ pub trait BaseDatabaseSchemaEditor: Send + Sync {
 	/// Generate ALTER TABLE DROP COLUMN statement using SeaQuery
 	fn drop_column_statement(&self, table: &str, column: &str) -> TableAlterStatement {
 		let mut stmt = Table::alter();
-		stmt.table(Alias::new(table));
+		stmt.table(table_ref(table));
 		stmt.drop_column(Alias::new(column));
 
 		stmt.to_owned()
@@ -255,8 +329,10 @@ pub trait BaseDatabaseSchemaEditor: Send + Sync {
 	/// Note: SeaQuery doesn't support RENAME COLUMN, so we use raw SQL.
 	fn rename_column_statement(&self, table: &str, old_name: &str, new_name: &str) -> String {
 		format!(
-			"ALTER TABLE \"{}\" RENAME COLUMN \"{}\" TO \"{}\"",
-			table, old_name, new_name
+			"ALTER TABLE {} RENAME COLUMN \"{}\" TO \"{}\"",
+			quote_qualified_name(table),
+			old_name,
+			new_name
 		)
 	}
 
@@ -274,8 +350,10 @@ pub trait BaseDatabaseSchemaEditor: Send + Sync {
 	/// - CockroachDB: Same as PostgreSQL
 	fn alter_column_statement(&self, table: &str, column: &str, new_type: &str) -> String {
 		format!(
-			"ALTER TABLE \"{}\" ALTER COLUMN \"{}\" TYPE {}",
-			table, column, new_type
+			"ALTER TABLE {} ALTER COLUMN \"{}\" TYPE {}",
+			quote_qualified_name(table),
+			column,
+			new_type
 		)
 	}
 
@@ -294,10 +372,10 @@ pub trait BaseDatabaseSchemaEditor: Send + Sync {
 			// SeaQuery doesn't support partial indexes, return error to indicate fallback needed
 			// Always use double quotes for PostgreSQL identifier safety
 			return Err(format!(
-				"Partial indexes not supported by SeaQuery. Use raw SQL: CREATE {}INDEX \"{}\" ON \"{}\" ({}) WHERE {}",
+				"Partial indexes not supported by SeaQuery. Use raw SQL: CREATE {}INDEX \"{}\" ON {} ({}) WHERE {}",
 				if unique { "UNIQUE " } else { "" },
 				name,
-				table,
+				quote_qualified_name(table),
 				columns
 					.iter()
 					.map(|c| format!("\"{}\"", c))
@@ -308,7 +386,7 @@ pub trait BaseDatabaseSchemaEditor: Send + Sync {
 		}
 
 		let mut stmt = Index::create();
-		stmt.name(name).table(Alias::new(table));
+		stmt.name(name).table(table_ref(table));
 
 		if unique {
 			stmt.unique();
@@ -414,6 +492,50 @@ pub trait BaseDatabaseSchemaEditor: Send + Sync {
 		)
 	}
 
+	/// Generate a statement setting the schema search path of the connection
+	///
+	/// Unqualified table names resolve against the schemas in order, which
+	/// lets schema-per-tenant deployments switch tenants per connection.
+	/// Only meaningful for PostgreSQL (and CockroachDB).
+	///
+	/// # Errors
+	///
+	/// Returns [`SchemaEditorError::InvalidOperation`] if `schemas` contains
+	/// no non-empty schema name.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// # use reinhardt_db::backends::schema::BaseDatabaseSchemaEditor;
+	/// # use reinhardt_db::backends::DatabaseType;
+	/// # use async_trait::async_trait;
+	/// # use reinhardt_db::backends::schema::SchemaEditorResult;
+	/// struct TestEditor;
+	///
+	/// #[async_trait]
+	/// impl BaseDatabaseSchemaEditor for TestEditor {
+	///     fn database_type(&self) -> DatabaseType {
+	///         DatabaseType::Postgres
+	///     }
+	///
+	///     async fn execute(&mut self, _sql: &str) -> SchemaEditorResult<()> {
+	///         Ok(())
+	///     }
+	/// }
+	///
+	/// let editor = TestEditor;
+	/// let sql = editor.set_search_path_statement(&["tenant_a", "public"]).unwrap();
+	/// assert_eq!(sql, "SET search_path TO \"tenant_a\", \"public\"");
+	/// assert!(editor.set_search_path_statement(&[]).is_err());
+	/// ```
+	fn set_search_path_statement(&self, schemas: &[&str]) -> SchemaEditorResult<String> {
+		set_search_path_sql(schemas).ok_or_else(|| {
+			SchemaEditorError::InvalidOperation(
+				"search_path requires at least one schema".to_string(),
+			)
+		})
+	}
+
 	/// Build SQL string from TableCreateStatement using appropriate QueryBuilder
 	fn build_create_table_sql(&self, stmt: &TableCreateStatement) -> String {
 		use super::types::DatabaseType;
@@ -634,6 +756,64 @@ mod tests {
 		};
 		assert_eq!(alter_stmt.table_name(), "posts");
 	}
+	#[test]
+	fn test_schema_qualified_table_statements() {
+		use sea_query::PostgresQueryBuilder;
+
+		let editor = TestSchemaEditor;
+
+		let create_sql = editor
+			.create_table_statement("tenant_a.users", &[("id", "INTEGER")])
+			.to_string(PostgresQueryBuilder);
+		let drop_sql = editor
+			.drop_table_statement("tenant_a.users", false)
+			.to_string(PostgresQueryBuilder);
+		let rename_sql = editor.rename_column_statement("tenant_a.users", "name", "full_name");
+
+		assert!(create_sql.contains("\"tenant_a\".\"users\""));
+		assert_eq!(drop_sql, "DROP TABLE IF EXISTS \"tenant_a\".\"users\"");
+		assert_eq!(
+			rename_sql,
+			"ALTER TABLE \"tenant_a\".\"users\" RENAME COLUMN \"name\" TO \"full_name\""
+		);
+	}
+
+	#[test]
+	fn test_set_search_path_statement() {
+		let editor = TestSchemaEditor;
+
+		let sql = editor
+			.set_search_path_statement(&["tenant_a", "", "public"])
+			.unwrap();
+
+		assert_eq!(sql, "SET search_path TO \"tenant_a\", \"public\"");
+		assert!(matches!(
+			editor.set_search_path_statement(&[]),
+			Err(SchemaEditorError::InvalidOperation(_))
+		));
+		assert!(editor.set_search_path_statement(&[""]).is_err());
+	}
+
+	#[test]
+	fn test_quoted_qualified_names_keep_dots() {
+		assert_eq!(
+			split_qualified_name("\"tenant.a\".\"users.v2\""),
+			(Some("tenant.a".to_string()), "users.v2".to_string())
+		);
+		assert_eq!(
+			split_qualified_name("\"odd\"\"name.x\""),
+			(None, "odd\"name.x".to_string())
+		);
+		assert_eq!(
+			split_qualified_name("tenant_a.users"),
+			(Some("tenant_a".to_string()), "users".to_string())
+		);
+		assert_eq!(
+			quote_qualified_name("\"tenant.a\".users"),
+			"\"tenant.a\".\"users\""
+		);
+		assert_eq!(quote_qualified_name("\"my.table\""), "\"my.table\"");
+	}
 }
 
 #[cfg(test)]
@@ -0,0 +1,436 @@
+/// Declarative table partitioning
+///
+/// This module provides backend-agnostic descriptions of partitioned tables
+/// and their partitions. Backend schema editors turn them into DDL:
+/// - `PostgreSQLSchemaEditor` uses `PARTITION BY` / `PARTITION OF`
+/// - `MySQLSchemaEditor` uses `PARTITION BY` / `ADD PARTITION`
+///
+/// It also provides [`TimePartitionRollover`], a maintenance helper computing
+/// which time-based partitions must be created ahead of time and which ones
+/// have fallen out of the retention window.
+///
+/// # Example
+///
+/// ```rust
+/// # use reinhardt_db::backends::schema::partitioning::{PartitionInterval, TimePartitionRollover};
+/// use chrono::NaiveDate;
+///
+/// let rollover = TimePartitionRollover::new("events", PartitionInterval::Month)
+///     .premake(2)
+///     .retention(3);
+/// let today = NaiveDate::from_ymd_opt(2024, 5, 15).unwrap();
+/// let plan = rollover.plan(today, &["events_p2024_01", "events_p2024_05"]);
+///
+/// let created: Vec<&str> = plan.create.iter().map(|p| p.name.as_str()).collect();
+/// assert_eq!(created, vec!["events_p2024_06", "events_p2024_07"]);
+/// assert_eq!(plan.drop, vec!["events_p2024_01".to_string()]);
+/// ```
+use chrono::{Datelike, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Partitioning method
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PartitionMethod {
+	/// Partitions hold contiguous ranges of the key
+	Range,
+	/// Partitions hold explicit lists of key values
+	List,
+	/// Rows are distributed by hashing the key
+	Hash,
+}
+
+impl PartitionMethod {
+	/// Get the SQL keyword of the method
+	pub fn as_sql(&self) -> &'static str {
+		match self {
+			PartitionMethod::Range => "RANGE",
+			PartitionMethod::List => "LIST",
+			PartitionMethod::Hash => "HASH",
+		}
+	}
+}
+
+/// Partition key of a partitioned table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartitionKey {
+	/// Partitioning method
+	pub method: PartitionMethod,
+	/// Key columns
+	pub columns: Vec<String>,
+}
+
+impl PartitionKey {
+	/// Partition by ranges of the given columns
+	pub fn range(columns: &[&str]) -> Self {
+		Self::new(PartitionMethod::Range, columns)
+	}
+
+	/// Partition by lists of values of the given column
+	pub fn list(column: &str) -> Self {
+		Self::new(PartitionMethod::List, &[column])
+	}
+
+	/// Partition by hash of the given columns
+	pub fn hash(columns: &[&str]) -> Self {
+		Self::new(PartitionMethod::Hash, columns)
+	}
+
+	fn new(method: PartitionMethod, columns: &[&str]) -> Self {
+		Self {
+			method,
+			columns: columns.iter().map(|c| c.to_string()).collect(),
+		}
+	}
+}
+
+/// Values held by a single partition
+///
+/// Bound values are SQL literals inserted verbatim, e.g. `'2024-01-01'` or `10`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PartitionBound {
+	/// Range partition holding `from <= key < to`
+	///
+	/// MySQL only uses the upper bound (`VALUES LESS THAN`).
+	Range { from: String, to: String },
+	/// Range partition without upper bound
+	///
+	/// PostgreSQL: `FROM (from) TO (MAXVALUE)`, MySQL: `VALUES LESS THAN (MAXVALUE)`.
+	RangeFrom { from: String },
+	/// List partition holding the given values
+	List(Vec<String>),
+	/// Hash partition holding rows where `hash(key) % modulus == remainder`
+	///
+	/// MySQL only uses the partition count, through the `PARTITION BY` clause.
+	Hash { modulus: u32, remainder: u32 },
+	/// Default partition holding rows matched by no other partition
+	///
+	/// PostgreSQL only.
+	Default,
+}
+
+impl PartitionBound {
+	/// Create a range bound from SQL literals
+	pub fn range(from: impl Into<String>, to: impl Into<String>) -> Self {
+		Self::Range {
+			from: from.into(),
+			to: to.into(),
+		}
+	}
+
+	/// Create a list bound from SQL literals
+	pub fn list(values: &[&str]) -> Self {
+		Self::List(values.iter().map(|v| v.to_string()).collect())
+	}
+}
+
+/// Single partition of a partitioned table
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TablePartition {
+	/// Partition name
+	pub name: String,
+	/// Values held by the partition
+	pub bound: PartitionBound,
+}
+
+impl TablePartition {
+	/// Create a partition definition
+	pub fn new(name: impl Into<String>, bound: PartitionBound) -> Self {
+		Self {
+			name: name.into(),
+			bound,
+		}
+	}
+}
+
+/// Length of time covered by one time-based partition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PartitionInterval {
+	Day,
+	Month,
+	Year,
+}
+
+impl PartitionInterval {
+	/// Get the first day of the period containing `date`
+	pub fn period_start(&self, date: NaiveDate) -> NaiveDate {
+		match self {
+			PartitionInterval::Day => date,
+			PartitionInterval::Month => date.with_day(1).unwrap_or(date),
+			PartitionInterval::Year => date.with_ordinal(1).unwrap_or(date),
+		}
+	}
+
+	/// Get the first day of the period following the one starting at `start`
+	pub fn next_period(&self, start: NaiveDate) -> NaiveDate {
+		match self {
+			PartitionInterval::Day => start.succ_opt().unwrap_or(start),
+			PartitionInterval::Month => start.checked_add_months(Months::new(1)).unwrap_or(start),
+			PartitionInterval::Year => start.checked_add_months(Months::new(12)).unwrap_or(start),
+		}
+	}
+
+	/// Get the partition name suffix of the period starting at `start`
+	pub fn suffix(&self, start: NaiveDate) -> String {
+		match self {
+			PartitionInterval::Day => start.format("%Y_%m_%d").to_string(),
+			PartitionInterval::Month => start.format("%Y_%m").to_string(),
+			PartitionInterval::Year => start.format("%Y").to_string(),
+		}
+	}
+}
+
+/// Time-based range partition computed by [`TimePartitionRollover`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimePartition {
+	/// Partition name
+	pub name: String,
+	/// Inclusive lower bound
+	pub from: NaiveDate,
+	/// Exclusive upper bound
+	pub to: NaiveDate,
+}
+
+impl TimePartition {
+	/// Convert to a partition definition with date literals as bounds
+	pub fn to_partition(&self) -> TablePartition {
+		TablePartition::new(
+			self.name.clone(),
+			PartitionBound::range(
+				format!("'{}'", self.from.format("%Y-%m-%d")),
+				format!("'{}'", self.to.format("%Y-%m-%d")),
+			),
+		)
+	}
+}
+
+/// Partitions to create and drop during a rollover
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RolloverPlan {
+	/// Missing partitions, oldest first
+	pub create: Vec<TimePartition>,
+	/// Expired partition names, oldest first
+	pub drop: Vec<String>,
+}
+
+impl RolloverPlan {
+	/// Check whether the plan has nothing to do
+	pub fn is_empty(&self) -> bool {
+		self.create.is_empty() && self.drop.is_empty()
+	}
+}
+
+/// Maintenance helper for time-based range partitions
+///
+/// Partitions are named `<table>_p<suffix>` where the suffix is the start of
+/// the period (`2024_05` for monthly partitions). Run [`plan`](Self::plan)
+/// periodically and apply the resulting DDL through the schema editor's
+/// `rollover_sql`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimePartitionRollover {
+	/// Partitioned table
+	pub table: String,
+	/// Period covered by each partition
+	pub interval: PartitionInterval,
+	/// Number of future periods to create ahead of time
+	pub premake: u32,
+	/// Number of past periods to keep, `None` to keep everything
+	pub retention: Option<u32>,
+}
+
+impl TimePartitionRollover {
+	/// Create a rollover helper creating the current and next period
+	pub fn new(table: impl Into<String>, interval: PartitionInterval) -> Self {
+		Self {
+			table: table.into(),
+			interval,
+			premake: 1,
+			retention: None,
+		}
+	}
+
+	/// Set the number of future periods to create ahead of time
+	pub fn premake(mut self, periods: u32) -> Self {
+		self.premake = periods;
+		self
+	}
+
+	/// Keep only the given number of past periods
+	pub fn retention(mut self, periods: u32) -> Self {
+		self.retention = Some(periods);
+		self
+	}
+
+	/// Get the name of the partition of the period starting at `start`
+	pub fn partition_name(&self, start: NaiveDate) -> String {
+		format!("{}_p{}", self.table, self.interval.suffix(start))
+	}
+
+	/// Compute the partitions to create and drop on `today`
+	///
+	/// `existing` lists the partitions currently attached to the table.
+	/// Partitions not following the naming scheme are never dropped.
+	pub fn plan(&self, today: NaiveDate, existing: &[&str]) -> RolloverPlan {
+		let current = self.interval.period_start(today);
+
+		let mut create = Vec::new();
+		let mut start = current;
+		for _ in 0..=self.premake {
+			let end = self.interval.next_period(start);
+			let name = self.partition_name(start);
+			if !existing.contains(&name.as_str()) {
+				create.push(TimePartition {
+					name,
+					from: start,
+					to: end,
+				});
+			}
+			start = end;
+		}
+
+		let mut drop = Vec::new();
+		if let Some(retention) = self.retention {
+			let mut oldest_kept = current;
+			for _ in 0..retention {
+				oldest_kept = self.previous_period(oldest_kept);
+			}
+			let cutoff = self.partition_name(oldest_kept);
+			let prefix = format!("{}_p", self.table);
+			drop = existing
+				.iter()
+				.filter(|name| {
+					name.strip_prefix(&prefix).is_some_and(|suffix| {
+						suffix.len() == self.interval.suffix(current).len()
+							&& suffix.chars().all(|c| c.is_ascii_digit() || c == '_')
+					}) && **name < cutoff.as_str()
+				})
+				.map(|name| name.to_string())
+				.collect();
+			drop.sort();
+		}
+
+		RolloverPlan { create, drop }
+	}
+
+	fn previous_period(&self, start: NaiveDate) -> NaiveDate {
+		match self.interval {
+			PartitionInterval::Day => start.pred_opt().unwrap_or(start),
+			PartitionInterval::Month => start.checked_sub_months(Months::new(1)).unwrap_or(start),
+			PartitionInterval::Year => start.checked_sub_months(Months::new(12)).unwrap_or(start),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+
+	fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+		NaiveDate::from_ymd_opt(y, m, d).unwrap()
+	}
+
+	#[rstest]
+	#[case(
+		PartitionInterval::Day,
+		date(2024, 2, 28),
+		date(2024, 2, 28),
+		date(2024, 2, 29)
+	)]
+	#[case(
+		PartitionInterval::Month,
+		date(2024, 12, 31),
+		date(2024, 12, 1),
+		date(2025, 1, 1)
+	)]
+	#[case(
+		PartitionInterval::Year,
+		date(2024, 7, 4),
+		date(2024, 1, 1),
+		date(2025, 1, 1)
+	)]
+	fn test_interval_periods(
+		#[case] interval: PartitionInterval,
+		#[case] today: NaiveDate,
+		#[case] start: NaiveDate,
+		#[case] next: NaiveDate,
+	) {
+		assert_eq!(interval.period_start(today), start);
+		assert_eq!(interval.next_period(start), next);
+	}
+
+	#[rstest]
+	fn test_plan_creates_current_and_premade_periods() {
+		// Arrange
+		let rollover = TimePartitionRollover::new("logs", PartitionInterval::Day).premake(2);
+
+		// Act
+		let plan = rollover.plan(date(2024, 3, 1), &[]);
+
+		// Assert
+		assert_eq!(
+			plan.create,
+			vec![
+				TimePartition {
+					name: "logs_p2024_03_01".to_string(),
+					from: date(2024, 3, 1),
+					to: date(2024, 3, 2),
+				},
+				TimePartition {
+					name: "logs_p2024_03_02".to_string(),
+					from: date(2024, 3, 2),
+					to: date(2024, 3, 3),
+				},
+				TimePartition {
+					name: "logs_p2024_03_03".to_string(),
+					from: date(2024, 3, 3),
+					to: date(2024, 3, 4),
+				},
+			]
+		);
+		assert!(plan.drop.is_empty());
+	}
+
+	#[rstest]
+	fn test_plan_drops_only_expired_managed_partitions() {
+		// Arrange
+		let rollover = TimePartitionRollover::new("events", PartitionInterval::Year)
+			.premake(0)
+			.retention(1);
+		let existing = [
+			"events_p2021",
+			"events_p2022",
+			"events_p2023",
+			"events_p2024",
+			"events_default",
+		];
+
+		// Act
+		let plan = rollover.plan(date(2024, 6, 1), &existing);
+
+		// Assert
+		assert!(plan.create.is_empty());
+		assert_eq!(
+			plan.drop,
+			vec!["events_p2021".to_string(), "events_p2022".to_string()]
+		);
+	}
+
+	#[rstest]
+	fn test_time_partition_bounds_are_date_literals() {
+		// Arrange
+		let partition = TimePartition {
+			name: "events_p2024_05".to_string(),
+			from: date(2024, 5, 1),
+			to: date(2024, 6, 1),
+		};
+
+		// Act
+		let def = partition.to_partition();
+
+		// Assert
+		assert_eq!(
+			def.bound,
+			PartitionBound::range("'2024-05-01'", "'2024-06-01'")
+		);
+	}
+}
@@ -143,6 +143,10 @@ impl Default for Row {
 	}
 }
 
+/// Stream of rows returned by [`DatabaseBackend::fetch_stream`](super::backend::DatabaseBackend::fetch_stream)
+pub type RowStream<'a> =
+	std::pin::Pin<Box<dyn futures::Stream<Item = Result<Row, DatabaseError>> + Send + 'a>>;
+
 // Type conversions for QueryValue
 impl TryFrom<QueryValue> for i64 {
 	type Error = DatabaseError;
@@ -11,6 +11,7 @@
 //! - **ORM integration**: Seamless integration with reinhardt-orm
 //! - **Generic relations**: Type-safe polymorphic relationships
 //! - **Database persistence**: Store content types in database with caching
+//! - **Startup sync**: Create missing content types when the application starts
 //!
 //! ## Planned Features
 //!
@@ -50,6 +51,9 @@ pub mod serialization;
 pub mod shortcuts;
 pub mod sync;
 
+#[cfg(feature = "database")]
+pub mod cache;
+
 #[cfg(feature = "database")]
 pub mod multi_db;
 
@@ -77,7 +81,10 @@ pub use persistence::{
 pub use persistence::PersistenceError;
 
 #[cfg(feature = "database")]
-pub use multi_db::MultiDbContentTypeManager;
+pub use cache::{CachedContentTypes, InvalidationHook};
+
+#[cfg(feature = "database")]
+pub use multi_db::{MultiDbContentTypeManager, ResolvedGenericForeignKey};
 
 #[cfg(feature = "database")]
 pub use orm_integration::{ContentTypeQuery, ContentTypeTransaction};
@@ -0,0 +1,477 @@
+//! Cached ContentType lookups
+//!
+//! [`CachedContentTypes`] wraps a persistence backend with an in-memory
+//! [`ContentTypeRegistry`], so repeated lookups by natural key or ID do not
+//! query the database. It also provides the synchronization Django performs
+//! after `migrate`: content types of all known models are created when
+//! missing. The `migrate` command runs it through [`create_contenttypes`].
+//!
+//! Rows loaded from the database always replace cached entries with the
+//! same natural key, so IDs assigned in memory never shadow database IDs.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use reinhardt_db::contenttypes::cache::CachedContentTypes;
+//! use reinhardt_db::contenttypes::persistence::{ContentTypePersistence, ContentTypePersistenceBackend};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let persistence = ContentTypePersistence::new("sqlite::memory:?cache=shared").await?;
+//! persistence.create_table().await?;
+//!
+//! let content_types = CachedContentTypes::new(persistence);
+//!
+//! // Create missing content types at startup and fill the cache
+//! content_types.sync_models([("auth", "User"), ("blog", "Post")]).await?;
+//!
+//! // Served from the cache
+//! let ct = content_types.get("blog", "Post").await?;
+//! assert!(ct.is_some());
+//! # Ok(())
+//! # }
+//! ```
+
+#[cfg(feature = "database")]
+use async_trait::async_trait;
+#[cfg(feature = "database")]
+use parking_lot::RwLock;
+#[cfg(feature = "database")]
+use std::sync::Arc;
+
+#[cfg(feature = "database")]
+use super::persistence::{ContentTypePersistence, ContentTypePersistenceBackend, PersistenceError};
+#[cfg(feature = "database")]
+use super::{ContentType, ContentTypeRegistry};
+
+/// Callback invoked with each content type evicted from the cache
+#[cfg(feature = "database")]
+pub type InvalidationHook = Arc<dyn Fn(&ContentType) + Send + Sync>;
+
+/// ContentType persistence backend with an in-memory cache
+///
+/// Implements [`ContentTypePersistenceBackend`] itself, so it can be used
+/// anywhere a backend is expected. Writes through `save` and `delete` keep the
+/// cache consistent; changes made to the table by other processes require an
+/// explicit [`invalidate`](Self::invalidate) or [`clear_cache`](Self::clear_cache).
+#[cfg(feature = "database")]
+#[derive(Clone)]
+pub struct CachedContentTypes<B = ContentTypePersistence> {
+	backend: B,
+	cache: Arc<ContentTypeRegistry>,
+	hooks: Arc<RwLock<Vec<InvalidationHook>>>,
+}
+
+#[cfg(feature = "database")]
+impl<B: ContentTypePersistenceBackend> CachedContentTypes<B> {
+	/// Wrap a persistence backend with an empty cache
+	pub fn new(backend: B) -> Self {
+		Self {
+			backend,
+			cache: Arc::new(ContentTypeRegistry::new()),
+			hooks: Arc::new(RwLock::new(Vec::new())),
+		}
+	}
+
+	/// Use an existing registry as the cache
+	///
+	/// Sharing a registry lets code that only has access to the registry see
+	/// the content types loaded from the database. Content types the
+	/// registry assigned IDs to in memory are replaced by their database rows
+	/// when loaded.
+	pub fn with_cache(mut self, cache: Arc<ContentTypeRegistry>) -> Self {
+		self.cache = cache;
+		self
+	}
+
+	/// Get the underlying persistence backend
+	pub fn backend(&self) -> &B {
+		&self.backend
+	}
+
+	/// Get the registry used as the cache
+	pub fn cache(&self) -> &Arc<ContentTypeRegistry> {
+		&self.cache
+	}
+
+	/// Register a callback invoked whenever a content type leaves the cache
+	///
+	/// Use it to drop data derived from content types, such as cached
+	/// permissions or generic relation lookups.
+	pub fn on_invalidate<F>(&self, hook: F)
+	where
+		F: Fn(&ContentType) + Send + Sync + 'static,
+	{
+		self.hooks.write().push(Arc::new(hook));
+	}
+
+	/// Load every content type from the database into the cache
+	///
+	/// Returns the number of cached content types.
+	pub async fn warm(&self) -> Result<usize, PersistenceError> {
+		let content_types = self.backend.load_all().await?;
+		let count = content_types.len();
+		for ct in content_types {
+			self.cache.insert(ct);
+		}
+		Ok(count)
+	}
+
+	/// Create the content types of the given models when missing
+	///
+	/// This is the equivalent of Django's post-migrate `create_contenttypes`
+	/// and is meant to run once at application startup. The cache is warmed
+	/// first so only missing content types hit the database.
+	pub async fn sync_models<I, A, M>(
+		&self,
+		models: I,
+	) -> Result<Vec<ContentType>, PersistenceError>
+	where
+		I: IntoIterator<Item = (A, M)>,
+		A: AsRef<str>,
+		M: AsRef<str>,
+	{
+		let existing = self.backend.load_all().await?;
+		let mut known = std::collections::HashSet::with_capacity(existing.len());
+		for ct in existing {
+			known.insert(ct.natural_key());
+			self.cache.insert(ct);
+		}
+
+		let mut created = Vec::new();
+		for (app_label, model) in models {
+			let (app_label, model) = (app_label.as_ref(), model.as_ref());
+			// The cache may hold in-memory entries, so check the database rows
+			if known.contains(&(app_label.to_string(), model.to_string())) {
+				continue;
+			}
+			let ct = self.backend.get_or_create(app_label, model).await?;
+			known.insert(ct.natural_key());
+			created.push(self.cache.insert(ct));
+		}
+		Ok(created)
+	}
+
+	/// Create the content types registered in an in-memory registry when missing
+	pub async fn sync_registry(
+		&self,
+		registry: &ContentTypeRegistry,
+	) -> Result<Vec<ContentType>, PersistenceError> {
+		self.sync_models(
+			registry
+				.all()
+				.into_iter()
+				.map(|ct| (ct.app_label, ct.model)),
+		)
+		.await
+	}
+
+	/// Evict a content type from the cache by app label and model name
+	pub fn invalidate(&self, app_label: &str, model: &str) -> Option<ContentType> {
+		let removed = self.cache.remove(app_label, model);
+		if let Some(ct) = &removed {
+			self.notify(ct);
+		}
+		removed
+	}
+
+	/// Evict a content type from the cache by ID
+	pub fn invalidate_id(&self, id: i64) -> Option<ContentType> {
+		let removed = self.cache.remove_by_id(id);
+		if let Some(ct) = &removed {
+			self.notify(ct);
+		}
+		removed
+	}
+
+	/// Evict every cached content type
+	pub fn clear_cache(&self) {
+		let evicted = self.cache.all();
+		self.cache.clear();
+		for ct in &evicted {
+			self.notify(ct);
+		}
+	}
+
+	fn notify(&self, ct: &ContentType) {
+		// Clone the hooks so a hook may register further hooks without deadlocking
+		let hooks = self.hooks.read().clone();
+		for hook in hooks {
+			hook(ct);
+		}
+	}
+}
+
+/// Create the content type table and the content types of `models` when missing
+///
+/// This is the post-migrate step of the `migrate` command, the equivalent
+/// of Django's `create_contenttypes`. Returns the content types created.
+#[cfg(feature = "database")]
+pub async fn create_contenttypes<I, A, M>(
+	database_url: &str,
+	models: I,
+) -> Result<Vec<ContentType>, PersistenceError>
+where
+	I: IntoIterator<Item = (A, M)>,
+	A: AsRef<str>,
+	M: AsRef<str>,
+{
+	sqlx::any::install_default_drivers();
+	let persistence = ContentTypePersistence::new(database_url).await?;
+	persistence.create_table().await?;
+	CachedContentTypes::new(persistence)
+		.sync_models(models)
+		.await
+}
+
+#[cfg(feature = "database")]
+#[async_trait]
+impl<B: ContentTypePersistenceBackend> ContentTypePersistenceBackend for CachedContentTypes<B> {
+	async fn get(
+		&self,
+		app_label: &str,
+		model: &str,
+	) -> Result<Option<ContentType>, PersistenceError> {
+		if let Some(ct) = self.cache.get(app_label, model) {
+			return Ok(Some(ct));
+		}
+
+		let ct = self.backend.get(app_label, model).await?;
+		Ok(ct.map(|ct| self.cache.insert(ct)))
+	}
+
+	async fn get_by_id(&self, id: i64) -> Result<Option<ContentType>, PersistenceError> {
+		if let Some(ct) = self.cache.get_by_id(id) {
+			return Ok(Some(ct));
+		}
+
+		let ct = self.backend.get_by_id(id).await?;
+		Ok(ct.map(|ct| self.cache.insert(ct)))
+	}
+
+	async fn get_or_create(
+		&self,
+		app_label: &str,
+		model: &str,
+	) -> Result<ContentType, PersistenceError> {
+		if let Some(ct) = self.cache.get(app_label, model) {
+			return Ok(ct);
+		}
+
+		let ct = self.backend.get_or_create(app_label, model).await?;
+		Ok(self.cache.insert(ct))
+	}
+
+	async fn load_all(&self) -> Result<Vec<ContentType>, PersistenceError> {
+		let content_types = self.backend.load_all().await?;
+		for ct in &content_types {
+			self.cache.insert(ct.clone());
+		}
+		Ok(content_types)
+	}
+
+	async fn save(&self, ct: &ContentType) -> Result<ContentType, PersistenceError> {
+		let saved = self.backend.save(ct).await?;
+		// A rename changes the natural key, so drop the entry cached under the old one
+		if let Some(id) = saved.id {
+			self.invalidate_id(id);
+		}
+		Ok(self.cache.insert(saved))
+	}
+
+	async fn delete(&self, id: i64) -> Result<(), PersistenceError> {
+		self.backend.delete(id).await?;
+		self.invalidate_id(id);
+		Ok(())
+	}
+
+	async fn exists(&self, app_label: &str, model: &str) -> Result<bool, PersistenceError> {
+		if self.cache.get(app_label, model).is_some() {
+			return Ok(true);
+		}
+		self.backend.exists(app_label, model).await
+	}
+}
+
+#[cfg(all(test, feature = "database"))]
+mod tests {
+	use super::*;
+	use parking_lot::Mutex;
+	use std::collections::HashMap;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	/// In-memory backend counting database round trips
+	#[derive(Default)]
+	struct CountingBackend {
+		rows: Mutex<HashMap<i64, ContentType>>,
+		queries: AtomicUsize,
+	}
+
+	impl CountingBackend {
+		fn queries(&self) -> usize {
+			self.queries.load(Ordering::SeqCst)
+		}
+
+		fn hit(&self) {
+			self.queries.fetch_add(1, Ordering::SeqCst);
+		}
+	}
+
+	#[async_trait]
+	impl ContentTypePersistenceBackend for Arc<CountingBackend> {
+		async fn get(
+			&self,
+			app_label: &str,
+			model: &str,
+		) -> Result<Option<ContentType>, PersistenceError> {
+			self.hit();
+			Ok(self
+				.rows
+				.lock()
+				.values()
+				.find(|ct| ct.app_label == app_label && ct.model == model)
+				.cloned())
+		}
+
+		async fn get_by_id(&self, id: i64) -> Result<Option<ContentType>, PersistenceError> {
+			self.hit();
+			Ok(self.rows.lock().get(&id).cloned())
+		}
+
+		async fn get_or_create(
+			&self,
+			app_label: &str,
+			model: &str,
+		) -> Result<ContentType, PersistenceError> {
+			if let Some(ct) = self.get(app_label, model).await? {
+				return Ok(ct);
+			}
+			self.save(&ContentType::new(app_label, model)).await
+		}
+
+		async fn load_all(&self) -> Result<Vec<ContentType>, PersistenceError> {
+			self.hit();
+			Ok(self.rows.lock().values().cloned().collect())
+		}
+
+		async fn save(&self, ct: &ContentType) -> Result<ContentType, PersistenceError> {
+			self.hit();
+			let mut rows = self.rows.lock();
+			let id = ct.id.unwrap_or(rows.len() as i64 + 1);
+			let saved = ct.clone().with_id(id);
+			rows.insert(id, saved.clone());
+			Ok(saved)
+		}
+
+		async fn delete(&self, id: i64) -> Result<(), PersistenceError> {
+			self.hit();
+			self.rows.lock().remove(&id);
+			Ok(())
+		}
+
+		async fn exists(&self, app_label: &str, model: &str) -> Result<bool, PersistenceError> {
+			Ok(self.get(app_label, model).await?.is_some())
+		}
+	}
+
+	#[tokio::test]
+	async fn test_repeated_lookups_hit_cache() {
+		let backend = Arc::new(CountingBackend::default());
+		let content_types = CachedContentTypes::new(backend.clone());
+
+		let ct = content_types.get_or_create("blog", "Post").await.unwrap();
+		let queries = backend.queries();
+
+		for _ in 0..3 {
+			assert_eq!(
+				content_types.get("blog", "Post").await.unwrap(),
+				Some(ct.clone())
+			);
+			assert_eq!(
+				content_types.get_by_id(ct.id.unwrap()).await.unwrap(),
+				Some(ct.clone())
+			);
+		}
+		assert_eq!(backend.queries(), queries);
+	}
+
+	#[tokio::test]
+	async fn test_sync_models_creates_missing_content_types() {
+		let backend = Arc::new(CountingBackend::default());
+		backend
+			.save(&ContentType::new("auth", "User"))
+			.await
+			.unwrap();
+		let content_types = CachedContentTypes::new(backend.clone());
+
+		let created = content_types
+			.sync_models([("auth", "User"), ("blog", "Post")])
+			.await
+			.unwrap();
+
+		assert_eq!(created.len(), 1);
+		assert_eq!(created[0].qualified_name(), "blog.Post");
+		assert_eq!(backend.rows.lock().len(), 2);
+		assert!(content_types.cache().get("auth", "User").is_some());
+	}
+
+	#[tokio::test]
+	async fn test_database_ids_replace_shared_registry_ids() {
+		let backend = Arc::new(CountingBackend::default());
+		backend
+			.save(&ContentType::new("shop", "Order").with_id(7))
+			.await
+			.unwrap();
+		let registry = Arc::new(ContentTypeRegistry::new());
+		let in_memory = registry.register(ContentType::new("blog", "Post"));
+		registry.register(ContentType::new("shop", "Order"));
+		let content_types = CachedContentTypes::new(backend.clone()).with_cache(registry.clone());
+
+		content_types
+			.sync_models([("blog", "Post"), ("shop", "Order")])
+			.await
+			.unwrap();
+
+		// The in-memory "blog.Post" was not in the database, so it is created there
+		let post = registry.get("blog", "Post").unwrap();
+		assert_eq!(backend.rows.lock().get(&post.id.unwrap()), Some(&post));
+		assert_eq!(registry.get("shop", "Order").unwrap().id, Some(7));
+		assert!(registry.get_by_id(in_memory.id.unwrap()).is_none());
+	}
+
+	#[tokio::test]
+	async fn test_create_contenttypes_on_sqlite() {
+		let created = create_contenttypes("sqlite::memory:?cache=shared", [("blog", "Post")])
+			.await
+			.unwrap();
+
+		assert_eq!(created.len(), 1);
+		assert_eq!(created[0].qualified_name(), "blog.Post");
+		assert!(created[0].id.is_some());
+	}
+
+	#[tokio::test]
+	async fn test_save_and_delete_invalidate_cache() {
+		let backend = Arc::new(CountingBackend::default());
+		let content_types = CachedContentTypes::new(backend.clone());
+		let evicted = Arc::new(Mutex::new(Vec::new()));
+		let hook_evicted = evicted.clone();
+		content_types.on_invalidate(move |ct| hook_evicted.lock().push(ct.qualified_name()));
+
+		let ct = content_types.get_or_create("blog", "Post").await.unwrap();
+		let renamed = ContentType {
+			model: "Article".to_string(),
+			..ct.clone()
+		};
+		content_types.save(&renamed).await.unwrap();
+
+		assert!(content_types.cache().get("blog", "Post").is_none());
+		assert_eq!(
+			content_types.cache().get_by_id(ct.id.unwrap()),
+			Some(renamed.clone())
+		);
+
+		content_types.delete(ct.id.unwrap()).await.unwrap();
+		assert!(content_types.cache().get("blog", "Article").is_none());
+		assert_eq!(*evicted.lock(), vec!["blog.Post", "blog.Article"]);
+	}
+}
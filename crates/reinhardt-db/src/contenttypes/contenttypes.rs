@@ -3,6 +3,9 @@
 ///
 /// This module provides both string-based (runtime) and type-safe (compile-time)
 /// content type registry mechanisms.
+use crate::orm::{Filter, FilterOperator, FilterValue, Model, QuerySet};
+use reinhardt_core::exception::{Error, Result};
+use sea_query::{Alias, Condition, Expr, ExprTrait};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::RwLock;
@@ -38,6 +41,30 @@ impl ContentType {
 	pub fn qualified_name(&self) -> String {
 		format!("{}.{}", self.app_label, self.model)
 	}
+
+	/// Get the id of this content type
+	///
+	/// Falls back to the id registered in [`CONTENT_TYPE_REGISTRY`] under
+	/// the same natural key when the content type carries none.
+	///
+	/// # Errors
+	///
+	/// Returns [`Error::ImproperlyConfigured`] when the content type has
+	/// neither an id nor a registered counterpart.
+	pub fn resolve_id(&self) -> Result<i64> {
+		self.id
+			.or_else(|| {
+				CONTENT_TYPE_REGISTRY
+					.get(&self.app_label, &self.model)
+					.and_then(|ct| ct.id)
+			})
+			.ok_or_else(|| {
+				Error::ImproperlyConfigured(format!(
+					"Content type {} has no id; register or sync it first",
+					self.qualified_name()
+				))
+			})
+	}
 }
 
 /// Registry for managing content types
@@ -81,6 +108,34 @@ impl ContentTypeRegistry {
 		ct
 	}
 
+	/// Store a content type whose ID comes from the database
+	///
+	/// Unlike [`register`](Self::register), an entry registered under the
+	/// same natural key is replaced, so the database ID wins over an ID
+	/// assigned in memory. Later in-memory IDs are allocated above it.
+	pub fn insert(&self, ct: ContentType) -> ContentType {
+		let key = ct.natural_key();
+		let mut types = self.types.write().unwrap();
+		let mut by_id = self.by_id.write().unwrap();
+
+		if let Some(previous_id) = types.insert(key.clone(), ct.clone()).and_then(|ct| ct.id) {
+			by_id.remove(&previous_id);
+		}
+		if let Some(id) = ct.id {
+			// Another model that was given this ID in memory loses it
+			if let Some(other) = by_id.insert(id, ct.clone())
+				&& other.natural_key() != key
+			{
+				types.remove(&other.natural_key());
+			}
+			let mut next_id = self.next_id.write().unwrap();
+			if *next_id <= id {
+				*next_id = id + 1;
+			}
+		}
+		ct
+	}
+
 	/// Get content type by app label and model name
 	pub fn get(&self, app_label: &str, model: &str) -> Option<ContentType> {
 		let key = (app_label.to_string(), model.to_string());
@@ -106,6 +161,23 @@ impl ContentTypeRegistry {
 		self.types.read().unwrap().values().cloned().collect()
 	}
 
+	/// Remove a content type by app label and model name
+	pub fn remove(&self, app_label: &str, model: &str) -> Option<ContentType> {
+		let key = (app_label.to_string(), model.to_string());
+		let removed = self.types.write().unwrap().remove(&key)?;
+		if let Some(id) = removed.id {
+			self.by_id.write().unwrap().remove(&id);
+		}
+		Some(removed)
+	}
+
+	/// Remove a content type by ID
+	pub fn remove_by_id(&self, id: i64) -> Option<ContentType> {
+		let removed = self.by_id.write().unwrap().remove(&id)?;
+		self.types.write().unwrap().remove(&removed.natural_key());
+		Some(removed)
+	}
+
 	/// Clear all registered types (mainly for testing)
 	pub fn clear(&self) {
 		self.types.write().unwrap().clear();
@@ -172,9 +244,40 @@ pub trait GenericRelatable {
 }
 
 /// Helper for building generic relation queries
+///
+/// Compiles to ORM filters on the content type and object id columns of the
+/// model holding the `GenericForeignKey`, so it can be applied to any
+/// `QuerySet` or used as a join condition.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_db::contenttypes::{ContentType, GenericRelationQuery};
+/// use sea_query::{Alias, Asterisk, PostgresQueryBuilder, Query};
+///
+/// let ct = ContentType::new("blog", "Post").with_id(3);
+/// let mut query = GenericRelationQuery::new(ct);
+/// query.add_object(42);
+///
+/// assert_eq!(query.filters().unwrap().len(), 2);
+///
+/// let sql = Query::select()
+///     .column(Asterisk)
+///     .from(Alias::new("posts"))
+///     .inner_join(
+///         Alias::new("comments"),
+///         query.join_condition("comments", "posts", "id").unwrap(),
+///     )
+///     .to_string(PostgresQueryBuilder);
+/// assert!(sql.contains(
+///     r#"ON "comments"."content_type_id" = 3 AND "comments"."object_id" = "posts"."id""#
+/// ));
+/// ```
 pub struct GenericRelationQuery {
 	content_type: ContentType,
 	object_ids: Vec<i64>,
+	ct_field: String,
+	fk_field: String,
 }
 
 impl GenericRelationQuery {
@@ -182,13 +285,87 @@ impl GenericRelationQuery {
 		Self {
 			content_type,
 			object_ids: Vec::new(),
+			ct_field: "content_type_id".to_string(),
+			fk_field: "object_id".to_string(),
 		}
 	}
 
+	/// Create a query matching the rows that point to `target`
+	pub fn for_object<R: GenericRelatable>(target: &R) -> Self {
+		let mut query = Self::new(R::get_content_type());
+		query.add_object(target.get_object_id());
+		query
+	}
+
+	/// Set the content type and object id column names
+	pub fn with_fields(mut self, ct_field: impl Into<String>, fk_field: impl Into<String>) -> Self {
+		self.ct_field = ct_field.into();
+		self.fk_field = fk_field.into();
+		self
+	}
+
 	pub fn add_object(&mut self, object_id: i64) {
 		self.object_ids.push(object_id);
 	}
 
+	/// Content type the related rows must point to
+	pub fn content_type(&self) -> &ContentType {
+		&self.content_type
+	}
+
+	/// Object ids the related rows must point to
+	pub fn object_ids(&self) -> &[i64] {
+		&self.object_ids
+	}
+
+	/// ORM filters selecting the related rows
+	///
+	/// A single object compiles to an equality, several objects to an `IN`
+	/// clause. Without any object the filters match nothing.
+	///
+	/// # Errors
+	///
+	/// Fails when the content type id cannot be resolved, see
+	/// [`ContentType::resolve_id`].
+	pub fn filters(&self) -> Result<Vec<Filter>> {
+		Ok(generic_relation_filters(
+			&self.ct_field,
+			&self.fk_field,
+			self.content_type.resolve_id()?,
+			&self.object_ids,
+		))
+	}
+
+	/// Restrict a QuerySet to the related rows
+	pub fn apply<T: Model>(&self, queryset: QuerySet<T>) -> Result<QuerySet<T>> {
+		Ok(self
+			.filters()?
+			.into_iter()
+			.fold(queryset, |queryset, filter| queryset.filter(filter)))
+	}
+
+	/// Join condition between the related table and the owner table
+	///
+	/// Matches the related rows pointing to the owner's content type and
+	/// primary key, for use in sea-query joins.
+	pub fn join_condition(
+		&self,
+		related_table: &str,
+		owner_table: &str,
+		owner_pk: &str,
+	) -> Result<Condition> {
+		let related = Alias::new(related_table);
+		Ok(Condition::all()
+			.add(
+				Expr::col((related.clone(), Alias::new(&self.ct_field)))
+					.eq(self.content_type.resolve_id()?),
+			)
+			.add(
+				Expr::col((related, Alias::new(&self.fk_field)))
+					.equals((Alias::new(owner_table), Alias::new(owner_pk))),
+			))
+	}
+
 	pub fn to_sql(&self, table: &str) -> String {
 		let ct_id = self.content_type.id.unwrap_or(0);
 		let ids = self
@@ -199,12 +376,42 @@ impl GenericRelationQuery {
 			.join(", ");
 
 		format!(
-			"SELECT * FROM {} WHERE content_type_id = {} AND object_id IN ({})",
-			table, ct_id, ids
+			"SELECT * FROM {} WHERE {} = {} AND {} IN ({})",
+			table, self.ct_field, ct_id, self.fk_field, ids
 		)
 	}
 }
 
+/// Filters selecting the rows whose generic foreign key points to one of
+/// `object_ids` of a content type
+pub(crate) fn generic_relation_filters(
+	ct_field: &str,
+	fk_field: &str,
+	content_type_id: i64,
+	object_ids: &[i64],
+) -> Vec<Filter> {
+	let ct_filter = Filter::new(
+		ct_field.to_string(),
+		FilterOperator::Eq,
+		FilterValue::Integer(content_type_id),
+	);
+	let fk_filter = match object_ids {
+		[object_id] => Filter::new(
+			fk_field.to_string(),
+			FilterOperator::Eq,
+			FilterValue::Integer(*object_id),
+		),
+		object_ids => Filter::new(
+			fk_field.to_string(),
+			FilterOperator::In,
+			FilterValue::String(
+				serde_json::to_string(object_ids).unwrap_or_else(|_| "[]".to_string()),
+			),
+		),
+	};
+	vec![ct_filter, fk_filter]
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -319,6 +526,37 @@ mod tests {
 		assert!(sql.contains("object_id IN (1, 2, 3)"));
 	}
 
+	#[test]
+	fn test_registry_remove() {
+		let registry = ContentTypeRegistry::new();
+		let post = registry.register(ContentType::new("blog", "Post"));
+		let tag = registry.register(ContentType::new("blog", "Tag"));
+
+		assert_eq!(registry.remove("blog", "Post"), Some(post.clone()));
+		assert!(registry.get_by_id(post.id.unwrap()).is_none());
+
+		assert_eq!(registry.remove_by_id(tag.id.unwrap()), Some(tag));
+		assert!(registry.get("blog", "Tag").is_none());
+		assert!(registry.remove("blog", "Tag").is_none());
+	}
+
+	#[test]
+	fn test_registry_insert_replaces_in_memory_id() {
+		let registry = ContentTypeRegistry::new();
+		let post = registry.register(ContentType::new("blog", "Post"));
+		let tag = registry.register(ContentType::new("blog", "Tag"));
+
+		let stored = registry.insert(ContentType::new("blog", "Post").with_id(tag.id.unwrap()));
+
+		assert_eq!(registry.get("blog", "Post"), Some(stored.clone()));
+		assert_eq!(registry.get_by_id(tag.id.unwrap()), Some(stored));
+		assert!(registry.get_by_id(post.id.unwrap()).is_none());
+		// The model that held the ID in memory is dropped and gets a fresh one
+		assert!(registry.get("blog", "Tag").is_none());
+		let tag = registry.register(ContentType::new("blog", "Tag"));
+		assert!(tag.id.unwrap() > 2);
+	}
+
 	#[test]
 	fn test_registry_clear() {
 		let registry = ContentTypeRegistry::new();
@@ -3,6 +3,15 @@
 //! This module provides ContentType management functionality across multiple databases.
 //! It maintains independent ContentType caches for each database and supports
 //! cross-database queries.
+//!
+//! ## Routing
+//!
+//! With a [`DatabaseRouter`] attached, content types are stored in the database
+//! the router assigns to their model, like Django storing content types next
+//! to the models they describe. Generic foreign keys are resolved in two
+//! steps: the content type ID is looked up in the database holding the
+//! referencing row, then the target object is located in the database its
+//! model is routed to.
 
 #[cfg(feature = "database")]
 use parking_lot::RwLock;
@@ -11,10 +20,26 @@ use std::collections::HashMap;
 #[cfg(feature = "database")]
 use std::sync::Arc;
 
+#[cfg(feature = "database")]
+use super::generic_fk::GenericForeignKeyField;
 #[cfg(feature = "database")]
 use super::persistence::{ContentTypePersistence, ContentTypePersistenceBackend, PersistenceError};
 #[cfg(feature = "database")]
 use super::{ContentType, ContentTypeRegistry};
+#[cfg(feature = "database")]
+use crate::orm::database_routing::DatabaseRouter;
+
+/// A generic foreign key resolved across databases
+#[cfg(feature = "database")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedGenericForeignKey {
+	/// Content type of the target, as stored in the target database
+	pub content_type: ContentType,
+	/// Primary key of the target object
+	pub object_id: i64,
+	/// Database the target object lives in
+	pub db_alias: String,
+}
 
 /// ContentType management in multi-database environments
 ///
@@ -47,6 +72,8 @@ pub struct MultiDbContentTypeManager {
 	registries: Arc<RwLock<HashMap<String, Arc<ContentTypeRegistry>>>>,
 	/// Default database name
 	default_db: Option<String>,
+	/// Router selecting the database of each model
+	router: Option<Arc<DatabaseRouter>>,
 }
 
 #[cfg(feature = "database")]
@@ -65,6 +92,7 @@ impl MultiDbContentTypeManager {
 			databases: Arc::new(RwLock::new(HashMap::new())),
 			registries: Arc::new(RwLock::new(HashMap::new())),
 			default_db: None,
+			router: None,
 		}
 	}
 
@@ -90,6 +118,67 @@ impl MultiDbContentTypeManager {
 		self.default_db.as_deref()
 	}
 
+	/// Route content types to the database of their model
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_db::contenttypes::multi_db::MultiDbContentTypeManager;
+	/// use reinhardt_db::orm::database_routing::DatabaseRouter;
+	///
+	/// let router = DatabaseRouter::new("default").add_rule("AccessLog", "analytics");
+	/// let manager = MultiDbContentTypeManager::new().with_router(router);
+	///
+	/// assert_eq!(manager.db_for_read("logs", "AccessLog").as_deref(), Some("analytics"));
+	/// assert_eq!(manager.db_for_write("auth", "User").as_deref(), Some("default"));
+	/// ```
+	pub fn with_router(mut self, router: impl Into<Arc<DatabaseRouter>>) -> Self {
+		self.router = Some(router.into());
+		self
+	}
+
+	/// Get the database router, if any
+	pub fn router(&self) -> Option<&Arc<DatabaseRouter>> {
+		self.router.as_ref()
+	}
+
+	/// Database alias used to read the content type of a model
+	///
+	/// Router rules may name either the model (`Post`) or the qualified name
+	/// (`blog.Post`); the qualified name wins. Without a router the default
+	/// database is used.
+	pub fn db_for_read(&self, app_label: &str, model: &str) -> Option<String> {
+		self.route(app_label, model, DatabaseRouter::db_for_read)
+	}
+
+	/// Database alias used to write the content type of a model
+	pub fn db_for_write(&self, app_label: &str, model: &str) -> Option<String> {
+		self.route(app_label, model, DatabaseRouter::db_for_write)
+	}
+
+	fn route(
+		&self,
+		app_label: &str,
+		model: &str,
+		select: fn(&DatabaseRouter, &str) -> String,
+	) -> Option<String> {
+		let Some(router) = &self.router else {
+			return self.default_db.clone();
+		};
+		let qualified = format!("{}.{}", app_label, model);
+		if router.has_rule(&qualified) {
+			Some(select(router, &qualified))
+		} else {
+			Some(select(router, model))
+		}
+	}
+
+	fn routed_db(&self, db_alias: Option<String>) -> Result<String, PersistenceError> {
+		db_alias.ok_or_else(|| {
+			PersistenceError::DatabaseError("No database configured for content types".to_string())
+		})
+	}
+
 	/// Add a database
 	///
 	/// # Example
@@ -186,6 +275,98 @@ impl MultiDbContentTypeManager {
 		self.registries.read().get(db_alias).cloned()
 	}
 
+	/// Get the registry of the database a model is routed to
+	pub fn registry_for_model(
+		&self,
+		app_label: &str,
+		model: &str,
+	) -> Option<Arc<ContentTypeRegistry>> {
+		self.db_for_read(app_label, model)
+			.and_then(|db_alias| self.get_registry(&db_alias))
+	}
+
+	/// Get the ContentType of a model from the database it is routed to
+	pub async fn get_for_model(
+		&self,
+		app_label: &str,
+		model: &str,
+	) -> Result<Option<ContentType>, PersistenceError> {
+		let db_alias = self.routed_db(self.db_for_read(app_label, model))?;
+		self.get(&db_alias, app_label, model).await
+	}
+
+	/// Get or create the ContentType of a model in the database it is routed to
+	pub async fn get_or_create_for_model(
+		&self,
+		app_label: &str,
+		model: &str,
+	) -> Result<ContentType, PersistenceError> {
+		let db_alias = self.routed_db(self.db_for_write(app_label, model))?;
+		self.get_or_create(&db_alias, app_label, model).await
+	}
+
+	/// Resolve a generic foreign key stored in `source_db`
+	///
+	/// The content type ID is only meaningful in the database holding the
+	/// referencing row. The returned content type is the one of the database
+	/// the target model is routed to, which may carry a different ID. Returns
+	/// `None` when the field is unset or the content type is unknown.
+	///
+	/// The read database is only queried; a content type missing there is
+	/// created through the model's write database.
+	pub async fn resolve_generic_fk(
+		&self,
+		source_db: &str,
+		gfk: &GenericForeignKeyField,
+	) -> Result<Option<ResolvedGenericForeignKey>, PersistenceError> {
+		let (Some(content_type_id), Some(object_id)) = (gfk.content_type_id(), gfk.object_id())
+		else {
+			return Ok(None);
+		};
+		let Some(source_ct) = self.get_by_id(source_db, content_type_id).await? else {
+			return Ok(None);
+		};
+
+		let db_alias = self
+			.db_for_read(&source_ct.app_label, &source_ct.model)
+			.unwrap_or_else(|| source_db.to_string());
+		let content_type = if db_alias == source_db {
+			source_ct
+		} else if let Some(ct) = self
+			.get(&db_alias, &source_ct.app_label, &source_ct.model)
+			.await?
+		{
+			ct
+		} else {
+			self.get_or_create_for_model(&source_ct.app_label, &source_ct.model)
+				.await?
+		};
+
+		Ok(Some(ResolvedGenericForeignKey {
+			content_type,
+			object_id,
+			db_alias,
+		}))
+	}
+
+	/// Point a generic foreign key stored in `source_db` at an object
+	///
+	/// Uses the content type ID of `source_db`, creating the content type
+	/// there when needed, so the reference stays valid wherever the target
+	/// model lives.
+	pub async fn set_generic_fk(
+		&self,
+		source_db: &str,
+		gfk: &mut GenericForeignKeyField,
+		app_label: &str,
+		model: &str,
+		object_id: i64,
+	) -> Result<(), PersistenceError> {
+		let ct = self.get_or_create(source_db, app_label, model).await?;
+		gfk.set(&ct, object_id);
+		Ok(())
+	}
+
 	/// Get or create a ContentType
 	///
 	/// # Example
@@ -647,6 +828,127 @@ mod tests {
 		assert!(result.is_err());
 	}
 
+	async fn routed_manager() -> MultiDbContentTypeManager {
+		init_drivers();
+		let router = DatabaseRouter::new("primary")
+			.add_rule("RoutedLog", "analytics")
+			.add_rule("audit.RoutedEvent", "analytics");
+		let mut manager = MultiDbContentTypeManager::new().with_router(router);
+		manager
+			.add_database("primary", "sqlite::memory:?mode=rwc&cache=shared")
+			.await
+			.expect("Failed to add primary");
+		manager
+			.add_database("analytics", "sqlite::memory:?mode=rwc&cache=shared")
+			.await
+			.expect("Failed to add analytics");
+		manager
+	}
+
+	#[tokio::test]
+	async fn test_multi_db_router_selects_database() {
+		let manager = routed_manager().await;
+
+		assert_eq!(
+			manager.db_for_write("logs", "RoutedLog").as_deref(),
+			Some("analytics")
+		);
+		assert_eq!(
+			manager.db_for_read("audit", "RoutedEvent").as_deref(),
+			Some("analytics")
+		);
+		assert_eq!(
+			manager.db_for_read("blog", "RoutedEvent").as_deref(),
+			Some("primary")
+		);
+	}
+
+	#[tokio::test]
+	async fn test_multi_db_get_or_create_for_model_uses_routed_registry() {
+		let manager = routed_manager().await;
+
+		manager
+			.get_or_create_for_model("logs", "RoutedLog")
+			.await
+			.expect("Failed to create");
+
+		let analytics = manager.registry_for_model("logs", "RoutedLog").unwrap();
+		assert!(analytics.get("logs", "RoutedLog").is_some());
+		let primary = manager.get_registry("primary").unwrap();
+		assert!(primary.get("logs", "RoutedLog").is_none());
+	}
+
+	#[tokio::test]
+	async fn test_multi_db_resolve_generic_fk_across_databases() {
+		let manager = routed_manager().await;
+
+		// A comment stored in "primary" points to a log routed to "analytics"
+		let mut gfk = GenericForeignKeyField::new();
+		manager
+			.set_generic_fk("primary", &mut gfk, "logs", "RoutedLog", 7)
+			.await
+			.expect("Failed to set");
+
+		let resolved = manager
+			.resolve_generic_fk("primary", &gfk)
+			.await
+			.expect("Failed to resolve")
+			.unwrap();
+
+		assert_eq!(resolved.db_alias, "analytics");
+		assert_eq!(resolved.object_id, 7);
+		assert_eq!(resolved.content_type.model, "RoutedLog");
+		assert!(
+			manager
+				.resolve_generic_fk("primary", &GenericForeignKeyField::new())
+				.await
+				.unwrap()
+				.is_none()
+		);
+	}
+
+	#[tokio::test]
+	async fn test_multi_db_resolve_generic_fk_does_not_write_to_read_database() {
+		init_drivers();
+		let router = DatabaseRouter::new("primary").add_read_write_rule(
+			"ReplicatedLog",
+			"replica",
+			"writer",
+		);
+		let mut manager = MultiDbContentTypeManager::new().with_router(router);
+		for alias in ["primary", "replica", "writer"] {
+			manager
+				.add_database(alias, "sqlite::memory:?mode=rwc&cache=shared")
+				.await
+				.expect("Failed to add database");
+		}
+
+		let mut gfk = GenericForeignKeyField::new();
+		manager
+			.set_generic_fk("primary", &mut gfk, "logs", "ReplicatedLog", 3)
+			.await
+			.expect("Failed to set");
+
+		let resolved = manager
+			.resolve_generic_fk("primary", &gfk)
+			.await
+			.expect("Failed to resolve")
+			.unwrap();
+
+		assert_eq!(resolved.db_alias, "replica");
+		assert_eq!(resolved.content_type.model, "ReplicatedLog");
+		let replica = manager.get_database("replica").unwrap();
+		assert!(
+			replica
+				.get("logs", "ReplicatedLog")
+				.await
+				.unwrap()
+				.is_none()
+		);
+		let writer = manager.get_database("writer").unwrap();
+		assert!(writer.get("logs", "ReplicatedLog").await.unwrap().is_some());
+	}
+
 	#[tokio::test]
 	async fn test_multi_db_isolated_registries() {
 		init_drivers();
@@ -301,33 +301,36 @@ impl ContentTypePersistence {
 			PersistenceError::DatabaseError(format!("Failed to acquire connection: {}", e))
 		})?;
 
-		let stmt = Table::create()
-			.table(Alias::new("django_content_type"))
-			.if_not_exists()
-			.col(
-				ColumnDef::new(Alias::new("id"))
-					.integer()
-					.not_null()
-					.auto_increment()
-					.primary_key(),
-			)
-			.col(
-				ColumnDef::new(Alias::new("app_label"))
-					.string_len(100)
-					.not_null(),
-			)
-			.col(
-				ColumnDef::new(Alias::new("model"))
-					.string_len(100)
-					.not_null(),
-			)
-			.to_owned();
+		// Built in a block: the statement is not Send and must not live across an await
+		let sql = {
+			let stmt = Table::create()
+				.table(Alias::new("django_content_type"))
+				.if_not_exists()
+				.col(
+					ColumnDef::new(Alias::new("id"))
+						.integer()
+						.not_null()
+						.auto_increment()
+						.primary_key(),
+				)
+				.col(
+					ColumnDef::new(Alias::new("app_label"))
+						.string_len(100)
+						.not_null(),
+				)
+				.col(
+					ColumnDef::new(Alias::new("model"))
+						.string_len(100)
+						.not_null(),
+				)
+				.to_owned();
 
-		// Select appropriate QueryBuilder based on database URL
-		let sql = if self.database_url.starts_with("postgres") {
-			stmt.to_string(PostgresQueryBuilder)
-		} else {
-			stmt.to_string(SqliteQueryBuilder)
+			// Select appropriate QueryBuilder based on database URL
+			if self.database_url.starts_with("postgres") {
+				stmt.to_string(PostgresQueryBuilder)
+			} else {
+				stmt.to_string(SqliteQueryBuilder)
+			}
 		};
 		let sql_leaked: &'static str = Box::leak(sql.into_boxed_str());
 
@@ -47,6 +47,8 @@ mod to_tokens;
 // Re-export commonly used types for convenience
 pub use fields::{AddField, AlterField, RemoveField, RenameField};
 pub use models::{CreateModel, DeleteModel, FieldDefinition, MoveModel, RenameModel};
+#[cfg(feature = "postgres")]
+pub use postgres::{AddSearchVector, CreateTextSearchConfiguration};
 pub use postgres::{CreateCollation, CreateExtension, DropExtension};
 pub use special::{RunCode, RunSQL, StateOperation};
 
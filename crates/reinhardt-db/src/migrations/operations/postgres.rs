@@ -6,6 +6,7 @@
 //! - Create and manage PostgreSQL extensions
 //! - Use PostgreSQL-specific index types (GIN, GiST, BRIN, etc.)
 //! - Work with PostgreSQL functions and triggers
+//! - Manage full-text search columns, indexes and configurations
 //!
 //! # Example
 //!
@@ -20,6 +21,8 @@
 //! ```
 
 use super::super::ProjectState;
+#[cfg(feature = "postgres")]
+use crate::backends::drivers::postgresql::fts::{TextSearchConfiguration, TsVectorColumn};
 use crate::backends::schema::BaseDatabaseSchemaEditor;
 use pg_escape::quote_literal;
 use serde::{Deserialize, Serialize};
@@ -276,6 +279,119 @@ impl CreateCollation {
 	}
 }
 
+/// Add a full-text search `tsvector` column with a GIN index
+///
+/// The column is a stored generated column, so it is kept up to date by
+/// PostgreSQL without triggers.
+///
+/// # Example
+///
+/// ```rust
+/// use reinhardt_db::backends::drivers::postgresql::fts::{SearchWeight, TsVectorColumn};
+/// use reinhardt_db::migrations::operations::postgres::AddSearchVector;
+///
+/// let op = AddSearchVector::new(
+///     "articles",
+///     TsVectorColumn::new("search_vector")
+///         .weighted_source("title", SearchWeight::A)
+///         .weighted_source("body", SearchWeight::B),
+/// );
+/// assert_eq!(op.index_name, "articles_search_vector_gin");
+/// ```
+#[cfg(feature = "postgres")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddSearchVector {
+	pub table: String,
+	pub column: TsVectorColumn,
+	pub index_name: String,
+}
+
+#[cfg(feature = "postgres")]
+impl AddSearchVector {
+	/// Create a new AddSearchVector operation
+	///
+	/// The index is named `<table>_<column>_gin` unless overridden.
+	pub fn new(table: impl Into<String>, column: TsVectorColumn) -> Self {
+		let table = table.into();
+		let index_name = format!("{}_{}_gin", table, column.name);
+		Self {
+			table,
+			column,
+			index_name,
+		}
+	}
+
+	/// Set the GIN index name
+	pub fn with_index_name(mut self, index_name: impl Into<String>) -> Self {
+		self.index_name = index_name.into();
+		self
+	}
+
+	/// Apply to project state
+	pub fn state_forwards(&self, _app_label: &str, _state: &mut ProjectState) {}
+
+	/// Generate SQL
+	pub fn database_forwards(&self, _schema_editor: &dyn BaseDatabaseSchemaEditor) -> Vec<String> {
+		vec![
+			self.column.add_column_sql(&self.table),
+			format!(
+				"CREATE INDEX \"{}\" ON \"{}\" USING GIN (\"{}\");",
+				self.index_name, self.table, self.column.name
+			),
+		]
+	}
+
+	/// Generate reverse SQL
+	pub fn database_backwards(&self, _schema_editor: &dyn BaseDatabaseSchemaEditor) -> Vec<String> {
+		vec![
+			format!("DROP INDEX IF EXISTS \"{}\";", self.index_name),
+			self.column.drop_column_sql(&self.table),
+		]
+	}
+}
+
+/// Create a PostgreSQL text search configuration
+///
+/// # Example
+///
+/// ```rust
+/// use reinhardt_db::backends::drivers::postgresql::fts::TextSearchConfiguration;
+/// use reinhardt_db::migrations::operations::postgres::CreateTextSearchConfiguration;
+///
+/// let op = CreateTextSearchConfiguration::new(
+///     TextSearchConfiguration::new("english_unaccent")
+///         .copy_from("english")
+///         .mapping(&["hword", "hword_part", "word"], &["unaccent", "english_stem"]),
+/// );
+/// assert_eq!(op.config.name, "english_unaccent");
+/// ```
+#[cfg(feature = "postgres")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateTextSearchConfiguration {
+	pub config: TextSearchConfiguration,
+}
+
+#[cfg(feature = "postgres")]
+impl CreateTextSearchConfiguration {
+	/// Create a new CreateTextSearchConfiguration operation
+	pub fn new(config: TextSearchConfiguration) -> Self {
+		Self { config }
+	}
+
+	/// Apply to project state
+	pub fn state_forwards(&self, _app_label: &str, _state: &mut ProjectState) {}
+
+	/// Generate SQL
+	pub fn database_forwards(&self, _schema_editor: &dyn BaseDatabaseSchemaEditor) -> Vec<String> {
+		self.config.create_sql()
+	}
+
+	/// Generate reverse SQL
+	pub fn database_backwards(&self, _schema_editor: &dyn BaseDatabaseSchemaEditor) -> Vec<String> {
+		vec![self.config.drop_sql()]
+	}
+}
+
 /// Commonly used PostgreSQL extensions
 pub mod extensions {
 	use super::CreateExtension;
@@ -363,6 +479,35 @@ impl MigrationOperation for CreateCollation {
 	}
 }
 
+#[cfg(feature = "postgres")]
+impl MigrationOperation for AddSearchVector {
+	fn migration_name_fragment(&self) -> Option<String> {
+		Some(format!(
+			"{}_add_search_vector_{}",
+			self.table.to_lowercase(),
+			self.column.name.to_lowercase()
+		))
+	}
+
+	fn describe(&self) -> String {
+		format!("Add search vector {} to {}", self.column.name, self.table)
+	}
+}
+
+#[cfg(feature = "postgres")]
+impl MigrationOperation for CreateTextSearchConfiguration {
+	fn migration_name_fragment(&self) -> Option<String> {
+		Some(format!(
+			"create_text_search_config_{}",
+			self.config.name.to_lowercase()
+		))
+	}
+
+	fn describe(&self) -> String {
+		format!("Create text search configuration {}", self.config.name)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -455,4 +600,61 @@ mod tests {
 		let postgis = extensions::postgis();
 		assert_eq!(postgis.name, "postgis");
 	}
+
+	#[cfg(feature = "postgres")]
+	#[test]
+	fn test_add_search_vector() {
+		use crate::backends::drivers::postgresql::fts::SearchWeight;
+		use crate::backends::schema::test_utils::MockSchemaEditor;
+
+		let op = AddSearchVector::new(
+			"articles",
+			TsVectorColumn::new("search").weighted_source("title", SearchWeight::A),
+		);
+		let editor = MockSchemaEditor::new();
+
+		let forwards = op.database_forwards(&editor);
+		assert_eq!(forwards.len(), 2);
+		assert!(forwards[0].contains("tsvector GENERATED ALWAYS AS"));
+		assert_eq!(
+			forwards[1],
+			"CREATE INDEX \"articles_search_gin\" ON \"articles\" USING GIN (\"search\");"
+		);
+
+		let backwards = op.database_backwards(&editor);
+		assert_eq!(
+			backwards,
+			vec![
+				"DROP INDEX IF EXISTS \"articles_search_gin\";".to_string(),
+				"ALTER TABLE \"articles\" DROP COLUMN IF EXISTS \"search\"".to_string(),
+			]
+		);
+		assert_eq!(
+			op.migration_name_fragment(),
+			Some("articles_add_search_vector_search".to_string())
+		);
+	}
+
+	#[cfg(feature = "postgres")]
+	#[test]
+	fn test_create_text_search_configuration() {
+		use crate::backends::schema::test_utils::MockSchemaEditor;
+
+		let op = CreateTextSearchConfiguration::new(
+			TextSearchConfiguration::new("english_unaccent")
+				.copy_from("english")
+				.mapping(&["word"], &["unaccent", "english_stem"]),
+		);
+		let editor = MockSchemaEditor::new();
+
+		let forwards = op.database_forwards(&editor);
+		assert_eq!(forwards.len(), 2);
+		assert!(forwards[0].contains("COPY = \"english\""));
+
+		let backwards = op.database_backwards(&editor);
+		assert_eq!(
+			backwards,
+			vec!["DROP TEXT SEARCH CONFIGURATION IF EXISTS \"english_unaccent\"".to_string()]
+		);
+	}
 }
@@ -4,6 +4,7 @@
 //! the backend-specific connection implementations.
 
 use async_trait::async_trait;
+use futures::{Stream, StreamExt};
 
 /// Re-export backends types
 pub use crate::backends::connection::DatabaseConnection as BackendsConnection;
@@ -307,6 +308,20 @@ impl DatabaseConnection {
 		Ok(rows.into_iter().map(QueryRow::from_backend_row).collect())
 	}
 
+	/// Execute a SQL query and stream the resulting rows
+	///
+	/// Unlike [`query`](Self::query), rows are not collected in memory, which
+	/// makes this suitable for large result sets such as exports.
+	pub fn query_stream<'a>(
+		&'a self,
+		sql: &'a str,
+		params: Vec<QueryValue>,
+	) -> impl Stream<Item = Result<QueryRow, anyhow::Error>> + Send + 'a {
+		self.inner
+			.fetch_stream(sql, params)
+			.map(|row| Ok(QueryRow::from_backend_row(row?)))
+	}
+
 	/// Begin a database transaction
 	///
 	/// # Examples
@@ -14,6 +14,8 @@
 //! - `pool_timeout`: Timeout for acquiring a connection from the pool
 //! - `pool_idle_timeout`: Maximum idle time before a connection is closed
 //! - `pool_max_lifetime`: Maximum lifetime of a connection before it's closed
+//! - `statement_cache_size`: Prepared statements cached per connection
+//! - `search_path`: PostgreSQL schemas searched for unqualified table names
 //!
 //! ## Examples
 //!
@@ -41,6 +43,7 @@
 //! Copyright 2005-2025 SQLAlchemy authors and contributors
 //! Licensed under MIT License. See THIRD-PARTY-NOTICES for details.
 
+use crate::backends::optimization::{DEFAULT_STATEMENT_CACHE_CAPACITY, StatementCacheMetrics};
 use crate::backends::{DatabaseError, DatabaseType, Row as DbRow, connection::DatabaseConnection};
 use sqlx::{Any, AnyPool, pool::PoolOptions};
 use std::time::Duration;
@@ -71,6 +74,12 @@ pub struct EngineConfig {
 
 	/// Enable query result caching
 	pub query_cache_size: usize,
+
+	/// Number of prepared statements cached per connection (0 = disabled)
+	pub statement_cache_size: usize,
+
+	/// PostgreSQL schema search path set on every connection (empty = server default)
+	pub search_path: Vec<String>,
 }
 
 impl Default for EngineConfig {
@@ -84,6 +93,8 @@ impl Default for EngineConfig {
 			pool_max_lifetime: Some(1800), // 30 minutes
 			echo: false,
 			query_cache_size: 500,
+			statement_cache_size: DEFAULT_STATEMENT_CACHE_CAPACITY,
+			search_path: Vec::new(),
 		}
 	}
 }
@@ -131,6 +142,39 @@ impl EngineConfig {
 		self.query_cache_size = size;
 		self
 	}
+
+	/// Set the number of prepared statements cached per connection
+	///
+	/// Applies to engines created with [`DatabaseEngine::from_config`].
+	/// A size of zero disables prepared statement caching.
+	pub fn with_statement_cache_size(mut self, size: usize) -> Self {
+		self.statement_cache_size = size;
+		self
+	}
+
+	/// Set the PostgreSQL schema search path of every connection
+	///
+	/// Applies to PostgreSQL engines created with [`DatabaseEngine::from_config`].
+	/// Unqualified table names resolve against the schemas in order, which
+	/// supports schema-per-tenant deployments.
+	pub fn with_search_path(mut self, schemas: &[&str]) -> Self {
+		self.search_path = schemas.iter().map(|s| s.to_string()).collect();
+		self
+	}
+
+	fn pool_options<DB: sqlx::Database>(&self) -> PoolOptions<DB> {
+		let mut pool_options = PoolOptions::<DB>::new()
+			.min_connections(self.pool_min_size)
+			.max_connections(self.pool_max_size)
+			.acquire_timeout(Duration::from_secs(self.pool_timeout));
+		if let Some(idle_timeout) = self.pool_idle_timeout {
+			pool_options = pool_options.idle_timeout(Duration::from_secs(idle_timeout));
+		}
+		if let Some(max_lifetime) = self.pool_max_lifetime {
+			pool_options = pool_options.max_lifetime(Duration::from_secs(max_lifetime));
+		}
+		pool_options
+	}
 }
 
 /// Database engine - manages connections and execution
@@ -143,22 +187,7 @@ impl Engine {
 	/// Create a new engine from config
 	///
 	pub async fn from_config(config: EngineConfig) -> Result<Self, sqlx::Error> {
-		let mut pool_options = PoolOptions::<Any>::new()
-			.min_connections(config.pool_min_size)
-			.max_connections(config.pool_max_size)
-			.acquire_timeout(Duration::from_secs(config.pool_timeout));
-
-		// Set idle timeout if specified
-		if let Some(idle_timeout) = config.pool_idle_timeout {
-			pool_options = pool_options.idle_timeout(Duration::from_secs(idle_timeout));
-		}
-
-		// Set max lifetime if specified
-		if let Some(max_lifetime) = config.pool_max_lifetime {
-			pool_options = pool_options.max_lifetime(Duration::from_secs(max_lifetime));
-		}
-
-		let pool = pool_options.connect(&config.url).await?;
+		let pool = config.pool_options::<Any>().connect(&config.url).await?;
 
 		Ok(Self { pool, config })
 	}
@@ -302,6 +331,97 @@ impl DatabaseEngine {
 		}
 	}
 
+	/// Create a new engine from configuration
+	///
+	/// The backend is selected from the URL scheme. Pool sizes, timeouts, the
+	/// prepared statement cache size and the PostgreSQL search path are taken
+	/// from the configuration.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+	/// use reinhardt_db::orm::engine::{DatabaseEngine, EngineConfig};
+	///
+	/// let config = EngineConfig::new("postgres://localhost/mydb").with_statement_cache_size(250);
+	/// let engine = DatabaseEngine::from_config(config).await?;
+	///
+	/// engine.fetch_all("SELECT 1").await?;
+	/// let metrics = engine.statement_cache_metrics().unwrap();
+	/// assert_eq!(metrics.capacity, 250);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub async fn from_config(config: EngineConfig) -> Result<Self, DatabaseError> {
+		let capacity = config.statement_cache_size;
+		let url = config.url.as_str();
+
+		#[cfg(feature = "postgres")]
+		if url.starts_with("postgres://") || url.starts_with("postgresql://") {
+			use crate::backends::dialect::PostgresBackend;
+			use crate::backends::schema::set_search_path_sql;
+			use sqlx::postgres::PgConnectOptions;
+			use std::str::FromStr;
+
+			let options = PgConnectOptions::from_str(url)?.statement_cache_capacity(capacity);
+			let mut pool_options = config.pool_options::<sqlx::Postgres>();
+			if let Some(search_path_sql) = set_search_path_sql(&config.search_path) {
+				use sqlx::Executor;
+
+				let search_path_sql = std::sync::Arc::new(search_path_sql);
+				pool_options = pool_options.after_connect(move |conn, _meta| {
+					let search_path_sql = std::sync::Arc::clone(&search_path_sql);
+					Box::pin(async move {
+						conn.execute(search_path_sql.as_str()).await?;
+						Ok(())
+					})
+				});
+			}
+			let pool = pool_options.connect_with(options).await?;
+			let backend = PostgresBackend::new(pool).with_statement_cache_capacity(capacity);
+			let connection = DatabaseConnection::new(std::sync::Arc::new(backend));
+			return Ok(Self::with_config(
+				connection,
+				DatabaseType::Postgres,
+				config,
+			));
+		}
+
+		#[cfg(feature = "mysql")]
+		if url.starts_with("mysql://") || url.starts_with("mariadb://") {
+			use crate::backends::dialect::MySqlBackend;
+			use sqlx::mysql::MySqlConnectOptions;
+			use std::str::FromStr;
+
+			let options = MySqlConnectOptions::from_str(url)?.statement_cache_capacity(capacity);
+			let pool = config.pool_options().connect_with(options).await?;
+			let backend = MySqlBackend::new(pool).with_statement_cache_capacity(capacity);
+			let connection = DatabaseConnection::new(std::sync::Arc::new(backend));
+			return Ok(Self::with_config(connection, DatabaseType::Mysql, config));
+		}
+
+		#[cfg(feature = "sqlite")]
+		if url.starts_with("sqlite:") {
+			use crate::backends::dialect::SqliteBackend;
+			use sqlx::sqlite::SqliteConnectOptions;
+			use std::str::FromStr;
+
+			let options = SqliteConnectOptions::from_str(url)?
+				.create_if_missing(true)
+				.statement_cache_capacity(capacity);
+			let pool = config.pool_options().connect_with(options).await?;
+			let backend = SqliteBackend::new(pool).with_statement_cache_capacity(capacity);
+			let connection = DatabaseConnection::new(std::sync::Arc::new(backend));
+			return Ok(Self::with_config(connection, DatabaseType::Sqlite, config));
+		}
+
+		let _ = capacity;
+		Err(DatabaseError::ConfigError(format!(
+			"Unsupported database URL or backend feature not enabled: {}",
+			url
+		)))
+	}
+
 	/// Create a new PostgreSQL engine
 	///
 	/// # Examples
@@ -368,6 +488,14 @@ impl DatabaseEngine {
 		&self.config
 	}
 
+	/// Get prepared statement cache hit/miss metrics
+	///
+	/// Hits and misses are taken from the statement cache of the connection
+	/// running each query and summed over the pool.
+	pub fn statement_cache_metrics(&self) -> Option<StatementCacheMetrics> {
+		self.connection.statement_cache_metrics()
+	}
+
 	/// Execute a SQL statement
 	///
 	/// # Examples
@@ -554,4 +682,64 @@ mod tests {
 		assert_eq!(rows.len(), 1);
 		pool.close().await;
 	}
+
+	#[cfg(feature = "sqlite")]
+	#[tokio::test]
+	async fn test_engine_statement_cache_metrics() {
+		use super::{DatabaseEngine, EngineConfig};
+
+		let config = EngineConfig::new("sqlite::memory:")
+			.with_pool_size(1, 1)
+			.with_statement_cache_size(8);
+		let engine = DatabaseEngine::from_config(config)
+			.await
+			.expect("Failed to create engine");
+
+		engine.fetch_all("SELECT 1").await.unwrap();
+		engine.fetch_all("SELECT 1").await.unwrap();
+
+		let metrics = engine.statement_cache_metrics().unwrap();
+		assert_eq!(metrics.capacity, 8);
+		assert_eq!(metrics.hits, 1);
+		assert_eq!(metrics.misses, 1);
+	}
+
+	#[cfg(feature = "sqlite")]
+	#[tokio::test]
+	async fn test_statement_metrics_are_per_connection() {
+		use crate::backends::backend::DatabaseBackend;
+		use crate::backends::dialect::SqliteBackend;
+		use sqlx::sqlite::SqliteConnectOptions;
+		use std::str::FromStr;
+
+		let options = SqliteConnectOptions::from_str("sqlite::memory:")
+			.unwrap()
+			.statement_cache_capacity(8);
+		let pool = SqlitePoolOptions::new()
+			.min_connections(2)
+			.max_connections(2)
+			.connect_with(options)
+			.await
+			.unwrap();
+		let backend = SqliteBackend::new(pool.clone()).with_statement_cache_capacity(8);
+
+		// Prepare the statement on one connection and hold it, so the
+		// backend has to run the query on the other connection
+		let mut held = pool.acquire().await.unwrap();
+		sqlx::query("SELECT 1").execute(&mut *held).await.unwrap();
+		// Miss: the other connection has to prepare the statement
+		backend.fetch_all("SELECT 1", vec![]).await.unwrap();
+		// Hit: prepared on the other connection by the previous query
+		backend.fetch_all("SELECT 1", vec![]).await.unwrap();
+		drop(held);
+		// Hit: both connections have the statement prepared
+		backend.fetch_all("SELECT 1", vec![]).await.unwrap();
+		backend.fetch_all("SELECT 2", vec![]).await.unwrap();
+		assert_eq!(pool.size(), 2);
+
+		let metrics = backend.statement_cache_metrics().unwrap();
+		assert_eq!(metrics.hits, 2);
+		assert_eq!(metrics.misses, 2);
+		assert_eq!(metrics.undetermined, 0);
+	}
 }
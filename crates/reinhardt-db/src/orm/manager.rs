@@ -1,4 +1,5 @@
-use super::connection::{DatabaseBackend, DatabaseConnection};
+use super::connection::{DatabaseBackend, DatabaseConnection, QueryValue};
+use super::transaction::TransactionScope;
 use super::{Model, QuerySet};
 use sea_query::{
 	Alias, DeleteStatement, Expr, ExprTrait, InsertStatement, MysqlQueryBuilder,
@@ -249,6 +250,26 @@ impl<M: Model> Manager<M> {
 		QuerySet::new().filter(filter)
 	}
 
+	/// Filter records whose generic foreign key points to `target`
+	///
+	/// Uses the default `content_type_id` / `object_id` columns. Build a
+	/// `GenericRelationQuery` with custom field names for other layouts.
+	///
+	/// # Example
+	///
+	/// ```ignore
+	/// let comments = Comment::objects()
+	///     .filter_generic(&post)?
+	///     .all()
+	///     .await?;
+	/// ```
+	pub fn filter_generic<R: crate::contenttypes::GenericRelatable>(
+		&self,
+		target: &R,
+	) -> reinhardt_core::exception::Result<QuerySet<M>> {
+		QuerySet::new().filter_generic(target)
+	}
+
 	/// Get a single record by primary key
 	/// Returns a QuerySet filtered by the primary key field
 	pub fn get(&self, pk: M::PrimaryKey) -> QuerySet<M> {
@@ -685,14 +706,25 @@ impl<M: Model> Manager<M> {
 
 	/// Create a new record with an explicit database connection
 	///
-	/// This method allows using a specific connection, which is essential for
-	/// transaction support. When operations are performed within a transaction,
-	/// the same connection must be used throughout.
+	/// The statement runs on a connection from `conn`'s pool, so it is not
+	/// part of a transaction started on `conn`; use
+	/// [`create_in_tx`](Self::create_in_tx) for that.
 	///
 	/// # Arguments
 	///
 	/// * `conn` - The database connection to use
 	/// * `model` - The model to create
+	pub async fn create_with_conn(
+		&self,
+		conn: &DatabaseConnection,
+		model: &M,
+	) -> reinhardt_core::exception::Result<M> {
+		let (sql, values) = Self::insert_sql(model, conn.backend())?;
+		let row = conn.query_one(&sql, values).await?;
+		Self::model_from_row(row.data)
+	}
+
+	/// Create a new record inside a transaction
 	///
 	/// # Examples
 	///
@@ -702,20 +734,37 @@ impl<M: Model> Manager<M> {
 	/// use reinhardt_db::orm::manager::get_connection;
 	///
 	/// let conn = get_connection().await?;
-	/// let tx = TransactionScope::begin(&conn).await?;
+	/// let mut tx = TransactionScope::begin(&conn).await?;
 	///
 	/// // Create within transaction
-	/// let created = manager.create_with_conn(&conn, model).await?;
+	/// let created = manager.create_in_tx(&mut tx, model).await?;
 	///
 	/// tx.commit().await?;
 	/// # Ok(())
 	/// # }
 	/// ```
-	pub async fn create_with_conn(
+	pub async fn create_in_tx(
 		&self,
-		conn: &DatabaseConnection,
+		tx: &mut TransactionScope,
 		model: &M,
 	) -> reinhardt_core::exception::Result<M> {
+		let (sql, values) = Self::insert_sql(model, tx.backend())?;
+		let row = tx.query_one(&sql, values).await?;
+		Self::model_from_row(row.data)
+	}
+
+	/// Deserialize a model from a returned row
+	fn model_from_row(data: serde_json::Value) -> reinhardt_core::exception::Result<M> {
+		// row.data is already serde_json::Value::Object so deserialize directly
+		serde_json::from_value(data)
+			.map_err(|e| reinhardt_core::exception::Error::Database(e.to_string()))
+	}
+
+	/// Build the INSERT ... RETURNING statement for `model`
+	fn insert_sql(
+		model: &M,
+		backend: DatabaseBackend,
+	) -> reinhardt_core::exception::Result<(String, Vec<QueryValue>)> {
 		let json = serde_json::to_value(model)
 			.map_err(|e| reinhardt_core::exception::Error::Database(e.to_string()))?;
 
@@ -778,18 +827,14 @@ impl<M: Model> Manager<M> {
 		let all_columns: Vec<_> = obj.keys().map(|k| Alias::new(k.as_str())).collect();
 		stmt.returning(Query::returning().columns(all_columns));
 
-		let (sql, values) = build_insert_sql(&stmt, conn.backend());
+		let (sql, values) = build_insert_sql(&stmt, backend);
 		let values: Vec<_> = values
 			.0
 			.into_iter()
 			.map(Self::sea_value_to_query_value)
 			.collect();
 
-		let row = conn.query_one(&sql, values).await?;
-
-		// row.data is already serde_json::Value::Object so deserialize directly
-		serde_json::from_value(row.data.clone())
-			.map_err(|e| reinhardt_core::exception::Error::Database(e.to_string()))
+		Ok((sql, values))
 	}
 
 	/// Convert serde_json::Value to sea_query::Value for parameter binding
@@ -945,13 +990,25 @@ impl<M: Model> Manager<M> {
 
 	/// Update an existing record with an explicit database connection
 	///
-	/// This method allows using a specific connection, which is essential for
-	/// transaction support.
+	/// The statement runs on a connection from `conn`'s pool, so it is not
+	/// part of a transaction started on `conn`; use
+	/// [`update_in_tx`](Self::update_in_tx) for that.
 	///
 	/// # Arguments
 	///
 	/// * `conn` - The database connection to use
 	/// * `model` - The model to update (must have primary key set)
+	pub async fn update_with_conn(
+		&self,
+		conn: &DatabaseConnection,
+		model: &M,
+	) -> reinhardt_core::exception::Result<M> {
+		let (sql, values) = Self::update_sql(model, conn.backend())?;
+		let row = conn.query_one(&sql, values).await?;
+		Self::model_from_row(row.data)
+	}
+
+	/// Update an existing record inside a transaction
 	///
 	/// # Examples
 	///
@@ -961,20 +1018,30 @@ impl<M: Model> Manager<M> {
 	/// use reinhardt_db::orm::manager::get_connection;
 	///
 	/// let conn = get_connection().await?;
-	/// let tx = TransactionScope::begin(&conn).await?;
+	/// let mut tx = TransactionScope::begin(&conn).await?;
 	///
 	/// // Update within transaction
-	/// let updated = manager.update_with_conn(&conn, model).await?;
+	/// let updated = manager.update_in_tx(&mut tx, model).await?;
 	///
 	/// tx.commit().await?;
 	/// # Ok(())
 	/// # }
 	/// ```
-	pub async fn update_with_conn(
+	pub async fn update_in_tx(
 		&self,
-		conn: &DatabaseConnection,
+		tx: &mut TransactionScope,
 		model: &M,
 	) -> reinhardt_core::exception::Result<M> {
+		let (sql, values) = Self::update_sql(model, tx.backend())?;
+		let row = tx.query_one(&sql, values).await?;
+		Self::model_from_row(row.data)
+	}
+
+	/// Build the UPDATE ... RETURNING statement for `model`
+	fn update_sql(
+		model: &M,
+		backend: DatabaseBackend,
+	) -> reinhardt_core::exception::Result<(String, Vec<QueryValue>)> {
 		let pk = model.primary_key().ok_or_else(|| {
 			reinhardt_core::exception::Error::Database("Model must have primary key".to_string())
 		})?;
@@ -1021,17 +1088,14 @@ impl<M: Model> Manager<M> {
 		let all_columns: Vec<_> = obj.keys().map(|k| Alias::new(k.as_str())).collect();
 		stmt.returning(Query::returning().columns(all_columns));
 
-		let (sql, values) = build_update_sql(&stmt, conn.backend());
+		let (sql, values) = build_update_sql(&stmt, backend);
 		let values: Vec<_> = values
 			.0
 			.into_iter()
 			.map(Self::sea_value_to_query_value)
 			.collect();
 
-		let row = conn.query_one(&sql, values).await?;
-		// row.data is already serde_json::Value::Object so deserialize directly
-		serde_json::from_value(row.data.clone())
-			.map_err(|e| reinhardt_core::exception::Error::Database(e.to_string()))
+		Ok((sql, values))
 	}
 
 	/// Delete a record using SeaQuery for SQL injection protection
@@ -1042,13 +1106,25 @@ impl<M: Model> Manager<M> {
 
 	/// Delete a record with an explicit database connection
 	///
-	/// This method allows using a specific connection, which is essential for
-	/// transaction support.
+	/// The statement runs on a connection from `conn`'s pool, so it is not
+	/// part of a transaction started on `conn`; use
+	/// [`delete_in_tx`](Self::delete_in_tx) for that.
 	///
 	/// # Arguments
 	///
 	/// * `conn` - The database connection to use
 	/// * `pk` - The primary key of the record to delete
+	pub async fn delete_with_conn(
+		&self,
+		conn: &DatabaseConnection,
+		pk: M::PrimaryKey,
+	) -> reinhardt_core::exception::Result<()> {
+		let (sql, values) = Self::delete_sql(pk, conn.backend());
+		conn.execute(&sql, values).await?;
+		Ok(())
+	}
+
+	/// Delete a record inside a transaction
 	///
 	/// # Examples
 	///
@@ -1058,20 +1134,27 @@ impl<M: Model> Manager<M> {
 	/// use reinhardt_db::orm::manager::get_connection;
 	///
 	/// let conn = get_connection().await?;
-	/// let tx = TransactionScope::begin(&conn).await?;
+	/// let mut tx = TransactionScope::begin(&conn).await?;
 	///
 	/// // Delete within transaction
-	/// manager.delete_with_conn(&conn, pk).await?;
+	/// manager.delete_in_tx(&mut tx, pk).await?;
 	///
 	/// tx.commit().await?;
 	/// # Ok(())
 	/// # }
 	/// ```
-	pub async fn delete_with_conn(
+	pub async fn delete_in_tx(
 		&self,
-		conn: &DatabaseConnection,
+		tx: &mut TransactionScope,
 		pk: M::PrimaryKey,
 	) -> reinhardt_core::exception::Result<()> {
+		let (sql, values) = Self::delete_sql(pk, tx.backend());
+		tx.execute(&sql, values).await?;
+		Ok(())
+	}
+
+	/// Build the DELETE statement for the record with primary key `pk`
+	fn delete_sql(pk: M::PrimaryKey, backend: DatabaseBackend) -> (String, Vec<QueryValue>) {
 		// Build SeaQuery DELETE statement
 		let mut stmt = Query::delete();
 
@@ -1088,15 +1171,14 @@ impl<M: Model> Manager<M> {
 		stmt.from_table(Alias::new(M::table_name()))
 			.and_where(Expr::col(Alias::new(M::primary_key_field())).eq(pk_value));
 
-		let (sql, values) = build_delete_sql(&stmt, conn.backend());
+		let (sql, values) = build_delete_sql(&stmt, backend);
 		let values: Vec<_> = values
 			.0
 			.into_iter()
 			.map(Self::sea_value_to_query_value)
 			.collect();
 
-		conn.execute(&sql, values).await?;
-		Ok(())
+		(sql, values)
 	}
 
 	/// Count records using SeaQuery
@@ -1107,8 +1189,8 @@ impl<M: Model> Manager<M> {
 
 	/// Count records with an explicit database connection
 	///
-	/// This method allows using a specific connection, which is essential for
-	/// verifying data within a transaction before commit/rollback.
+	/// The query runs on a connection from `conn`'s pool and therefore does
+	/// not see uncommitted writes of a [`TransactionScope`].
 	///
 	/// # Arguments
 	///
@@ -1117,17 +1199,12 @@ impl<M: Model> Manager<M> {
 	/// # Examples
 	///
 	/// ```no_run
-	/// # use reinhardt_db::orm::{Model, Manager, TransactionScope};
+	/// # use reinhardt_db::orm::{Model, Manager};
 	/// # async fn example<M: Model>(manager: Manager<M>) -> reinhardt_core::exception::Result<()> {
 	/// use reinhardt_db::orm::manager::get_connection;
 	///
 	/// let conn = get_connection().await?;
-	/// let tx = TransactionScope::begin(&conn).await?;
-	///
-	/// // Count within transaction (sees uncommitted data)
 	/// let count = manager.count_with_conn(&conn).await?;
-	///
-	/// tx.commit().await?;
 	/// # Ok(())
 	/// # }
 	/// ```
@@ -1327,9 +1404,63 @@ impl<M: Model> Manager<M> {
 		}
 
 		let conn = get_connection().await?;
-		let batch_size = batch_size.unwrap_or(models.len());
 		let mut results = Vec::new();
 
+		for sql in self.bulk_create_statements(&models, batch_size, ignore_conflicts)? {
+			// Execute and get results
+			if ignore_conflicts {
+				conn.execute(&sql, vec![]).await?;
+				// Note: Can't get RETURNING with DO NOTHING, skip results
+				// Return empty vec for ignored conflicts
+			} else {
+				let rows = conn.query(&sql, vec![]).await?;
+				for row in rows {
+					results.push(Self::model_from_row(row.data)?);
+				}
+			}
+		}
+
+		Ok(results)
+	}
+
+	/// Bulk create multiple records inside a transaction
+	///
+	/// Behaves like [`bulk_create`](Self::bulk_create), but every batch runs
+	/// on the transaction's connection, so rolling `tx` back discards them all.
+	pub async fn bulk_create_in_tx(
+		&self,
+		tx: &mut TransactionScope,
+		models: Vec<M>,
+		batch_size: Option<usize>,
+		ignore_conflicts: bool,
+	) -> reinhardt_core::exception::Result<Vec<M>> {
+		let mut results = Vec::new();
+
+		for sql in self.bulk_create_statements(&models, batch_size, ignore_conflicts)? {
+			if ignore_conflicts {
+				tx.execute(&sql, vec![]).await?;
+			} else {
+				for row in tx.query(&sql, vec![]).await? {
+					results.push(Self::model_from_row(row.data)?);
+				}
+			}
+		}
+
+		Ok(results)
+	}
+
+	/// Build one INSERT statement per batch of `models`
+	///
+	/// Statements end with `RETURNING *` unless conflicts are ignored.
+	fn bulk_create_statements(
+		&self,
+		models: &[M],
+		batch_size: Option<usize>,
+		ignore_conflicts: bool,
+	) -> reinhardt_core::exception::Result<Vec<String>> {
+		let batch_size = batch_size.unwrap_or(models.len()).max(1);
+		let mut statements = Vec::new();
+
 		for chunk in models.chunks(batch_size) {
 			// Extract fields from first model
 			let json = serde_json::to_value(&chunk[0])
@@ -1363,25 +1494,14 @@ impl<M: Model> Manager<M> {
 				.collect();
 
 			let sql = self.bulk_create_sql_detailed(&field_names, &value_rows, ignore_conflicts);
-
-			// Execute and get results
-			if ignore_conflicts {
-				conn.execute(&sql, vec![]).await?;
-				// Note: Can't get RETURNING with DO NOTHING, skip results
-				// Return empty vec for ignored conflicts
+			statements.push(if ignore_conflicts {
+				sql
 			} else {
-				let sql_with_returning = sql + " RETURNING *";
-				let rows = conn.query(&sql_with_returning, vec![]).await?;
-				for row in rows {
-					// row.data is already serde_json::Value::Object so deserialize directly
-					let model: M = serde_json::from_value(row.data.clone())
-						.map_err(|e| reinhardt_core::exception::Error::Database(e.to_string()))?;
-					results.push(model);
-				}
-			}
+				sql + " RETURNING *"
+			});
 		}
 
-		Ok(results)
+		Ok(statements)
 	}
 
 	/// Bulk update multiple records efficiently (Django's bulk_update)
@@ -1406,9 +1526,52 @@ impl<M: Model> Manager<M> {
 		}
 
 		let conn = get_connection().await?;
-		let batch_size = batch_size.unwrap_or(models.len());
 		let mut total_updated = 0;
 
+		for sql in self.bulk_update_statements(&models, &fields, batch_size, conn.backend()) {
+			let rows_affected = conn.execute(&sql, vec![]).await?;
+			total_updated += rows_affected as usize;
+		}
+
+		Ok(total_updated)
+	}
+
+	/// Bulk update multiple records inside a transaction
+	///
+	/// Behaves like [`bulk_update`](Self::bulk_update), but every batch runs
+	/// on the transaction's connection, so rolling `tx` back discards them all.
+	pub async fn bulk_update_in_tx(
+		&self,
+		tx: &mut TransactionScope,
+		models: Vec<M>,
+		fields: Vec<String>,
+		batch_size: Option<usize>,
+	) -> reinhardt_core::exception::Result<usize> {
+		let mut total_updated = 0;
+
+		for sql in self.bulk_update_statements(&models, &fields, batch_size, tx.backend()) {
+			let rows_affected = tx.execute(&sql, vec![]).await?;
+			total_updated += rows_affected as usize;
+		}
+
+		Ok(total_updated)
+	}
+
+	/// Build one UPDATE statement per batch of `models`
+	fn bulk_update_statements(
+		&self,
+		models: &[M],
+		fields: &[String],
+		batch_size: Option<usize>,
+		backend: DatabaseBackend,
+	) -> Vec<String> {
+		if models.is_empty() || fields.is_empty() {
+			return Vec::new();
+		}
+
+		let batch_size = batch_size.unwrap_or(models.len()).max(1);
+		let mut statements = Vec::new();
+
 		for chunk in models.chunks(batch_size) {
 			// Build updates structure
 			let updates: Vec<(M::PrimaryKey, HashMap<String, serde_json::Value>)> = chunk
@@ -1419,7 +1582,7 @@ impl<M: Model> Manager<M> {
 					let obj = json.as_object()?;
 
 					let mut field_map = HashMap::new();
-					for field in &fields {
+					for field in fields {
 						if let Some(val) = obj.get(field) {
 							field_map.insert(field.clone(), val.clone());
 						}
@@ -1430,13 +1593,11 @@ impl<M: Model> Manager<M> {
 				.collect();
 
 			if !updates.is_empty() {
-				let sql = self.bulk_update_sql_detailed(&updates, &fields, conn.backend());
-				let rows_affected = conn.execute(&sql, vec![]).await?;
-				total_updated += rows_affected as usize;
+				statements.push(self.bulk_update_sql_detailed(&updates, fields, backend));
 			}
 		}
 
-		Ok(total_updated)
+		statements
 	}
 
 	/// Get or create - SQL generation using SeaQuery (for testing)
@@ -1788,6 +1949,41 @@ mod tests {
 		assert!(sql.contains("WHERE"));
 	}
 
+	#[test]
+	fn test_bulk_statements_split_batches() {
+		let manager = TestUser::objects();
+		let users: Vec<TestUser> = (0..3)
+			.map(|i| TestUser {
+				id: Some(i + 1),
+				name: format!("user{}", i),
+				email: format!("user{}@example.com", i),
+			})
+			.collect();
+
+		let inserts = manager
+			.bulk_create_statements(&users, Some(2), false)
+			.unwrap();
+		assert_eq!(inserts.len(), 2);
+		assert!(inserts.iter().all(|sql| sql.ends_with(" RETURNING *")));
+
+		let ignored = manager.bulk_create_statements(&users, None, true).unwrap();
+		assert_eq!(ignored.len(), 1);
+		assert!(!ignored[0].contains("RETURNING"));
+
+		let updates = manager.bulk_update_statements(
+			&users,
+			&["name".to_string()],
+			Some(2),
+			DatabaseBackend::Postgres,
+		);
+		assert_eq!(updates.len(), 2);
+		assert!(
+			manager
+				.bulk_update_statements(&users, &[], Some(2), DatabaseBackend::Postgres)
+				.is_empty()
+		);
+	}
+
 	#[test]
 	fn test_bulk_create_empty() {
 		use serde_json::Value;
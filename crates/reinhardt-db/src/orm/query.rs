@@ -473,6 +473,20 @@ where
 		self
 	}
 
+	/// Filter rows whose generic foreign key points to `target`
+	///
+	/// Compiles to `content_type_id = <ct> AND object_id = <pk>`.
+	///
+	/// # Errors
+	///
+	/// Fails when the content type id of `target` cannot be resolved.
+	pub fn filter_generic<R: crate::contenttypes::GenericRelatable>(
+		self,
+		target: &R,
+	) -> reinhardt_core::exception::Result<Self> {
+		crate::contenttypes::GenericRelationQuery::for_object(target).apply(self)
+	}
+
 	/// Create a QuerySet from a subquery (FROM clause subquery / derived table)
 	///
 	/// This method creates a new QuerySet that uses a subquery as its data source
@@ -3635,7 +3649,40 @@ where
 	where
 		T: serde::de::DeserializeOwned,
 	{
-		let stmt = if self.select_related_fields.is_empty() {
+		let sql = self.all_select_stmt().to_string(PostgresQueryBuilder);
+		let rows = conn.query(&sql, vec![]).await?;
+		Self::rows_to_models(rows)
+	}
+
+	/// Execute the queryset inside a transaction and return all matching records
+	///
+	/// Unlike [`all_with_db`](Self::all_with_db), the query runs on the
+	/// transaction's connection and therefore sees its uncommitted writes.
+	pub async fn all_in_tx(
+		&self,
+		tx: &mut super::transaction::TransactionScope,
+	) -> reinhardt_core::exception::Result<Vec<T>>
+	where
+		T: serde::de::DeserializeOwned,
+	{
+		let sql = match tx.backend() {
+			super::connection::DatabaseBackend::Postgres => {
+				self.all_select_stmt().to_string(PostgresQueryBuilder)
+			}
+			super::connection::DatabaseBackend::MySql => self
+				.all_select_stmt()
+				.to_string(sea_query::MysqlQueryBuilder),
+			super::connection::DatabaseBackend::Sqlite => self
+				.all_select_stmt()
+				.to_string(sea_query::SqliteQueryBuilder),
+		};
+		let rows = tx.query(&sql, vec![]).await?;
+		Self::rows_to_models(rows)
+	}
+
+	/// Build the SELECT statement used by `all_with_db` and `all_in_tx`
+	fn all_select_stmt(&self) -> SelectStatement {
+		if self.select_related_fields.is_empty() {
 			let mut stmt = SeaQuery::select();
 			stmt.from(Alias::new(T::table_name()));
 
@@ -3696,11 +3743,16 @@ where
 			stmt.to_owned()
 		} else {
 			self.select_related_query()
-		};
-
-		let sql = stmt.to_string(PostgresQueryBuilder);
+		}
+	}
 
-		let rows = conn.query(&sql, vec![]).await?;
+	/// Deserialize query rows into models
+	fn rows_to_models(
+		rows: Vec<super::connection::QueryRow>,
+	) -> reinhardt_core::exception::Result<Vec<T>>
+	where
+		T: serde::de::DeserializeOwned,
+	{
 		rows.into_iter()
 			.map(|row| {
 				serde_json::from_value(serde_json::to_value(&row.data).map_err(|e| {
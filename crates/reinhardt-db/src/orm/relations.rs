@@ -25,10 +25,29 @@
 //! // Get all comments for a post
 //! let comments = post.comments.all().await?;
 //! ```
+//!
+//! # Prefetching
+//!
+//! `GenericRelationConfig::prefetch` loads the related rows of many owners in
+//! a single query, avoiding one query per owner:
+//!
+//! ```rust,ignore
+//! let config = GenericRelationConfig::new("Comment");
+//! let sets = config
+//!     .prefetch::<Comment, Post>(&posts, |comment| Some(comment.object_id))
+//!     .await?;
+//! for (post, comments) in posts.iter().zip(sets) {
+//!     // No additional query
+//!     println!("{}: {} comments", post.title, comments.count().await?);
+//! }
+//! ```
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::marker::PhantomData;
 
+use crate::contenttypes::GenericRelatable;
+use crate::contenttypes::contenttypes::generic_relation_filters;
 use crate::orm::Model;
 
 /// A set of objects that have a GenericForeignKey pointing to the owner model
@@ -66,6 +85,9 @@ pub struct GenericRelationSet<T> {
 	ct_field: String,
 	/// Field name for object id in the related model
 	fk_field: String,
+	/// Related rows loaded ahead of time by a prefetch
+	#[serde(skip)]
+	prefetched: Option<Vec<T>>,
 	/// Phantom data for the related model type
 	#[serde(skip)]
 	_phantom: PhantomData<T>,
@@ -101,10 +123,34 @@ impl<T> GenericRelationSet<T> {
 			object_id,
 			ct_field: ct_field.into(),
 			fk_field: fk_field.into(),
+			prefetched: None,
 			_phantom: PhantomData,
 		}
 	}
 
+	/// Create the relation set of an owner instance with default field names
+	///
+	/// # Errors
+	///
+	/// Fails when the owner's content type id cannot be resolved.
+	pub fn for_object<O: GenericRelatable>(owner: &O) -> reinhardt_core::exception::Result<Self> {
+		Ok(Self::with_defaults(
+			O::get_content_type().resolve_id()?,
+			owner.get_object_id(),
+		))
+	}
+
+	/// Use rows loaded ahead of time instead of querying the database
+	pub fn with_prefetched(mut self, rows: Vec<T>) -> Self {
+		self.prefetched = Some(rows);
+		self
+	}
+
+	/// Whether the related rows were loaded by a prefetch
+	pub fn is_prefetched(&self) -> bool {
+		self.prefetched.is_some()
+	}
+
 	/// Create a new GenericRelationSet with default field names
 	///
 	/// Uses "content_type_id" and "object_id" as the default field names.
@@ -202,21 +248,16 @@ impl<T: Model> GenericRelationSet<T> {
 	///     .await?;
 	/// ```
 	pub fn query(&self) -> super::query::QuerySet<T> {
-		use crate::orm::query::{Filter, FilterOperator, FilterValue};
-
-		// Create filters for content_type_id and object_id
-		let ct_filter = Filter::new(
-			self.ct_field.clone(),
-			FilterOperator::Eq,
-			FilterValue::Integer(self.content_type_id),
-		);
-		let fk_filter = Filter::new(
-			self.fk_field.clone(),
-			FilterOperator::Eq,
-			FilterValue::Integer(self.object_id),
-		);
-
-		T::objects().all().filter(ct_filter).filter(fk_filter)
+		generic_relation_filters(
+			&self.ct_field,
+			&self.fk_field,
+			self.content_type_id,
+			&[self.object_id],
+		)
+		.into_iter()
+		.fold(T::objects().all(), |queryset, filter| {
+			queryset.filter(filter)
+		})
 	}
 
 	/// Get all related objects
@@ -237,7 +278,10 @@ impl<T: Model> GenericRelationSet<T> {
 	/// }
 	/// ```
 	pub async fn all(&self) -> reinhardt_core::exception::Result<Vec<T>> {
-		self.query().all().await
+		match &self.prefetched {
+			Some(rows) => Ok(rows.clone()),
+			None => self.query().all().await,
+		}
 	}
 
 	/// Count related objects
@@ -251,7 +295,10 @@ impl<T: Model> GenericRelationSet<T> {
 	/// println!("Post has {} comments", comment_count);
 	/// ```
 	pub async fn count(&self) -> reinhardt_core::exception::Result<usize> {
-		self.query().count().await
+		match &self.prefetched {
+			Some(rows) => Ok(rows.len()),
+			None => self.query().count().await,
+		}
 	}
 
 	/// Check if any related objects exist
@@ -277,7 +324,10 @@ impl<T: Model> GenericRelationSet<T> {
 	/// }
 	/// ```
 	pub async fn first(&self) -> reinhardt_core::exception::Result<Option<T>> {
-		self.query().first().await
+		match &self.prefetched {
+			Some(rows) => Ok(rows.first().cloned()),
+			None => self.query().first().await,
+		}
 	}
 }
 
@@ -366,11 +416,254 @@ impl GenericRelationConfig {
 	pub fn get_related_name(&self) -> Option<&str> {
 		self.related_name.as_deref()
 	}
+
+	/// Reverse accessor for an owner instance
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_db::contenttypes::{ContentType, GenericRelatable};
+	/// use reinhardt_db::orm::relations::{GenericRelationConfig, GenericRelationSet};
+	///
+	/// struct Post { id: i64 }
+	///
+	/// impl GenericRelatable for Post {
+	///     fn get_content_type() -> ContentType {
+	///         ContentType::new("blog", "Post").with_id(3)
+	///     }
+	///     fn get_object_id(&self) -> i64 { self.id }
+	/// }
+	///
+	/// let config = GenericRelationConfig::new("Comment").fk_field("target_id");
+	/// let comments: GenericRelationSet<()> = config.accessor(&Post { id: 7 }).unwrap();
+	/// assert_eq!(comments.where_clause(), "content_type_id = 3 AND target_id = 7");
+	/// ```
+	pub fn accessor<T, O: GenericRelatable>(
+		&self,
+		owner: &O,
+	) -> reinhardt_core::exception::Result<GenericRelationSet<T>> {
+		Ok(self.accessor_with_id(O::get_content_type().resolve_id()?, owner))
+	}
+
+	fn accessor_with_id<T, O: GenericRelatable>(
+		&self,
+		content_type_id: i64,
+		owner: &O,
+	) -> GenericRelationSet<T> {
+		GenericRelationSet::new(
+			content_type_id,
+			owner.get_object_id(),
+			self.ct_field.clone(),
+			self.fk_field.clone(),
+		)
+	}
+
+	/// Query loading the related rows of several owners at once
+	///
+	/// Generates `WHERE ct_field = <ct> AND fk_field IN (<object_ids>)`.
+	pub fn prefetch_query<T: Model>(
+		&self,
+		content_type_id: i64,
+		object_ids: &[i64],
+	) -> super::query::QuerySet<T> {
+		generic_relation_filters(&self.ct_field, &self.fk_field, content_type_id, object_ids)
+			.into_iter()
+			.fold(T::objects().all(), |queryset, filter| {
+				queryset.filter(filter)
+			})
+	}
+
+	/// Group prefetched rows by the object id they point to
+	///
+	/// `object_id` reads the object id from the generic foreign key of a
+	/// row. Rows whose generic foreign key is unset are skipped.
+	pub fn group_by_object<T>(
+		&self,
+		rows: Vec<T>,
+		object_id: impl Fn(&T) -> Option<i64>,
+	) -> HashMap<i64, Vec<T>> {
+		let mut grouped: HashMap<i64, Vec<T>> = HashMap::new();
+		for row in rows {
+			if let Some(object_id) = object_id(&row) {
+				grouped.entry(object_id).or_default().push(row);
+			}
+		}
+		grouped
+	}
+
+	/// Load the reverse relation of every owner with a single query
+	///
+	/// `object_id` reads the object id from the generic foreign key of a
+	/// related row. Returns one prefetched [`GenericRelationSet`] per owner,
+	/// in order.
+	///
+	/// # Errors
+	///
+	/// Fails when the owners' content type id cannot be resolved or the
+	/// query fails.
+	pub async fn prefetch<T: Model, O: GenericRelatable>(
+		&self,
+		owners: &[O],
+		object_id: impl Fn(&T) -> Option<i64>,
+	) -> reinhardt_core::exception::Result<Vec<GenericRelationSet<T>>> {
+		if owners.is_empty() {
+			return Ok(Vec::new());
+		}
+
+		let content_type_id = O::get_content_type().resolve_id()?;
+		let object_ids: Vec<i64> = owners.iter().map(|owner| owner.get_object_id()).collect();
+		let rows = self
+			.prefetch_query::<T>(content_type_id, &object_ids)
+			.all()
+			.await?;
+		let grouped = self.group_by_object(rows, object_id);
+
+		Ok(owners
+			.iter()
+			.map(|owner| {
+				let rows = grouped
+					.get(&owner.get_object_id())
+					.cloned()
+					.unwrap_or_default();
+				self.accessor_with_id(content_type_id, owner)
+					.with_prefetched(rows)
+			})
+			.collect())
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::contenttypes::ContentType;
+	use crate::orm::QuerySet;
+
+	#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+	struct Comment {
+		id: Option<i64>,
+		content_type_id: i64,
+		object_id: i64,
+		text: String,
+	}
+
+	#[derive(Debug, Clone)]
+	struct CommentFields;
+
+	impl crate::orm::model::FieldSelector for CommentFields {
+		fn with_alias(self, _alias: &str) -> Self {
+			self
+		}
+	}
+
+	impl Model for Comment {
+		type PrimaryKey = i64;
+		type Fields = CommentFields;
+
+		fn table_name() -> &'static str {
+			"comments"
+		}
+
+		fn new_fields() -> Self::Fields {
+			CommentFields
+		}
+
+		fn primary_key(&self) -> Option<Self::PrimaryKey> {
+			self.id
+		}
+
+		fn set_primary_key(&mut self, value: Self::PrimaryKey) {
+			self.id = Some(value);
+		}
+	}
+
+	struct Post {
+		id: i64,
+	}
+
+	impl GenericRelatable for Post {
+		fn get_content_type() -> ContentType {
+			ContentType::new("blog", "Post").with_id(3)
+		}
+
+		fn get_object_id(&self) -> i64 {
+			self.id
+		}
+	}
+
+	fn comment(object_id: i64, text: &str) -> Comment {
+		Comment {
+			id: None,
+			content_type_id: 3,
+			object_id,
+			text: text.to_string(),
+		}
+	}
+
+	#[test]
+	fn test_filter_generic_compiles_to_sql() {
+		let sql = QuerySet::<Comment>::new()
+			.filter_generic(&Post { id: 7 })
+			.unwrap()
+			.to_sql();
+
+		assert!(sql.contains("\"content_type_id\" = 3"), "{}", sql);
+		assert!(sql.contains("\"object_id\" = 7"), "{}", sql);
+	}
+
+	#[test]
+	fn test_prefetch_query_uses_in_clause() {
+		let config = GenericRelationConfig::new("Comment");
+		let sql = config.prefetch_query::<Comment>(3, &[1, 2]).to_sql();
+
+		assert!(sql.contains("\"content_type_id\" = 3"), "{}", sql);
+		assert!(sql.contains("\"object_id\" IN (1, 2)"), "{}", sql);
+	}
+
+	#[test]
+	fn test_group_by_object() {
+		let config = GenericRelationConfig::new("Comment");
+		let grouped = config.group_by_object(
+			vec![comment(1, "a"), comment(2, "b"), comment(1, "c")],
+			|comment| Some(comment.object_id),
+		);
+
+		assert_eq!(grouped[&1].len(), 2);
+		assert_eq!(grouped[&2][0].text, "b");
+	}
+
+	#[test]
+	fn test_unresolved_content_type_is_an_error() {
+		struct Draft;
+
+		impl GenericRelatable for Draft {
+			fn get_content_type() -> ContentType {
+				ContentType::new("blog", "UnregisteredDraft")
+			}
+
+			fn get_object_id(&self) -> i64 {
+				1
+			}
+		}
+
+		let config = GenericRelationConfig::new("Comment");
+
+		assert!(QuerySet::<Comment>::new().filter_generic(&Draft).is_err());
+		assert!(config.accessor::<Comment, _>(&Draft).is_err());
+		assert!(GenericRelationSet::<Comment>::for_object(&Draft).is_err());
+	}
+
+	#[tokio::test]
+	async fn test_prefetched_set_does_not_query() {
+		let config = GenericRelationConfig::new("Comment");
+		let comments: GenericRelationSet<Comment> = config
+			.accessor(&Post { id: 1 })
+			.unwrap()
+			.with_prefetched(vec![comment(1, "a")]);
+
+		assert!(comments.is_prefetched());
+		assert_eq!(comments.count().await.unwrap(), 1);
+		assert_eq!(comments.first().await.unwrap().unwrap().text, "a");
+	}
 
 	#[test]
 	fn test_generic_relation_set_new() {
@@ -627,6 +627,7 @@ impl<F> Atomic<F> {
 /// ```
 pub struct TransactionScope {
 	executor: Option<Box<dyn super::connection::TransactionExecutor>>,
+	backend: super::connection::DatabaseBackend,
 	committed: bool,
 }
 
@@ -658,6 +659,7 @@ impl TransactionScope {
 		let executor = conn.begin().await?;
 		Ok(Self {
 			executor: Some(executor),
+			backend: conn.backend(),
 			committed: false,
 		})
 	}
@@ -694,10 +696,19 @@ impl TransactionScope {
 		let executor = conn.begin_with_isolation(level.to_backends_level()).await?;
 		Ok(Self {
 			executor: Some(executor),
+			backend: conn.backend(),
 			committed: false,
 		})
 	}
 
+	/// Backend of the connection the transaction runs on
+	///
+	/// Used to build SQL in the right dialect for statements executed
+	/// through the scope.
+	pub fn backend(&self) -> super::connection::DatabaseBackend {
+		self.backend
+	}
+
 	/// Execute a SQL statement within the transaction
 	///
 	/// # Examples
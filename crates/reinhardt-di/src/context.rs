@@ -1,10 +1,14 @@
 //! Injection context for dependency resolution
 
 use crate::function_handle::FunctionHandle;
-use crate::override_registry::OverrideRegistry;
+use crate::override_registry::{OverrideGuard, OverrideRegistry};
+use crate::registry::{DependencyRegistry, DependencyScope, FactoryTrait, global_registry};
 use crate::scope::{RequestScope, SingletonScope};
-use std::any::Any;
-use std::sync::Arc;
+use crate::{DiResult, Injectable};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
 
 // Re-export ParamContext and Request types for convenience
 #[cfg(feature = "params")]
@@ -44,6 +48,8 @@ pub struct InjectionContext {
 	singleton_scope: Arc<SingletonScope>,
 	/// Override registry for dependency substitution (e.g., for testing)
 	override_registry: Arc<OverrideRegistry>,
+	/// Lifetimes declared for this context, taking precedence over factories
+	lifetimes: Arc<RwLock<HashMap<TypeId, DependencyScope>>>,
 	/// HTTP request for parameter extraction
 	#[cfg(feature = "params")]
 	request: Option<Arc<Request>>,
@@ -66,6 +72,7 @@ pub struct InjectionContext {
 /// ```
 pub struct InjectionContextBuilder {
 	singleton_scope: Arc<SingletonScope>,
+	lifetimes: HashMap<TypeId, DependencyScope>,
 	#[cfg(feature = "params")]
 	request: Option<Request>,
 	#[cfg(feature = "params")]
@@ -145,6 +152,63 @@ impl InjectionContextBuilder {
 		self
 	}
 
+	/// Declare the lifetime of an injectable type.
+	///
+	/// The lifetime only applies to the built context. See
+	/// [`InjectionContext::register_lifetime`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_di::{DependencyScope, InjectionContext, SingletonScope};
+	///
+	/// #[derive(Clone, Default)]
+	/// struct Settings;
+	///
+	/// let ctx = InjectionContext::builder(SingletonScope::new())
+	///     .with_lifetime::<Settings>(DependencyScope::Singleton)
+	///     .build();
+	/// assert_eq!(ctx.lifetime::<Settings>(), Some(DependencyScope::Singleton));
+	/// ```
+	pub fn with_lifetime<T: Any>(mut self, lifetime: DependencyScope) -> Self {
+		self.lifetimes.insert(TypeId::of::<T>(), lifetime);
+		self
+	}
+
+	/// Register an async factory that constructs `T`.
+	///
+	/// See [`InjectionContext::register_factory`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_di::{DependencyScope, InjectionContext, SingletonScope};
+	///
+	/// struct Client {
+	///     endpoint: String,
+	/// }
+	///
+	/// let endpoint = "https://api.example.com".to_string();
+	/// let ctx = InjectionContext::builder(SingletonScope::new())
+	///     .with_factory(DependencyScope::Singleton, move |_ctx| {
+	///         let endpoint = endpoint.clone();
+	///         async move { Ok(Client { endpoint }) }
+	///     })
+	///     .build();
+	/// assert!(ctx.has_factory::<Client>());
+	/// ```
+	pub fn with_factory<T, F, Fut>(self, scope: DependencyScope, factory: F) -> Self
+	where
+		T: Any + Send + Sync + 'static,
+		F: Fn(Arc<InjectionContext>) -> Fut + Send + Sync + 'static,
+		Fut: Future<Output = DiResult<T>> + Send + Sync + 'static,
+	{
+		self.singleton_scope
+			.registry()
+			.register_async::<T, _, _>(scope, factory);
+		self
+	}
+
 	/// Build the final `InjectionContext` instance.
 	///
 	/// # Examples
@@ -160,6 +224,7 @@ impl InjectionContextBuilder {
 			request_scope: RequestScope::new(),
 			singleton_scope: self.singleton_scope,
 			override_registry: Arc::new(OverrideRegistry::new()),
+			lifetimes: Arc::new(RwLock::new(self.lifetimes)),
 			#[cfg(feature = "params")]
 			request: self.request.map(Arc::new),
 			#[cfg(feature = "params")]
@@ -184,6 +249,7 @@ impl InjectionContext {
 	pub fn builder(singleton_scope: impl Into<Arc<SingletonScope>>) -> InjectionContextBuilder {
 		InjectionContextBuilder {
 			singleton_scope: singleton_scope.into(),
+			lifetimes: HashMap::new(),
 			#[cfg(feature = "params")]
 			request: None,
 			#[cfg(feature = "params")]
@@ -357,6 +423,408 @@ impl InjectionContext {
 		self.singleton_scope.set(value);
 	}
 
+	/// Declares how long an injected `T` lives.
+	///
+	/// - [`DependencyScope::Singleton`]: created once and shared process-wide
+	/// - [`DependencyScope::Request`]: created once per request context
+	/// - [`DependencyScope::Transient`]: created fresh on every injection
+	///
+	/// A declared lifetime takes precedence over the `use_cache` flag of
+	/// [`Depends`](crate::Depends) and [`Injected`](crate::Injected). It only
+	/// applies to this context; to declare a lifetime for every request,
+	/// register the factory with that scope instead.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_di::{DependencyScope, Depends, DiResult, Injectable, InjectionContext, SingletonScope};
+	/// use std::sync::Arc;
+	///
+	/// #[derive(Clone, Default)]
+	/// struct Settings;
+	///
+	/// #[async_trait::async_trait]
+	/// impl Injectable for Settings {
+	///     async fn inject(_ctx: &InjectionContext) -> DiResult<Self> {
+	///         Ok(Settings)
+	///     }
+	/// }
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> reinhardt_di::DiResult<()> {
+	/// let singleton_scope = Arc::new(SingletonScope::new());
+	/// let ctx = InjectionContext::builder(singleton_scope.clone()).build();
+	/// ctx.register_lifetime::<Settings>(DependencyScope::Singleton);
+	///
+	/// Depends::<Settings>::builder().resolve(&ctx).await?;
+	///
+	/// // Another request sees the same instance
+	/// let other = InjectionContext::builder(singleton_scope).build();
+	/// assert!(other.get_singleton::<Settings>().is_some());
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn register_lifetime<T: Any>(&self, lifetime: DependencyScope) {
+		let mut lifetimes = self.lifetimes.write().unwrap();
+		lifetimes.insert(TypeId::of::<T>(), lifetime);
+	}
+
+	/// Registers an async factory that constructs `T` with the given scope.
+	///
+	/// The factory lets types that cannot implement [`Injectable`], such as
+	/// third-party clients, be built from runtime configuration captured by
+	/// the closure. Registration is shared by every context using the same
+	/// singleton scope, and takes precedence over the global registry in
+	/// [`resolve`](Self::resolve).
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_di::{DependencyScope, InjectionContext, SingletonScope};
+	/// use std::sync::Arc;
+	///
+	/// struct Pool {
+	///     url: String,
+	/// }
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> reinhardt_di::DiResult<()> {
+	/// let database_url = std::env::var("DATABASE_URL")
+	///     .unwrap_or_else(|_| "postgres://localhost/app".to_string());
+	///
+	/// let ctx = InjectionContext::builder(SingletonScope::new()).build();
+	/// ctx.register_factory(DependencyScope::Singleton, move |_ctx| {
+	///     let url = database_url.clone();
+	///     async move { Ok(Pool { url }) }
+	/// });
+	///
+	/// let pool: Arc<Pool> = ctx.resolve().await?;
+	/// assert!(pool.url.starts_with("postgres://"));
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn register_factory<T, F, Fut>(&self, scope: DependencyScope, factory: F)
+	where
+		T: Any + Send + Sync + 'static,
+		F: Fn(Arc<InjectionContext>) -> Fut + Send + Sync + 'static,
+		Fut: Future<Output = DiResult<T>> + Send + Sync + 'static,
+	{
+		self.singleton_scope
+			.registry()
+			.register_async::<T, _, _>(scope, factory);
+	}
+
+	/// Registers a factory object that constructs `T` with the given scope.
+	///
+	/// Use this instead of [`register_factory`](Self::register_factory) when
+	/// the factory carries its own configuration and logic.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_di::{DependencyScope, DiResult, FactoryTrait, InjectionContext, SingletonScope};
+	/// use std::any::Any;
+	/// use std::sync::Arc;
+	///
+	/// struct FeatureFlags {
+	///     beta: bool,
+	/// }
+	///
+	/// struct FlagsFactory {
+	///     beta: bool,
+	/// }
+	///
+	/// #[async_trait::async_trait]
+	/// impl FactoryTrait for FlagsFactory {
+	///     async fn create(&self, _ctx: &InjectionContext) -> DiResult<Arc<dyn Any + Send + Sync>> {
+	///         Ok(Arc::new(FeatureFlags { beta: self.beta }))
+	///     }
+	/// }
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> DiResult<()> {
+	/// let ctx = InjectionContext::builder(SingletonScope::new()).build();
+	/// ctx.register_provider::<FeatureFlags>(DependencyScope::Request, FlagsFactory { beta: true });
+	///
+	/// let flags = ctx.resolve::<FeatureFlags>().await?;
+	/// assert!(flags.beta);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn register_provider<T: Any + Send + Sync + 'static>(
+		&self,
+		scope: DependencyScope,
+		factory: impl FactoryTrait + 'static,
+	) {
+		self.singleton_scope
+			.registry()
+			.register::<T>(scope, factory);
+	}
+
+	/// Binds an implementation into the multi-binding for `T`.
+	///
+	/// `T` is usually a trait object; every implementation bound to it is
+	/// injected together through [`Multi`](crate::Multi), forming a
+	/// plugin-style extension point. Bindings are shared by every context
+	/// using the same singleton scope.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_di::{InjectionContext, SingletonScope};
+	/// use std::sync::Arc;
+	///
+	/// trait Notifier: Send + Sync {
+	///     fn channel(&self) -> &str;
+	/// }
+	/// struct Email;
+	/// impl Notifier for Email {
+	///     fn channel(&self) -> &str { "email" }
+	/// }
+	/// struct Slack;
+	/// impl Notifier for Slack {
+	///     fn channel(&self) -> &str { "slack" }
+	/// }
+	///
+	/// let ctx = InjectionContext::builder(SingletonScope::new()).build();
+	/// ctx.bind_multi::<dyn Notifier>(Arc::new(Email));
+	/// ctx.bind_multi::<dyn Notifier>(Arc::new(Slack));
+	///
+	/// let channels: Vec<_> = ctx
+	///     .resolve_all::<dyn Notifier>()
+	///     .iter()
+	///     .map(|n| n.channel().to_string())
+	///     .collect();
+	/// assert_eq!(channels, ["email", "slack"]);
+	/// ```
+	pub fn bind_multi<T: ?Sized + Send + Sync + 'static>(&self, implementation: Arc<T>) {
+		self.singleton_scope.registry().bind_multi(implementation);
+	}
+
+	/// Returns every implementation bound to `T`.
+	///
+	/// Bindings made on this context come first, followed by those in the
+	/// global registry. An unbound `T` yields an empty list.
+	pub fn resolve_all<T: ?Sized + Send + Sync + 'static>(&self) -> Vec<Arc<T>> {
+		let mut all = self.singleton_scope.registry().get_multi::<T>();
+		all.extend(global_registry().get_multi::<T>());
+		all
+	}
+
+	/// Resolves `T` like [`resolve`](Self::resolve), returning `None` instead
+	/// of an error when no factory is registered for it. A missing
+	/// sub-dependency of `T` is still returned as an error.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_di::{InjectionContext, SingletonScope};
+	///
+	/// struct MetricsExporter;
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> reinhardt_di::DiResult<()> {
+	/// let ctx = InjectionContext::builder(SingletonScope::new()).build();
+	/// assert!(ctx.resolve_optional::<MetricsExporter>().await?.is_none());
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub async fn resolve_optional<T: Any + Send + Sync + 'static>(
+		&self,
+	) -> DiResult<Option<Arc<T>>> {
+		match self.resolve::<T>().await {
+			Ok(value) => Ok(Some(value)),
+			Err(err) if err.is_not_registered_for::<T>() => Ok(None),
+			Err(err) => Err(err),
+		}
+	}
+
+	/// Returns true if a factory for `T` was registered on this context's
+	/// singleton scope.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_di::{InjectionContext, SingletonScope};
+	///
+	/// let ctx = InjectionContext::builder(SingletonScope::new()).build();
+	/// assert!(!ctx.has_factory::<String>());
+	/// ```
+	pub fn has_factory<T: Any + 'static>(&self) -> bool {
+		self.singleton_scope.registry().is_registered::<T>()
+	}
+
+	/// Returns the registry holding the factory for `T`, preferring factories
+	/// registered on this context over the global registry.
+	fn registry_for<T: Any + 'static>(&self) -> &Arc<DependencyRegistry> {
+		let local = self.singleton_scope.registry();
+		if local.is_registered::<T>() {
+			local
+		} else {
+			global_registry()
+		}
+	}
+
+	/// Registers a hook run once when the server starts.
+	///
+	/// Startup hooks run in registration order and typically resolve
+	/// singletons so pooled resources are ready before the first request.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_di::{DependencyScope, InjectionContext, SingletonScope};
+	///
+	/// struct Engine;
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> reinhardt_di::DiResult<()> {
+	/// let ctx = InjectionContext::builder(SingletonScope::new()).build();
+	/// ctx.register_factory(DependencyScope::Singleton, |_ctx| async { Ok(Engine) });
+	/// ctx.on_startup(|ctx| async move {
+	///     ctx.resolve::<Engine>().await?;
+	///     Ok(())
+	/// });
+	///
+	/// ctx.run_startup().await?;
+	/// assert!(ctx.get_singleton::<Engine>().is_some());
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn on_startup<F, Fut>(&self, hook: F)
+	where
+		F: Fn(Arc<InjectionContext>) -> Fut + Send + Sync + 'static,
+		Fut: Future<Output = DiResult<()>> + Send + 'static,
+	{
+		self.singleton_scope.hooks().add_startup(hook);
+	}
+
+	/// Registers a hook run once when the server shuts down.
+	///
+	/// Shutdown hooks run in reverse registration order, so resources are
+	/// closed before the resources they were built from. Register a shutdown
+	/// hook after the startup hook whose resources it releases, so it also
+	/// runs when a later startup hook fails.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_di::{InjectionContext, SingletonScope};
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> reinhardt_di::DiResult<()> {
+	/// let ctx = InjectionContext::builder(SingletonScope::new()).build();
+	/// ctx.on_shutdown(|_ctx| async move {
+	///     // e.g. close the pool held in `ctx.get_singleton::<Pool>()`
+	///     Ok(())
+	/// });
+	///
+	/// ctx.run_shutdown().await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn on_shutdown<F, Fut>(&self, hook: F)
+	where
+		F: Fn(Arc<InjectionContext>) -> Fut + Send + Sync + 'static,
+		Fut: Future<Output = DiResult<()>> + Send + 'static,
+	{
+		self.singleton_scope.hooks().add_shutdown(hook);
+	}
+
+	/// Runs the startup hooks, stopping at the first error.
+	///
+	/// If a hook fails, the shutdown hooks registered after the hooks that
+	/// already completed are run before the error is returned. Called by the
+	/// server before it accepts connections.
+	pub async fn run_startup(&self) -> DiResult<()> {
+		let hooks = self.singleton_scope.hooks().clone();
+		hooks.run_startup(Arc::new(self.clone())).await
+	}
+
+	/// Runs every shutdown hook, returning the first error.
+	///
+	/// Called by the server once it has stopped accepting connections.
+	pub async fn run_shutdown(&self) -> DiResult<()> {
+		let hooks = self.singleton_scope.hooks().clone();
+		hooks.run_shutdown(Arc::new(self.clone())).await
+	}
+
+	/// Returns the lifetime of `T`.
+	///
+	/// Lifetimes registered on the context take precedence over the scope of a
+	/// factory. Returns `None` when neither declares one.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_di::{DependencyScope, InjectionContext, SingletonScope};
+	///
+	/// let ctx = InjectionContext::builder(SingletonScope::new()).build();
+	/// assert_eq!(ctx.lifetime::<u32>(), None);
+	///
+	/// ctx.register_lifetime::<u32>(DependencyScope::Request);
+	/// assert_eq!(ctx.lifetime::<u32>(), Some(DependencyScope::Request));
+	/// ```
+	pub fn lifetime<T: Any>(&self) -> Option<DependencyScope> {
+		let declared = self
+			.lifetimes
+			.read()
+			.unwrap()
+			.get(&TypeId::of::<T>())
+			.copied();
+		declared.or_else(|| self.registry_for::<T>().get_scope::<T>())
+	}
+
+	/// Returns a cached `T` according to its lifetime.
+	///
+	/// Without a declared lifetime, the request cache is used when `use_cache`
+	/// is set.
+	pub(crate) fn cached<T: Any + Send + Sync>(&self, use_cache: bool) -> Option<Arc<T>> {
+		if let Some(overridden) = self.override_registry.get_type::<T>() {
+			return Some(overridden);
+		}
+		match self.lifetime::<T>() {
+			Some(DependencyScope::Singleton) => self.get_singleton::<T>(),
+			Some(DependencyScope::Request) => self.get_request::<T>(),
+			Some(DependencyScope::Transient) => None,
+			None if use_cache => self.get_request::<T>(),
+			None => None,
+		}
+	}
+
+	/// Injects `T` according to its lifetime, caching the new instance.
+	///
+	/// Without a declared lifetime, `use_cache` selects between the request
+	/// cache and a fresh uncached instance.
+	pub(crate) async fn inject_with_lifetime<T: Injectable + Clone>(
+		&self,
+		use_cache: bool,
+	) -> DiResult<T> {
+		if let Some(cached) = self.cached::<T>(use_cache) {
+			return Ok(Arc::try_unwrap(cached).unwrap_or_else(|arc| (*arc).clone()));
+		}
+
+		match self.lifetime::<T>() {
+			Some(DependencyScope::Singleton) => {
+				let value = T::inject(self).await?;
+				self.set_singleton(value.clone());
+				Ok(value)
+			}
+			Some(DependencyScope::Request) => {
+				let value = T::inject(self).await?;
+				self.set_request(value.clone());
+				Ok(value)
+			}
+			Some(DependencyScope::Transient) => T::inject_uncached(self).await,
+			None if use_cache => {
+				let value = T::inject(self).await?;
+				self.set_request(value.clone());
+				Ok(value)
+			}
+			None => T::inject_uncached(self).await,
+		}
+	}
+
 	/// Returns a reference to the singleton scope.
 	///
 	/// This is useful for advanced scenarios where direct access to the
@@ -365,6 +833,49 @@ impl InjectionContext {
 		&self.singleton_scope
 	}
 
+	/// Substitutes `value` for every injection of `T` until the guard is dropped.
+	///
+	/// Like FastAPI's `dependency_overrides`, this lets tests swap in fakes for
+	/// dependencies such as databases or caches without changing handler code.
+	/// The override applies to [`Depends`](crate::Depends),
+	/// [`Injected`](crate::Injected) and [`resolve`](Self::resolve), and is
+	/// shared by clones of this context. Dropping the guard restores the
+	/// previous override, if any.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_di::{Depends, DiResult, Injectable, InjectionContext, SingletonScope};
+	///
+	/// #[derive(Clone)]
+	/// struct Database {
+	///     url: String,
+	/// }
+	///
+	/// #[async_trait::async_trait]
+	/// impl Injectable for Database {
+	///     async fn inject(_ctx: &InjectionContext) -> DiResult<Self> {
+	///         Ok(Database { url: "postgres://prod".to_string() })
+	///     }
+	/// }
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> DiResult<()> {
+	/// let ctx = InjectionContext::builder(SingletonScope::new()).build();
+	/// {
+	///     let _guard = ctx.override_dependency(Database { url: "sqlite::memory:".to_string() });
+	///     let db = Depends::<Database>::builder_no_cache().resolve(&ctx).await?;
+	///     assert_eq!(db.url, "sqlite::memory:");
+	/// }
+	/// let db = Depends::<Database>::builder_no_cache().resolve(&ctx).await?;
+	/// assert_eq!(db.url, "postgres://prod");
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn override_dependency<T: Any + Send + Sync>(&self, value: T) -> OverrideGuard {
+		OverrideGuard::new(self.override_registry.clone(), value)
+	}
+
 	/// Returns a reference to the override registry.
 	///
 	/// The override registry stores function-level overrides that take
@@ -476,7 +987,8 @@ impl InjectionContext {
 	///
 	/// This method implements the core dependency resolution logic:
 	/// 1. Check cache based on scope (Request or Singleton)
-	/// 2. If not cached, create using the factory from the global registry
+	/// 2. If not cached, create using the factory registered on this context,
+	///    or else the one from the global registry
 	/// 3. Cache the result according to the scope
 	///
 	/// # Examples
@@ -504,19 +1016,19 @@ impl InjectionContext {
 	/// ```
 	pub async fn resolve<T: Any + Send + Sync + 'static>(&self) -> crate::DiResult<Arc<T>> {
 		use crate::cycle_detection::{begin_resolution, register_type_name};
-		use crate::registry::{DependencyScope, global_registry};
 
 		let type_id = std::any::TypeId::of::<T>();
 		let type_name = std::any::type_name::<T>();
-		let registry = global_registry();
 
 		// Register type name (for error messages)
 		register_type_name::<T>(type_name);
 
+		if let Some(overridden) = self.override_registry.get_type::<T>() {
+			return Ok(overridden);
+		}
+
 		// [Fast path] Skip circular detection on cache hit
-		let scope = registry
-			.get_scope::<T>()
-			.unwrap_or(DependencyScope::Singleton);
+		let scope = self.lifetime::<T>().unwrap_or(DependencyScope::Singleton);
 		match scope {
 			DependencyScope::Singleton => {
 				if let Some(cached) = self.get_singleton::<T>() {
@@ -542,11 +1054,9 @@ impl InjectionContext {
 
 	async fn resolve_internal<T: Any + Send + Sync + 'static>(
 		&self,
-		scope: crate::registry::DependencyScope,
+		scope: DependencyScope,
 	) -> crate::DiResult<Arc<T>> {
-		use crate::registry::{DependencyScope, global_registry};
-
-		let registry = global_registry();
+		let registry = self.registry_for::<T>();
 
 		match scope {
 			DependencyScope::Singleton => {
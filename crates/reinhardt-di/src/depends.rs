@@ -2,7 +2,7 @@
 //!
 //! FastAPI-inspired dependency injection wrapper that provides:
 //! - Automatic dependency resolution
-//! - Caching control via `use_cache` parameter
+//! - Caching control via `use_cache` parameter or a registered lifetime
 //! - Type-safe dependency injection
 //!
 //! ## Examples
@@ -123,6 +123,9 @@ where
 	/// 2. Call `T::inject(ctx)` if not cached or cache is disabled
 	/// 3. Store in cache if `use_cache` is true
 	///
+	/// A lifetime registered with [`InjectionContext::register_lifetime`]
+	/// takes precedence over `use_cache`.
+	///
 	/// # Examples
 	///
 	/// ```no_run
@@ -149,19 +152,7 @@ where
 	/// # }
 	/// ```
 	pub async fn resolve(ctx: &InjectionContext, use_cache: bool) -> DiResult<Self> {
-		let value = if use_cache {
-			// Try to get from cache first
-			if let Some(cached) = ctx.get_request::<T>() {
-				Arc::try_unwrap(cached).unwrap_or_else(|arc| (*arc).clone())
-			} else {
-				let v = T::inject(ctx).await?;
-				ctx.set_request(v.clone());
-				v
-			}
-		} else {
-			// Skip cache - always create new instance using inject_uncached()
-			T::inject_uncached(ctx).await?
-		};
+		let value = ctx.inject_with_lifetime::<T>(use_cache).await?;
 
 		Ok(Self {
 			inner: Arc::new(value),
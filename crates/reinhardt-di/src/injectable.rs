@@ -67,6 +67,55 @@ pub trait Injectable: Sized + Send + Sync + 'static {
 	}
 }
 
+/// Optional injection for `Option<T>`
+///
+/// Resolves to `None` when `T` itself has no provider (see
+/// [`DiError::is_not_registered_for`](crate::DiError::is_not_registered_for)).
+/// Any other injection error, including a missing sub-dependency of `T`, is
+/// returned as is.
+///
+/// ```rust,no_run
+/// use reinhardt_di::{Depends, DiError, DiResult, Injectable, InjectionContext, SingletonScope};
+///
+/// #[derive(Clone)]
+/// struct RedisCache;
+///
+/// #[async_trait::async_trait]
+/// impl Injectable for RedisCache {
+///     async fn inject(_ctx: &InjectionContext) -> DiResult<Self> {
+///         Err(DiError::NotRegistered {
+///             type_name: "RedisCache".to_string(),
+///             hint: "Set REDIS_URL to enable caching".to_string(),
+///         })
+///     }
+/// }
+///
+/// # async fn example() -> DiResult<()> {
+/// let ctx = InjectionContext::builder(SingletonScope::new()).build();
+/// let cache = Depends::<Option<RedisCache>>::builder().resolve(&ctx).await?;
+/// assert!(cache.is_none());
+/// # Ok(())
+/// # }
+/// ```
+#[async_trait::async_trait]
+impl<T: Injectable> Injectable for Option<T> {
+	async fn inject(ctx: &InjectionContext) -> DiResult<Self> {
+		match T::inject(ctx).await {
+			Ok(value) => Ok(Some(value)),
+			Err(err) if err.is_not_registered_for::<T>() => Ok(None),
+			Err(err) => Err(err),
+		}
+	}
+
+	async fn inject_uncached(ctx: &InjectionContext) -> DiResult<Self> {
+		match T::inject_uncached(ctx).await {
+			Ok(value) => Ok(Some(value)),
+			Err(err) if err.is_not_registered_for::<T>() => Ok(None),
+			Err(err) => Err(err),
+		}
+	}
+}
+
 /// Blanket implementation of Injectable for `Arc<T>`
 ///
 /// This allows using `Arc<T>` directly in endpoint handlers with `#[inject]`:
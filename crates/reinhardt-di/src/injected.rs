@@ -173,23 +173,11 @@ where
 	/// # Arguments
 	///
 	/// * `ctx` - Injection context
-	/// * `use_cache` - Whether to use request-scoped cache
+	/// * `use_cache` - Whether to use request-scoped cache, unless `T` has a
+	///   registered lifetime
 	async fn resolve_with_cache(ctx: &InjectionContext, use_cache: bool) -> DiResult<Self> {
-		let value = if use_cache {
-			// Check request cache first
-			if let Some(cached) = ctx.get_request::<T>() {
-				Arc::try_unwrap(cached).unwrap_or_else(|arc| (*arc).clone())
-			} else {
-				// Begin circular dependency detection
-				let type_id = TypeId::of::<T>();
-				let type_name = std::any::type_name::<T>();
-				let _guard = begin_resolution(type_id, type_name)
-					.map_err(|e| DiError::CircularDependency(e.to_string()))?;
-
-				let v = T::inject(ctx).await?;
-				ctx.set_request(v.clone());
-				v
-			}
+		let value = if let Some(cached) = ctx.cached::<T>(use_cache) {
+			Arc::try_unwrap(cached).unwrap_or_else(|arc| (*arc).clone())
 		} else {
 			// Begin circular dependency detection (even for uncached)
 			let type_id = TypeId::of::<T>();
@@ -197,15 +185,21 @@ where
 			let _guard = begin_resolution(type_id, type_name)
 				.map_err(|e| DiError::CircularDependency(e.to_string()))?;
 
-			// Skip cache
-			T::inject_uncached(ctx).await?
+			ctx.inject_with_lifetime::<T>(use_cache).await?
 		};
 
+		let lifetime = ctx.lifetime::<T>();
 		Ok(Self {
 			inner: Arc::new(value),
 			metadata: InjectionMetadata {
-				scope: DependencyScope::Request,
-				cached: use_cache,
+				scope: match lifetime {
+					Some(crate::DependencyScope::Singleton) => DependencyScope::Singleton,
+					_ => DependencyScope::Request,
+				},
+				cached: match lifetime {
+					Some(lifetime) => lifetime != crate::DependencyScope::Transient,
+					None => use_cache,
+				},
 			},
 		})
 	}
@@ -197,6 +197,8 @@ pub mod function_handle;
 pub mod graph;
 pub mod injectable;
 pub mod injected;
+pub mod lifecycle;
+pub mod multi;
 pub mod override_registry;
 pub mod provider;
 pub mod registry;
@@ -207,7 +209,7 @@ use thiserror::Error;
 pub use context::{InjectionContext, InjectionContextBuilder, RequestContext};
 pub use cycle_detection::{CycleError, ResolutionGuard, begin_resolution, register_type_name};
 pub use function_handle::FunctionHandle;
-pub use override_registry::OverrideRegistry;
+pub use override_registry::{OverrideGuard, OverrideRegistry};
 
 #[cfg(feature = "params")]
 pub use context::{ParamContext, Request};
@@ -216,6 +218,8 @@ pub use injectable::Injectable;
 pub use injected::{
 	DependencyScope as InjectedScope, Injected, InjectionMetadata, OptionalInjected,
 };
+pub use lifecycle::LifecycleHooks;
+pub use multi::Multi;
 pub use provider::{Provider, ProviderFn};
 pub use registry::{
 	DependencyRegistration, DependencyRegistry, DependencyScope, FactoryTrait, global_registry,
@@ -256,6 +260,56 @@ pub enum DiError {
 	Internal { message: String },
 }
 
+impl DiError {
+	/// Returns true if the error means no provider exists for the dependency.
+	///
+	/// Optional injection treats these errors as an absent dependency.
+	pub fn is_not_registered(&self) -> bool {
+		matches!(
+			self,
+			DiError::NotFound(_)
+				| DiError::NotRegistered { .. }
+				| DiError::DependencyNotRegistered { .. }
+		)
+	}
+
+	/// Returns true if the error means `T` itself has no provider.
+	///
+	/// Unlike [`is_not_registered`](Self::is_not_registered), an error about
+	/// a missing sub-dependency of `T` does not match, so a misconfigured
+	/// dependency graph is not mistaken for an absent optional dependency.
+	/// The error's type name may be the full path of `T` or its bare name.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_di::DiError;
+	///
+	/// struct Cache;
+	///
+	/// let err = DiError::NotRegistered {
+	///     type_name: "Cache".to_string(),
+	///     hint: String::new(),
+	/// };
+	/// assert!(err.is_not_registered_for::<Cache>());
+	/// assert!(!err.is_not_registered_for::<String>());
+	/// ```
+	pub fn is_not_registered_for<T: ?Sized>(&self) -> bool {
+		let (DiError::NotRegistered { type_name, .. }
+		| DiError::DependencyNotRegistered { type_name }) = self
+		else {
+			return false;
+		};
+		let full_name = std::any::type_name::<T>();
+		let bare_name = full_name
+			.split('<')
+			.next()
+			.and_then(|path| path.rsplit("::").next())
+			.unwrap_or(full_name);
+		type_name == full_name || type_name == bare_name
+	}
+}
+
 impl From<DiError> for reinhardt_core::exception::Error {
 	fn from(err: DiError) -> Self {
 		reinhardt_core::exception::Error::Internal(format!("Dependency injection error: {}", err))
@@ -0,0 +1,197 @@
+//! Startup and shutdown hooks for application-wide dependencies
+//!
+//! Hooks let pooled resources such as database engines or cache clients be
+//! created once when the server starts and closed when it shuts down. They are
+//! registered on an [`InjectionContext`] and stored in its singleton scope, so
+//! every context sharing that scope sees the same hooks.
+
+use crate::{DiResult, InjectionContext};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+
+/// Type-erased lifecycle hook
+type Hook = Arc<
+	dyn Fn(Arc<InjectionContext>) -> Pin<Box<dyn Future<Output = DiResult<()>> + Send>>
+		+ Send
+		+ Sync,
+>;
+
+/// Shutdown hook with the number of startup hooks registered before it
+type ShutdownHook = (usize, Hook);
+
+/// Startup and shutdown hooks registered for an application
+///
+/// A shutdown hook registered after a startup hook is treated as the cleanup
+/// for it: if a later startup hook fails, the shutdown hooks of the startup
+/// hooks that completed are run before the error is returned.
+#[derive(Default)]
+pub struct LifecycleHooks {
+	startup: RwLock<Vec<Hook>>,
+	shutdown: RwLock<Vec<ShutdownHook>>,
+}
+
+impl LifecycleHooks {
+	/// Creates an empty set of hooks.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_di::LifecycleHooks;
+	///
+	/// let hooks = LifecycleHooks::new();
+	/// assert_eq!(hooks.startup_count(), 0);
+	/// ```
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns the number of registered startup hooks.
+	pub fn startup_count(&self) -> usize {
+		self.startup.read().unwrap().len()
+	}
+
+	/// Returns the number of registered shutdown hooks.
+	pub fn shutdown_count(&self) -> usize {
+		self.shutdown.read().unwrap().len()
+	}
+
+	pub(crate) fn add_startup<F, Fut>(&self, hook: F)
+	where
+		F: Fn(Arc<InjectionContext>) -> Fut + Send + Sync + 'static,
+		Fut: Future<Output = DiResult<()>> + Send + 'static,
+	{
+		self.startup.write().unwrap().push(erase(hook));
+	}
+
+	pub(crate) fn add_shutdown<F, Fut>(&self, hook: F)
+	where
+		F: Fn(Arc<InjectionContext>) -> Fut + Send + Sync + 'static,
+		Fut: Future<Output = DiResult<()>> + Send + 'static,
+	{
+		let registered_after = self.startup_count();
+		self.shutdown
+			.write()
+			.unwrap()
+			.push((registered_after, erase(hook)));
+	}
+
+	/// Runs startup hooks in registration order, stopping at the first error.
+	///
+	/// On error, the shutdown hooks covering the startup hooks that completed
+	/// are run, and the startup error is returned.
+	pub(crate) async fn run_startup(&self, ctx: Arc<InjectionContext>) -> DiResult<()> {
+		let hooks = self.startup.read().unwrap().clone();
+		for (completed, hook) in hooks.into_iter().enumerate() {
+			if let Err(err) = hook(ctx.clone()).await {
+				// The startup error is more useful than any cleanup failure
+				let _ = self.shutdown_started(ctx, completed).await;
+				return Err(err);
+			}
+		}
+		Ok(())
+	}
+
+	/// Runs every shutdown hook in reverse registration order.
+	///
+	/// A failing hook does not prevent later ones from running; the first
+	/// error is returned once all hooks have run.
+	pub(crate) async fn run_shutdown(&self, ctx: Arc<InjectionContext>) -> DiResult<()> {
+		self.shutdown_started(ctx, usize::MAX).await
+	}
+
+	/// Runs, in reverse order, the shutdown hooks registered after at most
+	/// `started` startup hooks.
+	async fn shutdown_started(&self, ctx: Arc<InjectionContext>, started: usize) -> DiResult<()> {
+		let hooks: Vec<Hook> = self
+			.shutdown
+			.read()
+			.unwrap()
+			.iter()
+			.filter(|(registered_after, _)| *registered_after <= started)
+			.map(|(_, hook)| hook.clone())
+			.collect();
+		let mut first_error = None;
+		for hook in hooks.iter().rev() {
+			if let Err(err) = hook(ctx.clone()).await {
+				first_error.get_or_insert(err);
+			}
+		}
+		first_error.map_or(Ok(()), Err)
+	}
+}
+
+fn erase<F, Fut>(hook: F) -> Hook
+where
+	F: Fn(Arc<InjectionContext>) -> Fut + Send + Sync + 'static,
+	Fut: Future<Output = DiResult<()>> + Send + 'static,
+{
+	Arc::new(move |ctx| Box::pin(hook(ctx)))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{DiError, SingletonScope};
+	use std::sync::Mutex;
+
+	#[tokio::test]
+	async fn test_shutdown_runs_in_reverse_and_continues_after_error() {
+		let hooks = LifecycleHooks::new();
+		let calls = Arc::new(Mutex::new(Vec::new()));
+
+		for name in ["engine", "cache"] {
+			let calls = calls.clone();
+			hooks.add_shutdown(move |_ctx| {
+				let calls = calls.clone();
+				async move {
+					calls.lock().unwrap().push(name);
+					if name == "cache" {
+						return Err(DiError::ProviderError("cache close failed".to_string()));
+					}
+					Ok(())
+				}
+			});
+		}
+
+		let ctx = Arc::new(InjectionContext::builder(SingletonScope::new()).build());
+		let result = hooks.run_shutdown(ctx).await;
+
+		assert!(matches!(result, Err(DiError::ProviderError(_))));
+		assert_eq!(*calls.lock().unwrap(), vec!["cache", "engine"]);
+	}
+
+	#[tokio::test]
+	async fn test_failed_startup_shuts_down_completed_hooks() {
+		let hooks = LifecycleHooks::new();
+		let calls = Arc::new(Mutex::new(Vec::new()));
+
+		for (name, fails) in [("engine", false), ("cache", true)] {
+			let startup_calls = calls.clone();
+			hooks.add_startup(move |_ctx| {
+				let calls = startup_calls.clone();
+				async move {
+					if fails {
+						return Err(DiError::ProviderError(format!("{} failed", name)));
+					}
+					calls.lock().unwrap().push(format!("start {}", name));
+					Ok(())
+				}
+			});
+			let shutdown_calls = calls.clone();
+			hooks.add_shutdown(move |_ctx| {
+				let calls = shutdown_calls.clone();
+				async move {
+					calls.lock().unwrap().push(format!("stop {}", name));
+					Ok(())
+				}
+			});
+		}
+
+		let ctx = Arc::new(InjectionContext::builder(SingletonScope::new()).build());
+		let result = hooks.run_startup(ctx).await;
+
+		assert!(matches!(result, Err(DiError::ProviderError(ref msg)) if msg == "cache failed"));
+		assert_eq!(*calls.lock().unwrap(), vec!["start engine", "stop engine"]);
+	}
+}
@@ -0,0 +1,97 @@
+//! Multi-binding injection
+//!
+//! A multi-binding collects every implementation bound to a type, usually a
+//! trait object, so handlers can receive all of them as one dependency. This is
+//! the building block for plugin-style extension points.
+
+use crate::{DiResult, Injectable, InjectionContext};
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// All implementations bound to `T` with
+/// [`InjectionContext::bind_multi`](crate::InjectionContext::bind_multi).
+///
+/// Injecting `Multi<T>` never fails; it is empty when nothing is bound.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_di::{Depends, InjectionContext, Multi, SingletonScope};
+/// use std::sync::Arc;
+///
+/// trait AuditSink: Send + Sync {
+///     fn name(&self) -> &str;
+/// }
+/// struct StdoutSink;
+/// impl AuditSink for StdoutSink {
+///     fn name(&self) -> &str { "stdout" }
+/// }
+///
+/// # #[tokio::main]
+/// # async fn main() -> reinhardt_di::DiResult<()> {
+/// let ctx = InjectionContext::builder(SingletonScope::new()).build();
+/// ctx.bind_multi::<dyn AuditSink>(Arc::new(StdoutSink));
+///
+/// let sinks = Depends::<Multi<dyn AuditSink>>::builder().resolve(&ctx).await?;
+/// assert_eq!(sinks.len(), 1);
+/// assert_eq!(sinks[0].name(), "stdout");
+/// # Ok(())
+/// # }
+/// ```
+pub struct Multi<T: ?Sized> {
+	items: Vec<Arc<T>>,
+}
+
+impl<T: ?Sized> Multi<T> {
+	/// Create from a list of implementations (for testing).
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_di::Multi;
+	/// use std::sync::Arc;
+	///
+	/// let multi = Multi::from_vec(vec![Arc::new(1), Arc::new(2)]);
+	/// assert_eq!(multi.len(), 2);
+	/// ```
+	pub fn from_vec(items: Vec<Arc<T>>) -> Self {
+		Self { items }
+	}
+
+	/// Extract the list of implementations.
+	pub fn into_vec(self) -> Vec<Arc<T>> {
+		self.items
+	}
+}
+
+impl<T: ?Sized> Clone for Multi<T> {
+	fn clone(&self) -> Self {
+		Self {
+			items: self.items.clone(),
+		}
+	}
+}
+
+impl<T: ?Sized> Deref for Multi<T> {
+	type Target = [Arc<T>];
+
+	fn deref(&self) -> &Self::Target {
+		&self.items
+	}
+}
+
+impl<T: ?Sized> IntoIterator for Multi<T> {
+	type Item = Arc<T>;
+	type IntoIter = std::vec::IntoIter<Arc<T>>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.items.into_iter()
+	}
+}
+
+#[async_trait::async_trait]
+impl<T: ?Sized + Send + Sync + 'static> Injectable for Multi<T> {
+	async fn inject(ctx: &InjectionContext) -> DiResult<Self> {
+		Ok(Self::from_vec(ctx.resolve_all::<T>()))
+	}
+}
@@ -2,9 +2,10 @@
 //!
 //! This module provides a registry for storing override values that take precedence
 //! over normal dependency resolution. Overrides are keyed by function pointer addresses,
-//! allowing specific injectable functions to be mocked in tests.
+//! allowing specific injectable functions to be mocked in tests, or by type, replacing
+//! every injection of that type.
 
-use std::any::Any;
+use std::any::{Any, TypeId};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
@@ -40,6 +41,8 @@ use std::sync::{Arc, RwLock};
 pub struct OverrideRegistry {
 	/// Function pointer address → Override value
 	overrides: RwLock<HashMap<usize, Arc<dyn Any + Send + Sync>>>,
+	/// Dependency type → Override value
+	type_overrides: RwLock<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>,
 }
 
 impl OverrideRegistry {
@@ -55,6 +58,7 @@ impl OverrideRegistry {
 	pub fn new() -> Self {
 		Self {
 			overrides: RwLock::new(HashMap::new()),
+			type_overrides: RwLock::new(HashMap::new()),
 		}
 	}
 
@@ -155,6 +159,7 @@ impl OverrideRegistry {
 	/// ```
 	pub fn clear(&self) {
 		self.overrides.write().unwrap().clear();
+		self.type_overrides.write().unwrap().clear();
 	}
 
 	/// Checks if an override exists for the given function pointer.
@@ -214,6 +219,112 @@ impl OverrideRegistry {
 	pub fn is_empty(&self) -> bool {
 		self.overrides.read().unwrap().is_empty()
 	}
+
+	/// Sets an override used for every injection of type `T`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use reinhardt_di::OverrideRegistry;
+	///
+	/// let registry = OverrideRegistry::new();
+	/// registry.set_type(100i32);
+	/// assert_eq!(registry.get_type::<i32>().as_deref(), Some(&100));
+	/// ```
+	pub fn set_type<T: Any + Send + Sync>(&self, value: T) {
+		self.replace_type(TypeId::of::<T>(), Some(Arc::new(value)));
+	}
+
+	/// Gets the override for type `T`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use reinhardt_di::OverrideRegistry;
+	///
+	/// let registry = OverrideRegistry::new();
+	/// assert!(registry.get_type::<i32>().is_none());
+	/// ```
+	pub fn get_type<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+		self.type_overrides
+			.read()
+			.unwrap()
+			.get(&TypeId::of::<T>())
+			.and_then(|arc| arc.clone().downcast::<T>().ok())
+	}
+
+	/// Removes the override for type `T`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use reinhardt_di::OverrideRegistry;
+	///
+	/// let registry = OverrideRegistry::new();
+	/// registry.set_type(100i32);
+	/// registry.remove_type::<i32>();
+	/// assert!(registry.get_type::<i32>().is_none());
+	/// ```
+	pub fn remove_type<T: Any>(&self) {
+		self.replace_type(TypeId::of::<T>(), None);
+	}
+
+	/// Replaces the override for a type, returning the previous one
+	fn replace_type(
+		&self,
+		type_id: TypeId,
+		value: Option<Arc<dyn Any + Send + Sync>>,
+	) -> Option<Arc<dyn Any + Send + Sync>> {
+		let mut overrides = self.type_overrides.write().unwrap();
+		match value {
+			Some(value) => overrides.insert(type_id, value),
+			None => overrides.remove(&type_id),
+		}
+	}
+}
+
+/// Scoped type override that restores the previous override on drop.
+///
+/// Created by [`InjectionContext::override_dependency`](crate::InjectionContext::override_dependency).
+/// Nested guards for the same type must be dropped in reverse order of creation.
+///
+/// # Examples
+///
+/// ```rust
+/// use reinhardt_di::{InjectionContext, SingletonScope};
+///
+/// let ctx = InjectionContext::builder(SingletonScope::new()).build();
+/// {
+///     let _guard = ctx.override_dependency(100i32);
+///     assert_eq!(ctx.overrides().get_type::<i32>().as_deref(), Some(&100));
+/// }
+/// assert!(ctx.overrides().get_type::<i32>().is_none());
+/// ```
+#[must_use = "the override is removed as soon as the guard is dropped"]
+pub struct OverrideGuard {
+	registry: Arc<OverrideRegistry>,
+	type_id: TypeId,
+	previous: Option<Arc<dyn Any + Send + Sync>>,
+}
+
+impl OverrideGuard {
+	/// Installs `value` as the override for `T`, remembering the previous one.
+	pub(crate) fn new<T: Any + Send + Sync>(registry: Arc<OverrideRegistry>, value: T) -> Self {
+		let type_id = TypeId::of::<T>();
+		let previous = registry.replace_type(type_id, Some(Arc::new(value)));
+		Self {
+			registry,
+			type_id,
+			previous,
+		}
+	}
+}
+
+impl Drop for OverrideGuard {
+	fn drop(&mut self) {
+		self.registry
+			.replace_type(self.type_id, self.previous.take());
+	}
 }
 
 #[cfg(test)]
@@ -286,6 +397,34 @@ mod tests {
 		assert!(registry.is_empty());
 	}
 
+	#[test]
+	fn test_override_guard_restores_previous() {
+		let registry = Arc::new(OverrideRegistry::new());
+		registry.set_type("original".to_string());
+
+		{
+			let _outer = OverrideGuard::new(registry.clone(), "outer".to_string());
+			{
+				let _inner = OverrideGuard::new(registry.clone(), "inner".to_string());
+				assert_eq!(
+					registry.get_type::<String>().as_deref().map(String::as_str),
+					Some("inner")
+				);
+			}
+			assert_eq!(
+				registry.get_type::<String>().as_deref().map(String::as_str),
+				Some("outer")
+			);
+		}
+		assert_eq!(
+			registry.get_type::<String>().as_deref().map(String::as_str),
+			Some("original")
+		);
+
+		registry.clear();
+		assert!(registry.get_type::<String>().is_none());
+	}
+
 	#[test]
 	fn test_type_mismatch_returns_none() {
 		let registry = OverrideRegistry::new();
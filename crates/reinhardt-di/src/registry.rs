@@ -86,6 +86,8 @@ pub struct DependencyRegistry {
 	dependencies: DashMap<TypeId, Vec<TypeId>>,
 	/// Maps type ID to its type name for debugging
 	type_names: DashMap<TypeId, &'static str>,
+	/// Multi-bindings: every implementation bound to a (usually `dyn Trait`) type
+	multi_bindings: DashMap<TypeId, Vec<Arc<dyn Any + Send + Sync>>>,
 }
 
 impl DependencyRegistry {
@@ -96,6 +98,7 @@ impl DependencyRegistry {
 			scopes: DashMap::new(),
 			dependencies: DashMap::new(),
 			type_names: DashMap::new(),
+			multi_bindings: DashMap::new(),
 		}
 	}
 
@@ -167,6 +170,45 @@ impl DependencyRegistry {
 			})
 	}
 
+	/// Add an implementation to the multi-binding for `T`
+	///
+	/// `T` is usually a trait object, so several implementations can be
+	/// collected under one extension point.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_di::DependencyRegistry;
+	/// use std::sync::Arc;
+	///
+	/// trait Plugin: Send + Sync {}
+	/// struct Audit;
+	/// impl Plugin for Audit {}
+	///
+	/// let registry = DependencyRegistry::new();
+	/// registry.bind_multi::<dyn Plugin>(Arc::new(Audit));
+	/// assert_eq!(registry.get_multi::<dyn Plugin>().len(), 1);
+	/// ```
+	pub fn bind_multi<T: ?Sized + Send + Sync + 'static>(&self, implementation: Arc<T>) {
+		self.multi_bindings
+			.entry(TypeId::of::<T>())
+			.or_default()
+			.push(Arc::new(implementation));
+	}
+
+	/// Get every implementation bound to `T`, in binding order
+	pub fn get_multi<T: ?Sized + Send + Sync + 'static>(&self) -> Vec<Arc<T>> {
+		self.multi_bindings
+			.get(&TypeId::of::<T>())
+			.map(|bindings| {
+				bindings
+					.iter()
+					.filter_map(|binding| binding.downcast_ref::<Arc<T>>().cloned())
+					.collect()
+			})
+			.unwrap_or_default()
+	}
+
 	/// Get the direct dependencies of a type
 	///
 	/// Returns a vector of TypeIds representing the types that the given type directly depends on.
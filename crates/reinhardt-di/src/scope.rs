@@ -1,5 +1,7 @@
 //! Dependency scopes
 
+use crate::lifecycle::LifecycleHooks;
+use crate::registry::DependencyRegistry;
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
@@ -83,6 +85,10 @@ impl Default for RequestScope {
 
 pub struct SingletonScope {
 	cache: Arc<RwLock<HashMap<TypeId, Arc<dyn Any + Send + Sync>>>>,
+	/// Factories registered at runtime for this application
+	registry: Arc<DependencyRegistry>,
+	/// Startup and shutdown hooks for this application
+	hooks: Arc<LifecycleHooks>,
 }
 
 impl SingletonScope {
@@ -98,8 +104,40 @@ impl SingletonScope {
 	pub fn new() -> Self {
 		Self {
 			cache: Arc::new(RwLock::new(HashMap::new())),
+			registry: Arc::new(DependencyRegistry::new()),
+			hooks: Arc::new(LifecycleHooks::new()),
 		}
 	}
+	/// Returns the registry of factories registered at runtime.
+	///
+	/// Unlike the [global registry](crate::global_registry), which is filled at
+	/// compile time by `#[injectable_factory]`, this registry belongs to the
+	/// application owning this scope.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_di::SingletonScope;
+	///
+	/// let scope = SingletonScope::new();
+	/// assert!(scope.registry().is_empty());
+	/// ```
+	pub fn registry(&self) -> &Arc<DependencyRegistry> {
+		&self.registry
+	}
+	/// Returns the startup and shutdown hooks of this application.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_di::SingletonScope;
+	///
+	/// let scope = SingletonScope::new();
+	/// assert_eq!(scope.hooks().shutdown_count(), 0);
+	/// ```
+	pub fn hooks(&self) -> &Arc<LifecycleHooks> {
+		&self.hooks
+	}
 	/// Retrieves a singleton value from the cache by type.
 	///
 	/// Returns `None` if no value of type `T` exists in the singleton cache.
@@ -0,0 +1,151 @@
+//! Dependency lifetime tests
+//!
+//! These tests verify that a lifetime registered for a type decides how it is
+//! cached, regardless of the `use_cache` flag at the injection site:
+//! 1. Singleton dependencies are shared across request contexts
+//! 2. Request dependencies are cached within one request context only
+//! 3. Transient dependencies are created on every injection
+
+use reinhardt_di::{
+	DependencyScope, Depends, DiResult, Injectable, Injected, InjectionContext, SingletonScope,
+};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static SINGLETON_CREATED: AtomicUsize = AtomicUsize::new(0);
+static REQUEST_CREATED: AtomicUsize = AtomicUsize::new(0);
+static TRANSIENT_CREATED: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone)]
+struct AppConfig {
+	id: usize,
+}
+
+#[async_trait::async_trait]
+impl Injectable for AppConfig {
+	async fn inject(_ctx: &InjectionContext) -> DiResult<Self> {
+		Ok(AppConfig {
+			id: SINGLETON_CREATED.fetch_add(1, Ordering::SeqCst),
+		})
+	}
+}
+
+#[derive(Clone)]
+struct UnitOfWork {
+	id: usize,
+}
+
+#[async_trait::async_trait]
+impl Injectable for UnitOfWork {
+	async fn inject(_ctx: &InjectionContext) -> DiResult<Self> {
+		Ok(UnitOfWork {
+			id: REQUEST_CREATED.fetch_add(1, Ordering::SeqCst),
+		})
+	}
+}
+
+#[derive(Clone)]
+struct RequestId {
+	id: usize,
+}
+
+#[async_trait::async_trait]
+impl Injectable for RequestId {
+	async fn inject(_ctx: &InjectionContext) -> DiResult<Self> {
+		Ok(RequestId {
+			id: TRANSIENT_CREATED.fetch_add(1, Ordering::SeqCst),
+		})
+	}
+}
+
+#[tokio::test]
+async fn test_singleton_lifetime_shared_across_requests() {
+	let singleton = Arc::new(SingletonScope::new());
+	let request1 = InjectionContext::builder(Arc::clone(&singleton))
+		.with_lifetime::<AppConfig>(DependencyScope::Singleton)
+		.build();
+	let request2 = InjectionContext::builder(Arc::clone(&singleton))
+		.with_lifetime::<AppConfig>(DependencyScope::Singleton)
+		.build();
+
+	// The lifetime wins over `use_cache = false`
+	let first = Depends::<AppConfig>::builder_no_cache()
+		.resolve(&request1)
+		.await
+		.unwrap();
+	let second = Injected::<AppConfig>::resolve(&request2).await.unwrap();
+
+	assert_eq!(first.id, second.id);
+	assert_eq!(SINGLETON_CREATED.load(Ordering::SeqCst), 1);
+	assert_eq!(
+		second.metadata().scope,
+		reinhardt_di::InjectedScope::Singleton
+	);
+	assert!(second.metadata().cached);
+}
+
+#[tokio::test]
+async fn test_request_lifetime_cached_per_request() {
+	let singleton = Arc::new(SingletonScope::new());
+	let request1 = InjectionContext::builder(Arc::clone(&singleton))
+		.with_lifetime::<UnitOfWork>(DependencyScope::Request)
+		.build();
+	let request2 = InjectionContext::builder(Arc::clone(&singleton))
+		.with_lifetime::<UnitOfWork>(DependencyScope::Request)
+		.build();
+
+	let a = Depends::<UnitOfWork>::builder_no_cache()
+		.resolve(&request1)
+		.await
+		.unwrap();
+	let b = Injected::<UnitOfWork>::resolve_uncached(&request1)
+		.await
+		.unwrap();
+	let c = Depends::<UnitOfWork>::builder()
+		.resolve(&request2)
+		.await
+		.unwrap();
+
+	assert_eq!(a.id, b.id);
+	assert_ne!(a.id, c.id);
+	assert_eq!(REQUEST_CREATED.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_transient_lifetime_never_cached() {
+	let singleton = Arc::new(SingletonScope::new());
+	let ctx = InjectionContext::builder(singleton).build();
+	ctx.register_lifetime::<RequestId>(DependencyScope::Transient);
+
+	// The lifetime wins over `use_cache = true`
+	let a = Depends::<RequestId>::builder().resolve(&ctx).await.unwrap();
+	let b = Injected::<RequestId>::resolve(&ctx).await.unwrap();
+
+	assert_ne!(a.id, b.id);
+	assert!(ctx.get_request::<RequestId>().is_none());
+	assert!(!b.metadata().cached);
+}
+
+#[tokio::test]
+async fn test_lifetime_is_local_to_context() {
+	let singleton = Arc::new(SingletonScope::new());
+	let request1 = InjectionContext::builder(Arc::clone(&singleton))
+		.with_lifetime::<RequestId>(DependencyScope::Transient)
+		.build();
+	let request2 = InjectionContext::builder(Arc::clone(&singleton)).build();
+	request2.register_lifetime::<AppConfig>(DependencyScope::Singleton);
+
+	assert_eq!(
+		request1.lifetime::<RequestId>(),
+		Some(DependencyScope::Transient)
+	);
+	assert_eq!(request2.lifetime::<RequestId>(), None);
+	assert_eq!(request1.lifetime::<AppConfig>(), None);
+
+	// Clones of a context share its lifetimes
+	let clone = request2.clone();
+	assert_eq!(
+		clone.lifetime::<AppConfig>(),
+		Some(DependencyScope::Singleton)
+	);
+}
@@ -8,7 +8,7 @@
 //! 3. Overrides can be set and cleared dynamically
 //! 4. Overrides work with different route configurations (main app, router, decorators)
 
-use reinhardt_di::{DiResult, Injectable, InjectionContext, SingletonScope};
+use reinhardt_di::{Depends, DiResult, Injectable, Injected, InjectionContext, SingletonScope};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
@@ -531,3 +531,63 @@ async fn test_override_replacement() {
 	let result2 = execute_endpoint_with_dependency(&ctx).await.unwrap();
 	assert_eq!(result2.q, Some("second".to_string()));
 }
+
+// Database dependency used by a handler through a sub-dependency
+#[derive(Clone, Debug, PartialEq)]
+struct Database {
+	url: String,
+}
+
+#[async_trait::async_trait]
+impl Injectable for Database {
+	async fn inject(_ctx: &InjectionContext) -> DiResult<Self> {
+		Ok(Database {
+			url: "postgres://production".to_string(),
+		})
+	}
+}
+
+#[derive(Clone)]
+struct UserRepository {
+	db: Database,
+}
+
+#[async_trait::async_trait]
+impl Injectable for UserRepository {
+	async fn inject(ctx: &InjectionContext) -> DiResult<Self> {
+		let db = Injected::<Database>::resolve_uncached(ctx).await?;
+		Ok(UserRepository {
+			db: db.into_inner(),
+		})
+	}
+}
+
+// Test type overrides reach sub-dependencies and are restored on drop
+#[tokio::test]
+async fn test_scoped_type_override_restored_on_drop() {
+	let singleton = Arc::new(SingletonScope::new());
+	let ctx = InjectionContext::builder(singleton).build();
+
+	{
+		let _guard = ctx.override_dependency(Database {
+			url: "sqlite::memory:".to_string(),
+		});
+
+		let repo = Depends::<UserRepository>::builder_no_cache()
+			.resolve(&ctx)
+			.await
+			.unwrap();
+		assert_eq!(repo.db.url, "sqlite::memory:");
+
+		// Clones of the context share the override
+		let request_ctx = ctx.clone();
+		let db = Injected::<Database>::resolve(&request_ctx).await.unwrap();
+		assert_eq!(db.url, "sqlite::memory:");
+	}
+
+	let repo = Depends::<UserRepository>::builder_no_cache()
+		.resolve(&ctx)
+		.await
+		.unwrap();
+	assert_eq!(repo.db.url, "postgres://production");
+}
@@ -0,0 +1,100 @@
+//! Runtime factory registration tests
+//!
+//! These tests verify that:
+//! 1. Types without an `Injectable` impl can be built by registered factories
+//! 2. Factories capture runtime configuration
+//! 3. The registered scope decides how instances are shared
+
+use reinhardt_di::{DependencyScope, DiResult, FactoryTrait, InjectionContext, SingletonScope};
+use std::any::Any;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Stand-in for a third-party client that cannot implement Injectable
+struct HttpClient {
+	base_url: String,
+	timeout_secs: u64,
+}
+
+struct Settings {
+	api_url: String,
+	timeout_secs: u64,
+}
+
+// Stand-in for a third-party connection handed out per request
+struct Connection {
+	id: usize,
+}
+
+struct ConnectionFactory {
+	opened: Arc<AtomicUsize>,
+}
+
+#[async_trait::async_trait]
+impl FactoryTrait for ConnectionFactory {
+	async fn create(&self, _ctx: &InjectionContext) -> DiResult<Arc<dyn Any + Send + Sync>> {
+		let id = self.opened.fetch_add(1, Ordering::SeqCst);
+		Ok(Arc::new(Connection { id }))
+	}
+}
+
+#[tokio::test]
+async fn test_factory_built_from_runtime_settings() {
+	let settings = Settings {
+		api_url: "https://payments.example.com".to_string(),
+		timeout_secs: 5,
+	};
+
+	let singleton = Arc::new(SingletonScope::new());
+	let ctx = InjectionContext::builder(Arc::clone(&singleton))
+		.with_factory(DependencyScope::Singleton, move |_ctx| {
+			let base_url = settings.api_url.clone();
+			let timeout_secs = settings.timeout_secs;
+			async move {
+				Ok(HttpClient {
+					base_url,
+					timeout_secs,
+				})
+			}
+		})
+		.build();
+
+	let client = ctx.resolve::<HttpClient>().await.unwrap();
+	assert_eq!(client.base_url, "https://payments.example.com");
+	assert_eq!(client.timeout_secs, 5);
+
+	// A later request context shares the singleton instance
+	let other = InjectionContext::builder(singleton).build();
+	let again = other.resolve::<HttpClient>().await.unwrap();
+	assert!(Arc::ptr_eq(&client, &again));
+}
+
+#[tokio::test]
+async fn test_provider_object_with_request_scope() {
+	let opened = Arc::new(AtomicUsize::new(0));
+	let singleton = Arc::new(SingletonScope::new());
+	let request1 = InjectionContext::builder(Arc::clone(&singleton)).build();
+	request1.register_provider::<Connection>(
+		DependencyScope::Request,
+		ConnectionFactory {
+			opened: Arc::clone(&opened),
+		},
+	);
+	let request2 = InjectionContext::builder(singleton).build();
+
+	let a = request1.resolve::<Connection>().await.unwrap();
+	let b = request1.resolve::<Connection>().await.unwrap();
+	let c = request2.resolve::<Connection>().await.unwrap();
+
+	assert_eq!(a.id, b.id);
+	assert_ne!(a.id, c.id);
+	assert_eq!(opened.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_unregistered_type_is_not_found() {
+	let ctx = InjectionContext::builder(SingletonScope::new()).build();
+
+	let result = ctx.resolve::<HttpClient>().await;
+	assert!(result.is_err());
+}
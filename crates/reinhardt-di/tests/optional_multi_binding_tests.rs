@@ -0,0 +1,138 @@
+//! Optional and multi-binding injection tests
+//!
+//! These tests verify that:
+//! 1. `Option<T>` resolves to `None` only when `T` has no provider
+//! 2. Other injection errors, including a missing sub-dependency of `T`,
+//!    still propagate through `Option<T>`
+//! 3. `Multi<T>` collects every implementation bound to a trait
+
+use reinhardt_di::{
+	DependencyScope, Depends, DiError, DiResult, Injectable, Injected, InjectionContext, Multi,
+	SingletonScope,
+};
+use std::sync::Arc;
+
+#[derive(Clone)]
+struct SearchIndex;
+
+#[async_trait::async_trait]
+impl Injectable for SearchIndex {
+	async fn inject(ctx: &InjectionContext) -> DiResult<Self> {
+		match ctx.get_singleton::<String>() {
+			Some(_) => Ok(SearchIndex),
+			None => Err(DiError::NotRegistered {
+				type_name: "SearchIndex".to_string(),
+				hint: "Configure SEARCH_URL to enable search".to_string(),
+			}),
+		}
+	}
+}
+
+#[derive(Clone, Debug)]
+struct BrokenService;
+
+#[async_trait::async_trait]
+impl Injectable for BrokenService {
+	async fn inject(_ctx: &InjectionContext) -> DiResult<Self> {
+		Err(DiError::ProviderError("connection refused".to_string()))
+	}
+}
+
+#[derive(Clone)]
+struct SearchPage;
+
+#[async_trait::async_trait]
+impl Injectable for SearchPage {
+	async fn inject(ctx: &InjectionContext) -> DiResult<Self> {
+		SearchIndex::inject(ctx).await.map(|_| SearchPage)
+	}
+}
+
+trait Plugin: Send + Sync {
+	fn name(&self) -> &'static str;
+}
+
+struct Audit;
+impl Plugin for Audit {
+	fn name(&self) -> &'static str {
+		"audit"
+	}
+}
+
+struct Metrics;
+impl Plugin for Metrics {
+	fn name(&self) -> &'static str {
+		"metrics"
+	}
+}
+
+struct Tracer;
+
+#[tokio::test]
+async fn test_option_resolves_none_when_unregistered() {
+	let ctx = InjectionContext::builder(SingletonScope::new()).build();
+	let search = Depends::<Option<SearchIndex>>::builder()
+		.resolve(&ctx)
+		.await
+		.unwrap();
+	assert!(search.is_none());
+
+	let ctx = InjectionContext::builder(SingletonScope::new())
+		.singleton("http://search:9200".to_string())
+		.build();
+	let search = Injected::<Option<SearchIndex>>::resolve(&ctx)
+		.await
+		.unwrap();
+	assert!(search.is_some());
+}
+
+#[tokio::test]
+async fn test_option_propagates_other_errors() {
+	let ctx = InjectionContext::builder(SingletonScope::new()).build();
+
+	let result = Depends::<Option<BrokenService>>::builder()
+		.resolve(&ctx)
+		.await;
+	assert!(matches!(result, Err(DiError::ProviderError(_))));
+}
+
+#[tokio::test]
+async fn test_option_propagates_missing_sub_dependency() {
+	let ctx = InjectionContext::builder(SingletonScope::new()).build();
+
+	let result = Depends::<Option<SearchPage>>::builder().resolve(&ctx).await;
+	assert!(matches!(
+		result,
+		Err(DiError::NotRegistered { type_name, .. }) if type_name == "SearchIndex"
+	));
+}
+
+#[tokio::test]
+async fn test_resolve_optional_uses_registered_factory() {
+	let ctx = InjectionContext::builder(SingletonScope::new()).build();
+	assert!(ctx.resolve_optional::<Tracer>().await.unwrap().is_none());
+
+	ctx.register_factory(DependencyScope::Singleton, |_ctx| async { Ok(Tracer) });
+	assert!(ctx.resolve_optional::<Tracer>().await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn test_multi_binding_injects_all_implementations() {
+	let singleton = Arc::new(SingletonScope::new());
+	let ctx = InjectionContext::builder(Arc::clone(&singleton)).build();
+
+	let empty = Injected::<Multi<dyn Plugin>>::resolve(&ctx).await.unwrap();
+	assert!(empty.is_empty());
+
+	ctx.bind_multi::<dyn Plugin>(Arc::new(Audit));
+	ctx.bind_multi::<dyn Plugin>(Arc::new(Metrics));
+
+	// A new request context sees the application's bindings
+	let request = InjectionContext::builder(singleton).build();
+	let plugins = Depends::<Multi<dyn Plugin>>::builder()
+		.resolve(&request)
+		.await
+		.unwrap();
+	let names: Vec<_> = plugins.iter().map(|p| p.name()).collect();
+	assert_eq!(names, ["audit", "metrics"]);
+}
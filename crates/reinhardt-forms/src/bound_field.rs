@@ -1,4 +1,7 @@
 use crate::field::{FormField, Widget};
+use crate::renderer::{FormRenderer, errors_list};
+use reinhardt_core::security::xss::{escape_html, escape_html_attr};
+use std::collections::HashMap;
 
 /// BoundField represents a field bound to form data
 pub struct BoundField<'a> {
@@ -224,6 +227,112 @@ impl<'a> BoundField<'a> {
 	pub fn is_required(&self) -> bool {
 		self.field.required()
 	}
+	/// Get the label, or one derived from the field name ("first_name" -> "First name")
+	pub fn label_text(&self) -> String {
+		if let Some(label) = self.label() {
+			return label.to_string();
+		}
+		let text = self.name().replace('_', " ");
+		let mut chars = text.chars();
+		match chars.next() {
+			Some(first) => first.to_uppercase().chain(chars).collect(),
+			None => text,
+		}
+	}
+	/// Get the value as the string a widget renders
+	pub fn value_string(&self) -> Option<String> {
+		match self.value()? {
+			serde_json::Value::Null => None,
+			serde_json::Value::String(s) => Some(s.clone()),
+			other => Some(other.to_string()),
+		}
+	}
+	/// Get the attributes passed to the widget: id, required and ARIA hooks
+	///
+	/// `aria-describedby` points at the help text and error list rendered by
+	/// [`help_text_html`](Self::help_text_html) and [`errors_html`](Self::errors_html).
+	pub fn widget_attrs(&self) -> HashMap<String, String> {
+		let id = self.id_for_label();
+		let mut attrs = HashMap::new();
+		if self.is_required() && !self.widget().is_hidden() {
+			attrs.insert("required".to_string(), String::new());
+		}
+		let mut described_by = Vec::new();
+		if self.help_text().is_some() {
+			described_by.push(format!("{}_helptext", id));
+		}
+		if self.has_errors() {
+			attrs.insert("aria-invalid".to_string(), "true".to_string());
+			described_by.push(format!("{}_error", id));
+		}
+		if !described_by.is_empty() {
+			attrs.insert("aria-describedby".to_string(), described_by.join(" "));
+		}
+		attrs.insert("id".to_string(), id);
+		attrs
+	}
+	/// Render the widget with [`widget_attrs`](Self::widget_attrs), merged with `attrs`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_forms::{BoundField, CharField, FormField};
+	///
+	/// let mut field = CharField::new("email".to_string());
+	/// field.required = true;
+	/// let field: Box<dyn FormField> = Box::new(field);
+	/// let errors = vec!["Enter a valid email".to_string()];
+	///
+	/// let bound = BoundField::new("form".to_string(), field.as_ref(), None, &errors, "");
+	/// let html = bound.as_widget(None);
+	/// assert!(html.contains("aria-invalid=\"true\""));
+	/// assert!(html.contains("aria-describedby=\"id_email_error\""));
+	/// assert!(html.contains(" required"));
+	/// ```
+	pub fn as_widget(&self, attrs: Option<&HashMap<String, String>>) -> String {
+		let mut widget_attrs = self.widget_attrs();
+		if let Some(attrs) = attrs {
+			widget_attrs.extend(attrs.iter().map(|(k, v)| (k.clone(), v.clone())));
+		}
+		self.widget().render_default(
+			&self.html_name(),
+			self.value_string().as_deref(),
+			Some(&widget_attrs),
+		)
+	}
+	/// Render the `<label>` for this field
+	pub fn label_tag(&self) -> String {
+		FormRenderer::default().label_tag(self)
+	}
+	/// Render the help text, or an empty string if there is none
+	pub fn help_text_html(&self) -> String {
+		self.help_text()
+			.map(|help| {
+				format!(
+					"<span class=\"helptext\" id=\"{}_helptext\">{}</span>",
+					escape_html_attr(&self.id_for_label()),
+					escape_html(help)
+				)
+			})
+			.unwrap_or_default()
+	}
+	/// Render the errors as a list, or an empty string if there are none
+	pub fn errors_html(&self) -> String {
+		if self.errors.is_empty() {
+			return String::new();
+		}
+		errors_list(
+			self.errors,
+			"errorlist",
+			Some(&format!("{}_error", self.id_for_label())),
+		)
+	}
+	/// Render the complete field row with the built-in markup
+	///
+	/// Use [`FormRenderer::render_field`] to apply project templates.
+	pub fn render(&self) -> String {
+		FormRenderer::default().default_field_row(self)
+	}
 }
 
 #[cfg(test)]
@@ -1,5 +1,6 @@
+use reinhardt_core::security::xss::{escape_html, escape_html_attr};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ErrorType {
@@ -93,8 +94,34 @@ pub enum Widget {
 }
 
 impl Widget {
+	/// Name of the template that overrides this widget in a
+	/// [`FormRenderer`](crate::renderer::FormRenderer)
+	pub fn template_name(&self) -> &'static str {
+		match self {
+			Widget::TextInput => "text_input",
+			Widget::PasswordInput => "password_input",
+			Widget::EmailInput => "email_input",
+			Widget::NumberInput => "number_input",
+			Widget::TextArea => "textarea",
+			Widget::Select { .. } => "select",
+			Widget::CheckboxInput => "checkbox_input",
+			Widget::RadioSelect { .. } => "radio_select",
+			Widget::DateInput => "date_input",
+			Widget::DateTimeInput => "datetime_input",
+			Widget::FileInput => "file_input",
+			Widget::HiddenInput => "hidden_input",
+		}
+	}
+	/// Whether the widget renders without a visible control
+	pub fn is_hidden(&self) -> bool {
+		matches!(self, Widget::HiddenInput)
+	}
 	/// Renders the widget as HTML
 	///
+	/// Same as [`Widget::render_default`]. Use
+	/// [`FormRenderer::render_widget`](crate::renderer::FormRenderer::render_widget)
+	/// to apply project templates.
+	///
 	/// # Examples
 	///
 	/// ```
@@ -113,80 +140,61 @@ impl Widget {
 		value: Option<&str>,
 		attrs: Option<&HashMap<String, String>>,
 	) -> String {
-		let mut html = String::new();
-		let default_attrs = HashMap::new();
-		let attrs = attrs.unwrap_or(&default_attrs);
-
-		// Build common attributes
-		let mut common_attrs = String::new();
-		for (key, val) in attrs {
-			common_attrs.push_str(&format!(" {}=\"{}\"", key, val));
-		}
+		self.render_default(name, value, attrs)
+	}
+	/// Renders the built-in HTML for the widget
+	///
+	/// Values and attributes are HTML-escaped and attributes are emitted in
+	/// name order. The `id` defaults to `id_{name}`; an attribute with an empty
+	/// value (e.g. `required`) is rendered as a bare boolean attribute.
+	/// Password inputs never echo their value back.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_forms::Widget;
+	/// use std::collections::HashMap;
+	///
+	/// let mut attrs = HashMap::new();
+	/// attrs.insert("class".to_string(), "wide".to_string());
+	/// attrs.insert("required".to_string(), String::new());
+	///
+	/// let html = Widget::TextInput.render_default("q", Some("<b>"), Some(&attrs));
+	/// assert_eq!(
+	///     html,
+	///     "<input type=\"text\" name=\"q\" value=\"&lt;b&gt;\" class=\"wide\" id=\"id_q\" required />"
+	/// );
+	///
+	/// let select = Widget::Select {
+	///     choices: vec![("s".to_string(), "Small".to_string())],
+	/// };
+	/// assert!(select.render_default("size", Some("s"), None)
+	///     .contains("<option value=\"s\" selected>Small</option>"));
+	/// ```
+	pub fn render_default(
+		&self,
+		name: &str,
+		value: Option<&str>,
+		attrs: Option<&HashMap<String, String>>,
+	) -> String {
+		let mut attrs: BTreeMap<String, String> = attrs
+			.map(|attrs| attrs.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+			.unwrap_or_default();
+		let id = attrs
+			.entry("id".to_string())
+			.or_insert_with(|| format!("id_{}", name))
+			.clone();
+		let name = escape_html_attr(name);
 
 		match self {
-			Widget::TextInput => {
-				html.push_str(&format!(
-					"<input type=\"text\" name=\"{}\" value=\"{}\"{}",
-					name,
-					value.unwrap_or(""),
-					common_attrs
-				));
-				if !attrs.contains_key("id") {
-					html.push_str(&format!(" id=\"id_{}\"", name));
-				}
-				html.push_str(" />");
-			}
-			Widget::PasswordInput => {
-				html.push_str(&format!(
-					"<input type=\"password\" name=\"{}\" value=\"{}\"{}",
-					name,
-					value.unwrap_or(""),
-					common_attrs
-				));
-				if !attrs.contains_key("id") {
-					html.push_str(&format!(" id=\"id_{}\"", name));
-				}
-				html.push_str(" />");
-			}
-			Widget::EmailInput => {
-				html.push_str(&format!(
-					"<input type=\"email\" name=\"{}\" value=\"{}\"{}",
-					name,
-					value.unwrap_or(""),
-					common_attrs
-				));
-				if !attrs.contains_key("id") {
-					html.push_str(&format!(" id=\"id_{}\"", name));
-				}
-				html.push_str(" />");
-			}
-			Widget::NumberInput => {
-				html.push_str(&format!(
-					"<input type=\"number\" name=\"{}\" value=\"{}\"{}",
-					name,
-					value.unwrap_or(""),
-					common_attrs
-				));
-				if !attrs.contains_key("id") {
-					html.push_str(&format!(" id=\"id_{}\"", name));
-				}
-				html.push_str(" />");
-			}
-			Widget::TextArea => {
-				html.push_str(&format!("<textarea name=\"{}\"{}", name, common_attrs));
-				if !attrs.contains_key("id") {
-					html.push_str(&format!(" id=\"id_{}\"", name));
-				}
-				html.push('>');
-				html.push_str(value.unwrap_or(""));
-				html.push_str("</textarea>");
-			}
+			Widget::TextArea => format!(
+				"<textarea name=\"{}\"{}>{}</textarea>",
+				name,
+				render_attrs(&attrs),
+				escape_html(value.unwrap_or(""))
+			),
 			Widget::Select { choices } => {
-				html.push_str(&format!("<select name=\"{}\"{}", name, common_attrs));
-				if !attrs.contains_key("id") {
-					html.push_str(&format!(" id=\"id_{}\"", name));
-				}
-				html.push('>');
+				let mut html = format!("<select name=\"{}\"{}>", name, render_attrs(&attrs));
 				for (choice_value, choice_label) in choices {
 					let selected = if Some(choice_value.as_str()) == value {
 						" selected"
@@ -195,86 +203,102 @@ impl Widget {
 					};
 					html.push_str(&format!(
 						"<option value=\"{}\"{}>{}</option>",
-						choice_value, selected, choice_label
+						escape_html_attr(choice_value),
+						selected,
+						escape_html(choice_label)
 					));
 				}
 				html.push_str("</select>");
+				html
 			}
 			Widget::CheckboxInput => {
-				html.push_str(&format!("<input type=\"checkbox\" name=\"{}\"", name));
-				if value == Some("true") || value == Some("on") {
-					html.push_str(" checked");
-				}
-				html.push_str(&common_attrs);
-				if !attrs.contains_key("id") {
-					html.push_str(&format!(" id=\"id_{}\"", name));
-				}
-				html.push_str(" />");
+				let checked = match value {
+					Some(v) => matches!(v.to_ascii_lowercase().as_str(), "true" | "on" | "1"),
+					None => false,
+				};
+				format!(
+					"<input type=\"checkbox\" name=\"{}\"{}{} />",
+					name,
+					if checked { " checked" } else { "" },
+					render_attrs(&attrs)
+				)
 			}
 			Widget::RadioSelect { choices } => {
+				// The id goes on the wrapper; each option gets its own id
+				attrs.remove("id");
+				let mut html = format!("<div id=\"{}\">", escape_html_attr(&id));
 				for (i, (choice_value, choice_label)) in choices.iter().enumerate() {
+					let option_id = escape_html_attr(&format!("{}_{}", id, i));
 					let checked = if Some(choice_value.as_str()) == value {
 						" checked"
 					} else {
 						""
 					};
 					html.push_str(&format!(
-						"<input type=\"radio\" name=\"{}\" value=\"{}\" id=\"id_{}_{}\"{}{} />",
-						name, choice_value, name, i, checked, common_attrs
+						"<div><label for=\"{}\"><input type=\"radio\" name=\"{}\" value=\"{}\" id=\"{}\"{}{} /> {}</label></div>",
+						option_id,
+						name,
+						escape_html_attr(choice_value),
+						option_id,
+						checked,
+						render_attrs(&attrs),
+						escape_html(choice_label)
 					));
-					html.push_str(&format!(
-						"<label for=\"id_{}_{}\">{}</label>",
-						name, i, choice_label
-					));
-				}
-			}
-			Widget::DateInput => {
-				html.push_str(&format!(
-					"<input type=\"date\" name=\"{}\" value=\"{}\"{}",
-					name,
-					value.unwrap_or(""),
-					common_attrs
-				));
-				if !attrs.contains_key("id") {
-					html.push_str(&format!(" id=\"id_{}\"", name));
 				}
-				html.push_str(" />");
+				html.push_str("</div>");
+				html
 			}
-			Widget::DateTimeInput => {
-				html.push_str(&format!(
-					"<input type=\"datetime-local\" name=\"{}\" value=\"{}\"{}",
+			Widget::FileInput => format!(
+				"<input type=\"file\" name=\"{}\"{} />",
+				name,
+				render_attrs(&attrs)
+			),
+			Widget::TextInput
+			| Widget::PasswordInput
+			| Widget::EmailInput
+			| Widget::NumberInput
+			| Widget::DateInput
+			| Widget::DateTimeInput
+			| Widget::HiddenInput => {
+				let input_type = match self {
+					Widget::PasswordInput => "password",
+					Widget::EmailInput => "email",
+					Widget::NumberInput => "number",
+					Widget::DateInput => "date",
+					Widget::DateTimeInput => "datetime-local",
+					Widget::HiddenInput => "hidden",
+					_ => "text",
+				};
+				let value = match self {
+					Widget::PasswordInput => "",
+					_ => value.unwrap_or(""),
+				};
+				format!(
+					"<input type=\"{}\" name=\"{}\" value=\"{}\"{} />",
+					input_type,
 					name,
-					value.unwrap_or(""),
-					common_attrs
-				));
-				if !attrs.contains_key("id") {
-					html.push_str(&format!(" id=\"id_{}\"", name));
-				}
-				html.push_str(" />");
-			}
-			Widget::FileInput => {
-				html.push_str(&format!(
-					"<input type=\"file\" name=\"{}\"{}",
-					name, common_attrs
-				));
-				if !attrs.contains_key("id") {
-					html.push_str(&format!(" id=\"id_{}\"", name));
-				}
-				html.push_str(" />");
-			}
-			Widget::HiddenInput => {
-				html.push_str(&format!(
-					"<input type=\"hidden\" name=\"{}\" value=\"{}\" />",
-					name,
-					value.unwrap_or("")
-				));
+					escape_html_attr(value),
+					render_attrs(&attrs)
+				)
 			}
 		}
-
-		html
 	}
 }
 
+/// Render attributes as ` key="value"`, with empty values as bare attributes
+pub(crate) fn render_attrs(attrs: &BTreeMap<String, String>) -> String {
+	attrs
+		.iter()
+		.map(|(key, value)| {
+			if value.is_empty() {
+				format!(" {}", escape_html_attr(key))
+			} else {
+				format!(" {}=\"{}\"", escape_html_attr(key), escape_html_attr(value))
+			}
+		})
+		.collect()
+}
+
 /// Base field trait for forms
 ///
 /// This trait is specifically for form fields. For ORM fields, use `reinhardt_db::orm::Field`.
@@ -120,6 +120,10 @@ pub struct ImageField {
 	pub initial: Option<serde_json::Value>,
 	pub max_length: Option<usize>,
 	pub allow_empty_file: bool,
+	pub min_width: Option<u32>,
+	pub max_width: Option<u32>,
+	pub min_height: Option<u32>,
+	pub max_height: Option<u32>,
 }
 
 impl ImageField {
@@ -133,9 +137,67 @@ impl ImageField {
 			initial: None,
 			max_length: None,
 			allow_empty_file: false,
+			min_width: None,
+			max_width: None,
+			min_height: None,
+			max_height: None,
 		}
 	}
 
+	fn has_dimension_limits(&self) -> bool {
+		self.min_width.is_some()
+			|| self.max_width.is_some()
+			|| self.min_height.is_some()
+			|| self.max_height.is_some()
+	}
+
+	/// Check the "width" and "height" of a cleaned value against the limits
+	fn validate_dimensions(
+		&self,
+		obj: &serde_json::Map<String, serde_json::Value>,
+	) -> FieldResult<()> {
+		let dimension = |key: &str| obj.get(key).and_then(|v| v.as_u64());
+		let (Some(width), Some(height)) = (dimension("width"), dimension("height")) else {
+			return Err(FieldError::Validation(
+				"Unable to determine the image dimensions".to_string(),
+			));
+		};
+
+		if let Some(min) = self.min_width
+			&& width < u64::from(min)
+		{
+			return Err(FieldError::Validation(format!(
+				"Image width must be at least {}px (got {}px)",
+				min, width
+			)));
+		}
+		if let Some(max) = self.max_width
+			&& width > u64::from(max)
+		{
+			return Err(FieldError::Validation(format!(
+				"Image width must be at most {}px (got {}px)",
+				max, width
+			)));
+		}
+		if let Some(min) = self.min_height
+			&& height < u64::from(min)
+		{
+			return Err(FieldError::Validation(format!(
+				"Image height must be at least {}px (got {}px)",
+				min, height
+			)));
+		}
+		if let Some(max) = self.max_height
+			&& height > u64::from(max)
+		{
+			return Err(FieldError::Validation(format!(
+				"Image height must be at most {}px (got {}px)",
+				max, height
+			)));
+		}
+		Ok(())
+	}
+
 	fn is_valid_image_extension(filename: &str) -> bool {
 		let valid_extensions = ["jpg", "jpeg", "png", "gif", "webp", "bmp", "svg"];
 		filename
@@ -219,6 +281,11 @@ impl FormField for ImageField {
 					));
 				}
 
+				// Check dimensions, as reported by file_handling::UploadedFile::to_form_value
+				if self.has_dimension_limits() {
+					self.validate_dimensions(obj)?;
+				}
+
 				Ok(v.clone())
 			}
 		}
@@ -282,4 +349,38 @@ mod tests {
 			Err(FieldError::Validation(_))
 		));
 	}
+
+	#[test]
+	fn test_imagefield_dimension_limits() {
+		let mut field = ImageField::new("photo".to_string());
+		field.min_width = Some(100);
+		field.max_height = Some(200);
+
+		let ok = serde_json::json!({
+			"filename": "test.png",
+			"size": 1024,
+			"width": 150,
+			"height": 200
+		});
+		let too_narrow = serde_json::json!({
+			"filename": "test.png",
+			"size": 1024,
+			"width": 99,
+			"height": 50
+		});
+		let unknown = serde_json::json!({
+			"filename": "test.png",
+			"size": 1024
+		});
+
+		assert!(field.clean(Some(&ok)).is_ok());
+		assert!(matches!(
+			field.clean(Some(&too_narrow)),
+			Err(FieldError::Validation(_))
+		));
+		assert!(matches!(
+			field.clean(Some(&unknown)),
+			Err(FieldError::Validation(_))
+		));
+	}
 }
@@ -0,0 +1,958 @@
+//! File upload handling
+//!
+//! Parses `multipart/form-data` bodies into [`UploadedFile`]s, validates them
+//! by size, extension, MIME type and image dimensions, and saves them through
+//! a [`Storage`] backend. Bodies are read chunk by chunk: files above the
+//! memory threshold are written to a temporary file as they arrive, and size
+//! limits are enforced before the rest of the body is read.
+//!
+//! MIME type validation sniffs the content instead of trusting the
+//! Content-Type sent by the client.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use reinhardt_forms::file_handling::{FileUploadHandler, FileValidator, save_uploaded_file};
+//!
+//! let data = FileUploadHandler::new()
+//!     .with_max_file_size(10 * 1024 * 1024)
+//!     .parse_multipart_stream(content_type, body_stream)
+//!     .await?;
+//!
+//! let avatar = data.file("avatar").unwrap();
+//! FileValidator::new()
+//!     .with_extensions(vec!["png".to_string(), "jpg".to_string()])
+//!     .validate(avatar)?;
+//!
+//! form.bind(data.form_data());
+//! if form.is_valid() {
+//!     let stored = save_uploaded_file(&storage, "avatars", avatar).await?;
+//! }
+//! ```
+
+use crate::form::FormError;
+use bytes::Bytes;
+use futures_util::stream::{self, Stream};
+use reinhardt_core::parsers::parser;
+use reinhardt_core::parsers::streaming_multipart::{SpooledFile, StreamingMultiPartParser};
+use reinhardt_core::validators::{
+	FileSizeValidator, FileTypeValidator, ImageDimensionValidator, ValidationError, Validator,
+};
+use reinhardt_utils::storage::{FileMetadata, Storage, StorageError};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use std::io::{Cursor, Read, Write};
+use std::path::{Path, PathBuf};
+use tempfile::NamedTempFile;
+
+/// Default size above which uploads are spooled to disk (2.5 MB, as in Django)
+pub const DEFAULT_MAX_MEMORY_SIZE: usize = 2_621_440;
+
+/// Default maximum size of a non-file field (2.5 MB, as in Django)
+pub const DEFAULT_MAX_FIELD_SIZE: usize = 2_621_440;
+
+/// Number of leading bytes read to sniff the content type
+const SNIFF_LENGTH: usize = 16;
+
+/// Leading bytes of formats recognized by [`sniff_mime_type`]
+///
+/// `None` matches any byte, for formats with a variable size field.
+const SIGNATURES: &[(&[Option<u8>], &str)] = &[
+	(&bytes_pattern(b"\x89PNG\r\n\x1a\n"), "image/png"),
+	(&bytes_pattern(b"\xff\xd8\xff"), "image/jpeg"),
+	(&bytes_pattern(b"GIF87a"), "image/gif"),
+	(&bytes_pattern(b"GIF89a"), "image/gif"),
+	(
+		&[
+			Some(b'R'),
+			Some(b'I'),
+			Some(b'F'),
+			Some(b'F'),
+			None,
+			None,
+			None,
+			None,
+			Some(b'W'),
+			Some(b'E'),
+			Some(b'B'),
+			Some(b'P'),
+		],
+		"image/webp",
+	),
+	(&bytes_pattern(b"II*\0"), "image/tiff"),
+	(&bytes_pattern(b"MM\0*"), "image/tiff"),
+	(&bytes_pattern(b"%PDF-"), "application/pdf"),
+	(&bytes_pattern(b"PK\x03\x04"), "application/zip"),
+	(&bytes_pattern(b"\x1f\x8b"), "application/gzip"),
+	(&bytes_pattern(b"\0asm"), "application/wasm"),
+	(&bytes_pattern(b"\x7fELF"), "application/x-executable"),
+	(&bytes_pattern(b"MZ"), "application/x-msdownload"),
+];
+
+const fn bytes_pattern<const N: usize>(bytes: &[u8; N]) -> [Option<u8>; N] {
+	let mut pattern = [None; N];
+	let mut i = 0;
+	while i < N {
+		pattern[i] = Some(bytes[i]);
+		i += 1;
+	}
+	pattern
+}
+
+/// Detect the MIME type of a file from its leading bytes
+///
+/// Only recognizes binary formats with a fixed signature; text formats and
+/// unknown content return `None`.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_forms::file_handling::sniff_mime_type;
+///
+/// assert_eq!(sniff_mime_type(b"%PDF-1.7"), Some("application/pdf"));
+/// assert_eq!(sniff_mime_type(b"MZ\x90\x00"), Some("application/x-msdownload"));
+/// assert_eq!(sniff_mime_type(b"hello"), None);
+/// ```
+pub fn sniff_mime_type(head: &[u8]) -> Option<&'static str> {
+	SIGNATURES
+		.iter()
+		.find(|(signature, _)| {
+			head.len() >= signature.len()
+				&& signature
+					.iter()
+					.zip(head)
+					.all(|(expected, byte)| expected.is_none_or(|expected| expected == *byte))
+		})
+		.map(|(_, mime_type)| *mime_type)
+}
+
+/// Errors raised while handling uploaded files
+#[derive(Debug, thiserror::Error)]
+pub enum FileUploadError {
+	#[error("Failed to parse upload: {0}")]
+	Parse(String),
+	#[error("File '{filename}' exceeds maximum size of {max_size} bytes")]
+	TooLarge { filename: String, max_size: usize },
+	#[error("{0}")]
+	Invalid(#[from] ValidationError),
+	#[error("Upload a valid image: {0}")]
+	InvalidImage(String),
+	#[error("IO error: {0}")]
+	Io(#[from] std::io::Error),
+	#[error("Storage error: {0}")]
+	Storage(#[from] StorageError),
+}
+
+impl From<FileUploadError> for FormError {
+	fn from(error: FileUploadError) -> Self {
+		FormError::Validation(error.to_string())
+	}
+}
+
+/// Where the content of an uploaded file is kept
+#[derive(Debug)]
+enum FileContent {
+	Memory(Bytes),
+	Spooled(NamedTempFile),
+}
+
+/// A file received from a multipart upload
+#[derive(Debug)]
+pub struct UploadedFile {
+	/// Name of the form field the file was submitted with
+	pub field_name: String,
+	/// Original filename as sent by the client
+	pub filename: String,
+	/// Content type as declared by the client
+	pub content_type: Option<String>,
+	/// Size in bytes
+	pub size: usize,
+	content: FileContent,
+}
+
+impl UploadedFile {
+	/// Create an uploaded file held in memory
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bytes::Bytes;
+	/// use reinhardt_forms::file_handling::UploadedFile;
+	///
+	/// let file = UploadedFile::in_memory("doc", "Report.PDF", Bytes::from("%PDF"));
+	/// assert_eq!(file.size, 4);
+	/// assert_eq!(file.extension(), Some("pdf".to_string()));
+	/// assert!(!file.is_spooled());
+	/// ```
+	pub fn in_memory(
+		field_name: impl Into<String>,
+		filename: impl Into<String>,
+		data: Bytes,
+	) -> Self {
+		Self {
+			field_name: field_name.into(),
+			filename: filename.into(),
+			content_type: None,
+			size: data.len(),
+			content: FileContent::Memory(data),
+		}
+	}
+	pub fn with_content_type(mut self, content_type: impl Into<String>) -> Self {
+		self.content_type = Some(content_type.into());
+		self
+	}
+	/// Get the lowercased file extension, if any
+	pub fn extension(&self) -> Option<String> {
+		Path::new(&self.filename)
+			.extension()
+			.map(|ext| ext.to_string_lossy().to_lowercase())
+	}
+	/// Whether the content was spooled to a temporary file
+	pub fn is_spooled(&self) -> bool {
+		matches!(self.content, FileContent::Spooled(_))
+	}
+	/// Get the temporary file path of a spooled upload
+	///
+	/// The file is removed when the `UploadedFile` is dropped.
+	pub fn temporary_path(&self) -> Option<&Path> {
+		match &self.content {
+			FileContent::Spooled(file) => Some(file.path()),
+			FileContent::Memory(_) => None,
+		}
+	}
+	/// Read the whole content, loading spooled files from disk
+	pub fn read(&self) -> Result<Bytes, FileUploadError> {
+		match &self.content {
+			FileContent::Memory(data) => Ok(data.clone()),
+			FileContent::Spooled(file) => Ok(Bytes::from(std::fs::read(file.path())?)),
+		}
+	}
+	/// Read up to `len` leading bytes, without loading spooled files
+	fn head(&self, len: usize) -> Result<Vec<u8>, FileUploadError> {
+		match &self.content {
+			FileContent::Memory(data) => Ok(data[..len.min(data.len())].to_vec()),
+			FileContent::Spooled(file) => {
+				let mut head = Vec::with_capacity(len);
+				std::fs::File::open(file.path())?
+					.take(len as u64)
+					.read_to_end(&mut head)?;
+				Ok(head)
+			}
+		}
+	}
+	/// Get the MIME type detected from the content
+	///
+	/// Falls back to the type guessed from the extension when the content is
+	/// not a recognized binary format. Unlike [`mime_type`](Self::mime_type),
+	/// the Content-Type sent by the client is ignored, so this is the type
+	/// to validate against.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bytes::Bytes;
+	/// use reinhardt_forms::file_handling::UploadedFile;
+	///
+	/// let file = UploadedFile::in_memory("doc", "notes.txt", Bytes::from_static(b"MZ\x90\x00"))
+	///     .with_content_type("text/plain");
+	/// assert_eq!(file.sniffed_mime_type().unwrap(), Some("application/x-msdownload".to_string()));
+	/// ```
+	pub fn sniffed_mime_type(&self) -> Result<Option<String>, FileUploadError> {
+		let guessed = mime_guess::from_path(&self.filename).first_raw();
+		Ok(match sniff_mime_type(&self.head(SNIFF_LENGTH)?) {
+			// Office documents, JARs and EPUBs are ZIP containers
+			Some("application/zip") if guessed.is_some_and(|guessed| !is_sniffable(guessed)) => {
+				guessed.map(String::from)
+			}
+			Some(sniffed) => Some(sniffed.to_string()),
+			// A binary extension whose signature is missing is not trusted
+			None if guessed.is_some_and(is_sniffable) => None,
+			None => guessed.map(String::from),
+		})
+	}
+	/// Get the MIME type declared by the client, or guessed from the extension
+	pub fn mime_type(&self) -> Option<String> {
+		self.content_type.clone().or_else(|| {
+			mime_guess::from_path(&self.filename)
+				.first_raw()
+				.map(String::from)
+		})
+	}
+	/// Get the image dimensions as `(width, height)` if the file is a readable image
+	pub fn image_dimensions(&self) -> Option<(u32, u32)> {
+		match &self.content {
+			FileContent::Memory(data) => image::ImageReader::new(Cursor::new(data))
+				.with_guessed_format()
+				.ok()?
+				.into_dimensions()
+				.ok(),
+			FileContent::Spooled(file) => image::image_dimensions(file.path()).ok(),
+		}
+	}
+	/// Convert to the value [`FileField`](crate::FileField) and
+	/// [`ImageField`](crate::ImageField) expect when cleaning
+	///
+	/// Images also carry their `width` and `height`, which `ImageField` checks
+	/// against its dimension limits.
+	pub fn to_form_value(&self) -> Value {
+		let mut value = json!({
+			"filename": self.filename,
+			"size": self.size,
+			"content_type": self.mime_type(),
+		});
+		if let Some((width, height)) = self.image_dimensions() {
+			value["width"] = json!(width);
+			value["height"] = json!(height);
+		}
+		value
+	}
+}
+
+/// Fields and files parsed from a multipart body
+#[derive(Debug, Default)]
+pub struct MultipartData {
+	pub fields: HashMap<String, String>,
+	pub files: HashMap<String, Vec<UploadedFile>>,
+}
+
+impl MultipartData {
+	/// Get the first file submitted for a field
+	pub fn file(&self, field_name: &str) -> Option<&UploadedFile> {
+		self.files.get(field_name).and_then(|files| files.first())
+	}
+	/// Build data for [`Form::bind`](crate::Form::bind)
+	///
+	/// Text fields become strings and each file field becomes the value
+	/// returned by [`UploadedFile::to_form_value`] for its first file.
+	pub fn form_data(&self) -> HashMap<String, Value> {
+		let mut data: HashMap<String, Value> = self
+			.fields
+			.iter()
+			.map(|(name, value)| (name.clone(), Value::String(value.clone())))
+			.collect();
+		for (name, files) in &self.files {
+			if let Some(file) = files.first() {
+				data.insert(name.clone(), file.to_form_value());
+			}
+		}
+		data
+	}
+}
+
+/// Whether [`sniff_mime_type`] can recognize content of this type
+fn is_sniffable(mime_type: &str) -> bool {
+	SIGNATURES.iter().any(|(_, sniffed)| *sniffed == mime_type)
+}
+
+/// Turns raw multipart uploads into [`UploadedFile`]s
+#[derive(Debug, Clone)]
+pub struct FileUploadHandler {
+	max_memory_size: usize,
+	max_file_size: Option<usize>,
+	max_field_size: usize,
+	max_total_size: Option<usize>,
+	temp_dir: Option<PathBuf>,
+}
+
+impl FileUploadHandler {
+	/// Create a handler that spools files above [`DEFAULT_MAX_MEMORY_SIZE`]
+	/// and rejects non-file fields above [`DEFAULT_MAX_FIELD_SIZE`]
+	pub fn new() -> Self {
+		Self {
+			max_memory_size: DEFAULT_MAX_MEMORY_SIZE,
+			max_file_size: None,
+			max_field_size: DEFAULT_MAX_FIELD_SIZE,
+			max_total_size: None,
+			temp_dir: None,
+		}
+	}
+	/// Set the size in bytes above which files are spooled to disk
+	pub fn with_max_memory_size(mut self, size: usize) -> Self {
+		self.max_memory_size = size;
+		self
+	}
+	/// Reject files larger than `size` bytes
+	pub fn with_max_file_size(mut self, size: usize) -> Self {
+		self.max_file_size = Some(size);
+		self
+	}
+	/// Reject non-file fields larger than `size` bytes
+	pub fn with_max_field_size(mut self, size: usize) -> Self {
+		self.max_field_size = size;
+		self
+	}
+	/// Reject bodies whose parts add up to more than `size` bytes
+	pub fn with_max_total_size(mut self, size: usize) -> Self {
+		self.max_total_size = Some(size);
+		self
+	}
+	/// Set the directory for spooled files (defaults to the system temp dir)
+	pub fn with_temp_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+		self.temp_dir = Some(dir.into());
+		self
+	}
+
+	/// Convert a file produced by the request parsers, spooling it if large
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bytes::Bytes;
+	/// use reinhardt_core::parsers::parser::UploadedFile as ParsedFile;
+	/// use reinhardt_forms::file_handling::FileUploadHandler;
+	///
+	/// let parsed = ParsedFile::new("doc".to_string(), Bytes::from(vec![0u8; 64]))
+	///     .with_filename("data.bin".to_string());
+	///
+	/// let handler = FileUploadHandler::new().with_max_memory_size(16);
+	/// let file = handler.handle(parsed).unwrap();
+	/// assert!(file.is_spooled());
+	/// assert_eq!(file.read().unwrap().len(), 64);
+	/// ```
+	pub fn handle(&self, file: parser::UploadedFile) -> Result<UploadedFile, FileUploadError> {
+		let filename = file.filename.unwrap_or_default();
+		if let Some(max_size) = self.max_file_size
+			&& file.size > max_size
+		{
+			return Err(FileUploadError::TooLarge { filename, max_size });
+		}
+
+		let content = if file.size > self.max_memory_size {
+			let mut spooled = self.temp_file()?;
+			spooled.write_all(&file.data)?;
+			spooled.flush()?;
+			FileContent::Spooled(spooled)
+		} else {
+			FileContent::Memory(file.data)
+		};
+
+		Ok(UploadedFile {
+			field_name: file.name,
+			filename,
+			content_type: file.content_type,
+			size: file.size,
+			content,
+		})
+	}
+
+	/// Parse a `multipart/form-data` body that is already in memory
+	///
+	/// `content_type` is the request's Content-Type header, including the
+	/// boundary parameter. Prefer [`parse_multipart_stream`](Self::parse_multipart_stream)
+	/// for request bodies, so that uploads are not buffered in memory.
+	pub async fn parse_multipart(
+		&self,
+		content_type: &str,
+		body: Bytes,
+	) -> Result<MultipartData, FileUploadError> {
+		self.parse_multipart_stream(
+			content_type,
+			stream::once(async move { Ok::<_, std::io::Error>(body) }),
+		)
+		.await
+	}
+
+	/// Parse a `multipart/form-data` body from a stream of chunks
+	///
+	/// Files above the memory threshold are written to disk as they arrive,
+	/// and parsing stops as soon as a size limit is exceeded.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use bytes::Bytes;
+	/// use futures_util::stream;
+	/// use reinhardt_forms::file_handling::FileUploadHandler;
+	///
+	/// # tokio_test::block_on(async {
+	/// let chunks = vec![
+	///     Ok::<_, std::io::Error>(Bytes::from(
+	///         "--b\r\nContent-Disposition: form-data; name=\"doc\"; filename=\"a.txt\"\r\n\r\n0123",
+	///     )),
+	///     Ok(Bytes::from("456789\r\n--b--\r\n")),
+	/// ];
+	///
+	/// let data = FileUploadHandler::new()
+	///     .with_max_memory_size(4)
+	///     .parse_multipart_stream("multipart/form-data; boundary=b", stream::iter(chunks))
+	///     .await
+	///     .unwrap();
+	///
+	/// let doc = data.file("doc").unwrap();
+	/// assert!(doc.is_spooled());
+	/// assert_eq!(doc.read().unwrap(), Bytes::from("0123456789"));
+	/// # });
+	/// ```
+	pub async fn parse_multipart_stream<S>(
+		&self,
+		content_type: &str,
+		body: S,
+	) -> Result<MultipartData, FileUploadError>
+	where
+		S: Stream<Item = Result<Bytes, std::io::Error>> + Send + 'static,
+	{
+		let mut parser = StreamingMultiPartParser::new()
+			.memory_threshold(self.max_memory_size)
+			.max_field_size(self.max_field_size);
+		parser.spool_dir = self.temp_dir.clone();
+		parser.max_file_size = self.max_file_size;
+		parser.max_total_size = self.max_total_size;
+
+		let parsed = parser
+			.parse_stream(content_type, body)
+			.await
+			.map_err(|e| FileUploadError::Parse(e.to_string()))?;
+
+		let mut data = MultipartData {
+			fields: parsed.fields,
+			files: HashMap::new(),
+		};
+		for file in parsed.files {
+			let file = self.adopt(file).await?;
+			data.files
+				.entry(file.field_name.clone())
+				.or_default()
+				.push(file);
+		}
+		Ok(data)
+	}
+
+	/// Take over a file from the streaming parser without copying its content
+	async fn adopt(&self, file: SpooledFile) -> Result<UploadedFile, FileUploadError> {
+		let field_name = file.name.clone();
+		let filename = file.filename.clone().unwrap_or_default();
+		let content_type = file.content_type.clone();
+		let size = file.size;
+
+		let content = if file.is_in_memory() {
+			FileContent::Memory(
+				file.read()
+					.await
+					.map_err(|e| FileUploadError::Parse(e.to_string()))?,
+			)
+		} else {
+			// Moved over an empty temporary file, which then owns the content
+			let spooled = self.temp_file()?;
+			file.persist(spooled.path())
+				.await
+				.map_err(|e| FileUploadError::Parse(e.to_string()))?;
+			FileContent::Spooled(spooled)
+		};
+
+		Ok(UploadedFile {
+			field_name,
+			filename,
+			content_type,
+			size,
+			content,
+		})
+	}
+
+	fn temp_file(&self) -> Result<NamedTempFile, FileUploadError> {
+		Ok(match &self.temp_dir {
+			Some(dir) => NamedTempFile::new_in(dir)?,
+			None => NamedTempFile::new()?,
+		})
+	}
+}
+
+impl Default for FileUploadHandler {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Validates uploaded files by size, type and image dimensions
+///
+/// # Examples
+///
+/// ```
+/// use bytes::Bytes;
+/// use reinhardt_forms::file_handling::{FileValidator, UploadedFile};
+///
+/// let validator = FileValidator::new()
+///     .with_max_size(1024)
+///     .with_extensions(vec!["txt".to_string()])
+///     .with_mime_types(vec!["text/plain".to_string()]);
+///
+/// let file = UploadedFile::in_memory("notes", "notes.txt", Bytes::from("hello"));
+/// assert!(validator.validate(&file).is_ok());
+///
+/// let file = UploadedFile::in_memory("notes", "notes.exe", Bytes::from("MZ"));
+/// assert!(validator.validate(&file).is_err());
+///
+/// // The content decides, not the name or the declared type
+/// let file = UploadedFile::in_memory("notes", "notes.txt", Bytes::from_static(b"MZ\x90\x00"))
+///     .with_content_type("text/plain");
+/// assert!(validator.validate(&file).is_err());
+/// ```
+#[derive(Default)]
+pub struct FileValidator {
+	max_size: Option<usize>,
+	file_type: FileTypeValidator,
+	image: Option<ImageDimensionValidator>,
+}
+
+impl FileValidator {
+	pub fn new() -> Self {
+		Self::default()
+	}
+	pub fn with_max_size(mut self, max_size: usize) -> Self {
+		self.max_size = Some(max_size);
+		self
+	}
+	/// Only accept these extensions (without the dot, case-insensitive)
+	pub fn with_extensions(mut self, extensions: Vec<String>) -> Self {
+		self.file_type.allowed_extensions = Some(extensions);
+		self
+	}
+	/// Only accept these MIME types
+	///
+	/// The type is detected from the content with
+	/// [`UploadedFile::sniffed_mime_type`]; the declared content type is not
+	/// trusted.
+	pub fn with_mime_types(mut self, mime_types: Vec<String>) -> Self {
+		self.file_type.allowed_mime_types = Some(mime_types);
+		self
+	}
+	/// Require the file to be an image within the given dimensions
+	pub fn with_image_dimensions(mut self, validator: ImageDimensionValidator) -> Self {
+		self.image = Some(validator);
+		self
+	}
+
+	pub fn validate(&self, file: &UploadedFile) -> Result<(), FileUploadError> {
+		if let Some(max) = self.max_size {
+			FileSizeValidator::max(max as u64).validate(&(file.size as u64))?;
+		}
+		self.file_type.validate_filename(&file.filename)?;
+		if self.file_type.allowed_mime_types.is_some() {
+			let mime_type = file.sniffed_mime_type()?.unwrap_or_default();
+			self.file_type.validate_mime_type(&mime_type)?;
+		}
+		if let Some(ref image) = self.image {
+			image.validate_bytes(&file.read()?).map_err(|e| match e {
+				ValidationError::ImageReadError(msg) => FileUploadError::InvalidImage(msg),
+				other => FileUploadError::Invalid(other),
+			})?;
+		}
+		Ok(())
+	}
+}
+
+/// Reduce a client-supplied filename to a safe base name
+///
+/// Directory components are dropped and characters outside
+/// `[A-Za-z0-9._-]` are replaced with underscores.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_forms::file_handling::sanitize_filename;
+///
+/// assert_eq!(sanitize_filename("../../etc/passwd"), "passwd");
+/// assert_eq!(sanitize_filename("C:\\Users\\me\\my photo.png"), "my_photo.png");
+/// assert_eq!(sanitize_filename(".."), "upload");
+/// ```
+pub fn sanitize_filename(filename: &str) -> String {
+	let base = filename.rsplit(['/', '\\']).next().unwrap_or_default();
+	let sanitized: String = base
+		.chars()
+		.map(|c| {
+			if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+				c
+			} else {
+				'_'
+			}
+		})
+		.collect();
+	if sanitized.trim_matches('.').is_empty() {
+		"upload".to_string()
+	} else {
+		sanitized
+	}
+}
+
+/// Save an uploaded file under `upload_to` in a storage backend
+///
+/// The filename is sanitized, and a numeric suffix is appended when a file
+/// with the same name already exists, so existing files are never overwritten.
+pub async fn save_uploaded_file(
+	storage: &dyn Storage,
+	upload_to: &str,
+	file: &UploadedFile,
+) -> Result<FileMetadata, FileUploadError> {
+	let name = sanitize_filename(&file.filename);
+	let dir = upload_to.trim_matches('/');
+	let join = |name: &str| {
+		if dir.is_empty() {
+			name.to_string()
+		} else {
+			format!("{}/{}", dir, name)
+		}
+	};
+
+	let (stem, ext) = match name.rsplit_once('.') {
+		Some((stem, ext)) if !stem.is_empty() => (stem.to_string(), format!(".{}", ext)),
+		_ => (name.clone(), String::new()),
+	};
+	let mut path = join(&name);
+	let mut suffix = 1;
+	while storage.exists(&path).await? {
+		path = join(&format!("{}_{}{}", stem, suffix, ext));
+		suffix += 1;
+	}
+
+	let mut metadata = storage.save(&path, &file.read()?).await?;
+	if metadata.content_type.is_none() {
+		metadata.content_type = file.mime_type();
+	}
+	Ok(metadata)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use reinhardt_utils::storage::InMemoryStorage;
+
+	fn png(width: u32, height: u32) -> Bytes {
+		let mut data = Vec::new();
+		image::RgbImage::new(width, height)
+			.write_to(&mut Cursor::new(&mut data), image::ImageFormat::Png)
+			.unwrap();
+		Bytes::from(data)
+	}
+
+	fn multipart_body(boundary: &str) -> Bytes {
+		Bytes::from(format!(
+			"--{b}\r\n\
+			 Content-Disposition: form-data; name=\"title\"\r\n\r\n\
+			 Holiday\r\n\
+			 --{b}\r\n\
+			 Content-Disposition: form-data; name=\"doc\"; filename=\"notes.txt\"\r\n\
+			 Content-Type: text/plain\r\n\r\n\
+			 hello world\r\n\
+			 --{b}--\r\n",
+			b = boundary
+		))
+	}
+
+	#[test]
+	fn test_parse_multipart_into_form_data() {
+		let handler = FileUploadHandler::new();
+		let data = tokio_test::block_on(
+			handler.parse_multipart("multipart/form-data; boundary=XyZ", multipart_body("XyZ")),
+		)
+		.unwrap();
+
+		let doc = data.file("doc").unwrap();
+		assert_eq!(doc.filename, "notes.txt");
+		assert_eq!(doc.read().unwrap(), Bytes::from("hello world"));
+
+		let form_data = data.form_data();
+		assert_eq!(form_data["title"], json!("Holiday"));
+		assert_eq!(form_data["doc"]["size"], json!(11));
+		assert_eq!(form_data["doc"]["content_type"], json!("text/plain"));
+	}
+
+	#[test]
+	fn test_parse_multipart_enforces_max_file_size() {
+		let handler = FileUploadHandler::new().with_max_file_size(4);
+		let result = tokio_test::block_on(
+			handler.parse_multipart("multipart/form-data; boundary=XyZ", multipart_body("XyZ")),
+		);
+
+		assert!(matches!(result, Err(FileUploadError::Parse(_))));
+	}
+
+	#[test]
+	fn test_parse_multipart_stream_stops_at_size_limit() {
+		use futures_util::StreamExt;
+		use std::sync::Arc;
+		use std::sync::atomic::{AtomicUsize, Ordering};
+
+		let dir = tempfile::tempdir().unwrap();
+		let header = Bytes::from(
+			"--XyZ\r\nContent-Disposition: form-data; name=\"doc\"; filename=\"big.bin\"\r\n\r\n",
+		);
+		let chunks =
+			std::iter::once(header).chain(std::iter::repeat_n(Bytes::from(vec![b'x'; 64]), 1000));
+		let read = Arc::new(AtomicUsize::new(0));
+		let counter = read.clone();
+		// Chunks arrive one at a time, as from a socket
+		let body = stream::iter(chunks).then(move |chunk| {
+			counter.fetch_add(1, Ordering::SeqCst);
+			let mut arrived = false;
+			std::future::poll_fn(move |cx| {
+				if std::mem::replace(&mut arrived, true) {
+					std::task::Poll::Ready(Ok::<_, std::io::Error>(chunk.clone()))
+				} else {
+					cx.waker().wake_by_ref();
+					std::task::Poll::Pending
+				}
+			})
+		});
+		let handler = FileUploadHandler::new()
+			.with_max_memory_size(16)
+			.with_max_file_size(100)
+			.with_temp_dir(dir.path());
+
+		let result = tokio_test::block_on(
+			handler.parse_multipart_stream("multipart/form-data; boundary=XyZ", body),
+		);
+
+		let Err(FileUploadError::Parse(message)) = result else {
+			panic!("expected a parse error");
+		};
+		assert!(message.contains("exceeds maximum size"), "{}", message);
+		assert!(read.load(Ordering::SeqCst) < 10);
+		assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+	}
+
+	#[test]
+	fn test_parse_multipart_stream_spools_into_temp_dir() {
+		let dir = tempfile::tempdir().unwrap();
+		let handler = FileUploadHandler::new()
+			.with_max_memory_size(4)
+			.with_temp_dir(dir.path());
+
+		let data = tokio_test::block_on(
+			handler.parse_multipart("multipart/form-data; boundary=XyZ", multipart_body("XyZ")),
+		)
+		.unwrap();
+
+		let doc = data.file("doc").unwrap();
+		assert!(doc.temporary_path().unwrap().starts_with(dir.path()));
+		assert_eq!(doc.read().unwrap(), Bytes::from("hello world"));
+		assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+		drop(data);
+		assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+	}
+
+	#[test]
+	fn test_parse_multipart_rejects_oversized_field() {
+		let handler = FileUploadHandler::new().with_max_field_size(3);
+		let result = tokio_test::block_on(
+			handler.parse_multipart("multipart/form-data; boundary=XyZ", multipart_body("XyZ")),
+		);
+
+		assert!(matches!(result, Err(FileUploadError::Parse(_))));
+	}
+
+	#[test]
+	fn test_spooled_file_is_removed_on_drop() {
+		let dir = tempfile::tempdir().unwrap();
+		let parsed = parser::UploadedFile::new("doc".to_string(), Bytes::from("0123456789"))
+			.with_filename("data.bin".to_string());
+
+		let file = FileUploadHandler::new()
+			.with_max_memory_size(4)
+			.with_temp_dir(dir.path())
+			.handle(parsed)
+			.unwrap();
+		let path = file.temporary_path().unwrap().to_path_buf();
+
+		assert!(path.starts_with(dir.path()));
+		assert_eq!(file.read().unwrap(), Bytes::from("0123456789"));
+		drop(file);
+		assert!(!path.exists());
+	}
+
+	#[test]
+	fn test_validator_rejects_oversized_and_mismatched_mime() {
+		let file =
+			UploadedFile::in_memory("doc", "notes.txt", Bytes::from_static(b"MZ\x90\x00hello"))
+				.with_content_type("text/plain");
+
+		assert!(matches!(
+			FileValidator::new().with_max_size(2).validate(&file),
+			Err(FileUploadError::Invalid(
+				ValidationError::FileSizeTooLarge { .. }
+			))
+		));
+		assert!(
+			FileValidator::new()
+				.with_mime_types(vec!["text/plain".to_string()])
+				.validate(&file)
+				.is_err()
+		);
+	}
+
+	#[rstest::rstest]
+	#[case("photo.png", png(2, 2), Some("image/png"))]
+	#[case("photo.png", Bytes::from("<svg onload=alert(1)>"), None)]
+	#[case("photo.jpg", png(2, 2), Some("image/png"))]
+	#[case("notes.txt", Bytes::from("hello"), Some("text/plain"))]
+	#[case(
+		"report.docx",
+		Bytes::from_static(b"PK\x03\x04rest"),
+		Some("application/vnd.openxmlformats-officedocument.wordprocessingml.document")
+	)]
+	#[case(
+		"archive.zip",
+		Bytes::from_static(b"PK\x03\x04rest"),
+		Some("application/zip")
+	)]
+	fn test_sniffed_mime_type(
+		#[case] filename: &str,
+		#[case] content: Bytes,
+		#[case] expected: Option<&str>,
+	) {
+		let file =
+			UploadedFile::in_memory("file", filename, content).with_content_type("image/png");
+
+		assert_eq!(file.sniffed_mime_type().unwrap().as_deref(), expected);
+	}
+
+	#[test]
+	fn test_validator_sniffs_spooled_files() {
+		let parsed = parser::UploadedFile::new("photo".to_string(), Bytes::from("not an image"))
+			.with_filename("photo.png".to_string())
+			.with_content_type("image/png".to_string());
+		let file = FileUploadHandler::new()
+			.with_max_memory_size(4)
+			.handle(parsed)
+			.unwrap();
+
+		let result = FileValidator::new()
+			.with_mime_types(vec!["image/png".to_string()])
+			.validate(&file);
+
+		assert!(file.is_spooled());
+		assert!(matches!(result, Err(FileUploadError::Invalid(_))));
+	}
+
+	#[test]
+	fn test_validator_checks_image_dimensions() {
+		let image = UploadedFile::in_memory("photo", "photo.png", png(40, 20));
+		let validator = FileValidator::new()
+			.with_image_dimensions(ImageDimensionValidator::new().with_min_width(50));
+
+		assert_eq!(image.image_dimensions(), Some((40, 20)));
+		assert!(matches!(
+			validator.validate(&image),
+			Err(FileUploadError::Invalid(
+				ValidationError::ImageWidthTooSmall { .. }
+			))
+		));
+
+		let not_image = UploadedFile::in_memory("photo", "photo.png", Bytes::from("nope"));
+		assert!(matches!(
+			validator.validate(&not_image),
+			Err(FileUploadError::InvalidImage(_))
+		));
+	}
+
+	#[test]
+	fn test_save_uploaded_file_avoids_overwriting() {
+		let storage = InMemoryStorage::new("media", "/media/");
+		let file = UploadedFile::in_memory("doc", "../My Notes.txt", Bytes::from("hello"));
+
+		let first = tokio_test::block_on(save_uploaded_file(&storage, "docs/", &file)).unwrap();
+		let second = tokio_test::block_on(save_uploaded_file(&storage, "docs/", &file)).unwrap();
+
+		assert_eq!(first.path, "docs/My_Notes.txt");
+		assert_eq!(second.path, "docs/My_Notes_1.txt");
+		assert_eq!(first.content_type, Some("text/plain".to_string()));
+	}
+}
@@ -2,7 +2,9 @@ use crate::bound_field::BoundField;
 use crate::field::{FieldError, FormField};
 use crate::wasm_compat::ValidationRule;
 use std::collections::HashMap;
+use std::future::Future;
 use std::ops::Index;
+use std::pin::Pin;
 
 #[derive(Debug, thiserror::Error)]
 pub enum FormError {
@@ -10,14 +12,51 @@ pub enum FormError {
 	Field { field: String, error: FieldError },
 	#[error("Validation error: {0}")]
 	Validation(String),
+	/// Several errors at once, e.g. from a clean function comparing fields
+	#[error("{} validation errors", .0.len())]
+	Multiple(Vec<FormError>),
+}
+
+impl FormError {
+	/// Create an error attached to a specific field
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_forms::FormError;
+	///
+	/// let error = FormError::field("end", "End must be after start");
+	/// assert!(matches!(error, FormError::Field { ref field, .. } if field == "end"));
+	/// ```
+	pub fn field(field: impl Into<String>, message: impl Into<String>) -> Self {
+		FormError::Field {
+			field: field.into(),
+			error: FieldError::Validation(message.into()),
+		}
+	}
+	/// Create an error that is not tied to any field
+	pub fn non_field(message: impl Into<String>) -> Self {
+		FormError::Validation(message.into())
+	}
 }
 
 pub type FormResult<T> = Result<T, FormError>;
 
+type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
 type CleanFunction =
 	Box<dyn Fn(&HashMap<String, serde_json::Value>) -> FormResult<()> + Send + Sync>;
 type FieldCleanFunction =
 	Box<dyn Fn(&serde_json::Value) -> FormResult<serde_json::Value> + Send + Sync>;
+type AsyncCleanFunction =
+	Box<dyn Fn(HashMap<String, serde_json::Value>) -> BoxFuture<FormResult<()>> + Send + Sync>;
+type AsyncFieldValidator =
+	Box<dyn Fn(serde_json::Value) -> BoxFuture<FormResult<()>> + Send + Sync>;
+
+/// Non-field error recorded when [`Form::is_valid`] is called on a form with
+/// async validators.
+pub const ASYNC_VALIDATION_REQUIRED: &str =
+	"This form has async validators and must be validated with is_valid_async";
 
 /// Special key for form-level (non-field-specific) errors.
 ///
@@ -34,6 +73,9 @@ pub struct Form {
 	is_bound: bool,
 	clean_functions: Vec<CleanFunction>,
 	field_clean_functions: HashMap<String, FieldCleanFunction>,
+	/// Validators run by `is_valid_async`, after the synchronous checks
+	async_clean_functions: Vec<AsyncCleanFunction>,
+	async_field_validators: Vec<(String, AsyncFieldValidator)>,
 	prefix: String,
 	/// Client-side validation rules (Phase 2-A)
 	/// These rules are transmitted to the client for UX enhancement.
@@ -62,6 +104,8 @@ impl Form {
 			is_bound: false,
 			clean_functions: vec![],
 			field_clean_functions: HashMap::new(),
+			async_clean_functions: vec![],
+			async_field_validators: vec![],
 			prefix: String::new(),
 			validation_rules: vec![],
 		}
@@ -90,6 +134,8 @@ impl Form {
 			is_bound: false,
 			clean_functions: vec![],
 			field_clean_functions: HashMap::new(),
+			async_clean_functions: vec![],
+			async_field_validators: vec![],
 			prefix: String::new(),
 			validation_rules: vec![],
 		}
@@ -114,6 +160,8 @@ impl Form {
 			is_bound: false,
 			clean_functions: vec![],
 			field_clean_functions: HashMap::new(),
+			async_clean_functions: vec![],
+			async_field_validators: vec![],
 			prefix,
 			validation_rules: vec![],
 		}
@@ -173,7 +221,24 @@ impl Form {
 	/// assert!(form.errors().is_empty());
 	/// assert_eq!(form.cleaned_data().get("username"), Some(&json!("john")));
 	/// ```
+	///
+	/// Async validators cannot run here. If any are registered, the form is
+	/// reported invalid with [`ASYNC_VALIDATION_REQUIRED`] as a non-field
+	/// error; use [`Form::is_valid_async`] instead.
 	pub fn is_valid(&mut self) -> bool {
+		let valid = self.validate();
+		if self.is_bound && self.has_async_validators() {
+			self.add_error(None, ASYNC_VALIDATION_REQUIRED);
+			return false;
+		}
+		valid
+	}
+	/// Whether async validators are registered, requiring [`Form::is_valid_async`]
+	pub fn has_async_validators(&self) -> bool {
+		!self.async_field_validators.is_empty() || !self.async_clean_functions.is_empty()
+	}
+	/// Run the synchronous validation
+	fn validate(&mut self) -> bool {
 		if !self.is_bound {
 			return false;
 		}
@@ -212,27 +277,106 @@ impl Form {
 		}
 
 		// Run custom clean functions
+		let mut clean_errors = Vec::new();
 		for clean_fn in &self.clean_functions {
 			if let Err(e) = clean_fn(&self.data) {
-				match e {
-					FormError::Field { field, error } => {
-						self.errors
-							.entry(field)
-							.or_default()
-							.push(error.to_string());
-					}
-					FormError::Validation(msg) => {
-						self.errors
-							.entry(ALL_FIELDS_KEY.to_string())
-							.or_default()
-							.push(msg);
-					}
-				}
+				clean_errors.push(e);
+			}
+		}
+		for e in clean_errors {
+			self.record_error(None, e);
+		}
+
+		self.errors.is_empty()
+	}
+	/// Validate the form, then run the async validators
+	///
+	/// Async field validators only run for fields that passed synchronous
+	/// validation, so that e.g. a uniqueness query never sees malformed input.
+	/// Async clean functions receive a snapshot of the cleaned data.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_forms::{CharField, Form, FormError};
+	/// use std::collections::HashMap;
+	/// use serde_json::json;
+	///
+	/// let mut form = Form::new();
+	/// form.add_field(Box::new(CharField::new("username".to_string())));
+	/// form.add_async_field_validator("username", |value| async move {
+	///     // e.g. `User::objects().filter(...).exists().await`
+	///     if value == json!("admin") {
+	///         Err(FormError::Validation("This username is taken".to_string()))
+	///     } else {
+	///         Ok(())
+	///     }
+	/// });
+	///
+	/// form.bind(HashMap::from([("username".to_string(), json!("admin"))]));
+	/// assert!(!tokio_test::block_on(form.is_valid_async()));
+	/// assert_eq!(form.errors()["username"], vec!["This username is taken"]);
+	/// ```
+	pub async fn is_valid_async(&mut self) -> bool {
+		// Unbound forms have nothing to check; invalid fields are skipped below
+		if !self.validate() && !self.is_bound {
+			return false;
+		}
+
+		let mut pending = Vec::new();
+		for (field_name, validator) in &self.async_field_validators {
+			if self.errors.contains_key(field_name) {
+				continue;
+			}
+			if let Some(value) = self.data.get(field_name) {
+				pending.push((field_name.clone(), validator(value.clone())));
+			}
+		}
+		for (field_name, future) in pending {
+			if let Err(e) = future.await {
+				self.record_error(Some(&field_name), e);
+			}
+		}
+
+		let mut pending = Vec::new();
+		for clean_fn in &self.async_clean_functions {
+			pending.push(clean_fn(self.data.clone()));
+		}
+		for future in pending {
+			if let Err(e) = future.await {
+				self.record_error(None, e);
 			}
 		}
 
 		self.errors.is_empty()
 	}
+	/// Attach an error to a field, or to the form when `field` is `None`
+	///
+	/// Useful for errors discovered after validation, such as a failed save.
+	pub fn add_error(&mut self, field: Option<&str>, message: impl Into<String>) {
+		let key = field.unwrap_or(ALL_FIELDS_KEY).to_string();
+		self.errors.entry(key).or_default().push(message.into());
+	}
+	/// Record an error returned by a validator
+	///
+	/// Field errors go to their field; other errors go to `default_field`, or
+	/// to the non-field errors if there is none.
+	fn record_error(&mut self, default_field: Option<&str>, error: FormError) {
+		match error {
+			FormError::Field { field, error } => {
+				self.errors
+					.entry(field)
+					.or_default()
+					.push(error.to_string());
+			}
+			FormError::Validation(msg) => self.add_error(default_field, msg),
+			FormError::Multiple(errors) => {
+				for error in errors {
+					self.record_error(default_field, error);
+				}
+			}
+		}
+	}
 	pub fn cleaned_data(&self) -> &HashMap<String, serde_json::Value> {
 		&self.data
 	}
@@ -337,6 +481,47 @@ impl Form {
 	{
 		self.clean_functions.push(Box::new(f));
 	}
+	/// Add an async clean function, run by [`Form::is_valid_async`]
+	///
+	/// Return [`FormError::Multiple`] to report several errors, each attached
+	/// to a field with [`FormError::field`] or to the form with
+	/// [`FormError::non_field`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_forms::{Form, FormError};
+	///
+	/// let mut form = Form::new();
+	/// form.add_async_clean_function(|data| async move {
+	///     if data.get("coupon").is_some() && data.get("email").is_none() {
+	///         return Err(FormError::field("email", "Required when using a coupon"));
+	///     }
+	///     Ok(())
+	/// });
+	/// ```
+	pub fn add_async_clean_function<F, Fut>(&mut self, f: F)
+	where
+		F: Fn(HashMap<String, serde_json::Value>) -> Fut + Send + Sync + 'static,
+		Fut: Future<Output = FormResult<()>> + Send + 'static,
+	{
+		self.async_clean_functions
+			.push(Box::new(move |data| Box::pin(f(data))));
+	}
+	/// Add an async validator for a field, run by [`Form::is_valid_async`]
+	///
+	/// The validator receives the field's cleaned value. Errors other than
+	/// [`FormError::Field`] are attached to this field.
+	pub fn add_async_field_validator<F, Fut>(&mut self, field_name: &str, f: F)
+	where
+		F: Fn(serde_json::Value) -> Fut + Send + Sync + 'static,
+		Fut: Future<Output = FormResult<()>> + Send + 'static,
+	{
+		self.async_field_validators.push((
+			field_name.to_string(),
+			Box::new(move |value| Box::pin(f(value))),
+		));
+	}
 	/// Add a custom clean function for a specific field
 	///
 	/// # Examples
@@ -602,6 +787,33 @@ impl Form {
 			format!("{}-{}", self.prefix, field_name)
 		}
 	}
+	/// Render the form as HTML with the built-in markup
+	///
+	/// Use [`FormRenderer::render_form`](crate::renderer::FormRenderer::render_form)
+	/// to apply project templates. The `<form>` element, CSRF token and submit
+	/// button are left to the surrounding template.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_forms::{CharField, Form};
+	///
+	/// let mut form = Form::with_prefix("user".to_string());
+	/// form.add_field(Box::new(CharField::new("name".to_string())));
+	///
+	/// let html = form.render();
+	/// assert!(html.contains("<label for=\"id_user-name\">Name:</label>"));
+	/// assert!(html.contains("name=\"user-name\""));
+	/// ```
+	pub fn render(&self) -> String {
+		let mut html = crate::renderer::non_field_errors_html(self);
+		for field in self.fields() {
+			if let Some(bound) = self.get_bound_field(field.name()) {
+				html.push_str(&bound.render());
+			}
+		}
+		html
+	}
 	pub fn get_bound_field<'a>(&'a self, name: &str) -> Option<BoundField<'a>> {
 		let field = self.get_field(name)?;
 		let data = self.data.get(name);
@@ -636,6 +848,7 @@ impl Index<&str> for Form {
 
 #[cfg(test)]
 mod tests {
+	use serde_json::json;
 	use super::*;
 	use crate::fields::CharField;
 
@@ -1007,6 +1220,125 @@ mod tests {
 		assert_eq!(form.add_prefix_to_field_name("email"), "user-email");
 	}
 
+	#[test]
+	fn test_form_clean_function_multiple_errors() {
+		let mut form = Form::new();
+		form.add_field(Box::new(CharField::new("start".to_string())));
+		form.add_field(Box::new(CharField::new("end".to_string())));
+		form.add_clean_function(|data| {
+			if data.get("start") == data.get("end") {
+				return Err(FormError::Multiple(vec![
+					FormError::field("end", "End must differ from start"),
+					FormError::non_field("Invalid range"),
+				]));
+			}
+			Ok(())
+		});
+
+		let mut data = HashMap::new();
+		data.insert("start".to_string(), json!("a"));
+		data.insert("end".to_string(), json!("a"));
+		form.bind(data);
+
+		assert!(!form.is_valid());
+		assert_eq!(form.errors()["end"], vec!["End must differ from start"]);
+		assert_eq!(form.errors()[ALL_FIELDS_KEY], vec!["Invalid range"]);
+		assert!(!form.errors().contains_key("start"));
+	}
+
+	#[test]
+	fn test_form_async_field_validator_skips_invalid_fields() {
+		use std::sync::Arc;
+		use std::sync::atomic::{AtomicUsize, Ordering};
+
+		let calls = Arc::new(AtomicUsize::new(0));
+		let mut form = Form::new();
+		let mut field = CharField::new("username".to_string());
+		field.max_length = Some(5);
+		form.add_field(Box::new(field));
+		let counter = calls.clone();
+		form.add_async_field_validator("username", move |value| {
+			counter.fetch_add(1, Ordering::SeqCst);
+			async move {
+				if value == json!("taken") {
+					Err(FormError::non_field("Already exists"))
+				} else {
+					Ok(())
+				}
+			}
+		});
+
+		let mut data = HashMap::new();
+		data.insert("username".to_string(), json!("too_long_name"));
+		form.bind(data);
+		assert!(!tokio_test::block_on(form.is_valid_async()));
+		assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+		let mut data = HashMap::new();
+		data.insert("username".to_string(), json!("taken"));
+		form.bind(data);
+		assert!(!tokio_test::block_on(form.is_valid_async()));
+		assert_eq!(form.errors()["username"], vec!["Already exists"]);
+
+		let mut data = HashMap::new();
+		data.insert("username".to_string(), json!("free"));
+		form.bind(data);
+		assert!(tokio_test::block_on(form.is_valid_async()));
+	}
+
+	#[test]
+	fn test_form_async_clean_function() {
+		let mut form = Form::new();
+		form.add_field(Box::new(CharField::new("password".to_string())));
+		form.add_field(Box::new(CharField::new("confirm".to_string())));
+		form.add_async_clean_function(|data| async move {
+			if data.get("password") != data.get("confirm") {
+				return Err(FormError::field("confirm", "Passwords do not match"));
+			}
+			Ok(())
+		});
+
+		let mut data = HashMap::new();
+		data.insert("password".to_string(), json!("secret"));
+		data.insert("confirm".to_string(), json!("other"));
+		form.bind(data);
+
+		assert!(!tokio_test::block_on(form.is_valid_async()));
+		assert_eq!(form.errors()["confirm"], vec!["Passwords do not match"]);
+	}
+
+	#[test]
+	fn test_form_sync_validation_rejects_async_validators() {
+		let mut form = Form::new();
+		form.add_field(Box::new(CharField::new("username".to_string())));
+		form.add_async_field_validator("username", |_| async {
+			Err(FormError::non_field("Already exists"))
+		});
+
+		let mut data = HashMap::new();
+		data.insert("username".to_string(), json!("taken"));
+		form.bind(data);
+
+		assert!(!form.is_valid());
+		assert_eq!(
+			form.errors()[ALL_FIELDS_KEY],
+			vec![ASYNC_VALIDATION_REQUIRED]
+		);
+		assert!(!tokio_test::block_on(form.is_valid_async()));
+		assert_eq!(form.errors()["username"], vec!["Already exists"]);
+		assert!(!form.errors().contains_key(ALL_FIELDS_KEY));
+	}
+
+	#[test]
+	fn test_form_add_error() {
+		let mut form = Form::new();
+		form.add_error(Some("email"), "Could not send mail");
+		form.add_error(None, "Try again later");
+
+		assert_eq!(form.errors()["email"], vec!["Could not send mail"]);
+		assert_eq!(form.errors()[ALL_FIELDS_KEY], vec!["Try again later"]);
+	}
+
 	#[test]
 	fn test_form_field_clean_function() {
 		let mut form = Form::new();
@@ -1,5 +1,157 @@
-use crate::form::Form;
+use crate::field::Widget;
+use crate::fields::{BooleanField, IntegerField};
+use crate::form::{Form, FormError};
+use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Management form key holding the number of submitted forms
+pub const TOTAL_FORMS: &str = "TOTAL_FORMS";
+/// Management form key holding the number of forms bound to existing objects
+pub const INITIAL_FORMS: &str = "INITIAL_FORMS";
+/// Management form key holding the minimum number of forms
+pub const MIN_NUM_FORMS: &str = "MIN_NUM_FORMS";
+/// Management form key holding the maximum number of forms
+pub const MAX_NUM_FORMS: &str = "MAX_NUM_FORMS";
+/// Name of the per-form field that marks a form for deletion
+pub const DELETION_FIELD_NAME: &str = "DELETE";
+/// Name of the per-form field that holds a form's position
+pub const ORDERING_FIELD_NAME: &str = "ORDER";
+/// Placeholder used in place of the form index by [`FormSet::empty_form`]
+pub const PREFIX_PLACEHOLDER: &str = "__prefix__";
+
+type FormFactory = Arc<dyn Fn() -> Form + Send + Sync>;
+
+/// Management form data tracking the forms submitted with a formset
+///
+/// Rendered as hidden inputs alongside the formset so that forms added or
+/// removed on the client are picked up when the formset is bound.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManagementForm {
+	pub total_forms: usize,
+	pub initial_forms: usize,
+	pub min_num_forms: usize,
+	pub max_num_forms: Option<usize>,
+}
+
+impl ManagementForm {
+	/// Parse the management form from submitted data
+	///
+	/// `TOTAL_FORMS` and `INITIAL_FORMS` are required, since a formset cannot
+	/// be bound reliably without them.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_forms::formset::ManagementForm;
+	/// use std::collections::HashMap;
+	/// use serde_json::json;
+	///
+	/// let mut data = HashMap::new();
+	/// data.insert("item-TOTAL_FORMS".to_string(), json!("3"));
+	/// data.insert("item-INITIAL_FORMS".to_string(), json!(1));
+	///
+	/// let management = ManagementForm::from_data("item", &data).unwrap();
+	/// assert_eq!(management.total_forms, 3);
+	/// assert_eq!(management.initial_forms, 1);
+	/// assert!(ManagementForm::from_data("other", &data).is_err());
+	/// ```
+	pub fn from_data(prefix: &str, data: &HashMap<String, Value>) -> Result<Self, FormError> {
+		let read = |key: &str| -> Result<Option<usize>, FormError> {
+			let name = format!("{}-{}", prefix, key);
+			match data.get(&name) {
+				None | Some(Value::Null) => Ok(None),
+				Some(value) => parse_count(value).map(Some).ok_or_else(|| {
+					FormError::Validation(format!("ManagementForm field {} is invalid", name))
+				}),
+			}
+		};
+		let required = |key: &str| -> Result<usize, FormError> {
+			read(key)?.ok_or_else(|| {
+				FormError::Validation(format!(
+					"ManagementForm data is missing or has been tampered with: {}-{}",
+					prefix, key
+				))
+			})
+		};
+
+		Ok(Self {
+			total_forms: required(TOTAL_FORMS)?,
+			initial_forms: required(INITIAL_FORMS)?,
+			min_num_forms: read(MIN_NUM_FORMS)?.unwrap_or(0),
+			max_num_forms: read(MAX_NUM_FORMS)?,
+		})
+	}
+	/// Convert the management form into prefixed key/value pairs
+	pub fn to_data(&self, prefix: &str) -> HashMap<String, String> {
+		let mut data = HashMap::new();
+		data.insert(
+			format!("{}-{}", prefix, TOTAL_FORMS),
+			self.total_forms.to_string(),
+		);
+		data.insert(
+			format!("{}-{}", prefix, INITIAL_FORMS),
+			self.initial_forms.to_string(),
+		);
+		data.insert(
+			format!("{}-{}", prefix, MIN_NUM_FORMS),
+			self.min_num_forms.to_string(),
+		);
+		if let Some(max) = self.max_num_forms {
+			data.insert(format!("{}-{}", prefix, MAX_NUM_FORMS), max.to_string());
+		}
+		data
+	}
+	/// Render the management form as hidden inputs
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_forms::formset::ManagementForm;
+	///
+	/// let management = ManagementForm {
+	///     total_forms: 2,
+	///     initial_forms: 0,
+	///     min_num_forms: 0,
+	///     max_num_forms: None,
+	/// };
+	/// let html = management.render_html("item");
+	/// assert!(html.contains("name=\"item-TOTAL_FORMS\" value=\"2\""));
+	/// assert!(!html.contains("MAX_NUM_FORMS"));
+	/// ```
+	pub fn render_html(&self, prefix: &str) -> String {
+		let mut keys = vec![TOTAL_FORMS, INITIAL_FORMS, MIN_NUM_FORMS];
+		if self.max_num_forms.is_some() {
+			keys.push(MAX_NUM_FORMS);
+		}
+		let data = self.to_data(prefix);
+		keys.into_iter()
+			.map(|key| {
+				let name = format!("{}-{}", prefix, key);
+				Widget::HiddenInput.render_html(&name, data.get(&name).map(String::as_str), None)
+			})
+			.collect()
+	}
+}
+
+/// Parse a count submitted as either a JSON number or a string
+fn parse_count(value: &Value) -> Option<usize> {
+	match value {
+		Value::Number(n) => n.as_u64().and_then(|n| usize::try_from(n).ok()),
+		Value::String(s) => s.trim().parse().ok(),
+		_ => None,
+	}
+}
+
+/// Whether a submitted value counts as checked (e.g. "on", "true", `true`)
+fn is_checked(value: Option<&Value>) -> bool {
+	match value {
+		Some(Value::Bool(b)) => *b,
+		Some(Value::String(s)) => !(s.is_empty() || s.eq_ignore_ascii_case("false") || s == "0"),
+		Some(Value::Number(n)) => n.as_f64().is_some_and(|n| n != 0.0),
+		_ => false,
+	}
+}
 
 /// FormSet manages multiple forms
 pub struct FormSet {
@@ -10,7 +162,11 @@ pub struct FormSet {
 	extra: usize,
 	max_num: Option<usize>,
 	min_num: usize,
+	absolute_max: usize,
 	errors: Vec<String>,
+	form_factory: Option<FormFactory>,
+	/// Number of bound forms backed by existing objects, set by [`FormSet::bind`]
+	initial_form_count: Option<usize>,
 }
 
 impl FormSet {
@@ -34,7 +190,10 @@ impl FormSet {
 			extra: 1,
 			max_num: Some(1000),
 			min_num: 0,
+			absolute_max: 2000,
 			errors: vec![],
+			form_factory: None,
+			initial_form_count: None,
 		}
 	}
 
@@ -65,6 +224,69 @@ impl FormSet {
 		self.min_num = min_num;
 		self
 	}
+	/// Set the hard limit on `TOTAL_FORMS` accepted by [`FormSet::bind`]
+	///
+	/// Guards against clients submitting an arbitrarily large form count.
+	pub fn with_absolute_max(mut self, absolute_max: usize) -> Self {
+		self.absolute_max = absolute_max;
+		self
+	}
+	/// Set the function used to build each form when binding data
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_forms::{CharField, Form, FormSet};
+	///
+	/// let formset = FormSet::new("item".to_string()).with_form_factory(|| {
+	///     let mut form = Form::new();
+	///     form.add_field(Box::new(CharField::new("name".to_string())));
+	///     form
+	/// });
+	/// let empty = formset.empty_form();
+	/// assert_eq!(empty.prefix(), "item-__prefix__");
+	/// assert!(empty.get_field("name").is_some());
+	/// ```
+	pub fn with_form_factory<F>(mut self, factory: F) -> Self
+	where
+		F: Fn() -> Form + Send + Sync + 'static,
+	{
+		self.form_factory = Some(Arc::new(factory));
+		self
+	}
+	pub fn can_order(&self) -> bool {
+		self.can_order
+	}
+	pub fn absolute_max(&self) -> usize {
+		self.absolute_max
+	}
+	/// Get the prefix of the form at `index`, e.g. "form-0"
+	pub fn form_prefix(&self, index: usize) -> String {
+		format!("{}-{}", self.prefix, index)
+	}
+	/// Build a form with the given prefix, including the DELETE/ORDER fields
+	fn construct_form(&self, prefix: String) -> Form {
+		let mut form = self.form_factory.as_ref().map_or_else(Form::new, |f| f());
+		form.set_prefix(prefix);
+		if self.can_order {
+			let mut order = IntegerField::new(ORDERING_FIELD_NAME.to_string());
+			order.label = Some("Order".to_string());
+			form.add_field(Box::new(order));
+		}
+		if self.can_delete {
+			let mut delete = BooleanField::new(DELETION_FIELD_NAME.to_string());
+			delete.label = Some("Delete".to_string());
+			form.add_field(Box::new(delete));
+		}
+		form
+	}
+	/// Build an unbound form to be cloned on the client for dynamically added forms
+	///
+	/// Its prefix uses [`PREFIX_PLACEHOLDER`] in place of the form index, which
+	/// client code replaces with the next index before incrementing `TOTAL_FORMS`.
+	pub fn empty_form(&self) -> Form {
+		self.construct_form(format!("{}-{}", self.prefix, PREFIX_PLACEHOLDER))
+	}
 	/// Add a form to the formset
 	///
 	/// # Examples
@@ -90,7 +312,67 @@ impl FormSet {
 		self.forms.len()
 	}
 	pub fn total_form_count(&self) -> usize {
-		self.forms.len() + self.extra
+		match self.initial_form_count {
+			Some(_) => self.forms.len(),
+			None => self.forms.len() + self.extra,
+		}
+	}
+	/// Get the number of forms backed by existing objects
+	pub fn initial_form_count(&self) -> usize {
+		self.initial_form_count.unwrap_or(self.forms.len())
+	}
+	/// Whether the form at `index` is marked for deletion
+	pub fn is_deleted(&self, index: usize) -> bool {
+		self.can_delete
+			&& self
+				.forms
+				.get(index)
+				.is_some_and(|form| is_checked(form.cleaned_data().get(DELETION_FIELD_NAME)))
+	}
+	/// Whether the form at `index` is an extra form the user left untouched
+	///
+	/// Such forms are skipped during validation, like blank rows in an HTML table.
+	pub fn is_empty_extra(&self, index: usize) -> bool {
+		let Some(form) = self.forms.get(index) else {
+			return false;
+		};
+		if index < self.initial_form_count() {
+			return false;
+		}
+		form.cleaned_data().iter().all(|(name, value)| {
+			name == DELETION_FIELD_NAME
+				|| name == ORDERING_FIELD_NAME
+				|| value.is_null()
+				|| value.as_str().is_some_and(str::is_empty)
+				|| form.initial().get(name) == Some(value)
+		})
+	}
+	/// Get the forms marked for deletion
+	pub fn deleted_forms(&self) -> Vec<&Form> {
+		(0..self.forms.len())
+			.filter(|&i| self.is_deleted(i))
+			.map(|i| &self.forms[i])
+			.collect()
+	}
+	/// Get the forms to keep, sorted by their ORDER value when ordering is enabled
+	///
+	/// Deleted and untouched extra forms are left out. Forms without an ORDER
+	/// value come last, in submission order.
+	pub fn ordered_forms(&self) -> Vec<&Form> {
+		let mut indexed: Vec<(usize, Option<i64>)> = (0..self.forms.len())
+			.filter(|&i| !self.is_deleted(i) && !self.is_empty_extra(i))
+			.map(|i| {
+				let order = self.forms[i]
+					.cleaned_data()
+					.get(ORDERING_FIELD_NAME)
+					.and_then(|v| v.as_i64().or_else(|| v.as_str()?.trim().parse().ok()));
+				(i, order)
+			})
+			.collect();
+		if self.can_order {
+			indexed.sort_by_key(|&(i, order)| (order.is_none(), order, i));
+		}
+		indexed.into_iter().map(|(i, _)| &self.forms[i]).collect()
 	}
 	/// Validate all forms in the formset
 	///
@@ -107,16 +389,22 @@ impl FormSet {
 	pub fn is_valid(&mut self) -> bool {
 		self.errors.clear();
 
+		// Deleted forms and untouched extra forms are not validated
+		let skipped: Vec<bool> = (0..self.forms.len())
+			.map(|i| self.is_deleted(i) || self.is_empty_extra(i))
+			.collect();
+		let submitted = skipped.iter().filter(|skip| !**skip).count();
+
 		// Validate individual forms
 		let mut all_valid = true;
-		for form in &mut self.forms {
-			if !form.is_valid() {
+		for (form, skip) in self.forms.iter_mut().zip(&skipped) {
+			if !skip && !form.is_valid() {
 				all_valid = false;
 			}
 		}
 
 		// Check minimum number
-		if self.forms.len() < self.min_num {
+		if submitted < self.min_num {
 			self.errors
 				.push(format!("Please submit at least {} forms", self.min_num));
 			all_valid = false;
@@ -124,7 +412,7 @@ impl FormSet {
 
 		// Check maximum number
 		if let Some(max) = self.max_num
-			&& self.forms.len() > max
+			&& submitted > max
 		{
 			self.errors
 				.push(format!("Please submit no more than {} forms", max));
@@ -151,23 +439,106 @@ impl FormSet {
 	/// assert!(data.contains_key("form-TOTAL_FORMS"));
 	/// ```
 	pub fn management_form_data(&self) -> HashMap<String, String> {
-		let mut data = HashMap::new();
-		data.insert(
-			format!("{}-TOTAL_FORMS", self.prefix),
-			self.total_form_count().to_string(),
-		);
-		data.insert(
-			format!("{}-INITIAL_FORMS", self.prefix),
-			self.forms.len().to_string(),
-		);
-		data.insert(
-			format!("{}-MIN_NUM_FORMS", self.prefix),
-			self.min_num.to_string(),
-		);
-		if let Some(max) = self.max_num {
-			data.insert(format!("{}-MAX_NUM_FORMS", self.prefix), max.to_string());
+		self.management_form().to_data(&self.prefix)
+	}
+	/// Get the management form describing the current forms
+	pub fn management_form(&self) -> ManagementForm {
+		ManagementForm {
+			total_forms: self.total_form_count(),
+			initial_forms: self.initial_form_count(),
+			min_num_forms: self.min_num,
+			max_num_forms: self.max_num,
 		}
-		data
+	}
+	/// Render the management form as hidden inputs for inclusion in HTML
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_forms::FormSet;
+	///
+	/// let formset = FormSet::new("item".to_string()).with_extra(2);
+	/// let html = formset.management_form_html();
+	/// assert!(html.contains("name=\"item-TOTAL_FORMS\" value=\"2\""));
+	/// assert!(html.contains("name=\"item-INITIAL_FORMS\" value=\"0\""));
+	/// ```
+	pub fn management_form_html(&self) -> String {
+		self.management_form().render_html(&self.prefix)
+	}
+	/// Bind flat submitted data, as sent by an HTML form
+	///
+	/// Reads the management form to learn how many forms were submitted,
+	/// builds each one with the form factory and binds the `{prefix}-{i}-{field}`
+	/// values to it. Forms added on the client are picked up as long as
+	/// `TOTAL_FORMS` was incremented.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_forms::{CharField, Form, FormSet};
+	/// use std::collections::HashMap;
+	/// use serde_json::json;
+	///
+	/// let mut formset = FormSet::new("item".to_string())
+	///     .with_can_delete(true)
+	///     .with_form_factory(|| {
+	///         let mut form = Form::new();
+	///         form.add_field(Box::new(CharField::new("name".to_string())));
+	///         form
+	///     });
+	///
+	/// let mut data = HashMap::new();
+	/// data.insert("item-TOTAL_FORMS".to_string(), json!("2"));
+	/// data.insert("item-INITIAL_FORMS".to_string(), json!("0"));
+	/// data.insert("item-0-name".to_string(), json!("Pen"));
+	/// data.insert("item-1-name".to_string(), json!("Ink"));
+	/// data.insert("item-1-DELETE".to_string(), json!("on"));
+	///
+	/// formset.bind(data).unwrap();
+	/// assert!(formset.is_valid());
+	/// assert_eq!(formset.deleted_forms().len(), 1);
+	/// assert_eq!(formset.ordered_forms()[0].cleaned_data()["name"], json!("Pen"));
+	/// ```
+	pub fn bind(&mut self, data: HashMap<String, Value>) -> Result<(), FormError> {
+		let management = ManagementForm::from_data(&self.prefix, &data)?;
+		if management.total_forms > self.absolute_max {
+			return Err(FormError::Validation(format!(
+				"Please submit no more than {} forms",
+				self.absolute_max
+			)));
+		}
+
+		let mut per_form: Vec<HashMap<String, Value>> =
+			vec![HashMap::new(); management.total_forms];
+		let form_prefix = format!("{}-", self.prefix);
+		for (key, value) in data {
+			let Some((index, field)) = key
+				.strip_prefix(&form_prefix)
+				.and_then(|rest| rest.split_once('-'))
+			else {
+				continue;
+			};
+			if let Some(form_data) = index
+				.parse::<usize>()
+				.ok()
+				.and_then(|i| per_form.get_mut(i))
+			{
+				form_data.insert(field.to_string(), value);
+			}
+		}
+
+		self.forms = per_form
+			.into_iter()
+			.enumerate()
+			.map(|(i, form_data)| {
+				let mut form = self.construct_form(self.form_prefix(i));
+				form.bind(form_data);
+				form
+			})
+			.collect();
+		self.initial_form_count = Some(management.initial_forms.min(management.total_forms));
+		self.errors.clear();
+		Ok(())
 	}
 	/// Process bound data from HTML forms
 	///
@@ -211,6 +582,7 @@ impl Default for FormSet {
 mod tests {
 	use super::*;
 	use crate::fields::CharField;
+	use serde_json::json;
 
 	#[test]
 	fn test_formset_basic() {
@@ -277,4 +649,132 @@ mod tests {
 			Some(&"10".to_string())
 		);
 	}
+
+	fn item_formset() -> FormSet {
+		FormSet::new("item".to_string())
+			.with_can_delete(true)
+			.with_can_order(true)
+			.with_form_factory(|| {
+				let mut form = Form::new();
+				let mut name = CharField::new("name".to_string());
+				name.required = true;
+				form.add_field(Box::new(name));
+				form
+			})
+	}
+
+	fn submitted(total: usize, initial: usize) -> HashMap<String, serde_json::Value> {
+		let mut data = HashMap::new();
+		data.insert("item-TOTAL_FORMS".to_string(), json!(total.to_string()));
+		data.insert("item-INITIAL_FORMS".to_string(), json!(initial.to_string()));
+		data
+	}
+
+	#[test]
+	fn test_formset_bind_builds_prefixed_forms() {
+		let mut formset = item_formset();
+		let mut data = submitted(2, 1);
+		data.insert("item-0-name".to_string(), json!("Pen"));
+		data.insert("item-1-name".to_string(), json!("Ink"));
+		data.insert("other-0-name".to_string(), json!("ignored"));
+
+		formset.bind(data).unwrap();
+
+		assert_eq!(formset.form_count(), 2);
+		assert_eq!(formset.initial_form_count(), 1);
+		assert_eq!(formset.forms()[1].prefix(), "item-1");
+		assert!(formset.forms()[0].get_field("DELETE").is_some());
+		assert!(formset.forms()[0].get_field("ORDER").is_some());
+		assert!(formset.is_valid());
+		assert_eq!(formset.cleaned_data()[1].get("name"), Some(&json!("Ink")));
+	}
+
+	#[test]
+	fn test_formset_bind_requires_management_form() {
+		let mut formset = item_formset();
+		let mut data = HashMap::new();
+		data.insert("item-0-name".to_string(), json!("Pen"));
+
+		assert!(matches!(formset.bind(data), Err(FormError::Validation(_))));
+	}
+
+	#[test]
+	fn test_formset_bind_rejects_total_above_absolute_max() {
+		let mut formset = item_formset().with_absolute_max(5);
+
+		assert!(formset.bind(submitted(6, 0)).is_err());
+		assert!(formset.bind(submitted(5, 0)).is_ok());
+	}
+
+	#[test]
+	fn test_formset_skips_deleted_and_empty_extra_forms() {
+		let mut formset = item_formset().with_min_num(1);
+		let mut data = submitted(3, 1);
+		data.insert("item-0-name".to_string(), json!(""));
+		data.insert("item-0-DELETE".to_string(), json!("on"));
+		data.insert("item-1-name".to_string(), json!("Ink"));
+		data.insert("item-2-name".to_string(), json!(""));
+
+		formset.bind(data).unwrap();
+
+		assert!(formset.is_valid());
+		assert!(formset.is_deleted(0));
+		assert!(formset.is_empty_extra(2));
+		assert_eq!(formset.deleted_forms().len(), 1);
+		assert_eq!(formset.ordered_forms().len(), 1);
+	}
+
+	#[test]
+	fn test_formset_min_num_counts_only_submitted_forms() {
+		let mut formset = item_formset().with_min_num(1);
+		let mut data = submitted(2, 0);
+		data.insert("item-0-name".to_string(), json!(""));
+
+		formset.bind(data).unwrap();
+
+		assert!(!formset.is_valid());
+		assert_eq!(formset.errors(), ["Please submit at least 1 forms"]);
+	}
+
+	#[test]
+	fn test_formset_ordered_forms_sorts_by_order_field() {
+		let mut formset = item_formset();
+		let mut data = submitted(3, 0);
+		data.insert("item-0-name".to_string(), json!("C"));
+		data.insert("item-0-ORDER".to_string(), json!("3"));
+		data.insert("item-1-name".to_string(), json!("last"));
+		data.insert("item-2-name".to_string(), json!("A"));
+		data.insert("item-2-ORDER".to_string(), json!("1"));
+
+		formset.bind(data).unwrap();
+		assert!(formset.is_valid());
+
+		let names: Vec<_> = formset
+			.ordered_forms()
+			.iter()
+			.map(|form| form.cleaned_data()["name"].clone())
+			.collect();
+		assert_eq!(names, vec![json!("A"), json!("C"), json!("last")]);
+	}
+
+	#[test]
+	fn test_formset_management_form_round_trip() {
+		let mut formset = item_formset().with_max_num(Some(4));
+		let mut data = submitted(2, 0);
+		data.insert("item-0-name".to_string(), json!("Pen"));
+		formset.bind(data).unwrap();
+
+		let management = formset.management_form();
+		let data: HashMap<_, _> = management
+			.to_data("item")
+			.into_iter()
+			.map(|(k, v)| (k, json!(v)))
+			.collect();
+
+		assert_eq!(management.total_forms, 2);
+		assert_eq!(
+			ManagementForm::from_data("item", &data).unwrap(),
+			management
+		);
+	}
 }
@@ -8,7 +8,8 @@
 //! form system, focusing on data validation and multi-step form wizards.
 //!
 //! This crate is designed to be WASM-compatible, providing a pure form processing layer
-//! without HTML generation or platform-specific features.
+//! without platform-specific features. Server-side HTML output is available through
+//! the [`renderer`] module.
 //!
 //! ## Features
 //!
@@ -16,6 +17,8 @@
 //! - **[`ModelForm`]**: Auto-generated forms from model definitions
 //! - **[`FormSet`]**: Handle multiple forms of the same type
 //! - **[`FormWizard`]**: Multi-step form workflows
+//! - **[`FormRenderer`]**: Server-side HTML rendering with template overrides
+//! - **[`security`]**: Spam protection with honeypots, submit timers and throttling
 //! - **Field Types**: 20+ field types (CharField, IntegerField, EmailField, etc.)
 //! - **WASM Support**: Compatible with WebAssembly targets via `wasm_compat` module
 //!
@@ -121,11 +124,15 @@
 pub mod bound_field;
 pub mod field;
 pub mod fields;
+#[cfg(feature = "file-handling")]
+pub mod file_handling;
 pub mod form;
 pub mod formset;
 pub mod formsets;
 pub mod model_form;
 pub mod model_formset;
+pub mod renderer;
+pub mod security;
 pub mod wasm_compat;
 pub mod wizard;
 
@@ -146,12 +153,17 @@ pub use fields::{
 	TimeField, URLField, UUIDField,
 };
 pub use form::{Form, FormError, FormResult};
-pub use formset::FormSet;
+pub use formset::{FormSet, ManagementForm};
 pub use formsets::{
 	FormSetFactory,
 	InlineFormSet,
 	ModelFormSet as AdvancedModelFormSet, // Renamed to avoid conflict
 };
-pub use model_form::{FieldType, FormModel, ModelForm, ModelFormBuilder, ModelFormConfig};
+pub use model_form::{
+	FieldType, FormModel, ModelFieldInfo, ModelForm, ModelFormBuilder, ModelFormConfig,
+};
 pub use model_formset::{ModelFormSet, ModelFormSetBuilder, ModelFormSetConfig};
-pub use wizard::{FormWizard, WizardStep};
+pub use renderer::FormRenderer;
+pub use wizard::{
+	FormWizard, InMemoryWizardStorage, WizardSessionData, WizardState, WizardStep, WizardStorage,
+};
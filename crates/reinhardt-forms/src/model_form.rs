@@ -3,11 +3,17 @@
 //! ModelForms automatically generate forms from ORM models, handling field
 //! inference, validation, and saving.
 
-use crate::{CharField, EmailField, FloatField, Form, FormError, FormField, IntegerField, Widget};
+use crate::{
+	BooleanField, CharField, ChoiceField, DateField, DateTimeField, EmailField, FloatField, Form,
+	FormError, FormField, IntegerField, JSONField, TimeField, URLField, Widget,
+};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::marker::PhantomData;
 
+#[cfg(feature = "orm")]
+pub mod orm;
+
 /// Field type metadata for ModelForm field inference
 #[derive(Debug, Clone)]
 pub enum FieldType {
@@ -24,6 +30,84 @@ pub enum FieldType {
 	Json,
 }
 
+/// Field metadata for ModelForm generation
+///
+/// Mirrors the options of an ORM field declaration. With the `orm` feature,
+/// it can be converted from `reinhardt_db::orm::inspection::FieldInfo`.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_forms::model_form::{FieldType, ModelFieldInfo};
+/// use serde_json::json;
+///
+/// let info = ModelFieldInfo::new(FieldType::Char { max_length: Some(1) })
+///     .choices(vec![("s".to_string(), "Small".to_string())])
+///     .default_value(json!("s"));
+/// assert!(info.is_required());
+/// assert!(!info.clone().blank(true).is_required());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ModelFieldInfo {
+	/// Field type
+	pub field_type: FieldType,
+	/// Whether the column accepts NULL
+	pub null: bool,
+	/// Whether the field may be left empty in forms
+	pub blank: bool,
+	/// Whether the field appears in forms at all
+	pub editable: bool,
+	/// Whether the field is the model's primary key
+	pub primary_key: bool,
+	/// Allowed values as `(value, label)` pairs
+	pub choices: Option<Vec<(String, String)>>,
+	/// Default value, used as the form field's initial value
+	pub default: Option<Value>,
+}
+
+impl ModelFieldInfo {
+	/// Create metadata for a required, editable field
+	pub fn new(field_type: FieldType) -> Self {
+		Self {
+			field_type,
+			null: false,
+			blank: false,
+			editable: true,
+			primary_key: false,
+			choices: None,
+			default: None,
+		}
+	}
+	pub fn null(mut self, null: bool) -> Self {
+		self.null = null;
+		self
+	}
+	pub fn blank(mut self, blank: bool) -> Self {
+		self.blank = blank;
+		self
+	}
+	pub fn editable(mut self, editable: bool) -> Self {
+		self.editable = editable;
+		self
+	}
+	pub fn primary_key(mut self, primary_key: bool) -> Self {
+		self.primary_key = primary_key;
+		self
+	}
+	pub fn choices(mut self, choices: Vec<(String, String)>) -> Self {
+		self.choices = Some(choices);
+		self
+	}
+	pub fn default_value(mut self, default: Value) -> Self {
+		self.default = Some(default);
+		self
+	}
+	/// Whether the form field must be filled in (neither blank nor null)
+	pub fn is_required(&self) -> bool {
+		!self.blank && !self.null
+	}
+}
+
 /// Trait for models that can be used with ModelForm
 ///
 /// This trait is specifically for form models. For ORM models, use `reinhardt_db::orm::Model`.
@@ -50,6 +134,26 @@ pub trait FormModel: Send + Sync {
 		None
 	}
 
+	/// Get full field metadata (nullability, choices, defaults) for a field
+	///
+	/// Defaults to a required, editable field of the type returned by
+	/// [`field_type`](FormModel::field_type). Primary keys and non-editable
+	/// fields are left out of generated forms.
+	fn field_info(name: &str) -> Option<ModelFieldInfo> {
+		Self::field_type(name).map(ModelFieldInfo::new)
+	}
+
+	/// Create a blank instance for forms bound without one
+	///
+	/// [`ModelForm::save`] fills it from the cleaned data. Returning `None`
+	/// (the default) makes saving require an existing instance.
+	fn new_instance() -> Option<Self>
+	where
+		Self: Sized,
+	{
+		None
+	}
+
 	/// Get a field value by name
 	fn get_field(&self, name: &str) -> Option<Value>;
 
@@ -166,99 +270,106 @@ pub struct ModelForm<T: FormModel> {
 }
 
 impl<T: FormModel> ModelForm<T> {
-	/// Create a form field from field type metadata
+	/// Create a form field from model field metadata
 	fn create_form_field(
 		name: &str,
-		field_type: FieldType,
+		info: &ModelFieldInfo,
 		config: &ModelFormConfig,
 	) -> Box<dyn FormField> {
 		let label = config.labels.get(name).cloned();
 		let help_text = config.help_texts.get(name).cloned();
 		let widget = config.widgets.get(name).cloned();
-
-		match field_type {
-			FieldType::Char { max_length } => {
-				let mut field = CharField::new(name.to_string());
-				if let Some(label) = label {
-					field.label = Some(label);
-				}
-				if let Some(help) = help_text {
-					field.help_text = Some(help);
-				}
+		let required = info.is_required();
+		let initial = info.default.clone();
+
+		// Applies the options shared by all field types except JSONField
+		macro_rules! configure {
+			($field:expr) => {{
+				let mut field = $field;
+				field.label = label;
+				field.help_text = help_text;
+				field.required = required;
+				field.initial = initial;
+				field
+			}};
+		}
+		macro_rules! with_widget {
+			($field:expr) => {{
+				let mut field = configure!($field);
 				if let Some(w) = widget {
 					field.widget = w;
 				}
-				field.max_length = max_length;
 				Box::new(field)
+			}};
+		}
+
+		if let Some(ref choices) = info.choices {
+			return with_widget!(ChoiceField::new(name.to_string(), choices.clone()));
+		}
+
+		match info.field_type {
+			FieldType::Char { max_length } => {
+				let mut field = CharField::new(name.to_string());
+				field.max_length = max_length;
+				with_widget!(field)
 			}
 			FieldType::Text => {
 				let mut field = CharField::new(name.to_string());
-				if let Some(label) = label {
-					field.label = Some(label);
-				}
-				if let Some(help) = help_text {
-					field.help_text = Some(help);
-				}
-				if let Some(w) = widget {
-					field.widget = w;
-				} else {
-					field.widget = Widget::TextArea;
-				}
-				Box::new(field)
-			}
-			FieldType::Email => {
-				let mut field = EmailField::new(name.to_string());
-				if let Some(label) = label {
-					field.label = Some(label);
-				}
-				if let Some(help) = help_text {
-					field.help_text = Some(help);
-				}
-				if let Some(w) = widget {
-					field.widget = w;
-				}
-				Box::new(field)
+				field.widget = Widget::TextArea;
+				with_widget!(field)
 			}
-			FieldType::Integer => {
-				let mut field = IntegerField::new(name.to_string());
-				if let Some(label) = label {
-					field.label = Some(label);
-				}
-				if let Some(help) = help_text {
-					field.help_text = Some(help);
-				}
-				if let Some(w) = widget {
-					field.widget = w;
-				}
+			FieldType::Email => with_widget!(EmailField::new(name.to_string())),
+			FieldType::Url => with_widget!(URLField::new(name.to_string())),
+			FieldType::Integer => with_widget!(IntegerField::new(name.to_string())),
+			FieldType::Float => with_widget!(FloatField::new(name.to_string())),
+			FieldType::DateTime => with_widget!(DateTimeField::new(name.to_string())),
+			FieldType::Date => with_widget!(DateField::new(name.to_string())),
+			FieldType::Time => with_widget!(TimeField::new(name.to_string())),
+			FieldType::Boolean => {
+				// An unchecked checkbox submits nothing, so `false` must be accepted
+				let mut field = configure!(BooleanField::new(name.to_string()));
+				field.required = false;
 				Box::new(field)
 			}
-			FieldType::Float => {
-				let mut field = FloatField::new(name.to_string());
-				if let Some(label) = label {
-					field.label = Some(label);
-				}
+			FieldType::Json => {
+				let mut field = JSONField::new(name);
+				field.required = required;
+				field.initial = initial;
 				if let Some(help) = help_text {
-					field.help_text = Some(help);
+					field.help_text = help;
 				}
 				if let Some(w) = widget {
 					field.widget = w;
 				}
 				Box::new(field)
 			}
-			// For unsupported types, default to CharField
-			_ => {
-				let mut field = CharField::new(name.to_string());
-				if let Some(label) = label {
-					field.label = Some(label);
-				}
-				if let Some(help) = help_text {
-					field.help_text = Some(help);
-				}
-				if let Some(w) = widget {
-					field.widget = w;
-				}
-				Box::new(field)
+		}
+	}
+
+	/// Register client-side validators mirroring a field's server-side checks
+	fn add_client_validators(form: &mut Form, name: &str, info: &ModelFieldInfo) {
+		if info.choices.is_some() {
+			return;
+		}
+		match info.field_type {
+			FieldType::Char {
+				max_length: Some(max),
+			} => form.add_validator_rule(
+				name,
+				"max_length",
+				serde_json::json!({ "max": max }),
+				format!("Ensure this value has at most {} characters", max),
+			),
+			FieldType::Email => form.add_validator_rule(
+				name,
+				"email",
+				serde_json::json!({}),
+				"Enter a valid email address",
+			),
+			FieldType::Url => {
+				form.add_validator_rule(name, "url", serde_json::json!({}), "Enter a valid URL")
 			}
+			_ => {}
 		}
 	}
 
@@ -294,11 +405,18 @@ impl<T: FormModel> ModelForm<T> {
 				.collect()
 		};
 
-		// Infer field types from model metadata and add to form
+		// Infer fields from model metadata, skipping primary keys and
+		// non-editable fields
+		let fields_to_include: Vec<String> = fields_to_include
+			.into_iter()
+			.filter(|name| {
+				T::field_info(name).is_none_or(|info| info.editable && !info.primary_key)
+			})
+			.collect();
 		for field_name in &fields_to_include {
-			if let Some(field_type) = T::field_type(field_name) {
-				let form_field = Self::create_form_field(field_name, field_type, &config);
-				form.add_field(form_field);
+			if let Some(info) = T::field_info(field_name) {
+				form.add_field(Self::create_form_field(field_name, &info, &config));
+				Self::add_client_validators(&mut form, field_name, &info);
 			}
 		}
 
@@ -383,7 +501,7 @@ impl<T: FormModel> ModelForm<T> {
 	///
 	/// let config = ModelFormConfig::new();
 	/// let mut form = ModelForm::<MyModel>::empty(config);
-	// Will panic without an instance, but shows the API
+	// Returns an error without an instance, but shows the API
 	// let result = form.save();
 	/// ```
 	pub fn save(&mut self) -> Result<T, FormError> {
@@ -391,15 +509,31 @@ impl<T: FormModel> ModelForm<T> {
 			return Err(FormError::Validation("Form is not valid".to_string()));
 		}
 
-		// Get or create instance
-		let mut instance = self.instance.take().unwrap_or_else(|| {
-			// This would require Model to implement Default or have a constructor
-			panic!("Cannot create new instance without existing instance")
-		});
+		let mut instance = self.construct_instance()?;
+
+		// Save the instance
+		if let Err(e) = instance.save() {
+			return Err(FormError::Validation(format!("Failed to save: {}", e)));
+		}
+
+		Ok(instance)
+	}
+	/// Apply the cleaned data to the instance without saving it
+	///
+	/// Uses [`FormModel::new_instance`] when the form has no instance.
+	fn construct_instance(&mut self) -> Result<T, FormError> {
+		let mut instance = self
+			.instance
+			.take()
+			.or_else(T::new_instance)
+			.ok_or_else(|| {
+				FormError::Validation(
+					"Cannot create new instance without existing instance".to_string(),
+				)
+			})?;
 
 		// Set field values from form's cleaned_data
-		let cleaned_data = self.form.cleaned_data();
-		for (field_name, value) in cleaned_data.iter() {
+		for (field_name, value) in self.form.cleaned_data().iter() {
 			if let Err(e) = instance.set_field(field_name, value.clone()) {
 				return Err(FormError::Validation(format!(
 					"Failed to set field {}: {}",
@@ -408,11 +542,6 @@ impl<T: FormModel> ModelForm<T> {
 			}
 		}
 
-		// Save the instance
-		if let Err(e) = instance.save() {
-			return Err(FormError::Validation(format!("Failed to save: {}", e)));
-		}
-
 		Ok(instance)
 	}
 	pub fn form(&self) -> &Form {
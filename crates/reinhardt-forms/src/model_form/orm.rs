@@ -0,0 +1,393 @@
+//! ORM integration for ModelForm
+//!
+//! Builds forms from `reinhardt_db` models using the field metadata generated
+//! by `#[model(...)]`, and persists them through [`Model::save`].
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use reinhardt_forms::model_form::orm::OrmModel;
+//! use reinhardt_forms::ModelFormBuilder;
+//!
+//! let mut form = ModelFormBuilder::<OrmModel<Article>>::new()
+//!     .exclude(vec!["created_at".to_string()])
+//!     .build(None);
+//! form.bind(data);
+//! let article: Article = form.save_model().await?;
+//! ```
+
+use super::{FieldType, FormModel, ModelFieldInfo, ModelForm};
+use crate::FormError;
+use reinhardt_db::orm::Model;
+use reinhardt_db::orm::fields::FieldKwarg;
+use reinhardt_db::orm::inspection::FieldInfo;
+use serde_json::{Map, Value};
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+impl From<&FieldInfo> for ModelFieldInfo {
+	fn from(info: &FieldInfo) -> Self {
+		Self {
+			field_type: field_type(info),
+			null: info.nullable,
+			blank: info.blank,
+			editable: info.editable,
+			primary_key: info.primary_key,
+			choices: info.choices.clone(),
+			default: info.default.as_ref().and_then(kwarg_to_value),
+		}
+	}
+}
+
+/// Map an ORM field type path (e.g. "reinhardt.orm.models.CharField") to a form field type
+fn field_type(info: &FieldInfo) -> FieldType {
+	let max_length = info
+		.attributes
+		.get("max_length")
+		.and_then(|length| match length {
+			FieldKwarg::Int(n) => usize::try_from(*n).ok(),
+			FieldKwarg::Uint(n) => usize::try_from(*n).ok(),
+			_ => None,
+		});
+
+	match info.field_type.rsplit('.').next().unwrap_or_default() {
+		"TextField" => FieldType::Text,
+		"EmailField" => FieldType::Email,
+		"URLField" => FieldType::Url,
+		"AutoField"
+		| "BigAutoField"
+		| "IntegerField"
+		| "BigIntegerField"
+		| "SmallIntegerField"
+		| "PositiveIntegerField" => FieldType::Integer,
+		"FloatField" | "DecimalField" => FieldType::Float,
+		"BooleanField" => FieldType::Boolean,
+		"DateTimeField" => FieldType::DateTime,
+		"DateField" => FieldType::Date,
+		"TimeField" => FieldType::Time,
+		"JSONField" => FieldType::Json,
+		// CharField, SlugField, UuidField and anything unknown are edited as text
+		_ => FieldType::Char { max_length },
+	}
+}
+
+/// Convert a field default to a JSON value; callable defaults have no static value
+fn kwarg_to_value(kwarg: &FieldKwarg) -> Option<Value> {
+	match kwarg {
+		FieldKwarg::String(s) => Some(Value::String(s.clone())),
+		FieldKwarg::Int(n) => Some(Value::from(*n)),
+		FieldKwarg::Uint(n) => Some(Value::from(*n)),
+		FieldKwarg::Bool(b) => Some(Value::Bool(*b)),
+		FieldKwarg::Float(f) => serde_json::Number::from_f64(*f).map(Value::Number),
+		FieldKwarg::Choices(_) | FieldKwarg::Callable(_) => None,
+	}
+}
+
+/// ModelForm adapter for ORM models
+///
+/// Field names and metadata come from [`Model::field_metadata`], and values
+/// are read and written through the model's serde representation. Prefer
+/// [`ModelForm::save_model`] in async code; the synchronous
+/// [`FormModel::save`] blocks on [`Model::save`], which requires a
+/// multi-threaded Tokio runtime when called from inside one.
+#[derive(Debug, Clone)]
+pub struct OrmModel<M>(pub M);
+
+impl<M: Model> OrmModel<M> {
+	/// Wrap a model instance
+	pub fn new(model: M) -> Self {
+		Self(model)
+	}
+	/// Unwrap the model instance
+	pub fn into_inner(self) -> M {
+		self.0
+	}
+
+	/// Build a new model from form values, falling back to field defaults
+	fn from_values(values: &std::collections::HashMap<String, Value>) -> Result<Self, String> {
+		let mut object = Map::new();
+		for info in M::field_metadata() {
+			let value = values
+				.get(&info.name)
+				.cloned()
+				.or_else(|| info.default.as_ref().and_then(kwarg_to_value))
+				.unwrap_or(Value::Null);
+			object.insert(info.name, value);
+		}
+		serde_json::from_value(Value::Object(object))
+			.map(Self)
+			.map_err(|e| e.to_string())
+	}
+}
+
+impl<M: Model> FormModel for OrmModel<M> {
+	fn field_names() -> Vec<String> {
+		M::field_metadata()
+			.into_iter()
+			.map(|info| info.name)
+			.collect()
+	}
+
+	fn field_type(name: &str) -> Option<FieldType> {
+		Self::field_info(name).map(|info| info.field_type)
+	}
+
+	fn field_info(name: &str) -> Option<ModelFieldInfo> {
+		M::field_metadata()
+			.iter()
+			.find(|info| info.name == name)
+			.map(ModelFieldInfo::from)
+	}
+
+	fn get_field(&self, name: &str) -> Option<Value> {
+		serde_json::to_value(&self.0).ok()?.get(name).cloned()
+	}
+
+	fn set_field(&mut self, name: &str, value: Value) -> Result<(), String> {
+		let mut json = serde_json::to_value(&self.0).map_err(|e| e.to_string())?;
+		let object = json
+			.as_object_mut()
+			.ok_or_else(|| "Model does not serialize to an object".to_string())?;
+		if !object.contains_key(name) {
+			return Err(format!("Unknown field: {}", name));
+		}
+		object.insert(name.to_string(), value);
+		self.0 = serde_json::from_value(json).map_err(|e| e.to_string())?;
+		Ok(())
+	}
+
+	fn save(&mut self) -> Result<(), String> {
+		let save = self.0.save();
+		let result = match Handle::try_current() {
+			Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::CurrentThread => {
+				return Err(
+					"Cannot block on a current-thread runtime; use ModelForm::save_model"
+						.to_string(),
+				);
+			}
+			Ok(handle) => tokio::task::block_in_place(|| handle.block_on(save)),
+			Err(_) => tokio::runtime::Builder::new_current_thread()
+				.enable_all()
+				.build()
+				.map_err(|e| e.to_string())?
+				.block_on(save),
+		};
+		result.map_err(|e| e.to_string())
+	}
+}
+
+impl<M: Model> ModelForm<OrmModel<M>> {
+	/// Validate the form and persist the model through the ORM
+	///
+	/// Without an instance, a new model is built from the cleaned data and
+	/// field defaults. [`Model::save`] inserts it when its primary key is
+	/// unset and updates the existing row otherwise.
+	pub async fn save_model(&mut self) -> Result<M, FormError> {
+		if !self.form.is_valid() || !self.is_valid() {
+			return Err(FormError::Validation("Form is not valid".to_string()));
+		}
+
+		if self.instance.is_none() {
+			let instance = OrmModel::from_values(self.form.cleaned_data())
+				.map_err(|e| FormError::Validation(format!("Failed to build model: {}", e)))?;
+			self.instance = Some(instance);
+		}
+		let mut model = self.construct_instance()?.into_inner();
+
+		model
+			.save()
+			.await
+			.map_err(|e| FormError::Validation(format!("Failed to save: {}", e)))?;
+
+		Ok(model)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ModelFormBuilder;
+	use reinhardt_db::orm::FieldSelector;
+	use reinhardt_db::orm::fields::{AutoField, CharField, Field, IntegerField};
+	use serde::{Deserialize, Serialize};
+	use serde_json::json;
+	use std::collections::HashMap;
+
+	#[derive(Debug, Clone, Serialize, Deserialize)]
+	struct Shirt {
+		id: Option<i64>,
+		name: String,
+		size: String,
+		stock: Option<i64>,
+	}
+
+	#[derive(Clone)]
+	struct ShirtFields;
+
+	impl FieldSelector for ShirtFields {
+		fn with_alias(self, _alias: &str) -> Self {
+			self
+		}
+	}
+
+	impl Model for Shirt {
+		type PrimaryKey = i64;
+		type Fields = ShirtFields;
+
+		fn table_name() -> &'static str {
+			"shirts"
+		}
+		fn new_fields() -> Self::Fields {
+			ShirtFields
+		}
+		fn primary_key(&self) -> Option<Self::PrimaryKey> {
+			self.id
+		}
+		fn set_primary_key(&mut self, value: Self::PrimaryKey) {
+			self.id = Some(value);
+		}
+
+		fn field_metadata() -> Vec<FieldInfo> {
+			let mut id = AutoField::new();
+			id.set_attributes_from_name("id");
+			let mut name = CharField::new(50);
+			name.set_attributes_from_name("name");
+			let mut size = CharField::with_choices(
+				1,
+				vec![
+					("S".to_string(), "Small".to_string()),
+					("L".to_string(), "Large".to_string()),
+				],
+			);
+			size.base.default = Some(FieldKwarg::String("S".to_string()));
+			size.set_attributes_from_name("size");
+			let mut stock = IntegerField::new();
+			stock.base.null = true;
+			stock.set_attributes_from_name("stock");
+
+			vec![
+				FieldInfo::from_field(&id),
+				FieldInfo::from_field(&name),
+				FieldInfo::from_field(&size),
+				FieldInfo::from_field(&stock),
+			]
+		}
+	}
+
+	#[test]
+	fn test_form_fields_from_model_metadata() {
+		let form = ModelFormBuilder::<OrmModel<Shirt>>::new().build(None);
+		let form = form.form();
+
+		assert!(form.get_field("id").is_none());
+
+		let name = form.get_field("name").unwrap();
+		assert!(name.required());
+		assert!(form.validation_rules().iter().any(|rule| matches!(
+			rule,
+			crate::wasm_compat::ValidationRule::ValidatorRef { field_name, params, .. }
+				if field_name == "name" && params == &json!({ "max": 50 })
+		)));
+
+		let size = form.get_field("size").unwrap();
+		assert!(matches!(size.widget(), crate::Widget::Select { choices } if choices.len() == 2));
+		assert_eq!(size.initial(), Some(&json!("S")));
+
+		assert!(!form.get_field("stock").unwrap().required());
+	}
+
+	#[test]
+	fn test_orm_model_field_access() {
+		let mut shirt = OrmModel::new(Shirt {
+			id: Some(1),
+			name: "Tee".to_string(),
+			size: "S".to_string(),
+			stock: None,
+		});
+
+		shirt.set_field("size", json!("L")).unwrap();
+
+		assert_eq!(shirt.get_field("size"), Some(json!("L")));
+		assert!(shirt.set_field("color", json!("red")).is_err());
+		assert!(shirt.set_field("stock", json!("many")).is_err());
+	}
+
+	#[test]
+	fn test_orm_model_from_values_uses_defaults() {
+		let mut values = HashMap::new();
+		values.insert("name".to_string(), json!("Tee"));
+
+		let shirt = OrmModel::<Shirt>::from_values(&values)
+			.unwrap()
+			.into_inner();
+
+		assert_eq!(shirt.id, None);
+		assert_eq!(shirt.size, "S");
+	}
+
+	#[test]
+	fn test_save_model_rejects_invalid_form() {
+		let mut form = ModelFormBuilder::<OrmModel<Shirt>>::new().build(None);
+		let mut data = HashMap::new();
+		data.insert("size".to_string(), json!("XL"));
+		form.bind(data);
+
+		let result = tokio_test::block_on(form.save_model());
+
+		assert!(matches!(result, Err(FormError::Validation(_))));
+	}
+
+	#[tokio::test(flavor = "multi_thread")]
+	async fn test_save_round_trip() {
+		let dir = tempfile::tempdir().unwrap();
+		let url = format!(
+			"sqlite://{}?mode=rwc",
+			dir.path().join("forms.db").display()
+		);
+		reinhardt_db::orm::reinitialize_database(&url)
+			.await
+			.unwrap();
+		let conn = reinhardt_db::orm::get_connection().await.unwrap();
+		conn.execute(
+			"CREATE TABLE shirts (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT NOT NULL, \
+			 size TEXT NOT NULL, stock INTEGER)",
+			vec![],
+		)
+		.await
+		.unwrap();
+
+		let mut form = ModelFormBuilder::<OrmModel<Shirt>>::new().build(None);
+		let mut data = HashMap::new();
+		data.insert("name".to_string(), json!("Tee"));
+		data.insert("size".to_string(), json!("L"));
+		form.bind(data);
+		let created = form.save_model().await.unwrap();
+		assert!(created.id.is_some());
+
+		// The synchronous save updates the row created above
+		let mut shirt = OrmModel::new(created);
+		shirt.set_field("stock", json!(3)).unwrap();
+		shirt.save().unwrap();
+
+		let rows = conn
+			.query("SELECT id, name, size, stock FROM shirts", vec![])
+			.await
+			.unwrap();
+		assert_eq!(rows.len(), 1);
+		assert_eq!(rows[0].data["id"], json!(shirt.0.id.unwrap()));
+		assert_eq!(rows[0].data["name"], "Tee");
+		assert_eq!(rows[0].data["size"], "L");
+		assert_eq!(rows[0].data["stock"], 3);
+	}
+
+	#[tokio::test]
+	async fn test_sync_save_needs_multi_thread_runtime() {
+		let mut shirt = OrmModel::new(Shirt {
+			id: None,
+			name: "Tee".to_string(),
+			size: "S".to_string(),
+			stock: None,
+		});
+
+		assert!(shirt.save().unwrap_err().contains("save_model"));
+	}
+}
@@ -0,0 +1,422 @@
+//! Server-side form rendering
+//!
+//! [`FormRenderer`] turns widgets, bound fields and whole forms into HTML.
+//! Projects can replace the markup of any widget or of the field row by
+//! registering a Tera template, and choose the CSS classes used as styling
+//! hooks for rows with errors or required fields.
+//!
+//! Templates are rendered with HTML autoescaping. Variables holding
+//! pre-rendered HTML (marked with * below) must be output with the `safe`
+//! filter.
+//!
+//! | Template | Variables |
+//! |----------|-----------|
+//! | widget templates (see [`Widget::template_name`]) | `name`, `id`, `value`, `attrs`*, `default`* |
+//! | `field_row` | `name`, `id`, `css_classes`, `label`*, `widget`*, `help_text`*, `errors`* |
+//!
+//! `attrs` and `default` are the rendered attribute list and the built-in
+//! markup of the widget, respectively.
+//!
+//! With the `di` feature, the renderer registered as a singleton is injected
+//! into handlers, and the default renderer when none is registered.
+//!
+//! ## Example
+//!
+//! ```
+//! use reinhardt_forms::renderer::FormRenderer;
+//! use reinhardt_forms::Widget;
+//!
+//! let renderer = FormRenderer::new()
+//!     .with_template("text_input", "<div class=\"control\">{{ default | safe }}</div>")
+//!     .unwrap();
+//!
+//! let html = renderer.render_widget(&Widget::TextInput, "q", None, None).unwrap();
+//! assert!(html.starts_with("<div class=\"control\"><input type=\"text\""));
+//! ```
+
+use crate::bound_field::BoundField;
+use crate::field::{Widget, render_attrs};
+use crate::form::{ALL_FIELDS_KEY, Form};
+use reinhardt_core::security::xss::{escape_html, escape_html_attr};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tera::Tera;
+
+/// Template name for the markup around a single field
+pub const FIELD_ROW_TEMPLATE: &str = "field_row";
+
+/// Error raised by a template override
+#[derive(Debug, thiserror::Error)]
+pub enum RenderError {
+	#[error("Template error: {0}")]
+	Template(String),
+}
+
+impl From<tera::Error> for RenderError {
+	fn from(error: tera::Error) -> Self {
+		// Tera reports the actual problem in the error's causes
+		let mut message = error.to_string();
+		let mut source = std::error::Error::source(&error);
+		while let Some(cause) = source {
+			message.push_str(": ");
+			message.push_str(&cause.to_string());
+			source = cause.source();
+		}
+		RenderError::Template(message)
+	}
+}
+
+pub type RenderResult<T> = Result<T, RenderError>;
+
+/// Renders widgets, fields and forms to HTML
+#[derive(Debug, Clone)]
+pub struct FormRenderer {
+	templates: Arc<Tera>,
+	error_css_class: String,
+	required_css_class: Option<String>,
+	label_suffix: String,
+}
+
+impl Default for FormRenderer {
+	fn default() -> Self {
+		let mut templates = Tera::default();
+		// Template names have no extension, so escape every template
+		templates.autoescape_on(vec![""]);
+		Self {
+			templates: Arc::new(templates),
+			error_css_class: "error".to_string(),
+			required_css_class: None,
+			label_suffix: ":".to_string(),
+		}
+	}
+}
+
+impl FormRenderer {
+	pub fn new() -> Self {
+		Self::default()
+	}
+	/// Override the markup of a widget or of [`FIELD_ROW_TEMPLATE`]
+	///
+	/// # Errors
+	///
+	/// Returns [`RenderError::Template`] if the template does not parse.
+	pub fn with_template(
+		mut self,
+		name: impl Into<String>,
+		template: impl Into<String>,
+	) -> RenderResult<Self> {
+		Arc::make_mut(&mut self.templates).add_raw_template(&name.into(), &template.into())?;
+		Ok(self)
+	}
+	/// Set the class added to field rows that have errors (default: "error")
+	pub fn with_error_css_class(mut self, class: impl Into<String>) -> Self {
+		self.error_css_class = class.into();
+		self
+	}
+	/// Set the class added to rows of required fields (default: none)
+	pub fn with_required_css_class(mut self, class: impl Into<String>) -> Self {
+		self.required_css_class = Some(class.into());
+		self
+	}
+	/// Set the text appended to labels (default: ":")
+	pub fn with_label_suffix(mut self, suffix: impl Into<String>) -> Self {
+		self.label_suffix = suffix.into();
+		self
+	}
+	/// Whether a template override is registered under `name`
+	pub fn has_template(&self, name: &str) -> bool {
+		self.templates.get_template_names().any(|n| n == name)
+	}
+
+	/// Render a widget, using its template override if one is registered
+	pub fn render_widget(
+		&self,
+		widget: &Widget,
+		name: &str,
+		value: Option<&str>,
+		attrs: Option<&HashMap<String, String>>,
+	) -> RenderResult<String> {
+		let default = widget.render_default(name, value, attrs);
+		if !self.has_template(widget.template_name()) {
+			return Ok(default);
+		}
+
+		let mut attrs: BTreeMap<String, String> = attrs
+			.map(|attrs| attrs.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+			.unwrap_or_default();
+		let id = attrs
+			.entry("id".to_string())
+			.or_insert_with(|| format!("id_{}", name))
+			.clone();
+		let mut context = tera::Context::new();
+		context.insert("name", name);
+		context.insert("id", &id);
+		context.insert("value", value.unwrap_or(""));
+		context.insert("attrs", &render_attrs(&attrs));
+		context.insert("default", &default);
+		Ok(self.templates.render(widget.template_name(), &context)?)
+	}
+
+	/// CSS classes for a field's row: the error and required hooks
+	pub fn css_classes(&self, field: &BoundField<'_>) -> String {
+		let mut classes = Vec::new();
+		if field.has_errors() {
+			classes.push(self.error_css_class.as_str());
+		}
+		if field.is_required()
+			&& let Some(ref class) = self.required_css_class
+		{
+			classes.push(class.as_str());
+		}
+		classes.join(" ")
+	}
+
+	/// Render a `<label>` for a field
+	pub fn label_tag(&self, field: &BoundField<'_>) -> String {
+		format!(
+			"<label for=\"{}\">{}{}</label>",
+			escape_html_attr(&field.id_for_label()),
+			escape_html(&field.label_text()),
+			escape_html(&self.label_suffix)
+		)
+	}
+
+	/// Render a field row: label, widget, help text and errors
+	pub fn render_field(&self, field: &BoundField<'_>) -> RenderResult<String> {
+		let widget = self.render_widget(
+			field.widget(),
+			&field.html_name(),
+			field.value_string().as_deref(),
+			Some(&field.widget_attrs()),
+		)?;
+		if field.widget().is_hidden() {
+			return Ok(widget);
+		}
+		if !self.has_template(FIELD_ROW_TEMPLATE) {
+			return Ok(self.field_row(field, &widget));
+		}
+
+		let mut context = tera::Context::new();
+		context.insert("name", &field.html_name());
+		context.insert("id", &field.id_for_label());
+		context.insert("css_classes", &self.css_classes(field));
+		context.insert("label", &self.label_tag(field));
+		context.insert("widget", &widget);
+		context.insert("help_text", &field.help_text_html());
+		context.insert("errors", &field.errors_html());
+		Ok(self.templates.render(FIELD_ROW_TEMPLATE, &context)?)
+	}
+
+	/// Render a whole form: non-field errors followed by each field's row
+	pub fn render_form(&self, form: &Form) -> RenderResult<String> {
+		let mut html = non_field_errors_html(form);
+		for field in form.fields() {
+			if let Some(bound) = form.get_bound_field(field.name()) {
+				html.push_str(&self.render_field(&bound)?);
+			}
+		}
+		Ok(html)
+	}
+
+	/// Render a field row with the built-in markup, ignoring templates
+	pub(crate) fn default_field_row(&self, field: &BoundField<'_>) -> String {
+		let widget = field.widget().render_default(
+			&field.html_name(),
+			field.value_string().as_deref(),
+			Some(&field.widget_attrs()),
+		);
+		if field.widget().is_hidden() {
+			return widget;
+		}
+		self.field_row(field, &widget)
+	}
+
+	/// Built-in markup around a rendered widget
+	fn field_row(&self, field: &BoundField<'_>, widget: &str) -> String {
+		let css_classes = self.css_classes(field);
+		let class = if css_classes.is_empty() {
+			String::new()
+		} else {
+			format!(" class=\"{}\"", escape_html_attr(&css_classes))
+		};
+		format!(
+			"<div{}>{}{}{}{}</div>",
+			class,
+			self.label_tag(field),
+			widget,
+			field.help_text_html(),
+			field.errors_html()
+		)
+	}
+}
+
+#[cfg(feature = "di")]
+mod di_integration {
+	use super::FormRenderer;
+	use reinhardt_di::{DiResult, Injectable, InjectionContext};
+
+	#[async_trait::async_trait]
+	impl Injectable for FormRenderer {
+		async fn inject(ctx: &InjectionContext) -> DiResult<Self> {
+			// Renderer configured by the project, if any
+			if let Some(renderer) = ctx.get_singleton::<FormRenderer>() {
+				return Ok((*renderer).clone());
+			}
+			Ok(FormRenderer::default())
+		}
+	}
+}
+
+/// Render the non-field errors of a form, or an empty string if there are none
+pub(crate) fn non_field_errors_html(form: &Form) -> String {
+	match form.errors().get(ALL_FIELDS_KEY) {
+		Some(errors) if !errors.is_empty() => errors_list(errors, "errorlist nonfield", None),
+		_ => String::new(),
+	}
+}
+
+/// Render error messages as a `<ul>` with the given class
+pub(crate) fn errors_list(errors: &[String], class: &str, id: Option<&str>) -> String {
+	let id = id
+		.map(|id| format!(" id=\"{}\"", escape_html_attr(id)))
+		.unwrap_or_default();
+	let items: String = errors
+		.iter()
+		.map(|error| format!("<li>{}</li>", escape_html(error)))
+		.collect();
+	format!("<ul class=\"{}\"{}>{}</ul>", class, id, items)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::fields::{CharField, ChoiceField};
+	use crate::form::Form;
+	use serde_json::json;
+
+	fn signup_form() -> Form {
+		let mut form = Form::new();
+		let mut username = CharField::new("user_name".to_string());
+		username.required = true;
+		username.help_text = Some("Letters only".to_string());
+		form.add_field(Box::new(username));
+		form.add_field(Box::new(ChoiceField::new(
+			"plan".to_string(),
+			vec![("free".to_string(), "Free".to_string())],
+		)));
+		form
+	}
+
+	#[test]
+	fn test_render_field_with_errors_and_aria() {
+		let mut form = signup_form();
+		form.bind(HashMap::from([("user_name".to_string(), json!(""))]));
+		assert!(!form.is_valid());
+
+		let renderer = FormRenderer::new().with_required_css_class("required");
+		let html = renderer
+			.render_field(&form.get_bound_field("user_name").unwrap())
+			.unwrap();
+
+		assert!(html.starts_with("<div class=\"error required\">"));
+		assert!(html.contains("<label for=\"id_user_name\">User name:</label>"));
+		assert!(html.contains("aria-invalid=\"true\""));
+		assert!(html.contains("aria-describedby=\"id_user_name_helptext id_user_name_error\""));
+		assert!(html.contains("<span class=\"helptext\" id=\"id_user_name_helptext\">"));
+		assert!(html.contains("<ul class=\"errorlist\" id=\"id_user_name_error\">"));
+	}
+
+	#[test]
+	fn test_field_row_template_override() {
+		let form = signup_form();
+		let renderer = FormRenderer::new()
+			.with_template(
+				FIELD_ROW_TEMPLATE,
+				"<p data-field=\"{{ name }}\">{{ label | safe }}{{ widget | safe }}</p>",
+			)
+			.unwrap();
+
+		let html = renderer
+			.render_field(&form.get_bound_field("plan").unwrap())
+			.unwrap();
+
+		assert!(html.starts_with("<p data-field=\"plan\"><label for=\"id_plan\">"));
+		assert!(html.contains("<select name=\"plan\""));
+		assert!(html.ends_with("</select></p>"));
+	}
+
+	#[test]
+	fn test_widget_template_escapes_values() {
+		let renderer = FormRenderer::new()
+			.with_template(
+				"text_input",
+				"<custom-input name=\"{{ name }}\" value=\"{{ value }}\"{{ attrs | safe }}></custom-input>",
+			)
+			.unwrap();
+
+		let html = renderer
+			.render_widget(&Widget::TextInput, "q", Some("\"><script>"), None)
+			.unwrap();
+
+		assert_eq!(
+			html,
+			"<custom-input name=\"q\" value=\"&quot;&gt;&lt;script&gt;\" id=\"id_q\"></custom-input>"
+		);
+	}
+
+	#[test]
+	fn test_template_errors() {
+		let result = FormRenderer::new().with_template("text_input", "{{ name");
+		assert!(matches!(result, Err(RenderError::Template(_))));
+
+		let renderer = FormRenderer::new()
+			.with_template("text_input", "{{ missing }}")
+			.unwrap();
+		let result = renderer.render_widget(&Widget::TextInput, "q", None, None);
+		assert!(
+			matches!(result, Err(RenderError::Template(message)) if message.contains("missing"))
+		);
+	}
+
+	#[test]
+	fn test_render_form_includes_non_field_errors() {
+		let mut form = signup_form();
+		form.add_clean_function(|_| {
+			Err(crate::FormError::Validation(
+				"Signups are closed".to_string(),
+			))
+		});
+		form.bind(HashMap::from([("user_name".to_string(), json!("alice"))]));
+		assert!(!form.is_valid());
+
+		let html = FormRenderer::new().render_form(&form).unwrap();
+
+		assert!(
+			html.starts_with("<ul class=\"errorlist nonfield\"><li>Signups are closed</li></ul>")
+		);
+		assert!(html.contains("value=\"alice\""));
+	}
+
+	#[cfg(feature = "di")]
+	#[tokio::test]
+	async fn test_renderer_is_injected() {
+		use reinhardt_di::{Injectable, InjectionContext, SingletonScope};
+
+		let renderer = FormRenderer::new()
+			.with_template("text_input", "<x-input name=\"{{ name }}\"></x-input>")
+			.unwrap();
+		let ctx = InjectionContext::builder(Arc::new(SingletonScope::new()))
+			.singleton(renderer)
+			.build();
+
+		let injected = FormRenderer::inject(&ctx).await.unwrap();
+		let html = injected
+			.render_widget(&Widget::TextInput, "q", None, None)
+			.unwrap();
+		assert_eq!(html, "<x-input name=\"q\"></x-input>");
+
+		let ctx = InjectionContext::builder(Arc::new(SingletonScope::new())).build();
+		let injected = FormRenderer::inject(&ctx).await.unwrap();
+		assert!(!injected.has_template("text_input"));
+	}
+}
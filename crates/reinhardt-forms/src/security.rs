@@ -0,0 +1,586 @@
+//! Spam protection for public forms
+//!
+//! Contact and signup forms are common targets for bots. This module offers
+//! three cheap defences that can be combined with [`SpamProtection`]:
+//!
+//! - [`HoneypotField`]: an input hidden from humans that bots tend to fill in
+//! - [`SubmitTimer`]: a signed timestamp rejecting forms submitted too quickly
+//!   after being rendered, or too long after
+//! - Throttling by client IP through `reinhardt-throttling`
+//!   (feature: `throttling`)
+//!
+//! ## Example
+//!
+//! ```
+//! use reinhardt_forms::security::{HoneypotField, SpamProtection, SubmitTimer};
+//! use reinhardt_forms::{CharField, Form};
+//! use std::time::Duration;
+//!
+//! let protection = SpamProtection::new()
+//!     .with_honeypot(HoneypotField::new("website"))
+//!     .with_timer(SubmitTimer::new(b"secret-key").with_min_time(Duration::from_secs(3)));
+//!
+//! let mut form = Form::new();
+//! form.add_field(Box::new(CharField::new("message".to_string())));
+//! protection.protect(&mut form);
+//!
+//! // Render alongside the form fields
+//! let html = protection.render_html();
+//! assert!(html.contains("name=\"website\""));
+//! assert!(html.contains("name=\"form_timestamp\""));
+//! ```
+
+use crate::form::{Form, FormError};
+use reinhardt_core::security::csrf::{generate_token_hmac, verify_token_hmac};
+use reinhardt_core::security::xss::{escape_html, escape_html_attr};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "throttling")]
+use reinhardt_throttling::Throttle;
+
+/// Default name of the honeypot input
+pub const DEFAULT_HONEYPOT_NAME: &str = "hp_field";
+/// Default name of the hidden timestamp input
+pub const DEFAULT_TIMESTAMP_NAME: &str = "form_timestamp";
+/// Default maximum age of a [`SubmitTimer`] token
+pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SecurityError {
+	#[error("Honeypot field was filled in")]
+	Honeypot,
+	#[error("Form was submitted too quickly")]
+	TooFast,
+	#[error("Form has expired")]
+	Expired,
+	#[error("Form timestamp is missing or invalid")]
+	InvalidTimestamp,
+	#[error("Too many submissions")]
+	Throttled { wait_secs: Option<u64> },
+	#[error("Throttle error: {0}")]
+	Throttle(String),
+}
+
+pub type SecurityResult<T> = Result<T, SecurityError>;
+
+/// A trap input hidden from humans with CSS
+///
+/// Browsers' autofill and screen readers skip it; bots filling every input
+/// give themselves away.
+#[derive(Debug, Clone)]
+pub struct HoneypotField {
+	name: String,
+	label: String,
+}
+
+impl HoneypotField {
+	/// Create a honeypot input with the given name
+	///
+	/// Pick a name that looks attractive to bots, such as "website" or "url".
+	pub fn new(name: impl Into<String>) -> Self {
+		Self {
+			name: name.into(),
+			label: "Leave this field empty".to_string(),
+		}
+	}
+	/// Set the label read out if the field is ever presented to a human
+	pub fn with_label(mut self, label: impl Into<String>) -> Self {
+		self.label = label.into();
+		self
+	}
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+	/// Render the input inside a visually hidden wrapper
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_forms::security::HoneypotField;
+	///
+	/// let html = HoneypotField::new("website").render_html();
+	/// assert!(html.starts_with("<div style=\"display:none\" aria-hidden=\"true\">"));
+	/// assert!(html.contains("<input type=\"text\" name=\"website\""));
+	/// ```
+	pub fn render_html(&self) -> String {
+		let name = escape_html_attr(&self.name);
+		format!(
+			"<div style=\"display:none\" aria-hidden=\"true\"><label for=\"id_{name}\">{}</label><input type=\"text\" name=\"{name}\" id=\"id_{name}\" value=\"\" tabindex=\"-1\" autocomplete=\"off\" /></div>",
+			escape_html(&self.label),
+		)
+	}
+	/// Check submitted data; the trap must be absent or empty
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_forms::security::{HoneypotField, SecurityError};
+	/// use serde_json::json;
+	/// use std::collections::HashMap;
+	///
+	/// let honeypot = HoneypotField::new("website");
+	/// assert!(honeypot.check(&HashMap::new()).is_ok());
+	///
+	/// let data = HashMap::from([("website".to_string(), json!("http://spam.example"))]);
+	/// assert_eq!(honeypot.check(&data), Err(SecurityError::Honeypot));
+	/// ```
+	pub fn check(&self, data: &HashMap<String, Value>) -> SecurityResult<()> {
+		match data.get(&self.name) {
+			None | Some(Value::Null) => Ok(()),
+			Some(Value::String(s)) if s.is_empty() => Ok(()),
+			Some(_) => Err(SecurityError::Honeypot),
+		}
+	}
+}
+
+impl Default for HoneypotField {
+	fn default() -> Self {
+		Self::new(DEFAULT_HONEYPOT_NAME)
+	}
+}
+
+/// Signed render timestamp enforcing a minimum time to fill in a form
+///
+/// The token is `{unix_timestamp}:{hmac}`, so clients cannot forge an older
+/// render time.
+#[derive(Clone)]
+pub struct SubmitTimer {
+	secret: Vec<u8>,
+	field_name: String,
+	min_time: Duration,
+	max_age: Duration,
+}
+
+impl std::fmt::Debug for SubmitTimer {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("SubmitTimer")
+			.field("field_name", &self.field_name)
+			.field("min_time", &self.min_time)
+			.field("max_age", &self.max_age)
+			.finish_non_exhaustive()
+	}
+}
+
+impl SubmitTimer {
+	/// Create a timer signing timestamps with `secret`
+	///
+	/// Defaults to a minimum of 2 seconds and a maximum age of
+	/// [`DEFAULT_MAX_AGE`], after which a token can no longer be replayed.
+	pub fn new(secret: &[u8]) -> Self {
+		Self {
+			secret: secret.to_vec(),
+			field_name: DEFAULT_TIMESTAMP_NAME.to_string(),
+			min_time: Duration::from_secs(2),
+			max_age: DEFAULT_MAX_AGE,
+		}
+	}
+	/// Set the name of the hidden timestamp input
+	pub fn with_field_name(mut self, name: impl Into<String>) -> Self {
+		self.field_name = name.into();
+		self
+	}
+	/// Reject submissions made sooner than this after rendering
+	pub fn with_min_time(mut self, min_time: Duration) -> Self {
+		self.min_time = min_time;
+		self
+	}
+	/// Reject submissions made later than this after rendering
+	pub fn with_max_age(mut self, max_age: Duration) -> Self {
+		self.max_age = max_age;
+		self
+	}
+	pub fn field_name(&self) -> &str {
+		&self.field_name
+	}
+	/// Issue a token for a form rendered now
+	pub fn issue(&self) -> String {
+		self.issue_at(chrono::Utc::now().timestamp())
+	}
+	/// Issue a token for a form rendered at `timestamp` (Unix seconds)
+	pub fn issue_at(&self, timestamp: i64) -> String {
+		let timestamp = timestamp.to_string();
+		let signature = generate_token_hmac(&self.secret, &timestamp);
+		format!("{}:{}", timestamp, signature)
+	}
+	/// Render the hidden timestamp input for a form rendered now
+	pub fn render_html(&self) -> String {
+		format!(
+			"<input type=\"hidden\" name=\"{}\" value=\"{}\" />",
+			escape_html_attr(&self.field_name),
+			escape_html_attr(&self.issue())
+		)
+	}
+	/// Verify a token submitted now
+	pub fn verify(&self, token: &str) -> SecurityResult<()> {
+		self.verify_at(token, chrono::Utc::now().timestamp())
+	}
+	/// Verify a token submitted at `now` (Unix seconds)
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_forms::security::{SecurityError, SubmitTimer};
+	/// use std::time::Duration;
+	///
+	/// let timer = SubmitTimer::new(b"secret").with_min_time(Duration::from_secs(5));
+	/// let token = timer.issue_at(1_000);
+	///
+	/// assert_eq!(timer.verify_at(&token, 1_002), Err(SecurityError::TooFast));
+	/// assert!(timer.verify_at(&token, 1_010).is_ok());
+	/// assert_eq!(
+	///     timer.verify_at("999:forged", 1_010),
+	///     Err(SecurityError::InvalidTimestamp)
+	/// );
+	/// ```
+	pub fn verify_at(&self, token: &str, now: i64) -> SecurityResult<()> {
+		let (timestamp, signature) = token
+			.split_once(':')
+			.ok_or(SecurityError::InvalidTimestamp)?;
+		if !verify_token_hmac(signature, &self.secret, timestamp) {
+			return Err(SecurityError::InvalidTimestamp);
+		}
+		let rendered_at: i64 = timestamp
+			.parse()
+			.map_err(|_| SecurityError::InvalidTimestamp)?;
+
+		let elapsed = now.saturating_sub(rendered_at);
+		if elapsed < 0 || (elapsed as u64) < self.min_time.as_secs() {
+			return Err(SecurityError::TooFast);
+		}
+		if elapsed as u64 > self.max_age.as_secs() {
+			return Err(SecurityError::Expired);
+		}
+		Ok(())
+	}
+	/// Check submitted data for a valid token
+	pub fn check(&self, data: &HashMap<String, Value>) -> SecurityResult<()> {
+		match data.get(&self.field_name) {
+			Some(Value::String(token)) => self.verify(token),
+			_ => Err(SecurityError::InvalidTimestamp),
+		}
+	}
+}
+
+/// Combined spam protection for a public form
+///
+/// Every check reports a non-field error whose message can be configured, so
+/// that the same wording can be shown regardless of which check failed.
+#[derive(Clone)]
+pub struct SpamProtection {
+	honeypot: Option<HoneypotField>,
+	timer: Option<SubmitTimer>,
+	#[cfg(feature = "throttling")]
+	throttle: Option<Arc<dyn Throttle>>,
+	scope: String,
+	messages: HashMap<&'static str, String>,
+}
+
+impl Default for SpamProtection {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl SpamProtection {
+	/// Create protection with no checks enabled
+	pub fn new() -> Self {
+		Self {
+			honeypot: None,
+			timer: None,
+			#[cfg(feature = "throttling")]
+			throttle: None,
+			scope: "form".to_string(),
+			messages: HashMap::new(),
+		}
+	}
+	pub fn with_honeypot(mut self, honeypot: HoneypotField) -> Self {
+		self.honeypot = Some(honeypot);
+		self
+	}
+	pub fn with_timer(mut self, timer: SubmitTimer) -> Self {
+		self.timer = Some(timer);
+		self
+	}
+	/// Throttle submissions per client IP
+	///
+	/// The throttle key is `{scope}:{ip}`, see [`SpamProtection::with_scope`].
+	#[cfg(feature = "throttling")]
+	pub fn with_throttle(mut self, throttle: impl Throttle + 'static) -> Self {
+		self.throttle = Some(Arc::new(throttle));
+		self
+	}
+	/// Set the prefix of throttle keys, to count each form separately
+	/// (default: "form")
+	pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+		self.scope = scope.into();
+		self
+	}
+	/// Set the message reported for a given error
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_forms::security::{SecurityError, SpamProtection};
+	///
+	/// let protection = SpamProtection::new()
+	///     .with_message(SecurityError::TooFast, "Please take your time.");
+	/// assert_eq!(protection.message(&SecurityError::TooFast), "Please take your time.");
+	/// assert_eq!(protection.message(&SecurityError::Expired), "Form has expired");
+	/// ```
+	pub fn with_message(mut self, error: SecurityError, message: impl Into<String>) -> Self {
+		self.messages
+			.insert(Self::message_key(&error), message.into());
+		self
+	}
+	/// Message reported on the form for an error
+	pub fn message(&self, error: &SecurityError) -> String {
+		self.messages
+			.get(Self::message_key(error))
+			.cloned()
+			.unwrap_or_else(|| error.to_string())
+	}
+	fn message_key(error: &SecurityError) -> &'static str {
+		match error {
+			SecurityError::Honeypot => "honeypot",
+			SecurityError::TooFast => "too_fast",
+			SecurityError::Expired => "expired",
+			SecurityError::InvalidTimestamp => "invalid_timestamp",
+			SecurityError::Throttled { .. } => "throttled",
+			SecurityError::Throttle(_) => "throttle",
+		}
+	}
+
+	/// Render the honeypot and timestamp inputs
+	pub fn render_html(&self) -> String {
+		let mut html = String::new();
+		if let Some(ref honeypot) = self.honeypot {
+			html.push_str(&honeypot.render_html());
+		}
+		if let Some(ref timer) = self.timer {
+			html.push_str(&timer.render_html());
+		}
+		html
+	}
+	/// Run the honeypot and timer checks against submitted data
+	pub fn check(&self, data: &HashMap<String, Value>) -> SecurityResult<()> {
+		if let Some(ref honeypot) = self.honeypot {
+			honeypot.check(data)?;
+		}
+		if let Some(ref timer) = self.timer {
+			timer.check(data)?;
+		}
+		Ok(())
+	}
+	/// Count a submission from `client_ip` against the throttle
+	#[cfg(feature = "throttling")]
+	pub async fn check_rate(&self, client_ip: &str) -> SecurityResult<()> {
+		let Some(ref throttle) = self.throttle else {
+			return Ok(());
+		};
+		let key = format!("{}:{}", self.scope, client_ip);
+		let allowed = throttle
+			.allow_request(&key)
+			.await
+			.map_err(|e| SecurityError::Throttle(e.to_string()))?;
+		if allowed {
+			return Ok(());
+		}
+		let wait_secs = throttle
+			.wait_time(&key)
+			.await
+			.map_err(|e| SecurityError::Throttle(e.to_string()))?;
+		Err(SecurityError::Throttled { wait_secs })
+	}
+	/// Add the honeypot and timer checks to a form's validation
+	///
+	/// Failures are reported as non-field errors.
+	pub fn protect(&self, form: &mut Form) {
+		let protection = Arc::new(self.clone());
+		form.add_clean_function(move |data| {
+			protection
+				.check(data)
+				.map_err(|e| FormError::non_field(protection.message(&e)))
+		});
+	}
+	/// Validate a protected form submitted by `client_ip`
+	///
+	/// Runs [`Form::is_valid_async`], then counts the submission against the
+	/// throttle, reporting a non-field error when the limit is exceeded.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_forms::security::{SecurityError, SpamProtection};
+	/// use reinhardt_forms::{CharField, Form};
+	/// use reinhardt_throttling::AnonRateThrottle;
+	/// use serde_json::json;
+	/// use std::collections::HashMap;
+	///
+	/// let protection = SpamProtection::new()
+	///     .with_throttle(AnonRateThrottle::new(1, 3600))
+	///     .with_message(SecurityError::Throttled { wait_secs: None }, "Slow down");
+	///
+	/// let mut form = Form::new();
+	/// form.add_field(Box::new(CharField::new("message".to_string())));
+	/// form.bind(HashMap::from([("message".to_string(), json!("hi"))]));
+	///
+	/// assert!(tokio_test::block_on(protection.is_valid(&mut form, "203.0.113.7")));
+	/// assert!(!tokio_test::block_on(protection.is_valid(&mut form, "203.0.113.7")));
+	/// assert_eq!(form.errors()["_all"], vec!["Slow down"]);
+	/// ```
+	#[cfg(feature = "throttling")]
+	pub async fn is_valid(&self, form: &mut Form, client_ip: &str) -> bool {
+		let valid = form.is_valid_async().await;
+		if let Err(e) = self.check_rate(client_ip).await {
+			form.add_error(None, self.message(&e));
+			return false;
+		}
+		valid
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::fields::CharField;
+	use crate::form::ALL_FIELDS_KEY;
+	use serde_json::json;
+
+	fn contact_form(protection: &SpamProtection, data: HashMap<String, Value>) -> Form {
+		let mut form = Form::new();
+		form.add_field(Box::new(CharField::new("message".to_string())));
+		protection.protect(&mut form);
+		form.bind(data);
+		form
+	}
+
+	#[test]
+	fn test_honeypot_rejects_filled_trap() {
+		let protection = SpamProtection::new()
+			.with_honeypot(HoneypotField::new("website"))
+			.with_message(SecurityError::Honeypot, "Submission rejected");
+
+		let mut form = contact_form(
+			&protection,
+			HashMap::from([
+				("message".to_string(), json!("Buy now")),
+				("website".to_string(), json!("http://spam.example")),
+			]),
+		);
+		assert!(!form.is_valid());
+		assert_eq!(form.errors()[ALL_FIELDS_KEY], vec!["Submission rejected"]);
+
+		let mut form = contact_form(
+			&protection,
+			HashMap::from([
+				("message".to_string(), json!("Hello")),
+				("website".to_string(), json!("")),
+			]),
+		);
+		assert!(form.is_valid());
+	}
+
+	#[test]
+	fn test_timer_rejects_missing_and_early_tokens() {
+		let timer = SubmitTimer::new(b"secret").with_min_time(Duration::from_secs(60));
+		let protection = SpamProtection::new().with_timer(timer.clone());
+
+		let mut form = contact_form(
+			&protection,
+			HashMap::from([("message".to_string(), json!("Hi"))]),
+		);
+		assert!(!form.is_valid());
+		assert_eq!(
+			form.errors()[ALL_FIELDS_KEY],
+			vec![SecurityError::InvalidTimestamp.to_string()]
+		);
+
+		let mut form = contact_form(
+			&protection,
+			HashMap::from([
+				("message".to_string(), json!("Hi")),
+				(DEFAULT_TIMESTAMP_NAME.to_string(), json!(timer.issue())),
+			]),
+		);
+		assert!(!form.is_valid());
+		assert_eq!(
+			form.errors()[ALL_FIELDS_KEY],
+			vec![SecurityError::TooFast.to_string()]
+		);
+
+		let rendered_at = chrono::Utc::now().timestamp() - 120;
+		let mut form = contact_form(
+			&protection,
+			HashMap::from([
+				("message".to_string(), json!("Hi")),
+				(
+					DEFAULT_TIMESTAMP_NAME.to_string(),
+					json!(timer.issue_at(rendered_at)),
+				),
+			]),
+		);
+		assert!(form.is_valid());
+	}
+
+	#[test]
+	fn test_timer_default_max_age() {
+		let timer = SubmitTimer::new(b"secret");
+		let token = timer.issue_at(1_000);
+		let max_age = DEFAULT_MAX_AGE.as_secs() as i64;
+
+		assert!(timer.verify_at(&token, 1_000 + max_age).is_ok());
+		assert_eq!(
+			timer.verify_at(&token, 1_001 + max_age),
+			Err(SecurityError::Expired)
+		);
+	}
+
+	#[test]
+	fn test_timer_max_age_and_tampering() {
+		let timer = SubmitTimer::new(b"secret")
+			.with_min_time(Duration::from_secs(0))
+			.with_max_age(Duration::from_secs(3600));
+		let token = timer.issue_at(1_000);
+
+		assert!(timer.verify_at(&token, 1_000).is_ok());
+		assert_eq!(timer.verify_at(&token, 5_000), Err(SecurityError::Expired));
+		// A token issued in the future is treated as too fast
+		assert_eq!(timer.verify_at(&token, 900), Err(SecurityError::TooFast));
+
+		let signature = token.split_once(':').unwrap().1;
+		let tampered = format!("500:{}", signature);
+		assert_eq!(
+			timer.verify_at(&tampered, 1_000),
+			Err(SecurityError::InvalidTimestamp)
+		);
+		assert_eq!(
+			SubmitTimer::new(b"other").verify_at(&token, 1_000),
+			Err(SecurityError::InvalidTimestamp)
+		);
+	}
+
+	#[cfg(feature = "throttling")]
+	#[test]
+	fn test_throttle_is_keyed_by_scope_and_ip() {
+		use reinhardt_throttling::AnonRateThrottle;
+
+		let protection = SpamProtection::new()
+			.with_scope("signup")
+			.with_throttle(AnonRateThrottle::new(2, 3600));
+
+		tokio_test::block_on(async {
+			assert!(protection.check_rate("10.0.0.1").await.is_ok());
+			assert!(protection.check_rate("10.0.0.1").await.is_ok());
+			assert_eq!(
+				protection.check_rate("10.0.0.1").await,
+				Err(SecurityError::Throttled {
+					wait_secs: Some(3600)
+				})
+			);
+			assert!(protection.check_rate("10.0.0.2").await.is_ok());
+		});
+	}
+}
@@ -1,14 +1,68 @@
 use crate::form::{Form, FormError};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+#[cfg(feature = "sessions")]
+pub mod session;
+
 /// Type alias for wizard session data
-type WizardSessionData = HashMap<String, HashMap<String, serde_json::Value>>;
+pub type WizardSessionData = HashMap<String, HashMap<String, serde_json::Value>>;
 
 /// Type alias for wizard step condition function
 type WizardConditionFn = Box<dyn Fn(&WizardSessionData) -> bool + Send + Sync>;
 
+/// Wizard progress persisted between requests
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WizardState {
+	/// Name of the step the user is on
+	pub current_step: Option<String>,
+	/// Submitted data per step name
+	pub step_data: WizardSessionData,
+}
+
+/// Storage for wizard progress between requests
+///
+/// With the `sessions` feature, `reinhardt_auth::sessions::Session` implements
+/// this trait so that wizard progress lives in the user's session.
+pub trait WizardStorage {
+	/// Load the state stored under `key`, if any
+	fn load(&mut self, key: &str) -> Result<Option<WizardState>, FormError>;
+	/// Store the state under `key`
+	fn save(&mut self, key: &str, state: &WizardState) -> Result<(), FormError>;
+	/// Remove the state stored under `key`
+	fn clear(&mut self, key: &str);
+}
+
+/// In-memory wizard storage, mainly for tests and single-process use
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryWizardStorage {
+	states: HashMap<String, WizardState>,
+}
+
+impl InMemoryWizardStorage {
+	pub fn new() -> Self {
+		Self::default()
+	}
+}
+
+impl WizardStorage for InMemoryWizardStorage {
+	fn load(&mut self, key: &str) -> Result<Option<WizardState>, FormError> {
+		Ok(self.states.get(key).cloned())
+	}
+
+	fn save(&mut self, key: &str, state: &WizardState) -> Result<(), FormError> {
+		self.states.insert(key.to_string(), state.clone());
+		Ok(())
+	}
+
+	fn clear(&mut self, key: &str) {
+		self.states.remove(key);
+	}
+}
+
 /// FormWizard manages multi-step forms
 pub struct FormWizard {
+	prefix: String,
 	steps: Vec<WizardStep>,
 	current_step: usize,
 	session_data: WizardSessionData,
@@ -85,14 +139,22 @@ impl FormWizard {
 	/// assert_eq!(wizard.current_step(), 0);
 	/// assert!(wizard.steps().is_empty());
 	/// ```
-	pub fn new(_prefix: String) -> Self {
+	pub fn new(prefix: String) -> Self {
 		Self {
+			prefix,
 			steps: vec![],
 			current_step: 0,
 			session_data: HashMap::new(),
 		}
 	}
 
+	pub fn prefix(&self) -> &str {
+		&self.prefix
+	}
+	/// Key the wizard state is stored under, e.g. "wizard_registration"
+	pub fn storage_key(&self) -> String {
+		format!("wizard_{}", self.prefix)
+	}
 	pub fn steps(&self) -> &Vec<WizardStep> {
 		&self.steps
 	}
@@ -155,15 +217,18 @@ impl FormWizard {
 			return Err("Already at last step".to_string());
 		}
 
-		// Find next available step
-		for i in (self.current_step + 1)..self.steps.len() {
-			if self.steps[i].is_available(&self.session_data) {
+		match self.next_available_step() {
+			Some(i) => {
 				self.current_step = i;
-				return Ok(());
+				Ok(())
 			}
+			None => Err("No available next step".to_string()),
 		}
-
-		Err("No available next step".to_string())
+	}
+	/// Get the index of the next step whose condition currently holds
+	fn next_available_step(&self) -> Option<usize> {
+		((self.current_step + 1)..self.steps.len())
+			.find(|&i| self.steps[i].is_available(&self.session_data))
 	}
 	/// Move to the previous step
 	///
@@ -264,6 +329,35 @@ impl FormWizard {
 		self.session_data.clear();
 		self.current_step = 0;
 	}
+	/// Restore progress from storage
+	///
+	/// Returns `false` when nothing was stored. A stored step that no longer
+	/// exists or whose condition no longer holds falls back to the first step.
+	pub fn load<S: WizardStorage + ?Sized>(&mut self, storage: &mut S) -> Result<bool, FormError> {
+		let Some(state) = storage.load(&self.storage_key())? else {
+			return Ok(false);
+		};
+		self.session_data = state.step_data;
+		self.current_step = state
+			.current_step
+			.and_then(|name| self.steps.iter().position(|step| step.name == name))
+			.filter(|&i| self.steps[i].is_available(&self.session_data))
+			.unwrap_or(0);
+		Ok(true)
+	}
+	/// Save progress to storage so the next request can resume it
+	pub fn persist<S: WizardStorage + ?Sized>(&self, storage: &mut S) -> Result<(), FormError> {
+		let state = WizardState {
+			current_step: self.current_step_name().map(String::from),
+			step_data: self.session_data.clone(),
+		};
+		storage.save(&self.storage_key(), &state)
+	}
+	/// Clear progress both in the wizard and in storage
+	pub fn reset<S: WizardStorage + ?Sized>(&mut self, storage: &mut S) {
+		self.clear_data();
+		storage.clear(&self.storage_key());
+	}
 	/// Process current step and move to next if valid
 	///
 	/// # Examples
@@ -290,11 +384,14 @@ impl FormWizard {
 			if form.is_valid() {
 				self.save_step_data(data)?;
 
-				if !self.is_last_step() {
-					self.next_step().map_err(FormError::Validation)?;
-					Ok(false) // Not done yet
-				} else {
-					Ok(true) // Wizard complete
+				// Steps whose condition no longer holds are skipped; when none
+				// remain, the wizard is complete
+				match self.next_available_step() {
+					Some(i) => {
+						self.current_step = i;
+						Ok(false) // Not done yet
+					}
+					None => Ok(true), // Wizard complete
 				}
 			} else {
 				Err(FormError::Validation("Form validation failed".to_string()))
@@ -303,6 +400,64 @@ impl FormWizard {
 			Err(FormError::Validation("Invalid step".to_string()))
 		}
 	}
+	/// Validate every step and pass the cleaned data to `callback`
+	///
+	/// Stored data of each available step is validated again with the step's
+	/// form; steps skipped by their condition are left out. If a step fails,
+	/// the wizard moves back to it and returns the error without calling
+	/// `callback`. On success the wizard data is cleared.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_forms::{CharField, Form, FormWizard, WizardStep};
+	/// use std::collections::HashMap;
+	/// use serde_json::json;
+	///
+	/// let mut wizard = FormWizard::new("signup".to_string());
+	/// let mut form = Form::new();
+	/// form.add_field(Box::new(CharField::new("username".to_string())));
+	/// wizard.add_step(WizardStep::new("account".to_string(), form));
+	///
+	/// let mut data = HashMap::new();
+	/// data.insert("username".to_string(), json!("alice"));
+	/// assert!(wizard.process_step(data).unwrap());
+	///
+	/// let username = wizard
+	///     .done(|data| Ok(data["account"]["username"].clone()))
+	///     .unwrap();
+	/// assert_eq!(username, json!("alice"));
+	/// ```
+	pub fn done<F, T>(&mut self, callback: F) -> Result<T, FormError>
+	where
+		F: FnOnce(WizardSessionData) -> Result<T, FormError>,
+	{
+		let mut cleaned = HashMap::new();
+		for i in 0..self.steps.len() {
+			if !self.steps[i].is_available(&self.session_data) {
+				continue;
+			}
+			let data = self
+				.session_data
+				.get(&self.steps[i].name)
+				.cloned()
+				.unwrap_or_default();
+			let step = &mut self.steps[i];
+			step.form.bind(data);
+			if !step.form.is_valid() {
+				self.current_step = i;
+				return Err(FormError::Validation(format!(
+					"Step '{}' is not valid",
+					step.name
+				)));
+			}
+			cleaned.insert(step.name.clone(), step.form.cleaned_data().clone());
+		}
+
+		let result = callback(cleaned)?;
+		self.clear_data();
+		Ok(result)
+	}
 	pub fn progress_percentage(&self) -> f32 {
 		if self.steps.is_empty() {
 			return 0.0;
@@ -424,4 +579,92 @@ mod tests {
 		assert_eq!(wizard.current_step(), 2);
 		assert_eq!(wizard.current_step_name(), Some("step3"));
 	}
+
+	fn signup_wizard() -> FormWizard {
+		let mut wizard = FormWizard::new("signup".to_string());
+
+		let mut account = Form::new();
+		let mut plan = CharField::new("plan".to_string());
+		plan.required = true;
+		account.add_field(Box::new(plan));
+		wizard.add_step(WizardStep::new("account".to_string(), account));
+
+		let mut billing = Form::new();
+		let mut card = CharField::new("card".to_string());
+		card.required = true;
+		billing.add_field(Box::new(card));
+		wizard.add_step(
+			WizardStep::new("billing".to_string(), billing).with_condition(|data| {
+				data.get("account")
+					.and_then(|d| d.get("plan"))
+					.is_some_and(|plan| plan != "free")
+			}),
+		);
+
+		let mut profile = Form::new();
+		profile.add_field(Box::new(CharField::new("bio".to_string())));
+		wizard.add_step(WizardStep::new("profile".to_string(), profile));
+		wizard
+	}
+
+	fn step_data(key: &str, value: &str) -> HashMap<String, serde_json::Value> {
+		let mut data = HashMap::new();
+		data.insert(key.to_string(), serde_json::json!(value));
+		data
+	}
+
+	#[test]
+	fn test_wizard_skips_unavailable_steps() {
+		let mut wizard = signup_wizard();
+
+		assert!(!wizard.process_step(step_data("plan", "free")).unwrap());
+		assert_eq!(wizard.current_step_name(), Some("profile"));
+		assert!(wizard.process_step(step_data("bio", "hi")).unwrap());
+
+		let data = wizard.done(Ok).unwrap();
+		assert!(!data.contains_key("billing"));
+		assert_eq!(data["profile"]["bio"], serde_json::json!("hi"));
+		assert!(wizard.get_all_data().is_empty());
+	}
+
+	#[test]
+	fn test_wizard_state_persists_between_requests() {
+		let mut storage = InMemoryWizardStorage::new();
+
+		let mut wizard = signup_wizard();
+		assert!(!wizard.load(&mut storage).unwrap());
+		wizard.process_step(step_data("plan", "pro")).unwrap();
+		wizard.persist(&mut storage).unwrap();
+
+		// A new request builds a fresh wizard and resumes from storage
+		let mut wizard = signup_wizard();
+		assert!(wizard.load(&mut storage).unwrap());
+		assert_eq!(wizard.current_step_name(), Some("billing"));
+		assert_eq!(
+			wizard.get_step_data("account").unwrap()["plan"],
+			serde_json::json!("pro")
+		);
+
+		wizard.reset(&mut storage);
+		assert!(storage.load("wizard_signup").unwrap().is_none());
+		assert_eq!(wizard.current_step(), 0);
+	}
+
+	#[test]
+	fn test_wizard_done_returns_to_invalid_step() {
+		let mut wizard = signup_wizard();
+		wizard.process_step(step_data("plan", "pro")).unwrap();
+		// Billing was never submitted
+		wizard.goto_step("profile").unwrap();
+
+		let mut called = false;
+		let result = wizard.done(|_| {
+			called = true;
+			Ok(())
+		});
+
+		assert!(result.is_err());
+		assert!(!called);
+		assert_eq!(wizard.current_step_name(), Some("billing"));
+	}
 }
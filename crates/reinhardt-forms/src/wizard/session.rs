@@ -0,0 +1,76 @@
+//! Session storage for FormWizard
+//!
+//! Stores wizard progress in a `reinhardt_auth` session under
+//! [`FormWizard::storage_key`](super::FormWizard::storage_key). The session is
+//! only modified here; saving it is left to the session middleware.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! let mut wizard = build_signup_wizard();
+//! wizard.load(&mut session)?;
+//! if wizard.process_step(data)? {
+//!     wizard.done(|data| create_account(data))?;
+//!     wizard.reset(&mut session);
+//! } else {
+//!     wizard.persist(&mut session)?;
+//! }
+//! ```
+
+use super::{WizardState, WizardStorage};
+use crate::FormError;
+use reinhardt_auth::sessions::{Session, SessionBackend};
+
+impl<B: SessionBackend> WizardStorage for Session<B> {
+	fn load(&mut self, key: &str) -> Result<Option<WizardState>, FormError> {
+		self.get(key)
+			.map_err(|e| FormError::Validation(format!("Invalid wizard state: {}", e)))
+	}
+
+	fn save(&mut self, key: &str, state: &WizardState) -> Result<(), FormError> {
+		self.set(key, state)
+			.map_err(|e| FormError::Validation(format!("Failed to store wizard state: {}", e)))
+	}
+
+	fn clear(&mut self, key: &str) {
+		self.delete(key);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::wizard::{FormWizard, WizardStep, WizardStorage};
+	use crate::{CharField, Form};
+	use reinhardt_auth::sessions::{InMemorySessionBackend, Session};
+	use serde_json::json;
+	use std::collections::HashMap;
+
+	fn wizard() -> FormWizard {
+		let mut wizard = FormWizard::new("checkout".to_string());
+		for name in ["address", "payment"] {
+			let mut form = Form::new();
+			form.add_field(Box::new(CharField::new("value".to_string())));
+			wizard.add_step(WizardStep::new(name.to_string(), form));
+		}
+		wizard
+	}
+
+	#[test]
+	fn test_wizard_state_round_trips_through_session() {
+		let mut session = Session::new(InMemorySessionBackend::new());
+		let mut data = HashMap::new();
+		data.insert("value".to_string(), json!("Main St"));
+
+		let mut first = wizard();
+		first.process_step(data).unwrap();
+		first.persist(&mut session).unwrap();
+		assert!(session.is_modified());
+
+		let mut second = wizard();
+		assert!(second.load(&mut session).unwrap());
+		assert_eq!(second.current_step_name(), Some("payment"));
+
+		second.reset(&mut session);
+		assert!(session.load("wizard_checkout").unwrap().is_none());
+	}
+}
@@ -9,6 +9,7 @@ use syn::{DeriveInput, parse_macro_input};
 mod convert;
 mod crate_paths;
 mod graphql_handler;
+mod permission;
 mod subscription;
 
 /// Generate automatic conversion between Protobuf and GraphQL types
@@ -65,3 +66,25 @@ pub fn graphql_handler(_attr: TokenStream, item: TokenStream) -> TokenStream {
 		.unwrap_or_else(|err| err.to_compile_error())
 		.into()
 }
+
+/// Attribute macro requiring permissions before a GraphQL resolver runs
+///
+/// Takes one or more Django-style permission strings (`"app_label.codename"`),
+/// validated at compile time. The resolver must be `async` and take an
+/// `async_graphql::Context<'_>` parameter; the current user is read from the
+/// context data as `Arc<dyn PermissionsMixin>`, the same user REST views
+/// check with `#[permission_required]`.
+///
+/// # Error Handling
+///
+/// Anonymous requests fail with "Authentication required", and users lacking
+/// any of the permissions fail with "Permission denied"; the resolver body
+/// does not run in either case.
+#[proc_macro_attribute]
+pub fn graphql_permission(attr: TokenStream, item: TokenStream) -> TokenStream {
+	let input = parse_macro_input!(item as syn::ItemFn);
+
+	permission::expand_graphql_permission(attr.into(), input)
+		.unwrap_or_else(|err| err.to_compile_error())
+		.into()
+}
@@ -0,0 +1,171 @@
+//! GraphQL permission macro implementation
+//!
+//! Provides the `#[graphql_permission]` attribute macro, which checks the
+//! current user's permissions before running a resolver.
+
+use crate::crate_paths::get_reinhardt_graphql_crate;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+	Error, FnArg, ItemFn, LitStr, Pat, Result, Token, Type, parse::Parser, punctuated::Punctuated,
+};
+
+/// Whether `s` is a valid identifier part of a permission string
+fn is_identifier(s: &str) -> bool {
+	let mut chars = s.chars();
+	chars
+		.next()
+		.is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+		&& chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Validate a permission string in `"app_label.codename"` format
+fn validate_permission(permission: &LitStr) -> Result<()> {
+	let value = permission.value();
+	match value.split_once('.') {
+		Some((app_label, codename)) if is_identifier(app_label) && is_identifier(codename) => {
+			Ok(())
+		}
+		_ => Err(Error::new(
+			permission.span(),
+			format!(
+				"Invalid permission string '{}': expected 'app_label.codename' (e.g., 'blog.view_article')",
+				value
+			),
+		)),
+	}
+}
+
+/// Find the `&Context<'_>` parameter of a resolver
+fn find_context_param(input: &ItemFn) -> Option<&Pat> {
+	input.sig.inputs.iter().find_map(|arg| {
+		if let FnArg::Typed(pat_type) = arg
+			&& let Type::Reference(type_ref) = &*pat_type.ty
+			&& let Type::Path(type_path) = &*type_ref.elem
+			&& type_path
+				.path
+				.segments
+				.last()
+				.is_some_and(|seg| seg.ident == "Context")
+		{
+			return Some(&*pat_type.pat);
+		}
+		None
+	})
+}
+
+/// Prepend a permission check to the resolver body
+pub(crate) fn expand_graphql_permission(args: TokenStream, input: ItemFn) -> Result<TokenStream> {
+	let permissions = Punctuated::<LitStr, Token![,]>::parse_terminated.parse2(args)?;
+	if permissions.is_empty() {
+		return Err(Error::new(
+			Span::call_site(),
+			"#[graphql_permission] requires at least one permission, e.g. #[graphql_permission(\"blog.view_article\")]",
+		));
+	}
+	for permission in &permissions {
+		validate_permission(permission)?;
+	}
+
+	if input.sig.asyncness.is_none() {
+		return Err(Error::new_spanned(
+			input.sig.fn_token,
+			"#[graphql_permission] can only be applied to async resolvers",
+		));
+	}
+	let context = find_context_param(&input).ok_or_else(|| {
+		Error::new_spanned(
+			&input.sig,
+			"#[graphql_permission] requires an async_graphql::Context parameter",
+		)
+	})?;
+
+	let graphql_crate = get_reinhardt_graphql_crate();
+	let permissions: Vec<_> = permissions.iter().collect();
+	let perm_doc = format!(
+		"Required permissions: {}",
+		permissions
+			.iter()
+			.map(|p| p.value())
+			.collect::<Vec<_>>()
+			.join(", ")
+	);
+
+	let ItemFn {
+		attrs,
+		vis,
+		sig,
+		block,
+	} = input.clone();
+	let stmts = &block.stmts;
+
+	Ok(quote! {
+		#(#attrs)*
+		#[doc = #perm_doc]
+		#vis #sig {
+			#graphql_crate::permission::check_permissions(#context, &[#(#permissions),*])?;
+			#(#stmts)*
+		}
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use syn::parse_quote;
+
+	#[test]
+	fn test_validate_permission() {
+		for valid in ["blog.view_article", "my_app.add_post2", "_app._perm"] {
+			let literal = LitStr::new(valid, Span::call_site());
+			assert!(validate_permission(&literal).is_ok(), "{}", valid);
+		}
+		for invalid in [
+			"blog",
+			"blog.view.article",
+			".view_article",
+			"blog.",
+			"blog.view article",
+			"1blog.view",
+		] {
+			let literal = LitStr::new(invalid, Span::call_site());
+			assert!(validate_permission(&literal).is_err(), "{}", invalid);
+		}
+	}
+
+	#[test]
+	fn test_check_prepended() {
+		let input: ItemFn = parse_quote! {
+			async fn articles(&self, ctx: &Context<'_>) -> Result<i32> {
+				Ok(1)
+			}
+		};
+
+		let output =
+			expand_graphql_permission(quote!("blog.view_article", "blog.list_article"), input)
+				.unwrap()
+				.to_string();
+
+		assert!(output.contains(
+			"permission :: check_permissions (ctx , & [\"blog.view_article\" , \"blog.list_article\"]) ?"
+		));
+		assert!(output.contains("Required permissions: blog.view_article, blog.list_article"));
+	}
+
+	#[test]
+	fn test_requires_context() {
+		let input: ItemFn = parse_quote! {
+			async fn articles(&self) -> Result<i32> {
+				Ok(1)
+			}
+		};
+
+		let error = expand_graphql_permission(quote!("blog.view_article"), input).unwrap_err();
+
+		assert!(
+			error
+				.to_string()
+				.contains("requires an async_graphql::Context")
+		);
+	}
+}
@@ -0,0 +1,673 @@
+//! Relay-style connections with keyset cursor pagination
+//!
+//! Implements the [Relay Cursor Connections Specification][spec] on top of
+//! keyset (seek) pagination: a cursor encodes the value of an ordered key
+//! field, so the next page is fetched with `key > cursor` instead of an
+//! `OFFSET`, and stays stable while rows are inserted or deleted.
+//!
+//! [`KeysetPagination`] turns `first`/`after`/`last`/`before` arguments into a
+//! [`KeysetWindow`] describing the rows to fetch, and the fetched rows into a
+//! [`Connection`]. With the `orm` feature, [`KeysetPagination::paginate`] runs
+//! the query against an ORM `QuerySet` directly.
+//!
+//! [spec]: https://relay.dev/graphql/connections.htm
+//!
+//! # Examples
+//!
+//! ```
+//! use async_graphql::{EmptyMutation, EmptySubscription, Object, Result, Schema, SimpleObject};
+//! use reinhardt_graphql::connection::{ConnectionArgs, Connection, KeysetPagination};
+//!
+//! #[derive(Clone, serde::Serialize, SimpleObject)]
+//! struct Post {
+//!     id: i64,
+//!     title: String,
+//! }
+//!
+//! struct Query;
+//!
+//! #[Object]
+//! impl Query {
+//!     async fn posts(
+//!         &self,
+//!         first: Option<i32>,
+//!         after: Option<String>,
+//!         last: Option<i32>,
+//!         before: Option<String>,
+//!     ) -> Result<Connection<Post>> {
+//!         let args = ConnectionArgs::new(first, after, last, before);
+//!         let pagination = KeysetPagination::new("id");
+//!         let window = pagination.window(&args)?;
+//!
+//!         // Fetch rows inside the window, in window order, limited to
+//!         // `window.fetch_limit()`
+//!         let posts: Vec<Post> = (1..=50)
+//!             .map(|id| Post { id, title: format!("Post {}", id) })
+//!             .collect();
+//!         let rows = window.apply(posts, |post| serde_json::json!(post.id));
+//!
+//!         pagination.connection(&window, rows)
+//!     }
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+//! let response = schema
+//!     .execute("{ posts(first: 2) { edges { node { id } } pageInfo { hasNextPage } } }")
+//!     .await;
+//! assert!(response.errors.is_empty());
+//! # });
+//! ```
+
+use async_graphql::{Error, Object, OutputType, Result as GqlResult, SimpleObject, TypeName};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+
+/// Information about the current page of a [`Connection`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, SimpleObject)]
+pub struct PageInfo {
+	/// Whether more edges exist after this page
+	pub has_next_page: bool,
+	/// Whether more edges exist before this page
+	pub has_previous_page: bool,
+	/// Cursor of the first edge
+	pub start_cursor: Option<String>,
+	/// Cursor of the last edge
+	pub end_cursor: Option<String>,
+}
+
+/// A node with the cursor pointing at it
+///
+/// Exposed in GraphQL as `{Node}Edge`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edge<T> {
+	/// The item at the end of the edge
+	pub node: T,
+	/// Opaque cursor for paginating from this edge
+	pub cursor: String,
+}
+
+#[Object(name_type)]
+impl<T: OutputType> Edge<T> {
+	/// The item at the end of the edge
+	async fn node(&self) -> &T {
+		&self.node
+	}
+
+	/// A cursor for use in pagination
+	async fn cursor(&self) -> &str {
+		&self.cursor
+	}
+}
+
+impl<T: OutputType> TypeName for Edge<T> {
+	fn type_name() -> Cow<'static, str> {
+		format!("{}Edge", T::type_name()).into()
+	}
+}
+
+/// One page of a Relay connection
+///
+/// Exposed in GraphQL as `{Node}Connection` with `edges`, `nodes` and
+/// `pageInfo` fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Connection<T> {
+	/// Edges on this page, in key order
+	pub edges: Vec<Edge<T>>,
+	/// Information about this page
+	pub page_info: PageInfo,
+}
+
+impl<T> Connection<T> {
+	/// Convert every node, keeping cursors and page info
+	///
+	/// Useful for exposing ORM models through a separate GraphQL type.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_graphql::connection::{Connection, Edge, PageInfo};
+	///
+	/// let connection = Connection {
+	///     edges: vec![Edge { node: 7, cursor: "Nw".to_string() }],
+	///     page_info: PageInfo::default(),
+	/// };
+	/// let connection = connection.map(|n| n.to_string());
+	/// assert_eq!(connection.edges[0].node, "7");
+	/// ```
+	pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Connection<U> {
+		Connection {
+			edges: self
+				.edges
+				.into_iter()
+				.map(|edge| Edge {
+					node: f(edge.node),
+					cursor: edge.cursor,
+				})
+				.collect(),
+			page_info: self.page_info,
+		}
+	}
+}
+
+#[Object(name_type)]
+impl<T: OutputType> Connection<T> {
+	/// A list of edges
+	async fn edges(&self) -> &[Edge<T>] {
+		&self.edges
+	}
+
+	/// The nodes of all edges
+	async fn nodes(&self) -> Vec<&T> {
+		self.edges.iter().map(|edge| &edge.node).collect()
+	}
+
+	/// Information to aid in pagination
+	async fn page_info(&self) -> &PageInfo {
+		&self.page_info
+	}
+}
+
+impl<T: OutputType> TypeName for Connection<T> {
+	fn type_name() -> Cow<'static, str> {
+		format!("{}Connection", T::type_name()).into()
+	}
+}
+
+/// Encode a key value as an opaque cursor
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_graphql::connection::{decode_cursor, encode_cursor};
+/// use serde_json::json;
+///
+/// let cursor = encode_cursor(&json!(42));
+/// assert_eq!(decode_cursor(&cursor).unwrap(), json!(42));
+/// ```
+pub fn encode_cursor(key: &JsonValue) -> String {
+	URL_SAFE_NO_PAD.encode(key.to_string())
+}
+
+/// Decode a cursor created by [`encode_cursor`]
+///
+/// # Errors
+///
+/// Fails if the cursor is not valid base64-encoded JSON.
+pub fn decode_cursor(cursor: &str) -> GqlResult<JsonValue> {
+	URL_SAFE_NO_PAD
+		.decode(cursor)
+		.ok()
+		.and_then(|bytes| serde_json::from_slice(&bytes).ok())
+		.ok_or_else(|| Error::new(format!("Invalid cursor: {}", cursor)))
+}
+
+/// Pagination arguments of a connection field
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectionArgs {
+	/// Number of edges to return after `after`
+	pub first: Option<i32>,
+	/// Cursor to return edges after
+	pub after: Option<String>,
+	/// Number of edges to return before `before`
+	pub last: Option<i32>,
+	/// Cursor to return edges before
+	pub before: Option<String>,
+}
+
+impl ConnectionArgs {
+	/// Collect the four standard Relay arguments
+	pub fn new(
+		first: Option<i32>,
+		after: Option<String>,
+		last: Option<i32>,
+		before: Option<String>,
+	) -> Self {
+		Self {
+			first,
+			after,
+			last,
+			before,
+		}
+	}
+}
+
+/// The rows needed for one page, decoded from [`ConnectionArgs`]
+///
+/// Rows must be fetched with their key strictly between `after` and
+/// `before` (where set), ordered by key ascending for forward pagination or
+/// descending for backward pagination, and limited to
+/// [`fetch_limit`](Self::fetch_limit) rows. The extra row tells whether
+/// another page exists.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeysetWindow {
+	/// Exclusive lower bound of the key
+	pub after: Option<JsonValue>,
+	/// Exclusive upper bound of the key
+	pub before: Option<JsonValue>,
+	/// Number of edges on the page
+	pub limit: usize,
+	/// Whether the page is taken from the end (`last`) of the window
+	pub backward: bool,
+}
+
+impl KeysetWindow {
+	/// Number of rows to fetch: one more than the page size
+	pub fn fetch_limit(&self) -> usize {
+		self.limit + 1
+	}
+
+	/// Select the rows of this window from an in-memory collection
+	///
+	/// Returns rows in fetch order, ready for
+	/// [`KeysetPagination::connection`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_graphql::connection::KeysetWindow;
+	/// use serde_json::json;
+	///
+	/// let window = KeysetWindow { after: Some(json!(2)), before: None, limit: 2, backward: false };
+	/// let rows = window.apply(vec![1, 2, 3, 4, 5, 6], |n| json!(n));
+	/// assert_eq!(rows, vec![3, 4, 5]);
+	/// ```
+	pub fn apply<T>(&self, rows: Vec<T>, key: impl Fn(&T) -> JsonValue) -> Vec<T> {
+		let mut rows: Vec<(JsonValue, T)> = rows
+			.into_iter()
+			.map(|row| (key(&row), row))
+			.filter(|(k, _)| {
+				self.after
+					.as_ref()
+					.is_none_or(|after| compare_keys(k, after) == Ordering::Greater)
+					&& self
+						.before
+						.as_ref()
+						.is_none_or(|before| compare_keys(k, before) == Ordering::Less)
+			})
+			.collect();
+		rows.sort_by(|(a, _), (b, _)| compare_keys(a, b));
+		if self.backward {
+			rows.reverse();
+		}
+		rows.into_iter()
+			.take(self.fetch_limit())
+			.map(|(_, row)| row)
+			.collect()
+	}
+}
+
+fn compare_keys(a: &JsonValue, b: &JsonValue) -> Ordering {
+	match (a, b) {
+		(JsonValue::Number(a), JsonValue::Number(b)) => a
+			.as_f64()
+			.partial_cmp(&b.as_f64())
+			.unwrap_or(Ordering::Equal),
+		(JsonValue::String(a), JsonValue::String(b)) => a.cmp(b),
+		_ => a.to_string().cmp(&b.to_string()),
+	}
+}
+
+/// Keyset pagination over a unique, ordered key field
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_graphql::connection::{ConnectionArgs, KeysetPagination};
+///
+/// let pagination = KeysetPagination::new("id")
+///     .default_page_size(20)
+///     .max_page_size(50);
+///
+/// let window = pagination
+///     .window(&ConnectionArgs::new(Some(500), None, None, None))
+///     .unwrap();
+/// assert_eq!(window.limit, 50);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KeysetPagination {
+	key_field: String,
+	default_page_size: usize,
+	max_page_size: Option<usize>,
+}
+
+impl KeysetPagination {
+	/// Paginate by `key_field`, with 10 edges per page by default and at
+	/// most 100
+	///
+	/// The key must be unique so that cursors identify a single position.
+	pub fn new(key_field: impl Into<String>) -> Self {
+		Self {
+			key_field: key_field.into(),
+			default_page_size: 10,
+			max_page_size: Some(100),
+		}
+	}
+
+	/// Set the page size used when neither `first` nor `last` is given
+	pub fn default_page_size(mut self, size: usize) -> Self {
+		self.default_page_size = size;
+		self
+	}
+
+	/// Set the largest page size clients may request
+	pub fn max_page_size(mut self, size: usize) -> Self {
+		self.max_page_size = Some(size);
+		self
+	}
+
+	/// Allow clients to request pages of any size
+	pub fn unlimited_page_size(mut self) -> Self {
+		self.max_page_size = None;
+		self
+	}
+
+	/// Decode connection arguments into the window of rows to fetch
+	///
+	/// # Errors
+	///
+	/// Fails if both `first` and `last` are given, either is negative, or a
+	/// cursor is invalid.
+	pub fn window(&self, args: &ConnectionArgs) -> GqlResult<KeysetWindow> {
+		let count = |name: &str, value: Option<i32>| -> GqlResult<Option<usize>> {
+			value
+				.map(|value| {
+					usize::try_from(value)
+						.map_err(|_| Error::new(format!("`{}` must not be negative", name)))
+				})
+				.transpose()
+		};
+		let first = count("first", args.first)?;
+		let last = count("last", args.last)?;
+		let (size, backward) = match (first, last) {
+			(Some(_), Some(_)) => {
+				return Err(Error::new(
+					"Passing both `first` and `last` is not supported",
+				));
+			}
+			(Some(first), None) => (first, false),
+			(None, Some(last)) => (last, true),
+			// Without a page size, `before` alone still pages backward
+			(None, None) => (
+				self.default_page_size,
+				args.before.is_some() && args.after.is_none(),
+			),
+		};
+		let cursor = |cursor: &Option<String>| cursor.as_deref().map(decode_cursor).transpose();
+
+		Ok(KeysetWindow {
+			after: cursor(&args.after)?,
+			before: cursor(&args.before)?,
+			limit: self.max_page_size.map_or(size, |max| size.min(max)),
+			backward,
+		})
+	}
+
+	/// Build the connection for rows fetched for `window`
+	///
+	/// Rows must be in fetch order (see [`KeysetWindow`]); each row's cursor
+	/// is its `key_field` value once serialized.
+	///
+	/// # Errors
+	///
+	/// Fails if a row cannot be serialized or lacks the key field.
+	pub fn connection<T: Serialize>(
+		&self,
+		window: &KeysetWindow,
+		mut rows: Vec<T>,
+	) -> GqlResult<Connection<T>> {
+		let has_more = rows.len() > window.limit;
+		rows.truncate(window.limit);
+		if window.backward {
+			rows.reverse();
+		}
+
+		let edges = rows
+			.into_iter()
+			.map(|node| {
+				let key = match serde_json::to_value(&node)? {
+					JsonValue::Object(mut fields) => fields.remove(&self.key_field),
+					_ => None,
+				}
+				.filter(|key| !key.is_null())
+				.ok_or_else(|| {
+					Error::new(format!("Row has no `{}` to paginate by", self.key_field))
+				})?;
+				Ok(Edge {
+					node,
+					cursor: encode_cursor(&key),
+				})
+			})
+			.collect::<GqlResult<Vec<_>>>()?;
+
+		// Per the spec, the opposite direction is only reported when it can
+		// be determined cheaply: a cursor in that direction means rows exist
+		let (has_next_page, has_previous_page) = if window.backward {
+			(window.before.is_some(), has_more)
+		} else {
+			(has_more, window.after.is_some())
+		};
+
+		Ok(Connection {
+			page_info: PageInfo {
+				has_next_page,
+				has_previous_page,
+				start_cursor: edges.first().map(|edge| edge.cursor.clone()),
+				end_cursor: edges.last().map(|edge| edge.cursor.clone()),
+			},
+			edges,
+		})
+	}
+
+	/// Fetch one page of a `QuerySet` as a connection
+	///
+	/// Adds the key bounds, ordering and limit to `queryset`, so existing
+	/// filters are kept but any ordering is replaced.
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use reinhardt_graphql::connection::{ConnectionArgs, KeysetPagination};
+	/// # use reinhardt_db::orm::Model;
+	/// # use serde::{Deserialize, Serialize};
+	/// # #[derive(Debug, Clone, Serialize, Deserialize)]
+	/// # struct Post { id: Option<i64> }
+	/// # #[derive(Clone)]
+	/// # struct PostFields;
+	/// # impl reinhardt_db::orm::model::FieldSelector for PostFields {
+	/// #     fn with_alias(self, _alias: &str) -> Self { self }
+	/// # }
+	/// # impl Model for Post {
+	/// #     type PrimaryKey = i64;
+	/// #     type Fields = PostFields;
+	/// #     fn table_name() -> &'static str { "posts" }
+	/// #     fn new_fields() -> Self::Fields { PostFields }
+	/// #     fn primary_key(&self) -> Option<Self::PrimaryKey> { self.id }
+	/// #     fn set_primary_key(&mut self, value: Self::PrimaryKey) { self.id = Some(value); }
+	/// # }
+	///
+	/// # async fn example() -> async_graphql::Result<()> {
+	/// let args = ConnectionArgs::new(Some(10), None, None, None);
+	/// let page = KeysetPagination::new("id")
+	///     .paginate(Post::objects().all(), &args)
+	///     .await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	#[cfg(feature = "orm")]
+	pub async fn paginate<M>(
+		&self,
+		queryset: reinhardt_db::orm::QuerySet<M>,
+		args: &ConnectionArgs,
+	) -> GqlResult<Connection<M>>
+	where
+		M: reinhardt_db::orm::Model,
+	{
+		use crate::model::filter_value;
+		use reinhardt_db::orm::{Filter, FilterOperator};
+
+		let window = self.window(args)?;
+		let mut queryset = queryset;
+		if let Some(after) = &window.after {
+			queryset = queryset.filter(Filter::new(
+				self.key_field.clone(),
+				FilterOperator::Gt,
+				filter_value(after),
+			));
+		}
+		if let Some(before) = &window.before {
+			queryset = queryset.filter(Filter::new(
+				self.key_field.clone(),
+				FilterOperator::Lt,
+				filter_value(before),
+			));
+		}
+		let ordering = if window.backward {
+			format!("-{}", self.key_field)
+		} else {
+			self.key_field.clone()
+		};
+		let rows = queryset
+			.order_by(&[&ordering])
+			.limit(window.fetch_limit())
+			.all()
+			.await
+			.map_err(|e| Error::new(e.to_string()))?;
+
+		self.connection(&window, rows)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+	use serde_json::json;
+
+	#[derive(Debug, Clone, PartialEq, Serialize)]
+	struct Row {
+		id: i64,
+	}
+
+	fn rows() -> Vec<Row> {
+		(1..=10).map(|id| Row { id }).collect()
+	}
+
+	fn page(args: ConnectionArgs) -> Connection<Row> {
+		let pagination = KeysetPagination::new("id");
+		let window = pagination.window(&args).unwrap();
+		let rows = window.apply(rows(), |row| json!(row.id));
+		pagination.connection(&window, rows).unwrap()
+	}
+
+	fn ids(connection: &Connection<Row>) -> Vec<i64> {
+		connection.edges.iter().map(|edge| edge.node.id).collect()
+	}
+
+	fn cursor(id: i64) -> Option<String> {
+		Some(encode_cursor(&json!(id)))
+	}
+
+	#[rstest]
+	fn test_first_page() {
+		let connection = page(ConnectionArgs::new(Some(3), None, None, None));
+
+		assert_eq!(ids(&connection), vec![1, 2, 3]);
+		assert!(connection.page_info.has_next_page);
+		assert!(!connection.page_info.has_previous_page);
+		assert_eq!(connection.page_info.start_cursor, cursor(1));
+		assert_eq!(connection.page_info.end_cursor, cursor(3));
+	}
+
+	#[rstest]
+	fn test_forward_from_end_cursor() {
+		let connection = page(ConnectionArgs::new(Some(4), cursor(8), None, None));
+
+		assert_eq!(ids(&connection), vec![9, 10]);
+		assert!(!connection.page_info.has_next_page);
+		assert!(connection.page_info.has_previous_page);
+	}
+
+	#[rstest]
+	fn test_last_before_cursor() {
+		let connection = page(ConnectionArgs::new(None, None, Some(3), cursor(5)));
+
+		assert_eq!(ids(&connection), vec![2, 3, 4]);
+		assert!(connection.page_info.has_next_page);
+		assert!(connection.page_info.has_previous_page);
+	}
+
+	#[rstest]
+	fn test_last_page_from_end() {
+		let connection = page(ConnectionArgs::new(None, None, Some(3), None));
+
+		assert_eq!(ids(&connection), vec![8, 9, 10]);
+		assert!(!connection.page_info.has_next_page);
+		assert!(connection.page_info.has_previous_page);
+	}
+
+	#[rstest]
+	fn test_after_and_before_bound_the_window() {
+		let connection = page(ConnectionArgs::new(Some(10), cursor(3), None, cursor(6)));
+
+		assert_eq!(ids(&connection), vec![4, 5]);
+		assert!(!connection.page_info.has_next_page);
+	}
+
+	#[rstest]
+	fn test_empty_page_has_no_cursors() {
+		let connection = page(ConnectionArgs::new(Some(5), cursor(10), None, None));
+
+		assert!(connection.edges.is_empty());
+		assert_eq!(connection.page_info.start_cursor, None);
+		assert_eq!(connection.page_info.end_cursor, None);
+	}
+
+	#[rstest]
+	#[case(
+		ConnectionArgs::new(Some(1), None, Some(1), None),
+		"both `first` and `last`"
+	)]
+	#[case(ConnectionArgs::new(Some(-1), None, None, None), "`first` must not be negative")]
+	#[case(ConnectionArgs::new(None, None, Some(-2), None), "`last` must not be negative")]
+	#[case(ConnectionArgs::new(Some(1), Some("%%".to_string()), None, None), "Invalid cursor")]
+	fn test_invalid_arguments(#[case] args: ConnectionArgs, #[case] message: &str) {
+		let error = KeysetPagination::new("id").window(&args).unwrap_err();
+
+		assert!(error.message.contains(message), "{}", error.message);
+	}
+
+	#[rstest]
+	#[case(None, 10)]
+	#[case(Some(1000), 100)]
+	#[case(Some(0), 0)]
+	fn test_page_size_defaults_and_limits(#[case] first: Option<i32>, #[case] expected: usize) {
+		let window = KeysetPagination::new("id")
+			.window(&ConnectionArgs::new(first, None, None, None))
+			.unwrap();
+
+		assert_eq!(window.limit, expected);
+	}
+
+	#[rstest]
+	fn test_row_without_key_is_rejected() {
+		let pagination = KeysetPagination::new("missing");
+		let window = pagination.window(&ConnectionArgs::default()).unwrap();
+
+		let result = pagination.connection(&window, rows());
+
+		assert!(result.is_err());
+	}
+
+	#[rstest]
+	#[case(json!(42))]
+	#[case(json!("2024-01-01T00:00:00Z"))]
+	#[case(json!([3, "b"]))]
+	fn test_cursor_round_trip(#[case] key: JsonValue) {
+		assert_eq!(decode_cursor(&encode_cursor(&key)).unwrap(), key);
+	}
+}
@@ -7,11 +7,13 @@ use async_trait::async_trait;
 use serde_json::Value;
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{RwLock, oneshot};
 
 /// Error types for data loader operations
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, Clone, thiserror::Error)]
 pub enum LoaderError {
 	#[error("Loader error: {0}")]
 	Load(String),
@@ -60,6 +62,250 @@ pub trait DataLoader: Send + Sync + 'static {
 	async fn load_many(&self, keys: Vec<Self::Key>) -> Result<Vec<Self::Value>, LoaderError>;
 }
 
+type Waiter<V> = oneshot::Sender<Result<V, LoaderError>>;
+
+/// Keys of one batch with everyone waiting on each key
+type Batch<K, V> = Vec<(K, Vec<Waiter<V>>)>;
+
+struct BatchState<K, V> {
+	/// Values loaded so far in this request
+	cache: HashMap<K, V>,
+	/// Keys waiting for the next batch, with everyone waiting on them
+	pending: HashMap<K, Vec<Waiter<V>>>,
+	/// Whether a dispatch task has been spawned for the pending keys
+	dispatch_scheduled: bool,
+}
+
+/// Batching and caching wrapper around a [`DataLoader`]
+///
+/// Keys requested through [`load`](Self::load) are collected until the
+/// current task yields (or for [`with_delay`](Self::with_delay)), then
+/// resolved with a single [`DataLoader::load_many`] call. Loaded values are
+/// cached, so create one `BatchLoader` per request to avoid serving stale
+/// data across requests.
+///
+/// Sibling resolvers that each load one related object, such as the author
+/// of every post in a list, therefore cause one query instead of N.
+///
+/// Dispatching spawns a task, so loading must happen inside a Tokio runtime.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_graphql::context::{BatchLoader, DataLoader, LoaderError};
+/// use async_trait::async_trait;
+///
+/// struct SquareLoader;
+///
+/// #[async_trait]
+/// impl DataLoader for SquareLoader {
+///     type Key = u32;
+///     type Value = u32;
+///
+///     async fn load(&self, key: Self::Key) -> Result<Self::Value, LoaderError> {
+///         Ok(key * key)
+///     }
+///
+///     async fn load_many(&self, keys: Vec<Self::Key>) -> Result<Vec<Self::Value>, LoaderError> {
+///         Ok(keys.into_iter().map(|k| k * k).collect())
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let loader = BatchLoader::new(SquareLoader);
+///
+/// // Both keys are resolved by one `load_many(vec![2, 3])` call
+/// let (a, b) = tokio::join!(loader.load(2), loader.load(3));
+/// assert_eq!(a.unwrap(), 4);
+/// assert_eq!(b.unwrap(), 9);
+/// # });
+/// ```
+pub struct BatchLoader<L: DataLoader> {
+	loader: Arc<L>,
+	state: Arc<Mutex<BatchState<L::Key, L::Value>>>,
+	delay: Duration,
+	max_batch_size: usize,
+}
+
+impl<L> BatchLoader<L>
+where
+	L: DataLoader,
+	L::Key: Eq + Hash + Clone + Sync,
+	L::Value: Clone + Sync,
+{
+	/// Wrap `loader` with an empty cache
+	pub fn new(loader: L) -> Self {
+		Self {
+			loader: Arc::new(loader),
+			state: Arc::new(Mutex::new(BatchState {
+				cache: HashMap::new(),
+				pending: HashMap::new(),
+				dispatch_scheduled: false,
+			})),
+			delay: Duration::ZERO,
+			max_batch_size: 1000,
+		}
+	}
+
+	/// Wait `delay` for more keys before dispatching a batch
+	///
+	/// By default a batch is dispatched as soon as the requesting task
+	/// yields, which collects every key requested by sibling resolvers.
+	pub fn with_delay(mut self, delay: Duration) -> Self {
+		self.delay = delay;
+		self
+	}
+
+	/// Split batches larger than `size` keys into several `load_many` calls
+	///
+	/// # Panics
+	///
+	/// Panics if `size` is zero.
+	pub fn with_max_batch_size(mut self, size: usize) -> Self {
+		assert!(size > 0, "max batch size must be positive");
+		self.max_batch_size = size;
+		self
+	}
+
+	/// Load a value, batched with other keys requested in the same tick
+	///
+	/// # Errors
+	///
+	/// Returns the error of the batch the key was loaded in. Errors are not
+	/// cached, so a later call retries the key.
+	pub async fn load(&self, key: L::Key) -> Result<L::Value, LoaderError> {
+		let receiver = {
+			let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+			if let Some(value) = state.cache.get(&key) {
+				return Ok(value.clone());
+			}
+			let (sender, receiver) = oneshot::channel();
+			state.pending.entry(key).or_default().push(sender);
+			if !state.dispatch_scheduled {
+				state.dispatch_scheduled = true;
+				self.schedule_dispatch();
+			}
+			receiver
+		};
+		receiver
+			.await
+			.map_err(|_| LoaderError::Load("Batch dispatch was cancelled".to_string()))?
+	}
+
+	/// Load several values in one batch, in the order of `keys`
+	///
+	/// # Errors
+	///
+	/// Fails if loading any of the keys fails.
+	pub async fn load_many(&self, keys: Vec<L::Key>) -> Result<Vec<L::Value>, LoaderError> {
+		futures_util::future::try_join_all(keys.into_iter().map(|key| self.load(key))).await
+	}
+
+	/// Cache a value without loading it, e.g. after a mutation returned it
+	pub fn prime(&self, key: L::Key, value: L::Value) {
+		let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+		state.cache.insert(key, value);
+	}
+
+	/// Remove a cached value so the next load fetches it again
+	pub fn clear(&self, key: &L::Key) {
+		let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+		state.cache.remove(key);
+	}
+
+	/// Remove all cached values
+	pub fn clear_all(&self) {
+		let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+		state.cache.clear();
+	}
+
+	fn schedule_dispatch(&self) {
+		let loader = Arc::clone(&self.loader);
+		let state = Arc::clone(&self.state);
+		let delay = self.delay;
+		let max_batch_size = self.max_batch_size;
+
+		tokio::spawn(async move {
+			if delay.is_zero() {
+				tokio::task::yield_now().await;
+			} else {
+				tokio::time::sleep(delay).await;
+			}
+
+			let pending: Vec<_> = {
+				let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+				state.dispatch_scheduled = false;
+				std::mem::take(&mut state.pending).into_iter().collect()
+			};
+			let mut pending = pending.into_iter().peekable();
+			let mut batches = Vec::new();
+			while pending.peek().is_some() {
+				let batch: Vec<_> = pending.by_ref().take(max_batch_size).collect();
+				batches.push(Self::dispatch(&loader, &state, batch));
+			}
+			futures_util::future::join_all(batches).await;
+		});
+	}
+
+	async fn dispatch(
+		loader: &L,
+		state: &Mutex<BatchState<L::Key, L::Value>>,
+		batch: Batch<L::Key, L::Value>,
+	) {
+		let keys = batch.iter().map(|(key, _)| key.clone()).collect();
+		let result = match loader.load_many(keys).await {
+			Ok(values) if values.len() != batch.len() => Err(LoaderError::InvalidData(format!(
+				"load_many returned {} values for {} keys",
+				values.len(),
+				batch.len()
+			))),
+			result => result,
+		};
+
+		match result {
+			Ok(values) => {
+				{
+					let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+					for ((key, _), value) in batch.iter().zip(&values) {
+						state.cache.insert(key.clone(), value.clone());
+					}
+				}
+				for ((_, waiters), value) in batch.into_iter().zip(values) {
+					for waiter in waiters {
+						let _ = waiter.send(Ok(value.clone()));
+					}
+				}
+			}
+			Err(error) => {
+				for (_, waiters) in batch {
+					for waiter in waiters {
+						let _ = waiter.send(Err(error.clone()));
+					}
+				}
+			}
+		}
+	}
+}
+
+#[async_trait]
+impl<L> DataLoader for BatchLoader<L>
+where
+	L: DataLoader,
+	L::Key: Eq + Hash + Clone + Sync,
+	L::Value: Clone + Sync,
+{
+	type Key = L::Key;
+	type Value = L::Value;
+
+	async fn load(&self, key: Self::Key) -> Result<Self::Value, LoaderError> {
+		BatchLoader::load(self, key).await
+	}
+
+	async fn load_many(&self, keys: Vec<Self::Key>) -> Result<Vec<Self::Value>, LoaderError> {
+		BatchLoader::load_many(self, keys).await
+	}
+}
+
 /// GraphQL context for managing request-scoped data
 ///
 /// Provides access to request information, user authentication state,
@@ -7,6 +7,10 @@
 //! - **graphql-grpc**: GraphQL facade over gRPC for Query/Mutation
 //! - **subscription**: gRPC-based Subscriptions (Rust 2024 compatible)
 //! - **di**: Dependency injection support for GraphQL resolvers
+//! - **orm**: CRUD schemas generated from ORM models (see [`model`])
+//! - **websocket**: Subscriptions over graphql-ws WebSocket protocols (see [`websocket`])
+//! - **persisted-queries**: Automatic persisted queries (see [`persisted_query`])
+//! - **auth**: Permission checks shared with REST views (see [`permission`])
 //! - **full**: All features enabled
 //!
 //! # Dependency Injection
@@ -68,6 +72,7 @@
 //! }
 //! ```
 
+pub mod connection;
 pub mod context;
 pub mod schema;
 pub mod subscription;
@@ -79,13 +84,44 @@ pub mod di;
 #[cfg(feature = "graphql-grpc")]
 pub mod grpc_service;
 
-pub use context::{DataLoader, GraphQLContext, LoaderError};
+#[cfg(feature = "orm")]
+pub mod model;
+
+#[cfg(feature = "auth")]
+pub mod permission;
+
+#[cfg(feature = "persisted-queries")]
+pub mod persisted_query;
+
+#[cfg(feature = "websocket")]
+pub mod websocket;
+
+pub use connection::{Connection, ConnectionArgs, Edge, KeysetPagination, PageInfo};
+pub use context::{BatchLoader, DataLoader, GraphQLContext, LoaderError};
 pub use schema::{AppSchema, CreateUserInput, Mutation, Query, User, UserStorage, create_schema};
 pub use subscription::{EventBroadcaster, SubscriptionRoot, UserEvent};
 
 #[cfg(feature = "graphql-grpc")]
 pub use grpc_service::GraphQLGrpcService;
 
+#[cfg(feature = "orm")]
+pub use model::{
+	FieldFilter, ListQuery, Lookup, ModelLoader, ModelSchemaBuilder, ModelStore, ModelType,
+	OrmStore,
+};
+
+#[cfg(feature = "auth")]
+pub use permission::{PermissionGuard, PermissionRequestExt};
+
+#[cfg(feature = "auth")]
+pub use reinhardt_graphql_macros::graphql_permission;
+
+#[cfg(feature = "persisted-queries")]
+pub use persisted_query::PersistedQueries;
+
+#[cfg(feature = "websocket")]
+pub use websocket::{GraphQLWsConsumer, negotiate_subprotocol};
+
 // gRPC integration: re-export of adapter traits and derive macros
 #[cfg(any(feature = "graphql-grpc", feature = "subscription"))]
 pub use reinhardt_grpc::{GrpcServiceAdapter, GrpcSubscriptionAdapter};
@@ -0,0 +1,966 @@
+//! GraphQL types and CRUD resolvers generated from ORM models
+//!
+//! [`ModelSchemaBuilder`] turns ORM [`Model`]s into a dynamic GraphQL schema.
+//! For a model registered as `Article`, it generates:
+//!
+//! - an `Article` object type with one field per model field
+//! - `article(id: ID!): Article` and
+//!   `articles(filter: ArticleFilter, limit: Int, offset: Int): [Article!]!`
+//!   queries
+//! - `createArticle(input: CreateArticleInput!): Article!`,
+//!   `updateArticle(id: ID!, input: UpdateArticleInput!): Article!` and
+//!   `deleteArticle(id: ID!): Boolean!` mutations
+//!
+//! Fields are taken from [`Model::field_metadata`], which the `#[model(...)]`
+//! macro generates, and exposed in camelCase. The primary key becomes an `ID`,
+//! integer fields `Int`, float fields `Float`, boolean fields `Boolean`, and
+//! every other field a `String` holding its serialized value.
+//!
+//! Filters match fields exactly, with `Gt`/`Gte`/`Lt`/`Lte` variants for
+//! numbers (`viewsGt`) and a `Contains` variant for strings (`titleContains`).
+//!
+//! Resolvers load and save models through a [`ModelStore`]. The default
+//! [`OrmStore`] uses the model's manager, so the schema reads and writes the
+//! database configured with `init_database`.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use reinhardt_graphql::model::{ModelSchemaBuilder, ModelType};
+//! # use reinhardt_db::orm::Model;
+//! # use reinhardt_db::orm::inspection::FieldInfo;
+//! # use serde::{Deserialize, Serialize};
+//! # #[derive(Debug, Clone, Serialize, Deserialize)]
+//! # struct Article { id: Option<i64>, title: String }
+//! # #[derive(Clone)]
+//! # struct ArticleFields;
+//! # impl reinhardt_db::orm::model::FieldSelector for ArticleFields {
+//! #     fn with_alias(self, _alias: &str) -> Self { self }
+//! # }
+//! # impl Model for Article {
+//! #     type PrimaryKey = i64;
+//! #     type Fields = ArticleFields;
+//! #     fn table_name() -> &'static str { "articles" }
+//! #     fn new_fields() -> Self::Fields { ArticleFields }
+//! #     fn primary_key(&self) -> Option<Self::PrimaryKey> { self.id }
+//! #     fn set_primary_key(&mut self, value: Self::PrimaryKey) { self.id = Some(value); }
+//! # }
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let schema = ModelSchemaBuilder::new()
+//!     .register(ModelType::<Article>::new("Article").read_only("created_at"))
+//!     .finish()?;
+//!
+//! let response = schema
+//!     .execute(r#"{ articles(filter: { titleContains: "Rust" }, limit: 10) { id title } }"#)
+//!     .await;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::context::{DataLoader, LoaderError};
+use async_graphql::dynamic::{
+	Field, FieldFuture, FieldValue, InputObject, InputValue, Object, ObjectAccessor,
+	ResolverContext, Schema, SchemaBuilder, SchemaError, TypeRef,
+};
+use async_graphql::{Error, Result as GqlResult, Value};
+use async_trait::async_trait;
+use reinhardt_db::orm::Model;
+use reinhardt_db::orm::inspection::FieldInfo;
+use reinhardt_db::orm::query::{Filter, FilterOperator, FilterValue};
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Comparison applied by a [`FieldFilter`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lookup {
+	/// Equal to the value (`title`)
+	Exact,
+	/// String contains the value (`titleContains`)
+	Contains,
+	/// Greater than the value (`viewsGt`)
+	Gt,
+	/// Greater than or equal to the value (`viewsGte`)
+	Gte,
+	/// Less than the value (`viewsLt`)
+	Lt,
+	/// Less than or equal to the value (`viewsLte`)
+	Lte,
+}
+
+impl Lookup {
+	fn suffix(self) -> &'static str {
+		match self {
+			Self::Exact => "",
+			Self::Contains => "Contains",
+			Self::Gt => "Gt",
+			Self::Gte => "Gte",
+			Self::Lt => "Lt",
+			Self::Lte => "Lte",
+		}
+	}
+}
+
+/// A condition on one model field, parsed from a list query's filter
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldFilter {
+	/// Model field name
+	pub field: String,
+	/// Comparison to apply
+	pub lookup: Lookup,
+	/// Value in the model's JSON representation
+	pub value: JsonValue,
+}
+
+impl FieldFilter {
+	/// Check whether a model serialized to JSON matches the filter
+	///
+	/// Useful for [`ModelStore`]s that filter in memory.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_graphql::model::{FieldFilter, Lookup};
+	/// use serde_json::json;
+	///
+	/// let filter = FieldFilter {
+	///     field: "views".to_string(),
+	///     lookup: Lookup::Gte,
+	///     value: json!(10),
+	/// };
+	/// assert!(filter.matches(&json!({ "views": 12 })));
+	/// assert!(!filter.matches(&json!({ "views": 3 })));
+	/// ```
+	pub fn matches(&self, row: &JsonValue) -> bool {
+		let actual = row.get(&self.field).unwrap_or(&JsonValue::Null);
+		match self.lookup {
+			Lookup::Exact => actual == &self.value,
+			Lookup::Contains => match (actual.as_str(), self.value.as_str()) {
+				(Some(actual), Some(expected)) => actual.contains(expected),
+				_ => false,
+			},
+			Lookup::Gt => compare(actual, &self.value) == Some(Ordering::Greater),
+			Lookup::Gte => matches!(
+				compare(actual, &self.value),
+				Some(Ordering::Greater | Ordering::Equal)
+			),
+			Lookup::Lt => compare(actual, &self.value) == Some(Ordering::Less),
+			Lookup::Lte => matches!(
+				compare(actual, &self.value),
+				Some(Ordering::Less | Ordering::Equal)
+			),
+		}
+	}
+}
+
+fn compare(a: &JsonValue, b: &JsonValue) -> Option<Ordering> {
+	match (a, b) {
+		(JsonValue::Number(a), JsonValue::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+		(JsonValue::String(a), JsonValue::String(b)) => Some(a.cmp(b)),
+		_ => None,
+	}
+}
+
+/// Arguments of a generated list query
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ListQuery {
+	/// Conditions that all must match
+	pub filters: Vec<FieldFilter>,
+	/// Maximum number of models to return
+	pub limit: Option<usize>,
+	/// Number of matching models to skip
+	pub offset: Option<usize>,
+}
+
+/// Storage used by the generated resolvers
+#[async_trait]
+pub trait ModelStore<M: Model>: Send + Sync {
+	/// Load a model by primary key
+	async fn get(&self, pk: M::PrimaryKey) -> GqlResult<Option<M>>;
+
+	/// List models matching a query
+	async fn list(&self, query: &ListQuery) -> GqlResult<Vec<M>>;
+
+	/// Insert a new model and return it with its primary key set
+	async fn create(&self, model: M) -> GqlResult<M>;
+
+	/// Save changes to an existing model
+	async fn update(&self, model: M) -> GqlResult<M>;
+
+	/// Delete a model, returning `false` if it did not exist
+	async fn delete(&self, pk: M::PrimaryKey) -> GqlResult<bool>;
+}
+
+/// [`ModelStore`] backed by the model's ORM manager
+pub struct OrmStore<M> {
+	_marker: PhantomData<fn() -> M>,
+}
+
+impl<M: Model> OrmStore<M> {
+	/// Create a store using `M::objects()`
+	pub fn new() -> Self {
+		Self {
+			_marker: PhantomData,
+		}
+	}
+}
+
+impl<M: Model> Default for OrmStore<M> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+fn orm_error(error: impl std::fmt::Display) -> Error {
+	Error::new(error.to_string())
+}
+
+pub(crate) fn filter_value(value: &JsonValue) -> FilterValue {
+	match value {
+		JsonValue::Null => FilterValue::Null,
+		JsonValue::Bool(b) => FilterValue::Boolean(*b),
+		JsonValue::Number(n) => match n.as_i64() {
+			Some(i) => FilterValue::Integer(i),
+			None => FilterValue::Float(n.as_f64().unwrap_or_default()),
+		},
+		JsonValue::String(s) => FilterValue::String(s.clone()),
+		other => FilterValue::String(other.to_string()),
+	}
+}
+
+impl FieldFilter {
+	fn to_orm_filter(&self) -> Filter {
+		let operator = match (self.lookup, &self.value) {
+			(Lookup::Exact, JsonValue::Null) => FilterOperator::IsNull,
+			(Lookup::Exact, _) => FilterOperator::Eq,
+			(Lookup::Contains, _) => FilterOperator::Contains,
+			(Lookup::Gt, _) => FilterOperator::Gt,
+			(Lookup::Gte, _) => FilterOperator::Gte,
+			(Lookup::Lt, _) => FilterOperator::Lt,
+			(Lookup::Lte, _) => FilterOperator::Lte,
+		};
+		Filter::new(self.field.clone(), operator, filter_value(&self.value))
+	}
+}
+
+#[async_trait]
+impl<M: Model + 'static> ModelStore<M> for OrmStore<M> {
+	async fn get(&self, pk: M::PrimaryKey) -> GqlResult<Option<M>> {
+		M::objects().get(pk).first().await.map_err(orm_error)
+	}
+
+	async fn list(&self, query: &ListQuery) -> GqlResult<Vec<M>> {
+		let mut queryset = M::objects().all();
+		for filter in &query.filters {
+			queryset = queryset.filter(filter.to_orm_filter());
+		}
+		if let Some(limit) = query.limit {
+			queryset = queryset.limit(limit);
+		}
+		if let Some(offset) = query.offset {
+			queryset = queryset.offset(offset);
+		}
+		queryset.all().await.map_err(orm_error)
+	}
+
+	async fn create(&self, model: M) -> GqlResult<M> {
+		M::objects().create(&model).await.map_err(orm_error)
+	}
+
+	async fn update(&self, model: M) -> GqlResult<M> {
+		M::objects().update(&model).await.map_err(orm_error)
+	}
+
+	async fn delete(&self, pk: M::PrimaryKey) -> GqlResult<bool> {
+		if self.get(pk.clone()).await?.is_none() {
+			return Ok(false);
+		}
+		M::objects().delete(pk).await.map_err(orm_error)?;
+		Ok(true)
+	}
+}
+
+/// [`DataLoader`] fetching models by primary key with one `IN` query
+///
+/// Wrap it in a [`BatchLoader`](crate::context::BatchLoader) per request so
+/// that nested resolvers loading related models by ID share a single
+/// `WHERE pk IN (...)` query. Keys without a matching row load as `None`.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use reinhardt_graphql::context::BatchLoader;
+/// use reinhardt_graphql::model::ModelLoader;
+/// # use reinhardt_db::orm::Model;
+/// # use serde::{Deserialize, Serialize};
+/// # #[derive(Debug, Clone, Serialize, Deserialize)]
+/// # struct Author { id: Option<i64>, name: String }
+/// # #[derive(Clone)]
+/// # struct AuthorFields;
+/// # impl reinhardt_db::orm::model::FieldSelector for AuthorFields {
+/// #     fn with_alias(self, _alias: &str) -> Self { self }
+/// # }
+/// # impl Model for Author {
+/// #     type PrimaryKey = i64;
+/// #     type Fields = AuthorFields;
+/// #     fn table_name() -> &'static str { "authors" }
+/// #     fn new_fields() -> Self::Fields { AuthorFields }
+/// #     fn primary_key(&self) -> Option<Self::PrimaryKey> { self.id }
+/// #     fn set_primary_key(&mut self, value: Self::PrimaryKey) { self.id = Some(value); }
+/// # }
+///
+/// # async fn example() -> Result<(), reinhardt_graphql::LoaderError> {
+/// let authors = BatchLoader::new(ModelLoader::<Author>::new());
+///
+/// // Resolved together by `SELECT ... FROM authors WHERE id IN (1, 2)`
+/// let (first, second) = tokio::join!(authors.load(1), authors.load(2));
+/// # Ok(())
+/// # }
+/// ```
+pub struct ModelLoader<M> {
+	_marker: PhantomData<fn() -> M>,
+}
+
+impl<M: Model> ModelLoader<M> {
+	/// Create a loader using `M::objects()`
+	pub fn new() -> Self {
+		Self {
+			_marker: PhantomData,
+		}
+	}
+}
+
+impl<M: Model> Default for ModelLoader<M> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[async_trait]
+impl<M> DataLoader for ModelLoader<M>
+where
+	M: Model + 'static,
+	M::PrimaryKey: Serialize + Eq + Hash + Send + Sync,
+{
+	type Key = M::PrimaryKey;
+	type Value = Option<M>;
+
+	async fn load(&self, key: Self::Key) -> Result<Self::Value, LoaderError> {
+		M::objects()
+			.get(key)
+			.first()
+			.await
+			.map_err(|e| LoaderError::Load(e.to_string()))
+	}
+
+	async fn load_many(&self, keys: Vec<Self::Key>) -> Result<Vec<Self::Value>, LoaderError> {
+		if keys.is_empty() {
+			return Ok(Vec::new());
+		}
+		// A JSON array keeps integer keys typed in the IN clause
+		let values =
+			serde_json::to_string(&keys).map_err(|e| LoaderError::InvalidData(e.to_string()))?;
+		let models = M::objects()
+			.filter(
+				M::primary_key_field(),
+				FilterOperator::In,
+				FilterValue::String(values),
+			)
+			.all()
+			.await
+			.map_err(|e| LoaderError::Load(e.to_string()))?;
+
+		let mut by_key: HashMap<M::PrimaryKey, M> = models
+			.into_iter()
+			.filter_map(|model| model.primary_key().map(|pk| (pk, model)))
+			.collect();
+		Ok(keys.iter().map(|key| by_key.remove(key)).collect())
+	}
+}
+
+/// GraphQL scalar a model field is exposed as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScalarKind {
+	Id,
+	Int,
+	Float,
+	Boolean,
+	String,
+}
+
+impl ScalarKind {
+	fn of(field: &FieldInfo) -> Self {
+		if field.primary_key {
+			return Self::Id;
+		}
+		let type_name = field.field_type.rsplit('.').next().unwrap_or_default();
+		match type_name {
+			name if name.ends_with("IntegerField") => Self::Int,
+			"FloatField" => Self::Float,
+			"BooleanField" => Self::Boolean,
+			_ => Self::String,
+		}
+	}
+
+	fn type_name(self) -> &'static str {
+		match self {
+			Self::Id => TypeRef::ID,
+			Self::Int => TypeRef::INT,
+			Self::Float => TypeRef::FLOAT,
+			Self::Boolean => TypeRef::BOOLEAN,
+			Self::String => TypeRef::STRING,
+		}
+	}
+
+	fn lookups(self) -> &'static [Lookup] {
+		match self {
+			Self::Int | Self::Float => &[
+				Lookup::Exact,
+				Lookup::Gt,
+				Lookup::Gte,
+				Lookup::Lt,
+				Lookup::Lte,
+			],
+			Self::String => &[Lookup::Exact, Lookup::Contains],
+			Self::Id | Self::Boolean => &[Lookup::Exact],
+		}
+	}
+
+	/// Convert a field value from the model's JSON to GraphQL
+	fn to_graphql(self, value: Option<&JsonValue>) -> Option<Value> {
+		match value? {
+			JsonValue::Null => None,
+			JsonValue::String(s) => Some(Value::String(s.clone())),
+			other if self == Self::Id || self == Self::String => {
+				Some(Value::String(other.to_string()))
+			}
+			other => Value::from_json(other.clone()).ok(),
+		}
+	}
+
+	/// Convert an input value from GraphQL to the model's JSON
+	fn to_json(self, value: &Value) -> GqlResult<JsonValue> {
+		let json = value.clone().into_json()?;
+		Ok(match json {
+			// IDs are strings in GraphQL but usually integers in models
+			JsonValue::String(s) if self == Self::Id => s
+				.parse::<i64>()
+				.map(JsonValue::from)
+				.unwrap_or(JsonValue::String(s)),
+			other => other,
+		})
+	}
+}
+
+/// A model field as exposed in the schema
+#[derive(Debug, Clone)]
+struct ModelField {
+	name: String,
+	graphql_name: String,
+	kind: ScalarKind,
+	nullable: bool,
+	required: bool,
+	writable: bool,
+}
+
+/// Convert a snake_case model field name to camelCase
+fn camel_case(name: &str) -> String {
+	let mut result = String::with_capacity(name.len());
+	let mut upper = false;
+	for c in name.chars() {
+		if c == '_' {
+			upper = !result.is_empty();
+		} else if upper {
+			result.extend(c.to_uppercase());
+			upper = false;
+		} else {
+			result.push(c);
+		}
+	}
+	result
+}
+
+/// Lowercase the first character of a type name
+fn lower_first(name: &str) -> String {
+	let mut chars = name.chars();
+	match chars.next() {
+		Some(first) => first.to_lowercase().chain(chars).collect(),
+		None => String::new(),
+	}
+}
+
+/// Configuration for exposing one model in a [`ModelSchemaBuilder`]
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use reinhardt_graphql::model::ModelType;
+/// # use reinhardt_db::orm::Model;
+/// # use serde::{Deserialize, Serialize};
+/// # #[derive(Debug, Clone, Serialize, Deserialize)]
+/// # struct Person { id: Option<i64>, name: String }
+/// # #[derive(Clone)]
+/// # struct PersonFields;
+/// # impl reinhardt_db::orm::model::FieldSelector for PersonFields {
+/// #     fn with_alias(self, _alias: &str) -> Self { self }
+/// # }
+/// # impl Model for Person {
+/// #     type PrimaryKey = i64;
+/// #     type Fields = PersonFields;
+/// #     fn table_name() -> &'static str { "people" }
+/// #     fn new_fields() -> Self::Fields { PersonFields }
+/// #     fn primary_key(&self) -> Option<Self::PrimaryKey> { self.id }
+/// #     fn set_primary_key(&mut self, value: Self::PrimaryKey) { self.id = Some(value); }
+/// # }
+///
+/// let person = ModelType::<Person>::new("Person")
+///     .with_plural("people")
+///     .exclude("password_hash")
+///     .read_only("created_at");
+/// ```
+pub struct ModelType<M: Model> {
+	type_name: String,
+	plural: Option<String>,
+	store: Arc<dyn ModelStore<M>>,
+	excluded: HashSet<String>,
+	read_only: HashSet<String>,
+}
+
+impl<M> ModelType<M>
+where
+	M: Model + 'static,
+	M::PrimaryKey: FromStr,
+{
+	/// Expose `M` as the GraphQL object type `type_name`, stored with [`OrmStore`]
+	pub fn new(type_name: impl Into<String>) -> Self {
+		Self {
+			type_name: type_name.into(),
+			plural: None,
+			store: Arc::new(OrmStore::<M>::new()),
+			excluded: HashSet::new(),
+			read_only: HashSet::new(),
+		}
+	}
+
+	/// Load and save models through `store` instead of the ORM
+	pub fn with_store(mut self, store: impl ModelStore<M> + 'static) -> Self {
+		self.store = Arc::new(store);
+		self
+	}
+
+	/// Set the list query name (defaults to the type name in lower camelCase
+	/// with an `s` appended)
+	pub fn with_plural(mut self, plural: impl Into<String>) -> Self {
+		self.plural = Some(plural.into());
+		self
+	}
+
+	/// Hide a model field from the schema
+	pub fn exclude(mut self, field: impl Into<String>) -> Self {
+		self.excluded.insert(field.into());
+		self
+	}
+
+	/// Expose a model field in queries but not in mutation inputs
+	pub fn read_only(mut self, field: impl Into<String>) -> Self {
+		self.read_only.insert(field.into());
+		self
+	}
+
+	fn fields(&self) -> Vec<ModelField> {
+		M::field_metadata()
+			.into_iter()
+			.filter(|field| !self.excluded.contains(&field.name))
+			.map(|field| {
+				let kind = ScalarKind::of(&field);
+				ModelField {
+					graphql_name: camel_case(&field.name),
+					kind,
+					nullable: field.nullable,
+					required: !field.nullable && !field.blank,
+					writable: kind != ScalarKind::Id
+						&& field.editable && !self.read_only.contains(&field.name),
+					name: field.name,
+				}
+			})
+			.collect()
+	}
+}
+
+/// Shared state of the resolvers generated for one model
+struct Resolvers<M: Model> {
+	type_name: String,
+	store: Arc<dyn ModelStore<M>>,
+	fields: Vec<ModelField>,
+	/// Filter input name -> (model field, lookup)
+	filters: HashMap<String, (ScalarKind, String, Lookup)>,
+}
+
+impl<M> Resolvers<M>
+where
+	M: Model + 'static,
+	M::PrimaryKey: FromStr,
+{
+	fn parse_pk(&self, ctx: &ResolverContext<'_>) -> GqlResult<M::PrimaryKey> {
+		let id = ctx.args.try_get("id")?.string()?;
+		id.parse()
+			.map_err(|_| Error::new(format!("Invalid {} ID: {}", self.type_name, id)))
+	}
+
+	fn to_field_value(model: &M) -> GqlResult<FieldValue<'static>> {
+		let row = serde_json::to_value(model)?;
+		Ok(FieldValue::owned_any(row))
+	}
+
+	/// Build a model from mutation input, starting from `base`
+	fn model_from_input(
+		&self,
+		input: ObjectAccessor<'_>,
+		mut base: serde_json::Map<String, JsonValue>,
+	) -> GqlResult<M> {
+		for field in self.fields.iter().filter(|field| field.writable) {
+			if let Some(value) = input.get(&field.graphql_name) {
+				base.insert(field.name.clone(), field.kind.to_json(value.as_value())?);
+			}
+		}
+		serde_json::from_value(JsonValue::Object(base))
+			.map_err(|e| Error::new(format!("Invalid {} input: {}", self.type_name, e)))
+	}
+
+	fn list_query(&self, ctx: &ResolverContext<'_>) -> GqlResult<ListQuery> {
+		let mut query = ListQuery::default();
+		if let Some(filter) = ctx.args.get("filter") {
+			for (name, value) in filter.object()?.iter() {
+				let Some((kind, field, lookup)) = self.filters.get(name.as_str()) else {
+					continue;
+				};
+				query.filters.push(FieldFilter {
+					field: field.clone(),
+					lookup: *lookup,
+					value: kind.to_json(value.as_value())?,
+				});
+			}
+		}
+		let count = |name: &str| -> GqlResult<Option<usize>> {
+			match ctx.args.get(name) {
+				Some(value) => {
+					let value = value.i64()?;
+					usize::try_from(value)
+						.map(Some)
+						.map_err(|_| Error::new(format!("{} must not be negative", name)))
+				}
+				None => Ok(None),
+			}
+		};
+		query.limit = count("limit")?;
+		query.offset = count("offset")?;
+		Ok(query)
+	}
+
+	async fn get(&self, ctx: &ResolverContext<'_>) -> GqlResult<Option<FieldValue<'static>>> {
+		let pk = self.parse_pk(ctx)?;
+		self.store
+			.get(pk)
+			.await?
+			.map(|model| Self::to_field_value(&model))
+			.transpose()
+	}
+
+	async fn list(&self, ctx: &ResolverContext<'_>) -> GqlResult<Option<FieldValue<'static>>> {
+		let query = self.list_query(ctx)?;
+		let models = self.store.list(&query).await?;
+		let values = models
+			.iter()
+			.map(Self::to_field_value)
+			.collect::<GqlResult<Vec<_>>>()?;
+		Ok(Some(FieldValue::list(values)))
+	}
+
+	async fn create(&self, ctx: &ResolverContext<'_>) -> GqlResult<Option<FieldValue<'static>>> {
+		let input = ctx.args.try_get("input")?.object()?;
+		let model = self.model_from_input(input, serde_json::Map::new())?;
+		let created = self.store.create(model).await?;
+		Self::to_field_value(&created).map(Some)
+	}
+
+	async fn update(&self, ctx: &ResolverContext<'_>) -> GqlResult<Option<FieldValue<'static>>> {
+		let pk = self.parse_pk(ctx)?;
+		let existing =
+			self.store.get(pk.clone()).await?.ok_or_else(|| {
+				Error::new(format!("{} with ID {} not found", self.type_name, pk))
+			})?;
+		let base = match serde_json::to_value(&existing)? {
+			JsonValue::Object(map) => map,
+			_ => {
+				return Err(Error::new(format!(
+					"{} must serialize to an object",
+					self.type_name
+				)));
+			}
+		};
+		let input = ctx.args.try_get("input")?.object()?;
+		let mut model = self.model_from_input(input, base)?;
+		model.set_primary_key(pk);
+		let updated = self.store.update(model).await?;
+		Self::to_field_value(&updated).map(Some)
+	}
+
+	async fn delete(&self, ctx: &ResolverContext<'_>) -> GqlResult<Option<FieldValue<'static>>> {
+		let pk = self.parse_pk(ctx)?;
+		let deleted = self.store.delete(pk).await?;
+		Ok(Some(FieldValue::value(deleted)))
+	}
+}
+
+/// Builds a GraphQL schema with CRUD operations for ORM models
+///
+/// See the [module documentation](self) for the generated types and
+/// operations.
+pub struct ModelSchemaBuilder {
+	query: Object,
+	mutation: Object,
+	types: Vec<async_graphql::dynamic::Type>,
+}
+
+impl ModelSchemaBuilder {
+	/// Create a builder with empty `Query` and `Mutation` roots
+	pub fn new() -> Self {
+		Self {
+			query: Object::new("Query"),
+			mutation: Object::new("Mutation"),
+			types: Vec::new(),
+		}
+	}
+
+	/// Add the types, queries and mutations for a model
+	pub fn register<M>(mut self, model: ModelType<M>) -> Self
+	where
+		M: Model + 'static,
+		M::PrimaryKey: FromStr,
+	{
+		let fields = model.fields();
+		let type_name = model.type_name.clone();
+		let single = lower_first(&type_name);
+		let plural = model
+			.plural
+			.clone()
+			.unwrap_or_else(|| format!("{}s", single));
+
+		let mut object = Object::new(&type_name);
+		let mut create_input = InputObject::new(format!("Create{}Input", type_name));
+		let mut update_input = InputObject::new(format!("Update{}Input", type_name));
+		let mut filter_input = InputObject::new(format!("{}Filter", type_name));
+		let mut filters = HashMap::new();
+
+		for field in &fields {
+			let scalar = field.kind.type_name();
+			let output_type = if field.nullable && field.kind != ScalarKind::Id {
+				TypeRef::named(scalar)
+			} else {
+				TypeRef::named_nn(scalar)
+			};
+			let (name, kind) = (field.name.clone(), field.kind);
+			object = object.field(Field::new(
+				field.graphql_name.clone(),
+				output_type,
+				move |ctx| {
+					let name = name.clone();
+					FieldFuture::new(async move {
+						let row = ctx.parent_value.try_downcast_ref::<JsonValue>()?;
+						Ok(kind.to_graphql(row.get(&name)).map(FieldValue::value))
+					})
+				},
+			));
+
+			if field.writable {
+				let create_type = if field.required {
+					TypeRef::named_nn(scalar)
+				} else {
+					TypeRef::named(scalar)
+				};
+				create_input =
+					create_input.field(InputValue::new(field.graphql_name.clone(), create_type));
+				update_input = update_input.field(InputValue::new(
+					field.graphql_name.clone(),
+					TypeRef::named(scalar),
+				));
+			}
+
+			for lookup in field.kind.lookups() {
+				let filter_name = format!("{}{}", field.graphql_name, lookup.suffix());
+				filter_input = filter_input
+					.field(InputValue::new(filter_name.clone(), TypeRef::named(scalar)));
+				filters.insert(filter_name, (field.kind, field.name.clone(), *lookup));
+			}
+		}
+
+		let resolvers = Arc::new(Resolvers {
+			type_name: type_name.clone(),
+			store: Arc::clone(&model.store),
+			fields,
+			filters,
+		});
+		let id_argument = || InputValue::new("id", TypeRef::named_nn(TypeRef::ID));
+
+		let r = Arc::clone(&resolvers);
+		self.query = self.query.field(
+			Field::new(single.clone(), TypeRef::named(&type_name), move |ctx| {
+				let r = Arc::clone(&r);
+				FieldFuture::new(async move { r.get(&ctx).await })
+			})
+			.argument(id_argument()),
+		);
+
+		let r = Arc::clone(&resolvers);
+		self.query = self.query.field(
+			Field::new(plural, TypeRef::named_nn_list_nn(&type_name), move |ctx| {
+				let r = Arc::clone(&r);
+				FieldFuture::new(async move { r.list(&ctx).await })
+			})
+			.argument(InputValue::new(
+				"filter",
+				TypeRef::named(filter_input.type_name()),
+			))
+			.argument(InputValue::new("limit", TypeRef::named(TypeRef::INT)))
+			.argument(InputValue::new("offset", TypeRef::named(TypeRef::INT))),
+		);
+
+		let r = Arc::clone(&resolvers);
+		self.mutation = self.mutation.field(
+			Field::new(
+				format!("create{}", type_name),
+				TypeRef::named_nn(&type_name),
+				move |ctx| {
+					let r = Arc::clone(&r);
+					FieldFuture::new(async move { r.create(&ctx).await })
+				},
+			)
+			.argument(InputValue::new(
+				"input",
+				TypeRef::named_nn(create_input.type_name()),
+			)),
+		);
+
+		let r = Arc::clone(&resolvers);
+		self.mutation = self.mutation.field(
+			Field::new(
+				format!("update{}", type_name),
+				TypeRef::named_nn(&type_name),
+				move |ctx| {
+					let r = Arc::clone(&r);
+					FieldFuture::new(async move { r.update(&ctx).await })
+				},
+			)
+			.argument(id_argument())
+			.argument(InputValue::new(
+				"input",
+				TypeRef::named_nn(update_input.type_name()),
+			)),
+		);
+
+		let r = Arc::clone(&resolvers);
+		self.mutation = self.mutation.field(
+			Field::new(
+				format!("delete{}", type_name),
+				TypeRef::named_nn(TypeRef::BOOLEAN),
+				move |ctx| {
+					let r = Arc::clone(&r);
+					FieldFuture::new(async move { r.delete(&ctx).await })
+				},
+			)
+			.argument(id_argument()),
+		);
+
+		self.types.push(object.into());
+		self.types.push(create_input.into());
+		self.types.push(update_input.into());
+		self.types.push(filter_input.into());
+		self
+	}
+
+	/// Convert into an async-graphql schema builder, for adding data,
+	/// extensions or further types before finishing the schema
+	pub fn into_schema_builder(self) -> SchemaBuilder {
+		let builder = Schema::build("Query", Some("Mutation"), None)
+			.register(self.query)
+			.register(self.mutation);
+		self.types
+			.into_iter()
+			.fold(builder, |builder, ty| builder.register(ty))
+	}
+
+	/// Build the schema
+	///
+	/// # Errors
+	///
+	/// Fails if the generated types are invalid, for example when no model
+	/// was registered or a model exposes no fields.
+	pub fn finish(self) -> Result<Schema, SchemaError> {
+		self.into_schema_builder().finish()
+	}
+}
+
+impl Default for ModelSchemaBuilder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+	use serde_json::json;
+
+	#[rstest]
+	#[case("title", "title")]
+	#[case("created_at", "createdAt")]
+	#[case("author_id", "authorId")]
+	#[case("_private", "private")]
+	fn test_camel_case(#[case] input: &str, #[case] expected: &str) {
+		assert_eq!(camel_case(input), expected);
+	}
+
+	#[rstest]
+	#[case(Lookup::Exact, json!("a"), json!("a"), true)]
+	#[case(Lookup::Exact, json!(null), json!(null), true)]
+	#[case(Lookup::Contains, json!("rustacean"), json!("rust"), true)]
+	#[case(Lookup::Contains, json!(1), json!("1"), false)]
+	#[case(Lookup::Gt, json!(2.5), json!(2), true)]
+	#[case(Lookup::Lte, json!(2), json!(2), true)]
+	#[case(Lookup::Lt, json!("2024-01-01"), json!("2025-01-01"), true)]
+	#[case(Lookup::Gte, json!(null), json!(1), false)]
+	fn test_field_filter_matches(
+		#[case] lookup: Lookup,
+		#[case] actual: JsonValue,
+		#[case] value: JsonValue,
+		#[case] expected: bool,
+	) {
+		let filter = FieldFilter {
+			field: "f".to_string(),
+			lookup,
+			value,
+		};
+
+		assert_eq!(filter.matches(&json!({ "f": actual })), expected);
+	}
+
+	#[test]
+	fn test_orm_filter_for_null_uses_is_null() {
+		let filter = FieldFilter {
+			field: "deleted_at".to_string(),
+			lookup: Lookup::Exact,
+			value: JsonValue::Null,
+		};
+
+		let orm_filter = filter.to_orm_filter();
+
+		assert!(matches!(orm_filter.operator, FilterOperator::IsNull));
+	}
+}
@@ -0,0 +1,152 @@
+//! Permission checks for GraphQL resolvers
+//!
+//! GraphQL resolvers use the same Django-style permission strings
+//! (`"app_label.codename"`) and the same user as REST views: the
+//! authentication middleware stores the user in the HTTP request extensions
+//! as `Arc<dyn PermissionsMixin>`, and [`PermissionRequestExt::with_request_user`]
+//! carries it over into the GraphQL request data.
+//!
+//! Permissions can then be enforced in several ways:
+//!
+//! - `#[graphql_permission("app.view_model")]` on a resolver taking a
+//!   `&Context<'_>` parameter
+//! - [`PermissionGuard`] as an async-graphql field guard
+//! - [`check_permissions`] inside resolver bodies
+//! - [`has_permissions`] in `#[graphql(visible = "...")]` functions, hiding
+//!   fields from introspection for users who may not read them (combine with
+//!   a guard, since hidden fields can still be queried)
+//!
+//! # Examples
+//!
+//! ```
+//! use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Request, Result, Schema};
+//! use reinhardt_auth::PermissionsMixin;
+//! use reinhardt_graphql::graphql_permission;
+//! use std::sync::Arc;
+//!
+//! struct Editor(Vec<String>);
+//!
+//! impl PermissionsMixin for Editor {
+//!     fn is_superuser(&self) -> bool {
+//!         false
+//!     }
+//!     fn user_permissions(&self) -> &[String] {
+//!         &self.0
+//!     }
+//!     fn groups(&self) -> &[String] {
+//!         &[]
+//!     }
+//! }
+//!
+//! struct Query;
+//!
+//! #[Object]
+//! impl Query {
+//!     #[graphql_permission("blog.view_article")]
+//!     async fn article_count(&self, ctx: &Context<'_>) -> Result<i32> {
+//!         Ok(3)
+//!     }
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+//!
+//! let anonymous = schema.execute("{ articleCount }").await;
+//! assert_eq!(anonymous.errors[0].message, "Authentication required");
+//!
+//! let user: Arc<dyn PermissionsMixin> = Arc::new(Editor(vec!["blog.view_article".to_string()]));
+//! let response = schema.execute(Request::new("{ articleCount }").data(user)).await;
+//! assert!(response.is_ok());
+//! # });
+//! ```
+
+use async_graphql::{Context, Error, ErrorExtensions, Guard, Request, Result};
+use reinhardt_auth::PermissionsMixin;
+use std::sync::Arc;
+
+/// Read the authenticated user stored by the authentication middleware
+pub fn user_from_request(request: &reinhardt_http::Request) -> Option<Arc<dyn PermissionsMixin>> {
+	request.extensions.get::<Arc<dyn PermissionsMixin>>()
+}
+
+/// Extension for passing the HTTP request's user to GraphQL resolvers
+pub trait PermissionRequestExt {
+	/// Add the user authenticated on `request`, if any, to the request data
+	fn with_request_user(self, request: &reinhardt_http::Request) -> Self;
+}
+
+impl PermissionRequestExt for Request {
+	fn with_request_user(self, request: &reinhardt_http::Request) -> Self {
+		match user_from_request(request) {
+			Some(user) => self.data(user),
+			None => self,
+		}
+	}
+}
+
+/// Whether the current user holds all of `permissions`
+///
+/// Anonymous requests hold no permissions. Superusers hold all of them.
+pub fn has_permissions(ctx: &Context<'_>, permissions: &[&str]) -> bool {
+	ctx.data_opt::<Arc<dyn PermissionsMixin>>()
+		.is_some_and(|user| user.has_perms(permissions))
+}
+
+/// Fail unless the current user holds all of `permissions`
+///
+/// The error carries a `code` extension of `UNAUTHENTICATED` for anonymous
+/// requests and `FORBIDDEN` for missing permissions.
+pub fn check_permissions(ctx: &Context<'_>, permissions: &[&str]) -> Result<()> {
+	let user = ctx.data_opt::<Arc<dyn PermissionsMixin>>().ok_or_else(|| {
+		Error::new("Authentication required").extend_with(|_, e| e.set("code", "UNAUTHENTICATED"))
+	})?;
+	if !user.has_perms(permissions) {
+		return Err(Error::new(format!(
+			"Permission denied. Required permissions: {}",
+			permissions.join(", ")
+		))
+		.extend_with(|_, e| e.set("code", "FORBIDDEN")));
+	}
+	Ok(())
+}
+
+/// Field guard requiring a set of permissions
+///
+/// # Examples
+///
+/// ```
+/// use async_graphql::SimpleObject;
+/// use reinhardt_graphql::permission::PermissionGuard;
+///
+/// #[derive(SimpleObject)]
+/// struct Employee {
+///     name: String,
+///     #[graphql(guard = "PermissionGuard::new(&[\"hr.view_salary\"])")]
+///     salary: i64,
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PermissionGuard {
+	permissions: Vec<String>,
+}
+
+impl PermissionGuard {
+	/// Require all of `permissions`
+	pub fn new(permissions: &[&str]) -> Self {
+		Self {
+			permissions: permissions.iter().map(|p| p.to_string()).collect(),
+		}
+	}
+
+	/// Permissions required by this guard
+	pub fn permissions(&self) -> &[String] {
+		&self.permissions
+	}
+}
+
+impl Guard for PermissionGuard {
+	async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+		let permissions: Vec<&str> = self.permissions.iter().map(String::as_str).collect();
+		check_permissions(ctx, &permissions)
+	}
+}
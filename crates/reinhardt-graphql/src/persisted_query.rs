@@ -0,0 +1,221 @@
+//! Automatic persisted queries (APQ)
+//!
+//! [`PersistedQueries`] implements the [Apollo APQ protocol][apq]: clients
+//! send only the SHA-256 hash of a query in the `persistedQuery` request
+//! extension. Unknown hashes are answered with a `PersistedQueryNotFound`
+//! error, after which the client retries with the full query and the hash,
+//! and the server stores the query document for later hash-only requests.
+//!
+//! Documents are stored in any `reinhardt_utils::cache::Cache` backend, so a
+//! Redis or Memcached cache shares registrations across server instances.
+//!
+//! [apq]: https://www.apollographql.com/docs/apollo-server/performance/apq
+//!
+//! # Examples
+//!
+//! ```
+//! use async_graphql::{EmptyMutation, EmptySubscription, Object, Request, Schema, Value};
+//! use reinhardt_graphql::persisted_query::{PersistedQueries, persisted_query_hash};
+//! use reinhardt_utils::cache::InMemoryCache;
+//! use serde_json::json;
+//! use std::sync::Arc;
+//!
+//! struct Query;
+//!
+//! #[Object]
+//! impl Query {
+//!     async fn version(&self) -> &str {
+//!         "1.0"
+//!     }
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+//!     .extension(PersistedQueries::new(Arc::new(InMemoryCache::new())))
+//!     .finish();
+//!
+//! let query = "{ version }";
+//! let extension = json!({ "version": 1, "sha256Hash": persisted_query_hash(query) });
+//! let persisted = |query: &str| {
+//!     let mut request = Request::new(query);
+//!     request
+//!         .extensions
+//!         .insert("persistedQuery".to_string(), Value::from_json(extension.clone()).unwrap());
+//!     request
+//! };
+//!
+//! // Unknown hash: the client must register the query
+//! let response = schema.execute(persisted("")).await;
+//! assert_eq!(response.errors[0].message, "PersistedQueryNotFound");
+//!
+//! // Registration with the full query
+//! assert!(schema.execute(persisted(query)).await.is_ok());
+//!
+//! // Later requests send only the hash
+//! let response = schema.execute(persisted("")).await;
+//! assert_eq!(response.data.into_json().unwrap(), json!({ "version": "1.0" }));
+//! # });
+//! ```
+
+use async_graphql::extensions::{
+	Extension, ExtensionContext, ExtensionFactory, NextPrepareRequest,
+};
+use async_graphql::{ErrorExtensionValues, Request, ServerError, ServerResult};
+use async_trait::async_trait;
+use reinhardt_utils::cache::Cache;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Error message clients expect for unregistered hashes
+pub const PERSISTED_QUERY_NOT_FOUND: &str = "PersistedQueryNotFound";
+
+/// Default prefix of cache keys holding query documents
+pub const DEFAULT_KEY_PREFIX: &str = "graphql:apq:";
+
+/// SHA-256 hash of a query document, as lowercase hex
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_graphql::persisted_query::persisted_query_hash;
+///
+/// assert_eq!(
+///     persisted_query_hash("{ version }"),
+///     "1dee97279832c351624025387be36873845c282288b1f0a51ccf63e6b5f7549f"
+/// );
+/// ```
+pub fn persisted_query_hash(query: &str) -> String {
+	format!("{:x}", Sha256::digest(query.as_bytes()))
+}
+
+/// Schema extension serving automatic persisted queries from a cache
+///
+/// See the [module documentation](self) for the protocol.
+pub struct PersistedQueries<C> {
+	cache: Arc<C>,
+	ttl: Option<Duration>,
+	key_prefix: String,
+}
+
+impl<C> PersistedQueries<C>
+where
+	C: Cache + 'static,
+{
+	/// Store query documents in `cache` without expiry
+	pub fn new(cache: Arc<C>) -> Self {
+		Self {
+			cache,
+			ttl: None,
+			key_prefix: DEFAULT_KEY_PREFIX.to_string(),
+		}
+	}
+
+	/// Expire stored documents after `ttl`
+	///
+	/// Clients re-register expired queries transparently.
+	pub fn with_ttl(mut self, ttl: Duration) -> Self {
+		self.ttl = Some(ttl);
+		self
+	}
+
+	/// Prefix cache keys with `prefix` instead of [`DEFAULT_KEY_PREFIX`]
+	pub fn with_key_prefix(mut self, prefix: impl Into<String>) -> Self {
+		self.key_prefix = prefix.into();
+		self
+	}
+}
+
+impl<C> ExtensionFactory for PersistedQueries<C>
+where
+	C: Cache + 'static,
+{
+	fn create(&self) -> Arc<dyn Extension> {
+		Arc::new(PersistedQueriesExtension {
+			cache: Arc::clone(&self.cache),
+			ttl: self.ttl,
+			key_prefix: self.key_prefix.clone(),
+		})
+	}
+}
+
+struct PersistedQueriesExtension<C> {
+	cache: Arc<C>,
+	ttl: Option<Duration>,
+	key_prefix: String,
+}
+
+/// `persistedQuery` request extension
+#[derive(serde::Deserialize)]
+struct PersistedQuery {
+	version: i32,
+	#[serde(rename = "sha256Hash")]
+	sha256_hash: String,
+}
+
+/// Build an error carrying an APQ error `code` extension
+fn apq_error(message: impl Into<String>, code: &str) -> ServerError {
+	let mut error = ServerError::new(message, None);
+	let mut extensions = ErrorExtensionValues::default();
+	extensions.set("code", code);
+	error.extensions = Some(extensions);
+	error
+}
+
+fn cache_error(error: impl std::fmt::Display) -> ServerError {
+	ServerError::new(format!("Persisted query cache error: {}", error), None)
+}
+
+#[async_trait]
+impl<C> Extension for PersistedQueriesExtension<C>
+where
+	C: Cache + 'static,
+{
+	async fn prepare_request(
+		&self,
+		ctx: &ExtensionContext<'_>,
+		mut request: Request,
+		next: NextPrepareRequest<'_>,
+	) -> ServerResult<Request> {
+		let Some(value) = request.extensions.remove("persistedQuery") else {
+			return next.run(ctx, request).await;
+		};
+		let persisted: PersistedQuery = async_graphql::from_value(value).map_err(|_| {
+			apq_error(
+				"Invalid \"persistedQuery\" extension",
+				"PERSISTED_QUERY_INVALID",
+			)
+		})?;
+		if persisted.version != 1 {
+			return Err(apq_error(
+				format!(
+					"Unsupported persisted query version {}, only version 1 is supported",
+					persisted.version
+				),
+				"PERSISTED_QUERY_INVALID",
+			));
+		}
+		let hash = persisted.sha256_hash.to_ascii_lowercase();
+		let key = format!("{}{}", self.key_prefix, hash);
+
+		if request.query.is_empty() {
+			let query: Option<String> = self.cache.get(&key).await.map_err(cache_error)?;
+			request.query = query
+				.ok_or_else(|| apq_error(PERSISTED_QUERY_NOT_FOUND, "PERSISTED_QUERY_NOT_FOUND"))?;
+		} else {
+			if persisted_query_hash(&request.query) != hash {
+				return Err(apq_error(
+					"Provided sha256Hash does not match query",
+					"PERSISTED_QUERY_HASH_MISMATCH",
+				));
+			}
+			// Only register documents that parse
+			request.parsed_query()?;
+			self.cache
+				.set(&key, &request.query, self.ttl)
+				.await
+				.map_err(cache_error)?;
+		}
+		next.run(ctx, request).await
+	}
+}
@@ -0,0 +1,306 @@
+//! GraphQL over WebSocket for browser subscriptions
+//!
+//! [`GraphQLWsConsumer`] is a `reinhardt-websockets` consumer speaking both
+//! GraphQL WebSocket protocols, so browser clients such as `graphql-ws` and
+//! Apollo's `subscriptions-transport-ws` can subscribe without a gRPC hop:
+//!
+//! - `graphql-transport-ws`: the current [graphql-ws protocol][graphql-ws]
+//! - `graphql-ws`: the legacy [subscriptions-transport-ws protocol][legacy]
+//!
+//! The protocol is chosen from the connection's negotiated subprotocol; use
+//! [`negotiate_subprotocol`] on the client's `Sec-WebSocket-Protocol` header
+//! during the handshake. Connections without a subprotocol use
+//! `graphql-transport-ws`.
+//!
+//! With an authenticator set, the `connection_init` payload must carry a
+//! token (`token`, `authToken` or a bearer `Authorization` value). The
+//! authenticated user is available to resolvers as
+//! `ctx.data::<Arc<dyn AuthUser>>()`.
+//!
+//! [graphql-ws]: https://github.com/enisdenjo/graphql-ws/blob/master/PROTOCOL.md
+//! [legacy]: https://github.com/apollographql/subscriptions-transport-ws/blob/master/PROTOCOL.md
+//!
+//! # Examples
+//!
+//! ```
+//! use async_graphql::{EmptyMutation, Object, Schema, Subscription};
+//! use futures_util::Stream;
+//! use reinhardt_graphql::websocket::GraphQLWsConsumer;
+//! use reinhardt_websockets::consumers::{ConsumerContext, WebSocketConsumer};
+//! use reinhardt_websockets::{Message, WebSocketConnection};
+//! use std::sync::Arc;
+//! use tokio::sync::mpsc;
+//!
+//! struct Query;
+//!
+//! #[Object]
+//! impl Query {
+//!     async fn version(&self) -> &str {
+//!         "1.0"
+//!     }
+//! }
+//!
+//! struct Subscription;
+//!
+//! #[Subscription]
+//! impl Subscription {
+//!     async fn ticks(&self) -> impl Stream<Item = i32> {
+//!         futures_util::stream::iter(1..=3)
+//!     }
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let consumer = GraphQLWsConsumer::new(Schema::new(Query, EmptyMutation, Subscription));
+//!
+//! let (tx, mut rx) = mpsc::unbounded_channel();
+//! let connection = Arc::new(WebSocketConnection::with_subprotocol(
+//!     "client-1".to_string(),
+//!     tx,
+//!     Some("graphql-transport-ws".to_string()),
+//! ));
+//! let mut context = ConsumerContext::new(connection);
+//!
+//! consumer.on_connect(&mut context).await.unwrap();
+//! consumer
+//!     .on_message(&mut context, Message::text(r#"{"type":"connection_init"}"#.to_string()))
+//!     .await
+//!     .unwrap();
+//!
+//! let ack = rx.recv().await.unwrap();
+//! assert_eq!(ack, Message::text(r#"{"type":"connection_ack"}"#.to_string()));
+//! # consumer.on_disconnect(&mut context).await.unwrap();
+//! # });
+//! ```
+
+use async_graphql::http::{WebSocket, WebSocketProtocols, WsMessage};
+use async_graphql::{Data, Error, Executor};
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use reinhardt_websockets::auth::{AuthUser, WebSocketAuthenticator};
+use reinhardt_websockets::consumers::{ConsumerContext, WebSocketConsumer};
+use reinhardt_websockets::{Message, WebSocketConnection, WebSocketError, WebSocketResult};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+/// Subprotocols accepted by [`GraphQLWsConsumer`], in order of preference
+pub const GRAPHQL_WS_SUBPROTOCOLS: [&str; 2] = async_graphql::http::ALL_WEBSOCKET_PROTOCOLS;
+
+/// Pick the subprotocol to accept from a `Sec-WebSocket-Protocol` header
+///
+/// Returns the first protocol requested by the client that is supported, or
+/// `None` if the client requested none of them.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_graphql::websocket::negotiate_subprotocol;
+///
+/// assert_eq!(
+///     negotiate_subprotocol("graphql-transport-ws, graphql-ws"),
+///     Some("graphql-transport-ws")
+/// );
+/// assert_eq!(negotiate_subprotocol("graphql-ws"), Some("graphql-ws"));
+/// assert_eq!(negotiate_subprotocol("chat"), None);
+/// ```
+pub fn negotiate_subprotocol(requested: &str) -> Option<&'static str> {
+	requested.split(',').map(str::trim).find_map(|protocol| {
+		GRAPHQL_WS_SUBPROTOCOLS
+			.into_iter()
+			.find(|supported| supported.eq_ignore_ascii_case(protocol))
+	})
+}
+
+/// Extract the auth token from a `connection_init` payload
+fn auth_token(payload: &Value) -> Option<&str> {
+	["token", "authToken", "Authorization", "authorization"]
+		.iter()
+		.find_map(|key| payload.get(key)?.as_str())
+		.map(|token| token.strip_prefix("Bearer ").unwrap_or(token))
+}
+
+/// WebSocket consumer serving GraphQL queries, mutations and subscriptions
+///
+/// One consumer serves any number of connections; each connection runs its
+/// operations in its own task until the client disconnects. See the
+/// [module documentation](self) for protocols and authentication.
+pub struct GraphQLWsConsumer<E> {
+	executor: E,
+	authenticator: Option<Arc<dyn WebSocketAuthenticator>>,
+	keepalive_timeout: Option<Duration>,
+	/// Incoming messages of each open connection, by connection ID
+	connections: Mutex<HashMap<String, mpsc::UnboundedSender<String>>>,
+}
+
+impl<E> GraphQLWsConsumer<E>
+where
+	E: Executor + Clone,
+{
+	/// Serve `executor`, usually an `async_graphql::Schema`
+	pub fn new(executor: E) -> Self {
+		Self {
+			executor,
+			authenticator: None,
+			keepalive_timeout: None,
+			connections: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Require clients to authenticate in their `connection_init` message
+	pub fn with_authenticator(mut self, authenticator: Arc<dyn WebSocketAuthenticator>) -> Self {
+		self.authenticator = Some(authenticator);
+		self
+	}
+
+	/// Close connections that send nothing for `timeout`
+	///
+	/// Only applies to `graphql-transport-ws`, where clients ping to stay
+	/// connected.
+	pub fn with_keepalive_timeout(mut self, timeout: Duration) -> Self {
+		self.keepalive_timeout = Some(timeout);
+		self
+	}
+
+	/// Number of connections currently served
+	pub fn connection_count(&self) -> usize {
+		self.connections
+			.lock()
+			.unwrap_or_else(|e| e.into_inner())
+			.len()
+	}
+
+	fn incoming(&self, connection_id: &str) -> Option<mpsc::UnboundedSender<String>> {
+		self.connections
+			.lock()
+			.unwrap_or_else(|e| e.into_inner())
+			.get(connection_id)
+			.cloned()
+	}
+
+	fn spawn_session(
+		&self,
+		connection: Arc<WebSocketConnection>,
+		protocol: WebSocketProtocols,
+		incoming: mpsc::UnboundedReceiver<String>,
+	) {
+		let authenticator = self.authenticator.clone();
+		let init_connection = Arc::clone(&connection);
+		let mut stream = WebSocket::new(
+			self.executor.clone(),
+			UnboundedReceiverStream::new(incoming),
+			protocol,
+		)
+		.keepalive_timeout(self.keepalive_timeout)
+		.on_connection_init(move |payload| async move {
+			let mut data = Data::default();
+			if let Some(authenticator) = authenticator {
+				let token = auth_token(&payload)
+					.ok_or_else(|| Error::new("Forbidden: missing authentication token"))?;
+				let user = authenticator
+					.authenticate(&init_connection, token)
+					.await
+					.map_err(|e| Error::new(format!("Forbidden: {}", e)))?;
+				data.insert(Arc::<dyn AuthUser>::from(user));
+			}
+			Ok(data)
+		});
+
+		tokio::spawn(async move {
+			while let Some(message) = stream.next().await {
+				let result = match message {
+					WsMessage::Text(text) => connection.send_text(text).await,
+					WsMessage::Close(code, reason) => {
+						let _ = connection.send(Message::Close { code, reason }).await;
+						break;
+					}
+				};
+				if result.is_err() {
+					break;
+				}
+			}
+		});
+	}
+}
+
+#[async_trait]
+impl<E> WebSocketConsumer for GraphQLWsConsumer<E>
+where
+	E: Executor + Clone,
+{
+	async fn on_connect(&self, context: &mut ConsumerContext) -> WebSocketResult<()> {
+		let protocol = match context.connection.subprotocol() {
+			Some(subprotocol) => subprotocol
+				.parse()
+				.map_err(|e: Error| WebSocketError::Protocol(e.message))?,
+			None => WebSocketProtocols::GraphQLWS,
+		};
+
+		let (sender, receiver) = mpsc::unbounded_channel();
+		self.connections
+			.lock()
+			.unwrap_or_else(|e| e.into_inner())
+			.insert(context.connection.id().to_string(), sender);
+		self.spawn_session(Arc::clone(&context.connection), protocol, receiver);
+		Ok(())
+	}
+
+	async fn on_message(
+		&self,
+		context: &mut ConsumerContext,
+		message: Message,
+	) -> WebSocketResult<()> {
+		let text = match message {
+			Message::Text { data } => data,
+			Message::Binary { data } => String::from_utf8(data)
+				.map_err(|_| WebSocketError::Protocol("Binary message is not UTF-8".to_string()))?,
+			Message::Ping | Message::Pong | Message::Close { .. } => return Ok(()),
+		};
+		let incoming = self.incoming(context.connection.id()).ok_or_else(|| {
+			WebSocketError::Connection(format!(
+				"Connection {} is not open",
+				context.connection.id()
+			))
+		})?;
+		incoming
+			.send(text)
+			.map_err(|_| WebSocketError::Connection("GraphQL session has ended".to_string()))
+	}
+
+	async fn on_disconnect(&self, context: &mut ConsumerContext) -> WebSocketResult<()> {
+		// Dropping the sender ends the session and all of its subscriptions
+		self.connections
+			.lock()
+			.unwrap_or_else(|e| e.into_inner())
+			.remove(context.connection.id());
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+	use serde_json::json;
+
+	#[rstest]
+	#[case("graphql-transport-ws", Some("graphql-transport-ws"))]
+	#[case("chat, GraphQL-WS", Some("graphql-ws"))]
+	#[case("graphql-ws, graphql-transport-ws", Some("graphql-ws"))]
+	#[case("", None)]
+	fn test_negotiate_subprotocol(#[case] requested: &str, #[case] expected: Option<&str>) {
+		assert_eq!(negotiate_subprotocol(requested), expected);
+	}
+
+	#[rstest]
+	#[case(json!({ "token": "abc" }), Some("abc"))]
+	#[case(json!({ "authToken": "abc" }), Some("abc"))]
+	#[case(json!({ "Authorization": "Bearer abc" }), Some("abc"))]
+	#[case(json!({ "token": 1 }), None)]
+	#[case(json!(null), None)]
+	fn test_auth_token(#[case] payload: Value, #[case] expected: Option<&str>) {
+		assert_eq!(auth_token(&payload), expected);
+	}
+}
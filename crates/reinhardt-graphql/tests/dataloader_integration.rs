@@ -2,11 +2,13 @@
 //!
 //! Tests DataLoader batch loading, caching, and N+1 query problem mitigation.
 
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Schema};
 use async_trait::async_trait;
-use reinhardt_graphql::context::{DataLoader, LoaderError};
+use reinhardt_graphql::context::{BatchLoader, DataLoader, LoaderError};
 use rstest::*;
-use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 /// Test DataLoader that tracks load calls for N+1 verification
 struct TestUserLoader {
@@ -247,3 +249,152 @@ async fn test_dataloader_performance_comparison() {
 		"Batch load should be faster than individual loads"
 	);
 }
+
+/// Loader recording the keys of every `load_many` call
+#[derive(Clone, Default)]
+struct RecordingLoader {
+	batches: Arc<Mutex<Vec<Vec<u32>>>>,
+}
+
+impl RecordingLoader {
+	fn batches(&self) -> Vec<Vec<u32>> {
+		let mut batches = self.batches.lock().unwrap().clone();
+		for batch in &mut batches {
+			batch.sort();
+		}
+		batches
+	}
+}
+
+#[async_trait]
+impl DataLoader for RecordingLoader {
+	type Key = u32;
+	type Value = String;
+
+	async fn load(&self, key: Self::Key) -> Result<Self::Value, LoaderError> {
+		Ok(format!("Author {}", key))
+	}
+
+	async fn load_many(&self, keys: Vec<Self::Key>) -> Result<Vec<Self::Value>, LoaderError> {
+		if keys.contains(&0) {
+			return Err(LoaderError::NotFound("0".to_string()));
+		}
+		self.batches.lock().unwrap().push(keys.clone());
+		Ok(keys.into_iter().map(|k| format!("Author {}", k)).collect())
+	}
+}
+
+/// Test: concurrent loads are collected into one deduplicated batch
+#[rstest]
+#[tokio::test]
+async fn test_batch_loader_collects_keys_in_one_batch() {
+	let inner = RecordingLoader::default();
+	let loader = BatchLoader::new(inner.clone());
+
+	let results = futures_util::future::join_all([3, 1, 3, 2].map(|key| loader.load(key))).await;
+
+	let results: Vec<String> = results.into_iter().map(Result::unwrap).collect();
+	assert_eq!(results, ["Author 3", "Author 1", "Author 3", "Author 2"]);
+	assert_eq!(inner.batches(), vec![vec![1, 2, 3]]);
+}
+
+/// Test: loaded values are cached for later loads
+#[rstest]
+#[tokio::test]
+async fn test_batch_loader_caches_values() {
+	let inner = RecordingLoader::default();
+	let loader = BatchLoader::new(inner.clone());
+
+	loader.load_many(vec![1, 2]).await.unwrap();
+	let again = loader.load_many(vec![2, 1, 4]).await.unwrap();
+
+	assert_eq!(again, ["Author 2", "Author 1", "Author 4"]);
+	assert_eq!(inner.batches(), vec![vec![1, 2], vec![4]]);
+}
+
+/// Test: primed and cleared keys control what is fetched
+#[rstest]
+#[tokio::test]
+async fn test_batch_loader_prime_and_clear() {
+	let inner = RecordingLoader::default();
+	let loader = BatchLoader::new(inner.clone());
+
+	loader.prime(7, "Primed".to_string());
+	assert_eq!(loader.load(7).await.unwrap(), "Primed");
+	assert!(inner.batches().is_empty());
+
+	loader.clear(&7);
+	assert_eq!(loader.load(7).await.unwrap(), "Author 7");
+	assert_eq!(inner.batches(), vec![vec![7]]);
+}
+
+/// Test: batches are split at the maximum batch size
+#[rstest]
+#[tokio::test]
+async fn test_batch_loader_max_batch_size() {
+	let inner = RecordingLoader::default();
+	let loader = BatchLoader::new(inner.clone())
+		.with_max_batch_size(2)
+		.with_delay(Duration::from_millis(5));
+
+	loader.load_many((1..=5).collect()).await.unwrap();
+
+	let mut sizes: Vec<usize> = inner.batches().iter().map(Vec::len).collect();
+	sizes.sort();
+	assert_eq!(sizes, vec![1, 2, 2]);
+}
+
+/// Test: a failed batch reports the error to every waiter without caching it
+#[rstest]
+#[tokio::test]
+async fn test_batch_loader_error_is_shared_and_not_cached() {
+	let inner = RecordingLoader::default();
+	let loader = BatchLoader::new(inner.clone());
+
+	let (a, b) = tokio::join!(loader.load(0), loader.load(1));
+
+	assert!(matches!(a, Err(LoaderError::NotFound(ref key)) if key == "0"));
+	assert!(matches!(b, Err(LoaderError::NotFound(_))));
+	assert_eq!(loader.load(1).await.unwrap(), "Author 1");
+}
+
+struct Post {
+	author_id: u32,
+}
+
+#[Object]
+impl Post {
+	async fn author(&self, ctx: &Context<'_>) -> async_graphql::Result<String> {
+		let loader = ctx.data_unchecked::<BatchLoader<RecordingLoader>>();
+		Ok(loader.load(self.author_id).await?)
+	}
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+	async fn posts(&self) -> Vec<Post> {
+		[1, 2, 1, 3, 2]
+			.into_iter()
+			.map(|author_id| Post { author_id })
+			.collect()
+	}
+}
+
+/// Test: nested resolvers share one batch instead of one query per post
+#[rstest]
+#[tokio::test]
+async fn test_batch_loader_prevents_n_plus_1_in_nested_resolvers() {
+	let inner = RecordingLoader::default();
+	let schema = Schema::build(Query, EmptyMutation, EmptySubscription)
+		.data(BatchLoader::new(inner.clone()))
+		.finish();
+
+	let response = schema.execute("{ posts { author } }").await;
+
+	assert!(response.errors.is_empty(), "{:?}", response.errors);
+	let data = response.data.into_json().unwrap();
+	assert_eq!(data["posts"][3]["author"], "Author 3");
+	assert_eq!(inner.batches(), vec![vec![1, 2, 3]]);
+}
@@ -0,0 +1,321 @@
+//! Integration tests for GraphQL schemas generated from ORM models
+//!
+//! These tests verify that `ModelSchemaBuilder` exposes a model's fields and
+//! CRUD operations, using an in-memory `ModelStore` in place of the database.
+
+#![cfg(feature = "orm")]
+
+use async_graphql::Result;
+use async_graphql::dynamic::Schema;
+use async_trait::async_trait;
+use reinhardt_db::orm::Model;
+use reinhardt_db::orm::inspection::FieldInfo;
+use reinhardt_db::orm::model::FieldSelector;
+use reinhardt_graphql::model::{ListQuery, ModelSchemaBuilder, ModelStore, ModelType};
+use rstest::*;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Article {
+	id: Option<i64>,
+	title: String,
+	#[serde(default)]
+	views: i64,
+	published: bool,
+	#[serde(default)]
+	summary: Option<String>,
+	#[serde(default)]
+	secret_note: String,
+}
+
+#[derive(Clone)]
+struct ArticleFields;
+
+impl FieldSelector for ArticleFields {
+	fn with_alias(self, _alias: &str) -> Self {
+		self
+	}
+}
+
+fn field(name: &str, field_type: &str, primary_key: bool, nullable: bool) -> FieldInfo {
+	FieldInfo {
+		name: name.to_string(),
+		field_type: format!("reinhardt.orm.models.{}", field_type),
+		nullable,
+		primary_key,
+		unique: primary_key,
+		blank: nullable,
+		editable: true,
+		default: None,
+		db_default: None,
+		db_column: None,
+		choices: None,
+		attributes: HashMap::new(),
+	}
+}
+
+impl Model for Article {
+	type PrimaryKey = i64;
+	type Fields = ArticleFields;
+
+	fn table_name() -> &'static str {
+		"articles"
+	}
+
+	fn new_fields() -> Self::Fields {
+		ArticleFields
+	}
+
+	fn primary_key(&self) -> Option<Self::PrimaryKey> {
+		self.id
+	}
+
+	fn set_primary_key(&mut self, value: Self::PrimaryKey) {
+		self.id = Some(value);
+	}
+
+	fn field_metadata() -> Vec<FieldInfo> {
+		vec![
+			field("id", "BigIntegerField", true, false),
+			field("title", "CharField", false, false),
+			field("views", "IntegerField", false, false),
+			field("published", "BooleanField", false, false),
+			field("summary", "TextField", false, true),
+			field("secret_note", "TextField", false, true),
+		]
+	}
+}
+
+/// In-memory store keyed by primary key
+#[derive(Clone, Default)]
+struct MemoryStore {
+	articles: Arc<Mutex<BTreeMap<i64, Article>>>,
+}
+
+#[async_trait]
+impl ModelStore<Article> for MemoryStore {
+	async fn get(&self, pk: i64) -> Result<Option<Article>> {
+		Ok(self.articles.lock().unwrap().get(&pk).cloned())
+	}
+
+	async fn list(&self, query: &ListQuery) -> Result<Vec<Article>> {
+		let articles = self.articles.lock().unwrap();
+		Ok(articles
+			.values()
+			.filter(|article| {
+				let row = serde_json::to_value(article).unwrap();
+				query.filters.iter().all(|filter| filter.matches(&row))
+			})
+			.skip(query.offset.unwrap_or(0))
+			.take(query.limit.unwrap_or(usize::MAX))
+			.cloned()
+			.collect())
+	}
+
+	async fn create(&self, mut article: Article) -> Result<Article> {
+		let mut articles = self.articles.lock().unwrap();
+		let id = articles.keys().last().copied().unwrap_or(0) + 1;
+		article.set_primary_key(id);
+		articles.insert(id, article.clone());
+		Ok(article)
+	}
+
+	async fn update(&self, article: Article) -> Result<Article> {
+		let id = article.id.expect("updated article has an ID");
+		self.articles.lock().unwrap().insert(id, article.clone());
+		Ok(article)
+	}
+
+	async fn delete(&self, pk: i64) -> Result<bool> {
+		Ok(self.articles.lock().unwrap().remove(&pk).is_some())
+	}
+}
+
+fn article(title: &str, views: i64, published: bool) -> Article {
+	Article {
+		id: None,
+		title: title.to_string(),
+		views,
+		published,
+		summary: None,
+		secret_note: "internal".to_string(),
+	}
+}
+
+#[fixture]
+async fn store() -> MemoryStore {
+	let store = MemoryStore::default();
+	store
+		.create(article("Intro to Rust", 120, true))
+		.await
+		.unwrap();
+	store.create(article("Async Rust", 40, true)).await.unwrap();
+	store
+		.create(article("Draft: GraphQL", 5, false))
+		.await
+		.unwrap();
+	store
+}
+
+fn schema(store: &MemoryStore) -> Schema {
+	ModelSchemaBuilder::new()
+		.register(
+			ModelType::<Article>::new("Article")
+				.with_store(store.clone())
+				.exclude("secret_note")
+				.read_only("views"),
+		)
+		.finish()
+		.unwrap()
+}
+
+async fn execute(schema: &Schema, query: &str) -> serde_json::Value {
+	let response = schema.execute(query).await;
+	assert!(response.errors.is_empty(), "{:?}", response.errors);
+	response.data.into_json().unwrap()
+}
+
+/// Test: the generated SDL exposes fields in camelCase and honours exclusions
+#[rstest]
+#[tokio::test]
+async fn test_generated_sdl(#[future] store: MemoryStore) {
+	let store = store.await;
+	let sdl = schema(&store).sdl();
+
+	assert!(sdl.contains("type Article {"));
+	assert!(sdl.contains("id: ID!"));
+	assert!(sdl.contains("views: Int!"));
+	assert!(sdl.contains("published: Boolean!"));
+	assert!(sdl.contains("summary: String"));
+	assert!(!sdl.contains("secretNote"));
+	assert!(sdl.contains("articles(filter: ArticleFilter, limit: Int, offset: Int): [Article!]!"));
+	assert!(sdl.contains("viewsGte: Int"));
+	assert!(sdl.contains("titleContains: String"));
+	assert!(sdl.contains("deleteArticle(id: ID!): Boolean!"));
+}
+
+/// Test: a model is fetched by ID, and unknown IDs resolve to null
+#[rstest]
+#[tokio::test]
+async fn test_get_by_id(#[future] store: MemoryStore) {
+	let store = store.await;
+	let schema = schema(&store);
+
+	let data = execute(
+		&schema,
+		r#"{ found: article(id: "2") { id title views } missing: article(id: "42") { id } }"#,
+	)
+	.await;
+
+	assert_eq!(
+		data,
+		json!({
+			"found": { "id": "2", "title": "Async Rust", "views": 40 },
+			"missing": null,
+		})
+	);
+}
+
+/// Test: list queries apply filters, limit and offset
+#[rstest]
+#[case(r#"filter: { published: true }"#, vec!["Intro to Rust", "Async Rust"])]
+#[case(r#"filter: { titleContains: "Rust", viewsGt: 50 }"#, vec!["Intro to Rust"])]
+#[case(r#"filter: { viewsLte: 40 }, limit: 1"#, vec!["Async Rust"])]
+#[case(r#"limit: 2, offset: 1"#, vec!["Async Rust", "Draft: GraphQL"])]
+#[tokio::test]
+async fn test_list_with_filters(
+	#[future] store: MemoryStore,
+	#[case] arguments: &str,
+	#[case] expected: Vec<&str>,
+) {
+	let store = store.await;
+	let schema = schema(&store);
+
+	let data = execute(
+		&schema,
+		&format!("{{ articles({}) {{ title }} }}", arguments),
+	)
+	.await;
+
+	let titles: Vec<&str> = data["articles"]
+		.as_array()
+		.unwrap()
+		.iter()
+		.map(|article| article["title"].as_str().unwrap())
+		.collect();
+	assert_eq!(titles, expected);
+}
+
+/// Test: create, update and delete mutations go through the store
+#[rstest]
+#[tokio::test]
+async fn test_crud_mutations(#[future] store: MemoryStore) {
+	let store = store.await;
+	let schema = schema(&store);
+
+	let created = execute(
+		&schema,
+		r#"mutation { createArticle(input: { title: "New", published: false }) { id title summary } }"#,
+	)
+	.await;
+	assert_eq!(
+		created["createArticle"],
+		json!({ "id": "4", "title": "New", "summary": null })
+	);
+
+	let updated = execute(
+		&schema,
+		r#"mutation { updateArticle(id: "4", input: { summary: "Short" }) { title summary } }"#,
+	)
+	.await;
+	assert_eq!(
+		updated["updateArticle"],
+		json!({ "title": "New", "summary": "Short" })
+	);
+	assert_eq!(
+		store.get(4).await.unwrap().unwrap().secret_note,
+		"",
+		"excluded fields use their serde default on create"
+	);
+
+	let deleted = execute(
+		&schema,
+		r#"mutation { first: deleteArticle(id: "4") second: deleteArticle(id: "4") }"#,
+	)
+	.await;
+	assert_eq!(deleted, json!({ "first": true, "second": false }));
+	assert!(store.get(4).await.unwrap().is_none());
+}
+
+/// Test: read-only fields are not accepted as mutation input
+#[rstest]
+#[tokio::test]
+async fn test_read_only_field_rejected(#[future] store: MemoryStore) {
+	let store = store.await;
+	let schema = schema(&store);
+
+	let response = schema
+		.execute(r#"mutation { updateArticle(id: "1", input: { views: 0 }) { views } }"#)
+		.await;
+
+	assert!(!response.errors.is_empty());
+	assert_eq!(store.get(1).await.unwrap().unwrap().views, 120);
+}
+
+/// Test: updating an unknown ID reports an error
+#[rstest]
+#[tokio::test]
+async fn test_update_missing_model(#[future] store: MemoryStore) {
+	let store = store.await;
+	let schema = schema(&store);
+
+	let response = schema
+		.execute(r#"mutation { updateArticle(id: "99", input: { title: "x" }) { id } }"#)
+		.await;
+
+	assert_eq!(response.errors.len(), 1);
+	assert!(response.errors[0].message.contains("not found"));
+}
@@ -0,0 +1,224 @@
+//! Integration tests for GraphQL permission checks
+//!
+//! These tests verify that `#[graphql_permission]`, `PermissionGuard` and
+//! visibility rules enforce reinhardt-auth permissions for the user carried
+//! over from the HTTP request.
+
+#![cfg(feature = "auth")]
+
+use async_graphql::{
+	Context, EmptyMutation, EmptySubscription, Object, Request, Response, Result, Schema,
+	SimpleObject,
+};
+use bytes::Bytes;
+use hyper::Method;
+use reinhardt_auth::PermissionsMixin;
+use reinhardt_graphql::graphql_permission;
+use reinhardt_graphql::permission::{PermissionGuard, PermissionRequestExt, has_permissions};
+use rstest::*;
+use serde_json::json;
+use std::sync::Arc;
+
+struct TestUser {
+	superuser: bool,
+	permissions: Vec<String>,
+}
+
+impl PermissionsMixin for TestUser {
+	fn is_superuser(&self) -> bool {
+		self.superuser
+	}
+
+	fn user_permissions(&self) -> &[String] {
+		&self.permissions
+	}
+
+	fn groups(&self) -> &[String] {
+		&[]
+	}
+}
+
+fn user(permissions: &[&str]) -> Arc<dyn PermissionsMixin> {
+	Arc::new(TestUser {
+		superuser: false,
+		permissions: permissions.iter().map(|p| p.to_string()).collect(),
+	})
+}
+
+fn can_view_salary(ctx: &Context<'_>) -> bool {
+	has_permissions(ctx, &["hr.view_salary"])
+}
+
+#[derive(SimpleObject)]
+struct Employee {
+	name: String,
+	#[graphql(guard = "PermissionGuard::new(&[\"hr.view_salary\"])")]
+	salary: i64,
+	#[graphql(
+		guard = "PermissionGuard::new(&[\"hr.view_salary\"])",
+		visible = "can_view_salary"
+	)]
+	bonus: i64,
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+	#[graphql_permission("hr.view_employee")]
+	async fn employee(&self, _ctx: &Context<'_>) -> Result<Employee> {
+		Ok(Employee {
+			name: "Alice".to_string(),
+			salary: 100,
+			bonus: 10,
+		})
+	}
+
+	#[graphql_permission("hr.view_employee", "hr.delete_employee")]
+	async fn can_delete(&self, _ctx: &Context<'_>) -> Result<bool> {
+		Ok(true)
+	}
+}
+
+type PermissionSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+#[fixture]
+fn schema() -> PermissionSchema {
+	Schema::new(Query, EmptyMutation, EmptySubscription)
+}
+
+async fn execute_as(
+	schema: &PermissionSchema,
+	query: &str,
+	user: Option<Arc<dyn PermissionsMixin>>,
+) -> Response {
+	let request = Request::new(query);
+	let request = match user {
+		Some(user) => request.data(user),
+		None => request,
+	};
+	schema.execute(request).await
+}
+
+fn error_code(response: &Response) -> serde_json::Value {
+	let extensions = response.errors[0].extensions.as_ref().unwrap();
+	extensions.get("code").unwrap().clone().into_json().unwrap()
+}
+
+/// Test: anonymous requests are rejected before the resolver runs
+#[rstest]
+#[tokio::test]
+async fn test_anonymous_rejected(schema: PermissionSchema) {
+	let response = execute_as(&schema, "{ employee { name } }", None).await;
+
+	assert_eq!(response.errors.len(), 1);
+	assert_eq!(response.errors[0].message, "Authentication required");
+	assert_eq!(error_code(&response), json!("UNAUTHENTICATED"));
+}
+
+/// Test: users need every permission listed on the resolver
+#[rstest]
+#[case(&["hr.view_employee"], false)]
+#[case(&["hr.view_employee", "hr.delete_employee"], true)]
+#[tokio::test]
+async fn test_all_permissions_required(
+	schema: PermissionSchema,
+	#[case] permissions: &[&str],
+	#[case] allowed: bool,
+) {
+	let response = execute_as(&schema, "{ canDelete }", Some(user(permissions))).await;
+
+	if allowed {
+		assert!(response.is_ok(), "{:?}", response.errors);
+	} else {
+		assert_eq!(error_code(&response), json!("FORBIDDEN"));
+		assert!(response.errors[0].message.contains("hr.delete_employee"));
+	}
+}
+
+/// Test: guarded fields fail individually while the rest of the object resolves
+#[rstest]
+#[tokio::test]
+async fn test_field_guard(schema: PermissionSchema) {
+	let response = execute_as(
+		&schema,
+		"{ employee { name salary } }",
+		Some(user(&["hr.view_employee"])),
+	)
+	.await;
+
+	assert_eq!(response.errors.len(), 1);
+	assert_eq!(error_code(&response), json!("FORBIDDEN"));
+
+	let response = execute_as(
+		&schema,
+		"{ employee { name salary bonus } }",
+		Some(user(&["hr.view_employee", "hr.view_salary"])),
+	)
+	.await;
+	assert!(response.is_ok(), "{:?}", response.errors);
+	assert_eq!(
+		response.data.into_json().unwrap(),
+		json!({ "employee": { "name": "Alice", "salary": 100, "bonus": 10 } })
+	);
+}
+
+/// Test: visibility rules hide fields from introspection for other users
+#[rstest]
+#[case(None, json!([{ "name": "name" }, { "name": "salary" }]))]
+#[case(
+	Some(user(&["hr.view_salary"])),
+	json!([{ "name": "name" }, { "name": "salary" }, { "name": "bonus" }])
+)]
+#[tokio::test]
+async fn test_field_visibility(
+	schema: PermissionSchema,
+	#[case] user: Option<Arc<dyn PermissionsMixin>>,
+	#[case] expected: serde_json::Value,
+) {
+	let introspection = r#"{ __type(name: "Employee") { fields { name } } }"#;
+	let response = execute_as(&schema, introspection, user).await;
+
+	assert!(response.is_ok(), "{:?}", response.errors);
+	assert_eq!(
+		response.data.into_json().unwrap()["__type"]["fields"],
+		expected
+	);
+}
+
+/// Test: superusers pass every permission check
+#[rstest]
+#[tokio::test]
+async fn test_superuser(schema: PermissionSchema) {
+	let admin: Arc<dyn PermissionsMixin> = Arc::new(TestUser {
+		superuser: true,
+		permissions: vec![],
+	});
+
+	let response = execute_as(
+		&schema,
+		"{ canDelete employee { salary bonus } }",
+		Some(admin),
+	)
+	.await;
+
+	assert!(response.is_ok(), "{:?}", response.errors);
+}
+
+/// Test: the user authenticated on the HTTP request reaches the resolvers
+#[rstest]
+#[tokio::test]
+async fn test_user_from_http_request(schema: PermissionSchema) {
+	let http_request = reinhardt_http::Request::builder()
+		.method(Method::POST)
+		.uri("/graphql")
+		.body(Bytes::new())
+		.build()
+		.unwrap();
+	http_request.extensions.insert(user(&["hr.view_employee"]));
+
+	let request = Request::new("{ employee { name } }").with_request_user(&http_request);
+	let response = schema.execute(request).await;
+
+	assert!(response.is_ok(), "{:?}", response.errors);
+}
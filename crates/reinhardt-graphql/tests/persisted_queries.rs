@@ -0,0 +1,139 @@
+//! Integration tests for automatic persisted queries
+//!
+//! These tests walk through the APQ handshake against a schema backed by an
+//! in-memory `reinhardt-utils` cache.
+
+#![cfg(feature = "persisted-queries")]
+
+use async_graphql::{EmptyMutation, EmptySubscription, Object, Request, Response, Schema, Value};
+use reinhardt_graphql::persisted_query::{PersistedQueries, persisted_query_hash};
+use reinhardt_utils::cache::{Cache, InMemoryCache};
+use rstest::*;
+use serde_json::json;
+use std::sync::Arc;
+
+const QUERY: &str = "{ greeting(name: \"APQ\") }";
+
+struct Query;
+
+#[Object]
+impl Query {
+	async fn greeting(&self, name: String) -> String {
+		format!("Hello, {}!", name)
+	}
+}
+
+type ApqSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+#[fixture]
+fn cache() -> Arc<InMemoryCache> {
+	Arc::new(InMemoryCache::new())
+}
+
+fn schema(cache: &Arc<InMemoryCache>) -> ApqSchema {
+	Schema::build(Query, EmptyMutation, EmptySubscription)
+		.extension(PersistedQueries::new(Arc::clone(cache)).with_key_prefix("test:apq:"))
+		.finish()
+}
+
+fn persisted(query: &str, extension: serde_json::Value) -> Request {
+	let mut request = Request::new(query);
+	request.extensions.insert(
+		"persistedQuery".to_string(),
+		Value::from_json(extension).unwrap(),
+	);
+	request
+}
+
+fn hash_extension(query: &str) -> serde_json::Value {
+	json!({ "version": 1, "sha256Hash": persisted_query_hash(query) })
+}
+
+fn error_code(response: &Response) -> serde_json::Value {
+	let extensions = response.errors[0].extensions.as_ref().unwrap();
+	extensions.get("code").unwrap().clone().into_json().unwrap()
+}
+
+/// Test: an unknown hash asks the client to register the query
+#[rstest]
+#[tokio::test]
+async fn test_unknown_hash(cache: Arc<InMemoryCache>) {
+	let response = schema(&cache)
+		.execute(persisted("", hash_extension(QUERY)))
+		.await;
+
+	assert_eq!(response.errors.len(), 1);
+	assert_eq!(response.errors[0].message, "PersistedQueryNotFound");
+	assert_eq!(error_code(&response), json!("PERSISTED_QUERY_NOT_FOUND"));
+}
+
+/// Test: registering a query lets later requests send only its hash
+#[rstest]
+#[tokio::test]
+async fn test_register_then_hash_only(cache: Arc<InMemoryCache>) {
+	let schema = schema(&cache);
+
+	let registered = schema
+		.execute(persisted(QUERY, hash_extension(QUERY)))
+		.await;
+	assert!(registered.is_ok(), "{:?}", registered.errors);
+	assert!(
+		cache
+			.has_key(&format!("test:apq:{}", persisted_query_hash(QUERY)))
+			.await
+			.unwrap()
+	);
+
+	let response = schema.execute(persisted("", hash_extension(QUERY))).await;
+	assert!(response.is_ok(), "{:?}", response.errors);
+	assert_eq!(
+		response.data.into_json().unwrap(),
+		json!({ "greeting": "Hello, APQ!" })
+	);
+}
+
+/// Test: a query that does not match its hash is rejected and not stored
+#[rstest]
+#[tokio::test]
+async fn test_hash_mismatch(cache: Arc<InMemoryCache>) {
+	let schema = schema(&cache);
+
+	let response = schema
+		.execute(persisted(
+			"{ greeting(name: \"other\") }",
+			hash_extension(QUERY),
+		))
+		.await;
+	assert_eq!(
+		error_code(&response),
+		json!("PERSISTED_QUERY_HASH_MISMATCH")
+	);
+
+	let response = schema.execute(persisted("", hash_extension(QUERY))).await;
+	assert_eq!(response.errors[0].message, "PersistedQueryNotFound");
+}
+
+/// Test: malformed extensions and unsupported versions are rejected
+#[rstest]
+#[case(json!({ "version": 2, "sha256Hash": persisted_query_hash(QUERY) }))]
+#[case(json!({ "version": 1 }))]
+#[tokio::test]
+async fn test_invalid_extension(cache: Arc<InMemoryCache>, #[case] extension: serde_json::Value) {
+	let response = schema(&cache).execute(persisted(QUERY, extension)).await;
+
+	assert_eq!(response.errors.len(), 1);
+	assert_eq!(error_code(&response), json!("PERSISTED_QUERY_INVALID"));
+}
+
+/// Test: requests without the extension are executed normally
+#[rstest]
+#[tokio::test]
+async fn test_plain_request(cache: Arc<InMemoryCache>) {
+	let response = schema(&cache).execute(QUERY).await;
+
+	assert!(response.is_ok(), "{:?}", response.errors);
+	assert_eq!(
+		response.data.into_json().unwrap(),
+		json!({ "greeting": "Hello, APQ!" })
+	);
+}
@@ -0,0 +1,129 @@
+//! Integration tests for Relay-style connections
+//!
+//! These tests verify that `Connection<T>` resolves through a schema with
+//! Relay type names and that cursors from one page fetch the next.
+
+use async_graphql::{EmptyMutation, EmptySubscription, Object, Result, Schema, SimpleObject};
+use reinhardt_graphql::connection::{Connection, ConnectionArgs, KeysetPagination};
+use rstest::*;
+use serde::Serialize;
+use serde_json::json;
+
+#[derive(Clone, Serialize, SimpleObject)]
+struct Book {
+	id: i64,
+	title: String,
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+	async fn books(
+		&self,
+		first: Option<i32>,
+		after: Option<String>,
+		last: Option<i32>,
+		before: Option<String>,
+	) -> Result<Connection<Book>> {
+		let pagination = KeysetPagination::new("id").max_page_size(3);
+		let window = pagination.window(&ConnectionArgs::new(first, after, last, before))?;
+		let books = (1..=7)
+			.map(|id| Book {
+				id,
+				title: format!("Book {}", id),
+			})
+			.collect();
+		let rows = window.apply(books, |book| json!(book.id));
+		pagination.connection(&window, rows)
+	}
+}
+
+#[fixture]
+fn schema() -> Schema<Query, EmptyMutation, EmptySubscription> {
+	Schema::new(Query, EmptyMutation, EmptySubscription)
+}
+
+async fn books(
+	schema: &Schema<Query, EmptyMutation, EmptySubscription>,
+	arguments: &str,
+) -> serde_json::Value {
+	let query = format!(
+		"{{ books({}) {{ nodes {{ id }} edges {{ cursor }} pageInfo {{ hasNextPage hasPreviousPage startCursor endCursor }} }} }}",
+		arguments
+	);
+	let response = schema.execute(query).await;
+	assert!(response.errors.is_empty(), "{:?}", response.errors);
+	response.data.into_json().unwrap()["books"].clone()
+}
+
+fn node_ids(page: &serde_json::Value) -> Vec<i64> {
+	page["nodes"]
+		.as_array()
+		.unwrap()
+		.iter()
+		.map(|node| node["id"].as_i64().unwrap())
+		.collect()
+}
+
+/// Test: connection and edge types are named after the node type
+#[rstest]
+fn test_type_names(schema: Schema<Query, EmptyMutation, EmptySubscription>) {
+	let sdl = schema.sdl();
+
+	assert!(sdl.contains("type BookConnection {"));
+	assert!(sdl.contains("edges: [BookEdge!]!"));
+	assert!(sdl.contains("type PageInfo {"));
+}
+
+/// Test: following end cursors walks forward through every page
+#[rstest]
+#[tokio::test]
+async fn test_forward_pagination(schema: Schema<Query, EmptyMutation, EmptySubscription>) {
+	let mut seen = Vec::new();
+	let mut after = String::new();
+	loop {
+		let page = books(&schema, &format!("first: 3{}", after)).await;
+		seen.extend(node_ids(&page));
+		if !page["pageInfo"]["hasNextPage"].as_bool().unwrap() {
+			break;
+		}
+		after = format!(
+			r#", after: "{}""#,
+			page["pageInfo"]["endCursor"].as_str().unwrap()
+		);
+	}
+
+	assert_eq!(seen, vec![1, 2, 3, 4, 5, 6, 7]);
+}
+
+/// Test: `last` with a start cursor pages backward
+#[rstest]
+#[tokio::test]
+async fn test_backward_pagination(schema: Schema<Query, EmptyMutation, EmptySubscription>) {
+	let last_page = books(&schema, "last: 2").await;
+	assert_eq!(node_ids(&last_page), vec![6, 7]);
+	assert_eq!(last_page["pageInfo"]["hasPreviousPage"], json!(true));
+
+	let before = last_page["pageInfo"]["startCursor"].as_str().unwrap();
+	let previous = books(&schema, &format!(r#"last: 10, before: "{}""#, before)).await;
+
+	assert_eq!(node_ids(&previous), vec![3, 4, 5]);
+	assert_eq!(previous["pageInfo"]["hasNextPage"], json!(true));
+	assert_eq!(
+		previous["pageInfo"]["endCursor"],
+		previous["edges"][2]["cursor"]
+	);
+}
+
+/// Test: invalid arguments surface as GraphQL errors
+#[rstest]
+#[tokio::test]
+async fn test_invalid_arguments(schema: Schema<Query, EmptyMutation, EmptySubscription>) {
+	let response = schema
+		.execute(r#"{ books(first: 1, last: 1) { nodes { id } } }"#)
+		.await;
+
+	assert_eq!(response.errors.len(), 1);
+	assert!(response.errors[0].message.contains("`first` and `last`"));
+}
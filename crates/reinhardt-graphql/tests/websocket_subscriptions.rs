@@ -0,0 +1,262 @@
+//! Integration tests for GraphQL subscriptions over WebSocket
+//!
+//! These tests drive `GraphQLWsConsumer` through a `WebSocketConnection`
+//! backed by a channel, playing the client side of both the
+//! `graphql-transport-ws` and legacy `graphql-ws` protocols.
+
+#![cfg(feature = "websocket")]
+
+use async_graphql::{Context, EmptyMutation, Object, Result, Schema, Subscription};
+use futures_util::Stream;
+use reinhardt_graphql::websocket::GraphQLWsConsumer;
+use reinhardt_websockets::auth::{AuthUser, SimpleAuthUser, TokenAuthenticator};
+use reinhardt_websockets::consumers::{ConsumerContext, WebSocketConsumer};
+use reinhardt_websockets::{Message, WebSocketConnection};
+use rstest::*;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+struct Query;
+
+#[Object]
+impl Query {
+	async fn version(&self) -> &str {
+		"1.0"
+	}
+}
+
+struct Subscription;
+
+#[Subscription]
+impl Subscription {
+	async fn counter(&self, to: i32) -> impl Stream<Item = i32> {
+		futures_util::stream::iter(1..=to)
+	}
+
+	async fn whoami(&self, ctx: &Context<'_>) -> Result<impl Stream<Item = String>> {
+		let user = ctx.data::<Arc<dyn AuthUser>>()?;
+		Ok(futures_util::stream::once(futures_util::future::ready(
+			user.username().to_string(),
+		)))
+	}
+}
+
+type WsSchema = Schema<Query, EmptyMutation, Subscription>;
+
+#[fixture]
+fn schema() -> WsSchema {
+	Schema::new(Query, EmptyMutation, Subscription)
+}
+
+/// Client side of one WebSocket connection
+struct Client {
+	consumer: Arc<GraphQLWsConsumer<WsSchema>>,
+	context: ConsumerContext,
+	outgoing: mpsc::UnboundedReceiver<Message>,
+}
+
+impl Client {
+	async fn connect(consumer: Arc<GraphQLWsConsumer<WsSchema>>, subprotocol: &str) -> Self {
+		let (tx, outgoing) = mpsc::unbounded_channel();
+		let connection = Arc::new(WebSocketConnection::with_subprotocol(
+			"client".to_string(),
+			tx,
+			Some(subprotocol.to_string()),
+		));
+		let mut context = ConsumerContext::new(connection);
+		consumer.on_connect(&mut context).await.unwrap();
+		Self {
+			consumer,
+			context,
+			outgoing,
+		}
+	}
+
+	async fn send(&mut self, message: Value) {
+		self.consumer
+			.on_message(&mut self.context, Message::text(message.to_string()))
+			.await
+			.unwrap();
+	}
+
+	async fn receive(&mut self) -> Message {
+		tokio::time::timeout(Duration::from_secs(5), self.outgoing.recv())
+			.await
+			.expect("timed out waiting for a message")
+			.expect("connection closed")
+	}
+
+	async fn receive_json(&mut self) -> Value {
+		match self.receive().await {
+			Message::Text { data } => serde_json::from_str(&data).unwrap(),
+			other => panic!("Expected text message, got {:?}", other),
+		}
+	}
+}
+
+/// Test: graphql-transport-ws acknowledges init and streams subscription events
+#[rstest]
+#[tokio::test]
+async fn test_graphql_transport_ws_subscription(schema: WsSchema) {
+	let consumer = Arc::new(GraphQLWsConsumer::new(schema));
+	let mut client = Client::connect(consumer.clone(), "graphql-transport-ws").await;
+
+	client.send(json!({ "type": "connection_init" })).await;
+	assert_eq!(
+		client.receive_json().await,
+		json!({ "type": "connection_ack" })
+	);
+
+	client
+		.send(json!({
+			"type": "subscribe",
+			"id": "1",
+			"payload": { "query": "subscription { counter(to: 3) }" },
+		}))
+		.await;
+	for value in 1..=3 {
+		assert_eq!(
+			client.receive_json().await,
+			json!({ "type": "next", "id": "1", "payload": { "data": { "counter": value } } })
+		);
+	}
+	assert_eq!(
+		client.receive_json().await,
+		json!({ "type": "complete", "id": "1" })
+	);
+
+	assert_eq!(consumer.connection_count(), 1);
+	consumer.on_disconnect(&mut client.context).await.unwrap();
+	assert_eq!(consumer.connection_count(), 0);
+}
+
+/// Test: queries also run over the socket
+#[rstest]
+#[tokio::test]
+async fn test_query_over_websocket(schema: WsSchema) {
+	let consumer = Arc::new(GraphQLWsConsumer::new(schema));
+	let mut client = Client::connect(consumer, "graphql-transport-ws").await;
+
+	client.send(json!({ "type": "connection_init" })).await;
+	client.receive_json().await;
+	client
+		.send(json!({
+			"type": "subscribe",
+			"id": "q",
+			"payload": { "query": "{ version }" },
+		}))
+		.await;
+
+	assert_eq!(
+		client.receive_json().await,
+		json!({ "type": "next", "id": "q", "payload": { "data": { "version": "1.0" } } })
+	);
+}
+
+/// Test: the legacy graphql-ws protocol uses start/data messages
+#[rstest]
+#[tokio::test]
+async fn test_legacy_graphql_ws_subscription(schema: WsSchema) {
+	let consumer = Arc::new(GraphQLWsConsumer::new(schema));
+	let mut client = Client::connect(consumer, "graphql-ws").await;
+
+	client.send(json!({ "type": "connection_init" })).await;
+	assert_eq!(
+		client.receive_json().await,
+		json!({ "type": "connection_ack" })
+	);
+
+	client
+		.send(json!({
+			"type": "start",
+			"id": "1",
+			"payload": { "query": "subscription { counter(to: 1) }" },
+		}))
+		.await;
+	assert_eq!(
+		client.receive_json().await,
+		json!({ "type": "data", "id": "1", "payload": { "data": { "counter": 1 } } })
+	);
+	assert_eq!(
+		client.receive_json().await,
+		json!({ "type": "complete", "id": "1" })
+	);
+}
+
+fn authenticated_consumer(schema: WsSchema) -> Arc<GraphQLWsConsumer<WsSchema>> {
+	let authenticator = TokenAuthenticator::new(vec![(
+		"secret".to_string(),
+		SimpleAuthUser::new("1".to_string(), "alice".to_string(), vec![]),
+	)]);
+	Arc::new(GraphQLWsConsumer::new(schema).with_authenticator(Arc::new(authenticator)))
+}
+
+/// Test: the authenticated user is visible to subscription resolvers
+#[rstest]
+#[tokio::test]
+async fn test_authenticated_subscription(schema: WsSchema) {
+	let mut client = Client::connect(authenticated_consumer(schema), "graphql-transport-ws").await;
+
+	client
+		.send(json!({
+			"type": "connection_init",
+			"payload": { "Authorization": "Bearer secret" },
+		}))
+		.await;
+	assert_eq!(
+		client.receive_json().await,
+		json!({ "type": "connection_ack" })
+	);
+
+	client
+		.send(json!({
+			"type": "subscribe",
+			"id": "1",
+			"payload": { "query": "subscription { whoami }" },
+		}))
+		.await;
+	assert_eq!(
+		client.receive_json().await,
+		json!({ "type": "next", "id": "1", "payload": { "data": { "whoami": "alice" } } })
+	);
+}
+
+/// Test: an invalid token closes graphql-transport-ws connections with 1002
+#[rstest]
+#[case(json!({ "token": "wrong" }))]
+#[case(json!({}))]
+#[tokio::test]
+async fn test_authentication_rejected(schema: WsSchema, #[case] payload: Value) {
+	let mut client = Client::connect(authenticated_consumer(schema), "graphql-transport-ws").await;
+
+	client
+		.send(json!({ "type": "connection_init", "payload": payload }))
+		.await;
+
+	match client.receive().await {
+		Message::Close { code, reason } => {
+			assert_eq!(code, 1002);
+			assert!(reason.contains("Forbidden"), "{}", reason);
+		}
+		other => panic!("Expected close message, got {:?}", other),
+	}
+}
+
+/// Test: unsupported subprotocols are refused on connect
+#[rstest]
+#[tokio::test]
+async fn test_unsupported_subprotocol(schema: WsSchema) {
+	let consumer = GraphQLWsConsumer::new(schema);
+	let (tx, _rx) = mpsc::unbounded_channel();
+	let connection = Arc::new(WebSocketConnection::with_subprotocol(
+		"client".to_string(),
+		tx,
+		Some("chat".to_string()),
+	));
+	let mut context = ConsumerContext::new(connection);
+
+	assert!(consumer.on_connect(&mut context).await.is_err());
+	assert_eq!(consumer.connection_count(), 0);
+}
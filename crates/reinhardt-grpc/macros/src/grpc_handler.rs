@@ -6,7 +6,10 @@
 use crate::crate_paths::{get_reinhardt_di_crate, get_reinhardt_grpc_crate};
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{Error, FnArg, ItemFn, Pat, PatType, Result, Token, Type, punctuated::Punctuated};
+use syn::{
+	Error, FnArg, ImplItem, ImplItemFn, ItemFn, ItemImpl, Pat, PatType, Result, Token, Type,
+	punctuated::Punctuated,
+};
 
 /// Information about parameter extractors
 #[derive(Clone)]
@@ -34,6 +37,30 @@ fn is_inject_attr(attr: &syn::Attribute) -> bool {
 	attr.path().is_ident("inject")
 }
 
+/// Check if an attribute is `#[principal]`
+fn is_principal_attr(attr: &syn::Attribute) -> bool {
+	attr.path().is_ident("principal")
+}
+
+/// Check if an attribute is handled by the macro (`#[inject]` or `#[principal]`)
+fn is_param_attr(attr: &syn::Attribute) -> bool {
+	is_inject_attr(attr) || is_principal_attr(attr)
+}
+
+/// Check if a type is `Option<T>`
+fn is_option_type(ty: &Type) -> bool {
+	if let Type::Path(type_path) = ty {
+		type_path
+			.path
+			.segments
+			.last()
+			.map(|seg| seg.ident == "Option")
+			.unwrap_or(false)
+	} else {
+		false
+	}
+}
+
 /// Parse `#[inject]` or `#[inject(cache = false)]` attributes
 fn parse_inject_options(attrs: &[syn::Attribute]) -> InjectOptions {
 	let mut options = InjectOptions {
@@ -89,14 +116,30 @@ fn detect_inject_params(inputs: &Punctuated<FnArg, Token![,]>) -> Vec<InjectInfo
 	inject_params
 }
 
+/// Detect parameters with `#[principal]` attribute
+fn detect_principal_params(inputs: &Punctuated<FnArg, Token![,]>) -> Vec<ParamInfo> {
+	inputs
+		.iter()
+		.filter_map(|input| match input {
+			FnArg::Typed(PatType { attrs, pat, ty, .. }) if attrs.iter().any(is_principal_attr) => {
+				Some(ParamInfo {
+					pat: pat.clone(),
+					ty: ty.clone(),
+				})
+			}
+			_ => None,
+		})
+		.collect()
+}
+
 /// Detect non-inject parameters (regular parameters)
 fn detect_regular_params(inputs: &Punctuated<FnArg, Token![,]>) -> Vec<ParamInfo> {
 	let mut params = Vec::new();
 
 	for input in inputs {
 		if let FnArg::Typed(pat_type) = input {
-			// Skip parameters with #[inject] attribute
-			if pat_type.attrs.iter().any(is_inject_attr) {
+			// Skip parameters with #[inject] or #[principal] attribute
+			if pat_type.attrs.iter().any(is_param_attr) {
 				continue;
 			}
 
@@ -116,14 +159,14 @@ fn detect_regular_params(inputs: &Punctuated<FnArg, Token![,]>) -> Vec<ParamInfo
 	params
 }
 
-/// Strip `#[inject]` attributes from function parameters
-fn strip_inject_attrs(inputs: &Punctuated<FnArg, Token![,]>) -> Vec<FnArg> {
+/// Strip `#[inject]` and `#[principal]` attributes from function parameters
+fn strip_param_attrs(inputs: &Punctuated<FnArg, Token![,]>) -> Vec<FnArg> {
 	inputs
 		.iter()
 		.map(|arg| {
 			if let FnArg::Typed(pat_type) = arg {
 				let mut pat_type = pat_type.clone();
-				pat_type.attrs.retain(|attr| !is_inject_attr(attr));
+				pat_type.attrs.retain(|attr| !is_param_attr(attr));
 				FnArg::Typed(pat_type)
 			} else {
 				arg.clone()
@@ -132,44 +175,9 @@ fn strip_inject_attrs(inputs: &Punctuated<FnArg, Token![,]>) -> Vec<FnArg> {
 		.collect()
 }
 
-/// Generate wrapper function with DI support
-pub(crate) fn expand_grpc_handler(input: ItemFn) -> Result<TokenStream> {
-	let inject_params = detect_inject_params(&input.sig.inputs);
-
-	// If no #[inject] parameters, return the function as-is
-	if inject_params.is_empty() {
-		return Ok(quote! { #input });
-	}
-
-	let regular_params = detect_regular_params(&input.sig.inputs);
-
-	// Original function name
-	let original_fn_name = &input.sig.ident;
-
-	// Create new name for the original function
-	let impl_fn_name = syn::Ident::new(&format!("{}_impl", original_fn_name), Span::call_site());
-
-	// Function visibility, attributes (excluding #[grpc_handler]), return type, etc.
-	let vis = &input.vis;
-	let fn_attrs: Vec<_> = input
-		.attrs
-		.iter()
-		.filter(|attr| !attr.path().is_ident("grpc_handler"))
-		.collect();
-	let return_type = &input.sig.output;
-	let asyncness = &input.sig.asyncness;
-	let generics = &input.sig.generics;
-
-	// Original function body
-	let body = &input.block;
-
-	// Strip #[inject] attributes from parameters for the impl function
-	let impl_inputs = strip_inject_attrs(&input.sig.inputs);
-	let impl_inputs = Punctuated::<FnArg, Token![,]>::from_iter(impl_inputs);
-
-	// Generate DI context extraction
-	// We need to detect which parameter is tonic::Request<T>
-	let request_param = regular_params
+/// Find the `tonic::Request<T>` parameter
+fn find_request_param(regular_params: &[ParamInfo]) -> Result<&ParamInfo> {
+	regular_params
 		.iter()
 		.find(|p| {
 			if let Type::Path(type_path) = &*p.ty {
@@ -188,25 +196,76 @@ pub(crate) fn expand_grpc_handler(input: ItemFn) -> Result<TokenStream> {
 				Span::call_site(),
 				"#[grpc_handler] requires a tonic::Request parameter",
 			)
-		})?;
+		})
+}
 
-	let request_pat = &request_param.pat;
+/// Generate the statements extracting the principal and resolving
+/// dependencies from the request extensions
+fn expand_param_prelude(
+	request_pat: &Pat,
+	inject_params: &[InjectInfo],
+	principal_params: &[ParamInfo],
+) -> TokenStream {
+	// `mut request` must be referred to as `request`
+	let request_pat = match request_pat {
+		Pat::Ident(pat_ident) => {
+			let ident = &pat_ident.ident;
+			quote! { #ident }
+		}
+		other => quote! { #other },
+	};
 
 	// Get dynamic crate paths
 	let di_crate = get_reinhardt_di_crate();
 	let grpc_crate = get_reinhardt_grpc_crate();
 
-	// Generate DI extraction code
-	let di_context_extraction = quote! {
-		let __di_ctx = #request_pat
-			.get_di_context::<::std::sync::Arc<#di_crate::InjectionContext>>()
-			.ok_or_else(|| {
-				::tonic::Status::internal(
-					"DI context not set. Ensure the request extensions contain InjectionContext"
-				)
-			})?;
+	// Generate DI extraction code (only needed when something is injected)
+	let di_context_extraction = if inject_params.is_empty() {
+		quote! {}
+	} else {
+		quote! {
+			use #grpc_crate::GrpcRequestExt;
+
+			let __di_ctx = #request_pat
+				.get_di_context::<::std::sync::Arc<#di_crate::InjectionContext>>()
+				.ok_or_else(|| {
+					::tonic::Status::from(#grpc_crate::GrpcError::Internal(
+						"DI context not set. Ensure the request extensions contain InjectionContext"
+							.to_string(),
+					))
+				})?;
+		}
 	};
 
+	// Generate principal extraction: `Option<Principal>` parameters accept
+	// anonymous calls, other parameters reject them
+	let principal_extractions: Vec<_> = principal_params
+		.iter()
+		.map(|param| {
+			let pat = &param.pat;
+			let ty = &param.ty;
+			let principal = quote! {
+				#request_pat
+					.extensions()
+					.get::<#grpc_crate::interceptor::Principal>()
+					.cloned()
+			};
+
+			if is_option_type(ty) {
+				quote! {
+					let #pat: #ty = #principal;
+				}
+			} else {
+				quote! {
+					let #pat: #ty = #principal
+						.ok_or_else(|| ::tonic::Status::from(#grpc_crate::GrpcError::Unauthenticated(
+							"Authentication required".to_string(),
+						)))?;
+				}
+			}
+		})
+		.collect();
+
 	// Generate injection calls
 	let injection_calls: Vec<_> = inject_params
 		.iter()
@@ -219,37 +278,93 @@ pub(crate) fn expand_grpc_handler(input: ItemFn) -> Result<TokenStream> {
 				quote! {
 					let #pat: #ty = #di_crate::Injected::<#ty>::resolve(&__di_ctx)
 						.await
-						.map_err(|e| ::tonic::Status::internal(
+						.map_err(|e| ::tonic::Status::from(#grpc_crate::GrpcError::Internal(
 							format!("Dependency injection failed for {}: {:?}", stringify!(#ty), e)
-						))?
+						)))?
 						.into_inner();
 				}
 			} else {
 				quote! {
 					let #pat: #ty = #di_crate::Injected::<#ty>::resolve_uncached(&__di_ctx)
 						.await
-						.map_err(|e| ::tonic::Status::internal(
+						.map_err(|e| ::tonic::Status::from(#grpc_crate::GrpcError::Internal(
 							format!("Dependency injection failed for {}: {:?}", stringify!(#ty), e)
-						))?
+						)))?
 						.into_inner();
 				}
 			}
 		})
 		.collect();
 
+	quote! {
+		// Extract the authenticated principal
+		#(#principal_extractions)*
+
+		// Extract DI context
+		#di_context_extraction
+
+		// Resolve dependencies
+		#(#injection_calls)*
+	}
+}
+
+/// Generate wrapper function with DI support
+pub(crate) fn expand_grpc_handler(input: ItemFn) -> Result<TokenStream> {
+	let inject_params = detect_inject_params(&input.sig.inputs);
+	let principal_params = detect_principal_params(&input.sig.inputs);
+
+	// If no #[inject] or #[principal] parameters, return the function as-is
+	if inject_params.is_empty() && principal_params.is_empty() {
+		return Ok(quote! { #input });
+	}
+
+	let regular_params = detect_regular_params(&input.sig.inputs);
+
+	// Original function name
+	let original_fn_name = &input.sig.ident;
+
+	// Create new name for the original function
+	let impl_fn_name = syn::Ident::new(&format!("{}_impl", original_fn_name), Span::call_site());
+
+	// Function visibility, attributes (excluding #[grpc_handler]), return type, etc.
+	let vis = &input.vis;
+	let fn_attrs: Vec<_> = input
+		.attrs
+		.iter()
+		.filter(|attr| !attr.path().is_ident("grpc_handler"))
+		.collect();
+	let return_type = &input.sig.output;
+	let asyncness = &input.sig.asyncness;
+	let generics = &input.sig.generics;
+
+	// Original function body
+	let body = &input.block;
+
+	// Strip #[inject] and #[principal] attributes from parameters for the impl function
+	let impl_inputs = strip_param_attrs(&input.sig.inputs);
+	let impl_inputs = Punctuated::<FnArg, Token![,]>::from_iter(impl_inputs);
+
+	// We need to detect which parameter is tonic::Request<T>
+	let request_param = find_request_param(&regular_params)?;
+	let prelude = expand_param_prelude(&request_param.pat, &inject_params, &principal_params);
+
 	// Check if this is a method (has &self parameter)
 	let has_self = regular_params
 		.first()
 		.map(|p| matches!(*p.pat, Pat::Verbatim(_)))
 		.unwrap_or(false);
 
-	// Collect parameter names for the original function call (excluding self)
-	let regular_args: Vec<_> = regular_params
+	// Collect parameter names for the original function call in declaration
+	// order (excluding self)
+	let call_args: Vec<_> = input
+		.sig
+		.inputs
 		.iter()
-		.skip(if has_self { 1 } else { 0 })
-		.map(|p| &p.pat)
+		.filter_map(|arg| match arg {
+			FnArg::Typed(pat_type) => Some(&pat_type.pat),
+			FnArg::Receiver(_) => None,
+		})
 		.collect();
-	let inject_args: Vec<_> = inject_params.iter().map(|param| &param.pat).collect();
 
 	// Wrapper function inputs (only regular parameters, without #[inject])
 	let wrapper_inputs: Vec<_> = regular_params
@@ -270,9 +385,9 @@ pub(crate) fn expand_grpc_handler(input: ItemFn) -> Result<TokenStream> {
 
 	// Generate the call to the impl function
 	let impl_call = if has_self {
-		quote! { self.#impl_fn_name(#(#regular_args,)* #(#inject_args),*).await }
+		quote! { self.#impl_fn_name(#(#call_args),*).await }
 	} else {
-		quote! { #impl_fn_name(#(#regular_args,)* #(#inject_args),*).await }
+		quote! { #impl_fn_name(#(#call_args),*).await }
 	};
 
 	// Generate the wrapper function
@@ -284,13 +399,7 @@ pub(crate) fn expand_grpc_handler(input: ItemFn) -> Result<TokenStream> {
 		// Wrapper function (keeps the original name)
 		#(#fn_attrs)*
 		#vis #asyncness fn #original_fn_name #generics(#wrapper_inputs) #return_type {
-			use #grpc_crate::GrpcRequestExt;
-
-			// Extract DI context
-			#di_context_extraction
-
-			// Resolve dependencies
-			#(#injection_calls)*
+			#prelude
 
 			// Call original function
 			#impl_call
@@ -299,3 +408,65 @@ pub(crate) fn expand_grpc_handler(input: ItemFn) -> Result<TokenStream> {
 
 	Ok(expanded)
 }
+
+/// Rewrite the methods of a tonic service trait implementation
+///
+/// Trait methods cannot gain extra parameters or helper methods, so
+/// `#[inject]` and `#[principal]` parameters are removed from the signature
+/// and resolved at the start of the method body instead. Because the body
+/// stays inside the trait implementation, server-streaming and
+/// bidirectional-streaming methods can return `Response<Self::XStream>`
+/// and move the resolved dependencies into the stream.
+pub(crate) fn expand_grpc_service_impl(mut input: ItemImpl) -> Result<TokenStream> {
+	for item in &mut input.items {
+		if let ImplItem::Fn(method) = item {
+			expand_service_method(method)?;
+		}
+	}
+
+	Ok(quote! { #input })
+}
+
+/// Resolve the `#[inject]` and `#[principal]` parameters of one service method
+fn expand_service_method(method: &mut ImplItemFn) -> Result<()> {
+	let inject_params = detect_inject_params(&method.sig.inputs);
+	let principal_params = detect_principal_params(&method.sig.inputs);
+
+	if inject_params.is_empty() && principal_params.is_empty() {
+		return Ok(());
+	}
+
+	if method.sig.asyncness.is_none() {
+		return Err(Error::new_spanned(
+			method.sig.fn_token,
+			"#[grpc_handler] service methods must be async; place #[grpc_handler] above #[tonic::async_trait]",
+		));
+	}
+
+	let regular_params = detect_regular_params(&method.sig.inputs);
+	let request_param = find_request_param(&regular_params)?;
+	let prelude = expand_param_prelude(&request_param.pat, &inject_params, &principal_params);
+
+	// Keep only the parameters declared by the service trait
+	method.sig.inputs = method
+		.sig
+		.inputs
+		.iter()
+		.filter(|arg| match arg {
+			FnArg::Typed(pat_type) => !pat_type.attrs.iter().any(is_param_attr),
+			FnArg::Receiver(_) => true,
+		})
+		.cloned()
+		.collect();
+
+	let body = &method.block;
+	method.block = syn::parse_quote! {
+		{
+			#prelude
+
+			#body
+		}
+	};
+
+	Ok(())
+}
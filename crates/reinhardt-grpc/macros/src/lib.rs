@@ -16,22 +16,65 @@ mod grpc_handler;
 /// Regular parameters are passed through as-is. Parameters marked with `#[inject]`
 /// are automatically resolved from the DI context.
 ///
+/// Parameters marked with `#[principal]` receive the `Principal` set by the
+/// `AuthMiddleware` of the service. A `Principal` parameter rejects anonymous
+/// calls with `tonic::Status::unauthenticated`, while `Option<Principal>`
+/// receives `None` for them.
+///
 /// # Requirements
 ///
 /// 1. The function must have a `tonic::Request<T>` parameter
-/// 2. The request must have an `InjectionContext` in its extensions
+/// 2. The request must have an `InjectionContext` in its extensions when
+///    `#[inject]` parameters are used
 /// 3. All injected types must implement `Injectable`
 /// 4. The function must be `async`
 ///
+/// # Streaming Methods
+///
+/// Server-streaming and bidirectional-streaming methods return
+/// `Response<Self::XStream>`, which only resolves inside the service trait
+/// implementation. For these, place `#[grpc_handler]` on the trait `impl`
+/// block above `#[tonic::async_trait]`. The marked parameters are removed
+/// from each method signature and resolved once per call at the start of
+/// the method body, so they can be moved into the returned stream.
+///
+/// ```rust,ignore
+/// #[grpc_handler]
+/// #[tonic::async_trait]
+/// impl UserService for UserServiceImpl {
+///     type ListUsersStream = ResponseStream<User>;
+///
+///     async fn list_users(
+///         &self,
+///         request: Request<ListUsersRequest>,
+///         #[inject] db: DatabaseConnection,
+///     ) -> Result<Response<Self::ListUsersStream>, Status> {
+///         let stream = async_stream::try_stream! {
+///             for user in db.fetch_users().await? {
+///                 yield user;
+///             }
+///         };
+///         Ok(Response::new(Box::pin(stream)))
+///     }
+/// }
+/// ```
+///
 /// # Error Handling
 ///
 /// If dependency injection fails, the function returns `tonic::Status::internal`
 /// with an error message describing the failure.
 #[proc_macro_attribute]
 pub fn grpc_handler(_attr: TokenStream, item: TokenStream) -> TokenStream {
-	let input = parse_macro_input!(item as syn::ItemFn);
+	let input = parse_macro_input!(item as syn::Item);
+
+	let expanded = match input {
+		syn::Item::Fn(item_fn) => grpc_handler::expand_grpc_handler(item_fn),
+		syn::Item::Impl(item_impl) => grpc_handler::expand_grpc_service_impl(item_impl),
+		other => Err(syn::Error::new_spanned(
+			other,
+			"#[grpc_handler] can only be applied to functions and service impl blocks",
+		)),
+	};
 
-	grpc_handler::expand_grpc_handler(input)
-		.unwrap_or_else(|err| err.to_compile_error())
-		.into()
+	expanded.unwrap_or_else(|err| err.to_compile_error()).into()
 }
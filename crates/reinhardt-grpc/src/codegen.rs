@@ -0,0 +1,628 @@
+//! Protobuf and tonic service generation for ORM models
+//!
+//! [`CrudCodegen`] renders a `.proto` file with a CRUD service per
+//! registered model and a Rust scaffold implementing each tonic service on
+//! top of a [`CrudHandler`](crate::crud::CrudHandler). Running it from
+//! `build.rs` keeps gRPC APIs in sync with the models they expose.
+//!
+//! For a model `Article`, the generated `ArticleService` offers
+//! `GetArticle`, `ListArticles` (returning `reinhardt.common.PageInfo`),
+//! `CreateArticle`, `UpdateArticle`, `DeleteArticle` and
+//! `BatchCreateArticles` (returning `reinhardt.common.BatchResult`).
+//!
+//! # Examples
+//!
+//! In `build.rs`:
+//!
+//! ```rust,no_run
+//! use reinhardt_grpc::codegen::{CrudCodegen, ProtoModel, ProtoType};
+//!
+//! fn main() -> std::io::Result<()> {
+//!     CrudCodegen::new("blog")
+//!         .model(
+//!             ProtoModel::new("Article")
+//!                 .primary_key("id", ProtoType::Int64)
+//!                 .field("title", ProtoType::String)
+//!                 .optional_field("summary", ProtoType::String),
+//!         )
+//!         .compile()
+//! }
+//! ```
+//!
+//! Then in the crate:
+//!
+//! ```rust,ignore
+//! pub mod blog {
+//!     tonic::include_proto!("blog");
+//!     include!(concat!(env!("OUT_DIR"), "/blog.crud.rs"));
+//! }
+//!
+//! let service = blog::ArticleCrudService::new(MyArticleHandler).into_server();
+//! ```
+//!
+//! `compile` also writes `{package}_descriptor.bin`, which can be passed to
+//! `GrpcServer::with_file_descriptor_set` through
+//! `tonic::include_file_descriptor_set!("blog_descriptor")`.
+
+use std::fmt::Write as _;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Common message definitions imported by generated files
+const COMMON_PROTO: &str = include_str!("../proto/common.proto");
+
+/// Import path of the common definitions
+const COMMON_IMPORT: &str = "reinhardt/common.proto";
+
+/// Scalar type of a generated message field
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtoType {
+	Bool,
+	Int32,
+	Int64,
+	Uint32,
+	Uint64,
+	Float,
+	Double,
+	String,
+	Bytes,
+	/// `reinhardt.common.Timestamp`
+	Timestamp,
+}
+
+impl ProtoType {
+	/// Protobuf spelling of the type
+	pub fn proto_name(self) -> &'static str {
+		match self {
+			Self::Bool => "bool",
+			Self::Int32 => "int32",
+			Self::Int64 => "int64",
+			Self::Uint32 => "uint32",
+			Self::Uint64 => "uint64",
+			Self::Float => "float",
+			Self::Double => "double",
+			Self::String => "string",
+			Self::Bytes => "bytes",
+			Self::Timestamp => "reinhardt.common.Timestamp",
+		}
+	}
+
+	/// Rust type prost generates for the type
+	fn rust_name(self) -> &'static str {
+		match self {
+			Self::Bool => "bool",
+			Self::Int32 => "i32",
+			Self::Int64 => "i64",
+			Self::Uint32 => "u32",
+			Self::Uint64 => "u64",
+			Self::Float => "f32",
+			Self::Double => "f64",
+			Self::String => "::std::string::String",
+			Self::Bytes => "::std::vec::Vec<u8>",
+			Self::Timestamp => "::reinhardt_grpc::proto::common::Timestamp",
+		}
+	}
+
+	/// Type used for an ORM field type such as `"reinhardt.orm.models.CharField"`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_grpc::codegen::ProtoType;
+	///
+	/// assert_eq!(ProtoType::from_field_type("reinhardt.orm.models.BigIntegerField"), ProtoType::Int64);
+	/// assert_eq!(ProtoType::from_field_type("CharField"), ProtoType::String);
+	/// ```
+	pub fn from_field_type(field_type: &str) -> Self {
+		match field_type.rsplit('.').next().unwrap_or_default() {
+			"BigIntegerField" | "BigAutoField" | "ForeignKey" | "OneToOneField" => Self::Int64,
+			"PositiveBigIntegerField" => Self::Uint64,
+			"PositiveIntegerField" | "PositiveSmallIntegerField" => Self::Uint32,
+			name if name.ends_with("IntegerField") || name == "AutoField" => Self::Int32,
+			"FloatField" => Self::Double,
+			"BooleanField" => Self::Bool,
+			"BinaryField" => Self::Bytes,
+			"DateTimeField" => Self::Timestamp,
+			_ => Self::String,
+		}
+	}
+}
+
+/// Field of a generated model message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtoField {
+	/// Field name in snake_case
+	pub name: String,
+	/// Field type
+	pub ty: ProtoType,
+	/// Whether the field is declared `optional`
+	pub optional: bool,
+}
+
+/// Model exposed through a generated CRUD service
+#[derive(Debug, Clone)]
+pub struct ProtoModel {
+	name: String,
+	plural: String,
+	primary_key: Option<ProtoField>,
+	fields: Vec<ProtoField>,
+}
+
+impl ProtoModel {
+	/// Describe the model message `name` (PascalCase)
+	///
+	/// The plural used in RPC names defaults to `name` followed by `s`.
+	pub fn new(name: impl Into<String>) -> Self {
+		let name = name.into();
+		Self {
+			plural: format!("{}s", name),
+			name,
+			primary_key: None,
+			fields: Vec::new(),
+		}
+	}
+
+	/// Describe a model from ORM field metadata
+	///
+	/// Nullable fields become `optional`; the primary key is the field marked
+	/// as such.
+	#[cfg(feature = "orm")]
+	pub fn from_model<M: reinhardt_db::orm::Model>(name: impl Into<String>) -> Self {
+		M::field_metadata()
+			.into_iter()
+			.fold(Self::new(name), |model, field| {
+				let ty = ProtoType::from_field_type(&field.field_type);
+				if field.primary_key {
+					model.primary_key(field.name, ty)
+				} else if field.nullable {
+					model.optional_field(field.name, ty)
+				} else {
+					model.field(field.name, ty)
+				}
+			})
+	}
+
+	/// Override the plural used in RPC names, e.g. `"Categories"`
+	pub fn with_plural(mut self, plural: impl Into<String>) -> Self {
+		self.plural = plural.into();
+		self
+	}
+
+	/// Set the primary key, which becomes the first message field
+	pub fn primary_key(mut self, name: impl Into<String>, ty: ProtoType) -> Self {
+		self.primary_key = Some(ProtoField {
+			name: name.into(),
+			ty,
+			optional: false,
+		});
+		self
+	}
+
+	/// Add a required field
+	pub fn field(mut self, name: impl Into<String>, ty: ProtoType) -> Self {
+		self.fields.push(ProtoField {
+			name: name.into(),
+			ty,
+			optional: false,
+		});
+		self
+	}
+
+	/// Add an `optional` field
+	pub fn optional_field(mut self, name: impl Into<String>, ty: ProtoType) -> Self {
+		self.fields.push(ProtoField {
+			name: name.into(),
+			ty,
+			optional: true,
+		});
+		self
+	}
+
+	/// Message name
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	fn primary_key_field(&self) -> io::Result<&ProtoField> {
+		self.primary_key.as_ref().ok_or_else(|| {
+			io::Error::new(
+				io::ErrorKind::InvalidInput,
+				format!("Model {} has no primary key", self.name),
+			)
+		})
+	}
+
+	/// Message field name for a single item, e.g. `article`
+	fn item_field(&self) -> String {
+		to_snake_case(&self.name)
+	}
+}
+
+/// Generator of `.proto` definitions and tonic scaffolding for models
+#[derive(Debug, Clone)]
+pub struct CrudCodegen {
+	package: String,
+	models: Vec<ProtoModel>,
+	proto_dir: Option<PathBuf>,
+	out_dir: Option<PathBuf>,
+}
+
+impl CrudCodegen {
+	/// Generate definitions into the Protobuf package `package`
+	pub fn new(package: impl Into<String>) -> Self {
+		Self {
+			package: package.into(),
+			models: Vec::new(),
+			proto_dir: None,
+			out_dir: None,
+		}
+	}
+
+	/// Add a model
+	pub fn model(mut self, model: ProtoModel) -> Self {
+		self.models.push(model);
+		self
+	}
+
+	/// Write `.proto` files to `dir` instead of `OUT_DIR/proto`
+	///
+	/// Point this at a checked-in directory to share the definitions with
+	/// clients in other languages.
+	pub fn with_proto_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+		self.proto_dir = Some(dir.into());
+		self
+	}
+
+	/// Write generated Rust code to `dir` instead of `OUT_DIR`
+	pub fn with_out_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+		self.out_dir = Some(dir.into());
+		self
+	}
+
+	/// Name of the generated `.proto` file
+	pub fn proto_file_name(&self) -> String {
+		format!("{}.proto", self.package.replace('.', "/"))
+	}
+
+	/// Render the `.proto` definitions
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_grpc::codegen::{CrudCodegen, ProtoModel, ProtoType};
+	///
+	/// let proto = CrudCodegen::new("blog")
+	///     .model(ProtoModel::new("Tag").primary_key("id", ProtoType::Int64).field("name", ProtoType::String))
+	///     .render_proto()
+	///     .unwrap();
+	///
+	/// assert!(proto.contains("message Tag {\n  int64 id = 1;\n  string name = 2;\n}"));
+	/// assert!(proto.contains("rpc ListTags(ListTagsRequest) returns (ListTagsResponse);"));
+	/// ```
+	pub fn render_proto(&self) -> io::Result<String> {
+		let mut out = String::new();
+		let _ = writeln!(out, "// Generated by reinhardt-grpc. Do not edit.");
+		let _ = writeln!(out, "syntax = \"proto3\";");
+		let _ = writeln!(out, "package {};", self.package);
+		let _ = writeln!(out);
+		let _ = writeln!(out, "import \"{}\";", COMMON_IMPORT);
+
+		for model in &self.models {
+			let pk = model.primary_key_field()?;
+			let name = &model.name;
+			let plural = &model.plural;
+			let item = model.item_field();
+
+			let _ = writeln!(out);
+			let _ = writeln!(out, "message {} {{", name);
+			for (number, field) in std::iter::once(pk).chain(&model.fields).enumerate() {
+				let _ = writeln!(
+					out,
+					"  {}{} {} = {};",
+					if field.optional { "optional " } else { "" },
+					field.ty.proto_name(),
+					field.name,
+					number + 1
+				);
+			}
+			let _ = writeln!(out, "}}");
+			let _ = write!(
+				out,
+				"
+message Get{name}Request {{
+  {pk_type} {pk} = 1;
+}}
+
+message List{plural}Request {{
+  int32 page = 1;
+  int32 per_page = 2;
+}}
+
+message List{plural}Response {{
+  repeated {name} items = 1;
+  reinhardt.common.PageInfo page_info = 2;
+}}
+
+message Create{name}Request {{
+  {name} {item} = 1;
+}}
+
+message Update{name}Request {{
+  {name} {item} = 1;
+}}
+
+message Delete{name}Request {{
+  {pk_type} {pk} = 1;
+}}
+
+message BatchCreate{plural}Request {{
+  repeated {name} items = 1;
+}}
+
+service {name}Service {{
+  rpc Get{name}(Get{name}Request) returns ({name});
+  rpc List{plural}(List{plural}Request) returns (List{plural}Response);
+  rpc Create{name}(Create{name}Request) returns ({name});
+  rpc Update{name}(Update{name}Request) returns ({name});
+  rpc Delete{name}(Delete{name}Request) returns (reinhardt.common.Empty);
+  rpc BatchCreate{plural}(BatchCreate{plural}Request) returns (reinhardt.common.BatchResult);
+}}
+",
+				pk_type = pk.ty.proto_name(),
+				pk = pk.name,
+			);
+		}
+		Ok(out)
+	}
+
+	/// Render the Rust scaffold implementing each generated service
+	///
+	/// The code is meant to be `include!`d next to the prost output for the
+	/// package. Each model gets a `{Model}CrudService<H>` delegating to a
+	/// `CrudHandler<{Model}, PrimaryKey>`.
+	pub fn render_service_scaffold(&self) -> io::Result<String> {
+		let mut out = String::from("// Generated by reinhardt-grpc. Do not edit.\n");
+		for model in &self.models {
+			let pk = model.primary_key_field()?;
+			let name = &model.name;
+			let plural = &model.plural;
+			let item = model.item_field();
+			let module = format!("{}_service_server", item);
+			let method = item.clone();
+			let plural_method = to_snake_case(plural);
+			let _ = write!(
+				out,
+				"
+/// `{name}Service` implementation backed by a `CrudHandler`
+pub struct {name}CrudService<H> {{
+	handler: H,
+}}
+
+impl<H> {name}CrudService<H>
+where
+	H: ::reinhardt_grpc::crud::CrudHandler<{name}, {pk_type}>,
+{{
+	/// Serve `handler`
+	pub fn new(handler: H) -> Self {{
+		Self {{ handler }}
+	}}
+
+	/// Wrap the service in its tonic server
+	pub fn into_server(self) -> {module}::{name}ServiceServer<Self> {{
+		{module}::{name}ServiceServer::new(self)
+	}}
+}}
+
+#[::tonic::async_trait]
+impl<H> {module}::{name}Service for {name}CrudService<H>
+where
+	H: ::reinhardt_grpc::crud::CrudHandler<{name}, {pk_type}>,
+{{
+	async fn get_{method}(
+		&self,
+		request: ::tonic::Request<Get{name}Request>,
+	) -> ::std::result::Result<::tonic::Response<{name}>, ::tonic::Status> {{
+		let pk = request.into_inner().{pk};
+		match self.handler.get(::std::clone::Clone::clone(&pk)).await? {{
+			::std::option::Option::Some(item) => ::std::result::Result::Ok(::tonic::Response::new(item)),
+			::std::option::Option::None => ::std::result::Result::Err(::tonic::Status::from(
+				::reinhardt_grpc::GrpcError::NotFound(::std::format!(\"{name} {{:?}} not found\", pk)),
+			)),
+		}}
+	}}
+
+	async fn list_{plural_method}(
+		&self,
+		request: ::tonic::Request<List{plural}Request>,
+	) -> ::std::result::Result<::tonic::Response<List{plural}Response>, ::tonic::Status> {{
+		let request = request.into_inner();
+		let pagination = ::reinhardt_grpc::crud::Pagination::new(request.page, request.per_page);
+		let (items, total) = self.handler.list(pagination).await?;
+		::std::result::Result::Ok(::tonic::Response::new(List{plural}Response {{
+			items,
+			page_info: ::std::option::Option::Some(pagination.page_info(total)),
+		}}))
+	}}
+
+	async fn create_{method}(
+		&self,
+		request: ::tonic::Request<Create{name}Request>,
+	) -> ::std::result::Result<::tonic::Response<{name}>, ::tonic::Status> {{
+		let item = request
+			.into_inner()
+			.{item}
+			.ok_or_else(|| ::reinhardt_grpc::GrpcError::FieldViolation {{
+				field: ::std::string::ToString::to_string(\"{item}\"),
+				message: ::std::string::ToString::to_string(\"{item} is required\"),
+			}})?;
+		::std::result::Result::Ok(::tonic::Response::new(self.handler.create(item).await?))
+	}}
+
+	async fn update_{method}(
+		&self,
+		request: ::tonic::Request<Update{name}Request>,
+	) -> ::std::result::Result<::tonic::Response<{name}>, ::tonic::Status> {{
+		let item = request
+			.into_inner()
+			.{item}
+			.ok_or_else(|| ::reinhardt_grpc::GrpcError::FieldViolation {{
+				field: ::std::string::ToString::to_string(\"{item}\"),
+				message: ::std::string::ToString::to_string(\"{item} is required\"),
+			}})?;
+		let pk = ::std::clone::Clone::clone(&item.{pk});
+		if self.handler.get(::std::clone::Clone::clone(&pk)).await?.is_none() {{
+			return ::std::result::Result::Err(::tonic::Status::from(
+				::reinhardt_grpc::GrpcError::NotFound(::std::format!(\"{name} {{:?}} not found\", pk)),
+			));
+		}}
+		::std::result::Result::Ok(::tonic::Response::new(self.handler.update(item).await?))
+	}}
+
+	async fn delete_{method}(
+		&self,
+		request: ::tonic::Request<Delete{name}Request>,
+	) -> ::std::result::Result<::tonic::Response<::reinhardt_grpc::proto::common::Empty>, ::tonic::Status> {{
+		let pk = request.into_inner().{pk};
+		if !self.handler.delete(::std::clone::Clone::clone(&pk)).await? {{
+			return ::std::result::Result::Err(::tonic::Status::from(
+				::reinhardt_grpc::GrpcError::NotFound(::std::format!(\"{name} {{:?}} not found\", pk)),
+			));
+		}}
+		::std::result::Result::Ok(::tonic::Response::new(::reinhardt_grpc::proto::common::Empty {{}}))
+	}}
+
+	async fn batch_create_{plural_method}(
+		&self,
+		request: ::tonic::Request<BatchCreate{plural}Request>,
+	) -> ::std::result::Result<::tonic::Response<::reinhardt_grpc::proto::common::BatchResult>, ::tonic::Status> {{
+		let mut results = ::std::vec::Vec::new();
+		for item in request.into_inner().items {{
+			results.push(self.handler.create(item).await);
+		}}
+		::std::result::Result::Ok(::tonic::Response::new(::reinhardt_grpc::crud::batch_result(results)))
+	}}
+}}
+",
+				pk_type = pk.ty.rust_name(),
+				pk = pk.name,
+			);
+		}
+		Ok(out)
+	}
+
+	/// Write the `.proto` files, returning the proto directory
+	///
+	/// The directory receives the package file and the imported
+	/// `reinhardt/common.proto`.
+	pub fn write_proto(&self) -> io::Result<PathBuf> {
+		let proto_dir = match &self.proto_dir {
+			Some(dir) => dir.clone(),
+			None => out_dir(&self.out_dir)?.join("proto"),
+		};
+		write_file(&proto_dir.join(COMMON_IMPORT), COMMON_PROTO)?;
+		write_file(
+			&proto_dir.join(self.proto_file_name()),
+			&self.render_proto()?,
+		)?;
+		Ok(proto_dir)
+	}
+
+	/// Generate the `.proto` files, prost messages, tonic services and the
+	/// CRUD scaffold
+	///
+	/// Meant to be called from `build.rs`. The scaffold is written to
+	/// `{package}.crud.rs` in the output directory; common types resolve to
+	/// `reinhardt_grpc::proto::common`.
+	pub fn compile(&self) -> io::Result<()> {
+		let out_dir = out_dir(&self.out_dir)?;
+		let proto_dir = self.write_proto()?;
+		tonic_prost_build::configure()
+			.build_server(true)
+			.build_client(true)
+			.out_dir(&out_dir)
+			.extern_path(".reinhardt.common", "::reinhardt_grpc::proto::common")
+			.file_descriptor_set_path(out_dir.join(format!("{}_descriptor.bin", self.package)))
+			.compile_protos(&[proto_dir.join(self.proto_file_name())], &[proto_dir])?;
+		write_file(
+			&out_dir.join(format!("{}.crud.rs", self.package)),
+			&self.render_service_scaffold()?,
+		)
+	}
+}
+
+fn out_dir(configured: &Option<PathBuf>) -> io::Result<PathBuf> {
+	match configured {
+		Some(dir) => Ok(dir.clone()),
+		None => std::env::var_os("OUT_DIR")
+			.map(PathBuf::from)
+			.ok_or_else(|| {
+				io::Error::new(
+					io::ErrorKind::NotFound,
+					"OUT_DIR is not set; call from build.rs or use with_out_dir",
+				)
+			}),
+	}
+}
+
+fn write_file(path: &Path, contents: &str) -> io::Result<()> {
+	if let Some(parent) = path.parent() {
+		std::fs::create_dir_all(parent)?;
+	}
+	std::fs::write(path, contents)
+}
+
+/// `BlogPost` -> `blog_post`, `HTTPLog` -> `http_log`
+fn to_snake_case(name: &str) -> String {
+	let chars: Vec<char> = name.chars().collect();
+	let mut snake = String::with_capacity(name.len() + 4);
+	for (i, &c) in chars.iter().enumerate() {
+		if c.is_uppercase() {
+			let after_lower = i > 0 && !chars[i - 1].is_uppercase();
+			let ends_acronym = i > 0
+				&& chars[i - 1].is_uppercase()
+				&& chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+			if after_lower || ends_acronym {
+				snake.push('_');
+			}
+			snake.extend(c.to_lowercase());
+		} else {
+			snake.push(c);
+		}
+	}
+	snake
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+
+	#[rstest]
+	#[case("Article", "article")]
+	#[case("BlogPost", "blog_post")]
+	#[case("Categories", "categories")]
+	#[case("HTTPLog", "http_log")]
+	fn test_to_snake_case(#[case] name: &str, #[case] expected: &str) {
+		assert_eq!(to_snake_case(name), expected);
+	}
+
+	#[rstest]
+	#[case("reinhardt.orm.models.IntegerField", ProtoType::Int32)]
+	#[case("reinhardt.orm.models.AutoField", ProtoType::Int32)]
+	#[case("reinhardt.orm.models.ForeignKey", ProtoType::Int64)]
+	#[case("reinhardt.orm.models.PositiveIntegerField", ProtoType::Uint32)]
+	#[case("reinhardt.orm.models.FloatField", ProtoType::Double)]
+	#[case("reinhardt.orm.models.DateTimeField", ProtoType::Timestamp)]
+	#[case("reinhardt.orm.models.DecimalField", ProtoType::String)]
+	fn test_from_field_type(#[case] field_type: &str, #[case] expected: ProtoType) {
+		assert_eq!(ProtoType::from_field_type(field_type), expected);
+	}
+
+	#[rstest]
+	fn test_missing_primary_key() {
+		let codegen = CrudCodegen::new("blog").model(ProtoModel::new("Tag"));
+
+		assert!(codegen.render_proto().is_err());
+		assert!(codegen.render_service_scaffold().is_err());
+	}
+}
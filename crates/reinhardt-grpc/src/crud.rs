@@ -0,0 +1,239 @@
+//! Runtime support for generated CRUD services
+//!
+//! Services generated by the `codegen` feature delegate to a
+//! [`CrudHandler`] and use the helpers here to build the common
+//! `PageInfo` and `BatchResult` messages.
+
+use crate::error::GrpcResult;
+use crate::proto::common::{BatchResult, PageInfo};
+use async_trait::async_trait;
+
+/// Default number of items per page when a request leaves `per_page` unset
+pub const DEFAULT_PER_PAGE: i32 = 20;
+
+/// Largest accepted `per_page`
+pub const MAX_PER_PAGE: i32 = 100;
+
+/// Storage operations behind a generated CRUD service
+///
+/// `M` is the generated Protobuf message of the model and `K` its primary
+/// key type.
+///
+/// # Examples
+///
+/// ```
+/// use async_trait::async_trait;
+/// use reinhardt_grpc::GrpcResult;
+/// use reinhardt_grpc::crud::{CrudHandler, Pagination};
+/// use std::collections::BTreeMap;
+/// use std::sync::Mutex;
+///
+/// #[derive(Clone)]
+/// struct Tag {
+///     id: i64,
+///     name: String,
+/// }
+///
+/// struct MemoryTags(Mutex<BTreeMap<i64, Tag>>);
+///
+/// #[async_trait]
+/// impl CrudHandler<Tag, i64> for MemoryTags {
+///     async fn get(&self, pk: i64) -> GrpcResult<Option<Tag>> {
+///         Ok(self.0.lock().unwrap().get(&pk).cloned())
+///     }
+///
+///     async fn list(&self, pagination: Pagination) -> GrpcResult<(Vec<Tag>, u64)> {
+///         let tags = self.0.lock().unwrap();
+///         let page = tags
+///             .values()
+///             .skip(pagination.offset())
+///             .take(pagination.limit())
+///             .cloned()
+///             .collect();
+///         Ok((page, tags.len() as u64))
+///     }
+///
+///     async fn create(&self, mut tag: Tag) -> GrpcResult<Tag> {
+///         let mut tags = self.0.lock().unwrap();
+///         tag.id = tags.keys().last().copied().unwrap_or(0) + 1;
+///         tags.insert(tag.id, tag.clone());
+///         Ok(tag)
+///     }
+///
+///     async fn update(&self, tag: Tag) -> GrpcResult<Tag> {
+///         self.0.lock().unwrap().insert(tag.id, tag.clone());
+///         Ok(tag)
+///     }
+///
+///     async fn delete(&self, pk: i64) -> GrpcResult<bool> {
+///         Ok(self.0.lock().unwrap().remove(&pk).is_some())
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait CrudHandler<M, K>: Send + Sync + 'static
+where
+	M: Send + 'static,
+	K: Send + 'static,
+{
+	/// Fetch one item by primary key
+	async fn get(&self, pk: K) -> GrpcResult<Option<M>>;
+
+	/// Fetch one page of items and the total item count
+	async fn list(&self, pagination: Pagination) -> GrpcResult<(Vec<M>, u64)>;
+
+	/// Insert an item, returning it with its assigned primary key
+	async fn create(&self, item: M) -> GrpcResult<M>;
+
+	/// Replace an existing item
+	async fn update(&self, item: M) -> GrpcResult<M>;
+
+	/// Delete an item, returning whether it existed
+	async fn delete(&self, pk: K) -> GrpcResult<bool>;
+}
+
+/// 1-based page requested by a list call
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pagination {
+	/// Page number, starting at 1
+	pub page: i32,
+	/// Items per page
+	pub per_page: i32,
+}
+
+impl Pagination {
+	/// Normalize request values
+	///
+	/// Non-positive pages become 1, unset (zero) `per_page` becomes
+	/// [`DEFAULT_PER_PAGE`], and `per_page` is capped at [`MAX_PER_PAGE`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_grpc::crud::Pagination;
+	///
+	/// let pagination = Pagination::new(0, 500);
+	/// assert_eq!(pagination.page, 1);
+	/// assert_eq!(pagination.per_page, 100);
+	/// ```
+	pub fn new(page: i32, per_page: i32) -> Self {
+		Self {
+			page: page.max(1),
+			per_page: if per_page <= 0 {
+				DEFAULT_PER_PAGE
+			} else {
+				per_page.min(MAX_PER_PAGE)
+			},
+		}
+	}
+
+	/// Number of items to skip
+	pub fn offset(&self) -> usize {
+		(self.page as usize - 1) * self.per_page as usize
+	}
+
+	/// Number of items to fetch
+	pub fn limit(&self) -> usize {
+		self.per_page as usize
+	}
+
+	/// Build the `PageInfo` for this page of `total` items
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_grpc::crud::Pagination;
+	///
+	/// let info = Pagination::new(2, 10).page_info(25);
+	/// assert_eq!(info.total, 25);
+	/// assert!(info.has_next);
+	/// assert!(info.has_prev);
+	/// ```
+	pub fn page_info(&self, total: u64) -> PageInfo {
+		let seen = self.offset() as u64 + self.limit() as u64;
+		PageInfo {
+			page: self.page,
+			per_page: self.per_page,
+			total: i32::try_from(total).unwrap_or(i32::MAX),
+			has_next: seen < total,
+			has_prev: self.page > 1,
+		}
+	}
+}
+
+/// Summarize the outcomes of a batch operation
+///
+/// Failures are reported with their index in `metadata["index"]`.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_grpc::GrpcError;
+/// use reinhardt_grpc::crud::batch_result;
+///
+/// let result = batch_result(vec![
+///     Ok(1),
+///     Err(GrpcError::InvalidArgument("name is required".to_string())),
+/// ]);
+/// assert_eq!(result.success_count, 1);
+/// assert_eq!(result.failure_count, 1);
+/// assert_eq!(result.errors[0].code, "INVALID_ARGUMENT");
+/// assert_eq!(result.errors[0].metadata["index"], "1");
+/// ```
+pub fn batch_result<T>(results: Vec<GrpcResult<T>>) -> BatchResult {
+	let mut batch = BatchResult::default();
+	for (index, result) in results.into_iter().enumerate() {
+		match result {
+			Ok(_) => batch.success_count += 1,
+			Err(error) => {
+				batch.failure_count += 1;
+				let mut details = error.details();
+				details
+					.metadata
+					.insert("index".to_string(), index.to_string());
+				batch.errors.push(details);
+			}
+		}
+	}
+	batch
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+
+	#[rstest]
+	#[case(1, 10, 25, true, false)]
+	#[case(3, 10, 25, false, true)]
+	#[case(1, 10, 10, false, false)]
+	#[case(1, 10, 0, false, false)]
+	fn test_page_info(
+		#[case] page: i32,
+		#[case] per_page: i32,
+		#[case] total: u64,
+		#[case] has_next: bool,
+		#[case] has_prev: bool,
+	) {
+		let info = Pagination::new(page, per_page).page_info(total);
+
+		assert_eq!(info.has_next, has_next);
+		assert_eq!(info.has_prev, has_prev);
+	}
+
+	#[rstest]
+	#[case(-1, -1, 1, DEFAULT_PER_PAGE)]
+	#[case(2, 0, 2, DEFAULT_PER_PAGE)]
+	#[case(1, 1000, 1, MAX_PER_PAGE)]
+	fn test_pagination_normalized(
+		#[case] page: i32,
+		#[case] per_page: i32,
+		#[case] expected_page: i32,
+		#[case] expected_per_page: i32,
+	) {
+		let pagination = Pagination::new(page, per_page);
+
+		assert_eq!(pagination.page, expected_page);
+		assert_eq!(pagination.per_page, expected_per_page);
+	}
+}
@@ -51,6 +51,14 @@
 //!     }
 //! }
 //! ```
+//!
+//! # Streaming Methods
+//!
+//! Server-streaming and bidirectional-streaming methods return
+//! `Response<Self::XStream>`, so they cannot be moved out of the trait
+//! implementation. Apply `#[grpc_handler]` to the trait `impl` block
+//! instead, above `#[tonic::async_trait]`. Dependencies are resolved once
+//! per call and can be moved into the returned stream.
 
 /// Extension trait for `tonic::Request` to support DI context extraction
 ///
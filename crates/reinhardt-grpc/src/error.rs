@@ -1,5 +1,42 @@
+//! Error type of gRPC services and its mapping to `tonic::Status`
+//!
+//! Framework errors (ORM, validation and permission errors) convert into
+//! [`GrpcError`], which converts into a `tonic::Status` with the matching
+//! gRPC code. The status carries a `reinhardt.common.Error` message in its
+//! details so that clients can read the error code and metadata such as the
+//! invalid field.
+//!
+//! Database and internal errors can reveal SQL, table names or connection
+//! strings, so their detail is logged and clients receive a generic message.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use reinhardt_grpc::error::IntoStatus;
+//!
+//! async fn get_user(&self, request: Request<GetUserRequest>) -> Result<Response<User>, Status> {
+//!     // `Error::NotFound` from the ORM becomes `Code::NotFound`
+//!     let user = User::objects().get(request.into_inner().id).await.into_status()?;
+//!     Ok(Response::new(user.into()))
+//! }
+//! ```
+
+use crate::proto::common::Error as ErrorDetails;
+use prost::Message;
+use reinhardt_core::exception::Error as FrameworkError;
+use reinhardt_core::validators::ValidationError;
+use std::collections::HashMap;
 use thiserror::Error;
 
+/// Message sent to clients in place of internal error details
+const INTERNAL_MESSAGE: &str = "Internal server error";
+
+/// Log the detail of a server-side error and return the generic message
+fn internal_message(error: &dyn std::fmt::Display) -> String {
+	tracing::error!(error = %error, "gRPC request failed with an internal error");
+	INTERNAL_MESSAGE.to_string()
+}
+
 #[derive(Debug, Error)]
 pub enum GrpcError {
 	#[error("Connection error: {0}")]
@@ -14,28 +51,257 @@ pub enum GrpcError {
 	#[error("Invalid argument: {0}")]
 	InvalidArgument(String),
 
+	#[error("Invalid field '{field}': {message}")]
+	FieldViolation { field: String, message: String },
+
+	#[error("Already exists: {0}")]
+	AlreadyExists(String),
+
+	#[error("Unauthenticated: {0}")]
+	Unauthenticated(String),
+
+	#[error("Permission denied: {0}")]
+	PermissionDenied(String),
+
+	#[error("Aborted: {0}")]
+	Aborted(String),
+
+	#[error("Unimplemented: {0}")]
+	Unimplemented(String),
+
 	#[error("Internal error: {0}")]
 	Internal(String),
 }
 
 pub type GrpcResult<T> = Result<T, GrpcError>;
 
+impl GrpcError {
+	/// gRPC status code of the error
+	pub fn code(&self) -> tonic::Code {
+		match self {
+			GrpcError::Connection(_) => tonic::Code::Unavailable,
+			GrpcError::NotFound(_) => tonic::Code::NotFound,
+			GrpcError::InvalidArgument(_) | GrpcError::FieldViolation { .. } => {
+				tonic::Code::InvalidArgument
+			}
+			GrpcError::AlreadyExists(_) => tonic::Code::AlreadyExists,
+			GrpcError::Unauthenticated(_) => tonic::Code::Unauthenticated,
+			GrpcError::PermissionDenied(_) => tonic::Code::PermissionDenied,
+			GrpcError::Aborted(_) => tonic::Code::Aborted,
+			GrpcError::Unimplemented(_) => tonic::Code::Unimplemented,
+			GrpcError::Service(_) | GrpcError::Internal(_) => tonic::Code::Internal,
+		}
+	}
+
+	/// Error code reported in `reinhardt.common.Error` messages
+	pub fn error_code(&self) -> &'static str {
+		match self {
+			GrpcError::Connection(_) => "UNAVAILABLE",
+			GrpcError::Service(_) => "SERVICE_ERROR",
+			GrpcError::NotFound(_) => "NOT_FOUND",
+			GrpcError::InvalidArgument(_) => "INVALID_ARGUMENT",
+			GrpcError::FieldViolation { .. } => "FIELD_VIOLATION",
+			GrpcError::AlreadyExists(_) => "ALREADY_EXISTS",
+			GrpcError::Unauthenticated(_) => "UNAUTHENTICATED",
+			GrpcError::PermissionDenied(_) => "PERMISSION_DENIED",
+			GrpcError::Aborted(_) => "ABORTED",
+			GrpcError::Unimplemented(_) => "UNIMPLEMENTED",
+			GrpcError::Internal(_) => "INTERNAL",
+		}
+	}
+
+	/// Message sent as the `grpc-message` of the status
+	pub fn message(&self) -> &str {
+		match self {
+			GrpcError::Connection(message)
+			| GrpcError::Service(message)
+			| GrpcError::NotFound(message)
+			| GrpcError::InvalidArgument(message)
+			| GrpcError::AlreadyExists(message)
+			| GrpcError::Unauthenticated(message)
+			| GrpcError::PermissionDenied(message)
+			| GrpcError::Aborted(message)
+			| GrpcError::Unimplemented(message)
+			| GrpcError::Internal(message)
+			| GrpcError::FieldViolation { message, .. } => message,
+		}
+	}
+
+	/// Structured `reinhardt.common.Error` describing the error
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_grpc::GrpcError;
+	///
+	/// let error = GrpcError::FieldViolation {
+	///     field: "email".to_string(),
+	///     message: "Invalid email".to_string(),
+	/// };
+	/// let details = error.details();
+	/// assert_eq!(details.code, "FIELD_VIOLATION");
+	/// assert_eq!(details.metadata["field"], "email");
+	/// ```
+	pub fn details(&self) -> ErrorDetails {
+		let mut metadata = HashMap::new();
+		if let GrpcError::FieldViolation { field, .. } = self {
+			metadata.insert("field".to_string(), field.clone());
+		}
+		ErrorDetails {
+			code: self.error_code().to_string(),
+			message: self.message().to_string(),
+			metadata,
+		}
+	}
+}
+
+/// Decode the `reinhardt.common.Error` attached to a status
+///
+/// Returns `None` if the status has no details or they are not a
+/// `reinhardt.common.Error` message.
+pub fn status_details(status: &tonic::Status) -> Option<ErrorDetails> {
+	if status.details().is_empty() {
+		return None;
+	}
+	ErrorDetails::decode(status.details()).ok()
+}
+
 impl From<tonic::Status> for GrpcError {
 	fn from(status: tonic::Status) -> Self {
+		let message = status.message().to_string();
 		match status.code() {
-			tonic::Code::NotFound => GrpcError::NotFound(status.message().to_string()),
-			tonic::Code::InvalidArgument => {
-				GrpcError::InvalidArgument(status.message().to_string())
+			tonic::Code::NotFound => GrpcError::NotFound(message),
+			tonic::Code::InvalidArgument => match status_details(&status)
+				.and_then(|mut details| details.metadata.remove("field"))
+			{
+				Some(field) => GrpcError::FieldViolation { field, message },
+				None => GrpcError::InvalidArgument(message),
+			},
+			tonic::Code::AlreadyExists => GrpcError::AlreadyExists(message),
+			tonic::Code::Unauthenticated => GrpcError::Unauthenticated(message),
+			tonic::Code::PermissionDenied => GrpcError::PermissionDenied(message),
+			tonic::Code::Aborted => GrpcError::Aborted(message),
+			tonic::Code::Unimplemented => GrpcError::Unimplemented(message),
+			tonic::Code::Unavailable => GrpcError::Connection(message),
+			_ => GrpcError::Internal(message),
+		}
+	}
+}
+
+impl From<GrpcError> for tonic::Status {
+	fn from(error: GrpcError) -> Self {
+		let details = error.details().encode_to_vec();
+		tonic::Status::with_details(error.code(), error.message(), details.into())
+	}
+}
+
+impl From<FrameworkError> for GrpcError {
+	fn from(error: FrameworkError) -> Self {
+		match error {
+			FrameworkError::Http(message)
+			| FrameworkError::Serialization(message)
+			| FrameworkError::Validation(message)
+			| FrameworkError::ParseError(message)
+			| FrameworkError::InvalidPage(message)
+			| FrameworkError::InvalidCursor(message)
+			| FrameworkError::InvalidLimit(message)
+			| FrameworkError::MissingParameter(message) => GrpcError::InvalidArgument(message),
+			FrameworkError::ParamValidation(context) => match context.field_name.clone() {
+				Some(field) => GrpcError::FieldViolation {
+					field,
+					message: context.message,
+				},
+				None => GrpcError::InvalidArgument(context.format_error()),
+			},
+			FrameworkError::BodyAlreadyConsumed | FrameworkError::MissingContentType => {
+				GrpcError::InvalidArgument(error.to_string())
+			}
+			FrameworkError::Authentication(message) => GrpcError::Unauthenticated(message),
+			FrameworkError::Authorization(message) => GrpcError::PermissionDenied(message),
+			FrameworkError::NotFound(message) | FrameworkError::TemplateNotFound(message) => {
+				GrpcError::NotFound(message)
 			}
-			tonic::Code::Unavailable => GrpcError::Connection(status.message().to_string()),
-			_ => GrpcError::Internal(status.message().to_string()),
+			FrameworkError::MethodNotAllowed(message) => GrpcError::Unimplemented(message),
+			FrameworkError::NotAcceptable(context)
+			| FrameworkError::UnsupportedMediaType(context) => {
+				GrpcError::InvalidArgument(context.format_error())
+			}
+			FrameworkError::Conflict(message) => GrpcError::AlreadyExists(message),
+			FrameworkError::Database(_)
+			| FrameworkError::Internal(_)
+			| FrameworkError::ImproperlyConfigured(_)
+			| FrameworkError::Other(_) => GrpcError::Internal(internal_message(&error)),
+		}
+	}
+}
+
+impl From<ValidationError> for GrpcError {
+	fn from(error: ValidationError) -> Self {
+		match &error {
+			ValidationError::NotUnique { field, .. } => GrpcError::FieldViolation {
+				field: field.clone(),
+				message: error.to_string(),
+			},
+			_ => GrpcError::InvalidArgument(error.to_string()),
+		}
+	}
+}
+
+#[cfg(feature = "orm")]
+impl From<reinhardt_db::DatabaseError> for GrpcError {
+	fn from(error: reinhardt_db::DatabaseError) -> Self {
+		use reinhardt_db::DatabaseError;
+
+		let message = internal_message(&error);
+		match error {
+			DatabaseError::ConnectionError(_)
+			| DatabaseError::ConnectionReset(_)
+			| DatabaseError::LockTimeout(_) => GrpcError::Connection("Database unavailable".to_string()),
+			DatabaseError::SerializationFailure(_) | DatabaseError::Deadlock(_) => {
+				GrpcError::Aborted("Transaction aborted, retry the request".to_string())
+			}
+			DatabaseError::UnsupportedFeature { .. } | DatabaseError::NotSupported(_) => {
+				GrpcError::Unimplemented("Operation not supported".to_string())
+			}
+			_ => GrpcError::Internal(message),
 		}
 	}
 }
 
+/// Conversion of framework results into `tonic::Status` results
+///
+/// Implemented for every `Result` whose error converts into [`GrpcError`],
+/// so handlers can use `?` on ORM, validation and permission errors.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_core::exception::Error;
+/// use reinhardt_grpc::error::IntoStatus;
+///
+/// let result: Result<(), Error> = Err(Error::Authorization("Staff only".to_string()));
+/// let status = result.into_status().unwrap_err();
+/// assert_eq!(status.code(), tonic::Code::PermissionDenied);
+/// assert_eq!(status.message(), "Staff only");
+/// ```
+pub trait IntoStatus<T> {
+	/// Convert the error into a `tonic::Status`
+	fn into_status(self) -> Result<T, tonic::Status>;
+}
+
+impl<T, E> IntoStatus<T> for Result<T, E>
+where
+	E: Into<GrpcError>,
+{
+	fn into_status(self) -> Result<T, tonic::Status> {
+		self.map_err(|error| tonic::Status::from(error.into()))
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use rstest::rstest;
 
 	#[test]
 	fn test_error_display() {
@@ -60,4 +326,69 @@ mod tests {
 		let error = GrpcError::from(status);
 		assert!(matches!(error, GrpcError::Connection(_)));
 	}
+
+	#[test]
+	fn test_into_tonic_status() {
+		let status = tonic::Status::from(GrpcError::NotFound("User 1".to_string()));
+		assert_eq!(status.code(), tonic::Code::NotFound);
+		assert_eq!(status.message(), "User 1");
+
+		let status = tonic::Status::from(GrpcError::Service("failed".to_string()));
+		assert_eq!(status.code(), tonic::Code::Internal);
+	}
+
+	#[rstest]
+	#[case(FrameworkError::Validation("bad".to_string()), tonic::Code::InvalidArgument)]
+	#[case(FrameworkError::Authentication("no token".to_string()), tonic::Code::Unauthenticated)]
+	#[case(FrameworkError::Authorization("staff only".to_string()), tonic::Code::PermissionDenied)]
+	#[case(FrameworkError::NotFound("User 1".to_string()), tonic::Code::NotFound)]
+	#[case(FrameworkError::Conflict("duplicate".to_string()), tonic::Code::AlreadyExists)]
+	#[case(FrameworkError::Database("timeout".to_string()), tonic::Code::Internal)]
+	fn test_framework_error_code(#[case] error: FrameworkError, #[case] code: tonic::Code) {
+		let status = tonic::Status::from(GrpcError::from(error));
+		assert_eq!(status.code(), code);
+	}
+
+	#[rstest]
+	#[case(FrameworkError::Database("relation \"users\" does not exist".to_string()))]
+	#[case(FrameworkError::Internal("pool exhausted at db.internal:5432".to_string()))]
+	#[case(FrameworkError::ImproperlyConfigured("DATABASE_URL missing".to_string()))]
+	fn test_internal_errors_hide_details(#[case] error: FrameworkError) {
+		let status = tonic::Status::from(GrpcError::from(error));
+
+		assert_eq!(status.code(), tonic::Code::Internal);
+		assert_eq!(status.message(), INTERNAL_MESSAGE);
+		assert_eq!(status_details(&status).unwrap().message, INTERNAL_MESSAGE);
+	}
+
+	#[test]
+	fn test_status_details_round_trip() {
+		let error = GrpcError::from(ValidationError::NotUnique {
+			field: "email".to_string(),
+			value: "a@example.com".to_string(),
+		});
+		let status = tonic::Status::from(error);
+
+		let details = status_details(&status).unwrap();
+		assert_eq!(status.code(), tonic::Code::InvalidArgument);
+		assert_eq!(details.code, "FIELD_VIOLATION");
+		assert_eq!(details.metadata["field"], "email");
+		assert!(matches!(
+			GrpcError::from(status),
+			GrpcError::FieldViolation { field, .. } if field == "email"
+		));
+	}
+
+	#[test]
+	fn test_param_validation_field() {
+		let context = reinhardt_core::exception::ParamErrorContext::new(
+			reinhardt_core::exception::ParamType::Json,
+			"missing field",
+		)
+		.with_field("name");
+
+		let error = GrpcError::from(FrameworkError::ParamValidation(Box::new(context)));
+
+		assert!(matches!(error, GrpcError::FieldViolation { field, .. } if field == "name"));
+	}
 }
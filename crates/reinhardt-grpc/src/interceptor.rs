@@ -0,0 +1,814 @@
+//! Middleware chains for gRPC services
+//!
+//! This module mirrors the HTTP `Middleware` trait for tonic services.
+//! A [`GrpcMiddleware`] receives the raw HTTP/2 request of a call and the
+//! next handler in the chain, so it can inspect metadata, reject the call
+//! with a [`Status`], or observe the response.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use reinhardt_grpc::interceptor::{
+//!     AuthMiddleware, GrpcLoggingMiddleware, GrpcMetricsMiddleware, GrpcMiddlewareChain,
+//!     MetadataTokenAuthenticator, Principal,
+//! };
+//! use std::sync::Arc;
+//!
+//! let authenticator = MetadataTokenAuthenticator::new()
+//!     .with_token("secret-token", Principal::new("1", "alice"));
+//!
+//! let service = GrpcMiddlewareChain::new()
+//!     .with_middleware(Arc::new(GrpcLoggingMiddleware::new()))
+//!     .with_middleware(Arc::new(GrpcMetricsMiddleware::new()))
+//!     .with_middleware(Arc::new(AuthMiddleware::new(Arc::new(authenticator))))
+//!     .service(UserServiceServer::new(UserServiceImpl));
+//!
+//! tonic::transport::Server::builder()
+//!     .add_service(service)
+//!     .serve(addr)
+//!     .await?;
+//! ```
+
+use crate::error::GrpcError;
+use async_trait::async_trait;
+use http_body::Body as _;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tonic::body::Body;
+use tonic::metadata::MetadataMap;
+use tonic::server::NamedService;
+use tonic::{Code, Status};
+use tower_service::Service;
+
+/// HTTP/2 request carrying a gRPC call
+pub type GrpcHttpRequest = http::Request<Body>;
+
+/// HTTP/2 response carrying a gRPC reply
+pub type GrpcHttpResponse = http::Response<Body>;
+
+/// Handler for a gRPC call
+///
+/// This is the gRPC counterpart of the HTTP `Handler` trait. The innermost
+/// handler of a chain forwards the call to the wrapped tonic service.
+#[async_trait]
+pub trait GrpcHandler: Send + Sync {
+	/// Handles a gRPC call and produces a response.
+	///
+	/// # Errors
+	///
+	/// Returns a `Status` if the call is rejected.
+	async fn handle(&self, request: GrpcHttpRequest) -> Result<GrpcHttpResponse, Status>;
+}
+
+/// Middleware wrapping the calls to a gRPC service
+///
+/// # Examples
+///
+/// ```
+/// use async_trait::async_trait;
+/// use reinhardt_grpc::interceptor::{
+///     GrpcHandler, GrpcHttpRequest, GrpcHttpResponse, GrpcMiddleware,
+/// };
+/// use std::sync::Arc;
+/// use tonic::Status;
+///
+/// struct RequireTenant;
+///
+/// #[async_trait]
+/// impl GrpcMiddleware for RequireTenant {
+///     async fn process(
+///         &self,
+///         request: GrpcHttpRequest,
+///         next: Arc<dyn GrpcHandler>,
+///     ) -> Result<GrpcHttpResponse, Status> {
+///         if !request.headers().contains_key("x-tenant") {
+///             return Err(Status::invalid_argument("x-tenant metadata is required"));
+///         }
+///         next.handle(request).await
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait GrpcMiddleware: Send + Sync {
+	/// Processes a call through this middleware.
+	///
+	/// # Errors
+	///
+	/// Returns a `Status` to reject the call. It is sent to the client as
+	/// a trailers-only response.
+	async fn process(
+		&self,
+		request: GrpcHttpRequest,
+		next: Arc<dyn GrpcHandler>,
+	) -> Result<GrpcHttpResponse, Status>;
+
+	/// Determines whether this middleware should run for the given call.
+	///
+	/// By default, returns `true` (always execute).
+	fn should_continue(&self, _request: &GrpcHttpRequest) -> bool {
+		true
+	}
+}
+
+/// Ordered list of middleware applied around tonic services
+///
+/// Middleware run in the order they were added: the first one sees the
+/// call first and the response last.
+#[derive(Clone, Default)]
+pub struct GrpcMiddlewareChain {
+	middlewares: Vec<Arc<dyn GrpcMiddleware>>,
+}
+
+impl GrpcMiddlewareChain {
+	/// Creates an empty chain.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a middleware to the chain using builder pattern.
+	pub fn with_middleware(mut self, middleware: Arc<dyn GrpcMiddleware>) -> Self {
+		self.middlewares.push(middleware);
+		self
+	}
+
+	/// Adds a middleware to the chain.
+	pub fn add_middleware(&mut self, middleware: Arc<dyn GrpcMiddleware>) {
+		self.middlewares.push(middleware);
+	}
+
+	/// Wraps a tonic service with this chain.
+	///
+	/// The returned service keeps the service name of `inner`, so it can be
+	/// passed to `Server::add_service` directly.
+	pub fn service<S>(&self, inner: S) -> GrpcMiddlewareService<S> {
+		GrpcMiddlewareService {
+			inner,
+			middlewares: self.middlewares.clone().into(),
+		}
+	}
+}
+
+/// Tonic service wrapped by a [`GrpcMiddlewareChain`]
+#[derive(Clone)]
+pub struct GrpcMiddlewareService<S> {
+	inner: S,
+	middlewares: Arc<[Arc<dyn GrpcMiddleware>]>,
+}
+
+impl<S: NamedService> NamedService for GrpcMiddlewareService<S> {
+	const NAME: &'static str = S::NAME;
+}
+
+impl<S> Service<GrpcHttpRequest> for GrpcMiddlewareService<S>
+where
+	S: Service<GrpcHttpRequest, Response = GrpcHttpResponse, Error = Infallible>
+		+ Clone
+		+ Send
+		+ Sync
+		+ 'static,
+	S::Future: Send + 'static,
+{
+	type Response = GrpcHttpResponse;
+	type Error = Infallible;
+	type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+	fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+		// The inner service is cloned and driven to readiness per call
+		Poll::Ready(Ok(()))
+	}
+
+	fn call(&mut self, request: GrpcHttpRequest) -> Self::Future {
+		let mut handler: Arc<dyn GrpcHandler> = Arc::new(InnerServiceHandler {
+			inner: self.inner.clone(),
+		});
+		for middleware in self
+			.middlewares
+			.iter()
+			.rev()
+			.filter(|mw| mw.should_continue(&request))
+		{
+			handler = Arc::new(ComposedHandler {
+				middleware: middleware.clone(),
+				next: handler,
+			});
+		}
+
+		Box::pin(async move {
+			Ok(handler
+				.handle(request)
+				.await
+				.unwrap_or_else(|status| status.into_http()))
+		})
+	}
+}
+
+/// Innermost handler forwarding the call to the tonic service.
+struct InnerServiceHandler<S> {
+	inner: S,
+}
+
+#[async_trait]
+impl<S> GrpcHandler for InnerServiceHandler<S>
+where
+	S: Service<GrpcHttpRequest, Response = GrpcHttpResponse, Error = Infallible>
+		+ Clone
+		+ Send
+		+ Sync
+		+ 'static,
+	S::Future: Send + 'static,
+{
+	async fn handle(&self, request: GrpcHttpRequest) -> Result<GrpcHttpResponse, Status> {
+		let mut service = self.inner.clone();
+		let Ok(()) = std::future::poll_fn(|cx| service.poll_ready(cx)).await;
+		let Ok(response) = service.call(request).await;
+		Ok(response)
+	}
+}
+
+/// Handler that composes a middleware with the next handler.
+struct ComposedHandler {
+	middleware: Arc<dyn GrpcMiddleware>,
+	next: Arc<dyn GrpcHandler>,
+}
+
+#[async_trait]
+impl GrpcHandler for ComposedHandler {
+	async fn handle(&self, request: GrpcHttpRequest) -> Result<GrpcHttpResponse, Status> {
+		self.middleware.process(request, self.next.clone()).await
+	}
+}
+
+/// Calls `on_status` with the final status of a call
+///
+/// Trailers-only responses carry the status in their headers. Otherwise it
+/// is read from the trailers once the body has been sent: a body ending
+/// without trailers counts as `OK`, and one dropped before its end (e.g.
+/// because the client went away) as `CANCELLED`.
+fn observe_status<F>(response: GrpcHttpResponse, on_status: F) -> GrpcHttpResponse
+where
+	F: FnOnce(Code) + Send + 'static,
+{
+	if let Some(status) = Status::from_header_map(response.headers()) {
+		on_status(status.code());
+		return response;
+	}
+	response.map(|body| {
+		Body::new(StatusBody {
+			inner: body,
+			on_status: Some(Box::new(on_status)),
+		})
+	})
+}
+
+/// Response body reporting the `grpc-status` of its trailers
+struct StatusBody {
+	inner: Body,
+	on_status: Option<Box<dyn FnOnce(Code) + Send>>,
+}
+
+impl StatusBody {
+	fn report(&mut self, code: Code) {
+		if let Some(on_status) = self.on_status.take() {
+			on_status(code);
+		}
+	}
+}
+
+impl http_body::Body for StatusBody {
+	type Data = <Body as http_body::Body>::Data;
+	type Error = Status;
+
+	fn poll_frame(
+		mut self: Pin<&mut Self>,
+		cx: &mut Context<'_>,
+	) -> Poll<Option<Result<http_body::Frame<Self::Data>, Self::Error>>> {
+		let poll = Pin::new(&mut self.inner).poll_frame(cx);
+		match &poll {
+			Poll::Ready(Some(Ok(frame))) => {
+				if let Some(trailers) = frame.trailers_ref() {
+					let code = Status::from_header_map(trailers)
+						.map(|status| status.code())
+						.unwrap_or(Code::Unknown);
+					self.report(code);
+				}
+			}
+			Poll::Ready(Some(Err(status))) => self.report(status.code()),
+			Poll::Ready(None) => self.report(Code::Ok),
+			Poll::Pending => {}
+		}
+		poll
+	}
+
+	fn is_end_stream(&self) -> bool {
+		self.inner.is_end_stream()
+	}
+
+	fn size_hint(&self) -> http_body::SizeHint {
+		self.inner.size_hint()
+	}
+}
+
+impl Drop for StatusBody {
+	fn drop(&mut self) {
+		// Empty bodies may be dropped without being polled to their end
+		if self.inner.is_end_stream() {
+			self.report(Code::Ok);
+		} else {
+			self.report(Code::Cancelled);
+		}
+	}
+}
+
+/// Authenticated caller of a gRPC method
+///
+/// Inserted into the request extensions by [`AuthMiddleware`] and available
+/// to handlers through [`PrincipalExt`] or `#[principal]` parameters of
+/// `#[grpc_handler]` functions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal {
+	/// User identifier
+	pub id: String,
+	/// Username
+	pub username: String,
+}
+
+impl Principal {
+	/// Creates a principal.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_grpc::interceptor::Principal;
+	///
+	/// let principal = Principal::new("42", "alice");
+	/// assert_eq!(principal.id, "42");
+	/// assert_eq!(principal.username, "alice");
+	/// ```
+	pub fn new(id: impl Into<String>, username: impl Into<String>) -> Self {
+		Self {
+			id: id.into(),
+			username: username.into(),
+		}
+	}
+}
+
+/// Extension trait for reading the authenticated principal of a call
+pub trait PrincipalExt {
+	/// Principal set by [`AuthMiddleware`], if the call was authenticated
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_grpc::interceptor::{Principal, PrincipalExt};
+	///
+	/// let mut request = tonic::Request::new(());
+	/// assert!(request.principal().is_none());
+	///
+	/// request.extensions_mut().insert(Principal::new("1", "alice"));
+	/// assert_eq!(request.principal().unwrap().username, "alice");
+	/// ```
+	fn principal(&self) -> Option<&Principal>;
+}
+
+impl<T> PrincipalExt for tonic::Request<T> {
+	fn principal(&self) -> Option<&Principal> {
+		self.extensions().get::<Principal>()
+	}
+}
+
+/// Resolves the principal of a call from its metadata
+#[async_trait]
+pub trait GrpcAuthenticator: Send + Sync {
+	/// Returns the principal, `None` for anonymous calls.
+	///
+	/// # Errors
+	///
+	/// Returns a `Status` (usually `Unauthenticated`) when credentials are
+	/// present but invalid.
+	async fn authenticate(&self, metadata: &MetadataMap) -> Result<Option<Principal>, Status>;
+}
+
+/// Token from an `authorization: Bearer <token>` metadata entry
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_grpc::interceptor::bearer_token;
+/// use tonic::metadata::MetadataMap;
+///
+/// let mut metadata = MetadataMap::new();
+/// metadata.insert("authorization", "Bearer abc".parse().unwrap());
+/// assert_eq!(bearer_token(&metadata), Some("abc"));
+/// ```
+pub fn bearer_token(metadata: &MetadataMap) -> Option<&str> {
+	metadata
+		.get("authorization")?
+		.to_str()
+		.ok()?
+		.strip_prefix("Bearer ")
+}
+
+/// Authenticator for static tokens sent as metadata
+///
+/// Tokens are read from the `authorization` metadata entry (with an optional
+/// `Bearer ` prefix) unless another key is configured.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_grpc::interceptor::{GrpcAuthenticator, MetadataTokenAuthenticator, Principal};
+/// use tonic::metadata::MetadataMap;
+///
+/// # tokio_test::block_on(async {
+/// let authenticator = MetadataTokenAuthenticator::new()
+///     .with_key("x-api-key")
+///     .with_token("secret", Principal::new("1", "service-a"));
+///
+/// let mut metadata = MetadataMap::new();
+/// metadata.insert("x-api-key", "secret".parse().unwrap());
+///
+/// let principal = authenticator.authenticate(&metadata).await.unwrap();
+/// assert_eq!(principal.unwrap().username, "service-a");
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct MetadataTokenAuthenticator {
+	key: &'static str,
+	tokens: HashMap<String, Principal>,
+}
+
+impl MetadataTokenAuthenticator {
+	/// Creates an authenticator with no tokens.
+	pub fn new() -> Self {
+		Self {
+			key: "authorization",
+			tokens: HashMap::new(),
+		}
+	}
+
+	/// Sets the metadata key holding the token.
+	pub fn with_key(mut self, key: &'static str) -> Self {
+		self.key = key;
+		self
+	}
+
+	/// Registers a token and the principal it authenticates.
+	pub fn with_token(mut self, token: impl Into<String>, principal: Principal) -> Self {
+		self.tokens.insert(token.into(), principal);
+		self
+	}
+}
+
+impl Default for MetadataTokenAuthenticator {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[async_trait]
+impl GrpcAuthenticator for MetadataTokenAuthenticator {
+	async fn authenticate(&self, metadata: &MetadataMap) -> Result<Option<Principal>, Status> {
+		let Some(value) = metadata.get(self.key) else {
+			return Ok(None);
+		};
+		let value = value
+			.to_str()
+			.map_err(|_| Status::from(GrpcError::Unauthenticated("Invalid token".to_string())))?;
+		let token = value.strip_prefix("Bearer ").unwrap_or(value);
+
+		self.tokens
+			.get(token)
+			.cloned()
+			.map(Some)
+			.ok_or_else(|| Status::from(GrpcError::Unauthenticated("Invalid token".to_string())))
+	}
+}
+
+/// Authenticator for JWT bearer tokens issued by `reinhardt_auth::JwtAuth`
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_auth::JwtAuth;
+/// use reinhardt_grpc::interceptor::{GrpcAuthenticator, JwtAuthenticator};
+/// use tonic::metadata::MetadataMap;
+///
+/// # tokio_test::block_on(async {
+/// let jwt = JwtAuth::new(b"secret");
+/// let token = jwt.generate_token("42".to_string(), "alice".to_string()).unwrap();
+///
+/// let mut metadata = MetadataMap::new();
+/// metadata.insert("authorization", format!("Bearer {token}").parse().unwrap());
+///
+/// let principal = JwtAuthenticator::new(jwt).authenticate(&metadata).await.unwrap();
+/// assert_eq!(principal.unwrap().id, "42");
+/// # });
+/// ```
+#[cfg(feature = "auth")]
+#[derive(Clone)]
+pub struct JwtAuthenticator {
+	jwt: reinhardt_auth::JwtAuth,
+}
+
+#[cfg(feature = "auth")]
+impl JwtAuthenticator {
+	/// Creates an authenticator verifying tokens with `jwt`.
+	pub fn new(jwt: reinhardt_auth::JwtAuth) -> Self {
+		Self { jwt }
+	}
+}
+
+#[cfg(feature = "auth")]
+#[async_trait]
+impl GrpcAuthenticator for JwtAuthenticator {
+	async fn authenticate(&self, metadata: &MetadataMap) -> Result<Option<Principal>, Status> {
+		let Some(token) = bearer_token(metadata) else {
+			return Ok(None);
+		};
+		let claims = self
+			.jwt
+			.verify_token(token)
+			.map_err(|_| Status::from(GrpcError::Unauthenticated("Invalid token".to_string())))?;
+
+		Ok(Some(Principal::new(claims.sub, claims.username)))
+	}
+}
+
+/// Middleware authenticating calls and exposing their [`Principal`]
+///
+/// Anonymous calls are rejected with `Unauthenticated` unless
+/// [`allow_anonymous`](Self::allow_anonymous) is set.
+pub struct AuthMiddleware {
+	authenticator: Arc<dyn GrpcAuthenticator>,
+	allow_anonymous: bool,
+}
+
+impl AuthMiddleware {
+	/// Creates a middleware requiring authentication by `authenticator`.
+	pub fn new(authenticator: Arc<dyn GrpcAuthenticator>) -> Self {
+		Self {
+			authenticator,
+			allow_anonymous: false,
+		}
+	}
+
+	/// Lets calls without credentials through without a principal.
+	pub fn allow_anonymous(mut self) -> Self {
+		self.allow_anonymous = true;
+		self
+	}
+}
+
+#[async_trait]
+impl GrpcMiddleware for AuthMiddleware {
+	async fn process(
+		&self,
+		mut request: GrpcHttpRequest,
+		next: Arc<dyn GrpcHandler>,
+	) -> Result<GrpcHttpResponse, Status> {
+		let metadata = MetadataMap::from_headers(request.headers().clone());
+		match self.authenticator.authenticate(&metadata).await? {
+			Some(principal) => {
+				request.extensions_mut().insert(principal);
+			}
+			None if !self.allow_anonymous => {
+				return Err(
+					GrpcError::Unauthenticated("Authentication required".to_string()).into(),
+				);
+			}
+			None => {}
+		}
+
+		next.handle(request).await
+	}
+}
+
+/// Middleware printing one line per call
+///
+/// Lines have the form `gRPC /package.Service/Method OK 3ms`. They are
+/// printed once the response has been sent, with the status from its
+/// trailers.
+#[derive(Debug, Default)]
+pub struct GrpcLoggingMiddleware;
+
+impl GrpcLoggingMiddleware {
+	/// Creates a logging middleware.
+	pub fn new() -> Self {
+		Self
+	}
+}
+
+#[async_trait]
+impl GrpcMiddleware for GrpcLoggingMiddleware {
+	async fn process(
+		&self,
+		request: GrpcHttpRequest,
+		next: Arc<dyn GrpcHandler>,
+	) -> Result<GrpcHttpResponse, Status> {
+		let start = Instant::now();
+		let method = request.uri().path().to_string();
+
+		match next.handle(request).await {
+			Ok(response) => Ok(observe_status(response, move |code| {
+				println!(
+					"gRPC {} {:?} {}ms",
+					method,
+					code,
+					start.elapsed().as_millis()
+				)
+			})),
+			Err(status) => {
+				eprintln!(
+					"gRPC {} {:?} {}ms: {}",
+					method,
+					status.code(),
+					start.elapsed().as_millis(),
+					status.message()
+				);
+				Err(status)
+			}
+		}
+	}
+}
+
+/// Call statistics of one gRPC method
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MethodStats {
+	/// Number of calls
+	pub calls: u64,
+	/// Number of calls that ended with a non-OK status
+	pub errors: u64,
+	/// Accumulated time spent in the service
+	pub total_duration: Duration,
+}
+
+/// Per-method call statistics collected by [`GrpcMetricsMiddleware`]
+#[derive(Debug, Default)]
+pub struct GrpcMetricsStore {
+	methods: Mutex<HashMap<String, MethodStats>>,
+}
+
+impl GrpcMetricsStore {
+	/// Creates an empty store.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Records a finished call.
+	pub fn record(&self, method: &str, code: Code, duration: Duration) {
+		let mut methods = self.methods.lock().unwrap_or_else(|e| e.into_inner());
+		let stats = methods.entry(method.to_string()).or_default();
+		stats.calls += 1;
+		if code != Code::Ok {
+			stats.errors += 1;
+		}
+		stats.total_duration += duration;
+	}
+
+	/// Statistics of `method` (e.g. `/package.Service/Method`)
+	pub fn method_stats(&self, method: &str) -> MethodStats {
+		self.methods
+			.lock()
+			.unwrap_or_else(|e| e.into_inner())
+			.get(method)
+			.cloned()
+			.unwrap_or_default()
+	}
+
+	/// Total number of recorded calls
+	pub fn total_calls(&self) -> u64 {
+		self.methods
+			.lock()
+			.unwrap_or_else(|e| e.into_inner())
+			.values()
+			.map(|stats| stats.calls)
+			.sum()
+	}
+
+	/// Exports the statistics in Prometheus text format.
+	pub fn export_prometheus(&self) -> String {
+		let methods = self.methods.lock().unwrap_or_else(|e| e.into_inner());
+		let mut names: Vec<_> = methods.keys().collect();
+		names.sort();
+
+		let mut output = String::new();
+		output.push_str("# HELP grpc_server_handled_total Total number of gRPC calls\n");
+		output.push_str("# TYPE grpc_server_handled_total counter\n");
+		for name in &names {
+			output.push_str(&format!(
+				"grpc_server_handled_total{{method=\"{}\"}} {}\n",
+				name, methods[*name].calls
+			));
+		}
+		output.push_str("# HELP grpc_server_errors_total Total number of failed gRPC calls\n");
+		output.push_str("# TYPE grpc_server_errors_total counter\n");
+		for name in &names {
+			output.push_str(&format!(
+				"grpc_server_errors_total{{method=\"{}\"}} {}\n",
+				name, methods[*name].errors
+			));
+		}
+		output.push_str(
+			"# HELP grpc_server_handling_seconds_sum Total time spent handling gRPC calls\n",
+		);
+		output.push_str("# TYPE grpc_server_handling_seconds_sum counter\n");
+		for name in &names {
+			output.push_str(&format!(
+				"grpc_server_handling_seconds_sum{{method=\"{}\"}} {}\n",
+				name,
+				methods[*name].total_duration.as_secs_f64()
+			));
+		}
+		output
+	}
+}
+
+/// Middleware recording per-method call counts, errors and latency
+///
+/// Calls are recorded once the response has been sent, so that errors
+/// reported in the trailers are counted.
+#[derive(Debug, Default)]
+pub struct GrpcMetricsMiddleware {
+	store: Arc<GrpcMetricsStore>,
+}
+
+impl GrpcMetricsMiddleware {
+	/// Creates a middleware with its own store.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Creates a middleware recording into a shared store.
+	pub fn from_arc(store: Arc<GrpcMetricsStore>) -> Self {
+		Self { store }
+	}
+
+	/// Store holding the recorded statistics
+	pub fn store(&self) -> &GrpcMetricsStore {
+		&self.store
+	}
+}
+
+#[async_trait]
+impl GrpcMiddleware for GrpcMetricsMiddleware {
+	async fn process(
+		&self,
+		request: GrpcHttpRequest,
+		next: Arc<dyn GrpcHandler>,
+	) -> Result<GrpcHttpResponse, Status> {
+		let start = Instant::now();
+		let method = request.uri().path().to_string();
+
+		match next.handle(request).await {
+			Ok(response) => {
+				let store = self.store.clone();
+				Ok(observe_status(response, move |code| {
+					store.record(&method, code, start.elapsed())
+				}))
+			}
+			Err(status) => {
+				self.store.record(&method, status.code(), start.elapsed());
+				Err(status)
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+
+	#[rstest]
+	#[case("Bearer abc", Some("abc"))]
+	#[case("Basic abc", None)]
+	#[case("abc", None)]
+	fn test_bearer_token(#[case] header: &str, #[case] expected: Option<&str>) {
+		let mut metadata = MetadataMap::new();
+		metadata.insert("authorization", header.parse().unwrap());
+
+		assert_eq!(bearer_token(&metadata), expected);
+	}
+
+	#[rstest]
+	fn test_metrics_store_records_errors() {
+		let store = GrpcMetricsStore::new();
+		store.record("/test.Svc/Get", Code::Ok, Duration::from_millis(5));
+		store.record("/test.Svc/Get", Code::NotFound, Duration::from_millis(5));
+
+		let stats = store.method_stats("/test.Svc/Get");
+		assert_eq!(stats.calls, 2);
+		assert_eq!(stats.errors, 1);
+		assert_eq!(stats.total_duration, Duration::from_millis(10));
+		assert!(
+			store
+				.export_prometheus()
+				.contains("grpc_server_errors_total{method=\"/test.Svc/Get\"} 1")
+		);
+	}
+}
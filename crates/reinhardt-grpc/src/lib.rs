@@ -6,8 +6,12 @@
 //!
 //! - Common Protobuf types (Empty, Timestamp, Error, PageInfo, BatchResult)
 //! - GraphQL over gRPC types (GraphQLRequest, GraphQLResponse, SubscriptionEvent)
-//! - gRPC error handling
+//! - gRPC error handling, mapping framework errors to status codes with structured details
 //! - gRPC service adapter trait
+//! - Middleware chains with authentication, logging and metrics
+//! - Server with health checking (`health` feature) and reflection (`reflection` feature)
+//! - grpc-web support for browser clients (with `web` feature)
+//! - CRUD service generation for models (with `codegen` feature)
 //! - Dependency injection support (with `di` feature)
 //!
 //! # Usage
@@ -47,11 +51,20 @@
 //! ```
 
 pub mod adapter;
+pub mod crud;
 pub mod error;
+pub mod interceptor;
+pub mod server;
+
+#[cfg(feature = "codegen")]
+pub mod codegen;
 
 #[cfg(feature = "di")]
 pub mod di;
 
+#[cfg(feature = "web")]
+pub mod web;
+
 // Generated Protobuf code (common types provided by the framework)
 pub mod proto {
 	pub mod common {
@@ -61,10 +74,17 @@ pub mod proto {
 	pub mod graphql {
 		tonic::include_proto!("reinhardt.graphql");
 	}
+
+	/// Encoded `FileDescriptorSet` of the common types, used by reflection
+	pub const FILE_DESCRIPTOR_SET: &[u8] =
+		tonic::include_file_descriptor_set!("reinhardt_descriptor");
 }
 
 pub use adapter::{GrpcServiceAdapter, GrpcSubscriptionAdapter};
-pub use error::{GrpcError, GrpcResult};
+pub use crud::{CrudHandler, Pagination};
+pub use error::{GrpcError, GrpcResult, IntoStatus};
+pub use interceptor::{GrpcMiddleware, GrpcMiddlewareChain, Principal, PrincipalExt};
+pub use server::GrpcServer;
 
 #[cfg(feature = "di")]
 pub use di::GrpcRequestExt;
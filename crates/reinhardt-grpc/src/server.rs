@@ -0,0 +1,253 @@
+//! gRPC server with built-in health checking and reflection
+//!
+//! [`GrpcServer`] collects tonic services and serves them together with
+//! the standard `grpc.health.v1.Health` service (with the `health`
+//! feature) and server reflection (with the `reflection` feature). Every
+//! added service is reported as `SERVING` and advertised through
+//! reflection, so Kubernetes gRPC probes and `grpcurl` work without extra
+//! setup.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use reinhardt_grpc::server::GrpcServer;
+//!
+//! GrpcServer::new()
+//!     .with_file_descriptor_set(tonic::include_file_descriptor_set!("blog_descriptor"))
+//!     .add_service(blog::ArticleCrudService::new(ArticleHandler).into_server())
+//!     .serve("0.0.0.0:50051".parse()?)
+//!     .await?;
+//! ```
+
+use crate::error::{GrpcError, GrpcResult};
+use crate::interceptor::{GrpcHttpRequest, GrpcHttpResponse, GrpcMiddlewareChain};
+use std::convert::Infallible;
+use std::future::Future;
+use std::net::SocketAddr;
+use tonic::server::NamedService;
+use tonic::service::{Routes, RoutesBuilder};
+use tower_service::Service;
+
+#[cfg(feature = "health")]
+pub use tonic_health::ServingStatus;
+#[cfg(feature = "health")]
+pub use tonic_health::server::HealthReporter;
+
+/// Name of the gRPC health checking service
+pub const HEALTH_SERVICE_NAME: &str = "grpc.health.v1.Health";
+
+/// Name of the gRPC server reflection service
+pub const REFLECTION_SERVICE_NAME: &str = "grpc.reflection.v1.ServerReflection";
+
+/// Adds a service, wrapped in the final middleware chain, to the routes
+type ServiceRegistration = Box<dyn FnOnce(&GrpcMiddlewareChain, &mut RoutesBuilder) + Send + Sync>;
+
+/// Builder serving tonic services with health checking and reflection
+pub struct GrpcServer {
+	services: Vec<ServiceRegistration>,
+	service_names: Vec<&'static str>,
+	middleware: GrpcMiddlewareChain,
+	#[cfg_attr(not(feature = "health"), allow(dead_code))]
+	health: bool,
+	#[cfg_attr(not(feature = "reflection"), allow(dead_code))]
+	reflection: bool,
+	#[cfg(feature = "health")]
+	health_reporter: HealthReporter,
+	#[cfg(feature = "reflection")]
+	file_descriptor_sets: Vec<&'static [u8]>,
+	#[cfg(feature = "web")]
+	grpc_web: Option<crate::web::GrpcWeb>,
+}
+
+impl GrpcServer {
+	/// Creates a server with health checking and reflection enabled.
+	///
+	/// The descriptors of the framework's common types are registered for
+	/// reflection.
+	pub fn new() -> Self {
+		Self {
+			services: Vec::new(),
+			service_names: Vec::new(),
+			middleware: GrpcMiddlewareChain::new(),
+			health: cfg!(feature = "health"),
+			reflection: cfg!(feature = "reflection"),
+			#[cfg(feature = "health")]
+			health_reporter: HealthReporter::new(),
+			#[cfg(feature = "reflection")]
+			file_descriptor_sets: vec![crate::proto::FILE_DESCRIPTOR_SET],
+			#[cfg(feature = "web")]
+			grpc_web: None,
+		}
+	}
+
+	/// Sets the middleware applied to the added services.
+	///
+	/// The chain is applied when the routes are built, so it wraps every
+	/// service whether it was added before or after this call. Health
+	/// checking and reflection are served without middleware so that probes
+	/// do not need credentials.
+	pub fn with_middleware(mut self, middleware: GrpcMiddlewareChain) -> Self {
+		self.middleware = middleware;
+		self
+	}
+
+	/// Adds a tonic service.
+	///
+	/// The service is reported as `SERVING` by the health service and
+	/// advertised by reflection.
+	pub fn add_service<S>(mut self, service: S) -> Self
+	where
+		S: Service<GrpcHttpRequest, Response = GrpcHttpResponse, Error = Infallible>
+			+ NamedService
+			+ Clone
+			+ Send
+			+ Sync
+			+ 'static,
+		S::Future: Send + 'static,
+	{
+		self.service_names.push(S::NAME);
+		self.services.push(Box::new(move |middleware, routes| {
+			routes.add_service(middleware.service(service));
+		}));
+		self
+	}
+
+	/// Disables the health checking service.
+	pub fn without_health(mut self) -> Self {
+		self.health = false;
+		self
+	}
+
+	/// Disables the reflection service.
+	pub fn without_reflection(mut self) -> Self {
+		self.reflection = false;
+		self
+	}
+
+	/// Registers an encoded `FileDescriptorSet` for reflection.
+	///
+	/// Use `tonic::include_file_descriptor_set!` with the descriptor written
+	/// by `CrudCodegen::compile` or `tonic_prost_build::Builder::file_descriptor_set_path`.
+	#[cfg(feature = "reflection")]
+	pub fn with_file_descriptor_set(mut self, file_descriptor_set: &'static [u8]) -> Self {
+		self.file_descriptor_sets.push(file_descriptor_set);
+		self
+	}
+
+	/// Accepts grpc-web calls from browser clients.
+	///
+	/// HTTP/1.1 connections are accepted so that browsers without HTTP/2
+	/// support can connect.
+	#[cfg(feature = "web")]
+	pub fn with_grpc_web(mut self, grpc_web: crate::web::GrpcWeb) -> Self {
+		self.grpc_web = Some(grpc_web);
+		self
+	}
+
+	/// Handle for changing the reported status of services at runtime
+	#[cfg(feature = "health")]
+	pub fn health_reporter(&self) -> HealthReporter {
+		self.health_reporter.clone()
+	}
+
+	/// Names of the added services
+	pub fn service_names(&self) -> &[&'static str] {
+		&self.service_names
+	}
+
+	/// Builds the routes of all services, including health and reflection.
+	///
+	/// # Errors
+	///
+	/// Returns `GrpcError::Internal` if a registered file descriptor set
+	/// cannot be decoded.
+	pub async fn into_routes(self) -> GrpcResult<Routes> {
+		let mut routes = RoutesBuilder::default();
+		for register in self.services {
+			register(&self.middleware, &mut routes);
+		}
+
+		#[cfg(feature = "health")]
+		if self.health {
+			for name in &self.service_names {
+				self.health_reporter
+					.set_service_status(name, ServingStatus::Serving)
+					.await;
+			}
+			routes.add_service(tonic_health::pb::health_server::HealthServer::new(
+				tonic_health::server::HealthService::from_health_reporter(
+					self.health_reporter.clone(),
+				),
+			));
+		}
+
+		#[cfg(feature = "reflection")]
+		if self.reflection {
+			let mut builder = tonic_reflection::server::Builder::configure()
+				.with_service_name(REFLECTION_SERVICE_NAME);
+			for file_descriptor_set in self.file_descriptor_sets {
+				builder = builder.register_encoded_file_descriptor_set(file_descriptor_set);
+			}
+			if self.health {
+				builder = builder
+					.register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+					.with_service_name(HEALTH_SERVICE_NAME);
+			}
+			for name in &self.service_names {
+				builder = builder.with_service_name(*name);
+			}
+			let reflection = builder
+				.build_v1()
+				.map_err(|e| GrpcError::Internal(format!("Invalid file descriptor set: {}", e)))?;
+			routes.add_service(reflection);
+		}
+
+		Ok(routes.routes())
+	}
+
+	/// Serves all services on `addr`.
+	///
+	/// # Errors
+	///
+	/// Returns `GrpcError::Connection` if the server fails to bind or serve.
+	pub async fn serve(self, addr: SocketAddr) -> GrpcResult<()> {
+		self.serve_with_shutdown(addr, std::future::pending()).await
+	}
+
+	/// Serves all services on `addr` until `signal` completes.
+	///
+	/// # Errors
+	///
+	/// Returns `GrpcError::Connection` if the server fails to bind or serve.
+	pub async fn serve_with_shutdown<F>(self, addr: SocketAddr, signal: F) -> GrpcResult<()>
+	where
+		F: Future<Output = ()>,
+	{
+		#[cfg(feature = "web")]
+		let grpc_web = self.grpc_web.clone();
+		let routes = self.into_routes().await?;
+
+		#[cfg(feature = "web")]
+		if let Some(grpc_web) = grpc_web {
+			return tonic::transport::Server::builder()
+				.accept_http1(true)
+				.layer(grpc_web)
+				.add_routes(routes)
+				.serve_with_shutdown(addr, signal)
+				.await
+				.map_err(|e| GrpcError::Connection(e.to_string()));
+		}
+
+		tonic::transport::Server::builder()
+			.add_routes(routes)
+			.serve_with_shutdown(addr, signal)
+			.await
+			.map_err(|e| GrpcError::Connection(e.to_string()))
+	}
+}
+
+impl Default for GrpcServer {
+	fn default() -> Self {
+		Self::new()
+	}
+}
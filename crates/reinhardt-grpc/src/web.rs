@@ -0,0 +1,192 @@
+//! grpc-web support for browser clients
+//!
+//! [`GrpcWeb`] is a tower layer translating grpc-web requests (as sent by
+//! reinhardt-pages WASM clients and other browser libraries) to regular
+//! gRPC calls, so services can be called without an Envoy proxy. It also
+//! answers CORS preflight requests and exposes the `grpc-status` and
+//! `grpc-message` headers to scripts.
+//!
+//! Only unary and server-streaming calls are available over grpc-web.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use reinhardt_grpc::server::GrpcServer;
+//! use reinhardt_grpc::web::GrpcWeb;
+//!
+//! GrpcServer::new()
+//!     .with_grpc_web(GrpcWeb::new().allow_origin("https://app.example.com"))
+//!     .add_service(UserServiceServer::new(UserServiceImpl))
+//!     .serve(addr)
+//!     .await?;
+//! ```
+
+use http::header::{self, HeaderValue};
+use http::{Method, StatusCode};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tonic::body::Body;
+use tonic::server::NamedService;
+use tonic_web::{GrpcWebLayer, GrpcWebService};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Response headers readable by browser scripts
+const EXPOSED_HEADERS: &str = "grpc-status, grpc-message, grpc-status-details-bin";
+
+/// Request headers allowed in preflight responses when none are requested
+const DEFAULT_ALLOWED_HEADERS: &str =
+	"content-type, x-grpc-web, x-user-agent, grpc-timeout, authorization";
+
+/// Preflight cache duration in seconds
+const MAX_AGE: &str = "86400";
+
+/// grpc-web translation layer with CORS handling
+///
+/// Cross-origin calls are rejected unless their origin was added with
+/// [`allow_origin`](Self::allow_origin). Requests without an `Origin`
+/// header (same-origin or non-browser clients) are always accepted.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_grpc::web::GrpcWeb;
+///
+/// let web = GrpcWeb::new().allow_origin("https://app.example.com");
+/// assert!(web.is_origin_allowed("https://app.example.com"));
+/// assert!(!web.is_origin_allowed("https://evil.example.com"));
+///
+/// assert!(!GrpcWeb::new().is_origin_allowed("https://any.example.com"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GrpcWeb {
+	allowed_origins: Arc<Vec<String>>,
+}
+
+impl GrpcWeb {
+	/// Creates a layer rejecting grpc-web calls from every other origin.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Accepts cross-origin calls from `origin`.
+	pub fn allow_origin(mut self, origin: impl Into<String>) -> Self {
+		Arc::make_mut(&mut self.allowed_origins).push(origin.into());
+		self
+	}
+
+	/// Whether calls from `origin` are accepted
+	pub fn is_origin_allowed(&self, origin: &str) -> bool {
+		self.allowed_origins.iter().any(|o| o == origin)
+	}
+}
+
+impl<S> Layer<S> for GrpcWeb {
+	type Service = GrpcWebCors<GrpcWebService<S>>;
+
+	fn layer(&self, inner: S) -> Self::Service {
+		GrpcWebCors {
+			inner: GrpcWebLayer::new().layer(inner),
+			config: self.clone(),
+		}
+	}
+}
+
+/// Service produced by [`GrpcWeb`]
+#[derive(Debug, Clone)]
+pub struct GrpcWebCors<S> {
+	inner: S,
+	config: GrpcWeb,
+}
+
+impl<S: NamedService> NamedService for GrpcWebCors<S> {
+	const NAME: &'static str = S::NAME;
+}
+
+impl<S> Service<http::Request<Body>> for GrpcWebCors<S>
+where
+	S: Service<http::Request<Body>, Response = http::Response<Body>> + Send + 'static,
+	S::Future: Send + 'static,
+{
+	type Response = http::Response<Body>;
+	type Error = S::Error;
+	type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+	fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+		self.inner.poll_ready(cx)
+	}
+
+	fn call(&mut self, request: http::Request<Body>) -> Self::Future {
+		let origin = request.headers().get(header::ORIGIN).cloned();
+
+		if let Some(origin) = &origin
+			&& !origin
+				.to_str()
+				.is_ok_and(|origin| self.config.is_origin_allowed(origin))
+		{
+			return Box::pin(async { Ok(empty_response(StatusCode::FORBIDDEN)) });
+		}
+
+		if request.method() == Method::OPTIONS
+			&& request
+				.headers()
+				.contains_key(header::ACCESS_CONTROL_REQUEST_METHOD)
+		{
+			let response = preflight_response(&request, origin);
+			return Box::pin(async { Ok(response) });
+		}
+
+		let future = self.inner.call(request);
+		Box::pin(async move {
+			let mut response = future.await?;
+			if let Some(origin) = origin {
+				let headers = response.headers_mut();
+				headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+				headers.insert(
+					header::ACCESS_CONTROL_EXPOSE_HEADERS,
+					HeaderValue::from_static(EXPOSED_HEADERS),
+				);
+				headers.append(header::VARY, HeaderValue::from_static("origin"));
+			}
+			Ok(response)
+		})
+	}
+}
+
+fn empty_response(status: StatusCode) -> http::Response<Body> {
+	let mut response = http::Response::new(Body::empty());
+	*response.status_mut() = status;
+	response
+}
+
+/// Answer to a CORS preflight request
+fn preflight_response(
+	request: &http::Request<Body>,
+	origin: Option<HeaderValue>,
+) -> http::Response<Body> {
+	let mut response = empty_response(StatusCode::NO_CONTENT);
+	let headers = response.headers_mut();
+	if let Some(origin) = origin {
+		headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+	}
+	headers.insert(
+		header::ACCESS_CONTROL_ALLOW_METHODS,
+		HeaderValue::from_static("POST"),
+	);
+	headers.insert(
+		header::ACCESS_CONTROL_ALLOW_HEADERS,
+		request
+			.headers()
+			.get(header::ACCESS_CONTROL_REQUEST_HEADERS)
+			.cloned()
+			.unwrap_or(HeaderValue::from_static(DEFAULT_ALLOWED_HEADERS)),
+	);
+	headers.insert(
+		header::ACCESS_CONTROL_MAX_AGE,
+		HeaderValue::from_static(MAX_AGE),
+	);
+	headers.insert(header::VARY, HeaderValue::from_static("origin"));
+	response
+}
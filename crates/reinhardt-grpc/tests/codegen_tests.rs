@@ -0,0 +1,100 @@
+//! Tests for CRUD service generation
+//!
+//! These tests verify the `.proto` definitions and tonic scaffolding
+//! rendered by `CrudCodegen` for registered models.
+
+#![cfg(feature = "codegen")]
+
+use reinhardt_grpc::codegen::{CrudCodegen, ProtoModel, ProtoType};
+use rstest::{fixture, rstest};
+
+#[fixture]
+fn codegen() -> CrudCodegen {
+	CrudCodegen::new("blog")
+		.model(
+			ProtoModel::new("Article")
+				.primary_key("id", ProtoType::Int64)
+				.field("title", ProtoType::String)
+				.optional_field("summary", ProtoType::String)
+				.field("published_at", ProtoType::Timestamp),
+		)
+		.model(
+			ProtoModel::new("Category")
+				.with_plural("Categories")
+				.primary_key("slug", ProtoType::String),
+		)
+}
+
+#[rstest]
+fn test_model_message(codegen: CrudCodegen) {
+	let proto = codegen.render_proto().unwrap();
+
+	assert!(proto.starts_with(
+		"// Generated by reinhardt-grpc. Do not edit.\nsyntax = \"proto3\";\npackage blog;"
+	));
+	assert!(proto.contains("import \"reinhardt/common.proto\";"));
+	assert!(proto.contains(
+		"message Article {\n  int64 id = 1;\n  string title = 2;\n  optional string summary = 3;\n  reinhardt.common.Timestamp published_at = 4;\n}"
+	));
+}
+
+#[rstest]
+fn test_crud_service(codegen: CrudCodegen) {
+	let proto = codegen.render_proto().unwrap();
+
+	assert!(proto.contains("service ArticleService {"));
+	assert!(proto.contains("rpc GetArticle(GetArticleRequest) returns (Article);"));
+	assert!(
+		proto.contains("rpc DeleteArticle(DeleteArticleRequest) returns (reinhardt.common.Empty);")
+	);
+	assert!(proto.contains("reinhardt.common.PageInfo page_info = 2;"));
+	assert!(proto.contains(
+		"rpc BatchCreateArticles(BatchCreateArticlesRequest) returns (reinhardt.common.BatchResult);"
+	));
+}
+
+#[rstest]
+fn test_custom_plural_and_key(codegen: CrudCodegen) {
+	let proto = codegen.render_proto().unwrap();
+
+	assert!(
+		proto.contains(
+			"rpc ListCategories(ListCategoriesRequest) returns (ListCategoriesResponse);"
+		)
+	);
+	assert!(proto.contains("message GetCategoryRequest {\n  string slug = 1;\n}"));
+}
+
+#[rstest]
+fn test_service_scaffold(codegen: CrudCodegen) {
+	let scaffold = codegen.render_service_scaffold().unwrap();
+
+	assert!(scaffold.contains("pub struct ArticleCrudService<H>"));
+	assert!(scaffold.contains("::reinhardt_grpc::crud::CrudHandler<Article, i64>"));
+	assert!(
+		scaffold
+			.contains("impl<H> article_service_server::ArticleService for ArticleCrudService<H>")
+	);
+	assert!(scaffold.contains("async fn list_categories("));
+	assert!(
+		scaffold.contains("::reinhardt_grpc::crud::CrudHandler<Category, ::std::string::String>")
+	);
+}
+
+#[rstest]
+fn test_write_proto(codegen: CrudCodegen) {
+	let dir = std::env::temp_dir().join(format!("reinhardt-grpc-codegen-{}", std::process::id()));
+	let codegen = codegen.with_proto_dir(&dir);
+
+	let proto_dir = codegen.write_proto().unwrap();
+
+	assert_eq!(proto_dir, dir);
+	let written = std::fs::read_to_string(dir.join("blog.proto")).unwrap();
+	assert_eq!(written, codegen.render_proto().unwrap());
+	assert!(
+		std::fs::read_to_string(dir.join("reinhardt/common.proto"))
+			.unwrap()
+			.contains("message PageInfo")
+	);
+	std::fs::remove_dir_all(dir).unwrap();
+}
@@ -7,7 +7,10 @@
 
 use reinhardt_di::{DiError, Injectable, InjectionContext, SingletonScope};
 use reinhardt_grpc::grpc_handler;
+use reinhardt_grpc::interceptor::Principal;
+use std::pin::Pin;
 use std::sync::{Arc, Mutex};
+use tokio_stream::{Stream, StreamExt};
 use tonic::{Request, Response, Status};
 
 /// Mock database connection for testing
@@ -124,6 +127,90 @@ impl TestService {
 			.map_err(|e| Status::not_found(e))?;
 		Ok(Response::new(user))
 	}
+
+	#[grpc_handler]
+	async fn whoami(
+		&self,
+		_request: Request<()>,
+		#[principal] principal: Principal,
+	) -> Result<Response<String>, Status> {
+		Ok(Response::new(principal.username))
+	}
+
+	#[grpc_handler]
+	async fn greet(
+		&self,
+		#[principal] principal: Option<Principal>,
+		request: Request<GetUserRequest>,
+		#[inject] db: MockDatabase,
+	) -> Result<Response<String>, Status> {
+		let user = db
+			.fetch_user(&request.into_inner().id)
+			.await
+			.map_err(Status::not_found)?;
+		let caller = principal.map_or("anonymous".to_string(), |p| p.username);
+		Ok(Response::new(format!("{} -> {}", caller, user)))
+	}
+}
+
+type UserStream = Pin<Box<dyn Stream<Item = Result<String, Status>> + Send>>;
+
+/// Streaming service trait, as generated by tonic
+#[tonic::async_trait]
+trait UserStreaming: Send + Sync + 'static {
+	type ListUsersStream: Stream<Item = Result<String, Status>> + Send + 'static;
+
+	async fn list_users(
+		&self,
+		request: Request<Vec<String>>,
+	) -> Result<Response<Self::ListUsersStream>, Status>;
+
+	type LookupStream: Stream<Item = Result<String, Status>> + Send + 'static;
+
+	async fn lookup(
+		&self,
+		request: Request<tokio_stream::Iter<std::vec::IntoIter<String>>>,
+	) -> Result<Response<Self::LookupStream>, Status>;
+}
+
+struct StreamingService;
+
+#[grpc_handler]
+#[tonic::async_trait]
+impl UserStreaming for StreamingService {
+	type ListUsersStream = UserStream;
+
+	async fn list_users(
+		&self,
+		request: Request<Vec<String>>,
+		#[inject] db: MockDatabase,
+	) -> Result<Response<Self::ListUsersStream>, Status> {
+		let stream = tokio_stream::iter(request.into_inner()).then(move |id| {
+			let db = db.clone();
+			async move { db.fetch_user(&id).await.map_err(Status::not_found) }
+		});
+		Ok(Response::new(Box::pin(stream)))
+	}
+
+	type LookupStream = UserStream;
+
+	async fn lookup(
+		&self,
+		mut request: Request<tokio_stream::Iter<std::vec::IntoIter<String>>>,
+		#[principal] principal: Principal,
+		#[inject] db: MockDatabase,
+	) -> Result<Response<Self::LookupStream>, Status> {
+		let ids = std::mem::replace(request.get_mut(), tokio_stream::iter(Vec::new()));
+		let stream = ids.then(move |id| {
+			let db = db.clone();
+			let caller = principal.username.clone();
+			async move {
+				let user = db.fetch_user(&id).await.map_err(Status::not_found)?;
+				Ok(format!("{} -> {}", caller, user))
+			}
+		});
+		Ok(Response::new(Box::pin(stream)))
+	}
 }
 
 #[tokio::test]
@@ -219,3 +306,104 @@ async fn test_grpc_handler_cache_control() {
 	let response2 = service.get_user_uncached(request2).await;
 	assert!(response2.is_ok());
 }
+
+#[tokio::test]
+async fn test_grpc_handler_principal() {
+	let service = TestService {};
+	let mut request = Request::new(());
+	request
+		.extensions_mut()
+		.insert(Principal::new("1", "alice"));
+
+	let response = service.whoami(request).await;
+
+	assert_eq!(response.unwrap().into_inner(), "alice");
+}
+
+#[tokio::test]
+async fn test_grpc_handler_principal_required() {
+	let service = TestService {};
+
+	let response = service.whoami(Request::new(())).await;
+
+	let status = response.unwrap_err();
+	assert_eq!(status.code(), tonic::Code::Unauthenticated);
+}
+
+#[tokio::test]
+async fn test_grpc_handler_optional_principal_with_di() {
+	let singleton_scope = Arc::new(SingletonScope::new());
+	let ctx = Arc::new(InjectionContext::builder(singleton_scope).build());
+	let service = TestService {};
+
+	let mut request = Request::new(GetUserRequest {
+		id: "7".to_string(),
+	});
+	request.extensions_mut().insert(ctx.clone());
+	let anonymous = service.greet(request).await.unwrap().into_inner();
+
+	let mut request = Request::new(GetUserRequest {
+		id: "7".to_string(),
+	});
+	request.extensions_mut().insert(ctx);
+	request
+		.extensions_mut()
+		.insert(Principal::new("1", "alice"));
+	let authenticated = service.greet(request).await.unwrap().into_inner();
+
+	assert_eq!(anonymous, "anonymous -> User:7");
+	assert_eq!(authenticated, "alice -> User:7");
+}
+
+#[tokio::test]
+async fn test_grpc_handler_server_streaming() {
+	let singleton_scope = Arc::new(SingletonScope::new());
+	let ctx = Arc::new(InjectionContext::builder(singleton_scope).build());
+
+	let mut request = Request::new(vec!["1".to_string(), "2".to_string()]);
+	request.extensions_mut().insert(ctx);
+	let stream = StreamingService
+		.list_users(request)
+		.await
+		.unwrap()
+		.into_inner();
+
+	let users: Vec<_> = stream.collect::<Result<_, _>>().await.unwrap();
+	assert_eq!(users, vec!["User:1", "User:2"]);
+}
+
+#[tokio::test]
+async fn test_grpc_handler_streaming_missing_di_context() {
+	let request = Request::new(vec!["1".to_string()]);
+
+	let result = StreamingService.list_users(request).await;
+
+	let status = result.err().unwrap();
+	assert_eq!(status.code(), tonic::Code::Internal);
+	assert!(status.message().contains("DI context not set"));
+}
+
+#[tokio::test]
+async fn test_grpc_handler_bidi_streaming() {
+	let singleton_scope = Arc::new(SingletonScope::new());
+	let ctx = Arc::new(InjectionContext::builder(singleton_scope).build());
+	let ids = vec!["3".to_string(), "4".to_string()];
+
+	let mut request = Request::new(tokio_stream::iter(ids.clone()));
+	request.extensions_mut().insert(ctx);
+	request
+		.extensions_mut()
+		.insert(Principal::new("1", "alice"));
+	let stream = StreamingService.lookup(request).await.unwrap().into_inner();
+	let users: Vec<_> = stream.collect::<Result<_, _>>().await.unwrap();
+
+	let anonymous = StreamingService
+		.lookup(Request::new(tokio_stream::iter(ids)))
+		.await;
+
+	assert_eq!(users, vec!["alice -> User:3", "alice -> User:4"]);
+	assert_eq!(
+		anonymous.err().unwrap().code(),
+		tonic::Code::Unauthenticated
+	);
+}
@@ -0,0 +1,249 @@
+//! Integration tests for gRPC middleware chains
+//!
+//! These tests verify that `GrpcMiddlewareChain` runs middleware around a
+//! tonic service and that the authentication, logging and metrics
+//! middleware behave as documented.
+
+use async_trait::async_trait;
+use http_body_util::BodyExt;
+use reinhardt_grpc::interceptor::{
+	AuthMiddleware, GrpcHandler, GrpcHttpRequest, GrpcHttpResponse, GrpcLoggingMiddleware,
+	GrpcMetricsMiddleware, GrpcMetricsStore, GrpcMiddleware, GrpcMiddlewareChain,
+	MetadataTokenAuthenticator, Principal,
+};
+use rstest::*;
+use std::convert::Infallible;
+use std::future::{Ready, ready};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use tonic::Code;
+use tonic::body::Body;
+use tonic::server::NamedService;
+use tower_service::Service;
+
+/// Service echoing the authenticated username in the `x-username` header
+#[derive(Clone)]
+struct EchoService;
+
+impl NamedService for EchoService {
+	const NAME: &'static str = "test.Echo";
+}
+
+impl Service<GrpcHttpRequest> for EchoService {
+	type Response = GrpcHttpResponse;
+	type Error = Infallible;
+	type Future = Ready<Result<GrpcHttpResponse, Infallible>>;
+
+	fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+		Poll::Ready(Ok(()))
+	}
+
+	fn call(&mut self, request: GrpcHttpRequest) -> Self::Future {
+		let mut response = http::Response::new(Body::empty());
+		if let Some(principal) = request.extensions().get::<Principal>() {
+			response
+				.headers_mut()
+				.insert("x-username", principal.username.parse().unwrap());
+		}
+		ready(Ok(response))
+	}
+}
+
+/// Service failing with `NOT_FOUND` in the trailers after the headers
+#[derive(Clone)]
+struct MissingService;
+
+impl NamedService for MissingService {
+	const NAME: &'static str = "test.Missing";
+}
+
+impl Service<GrpcHttpRequest> for MissingService {
+	type Response = GrpcHttpResponse;
+	type Error = Infallible;
+	type Future = Ready<Result<GrpcHttpResponse, Infallible>>;
+
+	fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+		Poll::Ready(Ok(()))
+	}
+
+	fn call(&mut self, _request: GrpcHttpRequest) -> Self::Future {
+		let mut trailers = http::HeaderMap::new();
+		trailers.insert("grpc-status", "5".parse().unwrap());
+		let body = http_body_util::Empty::<bytes::Bytes>::new()
+			.with_trailers(async move { Some(Ok(trailers)) });
+		ready(Ok(http::Response::new(Body::new(body))))
+	}
+}
+
+/// Middleware recording the order in which it runs
+struct RecordingMiddleware {
+	name: &'static str,
+	log: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl GrpcMiddleware for RecordingMiddleware {
+	async fn process(
+		&self,
+		request: GrpcHttpRequest,
+		next: Arc<dyn GrpcHandler>,
+	) -> Result<GrpcHttpResponse, tonic::Status> {
+		self.log
+			.lock()
+			.unwrap()
+			.push(format!("{} before", self.name));
+		let response = next.handle(request).await;
+		self.log
+			.lock()
+			.unwrap()
+			.push(format!("{} after", self.name));
+		response
+	}
+}
+
+fn call_request(token: Option<&str>) -> GrpcHttpRequest {
+	let mut builder = http::Request::builder()
+		.method("POST")
+		.uri("/test.Echo/Say")
+		.header("content-type", "application/grpc");
+	if let Some(token) = token {
+		builder = builder.header("authorization", format!("Bearer {}", token));
+	}
+	builder.body(Body::empty()).unwrap()
+}
+
+async fn call(chain: &GrpcMiddlewareChain, request: GrpcHttpRequest) -> GrpcHttpResponse {
+	let Ok(response) = chain.service(EchoService).call(request).await;
+	response
+}
+
+fn grpc_status(response: &GrpcHttpResponse) -> Option<Code> {
+	tonic::Status::from_header_map(response.headers()).map(|status| status.code())
+}
+
+#[fixture]
+fn auth_chain() -> GrpcMiddlewareChain {
+	let authenticator =
+		MetadataTokenAuthenticator::new().with_token("secret", Principal::new("1", "alice"));
+	GrpcMiddlewareChain::new()
+		.with_middleware(Arc::new(AuthMiddleware::new(Arc::new(authenticator))))
+}
+
+/// Test: middleware run in insertion order around the service
+#[tokio::test]
+async fn test_chain_order() {
+	let log = Arc::new(Mutex::new(Vec::new()));
+	let chain = GrpcMiddlewareChain::new()
+		.with_middleware(Arc::new(RecordingMiddleware {
+			name: "outer",
+			log: log.clone(),
+		}))
+		.with_middleware(Arc::new(RecordingMiddleware {
+			name: "inner",
+			log: log.clone(),
+		}));
+
+	call(&chain, call_request(None)).await;
+
+	assert_eq!(
+		*log.lock().unwrap(),
+		vec!["outer before", "inner before", "inner after", "outer after"]
+	);
+}
+
+/// Test: the wrapped service keeps the name of the inner service
+#[rstest]
+fn test_service_name() {
+	fn name<S: NamedService>(_service: &S) -> &'static str {
+		S::NAME
+	}
+
+	assert_eq!(
+		name(&GrpcMiddlewareChain::new().service(EchoService)),
+		"test.Echo"
+	);
+}
+
+/// Test: a valid token exposes its principal to the service
+#[rstest]
+#[tokio::test]
+async fn test_auth_principal(auth_chain: GrpcMiddlewareChain) {
+	let response = call(&auth_chain, call_request(Some("secret"))).await;
+
+	assert_eq!(grpc_status(&response), None);
+	assert_eq!(response.headers()["x-username"], "alice");
+}
+
+/// Test: missing or unknown tokens are rejected with Unauthenticated
+#[rstest]
+#[case(None)]
+#[case(Some("wrong"))]
+#[tokio::test]
+async fn test_auth_rejected(auth_chain: GrpcMiddlewareChain, #[case] token: Option<&str>) {
+	let response = call(&auth_chain, call_request(token)).await;
+
+	assert_eq!(grpc_status(&response), Some(Code::Unauthenticated));
+	assert!(!response.headers().contains_key("x-username"));
+}
+
+/// Test: anonymous calls pass without a principal when allowed
+#[tokio::test]
+async fn test_auth_allow_anonymous() {
+	let authenticator = MetadataTokenAuthenticator::new();
+	let chain = GrpcMiddlewareChain::new().with_middleware(Arc::new(
+		AuthMiddleware::new(Arc::new(authenticator)).allow_anonymous(),
+	));
+
+	let response = call(&chain, call_request(None)).await;
+
+	assert_eq!(grpc_status(&response), None);
+	assert!(!response.headers().contains_key("x-username"));
+}
+
+/// Test: metrics record calls and rejected calls per method
+#[tokio::test]
+async fn test_metrics() {
+	let store = Arc::new(GrpcMetricsStore::new());
+	let mut chain = GrpcMiddlewareChain::new()
+		.with_middleware(Arc::new(GrpcLoggingMiddleware::new()))
+		.with_middleware(Arc::new(GrpcMetricsMiddleware::from_arc(store.clone())));
+	chain.add_middleware(Arc::new(AuthMiddleware::new(Arc::new(
+		MetadataTokenAuthenticator::new().with_token("secret", Principal::new("1", "alice")),
+	))));
+
+	call(&chain, call_request(Some("secret"))).await;
+	call(&chain, call_request(None)).await;
+
+	let stats = store.method_stats("/test.Echo/Say");
+	assert_eq!(stats.calls, 2);
+	assert_eq!(stats.errors, 1);
+	assert_eq!(store.total_calls(), 2);
+}
+
+/// Test: metrics read the status from the trailers once the body is sent
+#[tokio::test]
+async fn test_metrics_trailers_status() {
+	let store = Arc::new(GrpcMetricsStore::new());
+	let chain = GrpcMiddlewareChain::new()
+		.with_middleware(Arc::new(GrpcMetricsMiddleware::from_arc(store.clone())));
+	let request = http::Request::builder()
+		.method("POST")
+		.uri("/test.Missing/Get")
+		.body(Body::empty())
+		.unwrap();
+
+	let Ok(response) = chain.service(MissingService).call(request).await;
+	assert_eq!(store.total_calls(), 0);
+	let trailers = response
+		.into_body()
+		.collect()
+		.await
+		.unwrap()
+		.trailers()
+		.cloned();
+
+	assert_eq!(trailers.unwrap()["grpc-status"], "5");
+	let stats = store.method_stats("/test.Missing/Get");
+	assert_eq!(stats.calls, 1);
+	assert_eq!(stats.errors, 1);
+}
@@ -0,0 +1,211 @@
+//! Integration tests for the gRPC server
+//!
+//! These tests serve `GrpcServer` on a local port and verify that the
+//! health checking and reflection services are registered for the added
+//! services.
+
+#![cfg(all(feature = "health", feature = "reflection"))]
+
+use async_trait::async_trait;
+use reinhardt_grpc::interceptor::{
+	GrpcHandler, GrpcHttpRequest, GrpcHttpResponse, GrpcMiddleware, GrpcMiddlewareChain,
+};
+use reinhardt_grpc::server::{GrpcServer, HEALTH_SERVICE_NAME, REFLECTION_SERVICE_NAME};
+use rstest::*;
+use std::convert::Infallible;
+use std::future::{Ready, ready};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::body::Body;
+use tonic::server::NamedService;
+use tonic::transport::Channel;
+use tonic_health::pb::HealthCheckRequest;
+use tonic_health::pb::health_check_response::ServingStatus;
+use tonic_health::pb::health_client::HealthClient;
+use tonic_reflection::pb::v1::ServerReflectionRequest;
+use tonic_reflection::pb::v1::server_reflection_client::ServerReflectionClient;
+use tonic_reflection::pb::v1::server_reflection_request::MessageRequest;
+use tonic_reflection::pb::v1::server_reflection_response::MessageResponse;
+use tower_service::Service;
+
+#[derive(Clone)]
+struct EchoService;
+
+impl NamedService for EchoService {
+	const NAME: &'static str = "test.Echo";
+}
+
+impl Service<GrpcHttpRequest> for EchoService {
+	type Response = GrpcHttpResponse;
+	type Error = Infallible;
+	type Future = Ready<Result<GrpcHttpResponse, Infallible>>;
+
+	fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+		Poll::Ready(Ok(()))
+	}
+
+	fn call(&mut self, _request: GrpcHttpRequest) -> Self::Future {
+		ready(Ok(http::Response::new(Body::empty())))
+	}
+}
+
+/// Serve `server` on an ephemeral port and connect a channel to it
+async fn serve(server: GrpcServer) -> Channel {
+	let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+	let addr = listener.local_addr().unwrap();
+	let routes = server.into_routes().await.unwrap();
+	tokio::spawn(
+		tonic::transport::Server::builder()
+			.add_routes(routes)
+			.serve_with_incoming(TcpListenerStream::new(listener)),
+	);
+
+	Channel::from_shared(format!("http://{}", addr))
+		.unwrap()
+		.connect()
+		.await
+		.unwrap()
+}
+
+async fn health_status(channel: Channel, service: &str) -> Result<ServingStatus, tonic::Code> {
+	HealthClient::new(channel)
+		.check(HealthCheckRequest {
+			service: service.to_string(),
+		})
+		.await
+		.map(|response| response.into_inner().status())
+		.map_err(|status| status.code())
+}
+
+/// Middleware rejecting every call
+struct DenyAll;
+
+#[async_trait]
+impl GrpcMiddleware for DenyAll {
+	async fn process(
+		&self,
+		_request: GrpcHttpRequest,
+		_next: Arc<dyn GrpcHandler>,
+	) -> Result<GrpcHttpResponse, tonic::Status> {
+		Err(tonic::Status::permission_denied("denied"))
+	}
+}
+
+fn deny_all() -> GrpcMiddlewareChain {
+	GrpcMiddlewareChain::new().with_middleware(Arc::new(DenyAll))
+}
+
+#[fixture]
+fn server() -> GrpcServer {
+	GrpcServer::new().add_service(EchoService)
+}
+
+/// Test: added services and the server itself report SERVING
+#[rstest]
+#[case("")]
+#[case("test.Echo")]
+#[tokio::test]
+async fn test_health_serving(server: GrpcServer, #[case] service: &str) {
+	let channel = serve(server).await;
+
+	assert_eq!(
+		health_status(channel, service).await,
+		Ok(ServingStatus::Serving)
+	);
+}
+
+/// Test: unknown services are reported as NOT_FOUND
+#[rstest]
+#[tokio::test]
+async fn test_health_unknown_service(server: GrpcServer) {
+	let channel = serve(server).await;
+
+	assert_eq!(
+		health_status(channel, "test.Missing").await,
+		Err(tonic::Code::NotFound)
+	);
+}
+
+/// Test: status changes through the health reporter are visible to probes
+#[rstest]
+#[tokio::test]
+async fn test_health_reporter(server: GrpcServer) {
+	let reporter = server.health_reporter();
+	let channel = serve(server).await;
+
+	reporter
+		.set_service_status(
+			"test.Echo",
+			reinhardt_grpc::server::ServingStatus::NotServing,
+		)
+		.await;
+
+	assert_eq!(
+		health_status(channel, "test.Echo").await,
+		Ok(ServingStatus::NotServing)
+	);
+}
+
+/// Test: reflection lists the added services, health and reflection itself
+#[rstest]
+#[tokio::test]
+async fn test_reflection_list_services(server: GrpcServer) {
+	let channel = serve(server).await;
+
+	let request = ServerReflectionRequest {
+		host: String::new(),
+		message_request: Some(MessageRequest::ListServices(String::new())),
+	};
+	let mut stream = ServerReflectionClient::new(channel)
+		.server_reflection_info(tokio_stream::once(request))
+		.await
+		.unwrap()
+		.into_inner();
+	let response = stream.message().await.unwrap().unwrap();
+
+	let Some(MessageResponse::ListServicesResponse(list)) = response.message_response else {
+		panic!("unexpected reflection response");
+	};
+	let mut names: Vec<_> = list.service.into_iter().map(|s| s.name).collect();
+	names.sort();
+	assert_eq!(
+		names,
+		vec![HEALTH_SERVICE_NAME, REFLECTION_SERVICE_NAME, "test.Echo"]
+	);
+}
+
+/// Test: health and reflection can be disabled
+#[tokio::test]
+async fn test_without_health() {
+	let channel = serve(GrpcServer::new().without_health().add_service(EchoService)).await;
+
+	assert_eq!(
+		health_status(channel, "").await,
+		Err(tonic::Code::Unimplemented)
+	);
+}
+
+/// Test: middleware wraps services regardless of the call order
+#[rstest]
+#[case(GrpcServer::new().with_middleware(deny_all()).add_service(EchoService))]
+#[case(GrpcServer::new().add_service(EchoService).with_middleware(deny_all()))]
+#[tokio::test]
+async fn test_middleware_applies_regardless_of_order(#[case] server: GrpcServer) {
+	let mut routes = server.into_routes().await.unwrap();
+	let request = http::Request::builder()
+		.method(http::Method::POST)
+		.uri("/test.Echo/Call")
+		.header(http::header::CONTENT_TYPE, "application/grpc")
+		.body(Body::empty())
+		.unwrap();
+
+	std::future::poll_fn(|cx| routes.poll_ready(cx))
+		.await
+		.unwrap();
+	let response = routes.call(request).await.unwrap();
+
+	let status = tonic::Status::from_header_map(response.headers()).unwrap();
+	assert_eq!(status.code(), tonic::Code::PermissionDenied);
+}
@@ -0,0 +1,156 @@
+//! Integration tests for grpc-web support
+//!
+//! These tests send grpc-web requests, as a browser client would, to the
+//! health service of a `GrpcServer` wrapped in the `GrpcWeb` layer.
+
+#![cfg(all(feature = "web", feature = "health"))]
+
+use http::{Method, StatusCode, header};
+use http_body_util::BodyExt;
+use reinhardt_grpc::server::GrpcServer;
+use reinhardt_grpc::web::GrpcWeb;
+use rstest::*;
+use tonic::body::Body;
+use tower_layer::Layer;
+use tower_service::Service;
+
+const HEALTH_CHECK: &str = "/grpc.health.v1.Health/Check";
+
+/// Send `request` through the grpc-web layer in front of the server routes
+async fn call(web: GrpcWeb, request: http::Request<Body>) -> http::Response<Body> {
+	let routes = GrpcServer::new().into_routes().await.unwrap();
+	let mut service = web.layer(routes);
+	std::future::poll_fn(|cx| service.poll_ready(cx))
+		.await
+		.unwrap();
+	service.call(request).await.unwrap()
+}
+
+/// grpc-web call of `Health/Check` for the overall server status
+fn health_check(origin: Option<&str>) -> http::Request<Body> {
+	let mut builder = http::Request::builder()
+		.method(Method::POST)
+		.uri(HEALTH_CHECK)
+		.version(http::Version::HTTP_11)
+		.header(header::CONTENT_TYPE, "application/grpc-web+proto")
+		.header("x-grpc-web", "1");
+	if let Some(origin) = origin {
+		builder = builder.header(header::ORIGIN, origin);
+	}
+	// Uncompressed frame holding an empty HealthCheckRequest
+	builder
+		.body(Body::new(http_body_util::Full::new(bytes_frame(&[]))))
+		.unwrap()
+}
+
+fn bytes_frame(message: &[u8]) -> bytes::Bytes {
+	let mut frame = vec![0u8];
+	frame.extend_from_slice(&(message.len() as u32).to_be_bytes());
+	frame.extend_from_slice(message);
+	frame.into()
+}
+
+/// Test: unary calls are translated over HTTP/1.1
+#[tokio::test]
+async fn test_grpc_web_unary_call() {
+	let response = call(GrpcWeb::new(), health_check(None)).await;
+
+	assert_eq!(response.status(), StatusCode::OK);
+	assert_eq!(
+		response.headers()[header::CONTENT_TYPE],
+		"application/grpc-web+proto"
+	);
+	let body = response.into_body().collect().await.unwrap().to_bytes();
+	// Data frame with HealthCheckResponse { status: SERVING } ...
+	assert_eq!(&body[..7], &[0, 0, 0, 0, 2, 0x08, 0x01]);
+	// ... followed by a trailers frame carrying grpc-status
+	assert_eq!(body[7], 0x80);
+	assert!(String::from_utf8_lossy(&body[12..]).contains("grpc-status:0"));
+}
+
+/// Test: allowed origins receive CORS headers on responses
+#[tokio::test]
+async fn test_cors_headers() {
+	let web = GrpcWeb::new().allow_origin("https://app.example.com");
+
+	let response = call(web, health_check(Some("https://app.example.com"))).await;
+
+	assert_eq!(response.status(), StatusCode::OK);
+	let headers = response.headers();
+	assert_eq!(
+		headers[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+		"https://app.example.com"
+	);
+	assert!(
+		headers[header::ACCESS_CONTROL_EXPOSE_HEADERS]
+			.to_str()
+			.unwrap()
+			.contains("grpc-status")
+	);
+}
+
+/// Test: calls from other origins are rejected
+#[tokio::test]
+async fn test_disallowed_origin() {
+	let web = GrpcWeb::new().allow_origin("https://app.example.com");
+
+	let response = call(web, health_check(Some("https://evil.example.com"))).await;
+
+	assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+/// Test: without allowed origins every cross-origin call is rejected
+#[rstest]
+#[case(Method::POST)]
+#[case(Method::OPTIONS)]
+#[tokio::test]
+async fn test_empty_allow_list_denies_all(#[case] method: Method) {
+	let request = http::Request::builder()
+		.method(method)
+		.uri(HEALTH_CHECK)
+		.header(header::ORIGIN, "https://app.example.com")
+		.header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+		.body(Body::empty())
+		.unwrap();
+
+	let response = call(GrpcWeb::new(), request).await;
+
+	assert_eq!(response.status(), StatusCode::FORBIDDEN);
+	assert!(
+		!response
+			.headers()
+			.contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+	);
+}
+
+/// Test: preflight requests are answered without reaching the service
+#[rstest]
+#[case(
+	None,
+	"content-type, x-grpc-web, x-user-agent, grpc-timeout, authorization"
+)]
+#[case(Some("content-type, x-grpc-web"), "content-type, x-grpc-web")]
+#[tokio::test]
+async fn test_preflight(#[case] requested: Option<&str>, #[case] allowed: &str) {
+	let mut builder = http::Request::builder()
+		.method(Method::OPTIONS)
+		.uri(HEALTH_CHECK)
+		.header(header::ORIGIN, "https://app.example.com")
+		.header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST");
+	if let Some(requested) = requested {
+		builder = builder.header(header::ACCESS_CONTROL_REQUEST_HEADERS, requested);
+	}
+
+	let web = GrpcWeb::new().allow_origin("https://app.example.com");
+
+	let response = call(web, builder.body(Body::empty()).unwrap()).await;
+
+	assert_eq!(response.status(), StatusCode::NO_CONTENT);
+	let headers = response.headers();
+	assert_eq!(
+		headers[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+		"https://app.example.com"
+	);
+	assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_METHODS], "POST");
+	assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_HEADERS], allowed);
+}
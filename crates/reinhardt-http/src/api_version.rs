@@ -0,0 +1,47 @@
+//! API version request extension
+//!
+//! [`ApiVersion`] is shared between the versioning middleware and the router so
+//! that whichever component negotiates the version, handlers see the same type.
+
+/// API version negotiated for a request
+///
+/// Stored in request extensions by versioning middleware and versioned router
+/// mounts so handlers and serializers can read the active version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiVersion(pub String);
+
+impl ApiVersion {
+	/// Get the version string as a string slice
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_http::ApiVersion;
+	///
+	/// let version = ApiVersion::new("2.0".to_string());
+	/// assert_eq!(version.as_str(), "2.0");
+	/// ```
+	pub fn as_str(&self) -> &str {
+		&self.0
+	}
+
+	/// Create a new ApiVersion with the given version string
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_http::ApiVersion;
+	///
+	/// let version = ApiVersion::new("1.0".to_string());
+	/// assert_eq!(version.as_str(), "1.0");
+	/// ```
+	pub fn new(version: String) -> Self {
+		Self(version)
+	}
+}
+
+impl std::fmt::Display for ApiVersion {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.0)
+	}
+}
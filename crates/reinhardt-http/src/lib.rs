@@ -37,6 +37,7 @@
 //!     .unwrap();
 //! ```
 
+pub mod api_version;
 pub mod auth_state;
 pub mod chunked_upload;
 pub mod extensions;
@@ -47,6 +48,7 @@ pub mod request;
 pub mod response;
 pub mod upload;
 
+pub use api_version::ApiVersion;
 pub use auth_state::AuthState;
 pub use chunked_upload::{
 	ChunkedUploadError, ChunkedUploadManager, ChunkedUploadSession, UploadProgress,
@@ -59,6 +59,11 @@ pub trait Handler: Send + Sync {
 	///
 	/// Returns an error if the request cannot be processed.
 	async fn handle(&self, request: Request) -> Result<Response>;
+
+	/// Returns the type name of this handler, used by route introspection.
+	fn type_name(&self) -> &'static str {
+		std::any::type_name::<Self>()
+	}
 }
 
 /// Blanket implementation for `Arc<T>` where T: Handler.
@@ -70,6 +75,10 @@ impl<T: Handler + ?Sized> Handler for Arc<T> {
 	async fn handle(&self, request: Request) -> Result<Response> {
 		(**self).handle(request).await
 	}
+
+	fn type_name(&self) -> &'static str {
+		(**self).type_name()
+	}
 }
 
 /// Middleware trait for request/response processing.
@@ -115,6 +124,11 @@ pub trait Middleware: Send + Sync {
 	fn should_continue(&self, _request: &Request) -> bool {
 		true
 	}
+
+	/// Returns the type name of this middleware, used by route introspection.
+	fn type_name(&self) -> &'static str {
+		std::any::type_name::<Self>()
+	}
 }
 
 /// Middleware chain - composes multiple middleware into a single handler.
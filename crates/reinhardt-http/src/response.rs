@@ -409,9 +409,16 @@ impl From<crate::Error> for Response {
 	fn from(error: crate::Error) -> Self {
 		let status =
 			StatusCode::from_u16(error.status_code()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
-		let body = serde_json::json!({
+		let mut body = serde_json::json!({
 			"error": error.to_string(),
 		});
+		// 406/415 responses list the supported values and the offending header
+		if let Some(ctx) = error.negotiation_context()
+			&& let serde_json::Value::Object(details) = ctx.to_json()
+			&& let Some(fields) = body.as_object_mut()
+		{
+			fields.extend(details);
+		}
 
 		Response::new(status)
 			.with_json(&body)
@@ -348,3 +348,41 @@ async fn test_request_response_query_params() {
 	assert!(body.contains(r#""query":"test""#));
 	assert!(body.contains(r#""limit":"20""#));
 }
+
+/// Test 415 responses describe the rejected Content-Type
+#[test]
+fn test_unsupported_media_type_response_lists_supported_types() {
+	use reinhardt_core::exception::NegotiationErrorContext;
+
+	let ctx = NegotiationErrorContext::content_type(
+		Some("text/csv"),
+		["application/json", "application/x-www-form-urlencoded"],
+	);
+	let response = Response::from(Error::UnsupportedMediaType(Box::new(ctx)));
+
+	assert_eq!(response.status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+	let body: serde_json::Value = serde_json::from_slice(&response.body).unwrap();
+	assert_eq!(body["header"], "Content-Type");
+	assert_eq!(body["received"], "text/csv");
+	assert_eq!(
+		body["supported"],
+		serde_json::json!(["application/json", "application/x-www-form-urlencoded"])
+	);
+}
+
+/// Test 406 responses from failed negotiation describe the rejected header
+#[test]
+fn test_not_acceptable_response_from_language_negotiation() {
+	use reinhardt_core::negotiation::language::{Language, LanguageNegotiator};
+
+	let err = LanguageNegotiator::new()
+		.select_acceptable("de", &[Language::new("en"), Language::new("ja")])
+		.unwrap_err();
+	let response = Response::from(err);
+
+	assert_eq!(response.status, StatusCode::NOT_ACCEPTABLE);
+	let body: serde_json::Value = serde_json::from_slice(&response.body).unwrap();
+	assert_eq!(body["header"], "Accept-Language");
+	assert_eq!(body["received"], "de");
+	assert_eq!(body["supported"], serde_json::json!(["en", "ja"]));
+}
@@ -43,6 +43,10 @@
 //!
 //! - **`RateLimitMiddleware`**: API rate limiting with multiple strategies
 //!   (requires `rate-limit` feature)
+//! - **`ThrottleMiddleware`**: Applies a `reinhardt-throttling` throttle with
+//!   `Retry-After` and `RateLimit-*` headers (requires `rate-limit` feature)
+//! - **`ConcurrencyLimitMiddleware`**: Limits simultaneous in-flight requests
+//!   per client (requires `rate-limit` feature)
 //! - **[`CircuitBreakerMiddleware`]**: Circuit breaker pattern for fault tolerance
 //! - **[`TimeoutMiddleware`]**: Request timeout handling
 //!
@@ -121,6 +125,8 @@ pub mod https_redirect;
 pub mod locale;
 pub mod logging;
 pub mod messages;
+pub mod messages_api;
+pub mod messages_context;
 pub mod metrics;
 #[cfg(feature = "rate-limit")]
 pub mod rate_limit;
@@ -167,10 +173,18 @@ pub use honeypot::{HoneypotError, HoneypotField};
 pub use https_redirect::{HttpsRedirectConfig, HttpsRedirectMiddleware};
 pub use locale::{LocaleConfig, LocaleMiddleware};
 pub use logging::{LoggingConfig, LoggingMiddleware};
-pub use messages::{CookieStorage, Message, MessageLevel, MessageStorage, SessionStorage};
+pub use messages::{
+	CookieStorage, Message, MessageLevel, MessageLevels, MessageStorage, RequestMessages,
+	SessionStorage, add_message, get_level, get_messages, set_level,
+};
+pub use messages_api::{MessageBroadcaster, MessagesApiHandler, messages_response};
+pub use messages_context::{MessageContext, context_processor};
 pub use metrics::{MetricsConfig, MetricsMiddleware, MetricsStore};
 #[cfg(feature = "rate-limit")]
-pub use rate_limit::{RateLimitConfig, RateLimitMiddleware, RateLimitStore, RateLimitStrategy};
+pub use rate_limit::{
+	ConcurrencyLimitMiddleware, RateLimitConfig, RateLimitMiddleware, RateLimitStore,
+	RateLimitStrategy, ThrottleMiddleware, TrustedProxies,
+};
 pub use redirect_fallback::{RedirectFallbackMiddleware, RedirectResponseConfig};
 pub use request_id::{REQUEST_ID_HEADER, RequestIdConfig, RequestIdMiddleware};
 #[cfg(feature = "security")]
@@ -13,35 +13,171 @@
 //! ```
 //!
 //! This allows `MessageMiddleware` to use the session ID from
-//! `SessionMiddleware` via request extensions.
+//! `SessionMiddleware` via request extensions. Requests without a session
+//! can still add messages, but storages keyed by session (such as
+//! [`SessionStorage`]) drop them instead of sharing one bucket between all
+//! anonymous clients.
+//!
+//! # Adding Messages
+//!
+//! Handlers queue messages with [`add_message`] and read pending ones with
+//! [`get_messages`]. Messages that are not read during the request are
+//! persisted back to the storage and shown on a later request. Storage is
+//! only updated once the handler has produced a response, so a failed
+//! request does not lose the pending messages.
+//!
+//! # Levels
+//!
+//! Messages below the minimum level are dropped, like Django's
+//! `MESSAGE_LEVEL`. The level can be set on the middleware, per request with
+//! [`set_level`], and per storage with `with_min_level`. Custom levels are
+//! plain numeric values; their tags are registered in [`MessageLevels`].
+//! Levels are serialized as numbers so they round-trip unchanged through
+//! every storage.
+//!
+//! # Templates
+//!
+//! [`context_processor`](crate::messages_context::context_processor) exposes the pending messages to
+//! templates as a `messages` variable, mirroring Django's
+//! `django.contrib.messages.context_processors.messages`.
 
 use async_trait::async_trait;
-use hyper::header::COOKIE;
+use hyper::header::{COOKIE, HeaderValue, SET_COOKIE};
+use reinhardt_conf::settings::Settings;
+use reinhardt_core::exception::Error;
+use reinhardt_core::security::csrf::{generate_token_hmac, verify_token_hmac};
 use reinhardt_http::{Handler, Middleware, Request, Response, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 
+use crate::messages_api::MessageBroadcaster;
 use crate::session::SessionData;
 
 /// Message header for passing messages between middleware and handlers
 pub const MESSAGE_HEADER: &str = "X-Messages";
 
 /// Message severity levels
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum MessageLevel {
-	Debug,
-	Info,
-	Success,
-	Warning,
-	Error,
+///
+/// This is the core [`Level`](reinhardt_core::messages::Level), so
+/// project-defined levels are written as `MessageLevel::Custom(35)`.
+pub use reinhardt_core::messages::Level as MessageLevel;
+
+/// Serde format of [`Message::level`]: the numeric value of the level
+mod level_value {
+	use super::MessageLevel;
+	use serde::{Deserialize, Deserializer, Serializer};
+	use std::str::FromStr;
+
+	pub(super) fn serialize<S: Serializer>(
+		level: &MessageLevel,
+		serializer: S,
+	) -> Result<S::Ok, S::Error> {
+		serializer.serialize_i32(level.value())
+	}
+
+	pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+		deserializer: D,
+	) -> Result<MessageLevel, D::Error> {
+		// Level names are accepted for data written before levels were numeric
+		#[derive(Deserialize)]
+		#[serde(untagged)]
+		enum Repr {
+			Value(i32),
+			Name(String),
+		}
+
+		match Repr::deserialize(deserializer)? {
+			Repr::Value(value) => Ok(MessageLevel::from_value(value)),
+			Repr::Name(name) => MessageLevel::from_str(&name).map_err(serde::de::Error::custom),
+		}
+	}
+}
+
+/// Tags for message levels, including project-defined ones
+///
+/// Equivalent to Django's `MESSAGE_TAGS` setting.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_middleware::messages::{Message, MessageLevel, MessageLevels};
+///
+/// let levels = MessageLevels::new().with_level(35, "critical");
+/// assert_eq!(levels.tag(MessageLevel::Custom(35)), Some("critical"));
+/// assert_eq!(levels.level("critical"), Some(MessageLevel::Custom(35)));
+///
+/// let message = Message::new(MessageLevel::Custom(35), "Disk almost full".to_string())
+///     .with_tags(["sticky"]);
+/// assert_eq!(message.tags(&levels), vec!["sticky", "critical"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MessageLevels {
+	tags: HashMap<i32, String>,
+}
+
+impl MessageLevels {
+	/// Create a registry with tags for the built-in levels
+	pub fn new() -> Self {
+		let tags = [
+			(MessageLevel::Debug, "debug"),
+			(MessageLevel::Info, "info"),
+			(MessageLevel::Success, "success"),
+			(MessageLevel::Warning, "warning"),
+			(MessageLevel::Error, "error"),
+		]
+		.into_iter()
+		.map(|(level, tag)| (level.value(), tag.to_string()))
+		.collect();
+		Self { tags }
+	}
+
+	/// Register a level value with its tag, replacing any existing tag
+	pub fn with_level(mut self, value: i32, tag: impl Into<String>) -> Self {
+		self.register(value, tag);
+		self
+	}
+
+	/// Register a level value with its tag, replacing any existing tag
+	pub fn register(&mut self, value: i32, tag: impl Into<String>) {
+		self.tags.insert(value, tag.into());
+	}
+
+	/// Tag for a level
+	pub fn tag(&self, level: MessageLevel) -> Option<&str> {
+		self.tags.get(&level.value()).map(String::as_str)
+	}
+
+	/// Level registered under a tag
+	pub fn level(&self, tag: &str) -> Option<MessageLevel> {
+		self.tags
+			.iter()
+			.find(|(_, registered)| registered.as_str() == tag)
+			.map(|(value, _)| MessageLevel::from_value(*value))
+	}
+}
+
+impl Default for MessageLevels {
+	fn default() -> Self {
+		Self::new()
+	}
 }
 
 /// A single flash message
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Message {
+	#[serde(with = "level_value")]
 	pub level: MessageLevel,
 	pub text: String,
+	/// Extra tags rendered alongside the level tag
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub extra_tags: Vec<String>,
+	/// Whether the text is trusted HTML that templates must not escape
+	///
+	/// Kept by every storage so it survives a redirect; [`CookieStorage`]
+	/// signs its cookie, so clients cannot set it.
+	#[serde(default, skip_serializing_if = "std::ops::Not::not")]
+	pub safe: bool,
 }
 
 impl Message {
@@ -56,7 +192,44 @@ impl Message {
 	/// assert_eq!(msg.level, MessageLevel::Success);
 	/// ```
 	pub fn new(level: MessageLevel, text: String) -> Self {
-		Self { level, text }
+		Self {
+			level,
+			text,
+			extra_tags: Vec::new(),
+			safe: false,
+		}
+	}
+
+	/// Mark the text as trusted HTML, like Django's `mark_safe`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_middleware::messages::Message;
+	///
+	/// let msg = Message::success("<b>Saved</b>".to_string()).mark_safe();
+	/// assert!(msg.safe);
+	/// ```
+	pub fn mark_safe(mut self) -> Self {
+		self.safe = true;
+		self
+	}
+
+	/// Set the extra tags of the message
+	pub fn with_tags<I, T>(mut self, tags: I) -> Self
+	where
+		I: IntoIterator<Item = T>,
+		T: Into<String>,
+	{
+		self.extra_tags = tags.into_iter().map(Into::into).collect();
+		self
+	}
+
+	/// Extra tags followed by the level tag, as in Django's `message.tags`
+	pub fn tags(&self, levels: &MessageLevels) -> Vec<String> {
+		let mut tags = self.extra_tags.clone();
+		tags.extend(levels.tag(self.level).map(str::to_string));
+		tags
 	}
 
 	/// Create a debug message
@@ -93,6 +266,75 @@ pub trait MessageStorage: Send + Sync {
 	fn get_and_clear_messages(&self, session_id: &str) -> Vec<Message>;
 	/// Get messages without clearing
 	fn get_messages(&self, session_id: &str) -> Vec<Message>;
+
+	/// Remove the given messages of a session, keeping the others in order
+	///
+	/// Each entry of `messages` removes at most one equal stored message.
+	fn remove_messages(&self, session_id: &str, messages: &[Message]) {
+		let mut to_remove = messages.to_vec();
+		for message in self.get_and_clear_messages(session_id) {
+			match to_remove.iter().position(|removed| *removed == message) {
+				Some(index) => {
+					to_remove.swap_remove(index);
+				}
+				None => self.add_message(session_id, message),
+			}
+		}
+	}
+
+	/// Minimum level this storage keeps, if any
+	fn min_level(&self) -> Option<MessageLevel> {
+		None
+	}
+
+	/// Whether a message passes the storage's minimum level
+	fn accepts(&self, message: &Message) -> bool {
+		self.min_level().is_none_or(|min| message.level >= min)
+	}
+
+	/// Load the pending messages for a request at request start
+	///
+	/// Loading does not consume the messages; they are only removed by
+	/// [`store`](Self::store) once the request succeeded. `session_id` is
+	/// `None` for requests without a session, which have no messages here.
+	fn load(&self, session_id: Option<&str>, _request: &Request) -> Vec<Message> {
+		session_id
+			.map(|session_id| self.get_messages(session_id))
+			.unwrap_or_default()
+	}
+
+	/// Persist unread messages once the request has been handled
+	///
+	/// `loaded` is the number of messages returned by [`load`](Self::load);
+	/// they are replaced by `messages`, while messages added to the storage
+	/// by other requests in the meantime are kept. Without a session the
+	/// messages cannot be stored and are dropped.
+	fn store(
+		&self,
+		session_id: Option<&str>,
+		loaded: usize,
+		messages: Vec<Message>,
+		_response: &mut Response,
+	) -> Result<()> {
+		let Some(session_id) = session_id else {
+			if !messages.is_empty() {
+				log::warn!(
+					"Dropping {} message(s): the request has no session",
+					messages.len()
+				);
+			}
+			return Ok(());
+		};
+		if loaded == 0 && messages.is_empty() {
+			return Ok(());
+		}
+		let mut queued = self.get_and_clear_messages(session_id);
+		queued.drain(..loaded.min(queued.len()));
+		for message in messages.into_iter().chain(queued) {
+			self.add_message(session_id, message);
+		}
+		Ok(())
+	}
 }
 
 /// Session-based message storage
@@ -101,6 +343,7 @@ pub trait MessageStorage: Send + Sync {
 /// In production, this should be backed by a persistent session store.
 pub struct SessionStorage {
 	messages: Arc<RwLock<HashMap<String, Vec<Message>>>>,
+	min_level: Option<MessageLevel>,
 }
 
 impl SessionStorage {
@@ -116,8 +359,26 @@ impl SessionStorage {
 	pub fn new() -> Self {
 		Self {
 			messages: Arc::new(RwLock::new(HashMap::new())),
+			min_level: None,
 		}
 	}
+
+	/// Drop messages below `level`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_middleware::messages::{Message, MessageLevel, MessageStorage, SessionStorage};
+	///
+	/// let storage = SessionStorage::new().with_min_level(MessageLevel::Warning);
+	/// storage.add_message("s", Message::info("Ignored".to_string()));
+	/// storage.add_message("s", Message::error("Kept".to_string()));
+	/// assert_eq!(storage.get_messages("s").len(), 1);
+	/// ```
+	pub fn with_min_level(mut self, level: MessageLevel) -> Self {
+		self.min_level = Some(level);
+		self
+	}
 }
 
 impl Default for SessionStorage {
@@ -128,6 +389,9 @@ impl Default for SessionStorage {
 
 impl MessageStorage for SessionStorage {
 	fn add_message(&self, session_id: &str, message: Message) {
+		if !self.accepts(&message) {
+			return;
+		}
 		let mut messages = self.messages.write().unwrap();
 		messages
 			.entry(session_id.to_string())
@@ -144,17 +408,42 @@ impl MessageStorage for SessionStorage {
 		let messages = self.messages.read().unwrap();
 		messages.get(session_id).cloned().unwrap_or_default()
 	}
+
+	fn min_level(&self) -> Option<MessageLevel> {
+		self.min_level
+	}
 }
 
 /// Cookie-based message storage
 ///
-/// Stores messages in memory similar to SessionStorage but designed
-/// to be serialized to cookies in production.
+/// Messages added directly through [`MessageStorage::add_message`] are queued
+/// in memory. When used by [`MessageMiddleware`], pending messages are read
+/// from the request cookie and unread messages are written back with
+/// `Set-Cookie`, dropping the oldest ones if the cookie would be too large.
+///
+/// The cookie value is signed with HMAC-SHA256 using the project's
+/// `SECRET_KEY`, like Django's `CookieStorage`; values with a missing or bad
+/// signature are discarded. Build the storage with
+/// [`from_settings`](Self::from_settings) or set the key with
+/// [`with_secret_key`](Self::with_secret_key); without a key the cookie is
+/// neither read nor written and storing messages fails.
+///
+/// Messages added directly with [`MessageStorage::add_message`] are moved
+/// into the cookie by the next response of the same session.
 pub struct CookieStorage {
 	messages: Arc<RwLock<HashMap<String, Vec<Message>>>>,
+	secret_key: Option<Arc<Vec<u8>>>,
+	cookie_name: String,
+	max_cookie_size: usize,
+	min_level: Option<MessageLevel>,
 }
 
 impl CookieStorage {
+	/// Default cookie name, matching Django
+	pub const DEFAULT_COOKIE_NAME: &'static str = "messages";
+	/// Default maximum cookie value size in bytes
+	pub const DEFAULT_MAX_SIZE: usize = 2048;
+
 	/// Create a new CookieStorage
 	///
 	/// # Examples
@@ -167,7 +456,104 @@ impl CookieStorage {
 	pub fn new() -> Self {
 		Self {
 			messages: Arc::new(RwLock::new(HashMap::new())),
+			secret_key: None,
+			cookie_name: Self::DEFAULT_COOKIE_NAME.to_string(),
+			max_cookie_size: Self::DEFAULT_MAX_SIZE,
+			min_level: None,
+		}
+	}
+
+	/// Drop messages below `level`
+	pub fn with_min_level(mut self, level: MessageLevel) -> Self {
+		self.min_level = Some(level);
+		self
+	}
+
+	/// Set the key used to sign the cookie, usually the project's `SECRET_KEY`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_middleware::messages::CookieStorage;
+	///
+	/// let storage = CookieStorage::new().with_secret_key("my-secret-key");
+	/// ```
+	pub fn with_secret_key(mut self, key: impl AsRef<[u8]>) -> Self {
+		self.secret_key = Some(Arc::new(key.as_ref().to_vec()));
+		self
+	}
+
+	/// Create a storage signing the cookie with the settings' `SECRET_KEY`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_conf::settings::Settings;
+	/// use reinhardt_middleware::messages::CookieStorage;
+	///
+	/// let settings = Settings::new(std::env::temp_dir(), "my-secret-key".to_string());
+	/// let storage = CookieStorage::from_settings(&settings);
+	/// ```
+	pub fn from_settings(settings: &Settings) -> Self {
+		Self::new().with_secret_key(&settings.secret_key)
+	}
+
+	/// Set the name of the cookie holding the messages
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_middleware::messages::CookieStorage;
+	///
+	/// let storage = CookieStorage::new().with_cookie_name("flash");
+	/// assert_eq!(storage.cookie_name(), "flash");
+	/// ```
+	pub fn with_cookie_name(mut self, name: impl Into<String>) -> Self {
+		self.cookie_name = name.into();
+		self
+	}
+
+	/// Set the maximum size of the encoded cookie value
+	pub fn with_max_size(mut self, size: usize) -> Self {
+		self.max_cookie_size = size;
+		self
+	}
+
+	/// Name of the cookie holding the messages
+	pub fn cookie_name(&self) -> &str {
+		&self.cookie_name
+	}
+
+	/// Encode messages as a signed cookie value (`<payload>:<signature>`)
+	fn encode(&self, messages: &[Message]) -> Result<String> {
+		let secret_key = self.secret_key.as_ref().ok_or_else(|| {
+			Error::ImproperlyConfigured(
+				"CookieStorage requires a secret key; use CookieStorage::from_settings".to_string(),
+			)
+		})?;
+		let json = serde_json::to_string(messages)?;
+		let payload: String = url::form_urlencoded::byte_serialize(json.as_bytes()).collect();
+		let signature = generate_token_hmac(secret_key, &payload);
+		Ok(format!("{}:{}", payload, signature))
+	}
+
+	/// Decode a signed cookie value, ignoring tampered or malformed data
+	fn decode(&self, value: &str) -> Vec<Message> {
+		let Some(secret_key) = &self.secret_key else {
+			return Vec::new();
+		};
+		// The payload is URL-encoded, so `:` only appears as the separator
+		let Some((payload, signature)) = value.rsplit_once(':') else {
+			return Vec::new();
+		};
+		if !verify_token_hmac(signature, secret_key, payload) {
+			return Vec::new();
 		}
+		// The payload contains no `&` or `=`, so it parses as a single key
+		url::form_urlencoded::parse(payload.as_bytes())
+			.next()
+			.and_then(|(json, _)| serde_json::from_str(&json).ok())
+			.unwrap_or_default()
 	}
 }
 
@@ -179,6 +565,9 @@ impl Default for CookieStorage {
 
 impl MessageStorage for CookieStorage {
 	fn add_message(&self, session_id: &str, message: Message) {
+		if !self.accepts(&message) {
+			return;
+		}
 		let mut messages = self.messages.write().unwrap();
 		messages
 			.entry(session_id.to_string())
@@ -195,6 +584,223 @@ impl MessageStorage for CookieStorage {
 		let messages = self.messages.read().unwrap();
 		messages.get(session_id).cloned().unwrap_or_default()
 	}
+
+	fn min_level(&self) -> Option<MessageLevel> {
+		self.min_level
+	}
+
+	fn load(&self, _session_id: Option<&str>, request: &Request) -> Vec<Message> {
+		cookie_value(request, &self.cookie_name)
+			.map(|value| self.decode(&value))
+			.unwrap_or_default()
+	}
+
+	fn store(
+		&self,
+		session_id: Option<&str>,
+		loaded: usize,
+		mut messages: Vec<Message>,
+		response: &mut Response,
+	) -> Result<()> {
+		// The cookie is rewritten as a whole, so it replaces the loaded messages
+		if let Some(session_id) = session_id {
+			messages.extend(self.get_and_clear_messages(session_id));
+		}
+		if loaded == 0 && messages.is_empty() {
+			// No cookie to write or clear
+			return Ok(());
+		}
+		messages.retain(|message| self.accepts(message));
+		let mut value = self.encode(&messages)?;
+		while value.len() > self.max_cookie_size && !messages.is_empty() {
+			messages.remove(0);
+			value = self.encode(&messages)?;
+		}
+		let cookie = if messages.is_empty() {
+			format!(
+				"{}=; Path=/; HttpOnly; SameSite=Lax; Max-Age=0",
+				self.cookie_name
+			)
+		} else {
+			format!(
+				"{}={}; Path=/; HttpOnly; SameSite=Lax",
+				self.cookie_name, value
+			)
+		};
+		let header = HeaderValue::from_str(&cookie)
+			.map_err(|e| Error::Internal(format!("Failed to create cookie header: {}", e)))?;
+		response.headers.append(SET_COOKIE, header);
+		Ok(())
+	}
+}
+
+/// Read a cookie from the request's `Cookie` header
+fn cookie_value(request: &Request, name: &str) -> Option<String> {
+	request
+		.headers
+		.get(COOKIE)
+		.and_then(|c| c.to_str().ok())
+		.and_then(|cookies| {
+			cookies.split(';').find_map(|cookie| {
+				let (key, value) = cookie.trim().split_once('=')?;
+				(key == name).then(|| value.to_string())
+			})
+		})
+}
+
+/// Messages available to a single request
+///
+/// Inserted into the request extensions by [`MessageMiddleware`]. Holds the
+/// pending messages loaded from storage and any added while handling the
+/// request; whatever is still here when the response is produced is stored
+/// for the next request.
+#[derive(Clone, Default)]
+pub struct RequestMessages {
+	messages: Arc<Mutex<Vec<Message>>>,
+	level: Arc<Mutex<MessageLevel>>,
+}
+
+impl RequestMessages {
+	/// Create a container holding `messages`
+	pub fn new(messages: Vec<Message>) -> Self {
+		Self {
+			messages: Arc::new(Mutex::new(messages)),
+			level: Arc::default(),
+		}
+	}
+
+	/// Set the initial minimum level for new messages
+	pub fn with_level(self, level: MessageLevel) -> Self {
+		self.set_level(level);
+		self
+	}
+
+	/// Minimum level for new messages
+	pub fn level(&self) -> MessageLevel {
+		*self.level.lock().unwrap()
+	}
+
+	/// Change the minimum level for messages added from now on
+	pub fn set_level(&self, level: MessageLevel) {
+		*self.level.lock().unwrap() = level;
+	}
+
+	/// Queue a message, returning `false` if it is below the minimum level
+	pub fn add(&self, message: Message) -> bool {
+		if message.level < self.level() {
+			return false;
+		}
+		self.messages.lock().unwrap().push(message);
+		true
+	}
+
+	/// Take all messages, marking them as read
+	pub fn take(&self) -> Vec<Message> {
+		std::mem::take(&mut *self.messages.lock().unwrap())
+	}
+
+	/// Messages that have not been read yet
+	pub fn pending(&self) -> Vec<Message> {
+		self.messages.lock().unwrap().clone()
+	}
+}
+
+/// Queue a message for the current request's user
+///
+/// Messages below the request's minimum level are silently dropped. Fails
+/// when [`MessageMiddleware`] is not installed.
+///
+/// # Examples
+///
+/// ```
+/// use bytes::Bytes;
+/// use hyper::{HeaderMap, Method, Version};
+/// use reinhardt_http::Request;
+/// use reinhardt_middleware::messages::{MessageLevel, RequestMessages, add_message, get_messages};
+///
+/// let request = Request::builder()
+///     .method(Method::POST)
+///     .uri("/articles/")
+///     .version(Version::HTTP_11)
+///     .headers(HeaderMap::new())
+///     .body(Bytes::new())
+///     .build()
+///     .unwrap();
+/// assert!(add_message(&request, MessageLevel::Success, "Saved").is_err());
+///
+/// request.extensions.insert(RequestMessages::default());
+/// add_message(&request, MessageLevel::Success, "Saved").unwrap();
+/// assert_eq!(get_messages(&request)[0].text, "Saved");
+/// assert!(get_messages(&request).is_empty());
+/// ```
+pub fn add_message(request: &Request, level: MessageLevel, text: impl Into<String>) -> Result<()> {
+	request_messages(request)?.add(Message::new(level, text.into()));
+	Ok(())
+}
+
+/// Change the minimum level of messages recorded for the current request
+///
+/// # Examples
+///
+/// ```
+/// use bytes::Bytes;
+/// use hyper::{HeaderMap, Method, Version};
+/// use reinhardt_http::Request;
+/// use reinhardt_middleware::messages::{
+///     MessageLevel, RequestMessages, add_message, get_level, get_messages, set_level,
+/// };
+///
+/// let request = Request::builder()
+///     .method(Method::GET)
+///     .uri("/")
+///     .version(Version::HTTP_11)
+///     .headers(HeaderMap::new())
+///     .body(Bytes::new())
+///     .build()
+///     .unwrap();
+/// request.extensions.insert(RequestMessages::default());
+///
+/// add_message(&request, MessageLevel::Debug, "Hidden").unwrap();
+/// set_level(&request, MessageLevel::Debug).unwrap();
+/// add_message(&request, MessageLevel::Debug, "Shown").unwrap();
+///
+/// assert_eq!(get_level(&request), Some(MessageLevel::Debug));
+/// let messages = get_messages(&request);
+/// assert_eq!(messages.len(), 1);
+/// assert_eq!(messages[0].text, "Shown");
+/// ```
+pub fn set_level(request: &Request, level: MessageLevel) -> Result<()> {
+	request_messages(request)?.set_level(level);
+	Ok(())
+}
+
+/// Minimum level of messages recorded for the current request
+///
+/// Returns `None` when [`MessageMiddleware`] is not installed.
+pub fn get_level(request: &Request) -> Option<MessageLevel> {
+	request
+		.extensions
+		.get::<RequestMessages>()
+		.map(|messages| messages.level())
+}
+
+fn request_messages(request: &Request) -> Result<RequestMessages> {
+	request.extensions.get::<RequestMessages>().ok_or_else(|| {
+		Error::ImproperlyConfigured(
+			"messages require MessageMiddleware to be installed".to_string(),
+		)
+	})
+}
+
+/// Read and consume the messages for the current request
+///
+/// Returns an empty list when [`MessageMiddleware`] is not installed.
+pub fn get_messages(request: &Request) -> Vec<Message> {
+	request
+		.extensions
+		.get::<RequestMessages>()
+		.map(|messages| messages.take())
+		.unwrap_or_default()
 }
 
 /// Message framework middleware
@@ -246,9 +852,11 @@ impl MessageStorage for CookieStorage {
 /// assert_eq!(messages[0].level, MessageLevel::Success);
 /// # });
 /// ```
-#[allow(dead_code)]
 pub struct MessageMiddleware {
 	storage: Arc<dyn MessageStorage>,
+	level: MessageLevel,
+	levels: MessageLevels,
+	broadcaster: Option<MessageBroadcaster>,
 }
 
 impl MessageMiddleware {
@@ -264,49 +872,89 @@ impl MessageMiddleware {
 	/// let middleware = MessageMiddleware::new(storage);
 	/// ```
 	pub fn new(storage: Arc<dyn MessageStorage>) -> Self {
-		Self { storage }
+		Self {
+			storage,
+			level: MessageLevel::default(),
+			levels: MessageLevels::new(),
+			broadcaster: None,
+		}
+	}
+
+	/// Set the default minimum level for each request (Django's `MESSAGE_LEVEL`)
+	///
+	/// Defaults to [`MessageLevel::Info`].
+	pub fn with_level(mut self, level: MessageLevel) -> Self {
+		self.level = level;
+		self
+	}
+
+	/// Set the level tags exposed to handlers through the request extensions
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::sync::Arc;
+	/// use reinhardt_middleware::messages::{MessageLevels, MessageMiddleware, SessionStorage};
+	///
+	/// let middleware = MessageMiddleware::new(Arc::new(SessionStorage::new()))
+	///     .with_levels(MessageLevels::new().with_level(35, "critical"));
+	/// ```
+	pub fn with_levels(mut self, levels: MessageLevels) -> Self {
+		self.levels = levels;
+		self
+	}
+
+	/// Notify live subscribers after storing unread messages
+	///
+	/// The broadcaster should read from the same storage as the middleware.
+	/// See [`crate::messages_api`].
+	pub fn with_broadcaster(mut self, broadcaster: MessageBroadcaster) -> Self {
+		self.broadcaster = Some(broadcaster);
+		self
 	}
 
 	/// Extract session ID from request
 	///
 	/// This method first checks for `SessionData` in request extensions
 	/// (set by `SessionMiddleware`), then falls back to cookie extraction.
-	fn get_session_id(request: &Request) -> String {
+	/// Returns `None` when the request has no session.
+	pub(crate) fn session_id(request: &Request) -> Option<String> {
 		// Check for SessionData set by SessionMiddleware
 		if let Some(session_data) = request.extensions.get::<SessionData>() {
-			return session_data.id.clone();
+			return Some(session_data.id.clone());
 		}
 
 		// Fallback: extract from cookie
-		request
-			.headers
-			.get(COOKIE)
-			.and_then(|c| c.to_str().ok())
-			.and_then(|cookies| {
-				for cookie in cookies.split(';') {
-					let cookie = cookie.trim();
-					if let Some((name, value)) = cookie.split_once('=')
-						&& name == "sessionid"
-					{
-						return Some(value.to_string());
-					}
-				}
-				None
-			})
-			.unwrap_or_else(|| "default".to_string())
+		cookie_value(request, "sessionid").filter(|id| !id.is_empty())
 	}
 }
 
 #[async_trait]
 impl Middleware for MessageMiddleware {
 	async fn process(&self, request: Request, handler: Arc<dyn Handler>) -> Result<Response> {
-		let _session_id = Self::get_session_id(&request);
-
-		// Process the request
-		let response = handler.handle(request).await?;
-
-		// Messages are stored in the storage and can be retrieved by handlers
-		// In a complete implementation, we'd add messages to the response or template context
+		let session_id = Self::session_id(&request);
+		let session_id = session_id.as_deref();
+
+		// Load pending messages and expose them to handlers; they stay in
+		// storage until the handler succeeded
+		let loaded = self.storage.load(session_id, &request);
+		let loaded_count = loaded.len();
+		let messages = RequestMessages::new(loaded).with_level(self.level);
+		request.extensions.insert(messages.clone());
+		request.extensions.insert(self.levels.clone());
+
+		let mut response = handler.handle(request).await?;
+
+		// Persist whatever the handler did not read
+		let unread = messages.take();
+		let has_unread = !unread.is_empty();
+		self.storage
+			.store(session_id, loaded_count, unread, &mut response)?;
+		if let (Some(broadcaster), Some(session_id)) = (&self.broadcaster, session_id)
+			&& has_unread
+		{
+			broadcaster.notify(session_id);
+		}
 		Ok(response)
 	}
 }
@@ -420,7 +1068,7 @@ mod tests {
 	#[async_trait]
 	impl Handler for TestHandler {
 		async fn handle(&self, request: Request) -> Result<Response> {
-			let session_id = MessageMiddleware::get_session_id(&request);
+			let session_id = MessageMiddleware::session_id(&request).unwrap();
 			self.storage
 				.add_message(&session_id, Message::success("Test message".to_string()));
 			Ok(Response::new(StatusCode::OK).with_body(Bytes::from("OK")))
@@ -457,19 +1105,43 @@ mod tests {
 	}
 
 	#[tokio::test]
-	async fn test_middleware_default_session() {
+	async fn test_middleware_without_session_does_not_persist() {
 		let storage: Arc<dyn MessageStorage> = Arc::new(SessionStorage::new());
+		storage.add_message("default", Message::info("Someone else's".to_string()));
+		let middleware = MessageMiddleware::new(storage.clone());
+
+		// No session cookie
+		let response = middleware
+			.process(request_with_cookie(None), Arc::new(AddMessageHandler))
+			.await
+			.unwrap();
+		assert_eq!(response.status, StatusCode::OK);
+
+		let response = middleware
+			.process(request_with_cookie(None), Arc::new(ReadMessagesHandler))
+			.await
+			.unwrap();
+		assert_eq!(response.body, Bytes::from(""));
+		assert_eq!(storage.get_messages("default").len(), 1);
+	}
+
+	#[tokio::test]
+	async fn test_middleware_with_cookie_storage() {
+		let storage: Arc<dyn MessageStorage> =
+			Arc::new(CookieStorage::new().with_secret_key("test-secret"));
 		let middleware = MessageMiddleware::new(storage.clone());
 		let handler = Arc::new(TestHandler {
 			storage: storage.clone(),
 		});
 
-		// No session cookie
+		let mut headers = HeaderMap::new();
+		headers.insert(COOKIE, "sessionid=cookie-session".parse().unwrap());
+
 		let request = Request::builder()
 			.method(Method::GET)
 			.uri("/page")
 			.version(Version::HTTP_11)
-			.headers(HeaderMap::new())
+			.headers(headers)
 			.body(Bytes::new())
 			.build()
 			.unwrap();
@@ -477,37 +1149,326 @@ mod tests {
 		let response = middleware.process(request, handler).await.unwrap();
 		assert_eq!(response.status, StatusCode::OK);
 
-		// Should use default session
-		let messages = storage.get_messages("default");
-		assert_eq!(messages.len(), 1);
+		// The message was moved into the cookie
+		assert!(storage.get_messages("cookie-session").is_empty());
+		let set_cookie = response.headers.get(SET_COOKIE).unwrap().to_str().unwrap();
+		let cookie = set_cookie.split(';').next().unwrap();
+		let response = middleware
+			.process(
+				request_with_cookie(Some(cookie)),
+				Arc::new(ReadMessagesHandler),
+			)
+			.await
+			.unwrap();
+		assert_eq!(response.body, Bytes::from("Test message"));
 	}
 
 	#[tokio::test]
-	async fn test_middleware_with_cookie_storage() {
-		let storage: Arc<dyn MessageStorage> = Arc::new(CookieStorage::new());
+	async fn test_cookie_storage_without_secret_key_fails_to_store() {
+		let middleware = MessageMiddleware::new(Arc::new(CookieStorage::new()));
+
+		let result = middleware
+			.process(request_with_cookie(None), Arc::new(AddMessageHandler))
+			.await;
+
+		assert!(matches!(result, Err(Error::ImproperlyConfigured(_))));
+	}
+
+	#[test]
+	fn test_cookie_storage_from_settings_uses_secret_key() {
+		let settings = Settings::new(std::env::temp_dir(), "settings-secret".to_string());
+		let storage = CookieStorage::from_settings(&settings);
+		let value = storage
+			.encode(&[Message::info("Signed".to_string())])
+			.unwrap();
+
+		let same_key = CookieStorage::new().with_secret_key("settings-secret");
+		assert_eq!(same_key.decode(&value)[0].text, "Signed");
+		let other_key = CookieStorage::new().with_secret_key("other-secret");
+		assert!(other_key.decode(&value).is_empty());
+	}
+
+	struct FailingHandler;
+
+	#[async_trait]
+	impl Handler for FailingHandler {
+		async fn handle(&self, request: Request) -> Result<Response> {
+			assert_eq!(get_messages(&request).len(), 1);
+			Err(Error::Internal("boom".to_string()))
+		}
+	}
+
+	#[tokio::test]
+	async fn test_messages_survive_failed_request() {
+		let storage: Arc<dyn MessageStorage> = Arc::new(SessionStorage::new());
+		storage.add_message("abc", Message::info("Welcome back".to_string()));
 		let middleware = MessageMiddleware::new(storage.clone());
-		let handler = Arc::new(TestHandler {
-			storage: storage.clone(),
-		});
 
-		let mut headers = HeaderMap::new();
-		headers.insert(COOKIE, "sessionid=cookie-session".parse().unwrap());
+		let result = middleware
+			.process(
+				request_with_cookie(Some("sessionid=abc")),
+				Arc::new(FailingHandler),
+			)
+			.await;
 
-		let request = Request::builder()
+		assert!(result.is_err());
+		assert_eq!(storage.get_messages("abc")[0].text, "Welcome back");
+	}
+
+	struct AddMessageHandler;
+
+	#[async_trait]
+	impl Handler for AddMessageHandler {
+		async fn handle(&self, request: Request) -> Result<Response> {
+			add_message(&request, MessageLevel::Success, "Article saved")?;
+			Ok(Response::new(StatusCode::OK))
+		}
+	}
+
+	struct ReadMessagesHandler;
+
+	#[async_trait]
+	impl Handler for ReadMessagesHandler {
+		async fn handle(&self, request: Request) -> Result<Response> {
+			let texts: Vec<String> = get_messages(&request)
+				.into_iter()
+				.map(|message| message.text)
+				.collect();
+			Ok(Response::new(StatusCode::OK).with_body(Bytes::from(texts.join(","))))
+		}
+	}
+
+	fn request_with_cookie(cookie: Option<&str>) -> Request {
+		let mut headers = HeaderMap::new();
+		if let Some(cookie) = cookie {
+			headers.insert(COOKIE, cookie.parse().unwrap());
+		}
+		Request::builder()
 			.method(Method::GET)
 			.uri("/page")
 			.version(Version::HTTP_11)
 			.headers(headers)
 			.body(Bytes::new())
 			.build()
+			.unwrap()
+	}
+
+	#[tokio::test]
+	async fn test_cookie_storage_round_trip() {
+		let middleware = MessageMiddleware::new(Arc::new(
+			CookieStorage::new().with_secret_key("test-secret"),
+		));
+
+		let response = middleware
+			.process(request_with_cookie(None), Arc::new(AddMessageHandler))
+			.await
 			.unwrap();
+		let set_cookie = response.headers.get(SET_COOKIE).unwrap().to_str().unwrap();
+		let cookie = set_cookie.split(';').next().unwrap().to_string();
+		assert!(cookie.starts_with("messages="));
+
+		let response = middleware
+			.process(
+				request_with_cookie(Some(&cookie)),
+				Arc::new(ReadMessagesHandler),
+			)
+			.await
+			.unwrap();
+		assert_eq!(response.body, Bytes::from("Article saved"));
+		let cleared = response.headers.get(SET_COOKIE).unwrap().to_str().unwrap();
+		assert!(cleared.contains("Max-Age=0"));
+	}
 
-		let response = middleware.process(request, handler).await.unwrap();
-		assert_eq!(response.status, StatusCode::OK);
+	#[tokio::test]
+	async fn test_cookie_storage_rejects_tampered_cookie() {
+		let storage = CookieStorage::new().with_secret_key("test-secret");
+		let middleware = MessageMiddleware::new(Arc::new(storage));
+
+		// Unsigned JSON forged by the client
+		let forged: String =
+			url::form_urlencoded::byte_serialize(br#"[{"level":20,"text":"Forged"}]"#).collect();
+		let response = middleware
+			.process(
+				request_with_cookie(Some(&format!("messages={}", forged))),
+				Arc::new(ReadMessagesHandler),
+			)
+			.await
+			.unwrap();
+		assert_eq!(response.body, Bytes::from(""));
 
-		// Verify message was stored
-		let messages = storage.get_and_clear_messages("cookie-session");
-		assert_eq!(messages.len(), 1);
-		assert_eq!(messages[0].level, MessageLevel::Success);
+		// Signed by a different key
+		let other = CookieStorage::new().with_secret_key("other-secret");
+		let value = other
+			.encode(&[Message::info("Forged".to_string())])
+			.unwrap();
+		let response = middleware
+			.process(
+				request_with_cookie(Some(&format!("messages={}", value))),
+				Arc::new(ReadMessagesHandler),
+			)
+			.await
+			.unwrap();
+		assert_eq!(response.body, Bytes::from(""));
+
+		// Valid signature over a modified payload
+		let storage = CookieStorage::new().with_secret_key("test-secret");
+		let value = storage
+			.encode(&[Message::info("Genuine".to_string())])
+			.unwrap();
+		let tampered = value.replacen("Genuine", "Forged!", 1);
+		assert!(storage.decode(&tampered).is_empty());
+		assert_eq!(storage.decode(&value)[0].text, "Genuine");
+	}
+
+	#[tokio::test]
+	async fn test_unread_messages_persist_in_session_storage() {
+		let storage: Arc<dyn MessageStorage> = Arc::new(SessionStorage::new());
+		let middleware = MessageMiddleware::new(storage.clone());
+		storage.add_message("abc", Message::info("Welcome back".to_string()));
+
+		// The pending message is neither read nor lost
+		middleware
+			.process(
+				request_with_cookie(Some("sessionid=abc")),
+				Arc::new(AddMessageHandler),
+			)
+			.await
+			.unwrap();
+		let texts: Vec<String> = storage
+			.get_messages("abc")
+			.into_iter()
+			.map(|message| message.text)
+			.collect();
+		assert_eq!(texts, vec!["Welcome back", "Article saved"]);
+
+		let response = middleware
+			.process(
+				request_with_cookie(Some("sessionid=abc")),
+				Arc::new(ReadMessagesHandler),
+			)
+			.await
+			.unwrap();
+		assert_eq!(response.body, Bytes::from("Welcome back,Article saved"));
+		assert!(storage.get_messages("abc").is_empty());
+	}
+
+	#[test]
+	fn test_cookie_storage_drops_oldest_messages_when_too_large() {
+		let storage = CookieStorage::new()
+			.with_secret_key("test-secret")
+			.with_max_size(200);
+		let messages: Vec<Message> = (0..10)
+			.map(|i| Message::info(format!("Message number {}", i)))
+			.collect();
+		let mut response = Response::new(StatusCode::OK);
+
+		storage
+			.store(Some("s"), 0, messages, &mut response)
+			.unwrap();
+
+		let set_cookie = response.headers.get(SET_COOKIE).unwrap().to_str().unwrap();
+		let value = set_cookie
+			.split(';')
+			.next()
+			.unwrap()
+			.trim_start_matches("messages=");
+		let kept = storage.decode(value);
+		assert!(kept.len() < 10);
+		assert_eq!(kept.last().unwrap().text, "Message number 9");
+	}
+
+	#[test]
+	fn test_message_level_serializes_as_value() {
+		let message = Message::new(MessageLevel::Custom(35), "Disk almost full".to_string())
+			.with_tags(["sticky"]);
+
+		let json = serde_json::to_string(&message).unwrap();
+		assert_eq!(
+			json,
+			r#"{"level":35,"text":"Disk almost full","extra_tags":["sticky"]}"#
+		);
+
+		let storage = CookieStorage::new().with_secret_key("test-secret");
+		let decoded = storage.decode(&storage.encode(&[message]).unwrap());
+		assert_eq!(decoded[0].level, MessageLevel::Custom(35));
+		assert_eq!(decoded[0].extra_tags, vec!["sticky"]);
+
+		// Level names written by earlier versions still load
+		let legacy: Message = serde_json::from_str(r#"{"level":"Warning","text":"Old"}"#).unwrap();
+		assert_eq!(legacy.level, MessageLevel::Warning);
+		assert!(legacy.extra_tags.is_empty());
+	}
+
+	#[test]
+	fn test_cookie_storage_min_level() {
+		let storage = CookieStorage::new()
+			.with_secret_key("test-secret")
+			.with_min_level(MessageLevel::Warning);
+		let mut response = Response::new(StatusCode::OK);
+
+		storage
+			.store(
+				Some("s"),
+				0,
+				vec![
+					Message::info("Dropped".to_string()),
+					Message::error("Kept".to_string()),
+				],
+				&mut response,
+			)
+			.unwrap();
+
+		let set_cookie = response.headers.get(SET_COOKIE).unwrap().to_str().unwrap();
+		let value = set_cookie
+			.split(';')
+			.next()
+			.unwrap()
+			.trim_start_matches("messages=");
+		let kept = storage.decode(value);
+		assert_eq!(kept.len(), 1);
+		assert_eq!(kept[0].text, "Kept");
+	}
+
+	struct DebugMessageHandler;
+
+	#[async_trait]
+	impl Handler for DebugMessageHandler {
+		async fn handle(&self, request: Request) -> Result<Response> {
+			add_message(&request, MessageLevel::Debug, "Query took 3ms")?;
+			add_message(&request, MessageLevel::Custom(35), "Disk almost full")?;
+			let levels = request.extensions.get::<MessageLevels>().unwrap();
+			assert_eq!(levels.tag(MessageLevel::Custom(35)), Some("critical"));
+			Ok(Response::new(StatusCode::OK))
+		}
+	}
+
+	#[tokio::test]
+	async fn test_middleware_level_filters_messages() {
+		let storage: Arc<dyn MessageStorage> = Arc::new(SessionStorage::new());
+		let middleware = MessageMiddleware::new(storage.clone())
+			.with_levels(MessageLevels::new().with_level(35, "critical"));
+
+		middleware
+			.process(
+				request_with_cookie(Some("sessionid=abc")),
+				Arc::new(DebugMessageHandler),
+			)
+			.await
+			.unwrap();
+		let stored = storage.get_and_clear_messages("abc");
+		assert_eq!(stored.len(), 1);
+		assert_eq!(stored[0].level, MessageLevel::Custom(35));
+
+		let middleware = MessageMiddleware::new(storage.clone())
+			.with_level(MessageLevel::Debug)
+			.with_levels(MessageLevels::new().with_level(35, "critical"));
+		middleware
+			.process(
+				request_with_cookie(Some("sessionid=abc")),
+				Arc::new(DebugMessageHandler),
+			)
+			.await
+			.unwrap();
+		assert_eq!(storage.get_messages("abc").len(), 2);
 	}
 }
@@ -0,0 +1,453 @@
+//! JSON and Server-Sent Events access to flash messages
+//!
+//! Single-page frontends cannot rely on a full page render to show flash
+//! messages. This module offers two ways to deliver them:
+//!
+//! - [`MessagesApiHandler`] returns (and consumes) the pending messages of the
+//!   current request as JSON. Mount it behind `MessageMiddleware`.
+//! - [`MessageBroadcaster`] pushes messages to clients subscribed through an
+//!   SSE stream. When attached with `MessageMiddleware::with_broadcaster`,
+//!   messages left unread by a handler are delivered live to any open stream
+//!   of the same session instead of waiting for the next request. On the
+//!   client, `reinhardt_pages::reactive::hooks::use_flash_messages` consumes
+//!   the stream.
+//!
+//! # JSON Format
+//!
+//! ```json
+//! {"messages": [{"level": 25, "tag": "success", "text": "Saved", "tags": ["success"]}]}
+//! ```
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::stream::{self, StreamExt};
+use hyper::StatusCode;
+use hyper::header::{CACHE_CONTROL, CONTENT_TYPE, HeaderValue};
+use reinhardt_core::exception::Error;
+use reinhardt_http::{Handler, Request, Response, Result, StreamBody, StreamingResponse};
+use serde_json::{Value, json};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+use crate::messages::{Message, MessageLevels, MessageMiddleware, MessageStorage, get_messages};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Number of notifications buffered per session for slow subscribers
+const CHANNEL_CAPACITY: usize = 32;
+
+/// JSON representation of a message
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_middleware::messages::{Message, MessageLevels};
+/// use reinhardt_middleware::messages_api::message_to_json;
+///
+/// let message = Message::success("Saved".to_string());
+/// let json = message_to_json(&message, &MessageLevels::new());
+/// assert_eq!(json["level"], 25);
+/// assert_eq!(json["tag"], "success");
+/// assert_eq!(json["text"], "Saved");
+/// ```
+pub fn message_to_json(message: &Message, levels: &MessageLevels) -> Value {
+	json!({
+		"level": message.level.value(),
+		"tag": levels.tag(message.level),
+		"text": message.text,
+		"tags": message.tags(levels),
+	})
+}
+
+/// Consume the pending messages of a request and render them as a JSON response
+pub fn messages_response(request: &Request) -> Result<Response> {
+	let levels = request
+		.extensions
+		.get::<MessageLevels>()
+		.unwrap_or_default();
+	let messages: Vec<Value> = get_messages(request)
+		.iter()
+		.map(|message| message_to_json(message, &levels))
+		.collect();
+
+	Ok(Response::ok()
+		.with_json(&json!({ "messages": messages }))?
+		.with_header("Cache-Control", "no-store"))
+}
+
+/// Handler returning the pending messages as JSON
+///
+/// Reading the messages marks them as consumed, exactly like rendering them
+/// in a template would.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use bytes::Bytes;
+/// use hyper::{HeaderMap, Method, Version};
+/// use reinhardt_http::{Handler, Middleware, Request};
+/// use reinhardt_middleware::messages::{
+///     Message, MessageMiddleware, MessageStorage, SessionStorage,
+/// };
+/// use reinhardt_middleware::messages_api::MessagesApiHandler;
+///
+/// # tokio_test::block_on(async {
+/// let storage = Arc::new(SessionStorage::new());
+/// storage.add_message("abc", Message::success("Saved".to_string()));
+/// let middleware = MessageMiddleware::new(storage);
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert(hyper::header::COOKIE, "sessionid=abc".parse().unwrap());
+/// let request = Request::builder()
+///     .method(Method::GET)
+///     .uri("/api/messages/")
+///     .version(Version::HTTP_11)
+///     .headers(headers)
+///     .body(Bytes::new())
+///     .build()
+///     .unwrap();
+/// let response = middleware.process(request, Arc::new(MessagesApiHandler)).await.unwrap();
+///
+/// let body: serde_json::Value = serde_json::from_slice(&response.body).unwrap();
+/// assert_eq!(body["messages"][0]["text"], "Saved");
+/// # });
+/// ```
+pub struct MessagesApiHandler;
+
+#[async_trait]
+impl Handler for MessagesApiHandler {
+	async fn handle(&self, request: Request) -> Result<Response> {
+		messages_response(&request)
+	}
+}
+
+/// Delivers stored messages to live subscribers, keyed by session ID
+///
+/// The message storage stays the source of truth: `MessageMiddleware` stores
+/// unread messages as usual and then [`notify`](Self::notify)s the session.
+/// Each SSE stream reads the pending messages from the storage and removes a
+/// message only after its event has been handed to the connection, so a
+/// client that disconnects mid-way sees the rest on its next page load.
+///
+/// Notifications are in-process only. With a storage shared between
+/// processes, streams also re-check the storage every
+/// [`poll_interval`](Self::with_poll_interval), so messages added by another
+/// worker are delivered too, just with that extra latency. Live delivery
+/// requires a server-side storage such as [`SessionStorage`]; messages kept in
+/// a cookie are not visible to the stream.
+///
+/// [`SessionStorage`]: crate::messages::SessionStorage
+#[derive(Clone)]
+pub struct MessageBroadcaster {
+	storage: Arc<dyn MessageStorage>,
+	channels: Arc<RwLock<HashMap<String, broadcast::Sender<()>>>>,
+	levels: MessageLevels,
+	poll_interval: Duration,
+}
+
+impl MessageBroadcaster {
+	/// Default interval at which streams re-check the storage
+	pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+	/// Create a broadcaster delivering the messages of `storage`
+	///
+	/// Pass the same storage as the one given to `MessageMiddleware`.
+	pub fn new(storage: Arc<dyn MessageStorage>) -> Self {
+		Self {
+			storage,
+			channels: Arc::default(),
+			levels: MessageLevels::default(),
+			poll_interval: Self::DEFAULT_POLL_INTERVAL,
+		}
+	}
+
+	/// Set the level tags used when rendering events
+	pub fn with_levels(mut self, levels: MessageLevels) -> Self {
+		self.levels = levels;
+		self
+	}
+
+	/// Set how often streams re-check the storage without a notification
+	pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+		self.poll_interval = interval;
+		self
+	}
+
+	/// Subscribe to the notifications of a session
+	fn subscribe(&self, session_id: &str) -> broadcast::Receiver<()> {
+		let mut channels = self.channels.write().unwrap();
+		channels
+			.entry(session_id.to_string())
+			.or_insert_with(|| broadcast::channel(CHANNEL_CAPACITY).0)
+			.subscribe()
+	}
+
+	/// Wake the streams of a session so they deliver its pending messages
+	///
+	/// Returns `false` if no stream of the session is open in this process.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::sync::Arc;
+	/// use reinhardt_middleware::messages::SessionStorage;
+	/// use reinhardt_middleware::messages_api::MessageBroadcaster;
+	///
+	/// let broadcaster = MessageBroadcaster::new(Arc::new(SessionStorage::new()));
+	/// assert!(!broadcaster.notify("abc"));
+	///
+	/// let _stream = broadcaster.sse("abc");
+	/// assert!(broadcaster.notify("abc"));
+	/// ```
+	pub fn notify(&self, session_id: &str) -> bool {
+		let channels = self.channels.read().unwrap();
+		channels
+			.get(session_id)
+			.is_some_and(|sender| sender.send(()).is_ok())
+	}
+
+	/// Number of sessions with at least one open stream
+	pub fn session_count(&self) -> usize {
+		self.channels.read().unwrap().len()
+	}
+
+	/// Server-Sent Events stream of a session's messages
+	///
+	/// Each message becomes an event named `message` whose data is the JSON
+	/// produced by [`message_to_json`].
+	pub fn sse(&self, session_id: &str) -> StreamingResponse<StreamBody> {
+		let subscription = Subscription {
+			session_id: session_id.to_string(),
+			receiver: Some(self.subscribe(session_id)),
+			broadcaster: self.clone(),
+			pending: VecDeque::new(),
+			delivered: None,
+		};
+		let events = stream::unfold(subscription, |mut subscription| async move {
+			let event = subscription.next_event().await;
+			Some((Ok::<_, BoxError>(event), subscription))
+		});
+
+		StreamingResponse::with_status(events.boxed() as StreamBody, StatusCode::OK)
+			.header(CONTENT_TYPE, HeaderValue::from_static("text/event-stream"))
+			.header(CACHE_CONTROL, HeaderValue::from_static("no-cache"))
+	}
+
+	/// Server-Sent Events stream for the session of `request`
+	///
+	/// Fails with [`Error::Authentication`] when the request has no session,
+	/// since anonymous clients would otherwise share one stream.
+	pub fn sse_for_request(&self, request: &Request) -> Result<StreamingResponse<StreamBody>> {
+		let session_id = MessageMiddleware::session_id(request).ok_or_else(|| {
+			Error::Authentication("A session is required to subscribe to messages".to_string())
+		})?;
+		Ok(self.sse(&session_id))
+	}
+}
+
+/// State of one SSE stream
+struct Subscription {
+	session_id: String,
+	receiver: Option<broadcast::Receiver<()>>,
+	broadcaster: MessageBroadcaster,
+	/// Messages read from storage and not sent yet
+	pending: VecDeque<Message>,
+	/// Message whose event was handed out by the previous poll
+	delivered: Option<Message>,
+}
+
+impl Subscription {
+	/// Wait for the next message and render it as an event
+	async fn next_event(&mut self) -> Bytes {
+		// Being polled again means the previous event was consumed
+		if let Some(message) = self.delivered.take() {
+			self.broadcaster
+				.storage
+				.remove_messages(&self.session_id, std::slice::from_ref(&message));
+		}
+		loop {
+			if let Some(message) = self.pending.pop_front() {
+				let event = sse_event(&message_to_json(&message, &self.broadcaster.levels));
+				self.delivered = Some(message);
+				return event;
+			}
+			self.pending = self
+				.broadcaster
+				.storage
+				.get_messages(&self.session_id)
+				.into();
+			if !self.pending.is_empty() {
+				continue;
+			}
+			let receiver = self.receiver.as_mut().expect("receiver is set until drop");
+			// Lagged and closed channels just fall back to re-reading storage
+			let _ = tokio::time::timeout(self.broadcaster.poll_interval, receiver.recv()).await;
+		}
+	}
+}
+
+impl Drop for Subscription {
+	fn drop(&mut self) {
+		// Drop our receiver first so the count reflects the other streams
+		drop(self.receiver.take());
+		let mut channels = self.broadcaster.channels.write().unwrap();
+		if channels
+			.get(&self.session_id)
+			.is_some_and(|sender| sender.receiver_count() == 0)
+		{
+			channels.remove(&self.session_id);
+		}
+	}
+}
+
+/// Format a JSON payload as an SSE `message` event
+fn sse_event(data: &Value) -> Bytes {
+	Bytes::from(format!("event: message\ndata: {}\n\n", data))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::messages::{MessageLevel, MessageStorage, SessionStorage, add_message};
+	use hyper::header::COOKIE;
+	use hyper::{HeaderMap, Method, Version};
+	use reinhardt_http::Middleware;
+
+	struct AddMessageHandler;
+
+	#[async_trait]
+	impl Handler for AddMessageHandler {
+		async fn handle(&self, request: Request) -> Result<Response> {
+			add_message(&request, MessageLevel::Success, "Article saved")?;
+			Ok(Response::ok())
+		}
+	}
+
+	fn request() -> Request {
+		let mut headers = HeaderMap::new();
+		headers.insert(COOKIE, "sessionid=abc".parse().unwrap());
+		Request::builder()
+			.method(Method::POST)
+			.uri("/articles/")
+			.version(Version::HTTP_11)
+			.headers(headers)
+			.body(Bytes::new())
+			.build()
+			.unwrap()
+	}
+
+	fn read_event(event: Bytes) -> String {
+		String::from_utf8(event.to_vec()).unwrap()
+	}
+
+	#[tokio::test]
+	async fn test_sse_stream_receives_unread_messages() {
+		let storage = Arc::new(SessionStorage::new());
+		let broadcaster = MessageBroadcaster::new(storage.clone());
+		let middleware =
+			MessageMiddleware::new(storage.clone()).with_broadcaster(broadcaster.clone());
+		let response = broadcaster.sse("abc");
+		assert_eq!(
+			response.headers.get(CONTENT_TYPE).unwrap(),
+			"text/event-stream"
+		);
+		let mut events = response.into_stream();
+
+		middleware
+			.process(request(), Arc::new(AddMessageHandler))
+			.await
+			.unwrap();
+
+		let event = read_event(events.next().await.unwrap().unwrap());
+		assert!(event.starts_with("event: message\ndata: "));
+		assert!(event.contains(r#""text":"Article saved""#));
+		// Handed out but not yet consumed by the connection
+		assert_eq!(storage.get_messages("abc").len(), 1);
+
+		// Polling for the next event acknowledges the previous one
+		let next = tokio::time::timeout(Duration::from_millis(50), events.next()).await;
+		assert!(next.is_err());
+		assert!(storage.get_messages("abc").is_empty());
+	}
+
+	#[tokio::test]
+	async fn test_sse_stream_delivers_messages_stored_elsewhere() {
+		let storage = Arc::new(SessionStorage::new());
+		let broadcaster =
+			MessageBroadcaster::new(storage.clone()).with_poll_interval(Duration::from_millis(10));
+		let mut events = broadcaster.sse("abc").into_stream();
+
+		// Added without a notification, e.g. by another worker
+		storage.add_message("abc", Message::info("From elsewhere".to_string()));
+
+		let event = read_event(events.next().await.unwrap().unwrap());
+		assert!(event.contains(r#""text":"From elsewhere""#));
+	}
+
+	#[tokio::test]
+	async fn test_messages_stay_in_storage_without_subscribers() {
+		let storage = Arc::new(SessionStorage::new());
+		let middleware = MessageMiddleware::new(storage.clone())
+			.with_broadcaster(MessageBroadcaster::new(storage.clone()));
+
+		middleware
+			.process(request(), Arc::new(AddMessageHandler))
+			.await
+			.unwrap();
+
+		assert_eq!(storage.get_messages("abc").len(), 1);
+	}
+
+	#[test]
+	fn test_channel_is_removed_when_stream_is_dropped() {
+		let broadcaster = MessageBroadcaster::new(Arc::new(SessionStorage::new()));
+		let first = broadcaster.sse("abc");
+		let second = broadcaster.sse("abc");
+		assert_eq!(broadcaster.session_count(), 1);
+
+		drop(first);
+		assert_eq!(broadcaster.session_count(), 1);
+		drop(second);
+		assert_eq!(broadcaster.session_count(), 0);
+		assert!(!broadcaster.notify("abc"));
+	}
+
+	#[test]
+	fn test_sse_for_request_requires_session() {
+		let broadcaster = MessageBroadcaster::new(Arc::new(SessionStorage::new()));
+		let anonymous = Request::builder()
+			.method(Method::GET)
+			.uri("/messages/stream/")
+			.version(Version::HTTP_11)
+			.headers(HeaderMap::new())
+			.body(Bytes::new())
+			.build()
+			.unwrap();
+
+		assert!(matches!(
+			broadcaster.sse_for_request(&anonymous),
+			Err(Error::Authentication(_))
+		));
+		assert!(broadcaster.sse_for_request(&request()).is_ok());
+	}
+
+	#[tokio::test]
+	async fn test_messages_api_consumes_messages() {
+		let storage = Arc::new(SessionStorage::new());
+		storage.add_message("abc", Message::warning("Check settings".to_string()));
+		let middleware = MessageMiddleware::new(storage.clone());
+
+		let response = middleware
+			.process(request(), Arc::new(MessagesApiHandler))
+			.await
+			.unwrap();
+
+		let body: Value = serde_json::from_slice(&response.body).unwrap();
+		assert_eq!(body["messages"][0]["tag"], "warning");
+		assert_eq!(body["messages"][0]["level"], 30);
+		assert!(storage.get_messages("abc").is_empty());
+	}
+}
@@ -0,0 +1,321 @@
+//! Template context for flash messages
+//!
+//! [`context_processor`] is the counterpart of Django's
+//! `django.contrib.messages.context_processors.messages`. It consumes the
+//! pending messages of the request and returns the variables templates expect:
+//!
+//! - `messages`: list of [`MessageContext`], wrapped in the core
+//!   [`MessagesContext`]
+//! - `DEFAULT_MESSAGE_LEVELS`: built-in level names mapped to their values
+//!
+//! The returned map can be passed to `tera::Context::from_serialize`, and the
+//! browsable API renders it when registered with
+//! `BrowsableApiMiddleware::with_context_processor`.
+//!
+//! # Template Usage
+//!
+//! ```text
+//! {% for message in messages %}
+//!   <li class="{{ message.tags }}">{{ message.html | safe }}</li>
+//! {% endfor %}
+//! ```
+//!
+//! `html` is already escaped unless the message was created with
+//! [`Message::mark_safe`], so it must be rendered with the `safe` filter to
+//! avoid double escaping. `text` is the raw message text.
+
+use reinhardt_core::messages::MessagesContext;
+use reinhardt_core::security::escape_html;
+use reinhardt_http::Request;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::messages::{Message, MessageLevel, MessageLevels, get_messages};
+
+/// Template variable holding the messages
+pub const MESSAGES_KEY: &str = "messages";
+
+/// Template variable holding the built-in level values
+pub const DEFAULT_MESSAGE_LEVELS_KEY: &str = "DEFAULT_MESSAGE_LEVELS";
+
+/// A message as seen by templates
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_middleware::messages::{Message, MessageLevels};
+/// use reinhardt_middleware::messages_context::MessageContext;
+///
+/// let message = Message::error("<b>Failed</b>".to_string()).with_tags(["sticky"]);
+/// let context = MessageContext::new(&message, &MessageLevels::new());
+/// assert_eq!(context.level, 40);
+/// assert_eq!(context.level_tag, "error");
+/// assert_eq!(context.tags, "sticky error");
+/// assert_eq!(context.html, "&lt;b&gt;Failed&lt;/b&gt;");
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MessageContext {
+	/// Numeric level
+	pub level: i32,
+	/// Tag of the level, empty when none is registered
+	pub level_tag: String,
+	/// Extra tags followed by the level tag, separated by spaces
+	pub tags: String,
+	/// Extra tags separated by spaces
+	pub extra_tags: String,
+	/// Raw message text
+	pub text: String,
+	/// Whether the text is trusted HTML
+	pub safe: bool,
+	/// Text ready to be inserted into HTML
+	pub html: String,
+}
+
+impl MessageContext {
+	/// Build the template representation of a message
+	pub fn new(message: &Message, levels: &MessageLevels) -> Self {
+		let html = if message.safe {
+			message.text.clone()
+		} else {
+			escape_html(&message.text)
+		};
+
+		Self {
+			level: message.level.value(),
+			level_tag: levels.tag(message.level).unwrap_or_default().to_string(),
+			tags: message.tags(levels).join(" "),
+			extra_tags: message.extra_tags.join(" "),
+			text: message.text.clone(),
+			safe: message.safe,
+			html,
+		}
+	}
+}
+
+/// Built-in level names mapped to their values, like Django's `DEFAULT_MESSAGE_LEVELS`
+pub fn default_message_levels() -> BTreeMap<&'static str, i32> {
+	[
+		("DEBUG", MessageLevel::Debug),
+		("INFO", MessageLevel::Info),
+		("SUCCESS", MessageLevel::Success),
+		("WARNING", MessageLevel::Warning),
+		("ERROR", MessageLevel::Error),
+	]
+	.into_iter()
+	.map(|(name, level)| (name, level.value()))
+	.collect()
+}
+
+/// Template variables for the pending messages of a request
+///
+/// Reading the messages marks them as consumed. When `MessageMiddleware` is
+/// not installed, `messages` is an empty list.
+///
+/// # Examples
+///
+/// ```
+/// use bytes::Bytes;
+/// use hyper::{HeaderMap, Method, Version};
+/// use reinhardt_http::Request;
+/// use reinhardt_middleware::messages::{MessageLevel, RequestMessages, add_message};
+/// use reinhardt_middleware::messages_context::context_processor;
+///
+/// let request = Request::builder()
+///     .method(Method::GET)
+///     .uri("/")
+///     .version(Version::HTTP_11)
+///     .headers(HeaderMap::new())
+///     .body(Bytes::new())
+///     .build()
+///     .unwrap();
+/// request.extensions.insert(RequestMessages::default());
+/// add_message(&request, MessageLevel::Success, "Saved").unwrap();
+///
+/// let context = context_processor(&request);
+/// assert_eq!(context["messages"][0]["level_tag"], "success");
+/// assert_eq!(context["messages"][0]["text"], "Saved");
+/// assert_eq!(context["DEFAULT_MESSAGE_LEVELS"]["SUCCESS"], 25);
+/// ```
+pub fn context_processor(request: &Request) -> HashMap<String, Value> {
+	let levels = request
+		.extensions
+		.get::<MessageLevels>()
+		.unwrap_or_default();
+	let context = MessagesContext::new(
+		get_messages(request)
+			.iter()
+			.map(|message| MessageContext::new(message, &levels))
+			.collect(),
+	);
+
+	HashMap::from([
+		(
+			MESSAGES_KEY.to_string(),
+			serde_json::to_value(context.messages).unwrap_or_default(),
+		),
+		(
+			DEFAULT_MESSAGE_LEVELS_KEY.to_string(),
+			serde_json::to_value(default_message_levels()).unwrap_or_default(),
+		),
+	])
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::messages::{CookieStorage, MessageMiddleware, MessageStorage, SessionStorage};
+	use async_trait::async_trait;
+	use bytes::Bytes;
+	use hyper::header::COOKIE;
+	use hyper::{HeaderMap, Method, Version};
+	use reinhardt_core::security::csrf::generate_token_hmac;
+	use reinhardt_http::{Handler, Middleware, Response, Result};
+	use std::sync::Arc;
+
+	struct RenderHandler;
+
+	#[async_trait]
+	impl Handler for RenderHandler {
+		async fn handle(&self, request: Request) -> Result<Response> {
+			let context = context_processor(&request);
+			Response::ok().with_json(&context)
+		}
+	}
+
+	fn request() -> Request {
+		Request::builder()
+			.method(Method::GET)
+			.uri("/")
+			.version(Version::HTTP_11)
+			.headers(HeaderMap::new())
+			.body(Bytes::new())
+			.build()
+			.unwrap()
+	}
+
+	#[test]
+	fn test_safe_message_is_not_escaped() {
+		let message = Message::info("<a href=\"/undo/\">Undo</a>".to_string()).mark_safe();
+		let context = MessageContext::new(&message, &MessageLevels::new());
+
+		assert!(context.safe);
+		assert_eq!(context.html, "<a href=\"/undo/\">Undo</a>");
+	}
+
+	#[test]
+	fn test_custom_level_uses_registered_tag() {
+		let levels = MessageLevels::new().with_level(35, "critical");
+		let message = Message::new(MessageLevel::Custom(35), "Disk full".to_string());
+		let context = MessageContext::new(&message, &levels);
+
+		assert_eq!(context.level_tag, "critical");
+		assert_eq!(context.tags, "critical");
+		assert_eq!(context.extra_tags, "");
+	}
+
+	#[test]
+	fn test_context_without_middleware_is_empty() {
+		let context = context_processor(&request());
+
+		assert_eq!(context[MESSAGES_KEY], Value::Array(Vec::new()));
+		assert_eq!(context[DEFAULT_MESSAGE_LEVELS_KEY]["ERROR"], 40);
+	}
+
+	#[tokio::test]
+	async fn test_rendered_messages_are_consumed() {
+		let storage = Arc::new(SessionStorage::new());
+		storage.add_message("abc", Message::warning("Check <settings>".to_string()));
+		let middleware = MessageMiddleware::new(storage.clone())
+			.with_levels(MessageLevels::new().with_level(30, "alert-warning"));
+		let mut request = request();
+		request
+			.headers
+			.insert(COOKIE, "sessionid=abc".parse().unwrap());
+
+		let response = middleware
+			.process(request, Arc::new(RenderHandler))
+			.await
+			.unwrap();
+
+		let body: Value = serde_json::from_slice(&response.body).unwrap();
+		assert_eq!(body["messages"][0]["tags"], "alert-warning");
+		assert_eq!(body["messages"][0]["html"], "Check &lt;settings&gt;");
+		assert!(storage.get_messages("abc").is_empty());
+	}
+
+	#[tokio::test]
+	async fn test_safe_message_survives_redirect() {
+		let storage = CookieStorage::new().with_secret_key("test-secret");
+		let middleware = MessageMiddleware::new(Arc::new(storage));
+
+		struct AddHandler;
+
+		#[async_trait]
+		impl Handler for AddHandler {
+			async fn handle(&self, request: Request) -> Result<Response> {
+				let message =
+					Message::success("<a href=\"/undo/\">Undo</a>".to_string()).mark_safe();
+				request
+					.extensions
+					.get::<crate::messages::RequestMessages>()
+					.unwrap()
+					.add(message);
+				Ok(Response::new(hyper::StatusCode::FOUND))
+			}
+		}
+
+		// POST adds the message, the redirect target renders it
+		let response = middleware
+			.process(request(), Arc::new(AddHandler))
+			.await
+			.unwrap();
+		let cookie = response.headers.get(hyper::header::SET_COOKIE).unwrap();
+		let cookie = cookie
+			.to_str()
+			.unwrap()
+			.split(';')
+			.next()
+			.unwrap()
+			.to_string();
+		let mut request = request();
+		request.headers.insert(COOKIE, cookie.parse().unwrap());
+
+		let response = middleware
+			.process(request, Arc::new(RenderHandler))
+			.await
+			.unwrap();
+
+		let body: Value = serde_json::from_slice(&response.body).unwrap();
+		assert_eq!(body["messages"][0]["safe"], true);
+		assert_eq!(body["messages"][0]["html"], "<a href=\"/undo/\">Undo</a>");
+	}
+
+	#[tokio::test]
+	async fn test_forged_cookie_cannot_mark_message_safe() {
+		let middleware = MessageMiddleware::new(Arc::new(
+			CookieStorage::new().with_secret_key("test-secret"),
+		));
+		// A cookie signed with another key is discarded
+		let payload: String = url::form_urlencoded::byte_serialize(
+			br#"[{"level":20,"text":"<script>alert(1)</script>","safe":true}]"#,
+		)
+		.collect();
+		let signature = generate_token_hmac(b"forged-secret", &payload);
+		let mut request = request();
+		request.headers.insert(
+			COOKIE,
+			format!("messages={}:{}", payload, signature)
+				.parse()
+				.unwrap(),
+		);
+
+		let response = middleware
+			.process(request, Arc::new(RenderHandler))
+			.await
+			.unwrap();
+
+		let body: Value = serde_json::from_slice(&response.body).unwrap();
+		assert_eq!(body["messages"], Value::Array(Vec::new()));
+	}
+}
@@ -7,9 +7,25 @@ use async_trait::async_trait;
 use hyper::StatusCode;
 use reinhardt_http::{Handler, Middleware, Request, Response, Result};
 use std::collections::HashMap;
+use std::fmt;
 use std::sync::{Arc, RwLock};
 use std::time::Instant;
 
+/// Source of additional Prometheus text appended to the export
+type Collector = Box<dyn Fn() -> String + Send + Sync>;
+
+/// Registered collectors, listed by name in debug output
+#[derive(Default)]
+struct Collectors(Vec<(String, Collector)>);
+
+impl fmt::Debug for Collectors {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_list()
+			.entries(self.0.iter().map(|(name, _)| name))
+			.finish()
+	}
+}
+
 /// Metrics storage
 #[derive(Debug, Default)]
 pub struct MetricsStore {
@@ -21,6 +37,8 @@ pub struct MetricsStore {
 	status_codes: RwLock<HashMap<u16, u64>>,
 	/// Custom metrics
 	custom_metrics: RwLock<HashMap<String, f64>>,
+	/// External metric collectors
+	collectors: RwLock<Collectors>,
 }
 
 impl MetricsStore {
@@ -55,6 +73,29 @@ impl MetricsStore {
 		metrics.insert(name, value);
 	}
 
+	/// Register a collector whose Prometheus text is appended to the export
+	///
+	/// Use this to publish metrics gathered elsewhere, such as cache statistics,
+	/// on the same endpoint. Collectors are kept across [`reset`](Self::reset).
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_middleware::MetricsStore;
+	///
+	/// let store = MetricsStore::new();
+	/// store.register_collector("queue", || "queue_depth 3\n".to_string());
+	///
+	/// assert!(store.export_prometheus().contains("queue_depth 3"));
+	/// ```
+	pub fn register_collector<F>(&self, name: impl Into<String>, collector: F)
+	where
+		F: Fn() -> String + Send + Sync + 'static,
+	{
+		let mut collectors = self.collectors.write().unwrap();
+		collectors.0.push((name.into(), Box::new(collector)));
+	}
+
 	/// Get all metrics in Prometheus text format
 	pub fn export_prometheus(&self) -> String {
 		let mut output = String::new();
@@ -145,6 +186,13 @@ impl MetricsStore {
 			}
 		}
 
+		// External collectors
+		let collectors = self.collectors.read().unwrap();
+		for (_, collector) in collectors.0.iter() {
+			output.push('\n');
+			output.push_str(&collector());
+		}
+
 		output
 	}
 
@@ -735,6 +783,19 @@ mod tests {
 		assert!(output.contains("http_response_time_seconds"));
 	}
 
+	#[tokio::test]
+	async fn test_registered_collectors() {
+		let store = MetricsStore::new();
+		store.register_collector("cache", || {
+			"cache_hits_total{pattern=\"user\"} 3\n".to_string()
+		});
+
+		store.reset();
+		let output = store.export_prometheus();
+
+		assert!(output.contains("cache_hits_total{pattern=\"user\"} 3"));
+	}
+
 	#[tokio::test]
 	async fn test_custom_metrics() {
 		let store = MetricsStore::new();
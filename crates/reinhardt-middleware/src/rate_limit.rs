@@ -2,12 +2,21 @@
 //!
 //! Provides request rate limiting per route or per user.
 //! Uses the Token Bucket algorithm to restrict excessive requests.
+//!
+//! [`ThrottleMiddleware`] applies any [`Throttle`] from `reinhardt-throttling`
+//! instead, reporting its quota with `Retry-After` and the draft
+//! `RateLimit-Limit`, `RateLimit-Remaining` and `RateLimit-Reset` headers,
+//! and [`ConcurrencyLimitMiddleware`] caps simultaneous in-flight requests.
+//! A [`LoadTracker`] attached to [`ThrottleMiddleware`] measures handler
+//! latency and errors for an adaptive throttle.
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use hyper::StatusCode;
 use reinhardt_http::{Handler, Middleware, Request, Response, Result};
+use reinhardt_throttling::{ConcurrencyThrottle, LoadTracker, RateLimitInfo, Throttle};
 use std::collections::HashMap;
+use std::net::IpAddr;
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 
@@ -411,46 +420,8 @@ impl RateLimitMiddleware {
 	}
 
 	/// Extract client IP address from request
-	///
-	/// Extracts the client IP in the following order:
-	/// 1. X-Forwarded-For header (first IP in the list)
-	/// 2. X-Real-IP header
-	/// 3. remote_addr field from the request
-	/// 4. Falls back to 127.0.0.1 if none available
 	fn extract_client_ip(&self, request: &Request) -> String {
-		// 1. Check X-Forwarded-For header
-		if let Some(xff) = request.headers.get("X-Forwarded-For")
-			&& let Ok(xff_str) = xff.to_str()
-		{
-			// X-Forwarded-For can contain multiple IPs: "client, proxy1, proxy2"
-			// Take the first (leftmost) IP as the original client IP
-			if let Some(first_ip) = xff_str.split(',').next() {
-				let trimmed = first_ip.trim();
-				// Validate IP format
-				if trimmed.parse::<std::net::IpAddr>().is_ok() {
-					return trimmed.to_string();
-				}
-			}
-		}
-
-		// 2. Check X-Real-IP header
-		if let Some(xri) = request.headers.get("X-Real-IP")
-			&& let Ok(ip_str) = xri.to_str()
-		{
-			let trimmed = ip_str.trim();
-			// Validate IP format
-			if trimmed.parse::<std::net::IpAddr>().is_ok() {
-				return trimmed.to_string();
-			}
-		}
-
-		// 3. Check remote_addr field
-		if let Some(addr) = request.remote_addr {
-			return addr.ip().to_string();
-		}
-
-		// 4. Fallback to localhost
-		"127.0.0.1".to_string()
+		client_ip(request)
 	}
 
 	/// Create a rate limit error response
@@ -469,6 +440,49 @@ impl RateLimitMiddleware {
 	}
 }
 
+/// Extract client IP address from request
+///
+/// Extracts the client IP in the following order:
+/// 1. X-Forwarded-For header (first IP in the list)
+/// 2. X-Real-IP header
+/// 3. remote_addr field from the request
+/// 4. Falls back to 127.0.0.1 if none available
+fn client_ip(request: &Request) -> String {
+	// 1. Check X-Forwarded-For header
+	if let Some(xff) = request.headers.get("X-Forwarded-For")
+		&& let Ok(xff_str) = xff.to_str()
+	{
+		// X-Forwarded-For can contain multiple IPs: "client, proxy1, proxy2"
+		// Take the first (leftmost) IP as the original client IP
+		if let Some(first_ip) = xff_str.split(',').next() {
+			let trimmed = first_ip.trim();
+			// Validate IP format
+			if trimmed.parse::<std::net::IpAddr>().is_ok() {
+				return trimmed.to_string();
+			}
+		}
+	}
+
+	// 2. Check X-Real-IP header
+	if let Some(xri) = request.headers.get("X-Real-IP")
+		&& let Ok(ip_str) = xri.to_str()
+	{
+		let trimmed = ip_str.trim();
+		// Validate IP format
+		if trimmed.parse::<std::net::IpAddr>().is_ok() {
+			return trimmed.to_string();
+		}
+	}
+
+	// 3. Check remote_addr field
+	if let Some(addr) = request.remote_addr {
+		return addr.ip().to_string();
+	}
+
+	// 4. Fallback to localhost
+	"127.0.0.1".to_string()
+}
+
 impl Default for RateLimitMiddleware {
 	fn default() -> Self {
 		Self::with_defaults()
@@ -525,6 +539,347 @@ impl Middleware for RateLimitMiddleware {
 	}
 }
 
+/// Function deriving the throttle key of a request
+pub type ThrottleKeyFn = Arc<dyn Fn(&Request) -> String + Send + Sync>;
+
+/// Proxies whose `X-Forwarded-For` entries are trusted
+///
+/// Forwarding headers can be set by any client, so they are only read when
+/// the peer is a known proxy. The address list is then walked from the
+/// right, skipping trusted proxies, and the first other address is the
+/// client. Without trusted proxies the peer address is the client.
+///
+/// # Examples
+///
+/// ```
+/// use bytes::Bytes;
+/// use hyper::{HeaderMap, Method, Version};
+/// use reinhardt_http::Request;
+/// use reinhardt_middleware::rate_limit::TrustedProxies;
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert("X-Forwarded-For", "198.51.100.7, 203.0.113.9".parse().unwrap());
+/// let request = Request::builder()
+///     .method(Method::GET)
+///     .uri("/")
+///     .version(Version::HTTP_11)
+///     .headers(headers)
+///     .remote_addr("10.0.0.1:443".parse().unwrap())
+///     .body(Bytes::new())
+///     .build()
+///     .unwrap();
+///
+/// // The header is ignored unless the peer is a trusted proxy
+/// assert_eq!(
+///     TrustedProxies::default().client_ip(&request),
+///     Some("10.0.0.1".parse().unwrap())
+/// );
+/// // The rightmost untrusted entry is the client, not the spoofable leftmost one
+/// let proxies = TrustedProxies::new(["10.0.0.1".parse().unwrap()]);
+/// assert_eq!(proxies.client_ip(&request), Some("203.0.113.9".parse().unwrap()));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies {
+	proxies: Vec<IpAddr>,
+}
+
+impl TrustedProxies {
+	/// Trust forwarding headers set by the given proxy addresses
+	pub fn new(proxies: impl IntoIterator<Item = IpAddr>) -> Self {
+		Self {
+			proxies: proxies.into_iter().collect(),
+		}
+	}
+
+	/// Whether `ip` is a trusted proxy
+	pub fn is_trusted(&self, ip: IpAddr) -> bool {
+		self.proxies.contains(&ip)
+	}
+
+	/// Address of the client that sent the request
+	///
+	/// Returns `None` when the request has no peer address.
+	pub fn client_ip(&self, request: &Request) -> Option<IpAddr> {
+		let mut client = request.remote_addr?.ip();
+		if !self.is_trusted(client) {
+			return Some(client);
+		}
+		let forwarded: Vec<&str> = request
+			.headers
+			.get_all("X-Forwarded-For")
+			.iter()
+			.filter_map(|value| value.to_str().ok())
+			.flat_map(|value| value.split(','))
+			.collect();
+		for entry in forwarded.into_iter().rev() {
+			// Entries left of a malformed one cannot be attributed
+			let Ok(ip) = entry.trim().parse::<IpAddr>() else {
+				break;
+			};
+			client = ip;
+			if !self.is_trusted(ip) {
+				break;
+			}
+		}
+		Some(client)
+	}
+
+	/// Throttle key of a request: its client address
+	fn key(&self, request: &Request) -> String {
+		self.client_ip(request)
+			.map(|ip| ip.to_string())
+			.unwrap_or_else(|| "127.0.0.1".to_string())
+	}
+}
+
+/// Middleware applying a [`Throttle`] to each request
+///
+/// Throttled requests get a 429 response. Both throttled and successful
+/// responses carry the quota reported by [`Throttle::check`] as
+/// `RateLimit-Limit`, `RateLimit-Remaining` and `RateLimit-Reset` headers,
+/// plus `Retry-After` when throttled. Requests are keyed by the peer
+/// address, or the client address reported by [`TrustedProxies`], unless a
+/// key function is set.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use reinhardt_middleware::rate_limit::ThrottleMiddleware;
+/// use reinhardt_throttling::AnonRateThrottle;
+/// use reinhardt_http::{Handler, Middleware, Request, Response};
+/// use hyper::{StatusCode, Method, Version, HeaderMap};
+/// use bytes::Bytes;
+///
+/// struct TestHandler;
+///
+/// #[async_trait::async_trait]
+/// impl Handler for TestHandler {
+///     async fn handle(&self, _request: Request) -> reinhardt_core::exception::Result<Response> {
+///         Ok(Response::new(StatusCode::OK).with_body(Bytes::from("OK")))
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let middleware = ThrottleMiddleware::new(Arc::new(AnonRateThrottle::new(100, 60)));
+/// let handler = Arc::new(TestHandler);
+///
+/// let request = Request::builder()
+///     .method(Method::GET)
+///     .uri("/api/data")
+///     .version(Version::HTTP_11)
+///     .headers(HeaderMap::new())
+///     .body(Bytes::new())
+///     .build()
+///     .unwrap();
+///
+/// let response = middleware.process(request, handler).await.unwrap();
+/// assert_eq!(response.status, StatusCode::OK);
+/// assert_eq!(response.headers.get("ratelimit-remaining").unwrap(), "99");
+/// # });
+/// ```
+pub struct ThrottleMiddleware {
+	throttle: Arc<dyn Throttle>,
+	key_fn: ThrottleKeyFn,
+	error_message: Option<String>,
+	load_tracker: Option<Arc<LoadTracker>>,
+}
+
+impl ThrottleMiddleware {
+	/// Create a middleware applying `throttle` per peer address
+	pub fn new(throttle: Arc<dyn Throttle>) -> Self {
+		Self {
+			throttle,
+			key_fn: Arc::new(|request| TrustedProxies::default().key(request)),
+			error_message: None,
+			load_tracker: None,
+		}
+	}
+
+	/// Record the latency and outcome of every handled request
+	///
+	/// Requests failing or answered with a 5xx status count as errors.
+	/// Sharing the tracker with an `AdaptiveThrottle` lets the throttle
+	/// adapt its rate to the measured load.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::sync::Arc;
+	/// use std::time::Duration;
+	/// use reinhardt_middleware::rate_limit::ThrottleMiddleware;
+	/// use reinhardt_throttling::{AdaptiveConfig, AdaptiveThrottle, LoadTracker, MemoryBackend};
+	///
+	/// let tracker = Arc::new(LoadTracker::new(Duration::from_secs(30)));
+	/// let throttle = AdaptiveThrottle::new(Arc::new(MemoryBackend::new()), AdaptiveConfig::default())
+	///     .with_load_tracker(Arc::clone(&tracker));
+	/// let middleware = ThrottleMiddleware::new(Arc::new(throttle)).with_load_tracker(tracker);
+	/// ```
+	pub fn with_load_tracker(mut self, tracker: Arc<LoadTracker>) -> Self {
+		self.load_tracker = Some(tracker);
+		self
+	}
+
+	/// Key requests by the client address forwarded by trusted proxies
+	pub fn with_trusted_proxies(mut self, proxies: TrustedProxies) -> Self {
+		self.key_fn = Arc::new(move |request| proxies.key(request));
+		self
+	}
+
+	/// Set the function deriving the throttle key of a request
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use std::sync::Arc;
+	/// use reinhardt_middleware::rate_limit::ThrottleMiddleware;
+	/// use reinhardt_throttling::ScopedRateThrottle;
+	///
+	/// let throttle = ScopedRateThrottle::new().add_scope("api", 100, 60);
+	/// let middleware = ThrottleMiddleware::new(Arc::new(throttle))
+	///     .with_key_fn(|request| format!("api:{}", request.uri.path()));
+	/// ```
+	pub fn with_key_fn<F>(mut self, key_fn: F) -> Self
+	where
+		F: Fn(&Request) -> String + Send + Sync + 'static,
+	{
+		self.key_fn = Arc::new(key_fn);
+		self
+	}
+
+	/// Set the body of throttled responses
+	pub fn with_error_message(mut self, message: String) -> Self {
+		self.error_message = Some(message);
+		self
+	}
+
+	fn apply_headers(response: &mut Response, info: &RateLimitInfo) {
+		for (name, value) in info.headers() {
+			if let (Ok(name), Ok(value)) = (
+				hyper::header::HeaderName::from_bytes(name.as_bytes()),
+				hyper::header::HeaderValue::from_str(&value),
+			) {
+				response.headers.insert(name, value);
+			}
+		}
+	}
+}
+
+#[async_trait]
+impl Middleware for ThrottleMiddleware {
+	async fn process(&self, request: Request, handler: Arc<dyn Handler>) -> Result<Response> {
+		let key = (self.key_fn)(&request);
+		let info = self
+			.throttle
+			.check(&key)
+			.await
+			.map_err(|e| reinhardt_core::exception::Error::Internal(e.to_string()))?;
+
+		let mut response = if info.allowed {
+			let started = Instant::now();
+			let result = handler.handle(request).await;
+			if let Some(tracker) = &self.load_tracker {
+				let error = result
+					.as_ref()
+					.map_or(true, |response| response.status.is_server_error());
+				tracker.record(started.elapsed(), error);
+			}
+			result?
+		} else {
+			let message = self
+				.error_message
+				.clone()
+				.unwrap_or_else(|| "Rate limit exceeded".to_string());
+			Response::new(StatusCode::TOO_MANY_REQUESTS).with_body(message.into_bytes())
+		};
+		Self::apply_headers(&mut response, &info);
+		Ok(response)
+	}
+}
+
+/// Middleware limiting simultaneous in-flight requests
+///
+/// A slot of the [`ConcurrencyThrottle`] is held while the handler runs and
+/// released when it completes. Requests over the limit get a 429 response.
+/// Requests are keyed like [`ThrottleMiddleware`] unless a key function is
+/// set.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_middleware::rate_limit::ConcurrencyLimitMiddleware;
+/// use reinhardt_throttling::ConcurrencyThrottle;
+///
+/// let throttle = ConcurrencyThrottle::new(10).add_scope("reports", 1);
+/// let middleware = ConcurrencyLimitMiddleware::new(throttle).with_key_fn(|request| {
+///     let user = request.extensions.get::<String>().unwrap_or_default();
+///     if request.uri.path().starts_with("/reports/") {
+///         format!("reports:{}", user)
+///     } else {
+///         user
+///     }
+/// });
+/// ```
+pub struct ConcurrencyLimitMiddleware {
+	throttle: ConcurrencyThrottle,
+	key_fn: ThrottleKeyFn,
+	error_message: Option<String>,
+}
+
+impl ConcurrencyLimitMiddleware {
+	/// Create a middleware applying `throttle` per peer address
+	pub fn new(throttle: ConcurrencyThrottle) -> Self {
+		Self {
+			throttle,
+			key_fn: Arc::new(|request| TrustedProxies::default().key(request)),
+			error_message: None,
+		}
+	}
+
+	/// Key requests by the client address forwarded by trusted proxies
+	pub fn with_trusted_proxies(mut self, proxies: TrustedProxies) -> Self {
+		self.key_fn = Arc::new(move |request| proxies.key(request));
+		self
+	}
+
+	/// Set the function deriving the throttle key of a request
+	pub fn with_key_fn<F>(mut self, key_fn: F) -> Self
+	where
+		F: Fn(&Request) -> String + Send + Sync + 'static,
+	{
+		self.key_fn = Arc::new(key_fn);
+		self
+	}
+
+	/// Set the body of rejected responses
+	pub fn with_error_message(mut self, message: String) -> Self {
+		self.error_message = Some(message);
+		self
+	}
+
+	/// The throttle tracking in-flight requests
+	pub fn throttle(&self) -> &ConcurrencyThrottle {
+		&self.throttle
+	}
+}
+
+#[async_trait]
+impl Middleware for ConcurrencyLimitMiddleware {
+	async fn process(&self, request: Request, handler: Arc<dyn Handler>) -> Result<Response> {
+		let key = (self.key_fn)(&request);
+		let Some(_permit) = self.throttle.try_acquire(&key) else {
+			let message = self
+				.error_message
+				.clone()
+				.unwrap_or_else(|| "Too many concurrent requests".to_string());
+			return Ok(Response::new(StatusCode::TOO_MANY_REQUESTS)
+				.with_header("Retry-After", "1")
+				.with_body(message.into_bytes()));
+		};
+		handler.handle(request).await
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1050,4 +1405,254 @@ mod tests {
 		let response2 = middleware.process(request2, handler).await.unwrap();
 		assert_eq!(response2.status, StatusCode::OK);
 	}
+
+	#[tokio::test]
+	async fn test_throttle_middleware_reports_quota() {
+		let throttle = reinhardt_throttling::AnonRateThrottle::new(2, 60);
+		let middleware = ThrottleMiddleware::new(Arc::new(throttle));
+		let handler = Arc::new(TestHandler::new(StatusCode::OK));
+		let request = || {
+			Request::builder()
+				.method(Method::GET)
+				.uri("/test")
+				.version(Version::HTTP_11)
+				.headers(HeaderMap::new())
+				.body(Bytes::new())
+				.build()
+				.unwrap()
+		};
+
+		let response = middleware
+			.process(request(), handler.clone())
+			.await
+			.unwrap();
+		assert_eq!(response.status, StatusCode::OK);
+		assert_eq!(response.headers.get("ratelimit-limit").unwrap(), "2");
+		assert_eq!(response.headers.get("ratelimit-remaining").unwrap(), "1");
+		assert_eq!(response.headers.get("ratelimit-reset").unwrap(), "60");
+		assert!(!response.headers.contains_key("retry-after"));
+
+		middleware
+			.process(request(), handler.clone())
+			.await
+			.unwrap();
+		let response = middleware.process(request(), handler).await.unwrap();
+		assert_eq!(response.status, StatusCode::TOO_MANY_REQUESTS);
+		assert_eq!(response.headers.get("ratelimit-remaining").unwrap(), "0");
+		assert_eq!(response.headers.get("retry-after").unwrap(), "60");
+	}
+
+	#[tokio::test]
+	async fn test_throttle_middleware_records_load() {
+		let tracker = Arc::new(LoadTracker::new(Duration::from_secs(60)));
+		let middleware = ThrottleMiddleware::new(Arc::new(
+			reinhardt_throttling::AnonRateThrottle::new(10, 60),
+		))
+		.with_load_tracker(tracker.clone());
+		let request = || {
+			Request::builder()
+				.method(Method::GET)
+				.uri("/test")
+				.version(Version::HTTP_11)
+				.headers(HeaderMap::new())
+				.body(Bytes::new())
+				.build()
+				.unwrap()
+		};
+
+		middleware
+			.process(request(), Arc::new(TestHandler::new(StatusCode::OK)))
+			.await
+			.unwrap();
+		middleware
+			.process(
+				request(),
+				Arc::new(TestHandler::new(StatusCode::SERVICE_UNAVAILABLE)),
+			)
+			.await
+			.unwrap();
+
+		assert_eq!(tracker.metrics().error_rate, 0.5);
+	}
+
+	#[tokio::test]
+	async fn test_throttle_middleware_key_fn() {
+		let throttle = reinhardt_throttling::ScopedRateThrottle::new().add_scope("api", 1, 60);
+		let middleware = ThrottleMiddleware::new(Arc::new(throttle))
+			.with_key_fn(|request| format!("api:{}", request.uri.path()));
+		let handler = Arc::new(TestHandler::new(StatusCode::OK));
+		let request = |path: &str| {
+			Request::builder()
+				.method(Method::GET)
+				.uri(path)
+				.version(Version::HTTP_11)
+				.headers(HeaderMap::new())
+				.body(Bytes::new())
+				.build()
+				.unwrap()
+		};
+
+		let response = middleware
+			.process(request("/a"), handler.clone())
+			.await
+			.unwrap();
+		assert_eq!(response.status, StatusCode::OK);
+		let response = middleware
+			.process(request("/a"), handler.clone())
+			.await
+			.unwrap();
+		assert_eq!(response.status, StatusCode::TOO_MANY_REQUESTS);
+		let response = middleware.process(request("/b"), handler).await.unwrap();
+		assert_eq!(response.status, StatusCode::OK);
+	}
+
+	#[tokio::test]
+	async fn test_throttle_middleware_ignores_spoofed_forwarded_for() {
+		let throttle = reinhardt_throttling::AnonRateThrottle::new(1, 60);
+		let handler = Arc::new(TestHandler::new(StatusCode::OK));
+		let request = |peer: &str, forwarded: &str| {
+			let mut headers = HeaderMap::new();
+			headers.insert("X-Forwarded-For", forwarded.parse().unwrap());
+			Request::builder()
+				.method(Method::GET)
+				.uri("/")
+				.version(Version::HTTP_11)
+				.headers(headers)
+				.remote_addr(peer.parse().unwrap())
+				.body(Bytes::new())
+				.build()
+				.unwrap()
+		};
+
+		// Rotating the header does not give a direct client a new quota
+		let middleware = ThrottleMiddleware::new(Arc::new(throttle));
+		let response = middleware
+			.process(request("192.0.2.1:1000", "203.0.113.1"), handler.clone())
+			.await
+			.unwrap();
+		assert_eq!(response.status, StatusCode::OK);
+		let response = middleware
+			.process(request("192.0.2.1:1000", "203.0.113.2"), handler.clone())
+			.await
+			.unwrap();
+		assert_eq!(response.status, StatusCode::TOO_MANY_REQUESTS);
+
+		// Behind a trusted proxy, a spoofed leftmost entry is not the key
+		let middleware =
+			ThrottleMiddleware::new(Arc::new(reinhardt_throttling::AnonRateThrottle::new(1, 60)))
+				.with_trusted_proxies(TrustedProxies::new(["10.0.0.1".parse().unwrap()]));
+		let response = middleware
+			.process(
+				request("10.0.0.1:1000", "198.51.100.1, 203.0.113.1"),
+				handler.clone(),
+			)
+			.await
+			.unwrap();
+		assert_eq!(response.status, StatusCode::OK);
+		let response = middleware
+			.process(
+				request("10.0.0.1:1000", "198.51.100.2, 203.0.113.1"),
+				handler,
+			)
+			.await
+			.unwrap();
+		assert_eq!(response.status, StatusCode::TOO_MANY_REQUESTS);
+	}
+
+	#[tokio::test]
+	async fn test_throttle_middleware_applies_geo_rules_to_client_address() {
+		let mut config = reinhardt_throttling::GeoRateConfig::new(HashMap::new(), (100, 60));
+		config.deny_network("203.0.113.0/24".parse().unwrap());
+		let throttle = reinhardt_throttling::GeoRateThrottle::new_without_geoip(
+			Arc::new(reinhardt_throttling::MemoryBackend::new()),
+			config,
+		);
+		let middleware = ThrottleMiddleware::new(Arc::new(throttle))
+			.with_trusted_proxies(TrustedProxies::new(["10.0.0.1".parse().unwrap()]));
+		let handler = Arc::new(TestHandler::new(StatusCode::OK));
+		let request = |peer: &str, forwarded: &str| {
+			let mut headers = HeaderMap::new();
+			headers.insert("X-Forwarded-For", forwarded.parse().unwrap());
+			Request::builder()
+				.method(Method::GET)
+				.uri("/")
+				.version(Version::HTTP_11)
+				.headers(headers)
+				.remote_addr(peer.parse().unwrap())
+				.body(Bytes::new())
+				.build()
+				.unwrap()
+		};
+
+		// Forwarded by the trusted proxy from a denied network
+		let response = middleware
+			.process(request("10.0.0.1:1000", "203.0.113.5"), handler.clone())
+			.await
+			.unwrap();
+		assert_eq!(response.status, StatusCode::TOO_MANY_REQUESTS);
+
+		// A denied client cannot get through by claiming another address
+		let response = middleware
+			.process(request("203.0.113.5:1000", "198.51.100.1"), handler.clone())
+			.await
+			.unwrap();
+		assert_eq!(response.status, StatusCode::TOO_MANY_REQUESTS);
+
+		let response = middleware
+			.process(request("198.51.100.1:1000", "203.0.113.5"), handler)
+			.await
+			.unwrap();
+		assert_eq!(response.status, StatusCode::OK);
+	}
+
+	#[tokio::test]
+	async fn test_concurrency_limit_middleware() {
+		struct SlowHandler {
+			release: Arc<tokio::sync::Notify>,
+		}
+
+		#[async_trait]
+		impl Handler for SlowHandler {
+			async fn handle(&self, _request: Request) -> Result<Response> {
+				self.release.notified().await;
+				Ok(Response::new(StatusCode::OK))
+			}
+		}
+
+		let middleware = Arc::new(ConcurrencyLimitMiddleware::new(ConcurrencyThrottle::new(1)));
+		let release = Arc::new(tokio::sync::Notify::new());
+		let handler: Arc<dyn Handler> = Arc::new(SlowHandler {
+			release: release.clone(),
+		});
+		let request = || {
+			Request::builder()
+				.method(Method::GET)
+				.uri("/reports")
+				.version(Version::HTTP_11)
+				.headers(HeaderMap::new())
+				.body(Bytes::new())
+				.build()
+				.unwrap()
+		};
+
+		let in_flight = tokio::spawn({
+			let middleware = middleware.clone();
+			let handler = handler.clone();
+			let request = request();
+			async move { middleware.process(request, handler).await.unwrap() }
+		});
+		while middleware.throttle().in_flight("127.0.0.1") == 0 {
+			tokio::task::yield_now().await;
+		}
+
+		let rejected = middleware
+			.process(request(), handler.clone())
+			.await
+			.unwrap();
+		assert_eq!(rejected.status, StatusCode::TOO_MANY_REQUESTS);
+
+		release.notify_one();
+		assert_eq!(in_flight.await.unwrap().status, StatusCode::OK);
+		assert_eq!(middleware.throttle().in_flight("127.0.0.1"), 0);
+	}
 }
@@ -458,7 +458,7 @@ impl Middleware for SessionMiddleware {
 
 		// Add Set-Cookie header
 		let cookie = self.build_cookie_header(&session.id);
-		response.headers.insert(
+		response.headers.append(
 			hyper::header::SET_COOKIE,
 			hyper::header::HeaderValue::from_str(&cookie).map_err(|e| {
 				reinhardt_core::exception::Error::Internal(format!(
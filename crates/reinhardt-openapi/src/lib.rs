@@ -28,6 +28,9 @@
 //! }
 //! ```
 //!
+//! Use `OpenApiRouter::wrap_router` instead of `wrap` to generate paths
+//! from every route registered on a `ServerRouter` or `UnifiedRouter`.
+//!
 //! ## Separation Rationale
 //!
 //! This crate exists separately from `reinhardt-rest` to break a circular
@@ -48,4 +51,4 @@
 
 mod router_wrapper;
 
-pub use router_wrapper::OpenApiRouter;
+pub use router_wrapper::{OpenApiRouter, RouteSource};
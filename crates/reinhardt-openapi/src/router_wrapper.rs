@@ -27,12 +27,49 @@
 use async_trait::async_trait;
 use reinhardt_http::Handler;
 use reinhardt_http::{Request, Response, Result};
-use reinhardt_rest::openapi::endpoints::generate_openapi_schema;
-use reinhardt_rest::openapi::{RedocUI, SwaggerUI};
+use reinhardt_rest::openapi::endpoints::{
+	generate_openapi_schema, generate_openapi_schema_for_routes,
+};
+use reinhardt_rest::openapi::{OpenApiSchema, RedocUI, RouteEntry, SwaggerUI};
 use reinhardt_urls::prelude::Route;
-use reinhardt_urls::routers::Router;
+use reinhardt_urls::routers::{Router, ServerRouter, UnifiedRouter};
 use std::sync::Arc;
 
+/// Routers that can list their routes for OpenAPI path generation
+///
+/// Implemented for [`ServerRouter`], [`UnifiedRouter`] and `Arc`s of
+/// either, so [`OpenApiRouter::wrap_router`] accepts the globally
+/// registered router as well as one under construction.
+pub trait RouteSource {
+	/// Every route reachable through this router, with full paths
+	fn route_entries(&self) -> Vec<RouteEntry>;
+}
+
+impl RouteSource for ServerRouter {
+	fn route_entries(&self) -> Vec<RouteEntry> {
+		self.get_all_routes()
+			.into_iter()
+			.map(|(path, name, _namespace, methods)| RouteEntry {
+				path,
+				methods: methods.iter().map(|m| m.as_str().to_string()).collect(),
+				name,
+			})
+			.collect()
+	}
+}
+
+impl RouteSource for UnifiedRouter {
+	fn route_entries(&self) -> Vec<RouteEntry> {
+		self.server_ref().route_entries()
+	}
+}
+
+impl<T: RouteSource + ?Sized> RouteSource for Arc<T> {
+	fn route_entries(&self) -> Vec<RouteEntry> {
+		(**self).route_entries()
+	}
+}
+
 /// Router wrapper that adds OpenAPI documentation endpoints
 ///
 /// This wrapper intercepts requests to OpenAPI documentation paths and
@@ -69,7 +106,36 @@ impl<H> OpenApiRouter<H> {
 	/// ```
 	pub fn wrap(handler: H) -> Self {
 		// Generate OpenAPI schema from global registry
-		let schema = generate_openapi_schema();
+		Self::with_schema(handler, generate_openapi_schema())
+	}
+
+	/// Wrap a router, documenting every route it has registered
+	///
+	/// Unlike [`wrap`](Self::wrap), paths are generated from the router's
+	/// route table, so ViewSets, class-based views and plain function routes
+	/// appear in the spec without manual registration. Metadata from
+	/// `#[get]`, `#[post]`, etc. is merged in where it matches a route.
+	///
+	/// # Example
+	///
+	/// ```rust,ignore
+	/// use reinhardt_openapi::OpenApiRouter;
+	/// use reinhardt_urls::routers::UnifiedRouter;
+	///
+	/// let router = UnifiedRouter::new()
+	///     .server(|s| s.viewset("users", UserViewSet::new()));
+	/// let wrapped = OpenApiRouter::wrap_router(router);
+	/// ```
+	pub fn wrap_router(router: H) -> Self
+	where
+		H: RouteSource,
+	{
+		let schema = generate_openapi_schema_for_routes(&router.route_entries());
+		Self::with_schema(router, schema)
+	}
+
+	/// Pre-render the documentation endpoints for `schema`
+	fn with_schema(handler: H, schema: OpenApiSchema) -> Self {
 		let openapi_json =
 			serde_json::to_string_pretty(&schema).expect("Failed to serialize OpenAPI schema");
 
@@ -260,6 +326,29 @@ mod tests {
 		assert!(body_str.contains("redoc"));
 	}
 
+	#[rstest]
+	#[tokio::test]
+	async fn test_wrap_router_documents_routes() {
+		let router = ServerRouter::new()
+			.with_prefix("/api")
+			.function("/health/", hyper::Method::GET, |_req| async {
+				Ok(Response::ok())
+			})
+			.function("/items/{id}/", hyper::Method::DELETE, |_req| async {
+				Ok(Response::ok())
+			});
+		let wrapped = OpenApiRouter::wrap_router(router);
+
+		let request = Request::builder().uri("/api/openapi.json").build().unwrap();
+		let response = wrapped.handle(request).await.unwrap();
+
+		let spec: serde_json::Value = serde_json::from_slice(&response.body).unwrap();
+		assert!(spec["paths"]["/api/health/"]["get"].is_object());
+		let delete = &spec["paths"]["/api/items/{id}/"]["delete"];
+		assert_eq!(delete["parameters"][0]["name"], "id");
+		assert_eq!(delete["parameters"][0]["in"], "path");
+	}
+
 	#[rstest]
 	#[tokio::test]
 	async fn test_delegation_to_inner_handler() {
@@ -9,6 +9,7 @@
 //! - **Component trait**: Define reusable UI components
 //! - **Page enum**: Unified representation of DOM elements, text, and fragments
 //! - **Props system**: Type-safe component properties
+//! - **ErrorBoundary**: Fallback rendering for failing children
 //!
 //! ## Usage
 //!
@@ -28,12 +29,17 @@
 //! let html = page.render_to_string();
 //! ```
 
+mod error_boundary;
 mod into_page;
 mod props;
 pub(crate) mod reactive_if;
 mod r#trait;
 
 // Re-export Page types (originally from into_page, now from reinhardt-types via into_page)
+pub use error_boundary::{
+	CapturedError, ErrorBoundary, ErrorReporter, ErrorSource, clear_error_reporter,
+	set_error_reporter,
+};
 #[cfg(not(target_arch = "wasm32"))]
 pub use into_page::DummyEvent;
 pub use into_page::PageExt;
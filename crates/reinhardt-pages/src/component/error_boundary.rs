@@ -0,0 +1,316 @@
+//! Error boundaries for component rendering.
+//!
+//! An [`ErrorBoundary`] wraps part of the page. When a child fails to render
+//! (by panicking or returning an `Err`) or a server function called on its
+//! behalf fails, the boundary renders a fallback view instead and reports
+//! the error through its `on_error` hook and the global reporter installed
+//! with [`set_error_reporter`].
+//!
+//! Panics are caught with [`std::panic::catch_unwind`], so they are only
+//! recoverable when the target unwinds on panic. On `wasm32-unknown-unknown`
+//! the default is to abort; use `Result`-returning children there.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::rc::Rc;
+
+use super::into_page::Page;
+use crate::reactive::Signal;
+use crate::server_fn::ServerFnError;
+
+/// Where a captured error originated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorSource {
+	/// A child panicked while rendering.
+	Panic,
+	/// A child returned an `Err` while rendering.
+	Render,
+	/// A server function call failed.
+	ServerFn,
+}
+
+/// An error captured by an [`ErrorBoundary`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedError {
+	/// Where the error originated
+	pub source: ErrorSource,
+	/// Human-readable error message
+	pub message: String,
+}
+
+impl CapturedError {
+	/// Creates a new captured error.
+	pub fn new(source: ErrorSource, message: impl Into<String>) -> Self {
+		Self {
+			source,
+			message: message.into(),
+		}
+	}
+}
+
+impl fmt::Display for CapturedError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.message)
+	}
+}
+
+impl std::error::Error for CapturedError {}
+
+impl From<ServerFnError> for CapturedError {
+	fn from(error: ServerFnError) -> Self {
+		Self::new(ErrorSource::ServerFn, error.to_string())
+	}
+}
+
+/// Callback receiving errors captured by error boundaries.
+pub type ErrorReporter = Rc<dyn Fn(&CapturedError)>;
+
+type Fallback = Rc<dyn Fn(&CapturedError) -> Page>;
+
+thread_local! {
+	static GLOBAL_REPORTER: RefCell<Option<ErrorReporter>> = const { RefCell::new(None) };
+}
+
+/// Installs a reporter called for every error captured by any boundary.
+///
+/// Use this to forward errors to logging or monitoring. Replaces any
+/// previously installed reporter.
+pub fn set_error_reporter<F>(reporter: F)
+where
+	F: Fn(&CapturedError) + 'static,
+{
+	GLOBAL_REPORTER.with(|r| *r.borrow_mut() = Some(Rc::new(reporter)));
+}
+
+/// Removes the reporter installed with [`set_error_reporter`].
+pub fn clear_error_reporter() {
+	GLOBAL_REPORTER.with(|r| *r.borrow_mut() = None);
+}
+
+/// Catches errors from child rendering and renders a fallback view.
+///
+/// # Example
+///
+/// ```ignore
+/// use reinhardt_pages::component::{ErrorBoundary, Page};
+///
+/// let boundary = ErrorBoundary::new(|error| {
+///     Page::text(format!("Something went wrong: {}", error))
+/// })
+/// .on_error(|error| log!("render failed: {}", error));
+///
+/// // Panics and `Err` results from children render the fallback
+/// let page = boundary.try_render(|| load_profile().map(render_profile));
+///
+/// // Server function errors switch the boundary to the fallback
+/// spawn_local({
+///     let boundary = boundary.clone();
+///     async move {
+///         if let Some(user) = boundary.capture_server_fn(get_user(1).await) {
+///             set_user(user);
+///         }
+///     }
+/// });
+/// ```
+#[derive(Clone)]
+pub struct ErrorBoundary {
+	error: Signal<Option<CapturedError>>,
+	fallback: Fallback,
+	on_error: Option<ErrorReporter>,
+}
+
+impl fmt::Debug for ErrorBoundary {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ErrorBoundary")
+			.field("error", &self.error.get_untracked())
+			.field("fallback", &"<closure>")
+			.finish()
+	}
+}
+
+impl ErrorBoundary {
+	/// Creates a boundary rendering `fallback` when an error is captured.
+	pub fn new<F>(fallback: F) -> Self
+	where
+		F: Fn(&CapturedError) -> Page + 'static,
+	{
+		Self {
+			error: Signal::new(None),
+			fallback: Rc::new(fallback),
+			on_error: None,
+		}
+	}
+
+	/// Sets a hook called with each error captured by this boundary.
+	pub fn on_error<F>(mut self, hook: F) -> Self
+	where
+		F: Fn(&CapturedError) + 'static,
+	{
+		self.on_error = Some(Rc::new(hook));
+		self
+	}
+
+	/// Returns the signal holding the currently captured error.
+	pub fn error(&self) -> &Signal<Option<CapturedError>> {
+		&self.error
+	}
+
+	/// Returns `true` if the boundary is showing its fallback.
+	pub fn has_error(&self) -> bool {
+		self.error.get_untracked().is_some()
+	}
+
+	/// Clears the captured error so children render again.
+	pub fn reset(&self) {
+		self.error.set(None);
+	}
+
+	/// Records an error, notifies the reporters and switches to the fallback.
+	pub fn capture(&self, error: CapturedError) {
+		if let Some(hook) = &self.on_error {
+			hook(&error);
+		}
+		let reporter = GLOBAL_REPORTER.with(|r| r.borrow().clone());
+		if let Some(reporter) = reporter {
+			reporter(&error);
+		}
+		self.error.set(Some(error));
+	}
+
+	/// Returns the value of a server function result, capturing its error.
+	pub fn capture_server_fn<T>(&self, result: Result<T, ServerFnError>) -> Option<T> {
+		match result {
+			Ok(value) => Some(value),
+			Err(error) => {
+				self.capture(error.into());
+				None
+			}
+		}
+	}
+
+	/// Renders `child`, falling back if it panics or an error was captured.
+	pub fn render<F>(&self, child: F) -> Page
+	where
+		F: Fn() -> Page + 'static,
+	{
+		self.try_render(move || Ok::<_, std::convert::Infallible>(child()))
+	}
+
+	/// Renders a fallible `child`, falling back if it panics, returns `Err`,
+	/// or an error was captured.
+	pub fn try_render<F, E>(&self, child: F) -> Page
+	where
+		F: Fn() -> Result<Page, E> + 'static,
+		E: fmt::Display,
+	{
+		let boundary = self.clone();
+		Page::reactive(move || {
+			if let Some(error) = boundary.error.get() {
+				return (boundary.fallback)(&error);
+			}
+			let error = match catch_unwind(AssertUnwindSafe(&child)) {
+				Ok(Ok(page)) => return page,
+				Ok(Err(e)) => CapturedError::new(ErrorSource::Render, e.to_string()),
+				Err(payload) => CapturedError::new(ErrorSource::Panic, panic_message(&*payload)),
+			};
+			let page = (boundary.fallback)(&error);
+			boundary.capture(error);
+			page
+		})
+	}
+}
+
+/// Extracts the message from a panic payload.
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+	if let Some(message) = payload.downcast_ref::<&str>() {
+		(*message).to_string()
+	} else if let Some(message) = payload.downcast_ref::<String>() {
+		message.clone()
+	} else {
+		"component panicked".to_string()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serial_test::serial;
+	use std::cell::Cell;
+
+	fn fallback(error: &CapturedError) -> Page {
+		Page::text(format!("error: {}", error))
+	}
+
+	#[test]
+	#[serial]
+	fn test_error_boundary_renders_child() {
+		let boundary = ErrorBoundary::new(fallback);
+
+		let html = boundary.render(|| Page::text("ok")).render_to_string();
+
+		assert_eq!(html, "ok");
+		assert!(!boundary.has_error());
+	}
+
+	#[test]
+	#[serial]
+	fn test_error_boundary_catches_panic() {
+		let boundary = ErrorBoundary::new(fallback);
+
+		let html = boundary.render(|| panic!("boom")).render_to_string();
+
+		assert_eq!(html, "error: boom");
+		assert_eq!(
+			boundary.error().get_untracked(),
+			Some(CapturedError::new(ErrorSource::Panic, "boom"))
+		);
+	}
+
+	#[test]
+	#[serial]
+	fn test_error_boundary_catches_err_and_reports() {
+		let reported = Rc::new(Cell::new(0));
+		let globally_reported = Rc::new(Cell::new(0));
+		set_error_reporter({
+			let globally_reported = Rc::clone(&globally_reported);
+			move |_| globally_reported.set(globally_reported.get() + 1)
+		});
+		let boundary = ErrorBoundary::new(fallback).on_error({
+			let reported = Rc::clone(&reported);
+			move |error| {
+				assert_eq!(error.source, ErrorSource::Render);
+				reported.set(reported.get() + 1);
+			}
+		});
+
+		let html = boundary
+			.try_render(|| Err::<Page, _>("not found"))
+			.render_to_string();
+		clear_error_reporter();
+
+		assert_eq!(html, "error: not found");
+		assert_eq!(reported.get(), 1);
+		assert_eq!(globally_reported.get(), 1);
+	}
+
+	#[test]
+	#[serial]
+	fn test_error_boundary_captures_server_fn_error_until_reset() {
+		let boundary = ErrorBoundary::new(fallback);
+
+		let value = boundary.capture_server_fn::<u32>(Err(ServerFnError::network("offline")));
+		let html = boundary.render(|| Page::text("ok")).render_to_string();
+
+		assert_eq!(value, None);
+		assert_eq!(html, "error: Network error: offline");
+		assert_eq!(
+			boundary.error().get_untracked().map(|e| e.source),
+			Some(ErrorSource::ServerFn)
+		);
+
+		boundary.reset();
+		let html = boundary.render(|| Page::text("ok")).render_to_string();
+		assert_eq!(html, "ok");
+	}
+}
@@ -7,6 +7,7 @@
 //! ## Features
 //!
 //! - **State Restoration**: Restore reactive state from embedded SSR data
+//!   (see [`use_hydrated_state`](crate::reactive::hooks::use_hydrated_state))
 //! - **Event Attachment**: Connect event handlers to existing DOM elements
 //! - **DOM Reconciliation**: Verify SSR output matches expected component structure
 //! - **Incremental Hydration**: Support partial page hydration (islands architecture)
@@ -34,9 +35,10 @@ pub use events::{
 	AttachOptions, EventBinding, EventRegistry, attach_event, attach_events_recursive,
 };
 pub use reconcile::{ReconcileError, ReconcileOptions, reconcile, reconcile_with_options};
+pub(crate) use runtime::restored_signal;
 pub use runtime::{
 	HydrationContext, HydrationError, hydrate, hydrate_root, init_hydration_state,
-	is_hydration_complete, on_hydration_complete,
+	is_hydration_complete, on_hydration_complete, with_hydration_state,
 };
 
 #[cfg(target_arch = "wasm32")]
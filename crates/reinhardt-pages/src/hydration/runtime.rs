@@ -153,6 +153,32 @@ impl HydrationContext {
 	}
 }
 
+thread_local! {
+	static RESTORED_STATE: std::cell::RefCell<Option<SsrState>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Runs `f` with `state` available for restoring hydratable signals.
+///
+/// [`use_hydrated_state`](crate::reactive::hooks::use_hydrated_state) calls
+/// made while `f` runs start from the values serialized during SSR instead
+/// of their initial values. [`hydrate`] wraps the component render with it.
+pub fn with_hydration_state<R>(state: SsrState, f: impl FnOnce() -> R) -> R {
+	let previous = RESTORED_STATE.with(|restored| restored.borrow_mut().replace(state));
+	let result = f();
+	RESTORED_STATE.with(|restored| *restored.borrow_mut() = previous);
+	result
+}
+
+/// Gets a signal value restored from SSR state, if hydration is in progress.
+pub(crate) fn restored_signal(id: &str) -> Option<serde_json::Value> {
+	RESTORED_STATE.with(|restored| {
+		restored
+			.borrow()
+			.as_ref()
+			.and_then(|state| state.get_signal(id).cloned())
+	})
+}
+
 /// Hydrates a component into the specified root element.
 #[cfg(target_arch = "wasm32")]
 pub fn hydrate<C: Component>(component: &C, root: &Element) -> Result<(), HydrationError> {
@@ -164,8 +190,8 @@ pub fn hydrate<C: Component>(component: &C, root: &Element) -> Result<(), Hydrat
 	// 1. Restore SSR state
 	let mut context = HydrationContext::from_window()?;
 
-	// 2. Render the component to get expected structure
-	let view = component.render();
+	// 2. Render the component to get expected structure, restoring signals
+	let view = with_hydration_state(context.state.clone(), || component.render());
 	web_sys::console::log_1(&"[Hydration] View rendered".into());
 
 	// 3. Reconcile DOM structure
@@ -447,6 +473,17 @@ mod tests {
 		assert!(err.to_string().contains("DOM structure mismatch"));
 	}
 
+	#[test]
+	fn test_with_hydration_state_restores_signals() {
+		let mut state = SsrState::new();
+		state.add_signal("count", 7);
+
+		let restored = with_hydration_state(state, || restored_signal("count"));
+
+		assert_eq!(restored, Some(serde_json::json!(7)));
+		assert_eq!(restored_signal("count"), None);
+	}
+
 	#[test]
 	fn test_hydration_context_from_window_non_wasm() {
 		// Non-WASM version should return empty context
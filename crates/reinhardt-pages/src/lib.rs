@@ -131,6 +131,9 @@
 //!
 //! **Note**: WebSocket functionality is WASM-only. On the server side (SSR),
 //! `use_websocket` returns a no-op handle with connection state always set to `Closed`.
+//!
+//! For server-pushed state, `use_live_signal` subscribes to a room and keeps a
+//! serde-typed signal up to date, reconnecting automatically on disconnect.
 
 #![warn(missing_docs)]
 
@@ -202,8 +205,8 @@ pub use callback::{Callback, IntoEventHandler, event_handler, into_event_handler
 #[cfg(not(target_arch = "wasm32"))]
 pub use component::DummyEvent;
 pub use component::{
-	Component, Head, IntoPage, LinkTag, MetaTag, Page, PageElement, PageExt, Props, ScriptTag,
-	StyleTag,
+	CapturedError, Component, ErrorBoundary, Head, IntoPage, LinkTag, MetaTag, Page, PageElement,
+	PageExt, Props, ScriptTag, StyleTag,
 };
 pub use csrf::{CsrfManager, get_csrf_token};
 pub use dom::{Document, Element, EventHandle, EventType, document};
@@ -221,11 +224,11 @@ pub use reactive::{
 };
 // Re-export Hooks API
 pub use reactive::{
-	ActionState, Dispatch, OptimisticState, Ref, SetState, SharedSetState, SharedSignal,
-	TransitionState, use_action_state, use_callback, use_context, use_debug_value,
-	use_deferred_value, use_effect, use_effect_event, use_id, use_layout_effect, use_memo,
-	use_optimistic, use_reducer, use_ref, use_shared_state, use_state, use_sync_external_store,
-	use_transition,
+	ActionState, Dispatch, LiveSignal, LiveSignalOptions, OptimisticState, Ref, SetState,
+	SharedSetState, SharedSignal, TransitionState, use_action_state, use_callback, use_context,
+	use_debug_value, use_deferred_value, use_effect, use_effect_event, use_hydrated_state, use_id,
+	use_layout_effect, use_live_signal, use_memo, use_optimistic, use_reducer, use_ref,
+	use_shared_state, use_state, use_sync_external_store, use_transition,
 };
 #[cfg(not(target_arch = "wasm32"))]
 pub use reinhardt_forms::{
@@ -36,10 +36,11 @@
 //! - [`use_ref`], [`use_reducer`], [`use_transition`], [`use_deferred_value`]
 //! - [`use_id`], [`use_layout_effect`], [`use_effect_event`], [`use_debug_value`]
 //! - [`use_optimistic`], [`use_action_state`], [`use_shared_state`]
-//! - [`use_sync_external_store`]
+//! - [`use_sync_external_store`], [`use_hydrated_state`], [`use_live_signal`]
 //!
 //! ## Component System
 //! - [`Component`], [`PageElement`], [`IntoPage`], [`Page`], [`Props`]
+//! - [`ErrorBoundary`], [`CapturedError`]
 //! - [`PageEventHandler`]
 //!
 //! ## Events and Callbacks
@@ -74,11 +75,11 @@ pub use crate::reactive::{
 
 // Hooks API
 pub use crate::reactive::{
-	ActionState, Dispatch, OptimisticState, Ref, SetState, SharedSetState, SharedSignal,
-	TransitionState, use_action_state, use_callback, use_context, use_debug_value,
-	use_deferred_value, use_effect, use_effect_event, use_id, use_layout_effect, use_memo,
-	use_optimistic, use_reducer, use_ref, use_shared_state, use_state, use_sync_external_store,
-	use_transition,
+	ActionState, Dispatch, LiveSignal, LiveSignalOptions, OptimisticState, Ref, SetState,
+	SharedSetState, SharedSignal, TransitionState, use_action_state, use_callback, use_context,
+	use_debug_value, use_deferred_value, use_effect, use_effect_event, use_hydrated_state, use_id,
+	use_layout_effect, use_live_signal, use_memo, use_optimistic, use_reducer, use_ref,
+	use_shared_state, use_state, use_sync_external_store, use_transition,
 };
 
 // WASM-only resource creation functions
@@ -90,8 +91,8 @@ pub use crate::reactive::{create_resource, create_resource_with_deps};
 // ============================================================================
 
 pub use crate::component::{
-	Component, Head, IntoPage, LinkTag, MetaTag, Page, PageElement, PageEventHandler, PageExt,
-	Props, ScriptTag, StyleTag,
+	CapturedError, Component, ErrorBoundary, Head, IntoPage, LinkTag, MetaTag, Page, PageElement,
+	PageEventHandler, PageExt, Props, ScriptTag, StyleTag,
 };
 
 // ============================================================================
@@ -135,12 +136,14 @@ pub use crate::csrf::{CsrfManager, get_csrf_token};
 
 pub use crate::hydration::{
 	HydrationContext, HydrationError, hydrate, init_hydration_state, is_hydration_complete,
-	on_hydration_complete,
+	on_hydration_complete, with_hydration_state,
 };
 
 #[cfg(target_arch = "wasm32")]
 pub use crate::hydration::mark_hydration_complete;
 pub use crate::ssr::{SsrOptions, SsrRenderer, SsrState};
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::ssr::{render_to_response, render_to_response_with_options};
 
 // ============================================================================
 // Static File URL Resolver
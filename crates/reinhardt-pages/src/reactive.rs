@@ -110,9 +110,10 @@ pub use resource::{create_resource, create_resource_with_deps};
 
 // Re-export hooks
 pub use hooks::{
-	ActionState, Dispatch, OptimisticState, Ref, SetState, SharedSetState, SharedSignal,
-	TransitionState, use_action_state, use_callback, use_context, use_debug_value,
-	use_deferred_value, use_effect, use_effect_event, use_id, use_layout_effect, use_memo,
-	use_optimistic, use_reducer, use_ref, use_shared_state, use_state, use_sync_external_store,
-	use_transition,
+	ActionState, Dispatch, FlashMessage, FlashMessages, LiveSignal, LiveSignalOptions,
+	OptimisticState, Ref, SetState, SharedSetState, SharedSignal, TransitionState,
+	use_action_state, use_callback, use_context, use_debug_value, use_deferred_value, use_effect,
+	use_effect_event, use_flash_messages, use_hydrated_state, use_id, use_layout_effect,
+	use_live_signal, use_memo, use_optimistic, use_reducer, use_ref, use_shared_state, use_state,
+	use_sync_external_store, use_transition,
 };
@@ -27,6 +27,7 @@
 //! ### State Hooks
 //! - [`use_state`] - Hold and update reactive state
 //! - [`use_reducer`] - State with reducer logic
+//! - [`use_hydrated_state`] - State serialized during SSR and restored on hydration
 //!
 //! ### Effect Hooks
 //! - [`use_effect`] - Side effects with automatic dependency tracking
@@ -50,6 +51,8 @@
 //! - [`use_id`] - Generate unique IDs
 //! - [`use_sync_external_store`] - Subscribe to external stores
 //! - [`use_websocket`] - WebSocket connections (WASM only)
+//! - [`use_live_signal`] - Signals updated from a WebSocket room (WASM only)
+//! - [`use_flash_messages`] - Flash messages pushed over Server-Sent Events (WASM only)
 //! - [`use_action_state`] - Form action state
 //! - [`use_optimistic`] - Optimistic UI updates
 //! - [`use_debug_value`] - DevTools labels
@@ -93,7 +96,10 @@ pub mod action;
 pub mod context;
 pub mod debug;
 pub mod effect;
+pub mod flash;
+pub mod hydrated;
 pub mod id;
+pub mod live;
 pub mod memo;
 pub mod refs;
 pub mod state;
@@ -106,7 +112,10 @@ pub use action::{ActionState, OptimisticState, use_action_state, use_optimistic}
 pub use context::use_context;
 pub use debug::{use_debug_value, use_effect_event};
 pub use effect::{use_effect, use_layout_effect};
+pub use flash::{FlashMessage, FlashMessages, use_flash_messages};
+pub use hydrated::use_hydrated_state;
 pub use id::use_id;
+pub use live::{LiveSignal, LiveSignalOptions, use_live_signal};
 pub use memo::{use_callback, use_memo};
 pub use refs::{Ref, use_ref};
 pub use state::{
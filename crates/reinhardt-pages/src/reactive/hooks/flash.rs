@@ -0,0 +1,195 @@
+//! Flash messages hook: use_flash_messages
+//!
+//! Subscribes to the Server-Sent Events stream served by
+//! `reinhardt_middleware::messages_api::MessageBroadcaster` and collects the
+//! flash messages pushed for the current session.
+//!
+//! # Wire format
+//!
+//! Each event is named `message` and carries the JSON produced by
+//! `reinhardt_middleware::messages_api::message_to_json`:
+//!
+//! ```json
+//! {"level": 25, "tag": "success", "text": "Saved", "tags": ["success"]}
+//! ```
+
+use serde::Deserialize;
+
+use crate::reactive::Signal;
+
+/// A flash message received from the server
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct FlashMessage {
+	/// Numeric level (`25` for success, `40` for error, ...)
+	pub level: i32,
+	/// Tag of the level, if the server knows one
+	#[serde(default)]
+	pub tag: Option<String>,
+	/// Message text
+	pub text: String,
+	/// Extra tags followed by the level tag, for CSS classes
+	#[serde(default)]
+	pub tags: Vec<String>,
+}
+
+/// Handle returned by [`use_flash_messages`]
+#[derive(Clone)]
+pub struct FlashMessages {
+	messages: Signal<Vec<FlashMessage>>,
+	close_fn: std::rc::Rc<dyn Fn()>,
+}
+
+impl FlashMessages {
+	/// Returns the messages received so far and not dismissed.
+	pub fn get(&self) -> Vec<FlashMessage> {
+		self.messages.get()
+	}
+
+	/// Returns the underlying signal.
+	pub fn signal(&self) -> &Signal<Vec<FlashMessage>> {
+		&self.messages
+	}
+
+	/// Removes the message at `index`, e.g. when its close button is clicked.
+	pub fn dismiss(&self, index: usize) {
+		self.messages.update(|messages| {
+			if index < messages.len() {
+				messages.remove(index);
+			}
+		});
+	}
+
+	/// Removes all messages.
+	pub fn clear(&self) {
+		self.messages.set(Vec::new());
+	}
+
+	/// Closes the event stream.
+	pub fn close(&self) {
+		(self.close_fn)()
+	}
+}
+
+/// Decodes the data of a `message` event.
+///
+/// # Errors
+///
+/// Returns an error if the data is not a valid message.
+pub fn decode_flash_message(data: &str) -> Result<FlashMessage, String> {
+	serde_json::from_str(data).map_err(|e| format!("Invalid flash message: {}", e))
+}
+
+/// Subscribes to the flash messages pushed for the current session.
+///
+/// `url` is the endpoint serving `MessageBroadcaster::sse_for_request`. The
+/// browser's `EventSource` sends the session cookie and reconnects on its
+/// own. Messages are appended to the signal as they arrive.
+///
+/// On the server (SSR) no connection is made and the list stays empty.
+///
+/// # Example
+///
+/// ```ignore
+/// use reinhardt_pages::reactive::hooks::use_flash_messages;
+///
+/// let flash = use_flash_messages("/messages/stream/");
+///
+/// page!(|| {
+///     ul {
+///         for message in flash.get() {
+///             li { class: message.tags.join(" "), message.text }
+///         }
+///     }
+/// })()
+/// ```
+#[cfg(target_arch = "wasm32")]
+pub fn use_flash_messages(url: &str) -> FlashMessages {
+	use wasm_bindgen::{JsCast, closure::Closure};
+	use web_sys::{EventSource, MessageEvent};
+
+	let messages = Signal::new(Vec::new());
+	let source = match EventSource::new(url) {
+		Ok(source) => source,
+		Err(e) => {
+			web_sys::console::error_1(&format!("Failed to open {}: {:?}", url, e).into());
+			return FlashMessages {
+				messages,
+				close_fn: std::rc::Rc::new(|| {}),
+			};
+		}
+	};
+
+	let onmessage = Closure::wrap(Box::new({
+		let messages = messages.clone();
+		move |e: MessageEvent| {
+			let Some(data) = e.data().as_string() else {
+				return;
+			};
+			match decode_flash_message(&data) {
+				Ok(message) => messages.update(|messages| messages.push(message)),
+				Err(e) => web_sys::console::warn_1(&e.into()),
+			}
+		}
+	}) as Box<dyn FnMut(MessageEvent)>);
+	source.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+	onmessage.forget(); // Intentional leak for event listener
+
+	FlashMessages {
+		messages,
+		close_fn: std::rc::Rc::new(move || source.close()),
+	}
+}
+
+/// Flash messages hook - SSR no-op implementation
+///
+/// Returns a handle whose message list stays empty.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn use_flash_messages(_url: &str) -> FlashMessages {
+	FlashMessages {
+		messages: Signal::new(Vec::new()),
+		close_fn: std::rc::Rc::new(|| {}),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serial_test::serial;
+
+	#[test]
+	fn test_decode_flash_message_matches_server_json() {
+		let message = decode_flash_message(
+			r#"{"level":25,"tag":"success","text":"Saved","tags":["sticky","success"]}"#,
+		)
+		.unwrap();
+
+		assert_eq!(
+			message,
+			FlashMessage {
+				level: 25,
+				tag: Some("success".to_string()),
+				text: "Saved".to_string(),
+				tags: vec!["sticky".to_string(), "success".to_string()],
+			}
+		);
+		assert!(decode_flash_message(r#"{"level":"high"}"#).is_err());
+	}
+
+	#[test]
+	#[serial]
+	#[cfg(not(target_arch = "wasm32"))]
+	fn test_use_flash_messages_ssr_is_empty_and_dismissable() {
+		let flash = use_flash_messages("/messages/stream/");
+		assert!(flash.get().is_empty());
+
+		flash.signal().set(vec![
+			decode_flash_message(r#"{"level":20,"text":"One"}"#).unwrap(),
+			decode_flash_message(r#"{"level":20,"text":"Two"}"#).unwrap(),
+		]);
+		flash.dismiss(0);
+		assert_eq!(flash.get()[0].text, "Two");
+		flash.dismiss(5);
+		flash.clear();
+		assert!(flash.get().is_empty());
+	}
+}
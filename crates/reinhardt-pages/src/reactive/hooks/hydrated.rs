@@ -0,0 +1,84 @@
+//! Hydrated state hook: use_hydrated_state
+//!
+//! State created with this hook survives the trip from server to client:
+//! during SSR its value is serialized into the page, and during hydration the
+//! client starts from that value instead of recomputing it.
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use super::state::{SetState, use_state};
+use crate::reactive::Signal;
+
+/// Creates reactive state that is serialized during SSR and restored on hydration.
+///
+/// `id` identifies the state in the serialized SSR payload and must be the
+/// same on server and client. `initial` is only called when no serialized
+/// value is available, e.g. on the server or in client-side rendering.
+///
+/// # Example
+///
+/// ```ignore
+/// use reinhardt_pages::reactive::hooks::use_hydrated_state;
+///
+/// fn todo_list(todos: Vec<String>) -> Page {
+///     // Rendered on the server from `todos`, restored on the client
+///     let (items, set_items) = use_hydrated_state("todos", || todos);
+///
+///     page!(|| {
+///         ul {
+///             p { format!("{} items", items.get().len()) }
+///         }
+///     })()
+/// }
+/// ```
+pub fn use_hydrated_state<T>(id: &str, initial: impl FnOnce() -> T) -> (Signal<T>, SetState<T>)
+where
+	T: Serialize + DeserializeOwned + Clone + 'static,
+{
+	let value = crate::hydration::restored_signal(id)
+		.and_then(|json| serde_json::from_value(json).ok())
+		.unwrap_or_else(initial);
+	crate::ssr::record_signal(id, &value);
+	use_state(value)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::hydration::with_hydration_state;
+	use crate::ssr::{SsrState, collect_state};
+	use serial_test::serial;
+
+	#[test]
+	#[serial]
+	fn test_use_hydrated_state_records_value_during_ssr() {
+		let ((signal, _), state) = collect_state(|| use_hydrated_state("count", || 5));
+
+		assert_eq!(signal.get(), 5);
+		assert_eq!(state.get_signal("count"), Some(&serde_json::json!(5)));
+	}
+
+	#[test]
+	#[serial]
+	fn test_use_hydrated_state_restores_serialized_value() {
+		let mut state = SsrState::new();
+		state.add_signal("count", 9);
+
+		let (signal, _) = with_hydration_state(state, || use_hydrated_state("count", || 0));
+
+		assert_eq!(signal.get(), 9);
+	}
+
+	#[test]
+	#[serial]
+	fn test_use_hydrated_state_falls_back_on_type_mismatch() {
+		let mut state = SsrState::new();
+		state.add_signal("name", 1);
+
+		let (signal, _) =
+			with_hydration_state(state, || use_hydrated_state("name", || "guest".to_string()));
+
+		assert_eq!(signal.get(), "guest");
+	}
+}
@@ -0,0 +1,337 @@
+//! Live signal hook: use_live_signal
+//!
+//! A live signal is a read-only [`Signal`] whose value is pushed by the
+//! server over a WebSocket. It is built on top of [`use_websocket`] and is
+//! intended for dashboards, notifications and other server-driven views.
+//!
+//! # Wire format
+//!
+//! When a room is configured, the client sends a subscription frame each time
+//! the connection opens (including after a reconnect), and an unsubscribe
+//! frame when the signal is closed:
+//!
+//! ```json
+//! {"type": "subscribe", "room": "dashboard"}
+//! {"type": "unsubscribe", "room": "dashboard"}
+//! ```
+//!
+//! `LiveRoomConsumer` in `reinhardt-websockets` handles these frames on the
+//! server and publishes values in the envelope below.
+//!
+//! Incoming text frames are accepted in two shapes:
+//!
+//! - an envelope `{"room": "dashboard", "payload": <T>}`, which is ignored when
+//!   the room does not match the subscribed one
+//! - a bare JSON value decoding directly to `T`
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use super::websocket::{
+	ConnectionState, UseWebSocketOptions, WebSocketHandle, WebSocketMessage, use_websocket,
+};
+use crate::reactive::Signal;
+
+/// Options for configuring a [`LiveSignal`]
+#[derive(Default)]
+pub struct LiveSignalOptions {
+	/// Room or channel to subscribe to once connected
+	///
+	/// When `None`, no subscription frame is sent and every message is
+	/// treated as a payload.
+	pub room: Option<String>,
+	/// Options for the underlying WebSocket connection
+	pub websocket: UseWebSocketOptions,
+}
+
+impl LiveSignalOptions {
+	/// Creates options subscribing to the given room.
+	pub fn room(room: impl Into<String>) -> Self {
+		Self {
+			room: Some(room.into()),
+			..Self::default()
+		}
+	}
+}
+
+/// A reactive value kept in sync with a WebSocket room
+///
+/// Created with [`use_live_signal`]. Reading the value inside an effect or
+/// view subscribes to updates like any other [`Signal`].
+pub struct LiveSignal<T: 'static> {
+	value: Signal<T>,
+	last_error: Signal<Option<String>>,
+	room: Option<String>,
+	ws: WebSocketHandle,
+}
+
+impl<T: Clone + 'static> LiveSignal<T> {
+	/// Returns the latest value received from the server.
+	pub fn get(&self) -> T {
+		self.value.get()
+	}
+
+	/// Returns the underlying signal.
+	pub fn signal(&self) -> &Signal<T> {
+		&self.value
+	}
+
+	/// Returns the connection state signal.
+	pub fn connection_state(&self) -> &Signal<ConnectionState> {
+		self.ws.connection_state()
+	}
+
+	/// Returns `true` if the connection is currently open.
+	pub fn is_connected(&self) -> bool {
+		self.ws.is_open()
+	}
+
+	/// Returns the last payload decoding error, if any.
+	///
+	/// Undecodable messages leave the value unchanged.
+	pub fn last_error(&self) -> &Signal<Option<String>> {
+		&self.last_error
+	}
+
+	/// Sends a JSON-serializable message to the server.
+	///
+	/// # Errors
+	///
+	/// Returns an error if serialization fails or the connection is not open.
+	pub fn send<M: Serialize>(&self, message: &M) -> Result<(), String> {
+		self.ws.send_json(message)
+	}
+
+	/// Unsubscribes from the room, closes the connection and stops reconnecting.
+	pub fn close(&self) {
+		if let Some(room) = &self.room
+			&& self.ws.is_open()
+		{
+			let _ = self.ws.send_text(unsubscribe_frame(room));
+		}
+		self.ws.close()
+	}
+}
+
+impl<T: Clone + 'static> Clone for LiveSignal<T> {
+	fn clone(&self) -> Self {
+		Self {
+			value: self.value.clone(),
+			last_error: self.last_error.clone(),
+			room: self.room.clone(),
+			ws: self.ws.clone(),
+		}
+	}
+}
+
+/// Builds the frame sent to subscribe to `room`.
+pub fn subscribe_frame(room: &str) -> String {
+	serde_json::json!({ "type": "subscribe", "room": room }).to_string()
+}
+
+/// Builds the frame sent to unsubscribe from `room`.
+pub fn unsubscribe_frame(room: &str) -> String {
+	serde_json::json!({ "type": "unsubscribe", "room": room }).to_string()
+}
+
+/// Decodes an incoming text frame into a payload for `room`.
+///
+/// Returns `Ok(None)` when the frame is an envelope addressed to another room.
+///
+/// # Errors
+///
+/// Returns an error if the frame is not valid JSON or the payload does not
+/// decode to `T`.
+pub fn decode_live_payload<T: DeserializeOwned>(
+	text: &str,
+	room: Option<&str>,
+) -> Result<Option<T>, String> {
+	let value: serde_json::Value =
+		serde_json::from_str(text).map_err(|e| format!("Invalid JSON message: {}", e))?;
+
+	let payload = match value {
+		serde_json::Value::Object(mut map)
+			if map.contains_key("payload") && map.get("room").is_some_and(|r| r.is_string()) =>
+		{
+			let target = map.get("room").and_then(|r| r.as_str());
+			if let Some(room) = room
+				&& target != Some(room)
+			{
+				return Ok(None);
+			}
+			map.remove("payload").unwrap_or_default()
+		}
+		other => other,
+	};
+
+	serde_json::from_value(payload)
+		.map(Some)
+		.map_err(|e| format!("Failed to decode payload: {}", e))
+}
+
+/// Creates a signal updated by messages from a WebSocket room.
+///
+/// The signal starts at `initial` and is replaced by each payload received
+/// from `url`. Reconnection follows `options.websocket`; the room
+/// subscription is re-sent every time the connection opens.
+///
+/// On the server (SSR) no connection is made and the signal keeps `initial`.
+///
+/// # Example
+///
+/// ```ignore
+/// use reinhardt_pages::reactive::hooks::{LiveSignalOptions, use_live_signal};
+///
+/// #[derive(Clone, serde::Deserialize)]
+/// struct Stats {
+///     active_users: u64,
+/// }
+///
+/// let stats = use_live_signal(
+///     "ws://localhost:8000/ws/stats",
+///     LiveSignalOptions::room("dashboard"),
+///     Stats { active_users: 0 },
+/// );
+///
+/// page!(|| {
+///     p { format!("{} users online", stats.get().active_users) }
+/// })()
+/// ```
+pub fn use_live_signal<T>(url: &str, options: LiveSignalOptions, initial: T) -> LiveSignal<T>
+where
+	T: DeserializeOwned + Clone + 'static,
+{
+	let LiveSignalOptions {
+		room,
+		websocket: mut ws_options,
+	} = options;
+
+	let value = Signal::new(initial);
+	let last_error = Signal::new(None);
+
+	// The handle only exists after `use_websocket` returns, while `on_open`
+	// must be passed in beforehand.
+	let handle_slot: Rc<RefCell<Option<WebSocketHandle>>> = Rc::new(RefCell::new(None));
+
+	let user_on_open = ws_options.on_open.take();
+	ws_options.on_open = Some(Rc::new({
+		let handle_slot = Rc::clone(&handle_slot);
+		let room = room.clone();
+		move || {
+			if let Some(room) = &room
+				&& let Some(ws) = handle_slot.borrow().as_ref()
+			{
+				let _ = ws.send_text(subscribe_frame(room));
+			}
+			if let Some(cb) = &user_on_open {
+				cb();
+			}
+		}
+	}));
+
+	let user_on_message = ws_options.on_message.take();
+	ws_options.on_message = Some(Rc::new({
+		let value = value.clone();
+		let last_error = last_error.clone();
+		let room = room.clone();
+		move |message: &WebSocketMessage| {
+			if let WebSocketMessage::Text(text) = message {
+				match decode_live_payload::<T>(text, room.as_deref()) {
+					Ok(Some(payload)) => value.set(payload),
+					Ok(None) => {}
+					Err(e) => last_error.set(Some(e)),
+				}
+			}
+			if let Some(cb) = &user_on_message {
+				cb(message);
+			}
+		}
+	}));
+
+	let ws = use_websocket(url, ws_options);
+	*handle_slot.borrow_mut() = Some(ws.clone());
+
+	LiveSignal {
+		value,
+		last_error,
+		room,
+		ws,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde::Deserialize;
+	use serial_test::serial;
+
+	#[derive(Debug, Clone, PartialEq, Deserialize)]
+	struct Stats {
+		active: u32,
+	}
+
+	#[test]
+	fn test_decode_live_payload_accepts_envelope_and_bare_value() {
+		let enveloped =
+			decode_live_payload::<Stats>(r#"{"room":"dash","payload":{"active":3}}"#, Some("dash"));
+		let bare = decode_live_payload::<Stats>(r#"{"active":4}"#, Some("dash"));
+
+		assert_eq!(enveloped, Ok(Some(Stats { active: 3 })));
+		assert_eq!(bare, Ok(Some(Stats { active: 4 })));
+	}
+
+	#[test]
+	fn test_decode_live_payload_filters_other_rooms() {
+		let result = decode_live_payload::<Stats>(
+			r#"{"room":"other","payload":{"active":3}}"#,
+			Some("dash"),
+		);
+
+		assert_eq!(result, Ok(None));
+	}
+
+	#[test]
+	fn test_decode_live_payload_rejects_invalid_payload() {
+		assert!(decode_live_payload::<Stats>("not json", None).is_err());
+		assert!(decode_live_payload::<Stats>(r#"{"active":"many"}"#, None).is_err());
+	}
+
+	#[test]
+	fn test_subscribe_frame() {
+		let frame: serde_json::Value = serde_json::from_str(&subscribe_frame("dash")).unwrap();
+
+		assert_eq!(
+			frame,
+			serde_json::json!({ "type": "subscribe", "room": "dash" })
+		);
+	}
+
+	#[test]
+	fn test_unsubscribe_frame() {
+		let frame: serde_json::Value = serde_json::from_str(&unsubscribe_frame("dash")).unwrap();
+
+		assert_eq!(
+			frame,
+			serde_json::json!({ "type": "unsubscribe", "room": "dash" })
+		);
+	}
+
+	#[test]
+	#[serial]
+	#[cfg(not(target_arch = "wasm32"))]
+	fn test_use_live_signal_ssr_keeps_initial_value() {
+		let live = use_live_signal(
+			"ws://test",
+			LiveSignalOptions::room("dash"),
+			Stats { active: 1 },
+		);
+
+		assert_eq!(live.get(), Stats { active: 1 });
+		assert!(!live.is_connected());
+		assert!(live.send(&"ping").is_err());
+		assert_eq!(live.last_error().get(), None);
+	}
+}
@@ -31,6 +31,9 @@ pub enum WebSocketMessage {
 	Binary(Vec<u8>),
 }
 
+/// Callback invoked with each received WebSocket message
+pub type MessageCallback = Rc<dyn Fn(&WebSocketMessage)>;
+
 /// Options for configuring WebSocket behavior
 pub struct UseWebSocketOptions {
 	/// Enable automatic reconnection on disconnect
@@ -38,9 +41,14 @@ pub struct UseWebSocketOptions {
 	/// Maximum number of reconnection attempts
 	pub max_reconnect_attempts: usize,
 	/// Initial delay before reconnecting (in milliseconds)
+	///
+	/// The delay doubles with each consecutive failed attempt, capped at
+	/// [`MAX_RECONNECT_DELAY_MS`].
 	pub reconnect_delay: u32,
-	/// Callback when connection opens
+	/// Callback when connection opens (called again after each reconnect)
 	pub on_open: Option<Rc<dyn Fn()>>,
+	/// Callback when a message is received
+	pub on_message: Option<MessageCallback>,
 	/// Callback when connection closes
 	pub on_close: Option<Rc<dyn Fn()>>,
 	/// Callback when error occurs
@@ -54,12 +62,27 @@ impl Default for UseWebSocketOptions {
 			max_reconnect_attempts: 5,
 			reconnect_delay: 1000,
 			on_open: None,
+			on_message: None,
 			on_close: None,
 			on_error: None,
 		}
 	}
 }
 
+/// Upper bound for the delay between reconnection attempts (in milliseconds)
+pub const MAX_RECONNECT_DELAY_MS: u32 = 30_000;
+
+/// Computes the delay before reconnection attempt number `attempt` (0-based).
+///
+/// Uses exponential backoff starting at `base_delay`, capped at
+/// [`MAX_RECONNECT_DELAY_MS`].
+pub fn reconnect_delay_ms(base_delay: u32, attempt: usize) -> u32 {
+	let factor = 1u32.checked_shl(attempt as u32).unwrap_or(u32::MAX);
+	base_delay
+		.saturating_mul(factor)
+		.min(MAX_RECONNECT_DELAY_MS)
+}
+
 /// Handle for controlling a WebSocket connection
 ///
 /// This struct provides methods to interact with the WebSocket connection,
@@ -140,7 +163,7 @@ impl Clone for WebSocketHandle {
 
 #[cfg(target_arch = "wasm32")]
 use {
-	std::cell::RefCell,
+	std::cell::{Cell, RefCell},
 	wasm_bindgen::{JsCast, JsValue, closure::Closure},
 	web_sys::{CloseEvent, ErrorEvent, MessageEvent, WebSocket},
 };
@@ -161,6 +184,11 @@ use {
 /// A `WebSocketHandle` that can be used to control the connection and
 /// reactively access its state.
 ///
+/// When `options.auto_reconnect` is enabled, an unexpected close schedules a
+/// new connection attempt with exponential backoff (see [`reconnect_delay_ms`]).
+/// The attempt counter resets once a connection opens, and calling
+/// [`WebSocketHandle::close`] disables reconnection.
+///
 /// # Example
 ///
 /// ```ignore
@@ -209,15 +237,28 @@ pub fn use_websocket(url: &str, options: UseWebSocketOptions) -> WebSocketHandle
 	let connection_state = Signal::new(ConnectionState::Connecting);
 	let latest_message = Signal::new(None);
 
+	// Reconnection bookkeeping
+	let attempts = Rc::new(Cell::new(0usize));
+	let manually_closed = Rc::new(Cell::new(false));
+	// Self-reference so the close handler can schedule another connection
+	let connect_ref: Rc<RefCell<Option<Rc<dyn Fn()>>>> = Rc::new(RefCell::new(None));
+
 	// Connection function
-	let connect = {
+	let connect: Rc<dyn Fn()> = Rc::new({
 		let ws_ref = Rc::clone(&ws_ref);
 		let connection_state = connection_state.clone();
 		let latest_message = latest_message.clone();
 		let url = url.clone();
 		let on_open = options.on_open.clone();
+		let on_message = options.on_message.clone();
 		let on_close = options.on_close.clone();
 		let on_error = options.on_error.clone();
+		let auto_reconnect = options.auto_reconnect;
+		let max_reconnect_attempts = options.max_reconnect_attempts;
+		let reconnect_delay = options.reconnect_delay;
+		let attempts = Rc::clone(&attempts);
+		let manually_closed = Rc::clone(&manually_closed);
+		let connect_ref = Rc::clone(&connect_ref);
 
 		move || {
 			// Create WebSocket connection
@@ -238,7 +279,9 @@ pub fn use_websocket(url: &str, options: UseWebSocketOptions) -> WebSocketHandle
 			// onopen handler
 			let connection_state_open = connection_state.clone();
 			let on_open_cb = on_open.clone();
+			let attempts_open = Rc::clone(&attempts);
 			let onopen = Closure::wrap(Box::new(move |_: JsValue| {
+				attempts_open.set(0);
 				connection_state_open.set(ConnectionState::Open);
 				if let Some(cb) = &on_open_cb {
 					cb();
@@ -249,18 +292,23 @@ pub fn use_websocket(url: &str, options: UseWebSocketOptions) -> WebSocketHandle
 
 			// onmessage handler
 			let latest_message_recv = latest_message.clone();
+			let on_message_cb = on_message.clone();
 			let onmessage = Closure::wrap(Box::new(move |e: MessageEvent| {
 				// Try text message first
-				if let Ok(txt) = e.data().dyn_into::<js_sys::JsString>() {
-					let text = txt.as_string().unwrap_or_default();
-					latest_message_recv.set(Some(WebSocketMessage::Text(text)));
+				let message = if let Ok(txt) = e.data().dyn_into::<js_sys::JsString>() {
+					WebSocketMessage::Text(txt.as_string().unwrap_or_default())
 				}
 				// Try binary message (ArrayBuffer)
 				else if let Ok(array_buffer) = e.data().dyn_into::<js_sys::ArrayBuffer>() {
 					let array = js_sys::Uint8Array::new(&array_buffer);
-					let vec = array.to_vec();
-					latest_message_recv.set(Some(WebSocketMessage::Binary(vec)));
+					WebSocketMessage::Binary(array.to_vec())
+				} else {
+					return;
+				};
+				if let Some(cb) = &on_message_cb {
+					cb(&message);
 				}
+				latest_message_recv.set(Some(message));
 			}) as Box<dyn FnMut(MessageEvent)>);
 			ws.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
 			onmessage.forget(); // Intentional leak for event listener
@@ -268,11 +316,35 @@ pub fn use_websocket(url: &str, options: UseWebSocketOptions) -> WebSocketHandle
 			// onclose handler
 			let connection_state_close = connection_state.clone();
 			let on_close_cb = on_close.clone();
+			let attempts_close = Rc::clone(&attempts);
+			let manually_closed_close = Rc::clone(&manually_closed);
+			let connect_ref_close = Rc::clone(&connect_ref);
 			let onclose = Closure::wrap(Box::new(move |_: CloseEvent| {
 				connection_state_close.set(ConnectionState::Closed);
 				if let Some(cb) = &on_close_cb {
 					cb();
 				}
+
+				let attempt = attempts_close.get();
+				if !auto_reconnect
+					|| manually_closed_close.get()
+					|| attempt >= max_reconnect_attempts
+				{
+					return;
+				}
+				let Some(reconnect) = connect_ref_close.borrow().clone() else {
+					return;
+				};
+				let Some(window) = web_sys::window() else {
+					return;
+				};
+				attempts_close.set(attempt + 1);
+				connection_state_close.set(ConnectionState::Connecting);
+				let callback = Closure::once_into_js(move || reconnect());
+				let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+					callback.unchecked_ref(),
+					reconnect_delay_ms(reconnect_delay, attempt) as i32,
+				);
 			}) as Box<dyn FnMut(CloseEvent)>);
 			ws.set_onclose(Some(onclose.as_ref().unchecked_ref()));
 			onclose.forget(); // Intentional leak for event listener
@@ -292,7 +364,9 @@ pub fn use_websocket(url: &str, options: UseWebSocketOptions) -> WebSocketHandle
 
 			*ws_ref.borrow_mut() = Some(ws);
 		}
-	};
+	});
+	// Intentional reference cycle: the connection lives as long as the page
+	*connect_ref.borrow_mut() = Some(Rc::clone(&connect));
 
 	// Initial connection
 	connect();
@@ -318,7 +392,9 @@ pub fn use_websocket(url: &str, options: UseWebSocketOptions) -> WebSocketHandle
 	// Close function
 	let close_fn = {
 		let ws_ref = Rc::clone(&ws_ref);
+		let manually_closed = Rc::clone(&manually_closed);
 		Rc::new(move || {
+			manually_closed.set(true);
 			if let Some(ws) = ws_ref.borrow().as_ref() {
 				let _ = ws.close();
 			}
@@ -377,6 +453,15 @@ mod tests {
 		assert!(!ws.is_open());
 	}
 
+	#[test]
+	fn test_reconnect_delay_backs_off_exponentially() {
+		assert_eq!(reconnect_delay_ms(1000, 0), 1000);
+		assert_eq!(reconnect_delay_ms(1000, 1), 2000);
+		assert_eq!(reconnect_delay_ms(1000, 3), 8000);
+		assert_eq!(reconnect_delay_ms(1000, 10), MAX_RECONNECT_DELAY_MS);
+		assert_eq!(reconnect_delay_ms(1000, 64), MAX_RECONNECT_DELAY_MS);
+	}
+
 	#[test]
 	fn test_connection_state_clone() {
 		let state1 = ConnectionState::Open;
@@ -398,6 +483,7 @@ mod tests {
 		assert_eq!(options.max_reconnect_attempts, 5);
 		assert_eq!(options.reconnect_delay, 1000);
 		assert!(options.on_open.is_none());
+		assert!(options.on_message.is_none());
 		assert!(options.on_close.is_none());
 		assert!(options.on_error.is_none());
 	}
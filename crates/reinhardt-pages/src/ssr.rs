@@ -10,6 +10,7 @@
 //! - **Hydration Markers**: Automatically embed markers for client-side hydration
 //! - **State Serialization**: Serialize reactive state for client restoration
 //! - **Layout Support**: Wrap rendered content in HTML layouts
+//! - **Handler Integration**: Return rendered pages from handlers with `render_to_response`
 //!
 //! ## Usage
 //!
@@ -30,10 +31,15 @@
 //!     "My Page Title",
 //!     Some("<!DOCTYPE html>...")
 //! );
+//!
+//! // Handler response with serialized hydration state
+//! let response = render_to_response(|| dashboard_page());
 //! ```
 
 mod markers;
 mod renderer;
+#[cfg(not(target_arch = "wasm32"))]
+mod response;
 mod state;
 
 pub use markers::{
@@ -41,4 +47,7 @@ pub use markers::{
 	HydrationStrategy,
 };
 pub use renderer::{SsrOptions, SsrRenderer};
-pub use state::{SsrState, StateEntry};
+#[cfg(not(target_arch = "wasm32"))]
+pub use response::{render_to_response, render_to_response_with_options};
+pub(crate) use state::record_signal;
+pub use state::{SsrState, StateEntry, collect_state};
@@ -1,7 +1,7 @@
 //! SSR Renderer for Component-based server-side rendering.
 
 use super::markers::{HydrationMarker, HydrationStrategy};
-use super::state::SsrState;
+use super::state::{SsrState, collect_state};
 use crate::auth::AuthData;
 use crate::component::{Component, Head, IntoPage, Page};
 
@@ -174,14 +174,20 @@ impl SsrRenderer {
 	}
 
 	/// Renders a component to an HTML string.
+	///
+	/// Hydratable state created while rendering is added to the SSR state.
 	pub fn render<C: Component>(&mut self, component: &C) -> String {
-		let view = component.render();
+		let (view, state) = collect_state(|| component.render());
+		self.state.merge(state);
 		self.render_view(&view)
 	}
 
 	/// Renders an IntoPage to an HTML string.
+	///
+	/// Hydratable state created while converting is added to the SSR state.
 	pub fn render_into_page<V: IntoPage>(&mut self, view: V) -> String {
-		let view = view.into_page();
+		let (view, state) = collect_state(|| view.into_page());
+		self.state.merge(state);
 		self.render_view(&view)
 	}
 
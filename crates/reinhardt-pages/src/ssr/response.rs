@@ -0,0 +1,84 @@
+//! HTTP response integration for SSR.
+//!
+//! Handlers return fully rendered pages, including serialized hydration
+//! state, with [`render_to_response`].
+
+use reinhardt_http::Response;
+
+use super::renderer::{SsrOptions, SsrRenderer};
+use super::state::collect_state;
+use crate::component::IntoPage;
+
+/// Renders a page into an HTML response with default options.
+///
+/// See [`render_to_response_with_options`].
+pub fn render_to_response<V, F>(view: F) -> Response
+where
+	V: IntoPage,
+	F: FnOnce() -> V,
+{
+	render_to_response_with_options(SsrOptions::default(), view)
+}
+
+/// Renders a page into an HTML response.
+///
+/// `view` builds the page; state created with
+/// [`use_hydrated_state`](crate::reactive::hooks::use_hydrated_state) while
+/// it runs is serialized into the document for client-side hydration. The
+/// page's attached `Head` (if any) is used for the document head.
+///
+/// # Example
+///
+/// ```ignore
+/// use reinhardt_http::{Request, Response};
+/// use reinhardt_pages::ssr::{SsrOptions, render_to_response_with_options};
+///
+/// async fn dashboard(request: Request) -> reinhardt_http::Result<Response> {
+///     let options = SsrOptions::new().csrf(csrf_token(&request));
+///     Ok(render_to_response_with_options(options, || dashboard_page()))
+/// }
+/// ```
+pub fn render_to_response_with_options<V, F>(options: SsrOptions, view: F) -> Response
+where
+	V: IntoPage,
+	F: FnOnce() -> V,
+{
+	let (page, state) = collect_state(|| view().into_page());
+	let mut renderer = SsrRenderer::with_options(options);
+	renderer.state_mut().merge(state);
+	let html = renderer.render_page_with_view_head(page);
+
+	Response::ok()
+		.with_header("Content-Type", "text/html; charset=utf-8")
+		.with_body(html)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::component::{Page, PageElement};
+	use crate::reactive::hooks::use_hydrated_state;
+	use serial_test::serial;
+
+	fn counter_page() -> Page {
+		let (count, _) = use_hydrated_state("count", || 3);
+		PageElement::new("p")
+			.child(format!("Count: {}", count.get()))
+			.into_page()
+	}
+
+	#[test]
+	#[serial]
+	fn test_render_to_response_embeds_state() {
+		let response = render_to_response(counter_page);
+		let body = String::from_utf8(response.body.to_vec()).unwrap();
+
+		assert_eq!(response.status, hyper::StatusCode::OK);
+		assert_eq!(
+			response.headers.get("Content-Type").unwrap(),
+			"text/html; charset=utf-8"
+		);
+		assert!(body.contains("<p>Count: 3</p>"));
+		assert!(body.contains("window.__REINHARDT_SSR_STATE__ = {\"signals\":{\"count\":3}"));
+	}
+}
@@ -4,6 +4,7 @@
 //! so it can be restored during client-side hydration.
 
 use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
 use std::collections::HashMap;
 
 /// The global JavaScript variable name for SSR state.
@@ -87,11 +88,17 @@ impl SsrState {
 		serde_json::to_string_pretty(self)
 	}
 
-	/// Generates a `<script>` tag containing the serialized state.
+	/// Generates a `<script>` tag assigning the serialized state to
+	/// `window.__REINHARDT_SSR_STATE__`.
+	///
+	/// `<` is escaped so that string values cannot close the script element.
 	pub fn to_script_tag(&self) -> String {
-		let json = self.to_json().unwrap_or_else(|_| "{}".to_string());
+		let json = self
+			.to_json()
+			.unwrap_or_else(|_| "{}".to_string())
+			.replace('<', "\\u003c");
 		format!(
-			r#"<script id="ssr-state" type="application/json">window.{} = {};</script>"#,
+			r#"<script id="ssr-state">window.{} = {};</script>"#,
 			SSR_STATE_VAR, json
 		)
 	}
@@ -109,6 +116,43 @@ impl SsrState {
 	}
 }
 
+thread_local! {
+	static COLLECTED_STATE: RefCell<Option<SsrState>> = const { RefCell::new(None) };
+}
+
+/// Runs `f`, collecting the signal values recorded while it runs.
+///
+/// Components record their hydratable state with
+/// [`use_hydrated_state`](crate::reactive::hooks::use_hydrated_state) while
+/// building their pages, so `f` should build the page, not just render it.
+/// Nested calls collect into the outermost state.
+pub fn collect_state<R>(f: impl FnOnce() -> R) -> (R, SsrState) {
+	let outer = COLLECTED_STATE.with(|collected| collected.borrow_mut().replace(SsrState::new()));
+	let result = f();
+	let state = COLLECTED_STATE.with(|collected| {
+		let mut collected = collected.borrow_mut();
+		let state = collected.take().unwrap_or_default();
+		match outer {
+			Some(mut outer) => {
+				outer.merge(state);
+				*collected = Some(outer);
+				SsrState::new()
+			}
+			None => state,
+		}
+	});
+	(result, state)
+}
+
+/// Records a signal value into the state being collected, if any.
+pub(crate) fn record_signal(id: &str, value: &impl Serialize) {
+	COLLECTED_STATE.with(|collected| {
+		if let Some(state) = collected.borrow_mut().as_mut() {
+			state.add_signal(id, value);
+		}
+	});
+}
+
 /// A single entry in the SSR state.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StateEntry {
@@ -212,6 +256,30 @@ mod tests {
 		assert!(script.contains("</script>"));
 	}
 
+	#[test]
+	fn test_ssr_state_script_tag_escapes_closing_tag() {
+		let mut state = SsrState::new();
+		state.add_signal("html", "</script><script>alert(1)</script>");
+		let script = state.to_script_tag();
+		assert_eq!(script.matches("</script>").count(), 1);
+		assert!(!script.contains("type=\"application/json\""));
+	}
+
+	#[test]
+	fn test_collect_state_records_signals() {
+		let (value, state) = collect_state(|| {
+			record_signal("count", &3);
+			"rendered"
+		});
+		assert_eq!(value, "rendered");
+		assert_eq!(state.get_signal("count"), Some(&serde_json::json!(3)));
+
+		// Outside a collection, recording is a no-op
+		record_signal("ignored", &1);
+		let ((), state) = collect_state(|| {});
+		assert!(state.is_empty());
+	}
+
 	#[test]
 	fn test_ssr_state_merge() {
 		let mut state1 = SsrState::new();
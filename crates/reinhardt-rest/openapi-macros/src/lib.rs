@@ -13,7 +13,7 @@ mod schema;
 mod serde_attrs;
 
 use crate::crate_paths::get_reinhardt_openapi_crate;
-use schema::{FieldAttributes, extract_field_attributes};
+use schema::{FieldAttributes, extract_container_attributes, extract_field_attributes};
 use serde_attrs::{TaggingStrategy, extract_serde_enum_attrs, extract_serde_variant_attrs};
 
 /// Derive macro for automatic OpenAPI schema generation.
@@ -27,12 +27,12 @@ use serde_attrs::{TaggingStrategy, extract_serde_enum_attrs, extract_serde_varia
 ///
 /// - `#[schema(title = "...")]` - Override the schema title (default: type name)
 /// - `#[schema(description = "...")]` - Schema description
-/// - `#[schema(example = "...")]` - Example value for the entire type
+/// - `#[schema(example = "...")]` - Example value for the entire type, as a JSON string
 ///
 /// ## Field Attributes (for structs)
 ///
 /// - `#[schema(description = "...")]` - Field description (also reads doc comments)
-/// - `#[schema(example = "...")]` - Example value for this field
+/// - `#[schema(example = ...)]` - Example value for this field (string, number or boolean literal)
 /// - `#[schema(default)]` - Mark field as having a default value
 /// - `#[schema(deprecated)]` - Mark field as deprecated
 /// - `#[schema(read_only)]` - Field is read-only (GET responses only)
@@ -60,6 +60,12 @@ use serde_attrs::{TaggingStrategy, extract_serde_enum_attrs, extract_serde_varia
 /// - **Tuple variants**: Generate array schema
 /// - **Struct variants**: Generate object schema with properties
 ///
+/// # Example Values
+///
+/// Fields without `#[schema(example = ...)]` get an example generated from
+/// their type, so `ToSchema::example()` of a derived type is always a
+/// complete payload.
+///
 #[proc_macro_derive(Schema, attributes(schema))]
 pub fn derive_schema(input: TokenStream) -> TokenStream {
 	let input = parse_macro_input!(input as DeriveInput);
@@ -93,6 +99,10 @@ fn derive_struct_schema(input: &DeriveInput, data: &syn::DataStruct) -> TokenStr
 
 	// Extract container-level attributes
 	let struct_name = name.to_string();
+	let container_attrs = match extract_container_attributes(&input.attrs) {
+		Ok(attrs) => attrs,
+		Err(e) => return e.to_compile_error().into(),
+	};
 
 	// Generate schema for each field
 	let mut field_schemas = Vec::new();
@@ -137,6 +147,7 @@ fn derive_struct_schema(input: &DeriveInput, data: &syn::DataStruct) -> TokenStr
 
 	// Get dynamic crate path
 	let openapi_crate = get_reinhardt_openapi_crate();
+	let container_example = container_example(&container_attrs, &openapi_crate);
 
 	// Generate inventory registration only for non-generic types
 	// Generic types cannot be registered at compile time since they don't have a concrete type
@@ -167,7 +178,9 @@ fn derive_struct_schema(input: &DeriveInput, data: &syn::DataStruct) -> TokenStr
 				#(#field_schemas)*
 				#required_builder
 
-				Schema::Object(builder.build())
+				let mut schema = Schema::Object(builder.build());
+				#container_example
+				schema
 			}
 
 			fn schema_name() -> Option<String> {
@@ -191,9 +204,14 @@ fn derive_enum_schema(input: &DeriveInput, data: &syn::DataEnum) -> TokenStream
 	// Extract serde enum attributes for tagging strategy
 	let serde_attrs = extract_serde_enum_attrs(&input.attrs);
 	let tagging = serde_attrs.tagging_strategy();
+	let container_attrs = match extract_container_attributes(&input.attrs) {
+		Ok(attrs) => attrs,
+		Err(e) => return e.to_compile_error().into(),
+	};
 
 	// Get dynamic crate path
 	let openapi_crate = get_reinhardt_openapi_crate();
+	let container_example = container_example(&container_attrs, &openapi_crate);
 
 	// Check if all variants are unit variants (simple string enum)
 	let all_unit_variants = data
@@ -226,7 +244,9 @@ fn derive_enum_schema(input: &DeriveInput, data: &syn::DataEnum) -> TokenStream
 	let expanded = quote! {
 		impl #impl_generics #openapi_crate::ToSchema for #name #ty_generics #where_clause {
 			fn schema() -> #openapi_crate::Schema {
-				#schema_body
+				let mut schema = { #schema_body };
+				#container_example
+				schema
 			}
 
 			fn schema_name() -> Option<String> {
@@ -240,6 +260,23 @@ fn derive_enum_schema(input: &DeriveInput, data: &syn::DataEnum) -> TokenStream
 	TokenStream::from(expanded)
 }
 
+/// Statement setting the container-level example on `schema`, if declared
+fn container_example(
+	attrs: &schema::ContainerAttributes,
+	openapi_crate: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+	match &attrs.example {
+		Some(example) => quote! {
+			#openapi_crate::examples::set_example(
+				&mut schema,
+				#openapi_crate::serde_json::from_str(#example)
+					.expect("schema example is validated at compile time"),
+			);
+		},
+		None => quote! {},
+	}
+}
+
 /// Generate schema for simple unit-variant enums (string enum)
 fn generate_simple_enum_schema(
 	data: &syn::DataEnum,
@@ -504,10 +541,10 @@ fn build_field_schema(field_type: &syn::Type, attrs: &FieldAttributes) -> proc_m
 
 	if let Some(ref example) = attrs.example {
 		modifications.push(quote! {
-			if let Schema::Object(mut obj) = schema {
-				obj.example = Some(serde_json::json!(#example));
-				schema = Schema::Object(obj);
-			}
+			#openapi_crate::examples::set_example(
+				&mut schema,
+				#openapi_crate::serde_json::json!(#example),
+			);
 		});
 	}
 
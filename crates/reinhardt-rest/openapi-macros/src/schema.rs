@@ -6,7 +6,8 @@ use syn::{Attribute, Lit, Meta, MetaNameValue};
 #[derive(Debug, Default, Clone)]
 pub(crate) struct FieldAttributes {
 	pub description: Option<String>,
-	pub example: Option<String>,
+	/// Literal (string, number or boolean) used as the field's example
+	pub example: Option<syn::Expr>,
 	pub format: Option<String>,
 	pub default: bool,
 	pub deprecated: bool,
@@ -127,12 +128,8 @@ pub(crate) fn extract_field_attributes(attrs: &[Attribute]) -> FieldAttributes {
 								}
 							}
 							"example" => {
-								if let syn::Expr::Lit(syn::ExprLit {
-									lit: Lit::Str(lit_str),
-									..
-								}) = nv.value
-								{
-									field_attrs.example = Some(lit_str.value());
+								if is_example_literal(&nv.value) {
+									field_attrs.example = Some(nv.value);
 								}
 							}
 							"format" => {
@@ -221,3 +218,82 @@ pub(crate) fn extract_field_attributes(attrs: &[Attribute]) -> FieldAttributes {
 
 	field_attrs
 }
+
+/// Check whether an expression is a literal usable as a JSON example
+///
+/// Accepts string, integer, float and boolean literals, including negated numbers.
+fn is_example_literal(expr: &syn::Expr) -> bool {
+	match expr {
+		syn::Expr::Lit(syn::ExprLit { lit, .. }) => matches!(
+			lit,
+			Lit::Str(_) | Lit::Int(_) | Lit::Float(_) | Lit::Bool(_)
+		),
+		syn::Expr::Unary(syn::ExprUnary {
+			op: syn::UnOp::Neg(_),
+			expr,
+			..
+		}) => matches!(
+			&**expr,
+			syn::Expr::Lit(syn::ExprLit {
+				lit: Lit::Int(_) | Lit::Float(_),
+				..
+			})
+		),
+		_ => false,
+	}
+}
+
+/// Container-level schema attributes
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ContainerAttributes {
+	/// JSON document used as the example for the whole type
+	pub example: Option<String>,
+}
+
+/// Extract schema attributes from a struct or enum
+///
+/// The container example must be a string literal holding valid JSON; it is
+/// checked here so mistakes are reported at compile time.
+pub(crate) fn extract_container_attributes(
+	attrs: &[Attribute],
+) -> syn::Result<ContainerAttributes> {
+	let mut container_attrs = ContainerAttributes::default();
+
+	for attr in attrs {
+		if !attr.path().is_ident("schema") {
+			continue;
+		}
+
+		let Ok(meta_list) = attr.meta.require_list() else {
+			continue;
+		};
+		for nested_meta in meta_list
+			.parse_args_with(syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated)
+			.unwrap_or_default()
+		{
+			if let Meta::NameValue(nv) = nested_meta
+				&& nv.path.is_ident("example")
+			{
+				let syn::Expr::Lit(syn::ExprLit {
+					lit: Lit::Str(lit_str),
+					..
+				}) = &nv.value
+				else {
+					return Err(syn::Error::new_spanned(
+						&nv.value,
+						"container example must be a string literal containing JSON",
+					));
+				};
+				if let Err(e) = serde_json::from_str::<serde_json::Value>(&lit_str.value()) {
+					return Err(syn::Error::new_spanned(
+						lit_str,
+						format!("invalid JSON in schema example: {}", e),
+					));
+				}
+				container_attrs.example = Some(lit_str.value());
+			}
+		}
+	}
+
+	Ok(container_attrs)
+}
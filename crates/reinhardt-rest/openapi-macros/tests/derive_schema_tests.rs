@@ -786,3 +786,92 @@ fn test_internally_tagged_with_unit_variant() {
 		_ => panic!("Expected OneOf schema for internally tagged enum with unit variants"),
 	}
 }
+
+#[test]
+fn test_field_examples_keep_literal_types() {
+	#[allow(dead_code)]
+	#[derive(Schema)]
+	struct Product {
+		#[schema(example = 42)]
+		id: i64,
+
+		#[schema(example = -1.5)]
+		discount: f64,
+
+		#[schema(example = false)]
+		in_stock: bool,
+
+		#[schema(example = "widget")]
+		name: String,
+
+		#[schema(example = "tools")]
+		tags: Vec<String>,
+	}
+
+	let example = Product::example();
+
+	assert_eq!(
+		example,
+		serde_json::json!({
+			"id": 42,
+			"discount": -1.5,
+			"in_stock": false,
+			"name": "widget",
+			"tags": "tools",
+		})
+	);
+}
+
+#[test]
+fn test_example_generated_from_field_types() {
+	#[allow(dead_code)]
+	#[derive(Schema)]
+	struct Account {
+		#[schema(format = "uuid")]
+		id: String,
+		#[schema(format = "email")]
+		email: String,
+		#[schema(minimum = 18)]
+		age: i32,
+		#[schema(format = "date-time")]
+		created_at: String,
+		nickname: Option<String>,
+		roles: Vec<String>,
+	}
+
+	let example = Account::example();
+
+	assert_eq!(example["id"], "3fa85f64-5717-4562-b3fc-2c963f66afa6");
+	assert_eq!(example["email"], "user@example.com");
+	assert_eq!(example["age"], 18);
+	assert_eq!(example["created_at"], "2024-01-01T00:00:00Z");
+	assert_eq!(example["nickname"], "string");
+	assert_eq!(example["roles"], serde_json::json!(["string"]));
+}
+
+#[test]
+fn test_container_example() {
+	#[allow(dead_code)]
+	#[derive(Schema)]
+	#[schema(example = r#"{"id": 7, "name": "Alice"}"#)]
+	struct User {
+		id: i64,
+		name: String,
+	}
+
+	#[allow(dead_code)]
+	#[derive(Schema)]
+	#[schema(example = r#""active""#)]
+	enum Status {
+		Active,
+		Inactive,
+	}
+
+	let expected = serde_json::json!({"id": 7, "name": "Alice"});
+	match User::schema() {
+		Schema::Object(obj) => assert_eq!(obj.example, Some(expected.clone())),
+		_ => panic!("Expected Object schema"),
+	}
+	assert_eq!(User::example(), expected);
+	assert_eq!(Status::example(), serde_json::json!("active"));
+}
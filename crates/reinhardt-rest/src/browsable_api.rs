@@ -22,7 +22,9 @@ pub enum Error {
 pub type Result<T> = std::result::Result<T, Error>;
 
 pub use middleware::{BrowsableApiConfig, BrowsableApiMiddleware};
-pub use renderer::{ApiContext, BrowsableApiRenderer, FormContext, FormField, SelectOption};
+pub use renderer::{
+	ApiContext, BrowsableApiRenderer, ContextProcessor, FormContext, FormField, SelectOption,
+};
 pub use response::BrowsableResponse;
 pub use template::ApiTemplate;
 
@@ -6,11 +6,14 @@
 use async_trait::async_trait;
 use hyper::{Method, Uri};
 use reinhardt_core::exception::Result;
+use reinhardt_core::negotiation::{MediaType, NegotiationContext, NegotiationStrategyRegistry};
 use reinhardt_http::{Handler, Middleware};
 use reinhardt_http::{Request, Response};
+use serde_json::Value;
+use std::collections::HashMap;
 use std::sync::Arc;
 
-use super::renderer::{ApiContext, BrowsableApiRenderer};
+use super::renderer::{ApiContext, BrowsableApiRenderer, ContextProcessor};
 
 /// Middleware configuration for Browsable API
 #[derive(Debug, Clone)]
@@ -36,10 +39,14 @@ impl Default for BrowsableApiConfig {
 /// Middleware for serving browsable API HTML responses
 ///
 /// This middleware automatically converts API responses to browsable HTML
-/// when the request is from a web browser (based on Accept header).
+/// when the request is from a web browser. The choice between the JSON and
+/// HTML renderers is made by a [`NegotiationStrategyRegistry`], so
+/// strategies can override the Accept header for particular clients.
 pub struct BrowsableApiMiddleware {
 	config: BrowsableApiConfig,
 	renderer: BrowsableApiRenderer,
+	context_processors: Vec<ContextProcessor>,
+	negotiation: NegotiationStrategyRegistry,
 }
 
 impl BrowsableApiMiddleware {
@@ -56,6 +63,8 @@ impl BrowsableApiMiddleware {
 		Self {
 			config: BrowsableApiConfig::default(),
 			renderer: BrowsableApiRenderer::new(),
+			context_processors: Vec::new(),
+			negotiation: NegotiationStrategyRegistry::new(),
 		}
 	}
 
@@ -82,18 +91,70 @@ impl BrowsableApiMiddleware {
 		Self {
 			config,
 			renderer: BrowsableApiRenderer::new(),
+			context_processors: Vec::new(),
+			negotiation: NegotiationStrategyRegistry::new(),
 		}
 	}
 
-	/// Check if the request prefers HTML response
-	fn prefers_html(request: &Request) -> bool {
-		if let Some(accept) = request.headers.get("Accept")
-			&& let Ok(accept_str) = accept.to_str()
-		{
-			// Check if Accept header contains text/html
-			return accept_str.contains("text/html");
+	/// Add a context processor whose variables are available to the template
+	///
+	/// Processors run after the handler, so they see what it added to the
+	/// request extensions, such as flash messages.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_rest::browsable_api::middleware::BrowsableApiMiddleware;
+	///
+	/// let middleware = BrowsableApiMiddleware::new()
+	///     .with_context_processor(reinhardt_middleware::context_processor);
+	/// ```
+	pub fn with_context_processor(
+		mut self,
+		processor: impl Fn(&Request) -> HashMap<String, Value> + Send + Sync + 'static,
+	) -> Self {
+		self.context_processors.push(Arc::new(processor));
+		self
+	}
+
+	/// Select between the JSON and HTML renderers with `negotiation`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_core::negotiation::{
+	///     IgnoreAcceptStrategy, MediaType, NegotiationStrategyRegistry,
+	/// };
+	/// use reinhardt_rest::browsable_api::middleware::BrowsableApiMiddleware;
+	///
+	/// // Scripted clients always get JSON
+	/// let middleware = BrowsableApiMiddleware::new().with_negotiation(
+	///     NegotiationStrategyRegistry::new().with_strategy(
+	///         IgnoreAcceptStrategy::new(["curl"])
+	///             .with_media_type(MediaType::new("application", "json")),
+	///     ),
+	/// );
+	/// ```
+	pub fn with_negotiation(mut self, negotiation: NegotiationStrategyRegistry) -> Self {
+		self.negotiation = negotiation;
+		self
+	}
+
+	/// Check if the negotiated renderer is the HTML one
+	///
+	/// Requests without an Accept header get JSON.
+	fn prefers_html(&self, request: &Request) -> bool {
+		let ctx = NegotiationContext::from_headers(&request.headers);
+		if ctx.accept.is_none() {
+			return false;
 		}
-		false
+		let renderers = [
+			MediaType::new("application", "json"),
+			MediaType::new("text", "html"),
+		];
+		self.negotiation
+			.select_renderer(&ctx, &renderers)
+			.is_ok_and(|(media_type, _)| media_type.subtype == "html")
 	}
 
 	/// Check if the response is JSON
@@ -112,6 +173,7 @@ impl BrowsableApiMiddleware {
 		request_uri: &Uri,
 		request_method: &Method,
 		response: Response,
+		extra: &HashMap<String, Value>,
 	) -> reinhardt_core::exception::Result<Response> {
 		// Parse JSON response
 		let json_body: serde_json::Value = serde_json::from_slice(&response.body).map_err(|e| {
@@ -145,7 +207,7 @@ impl BrowsableApiMiddleware {
 		};
 
 		// Render HTML
-		let html = self.renderer.render(&context).map_err(|e| {
+		let html = self.renderer.render_with(&context, extra).map_err(|e| {
 			reinhardt_core::exception::Error::Other(anyhow::anyhow!("Failed to render HTML: {}", e))
 		})?;
 
@@ -170,18 +232,38 @@ impl Middleware for BrowsableApiMiddleware {
 			return handler.handle(request).await;
 		}
 
-		let prefers_html = Self::prefers_html(&request);
+		let prefers_html = self.prefers_html(&request);
 
 		// Extract request info before moving request
 		let request_uri = request.uri.clone();
 		let request_method = request.method.clone();
+		// Context processors read the request after the handler ran; the
+		// extensions are shared, so a body-less copy sees its changes
+		let processed_request = (prefers_html && !self.context_processors.is_empty())
+			.then(|| {
+				let mut copy = Request::builder()
+					.method(request.method.clone())
+					.uri(request.uri.clone())
+					.version(request.version)
+					.headers(request.headers.clone())
+					.build()
+					.ok()?;
+				copy.extensions = request.extensions.clone();
+				Some(copy)
+			})
+			.flatten();
 
 		// Get response from handler
 		let response = handler.handle(request).await?;
 
 		// If client prefers HTML and response is JSON, convert to browsable HTML
 		if prefers_html && Self::is_json_response(&response) {
-			self.convert_to_html_with_info(&request_uri, &request_method, response)
+			let extra: HashMap<String, Value> = processed_request
+				.iter()
+				.flat_map(|request| self.context_processors.iter().map(move |p| p(request)))
+				.flatten()
+				.collect();
+			self.convert_to_html_with_info(&request_uri, &request_method, response, &extra)
 		} else {
 			Ok(response)
 		}
@@ -239,6 +321,96 @@ mod tests {
 		assert!(body.contains("test"), "Missing 'test' in body");
 	}
 
+	#[tokio::test]
+	async fn test_middleware_renders_flash_messages() {
+		use reinhardt_middleware::messages::{
+			MessageLevel, MessageMiddleware, SessionStorage, add_message,
+		};
+
+		struct AddMessageHandler;
+
+		#[async_trait]
+		impl Handler for AddMessageHandler {
+			async fn handle(&self, request: Request) -> Result<Response> {
+				add_message(&request, MessageLevel::Success, "Saved <item>").unwrap();
+				TestHandler.handle(request).await
+			}
+		}
+
+		let browsable = Arc::new(
+			BrowsableApiMiddleware::new()
+				.with_context_processor(reinhardt_middleware::context_processor),
+		);
+		let handler = Arc::new(
+			reinhardt_http::MiddlewareChain::new(Arc::new(AddMessageHandler))
+				.with_middleware(browsable),
+		);
+		let messages = MessageMiddleware::new(Arc::new(SessionStorage::new()));
+
+		let mut headers = HeaderMap::new();
+		headers.insert("Accept", "text/html".parse().unwrap());
+		let request = Request::builder()
+			.method(Method::GET)
+			.uri("/api/test")
+			.version(Version::HTTP_11)
+			.headers(headers)
+			.body(Bytes::new())
+			.build()
+			.unwrap();
+
+		let response = messages.process(request, handler).await.unwrap();
+
+		let body = String::from_utf8(response.body.to_vec()).unwrap();
+		assert!(
+			body.contains(r#"<li class="success">Saved &lt;item&gt;</li>"#),
+			"Missing flash message in body: {}",
+			body
+		);
+	}
+
+	#[tokio::test]
+	async fn test_middleware_negotiation_strategy_overrides_accept() {
+		use reinhardt_core::negotiation::IgnoreAcceptStrategy;
+
+		let middleware = BrowsableApiMiddleware::new().with_negotiation(
+			NegotiationStrategyRegistry::new().with_strategy(
+				IgnoreAcceptStrategy::new(["LegacyClient"])
+					.with_media_type(MediaType::new("application", "json")),
+			),
+		);
+		let request = |user_agent: &str| {
+			let mut headers = HeaderMap::new();
+			headers.insert("Accept", "text/html".parse().unwrap());
+			headers.insert("User-Agent", user_agent.parse().unwrap());
+			Request::builder()
+				.method(Method::GET)
+				.uri("/api/test")
+				.version(Version::HTTP_11)
+				.headers(headers)
+				.body(Bytes::new())
+				.build()
+				.unwrap()
+		};
+
+		let legacy = middleware
+			.process(request("LegacyClient/1.0"), Arc::new(TestHandler))
+			.await
+			.unwrap();
+		let browser = middleware
+			.process(request("Mozilla/5.0"), Arc::new(TestHandler))
+			.await
+			.unwrap();
+
+		assert_eq!(
+			legacy.headers.get("content-type").unwrap(),
+			"application/json"
+		);
+		assert_eq!(
+			browser.headers.get("content-type").unwrap(),
+			"text/html; charset=utf-8"
+		);
+	}
+
 	#[tokio::test]
 	async fn test_middleware_with_json_accept() {
 		let middleware = BrowsableApiMiddleware::new();
@@ -339,7 +511,7 @@ mod tests {
 			.build()
 			.unwrap();
 
-		assert!(BrowsableApiMiddleware::prefers_html(&request));
+		assert!(BrowsableApiMiddleware::new().prefers_html(&request));
 	}
 
 	#[tokio::test]
@@ -356,7 +528,7 @@ mod tests {
 			.build()
 			.unwrap();
 
-		assert!(!BrowsableApiMiddleware::prefers_html(&request));
+		assert!(!BrowsableApiMiddleware::new().prefers_html(&request));
 	}
 
 	#[tokio::test]
@@ -370,6 +542,6 @@ mod tests {
 			.build()
 			.unwrap();
 
-		assert!(!BrowsableApiMiddleware::prefers_html(&request));
+		assert!(!BrowsableApiMiddleware::new().prefers_html(&request));
 	}
 }
@@ -1,5 +1,7 @@
+use reinhardt_http::Request;
 use serde::Serialize;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tera::Tera;
 
@@ -23,6 +25,13 @@ impl From<tera::Error> for BrowsableApiError {
 
 pub type BrowsableApiResult<T> = Result<T, BrowsableApiError>;
 
+/// Computes extra template variables from the request, like Django's context
+/// processors
+///
+/// `reinhardt_middleware::context_processor` has this signature and adds the
+/// pending flash messages.
+pub type ContextProcessor = Arc<dyn Fn(&Request) -> HashMap<String, Value> + Send + Sync>;
+
 /// Context for rendering browsable API HTML
 #[derive(Debug, Clone, Serialize)]
 pub struct ApiContext {
@@ -104,8 +113,21 @@ impl BrowsableApiRenderer {
 	/// Render API context as HTML
 	///
 	pub fn render(&self, context: &ApiContext) -> BrowsableApiResult<String> {
+		self.render_with(context, &HashMap::new())
+	}
+
+	/// Render API context as HTML with extra template variables
+	///
+	/// The variables are typically produced by [`ContextProcessor`]s. They
+	/// cannot replace the variables of the API context.
+	pub fn render_with(
+		&self,
+		context: &ApiContext,
+		extra: &HashMap<String, Value>,
+	) -> BrowsableApiResult<String> {
 		// Convert the context to a Tera Context
-		let mut tera_context = tera::Context::from_serialize(context)?;
+		let mut tera_context = tera::Context::from_serialize(extra)?;
+		tera_context.extend(tera::Context::from_serialize(context)?);
 
 		// Add formatted JSON
 		let formatted_json = serde_json::to_string_pretty(&context.response_data)?;
@@ -225,6 +247,8 @@ impl BrowsableApiRenderer {
         .headers table { width: 100%; border-collapse: collapse; }
         .headers th, .headers td { text-align: left; padding: 8px; border-bottom: 1px solid #e0e0e0; }
         .headers th { font-weight: 500; background: #f5f5f5; }
+        .messages { list-style: none; margin: 0 0 20px 0; padding: 0; }
+        .messages li { padding: 10px 15px; margin-bottom: 8px; border-radius: 4px; background: #e3f2fd; }
     </style>
 </head>
 <body>
@@ -235,6 +259,14 @@ impl BrowsableApiRenderer {
         </div>
 
         <div class="content">
+            {% if messages %}
+            <ul class="messages">
+                {% for message in messages %}
+                <li class="{{ message.tags }}">{{ message.html | safe }}</li>
+                {% endfor %}
+            </ul>
+            {% endif %}
+
             <div class="allowed-methods">
                 <strong>Allowed methods:</strong>
                 {% for method_name in allowed_methods %}
@@ -15,9 +15,13 @@
 //! - **Customization**: Override and extend generated schemas
 //! - **Swagger UI**: Built-in Swagger UI and ReDoc integration
 //! - **YAML/JSON**: Export schemas in both formats
+//! - **TypeScript Client**: Typed interfaces and `fetch` wrappers generated from the schema
 //! - **Schema Registry**: Centralized schema management with `$ref` references
 //! - **Enum Support**: Tagged, adjacently tagged, and untagged enum handling
 //! - **Serde Integration**: Support for `#[serde(rename)]`, `#[serde(skip)]`, and more
+//! - **Security Schemes**: HTTP bearer/basic, API key and OAuth2 schemes with per-operation requirements
+//! - **Examples**: Declared or auto-generated request/response examples for Swagger UI
+//! - **Callbacks and Webhooks**: Document requests the API sends back to its consumers
 //!
 //! ## Quick Start
 //!
@@ -51,7 +55,7 @@
 //!     id: i64,
 //!     username: String,
 //!     email: String,
-//!     #[schema(example = "true")]
+//!     #[schema(example = true)]
 //!     is_active: bool,
 //! }
 //! ```
@@ -60,7 +64,7 @@
 //!
 //! Field-level attributes:
 //!
-//! - `#[schema(example = "...")]`: Provide example value for documentation
+//! - `#[schema(example = ...)]`: Provide example value for documentation (string, number or boolean literal)
 //! - `#[schema(skip)]`: Exclude field from schema
 //! - `#[schema(rename = "...")]`: Rename field in schema
 //! - `#[schema(description = "...")]`: Add field description
@@ -70,6 +74,7 @@
 //! Container-level attributes:
 //!
 //! - `#[schema(rename_all = "...")]`: Apply case transformation (camelCase, snake_case, etc.)
+//! - `#[schema(example = "...")]`: Example for the whole type, as a JSON string
 //!
 //! ### Serde Integration
 //!
@@ -151,6 +156,7 @@ pub mod config;
 pub mod endpoint_inspector;
 pub mod endpoints;
 pub mod enum_schema;
+pub mod examples;
 pub mod generator;
 // Allow module_inception: Re-exporting openapi submodule from openapi.rs
 // is intentional for compatibility with existing imports (`reinhardt_rest::openapi::OpenAPI`)
@@ -161,20 +167,23 @@ pub mod registry;
 pub mod schema_registration;
 pub mod serde_attrs;
 pub mod swagger;
+pub mod typescript;
 
 use thiserror::Error;
 
 pub use auto_schema::{SchemaObject, ToSchema};
 pub use config::OpenApiConfig;
-pub use endpoint_inspector::EndpointInspector;
-pub use endpoints::generate_openapi_schema;
+pub use endpoint_inspector::{EndpointInspector, RouteEntry};
+pub use endpoints::{generate_openapi_schema, generate_openapi_schema_for_routes};
 pub use enum_schema::{EnumSchemaBuilder, EnumTagging};
 pub use generator::SchemaGenerator;
 pub use openapi::{
-	ArrayBuilder, Components, ComponentsExt, Header, Info, MediaType, ObjectBuilder, OpenApiSchema,
-	OpenApiSchemaExt, Operation, OperationExt, Parameter, ParameterExt,
-	ParameterIn as ParameterLocation, PathItem, PathItemExt, RefOr, RequestBody, Required,
-	Response, ResponsesExt, Schema, SchemaExt, Server, Tag,
+	ArrayBuilder, AuthorizationCode, ClientCredentials, Components, ComponentsExt, Example,
+	ExampleBuilder, Flow, Header, HttpMethod, Implicit, Info, MediaType, ObjectBuilder,
+	OpenApiSchema, OpenApiSchemaExt, Operation, OperationExt, Parameter, ParameterExt,
+	ParameterIn as ParameterLocation, Password, PathItem, PathItemExt, RefOr, RequestBody,
+	Required, Response, ResponsesExt, Schema, SchemaExt, Scopes, SecurityRequirement,
+	SecurityScheme, SecuritySchemeExt, Server, Tag,
 };
 pub use param_metadata::{CookieParam, HeaderParam, ParameterMetadata, PathParam, QueryParam};
 pub use registry::SchemaRegistry;
@@ -182,10 +191,12 @@ pub use reinhardt_openapi_macros::Schema;
 pub use schema_registration::SchemaRegistration;
 pub use serde_attrs::{FieldMetadata, RenameAll, SchemaBuilderExt};
 pub use swagger::{RedocUI, SwaggerUI};
+pub use typescript::TypeScriptGenerator;
 pub use utoipa::Number;
 
-// Re-export utoipa and inventory for macro-generated code
+// Re-export utoipa, inventory and serde_json for macro-generated code
 pub use inventory;
+pub use serde_json;
 pub use utoipa;
 
 #[derive(Debug, Error)]
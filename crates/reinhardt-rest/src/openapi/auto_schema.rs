@@ -55,6 +55,14 @@ pub trait ToSchema {
 	fn schema_name() -> Option<String> {
 		None
 	}
+
+	/// Get an example value for documentation
+	///
+	/// Defaults to the example declared on [`schema`](Self::schema), or one
+	/// generated from its structure when none is declared.
+	fn example() -> serde_json::Value {
+		crate::openapi::examples::generate_example(&Self::schema())
+	}
 }
 
 /// A complete schema object with metadata
@@ -1,7 +1,8 @@
 //! Endpoint Inspector for Function-Based Routes
 //!
 //! Extracts endpoint metadata from HTTP method decorator macros
-//! (`#[get]`, `#[post]`, etc.) using the inventory crate.
+//! (`#[get]`, `#[post]`, etc.) using the inventory crate, and builds
+//! OpenAPI paths for the routes registered on a router.
 
 use super::SchemaError;
 use indexmap::IndexMap;
@@ -35,6 +36,45 @@ impl Default for InspectorConfig {
 	}
 }
 
+/// HTTP methods documented for routes that accept any method
+const ALL_METHODS: [&str; 5] = ["GET", "POST", "PUT", "PATCH", "DELETE"];
+
+/// A route registered on a router, as seen by schema generation
+///
+/// Routers convert their route tables into this form so that
+/// [`EndpointInspector::extract_route_paths`] can document them without
+/// depending on the router crate.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RouteEntry {
+	/// Full path pattern, e.g. `/api/users/{id}/`
+	pub path: String,
+	/// HTTP methods; empty means the route accepts any method
+	pub methods: Vec<String>,
+	/// Route name used for URL reversal
+	pub name: Option<String>,
+}
+
+impl RouteEntry {
+	/// Create a route entry for `path` accepting `methods`
+	pub fn new<I, M>(path: impl Into<String>, methods: I) -> Self
+	where
+		I: IntoIterator<Item = M>,
+		M: Into<String>,
+	{
+		Self {
+			path: path.into(),
+			methods: methods.into_iter().map(Into::into).collect(),
+			name: None,
+		}
+	}
+
+	/// Set the route name
+	pub fn with_name(mut self, name: impl Into<String>) -> Self {
+		self.name = Some(name.into());
+		self
+	}
+}
+
 /// Endpoint inspector for function-based routes
 ///
 /// Extracts endpoint information from HTTP method decorator macros
@@ -92,6 +132,150 @@ impl EndpointInspector {
 		Ok(paths)
 	}
 
+	/// Build OpenAPI paths for the routes registered on a router
+	///
+	/// Every route contributes one operation per method. When an endpoint
+	/// registered through `#[get]`, `#[post]`, etc. serves the same method at
+	/// the end of the route path, its metadata (operation id, tags, request
+	/// body) is used; other routes get a generic operation with their path
+	/// parameters. Methods other than GET/POST/PUT/PATCH/DELETE are skipped.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_rest::openapi::endpoint_inspector::{EndpointInspector, RouteEntry};
+	///
+	/// let inspector = EndpointInspector::new();
+	/// let paths = inspector
+	///     .extract_route_paths(&[RouteEntry::new("/api/users/{id}/", ["GET", "DELETE"])])
+	///     .unwrap();
+	///
+	/// let item = &paths["/api/users/{id}/"];
+	/// assert!(item.get.is_some());
+	/// assert!(item.delete.is_some());
+	/// ```
+	pub fn extract_route_paths(
+		&self,
+		routes: &[RouteEntry],
+	) -> Result<IndexMap<String, PathItem>, SchemaError> {
+		let endpoints: Vec<&EndpointMetadata> = inventory::iter::<EndpointMetadata>().collect();
+		let mut path_groups: IndexMap<String, Vec<(HttpMethod, Operation)>> = IndexMap::new();
+
+		for route in routes {
+			let path = self.normalize_route_path(&route.path);
+			let methods: Vec<&str> = if route.methods.is_empty() {
+				ALL_METHODS.to_vec()
+			} else {
+				route.methods.iter().map(String::as_str).collect()
+			};
+
+			for method in methods {
+				let Ok(http_method) = self.metadata_method_to_http_method(method) else {
+					continue;
+				};
+
+				// Prefer the most specific decorated endpoint for this route
+				let metadata = endpoints
+					.iter()
+					.filter(|metadata| {
+						metadata.method == method
+							&& path.ends_with(&self.normalize_path(metadata.path))
+					})
+					.max_by_key(|metadata| metadata.path.len());
+
+				let operation = match metadata {
+					Some(metadata) => {
+						let mut parameters = self.extract_path_parameters(metadata.path)?;
+						parameters.extend(self.untyped_path_parameters(&path, &parameters));
+						self.create_operation(metadata, parameters)
+					}
+					None => self.create_route_operation(route, method, &path),
+				};
+
+				let operations = path_groups.entry(path.clone()).or_default();
+				operations.retain(|(existing, _)| *existing != http_method);
+				operations.push((http_method, operation));
+			}
+		}
+
+		let mut paths = IndexMap::new();
+		for (path, operations) in path_groups {
+			let mut builder = PathItemBuilder::new();
+			for (http_method, operation) in operations {
+				builder = builder.operation(http_method, operation);
+			}
+			paths.insert(path, builder.build());
+		}
+
+		Ok(paths)
+	}
+
+	/// Normalize a router path pattern to OpenAPI format
+	///
+	/// Handles typed parameters (`{<int:id>}`), angle-bracket parameters
+	/// (`<id>`) and catch-all parameters (`{*rest}`) in addition to plain
+	/// `{id}` segments.
+	fn normalize_route_path(&self, path: &str) -> String {
+		let path = self.normalize_path(path);
+		let angle = Regex::new(r"<([^<>:]+)>").unwrap();
+		let catch_all = Regex::new(r"\{\*([^}]+)\}").unwrap();
+		let path = angle.replace_all(&path, "{$1}");
+		catch_all.replace_all(&path, "{$1}").to_string()
+	}
+
+	/// String path parameters for `{name}` segments not covered by `declared`
+	fn untyped_path_parameters(&self, path: &str, declared: &[Parameter]) -> Vec<Parameter> {
+		let re = Regex::new(r"\{([^{}]+)\}").unwrap();
+		re.captures_iter(path)
+			.map(|caps| caps[1].to_string())
+			.filter(|name| !declared.iter().any(|param| param.name == *name))
+			.map(|name| {
+				ParameterBuilder::new()
+					.name(name)
+					.parameter_in(ParameterIn::Path)
+					.required(utoipa::openapi::Required::True)
+					.schema(Some(Schema::Object(
+						ObjectBuilder::new().schema_type(Type::String).build(),
+					)))
+					.build()
+			})
+			.collect()
+	}
+
+	/// Create an Operation for a route without decorator metadata
+	fn create_route_operation(&self, route: &RouteEntry, method: &str, path: &str) -> Operation {
+		let method_lower = method.to_lowercase();
+		let operation_id = match &route.name {
+			Some(name) if route.methods.len() == 1 => name.clone(),
+			Some(name) => format!("{}_{}", name, method_lower),
+			None => {
+				let slug: Vec<&str> = path
+					.split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+					.filter(|part| !part.is_empty())
+					.collect();
+				format!("{}_{}", method_lower, slug.join("_"))
+			}
+		};
+
+		let mut builder = OperationBuilder::new()
+			.operation_id(Some(operation_id))
+			.summary(Some(format!("{} {}", method, path)))
+			.tags(Some(vec![self.config.default_tag.clone()]));
+
+		for param in self.untyped_path_parameters(path, &[]) {
+			builder = builder.parameter(param);
+		}
+
+		builder
+			.response(
+				"200",
+				ResponseBuilder::new()
+					.description("Successful response")
+					.build(),
+			)
+			.build()
+	}
+
 	/// Normalize Django-style path to OpenAPI format
 	///
 	/// Converts patterns like:
@@ -459,4 +643,60 @@ mod tests {
 		// Test invalid method
 		assert!(inspector.metadata_method_to_http_method("INVALID").is_err());
 	}
+
+	#[test]
+	fn test_normalize_route_path() {
+		let inspector = EndpointInspector::new();
+
+		assert_eq!(
+			inspector.normalize_route_path("/users/{<int:id>}/"),
+			"/users/{id}/"
+		);
+		assert_eq!(
+			inspector.normalize_route_path("/users/<id>/"),
+			"/users/{id}/"
+		);
+		assert_eq!(
+			inspector.normalize_route_path("/static/{*path}"),
+			"/static/{path}"
+		);
+		assert_eq!(
+			inspector.normalize_route_path("/users/{id}/"),
+			"/users/{id}/"
+		);
+	}
+
+	#[test]
+	fn test_extract_route_paths() {
+		let inspector = EndpointInspector::new();
+		let routes = vec![
+			RouteEntry::new("/api/articles/", ["GET", "POST"]),
+			RouteEntry::new("/api/articles/{slug}/", ["GET", "HEAD"]).with_name("article-detail"),
+			RouteEntry::new("/api/legacy/", Vec::<String>::new()),
+		];
+
+		let paths = inspector.extract_route_paths(&routes).unwrap();
+
+		let collection = &paths["/api/articles/"];
+		assert!(collection.get.is_some());
+		assert!(collection.post.is_some());
+
+		let detail = paths["/api/articles/{slug}/"].get.as_ref().unwrap();
+		assert_eq!(detail.operation_id.as_deref(), Some("article-detail_get"));
+		let params = detail.parameters.as_ref().unwrap();
+		assert_eq!(params.len(), 1);
+		assert_eq!(params[0].name, "slug");
+		assert!(paths["/api/articles/{slug}/"].head.is_none());
+
+		// Method-agnostic routes are documented for every common method
+		let legacy = &paths["/api/legacy/"];
+		assert!(legacy.get.is_some());
+		assert!(legacy.put.is_some());
+		assert!(legacy.patch.is_some());
+		assert!(legacy.delete.is_some());
+		assert_eq!(
+			legacy.post.as_ref().unwrap().operation_id.as_deref(),
+			Some("post_api_legacy")
+		);
+	}
 }
@@ -18,6 +18,7 @@
 //! # }
 //! ```
 
+use super::endpoint_inspector::RouteEntry;
 use super::generator::SchemaGenerator;
 use super::registry::get_all_schemas;
 use super::swagger::{RedocUI, SwaggerUI};
@@ -51,16 +52,7 @@ static REDOC_UI: LazyLock<RedocUI> = LazyLock::new(|| RedocUI::new(OPENAPI_SCHEM
 /// This function is public to allow `OpenApiRouter` wrapper to generate its own
 /// schema instance at wrap time.
 pub fn generate_openapi_schema() -> OpenApiSchema {
-	let mut generator = SchemaGenerator::new()
-		.title("API Documentation")
-		.version("1.0.0")
-		.description("Auto-generated API documentation");
-
-	// Register all schemas from global registry
-	let registry = generator.registry();
-	for (name, schema) in get_all_schemas().iter() {
-		registry.register(*name, schema.clone());
-	}
+	let mut generator = registry_generator();
 
 	// Add function-based endpoints from HTTP method decorators (#[get], #[post], etc.)
 	// Collects EndpointMetadata from global inventory via EndpointInspector
@@ -75,6 +67,44 @@ pub fn generate_openapi_schema() -> OpenApiSchema {
 		.expect("Failed to generate OpenAPI schema")
 }
 
+/// Generate OpenAPI schema for the routes registered on a router
+///
+/// Like [`generate_openapi_schema`], but paths come from `routes` rather
+/// than from decorator metadata alone, so ViewSets, class-based views and
+/// undecorated functions are documented too. Decorated endpoints still
+/// provide their operation metadata where they match a route.
+///
+/// # Example
+///
+/// ```rust
+/// use reinhardt_rest::openapi::{RouteEntry, generate_openapi_schema_for_routes};
+///
+/// let schema = generate_openapi_schema_for_routes(&[RouteEntry::new("/health/", ["GET"])]);
+/// assert!(schema.paths.paths.contains_key("/health/"));
+/// ```
+pub fn generate_openapi_schema_for_routes(routes: &[RouteEntry]) -> OpenApiSchema {
+	registry_generator()
+		.add_routes(routes)
+		.generate()
+		.expect("Failed to generate OpenAPI schema")
+}
+
+/// Schema generator pre-populated from the global schema registry
+fn registry_generator() -> SchemaGenerator {
+	let mut generator = SchemaGenerator::new()
+		.title("API Documentation")
+		.version("1.0.0")
+		.description("Auto-generated API documentation");
+
+	// Register all schemas from global registry
+	let registry = generator.registry();
+	for (name, schema) in get_all_schemas().iter() {
+		registry.register(*name, schema.clone());
+	}
+
+	generator
+}
+
 /// Swagger UI endpoint handler
 ///
 /// Serves the Swagger UI HTML page for interactive API documentation.
@@ -0,0 +1,330 @@
+//! Example values for OpenAPI schemas
+//!
+//! Builds plausible example payloads from a schema's structure so that
+//! documentation UIs such as Swagger UI show usable request and response
+//! bodies. Examples declared on a schema (for instance with
+//! `#[schema(example = ...)]`) always take precedence over generated values.
+//!
+//! # Example
+//!
+//! ```rust
+//! use reinhardt_rest::openapi::examples::generate_example;
+//! use reinhardt_rest::openapi::{Schema, SchemaExt};
+//!
+//! let schema = Schema::object_with_properties(
+//!     vec![("id", Schema::integer()), ("name", Schema::string())],
+//!     vec!["id", "name"],
+//! );
+//!
+//! assert_eq!(
+//!     generate_example(&schema),
+//!     serde_json::json!({"id": 0, "name": "string"})
+//! );
+//! ```
+
+use super::{Components, RefOr, Schema};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use utoipa::openapi::schema::{AdditionalProperties, ArrayItems, SchemaType, Type};
+
+/// Nesting depth after which generation stops, guarding against recursive schemas
+const MAX_DEPTH: usize = 8;
+
+/// Prefix of local component references
+const COMPONENT_REF_PREFIX: &str = "#/components/schemas/";
+
+/// Generate an example value for a schema
+///
+/// `$ref` references cannot be resolved without components and produce
+/// `null`; use [`generate_example_with_components`] for documents that
+/// reference shared schemas.
+pub fn generate_example(schema: &Schema) -> Value {
+	ExampleBuilder::default().schema(schema, 0)
+}
+
+/// Generate an example value, resolving `$ref` references against `components`
+pub fn generate_example_with_components(schema: &RefOr<Schema>, components: &Components) -> Value {
+	ExampleBuilder {
+		schemas: Some(&components.schemas),
+	}
+	.ref_or(schema, 0)
+}
+
+/// Set the example declared on a schema
+///
+/// Used by `#[derive(Schema)]` for `#[schema(example = ...)]`. Schema kinds
+/// that cannot carry an example are left unchanged.
+pub fn set_example(schema: &mut Schema, example: Value) {
+	match schema {
+		Schema::Object(object) => object.example = Some(example),
+		Schema::Array(array) => array.example = Some(example),
+		Schema::OneOf(one_of) => one_of.example = Some(example),
+		Schema::AnyOf(any_of) => any_of.example = Some(example),
+		Schema::AllOf(all_of) => all_of.example = Some(example),
+		_ => {}
+	}
+}
+
+#[derive(Default)]
+struct ExampleBuilder<'a> {
+	schemas: Option<&'a BTreeMap<String, RefOr<Schema>>>,
+}
+
+impl ExampleBuilder<'_> {
+	fn ref_or(&self, schema: &RefOr<Schema>, depth: usize) -> Value {
+		match schema {
+			RefOr::T(schema) => self.schema(schema, depth),
+			RefOr::Ref(reference) => {
+				let target = reference
+					.ref_location
+					.strip_prefix(COMPONENT_REF_PREFIX)
+					.and_then(|name| self.schemas?.get(name));
+				match target {
+					Some(target) if depth < MAX_DEPTH => self.ref_or(target, depth + 1),
+					_ => Value::Null,
+				}
+			}
+		}
+	}
+
+	fn schema(&self, schema: &Schema, depth: usize) -> Value {
+		if depth > MAX_DEPTH {
+			return Value::Null;
+		}
+
+		match schema {
+			Schema::Object(object) => {
+				if let Some(example) = &object.example {
+					return example.clone();
+				}
+				if let Some(example) = object.examples.first() {
+					return example.clone();
+				}
+				if let Some(default) = &object.default {
+					return default.clone();
+				}
+				if let Some(value) = object.enum_values.as_ref().and_then(|v| v.first()) {
+					return value.clone();
+				}
+
+				if !object.properties.is_empty() {
+					let properties = object
+						.properties
+						.iter()
+						.map(|(name, property)| (name.clone(), self.ref_or(property, depth + 1)))
+						.collect::<Map<_, _>>();
+					return Value::Object(properties);
+				}
+				if let Some(additional) = object.additional_properties.as_deref() {
+					let mut map = Map::new();
+					if let AdditionalProperties::RefOr(value_schema) = additional {
+						map.insert(
+							"additionalProp1".to_string(),
+							self.ref_or(value_schema, depth + 1),
+						);
+					}
+					return Value::Object(map);
+				}
+
+				let format = object
+					.format
+					.as_ref()
+					.and_then(|format| serde_json::to_value(format).ok());
+				let format = format.as_ref().and_then(Value::as_str);
+				let minimum = object
+					.minimum
+					.as_ref()
+					.and_then(|n| serde_json::to_value(n).ok());
+				primitive_example(&object.schema_type, format, minimum)
+			}
+			Schema::Array(array) => {
+				if let Some(example) = &array.example {
+					return example.clone();
+				}
+				if !array.prefix_items.is_empty() {
+					return Value::Array(
+						array
+							.prefix_items
+							.iter()
+							.map(|item| self.schema(item, depth + 1))
+							.collect(),
+					);
+				}
+				match &array.items {
+					ArrayItems::RefOrSchema(item) => {
+						Value::Array(vec![self.ref_or(item, depth + 1)])
+					}
+					_ => Value::Array(Vec::new()),
+				}
+			}
+			Schema::OneOf(one_of) => {
+				if let Some(example) = &one_of.example {
+					return example.clone();
+				}
+				one_of
+					.items
+					.first()
+					.map_or(Value::Null, |item| self.ref_or(item, depth + 1))
+			}
+			Schema::AnyOf(any_of) => {
+				if let Some(example) = &any_of.example {
+					return example.clone();
+				}
+				any_of
+					.items
+					.first()
+					.map_or(Value::Null, |item| self.ref_or(item, depth + 1))
+			}
+			Schema::AllOf(all_of) => {
+				if let Some(example) = &all_of.example {
+					return example.clone();
+				}
+				// Merge the properties of every part into one object
+				let mut merged = Map::new();
+				for item in &all_of.items {
+					match self.ref_or(item, depth + 1) {
+						Value::Object(map) => merged.extend(map),
+						other if all_of.items.len() == 1 => return other,
+						_ => {}
+					}
+				}
+				Value::Object(merged)
+			}
+			_ => Value::Null,
+		}
+	}
+}
+
+/// Example for a scalar schema, based on its type and format
+fn primitive_example(
+	schema_type: &SchemaType,
+	format: Option<&str>,
+	minimum: Option<Value>,
+) -> Value {
+	let ty = match schema_type {
+		SchemaType::Type(ty) => ty,
+		SchemaType::Array(types) => match types.iter().find(|ty| **ty != Type::Null) {
+			Some(ty) => ty,
+			None => return Value::Null,
+		},
+		SchemaType::AnyValue => return Value::Null,
+	};
+
+	match ty {
+		Type::String => Value::String(
+			match format {
+				Some("date-time") => "2024-01-01T00:00:00Z",
+				Some("date") => "2024-01-01",
+				Some("time") => "12:00:00",
+				Some("email") => "user@example.com",
+				Some("uri") | Some("url") => "https://example.com",
+				Some("uuid") => "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+				Some("ipv4") => "192.0.2.1",
+				Some("ipv6") => "2001:db8::1",
+				Some("hostname") => "example.com",
+				Some("password") => "********",
+				_ => "string",
+			}
+			.to_string(),
+		),
+		Type::Integer => match minimum.as_ref().and_then(Value::as_f64) {
+			Some(min) => Value::from(min.ceil() as i64),
+			None => Value::from(0),
+		},
+		Type::Number => minimum.unwrap_or(Value::from(0.0)),
+		Type::Boolean => Value::Bool(true),
+		Type::Object => Value::Object(Map::new()),
+		Type::Array => Value::Array(Vec::new()),
+		Type::Null => Value::Null,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::openapi::openapi::ComponentsBuilder;
+	use crate::openapi::{ObjectBuilder, SchemaExt};
+	use rstest::rstest;
+	use serde_json::json;
+	use utoipa::openapi::schema::{KnownFormat, SchemaFormat};
+
+	fn string_with_format(format: KnownFormat) -> Schema {
+		Schema::Object(
+			ObjectBuilder::new()
+				.schema_type(SchemaType::Type(Type::String))
+				.format(Some(SchemaFormat::KnownFormat(format)))
+				.build(),
+		)
+	}
+
+	#[rstest]
+	#[case(Schema::string(), json!("string"))]
+	#[case(Schema::integer(), json!(0))]
+	#[case(Schema::boolean(), json!(true))]
+	#[case(Schema::datetime(), json!("2024-01-01T00:00:00Z"))]
+	#[case(string_with_format(KnownFormat::Date), json!("2024-01-01"))]
+	#[case(Schema::array(Schema::string()), json!(["string"]))]
+	fn test_primitive_examples(#[case] schema: Schema, #[case] expected: Value) {
+		assert_eq!(generate_example(&schema), expected);
+	}
+
+	#[rstest]
+	fn test_declared_values_take_precedence() {
+		let mut age = Schema::integer();
+		set_example(&mut age, json!(42));
+		let schema = Schema::Object(
+			ObjectBuilder::new()
+				.schema_type(SchemaType::Type(Type::Object))
+				.property(
+					"status",
+					ObjectBuilder::new()
+						.schema_type(SchemaType::Type(Type::String))
+						.enum_values(Some(["active", "inactive"])),
+				)
+				.property("age", age)
+				.build(),
+		);
+
+		assert_eq!(
+			generate_example(&schema),
+			json!({"status": "active", "age": 42})
+		);
+	}
+
+	#[rstest]
+	fn test_references_are_resolved_with_components() {
+		let components = ComponentsBuilder::new()
+			.schema(
+				"User",
+				Schema::object_with_properties(vec![("name", Schema::string())], vec!["name"]),
+			)
+			.build();
+		let reference = RefOr::Ref(utoipa::openapi::Ref::from_schema_name("User"));
+
+		assert_eq!(
+			generate_example_with_components(&reference, &components),
+			json!({"name": "string"})
+		);
+		assert_eq!(
+			generate_example(&Schema::array(Schema::object())),
+			json!([{}])
+		);
+	}
+
+	#[rstest]
+	fn test_recursive_references_terminate() {
+		let components = ComponentsBuilder::new()
+			.schema(
+				"Node",
+				ObjectBuilder::new()
+					.schema_type(SchemaType::Type(Type::Object))
+					.property("next", utoipa::openapi::Ref::from_schema_name("Node")),
+			)
+			.build();
+		let reference = RefOr::Ref(utoipa::openapi::Ref::from_schema_name("Node"));
+
+		let example = generate_example_with_components(&reference, &components);
+
+		assert!(example["next"]["next"].is_object());
+	}
+}
@@ -3,10 +3,41 @@
 //! This module provides the main schema generator that integrates with the schema registry,
 //! enum schema builder, and serde attributes support.
 
-use super::endpoint_inspector::EndpointInspector;
+use super::endpoint_inspector::{EndpointInspector, RouteEntry};
+use super::examples::generate_example_with_components;
 use super::registry::SchemaRegistry;
-use super::{OpenApiSchema, PathItem, SchemaError};
+use super::typescript::TypeScriptGenerator;
+use super::{
+	Components, Example, HttpMethod, MediaType, OpenApiSchema, Operation, PathItem, RefOr,
+	SchemaError, SecurityRequirement, SecurityScheme,
+};
 use indexmap::IndexMap;
+use serde_json::{Map, Value};
+use utoipa::openapi::extensions::Extensions;
+
+/// Part of an operation an example payload belongs to
+enum ExampleTarget {
+	Request,
+	Response(String),
+}
+
+/// Named example attached to an operation's request or response body
+struct OperationExample {
+	path: String,
+	method: HttpMethod,
+	target: ExampleTarget,
+	name: String,
+	example: Example,
+}
+
+/// Callback request documented on an operation
+struct OperationCallback {
+	path: String,
+	method: HttpMethod,
+	name: String,
+	expression: String,
+	item: PathItem,
+}
 
 /// Schema generator for OpenAPI schemas
 ///
@@ -14,6 +45,9 @@ use indexmap::IndexMap;
 /// - Schema registry for component reuse
 /// - Advanced enum handling
 /// - Serde attributes integration
+/// - Security schemes with global and per-operation requirements
+/// - Request/response examples, declared or generated from schemas
+/// - Operation callbacks and top-level webhooks
 ///
 /// # Example
 ///
@@ -44,6 +78,13 @@ pub struct SchemaGenerator {
 	description: Option<String>,
 	registry: SchemaRegistry,
 	paths: IndexMap<String, PathItem>,
+	security_schemes: IndexMap<String, SecurityScheme>,
+	security: Vec<SecurityRequirement>,
+	operation_security: Vec<(String, HttpMethod, SecurityRequirement)>,
+	operation_examples: Vec<OperationExample>,
+	auto_examples: bool,
+	operation_callbacks: Vec<OperationCallback>,
+	webhooks: IndexMap<String, PathItem>,
 }
 
 impl SchemaGenerator {
@@ -63,6 +104,13 @@ impl SchemaGenerator {
 			description: None,
 			registry: SchemaRegistry::new(),
 			paths: IndexMap::new(),
+			security_schemes: IndexMap::new(),
+			security: Vec::new(),
+			operation_security: Vec::new(),
+			operation_examples: Vec::new(),
+			auto_examples: false,
+			operation_callbacks: Vec::new(),
+			webhooks: IndexMap::new(),
 		}
 	}
 
@@ -143,6 +191,314 @@ impl SchemaGenerator {
 		&self.registry
 	}
 
+	/// Add a path item to the schema, replacing any existing item for `path`
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_rest::openapi::generator::SchemaGenerator;
+	/// use reinhardt_rest::openapi::{HttpMethod, OperationExt, Operation, PathItem};
+	///
+	/// let generator = SchemaGenerator::new()
+	///     .add_path("/users", PathItem::new(HttpMethod::Get, Operation::create()));
+	///
+	/// let schema = generator.generate().unwrap();
+	/// assert!(schema.paths.paths.contains_key("/users"));
+	/// ```
+	pub fn add_path(mut self, path: impl Into<String>, item: PathItem) -> Self {
+		self.paths.insert(path.into(), item);
+		self
+	}
+
+	/// Declare a security scheme in `components.securitySchemes`
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_rest::openapi::generator::SchemaGenerator;
+	/// use reinhardt_rest::openapi::{SecurityScheme, SecuritySchemeExt};
+	///
+	/// let generator = SchemaGenerator::new()
+	///     .security_scheme("bearerAuth", SecurityScheme::bearer_jwt())
+	///     .security_scheme("apiKey", SecurityScheme::api_key_header("X-API-Key"));
+	///
+	/// let json = generator.to_json().unwrap();
+	/// assert!(json.contains("\"bearerFormat\": \"JWT\""));
+	/// ```
+	pub fn security_scheme(mut self, name: impl Into<String>, scheme: SecurityScheme) -> Self {
+		self.security_schemes.insert(name.into(), scheme);
+		self
+	}
+
+	/// Add a security requirement applying to every operation
+	///
+	/// Each call adds an alternative; a request must satisfy one of them.
+	/// Operations with their own requirements (see
+	/// [`operation_security`](Self::operation_security)) override this.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_rest::openapi::generator::SchemaGenerator;
+	/// use reinhardt_rest::openapi::{SecurityRequirement, SecurityScheme, SecuritySchemeExt};
+	///
+	/// let generator = SchemaGenerator::new()
+	///     .security_scheme("bearerAuth", SecurityScheme::bearer_jwt())
+	///     .security(SecurityRequirement::new("bearerAuth", Vec::<String>::new()));
+	///
+	/// let schema = generator.generate().unwrap();
+	/// assert_eq!(schema.security.unwrap().len(), 1);
+	/// ```
+	pub fn security(mut self, requirement: SecurityRequirement) -> Self {
+		self.security.push(requirement);
+		self
+	}
+
+	/// Add a security requirement to a single operation
+	///
+	/// The operation must exist when the schema is generated. Each call adds
+	/// an alternative, as with [`security`](Self::security); pass
+	/// `SecurityRequirement::default()` to mark an operation as public.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_rest::openapi::generator::SchemaGenerator;
+	/// use reinhardt_rest::openapi::{
+	///     AuthorizationCode, Flow, HttpMethod, Operation, OperationExt, PathItem, Scopes,
+	///     SecurityRequirement, SecurityScheme, SecuritySchemeExt,
+	/// };
+	///
+	/// let oauth = SecurityScheme::oauth2([Flow::AuthorizationCode(AuthorizationCode::new(
+	///     "https://auth.example.com/authorize",
+	///     "https://auth.example.com/token",
+	///     Scopes::from_iter([("posts:write", "Publish posts")]),
+	/// ))]);
+	///
+	/// let generator = SchemaGenerator::new()
+	///     .add_path("/posts", PathItem::new(HttpMethod::Post, Operation::create()))
+	///     .security_scheme("oauth", oauth)
+	///     .operation_security(
+	///         "/posts",
+	///         HttpMethod::Post,
+	///         SecurityRequirement::new("oauth", ["posts:write"]),
+	///     );
+	///
+	/// let schema = generator.generate().unwrap();
+	/// let operation = schema.paths.paths["/posts"].post.as_ref().unwrap();
+	/// assert!(operation.security.is_some());
+	/// ```
+	pub fn operation_security(
+		mut self,
+		path: impl Into<String>,
+		method: HttpMethod,
+		requirement: SecurityRequirement,
+	) -> Self {
+		self.operation_security
+			.push((path.into(), method, requirement));
+		self
+	}
+
+	/// Add a named example to an operation's request body
+	///
+	/// The example is added to every media type of the request body. The
+	/// operation and its request body must exist when the schema is generated.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_rest::openapi::generator::SchemaGenerator;
+	/// use reinhardt_rest::openapi::{
+	///     Example, HttpMethod, MediaType, Operation, OperationExt, PathItem, RequestBody,
+	///     Schema, SchemaExt,
+	/// };
+	///
+	/// let mut operation = Operation::create();
+	/// operation.request_body = Some(
+	///     RequestBody::builder()
+	///         .content("application/json", MediaType::new(Some(Schema::object())))
+	///         .build(),
+	/// );
+	///
+	/// let generator = SchemaGenerator::new()
+	///     .add_path("/users", PathItem::new(HttpMethod::Post, operation))
+	///     .request_example(
+	///         "/users",
+	///         HttpMethod::Post,
+	///         "alice",
+	///         Example::builder()
+	///             .summary("A new user")
+	///             .value(Some(serde_json::json!({"username": "alice"})))
+	///             .build(),
+	///     );
+	///
+	/// let json = generator.to_json().unwrap();
+	/// assert!(json.contains("\"summary\": \"A new user\""));
+	/// ```
+	pub fn request_example(
+		mut self,
+		path: impl Into<String>,
+		method: HttpMethod,
+		name: impl Into<String>,
+		example: Example,
+	) -> Self {
+		self.operation_examples.push(OperationExample {
+			path: path.into(),
+			method,
+			target: ExampleTarget::Request,
+			name: name.into(),
+			example,
+		});
+		self
+	}
+
+	/// Add a named example to one of an operation's responses
+	///
+	/// The example is added to every media type of the response for
+	/// `status` (for example `"200"` or `"default"`), which must exist when
+	/// the schema is generated.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_rest::openapi::generator::SchemaGenerator;
+	/// use reinhardt_rest::openapi::{
+	///     Example, HttpMethod, MediaType, Operation, OperationExt, PathItem, Response,
+	///     ResponsesExt, Schema, SchemaExt,
+	/// };
+	///
+	/// let mut operation = Operation::create();
+	/// let mut response = Response::new("The user");
+	/// response
+	///     .content
+	///     .insert("application/json".to_string(), MediaType::new(Some(Schema::object())));
+	/// operation.responses.responses.insert("200".to_string(), response.into());
+	///
+	/// let generator = SchemaGenerator::new()
+	///     .add_path("/users/{id}", PathItem::new(HttpMethod::Get, operation))
+	///     .response_example(
+	///         "/users/{id}",
+	///         HttpMethod::Get,
+	///         "200",
+	///         "alice",
+	///         Example::builder()
+	///             .value(Some(serde_json::json!({"id": 1, "username": "alice"})))
+	///             .build(),
+	///     );
+	///
+	/// assert!(generator.generate().is_ok());
+	/// ```
+	pub fn response_example(
+		mut self,
+		path: impl Into<String>,
+		method: HttpMethod,
+		status: impl Into<String>,
+		name: impl Into<String>,
+		example: Example,
+	) -> Self {
+		self.operation_examples.push(OperationExample {
+			path: path.into(),
+			method,
+			target: ExampleTarget::Response(status.into()),
+			name: name.into(),
+			example,
+		});
+		self
+	}
+
+	/// Generate examples for schemas and payloads that do not declare one
+	///
+	/// Component schemas and request/response media types without an
+	/// `example` or `examples` get a value built from their schema (see
+	/// [`generate_example`](super::examples::generate_example)), so
+	/// documentation UIs show usable payloads.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_rest::openapi::generator::SchemaGenerator;
+	/// use reinhardt_rest::openapi::{Schema, SchemaExt};
+	///
+	/// let mut generator = SchemaGenerator::new().auto_examples();
+	/// generator.registry().register(
+	///     "User",
+	///     Schema::object_with_properties(vec![("name", Schema::string())], vec!["name"]),
+	/// );
+	///
+	/// let json = generator.to_json().unwrap();
+	/// assert!(json.contains("\"example\""));
+	/// ```
+	pub fn auto_examples(mut self) -> Self {
+		self.auto_examples = true;
+		self
+	}
+
+	/// Document a callback request made after an operation
+	///
+	/// `expression` is the runtime expression giving the callback URL, for
+	/// example `{$request.body#/callback_url}`, and `item` describes the
+	/// request the server sends to it. Callbacks sharing a `name` are grouped
+	/// under that name. The operation must exist when the schema is generated.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_rest::openapi::generator::SchemaGenerator;
+	/// use reinhardt_rest::openapi::{HttpMethod, Operation, OperationExt, PathItem};
+	///
+	/// let generator = SchemaGenerator::new()
+	///     .add_path("/tasks", PathItem::new(HttpMethod::Post, Operation::create()))
+	///     .callback(
+	///         "/tasks",
+	///         HttpMethod::Post,
+	///         "taskCompleted",
+	///         "{$request.body#/callback_url}",
+	///         PathItem::new(HttpMethod::Post, Operation::create()),
+	///     );
+	///
+	/// let json = generator.to_json().unwrap();
+	/// assert!(json.contains("\"taskCompleted\""));
+	/// ```
+	pub fn callback(
+		mut self,
+		path: impl Into<String>,
+		method: HttpMethod,
+		name: impl Into<String>,
+		expression: impl Into<String>,
+		item: PathItem,
+	) -> Self {
+		self.operation_callbacks.push(OperationCallback {
+			path: path.into(),
+			method,
+			name: name.into(),
+			expression: expression.into(),
+			item,
+		});
+		self
+	}
+
+	/// Document a webhook the API sends to its consumers
+	///
+	/// Webhooks are listed in the top-level `webhooks` section of the
+	/// document, keyed by `name`, and are not tied to any API path.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_rest::openapi::generator::SchemaGenerator;
+	/// use reinhardt_rest::openapi::{HttpMethod, Operation, OperationExt, PathItem};
+	///
+	/// let generator = SchemaGenerator::new()
+	///     .webhook("userCreated", PathItem::new(HttpMethod::Post, Operation::create()));
+	///
+	/// let schema = serde_json::to_value(generator.generate().unwrap()).unwrap();
+	/// assert!(schema["webhooks"]["userCreated"]["post"].is_object());
+	/// ```
+	pub fn webhook(mut self, name: impl Into<String>, item: PathItem) -> Self {
+		self.webhooks.insert(name.into(), item);
+		self
+	}
+
 	/// Add function-based endpoints from HTTP method decorators
 	///
 	/// This method uses the `EndpointInspector` to collect endpoint metadata
@@ -181,6 +537,47 @@ impl SchemaGenerator {
 		self
 	}
 
+	/// Add paths for the routes registered on a router
+	///
+	/// Each route contributes an operation per HTTP method, enriched with
+	/// metadata from `#[get]`, `#[post]`, etc. where available (see
+	/// [`EndpointInspector::extract_route_paths`]). Paths already present are
+	/// replaced.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_rest::openapi::generator::SchemaGenerator;
+	/// use reinhardt_rest::openapi::RouteEntry;
+	///
+	/// let generator = SchemaGenerator::new()
+	///     .title("My API")
+	///     .add_routes(&[
+	///         RouteEntry::new("/api/users/", ["GET", "POST"]),
+	///         RouteEntry::new("/api/users/{id}/", ["GET", "PUT", "DELETE"]),
+	///     ]);
+	///
+	/// let schema = generator.generate().unwrap();
+	/// assert_eq!(schema.paths.paths.len(), 2);
+	/// ```
+	pub fn add_routes(mut self, routes: &[RouteEntry]) -> Self {
+		let inspector = EndpointInspector::new();
+
+		match inspector.extract_route_paths(routes) {
+			Ok(paths) => {
+				for (path, path_item) in paths {
+					self.paths.insert(path, path_item);
+				}
+			}
+			Err(e) => {
+				// Log error but don't fail the build
+				eprintln!("Warning: Failed to extract router endpoints: {}", e);
+			}
+		}
+
+		self
+	}
+
 	/// Add a single server function endpoint to the OpenAPI schema
 	///
 	/// This method adds a server function that implements `ServerFnRegistration` trait.
@@ -324,22 +721,77 @@ impl SchemaGenerator {
 			info_builder = info_builder.description(Some(desc.as_str()));
 		}
 
-		let components = self.registry.to_components();
+		let requirements = self
+			.security
+			.iter()
+			.chain(self.operation_security.iter().map(|(_, _, req)| req));
+		for requirement in requirements {
+			self.check_requirement(requirement)?;
+		}
+
+		let mut components = self.registry.to_components();
+		for (name, scheme) in &self.security_schemes {
+			components.add_security_scheme(name, scheme.clone());
+		}
+
+		let mut paths = self.paths.clone();
+		for (path, method, requirement) in &self.operation_security {
+			let operation = paths
+				.get_mut(path)
+				.and_then(|item| operation_mut(item, method))
+				.ok_or_else(|| unknown_operation("Security requirement", path, method))?;
+			operation
+				.security
+				.get_or_insert_with(Vec::new)
+				.push(requirement.clone());
+		}
+
+		for example in &self.operation_examples {
+			add_operation_example(&mut paths, example)?;
+		}
+
+		for callback in &self.operation_callbacks {
+			add_operation_callback(&mut paths, callback)?;
+		}
+
+		if self.auto_examples {
+			fill_examples(&mut components, &mut paths);
+		}
 
 		let mut builder = OpenApiBuilder::new()
 			.info(info_builder.build())
 			.components(Some(components));
 
+		if !self.security.is_empty() {
+			builder = builder.security(Some(self.security.clone()));
+		}
+
 		// Add paths if any exist
-		if !self.paths.is_empty() {
+		if !paths.is_empty() {
 			let mut paths_builder = utoipa::openapi::PathsBuilder::new();
-			for (path, path_item) in &self.paths {
-				paths_builder = paths_builder.path(path, path_item.clone());
+			for (path, path_item) in paths {
+				paths_builder = paths_builder.path(path, path_item);
 			}
 			builder = builder.paths(paths_builder);
 		}
 
-		Ok(builder.build())
+		let mut schema = builder.build();
+
+		// utoipa has no field for webhooks; they are serialized through the
+		// document's extensions, which are flattened into the top level
+		if !self.webhooks.is_empty() {
+			let webhooks = self
+				.webhooks
+				.iter()
+				.map(|(name, item)| Ok((name.clone(), serde_json::to_value(item)?)))
+				.collect::<Result<Map<_, _>, SchemaError>>()?;
+			schema
+				.extensions
+				.get_or_insert_with(Extensions::default)
+				.insert("webhooks".to_string(), Value::Object(webhooks));
+		}
+
+		Ok(schema)
 	}
 
 	/// Generate OpenAPI schema as JSON string
@@ -379,6 +831,222 @@ impl SchemaGenerator {
 		let schema = self.generate()?;
 		serde_yaml::to_string(&schema).map_err(|e| SchemaError::SerializationError(e.to_string()))
 	}
+
+	/// Generate a TypeScript API client for the schema
+	///
+	/// Uses the defaults of [`TypeScriptGenerator`]; use it directly to
+	/// customize the client class.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_rest::openapi::generator::SchemaGenerator;
+	/// use reinhardt_rest::openapi::{Schema, SchemaExt};
+	///
+	/// let mut generator = SchemaGenerator::new().title("My API");
+	/// generator.registry().register("User", Schema::object_with_properties(
+	///     vec![("name", Schema::string())],
+	///     vec!["name"],
+	/// ));
+	///
+	/// let client = generator.to_typescript().unwrap();
+	/// assert!(client.contains("export interface User {"));
+	/// ```
+	pub fn to_typescript(&self) -> Result<String, SchemaError> {
+		let schema = self.generate()?;
+		Ok(TypeScriptGenerator::new().generate(&schema))
+	}
+}
+
+impl SchemaGenerator {
+	/// Ensure every scheme named by a requirement has been declared
+	fn check_requirement(&self, requirement: &SecurityRequirement) -> Result<(), SchemaError> {
+		// SecurityRequirement keeps its scheme names private; read them back
+		// from the serialized form
+		let value = serde_json::to_value(requirement)?;
+		let names = value.as_object().into_iter().flat_map(|map| map.keys());
+		for name in names {
+			if !self.security_schemes.contains_key(name) {
+				return Err(SchemaError::InvalidSchema(format!(
+					"Security requirement references undeclared scheme '{}'",
+					name
+				)));
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Error for an addition (security requirement, example, callback) to a missing operation
+fn unknown_operation(what: &str, path: &str, method: &HttpMethod) -> SchemaError {
+	SchemaError::InvalidSchema(format!(
+		"{} for unknown operation {} {}",
+		what,
+		method_name(method),
+		path
+	))
+}
+
+/// Upper-case name of an HTTP method
+fn method_name(method: &HttpMethod) -> String {
+	serde_json::to_value(method)
+		.ok()
+		.and_then(|value| value.as_str().map(str::to_uppercase))
+		.unwrap_or_default()
+}
+
+/// Add a declared example to every media type of its request or response
+fn add_operation_example(
+	paths: &mut IndexMap<String, PathItem>,
+	example: &OperationExample,
+) -> Result<(), SchemaError> {
+	let OperationExample {
+		path,
+		method,
+		target,
+		name,
+		example,
+	} = example;
+
+	let operation = paths
+		.get_mut(path)
+		.and_then(|item| operation_mut(item, method))
+		.ok_or_else(|| unknown_operation("Example", path, method))?;
+
+	let contents: Vec<&mut MediaType> = match target {
+		ExampleTarget::Request => operation
+			.request_body
+			.as_mut()
+			.map(|body| body.content.values_mut().collect())
+			.unwrap_or_default(),
+		ExampleTarget::Response(status) => match operation.responses.responses.get_mut(status) {
+			Some(RefOr::T(response)) => response.content.values_mut().collect(),
+			_ => Vec::new(),
+		},
+	};
+	if contents.is_empty() {
+		let part = match target {
+			ExampleTarget::Request => "request body".to_string(),
+			ExampleTarget::Response(status) => format!("{} response", status),
+		};
+		return Err(SchemaError::InvalidSchema(format!(
+			"Example '{}' for {} {} has no {} content",
+			name,
+			method_name(method),
+			path,
+			part
+		)));
+	}
+
+	for content in contents {
+		content
+			.examples
+			.insert(name.clone(), RefOr::T(example.clone()));
+	}
+	Ok(())
+}
+
+/// Add a callback to its operation
+///
+/// `Operation::callbacks` cannot hold path items, so callbacks are written
+/// to the operation's extensions, which serialize as the `callbacks` field.
+fn add_operation_callback(
+	paths: &mut IndexMap<String, PathItem>,
+	callback: &OperationCallback,
+) -> Result<(), SchemaError> {
+	let OperationCallback {
+		path,
+		method,
+		name,
+		expression,
+		item,
+	} = callback;
+
+	let operation = paths
+		.get_mut(path)
+		.and_then(|item| operation_mut(item, method))
+		.ok_or_else(|| unknown_operation("Callback", path, method))?;
+
+	let callbacks = operation
+		.extensions
+		.get_or_insert_with(Extensions::default)
+		.entry("callbacks".to_string())
+		.or_insert_with(|| Value::Object(Map::new()));
+	if let Value::Object(callbacks) = callbacks
+		&& let Value::Object(expressions) = callbacks
+			.entry(name.clone())
+			.or_insert_with(|| Value::Object(Map::new()))
+	{
+		expressions.insert(expression.clone(), serde_json::to_value(item)?);
+	}
+	Ok(())
+}
+
+/// Generate examples for component schemas and media types lacking one
+fn fill_examples(components: &mut Components, paths: &mut IndexMap<String, PathItem>) {
+	let lookup = components.clone();
+
+	for schema in components.schemas.values_mut() {
+		if let RefOr::T(utoipa::openapi::Schema::Object(object)) = schema
+			&& object.example.is_none()
+			&& object.examples.is_empty()
+		{
+			object.example = Some(generate_example_with_components(
+				&RefOr::T(utoipa::openapi::Schema::Object(object.clone())),
+				&lookup,
+			));
+		}
+	}
+
+	for item in paths.values_mut() {
+		let operations = [
+			&mut item.get,
+			&mut item.put,
+			&mut item.post,
+			&mut item.delete,
+			&mut item.options,
+			&mut item.head,
+			&mut item.patch,
+			&mut item.trace,
+		];
+		for operation in operations.into_iter().flatten() {
+			let requests = operation
+				.request_body
+				.iter_mut()
+				.flat_map(|body| body.content.values_mut());
+			let responses = operation
+				.responses
+				.responses
+				.values_mut()
+				.filter_map(|response| match response {
+					RefOr::T(response) => Some(response.content.values_mut()),
+					RefOr::Ref(_) => None,
+				})
+				.flatten();
+			for content in requests.chain(responses) {
+				if content.example.is_none()
+					&& content.examples.is_empty()
+					&& let Some(schema) = &content.schema
+				{
+					content.example = Some(generate_example_with_components(schema, &lookup));
+				}
+			}
+		}
+	}
+}
+
+/// Get the operation for `method` on a path item
+fn operation_mut<'a>(item: &'a mut PathItem, method: &HttpMethod) -> Option<&'a mut Operation> {
+	match method {
+		HttpMethod::Get => item.get.as_mut(),
+		HttpMethod::Post => item.post.as_mut(),
+		HttpMethod::Put => item.put.as_mut(),
+		HttpMethod::Delete => item.delete.as_mut(),
+		HttpMethod::Options => item.options.as_mut(),
+		HttpMethod::Head => item.head.as_mut(),
+		HttpMethod::Patch => item.patch.as_mut(),
+		HttpMethod::Trace => item.trace.as_mut(),
+	}
 }
 
 impl Default for SchemaGenerator {
@@ -392,6 +1060,7 @@ mod tests {
 	use super::*;
 	use crate::openapi::Schema;
 	use crate::openapi::SchemaExt;
+	use crate::openapi::{ClientCredentials, Flow, OperationExt, Scopes, SecuritySchemeExt};
 
 	#[test]
 	fn test_new_generator() {
@@ -683,4 +1352,327 @@ mod tests {
 		assert!(required.contains(&serde_json::Value::String("price".to_string())));
 		assert!(!required.contains(&serde_json::Value::String("in_stock".to_string())));
 	}
+
+	#[test]
+	fn test_security_schemes_in_components() {
+		let generator = SchemaGenerator::new()
+			.title("Secure API")
+			.security_scheme("bearerAuth", SecurityScheme::bearer_jwt())
+			.security_scheme("apiKey", SecurityScheme::api_key_query("api_key"))
+			.security_scheme(
+				"oauth",
+				SecurityScheme::oauth2([Flow::ClientCredentials(ClientCredentials::new(
+					"https://auth.example.com/token",
+					Scopes::from_iter([("read", "Read access"), ("write", "Write access")]),
+				))]),
+			)
+			.security(SecurityRequirement::new("bearerAuth", Vec::<String>::new()));
+
+		let json = generator.to_json().unwrap();
+		let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+		let schemes = &parsed["components"]["securitySchemes"];
+		assert_eq!(schemes["bearerAuth"]["type"], "http");
+		assert_eq!(schemes["bearerAuth"]["scheme"], "bearer");
+		assert_eq!(schemes["bearerAuth"]["bearerFormat"], "JWT");
+		assert_eq!(schemes["apiKey"]["type"], "apiKey");
+		assert_eq!(schemes["apiKey"]["in"], "query");
+		assert_eq!(schemes["apiKey"]["name"], "api_key");
+		assert_eq!(schemes["oauth"]["type"], "oauth2");
+		assert_eq!(
+			schemes["oauth"]["flows"]["clientCredentials"]["scopes"]["write"],
+			"Write access"
+		);
+		assert_eq!(parsed["security"], serde_json::json!([{"bearerAuth": []}]));
+	}
+
+	#[test]
+	fn test_operation_security() {
+		let item = PathItem::new(HttpMethod::Get, Operation::create());
+		let generator = SchemaGenerator::new()
+			.add_path("/posts", item)
+			.add_path(
+				"/health",
+				PathItem::new(HttpMethod::Get, Operation::create()),
+			)
+			.security_scheme("oauth", SecurityScheme::bearer())
+			.security_scheme("apiKey", SecurityScheme::api_key_header("X-API-Key"))
+			.operation_security(
+				"/posts",
+				HttpMethod::Get,
+				SecurityRequirement::new("oauth", ["posts:read"]),
+			)
+			.operation_security(
+				"/posts",
+				HttpMethod::Get,
+				SecurityRequirement::new("apiKey", Vec::<String>::new()),
+			)
+			.operation_security("/health", HttpMethod::Get, SecurityRequirement::default());
+
+		let parsed: serde_json::Value =
+			serde_json::from_str(&generator.to_json().unwrap()).unwrap();
+
+		assert_eq!(
+			parsed["paths"]["/posts"]["get"]["security"],
+			serde_json::json!([{"oauth": ["posts:read"]}, {"apiKey": []}])
+		);
+		assert_eq!(
+			parsed["paths"]["/health"]["get"]["security"],
+			serde_json::json!([{}])
+		);
+	}
+
+	#[test]
+	fn test_undeclared_security_scheme_is_rejected() {
+		let generator = SchemaGenerator::new()
+			.security(SecurityRequirement::new("missing", Vec::<String>::new()));
+
+		let result = generator.generate();
+
+		assert!(matches!(result, Err(SchemaError::InvalidSchema(_))));
+	}
+
+	#[test]
+	fn test_security_for_unknown_operation_is_rejected() {
+		let generator = SchemaGenerator::new()
+			.add_path(
+				"/posts",
+				PathItem::new(HttpMethod::Get, Operation::create()),
+			)
+			.security_scheme("bearerAuth", SecurityScheme::bearer())
+			.operation_security(
+				"/posts",
+				HttpMethod::Delete,
+				SecurityRequirement::new("bearerAuth", Vec::<String>::new()),
+			);
+
+		let result = generator.generate();
+
+		assert!(matches!(result, Err(SchemaError::InvalidSchema(_))));
+	}
+
+	fn json_operation(with_body: bool) -> Operation {
+		use crate::openapi::{MediaType, RequestBody, Response};
+
+		let mut operation = Operation::create();
+		if with_body {
+			operation.request_body = Some(
+				RequestBody::builder()
+					.content(
+						"application/json",
+						MediaType::new(Some(RefOr::Ref(utoipa::openapi::Ref::from_schema_name(
+							"User",
+						)))),
+					)
+					.build(),
+			);
+		}
+		let mut response = Response::new("OK");
+		response.content.insert(
+			"application/json".to_string(),
+			MediaType::new(Some(Schema::array(Schema::string()))),
+		);
+		operation
+			.responses
+			.responses
+			.insert("200".to_string(), response.into());
+		operation
+	}
+
+	#[test]
+	fn test_operation_examples() {
+		let generator = SchemaGenerator::new()
+			.add_path(
+				"/users",
+				PathItem::new(HttpMethod::Post, json_operation(true)),
+			)
+			.request_example(
+				"/users",
+				HttpMethod::Post,
+				"alice",
+				Example::builder()
+					.summary("Create Alice")
+					.value(Some(serde_json::json!({"name": "alice"})))
+					.build(),
+			)
+			.response_example(
+				"/users",
+				HttpMethod::Post,
+				"200",
+				"names",
+				Example::builder()
+					.value(Some(serde_json::json!(["alice"])))
+					.build(),
+			);
+
+		let parsed: serde_json::Value =
+			serde_json::from_str(&generator.to_json().unwrap()).unwrap();
+		let operation = &parsed["paths"]["/users"]["post"];
+
+		assert_eq!(
+			operation["requestBody"]["content"]["application/json"]["examples"]["alice"],
+			serde_json::json!({"summary": "Create Alice", "value": {"name": "alice"}})
+		);
+		assert_eq!(
+			operation["responses"]["200"]["content"]["application/json"]["examples"]["names"]["value"],
+			serde_json::json!(["alice"])
+		);
+	}
+
+	#[test]
+	fn test_example_without_content_is_rejected() {
+		let example = Example::builder()
+			.value(Some(serde_json::json!({})))
+			.build();
+		let missing_body = SchemaGenerator::new()
+			.add_path(
+				"/users",
+				PathItem::new(HttpMethod::Get, json_operation(false)),
+			)
+			.request_example("/users", HttpMethod::Get, "empty", example.clone());
+		let missing_status = SchemaGenerator::new()
+			.add_path(
+				"/users",
+				PathItem::new(HttpMethod::Get, json_operation(false)),
+			)
+			.response_example("/users", HttpMethod::Get, "404", "empty", example.clone());
+		let missing_operation =
+			SchemaGenerator::new().request_example("/users", HttpMethod::Get, "empty", example);
+
+		for generator in [missing_body, missing_status, missing_operation] {
+			assert!(matches!(
+				generator.generate(),
+				Err(SchemaError::InvalidSchema(_))
+			));
+		}
+	}
+
+	#[test]
+	fn test_auto_examples() {
+		let mut generator = SchemaGenerator::new()
+			.add_path(
+				"/users",
+				PathItem::new(HttpMethod::Post, json_operation(true)),
+			)
+			.auto_examples();
+		generator.registry().register(
+			"User",
+			Schema::object_with_properties(
+				vec![("id", Schema::integer()), ("name", Schema::string())],
+				vec!["id", "name"],
+			),
+		);
+
+		let parsed: serde_json::Value =
+			serde_json::from_str(&generator.to_json().unwrap()).unwrap();
+		let operation = &parsed["paths"]["/users"]["post"];
+		let user = serde_json::json!({"id": 0, "name": "string"});
+
+		assert_eq!(parsed["components"]["schemas"]["User"]["example"], user);
+		assert_eq!(
+			operation["requestBody"]["content"]["application/json"]["example"],
+			user
+		);
+		assert_eq!(
+			operation["responses"]["200"]["content"]["application/json"]["example"],
+			serde_json::json!(["string"])
+		);
+	}
+
+	#[test]
+	fn test_auto_examples_keep_declared_examples() {
+		let generator = SchemaGenerator::new()
+			.add_path(
+				"/users",
+				PathItem::new(HttpMethod::Get, json_operation(false)),
+			)
+			.response_example(
+				"/users",
+				HttpMethod::Get,
+				"200",
+				"names",
+				Example::builder()
+					.value(Some(serde_json::json!(["bob"])))
+					.build(),
+			)
+			.auto_examples();
+
+		let parsed: serde_json::Value =
+			serde_json::from_str(&generator.to_json().unwrap()).unwrap();
+		let content =
+			&parsed["paths"]["/users"]["get"]["responses"]["200"]["content"]["application/json"];
+
+		assert!(content.get("example").is_none());
+		assert_eq!(
+			content["examples"]["names"]["value"],
+			serde_json::json!(["bob"])
+		);
+	}
+
+	#[test]
+	fn test_operation_callbacks() {
+		let generator = SchemaGenerator::new()
+			.add_path(
+				"/tasks",
+				PathItem::new(HttpMethod::Post, json_operation(true)),
+			)
+			.callback(
+				"/tasks",
+				HttpMethod::Post,
+				"taskCompleted",
+				"{$request.body#/callback_url}",
+				PathItem::new(HttpMethod::Post, json_operation(true)),
+			)
+			.callback(
+				"/tasks",
+				HttpMethod::Post,
+				"taskCompleted",
+				"{$request.body#/fallback_url}",
+				PathItem::new(HttpMethod::Post, json_operation(true)),
+			);
+
+		let schema = serde_json::to_value(generator.generate().unwrap()).unwrap();
+		let callbacks = &schema["paths"]["/tasks"]["post"]["callbacks"]["taskCompleted"];
+
+		assert!(callbacks["{$request.body#/callback_url}"]["post"].is_object());
+		assert!(callbacks["{$request.body#/fallback_url}"]["post"].is_object());
+	}
+
+	#[test]
+	fn test_callback_for_unknown_operation_is_rejected() {
+		let generator = SchemaGenerator::new().callback(
+			"/tasks",
+			HttpMethod::Post,
+			"taskCompleted",
+			"{$request.body#/callback_url}",
+			PathItem::new(HttpMethod::Post, Operation::create()),
+		);
+
+		let result = generator.generate();
+
+		assert!(matches!(
+			result,
+			Err(SchemaError::InvalidSchema(message))
+				if message == "Callback for unknown operation POST /tasks"
+		));
+	}
+
+	#[test]
+	fn test_webhooks() {
+		let generator = SchemaGenerator::new()
+			.webhook(
+				"userCreated",
+				PathItem::new(HttpMethod::Post, json_operation(true)),
+			)
+			.webhook(
+				"userDeleted",
+				PathItem::new(HttpMethod::Post, Operation::create()),
+			);
+
+		let schema = serde_json::to_value(generator.generate().unwrap()).unwrap();
+
+		assert!(schema["webhooks"]["userCreated"]["post"]["requestBody"].is_object());
+		assert!(schema["webhooks"]["userDeleted"]["post"].is_object());
+		assert_eq!(schema["paths"], serde_json::json!({}));
+	}
 }
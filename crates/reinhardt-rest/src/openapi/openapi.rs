@@ -14,16 +14,23 @@ pub use utoipa::openapi::request_body::RequestBody;
 pub use utoipa::openapi::response::{Response, Responses};
 
 // Re-export path operation types
-pub use utoipa::openapi::path::{Operation, Parameter, ParameterIn};
+pub use utoipa::openapi::path::{HttpMethod, Operation, Parameter, ParameterIn};
 
 // Re-export content-related types (MediaType)
 pub use utoipa::openapi::Content as MediaType;
 
+// Re-export example types for request/response payloads
+pub use utoipa::openapi::example::{Example, ExampleBuilder};
+
 // Re-export path-related types
 pub use utoipa::openapi::path::ParameterIn as ParameterLocation;
 
 // Re-export security-related types
-pub use utoipa::openapi::security::{ApiKey, ApiKeyValue, Http, HttpAuthScheme, SecurityScheme};
+pub use utoipa::openapi::security::{
+	ApiKey, ApiKeyValue, AuthorizationCode, ClientCredentials, Flow, Http, HttpAuthScheme,
+	HttpBuilder, Implicit, OAuth2, OpenIdConnect, Password, Scopes, SecurityRequirement,
+	SecurityScheme,
+};
 
 // Provide convenient type alias for API key location
 pub type ApiKeyLocation = utoipa::openapi::security::ApiKeyValue;
@@ -310,6 +317,65 @@ impl PathItemExt for PathItem {
 	}
 }
 
+/// Extension trait for SecurityScheme to provide convenient constructors
+pub trait SecuritySchemeExt {
+	/// Create an HTTP bearer scheme
+	fn bearer() -> SecurityScheme;
+
+	/// Create an HTTP bearer scheme with `bearerFormat: JWT`
+	fn bearer_jwt() -> SecurityScheme;
+
+	/// Create an HTTP basic scheme
+	fn basic() -> SecurityScheme;
+
+	/// Create an API key scheme read from the named header
+	fn api_key_header(name: impl Into<String>) -> SecurityScheme;
+
+	/// Create an API key scheme read from the named query parameter
+	fn api_key_query(name: impl Into<String>) -> SecurityScheme;
+
+	/// Create an API key scheme read from the named cookie
+	fn api_key_cookie(name: impl Into<String>) -> SecurityScheme;
+
+	/// Create an OAuth2 scheme supporting the given flows
+	fn oauth2(flows: impl IntoIterator<Item = Flow>) -> SecurityScheme;
+}
+
+impl SecuritySchemeExt for SecurityScheme {
+	fn bearer() -> SecurityScheme {
+		SecurityScheme::Http(Http::new(HttpAuthScheme::Bearer))
+	}
+
+	fn bearer_jwt() -> SecurityScheme {
+		SecurityScheme::Http(
+			HttpBuilder::new()
+				.scheme(HttpAuthScheme::Bearer)
+				.bearer_format("JWT")
+				.build(),
+		)
+	}
+
+	fn basic() -> SecurityScheme {
+		SecurityScheme::Http(Http::new(HttpAuthScheme::Basic))
+	}
+
+	fn api_key_header(name: impl Into<String>) -> SecurityScheme {
+		SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new(name)))
+	}
+
+	fn api_key_query(name: impl Into<String>) -> SecurityScheme {
+		SecurityScheme::ApiKey(ApiKey::Query(ApiKeyValue::new(name)))
+	}
+
+	fn api_key_cookie(name: impl Into<String>) -> SecurityScheme {
+		SecurityScheme::ApiKey(ApiKey::Cookie(ApiKeyValue::new(name)))
+	}
+
+	fn oauth2(flows: impl IntoIterator<Item = Flow>) -> SecurityScheme {
+		SecurityScheme::OAuth2(OAuth2::new(flows))
+	}
+}
+
 /// Extension trait for Parameter to provide convenient constructors
 pub trait ParameterExt {
 	/// Create a new Parameter with ParameterBuilder
@@ -0,0 +1,827 @@
+//! TypeScript client generation from OpenAPI schemas
+//!
+//! Emits a single TypeScript module containing an interface or type alias
+//! for every component schema and an `ApiClient` class with one typed
+//! `fetch` wrapper per operation, so frontends can consume the API without
+//! hand-maintaining types.
+//!
+//! # Example
+//!
+//! ```rust
+//! use reinhardt_rest::openapi::generator::SchemaGenerator;
+//! use reinhardt_rest::openapi::{HttpMethod, Operation, OperationExt, PathItem};
+//! use reinhardt_rest::openapi::typescript::TypeScriptGenerator;
+//!
+//! let mut operation = Operation::create();
+//! operation.operation_id = Some("list_users".to_string());
+//!
+//! let schema = SchemaGenerator::new()
+//!     .title("My API")
+//!     .add_path("/users/", PathItem::new(HttpMethod::Get, operation))
+//!     .generate()
+//!     .unwrap();
+//!
+//! let client = TypeScriptGenerator::new().generate(&schema);
+//! assert!(client.contains("async listUsers("));
+//! ```
+
+use super::{OpenApiSchema, Operation, Parameter, ParameterLocation, RefOr, Required, Schema};
+use std::collections::HashSet;
+use std::fmt::Write;
+use utoipa::openapi::Deprecated;
+use utoipa::openapi::schema::{AdditionalProperties, ArrayItems, SchemaType, Type};
+
+/// Prefix of local component references
+const COMPONENT_REF_PREFIX: &str = "#/components/schemas/";
+
+/// Name of the private request helper on the generated client
+const REQUEST_HELPER: &str = "request";
+
+/// Runtime support emitted before the client class
+const CLIENT_PRELUDE: &str = r#"export class ApiError extends Error {
+  constructor(
+    public readonly status: number,
+    public readonly body: unknown,
+  ) {
+    super(`Request failed with status ${status}`);
+    this.name = "ApiError";
+  }
+}
+
+export interface ClientOptions {
+  /** Prefix prepended to every request path */
+  baseUrl?: string;
+  /** Headers sent with every request, e.g. authorization */
+  headers?: Record<string, string>;
+  /** Custom fetch implementation */
+  fetch?: typeof fetch;
+}
+
+type QueryParams = Record<string, unknown>;
+"#;
+
+/// Generator of a TypeScript API client from an OpenAPI schema
+///
+/// # Example
+///
+/// ```rust
+/// use reinhardt_rest::openapi::typescript::TypeScriptGenerator;
+///
+/// let generator = TypeScriptGenerator::new()
+///     .client_name("PetStoreClient")
+///     .base_url("/api");
+/// ```
+#[derive(Debug, Clone)]
+pub struct TypeScriptGenerator {
+	client_name: String,
+	base_url: String,
+}
+
+impl TypeScriptGenerator {
+	/// Create a generator emitting an `ApiClient` class with no base URL
+	pub fn new() -> Self {
+		Self {
+			client_name: "ApiClient".to_string(),
+			base_url: String::new(),
+		}
+	}
+
+	/// Set the name of the generated client class
+	pub fn client_name(mut self, name: impl Into<String>) -> Self {
+		self.client_name = name.into();
+		self
+	}
+
+	/// Set the default base URL, overridable through `ClientOptions.baseUrl`
+	pub fn base_url(mut self, url: impl Into<String>) -> Self {
+		self.base_url = url.into();
+		self
+	}
+
+	/// Generate the TypeScript module for `schema`
+	pub fn generate(&self, schema: &OpenApiSchema) -> String {
+		let mut out = String::new();
+		let _ = writeln!(
+			out,
+			"// Generated from the OpenAPI schema \"{}\" {}. Do not edit by hand.",
+			schema.info.title, schema.info.version
+		);
+		out.push_str("/* eslint-disable */\n\n");
+
+		if let Some(components) = &schema.components {
+			for (name, component) in &components.schemas {
+				write_component(&mut out, &type_name(name), component);
+			}
+		}
+
+		out.push_str(CLIENT_PRELUDE);
+		self.write_client(&mut out, schema);
+		out
+	}
+
+	fn write_client(&self, out: &mut String, schema: &OpenApiSchema) {
+		let _ = writeln!(out, "\nexport class {} {{", self.client_name);
+		out.push_str("  private readonly baseUrl: string;\n");
+		out.push_str("  private readonly headers: Record<string, string>;\n");
+		out.push_str("  private readonly fetchImpl: typeof fetch;\n\n");
+		out.push_str("  constructor(options: ClientOptions = {}) {\n");
+		let _ = writeln!(
+			out,
+			"    this.baseUrl = options.baseUrl ?? {};",
+			string_literal(&self.base_url)
+		);
+		out.push_str("    this.headers = options.headers ?? {};\n");
+		out.push_str("    this.fetchImpl = options.fetch ?? globalThis.fetch.bind(globalThis);\n");
+		out.push_str("  }\n");
+
+		let mut used_names: HashSet<String> = HashSet::from([REQUEST_HELPER.to_string()]);
+		for (path, item) in &schema.paths.paths {
+			let operations = [
+				("GET", &item.get),
+				("PUT", &item.put),
+				("POST", &item.post),
+				("DELETE", &item.delete),
+				("OPTIONS", &item.options),
+				("HEAD", &item.head),
+				("PATCH", &item.patch),
+				("TRACE", &item.trace),
+			];
+			for (method, operation) in operations {
+				let Some(operation) = operation else {
+					continue;
+				};
+				let shared = item.parameters.as_deref().unwrap_or_default();
+				let name = unique_name(&mut used_names, operation_name(method, path, operation));
+				write_operation(out, &name, method, path, operation, shared);
+			}
+		}
+
+		let _ = write!(
+			out,
+			r#"
+  private async {REQUEST_HELPER}<T>(
+    method: string,
+    path: string,
+    query?: QueryParams,
+    body?: unknown,
+  ): Promise<T> {{
+    let url = this.baseUrl + path;
+    if (query) {{
+      const params = new URLSearchParams();
+      for (const [key, value] of Object.entries(query)) {{
+        const values = Array.isArray(value) ? value : [value];
+        for (const item of values) {{
+          if (item !== undefined && item !== null) {{
+            params.append(key, String(item));
+          }}
+        }}
+      }}
+      const search = params.toString();
+      if (search) {{
+        url += `?${{search}}`;
+      }}
+    }}
+    const headers: Record<string, string> = {{ Accept: "application/json", ...this.headers }};
+    if (body !== undefined) {{
+      headers["Content-Type"] = "application/json";
+    }}
+    const response = await this.fetchImpl(url, {{
+      method,
+      headers,
+      body: body === undefined ? undefined : JSON.stringify(body),
+    }});
+    const text = await response.text();
+    const data = text ? JSON.parse(text) : undefined;
+    if (!response.ok) {{
+      throw new ApiError(response.status, data);
+    }}
+    return data as T;
+  }}
+}}
+"#
+		);
+	}
+}
+
+impl Default for TypeScriptGenerator {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Write an interface (objects with properties) or type alias for a component
+fn write_component(out: &mut String, name: &str, schema: &RefOr<Schema>) {
+	if let RefOr::T(Schema::Object(object)) = schema
+		&& !object.properties.is_empty()
+		&& object.enum_values.is_none()
+	{
+		write_doc(out, "", object.description.as_deref(), None, false);
+		let _ = writeln!(out, "export interface {} {{", name);
+		for (property, property_schema) in &object.properties {
+			let description = match property_schema {
+				RefOr::T(Schema::Object(inner)) => inner.description.as_deref(),
+				_ => None,
+			};
+			write_doc(out, "  ", description, None, false);
+			let optional = if object.required.contains(property) {
+				""
+			} else {
+				"?"
+			};
+			let _ = writeln!(
+				out,
+				"  {}{}: {};",
+				property_key(property),
+				optional,
+				ts_type(property_schema)
+			);
+		}
+		out.push_str("}\n\n");
+		return;
+	}
+
+	if let RefOr::T(Schema::Object(object)) = schema {
+		write_doc(out, "", object.description.as_deref(), None, false);
+	}
+	let _ = writeln!(out, "export type {} = {};\n", name, ts_type(schema));
+}
+
+/// Write one client method
+fn write_operation(
+	out: &mut String,
+	name: &str,
+	method: &str,
+	path: &str,
+	operation: &Operation,
+	shared: &[Parameter],
+) {
+	// Operation parameters override path-level ones with the same name and location
+	let mut parameters: Vec<&Parameter> = operation.parameters.iter().flatten().collect();
+	for parameter in shared {
+		let overridden = parameters.iter().any(|p| {
+			p.name == parameter.name
+				&& location(&p.parameter_in) == location(&parameter.parameter_in)
+		});
+		if !overridden {
+			parameters.push(parameter);
+		}
+	}
+
+	let mut args = Vec::new();
+	let mut url = String::new();
+	let mut rest = path;
+	while let Some(start) = rest.find('{') {
+		let Some(end) = rest[start..].find('}') else {
+			break;
+		};
+		let param_name = &rest[start + 1..start + end];
+		let ty = parameters
+			.iter()
+			.find(|p| p.name == param_name && location(&p.parameter_in) == "path")
+			.and_then(|p| p.schema.as_ref())
+			.map_or_else(|| "string | number".to_string(), ts_type);
+		let ident = identifier(param_name);
+		url.push_str(&template_text(&rest[..start]));
+		let _ = write!(url, "${{encodeURIComponent(String({}))}}", ident);
+		args.push(format!("{}: {}", ident, ty));
+		rest = &rest[start + end + 1..];
+	}
+	url.push_str(&template_text(rest));
+
+	let body = operation.request_body.as_ref().map(|body| {
+		let ty = body
+			.content
+			.iter()
+			.find(|(media_type, _)| media_type.contains("json"))
+			.or_else(|| body.content.iter().next())
+			.and_then(|(_, content)| content.schema.as_ref())
+			.map_or_else(|| "unknown".to_string(), ts_type);
+		let required = matches!(body.required, Some(Required::True));
+		(ty, required)
+	});
+	if let Some((ty, true)) = &body {
+		args.push(format!("body: {}", ty));
+	}
+
+	let query: Vec<&Parameter> = parameters
+		.iter()
+		.copied()
+		.filter(|p| location(&p.parameter_in) == "query")
+		.collect();
+	if !query.is_empty() {
+		let fields: Vec<String> = query
+			.iter()
+			.map(|p| {
+				let optional = if matches!(p.required, Required::True) {
+					""
+				} else {
+					"?"
+				};
+				let ty = p
+					.schema
+					.as_ref()
+					.map_or_else(|| "string".to_string(), ts_type);
+				format!("{}{}: {}", property_key(&p.name), optional, ty)
+			})
+			.collect();
+		let optional = if query.iter().any(|p| matches!(p.required, Required::True)) {
+			""
+		} else {
+			"?"
+		};
+		args.push(format!("query{}: {{ {} }}", optional, fields.join("; ")));
+	}
+	if let Some((ty, false)) = &body {
+		args.push(format!("body?: {}", ty));
+	}
+
+	let deprecated = matches!(operation.deprecated, Some(Deprecated::True));
+	out.push('\n');
+	write_doc(
+		out,
+		"  ",
+		operation.summary.as_deref(),
+		operation.description.as_deref(),
+		deprecated,
+	);
+
+	let response = response_type(operation);
+	let query_arg = if query.is_empty() {
+		"undefined"
+	} else {
+		"query"
+	};
+	let call_args = match &body {
+		Some(_) => format!(", {}, body", query_arg),
+		None if !query.is_empty() => ", query".to_string(),
+		None => String::new(),
+	};
+	let _ = writeln!(
+		out,
+		"  async {}({}): Promise<{}> {{",
+		name,
+		args.join(", "),
+		response
+	);
+	let _ = writeln!(
+		out,
+		"    return this.{}<{}>(\"{}\", `{}`{});",
+		REQUEST_HELPER, response, method, url, call_args
+	);
+	out.push_str("  }\n");
+}
+
+/// Type of the first successful response's body
+fn response_type(operation: &Operation) -> String {
+	let success = operation
+		.responses
+		.responses
+		.iter()
+		.find(|(status, _)| status.starts_with('2'))
+		.or_else(|| operation.responses.responses.get_key_value("default"));
+	let Some((_, response)) = success else {
+		return "unknown".to_string();
+	};
+	let RefOr::T(response) = response else {
+		return "unknown".to_string();
+	};
+	if response.content.is_empty() {
+		return "void".to_string();
+	}
+	response
+		.content
+		.iter()
+		.find(|(media_type, _)| media_type.contains("json"))
+		.or_else(|| response.content.iter().next())
+		.and_then(|(_, content)| content.schema.as_ref())
+		.map_or_else(|| "unknown".to_string(), ts_type)
+}
+
+/// TypeScript type expression for a schema
+fn ts_type(schema: &RefOr<Schema>) -> String {
+	match schema {
+		RefOr::Ref(reference) => reference
+			.ref_location
+			.strip_prefix(COMPONENT_REF_PREFIX)
+			.map_or_else(|| "unknown".to_string(), type_name),
+		RefOr::T(schema) => schema_type(schema),
+	}
+}
+
+fn schema_type(schema: &Schema) -> String {
+	match schema {
+		Schema::Object(object) => {
+			if let Some(values) = &object.enum_values {
+				return union(values.iter().map(|value| value.to_string()).collect());
+			}
+
+			let mut types: Vec<String> = match &object.schema_type {
+				SchemaType::Type(ty) => vec![ty.clone()],
+				SchemaType::Array(types) => types.clone(),
+				SchemaType::AnyValue => Vec::new(),
+			}
+			.into_iter()
+			.map(|ty| match ty {
+				Type::String => "string".to_string(),
+				Type::Integer | Type::Number => "number".to_string(),
+				Type::Boolean => "boolean".to_string(),
+				Type::Null => "null".to_string(),
+				Type::Array => "unknown[]".to_string(),
+				Type::Object => object_type(object),
+			})
+			.collect();
+
+			if types.is_empty() {
+				if object.properties.is_empty() && object.additional_properties.is_none() {
+					return "unknown".to_string();
+				}
+				types.push(object_type(object));
+			}
+			types.dedup();
+			union(types)
+		}
+		Schema::Array(array) => {
+			if !array.prefix_items.is_empty() {
+				let items: Vec<String> = array.prefix_items.iter().map(schema_type).collect();
+				return format!("[{}]", items.join(", "));
+			}
+			let item = match &array.items {
+				ArrayItems::RefOrSchema(item) => ts_type(item),
+				_ => "unknown".to_string(),
+			};
+			if item.contains(['|', '&']) {
+				format!("({})[]", item)
+			} else {
+				format!("{}[]", item)
+			}
+		}
+		Schema::OneOf(one_of) => union(one_of.items.iter().map(ts_type).collect()),
+		Schema::AnyOf(any_of) => union(any_of.items.iter().map(ts_type).collect()),
+		Schema::AllOf(all_of) => {
+			let parts: Vec<String> = all_of
+				.items
+				.iter()
+				.map(|item| {
+					let ty = ts_type(item);
+					if ty.contains('|') {
+						format!("({})", ty)
+					} else {
+						ty
+					}
+				})
+				.collect();
+			if parts.is_empty() {
+				"unknown".to_string()
+			} else {
+				parts.join(" & ")
+			}
+		}
+		_ => "unknown".to_string(),
+	}
+}
+
+/// Inline object type for an object schema
+fn object_type(object: &utoipa::openapi::schema::Object) -> String {
+	if object.properties.is_empty() {
+		return match object.additional_properties.as_deref() {
+			Some(AdditionalProperties::RefOr(value)) => {
+				format!("Record<string, {}>", ts_type(value))
+			}
+			_ => "Record<string, unknown>".to_string(),
+		};
+	}
+
+	let fields: Vec<String> = object
+		.properties
+		.iter()
+		.map(|(name, schema)| {
+			let optional = if object.required.contains(name) {
+				""
+			} else {
+				"?"
+			};
+			format!("{}{}: {}", property_key(name), optional, ts_type(schema))
+		})
+		.collect();
+	format!("{{ {} }}", fields.join("; "))
+}
+
+fn union(types: Vec<String>) -> String {
+	if types.is_empty() {
+		"unknown".to_string()
+	} else {
+		types.join(" | ")
+	}
+}
+
+/// Write a JSDoc comment, if there is anything to document
+fn write_doc(
+	out: &mut String,
+	indent: &str,
+	summary: Option<&str>,
+	description: Option<&str>,
+	deprecated: bool,
+) {
+	let mut lines: Vec<String> = Vec::new();
+	for text in [summary, description].into_iter().flatten() {
+		if !lines.is_empty() {
+			lines.push(String::new());
+		}
+		lines.extend(text.lines().map(|line| line.replace("*/", "*\\/")));
+	}
+	if deprecated {
+		lines.push("@deprecated".to_string());
+	}
+	if lines.is_empty() {
+		return;
+	}
+
+	let _ = writeln!(out, "{}/**", indent);
+	for line in lines {
+		if line.is_empty() {
+			let _ = writeln!(out, "{} *", indent);
+		} else {
+			let _ = writeln!(out, "{} * {}", indent, line);
+		}
+	}
+	let _ = writeln!(out, "{} */", indent);
+}
+
+fn location(parameter_in: &ParameterLocation) -> &'static str {
+	match parameter_in {
+		ParameterLocation::Query => "query",
+		ParameterLocation::Path => "path",
+		ParameterLocation::Header => "header",
+		ParameterLocation::Cookie => "cookie",
+	}
+}
+
+/// Method name for an operation: its camel-cased `operationId`, or the
+/// HTTP method followed by the path segments
+fn operation_name(method: &str, path: &str, operation: &Operation) -> String {
+	if let Some(id) = &operation.operation_id {
+		let name = identifier(id);
+		if !name.is_empty() {
+			return name;
+		}
+	}
+
+	let mut name = method.to_lowercase();
+	for segment in path.split('/').filter(|s| !s.is_empty()) {
+		match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+			Some(param) => {
+				name.push_str("By");
+				name.push_str(&pascal_case(param));
+			}
+			None => name.push_str(&pascal_case(segment)),
+		}
+	}
+	name
+}
+
+/// Make `name` unique among the client's methods
+fn unique_name(used: &mut HashSet<String>, name: String) -> String {
+	let mut candidate = name.clone();
+	let mut counter = 2;
+	while used.contains(&candidate) {
+		candidate = format!("{}{}", name, counter);
+		counter += 1;
+	}
+	used.insert(candidate.clone());
+	candidate
+}
+
+/// Words of a name, split on any non-alphanumeric character
+fn words(name: &str) -> impl Iterator<Item = &str> {
+	name.split(|c: char| !c.is_ascii_alphanumeric())
+		.filter(|w| !w.is_empty())
+}
+
+fn capitalize(word: &str) -> String {
+	let mut chars = word.chars();
+	match chars.next() {
+		Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+		None => String::new(),
+	}
+}
+
+fn pascal_case(name: &str) -> String {
+	words(name).map(capitalize).collect()
+}
+
+/// camelCase identifier for a name such as `list_users` or `user-id`
+fn identifier(name: &str) -> String {
+	let pascal = pascal_case(name);
+	let mut chars = pascal.chars();
+	let ident = match chars.next() {
+		Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+		None => return String::new(),
+	};
+	if ident.starts_with(|c: char| c.is_ascii_digit()) {
+		format!("_{}", ident)
+	} else {
+		ident
+	}
+}
+
+/// Type name for a component, keeping `_` so names like `Array_User` survive
+fn type_name(name: &str) -> String {
+	let sanitized: String = name
+		.chars()
+		.map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+		.collect();
+	if sanitized.is_empty() || sanitized.starts_with(|c: char| c.is_ascii_digit()) {
+		format!("_{}", sanitized)
+	} else {
+		sanitized
+	}
+}
+
+/// Object key, quoted unless it is a valid identifier
+fn property_key(name: &str) -> String {
+	let valid = name
+		.chars()
+		.next()
+		.is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+		&& name
+			.chars()
+			.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+	if valid {
+		name.to_string()
+	} else {
+		string_literal(name)
+	}
+}
+
+fn string_literal(value: &str) -> String {
+	serde_json::Value::String(value.to_string()).to_string()
+}
+
+/// Escape literal path text for a template string
+fn template_text(text: &str) -> String {
+	text.replace('\\', "\\\\")
+		.replace('`', "\\`")
+		.replace("${", "\\${")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::openapi::generator::SchemaGenerator;
+	use crate::openapi::{
+		HttpMethod, MediaType, OperationExt, ParameterExt, PathItem, RequestBody, Response,
+		SchemaExt,
+	};
+	use rstest::rstest;
+
+	fn user_schema() -> Schema {
+		Schema::object_with_properties(
+			vec![
+				("id", Schema::integer()),
+				("user-name", Schema::string()),
+				("email", Schema::string()),
+			],
+			vec!["id", "user-name"],
+		)
+	}
+
+	fn json_content(schema: impl Into<RefOr<Schema>>) -> MediaType {
+		MediaType::new(Some(schema.into()))
+	}
+
+	fn api() -> OpenApiSchema {
+		let user_ref = || utoipa::openapi::Ref::from_schema_name("User");
+
+		let mut list = Operation::create();
+		list.operation_id = Some("list_users".to_string());
+		list.summary = Some("List users".to_string());
+		list.add_parameter(Parameter::new_simple(
+			"page",
+			ParameterLocation::Query,
+			Schema::integer(),
+			false,
+		));
+		let mut ok = Response::new("Users");
+		ok.content.insert(
+			"application/json".to_string(),
+			json_content(utoipa::openapi::schema::Array::new(user_ref())),
+		);
+		list.add_response("200", ok);
+
+		let mut create = Operation::create();
+		create.request_body = Some(
+			RequestBody::builder()
+				.content("application/json", json_content(user_ref()))
+				.required(Some(Required::True))
+				.build(),
+		);
+		let mut created = Response::new("Created");
+		created
+			.content
+			.insert("application/json".to_string(), json_content(user_ref()));
+		create.add_response("201", created);
+
+		let mut delete = Operation::create();
+		delete.add_parameter(Parameter::new_simple(
+			"id",
+			ParameterLocation::Path,
+			Schema::integer(),
+			true,
+		));
+		delete.deprecated = Some(Deprecated::True);
+		delete.add_response("204", Response::new("Deleted"));
+
+		let mut users = PathItem::new(HttpMethod::Get, list);
+		users.post = Some(create);
+
+		let mut generator = SchemaGenerator::new()
+			.title("Users API")
+			.add_path("/users/", users)
+			.add_path("/users/{id}/", PathItem::new(HttpMethod::Delete, delete));
+		generator.registry().register("User", user_schema());
+		generator.generate().unwrap()
+	}
+
+	#[rstest]
+	fn test_component_interfaces() {
+		let client = TypeScriptGenerator::new().generate(&api());
+
+		assert!(client.contains(
+			"export interface User {\n  email?: string;\n  id: number;\n  \"user-name\": string;\n}"
+		));
+	}
+
+	#[rstest]
+	fn test_operation_methods() {
+		let client = TypeScriptGenerator::new().generate(&api());
+
+		assert!(client.contains(
+			"  async listUsers(query?: { page?: number }): Promise<User[]> {\n    return this.request<User[]>(\"GET\", `/users/`, query);"
+		));
+		assert!(client.contains(
+			"  async postUsers(body: User): Promise<User> {\n    return this.request<User>(\"POST\", `/users/`, undefined, body);"
+		));
+		assert!(client.contains(
+			"  /**\n   * @deprecated\n   */\n  async deleteUsersById(id: number): Promise<void> {\n    return this.request<void>(\"DELETE\", `/users/${encodeURIComponent(String(id))}/`);"
+		));
+	}
+
+	#[rstest]
+	fn test_client_options() {
+		let client = TypeScriptGenerator::new()
+			.client_name("UsersClient")
+			.base_url("/api")
+			.generate(&api());
+
+		assert!(client.contains("export class UsersClient {"));
+		assert!(client.contains("this.baseUrl = options.baseUrl ?? \"/api\";"));
+	}
+
+	#[rstest]
+	#[case(Schema::string(), "string")]
+	#[case(Schema::array(Schema::integer()), "number[]")]
+	#[case(Schema::object(), "Record<string, unknown>")]
+	#[case(
+		crate::openapi::ObjectBuilder::new()
+			.schema_type(SchemaType::Array(vec![Type::String, Type::Null]))
+			.build()
+			.into(),
+		"string | null"
+	)]
+	#[case(
+		crate::openapi::ObjectBuilder::new()
+			.schema_type(SchemaType::Type(Type::String))
+			.enum_values(Some(["a", "b"]))
+			.build()
+			.into(),
+		"\"a\" | \"b\""
+	)]
+	fn test_schema_types(#[case] schema: Schema, #[case] expected: &str) {
+		assert_eq!(schema_type(&schema), expected);
+	}
+
+	#[rstest]
+	#[case("GET", "/users/", None, "getUsers")]
+	#[case("GET", "/users/{user_id}/posts/", None, "getUsersByUserIdPosts")]
+	#[case("POST", "/users/", Some("create-user"), "createUser")]
+	fn test_operation_name(
+		#[case] method: &str,
+		#[case] path: &str,
+		#[case] operation_id: Option<&str>,
+		#[case] expected: &str,
+	) {
+		let mut operation = Operation::create();
+		operation.operation_id = operation_id.map(str::to_string);
+
+		assert_eq!(operation_name(method, path, &operation), expected);
+	}
+
+	#[rstest]
+	fn test_method_names_are_unique() {
+		let mut used = HashSet::from([REQUEST_HELPER.to_string()]);
+
+		assert_eq!(unique_name(&mut used, "request".to_string()), "request2");
+		assert_eq!(unique_name(&mut used, "getUser".to_string()), "getUser");
+		assert_eq!(unique_name(&mut used, "getUser".to_string()), "getUser2");
+	}
+}
@@ -13,6 +13,9 @@
 //! - **[`ModelSerializer`]**: Auto-generated serializers from model definitions
 //! - **[`NestedSerializer`]**: Handle nested relationships
 //! - **[`HyperlinkedModelSerializer`]**: Serializers with hyperlinked relationships
+//! - **[`DynamicFieldsSerializer`]**: Trim output with `?fields=` and `?omit=`
+//! - **[`PermissionedSerializer`]**: Hide or lock fields based on the user's permissions
+//! - **[`VersionedSerializer`]**: Per-API-version field renames, additions and removals
 //! - **Field Types**: CharField, IntegerField, DateTimeField, etc.
 //! - **Validators**: UniqueValidator, custom validation functions
 //! - **Performance**: Query caching, N+1 detection, batch validation
@@ -196,6 +199,8 @@ pub use reinhardt_core::serializers::{
 // REST-specific modules (ORM-integrated features)
 pub mod cache_invalidation;
 pub mod content_negotiation;
+pub mod dynamic_fields;
+pub mod field_permissions;
 pub mod hyperlinked;
 pub mod introspection;
 pub mod meta;
@@ -212,18 +217,26 @@ pub mod relation_fields_orm;
 pub mod relations;
 pub mod validator_config;
 pub mod validators;
+pub mod versioned;
 
 // Re-export REST-specific types
 pub use cache_invalidation::{CacheInvalidator, InvalidationStrategy};
 pub use content_negotiation::ContentNegotiator;
+pub use dynamic_fields::{DynamicFieldsSerializer, FieldSelection, FieldTree};
+pub use field_permissions::{
+	FieldPermission, FieldPermissions, FieldRestriction, PermissionedSerializer,
+};
 pub use hyperlinked::{HyperlinkedModelSerializer, UrlReverser};
 pub use introspection::{FieldInfo, FieldIntrospector, TypeMapper};
-pub use meta::{DefaultMeta, MetaConfig, SerializerMeta};
+pub use meta::{DefaultMeta, ExtraKwargs, MetaConfig, SerializerMeta};
 pub use method_field::{
 	MethodFieldError, MethodFieldProvider, MethodFieldRegistry, SerializerMethodField,
 };
-pub use model_serializer::ModelSerializer;
-pub use nested::{ListSerializer, NestedSerializer, WritableNestedSerializer};
+pub use model_serializer::{ModelSerializer, ModelSerializerField};
+pub use nested::{
+	ListErrors, ListSerializer, ListWritePlan, NestedSerializer, NestedWritePlan, OrphanPolicy,
+	WritableNestedSerializer,
+};
 pub use nested_config::{NestedFieldConfig, NestedSerializerConfig};
 pub use nested_orm::{
 	ManyToManyManager, NestedSaveContext, NestedSerializerSave, TransactionHelper,
@@ -243,3 +256,4 @@ pub use relations::{
 };
 pub use validator_config::ValidatorConfig;
 pub use validators::{DatabaseValidatorError, UniqueTogetherValidator, UniqueValidator};
+pub use versioned::{VersionBoundSerializer, VersionTransform, VersionedSerializer};
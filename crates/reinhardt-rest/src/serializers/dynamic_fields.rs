@@ -0,0 +1,448 @@
+//! DynamicFieldsSerializer - Trim serialized output with `?fields=` and `?omit=`
+//!
+//! Clients on slow connections often need only a few fields of a resource.
+//! [`DynamicFieldsSerializer`] wraps any JSON serializer and keeps the fields
+//! requested in the query string:
+//!
+//! - `?fields=id,title,author.name` keeps `id`, `title` and the `name` of the
+//!   nested `author` object
+//! - `?omit=body,author.email` removes those paths from the output
+//!
+//! Requested paths are validated against the serializer's declared fields, so
+//! a typo is reported instead of silently returning an empty object. Lists are
+//! trimmed element by element, for both list endpoints and nested lists.
+//!
+//! # Examples
+//!
+//! ```
+//! use reinhardt_rest::serializers::{DynamicFieldsSerializer, FieldSelection, JsonSerializer};
+//! use reinhardt_rest::serializers::Serializer;
+//! use serde::{Deserialize, Serialize};
+//! use std::collections::HashMap;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Author { name: String, email: String }
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Post { id: i64, title: String, body: String, author: Author }
+//!
+//! let params = HashMap::from([("fields".to_string(), "id,author.name".to_string())]);
+//! let serializer = DynamicFieldsSerializer::new(
+//!     JsonSerializer::<Post>::new(),
+//!     vec!["id", "title", "body", "author", "author.name", "author.email"],
+//! )
+//! .with_selection(FieldSelection::from_query_params(&params));
+//!
+//! let post = Post {
+//!     id: 1,
+//!     title: "Hello".into(),
+//!     body: "...".into(),
+//!     author: Author { name: "Alice".into(), email: "alice@example.com".into() },
+//! };
+//! assert_eq!(
+//!     serializer.serialize(&post).unwrap(),
+//!     r#"{"author":{"name":"Alice"},"id":1}"#
+//! );
+//! ```
+
+use super::{Serializer, SerializerError, ValidatorError};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+
+/// Query parameter listing the fields to keep
+pub const FIELDS_PARAM: &str = "fields";
+/// Query parameter listing the fields to remove
+pub const OMIT_PARAM: &str = "omit";
+
+/// A set of dotted field paths stored as a tree
+///
+/// A node without children selects the whole field.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldTree {
+	children: BTreeMap<String, FieldTree>,
+}
+
+impl FieldTree {
+	/// Build a tree from dotted paths such as `author.name`
+	pub fn from_paths<I, S>(paths: I) -> Self
+	where
+		I: IntoIterator<Item = S>,
+		S: AsRef<str>,
+	{
+		let mut tree = Self::default();
+		for path in paths {
+			tree.insert(path.as_ref());
+		}
+		tree
+	}
+
+	fn insert(&mut self, path: &str) {
+		let mut node = self;
+		for segment in path.split('.').map(str::trim) {
+			if segment.is_empty() {
+				return;
+			}
+			node = node.children.entry(segment.to_string()).or_default();
+		}
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.children.is_empty()
+	}
+
+	/// Dotted paths of the tree's leaves
+	pub fn paths(&self) -> Vec<String> {
+		let mut paths = Vec::new();
+		for (name, child) in &self.children {
+			if child.is_empty() {
+				paths.push(name.clone());
+			} else {
+				paths.extend(
+					child
+						.paths()
+						.into_iter()
+						.map(|path| format!("{}.{}", name, path)),
+				);
+			}
+		}
+		paths
+	}
+
+	/// Keep only the selected fields of `value`
+	fn retain(&self, value: &mut Value) {
+		match value {
+			Value::Object(object) => {
+				object.retain(|name, _| self.children.contains_key(name));
+				for (name, child) in &self.children {
+					if !child.is_empty()
+						&& let Some(nested) = object.get_mut(name)
+					{
+						child.retain(nested);
+					}
+				}
+			}
+			Value::Array(items) => items.iter_mut().for_each(|item| self.retain(item)),
+			_ => {}
+		}
+	}
+
+	/// Remove the selected fields from `value`
+	fn remove(&self, value: &mut Value) {
+		match value {
+			Value::Object(object) => {
+				for (name, child) in &self.children {
+					if child.is_empty() {
+						object.remove(name);
+					} else if let Some(nested) = object.get_mut(name) {
+						child.remove(nested);
+					}
+				}
+			}
+			Value::Array(items) => items.iter_mut().for_each(|item| self.remove(item)),
+			_ => {}
+		}
+	}
+}
+
+/// Fields requested by a client through `?fields=` and `?omit=`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldSelection {
+	fields: Option<FieldTree>,
+	omit: FieldTree,
+}
+
+impl FieldSelection {
+	/// Select every field
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Read the selection from query parameters
+	///
+	/// Both parameters take comma-separated, possibly dotted, field paths.
+	/// An empty `fields` parameter is ignored.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_rest::serializers::FieldSelection;
+	/// use std::collections::HashMap;
+	///
+	/// let params = HashMap::from([
+	///     ("fields".to_string(), "id,author.name".to_string()),
+	///     ("omit".to_string(), "author.name".to_string()),
+	/// ]);
+	/// let selection = FieldSelection::from_query_params(&params);
+	/// assert_eq!(selection.fields(), Some(vec!["author.name".to_string(), "id".to_string()]));
+	/// assert_eq!(selection.omitted(), vec!["author.name".to_string()]);
+	/// ```
+	pub fn from_query_params(params: &HashMap<String, String>) -> Self {
+		let mut selection = Self::new();
+		if let Some(fields) = params.get(FIELDS_PARAM) {
+			selection = selection.with_fields(fields.split(','));
+		}
+		if let Some(omit) = params.get(OMIT_PARAM) {
+			selection = selection.with_omit(omit.split(','));
+		}
+		selection
+	}
+
+	/// Keep only these paths
+	pub fn with_fields<I, S>(mut self, paths: I) -> Self
+	where
+		I: IntoIterator<Item = S>,
+		S: AsRef<str>,
+	{
+		let tree = FieldTree::from_paths(paths);
+		self.fields = if tree.is_empty() { None } else { Some(tree) };
+		self
+	}
+
+	/// Remove these paths
+	pub fn with_omit<I, S>(mut self, paths: I) -> Self
+	where
+		I: IntoIterator<Item = S>,
+		S: AsRef<str>,
+	{
+		self.omit = FieldTree::from_paths(paths);
+		self
+	}
+
+	/// Requested paths, or `None` when all fields are requested
+	pub fn fields(&self) -> Option<Vec<String>> {
+		self.fields.as_ref().map(FieldTree::paths)
+	}
+
+	/// Omitted paths
+	pub fn omitted(&self) -> Vec<String> {
+		self.omit.paths()
+	}
+
+	/// Whether the selection leaves the output unchanged
+	pub fn is_empty(&self) -> bool {
+		self.fields.is_none() && self.omit.is_empty()
+	}
+
+	/// Trim a serialized value in place
+	pub fn apply(&self, value: &mut Value) {
+		if let Some(fields) = &self.fields {
+			fields.retain(value);
+		}
+		self.omit.remove(value);
+	}
+}
+
+/// Serializer wrapper trimming output to the fields requested by the client
+///
+/// `declared_fields` lists the paths clients may request. A nested path such
+/// as `author.name` is checked against the declared paths under `author`; if
+/// none are declared, any sub-path of `author` is accepted.
+pub struct DynamicFieldsSerializer<S> {
+	inner: S,
+	declared_fields: FieldTree,
+	selection: FieldSelection,
+}
+
+impl<S> DynamicFieldsSerializer<S>
+where
+	S: Serializer<Output = String>,
+{
+	/// Wrap a serializer, declaring the paths clients may select
+	pub fn new<I, P>(inner: S, declared_fields: I) -> Self
+	where
+		I: IntoIterator<Item = P>,
+		P: AsRef<str>,
+	{
+		Self {
+			inner,
+			declared_fields: FieldTree::from_paths(declared_fields),
+			selection: FieldSelection::new(),
+		}
+	}
+
+	/// Set the requested fields, typically from [`FieldSelection::from_query_params`]
+	pub fn with_selection(mut self, selection: FieldSelection) -> Self {
+		self.selection = selection;
+		self
+	}
+
+	pub fn selection(&self) -> &FieldSelection {
+		&self.selection
+	}
+
+	pub fn inner(&self) -> &S {
+		&self.inner
+	}
+
+	/// Check that every requested and omitted path is declared
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_rest::serializers::{DynamicFieldsSerializer, FieldSelection, JsonSerializer};
+	///
+	/// let serializer = DynamicFieldsSerializer::new(
+	///     JsonSerializer::<serde_json::Value>::new(),
+	///     vec!["id", "title"],
+	/// )
+	/// .with_selection(FieldSelection::new().with_fields(["id", "titel"]));
+	///
+	/// assert!(serializer.validate_selection().is_err());
+	/// ```
+	pub fn validate_selection(&self) -> Result<(), SerializerError> {
+		let requested = self.selection.fields().unwrap_or_default();
+		for path in requested.iter().chain(self.selection.omitted().iter()) {
+			if !self.is_declared(path) {
+				return Err(SerializerError::Validation(
+					ValidatorError::FieldValidation {
+						field_name: path.clone(),
+						value: path.clone(),
+						constraint: "fields".to_string(),
+						message: format!("Unknown field: {}", path),
+					},
+				));
+			}
+		}
+		Ok(())
+	}
+
+	fn is_declared(&self, path: &str) -> bool {
+		let mut node = &self.declared_fields;
+		for segment in path.split('.') {
+			if node.is_empty() {
+				// Nothing declared below this field
+				return true;
+			}
+			match node.children.get(segment) {
+				Some(child) => node = child,
+				None => return false,
+			}
+		}
+		true
+	}
+
+	/// Trim an already serialized value with the current selection
+	pub fn apply(&self, value: &mut Value) -> Result<(), SerializerError> {
+		self.validate_selection()?;
+		self.selection.apply(value);
+		Ok(())
+	}
+}
+
+impl<S> Serializer for DynamicFieldsSerializer<S>
+where
+	S: Serializer<Output = String>,
+{
+	type Input = S::Input;
+	type Output = String;
+
+	fn serialize(&self, input: &Self::Input) -> Result<Self::Output, SerializerError> {
+		let output = self.inner.serialize(input)?;
+		if self.selection.is_empty() {
+			return Ok(output);
+		}
+
+		let mut value: Value =
+			serde_json::from_str(&output).map_err(|e| SerializerError::Serde {
+				message: format!("Serialization error: {}", e),
+			})?;
+		self.apply(&mut value)?;
+		serde_json::to_string(&value).map_err(|e| SerializerError::Serde {
+			message: format!("Serialization error: {}", e),
+		})
+	}
+
+	fn deserialize(&self, output: &Self::Output) -> Result<Self::Input, SerializerError> {
+		self.inner.deserialize(output)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::serializers::JsonSerializer;
+	use serde_json::json;
+
+	fn serializer(selection: FieldSelection) -> DynamicFieldsSerializer<JsonSerializer<Value>> {
+		DynamicFieldsSerializer::new(
+			JsonSerializer::<Value>::new(),
+			vec!["id", "title", "author.name", "author.email", "tags"],
+		)
+		.with_selection(selection)
+	}
+
+	fn post() -> Value {
+		json!({
+			"id": 1,
+			"title": "Hello",
+			"author": {"name": "Alice", "email": "alice@example.com"},
+			"tags": [{"slug": "rust", "label": "Rust"}]
+		})
+	}
+
+	fn serialize(selection: FieldSelection, value: &Value) -> Value {
+		let output = serializer(selection).serialize(value).unwrap();
+		serde_json::from_str(&output).unwrap()
+	}
+
+	#[test]
+	fn test_fields_keeps_nested_paths() {
+		let output = serialize(
+			FieldSelection::new().with_fields(["id", "author.name"]),
+			&post(),
+		);
+
+		assert_eq!(output, json!({"id": 1, "author": {"name": "Alice"}}));
+	}
+
+	#[test]
+	fn test_omit_removes_nested_paths() {
+		let output = serialize(
+			FieldSelection::new().with_omit(["title", "author.email", "tags"]),
+			&post(),
+		);
+
+		assert_eq!(output, json!({"id": 1, "author": {"name": "Alice"}}));
+	}
+
+	#[test]
+	fn test_selection_applies_to_list_items() {
+		let output = serialize(
+			FieldSelection::new().with_fields(["id", "tags.slug"]),
+			&json!([post(), post()]),
+		);
+
+		assert_eq!(
+			output,
+			json!([
+				{"id": 1, "tags": [{"slug": "rust"}]},
+				{"id": 1, "tags": [{"slug": "rust"}]}
+			])
+		);
+	}
+
+	#[test]
+	fn test_unknown_fields_are_rejected() {
+		let unknown_nested = serializer(FieldSelection::new().with_fields(["author.phone"]));
+		assert!(unknown_nested.serialize(&post()).is_err());
+
+		let unknown_omit = serializer(FieldSelection::new().with_omit(["password"]));
+		assert!(unknown_omit.serialize(&post()).is_err());
+
+		// Nothing is declared below "tags", so any sub-path is accepted
+		let undeclared_children = serializer(FieldSelection::new().with_fields(["tags.slug"]));
+		assert!(undeclared_children.serialize(&post()).is_ok());
+	}
+
+	#[test]
+	fn test_query_params_ignore_blank_entries() {
+		let params = HashMap::from([(FIELDS_PARAM.to_string(), " id, ,title ".to_string())]);
+		let selection = FieldSelection::from_query_params(&params);
+
+		assert_eq!(
+			selection.fields(),
+			Some(vec!["id".to_string(), "title".to_string()])
+		);
+		assert!(FieldSelection::from_query_params(&HashMap::new()).is_empty());
+	}
+}
@@ -0,0 +1,416 @@
+//! Field-level visibility based on the requesting user's permissions
+//!
+//! Instead of maintaining separate "admin" and "public" serializers, mark the
+//! sensitive fields of one serializer with the permissions they require, or
+//! with a predicate on the user. Fields the caller may not access are either
+//! omitted from the output or kept visible but read-only.
+//!
+//! # Examples
+//!
+//! ```
+//! use reinhardt_auth::PermissionsMixin;
+//! use reinhardt_rest::serializers::{
+//!     FieldPermission, FieldPermissions, JsonSerializer, PermissionedSerializer, Serializer,
+//! };
+//! use serde_json::{Value, json};
+//!
+//! struct Staff { perms: Vec<String> }
+//!
+//! impl PermissionsMixin for Staff {
+//!     fn is_superuser(&self) -> bool { false }
+//!     fn user_permissions(&self) -> &[String] { &self.perms }
+//!     fn groups(&self) -> &[String] { &[] }
+//! }
+//!
+//! let permissions = FieldPermissions::<Staff>::new()
+//!     .field("email", FieldPermission::requires("accounts.view_email"))
+//!     .field("salary", FieldPermission::requires("hr.view_salary"));
+//!
+//! let user = json!({"username": "alice", "email": "alice@example.com", "salary": 100});
+//! let staff = Staff { perms: vec!["accounts.view_email".to_string()] };
+//!
+//! let serializer = PermissionedSerializer::new(JsonSerializer::<Value>::new(), &permissions)
+//!     .for_user(Some(&staff));
+//! assert_eq!(
+//!     serializer.serialize(&user).unwrap(),
+//!     r#"{"email":"alice@example.com","username":"alice"}"#
+//! );
+//! ```
+
+use super::{Serializer, SerializerError};
+use reinhardt_auth::PermissionsMixin;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// What happens to a field the caller may not access
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FieldRestriction {
+	/// Remove the field from the output and ignore it in input (default)
+	#[default]
+	Omit,
+	/// Keep the field in the output but ignore it in input
+	ReadOnly,
+}
+
+/// Predicate deciding whether a user may access a field
+pub type FieldPredicate<U> = Arc<dyn Fn(&U) -> bool + Send + Sync>;
+
+/// Access requirements of a single field
+///
+/// A user must hold every required permission and satisfy every predicate.
+/// Anonymous callers never satisfy a rule.
+pub struct FieldPermission<U: ?Sized> {
+	permissions: Vec<String>,
+	predicates: Vec<FieldPredicate<U>>,
+	restriction: FieldRestriction,
+}
+
+impl<U: ?Sized> Clone for FieldPermission<U> {
+	fn clone(&self) -> Self {
+		Self {
+			permissions: self.permissions.clone(),
+			predicates: self.predicates.clone(),
+			restriction: self.restriction,
+		}
+	}
+}
+
+impl<U: PermissionsMixin + ?Sized> FieldPermission<U> {
+	/// Require authentication only
+	pub fn authenticated() -> Self {
+		Self {
+			permissions: Vec::new(),
+			predicates: Vec::new(),
+			restriction: FieldRestriction::Omit,
+		}
+	}
+
+	/// Require a permission such as `"hr.view_salary"`
+	pub fn requires(permission: impl Into<String>) -> Self {
+		Self::authenticated().and_requires(permission)
+	}
+
+	/// Require a predicate on the user, e.g. `|user| user.is_superuser()`
+	pub fn when<F>(predicate: F) -> Self
+	where
+		F: Fn(&U) -> bool + Send + Sync + 'static,
+	{
+		Self::authenticated().and_when(predicate)
+	}
+
+	/// Also require `permission`
+	pub fn and_requires(mut self, permission: impl Into<String>) -> Self {
+		self.permissions.push(permission.into());
+		self
+	}
+
+	/// Also require `predicate`
+	pub fn and_when<F>(mut self, predicate: F) -> Self
+	where
+		F: Fn(&U) -> bool + Send + Sync + 'static,
+	{
+		self.predicates.push(Arc::new(predicate));
+		self
+	}
+
+	/// Keep the field visible but read-only for users without access
+	pub fn read_only_otherwise(mut self) -> Self {
+		self.restriction = FieldRestriction::ReadOnly;
+		self
+	}
+
+	pub fn restriction(&self) -> FieldRestriction {
+		self.restriction
+	}
+
+	/// Whether `user` may access the field
+	pub fn allows(&self, user: Option<&U>) -> bool {
+		let Some(user) = user else {
+			return false;
+		};
+		let permissions: Vec<&str> = self.permissions.iter().map(String::as_str).collect();
+		user.has_perms(&permissions) && self.predicates.iter().all(|predicate| predicate(user))
+	}
+}
+
+/// Access rules of a serializer's fields, keyed by field name
+///
+/// Fields without a rule are accessible to everyone.
+pub struct FieldPermissions<U: ?Sized> {
+	rules: HashMap<String, FieldPermission<U>>,
+}
+
+impl<U: ?Sized> Clone for FieldPermissions<U> {
+	fn clone(&self) -> Self {
+		Self {
+			rules: self.rules.clone(),
+		}
+	}
+}
+
+impl<U: ?Sized> Default for FieldPermissions<U> {
+	fn default() -> Self {
+		Self {
+			rules: HashMap::new(),
+		}
+	}
+}
+
+impl<U: PermissionsMixin + ?Sized> FieldPermissions<U> {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Set the access rule of a field
+	pub fn field(mut self, name: impl Into<String>, permission: FieldPermission<U>) -> Self {
+		self.rules.insert(name.into(), permission);
+		self
+	}
+
+	pub fn rule(&self, name: &str) -> Option<&FieldPermission<U>> {
+		self.rules.get(name)
+	}
+
+	/// How `name` is restricted for `user`, or `None` if it is accessible
+	pub fn restriction(&self, name: &str, user: Option<&U>) -> Option<FieldRestriction> {
+		self.rules
+			.get(name)
+			.filter(|rule| !rule.allows(user))
+			.map(FieldPermission::restriction)
+	}
+
+	fn restricted(&self, user: Option<&U>, restriction: FieldRestriction) -> Vec<String> {
+		let mut fields: Vec<String> = self
+			.rules
+			.iter()
+			.filter(|(_, rule)| rule.restriction == restriction && !rule.allows(user))
+			.map(|(name, _)| name.clone())
+			.collect();
+		fields.sort();
+		fields
+	}
+
+	/// Fields omitted from the output for `user`
+	pub fn hidden_fields(&self, user: Option<&U>) -> Vec<String> {
+		self.restricted(user, FieldRestriction::Omit)
+	}
+
+	/// Fields shown to `user` but not writable by them
+	pub fn read_only_fields(&self, user: Option<&U>) -> Vec<String> {
+		self.restricted(user, FieldRestriction::ReadOnly)
+	}
+
+	/// Remove the fields hidden from `user` from serialized output
+	///
+	/// Lists are filtered element by element.
+	pub fn filter_output(&self, value: &mut Value, user: Option<&U>) {
+		let hidden = self.hidden_fields(user);
+		if hidden.is_empty() {
+			return;
+		}
+		remove_fields(value, &hidden);
+	}
+
+	/// Drop the fields `user` may not write from input data
+	///
+	/// Like read-only fields, restricted fields in a payload are ignored
+	/// rather than rejected.
+	pub fn filter_input(&self, value: &mut Value, user: Option<&U>) {
+		let mut denied = self.hidden_fields(user);
+		denied.extend(self.read_only_fields(user));
+		remove_fields(value, &denied);
+	}
+}
+
+fn remove_fields(value: &mut Value, fields: &[String]) {
+	match value {
+		Value::Object(object) => {
+			for field in fields {
+				object.remove(field);
+			}
+		}
+		Value::Array(items) => items
+			.iter_mut()
+			.for_each(|item| remove_fields(item, fields)),
+		_ => {}
+	}
+}
+
+/// Serializer wrapper applying [`FieldPermissions`] for the requesting user
+///
+/// Output omits hidden fields; on deserialization, fields the user may not
+/// write are dropped before the inner serializer sees them.
+pub struct PermissionedSerializer<'a, S, U: ?Sized> {
+	inner: S,
+	permissions: &'a FieldPermissions<U>,
+	user: Option<&'a U>,
+}
+
+impl<'a, S, U> PermissionedSerializer<'a, S, U>
+where
+	S: Serializer<Output = String>,
+	U: PermissionsMixin + ?Sized,
+{
+	/// Wrap a serializer; the caller is anonymous until [`Self::for_user`]
+	pub fn new(inner: S, permissions: &'a FieldPermissions<U>) -> Self {
+		Self {
+			inner,
+			permissions,
+			user: None,
+		}
+	}
+
+	/// Set the requesting user
+	pub fn for_user(mut self, user: Option<&'a U>) -> Self {
+		self.user = user;
+		self
+	}
+
+	pub fn inner(&self) -> &S {
+		&self.inner
+	}
+}
+
+impl<S, U> Serializer for PermissionedSerializer<'_, S, U>
+where
+	S: Serializer<Output = String>,
+	U: PermissionsMixin + ?Sized,
+{
+	type Input = S::Input;
+	type Output = String;
+
+	fn serialize(&self, input: &Self::Input) -> Result<Self::Output, SerializerError> {
+		let output = self.inner.serialize(input)?;
+		if self.permissions.hidden_fields(self.user).is_empty() {
+			return Ok(output);
+		}
+
+		let mut value: Value =
+			serde_json::from_str(&output).map_err(|e| SerializerError::Serde {
+				message: format!("Serialization error: {}", e),
+			})?;
+		self.permissions.filter_output(&mut value, self.user);
+		serde_json::to_string(&value).map_err(|e| SerializerError::Serde {
+			message: format!("Serialization error: {}", e),
+		})
+	}
+
+	fn deserialize(&self, output: &Self::Output) -> Result<Self::Input, SerializerError> {
+		let mut value: Value =
+			serde_json::from_str(output).map_err(|e| SerializerError::Serde {
+				message: format!("Deserialization error: {}", e),
+			})?;
+		self.permissions.filter_input(&mut value, self.user);
+		let output = serde_json::to_string(&value).map_err(|e| SerializerError::Serde {
+			message: format!("Serialization error: {}", e),
+		})?;
+		self.inner.deserialize(&output)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::serializers::JsonSerializer;
+	use serde_json::json;
+
+	struct TestUser {
+		superuser: bool,
+		perms: Vec<String>,
+	}
+
+	impl PermissionsMixin for TestUser {
+		fn is_superuser(&self) -> bool {
+			self.superuser
+		}
+		fn user_permissions(&self) -> &[String] {
+			&self.perms
+		}
+		fn groups(&self) -> &[String] {
+			&[]
+		}
+	}
+
+	fn user(perms: &[&str]) -> TestUser {
+		TestUser {
+			superuser: false,
+			perms: perms.iter().map(|p| p.to_string()).collect(),
+		}
+	}
+
+	fn permissions() -> FieldPermissions<TestUser> {
+		FieldPermissions::new()
+			.field("email", FieldPermission::requires("accounts.view_email"))
+			.field(
+				"role",
+				FieldPermission::when(|user: &TestUser| user.is_superuser()).read_only_otherwise(),
+			)
+	}
+
+	fn account() -> Value {
+		json!({"username": "alice", "email": "alice@example.com", "role": "member"})
+	}
+
+	#[test]
+	fn test_anonymous_users_see_only_public_and_read_only_fields() {
+		let permissions = permissions();
+		let serializer = PermissionedSerializer::new(JsonSerializer::<Value>::new(), &permissions);
+
+		let output: Value =
+			serde_json::from_str(&serializer.serialize(&account()).unwrap()).unwrap();
+
+		assert_eq!(output, json!({"username": "alice", "role": "member"}));
+		assert_eq!(permissions.hidden_fields(None), vec!["email".to_string()]);
+		assert_eq!(permissions.read_only_fields(None), vec!["role".to_string()]);
+	}
+
+	#[test]
+	fn test_permissions_and_predicates_grant_access() {
+		let permissions = permissions();
+		let viewer = user(&["accounts.view_email"]);
+		let admin = TestUser {
+			superuser: true,
+			perms: vec![],
+		};
+
+		assert_eq!(permissions.restriction("email", Some(&viewer)), None);
+		assert_eq!(
+			permissions.restriction("role", Some(&viewer)),
+			Some(FieldRestriction::ReadOnly)
+		);
+		assert!(permissions.hidden_fields(Some(&admin)).is_empty());
+		assert!(permissions.read_only_fields(Some(&admin)).is_empty());
+	}
+
+	#[test]
+	fn test_output_lists_are_filtered_per_item() {
+		let permissions = permissions();
+		let mut value = json!([account(), account()]);
+
+		permissions.filter_output(&mut value, Some(&user(&[])));
+
+		assert!(
+			value
+				.as_array()
+				.unwrap()
+				.iter()
+				.all(|item| item.get("email").is_none() && item.get("role").is_some())
+		);
+	}
+
+	#[test]
+	fn test_deserialize_drops_restricted_fields() {
+		let permissions = permissions();
+		let viewer = user(&["accounts.view_email"]);
+		let serializer = PermissionedSerializer::new(JsonSerializer::<Value>::new(), &permissions)
+			.for_user(Some(&viewer));
+
+		let input = serializer.deserialize(&account().to_string()).unwrap();
+
+		assert_eq!(
+			input,
+			json!({"username": "alice", "email": "alice@example.com"})
+		);
+	}
+}
@@ -2,7 +2,61 @@
 ///
 /// This module provides Django REST Framework-style Meta configuration
 /// for customizing serializer behavior.
-use std::collections::HashSet;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// Per-field options overriding what a serializer derives from the model
+///
+/// Mirrors Django REST Framework's `extra_kwargs`. Unset options keep the
+/// derived behavior.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_rest::serializers::meta::{ExtraKwargs, MetaConfig};
+///
+/// let config = MetaConfig::new()
+///     .with_extra_kwargs("password", ExtraKwargs::new().write_only(true))
+///     .with_extra_kwargs("nickname", ExtraKwargs::new().required(false));
+///
+/// assert!(config.is_write_only("password"));
+/// assert_eq!(config.extra_kwargs("nickname").unwrap().required, Some(false));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExtraKwargs {
+	pub read_only: Option<bool>,
+	pub write_only: Option<bool>,
+	pub required: Option<bool>,
+	pub allow_null: Option<bool>,
+	pub default: Option<Value>,
+}
+
+impl ExtraKwargs {
+	pub fn new() -> Self {
+		<Self as Default>::default()
+	}
+	pub fn read_only(mut self, read_only: bool) -> Self {
+		self.read_only = Some(read_only);
+		self
+	}
+	pub fn write_only(mut self, write_only: bool) -> Self {
+		self.write_only = Some(write_only);
+		self
+	}
+	pub fn required(mut self, required: bool) -> Self {
+		self.required = Some(required);
+		self
+	}
+	pub fn allow_null(mut self, allow_null: bool) -> Self {
+		self.allow_null = Some(allow_null);
+		self
+	}
+	/// Value used on create when the field is omitted
+	pub fn default(mut self, default: Value) -> Self {
+		self.default = Some(default);
+		self
+	}
+}
 
 /// Meta configuration trait for serializers
 ///
@@ -57,6 +111,11 @@ pub trait SerializerMeta {
 		vec![]
 	}
 
+	/// Specify per-field option overrides
+	fn extra_kwargs() -> HashMap<String, ExtraKwargs> {
+		HashMap::new()
+	}
+
 	/// Get the effective field set after applying all configuration
 	///
 	/// This method computes the final set of fields to be used for serialization
@@ -117,6 +176,7 @@ pub struct MetaConfig {
 	exclude: Vec<String>,
 	read_only_fields: Vec<String>,
 	write_only_fields: Vec<String>,
+	extra_kwargs: HashMap<String, ExtraKwargs>,
 }
 
 impl MetaConfig {
@@ -125,6 +185,34 @@ impl MetaConfig {
 		Self::default()
 	}
 
+	/// Create a configuration from a [`SerializerMeta`] type
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_rest::serializers::meta::{MetaConfig, SerializerMeta};
+	///
+	/// struct UserMeta;
+	///
+	/// impl SerializerMeta for UserMeta {
+	///     fn exclude() -> Vec<String> {
+	///         vec!["password_hash".to_string()]
+	///     }
+	/// }
+	///
+	/// let config = MetaConfig::from_meta::<UserMeta>();
+	/// assert!(!config.is_field_included("password_hash"));
+	/// ```
+	pub fn from_meta<T: SerializerMeta>() -> Self {
+		Self {
+			fields: T::fields(),
+			exclude: T::exclude(),
+			read_only_fields: T::read_only_fields(),
+			write_only_fields: T::write_only_fields(),
+			extra_kwargs: T::extra_kwargs(),
+		}
+	}
+
 	/// Specify which fields to include
 	pub fn with_fields(mut self, fields: Vec<String>) -> Self {
 		self.fields = Some(fields);
@@ -149,6 +237,17 @@ impl MetaConfig {
 		self
 	}
 
+	/// Set option overrides for a field
+	pub fn with_extra_kwargs(mut self, field: impl Into<String>, kwargs: ExtraKwargs) -> Self {
+		self.extra_kwargs.insert(field.into(), kwargs);
+		self
+	}
+
+	/// Get the option overrides for a field
+	pub fn extra_kwargs(&self, field_name: &str) -> Option<&ExtraKwargs> {
+		self.extra_kwargs.get(field_name)
+	}
+
 	/// Get effective field set
 	pub fn effective_fields(&self, all_fields: &[String]) -> HashSet<String> {
 		let mut fields: HashSet<String> = if let Some(included) = &self.fields {
@@ -179,13 +278,21 @@ impl MetaConfig {
 	}
 
 	/// Check if a field is read-only
+	///
+	/// `extra_kwargs` take precedence over `read_only_fields`.
 	pub fn is_read_only(&self, field_name: &str) -> bool {
-		self.read_only_fields.contains(&field_name.to_string())
+		self.extra_kwargs(field_name)
+			.and_then(|kwargs| kwargs.read_only)
+			.unwrap_or_else(|| self.read_only_fields.contains(&field_name.to_string()))
 	}
 
 	/// Check if a field is write-only
+	///
+	/// `extra_kwargs` take precedence over `write_only_fields`.
 	pub fn is_write_only(&self, field_name: &str) -> bool {
-		self.write_only_fields.contains(&field_name.to_string())
+		self.extra_kwargs(field_name)
+			.and_then(|kwargs| kwargs.write_only)
+			.unwrap_or_else(|| self.write_only_fields.contains(&field_name.to_string()))
 	}
 
 	/// Get the list of included fields
@@ -258,6 +365,18 @@ mod tests {
 		assert!(!config.is_write_only("email"));
 	}
 
+	#[test]
+	fn test_meta_config_extra_kwargs_override_lists() {
+		let config = MetaConfig::new()
+			.with_read_only_fields(vec!["slug".to_string()])
+			.with_extra_kwargs("slug", ExtraKwargs::new().read_only(false))
+			.with_extra_kwargs("token", ExtraKwargs::new().write_only(true));
+
+		assert!(!config.is_read_only("slug"));
+		assert!(config.is_write_only("token"));
+		assert!(config.extra_kwargs("name").is_none());
+	}
+
 	#[test]
 	fn test_meta_config_effective_fields() {
 		let all_fields = vec![
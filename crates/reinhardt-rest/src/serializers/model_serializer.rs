@@ -2,18 +2,85 @@
 //!
 //! This module provides ModelSerializer that automatically generates
 //! serialization logic from ORM models.
+//!
+//! The field set is derived from the metadata generated by `#[model(...)]`
+//! ([`Model::field_metadata`]) and filtered by the serializer's [`MetaConfig`]:
+//!
+//! ```rust,ignore
+//! use reinhardt_rest::serializers::ModelSerializer;
+//! use reinhardt_rest::serializers::meta::{ExtraKwargs, SerializerMeta};
+//!
+//! struct UserMeta;
+//!
+//! impl SerializerMeta for UserMeta {
+//!     fn fields() -> Option<Vec<String>> {
+//!         Some(vec!["id".into(), "username".into(), "password".into()])
+//!     }
+//!     fn extra_kwargs() -> HashMap<String, ExtraKwargs> {
+//!         HashMap::from([("password".into(), ExtraKwargs::new().write_only(true))])
+//!     }
+//! }
+//!
+//! let serializer = ModelSerializer::<User>::from_meta::<UserMeta>();
+//! let user = serializer.create(&request_body).await?;
+//! let json = serializer.to_representation(&user)?;
+//! ```
 
 use super::introspection::{FieldInfo, FieldIntrospector};
-use super::meta::MetaConfig;
+use super::meta::{MetaConfig, SerializerMeta};
 use super::nested_config::{NestedFieldConfig, NestedSerializerConfig};
 use super::validator_config::ValidatorConfig;
 use super::validators::{UniqueTogetherValidator, UniqueValidator};
 use super::{Serializer, SerializerError, ValidatorError};
 use reinhardt_db::backends::DatabaseConnection;
 use reinhardt_db::orm::Model;
+use reinhardt_db::orm::fields::FieldKwarg;
+use serde_json::{Map, Value};
 use std::collections::HashMap;
 use std::marker::PhantomData;
 
+/// A serializer field derived from model metadata and Meta configuration
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelSerializerField {
+	pub name: String,
+	/// Model field type, e.g. "CharField" (or the Rust type for introspected fields)
+	pub field_type: String,
+	pub primary_key: bool,
+	pub read_only: bool,
+	pub write_only: bool,
+	pub required: bool,
+	pub allow_null: bool,
+	/// Value used on create when the field is omitted
+	pub default: Option<Value>,
+	pub choices: Option<Vec<(String, String)>>,
+}
+
+/// Convert a model field default to JSON; callable defaults have no static value
+fn kwarg_to_value(kwarg: &FieldKwarg) -> Option<Value> {
+	match kwarg {
+		FieldKwarg::String(s) => Some(Value::String(s.clone())),
+		FieldKwarg::Int(n) => Some(Value::from(*n)),
+		FieldKwarg::Uint(n) => Some(Value::from(*n)),
+		FieldKwarg::Bool(b) => Some(Value::Bool(*b)),
+		FieldKwarg::Float(f) => serde_json::Number::from_f64(*f).map(Value::Number),
+		FieldKwarg::Choices(_) | FieldKwarg::Callable(_) => None,
+	}
+}
+
+fn field_error(
+	field_name: &str,
+	value: &Value,
+	constraint: &str,
+	message: &str,
+) -> SerializerError {
+	SerializerError::Validation(ValidatorError::FieldValidation {
+		field_name: field_name.to_string(),
+		value: value.to_string(),
+		constraint: constraint.to_string(),
+		message: message.to_string(),
+	})
+}
+
 /// ModelSerializer provides automatic serialization for ORM models
 ///
 /// Inspired by Django REST Framework's ModelSerializer, this automatically
@@ -84,6 +151,32 @@ where
 		}
 	}
 
+	/// Create a ModelSerializer configured by a [`SerializerMeta`] type
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use reinhardt_rest::serializers::ModelSerializer;
+	/// # use reinhardt_rest::serializers::meta::SerializerMeta;
+	/// # use reinhardt_auth::DefaultUser;
+	/// #
+	/// struct UserMeta;
+	///
+	/// impl SerializerMeta for UserMeta {
+	///     fn read_only_fields() -> Vec<String> {
+	///         vec!["username".to_string()]
+	///     }
+	/// }
+	///
+	/// let serializer = ModelSerializer::<DefaultUser>::from_meta::<UserMeta>();
+	/// assert!(serializer.meta().is_read_only("username"));
+	/// ```
+	pub fn from_meta<T: SerializerMeta>() -> Self {
+		let mut serializer = Self::new();
+		serializer.meta = MetaConfig::from_meta::<T>();
+		serializer
+	}
+
 	/// Specify which fields to include in serialization
 	///
 	/// # Examples
@@ -148,6 +241,28 @@ where
 		self
 	}
 
+	/// Override options of a single field, like DRF's `extra_kwargs`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use reinhardt_rest::serializers::ModelSerializer;
+	/// # use reinhardt_rest::serializers::meta::ExtraKwargs;
+	/// # use reinhardt_auth::DefaultUser;
+	/// #
+	/// let serializer = ModelSerializer::<DefaultUser>::new()
+	///     .with_extra_kwargs("password_hash", ExtraKwargs::new().write_only(true));
+	/// assert!(serializer.meta().is_write_only("password_hash"));
+	/// ```
+	pub fn with_extra_kwargs(
+		mut self,
+		field: impl Into<String>,
+		kwargs: super::meta::ExtraKwargs,
+	) -> Self {
+		self.meta = self.meta.with_extra_kwargs(field, kwargs);
+		self
+	}
+
 	/// Get the meta configuration
 	pub fn meta(&self) -> &MetaConfig {
 		&self.meta
@@ -371,6 +486,228 @@ where
 			.and_then(|i| i.primary_key_field())
 	}
 
+	/// Build the serializer's field set
+	///
+	/// Fields come from [`Model::field_metadata`], or from the introspector
+	/// for models without metadata, in `fields` order when one is configured.
+	/// Non-editable fields and auto primary keys are read-only; a field is
+	/// required unless it is read-only, nullable, blank or has a default.
+	/// `read_only_fields`, `write_only_fields` and `extra_kwargs` then apply.
+	pub fn fields(&self) -> Vec<ModelSerializerField> {
+		let metadata = M::field_metadata();
+		let mut fields: Vec<ModelSerializerField> = if !metadata.is_empty() {
+			metadata
+				.iter()
+				.map(|info| {
+					let field_type = info
+						.field_type
+						.rsplit('.')
+						.next()
+						.unwrap_or_default()
+						.to_string();
+					let read_only =
+						!info.editable || (info.primary_key && field_type.ends_with("AutoField"));
+					let default = info.default.as_ref().and_then(kwarg_to_value);
+					ModelSerializerField {
+						name: info.name.clone(),
+						primary_key: info.primary_key,
+						read_only,
+						write_only: false,
+						required: !read_only
+							&& !info.nullable && !info.blank
+							&& info.default.is_none()
+							&& info.db_default.is_none(),
+						allow_null: info.nullable,
+						default,
+						choices: info.choices.clone(),
+						field_type,
+					}
+				})
+				.collect()
+		} else if let Some(introspector) = &self.introspector {
+			introspector
+				.get_fields()
+				.iter()
+				.map(|info| ModelSerializerField {
+					name: info.name.clone(),
+					field_type: info.type_name.clone(),
+					primary_key: info.is_primary_key,
+					read_only: false,
+					write_only: false,
+					required: !info.is_optional,
+					allow_null: info.is_optional,
+					default: None,
+					choices: None,
+				})
+				.collect()
+		} else {
+			vec![]
+		};
+
+		fields.retain(|field| self.meta.is_field_included(&field.name));
+		if let Some(order) = self.meta.fields() {
+			fields.sort_by_key(|field| order.iter().position(|name| *name == field.name));
+		}
+
+		for field in &mut fields {
+			field.read_only = field.read_only || self.meta.is_read_only(&field.name);
+			field.write_only = self.meta.is_write_only(&field.name);
+			if let Some(kwargs) = self.meta.extra_kwargs(&field.name) {
+				if let Some(read_only) = kwargs.read_only {
+					field.read_only = read_only;
+				}
+				if let Some(allow_null) = kwargs.allow_null {
+					field.allow_null = allow_null;
+				}
+				if kwargs.default.is_some() {
+					field.default = kwargs.default.clone();
+				}
+			}
+			field.required = match self.meta.extra_kwargs(&field.name).and_then(|k| k.required) {
+				Some(required) => required && !field.read_only,
+				None => field.required && !field.read_only && field.default.is_none(),
+			};
+		}
+		fields
+	}
+
+	/// Serialize an instance to a JSON object of its readable fields
+	///
+	/// Write-only and excluded fields are left out. Models without field
+	/// metadata or introspector keep every serialized field not excluded.
+	pub fn to_representation(&self, instance: &M) -> Result<Value, SerializerError> {
+		let mut json = serde_json::to_value(instance).map_err(|e| SerializerError::Serde {
+			message: format!("Serialization error: {}", e),
+		})?;
+		if let Value::Object(object) = &mut json {
+			let fields = self.fields();
+			if fields.is_empty() {
+				object.retain(|name, _| {
+					self.meta.is_field_included(name) && !self.meta.is_write_only(name)
+				});
+			} else {
+				let mut ordered = Map::new();
+				for field in fields.iter().filter(|field| !field.write_only) {
+					if let Some(value) = object.remove(&field.name) {
+						ordered.insert(field.name.clone(), value);
+					}
+				}
+				*object = ordered;
+			}
+		}
+		Ok(json)
+	}
+
+	/// Validate incoming data against the writable fields
+	///
+	/// Read-only and unknown keys are dropped. Unless `partial`, missing
+	/// required fields are errors and missing fields with a default get it.
+	pub fn validate_data(
+		&self,
+		data: &Value,
+		partial: bool,
+	) -> Result<Map<String, Value>, SerializerError> {
+		let object = data.as_object().ok_or_else(|| {
+			SerializerError::Validation(ValidatorError::Custom {
+				message: "Expected a JSON object".to_string(),
+			})
+		})?;
+
+		let mut validated = Map::new();
+		for field in self.fields().into_iter().filter(|field| !field.read_only) {
+			let Some(value) = object.get(&field.name) else {
+				if partial {
+					continue;
+				}
+				if field.required {
+					return Err(SerializerError::Validation(ValidatorError::RequiredField {
+						field_name: field.name.clone(),
+						message: "This field is required.".to_string(),
+					}));
+				}
+				if let Some(default) = field.default {
+					validated.insert(field.name, default);
+				}
+				continue;
+			};
+
+			if value.is_null() {
+				if !field.allow_null {
+					return Err(field_error(
+						&field.name,
+						value,
+						"null",
+						"This field may not be null.",
+					));
+				}
+			} else if let Some(choices) = &field.choices {
+				let as_string = value
+					.as_str()
+					.map(str::to_string)
+					.unwrap_or_else(|| value.to_string());
+				if !choices.iter().any(|(choice, _)| *choice == as_string) {
+					return Err(field_error(
+						&field.name,
+						value,
+						"choices",
+						"Value is not a valid choice.",
+					));
+				}
+			}
+			validated.insert(field.name, value.clone());
+		}
+		Ok(validated)
+	}
+
+	/// Validate data and insert a new instance through the ORM
+	///
+	/// Model fields absent from the validated data are set to null, so
+	/// they must be optional in the model struct.
+	pub async fn create(&self, data: &Value) -> Result<M, SerializerError> {
+		let mut object = self.validate_data(data, false)?;
+		for info in M::field_metadata() {
+			object.entry(info.name).or_insert(Value::Null);
+		}
+		let mut instance: M =
+			serde_json::from_value(Value::Object(object)).map_err(|e| SerializerError::Serde {
+				message: format!("Failed to build instance: {}", e),
+			})?;
+		self.validate(&instance)?;
+		instance.save().await.map_err(|e| SerializerError::Other {
+			message: format!("Failed to save instance: {}", e),
+		})?;
+		Ok(instance)
+	}
+
+	/// Validate data, apply it to an instance and update it through the ORM
+	///
+	/// With `partial`, only the fields present in `data` are validated and
+	/// changed (PATCH semantics).
+	pub async fn update(
+		&self,
+		instance: &M,
+		data: &Value,
+		partial: bool,
+	) -> Result<M, SerializerError> {
+		let validated = self.validate_data(data, partial)?;
+		let mut json = serde_json::to_value(instance).map_err(|e| SerializerError::Serde {
+			message: format!("Serialization error: {}", e),
+		})?;
+		let object = json.as_object_mut().ok_or_else(|| SerializerError::Serde {
+			message: "Model does not serialize to an object".to_string(),
+		})?;
+		object.extend(validated);
+
+		let mut updated: M = serde_json::from_value(json).map_err(|e| SerializerError::Serde {
+			message: format!("Failed to apply changes: {}", e),
+		})?;
+		self.validate(&updated)?;
+		updated.save().await.map_err(|e| SerializerError::Other {
+			message: format!("Failed to save instance: {}", e),
+		})?;
+		Ok(updated)
+	}
+
 	/// Add a unique field validator
 	///
 	/// # Examples
@@ -573,7 +910,8 @@ where
 	type Output = String;
 
 	fn serialize(&self, input: &Self::Input) -> Result<Self::Output, SerializerError> {
-		serde_json::to_string(input).map_err(|e| SerializerError::Serde {
+		let json = self.to_representation(input)?;
+		serde_json::to_string(&json).map_err(|e| SerializerError::Serde {
 			message: format!("Serialization error: {}", e),
 		})
 	}
@@ -584,3 +922,273 @@ where
 		})
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::serializers::meta::ExtraKwargs;
+	use reinhardt_db::orm::FieldSelector;
+	use reinhardt_db::orm::fields::{AutoField, CharField, Field, IntegerField};
+	use reinhardt_db::orm::inspection::FieldInfo as ModelFieldInfo;
+	use serde::{Deserialize, Serialize};
+	use serde_json::json;
+
+	#[derive(Debug, Clone, Serialize, Deserialize)]
+	struct Account {
+		id: Option<i64>,
+		username: String,
+		password: String,
+		plan: String,
+		age: Option<i64>,
+	}
+
+	#[derive(Clone)]
+	struct AccountFields;
+
+	impl FieldSelector for AccountFields {
+		fn with_alias(self, _alias: &str) -> Self {
+			self
+		}
+	}
+
+	impl Model for Account {
+		type PrimaryKey = i64;
+		type Fields = AccountFields;
+
+		fn table_name() -> &'static str {
+			"accounts"
+		}
+		fn new_fields() -> Self::Fields {
+			AccountFields
+		}
+		fn primary_key(&self) -> Option<Self::PrimaryKey> {
+			self.id
+		}
+		fn set_primary_key(&mut self, value: Self::PrimaryKey) {
+			self.id = Some(value);
+		}
+
+		fn field_metadata() -> Vec<ModelFieldInfo> {
+			let mut id = AutoField::new();
+			id.set_attributes_from_name("id");
+			let mut username = CharField::new(30);
+			username.set_attributes_from_name("username");
+			let mut password = CharField::new(128);
+			password.set_attributes_from_name("password");
+			let mut plan = CharField::with_choices(
+				10,
+				vec![
+					("free".to_string(), "Free".to_string()),
+					("pro".to_string(), "Pro".to_string()),
+				],
+			);
+			plan.base.default = Some(FieldKwarg::String("free".to_string()));
+			plan.set_attributes_from_name("plan");
+			let mut age = IntegerField::new();
+			age.base.null = true;
+			age.set_attributes_from_name("age");
+
+			vec![
+				ModelFieldInfo::from_field(&id),
+				ModelFieldInfo::from_field(&username),
+				ModelFieldInfo::from_field(&password),
+				ModelFieldInfo::from_field(&plan),
+				ModelFieldInfo::from_field(&age),
+			]
+		}
+	}
+
+	fn account() -> Account {
+		Account {
+			id: Some(1),
+			username: "alice".to_string(),
+			password: "hash".to_string(),
+			plan: "pro".to_string(),
+			age: None,
+		}
+	}
+
+	#[test]
+	fn test_fields_derived_from_model_metadata() {
+		let serializer = ModelSerializer::<Account>::new();
+		let fields = serializer.fields();
+		let by_name = |name: &str| fields.iter().find(|f| f.name == name).unwrap();
+
+		assert_eq!(fields.len(), 5);
+		assert!(by_name("id").read_only);
+		assert!(by_name("id").primary_key);
+		assert!(by_name("username").required);
+		assert!(!by_name("plan").required);
+		assert_eq!(by_name("plan").default, Some(json!("free")));
+		assert!(by_name("age").allow_null);
+		assert!(!by_name("age").required);
+	}
+
+	#[test]
+	fn test_fields_honor_meta_order_and_extra_kwargs() {
+		let serializer = ModelSerializer::<Account>::new()
+			.with_fields(vec![
+				"username".to_string(),
+				"id".to_string(),
+				"password".to_string(),
+			])
+			.with_extra_kwargs("password", ExtraKwargs::new().write_only(true))
+			.with_extra_kwargs("username", ExtraKwargs::new().required(false));
+
+		let fields = serializer.fields();
+		let names: Vec<_> = fields.iter().map(|f| f.name.as_str()).collect();
+
+		assert_eq!(names, vec!["username", "id", "password"]);
+		assert!(!fields[0].required);
+		assert!(fields[2].write_only);
+	}
+
+	#[test]
+	fn test_to_representation_skips_write_only_and_excluded() {
+		let serializer = ModelSerializer::<Account>::new()
+			.with_exclude(vec!["age".to_string()])
+			.with_write_only_fields(vec!["password".to_string()]);
+
+		let json = serializer.to_representation(&account()).unwrap();
+
+		assert_eq!(json, json!({"id": 1, "username": "alice", "plan": "pro"}));
+		assert_eq!(
+			serializer.serialize(&account()).unwrap(),
+			serde_json::to_string(&json).unwrap()
+		);
+	}
+
+	#[test]
+	fn test_validate_data_applies_defaults_and_drops_read_only() {
+		let serializer = ModelSerializer::<Account>::new();
+
+		let validated = serializer
+			.validate_data(
+				&json!({"id": 99, "username": "bob", "password": "x", "age": null}),
+				false,
+			)
+			.unwrap();
+
+		assert!(!validated.contains_key("id"));
+		assert_eq!(validated["plan"], json!("free"));
+		assert_eq!(validated["age"], Value::Null);
+	}
+
+	#[test]
+	fn test_validate_data_errors() {
+		let serializer = ModelSerializer::<Account>::new();
+
+		let missing = serializer.validate_data(&json!({"password": "x"}), false);
+		assert!(matches!(
+			missing,
+			Err(SerializerError::Validation(ValidatorError::RequiredField { ref field_name, .. }))
+				if field_name == "username"
+		));
+
+		let bad_choice = serializer.validate_data(
+			&json!({"username": "a", "password": "x", "plan": "gold"}),
+			false,
+		);
+		assert!(matches!(
+			bad_choice,
+			Err(SerializerError::Validation(ValidatorError::FieldValidation { ref constraint, .. }))
+				if constraint == "choices"
+		));
+
+		let null = serializer.validate_data(&json!({"username": null}), true);
+		assert!(matches!(
+			null,
+			Err(SerializerError::Validation(ValidatorError::FieldValidation { ref constraint, .. }))
+				if constraint == "null"
+		));
+
+		// Partial updates only validate provided fields
+		let partial = serializer.validate_data(&json!({"age": 30}), true).unwrap();
+		assert_eq!(partial.len(), 1);
+	}
+
+	#[tokio::test]
+	async fn test_create_and_update_rejects_invalid_data_before_saving() {
+		let serializer = ModelSerializer::<Account>::new();
+
+		let created = serializer.create(&json!({"password": "x"})).await;
+		assert!(matches!(
+			created,
+			Err(SerializerError::Validation(
+				ValidatorError::RequiredField { .. }
+			))
+		));
+
+		let updated = serializer
+			.update(&account(), &json!({"plan": "gold"}), true)
+			.await;
+		assert!(matches!(
+			updated,
+			Err(SerializerError::Validation(
+				ValidatorError::FieldValidation { .. }
+			))
+		));
+	}
+
+	#[tokio::test]
+	#[serial_test::serial(orm_database)]
+	async fn test_create_and_update_save_through_orm() {
+		use reinhardt_db::orm::manager::{get_connection, reinitialize_database};
+
+		let dir = tempfile::tempdir().unwrap();
+		let url = format!(
+			"sqlite://{}?mode=rwc",
+			dir.path().join("db.sqlite").display()
+		);
+		reinitialize_database(&url).await.unwrap();
+		let conn = get_connection().await.unwrap();
+		conn.execute(
+			"CREATE TABLE accounts (id INTEGER PRIMARY KEY AUTOINCREMENT, \
+			 username TEXT NOT NULL, password TEXT NOT NULL, plan TEXT NOT NULL, age INTEGER)",
+			vec![],
+		)
+		.await
+		.unwrap();
+		let serializer = ModelSerializer::<Account>::new();
+
+		let created = serializer
+			.create(&json!({"id": 42, "username": "bob", "password": "x"}))
+			.await
+			.unwrap();
+		let id = created.id.expect("created instance has a primary key");
+		assert_eq!(created.plan, "free");
+		assert_eq!(created.age, None);
+
+		let patched = serializer
+			.update(&created, &json!({"plan": "pro", "age": 30}), true)
+			.await
+			.unwrap();
+		assert_eq!(patched.id, Some(id));
+		assert_eq!(patched.username, "bob");
+		assert_eq!(patched.plan, "pro");
+		assert_eq!(patched.age, Some(30));
+
+		// A full update resets omitted fields to their defaults
+		let replaced = serializer
+			.update(
+				&patched,
+				&json!({"username": "robert", "password": "y"}),
+				false,
+			)
+			.await
+			.unwrap();
+		assert_eq!(replaced.plan, "free");
+
+		let row = conn
+			.query_one(
+				"SELECT COUNT(*) AS count, username, plan, age FROM accounts",
+				vec![],
+			)
+			.await
+			.unwrap();
+		assert_eq!(row.data["count"], 1);
+		assert_eq!(row.data["username"], "robert");
+		assert_eq!(row.data["plan"], "free");
+		assert_eq!(row.data["age"], 30);
+	}
+}
@@ -32,10 +32,12 @@
 //! This design avoids the N+1 query problem and gives developers explicit control
 //! over when and how relationships are loaded.
 
-use super::{SerializationArena, Serializer, SerializerError};
-use reinhardt_db::orm::Model;
+use super::{SerializationArena, Serializer, SerializerError, ValidatorError};
+use reinhardt_db::orm::{Manager, Model, TransactionScope, get_connection};
 use serde_json::Value;
+use std::collections::BTreeMap;
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 /// NestedSerializer - Serialize related models inline
 ///
@@ -334,11 +336,20 @@ impl<M: Model, R: Model> NestedSerializer<M, R> {
 	}
 }
 
+/// Validates a single item of a list payload
+pub type ItemValidator = Arc<dyn Fn(&Value) -> Result<(), SerializerError> + Send + Sync>;
+
 /// ListSerializer - Serialize collections of models
 ///
 /// Handles serializing multiple instances efficiently, useful for
 /// many-to-many and reverse foreign key relationships.
 ///
+/// It also writes collections in one request: [`ListSerializer::validate`]
+/// checks every item and reports errors per index,
+/// [`ListSerializer::bulk_create`] inserts the items in bulk and
+/// [`ListSerializer::bulk_update`] applies them to existing instances
+/// matched by primary key.
+///
 /// # Examples
 ///
 /// ```
@@ -370,16 +381,418 @@ impl<M: Model, R: Model> NestedSerializer<M, R> {
 /// let _: ListSerializer<User> = serializer;
 /// ```
 pub struct ListSerializer<M: Model> {
+	validators: Vec<ItemValidator>,
+	allow_empty: bool,
+	max_length: Option<usize>,
+	batch_size: Option<usize>,
 	_phantom: PhantomData<M>,
 }
 
+/// Validation errors of a list payload, reported per item index
+#[derive(Debug, Clone, Default)]
+pub struct ListErrors {
+	/// Errors about the payload as a whole, e.g. it is not a list
+	pub non_item: Vec<SerializerError>,
+	/// Errors of each invalid item, keyed by its index in the payload
+	pub items: BTreeMap<usize, Vec<SerializerError>>,
+}
+
+impl ListErrors {
+	fn payload(message: impl Into<String>) -> Self {
+		Self {
+			non_item: vec![SerializerError::Validation(ValidatorError::Custom {
+				message: message.into(),
+			})],
+			items: BTreeMap::new(),
+		}
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.non_item.is_empty() && self.items.is_empty()
+	}
+
+	/// Errors of the item at `index`
+	pub fn item(&self, index: usize) -> &[SerializerError] {
+		self.items
+			.get(&index)
+			.map(Vec::as_slice)
+			.unwrap_or_default()
+	}
+
+	/// Error messages as JSON, keyed by item index
+	///
+	/// ```json
+	/// {"non_item_errors": ["..."], "items": {"1": ["..."]}}
+	/// ```
+	pub fn to_json(&self) -> Value {
+		let messages = |errors: &[SerializerError]| -> Value {
+			errors.iter().map(|e| Value::String(e.message())).collect()
+		};
+		let items: serde_json::Map<String, Value> = self
+			.items
+			.iter()
+			.map(|(index, errors)| (index.to_string(), messages(errors)))
+			.collect();
+		serde_json::json!({
+			"non_item_errors": messages(&self.non_item),
+			"items": items,
+		})
+	}
+}
+
+impl std::fmt::Display for ListErrors {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let mut messages: Vec<String> = self.non_item.iter().map(|e| e.to_string()).collect();
+		for (index, errors) in &self.items {
+			messages.extend(errors.iter().map(|e| format!("[{}]: {}", index, e)));
+		}
+		write!(f, "{}", messages.join("; "))
+	}
+}
+
+impl std::error::Error for ListErrors {}
+
+impl From<ListErrors> for SerializerError {
+	fn from(errors: ListErrors) -> Self {
+		SerializerError::Validation(ValidatorError::Custom {
+			message: errors.to_string(),
+		})
+	}
+}
+
+/// Changes computed by [`ListSerializer::plan`]
+#[derive(Debug, Clone)]
+pub struct ListWritePlan<M: Model> {
+	/// Items without a primary key, to insert
+	pub create: Vec<M>,
+	/// Existing instances matched by primary key, with the submitted changes applied
+	pub update: Vec<M>,
+	/// Fields present in the submitted updates, excluding the primary key
+	pub update_fields: Vec<String>,
+}
+
 impl<M: Model> ListSerializer<M> {
 	/// Create a new ListSerializer
 	pub fn new() -> Self {
 		Self {
+			validators: Vec::new(),
+			allow_empty: true,
+			max_length: None,
+			batch_size: None,
 			_phantom: PhantomData,
 		}
 	}
+
+	/// Add a validator run on every item
+	///
+	/// Items are validated as JSON; for updates, the submitted fields merged
+	/// over the existing instance.
+	pub fn with_validator<F>(mut self, validator: F) -> Self
+	where
+		F: Fn(&Value) -> Result<(), SerializerError> + Send + Sync + 'static,
+	{
+		self.validators.push(Arc::new(validator));
+		self
+	}
+
+	/// Accept an empty list (default: true)
+	pub fn allow_empty(mut self, allow: bool) -> Self {
+		self.allow_empty = allow;
+		self
+	}
+
+	/// Reject payloads with more items than `max`
+	pub fn max_length(mut self, max: usize) -> Self {
+		self.max_length = Some(max);
+		self
+	}
+
+	/// Number of rows per bulk statement (default: all at once)
+	pub fn batch_size(mut self, size: usize) -> Self {
+		self.batch_size = Some(size);
+		self
+	}
+
+	fn items<'a>(&self, data: &'a Value) -> Result<&'a [Value], ListErrors> {
+		let Value::Array(items) = data else {
+			return Err(ListErrors::payload("Expected a list of items"));
+		};
+		if items.is_empty() && !self.allow_empty {
+			return Err(ListErrors::payload("This list may not be empty"));
+		}
+		if let Some(max) = self.max_length
+			&& items.len() > max
+		{
+			return Err(ListErrors::payload(format!(
+				"Ensure this list has at most {} items",
+				max
+			)));
+		}
+		Ok(items)
+	}
+
+	/// Run the validators on an item and parse it, collecting every error
+	fn validate_item(&self, item: Value) -> Result<M, Vec<SerializerError>> {
+		if !item.is_object() {
+			return Err(vec![SerializerError::Validation(ValidatorError::Custom {
+				message: "Expected an object".to_string(),
+			})]);
+		}
+		let errors: Vec<SerializerError> = self
+			.validators
+			.iter()
+			.filter_map(|validator| validator(&item).err())
+			.collect();
+		if !errors.is_empty() {
+			return Err(errors);
+		}
+		serde_json::from_value(item).map_err(|e| {
+			vec![SerializerError::Serde {
+				message: format!("Deserialization error: {}", e),
+			}]
+		})
+	}
+
+	/// Validate every item of a list payload
+	///
+	/// All items are checked before returning, so the errors cover the
+	/// whole batch rather than stopping at the first invalid item.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use reinhardt_rest::serializers::{ListSerializer, SerializerError};
+	/// # use serde::{Deserialize, Serialize};
+	/// # use serde_json::json;
+	/// # #[derive(Debug, Clone, Serialize, Deserialize)]
+	/// # struct Tag { id: Option<i64>, name: String }
+	/// # reinhardt_test::impl_test_model!(Tag, i64, "tags");
+	/// let serializer = ListSerializer::<Tag>::new().with_validator(|item| {
+	///     match item["name"].as_str() {
+	///         Some(name) if !name.is_empty() => Ok(()),
+	///         _ => Err(SerializerError::new("Name is required".to_string())),
+	///     }
+	/// });
+	///
+	/// let errors = serializer
+	///     .validate(&json!([{"name": "rust"}, {"name": ""}, {"name": "web"}, {}]))
+	///     .unwrap_err();
+	/// assert_eq!(errors.items.keys().copied().collect::<Vec<_>>(), vec![1, 3]);
+	/// ```
+	pub fn validate(&self, data: &Value) -> Result<Vec<M>, ListErrors> {
+		let items = self.items(data)?;
+		let mut errors = ListErrors::default();
+		let mut valid = Vec::with_capacity(items.len());
+		for (index, item) in items.iter().enumerate() {
+			match self.validate_item(item.clone()) {
+				Ok(instance) => valid.push(instance),
+				Err(item_errors) => {
+					errors.items.insert(index, item_errors);
+				}
+			}
+		}
+		if errors.is_empty() {
+			Ok(valid)
+		} else {
+			Err(errors)
+		}
+	}
+
+	/// Match a list payload against existing instances
+	///
+	/// Items without a primary key are created. Items with one are matched
+	/// to `existing` and their fields applied to it; a key matching no
+	/// existing instance is an error, so a request cannot modify rows
+	/// outside `existing`.
+	pub fn plan(&self, data: &Value, existing: &[M]) -> Result<ListWritePlan<M>, ListErrors> {
+		self.plan_items(data, existing, true)
+	}
+
+	fn plan_items(
+		&self,
+		data: &Value,
+		existing: &[M],
+		allow_create: bool,
+	) -> Result<ListWritePlan<M>, ListErrors> {
+		let items = self.items(data)?;
+		let pk_field = M::primary_key_field();
+		let existing_pks: Vec<Option<String>> = existing
+			.iter()
+			.map(|instance| instance.primary_key().map(|pk| pk.to_string()))
+			.collect();
+
+		let mut errors = ListErrors::default();
+		let mut plan = ListWritePlan {
+			create: vec![],
+			update: vec![],
+			update_fields: vec![],
+		};
+		let mut seen = vec![false; existing.len()];
+		for (index, item) in items.iter().enumerate() {
+			let pk = item.get(pk_field).filter(|pk| !pk.is_null());
+			let Some(pk) = pk else {
+				if !allow_create {
+					errors.items.insert(
+						index,
+						vec![SerializerError::Validation(ValidatorError::RequiredField {
+							field_name: pk_field.to_string(),
+							message: "A primary key is required to update an item".to_string(),
+						})],
+					);
+					continue;
+				}
+				match self.validate_item(item.clone()) {
+					Ok(instance) => plan.create.push(instance),
+					Err(item_errors) => {
+						errors.items.insert(index, item_errors);
+					}
+				}
+				continue;
+			};
+
+			let pk_string = pk
+				.as_str()
+				.map(str::to_string)
+				.unwrap_or_else(|| pk.to_string());
+			let position = existing_pks
+				.iter()
+				.position(|existing_pk| existing_pk.as_deref() == Some(pk_string.as_str()));
+			let Some(position) = position.filter(|&position| !seen[position]) else {
+				let message = match position {
+					Some(_) => "Item appears more than once in the list",
+					None => "Instance does not exist",
+				};
+				errors.items.insert(
+					index,
+					vec![SerializerError::Validation(
+						ValidatorError::FieldValidation {
+							field_name: pk_field.to_string(),
+							value: pk.to_string(),
+							constraint: "pk".to_string(),
+							message: message.to_string(),
+						},
+					)],
+				);
+				continue;
+			};
+			seen[position] = true;
+
+			let mut merged = match serde_json::to_value(&existing[position]) {
+				Ok(merged) => merged,
+				Err(e) => {
+					errors.items.insert(
+						index,
+						vec![SerializerError::Serde {
+							message: format!("Serialization error: {}", e),
+						}],
+					);
+					continue;
+				}
+			};
+			if let (Value::Object(object), Value::Object(fields)) = (&mut merged, item) {
+				for (name, value) in fields {
+					if name != pk_field && !plan.update_fields.contains(name) {
+						plan.update_fields.push(name.clone());
+					}
+					object.insert(name.clone(), value.clone());
+				}
+			}
+			match self.validate_item(merged) {
+				Ok(instance) => plan.update.push(instance),
+				Err(item_errors) => {
+					errors.items.insert(index, item_errors);
+				}
+			}
+		}
+
+		if errors.is_empty() {
+			Ok(plan)
+		} else {
+			Err(errors)
+		}
+	}
+
+	/// Validate a list payload and insert every item with one bulk insert
+	///
+	/// All batches run in one transaction, so either every item is
+	/// inserted or none is.
+	pub async fn bulk_create(&self, data: &Value) -> Result<Vec<M>, SerializerError> {
+		let instances = self.validate(data)?;
+		self.write(ListWritePlan {
+			create: instances,
+			update: vec![],
+			update_fields: vec![],
+		})
+		.await
+	}
+
+	/// Apply a list payload to `existing` instances matched by primary key
+	///
+	/// Every item must carry the primary key of one of `existing`. Only the
+	/// submitted fields are written, with one bulk update in a transaction.
+	pub async fn bulk_update(
+		&self,
+		data: &Value,
+		existing: &[M],
+	) -> Result<Vec<M>, SerializerError> {
+		let plan = self.plan_items(data, existing, false)?;
+		self.write(plan).await
+	}
+
+	/// Sync a collection: create items without a primary key and update the others
+	///
+	/// Inserts and updates run in one transaction, so a failure leaves the
+	/// collection unchanged. Returns the created instances followed by the
+	/// updated ones.
+	pub async fn save(&self, data: &Value, existing: &[M]) -> Result<Vec<M>, SerializerError> {
+		let plan = self.plan(data, existing)?;
+		self.write(plan).await
+	}
+
+	/// Write a plan in one transaction, rolling it back on any error
+	async fn write(&self, plan: ListWritePlan<M>) -> Result<Vec<M>, SerializerError> {
+		let conn = get_connection().await.map_err(database_error)?;
+		let mut tx = TransactionScope::begin(&conn)
+			.await
+			.map_err(database_error)?;
+
+		match self.write_in_tx(&mut tx, plan).await {
+			Ok(saved) => {
+				tx.commit().await.map_err(database_error)?;
+				Ok(saved)
+			}
+			Err(e) => {
+				if let Err(rollback_error) = tx.rollback().await {
+					log::warn!("Failed to roll back list write: {}", rollback_error);
+				}
+				Err(e)
+			}
+		}
+	}
+
+	async fn write_in_tx(
+		&self,
+		tx: &mut TransactionScope,
+		plan: ListWritePlan<M>,
+	) -> Result<Vec<M>, SerializerError> {
+		let manager = Manager::<M>::new();
+		let mut saved = manager
+			.bulk_create_in_tx(tx, plan.create, self.batch_size, false)
+			.await
+			.map_err(database_error)?;
+		manager
+			.bulk_update_in_tx(tx, plan.update.clone(), plan.update_fields, self.batch_size)
+			.await
+			.map_err(database_error)?;
+		saved.extend(plan.update);
+		Ok(saved)
+	}
+}
+
+fn database_error(e: impl std::fmt::Display) -> SerializerError {
+	SerializerError::Other {
+		message: format!("Database error: {}", e),
+	}
 }
 
 impl<M: Model> Default for ListSerializer<M> {
@@ -420,6 +833,21 @@ impl<M: Model> Serializer for ListSerializer<M> {
 /// This design gives you full control over transaction management and error handling
 /// while the serializer ensures data validity.
 ///
+/// For the common case, [`WritableNestedSerializer::save`] performs the whole
+/// write in one transaction: the parent is created or updated, nested
+/// instances are matched to existing rows by primary key, and rows missing
+/// from the payload are handled per [`OrphanPolicy`].
+///
+/// ```rust,ignore
+/// let serializer = WritableNestedSerializer::<Post, Comment>::new("comments")
+///     .foreign_key("post_id")
+///     .allow_create(true)
+///     .allow_update(true)
+///     .on_orphan(OrphanPolicy::Delete);
+///
+/// let (post, comments) = serializer.save(&request_body, Some(&post)).await?;
+/// ```
+///
 /// # Permission Control
 ///
 /// - `allow_create(bool)`: Allow creating new related instances (default: false)
@@ -583,9 +1011,34 @@ pub struct WritableNestedSerializer<M: Model, R: Model> {
 	relationship_field: String,
 	allow_create: bool,
 	allow_update: bool,
+	foreign_key: Option<String>,
+	orphan_policy: OrphanPolicy,
 	_phantom: PhantomData<(M, R)>,
 }
 
+/// What happens to existing related instances left out of a nested write
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OrphanPolicy {
+	/// Leave them untouched (default)
+	#[default]
+	Keep,
+	/// Delete them
+	Delete,
+	/// Clear their foreign key to the parent
+	SetNull,
+}
+
+/// Changes to related instances computed by [`WritableNestedSerializer::plan`]
+#[derive(Debug, Clone)]
+pub struct NestedWritePlan<R: Model> {
+	/// Instances without a primary key, to insert
+	pub create: Vec<R>,
+	/// Existing instances matched by primary key, with the submitted changes applied
+	pub update: Vec<R>,
+	/// Existing instances absent from the payload, handled per [`OrphanPolicy`]
+	pub orphans: Vec<R>,
+}
+
 impl<M: Model, R: Model> WritableNestedSerializer<M, R> {
 	/// Create a new WritableNestedSerializer
 	pub fn new(relationship_field: impl Into<String>) -> Self {
@@ -593,10 +1046,27 @@ impl<M: Model, R: Model> WritableNestedSerializer<M, R> {
 			relationship_field: relationship_field.into(),
 			allow_create: false,
 			allow_update: false,
+			foreign_key: None,
+			orphan_policy: OrphanPolicy::Keep,
 			_phantom: PhantomData,
 		}
 	}
 
+	/// Set the field on the related model that references the parent
+	///
+	/// Required by [`WritableNestedSerializer::save`]; the parent's primary
+	/// key is written to this field on every nested instance.
+	pub fn foreign_key(mut self, field: impl Into<String>) -> Self {
+		self.foreign_key = Some(field.into());
+		self
+	}
+
+	/// Set how existing related instances missing from the payload are handled
+	pub fn on_orphan(mut self, policy: OrphanPolicy) -> Self {
+		self.orphan_policy = policy;
+		self
+	}
+
 	/// Allow creating new related instances (default: false)
 	pub fn allow_create(mut self, allow: bool) -> Self {
 		self.allow_create = allow;
@@ -654,12 +1124,356 @@ impl<M: Model, R: Model> WritableNestedSerializer<M, R> {
 	/// assert!(!WritableNestedSerializer::<Post, Author>::is_create_operation(&update_data));
 	/// ```
 	pub fn is_create_operation(nested_value: &Value) -> bool {
-		if let Some(pk) = nested_value.get(M::primary_key_field()) {
+		if let Some(pk) = nested_value.get(R::primary_key_field()) {
 			pk.is_null()
 		} else {
 			true // No primary key field means create
 		}
 	}
+
+	/// Build an error located at `path` under the relationship field
+	fn nested_error(
+		&self,
+		path: &str,
+		value: &Value,
+		message: impl Into<String>,
+	) -> SerializerError {
+		SerializerError::Validation(ValidatorError::FieldValidation {
+			field_name: format!("{}{}", self.relationship_field, path),
+			value: value.to_string(),
+			constraint: "nested".to_string(),
+			message: message.into(),
+		})
+	}
+
+	/// Match nested data against existing related instances
+	///
+	/// Nested items are matched to `existing` by primary key. Items without
+	/// one are created; items whose key matches no existing instance are
+	/// rejected, so a request cannot take over unrelated rows. When
+	/// `parent_pk` is given it is written to the foreign key of every item.
+	///
+	/// Errors are reported under the relationship field, e.g.
+	/// `comments[1].id`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// # use reinhardt_rest::serializers::{OrphanPolicy, WritableNestedSerializer};
+	/// # use serde::{Deserialize, Serialize};
+	/// # use serde_json::json;
+	/// # #[derive(Debug, Clone, Serialize, Deserialize)]
+	/// # struct Post { id: Option<i64>, title: String }
+	/// # #[derive(Debug, Clone, Serialize, Deserialize)]
+	/// # struct Comment { id: Option<i64>, post_id: Option<i64>, text: String }
+	/// # reinhardt_test::impl_test_model!(Post, i64, "posts");
+	/// # reinhardt_test::impl_test_model!(Comment, i64, "comments");
+	/// let serializer = WritableNestedSerializer::<Post, Comment>::new("comments")
+	///     .foreign_key("post_id")
+	///     .allow_create(true)
+	///     .allow_update(true)
+	///     .on_orphan(OrphanPolicy::Delete);
+	///
+	/// let existing = vec![
+	///     Comment { id: Some(1), post_id: Some(7), text: "First".into() },
+	///     Comment { id: Some(2), post_id: Some(7), text: "Second".into() },
+	/// ];
+	/// let plan = serializer
+	///     .plan(
+	///         &json!([{"id": 1, "text": "Edited"}, {"text": "New"}]),
+	///         Some(&json!(7)),
+	///         &existing,
+	///     )
+	///     .unwrap();
+	///
+	/// assert_eq!(plan.update[0].text, "Edited");
+	/// assert_eq!(plan.create[0].post_id, Some(7));
+	/// assert_eq!(plan.orphans[0].id, Some(2));
+	/// ```
+	pub fn plan(
+		&self,
+		nested_value: &Value,
+		parent_pk: Option<&Value>,
+		existing: &[R],
+	) -> Result<NestedWritePlan<R>, SerializerError> {
+		let (items, many): (Vec<&Value>, bool) = match nested_value {
+			Value::Null => (vec![], true),
+			Value::Array(items) => (items.iter().collect(), true),
+			Value::Object(_) => (vec![nested_value], false),
+			other => {
+				return Err(self.nested_error(
+					"",
+					other,
+					"Expected an object or a list of objects",
+				));
+			}
+		};
+		let existing_pks: Vec<Option<String>> = existing
+			.iter()
+			.map(|instance| instance.primary_key().map(|pk| pk.to_string()))
+			.collect();
+
+		let mut plan = NestedWritePlan {
+			create: vec![],
+			update: vec![],
+			orphans: vec![],
+		};
+		let mut matched = vec![false; existing.len()];
+		for (index, item) in items.into_iter().enumerate() {
+			let path = if many {
+				format!("[{}]", index)
+			} else {
+				String::new()
+			};
+			let Value::Object(fields) = item else {
+				return Err(self.nested_error(&path, item, "Expected an object"));
+			};
+			let mut fields = fields.clone();
+			if let (Some(fk), Some(parent_pk)) = (&self.foreign_key, parent_pk) {
+				fields.insert(fk.clone(), parent_pk.clone());
+			}
+
+			let pk = fields
+				.get(R::primary_key_field())
+				.filter(|pk| !pk.is_null())
+				.cloned();
+			let Some(pk) = pk else {
+				if !self.allow_create {
+					return Err(self.nested_error(
+						&path,
+						item,
+						"Creating nested instances is not allowed",
+					));
+				}
+				plan.create
+					.push(self.parse_item(&path, Value::Object(fields))?);
+				continue;
+			};
+
+			if !self.allow_update {
+				return Err(self.nested_error(
+					&path,
+					item,
+					"Updating nested instances is not allowed",
+				));
+			}
+			let pk_string = pk
+				.as_str()
+				.map(str::to_string)
+				.unwrap_or_else(|| pk.to_string());
+			let Some(position) = existing_pks
+				.iter()
+				.position(|existing_pk| existing_pk.as_deref() == Some(pk_string.as_str()))
+			else {
+				return Err(self.nested_error(
+					&format!("{}.{}", path, R::primary_key_field()),
+					&pk,
+					"Related instance does not exist",
+				));
+			};
+			matched[position] = true;
+
+			let mut merged =
+				serde_json::to_value(&existing[position]).map_err(|e| SerializerError::Serde {
+					message: format!("Serialization error: {}", e),
+				})?;
+			if let Value::Object(ref mut object) = merged {
+				object.extend(fields);
+			}
+			plan.update.push(self.parse_item(&path, merged)?);
+		}
+
+		if self.orphan_policy != OrphanPolicy::Keep {
+			plan.orphans = existing
+				.iter()
+				.zip(matched)
+				.filter(|(_, matched)| !matched)
+				.map(|(instance, _)| instance.clone())
+				.collect();
+		}
+		Ok(plan)
+	}
+
+	fn parse_item(&self, path: &str, value: Value) -> Result<R, SerializerError> {
+		serde_json::from_value(value.clone())
+			.map_err(|e| self.nested_error(path, &value, e.to_string()))
+	}
+
+	/// Create or update the parent together with its nested instances
+	///
+	/// `data` is the parent payload including the nested field. Without an
+	/// `instance` the parent is inserted, otherwise the payload is applied to
+	/// it and it is updated. Nested instances are then created, updated and
+	/// orphans handled per [`OrphanPolicy`], all in one transaction: any
+	/// failure rolls back the whole write.
+	///
+	/// Returns the saved parent and the created and updated nested instances.
+	pub async fn save(
+		&self,
+		data: &Value,
+		instance: Option<&M>,
+	) -> Result<(M, Vec<R>), SerializerError> {
+		use reinhardt_db::orm::manager::get_connection;
+		use reinhardt_db::orm::transaction::TransactionScope;
+
+		let fk = self
+			.foreign_key
+			.as_ref()
+			.ok_or_else(|| SerializerError::Other {
+				message: format!(
+					"A foreign key is required to save nested '{}'",
+					self.relationship_field
+				),
+			})?;
+		let mut parent_data = data.as_object().cloned().ok_or_else(|| {
+			SerializerError::Validation(ValidatorError::Custom {
+				message: "Expected a JSON object".to_string(),
+			})
+		})?;
+		let nested = parent_data
+			.remove(&self.relationship_field)
+			.unwrap_or(Value::Null);
+
+		let parent_json = match instance {
+			Some(instance) => {
+				let mut json =
+					serde_json::to_value(instance).map_err(|e| SerializerError::Serde {
+						message: format!("Serialization error: {}", e),
+					})?;
+				if let Value::Object(ref mut object) = json {
+					object.extend(parent_data);
+				}
+				json
+			}
+			None => Value::Object(parent_data),
+		};
+		let parent: M =
+			serde_json::from_value(parent_json).map_err(|e| SerializerError::Serde {
+				message: format!("Deserialization error: {}", e),
+			})?;
+
+		let conn = get_connection().await.map_err(|e| SerializerError::Other {
+			message: format!("Failed to get connection: {}", e),
+		})?;
+		let mut tx = TransactionScope::begin(&conn)
+			.await
+			.map_err(|e| SerializerError::Other {
+				message: format!("Failed to begin transaction: {}", e),
+			})?;
+
+		let result = async {
+			let db_error = |e: reinhardt_core::exception::Error| SerializerError::Other {
+				message: format!("Database error: {}", e),
+			};
+			let parent_manager = reinhardt_db::orm::Manager::<M>::new();
+			let related_manager = reinhardt_db::orm::Manager::<R>::new();
+
+			let parent = if instance.is_some() {
+				parent_manager.update_in_tx(&mut tx, &parent).await
+			} else {
+				parent_manager.create_in_tx(&mut tx, &parent).await
+			}
+			.map_err(db_error)?;
+			let parent_pk = serde_json::to_value(&parent)
+				.map_err(|e| SerializerError::Serde {
+					message: format!("Serialization error: {}", e),
+				})?
+				.get(M::primary_key_field())
+				.cloned()
+				.unwrap_or(Value::Null);
+
+			let existing = if instance.is_some() {
+				related_manager
+					.filter(
+						fk.as_str(),
+						reinhardt_db::orm::FilterOperator::Eq,
+						filter_value(&parent_pk),
+					)
+					.all_in_tx(&mut tx)
+					.await
+					.map_err(db_error)?
+			} else {
+				vec![]
+			};
+			let plan = self.plan(&nested, Some(&parent_pk), &existing)?;
+
+			let mut saved = Vec::with_capacity(plan.create.len() + plan.update.len());
+			for related in &plan.create {
+				saved.push(
+					related_manager
+						.create_in_tx(&mut tx, related)
+						.await
+						.map_err(db_error)?,
+				);
+			}
+			for related in &plan.update {
+				saved.push(
+					related_manager
+						.update_in_tx(&mut tx, related)
+						.await
+						.map_err(db_error)?,
+				);
+			}
+			for orphan in plan.orphans {
+				match self.orphan_policy {
+					OrphanPolicy::Keep => {}
+					OrphanPolicy::Delete => {
+						if let Some(pk) = orphan.primary_key() {
+							related_manager
+								.delete_in_tx(&mut tx, pk)
+								.await
+								.map_err(db_error)?;
+						}
+					}
+					OrphanPolicy::SetNull => {
+						let mut json =
+							serde_json::to_value(&orphan).map_err(|e| SerializerError::Serde {
+								message: format!("Serialization error: {}", e),
+							})?;
+						if let Value::Object(ref mut object) = json {
+							object.insert(fk.clone(), Value::Null);
+						}
+						let orphan: R =
+							serde_json::from_value(json).map_err(|e| SerializerError::Serde {
+								message: format!("Failed to clear foreign key '{}': {}", fk, e),
+							})?;
+						related_manager
+							.update_in_tx(&mut tx, &orphan)
+							.await
+							.map_err(db_error)?;
+					}
+				}
+			}
+			Ok::<_, SerializerError>((parent, saved))
+		}
+		.await;
+
+		match result {
+			Ok(saved) => {
+				tx.commit().await.map_err(|e| SerializerError::Other {
+					message: format!("Failed to commit transaction: {}", e),
+				})?;
+				Ok(saved)
+			}
+			Err(e) => {
+				let _ = tx.rollback().await;
+				Err(e)
+			}
+		}
+	}
+}
+
+/// Convert a JSON primary key to a filter value
+fn filter_value(value: &Value) -> reinhardt_db::orm::FilterValue {
+	use reinhardt_db::orm::FilterValue;
+
+	match value {
+		Value::Number(n) if n.is_i64() => FilterValue::Integer(n.as_i64().unwrap_or_default()),
+		Value::String(s) => FilterValue::String(s.clone()),
+		Value::Bool(b) => FilterValue::Boolean(*b),
+		Value::Null => FilterValue::Null,
+		other => FilterValue::String(other.to_string()),
+	}
 }
 
 impl<M: Model, R: Model> Serializer for WritableNestedSerializer<M, R> {
@@ -687,7 +1501,7 @@ impl<M: Model, R: Model> Serializer for WritableNestedSerializer<M, R> {
 			// Validate permissions
 			if nested_value.is_object() {
 				// Single related object
-				if let Some(pk) = nested_value.get(M::primary_key_field()) {
+				if let Some(pk) = nested_value.get(R::primary_key_field()) {
 					if pk.is_null() && !self.allow_create {
 						return Err(SerializerError::Other {
 							message: "Creating nested instances is not allowed".to_string(),
@@ -701,7 +1515,7 @@ impl<M: Model, R: Model> Serializer for WritableNestedSerializer<M, R> {
 			} else if nested_value.is_array() {
 				// Multiple related objects
 				for item in nested_value.as_array().unwrap() {
-					if let Some(pk) = item.get(M::primary_key_field()) {
+					if let Some(pk) = item.get(R::primary_key_field()) {
 						if pk.is_null() && !self.allow_create {
 							return Err(SerializerError::Other {
 								message: "Creating nested instances is not allowed".to_string(),
@@ -803,6 +1617,122 @@ mod tests {
 		assert_eq!(value.as_array().unwrap().len(), 2);
 	}
 
+	fn title_required(item: &Value) -> Result<(), SerializerError> {
+		match item.get("title").and_then(Value::as_str) {
+			Some(title) if !title.is_empty() => Ok(()),
+			_ => Err(SerializerError::Validation(ValidatorError::RequiredField {
+				field_name: "title".to_string(),
+				message: "Title is required".to_string(),
+			})),
+		}
+	}
+
+	#[test]
+	fn test_list_validate_reports_errors_per_index() {
+		let serializer = ListSerializer::<Post>::new().with_validator(title_required);
+
+		let errors = serializer
+			.validate(&serde_json::json!([
+				{"title": "First"},
+				{"title": ""},
+				"not an object",
+				{"title": 3}
+			]))
+			.unwrap_err();
+
+		assert!(errors.non_item.is_empty());
+		assert_eq!(
+			errors.items.keys().copied().collect::<Vec<_>>(),
+			vec![1, 2, 3]
+		);
+		assert_eq!(errors.item(1)[0].message(), "Title is required");
+		assert!(errors.item(0).is_empty());
+		assert_eq!(errors.to_json()["items"]["1"][0], "Title is required");
+	}
+
+	#[test]
+	fn test_list_validate_checks_payload_shape() {
+		let serializer = ListSerializer::<Post>::new()
+			.allow_empty(false)
+			.max_length(1);
+
+		for payload in [
+			serde_json::json!({"title": "First"}),
+			serde_json::json!([]),
+			serde_json::json!([{"title": "a"}, {"title": "b"}]),
+		] {
+			let errors = serializer.validate(&payload).unwrap_err();
+			assert_eq!(errors.non_item.len(), 1);
+			assert!(errors.items.is_empty());
+		}
+	}
+
+	#[test]
+	fn test_list_plan_matches_updates_by_pk() {
+		let existing = vec![
+			Post {
+				id: Some(1),
+				title: "First".to_string(),
+			},
+			Post {
+				id: Some(2),
+				title: "Second".to_string(),
+			},
+		];
+		let serializer = ListSerializer::<Post>::new().with_validator(title_required);
+
+		let plan = serializer
+			.plan(
+				&serde_json::json!([{"id": 2, "title": "Edited"}, {"title": "New"}, {"id": 1}]),
+				&existing,
+			)
+			.unwrap();
+
+		assert_eq!(plan.create[0].title, "New");
+		assert_eq!(plan.update.len(), 2);
+		assert_eq!(
+			plan.update[0],
+			Post {
+				id: Some(2),
+				title: "Edited".to_string(),
+			}
+		);
+		assert_eq!(plan.update[1], existing[0]);
+		assert_eq!(plan.update_fields, vec!["title".to_string()]);
+	}
+
+	#[test]
+	fn test_list_plan_rejects_unknown_and_duplicate_pks() {
+		let existing = vec![Post {
+			id: Some(1),
+			title: "First".to_string(),
+		}];
+
+		let errors = ListSerializer::<Post>::new()
+			.plan(
+				&serde_json::json!([{"id": 1, "title": "a"}, {"id": 1, "title": "b"}, {"id": 9}]),
+				&existing,
+			)
+			.unwrap_err();
+
+		assert_eq!(errors.items.keys().copied().collect::<Vec<_>>(), vec![1, 2]);
+		assert_eq!(errors.item(2)[0].message(), "Instance does not exist");
+	}
+
+	#[test]
+	fn test_list_bulk_update_requires_pk() {
+		let serializer = ListSerializer::<Post>::new();
+
+		let errors = serializer
+			.plan_items(&serde_json::json!([{"title": "New"}]), &[], false)
+			.unwrap_err();
+
+		assert!(matches!(
+			errors.item(0)[0],
+			SerializerError::Validation(ValidatorError::RequiredField { .. })
+		));
+	}
+
 	#[test]
 	fn test_writable_nested_serializer_creation() {
 		let serializer = WritableNestedSerializer::<Post, Author>::new("author")
@@ -1099,4 +2029,156 @@ mod tests {
 		let result = serializer.serialize(&post);
 		assert!(result.is_ok());
 	}
+
+	#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+	struct Comment {
+		id: Option<i64>,
+		post_id: Option<i64>,
+		text: String,
+	}
+
+	reinhardt_test::impl_test_model!(Comment, i64, "comments");
+
+	fn comment(id: i64, text: &str) -> Comment {
+		Comment {
+			id: Some(id),
+			post_id: Some(7),
+			text: text.to_string(),
+		}
+	}
+
+	fn comments_serializer() -> WritableNestedSerializer<Post, Comment> {
+		WritableNestedSerializer::<Post, Comment>::new("comments")
+			.foreign_key("post_id")
+			.allow_create(true)
+			.allow_update(true)
+	}
+
+	fn nested_field_name(error: SerializerError) -> String {
+		match error {
+			SerializerError::Validation(ValidatorError::FieldValidation { field_name, .. }) => {
+				field_name
+			}
+			other => panic!("unexpected error: {}", other),
+		}
+	}
+
+	#[test]
+	fn test_plan_matches_by_pk_and_keeps_orphans_by_default() {
+		let existing = vec![comment(1, "First"), comment(2, "Second")];
+		let plan = comments_serializer()
+			.plan(
+				&serde_json::json!([{"id": 2, "text": "Edited"}, {"id": null, "text": "New"}]),
+				Some(&serde_json::json!(7)),
+				&existing,
+			)
+			.unwrap();
+
+		assert_eq!(plan.update, vec![comment(2, "Edited")]);
+		assert_eq!(
+			plan.create,
+			vec![Comment {
+				id: None,
+				post_id: Some(7),
+				text: "New".to_string(),
+			}]
+		);
+		assert!(plan.orphans.is_empty());
+	}
+
+	#[test]
+	fn test_plan_orphans_with_delete_policy() {
+		let existing = vec![comment(1, "First"), comment(2, "Second")];
+		let plan = comments_serializer()
+			.on_orphan(OrphanPolicy::Delete)
+			.plan(&serde_json::json!([]), None, &existing)
+			.unwrap();
+
+		assert_eq!(plan.orphans, existing);
+	}
+
+	#[test]
+	fn test_plan_rejects_unknown_pk() {
+		let error = comments_serializer()
+			.plan(
+				&serde_json::json!([{"text": "ok"}, {"id": 99, "text": "Hijack"}]),
+				None,
+				&[comment(1, "First")],
+			)
+			.unwrap_err();
+
+		assert_eq!(nested_field_name(error), "comments[1].id");
+	}
+
+	#[test]
+	fn test_plan_nests_child_validation_errors() {
+		let error = comments_serializer()
+			.plan(&serde_json::json!([{"id": null}]), None, &[])
+			.unwrap_err();
+		assert_eq!(nested_field_name(error), "comments[0]");
+
+		let error = WritableNestedSerializer::<Post, Comment>::new("comments")
+			.plan(&serde_json::json!({"text": "New"}), None, &[])
+			.unwrap_err();
+		assert_eq!(nested_field_name(error), "comments");
+	}
+
+	#[tokio::test]
+	async fn test_save_requires_foreign_key() {
+		let serializer = WritableNestedSerializer::<Post, Comment>::new("comments");
+		let result = serializer
+			.save(&serde_json::json!({"title": "Post", "comments": []}), None)
+			.await;
+
+		assert!(result.unwrap_err().message().contains("foreign key"));
+	}
+
+	#[tokio::test]
+	#[serial_test::serial(orm_database)]
+	async fn test_save_rolls_back_parent_when_child_insert_fails() {
+		use reinhardt_db::orm::manager::{get_connection, reinitialize_database};
+
+		let dir = tempfile::tempdir().unwrap();
+		let url = format!(
+			"sqlite://{}?mode=rwc",
+			dir.path().join("db.sqlite").display()
+		);
+		reinitialize_database(&url).await.unwrap();
+		let conn = get_connection().await.unwrap();
+		conn.execute(
+			"CREATE TABLE posts (id INTEGER PRIMARY KEY AUTOINCREMENT, title TEXT NOT NULL)",
+			vec![],
+		)
+		.await
+		.unwrap();
+		conn.execute(
+			"CREATE TABLE comments (id INTEGER PRIMARY KEY AUTOINCREMENT, \
+			 post_id INTEGER, text TEXT NOT NULL CHECK (text <> ''))",
+			vec![],
+		)
+		.await
+		.unwrap();
+
+		let result = comments_serializer()
+			.save(
+				&serde_json::json!({
+					"title": "Post",
+					"comments": [{"text": "ok"}, {"text": ""}],
+				}),
+				None,
+			)
+			.await;
+
+		assert!(result.unwrap_err().message().contains("Database error"));
+		let posts = conn
+			.query_one("SELECT COUNT(*) AS count FROM posts", vec![])
+			.await
+			.unwrap();
+		let comments = conn
+			.query_one("SELECT COUNT(*) AS count FROM comments", vec![])
+			.await
+			.unwrap();
+		assert_eq!(posts.data["count"], 0);
+		assert_eq!(comments.data["count"], 0);
+	}
 }
@@ -1,4 +1,5 @@
 /// Request Parsers - Re-exports from reinhardt-core::parsers
 pub use reinhardt_core::parsers::{
-	FileUploadParser, FormParser, JSONParser, MultiPartParser, ParseError, ParseResult, Parser,
+	BodySizeLimit, FileUploadParser, FormParser, JSONParser, MultiPartParser, ParseError,
+	ParseResult, Parser, ParserRegistry, SpooledFile, StreamedMultiPart, StreamingMultiPartParser,
 };
@@ -0,0 +1,439 @@
+//! VersionedSerializer - Per-version field transformations
+//!
+//! A serializer describes the current shape of a resource. Older API versions
+//! are supported by registering, per version, how that version differs:
+//! renamed fields, fields it does not have yet, and fields it still has. The
+//! version is selected from the request with any of the
+//! [versioning schemes](crate::versioning), so one serializer serves every
+//! version instead of a forked module per version.
+//!
+//! Transformations apply to output, and are reversed on input so clients of
+//! an older version can keep sending the payloads they know.
+//!
+//! # Examples
+//!
+//! ```
+//! use reinhardt_rest::serializers::{JsonSerializer, Serializer, VersionTransform, VersionedSerializer};
+//! use serde_json::{Value, json};
+//!
+//! let serializer = VersionedSerializer::new(JsonSerializer::<Value>::new()).version(
+//!     "1.0",
+//!     VersionTransform::new()
+//!         .rename("full_name", "name")
+//!         .remove("email")
+//!         .add("legacy", json!(true)),
+//! );
+//!
+//! let user = json!({"full_name": "Alice", "email": "alice@example.com"});
+//!
+//! let v1 = serializer.for_version("1.0");
+//! assert_eq!(v1.serialize(&user).unwrap(), r#"{"legacy":true,"name":"Alice"}"#);
+//!
+//! // Unregistered versions get the current representation
+//! let v2 = serializer.for_version("2.0");
+//! assert_eq!(
+//!     v2.serialize(&user).unwrap(),
+//!     r#"{"email":"alice@example.com","full_name":"Alice"}"#
+//! );
+//! ```
+
+use super::{Serializer, SerializerError};
+use crate::versioning::{BaseVersioning, RequestVersionExt};
+use reinhardt_http::Request;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Custom transformation of a serialized object
+pub type TransformFn = Arc<dyn Fn(&mut Map<String, Value>) + Send + Sync>;
+
+#[derive(Clone)]
+enum FieldChange {
+	Rename {
+		current: String,
+		version: String,
+	},
+	Remove {
+		field: String,
+		default: Option<Value>,
+	},
+	Add {
+		field: String,
+		value: Value,
+	},
+}
+
+/// How one API version differs from the current representation
+#[derive(Clone, Default)]
+pub struct VersionTransform {
+	changes: Vec<FieldChange>,
+	to_version: Vec<TransformFn>,
+	from_version: Vec<TransformFn>,
+}
+
+impl VersionTransform {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// The field `current` is called `version_name` in this version
+	pub fn rename(mut self, current: impl Into<String>, version_name: impl Into<String>) -> Self {
+		self.changes.push(FieldChange::Rename {
+			current: current.into(),
+			version: version_name.into(),
+		});
+		self
+	}
+
+	/// The field does not exist in this version
+	///
+	/// It is dropped from output and ignored in input.
+	pub fn remove(mut self, field: impl Into<String>) -> Self {
+		self.changes.push(FieldChange::Remove {
+			field: field.into(),
+			default: None,
+		});
+		self
+	}
+
+	/// The field does not exist in this version; input gets `default` for it
+	pub fn remove_with_default(mut self, field: impl Into<String>, default: Value) -> Self {
+		self.changes.push(FieldChange::Remove {
+			field: field.into(),
+			default: Some(default),
+		});
+		self
+	}
+
+	/// The field only exists in this version, always with `value`
+	///
+	/// It is added to output and dropped from input.
+	pub fn add(mut self, field: impl Into<String>, value: Value) -> Self {
+		self.changes.push(FieldChange::Add {
+			field: field.into(),
+			value,
+		});
+		self
+	}
+
+	/// Apply a custom change to output, after the field changes
+	pub fn map_output<F>(mut self, transform: F) -> Self
+	where
+		F: Fn(&mut Map<String, Value>) + Send + Sync + 'static,
+	{
+		self.to_version.push(Arc::new(transform));
+		self
+	}
+
+	/// Apply a custom change to input, before the field changes are reversed
+	pub fn map_input<F>(mut self, transform: F) -> Self
+	where
+		F: Fn(&mut Map<String, Value>) + Send + Sync + 'static,
+	{
+		self.from_version.push(Arc::new(transform));
+		self
+	}
+
+	/// Convert a current representation to this version
+	pub fn to_version(&self, value: &mut Value) {
+		for_each_object(value, &mut |object| {
+			for change in &self.changes {
+				match change {
+					FieldChange::Rename { current, version } => {
+						if let Some(field) = object.remove(current) {
+							object.insert(version.clone(), field);
+						}
+					}
+					FieldChange::Remove { field, .. } => {
+						object.remove(field);
+					}
+					FieldChange::Add { field, value } => {
+						object.insert(field.clone(), value.clone());
+					}
+				}
+			}
+			for transform in &self.to_version {
+				transform(object);
+			}
+		});
+	}
+
+	/// Convert a payload in this version to the current representation
+	pub fn from_version(&self, value: &mut Value) {
+		for_each_object(value, &mut |object| {
+			for transform in &self.from_version {
+				transform(object);
+			}
+			for change in self.changes.iter().rev() {
+				match change {
+					FieldChange::Rename { current, version } => {
+						if let Some(field) = object.remove(version) {
+							object.insert(current.clone(), field);
+						}
+					}
+					FieldChange::Remove { field, default } => {
+						object.remove(field);
+						if let Some(default) = default {
+							object.insert(field.clone(), default.clone());
+						}
+					}
+					FieldChange::Add { field, .. } => {
+						object.remove(field);
+					}
+				}
+			}
+		});
+	}
+}
+
+/// Apply `f` to an object, or to every object of a list
+fn for_each_object(value: &mut Value, f: &mut dyn FnMut(&mut Map<String, Value>)) {
+	match value {
+		Value::Object(object) => f(object),
+		Value::Array(items) => items.iter_mut().for_each(|item| for_each_object(item, f)),
+		_ => {}
+	}
+}
+
+/// Serializer with per-version field transformations
+pub struct VersionedSerializer<S> {
+	inner: Arc<S>,
+	transforms: Arc<HashMap<String, VersionTransform>>,
+}
+
+impl<S> Clone for VersionedSerializer<S> {
+	fn clone(&self) -> Self {
+		Self {
+			inner: self.inner.clone(),
+			transforms: self.transforms.clone(),
+		}
+	}
+}
+
+impl<S> VersionedSerializer<S>
+where
+	S: Serializer<Output = String>,
+{
+	/// Wrap a serializer producing the current representation
+	pub fn new(inner: S) -> Self {
+		Self {
+			inner: Arc::new(inner),
+			transforms: Arc::new(HashMap::new()),
+		}
+	}
+
+	/// Register the transformation for a version
+	pub fn version(mut self, version: impl Into<String>, transform: VersionTransform) -> Self {
+		Arc::make_mut(&mut self.transforms).insert(version.into(), transform);
+		self
+	}
+
+	/// Versions with a registered transformation
+	pub fn versions(&self) -> Vec<&str> {
+		let mut versions: Vec<&str> = self.transforms.keys().map(String::as_str).collect();
+		versions.sort_unstable();
+		versions
+	}
+
+	/// Serializer for a specific version
+	pub fn for_version(&self, version: impl Into<String>) -> VersionBoundSerializer<S> {
+		VersionBoundSerializer {
+			serializer: self.clone(),
+			version: version.into(),
+		}
+	}
+
+	/// Serializer for the version of a request
+	///
+	/// Uses the version stored by
+	/// [`VersioningMiddleware`](crate::versioning::VersioningMiddleware) if
+	/// present, otherwise asks `versioning` to determine it.
+	pub async fn for_request(
+		&self,
+		request: &Request,
+		versioning: &dyn BaseVersioning,
+	) -> Result<VersionBoundSerializer<S>, SerializerError> {
+		let version =
+			match request.version() {
+				Some(version) => version,
+				None => versioning.determine_version(request).await.map_err(|e| {
+					SerializerError::Other {
+						message: e.to_string(),
+					}
+				})?,
+			};
+		Ok(self.for_version(version))
+	}
+}
+
+/// A [`VersionedSerializer`] bound to one API version
+pub struct VersionBoundSerializer<S> {
+	serializer: VersionedSerializer<S>,
+	version: String,
+}
+
+impl<S> VersionBoundSerializer<S> {
+	pub fn version(&self) -> &str {
+		&self.version
+	}
+
+	fn transform(&self) -> Option<&VersionTransform> {
+		self.serializer.transforms.get(&self.version)
+	}
+}
+
+impl<S> Serializer for VersionBoundSerializer<S>
+where
+	S: Serializer<Output = String>,
+{
+	type Input = S::Input;
+	type Output = String;
+
+	fn serialize(&self, input: &Self::Input) -> Result<Self::Output, SerializerError> {
+		let output = self.serializer.inner.serialize(input)?;
+		let Some(transform) = self.transform() else {
+			return Ok(output);
+		};
+
+		let mut value: Value =
+			serde_json::from_str(&output).map_err(|e| SerializerError::Serde {
+				message: format!("Serialization error: {}", e),
+			})?;
+		transform.to_version(&mut value);
+		serde_json::to_string(&value).map_err(|e| SerializerError::Serde {
+			message: format!("Serialization error: {}", e),
+		})
+	}
+
+	fn deserialize(&self, output: &Self::Output) -> Result<Self::Input, SerializerError> {
+		let Some(transform) = self.transform() else {
+			return self.serializer.inner.deserialize(output);
+		};
+
+		let mut value: Value =
+			serde_json::from_str(output).map_err(|e| SerializerError::Serde {
+				message: format!("Deserialization error: {}", e),
+			})?;
+		transform.from_version(&mut value);
+		let current = serde_json::to_string(&value).map_err(|e| SerializerError::Serde {
+			message: format!("Serialization error: {}", e),
+		})?;
+		self.serializer.inner.deserialize(&current)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::serializers::JsonSerializer;
+	use crate::versioning::QueryParameterVersioning;
+	use bytes::Bytes;
+	use hyper::{HeaderMap, Method, Uri, Version};
+	use serde::{Deserialize, Serialize};
+	use serde_json::json;
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	struct User {
+		full_name: String,
+		email: String,
+	}
+
+	fn serializer() -> VersionedSerializer<JsonSerializer<User>> {
+		VersionedSerializer::new(JsonSerializer::<User>::new()).version(
+			"1.0",
+			VersionTransform::new()
+				.rename("full_name", "name")
+				.remove_with_default("email", json!(""))
+				.add("legacy", json!(true)),
+		)
+	}
+
+	fn user() -> User {
+		User {
+			full_name: "Alice".to_string(),
+			email: "alice@example.com".to_string(),
+		}
+	}
+
+	fn request(uri: &str) -> Request {
+		Request::builder()
+			.method(Method::GET)
+			.uri(uri.parse::<Uri>().unwrap())
+			.version(Version::HTTP_11)
+			.headers(HeaderMap::new())
+			.body(Bytes::new())
+			.build()
+			.unwrap()
+	}
+
+	#[test]
+	fn test_old_version_input_is_converted_back() {
+		let v1 = serializer().for_version("1.0");
+
+		let parsed = v1
+			.deserialize(&r#"{"name": "Alice", "legacy": true}"#.to_string())
+			.unwrap();
+
+		assert_eq!(
+			parsed,
+			User {
+				full_name: "Alice".to_string(),
+				email: String::new(),
+			}
+		);
+	}
+
+	#[test]
+	fn test_custom_transforms_and_lists() {
+		let transform = VersionTransform::new().map_output(|object| {
+			if let Some(Value::String(name)) = object.get_mut("name") {
+				*name = name.to_uppercase();
+			}
+		});
+		let mut value = json!([{"name": "a"}, {"name": "b"}]);
+
+		transform.to_version(&mut value);
+
+		assert_eq!(value, json!([{"name": "A"}, {"name": "B"}]));
+	}
+
+	#[tokio::test]
+	async fn test_version_selected_from_request() {
+		let versioning = QueryParameterVersioning::new()
+			.with_default_version("2.0")
+			.with_allowed_versions(vec!["1.0", "2.0"]);
+
+		let v1 = serializer()
+			.for_request(&request("/users/?version=1.0"), &versioning)
+			.await
+			.unwrap();
+		let current = serializer()
+			.for_request(&request("/users/"), &versioning)
+			.await
+			.unwrap();
+
+		assert_eq!(v1.version(), "1.0");
+		assert_eq!(
+			serde_json::from_str::<Value>(&v1.serialize(&user()).unwrap()).unwrap(),
+			json!({"name": "Alice", "legacy": true})
+		);
+		assert_eq!(current.version(), "2.0");
+		assert_eq!(
+			current
+				.deserialize(&current.serialize(&user()).unwrap())
+				.unwrap(),
+			user()
+		);
+	}
+
+	#[tokio::test]
+	async fn test_disallowed_request_version_is_an_error() {
+		let versioning = QueryParameterVersioning::new().with_allowed_versions(vec!["1.0"]);
+
+		let result = serializer()
+			.for_request(&request("/users/?version=9"), &versioning)
+			.await;
+
+		assert!(result.is_err());
+	}
+}
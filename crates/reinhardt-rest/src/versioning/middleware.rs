@@ -10,45 +10,7 @@ use reinhardt_http::{Handler, Middleware};
 use reinhardt_http::{Request, Response};
 use std::sync::Arc;
 
-/// API version extracted from request
-#[derive(Debug, Clone)]
-pub struct ApiVersion(pub String);
-
-impl ApiVersion {
-	/// Get the version string as a string slice
-	///
-	/// # Examples
-	///
-	/// ```
-	/// use reinhardt_rest::versioning::ApiVersion;
-	///
-	/// let version = ApiVersion::new("2.0".to_string());
-	/// assert_eq!(version.as_str(), "2.0");
-	/// ```
-	pub fn as_str(&self) -> &str {
-		&self.0
-	}
-
-	/// Create a new ApiVersion with the given version string
-	///
-	/// # Examples
-	///
-	/// ```
-	/// use reinhardt_rest::versioning::ApiVersion;
-	///
-	/// let version = ApiVersion::new("1.0".to_string());
-	/// assert_eq!(version.as_str(), "1.0");
-	/// ```
-	pub fn new(version: String) -> Self {
-		Self(version)
-	}
-}
-
-impl std::fmt::Display for ApiVersion {
-	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-		write!(f, "{}", self.0)
-	}
-}
+pub use reinhardt_http::ApiVersion;
 
 /// Middleware for automatic API version detection
 ///
@@ -92,7 +92,9 @@ impl HttpServer {
 	/// Set the dependency injection context for the server
 	///
 	/// When set, the DI context will be automatically injected into each request,
-	/// making it available for endpoints that use `#[inject]` parameters.
+	/// making it available for endpoints that use `#[inject]` parameters. Its
+	/// startup hooks run before the server accepts connections, and its shutdown
+	/// hooks run after a graceful shutdown stops accepting them.
 	///
 	/// # Examples
 	///
@@ -142,6 +144,14 @@ impl HttpServer {
 
 		Arc::new(chain)
 	}
+	/// Run the DI context's startup hooks, if a context is set
+	async fn run_startup_hooks(&self) -> Result<(), Box<dyn std::error::Error>> {
+		if let Some(ctx) = &self.di_context {
+			ctx.run_startup().await?;
+		}
+		Ok(())
+	}
+
 	/// Start the server and listen on the given address
 	///
 	/// This method starts the server and begins accepting connections.
@@ -173,6 +183,7 @@ impl HttpServer {
 	/// ```
 	pub async fn listen(self, addr: SocketAddr) -> Result<(), Box<dyn std::error::Error>> {
 		let listener = TcpListener::bind(addr).await?;
+		self.run_startup_hooks().await?;
 
 		// Build the handler with middleware chain
 		let handler = self.build_handler();
@@ -237,6 +248,7 @@ impl HttpServer {
 		let di_context = self.di_context.clone();
 
 		let mut shutdown_rx = coordinator.subscribe();
+		self.run_startup_hooks().await?;
 
 		loop {
 			tokio::select! {
@@ -269,10 +281,16 @@ impl HttpServer {
 			}
 		}
 
+		// Release application resources before reporting shutdown as complete
+		let shutdown_result = match &self.di_context {
+			Some(ctx) => ctx.run_shutdown().await,
+			None => Ok(()),
+		};
+
 		// Notify that server has stopped accepting connections
 		coordinator.notify_shutdown_complete();
 
-		Ok(())
+		shutdown_result.map_err(Into::into)
 	}
 	/// Handle a single TCP connection by processing HTTP requests
 	///
@@ -601,4 +619,51 @@ mod tests {
 		// Middlewares should be applied in order: First -> Second -> Handler
 		assert_eq!(body, "First:Second:Hello, World!");
 	}
+
+	#[tokio::test]
+	async fn test_lifecycle_hooks_run_around_graceful_shutdown() {
+		use reinhardt_di::SingletonScope;
+		use std::sync::Mutex;
+		use std::time::Duration;
+
+		let events = Arc::new(Mutex::new(Vec::new()));
+		let ctx = Arc::new(InjectionContext::builder(SingletonScope::new()).build());
+		let startup_events = events.clone();
+		ctx.on_startup(move |_ctx| {
+			let events = startup_events.clone();
+			async move {
+				events.lock().unwrap().push("startup");
+				Ok(())
+			}
+		});
+		let shutdown_events = events.clone();
+		ctx.on_shutdown(move |_ctx| {
+			let events = shutdown_events.clone();
+			async move {
+				events.lock().unwrap().push("shutdown");
+				Ok(())
+			}
+		});
+
+		let server = HttpServer::new(TestHandler).with_di_context(ctx);
+		let coordinator = ShutdownCoordinator::new(Duration::from_secs(5));
+		let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+		let server_task = tokio::spawn({
+			let coordinator = coordinator.clone();
+			async move {
+				server
+					.listen_with_shutdown(addr, coordinator)
+					.await
+					.map_err(|e| e.to_string())
+			}
+		});
+
+		while events.lock().unwrap().is_empty() {
+			tokio::time::sleep(Duration::from_millis(10)).await;
+		}
+		coordinator.shutdown();
+		server_task.await.unwrap().unwrap();
+
+		assert_eq!(*events.lock().unwrap(), vec!["startup", "shutdown"]);
+	}
 }
@@ -38,6 +38,15 @@ pub trait TaskBackend: Send + Sync {
 		task_id: TaskId,
 	) -> Result<Option<SerializedTask>, TaskExecutionError>;
 
+	/// Put a dequeued task back on the queue so another worker can run it
+	///
+	/// Returns `false` if the backend cannot re-enqueue tasks by ID. The
+	/// default implementation does not support re-enqueueing.
+	async fn requeue(&self, task_id: TaskId) -> Result<bool, TaskExecutionError> {
+		let _ = task_id;
+		Ok(false)
+	}
+
 	fn backend_name(&self) -> &str;
 }
 
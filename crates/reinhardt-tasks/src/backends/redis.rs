@@ -238,6 +238,18 @@ impl crate::backend::TaskBackend for RedisTaskBackend {
 		}
 	}
 
+	async fn requeue(&self, task_id: TaskId) -> Result<bool, TaskExecutionError> {
+		self.update_status(task_id, TaskStatus::Pending).await?;
+
+		let mut conn = (*self.connection).clone();
+		let _: () = conn
+			.rpush(self.queue_key(), task_id.to_string())
+			.await
+			.map_err(|e: RedisError| TaskExecutionError::BackendError(e.to_string()))?;
+
+		Ok(true)
+	}
+
 	fn backend_name(&self) -> &str {
 		"redis"
 	}
@@ -231,6 +231,12 @@ impl crate::backend::TaskBackend for SqliteBackend {
 		}
 	}
 
+	async fn requeue(&self, task_id: TaskId) -> Result<bool, TaskExecutionError> {
+		// Pending tasks are picked up again by `dequeue`
+		self.update_status(task_id, TaskStatus::Pending).await?;
+		Ok(true)
+	}
+
 	fn backend_name(&self) -> &str {
 		"sqlite"
 	}
@@ -450,6 +456,29 @@ mod tests {
 		assert_eq!(status, TaskStatus::Pending);
 	}
 
+	#[tokio::test]
+	async fn test_sqlite_backend_requeue() {
+		let backend = SqliteBackend::new("sqlite::memory:")
+			.await
+			.expect("Failed to create backend");
+
+		let task = Box::new(TestTask {
+			id: TaskId::new(),
+			name: "test_task".to_string(),
+		});
+		let task_id = task.id();
+		backend.enqueue(task).await.expect("Failed to enqueue");
+		assert_eq!(backend.dequeue().await.unwrap(), Some(task_id));
+		assert_eq!(backend.dequeue().await.unwrap(), None);
+
+		assert!(backend.requeue(task_id).await.unwrap());
+		assert_eq!(
+			backend.get_status(task_id).await.unwrap(),
+			TaskStatus::Pending
+		);
+		assert_eq!(backend.dequeue().await.unwrap(), Some(task_id));
+	}
+
 	#[tokio::test]
 	async fn test_sqlite_backend_not_found() {
 		let backend = SqliteBackend::new("sqlite::memory:")
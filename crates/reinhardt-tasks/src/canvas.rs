@@ -0,0 +1,371 @@
+//! Task workflow primitives
+//!
+//! Builds on [`TaskChain`](crate::TaskChain) with workflows described as
+//! values and executed through a [`TaskRegistry`]:
+//!
+//! - [`chain`] runs tasks in sequence, passing each task's result to the next
+//! - [`group`] runs tasks in parallel and collects their results
+//! - [`chord`] runs a group, then a callback receiving all of its results
+//!
+//! Every task result is stored in the [`ResultBackend`], and the next task
+//! reads its parent's result from there. The result is passed to a task by
+//! inserting it into its JSON arguments under the `parent_result` key, unless
+//! the signature is [immutable](Signature::immutable).
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use reinhardt_tasks::canvas::{CanvasExecutor, Signature, chain, chord, group};
+//! use reinhardt_tasks::{MemoryResultBackend, TaskRegistry};
+//! use serde_json::json;
+//! use std::sync::Arc;
+//!
+//! # async fn example() -> reinhardt_tasks::TaskResult<()> {
+//! let executor = CanvasExecutor::new(
+//!     Arc::new(TaskRegistry::new()),
+//!     Arc::new(MemoryResultBackend::new()),
+//! );
+//!
+//! // fetch -> parse, then sum the parsed values of three pages
+//! let workflow = chord(
+//!     (1..=3).map(|page| {
+//!         chain([
+//!             Signature::new("fetch", json!({ "page": page })).into(),
+//!             Signature::new("parse", json!({})).into(),
+//!         ])
+//!     }),
+//!     Signature::new("sum", json!({})),
+//! );
+//! let total = executor.apply(&workflow).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::result::{ResultBackend, TaskResultMetadata};
+use crate::{TaskError, TaskId, TaskRegistry, TaskResult, TaskStatus};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::task::JoinSet;
+
+/// Key under which the parent result is inserted into a task's arguments
+pub const PARENT_RESULT_KEY: &str = "parent_result";
+
+/// A task invocation: a registered task name with its arguments
+///
+/// # Examples
+///
+/// ```rust
+/// use reinhardt_tasks::canvas::Signature;
+/// use serde_json::json;
+///
+/// let signature = Signature::new("resize", json!({ "width": 100 }));
+/// assert_eq!(
+///     signature.arguments(Some(json!("img.png"))),
+///     json!({ "width": 100, "parent_result": "img.png" })
+/// );
+///
+/// let immutable = signature.immutable();
+/// assert_eq!(immutable.arguments(Some(json!("img.png"))), json!({ "width": 100 }));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Signature {
+	/// Name the task is registered under in the [`TaskRegistry`]
+	pub task_name: String,
+	/// JSON arguments passed to the task factory
+	pub args: Value,
+	/// Immutable signatures ignore their parent's result
+	#[serde(default)]
+	pub immutable: bool,
+}
+
+impl Signature {
+	/// Create a signature for a registered task
+	pub fn new(task_name: impl Into<String>, args: Value) -> Self {
+		Self {
+			task_name: task_name.into(),
+			args,
+			immutable: false,
+		}
+	}
+
+	/// Make the signature ignore its parent's result
+	pub fn immutable(mut self) -> Self {
+		self.immutable = true;
+		self
+	}
+
+	/// Build the arguments for a run receiving `parent_result`
+	///
+	/// Non-object arguments are wrapped as `{"args": ..., "parent_result": ...}`.
+	pub fn arguments(&self, parent_result: Option<Value>) -> Value {
+		let Some(parent_result) = parent_result.filter(|_| !self.immutable) else {
+			return self.args.clone();
+		};
+		let mut args = match &self.args {
+			Value::Object(map) => map.clone(),
+			Value::Null => serde_json::Map::new(),
+			other => serde_json::Map::from_iter([("args".to_string(), other.clone())]),
+		};
+		args.insert(PARENT_RESULT_KEY.to_string(), parent_result);
+		Value::Object(args)
+	}
+}
+
+/// A workflow of task signatures
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Canvas {
+	/// A single task
+	Task(Signature),
+	/// Workflows run in sequence, each receiving the previous result
+	Chain {
+		/// Steps in execution order
+		steps: Vec<Canvas>,
+	},
+	/// Workflows run in parallel; the result is the list of their results
+	Group {
+		/// Workflows to run
+		members: Vec<Canvas>,
+	},
+	/// A group followed by a callback receiving the list of its results
+	Chord {
+		/// Workflows run in parallel
+		header: Vec<Canvas>,
+		/// Callback run once every header workflow succeeded
+		body: Box<Canvas>,
+	},
+}
+
+impl From<Signature> for Canvas {
+	fn from(signature: Signature) -> Self {
+		Self::Task(signature)
+	}
+}
+
+/// Build a chain of workflows
+///
+/// # Examples
+///
+/// ```rust
+/// use reinhardt_tasks::canvas::{Canvas, Signature, chain};
+/// use serde_json::json;
+///
+/// let workflow = chain([
+///     Signature::new("download", json!({ "url": "https://example.com" })).into(),
+///     Signature::new("extract", json!({})).into(),
+/// ]);
+/// assert!(matches!(workflow, Canvas::Chain { ref steps } if steps.len() == 2));
+/// ```
+pub fn chain(steps: impl IntoIterator<Item = Canvas>) -> Canvas {
+	Canvas::Chain {
+		steps: steps.into_iter().collect(),
+	}
+}
+
+/// Build a group of workflows run in parallel
+pub fn group(members: impl IntoIterator<Item = Canvas>) -> Canvas {
+	Canvas::Group {
+		members: members.into_iter().collect(),
+	}
+}
+
+/// Build a chord: a group followed by a callback receiving all results
+pub fn chord(header: impl IntoIterator<Item = Canvas>, body: impl Into<Canvas>) -> Canvas {
+	Canvas::Chord {
+		header: header.into_iter().collect(),
+		body: Box::new(body.into()),
+	}
+}
+
+type CanvasFuture = Pin<Box<dyn Future<Output = TaskResult<Value>> + Send>>;
+
+/// Executes [`Canvas`] workflows
+///
+/// Tasks are created from their signatures with the registry and run in the
+/// current process; group members run concurrently on the Tokio runtime.
+#[derive(Clone)]
+pub struct CanvasExecutor {
+	registry: Arc<TaskRegistry>,
+	results: Arc<dyn ResultBackend>,
+}
+
+impl CanvasExecutor {
+	/// Create an executor
+	pub fn new(registry: Arc<TaskRegistry>, results: Arc<dyn ResultBackend>) -> Self {
+		Self { registry, results }
+	}
+
+	/// Run a workflow and return its result
+	///
+	/// The result of a task is the value returned by
+	/// [`TaskExecutor::execute_with_result`](crate::TaskExecutor::execute_with_result),
+	/// or `null` if it returned none. A chain returns its last result, a
+	/// group the list of its members' results, and a chord its callback's
+	/// result.
+	///
+	/// # Errors
+	///
+	/// Fails as soon as a chain step fails. A group or chord header waits
+	/// for all members and then fails if any member failed; the chord
+	/// callback does not run in that case.
+	pub async fn apply(&self, canvas: &Canvas) -> TaskResult<Value> {
+		self.run(canvas.clone(), None).await
+	}
+
+	fn run(&self, canvas: Canvas, parent: Option<TaskId>) -> CanvasFuture {
+		let executor = self.clone();
+		Box::pin(async move {
+			match canvas {
+				Canvas::Task(signature) => executor
+					.run_task(&signature, parent)
+					.await
+					.map(|(_, value)| value),
+				Canvas::Chain { steps } => {
+					let mut parent = parent;
+					let mut value = Value::Null;
+					for step in steps {
+						let (id, step_value) = match step {
+							Canvas::Task(signature) => {
+								executor.run_task(&signature, parent).await?
+							}
+							other => executor.run_stored(other, parent).await?,
+						};
+						parent = Some(id);
+						value = step_value;
+					}
+					Ok(value)
+				}
+				Canvas::Group { members } => executor.run_group(members, parent).await,
+				Canvas::Chord { header, body } => {
+					let (id, _) = executor
+						.run_stored(Canvas::Group { members: header }, parent)
+						.await?;
+					executor.run(*body, Some(id)).await
+				}
+			}
+		})
+	}
+
+	/// Run a nested workflow and store its result so a following step can
+	/// read it from the result backend
+	async fn run_stored(
+		&self,
+		canvas: Canvas,
+		parent: Option<TaskId>,
+	) -> TaskResult<(TaskId, Value)> {
+		let value = self.run(canvas, parent).await?;
+		let id = TaskId::new();
+		self.store(id, Ok(&value)).await?;
+		Ok((id, value))
+	}
+
+	async fn run_group(&self, members: Vec<Canvas>, parent: Option<TaskId>) -> TaskResult<Value> {
+		let mut running = JoinSet::new();
+		for (index, member) in members.into_iter().enumerate() {
+			let future = self.run(member, parent);
+			running.spawn(async move { (index, future.await) });
+		}
+
+		let mut results = vec![Value::Null; running.len()];
+		let mut errors = Vec::new();
+		while let Some(joined) = running.join_next().await {
+			match joined {
+				Ok((index, Ok(value))) => results[index] = value,
+				Ok((index, Err(e))) => errors.push(format!("member {}: {}", index, e)),
+				Err(e) => errors.push(e.to_string()),
+			}
+		}
+
+		if errors.is_empty() {
+			Ok(Value::Array(results))
+		} else {
+			Err(TaskError::ExecutionFailed(format!(
+				"Group failed: {}",
+				errors.join("; ")
+			)))
+		}
+	}
+
+	async fn run_task(
+		&self,
+		signature: &Signature,
+		parent: Option<TaskId>,
+	) -> TaskResult<(TaskId, Value)> {
+		let parent_result = match parent {
+			Some(parent) => Some(self.load(parent).await?),
+			None => None,
+		};
+		let args = signature.arguments(parent_result);
+		let task = self
+			.registry
+			.create(&signature.task_name, &args.to_string())
+			.await?;
+		let id = task.id();
+
+		let outcome = task
+			.execute_with_result()
+			.await
+			.map(|value| value.unwrap_or(Value::Null));
+		self.store(id, outcome.as_ref()).await?;
+		outcome.map(|value| (id, value))
+	}
+
+	async fn store(&self, id: TaskId, outcome: Result<&Value, &TaskError>) -> TaskResult<()> {
+		let metadata = match outcome {
+			Ok(value) => TaskResultMetadata::new(id, TaskStatus::Success, Some(value.to_string())),
+			Err(e) => TaskResultMetadata::with_error(id, e.to_string()),
+		};
+		self.results
+			.store_result(metadata)
+			.await
+			.map_err(|e| TaskError::QueueError(e.to_string()))
+	}
+
+	async fn load(&self, id: TaskId) -> TaskResult<Value> {
+		let metadata = self
+			.results
+			.get_result(id)
+			.await
+			.map_err(|e| TaskError::QueueError(e.to_string()))?
+			.ok_or_else(|| TaskError::TaskNotFound(id.to_string()))?;
+		let raw = metadata.result().unwrap_or("null");
+		serde_json::from_str(raw).map_err(|e| TaskError::SerializationError(e.to_string()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn test_signature_arguments_wrap_non_objects() {
+		let signature = Signature::new("t", json!([1, 2]));
+
+		assert_eq!(
+			signature.arguments(Some(json!(3))),
+			json!({ "args": [1, 2], "parent_result": 3 })
+		);
+		assert_eq!(signature.arguments(None), json!([1, 2]));
+		assert_eq!(
+			Signature::new("t", Value::Null).arguments(Some(json!(3))),
+			json!({ "parent_result": 3 })
+		);
+	}
+
+	#[test]
+	fn test_canvas_serialization_roundtrip() {
+		let workflow = chord(
+			[Signature::new("a", json!({})).into(), group([])],
+			Signature::new("b", json!({})).immutable(),
+		);
+
+		let json = serde_json::to_string(&workflow).unwrap();
+		let restored: Canvas = serde_json::from_str(&json).unwrap();
+
+		assert_eq!(restored, workflow);
+	}
+}
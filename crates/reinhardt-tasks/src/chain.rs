@@ -2,7 +2,11 @@
 //!
 //! Allows multiple tasks to be executed in sequence, with each task receiving
 //! the result of the previous task.
+//!
+//! For workflows built from task signatures, including parallel groups and
+//! chords, see the [`canvas`](crate::canvas) module.
 
+use crate::result::ResultBackend;
 use crate::{TaskBackend, TaskExecutionError, TaskId, TaskStatus};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -216,6 +220,51 @@ impl TaskChain {
 		matches!(self.status, ChainStatus::Completed | ChainStatus::Failed)
 	}
 
+	/// Get the stored result of the task preceding the current one
+	///
+	/// Lets the current task read its input from the result backend. Returns
+	/// `None` for the first task or if no result was stored.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_tasks::{MemoryResultBackend, ResultBackend, TaskChain, TaskId, TaskResultMetadata, TaskStatus};
+	///
+	/// # async fn example() -> Result<(), reinhardt_tasks::TaskExecutionError> {
+	/// let results = MemoryResultBackend::new();
+	/// let first = TaskId::new();
+	/// let mut chain = TaskChain::new("pipeline");
+	/// chain.add_task(first);
+	/// chain.add_task(TaskId::new());
+	///
+	/// results
+	///     .store_result(TaskResultMetadata::new(first, TaskStatus::Success, Some("42".to_string())))
+	///     .await?;
+	/// assert_eq!(chain.previous_result(&results).await?, None);
+	///
+	/// chain.advance();
+	/// assert_eq!(chain.previous_result(&results).await?, Some("42".to_string()));
+	/// # Ok(())
+	/// # }
+	/// # tokio::runtime::Runtime::new().unwrap().block_on(example()).unwrap();
+	/// ```
+	pub async fn previous_result(
+		&self,
+		results: &dyn ResultBackend,
+	) -> Result<Option<String>, TaskExecutionError> {
+		let Some(previous) = self
+			.current_index
+			.checked_sub(1)
+			.and_then(|index| self.task_ids.get(index))
+		else {
+			return Ok(None);
+		};
+		Ok(results
+			.get_result(*previous)
+			.await?
+			.and_then(|metadata| metadata.result().map(str::to_string)))
+	}
+
 	/// Execute the chain
 	///
 	/// This method will execute all tasks in the chain sequentially.
@@ -6,6 +6,8 @@
 //! - Detection of tasks ready for parallel execution
 //! - Cycle detection
 //! - Topological sorting for execution order
+//! - Concurrent execution of independent branches with configurable
+//!   [`FailurePolicy`]
 //!
 //! # Examples
 //!
@@ -32,9 +34,11 @@
 //! assert_eq!(order.len(), 3);
 //! ```
 
-use crate::{TaskError, TaskId, TaskResult};
+use crate::{TaskError, TaskExecutor, TaskId, TaskResult};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use tokio::task::JoinSet;
 
 /// Task node status within the DAG
 ///
@@ -58,6 +62,66 @@ pub enum TaskNodeStatus {
 	Completed,
 	/// Task failed during execution
 	Failed,
+	/// Task was not run because an upstream task failed
+	Skipped,
+}
+
+/// How a [`TaskDAG`] reacts when a task fails during execution
+///
+/// # Examples
+///
+/// ```rust
+/// use reinhardt_tasks::{FailurePolicy, TaskDAG};
+///
+/// let dag = TaskDAG::new().with_failure_policy(FailurePolicy::FailFast);
+/// assert_eq!(dag.failure_policy(), FailurePolicy::FailFast);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FailurePolicy {
+	/// Skip every task that has not started yet; running tasks finish
+	FailFast,
+	/// Skip only the tasks that transitively depend on the failed task
+	#[default]
+	SkipDependents,
+	/// Treat a failed dependency as finished and run dependents anyway
+	ContinueDependents,
+}
+
+/// Outcome of [`TaskDAG::execute`]
+///
+/// # Examples
+///
+/// ```rust
+/// use reinhardt_tasks::DagExecutionReport;
+///
+/// let report = DagExecutionReport::default();
+/// assert!(report.is_success());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DagExecutionReport {
+	/// Tasks that completed successfully, in completion order
+	pub completed: Vec<TaskId>,
+	/// Tasks that failed, with their error messages
+	pub failed: HashMap<TaskId, String>,
+	/// Tasks skipped because of an upstream failure
+	pub skipped: Vec<TaskId>,
+}
+
+impl DagExecutionReport {
+	/// Returns `true` if no task failed or was skipped
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use reinhardt_tasks::{DagExecutionReport, TaskId};
+	///
+	/// let mut report = DagExecutionReport::default();
+	/// report.skipped.push(TaskId::new());
+	/// assert!(!report.is_success());
+	/// ```
+	pub fn is_success(&self) -> bool {
+		self.failed.is_empty() && self.skipped.is_empty()
+	}
 }
 
 /// A node in the task dependency graph
@@ -204,6 +268,9 @@ pub struct TaskDAG {
 	nodes: HashMap<TaskId, TaskNode>,
 	/// Adjacency list: task -> tasks that depend on it
 	dependents: HashMap<TaskId, Vec<TaskId>>,
+	/// Reaction to task failures during execution
+	#[serde(default)]
+	failure_policy: FailurePolicy,
 }
 
 impl TaskDAG {
@@ -221,7 +288,86 @@ impl TaskDAG {
 		Self {
 			nodes: HashMap::new(),
 			dependents: HashMap::new(),
+			failure_policy: FailurePolicy::default(),
+		}
+	}
+
+	/// Build a DAG from tasks and their declared [`dependencies`](crate::Task::dependencies)
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use reinhardt_tasks::{Task, TaskDAG, TaskExecutor, TaskId, TaskResult};
+	/// use async_trait::async_trait;
+	/// use std::sync::Arc;
+	///
+	/// struct Step { id: TaskId, after: Vec<TaskId> }
+	///
+	/// impl Task for Step {
+	///     fn id(&self) -> TaskId { self.id }
+	///     fn name(&self) -> &str { "step" }
+	///     fn dependencies(&self) -> Vec<TaskId> { self.after.clone() }
+	/// }
+	///
+	/// #[async_trait]
+	/// impl TaskExecutor for Step {
+	///     async fn execute(&self) -> TaskResult<()> { Ok(()) }
+	/// }
+	///
+	/// let a = TaskId::new();
+	/// let b = TaskId::new();
+	/// let tasks: Vec<Arc<dyn TaskExecutor>> = vec![
+	///     Arc::new(Step { id: a, after: vec![] }),
+	///     Arc::new(Step { id: b, after: vec![a] }),
+	/// ];
+	///
+	/// let dag = TaskDAG::from_tasks(&tasks).unwrap();
+	/// assert_eq!(dag.get_task(b).unwrap().dependencies(), &[a]);
+	/// ```
+	///
+	/// # Errors
+	///
+	/// Returns an error if a task is duplicated, depends on a task that is not
+	/// in `tasks`, or the dependencies form a cycle.
+	pub fn from_tasks(tasks: &[Arc<dyn TaskExecutor>]) -> TaskResult<Self> {
+		let mut dag = Self::new();
+		for task in tasks {
+			dag.add_task(task.id())?;
+		}
+		for task in tasks {
+			for dependency in task.dependencies() {
+				dag.add_dependency(task.id(), dependency)?;
+			}
 		}
+		Ok(dag)
+	}
+
+	/// Set the failure policy used by [`execute`](Self::execute)
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use reinhardt_tasks::{FailurePolicy, TaskDAG};
+	///
+	/// let dag = TaskDAG::new().with_failure_policy(FailurePolicy::ContinueDependents);
+	/// assert_eq!(dag.failure_policy(), FailurePolicy::ContinueDependents);
+	/// ```
+	pub fn with_failure_policy(mut self, policy: FailurePolicy) -> Self {
+		self.failure_policy = policy;
+		self
+	}
+
+	/// Get the failure policy
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use reinhardt_tasks::{FailurePolicy, TaskDAG};
+	///
+	/// assert_eq!(TaskDAG::new().failure_policy(), FailurePolicy::SkipDependents);
+	/// ```
+	pub fn failure_policy(&self) -> FailurePolicy {
+		self.failure_policy
 	}
 
 	/// Add a task to the DAG
@@ -359,13 +505,13 @@ impl TaskDAG {
 		self.nodes
 			.values()
 			.filter(|node| {
-				// Task is ready if it's pending and all dependencies are completed
+				// Task is ready if it's pending and all dependencies are finished
 				node.status() == TaskNodeStatus::Pending
 					&& node
 						.dependencies()
 						.iter()
 						.all(|dep_id| match self.nodes.get(dep_id) {
-							Some(dep_node) => dep_node.status() == TaskNodeStatus::Completed,
+							Some(dep_node) => self.dependency_satisfied(dep_node.status()),
 							None => false,
 						})
 			})
@@ -373,6 +519,175 @@ impl TaskDAG {
 			.collect()
 	}
 
+	/// Whether a dependency in `status` allows its dependents to run
+	fn dependency_satisfied(&self, status: TaskNodeStatus) -> bool {
+		match status {
+			TaskNodeStatus::Completed => true,
+			TaskNodeStatus::Failed => self.failure_policy == FailurePolicy::ContinueDependents,
+			_ => false,
+		}
+	}
+
+	/// Mark every pending task that transitively depends on `task_id` as skipped
+	///
+	/// Returns the IDs of the newly skipped tasks.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use reinhardt_tasks::{TaskDAG, TaskId, TaskNodeStatus};
+	///
+	/// let mut dag = TaskDAG::new();
+	/// let task_a = TaskId::new();
+	/// let task_b = TaskId::new();
+	///
+	/// dag.add_task(task_a).unwrap();
+	/// dag.add_task(task_b).unwrap();
+	/// dag.add_dependency(task_b, task_a).unwrap();
+	///
+	/// dag.mark_failed(task_a).unwrap();
+	/// assert_eq!(dag.skip_dependents(task_a), vec![task_b]);
+	/// assert_eq!(dag.get_task(task_b).unwrap().status(), TaskNodeStatus::Skipped);
+	/// ```
+	pub fn skip_dependents(&mut self, task_id: TaskId) -> Vec<TaskId> {
+		let mut skipped = Vec::new();
+		let mut queue: VecDeque<TaskId> = self
+			.dependents
+			.get(&task_id)
+			.cloned()
+			.unwrap_or_default()
+			.into();
+
+		while let Some(dependent) = queue.pop_front() {
+			if let Some(node) = self.nodes.get_mut(&dependent)
+				&& node.status() == TaskNodeStatus::Pending
+			{
+				node.set_status(TaskNodeStatus::Skipped);
+				skipped.push(dependent);
+				if let Some(next) = self.dependents.get(&dependent) {
+					queue.extend(next.iter().copied());
+				}
+			}
+		}
+
+		skipped
+	}
+
+	/// Execute the DAG, running independent tasks concurrently
+	///
+	/// Each task starts as soon as all of its dependencies have finished.
+	/// Failures (including panics) are handled according to the DAG's
+	/// [`FailurePolicy`] and reported in the returned [`DagExecutionReport`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use reinhardt_tasks::{Task, TaskDAG, TaskExecutor, TaskId, TaskResult};
+	/// use async_trait::async_trait;
+	/// use std::sync::Arc;
+	///
+	/// struct Step { id: TaskId }
+	///
+	/// impl Task for Step {
+	///     fn id(&self) -> TaskId { self.id }
+	///     fn name(&self) -> &str { "step" }
+	/// }
+	///
+	/// #[async_trait]
+	/// impl TaskExecutor for Step {
+	///     async fn execute(&self) -> TaskResult<()> { Ok(()) }
+	/// }
+	///
+	/// # async fn example() -> TaskResult<()> {
+	/// let a = TaskId::new();
+	/// let b = TaskId::new();
+	/// let tasks: Vec<Arc<dyn TaskExecutor>> =
+	///     vec![Arc::new(Step { id: a }), Arc::new(Step { id: b })];
+	///
+	/// let mut dag = TaskDAG::from_tasks(&tasks)?;
+	/// dag.add_dependency(b, a)?;
+	///
+	/// let report = dag.execute(&tasks).await?;
+	/// assert_eq!(report.completed, vec![a, b]);
+	/// # Ok(())
+	/// # }
+	/// # tokio::runtime::Runtime::new().unwrap().block_on(example()).unwrap();
+	/// ```
+	///
+	/// # Errors
+	///
+	/// Returns an error if a task in the DAG has no executor in `tasks` or the
+	/// graph contains a cycle.
+	pub async fn execute(
+		&mut self,
+		tasks: &[Arc<dyn TaskExecutor>],
+	) -> TaskResult<DagExecutionReport> {
+		let executors: HashMap<TaskId, Arc<dyn TaskExecutor>> = tasks
+			.iter()
+			.map(|task| (task.id(), Arc::clone(task)))
+			.collect();
+		if let Some(missing) = self.nodes.keys().find(|id| !executors.contains_key(id)) {
+			return Err(TaskError::TaskNotFound(missing.to_string()));
+		}
+		self.topological_sort()?;
+
+		let mut report = DagExecutionReport::default();
+		let mut running: JoinSet<TaskResult<()>> = JoinSet::new();
+		let mut spawned: HashMap<tokio::task::Id, TaskId> = HashMap::new();
+		let mut aborted = false;
+
+		loop {
+			if !aborted {
+				for task_id in self.get_ready_tasks() {
+					self.mark_running(task_id)?;
+					let executor = Arc::clone(&executors[&task_id]);
+					let handle = running.spawn(async move { executor.execute().await });
+					spawned.insert(handle.id(), task_id);
+				}
+			}
+
+			let Some(joined) = running.join_next_with_id().await else {
+				break;
+			};
+			let (task_id, result) = match joined {
+				Ok((id, result)) => (spawned[&id], result),
+				Err(e) => (
+					spawned[&e.id()],
+					Err(TaskError::ExecutionFailed(format!("Task panicked: {}", e))),
+				),
+			};
+
+			match result {
+				Ok(()) => {
+					self.mark_completed(task_id)?;
+					report.completed.push(task_id);
+				}
+				Err(e) => {
+					self.mark_failed(task_id)?;
+					report.failed.insert(task_id, e.to_string());
+					match self.failure_policy {
+						FailurePolicy::FailFast => aborted = true,
+						FailurePolicy::SkipDependents => {
+							report.skipped.extend(self.skip_dependents(task_id));
+						}
+						FailurePolicy::ContinueDependents => {}
+					}
+				}
+			}
+		}
+
+		if aborted {
+			for node in self.nodes.values_mut() {
+				if node.status() == TaskNodeStatus::Pending {
+					node.set_status(TaskNodeStatus::Skipped);
+					report.skipped.push(node.id());
+				}
+			}
+		}
+
+		Ok(report)
+	}
+
 	/// Mark a task as completed
 	///
 	/// # Examples
@@ -5,13 +5,14 @@
 //! ## Features
 //!
 //! - Async task execution
-//! - Task scheduling (cron-like)
+//! - Task scheduling (cron-like), with persistent schedules and leader locks
 //! - Task retries with exponential backoff
-//! - Task priority
-//! - Task chaining
+//! - Per-task rate limits shared across workers
+//! - Task priority, with weighted polling of multiple queues and per-queue concurrency limits
+//! - Task chaining, groups and chords with result passing
 //! - Task dependencies and DAG execution
 //! - Result backend
-//! - Task execution metrics and monitoring
+//! - Task execution metrics and monitoring with Prometheus export
 //! - Worker load balancing (Round-robin, Least-connections, Weighted, Random)
 //! - Webhook notifications for task completion
 //!
@@ -63,6 +64,7 @@
 
 pub mod backend;
 pub mod backends;
+pub mod canvas;
 pub mod chain;
 pub mod dag;
 pub mod load_balancer;
@@ -70,12 +72,14 @@ pub mod locking;
 pub mod metrics;
 pub mod priority_queue;
 pub mod queue;
+pub mod rate_limit;
 pub mod registry;
 pub mod result;
 pub mod retry;
 pub mod scheduler;
 pub mod task;
 pub mod webhook;
+pub mod weighted_queue;
 pub mod worker;
 
 pub use backend::{
@@ -94,17 +98,22 @@ pub use backends::{SqsBackend, SqsConfig};
 
 #[cfg(feature = "rabbitmq-backend")]
 pub use backends::{RabbitMQBackend, RabbitMQConfig};
+pub use canvas::{Canvas, CanvasExecutor, Signature};
 pub use chain::{ChainStatus, TaskChain, TaskChainBuilder};
-pub use dag::{TaskDAG, TaskNode, TaskNodeStatus};
+pub use dag::{DagExecutionReport, FailurePolicy, TaskDAG, TaskNode, TaskNodeStatus};
 pub use load_balancer::{LoadBalancer, LoadBalancingStrategy, WorkerId, WorkerInfo, WorkerMetrics};
 pub use locking::{MemoryTaskLock, TaskLock};
 
 #[cfg(feature = "redis-backend")]
 pub use locking::RedisTaskLock;
-pub use metrics::{MetricsSnapshot, TaskCounts, TaskMetrics, WorkerStats};
+pub use metrics::{
+	DEFAULT_DURATION_BUCKETS, DurationHistogram, MetricsExporter, MetricsSnapshot,
+	PrometheusExporter, RunningTask, TaskCounts, TaskMetrics, TaskNameStats, WorkerStats,
+};
 pub use priority_queue::{Priority, PriorityTaskQueue};
 pub use queue::{QueueConfig, TaskQueue};
-pub use registry::{SerializedTask, TaskFactory, TaskRegistry};
+pub use rate_limit::{RateLimit, RateLimitDecision, RateLimiter};
+pub use registry::{SerializedTask, TaskFactory, TaskOptions, TaskRegistry};
 pub use result::{
 	MemoryResultBackend, ResultBackend, TaskOutput, TaskResult as TaskResultBackend,
 	TaskResultMetadata,
@@ -119,14 +128,26 @@ pub use backends::sqlite::SqliteResultBackend;
 #[cfg(feature = "sqs-backend")]
 pub use backends::sqs::SqsResultBackend;
 pub use retry::{RetryState, RetryStrategy};
-pub use scheduler::{CronSchedule, Schedule, Scheduler};
+pub use scheduler::{
+	CronSchedule, MemoryScheduleStore, PersistentSchedule, Schedule, ScheduleKind, ScheduleStore,
+	Scheduler,
+};
+
+#[cfg(feature = "redis-backend")]
+pub use scheduler::RedisScheduleStore;
+
+#[cfg(feature = "database-backend")]
+pub use scheduler::SqliteScheduleStore;
 pub use task::{
 	DEFAULT_TASK_QUEUE_NAME, TASK_MAX_PRIORITY, TASK_MIN_PRIORITY, Task, TaskExecutor, TaskId,
 	TaskPriority, TaskStatus,
 };
 pub use webhook::{
 	HttpWebhookSender, RetryConfig, TaskStatus as WebhookTaskStatus, WebhookConfig, WebhookError,
-	WebhookEvent, WebhookSender,
+	WebhookEvent, WebhookSender, sign_payload, verify_signature,
+};
+pub use weighted_queue::{
+	DequeuedTask, QueuePermit, QueueSpec, WeightedQueueSet, WeightingStrategy,
 };
 pub use worker::{Worker, WorkerConfig};
 
@@ -60,6 +60,25 @@ pub trait TaskLock: Send + Sync {
 			Ok(false)
 		}
 	}
+
+	/// Take one of `limit` slots of the counter `key`
+	///
+	/// Returns `true` if fewer than `limit` slots were taken, counting this
+	/// one. The counter starts when the first slot is taken and is dropped
+	/// after `ttl`.
+	///
+	/// The default implementation acquires one lock per slot, which costs up
+	/// to `limit` operations; the built-in locks override it with a single
+	/// atomic check-and-increment.
+	async fn acquire_slot(&self, key: TaskId, limit: u32, ttl: Duration) -> TaskResult<bool> {
+		for slot in 0..limit {
+			let slot_id = TaskId(uuid::Uuid::from_u128(key.0.as_u128() ^ u128::from(slot)));
+			if self.acquire(slot_id, ttl).await? {
+				return Ok(true);
+			}
+		}
+		Ok(false)
+	}
 }
 
 /// In-memory task lock for single-process testing
@@ -89,6 +108,8 @@ pub trait TaskLock: Send + Sync {
 /// ```
 pub struct MemoryTaskLock {
 	locks: Arc<RwLock<std::collections::HashMap<TaskId, i64>>>,
+	/// Slot counters with their expiry
+	slots: Arc<RwLock<std::collections::HashMap<TaskId, (u32, i64)>>>,
 }
 
 impl MemoryTaskLock {
@@ -104,6 +125,7 @@ impl MemoryTaskLock {
 	pub fn new() -> Self {
 		Self {
 			locks: Arc::new(RwLock::new(std::collections::HashMap::new())),
+			slots: Arc::new(RwLock::new(std::collections::HashMap::new())),
 		}
 	}
 
@@ -157,6 +179,19 @@ impl TaskLock for MemoryTaskLock {
 			.map(|&expiry| expiry > now)
 			.unwrap_or(false))
 	}
+
+	async fn acquire_slot(&self, key: TaskId, limit: u32, ttl: Duration) -> TaskResult<bool> {
+		let mut slots = self.slots.write().await;
+		let now = chrono::Utc::now().timestamp();
+		slots.retain(|_, &mut (_, expiry)| expiry > now);
+
+		let (taken, _) = slots.entry(key).or_insert((0, now + ttl.as_secs() as i64));
+		if *taken >= limit {
+			return Ok(false);
+		}
+		*taken += 1;
+		Ok(true)
+	}
 }
 
 #[cfg(feature = "redis-backend")]
@@ -292,6 +327,33 @@ impl TaskLock for RedisTaskLock {
 
 		result.map_err(|e| TaskError::ExecutionFailed(format!("Failed to check lock: {}", e)))
 	}
+
+	async fn acquire_slot(&self, key: TaskId, limit: u32, ttl: Duration) -> TaskResult<bool> {
+		use crate::TaskError;
+
+		// Check and increment in one round trip; the TTL starts with the counter
+		let script = redis::Script::new(
+			r"
+			local taken = tonumber(redis.call('GET', KEYS[1]) or '0')
+			if taken >= tonumber(ARGV[1]) then
+				return 0
+			end
+			if redis.call('INCR', KEYS[1]) == 1 then
+				redis.call('PEXPIRE', KEYS[1], ARGV[2])
+			end
+			return 1
+			",
+		);
+		let mut conn = (*self.connection).clone();
+		let acquired: i32 = script
+			.key(format!("{}slots:{}", self.key_prefix, key))
+			.arg(limit)
+			.arg(ttl.as_millis().max(1) as u64)
+			.invoke_async(&mut conn)
+			.await
+			.map_err(|e| TaskError::ExecutionFailed(format!("Failed to acquire slot: {}", e)))?;
+		Ok(acquired == 1)
+	}
 }
 
 #[cfg(test)]
@@ -371,4 +433,16 @@ mod tests {
 		let is_locked = lock.is_locked(task_id).await.unwrap();
 		assert!(is_locked);
 	}
+
+	#[tokio::test]
+	async fn test_memory_lock_acquire_slot() {
+		let lock = MemoryTaskLock::new();
+		let key = TaskId::new();
+		let ttl = Duration::from_secs(60);
+
+		assert!(lock.acquire_slot(key, 2, ttl).await.unwrap());
+		assert!(lock.acquire_slot(key, 2, ttl).await.unwrap());
+		assert!(!lock.acquire_slot(key, 2, ttl).await.unwrap());
+		assert!(lock.acquire_slot(TaskId::new(), 2, ttl).await.unwrap());
+	}
 }
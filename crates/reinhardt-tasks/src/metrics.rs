@@ -8,7 +8,9 @@
 //! - Success/failure rate metrics
 //! - Queue depth monitoring
 //! - Worker utilization metrics
+//! - Per-task-name counters and duration histograms
 //! - Snapshot capabilities for metrics reporting
+//! - Pluggable exporters, with a Prometheus text format exporter
 //!
 //! ## Example
 //!
@@ -34,6 +36,7 @@
 use crate::{TaskId, TaskResult};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 use tokio::sync::RwLock;
 
@@ -131,6 +134,317 @@ pub struct MetricsSnapshot {
 	pub queue_depths: HashMap<String, usize>,
 	/// Worker statistics by worker ID
 	pub worker_stats: HashMap<String, WorkerStats>,
+	/// Per-task-name statistics
+	pub task_stats: HashMap<String, TaskNameStats>,
+}
+
+/// Default histogram bucket upper bounds, in seconds
+pub const DEFAULT_DURATION_BUCKETS: &[f64] = &[
+	0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Histogram of task execution durations
+///
+/// Observations are counted in the first bucket whose upper bound is greater
+/// than or equal to the duration; longer durations only count towards the
+/// implicit `+Inf` bucket.
+///
+/// # Example
+///
+/// ```rust
+/// use reinhardt_tasks::DurationHistogram;
+/// use std::time::Duration;
+///
+/// let mut histogram = DurationHistogram::new(vec![0.1, 1.0]);
+/// histogram.observe(Duration::from_millis(50));
+/// histogram.observe(Duration::from_millis(500));
+/// histogram.observe(Duration::from_secs(5));
+///
+/// assert_eq!(histogram.cumulative_counts(), vec![(0.1, 1), (1.0, 2)]);
+/// assert_eq!(histogram.count(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DurationHistogram {
+	buckets: Vec<f64>,
+	counts: Vec<u64>,
+	count: u64,
+	sum: Duration,
+}
+
+impl DurationHistogram {
+	/// Create a histogram with the given bucket upper bounds in seconds
+	///
+	/// Bounds are sorted; duplicates are removed.
+	pub fn new(mut buckets: Vec<f64>) -> Self {
+		buckets.sort_by(f64::total_cmp);
+		buckets.dedup();
+		let counts = vec![0; buckets.len()];
+		Self {
+			buckets,
+			counts,
+			count: 0,
+			sum: Duration::ZERO,
+		}
+	}
+
+	/// Record a duration
+	pub fn observe(&mut self, duration: Duration) {
+		let seconds = duration.as_secs_f64();
+		if let Some(index) = self.buckets.iter().position(|bound| seconds <= *bound) {
+			self.counts[index] += 1;
+		}
+		self.count += 1;
+		self.sum += duration;
+	}
+
+	/// Bucket upper bounds paired with the number of observations at or below them
+	pub fn cumulative_counts(&self) -> Vec<(f64, u64)> {
+		let mut total = 0;
+		self.buckets
+			.iter()
+			.zip(&self.counts)
+			.map(|(bound, count)| {
+				total += count;
+				(*bound, total)
+			})
+			.collect()
+	}
+
+	/// Total number of observations
+	pub fn count(&self) -> u64 {
+		self.count
+	}
+
+	/// Sum of all observed durations
+	pub fn sum(&self) -> Duration {
+		self.sum
+	}
+}
+
+impl Default for DurationHistogram {
+	fn default() -> Self {
+		Self::new(DEFAULT_DURATION_BUCKETS.to_vec())
+	}
+}
+
+/// Counters and execution durations for a single task name
+///
+/// # Example
+///
+/// ```rust
+/// use reinhardt_tasks::TaskNameStats;
+///
+/// let stats = TaskNameStats::default();
+/// assert_eq!(stats.enqueued, 0);
+/// assert_eq!(stats.durations.count(), 0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TaskNameStats {
+	/// Number of enqueued tasks
+	pub enqueued: u64,
+	/// Number of started executions
+	pub started: u64,
+	/// Number of successful executions
+	pub succeeded: u64,
+	/// Number of failed executions
+	pub failed: u64,
+	/// Number of retries
+	pub retried: u64,
+	/// Execution durations of finished executions
+	pub durations: DurationHistogram,
+}
+
+/// Renders a [`MetricsSnapshot`] for a monitoring system
+///
+/// Implement this trait to plug a different output format into
+/// [`TaskMetrics::export`].
+pub trait MetricsExporter: Send + Sync {
+	/// MIME type of the exported document, for serving it over HTTP
+	fn content_type(&self) -> &'static str;
+
+	/// Render the snapshot
+	fn export(&self, snapshot: &MetricsSnapshot) -> String;
+}
+
+/// Exports metrics in the Prometheus text exposition format
+///
+/// Per-task-name metrics carry a `task` label. All metric names are prefixed
+/// with the namespace (`reinhardt_tasks` by default).
+///
+/// # Example
+///
+/// ```rust
+/// use reinhardt_tasks::{MetricsExporter, PrometheusExporter, TaskId, TaskMetrics};
+/// use std::time::Duration;
+///
+/// # async fn example() {
+/// let metrics = TaskMetrics::new();
+/// let task_id = TaskId::new();
+/// metrics.record_enqueued("send_email").await;
+/// let running = metrics.record_started(&task_id, "send_email").await;
+/// metrics.record_succeeded(&task_id, "send_email", Duration::from_millis(20)).await;
+/// drop(running);
+///
+/// let output = metrics.export(&PrometheusExporter::new()).await;
+/// assert!(output.contains("reinhardt_tasks_enqueued_total{task=\"send_email\"} 1"));
+/// assert!(output.contains("reinhardt_tasks_duration_seconds_count{task=\"send_email\"} 1"));
+/// # }
+/// # tokio::runtime::Runtime::new().unwrap().block_on(example());
+/// ```
+#[derive(Debug, Clone)]
+pub struct PrometheusExporter {
+	namespace: String,
+}
+
+impl PrometheusExporter {
+	/// Create an exporter using the `reinhardt_tasks` namespace
+	pub fn new() -> Self {
+		Self::with_namespace("reinhardt_tasks")
+	}
+
+	/// Create an exporter using a custom metric name prefix
+	pub fn with_namespace(namespace: impl Into<String>) -> Self {
+		Self {
+			namespace: namespace.into(),
+		}
+	}
+
+	fn write_header(&self, out: &mut String, name: &str, kind: &str, help: &str) {
+		out.push_str(&format!(
+			"# HELP {ns}_{name} {help}\n# TYPE {ns}_{name} {kind}\n",
+			ns = self.namespace
+		));
+	}
+
+	fn write_counter(
+		&self,
+		out: &mut String,
+		name: &str,
+		help: &str,
+		tasks: &[(&String, &TaskNameStats)],
+		value: fn(&TaskNameStats) -> u64,
+	) {
+		self.write_header(out, name, "counter", help);
+		for (task, stats) in tasks {
+			out.push_str(&format!(
+				"{}_{}{{task=\"{}\"}} {}\n",
+				self.namespace,
+				name,
+				escape_label(task),
+				value(stats)
+			));
+		}
+	}
+}
+
+impl Default for PrometheusExporter {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl MetricsExporter for PrometheusExporter {
+	fn content_type(&self) -> &'static str {
+		"text/plain; version=0.0.4"
+	}
+
+	fn export(&self, snapshot: &MetricsSnapshot) -> String {
+		let ns = &self.namespace;
+		let mut out = String::new();
+
+		self.write_header(&mut out, "running", "gauge", "Number of running tasks");
+		out.push_str(&format!("{ns}_running {}\n", snapshot.task_counts.running));
+
+		self.write_header(
+			&mut out,
+			"queue_depth",
+			"gauge",
+			"Number of tasks waiting in a queue",
+		);
+		let mut queues: Vec<_> = snapshot.queue_depths.iter().collect();
+		queues.sort();
+		for (queue, depth) in queues {
+			out.push_str(&format!(
+				"{ns}_queue_depth{{queue=\"{}\"}} {depth}\n",
+				escape_label(queue)
+			));
+		}
+
+		let mut tasks: Vec<_> = snapshot.task_stats.iter().collect();
+		tasks.sort_by(|a, b| a.0.cmp(b.0));
+
+		self.write_counter(
+			&mut out,
+			"enqueued_total",
+			"Total enqueued tasks",
+			&tasks,
+			|s| s.enqueued,
+		);
+		self.write_counter(
+			&mut out,
+			"started_total",
+			"Total started task executions",
+			&tasks,
+			|s| s.started,
+		);
+		self.write_counter(
+			&mut out,
+			"succeeded_total",
+			"Total successful task executions",
+			&tasks,
+			|s| s.succeeded,
+		);
+		self.write_counter(
+			&mut out,
+			"failed_total",
+			"Total failed task executions",
+			&tasks,
+			|s| s.failed,
+		);
+		self.write_counter(
+			&mut out,
+			"retried_total",
+			"Total task retries",
+			&tasks,
+			|s| s.retried,
+		);
+
+		self.write_header(
+			&mut out,
+			"duration_seconds",
+			"histogram",
+			"Task execution duration in seconds",
+		);
+		for (task, stats) in &tasks {
+			let task = escape_label(task);
+			for (bound, count) in stats.durations.cumulative_counts() {
+				out.push_str(&format!(
+					"{ns}_duration_seconds_bucket{{task=\"{task}\",le=\"{bound}\"}} {count}\n"
+				));
+			}
+			let count = stats.durations.count();
+			out.push_str(&format!(
+				"{ns}_duration_seconds_bucket{{task=\"{task}\",le=\"+Inf\"}} {count}\n"
+			));
+			out.push_str(&format!(
+				"{ns}_duration_seconds_sum{{task=\"{task}\"}} {}\n",
+				stats.durations.sum().as_secs_f64()
+			));
+			out.push_str(&format!(
+				"{ns}_duration_seconds_count{{task=\"{task}\"}} {count}\n"
+			));
+		}
+
+		out
+	}
+}
+
+/// Escape a Prometheus label value
+fn escape_label(value: &str) -> String {
+	value
+		.replace('\\', "\\\\")
+		.replace('"', "\\\"")
+		.replace('\n', "\\n")
 }
 
 /// Task metrics collector
@@ -159,9 +473,11 @@ pub struct MetricsSnapshot {
 #[derive(Clone)]
 pub struct TaskMetrics {
 	task_counts: Arc<RwLock<TaskCounts>>,
+	running: Arc<AtomicU64>,
 	execution_times: Arc<RwLock<Vec<Duration>>>,
 	queue_depths: Arc<RwLock<HashMap<String, usize>>>,
 	worker_stats: Arc<RwLock<HashMap<String, WorkerStats>>>,
+	task_stats: Arc<RwLock<HashMap<String, TaskNameStats>>>,
 }
 
 impl TaskMetrics {
@@ -177,9 +493,11 @@ impl TaskMetrics {
 	pub fn new() -> Self {
 		Self {
 			task_counts: Arc::new(RwLock::new(TaskCounts::default())),
+			running: Arc::new(AtomicU64::new(0)),
 			execution_times: Arc::new(RwLock::new(Vec::new())),
 			queue_depths: Arc::new(RwLock::new(HashMap::new())),
 			worker_stats: Arc::new(RwLock::new(HashMap::new())),
+			task_stats: Arc::new(RwLock::new(HashMap::new())),
 		}
 	}
 
@@ -200,8 +518,8 @@ impl TaskMetrics {
 	/// # }
 	/// ```
 	pub async fn record_task_start(&self, _task_id: &TaskId) -> TaskResult<()> {
+		self.running.fetch_add(1, Ordering::Relaxed);
 		let mut counts = self.task_counts.write().await;
-		counts.running += 1;
 		counts.total += 1;
 		Ok(())
 	}
@@ -227,17 +545,11 @@ impl TaskMetrics {
 	/// ```
 	pub async fn record_task_success(
 		&self,
-		_task_id: &TaskId,
+		task_id: &TaskId,
 		duration: Duration,
 	) -> TaskResult<()> {
-		let mut counts = self.task_counts.write().await;
-		counts.successful += 1;
-		counts.running = counts.running.saturating_sub(1);
-
-		let mut times = self.execution_times.write().await;
-		times.push(duration);
-
-		Ok(())
+		decrement_running(&self.running);
+		self.record_outcome(task_id, true, duration).await
 	}
 
 	/// Record a failed task
@@ -260,13 +572,26 @@ impl TaskMetrics {
 	/// # }
 	/// ```
 	pub async fn record_task_failure(
+		&self,
+		task_id: &TaskId,
+		duration: Duration,
+	) -> TaskResult<()> {
+		decrement_running(&self.running);
+		self.record_outcome(task_id, false, duration).await
+	}
+
+	async fn record_outcome(
 		&self,
 		_task_id: &TaskId,
+		succeeded: bool,
 		duration: Duration,
 	) -> TaskResult<()> {
 		let mut counts = self.task_counts.write().await;
-		counts.failed += 1;
-		counts.running = counts.running.saturating_sub(1);
+		if succeeded {
+			counts.successful += 1;
+		} else {
+			counts.failed += 1;
+		}
 
 		let mut times = self.execution_times.write().await;
 		times.push(duration);
@@ -326,6 +651,137 @@ impl TaskMetrics {
 		Ok(())
 	}
 
+	/// Record that a task was enqueued
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_tasks::TaskMetrics;
+	///
+	/// # async fn example() {
+	/// let metrics = TaskMetrics::new();
+	/// metrics.record_enqueued("send_email").await;
+	///
+	/// let snapshot = metrics.snapshot().await;
+	/// assert_eq!(snapshot.task_stats["send_email"].enqueued, 1);
+	/// # }
+	/// # tokio::runtime::Runtime::new().unwrap().block_on(example());
+	/// ```
+	pub async fn record_enqueued(&self, task_name: &str) {
+		self.update_task_stats(task_name, |stats| stats.enqueued += 1)
+			.await;
+		let mut counts = self.task_counts.write().await;
+		counts.pending += 1;
+	}
+
+	/// Record that execution of a task started
+	///
+	/// The task counts as running until the returned guard is dropped, so
+	/// executions that end early or are retried do not leave the gauge
+	/// behind. Also updates the global counts like
+	/// [`record_task_start`](Self::record_task_start).
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_tasks::{TaskId, TaskMetrics};
+	///
+	/// # async fn example() {
+	/// let metrics = TaskMetrics::new();
+	/// let running = metrics.record_started(&TaskId::new(), "send_email").await;
+	/// assert_eq!(metrics.snapshot().await.task_counts.running, 1);
+	///
+	/// drop(running);
+	/// assert_eq!(metrics.snapshot().await.task_counts.running, 0);
+	/// # }
+	/// # tokio::runtime::Runtime::new().unwrap().block_on(example());
+	/// ```
+	pub async fn record_started(&self, _task_id: &TaskId, task_name: &str) -> RunningTask {
+		self.update_task_stats(task_name, |stats| stats.started += 1)
+			.await;
+		self.running.fetch_add(1, Ordering::Relaxed);
+		let mut counts = self.task_counts.write().await;
+		counts.pending = counts.pending.saturating_sub(1);
+		counts.total += 1;
+		RunningTask {
+			running: Arc::clone(&self.running),
+		}
+	}
+
+	/// Record a successful execution of a task
+	///
+	/// Also updates the global counts like [`record_task_success`](Self::record_task_success),
+	/// except for the running gauge, which is released by the [`RunningTask`] guard.
+	pub async fn record_succeeded(&self, task_id: &TaskId, task_name: &str, duration: Duration) {
+		self.update_task_stats(task_name, |stats| {
+			stats.succeeded += 1;
+			stats.durations.observe(duration);
+		})
+		.await;
+		let _ = self.record_outcome(task_id, true, duration).await;
+	}
+
+	/// Record a failed execution of a task
+	///
+	/// Also updates the global counts like [`record_task_failure`](Self::record_task_failure),
+	/// except for the running gauge, which is released by the [`RunningTask`] guard.
+	pub async fn record_failed(&self, task_id: &TaskId, task_name: &str, duration: Duration) {
+		self.update_task_stats(task_name, |stats| {
+			stats.failed += 1;
+			stats.durations.observe(duration);
+		})
+		.await;
+		let _ = self.record_outcome(task_id, false, duration).await;
+	}
+
+	/// Record that a task is retried
+	///
+	/// The task counts as pending again until its next attempt starts.
+	pub async fn record_retried(&self, task_name: &str) {
+		self.update_task_stats(task_name, |stats| stats.retried += 1)
+			.await;
+		let mut counts = self.task_counts.write().await;
+		counts.pending += 1;
+	}
+
+	/// Get the statistics recorded for a task name
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_tasks::TaskMetrics;
+	///
+	/// # async fn example() {
+	/// let metrics = TaskMetrics::new();
+	/// metrics.record_retried("sync").await;
+	///
+	/// assert_eq!(metrics.task_stats("sync").await.unwrap().retried, 1);
+	/// assert!(metrics.task_stats("other").await.is_none());
+	/// # }
+	/// # tokio::runtime::Runtime::new().unwrap().block_on(example());
+	/// ```
+	pub async fn task_stats(&self, task_name: &str) -> Option<TaskNameStats> {
+		self.task_stats.read().await.get(task_name).cloned()
+	}
+
+	/// Render the current metrics with an exporter
+	pub async fn export(&self, exporter: &dyn MetricsExporter) -> String {
+		exporter.export(&self.snapshot().await)
+	}
+
+	/// Render the current metrics in the Prometheus text format
+	///
+	/// Shorthand for `export(&PrometheusExporter::new())`, suitable as the
+	/// body of a `/metrics` endpoint.
+	pub async fn export_prometheus(&self) -> String {
+		self.export(&PrometheusExporter::new()).await
+	}
+
+	async fn update_task_stats(&self, task_name: &str, update: impl FnOnce(&mut TaskNameStats)) {
+		let mut task_stats = self.task_stats.write().await;
+		update(task_stats.entry(task_name.to_string()).or_default());
+	}
+
 	/// Get a snapshot of current metrics
 	///
 	/// # Example
@@ -348,10 +804,12 @@ impl TaskMetrics {
 	/// # }
 	/// ```
 	pub async fn snapshot(&self) -> MetricsSnapshot {
-		let counts = self.task_counts.read().await.clone();
+		let mut counts = self.task_counts.read().await.clone();
+		counts.running = self.running.load(Ordering::Relaxed);
 		let times = self.execution_times.read().await.clone();
 		let depths = self.queue_depths.read().await.clone();
 		let workers = self.worker_stats.read().await.clone();
+		let task_stats = self.task_stats.read().await.clone();
 
 		let (average, p50, p95, p99) = Self::calculate_percentiles(&times);
 
@@ -363,6 +821,7 @@ impl TaskMetrics {
 			p99_execution_time: p99,
 			queue_depths: depths,
 			worker_stats: workers,
+			task_stats,
 		}
 	}
 
@@ -390,6 +849,7 @@ impl TaskMetrics {
 	pub async fn reset(&self) -> TaskResult<()> {
 		let mut counts = self.task_counts.write().await;
 		*counts = TaskCounts::default();
+		self.running.store(0, Ordering::Relaxed);
 
 		let mut times = self.execution_times.write().await;
 		times.clear();
@@ -400,6 +860,9 @@ impl TaskMetrics {
 		let mut workers = self.worker_stats.write().await;
 		workers.clear();
 
+		let mut task_stats = self.task_stats.write().await;
+		task_stats.clear();
+
 		Ok(())
 	}
 
@@ -446,6 +909,28 @@ impl Default for TaskMetrics {
 	}
 }
 
+/// Guard counting a task execution as running
+///
+/// Returned by [`TaskMetrics::record_started`]; the running gauge is
+/// decremented when the guard is dropped.
+#[must_use = "the task stops counting as running when the guard is dropped"]
+#[derive(Debug)]
+pub struct RunningTask {
+	running: Arc<AtomicU64>,
+}
+
+impl Drop for RunningTask {
+	fn drop(&mut self) {
+		decrement_running(&self.running);
+	}
+}
+
+fn decrement_running(running: &AtomicU64) {
+	let _ = running.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |count| {
+		count.checked_sub(1)
+	});
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -648,4 +1133,131 @@ mod tests {
 		assert_eq!(snapshot.p95_execution_time, Duration::from_millis(100));
 		assert_eq!(snapshot.p99_execution_time, Duration::from_millis(100));
 	}
+
+	#[tokio::test]
+	async fn test_per_task_name_counters() {
+		let metrics = TaskMetrics::new();
+		let task_id = TaskId::new();
+
+		metrics.record_enqueued("email").await;
+		metrics.record_enqueued("email").await;
+		metrics.record_enqueued("report").await;
+		let running = metrics.record_started(&task_id, "email").await;
+		metrics
+			.record_failed(&task_id, "email", Duration::from_millis(30))
+			.await;
+		drop(running);
+		let running = metrics.record_started(&task_id, "email").await;
+		metrics
+			.record_succeeded(&task_id, "email", Duration::from_millis(20))
+			.await;
+		drop(running);
+
+		let email = metrics.task_stats("email").await.unwrap();
+		assert_eq!(email.enqueued, 2);
+		assert_eq!(email.started, 2);
+		assert_eq!(email.failed, 1);
+		assert_eq!(email.succeeded, 1);
+		assert_eq!(email.durations.count(), 2);
+		assert_eq!(email.durations.sum(), Duration::from_millis(50));
+		assert_eq!(metrics.task_stats("report").await.unwrap().enqueued, 1);
+
+		let snapshot = metrics.snapshot().await;
+		assert_eq!(snapshot.task_counts.total, 2);
+		assert_eq!(snapshot.task_counts.successful, 1);
+		assert_eq!(snapshot.task_counts.failed, 1);
+		assert_eq!(snapshot.task_counts.pending, 1);
+		assert_eq!(snapshot.task_counts.running, 0);
+	}
+
+	#[tokio::test]
+	async fn test_retried_attempt_releases_running_gauge() {
+		let metrics = TaskMetrics::new();
+		let task_id = TaskId::new();
+
+		metrics.record_enqueued("sync").await;
+		let running = metrics.record_started(&task_id, "sync").await;
+		metrics.record_retried("sync").await;
+		drop(running);
+
+		let snapshot = metrics.snapshot().await;
+		assert_eq!(snapshot.task_counts.running, 0);
+		assert_eq!(snapshot.task_counts.pending, 1);
+
+		let _running = metrics.record_started(&task_id, "sync").await;
+		let snapshot = metrics.snapshot().await;
+		assert_eq!(snapshot.task_counts.running, 1);
+		assert_eq!(snapshot.task_counts.pending, 0);
+		assert_eq!(metrics.task_stats("sync").await.unwrap().retried, 1);
+	}
+
+	#[test]
+	fn test_duration_histogram_buckets() {
+		let mut histogram = DurationHistogram::new(vec![1.0, 0.1, 0.1]);
+
+		histogram.observe(Duration::from_millis(100));
+		histogram.observe(Duration::from_millis(200));
+		histogram.observe(Duration::from_secs(2));
+
+		assert_eq!(histogram.cumulative_counts(), vec![(0.1, 1), (1.0, 2)]);
+		assert_eq!(histogram.count(), 3);
+	}
+
+	#[tokio::test]
+	async fn test_prometheus_export() {
+		let metrics = TaskMetrics::new();
+		let task_id = TaskId::new();
+		metrics.record_enqueued("send \"mail\"").await;
+		let running = metrics.record_started(&task_id, "send \"mail\"").await;
+		metrics
+			.record_succeeded(&task_id, "send \"mail\"", Duration::from_millis(40))
+			.await;
+		drop(running);
+		metrics
+			.record_queue_depth("default".to_string(), 3)
+			.await
+			.unwrap();
+
+		let output = metrics.export_prometheus().await;
+
+		assert!(output.contains("# TYPE reinhardt_tasks_enqueued_total counter\n"));
+		assert!(output.contains("reinhardt_tasks_enqueued_total{task=\"send \\\"mail\\\"\"} 1\n"));
+		assert!(output.contains("reinhardt_tasks_failed_total{task=\"send \\\"mail\\\"\"} 0\n"));
+		assert!(output.contains(
+			"reinhardt_tasks_duration_seconds_bucket{task=\"send \\\"mail\\\"\",le=\"0.025\"} 0\n"
+		));
+		assert!(output.contains(
+			"reinhardt_tasks_duration_seconds_bucket{task=\"send \\\"mail\\\"\",le=\"0.05\"} 1\n"
+		));
+		assert!(output.contains(
+			"reinhardt_tasks_duration_seconds_bucket{task=\"send \\\"mail\\\"\",le=\"+Inf\"} 1\n"
+		));
+		assert!(output.contains("reinhardt_tasks_queue_depth{queue=\"default\"} 3\n"));
+		assert!(output.contains("reinhardt_tasks_running 0\n"));
+	}
+
+	#[tokio::test]
+	async fn test_custom_exporter() {
+		struct CountExporter;
+
+		impl MetricsExporter for CountExporter {
+			fn content_type(&self) -> &'static str {
+				"text/plain"
+			}
+
+			fn export(&self, snapshot: &MetricsSnapshot) -> String {
+				format!("tasks={}", snapshot.task_stats.len())
+			}
+		}
+
+		let metrics = TaskMetrics::new();
+		metrics.record_enqueued("a").await;
+		metrics.record_enqueued("b").await;
+
+		assert_eq!(metrics.export(&CountExporter).await, "tasks=2");
+		assert_eq!(
+			PrometheusExporter::with_namespace("jobs").content_type(),
+			"text/plain; version=0.0.4"
+		);
+	}
 }
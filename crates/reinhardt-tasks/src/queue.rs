@@ -1,7 +1,9 @@
 //! Task queue management
 
 use crate::backend::TaskExecutionError;
+use crate::metrics::TaskMetrics;
 use crate::{Task, TaskBackend, TaskId};
+use std::sync::Arc;
 
 #[derive(Debug, Clone)]
 pub struct QueueConfig {
@@ -24,28 +26,83 @@ impl Default for QueueConfig {
 	}
 }
 
-pub struct TaskQueue;
+#[derive(Default)]
+pub struct TaskQueue {
+	metrics: Option<Arc<TaskMetrics>>,
+}
 
 impl TaskQueue {
 	pub fn new() -> Self {
-		Self
+		Self { metrics: None }
 	}
 
 	pub fn with_config(_config: QueueConfig) -> Self {
-		Self
+		Self::new()
 	}
 
+	/// Set the metrics collector counting enqueued tasks
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use reinhardt_tasks::{TaskMetrics, TaskQueue};
+	/// use std::sync::Arc;
+	///
+	/// let metrics = Arc::new(TaskMetrics::new());
+	/// let queue = TaskQueue::new().with_metrics(Arc::clone(&metrics));
+	/// ```
+	pub fn with_metrics(mut self, metrics: Arc<TaskMetrics>) -> Self {
+		self.metrics = Some(metrics);
+		self
+	}
+
+	/// Enqueue a task on the backend
 	pub async fn enqueue(
 		&self,
-		_task: Box<dyn Task>,
-		_backend: &dyn TaskBackend,
+		task: Box<dyn Task>,
+		backend: &dyn TaskBackend,
 	) -> Result<TaskId, TaskExecutionError> {
-		Ok(TaskId::new())
+		let task_name = task.name().to_string();
+		let task_id = backend.enqueue(task).await?;
+		if let Some(ref metrics) = self.metrics {
+			metrics.record_enqueued(&task_name).await;
+		}
+		Ok(task_id)
 	}
 }
 
-impl Default for TaskQueue {
-	fn default() -> Self {
-		Self::new()
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{DummyBackend, TaskPriority};
+
+	struct NamedTask;
+
+	impl Task for NamedTask {
+		fn id(&self) -> TaskId {
+			TaskId::new()
+		}
+
+		fn name(&self) -> &str {
+			"send_email"
+		}
+
+		fn priority(&self) -> TaskPriority {
+			TaskPriority::default()
+		}
+	}
+
+	#[tokio::test]
+	async fn test_enqueue_records_metrics() {
+		let metrics = Arc::new(TaskMetrics::new());
+		let queue = TaskQueue::new().with_metrics(Arc::clone(&metrics));
+
+		queue
+			.enqueue(Box::new(NamedTask), &DummyBackend::new())
+			.await
+			.unwrap();
+
+		assert_eq!(metrics.task_stats("send_email").await.unwrap().enqueued, 1);
+		assert_eq!(metrics.snapshot().await.task_counts.pending, 1);
 	}
 }
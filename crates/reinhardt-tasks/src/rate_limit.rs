@@ -0,0 +1,315 @@
+//! Per-task rate limiting
+//!
+//! A [`RateLimit`] such as `"100/m"` caps how many times a task may start per
+//! period. Limits are attached to tasks on registration with
+//! [`TaskOptions`](crate::TaskOptions) and enforced by workers through a
+//! [`RateLimiter`].
+//!
+//! The limiter counts executions in fixed windows using a [`TaskLock`]: each
+//! window has `limit` slots, and an execution may start once it takes one of
+//! them with [`TaskLock::acquire_slot`]. With a shared lock such as
+//! `RedisTaskLock`, the limit applies across all workers.
+
+use crate::locking::TaskLock;
+use crate::{TaskError, TaskId, TaskResult};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Maximum number of executions per period
+///
+/// Parsed from `"<count>/<unit>"`, where the unit is `s`, `m`, `h` or `d`.
+///
+/// # Examples
+///
+/// ```rust
+/// use reinhardt_tasks::RateLimit;
+/// use std::time::Duration;
+///
+/// let limit: RateLimit = "100/m".parse().unwrap();
+/// assert_eq!(limit.limit, 100);
+/// assert_eq!(limit.period, Duration::from_secs(60));
+/// assert_eq!(limit.to_string(), "100/m");
+///
+/// assert!("100/week".parse::<RateLimit>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+	/// Executions allowed per period
+	pub limit: u32,
+	/// Length of a period
+	pub period: Duration,
+}
+
+impl RateLimit {
+	/// Create a rate limit
+	pub fn new(limit: u32, period: Duration) -> Self {
+		Self { limit, period }
+	}
+
+	/// Allow `limit` executions per second
+	pub fn per_second(limit: u32) -> Self {
+		Self::new(limit, Duration::from_secs(1))
+	}
+
+	/// Allow `limit` executions per minute
+	pub fn per_minute(limit: u32) -> Self {
+		Self::new(limit, Duration::from_secs(60))
+	}
+
+	/// Allow `limit` executions per hour
+	pub fn per_hour(limit: u32) -> Self {
+		Self::new(limit, Duration::from_secs(3600))
+	}
+}
+
+impl FromStr for RateLimit {
+	type Err = TaskError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let invalid = || {
+			TaskError::ExecutionFailed(format!(
+				"Invalid rate limit '{}': expected <count>/<s|m|h|d>",
+				s
+			))
+		};
+		let (count, unit) = s.trim().split_once('/').ok_or_else(invalid)?;
+		let limit = count.trim().parse().map_err(|_| invalid())?;
+		let seconds = match unit.trim() {
+			"s" => 1,
+			"m" => 60,
+			"h" => 3600,
+			"d" => 86400,
+			_ => return Err(invalid()),
+		};
+		Ok(Self::new(limit, Duration::from_secs(seconds)))
+	}
+}
+
+impl fmt::Display for RateLimit {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self.period.as_secs() {
+			1 => write!(f, "{}/s", self.limit),
+			60 => write!(f, "{}/m", self.limit),
+			3600 => write!(f, "{}/h", self.limit),
+			86400 => write!(f, "{}/d", self.limit),
+			seconds => write!(f, "{} per {}s", self.limit, seconds),
+		}
+	}
+}
+
+/// Outcome of a rate limit check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitDecision {
+	/// The execution may start
+	Allowed,
+	/// The limit is reached; try again after the given delay
+	Throttled {
+		/// Time until the current window ends
+		retry_after: Duration,
+	},
+}
+
+/// Enforces [`RateLimit`]s using a [`TaskLock`]
+///
+/// # Examples
+///
+/// ```rust
+/// use reinhardt_tasks::{MemoryTaskLock, RateLimit, RateLimitDecision, RateLimiter};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> reinhardt_tasks::TaskResult<()> {
+/// let limiter = RateLimiter::new(Arc::new(MemoryTaskLock::new()));
+/// let limit = RateLimit::per_minute(1);
+///
+/// assert_eq!(limiter.try_acquire("call_api", &limit).await?, RateLimitDecision::Allowed);
+/// assert!(matches!(
+///     limiter.try_acquire("call_api", &limit).await?,
+///     RateLimitDecision::Throttled { .. }
+/// ));
+/// # Ok(())
+/// # }
+/// # tokio::runtime::Runtime::new().unwrap().block_on(example()).unwrap();
+/// ```
+#[derive(Clone)]
+pub struct RateLimiter {
+	lock: Arc<dyn TaskLock>,
+}
+
+impl RateLimiter {
+	/// Create a limiter storing its counters in `lock`
+	pub fn new(lock: Arc<dyn TaskLock>) -> Self {
+		Self { lock }
+	}
+
+	/// Try to take one execution of `task_name` from the current window
+	pub async fn try_acquire(
+		&self,
+		task_name: &str,
+		limit: &RateLimit,
+	) -> TaskResult<RateLimitDecision> {
+		let period_ms = limit.period.as_millis().max(1);
+		let now_ms = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.unwrap_or_default()
+			.as_millis();
+		let window = now_ms / period_ms;
+		let retry_after = Duration::from_millis((period_ms - now_ms % period_ms) as u64);
+		// Slots only need to outlive their window
+		let ttl = Duration::from_secs(limit.period.as_secs().max(1) + 1);
+
+		if self
+			.lock
+			.acquire_slot(window_id(task_name, window), limit.limit, ttl)
+			.await?
+		{
+			Ok(RateLimitDecision::Allowed)
+		} else {
+			Ok(RateLimitDecision::Throttled { retry_after })
+		}
+	}
+
+	/// Wait until an execution of `task_name` is allowed
+	pub async fn acquire(&self, task_name: &str, limit: &RateLimit) -> TaskResult<()> {
+		loop {
+			match self.try_acquire(task_name, limit).await? {
+				RateLimitDecision::Allowed => return Ok(()),
+				RateLimitDecision::Throttled { retry_after } => {
+					tokio::time::sleep(retry_after).await;
+				}
+			}
+		}
+	}
+}
+
+/// Deterministic counter ID for a window, identical in every process
+fn window_id(task_name: &str, window: u128) -> TaskId {
+	// 64-bit FNV-1a over the key, with two offset bases for 128 bits
+	fn fnv1a(offset: u64, bytes: &[u8]) -> u64 {
+		bytes.iter().fold(offset, |hash, byte| {
+			(hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+		})
+	}
+	let key = format!("rate_limit:{}:{}", task_name, window);
+	let high = fnv1a(0xcbf2_9ce4_8422_2325, key.as_bytes());
+	let low = fnv1a(0x6c62_272e_07bb_0142, key.as_bytes());
+	TaskId(uuid::Uuid::from_u64_pair(high, low))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::MemoryTaskLock;
+	use rstest::rstest;
+
+	#[rstest]
+	#[case("10/s", 10, 1)]
+	#[case(" 100 / m ", 100, 60)]
+	#[case("5/h", 5, 3600)]
+	#[case("1/d", 1, 86400)]
+	fn test_parse_rate_limit(#[case] input: &str, #[case] limit: u32, #[case] seconds: u64) {
+		let parsed: RateLimit = input.parse().unwrap();
+
+		assert_eq!(parsed, RateLimit::new(limit, Duration::from_secs(seconds)));
+	}
+
+	#[rstest]
+	#[case("100")]
+	#[case("x/m")]
+	#[case("10/y")]
+	#[case("-1/s")]
+	fn test_parse_rate_limit_rejects_invalid(#[case] input: &str) {
+		assert!(input.parse::<RateLimit>().is_err());
+	}
+
+	#[test]
+	fn test_window_id_is_deterministic() {
+		assert_eq!(window_id("a", 1), window_id("a", 1));
+		assert_ne!(window_id("a", 1), window_id("a", 2));
+		assert_ne!(window_id("a", 1), window_id("b", 1));
+	}
+
+	#[tokio::test]
+	async fn test_limiter_allows_limit_per_window_and_isolates_tasks() {
+		let limiter = RateLimiter::new(Arc::new(MemoryTaskLock::new()));
+		let limit = RateLimit::per_hour(3);
+
+		for _ in 0..3 {
+			assert_eq!(
+				limiter.try_acquire("api", &limit).await.unwrap(),
+				RateLimitDecision::Allowed
+			);
+		}
+		let throttled = limiter.try_acquire("api", &limit).await.unwrap();
+		assert_eq!(
+			limiter.try_acquire("other", &limit).await.unwrap(),
+			RateLimitDecision::Allowed
+		);
+
+		match throttled {
+			RateLimitDecision::Throttled { retry_after } => {
+				assert!(retry_after <= Duration::from_secs(3600));
+			}
+			RateLimitDecision::Allowed => panic!("fourth call should be throttled"),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_zero_limit_always_throttles() {
+		let limiter = RateLimiter::new(Arc::new(MemoryTaskLock::new()));
+
+		let decision = limiter
+			.try_acquire("disabled", &RateLimit::per_second(0))
+			.await
+			.unwrap();
+
+		assert!(matches!(decision, RateLimitDecision::Throttled { .. }));
+	}
+
+	/// Counts lock operations of the wrapped lock
+	#[derive(Default)]
+	struct CountingLock {
+		inner: MemoryTaskLock,
+		calls: std::sync::atomic::AtomicUsize,
+	}
+
+	#[async_trait::async_trait]
+	impl TaskLock for CountingLock {
+		async fn acquire(&self, task_id: TaskId, ttl: Duration) -> TaskResult<bool> {
+			self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+			self.inner.acquire(task_id, ttl).await
+		}
+
+		async fn release(&self, task_id: TaskId) -> TaskResult<()> {
+			self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+			self.inner.release(task_id).await
+		}
+
+		async fn is_locked(&self, task_id: TaskId) -> TaskResult<bool> {
+			self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+			self.inner.is_locked(task_id).await
+		}
+
+		async fn acquire_slot(&self, key: TaskId, limit: u32, ttl: Duration) -> TaskResult<bool> {
+			self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+			self.inner.acquire_slot(key, limit, ttl).await
+		}
+	}
+
+	#[tokio::test]
+	async fn test_throttled_check_costs_one_lock_operation() {
+		let lock = Arc::new(CountingLock::default());
+		let limiter = RateLimiter::new(lock.clone());
+		let limit = RateLimit::per_minute(100);
+		for _ in 0..100 {
+			limiter.try_acquire("busy", &limit).await.unwrap();
+		}
+		lock.calls.store(0, std::sync::atomic::Ordering::SeqCst);
+
+		let decision = limiter.try_acquire("busy", &limit).await.unwrap();
+
+		assert!(matches!(decision, RateLimitDecision::Throttled { .. }));
+		assert_eq!(lock.calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+	}
+}
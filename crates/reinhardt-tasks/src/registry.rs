@@ -3,6 +3,7 @@
 //! This module provides a registry system to store and retrieve task executors
 //! by name, enabling dynamic task dispatch in distributed task systems.
 
+use crate::rate_limit::RateLimit;
 use crate::{TaskError, TaskExecutor, TaskResult};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -141,6 +142,45 @@ pub trait TaskFactory: Send + Sync {
 	async fn create(&self, data: &str) -> TaskResult<Box<dyn TaskExecutor>>;
 }
 
+/// Options applied to a registered task
+///
+/// # Examples
+///
+/// ```rust
+/// use reinhardt_tasks::TaskOptions;
+///
+/// let options = TaskOptions::new().rate_limit("100/m").unwrap();
+/// assert_eq!(options.rate_limit.unwrap().limit, 100);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskOptions {
+	/// Maximum executions per period across all workers sharing a task lock
+	pub rate_limit: Option<RateLimit>,
+}
+
+impl TaskOptions {
+	/// Create options with no limits
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Set the rate limit from a string such as `"100/m"`
+	///
+	/// # Errors
+	///
+	/// Returns an error if the rate limit cannot be parsed.
+	pub fn rate_limit(mut self, rate_limit: &str) -> TaskResult<Self> {
+		self.rate_limit = Some(rate_limit.parse()?);
+		Ok(self)
+	}
+
+	/// Set the rate limit
+	pub fn with_rate_limit(mut self, rate_limit: RateLimit) -> Self {
+		self.rate_limit = Some(rate_limit);
+		self
+	}
+}
+
 /// Global task registry for dynamic task dispatch
 ///
 /// This registry maintains a mapping of task names to their factory functions,
@@ -186,6 +226,7 @@ pub trait TaskFactory: Send + Sync {
 /// ```
 pub struct TaskRegistry {
 	factories: Arc<RwLock<HashMap<String, Arc<dyn TaskFactory>>>>,
+	options: Arc<RwLock<HashMap<String, TaskOptions>>>,
 }
 
 impl TaskRegistry {
@@ -201,6 +242,7 @@ impl TaskRegistry {
 	pub fn new() -> Self {
 		Self {
 			factories: Arc::new(RwLock::new(HashMap::new())),
+			options: Arc::new(RwLock::new(HashMap::new())),
 		}
 	}
 
@@ -237,7 +279,56 @@ impl TaskRegistry {
 	/// ```
 	pub async fn register(&self, name: String, factory: Arc<dyn TaskFactory>) {
 		let mut factories = self.factories.write().await;
-		factories.insert(name, factory);
+		factories.insert(name.clone(), factory);
+		self.options.write().await.remove(&name);
+	}
+
+	/// Register a task factory with options
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use reinhardt_tasks::{TaskOptions, TaskRegistry, TaskFactory};
+	/// use std::sync::Arc;
+	///
+	/// # async fn example(factory: Arc<dyn TaskFactory>) -> reinhardt_tasks::TaskResult<()> {
+	/// let registry = TaskRegistry::new();
+	/// registry
+	///     .register_with_options(
+	///         "call_payment_api".to_string(),
+	///         factory,
+	///         TaskOptions::new().rate_limit("100/m")?,
+	///     )
+	///     .await;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub async fn register_with_options(
+		&self,
+		name: String,
+		factory: Arc<dyn TaskFactory>,
+		options: TaskOptions,
+	) {
+		self.factories.write().await.insert(name.clone(), factory);
+		self.options.write().await.insert(name, options);
+	}
+
+	/// Get the options a task was registered with
+	///
+	/// Returns the default options for tasks registered without options and
+	/// `None` for unregistered tasks.
+	pub async fn options(&self, name: &str) -> Option<TaskOptions> {
+		if !self.has(name).await {
+			return None;
+		}
+		Some(
+			self.options
+				.read()
+				.await
+				.get(name)
+				.cloned()
+				.unwrap_or_default(),
+		)
 	}
 
 	/// Unregister a task factory
@@ -255,6 +346,7 @@ impl TaskRegistry {
 	pub async fn unregister(&self, name: &str) {
 		let mut factories = self.factories.write().await;
 		factories.remove(name);
+		self.options.write().await.remove(name);
 	}
 
 	/// Check if a task is registered
@@ -331,6 +423,7 @@ impl TaskRegistry {
 	pub async fn clear(&self) {
 		let mut factories = self.factories.write().await;
 		factories.clear();
+		self.options.write().await.clear();
 	}
 }
 
@@ -464,4 +557,27 @@ mod tests {
 		registry.clear().await;
 		assert!(!registry.has("task1").await);
 	}
+
+	#[tokio::test]
+	async fn test_registry_options() {
+		let registry = TaskRegistry::new();
+		registry
+			.register_with_options(
+				"limited".to_string(),
+				Arc::new(TestTaskFactory),
+				TaskOptions::new().rate_limit("10/s").unwrap(),
+			)
+			.await;
+		registry
+			.register("plain".to_string(), Arc::new(TestTaskFactory))
+			.await;
+
+		let limited = registry.options("limited").await.unwrap();
+		assert_eq!(limited.rate_limit, Some(RateLimit::per_second(10)));
+		assert_eq!(registry.options("plain").await, Some(TaskOptions::new()));
+		assert_eq!(registry.options("missing").await, None);
+
+		registry.unregister("limited").await;
+		assert_eq!(registry.options("limited").await, None);
+	}
 }
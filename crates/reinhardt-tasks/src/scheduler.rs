@@ -1,9 +1,19 @@
 //! Task scheduling
 
-use crate::TaskExecutor;
+pub mod persistent;
+
+#[cfg(feature = "redis-backend")]
+pub use persistent::RedisScheduleStore;
+#[cfg(feature = "database-backend")]
+pub use persistent::SqliteScheduleStore;
+pub use persistent::{MemoryScheduleStore, PersistentSchedule, ScheduleKind, ScheduleStore};
+
+use crate::{Task, TaskBackend, TaskError, TaskExecutor, TaskId, TaskResult};
 use chrono::{DateTime, Utc};
 use cron::Schedule as CronParser;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// Cron-like schedule for periodic tasks
 ///
@@ -71,6 +81,11 @@ impl Schedule for CronSchedule {
 
 /// Task scheduler for managing periodic tasks
 ///
+/// In-process tasks added with [`add_task`](Self::add_task) run inside the
+/// scheduler. Schedules kept in a [`ScheduleStore`] (see
+/// [`with_store`](Self::with_store)) are instead enqueued to a task backend,
+/// can be edited at runtime through the store, and survive restarts.
+///
 /// # Example
 ///
 /// ```rust
@@ -81,6 +96,32 @@ impl Schedule for CronSchedule {
 /// ```
 pub struct Scheduler {
 	tasks: Vec<(Box<dyn TaskExecutor>, Box<dyn Schedule>)>,
+	persistent: Option<PersistentBeat>,
+}
+
+/// Store-backed scheduling state of a [`Scheduler`]
+struct PersistentBeat {
+	store: Arc<dyn ScheduleStore>,
+	backend: Arc<dyn TaskBackend>,
+	instance_id: String,
+	lock_ttl: Duration,
+	poll_interval: Duration,
+}
+
+/// Task enqueued for a run of a persistent schedule
+struct ScheduledRun {
+	id: TaskId,
+	name: String,
+}
+
+impl Task for ScheduledRun {
+	fn id(&self) -> TaskId {
+		self.id
+	}
+
+	fn name(&self) -> &str {
+		&self.name
+	}
 }
 
 impl Scheduler {
@@ -94,7 +135,169 @@ impl Scheduler {
 	/// let scheduler = Scheduler::new();
 	/// ```
 	pub fn new() -> Self {
-		Self { tasks: Vec::new() }
+		Self {
+			tasks: Vec::new(),
+			persistent: None,
+		}
+	}
+
+	/// Enqueue schedules from `store` to `backend`
+	///
+	/// Several schedulers may share one store; a per-schedule lock ensures
+	/// each run is enqueued by only one of them.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_tasks::{DummyBackend, MemoryScheduleStore, Scheduler};
+	/// use std::sync::Arc;
+	///
+	/// let scheduler = Scheduler::new()
+	///     .with_store(Arc::new(MemoryScheduleStore::new()), Arc::new(DummyBackend::new()))
+	///     .with_instance_id("beat-1");
+	/// assert_eq!(scheduler.instance_id(), Some("beat-1"));
+	/// ```
+	pub fn with_store(
+		mut self,
+		store: Arc<dyn ScheduleStore>,
+		backend: Arc<dyn TaskBackend>,
+	) -> Self {
+		self.persistent = Some(PersistentBeat {
+			store,
+			backend,
+			instance_id: TaskId::new().to_string(),
+			lock_ttl: Duration::from_secs(30),
+			poll_interval: Duration::from_secs(1),
+		});
+		self
+	}
+
+	/// Set the identifier used as lock owner (defaults to a random ID)
+	///
+	/// Has no effect unless [`with_store`](Self::with_store) was called first.
+	pub fn with_instance_id(mut self, instance_id: impl Into<String>) -> Self {
+		if let Some(beat) = &mut self.persistent {
+			beat.instance_id = instance_id.into();
+		}
+		self
+	}
+
+	/// Set how long a schedule lock is held before it expires (default: 30 seconds)
+	///
+	/// Has no effect unless [`with_store`](Self::with_store) was called first.
+	pub fn with_lock_ttl(mut self, ttl: Duration) -> Self {
+		if let Some(beat) = &mut self.persistent {
+			beat.lock_ttl = ttl;
+		}
+		self
+	}
+
+	/// Set how often [`run`](Self::run) checks the store (default: 1 second)
+	///
+	/// Has no effect unless [`with_store`](Self::with_store) was called first.
+	pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+		if let Some(beat) = &mut self.persistent {
+			beat.poll_interval = interval;
+		}
+		self
+	}
+
+	/// Get the lock owner ID, if a store is configured
+	pub fn instance_id(&self) -> Option<&str> {
+		self.persistent
+			.as_ref()
+			.map(|beat| beat.instance_id.as_str())
+	}
+
+	/// Get the schedule store, if configured
+	pub fn store(&self) -> Option<&Arc<dyn ScheduleStore>> {
+		self.persistent.as_ref().map(|beat| &beat.store)
+	}
+
+	/// Add or replace a persistent schedule
+	///
+	/// # Errors
+	///
+	/// Returns an error if no store is configured or the store fails.
+	pub async fn save_schedule(&self, schedule: PersistentSchedule) -> TaskResult<()> {
+		self.require_store()?.save(schedule).await
+	}
+
+	/// Remove a persistent schedule, returning `true` if it existed
+	///
+	/// # Errors
+	///
+	/// Returns an error if no store is configured or the store fails.
+	pub async fn remove_schedule(&self, name: &str) -> TaskResult<bool> {
+		self.require_store()?.remove(name).await
+	}
+
+	fn require_store(&self) -> TaskResult<&Arc<dyn ScheduleStore>> {
+		self.store().ok_or_else(|| {
+			TaskError::QueueError("Scheduler has no schedule store configured".to_string())
+		})
+	}
+
+	/// Enqueue every persistent schedule that is due
+	///
+	/// Returns the IDs of the enqueued tasks. Schedules locked by another
+	/// scheduler instance are skipped.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_tasks::{
+	///     DummyBackend, MemoryScheduleStore, PersistentSchedule, ScheduleKind, Scheduler,
+	/// };
+	/// use std::sync::Arc;
+	///
+	/// # async fn example() -> reinhardt_tasks::TaskResult<()> {
+	/// let scheduler = Scheduler::new()
+	///     .with_store(Arc::new(MemoryScheduleStore::new()), Arc::new(DummyBackend::new()));
+	///
+	/// let mut schedule = PersistentSchedule::new("cleanup", "cleanup", ScheduleKind::Interval { seconds: 60 });
+	/// schedule.created_at -= chrono::Duration::minutes(5);
+	/// scheduler.save_schedule(schedule).await?;
+	///
+	/// assert_eq!(scheduler.tick().await?.len(), 1);
+	/// // Already ran; next run is a minute away
+	/// assert!(scheduler.tick().await?.is_empty());
+	/// # Ok(())
+	/// # }
+	/// # tokio::runtime::Runtime::new().unwrap().block_on(example()).unwrap();
+	/// ```
+	///
+	/// # Errors
+	///
+	/// Returns an error if no store is configured, or if the store or
+	/// backend fails.
+	pub async fn tick(&self) -> TaskResult<Vec<TaskId>> {
+		let beat = self.persistent.as_ref().ok_or_else(|| {
+			TaskError::QueueError("Scheduler has no schedule store configured".to_string())
+		})?;
+		let now = Utc::now();
+		let mut enqueued = Vec::new();
+
+		for schedule in beat.store.list().await? {
+			if !schedule.is_due(now) {
+				continue;
+			}
+			if !beat
+				.store
+				.try_lock(&schedule.name, &beat.instance_id, beat.lock_ttl)
+				.await?
+			{
+				continue;
+			}
+
+			let result = beat.enqueue_if_due(&schedule.name, now).await;
+			beat.store.unlock(&schedule.name, &beat.instance_id).await?;
+			if let Some(task_id) = result? {
+				enqueued.push(task_id);
+			}
+		}
+
+		Ok(enqueued)
 	}
 
 	/// Add a task with schedule
@@ -130,9 +333,13 @@ impl Scheduler {
 	/// # }
 	/// ```
 	pub async fn run(&self) {
-		use tokio::time::{Duration, sleep};
+		use tokio::time::sleep;
 
 		loop {
+			if let Err(e) = self.tick_if_configured().await {
+				eprintln!("Persistent schedule check failed: {}", e);
+			}
+
 			let now = Utc::now();
 			let mut next_check = None;
 
@@ -157,14 +364,51 @@ impl Scheduler {
 			}
 
 			// Sleep until the next scheduled task
-			if let Some(next) = next_check {
-				let duration = (next - now).to_std().unwrap_or(Duration::from_secs(1));
-				sleep(duration).await;
+			let mut duration = if let Some(next) = next_check {
+				(next - now).to_std().unwrap_or(Duration::from_secs(1))
 			} else {
 				// No tasks scheduled, check again in 60 seconds
-				sleep(Duration::from_secs(60)).await;
+				Duration::from_secs(60)
+			};
+			// Poll the store often enough to pick up runtime edits
+			if let Some(beat) = &self.persistent {
+				duration = duration.min(beat.poll_interval);
 			}
+			sleep(duration).await;
+		}
+	}
+
+	async fn tick_if_configured(&self) -> TaskResult<()> {
+		if self.persistent.is_some() {
+			self.tick().await?;
+		}
+		Ok(())
+	}
+}
+
+impl PersistentBeat {
+	/// Enqueue a schedule while holding its lock
+	///
+	/// The schedule is re-read so that a run already enqueued by another
+	/// instance (which released the lock in between) is not repeated.
+	async fn enqueue_if_due(&self, name: &str, now: DateTime<Utc>) -> TaskResult<Option<TaskId>> {
+		let Some(schedule) = self.store.get(name).await? else {
+			return Ok(None);
+		};
+		if !schedule.is_due(now) {
+			return Ok(None);
 		}
+
+		let task_id = self
+			.backend
+			.enqueue(Box::new(ScheduledRun {
+				id: TaskId::new(),
+				name: schedule.task_name,
+			}))
+			.await
+			.map_err(|e| TaskError::QueueError(e.to_string()))?;
+		self.store.mark_run(name, now).await?;
+		Ok(Some(task_id))
 	}
 }
 
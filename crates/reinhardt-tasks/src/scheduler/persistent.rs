@@ -0,0 +1,685 @@
+//! Persistent periodic schedules
+//!
+//! Schedules stored in a [`ScheduleStore`] can be edited at runtime and
+//! survive restarts. A [`Scheduler`](super::Scheduler) configured with a store
+//! enqueues due schedules to a task backend, taking a per-schedule lock so
+//! that only one of several scheduler instances enqueues each run.
+
+use crate::{TaskError, TaskResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use cron::Schedule as CronParser;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+/// When a persistent schedule fires
+///
+/// # Examples
+///
+/// ```rust
+/// use reinhardt_tasks::ScheduleKind;
+/// use chrono::{TimeZone, Utc};
+///
+/// let every_minute = ScheduleKind::Interval { seconds: 60 };
+/// let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+/// assert_eq!(
+///     every_minute.next_after(start),
+///     Some(Utc.with_ymd_and_hms(2024, 1, 1, 0, 1, 0).unwrap())
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScheduleKind {
+	/// Cron expression (with seconds field, as accepted by [`CronSchedule`](super::CronSchedule))
+	Cron {
+		/// Cron expression
+		expression: String,
+	},
+	/// Fixed interval between runs
+	Interval {
+		/// Interval length in seconds
+		seconds: u64,
+	},
+}
+
+impl ScheduleKind {
+	/// Calculate the first run time strictly after `after`
+	///
+	/// Returns `None` for invalid cron expressions or a zero interval.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use reinhardt_tasks::ScheduleKind;
+	/// use chrono::Utc;
+	///
+	/// let invalid = ScheduleKind::Cron { expression: "not cron".to_string() };
+	/// assert!(invalid.next_after(Utc::now()).is_none());
+	/// ```
+	pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+		match self {
+			Self::Cron { expression } => {
+				CronParser::from_str(expression).ok()?.after(&after).next()
+			}
+			Self::Interval { seconds } if *seconds > 0 => {
+				Some(after + chrono::Duration::seconds(i64::try_from(*seconds).ok()?))
+			}
+			Self::Interval { .. } => None,
+		}
+	}
+}
+
+/// A periodic schedule stored in a [`ScheduleStore`]
+///
+/// # Examples
+///
+/// ```rust
+/// use reinhardt_tasks::{PersistentSchedule, ScheduleKind};
+///
+/// let schedule = PersistentSchedule::new(
+///     "nightly-report",
+///     "generate_report",
+///     ScheduleKind::Cron { expression: "0 0 0 * * *".to_string() },
+/// );
+/// assert!(schedule.enabled);
+/// assert!(schedule.last_run_at.is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersistentSchedule {
+	/// Unique schedule name
+	pub name: String,
+	/// Name of the task enqueued on each run
+	pub task_name: String,
+	/// When the schedule fires
+	pub kind: ScheduleKind,
+	/// Disabled schedules are kept but never enqueued
+	pub enabled: bool,
+	/// Time of the last enqueued run
+	pub last_run_at: Option<DateTime<Utc>>,
+	/// Time the schedule was created
+	pub created_at: DateTime<Utc>,
+}
+
+impl PersistentSchedule {
+	/// Create a new enabled schedule
+	pub fn new(name: impl Into<String>, task_name: impl Into<String>, kind: ScheduleKind) -> Self {
+		Self {
+			name: name.into(),
+			task_name: task_name.into(),
+			kind,
+			enabled: true,
+			last_run_at: None,
+			created_at: Utc::now(),
+		}
+	}
+
+	/// Calculate the next time this schedule is due
+	///
+	/// Counted from the last run, or from creation if it never ran. A
+	/// schedule that missed several runs (e.g. while no scheduler was
+	/// running) is due once, not once per missed run.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use reinhardt_tasks::{PersistentSchedule, ScheduleKind};
+	///
+	/// let schedule = PersistentSchedule::new("s", "t", ScheduleKind::Interval { seconds: 30 });
+	/// let due = schedule.next_due().unwrap();
+	/// assert_eq!((due - schedule.created_at).num_seconds(), 30);
+	/// ```
+	pub fn next_due(&self) -> Option<DateTime<Utc>> {
+		self.kind
+			.next_after(self.last_run_at.unwrap_or(self.created_at))
+	}
+
+	/// Check whether the schedule should be enqueued at `now`
+	pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+		self.enabled && self.next_due().is_some_and(|due| due <= now)
+	}
+}
+
+/// Storage for persistent schedules and their leader locks
+///
+/// Implementations must be safe to share between several scheduler
+/// instances, possibly in different processes.
+#[async_trait]
+pub trait ScheduleStore: Send + Sync {
+	/// Insert or replace a schedule
+	async fn save(&self, schedule: PersistentSchedule) -> TaskResult<()>;
+
+	/// Get a schedule by name
+	async fn get(&self, name: &str) -> TaskResult<Option<PersistentSchedule>>;
+
+	/// List all schedules
+	async fn list(&self) -> TaskResult<Vec<PersistentSchedule>>;
+
+	/// Remove a schedule, returning `true` if it existed
+	async fn remove(&self, name: &str) -> TaskResult<bool>;
+
+	/// Record that a schedule was enqueued at `at`
+	async fn mark_run(&self, name: &str, at: DateTime<Utc>) -> TaskResult<()> {
+		let mut schedule = self
+			.get(name)
+			.await?
+			.ok_or_else(|| TaskError::TaskNotFound(name.to_string()))?;
+		schedule.last_run_at = Some(at);
+		self.save(schedule).await
+	}
+
+	/// Try to become the leader for a schedule
+	///
+	/// Returns `true` if `owner` now holds the lock, either newly or because
+	/// it already held it. The lock expires after `ttl`.
+	async fn try_lock(&self, name: &str, owner: &str, ttl: Duration) -> TaskResult<bool>;
+
+	/// Release a lock held by `owner`
+	async fn unlock(&self, name: &str, owner: &str) -> TaskResult<()>;
+}
+
+/// In-memory schedule store
+///
+/// Schedules do not survive a restart; useful for tests and for sharing
+/// schedules between scheduler instances in one process.
+///
+/// # Examples
+///
+/// ```rust
+/// use reinhardt_tasks::{MemoryScheduleStore, PersistentSchedule, ScheduleKind, ScheduleStore};
+///
+/// # async fn example() -> reinhardt_tasks::TaskResult<()> {
+/// let store = MemoryScheduleStore::new();
+/// store
+///     .save(PersistentSchedule::new("cleanup", "cleanup", ScheduleKind::Interval { seconds: 60 }))
+///     .await?;
+/// assert_eq!(store.list().await?.len(), 1);
+/// # Ok(())
+/// # }
+/// # tokio::runtime::Runtime::new().unwrap().block_on(example()).unwrap();
+/// ```
+#[derive(Default)]
+pub struct MemoryScheduleStore {
+	schedules: Arc<RwLock<HashMap<String, PersistentSchedule>>>,
+	locks: Arc<RwLock<HashMap<String, LockHolder>>>,
+}
+
+/// Current holder of an in-memory schedule lock
+struct LockHolder {
+	owner: String,
+	expires_at: DateTime<Utc>,
+}
+
+impl MemoryScheduleStore {
+	/// Create an empty in-memory store
+	pub fn new() -> Self {
+		Self::default()
+	}
+}
+
+#[async_trait]
+impl ScheduleStore for MemoryScheduleStore {
+	async fn save(&self, schedule: PersistentSchedule) -> TaskResult<()> {
+		self.schedules
+			.write()
+			.await
+			.insert(schedule.name.clone(), schedule);
+		Ok(())
+	}
+
+	async fn get(&self, name: &str) -> TaskResult<Option<PersistentSchedule>> {
+		Ok(self.schedules.read().await.get(name).cloned())
+	}
+
+	async fn list(&self) -> TaskResult<Vec<PersistentSchedule>> {
+		let mut schedules: Vec<_> = self.schedules.read().await.values().cloned().collect();
+		schedules.sort_by(|a, b| a.name.cmp(&b.name));
+		Ok(schedules)
+	}
+
+	async fn remove(&self, name: &str) -> TaskResult<bool> {
+		Ok(self.schedules.write().await.remove(name).is_some())
+	}
+
+	async fn try_lock(&self, name: &str, owner: &str, ttl: Duration) -> TaskResult<bool> {
+		let now = Utc::now();
+		let mut locks = self.locks.write().await;
+
+		if let Some(holder) = locks.get(name)
+			&& holder.owner != owner
+			&& holder.expires_at > now
+		{
+			return Ok(false);
+		}
+
+		let ttl = chrono::Duration::from_std(ttl)
+			.map_err(|e| TaskError::ExecutionFailed(format!("Invalid lock TTL: {}", e)))?;
+		locks.insert(
+			name.to_string(),
+			LockHolder {
+				owner: owner.to_string(),
+				expires_at: now + ttl,
+			},
+		);
+		Ok(true)
+	}
+
+	async fn unlock(&self, name: &str, owner: &str) -> TaskResult<()> {
+		let mut locks = self.locks.write().await;
+		if locks.get(name).is_some_and(|holder| holder.owner == owner) {
+			locks.remove(name);
+		}
+		Ok(())
+	}
+}
+
+#[cfg(feature = "redis-backend")]
+/// Redis-backed schedule store
+///
+/// Schedules are kept as JSON in a hash; leader locks use `SET NX PX`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use reinhardt_tasks::RedisScheduleStore;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let store = RedisScheduleStore::new("redis://127.0.0.1/").await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct RedisScheduleStore {
+	connection: Arc<redis::aio::ConnectionManager>,
+	key_prefix: String,
+}
+
+#[cfg(feature = "redis-backend")]
+impl RedisScheduleStore {
+	/// Create a Redis schedule store with the default key prefix
+	pub async fn new(redis_url: &str) -> Result<Self, redis::RedisError> {
+		Self::with_prefix(redis_url, "reinhardt:beat:".to_string()).await
+	}
+
+	/// Create a Redis schedule store with a custom key prefix
+	pub async fn with_prefix(
+		redis_url: &str,
+		key_prefix: String,
+	) -> Result<Self, redis::RedisError> {
+		let client = redis::Client::open(redis_url)?;
+		let connection = redis::aio::ConnectionManager::new(client).await?;
+
+		Ok(Self {
+			connection: Arc::new(connection),
+			key_prefix,
+		})
+	}
+
+	fn schedules_key(&self) -> String {
+		format!("{}schedules", self.key_prefix)
+	}
+
+	fn lock_key(&self, name: &str) -> String {
+		format!("{}lock:{}", self.key_prefix, name)
+	}
+}
+
+#[cfg(feature = "redis-backend")]
+fn redis_error(e: redis::RedisError) -> TaskError {
+	TaskError::ExecutionFailed(format!("Redis schedule store error: {}", e))
+}
+
+#[cfg(feature = "redis-backend")]
+#[async_trait]
+impl ScheduleStore for RedisScheduleStore {
+	async fn save(&self, schedule: PersistentSchedule) -> TaskResult<()> {
+		use redis::AsyncCommands;
+
+		let json = serde_json::to_string(&schedule)
+			.map_err(|e| TaskError::SerializationError(e.to_string()))?;
+		let mut conn = (*self.connection).clone();
+		let _: () = conn
+			.hset(self.schedules_key(), &schedule.name, json)
+			.await
+			.map_err(redis_error)?;
+		Ok(())
+	}
+
+	async fn get(&self, name: &str) -> TaskResult<Option<PersistentSchedule>> {
+		use redis::AsyncCommands;
+
+		let mut conn = (*self.connection).clone();
+		let json: Option<String> = conn
+			.hget(self.schedules_key(), name)
+			.await
+			.map_err(redis_error)?;
+		json.map(|json| {
+			serde_json::from_str(&json).map_err(|e| TaskError::SerializationError(e.to_string()))
+		})
+		.transpose()
+	}
+
+	async fn list(&self) -> TaskResult<Vec<PersistentSchedule>> {
+		use redis::AsyncCommands;
+
+		let mut conn = (*self.connection).clone();
+		let entries: HashMap<String, String> = conn
+			.hgetall(self.schedules_key())
+			.await
+			.map_err(redis_error)?;
+		let mut schedules = entries
+			.values()
+			.map(|json| {
+				serde_json::from_str(json).map_err(|e| TaskError::SerializationError(e.to_string()))
+			})
+			.collect::<TaskResult<Vec<PersistentSchedule>>>()?;
+		schedules.sort_by(|a, b| a.name.cmp(&b.name));
+		Ok(schedules)
+	}
+
+	async fn remove(&self, name: &str) -> TaskResult<bool> {
+		use redis::AsyncCommands;
+
+		let mut conn = (*self.connection).clone();
+		let removed: usize = conn
+			.hdel(self.schedules_key(), name)
+			.await
+			.map_err(redis_error)?;
+		Ok(removed > 0)
+	}
+
+	async fn try_lock(&self, name: &str, owner: &str, ttl: Duration) -> TaskResult<bool> {
+		// Acquire if free, or refresh the TTL if already held by `owner`
+		let script = redis::Script::new(
+			r"
+			if redis.call('GET', KEYS[1]) == ARGV[1] then
+				return redis.call('PEXPIRE', KEYS[1], ARGV[2])
+			end
+			if redis.call('SET', KEYS[1], ARGV[1], 'NX', 'PX', ARGV[2]) then
+				return 1
+			end
+			return 0
+			",
+		);
+		let mut conn = (*self.connection).clone();
+		let acquired: i32 = script
+			.key(self.lock_key(name))
+			.arg(owner)
+			.arg(ttl.as_millis().max(1) as u64)
+			.invoke_async(&mut conn)
+			.await
+			.map_err(redis_error)?;
+		Ok(acquired == 1)
+	}
+
+	async fn unlock(&self, name: &str, owner: &str) -> TaskResult<()> {
+		let script = redis::Script::new(
+			r"
+			if redis.call('GET', KEYS[1]) == ARGV[1] then
+				return redis.call('DEL', KEYS[1])
+			end
+			return 0
+			",
+		);
+		let mut conn = (*self.connection).clone();
+		let _: i32 = script
+			.key(self.lock_key(name))
+			.arg(owner)
+			.invoke_async(&mut conn)
+			.await
+			.map_err(redis_error)?;
+		Ok(())
+	}
+}
+
+#[cfg(feature = "database-backend")]
+/// SQLite-backed schedule store
+///
+/// # Examples
+///
+/// ```no_run
+/// use reinhardt_tasks::SqliteScheduleStore;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let store = SqliteScheduleStore::new("sqlite://beat.db").await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct SqliteScheduleStore {
+	pool: sqlx::SqlitePool,
+}
+
+#[cfg(feature = "database-backend")]
+impl SqliteScheduleStore {
+	/// Open (creating if needed) a SQLite schedule store
+	pub async fn new(database_url: &str) -> Result<Self, sqlx::Error> {
+		let options =
+			sqlx::sqlite::SqliteConnectOptions::from_str(database_url)?.create_if_missing(true);
+		let pool = sqlx::SqlitePool::connect_with(options).await?;
+
+		let store = Self { pool };
+		store.create_tables().await?;
+		Ok(store)
+	}
+
+	async fn create_tables(&self) -> Result<(), sqlx::Error> {
+		sqlx::query(
+			r#"
+            CREATE TABLE IF NOT EXISTS periodic_schedules (
+                name TEXT PRIMARY KEY,
+                data TEXT NOT NULL
+            )
+        "#,
+		)
+		.execute(&self.pool)
+		.await?;
+
+		sqlx::query(
+			r#"
+            CREATE TABLE IF NOT EXISTS periodic_schedule_locks (
+                name TEXT PRIMARY KEY,
+                owner TEXT NOT NULL,
+                expires_at INTEGER NOT NULL
+            )
+        "#,
+		)
+		.execute(&self.pool)
+		.await?;
+
+		Ok(())
+	}
+}
+
+#[cfg(feature = "database-backend")]
+fn sqlx_error(e: sqlx::Error) -> TaskError {
+	TaskError::ExecutionFailed(format!("SQLite schedule store error: {}", e))
+}
+
+#[cfg(feature = "database-backend")]
+#[async_trait]
+impl ScheduleStore for SqliteScheduleStore {
+	async fn save(&self, schedule: PersistentSchedule) -> TaskResult<()> {
+		let json = serde_json::to_string(&schedule)
+			.map_err(|e| TaskError::SerializationError(e.to_string()))?;
+		sqlx::query(
+			"INSERT INTO periodic_schedules (name, data) VALUES (?, ?)
+             ON CONFLICT(name) DO UPDATE SET data = excluded.data",
+		)
+		.bind(&schedule.name)
+		.bind(json)
+		.execute(&self.pool)
+		.await
+		.map_err(sqlx_error)?;
+		Ok(())
+	}
+
+	async fn get(&self, name: &str) -> TaskResult<Option<PersistentSchedule>> {
+		let json: Option<String> =
+			sqlx::query_scalar("SELECT data FROM periodic_schedules WHERE name = ?")
+				.bind(name)
+				.fetch_optional(&self.pool)
+				.await
+				.map_err(sqlx_error)?;
+		json.map(|json| {
+			serde_json::from_str(&json).map_err(|e| TaskError::SerializationError(e.to_string()))
+		})
+		.transpose()
+	}
+
+	async fn list(&self) -> TaskResult<Vec<PersistentSchedule>> {
+		let rows: Vec<String> =
+			sqlx::query_scalar("SELECT data FROM periodic_schedules ORDER BY name")
+				.fetch_all(&self.pool)
+				.await
+				.map_err(sqlx_error)?;
+		rows.iter()
+			.map(|json| {
+				serde_json::from_str(json).map_err(|e| TaskError::SerializationError(e.to_string()))
+			})
+			.collect()
+	}
+
+	async fn remove(&self, name: &str) -> TaskResult<bool> {
+		let result = sqlx::query("DELETE FROM periodic_schedules WHERE name = ?")
+			.bind(name)
+			.execute(&self.pool)
+			.await
+			.map_err(sqlx_error)?;
+		Ok(result.rows_affected() > 0)
+	}
+
+	async fn try_lock(&self, name: &str, owner: &str, ttl: Duration) -> TaskResult<bool> {
+		let now = Utc::now().timestamp_millis();
+		let expires_at = now.saturating_add(ttl.as_millis() as i64);
+		// Take over the row only if it is expired or already ours
+		let result = sqlx::query(
+			"INSERT INTO periodic_schedule_locks (name, owner, expires_at) VALUES (?, ?, ?)
+             ON CONFLICT(name) DO UPDATE SET owner = excluded.owner, expires_at = excluded.expires_at
+             WHERE periodic_schedule_locks.expires_at <= ? OR periodic_schedule_locks.owner = excluded.owner",
+		)
+		.bind(name)
+		.bind(owner)
+		.bind(expires_at)
+		.bind(now)
+		.execute(&self.pool)
+		.await
+		.map_err(sqlx_error)?;
+		Ok(result.rows_affected() == 1)
+	}
+
+	async fn unlock(&self, name: &str, owner: &str) -> TaskResult<()> {
+		sqlx::query("DELETE FROM periodic_schedule_locks WHERE name = ? AND owner = ?")
+			.bind(name)
+			.bind(owner)
+			.execute(&self.pool)
+			.await
+			.map_err(sqlx_error)?;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use chrono::TimeZone;
+
+	#[test]
+	fn test_interval_next_after() {
+		let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+
+		let next = ScheduleKind::Interval { seconds: 90 }.next_after(start);
+
+		assert_eq!(
+			next,
+			Some(Utc.with_ymd_and_hms(2024, 1, 1, 0, 1, 30).unwrap())
+		);
+		assert!(
+			ScheduleKind::Interval { seconds: 0 }
+				.next_after(start)
+				.is_none()
+		);
+	}
+
+	#[test]
+	fn test_cron_next_after() {
+		let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+		let kind = ScheduleKind::Cron {
+			expression: "0 30 * * * *".to_string(),
+		};
+
+		assert_eq!(
+			kind.next_after(start),
+			Some(Utc.with_ymd_and_hms(2024, 1, 1, 0, 30, 0).unwrap())
+		);
+	}
+
+	#[test]
+	fn test_schedule_is_due_after_last_run() {
+		let mut schedule =
+			PersistentSchedule::new("s", "t", ScheduleKind::Interval { seconds: 60 });
+		let now = schedule.created_at + chrono::Duration::seconds(61);
+
+		assert!(schedule.is_due(now));
+
+		schedule.last_run_at = Some(now);
+		assert!(!schedule.is_due(now));
+
+		schedule.last_run_at = None;
+		schedule.enabled = false;
+		assert!(!schedule.is_due(now));
+	}
+
+	#[tokio::test]
+	async fn test_memory_store_crud() {
+		let store = MemoryScheduleStore::new();
+		let schedule = PersistentSchedule::new("s", "t", ScheduleKind::Interval { seconds: 60 });
+
+		store.save(schedule.clone()).await.unwrap();
+		let at = Utc::now();
+		store.mark_run("s", at).await.unwrap();
+
+		assert_eq!(store.get("s").await.unwrap().unwrap().last_run_at, Some(at));
+		assert!(store.remove("s").await.unwrap());
+		assert!(!store.remove("s").await.unwrap());
+		assert!(store.list().await.unwrap().is_empty());
+		assert!(store.mark_run("s", at).await.is_err());
+	}
+
+	#[tokio::test]
+	async fn test_memory_store_lock_is_exclusive() {
+		let store = MemoryScheduleStore::new();
+		let ttl = Duration::from_secs(30);
+
+		assert!(store.try_lock("s", "a", ttl).await.unwrap());
+		assert!(store.try_lock("s", "a", ttl).await.unwrap());
+		assert!(!store.try_lock("s", "b", ttl).await.unwrap());
+
+		// Only the holder can release the lock
+		store.unlock("s", "b").await.unwrap();
+		assert!(!store.try_lock("s", "b", ttl).await.unwrap());
+		store.unlock("s", "a").await.unwrap();
+		assert!(store.try_lock("s", "b", ttl).await.unwrap());
+	}
+
+	#[tokio::test]
+	async fn test_memory_store_lock_expires() {
+		let store = MemoryScheduleStore::new();
+
+		assert!(
+			store
+				.try_lock("s", "a", Duration::from_millis(10))
+				.await
+				.unwrap()
+		);
+		tokio::time::sleep(Duration::from_millis(20)).await;
+
+		assert!(
+			store
+				.try_lock("s", "b", Duration::from_secs(30))
+				.await
+				.unwrap()
+		);
+	}
+}
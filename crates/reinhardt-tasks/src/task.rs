@@ -142,9 +142,25 @@ pub trait Task: Send + Sync {
 	fn priority(&self) -> TaskPriority {
 		TaskPriority::default()
 	}
+	/// IDs of tasks that must complete before this task runs
+	///
+	/// Used by [`TaskDAG::from_tasks`](crate::TaskDAG::from_tasks) to build
+	/// the dependency graph.
+	fn dependencies(&self) -> Vec<TaskId> {
+		Vec::new()
+	}
 }
 
 #[async_trait]
 pub trait TaskExecutor: Task {
 	async fn execute(&self) -> crate::TaskResult<()>;
+
+	/// Execute the task and return its result
+	///
+	/// Workflows built with the [`canvas`](crate::canvas) module store this
+	/// result in the result backend and pass it to the next task. The default
+	/// implementation runs [`execute`](Self::execute) and returns no result.
+	async fn execute_with_result(&self) -> crate::TaskResult<Option<serde_json::Value>> {
+		self.execute().await.map(|_| None)
+	}
 }
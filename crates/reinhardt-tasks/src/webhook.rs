@@ -1,7 +1,8 @@
-//! Webhook notifications for task completion events
+//! Webhook notifications for task lifecycle events
 //!
 //! This module provides webhook notification support for the Reinhardt tasks system.
-//! Webhooks are HTTP callbacks that are triggered when tasks complete, fail, or are cancelled.
+//! Webhooks are HTTP callbacks that are triggered when tasks complete, fail, are
+//! retried, or are cancelled.
 //!
 //! # Features
 //!
@@ -9,6 +10,18 @@
 //! - Exponential backoff with jitter for failed requests
 //! - Configurable timeout and max retries
 //! - Automatic serialization of task events to JSON
+//! - Per-webhook event filtering
+//! - HMAC-SHA256 payload signing
+//!
+//! # Signing
+//!
+//! When [`WebhookConfig::secret`] is set, each request carries two headers:
+//!
+//! - `X-Webhook-Timestamp`: Unix time of the delivery attempt, in seconds
+//! - `X-Webhook-Signature`: `sha256=` followed by the hex HMAC-SHA256 of
+//!   `"{timestamp}.{body}"` keyed with the secret
+//!
+//! Receivers can check both with [`verify_signature`].
 //!
 //! # Example
 //!
@@ -36,6 +49,8 @@
 //!     headers: HashMap::new(),
 //!     timeout: Duration::from_secs(5),
 //!     retry_config,
+//!     secret: None,
+//!     events: Vec::new(),
 //! };
 //!
 //! let sender = HttpWebhookSender::new(config);
@@ -60,8 +75,10 @@
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
+use sha2::Sha256;
 use std::collections::HashMap;
 use std::time::Duration;
 use thiserror::Error;
@@ -135,6 +152,8 @@ pub enum TaskStatus {
 	Success,
 	/// Task failed with an error
 	Failed,
+	/// Task failed and will be retried
+	Retry,
 	/// Task was cancelled
 	Cancelled,
 }
@@ -244,6 +263,8 @@ impl Default for RetryConfig {
 ///     headers,
 ///     timeout: Duration::from_secs(5),
 ///     retry_config: RetryConfig::default(),
+///     secret: None,
+///     events: Vec::new(),
 /// };
 ///
 /// assert_eq!(config.url, "https://api.example.com/webhooks");
@@ -261,6 +282,30 @@ pub struct WebhookConfig {
 	pub timeout: Duration,
 	/// Retry configuration
 	pub retry_config: RetryConfig,
+	/// Secret used to sign payloads; requests are unsigned when `None`
+	pub secret: Option<String>,
+	/// Events delivered to this webhook; all events when empty
+	pub events: Vec<TaskStatus>,
+}
+
+impl WebhookConfig {
+	/// Check whether events with `status` are delivered to this webhook
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_tasks::webhook::{TaskStatus, WebhookConfig};
+	///
+	/// let mut config = WebhookConfig::default();
+	/// assert!(config.subscribes(TaskStatus::Retry));
+	///
+	/// config.events = vec![TaskStatus::Failed];
+	/// assert!(config.subscribes(TaskStatus::Failed));
+	/// assert!(!config.subscribes(TaskStatus::Success));
+	/// ```
+	pub fn subscribes(&self, status: TaskStatus) -> bool {
+		self.events.is_empty() || self.events.contains(&status)
+	}
 }
 
 impl Default for WebhookConfig {
@@ -271,10 +316,53 @@ impl Default for WebhookConfig {
 			headers: HashMap::new(),
 			timeout: Duration::from_secs(5),
 			retry_config: RetryConfig::default(),
+			secret: None,
+			events: Vec::new(),
 		}
 	}
 }
 
+/// Header carrying the payload signature
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+
+/// Header carrying the timestamp included in the signature
+pub const TIMESTAMP_HEADER: &str = "X-Webhook-Timestamp";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Compute the signature header value for a payload
+///
+/// # Example
+///
+/// ```rust
+/// use reinhardt_tasks::webhook::{sign_payload, verify_signature};
+///
+/// let signature = sign_payload("secret", 1_700_000_000, r#"{"task_name":"report"}"#);
+/// assert!(signature.starts_with("sha256="));
+/// assert!(verify_signature("secret", 1_700_000_000, r#"{"task_name":"report"}"#, &signature));
+/// assert!(!verify_signature("other", 1_700_000_000, r#"{"task_name":"report"}"#, &signature));
+/// ```
+pub fn sign_payload(secret: &str, timestamp: i64, body: &str) -> String {
+	let mut mac =
+		HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+	mac.update(format!("{}.{}", timestamp, body).as_bytes());
+	format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Verify a signature produced by [`sign_payload`] in constant time
+pub fn verify_signature(secret: &str, timestamp: i64, body: &str, signature: &str) -> bool {
+	let Some(signature) = signature
+		.strip_prefix("sha256=")
+		.and_then(|hex_digest| hex::decode(hex_digest).ok())
+	else {
+		return false;
+	};
+	let mut mac =
+		HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+	mac.update(format!("{}.{}", timestamp, body).as_bytes());
+	mac.verify_slice(&signature).is_ok()
+}
+
 /// Trait for webhook senders
 ///
 /// # Example
@@ -323,6 +411,8 @@ pub trait WebhookSender: Send + Sync {
 ///     headers: Default::default(),
 ///     timeout: Duration::from_secs(5),
 ///     retry_config: RetryConfig::default(),
+///     secret: None,
+///     events: Vec::new(),
 /// };
 ///
 /// let sender = HttpWebhookSender::new(config);
@@ -365,6 +455,8 @@ impl HttpWebhookSender {
 	///     headers: Default::default(),
 	///     timeout: Duration::from_secs(5),
 	///     retry_config: RetryConfig::default(),
+	///     secret: None,
+	///     events: Vec::new(),
 	/// };
 	///
 	/// let sender = HttpWebhookSender::new(config);
@@ -411,6 +503,7 @@ impl HttpWebhookSender {
 						backoff
 					);
 
+					tokio::time::sleep(backoff).await;
 					retry_count += 1;
 				}
 			}
@@ -434,6 +527,16 @@ impl HttpWebhookSender {
 			request = request.header(key, value);
 		}
 
+		if let Some(secret) = &self.config.secret {
+			let timestamp = Utc::now().timestamp();
+			request = request
+				.header(TIMESTAMP_HEADER, timestamp.to_string())
+				.header(
+					SIGNATURE_HEADER,
+					sign_payload(secret, timestamp, &json_body),
+				);
+		}
+
 		// Send request
 		let response = request
 			.header("Content-Type", "application/json")
@@ -461,6 +564,9 @@ impl HttpWebhookSender {
 #[async_trait]
 impl WebhookSender for HttpWebhookSender {
 	async fn send(&self, event: &WebhookEvent) -> Result<(), WebhookError> {
+		if !self.config.subscribes(event.status) {
+			return Ok(());
+		}
 		self.send_with_retry(event).await
 	}
 }
@@ -534,6 +640,8 @@ mod tests {
 				max_backoff: Duration::from_secs(10),
 				backoff_multiplier: 2.0,
 			},
+			secret: None,
+			events: Vec::new(),
 		};
 
 		let sender = HttpWebhookSender::new(config);
@@ -649,6 +757,8 @@ mod tests {
 				max_backoff: Duration::from_secs(1),
 				backoff_multiplier: 2.0,
 			},
+			secret: None,
+			events: Vec::new(),
 		};
 
 		let sender = HttpWebhookSender::new(config);
@@ -708,6 +818,8 @@ mod tests {
 				max_backoff: Duration::from_secs(1),
 				backoff_multiplier: 2.0,
 			},
+			secret: None,
+			events: Vec::new(),
 		};
 
 		let sender = HttpWebhookSender::new(config);
@@ -755,6 +867,8 @@ mod tests {
 				max_backoff: Duration::from_secs(1),
 				backoff_multiplier: 2.0,
 			},
+			secret: None,
+			events: Vec::new(),
 		};
 
 		let sender = HttpWebhookSender::new(config);
@@ -808,6 +922,8 @@ mod tests {
 				max_backoff: Duration::from_secs(1),
 				backoff_multiplier: 2.0,
 			},
+			secret: None,
+			events: Vec::new(),
 		};
 
 		let sender = HttpWebhookSender::new(config);
@@ -829,4 +945,103 @@ mod tests {
 
 		mock.assert_async().await;
 	}
+
+	#[test]
+	fn test_verify_signature_rejects_tampering() {
+		let body = r#"{"task_name":"report"}"#;
+		let signature = sign_payload("secret", 1_700_000_000, body);
+
+		assert!(verify_signature("secret", 1_700_000_000, body, &signature));
+		assert!(!verify_signature("secret", 1_700_000_001, body, &signature));
+		assert!(!verify_signature("secret", 1_700_000_000, "{}", &signature));
+		assert!(!verify_signature(
+			"secret",
+			1_700_000_000,
+			body,
+			"sha256=zz"
+		));
+		assert!(!verify_signature(
+			"secret",
+			1_700_000_000,
+			body,
+			signature.trim_start_matches("sha256=")
+		));
+	}
+
+	#[tokio::test]
+	async fn test_webhook_signed_request() {
+		let mut server = mockito::Server::new_async().await;
+
+		let mock = server
+			.mock("POST", "/webhook")
+			.match_header(TIMESTAMP_HEADER, mockito::Matcher::Regex(r"^\d+$".into()))
+			.match_header(
+				SIGNATURE_HEADER,
+				mockito::Matcher::Regex("^sha256=[0-9a-f]{64}$".into()),
+			)
+			.with_status(200)
+			.create_async()
+			.await;
+
+		let config = WebhookConfig {
+			url: format!("{}/webhook", server.url()),
+			secret: Some("webhook-secret".to_string()),
+			..Default::default()
+		};
+		let sender = HttpWebhookSender::new(config);
+
+		let now = Utc::now();
+		let event = WebhookEvent {
+			task_id: TaskId::new(),
+			task_name: "test_task".to_string(),
+			status: TaskStatus::Retry,
+			result: None,
+			error: Some("timeout".to_string()),
+			started_at: now,
+			completed_at: now,
+			duration_ms: 100,
+		};
+
+		let result = sender.send(&event).await;
+		assert!(result.is_ok());
+
+		mock.assert_async().await;
+	}
+
+	#[tokio::test]
+	async fn test_webhook_skips_unsubscribed_events() {
+		let mut server = mockito::Server::new_async().await;
+
+		let mock = server
+			.mock("POST", "/webhook")
+			.with_status(200)
+			.expect(1)
+			.create_async()
+			.await;
+
+		let config = WebhookConfig {
+			url: format!("{}/webhook", server.url()),
+			events: vec![TaskStatus::Failed],
+			..Default::default()
+		};
+		let sender = HttpWebhookSender::new(config);
+
+		let now = Utc::now();
+		let mut event = WebhookEvent {
+			task_id: TaskId::new(),
+			task_name: "test_task".to_string(),
+			status: TaskStatus::Success,
+			result: Some("OK".to_string()),
+			error: None,
+			started_at: now,
+			completed_at: now,
+			duration_ms: 100,
+		};
+
+		assert!(sender.send(&event).await.is_ok());
+		event.status = TaskStatus::Failed;
+		assert!(sender.send(&event).await.is_ok());
+
+		mock.assert_async().await;
+	}
 }
@@ -0,0 +1,394 @@
+//! Weighted polling of multiple named queues
+//!
+//! A [`WeightedQueueSet`] groups several task backends, each acting as a named
+//! queue. Workers dequeue from the set, which decides which queue to poll
+//! first according to a [`WeightingStrategy`]. Queues can be given a
+//! concurrency limit so that bulk jobs cannot occupy every worker slot and
+//! starve interactive tasks.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use reinhardt_tasks::{DummyBackend, QueueSpec, WeightedQueueSet, WeightingStrategy};
+//! use std::sync::Arc;
+//!
+//! let queues = WeightedQueueSet::new(WeightingStrategy::Weighted)
+//!     .with_queue(QueueSpec::new("interactive", Arc::new(DummyBackend::new())).with_weight(10))
+//!     .with_queue(
+//!         QueueSpec::new("bulk", Arc::new(DummyBackend::new()))
+//!             .with_weight(1)
+//!             .with_max_concurrency(2),
+//!     );
+//!
+//! assert_eq!(queues.queue_names(), vec!["interactive", "bulk"]);
+//! ```
+
+use crate::{TaskBackend, TaskError, TaskId, TaskResult};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Strategy deciding which queue a worker polls first
+///
+/// Whatever the strategy, a worker falls back to the remaining queues (in
+/// descending weight order) when the preferred queue is empty or at its
+/// concurrency limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WeightingStrategy {
+	/// Poll each queue first in proportion to its weight (smooth weighted round-robin)
+	#[default]
+	Weighted,
+	/// Always poll queues in descending weight order
+	StrictPriority,
+	/// Poll each queue first in turn, ignoring weights
+	RoundRobin,
+}
+
+/// Configuration of a single queue in a [`WeightedQueueSet`]
+///
+/// # Example
+///
+/// ```rust
+/// use reinhardt_tasks::{DummyBackend, QueueSpec};
+/// use std::sync::Arc;
+///
+/// let spec = QueueSpec::new("reports", Arc::new(DummyBackend::new()))
+///     .with_weight(3)
+///     .with_max_concurrency(1);
+/// assert_eq!(spec.name, "reports");
+/// assert_eq!(spec.weight, 3);
+/// assert_eq!(spec.max_concurrency, Some(1));
+/// ```
+#[derive(Clone)]
+pub struct QueueSpec {
+	/// Queue name
+	pub name: String,
+	/// Backend holding the queue's tasks
+	pub backend: Arc<dyn TaskBackend>,
+	/// Relative polling weight (default: 1)
+	pub weight: u32,
+	/// Maximum number of tasks from this queue running at once
+	pub max_concurrency: Option<usize>,
+}
+
+impl QueueSpec {
+	/// Create a queue with weight 1 and no concurrency limit
+	pub fn new(name: impl Into<String>, backend: Arc<dyn TaskBackend>) -> Self {
+		Self {
+			name: name.into(),
+			backend,
+			weight: 1,
+			max_concurrency: None,
+		}
+	}
+
+	/// Set the polling weight
+	pub fn with_weight(mut self, weight: u32) -> Self {
+		self.weight = weight;
+		self
+	}
+
+	/// Limit how many tasks from this queue may run at once
+	pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+		self.max_concurrency = Some(max_concurrency);
+		self
+	}
+}
+
+struct QueueState {
+	spec: QueueSpec,
+	in_flight: Arc<AtomicUsize>,
+}
+
+impl QueueState {
+	/// Reserve a concurrency slot, failing if the queue is at its limit
+	fn try_acquire(&self) -> Option<QueuePermit> {
+		let limit = self.spec.max_concurrency.unwrap_or(usize::MAX);
+		self.in_flight
+			.fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+				(n < limit).then_some(n + 1)
+			})
+			.ok()?;
+		Some(QueuePermit {
+			in_flight: Arc::clone(&self.in_flight),
+		})
+	}
+
+	fn is_saturated(&self) -> bool {
+		self.spec
+			.max_concurrency
+			.is_some_and(|limit| self.in_flight.load(Ordering::Acquire) >= limit)
+	}
+}
+
+/// Concurrency slot held while a task from a queue runs
+///
+/// The slot is released when the permit is dropped.
+#[derive(Debug)]
+pub struct QueuePermit {
+	in_flight: Arc<AtomicUsize>,
+}
+
+impl Drop for QueuePermit {
+	fn drop(&mut self) {
+		self.in_flight.fetch_sub(1, Ordering::AcqRel);
+	}
+}
+
+/// A task dequeued from a [`WeightedQueueSet`]
+pub struct DequeuedTask {
+	/// ID of the dequeued task
+	pub task_id: TaskId,
+	/// Name of the queue the task came from
+	pub queue: String,
+	/// Backend of the queue, for status updates
+	pub backend: Arc<dyn TaskBackend>,
+	/// Concurrency slot, to be held until the task finishes
+	pub permit: QueuePermit,
+}
+
+/// A set of named queues polled according to a weighting strategy
+///
+/// # Example
+///
+/// ```rust
+/// use reinhardt_tasks::{DummyBackend, QueueSpec, WeightedQueueSet, WeightingStrategy};
+/// use std::sync::Arc;
+///
+/// # async fn example() -> reinhardt_tasks::TaskResult<()> {
+/// let queues = WeightedQueueSet::new(WeightingStrategy::StrictPriority)
+///     .with_queue(QueueSpec::new("default", Arc::new(DummyBackend::new())));
+///
+/// // DummyBackend never holds tasks
+/// assert!(queues.dequeue().await?.is_none());
+/// # Ok(())
+/// # }
+/// # tokio::runtime::Runtime::new().unwrap().block_on(example()).unwrap();
+/// ```
+pub struct WeightedQueueSet {
+	strategy: WeightingStrategy,
+	queues: Vec<QueueState>,
+	/// Per-queue smooth weighted round-robin counters, or the round-robin cursor
+	selection: Mutex<Selection>,
+}
+
+#[derive(Default)]
+struct Selection {
+	current_weights: Vec<i64>,
+	cursor: usize,
+}
+
+impl WeightedQueueSet {
+	/// Create an empty queue set
+	pub fn new(strategy: WeightingStrategy) -> Self {
+		Self {
+			strategy,
+			queues: Vec::new(),
+			selection: Mutex::new(Selection::default()),
+		}
+	}
+
+	/// Add a queue
+	///
+	/// A queue with the same name as an existing one replaces it.
+	pub fn with_queue(mut self, spec: QueueSpec) -> Self {
+		let state = QueueState {
+			spec,
+			in_flight: Arc::new(AtomicUsize::new(0)),
+		};
+		match self
+			.queues
+			.iter()
+			.position(|q| q.spec.name == state.spec.name)
+		{
+			Some(index) => self.queues[index] = state,
+			None => self.queues.push(state),
+		}
+		self.selection
+			.get_mut()
+			.unwrap_or_else(|e| e.into_inner())
+			.current_weights = vec![0; self.queues.len()];
+		self
+	}
+
+	/// Get the configured strategy
+	pub fn strategy(&self) -> WeightingStrategy {
+		self.strategy
+	}
+
+	/// Get the queue names in configuration order
+	pub fn queue_names(&self) -> Vec<&str> {
+		self.queues.iter().map(|q| q.spec.name.as_str()).collect()
+	}
+
+	/// Get the number of tasks from a queue currently running
+	///
+	/// Returns `None` for unknown queues.
+	pub fn in_flight(&self, queue: &str) -> Option<usize> {
+		self.queues
+			.iter()
+			.find(|q| q.spec.name == queue)
+			.map(|q| q.in_flight.load(Ordering::Acquire))
+	}
+
+	/// Dequeue the next task
+	///
+	/// Queues at their concurrency limit are skipped. Returns `None` when no
+	/// eligible queue has a task.
+	///
+	/// # Errors
+	///
+	/// Returns [`TaskError::QueueError`] if a backend fails to dequeue.
+	pub async fn dequeue(&self) -> TaskResult<Option<DequeuedTask>> {
+		for index in self.poll_order() {
+			let queue = &self.queues[index];
+			let Some(permit) = queue.try_acquire() else {
+				continue;
+			};
+			let task_id = queue
+				.spec
+				.backend
+				.dequeue()
+				.await
+				.map_err(|e| TaskError::QueueError(format!("{}: {}", queue.spec.name, e)))?;
+			if let Some(task_id) = task_id {
+				return Ok(Some(DequeuedTask {
+					task_id,
+					queue: queue.spec.name.clone(),
+					backend: Arc::clone(&queue.spec.backend),
+					permit,
+				}));
+			}
+		}
+		Ok(None)
+	}
+
+	/// Order in which queues are polled for the next dequeue
+	///
+	/// The first entry is chosen by the strategy among queues below their
+	/// concurrency limit; the others follow in descending weight order.
+	fn poll_order(&self) -> Vec<usize> {
+		let mut by_weight: Vec<usize> = (0..self.queues.len()).collect();
+		by_weight.sort_by_key(|&i| std::cmp::Reverse(self.queues[i].spec.weight));
+
+		let eligible: Vec<usize> = (0..self.queues.len())
+			.filter(|&i| !self.queues[i].is_saturated())
+			.collect();
+		let first = match self.strategy {
+			WeightingStrategy::StrictPriority => None,
+			WeightingStrategy::Weighted => self.next_weighted(&eligible),
+			WeightingStrategy::RoundRobin => self.next_round_robin(&eligible),
+		};
+
+		if let Some(first) = first {
+			by_weight.retain(|&i| i != first);
+			by_weight.insert(0, first);
+		}
+		by_weight
+	}
+
+	fn next_weighted(&self, eligible: &[usize]) -> Option<usize> {
+		let mut selection = self.selection.lock().unwrap_or_else(|e| e.into_inner());
+		let total: i64 = eligible
+			.iter()
+			.map(|&i| i64::from(self.queues[i].spec.weight))
+			.sum();
+		if total == 0 {
+			return None;
+		}
+
+		let mut best: Option<usize> = None;
+		for &i in eligible {
+			selection.current_weights[i] += i64::from(self.queues[i].spec.weight);
+			if best.is_none_or(|b| selection.current_weights[i] > selection.current_weights[b]) {
+				best = Some(i);
+			}
+		}
+		if let Some(best) = best {
+			selection.current_weights[best] -= total;
+		}
+		best
+	}
+
+	fn next_round_robin(&self, eligible: &[usize]) -> Option<usize> {
+		let mut selection = self.selection.lock().unwrap_or_else(|e| e.into_inner());
+		let len = self.queues.len();
+		let next = (0..len)
+			.map(|offset| (selection.cursor + offset) % len)
+			.find(|i| eligible.contains(i))?;
+		selection.cursor = (next + 1) % len;
+		Some(next)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::DummyBackend;
+
+	fn queue_set(strategy: WeightingStrategy, queues: &[(&str, u32)]) -> WeightedQueueSet {
+		queues
+			.iter()
+			.fold(WeightedQueueSet::new(strategy), |set, (name, weight)| {
+				set.with_queue(
+					QueueSpec::new(*name, Arc::new(DummyBackend::new())).with_weight(*weight),
+				)
+			})
+	}
+
+	fn first_polled(set: &WeightedQueueSet, rounds: usize) -> Vec<&str> {
+		(0..rounds)
+			.map(|_| set.queues[set.poll_order()[0]].spec.name.as_str())
+			.collect()
+	}
+
+	#[test]
+	fn test_weighted_polls_in_proportion_to_weight() {
+		let set = queue_set(WeightingStrategy::Weighted, &[("high", 3), ("low", 1)]);
+
+		let polled = first_polled(&set, 8);
+
+		assert_eq!(polled.iter().filter(|q| **q == "high").count(), 6);
+		assert_eq!(polled.iter().filter(|q| **q == "low").count(), 2);
+		// Smooth weighted round-robin interleaves instead of bursting
+		assert_eq!(&polled[..4], &["high", "high", "low", "high"]);
+	}
+
+	#[test]
+	fn test_strict_priority_and_round_robin_order() {
+		let strict = queue_set(
+			WeightingStrategy::StrictPriority,
+			&[("low", 1), ("high", 5)],
+		);
+		let round_robin = queue_set(WeightingStrategy::RoundRobin, &[("a", 5), ("b", 1)]);
+
+		assert_eq!(first_polled(&strict, 3), vec!["high", "high", "high"]);
+		assert_eq!(first_polled(&round_robin, 4), vec!["a", "b", "a", "b"]);
+	}
+
+	#[test]
+	fn test_concurrency_limit_permits() {
+		let set = WeightedQueueSet::new(WeightingStrategy::StrictPriority)
+			.with_queue(
+				QueueSpec::new("bulk", Arc::new(DummyBackend::new()))
+					.with_weight(10)
+					.with_max_concurrency(1),
+			)
+			.with_queue(QueueSpec::new("interactive", Arc::new(DummyBackend::new())));
+
+		let permit = set.queues[0].try_acquire().unwrap();
+		assert!(set.queues[0].try_acquire().is_none());
+		assert_eq!(set.in_flight("bulk"), Some(1));
+
+		drop(permit);
+		assert_eq!(set.in_flight("bulk"), Some(0));
+		assert_eq!(set.in_flight("missing"), None);
+	}
+
+	#[test]
+	fn test_with_queue_replaces_same_name() {
+		let set = queue_set(WeightingStrategy::Weighted, &[("a", 1), ("b", 1), ("a", 4)]);
+
+		assert_eq!(set.queue_names(), vec!["a", "b"]);
+		assert_eq!(set.queues[0].spec.weight, 4);
+	}
+}
@@ -2,15 +2,21 @@
 
 use crate::{
 	TaskBackend, TaskStatus,
-	locking::TaskLock,
+	locking::{MemoryTaskLock, TaskLock},
+	metrics::TaskMetrics,
+	rate_limit::RateLimiter,
 	registry::TaskRegistry,
 	result::{ResultBackend, TaskResultMetadata},
+	retry::RetryStrategy,
 	webhook::{HttpWebhookSender, WebhookConfig, WebhookEvent, WebhookSender},
+	weighted_queue::WeightedQueueSet,
 };
 use chrono::Utc;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::broadcast;
+use tokio::sync::{Semaphore, broadcast};
+use tokio::task::JoinSet;
 
 /// Worker configuration
 ///
@@ -35,6 +41,8 @@ pub struct WorkerConfig {
 	pub concurrency: usize,
 	pub poll_interval: Duration,
 	pub webhook_configs: Vec<WebhookConfig>,
+	/// Time in-flight tasks get to finish after a shutdown signal
+	pub shutdown_grace_period: Duration,
 }
 
 impl WorkerConfig {
@@ -55,6 +63,7 @@ impl WorkerConfig {
 			concurrency: 4,
 			poll_interval: Duration::from_secs(1),
 			webhook_configs: Vec::new(),
+			shutdown_grace_period: Duration::from_secs(30),
 		}
 	}
 
@@ -90,6 +99,27 @@ impl WorkerConfig {
 		self
 	}
 
+	/// Set how long in-flight tasks may run after a shutdown signal
+	///
+	/// Tasks still running when the grace period ends are cancelled and
+	/// re-enqueued, or marked as failed if the backend cannot re-enqueue
+	/// them.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use reinhardt_tasks::WorkerConfig;
+	/// use std::time::Duration;
+	///
+	/// let config = WorkerConfig::new("worker".to_string())
+	///     .with_shutdown_grace_period(Duration::from_secs(10));
+	/// assert_eq!(config.shutdown_grace_period, Duration::from_secs(10));
+	/// ```
+	pub fn with_shutdown_grace_period(mut self, grace_period: Duration) -> Self {
+		self.shutdown_grace_period = grace_period;
+		self
+	}
+
 	/// Add a webhook configuration
 	///
 	/// # Examples
@@ -104,6 +134,8 @@ impl WorkerConfig {
 	///     headers: Default::default(),
 	///     timeout: Duration::from_secs(5),
 	///     retry_config: Default::default(),
+	///     secret: None,
+	///     events: Vec::new(),
 	/// };
 	///
 	/// let config = WorkerConfig::new("worker".to_string())
@@ -147,6 +179,13 @@ impl Default for WorkerConfig {
 ///
 /// Polls the backend for tasks and executes them concurrently.
 ///
+/// On [`stop`](Worker::stop), or on SIGTERM after
+/// [`stop_on_signal`](Worker::stop_on_signal), the worker stops fetching tasks
+/// and waits up to [`WorkerConfig::shutdown_grace_period`] for in-flight
+/// tasks. Tasks still running after that are re-enqueued through
+/// [`TaskBackend::requeue`], or marked as failed if the backend cannot
+/// re-enqueue them.
+///
 /// # Examples
 ///
 /// ```rust,no_run
@@ -175,6 +214,9 @@ pub struct Worker {
 	task_lock: Option<Arc<dyn TaskLock>>,
 	result_backend: Option<Arc<dyn ResultBackend>>,
 	webhook_senders: Vec<Arc<dyn WebhookSender>>,
+	metrics: Option<Arc<TaskMetrics>>,
+	rate_limiter: RateLimiter,
+	retry_strategy: Option<RetryStrategy>,
 }
 
 impl Worker {
@@ -207,6 +249,9 @@ impl Worker {
 			task_lock: None,
 			result_backend: None,
 			webhook_senders,
+			metrics: None,
+			rate_limiter: RateLimiter::new(Arc::new(MemoryTaskLock::new())),
+			retry_strategy: None,
 		}
 	}
 
@@ -228,6 +273,10 @@ impl Worker {
 
 	/// Set the task lock for distributed task execution
 	///
+	/// The lock also stores rate limit counters, so a shared lock enforces
+	/// [`TaskOptions::rate_limit`](crate::TaskOptions) across all workers.
+	/// Without it, rate limits only apply within this worker.
+	///
 	/// # Examples
 	///
 	/// ```rust
@@ -238,6 +287,7 @@ impl Worker {
 	///     .with_lock(Arc::new(MemoryTaskLock::new()));
 	/// ```
 	pub fn with_lock(mut self, task_lock: Arc<dyn TaskLock>) -> Self {
+		self.rate_limiter = RateLimiter::new(Arc::clone(&task_lock));
 		self.task_lock = Some(task_lock);
 		self
 	}
@@ -258,6 +308,42 @@ impl Worker {
 		self
 	}
 
+	/// Set the metrics collector recording task executions
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use reinhardt_tasks::{TaskMetrics, Worker, WorkerConfig};
+	/// use std::sync::Arc;
+	///
+	/// let metrics = Arc::new(TaskMetrics::new());
+	/// let worker = Worker::new(WorkerConfig::default())
+	///     .with_metrics(Arc::clone(&metrics));
+	/// ```
+	pub fn with_metrics(mut self, metrics: Arc<TaskMetrics>) -> Self {
+		self.metrics = Some(metrics);
+		self
+	}
+
+	/// Set the strategy for retrying failed tasks
+	///
+	/// Failed attempts that will be retried are reported to webhooks as
+	/// [`TaskStatus::Retry`](crate::webhook::TaskStatus::Retry) events; only
+	/// the final failure is stored in the result backend.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use reinhardt_tasks::{RetryStrategy, Worker, WorkerConfig};
+	///
+	/// let worker = Worker::new(WorkerConfig::default())
+	///     .with_retry_strategy(RetryStrategy::exponential_backoff().with_max_retries(3));
+	/// ```
+	pub fn with_retry_strategy(mut self, retry_strategy: RetryStrategy) -> Self {
+		self.retry_strategy = Some(retry_strategy);
+		self
+	}
+
 	/// Run the worker loop
 	///
 	/// This method blocks until the worker is stopped via `stop()`.
@@ -297,7 +383,28 @@ impl Worker {
 					break;
 				}
 				_ = poll_interval.tick() => {
-					self.try_process_task(backend.clone()).await;
+					let Some(task_id) = self.try_dequeue(&backend).await else {
+						continue;
+					};
+					println!("[{}] Processing task: {}", self.config.name, task_id);
+					let processing = self.process_task(task_id, backend.clone());
+					tokio::pin!(processing);
+					tokio::select! {
+						_ = &mut processing => {}
+						_ = shutdown_rx.recv() => {
+							println!("[{}] Shutdown signal received", self.config.name);
+							let finished = tokio::time::timeout(
+								self.config.shutdown_grace_period,
+								&mut processing,
+							)
+							.await
+							.is_ok();
+							if !finished {
+								self.interrupt_task(task_id, &backend).await;
+							}
+							break;
+						}
+					}
 				}
 			}
 		}
@@ -306,52 +413,254 @@ impl Worker {
 		Ok(())
 	}
 
-	/// Try to process a single task from the backend
-	async fn try_process_task(&self, backend: Arc<dyn TaskBackend>) {
-		match backend.dequeue().await {
-			Ok(Some(task_id)) => {
-				println!("[{}] Processing task: {}", self.config.name, task_id);
+	/// Run the worker loop over a set of weighted queues
+	///
+	/// Unlike [`run`](Self::run), tasks execute concurrently: up to
+	/// `concurrency` tasks in total, and no more than each queue's
+	/// `max_concurrency` per queue. Queues are polled in the order chosen by
+	/// the set's [`WeightingStrategy`](crate::WeightingStrategy).
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use reinhardt_tasks::{DummyBackend, QueueSpec, WeightedQueueSet, WeightingStrategy, Worker, WorkerConfig};
+	/// use std::sync::Arc;
+	///
+	/// # async fn example() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+	/// let queues = WeightedQueueSet::new(WeightingStrategy::Weighted)
+	///     .with_queue(QueueSpec::new("interactive", Arc::new(DummyBackend::new())).with_weight(5))
+	///     .with_queue(
+	///         QueueSpec::new("bulk", Arc::new(DummyBackend::new())).with_max_concurrency(1),
+	///     );
+	///
+	/// let worker = Arc::new(Worker::new(WorkerConfig::default()));
+	/// worker.run_queues(Arc::new(queues)).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub async fn run_queues(
+		self: Arc<Self>,
+		queues: Arc<WeightedQueueSet>,
+	) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+		use tokio::time::interval;
 
-				// Execute task
-				match self.execute_task(task_id, backend.clone()).await {
-					Ok(_) => {
-						println!(
-							"[{}] Task {} completed successfully",
-							self.config.name, task_id
-						);
-						if let Err(e) = backend.update_status(task_id, TaskStatus::Success).await {
-							eprintln!(
-								"[{}] Failed to update task {} status: {}",
-								self.config.name, task_id, e
-							);
-						}
-					}
-					Err(e) => {
-						eprintln!("[{}] Task {} failed: {}", self.config.name, task_id, e);
-						if let Err(e) = backend.update_status(task_id, TaskStatus::Failure).await {
-							eprintln!(
-								"[{}] Failed to update task {} status: {}",
-								self.config.name, task_id, e
-							);
+		let mut shutdown_rx = self.shutdown_tx.subscribe();
+		let mut poll_interval = interval(self.config.poll_interval);
+		let slots = Arc::new(Semaphore::new(self.config.concurrency.max(1)));
+		let mut running = JoinSet::new();
+		let mut in_flight = HashMap::new();
+
+		println!(
+			"[{}] Worker started with concurrency {} on queues {:?}",
+			self.config.name,
+			self.config.concurrency,
+			queues.queue_names()
+		);
+
+		loop {
+			tokio::select! {
+				_ = shutdown_rx.recv() => {
+					println!("[{}] Shutdown signal received", self.config.name);
+					break;
+				}
+				Some(joined) = running.join_next_with_id(), if !running.is_empty() => {
+					let id = match joined {
+						Ok((id, ())) => id,
+						Err(e) => e.id(),
+					};
+					in_flight.remove(&id);
+				}
+				_ = poll_interval.tick() => {
+					// Fill free slots without waiting for the next tick
+					while let Ok(slot) = Arc::clone(&slots).try_acquire_owned() {
+						match queues.dequeue().await {
+							Ok(Some(dequeued)) => {
+								let task = (dequeued.task_id, Arc::clone(&dequeued.backend));
+								let worker = Arc::clone(&self);
+								let handle = running.spawn(async move {
+									println!(
+										"[{}] Processing task {} from queue {}",
+										worker.config.name, dequeued.task_id, dequeued.queue
+									);
+									worker
+										.process_task(dequeued.task_id, dequeued.backend)
+										.await;
+									drop(dequeued.permit);
+									drop(slot);
+								});
+								in_flight.insert(handle.id(), task);
+							}
+							Ok(None) => break,
+							Err(e) => {
+								eprintln!("[{}] Failed to dequeue task: {}", self.config.name, e);
+								break;
+							}
 						}
 					}
 				}
 			}
-			Ok(None) => {
-				// No tasks available - interval will automatically wait before next poll
+		}
+
+		// Let running tasks finish within the grace period, then cancel the
+		// rest and hand them back to their queues
+		let drained = tokio::time::timeout(self.config.shutdown_grace_period, async {
+			while let Some(joined) = running.join_next_with_id().await {
+				if let Ok((id, ())) = joined {
+					in_flight.remove(&id);
+				}
+			}
+		})
+		.await;
+		if drained.is_err() {
+			running.abort_all();
+			while let Some(joined) = running.join_next_with_id().await {
+				if let Ok((id, ())) = joined {
+					in_flight.remove(&id);
+				}
+			}
+		}
+		for (task_id, backend) in in_flight.into_values() {
+			self.interrupt_task(task_id, &backend).await;
+		}
+
+		println!("[{}] Worker stopped", self.config.name);
+		Ok(())
+	}
+
+	/// Try to dequeue a single task from the backend
+	async fn try_dequeue(&self, backend: &Arc<dyn TaskBackend>) -> Option<crate::TaskId> {
+		match backend.dequeue().await {
+			Ok(task_id) => {
+				// `None` means no tasks are available - interval will
+				// automatically wait before next poll
+				task_id
 			}
 			Err(e) => {
 				eprintln!("[{}] Failed to dequeue task: {}", self.config.name, e);
 				// Error occurred - interval will automatically wait before next poll
+				None
+			}
+		}
+	}
+
+	/// Hand a task cancelled by shutdown back to its backend
+	///
+	/// The task is re-enqueued if the backend supports it and marked as
+	/// failed otherwise.
+	async fn interrupt_task(&self, task_id: crate::TaskId, backend: &Arc<dyn TaskBackend>) {
+		// The cancelled execution never reached its own lock release
+		if let Some(ref lock) = self.task_lock
+			&& let Err(e) = lock.release(task_id).await
+		{
+			eprintln!(
+				"[{}] Failed to release lock for task {}: {}",
+				self.config.name, task_id, e
+			);
+		}
+
+		match backend.requeue(task_id).await {
+			Ok(true) => {
+				println!(
+					"[{}] Task {} interrupted by shutdown, re-enqueued",
+					self.config.name, task_id
+				);
+				return;
+			}
+			Ok(false) => {}
+			Err(e) => eprintln!(
+				"[{}] Failed to re-enqueue task {}: {}",
+				self.config.name, task_id, e
+			),
+		}
+
+		eprintln!(
+			"[{}] Task {} interrupted by shutdown",
+			self.config.name, task_id
+		);
+		if let Err(e) = backend.update_status(task_id, TaskStatus::Failure).await {
+			eprintln!(
+				"[{}] Failed to update task {} status: {}",
+				self.config.name, task_id, e
+			);
+		}
+		if let Some(ref result_backend) = self.result_backend {
+			let metadata = TaskResultMetadata::with_error(
+				task_id,
+				"Task interrupted by worker shutdown".to_string(),
+			);
+			if let Err(e) = result_backend.store_result(metadata).await {
+				eprintln!(
+					"[{}] Failed to store result for task {}: {}",
+					self.config.name, task_id, e
+				);
+			}
+		}
+	}
+
+	/// Execute a dequeued task and record its final status in the backend
+	async fn process_task(&self, task_id: crate::TaskId, backend: Arc<dyn TaskBackend>) {
+		let mut attempt = 0;
+		let outcome = loop {
+			let will_retry = self
+				.retry_strategy
+				.as_ref()
+				.is_some_and(|strategy| strategy.should_retry(attempt));
+			match self
+				.execute_task(task_id, backend.clone(), will_retry)
+				.await
+			{
+				Err(e) if will_retry => {
+					attempt += 1;
+					eprintln!(
+						"[{}] Task {} failed (attempt {}), retrying: {}",
+						self.config.name, task_id, attempt, e
+					);
+					if let Err(e) = backend.update_status(task_id, TaskStatus::Retry).await {
+						eprintln!(
+							"[{}] Failed to update task {} status: {}",
+							self.config.name, task_id, e
+						);
+					}
+					if let Some(strategy) = &self.retry_strategy {
+						tokio::time::sleep(strategy.calculate_delay(attempt)).await;
+					}
+				}
+				outcome => break outcome,
+			}
+		};
+		match outcome {
+			Ok(_) => {
+				println!(
+					"[{}] Task {} completed successfully",
+					self.config.name, task_id
+				);
+				if let Err(e) = backend.update_status(task_id, TaskStatus::Success).await {
+					eprintln!(
+						"[{}] Failed to update task {} status: {}",
+						self.config.name, task_id, e
+					);
+				}
+			}
+			Err(e) => {
+				eprintln!("[{}] Task {} failed: {}", self.config.name, task_id, e);
+				if let Err(e) = backend.update_status(task_id, TaskStatus::Failure).await {
+					eprintln!(
+						"[{}] Failed to update task {} status: {}",
+						self.config.name, task_id, e
+					);
+				}
 			}
 		}
 	}
 
 	/// Execute a task
+	///
+	/// `will_retry` marks an attempt that is retried if it fails.
 	async fn execute_task(
 		&self,
 		task_id: crate::TaskId,
 		backend: Arc<dyn TaskBackend>,
+		will_retry: bool,
 	) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
 		println!("[{}] Executing task: {}", self.config.name, task_id);
 
@@ -375,6 +684,12 @@ impl Worker {
 			None => "unknown_task".to_string(),
 		};
 
+		// Released on every exit path, including retried attempts and errors
+		let _running = match self.metrics {
+			Some(ref metrics) => Some(metrics.record_started(&task_id, &task_name).await),
+			None => None,
+		};
+
 		// Execute task with registry if available
 		let result: Result<(), Box<dyn std::error::Error + Send + Sync>> =
 			if let Some(ref registry) = self.registry {
@@ -386,6 +701,17 @@ impl Worker {
 							self.config.name, task_name
 						);
 
+						// Wait for a free slot if the task is rate limited
+						if let Some(limit) = registry
+							.options(serialized_task.name())
+							.await
+							.and_then(|options| options.rate_limit)
+						{
+							self.rate_limiter
+								.acquire(serialized_task.name(), &limit)
+								.await?;
+						}
+
 						// Deserialize task using registry to get concrete task instance
 						match registry
 							.create(serialized_task.name(), serialized_task.data())
@@ -438,14 +764,31 @@ impl Worker {
 		let completed_at = Utc::now();
 		let duration_ms = (completed_at - started_at).num_milliseconds() as u64;
 
+		if let Some(ref metrics) = self.metrics {
+			let duration = (completed_at - started_at).to_std().unwrap_or_default();
+			match &result {
+				Ok(_) => {
+					metrics
+						.record_succeeded(&task_id, &task_name, duration)
+						.await
+				}
+				Err(_) if will_retry => metrics.record_retried(&task_name).await,
+				Err(_) => metrics.record_failed(&task_id, &task_name, duration).await,
+			}
+		}
+
 		// Determine final task status
 		let (task_status, webhook_status) = match &result {
 			Ok(_) => (TaskStatus::Success, crate::webhook::TaskStatus::Success),
+			Err(_) if will_retry => (TaskStatus::Retry, crate::webhook::TaskStatus::Retry),
 			Err(_) => (TaskStatus::Failure, crate::webhook::TaskStatus::Failed),
 		};
 
-		// Store result if result backend is available
-		if let Some(ref result_backend) = self.result_backend {
+		// Store result if result backend is available; retried attempts are
+		// not final
+		if let Some(ref result_backend) = self.result_backend
+			&& task_status != TaskStatus::Retry
+		{
 			let metadata = match result {
 				Ok(_) => TaskResultMetadata::new(
 					task_id,
@@ -470,14 +813,17 @@ impl Worker {
 					crate::webhook::TaskStatus::Success => {
 						Some("Task completed successfully".to_string())
 					}
-					crate::webhook::TaskStatus::Failed => None,
-					crate::webhook::TaskStatus::Cancelled => None,
+					crate::webhook::TaskStatus::Failed
+					| crate::webhook::TaskStatus::Retry
+					| crate::webhook::TaskStatus::Cancelled => None,
 				},
 				error: match webhook_status {
-					crate::webhook::TaskStatus::Failed => match &result {
-						Err(e) => Some(e.to_string()),
-						_ => Some("Unknown error".to_string()),
-					},
+					crate::webhook::TaskStatus::Failed | crate::webhook::TaskStatus::Retry => {
+						match &result {
+							Err(e) => Some(e.to_string()),
+							_ => Some("Unknown error".to_string()),
+						}
+					}
 					_ => None,
 				},
 				started_at,
@@ -522,6 +868,69 @@ impl Worker {
 	pub async fn stop(&self) {
 		let _ = self.shutdown_tx.send(());
 	}
+
+	/// Stop the worker when the process receives SIGTERM or Ctrl+C
+	///
+	/// Spawns a task that waits for the signal and then behaves like
+	/// [`stop`](Self::stop): the worker stops fetching tasks and gives
+	/// in-flight tasks [`WorkerConfig::shutdown_grace_period`] to finish.
+	///
+	/// # Examples
+	///
+	/// ```rust,no_run
+	/// use reinhardt_tasks::{DummyBackend, Worker, WorkerConfig};
+	/// use std::sync::Arc;
+	///
+	/// # async fn example() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+	/// let worker = Worker::new(WorkerConfig::default());
+	/// worker.stop_on_signal();
+	///
+	/// worker.run(Arc::new(DummyBackend::new())).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn stop_on_signal(&self) -> tokio::task::JoinHandle<()> {
+		let shutdown_tx = self.shutdown_tx.clone();
+		let name = self.config.name.clone();
+		tokio::spawn(async move {
+			shutdown_signal().await;
+			println!("[{}] Termination signal received", name);
+			let _ = shutdown_tx.send(());
+		})
+	}
+}
+
+/// Wait for SIGTERM or Ctrl+C
+async fn shutdown_signal() {
+	use tokio::signal;
+
+	let ctrl_c = async {
+		if let Err(e) = signal::ctrl_c().await {
+			eprintln!("Failed to listen for Ctrl+C: {}", e);
+			std::future::pending::<()>().await;
+		}
+	};
+
+	#[cfg(unix)]
+	let terminate = async {
+		match signal::unix::signal(signal::unix::SignalKind::terminate()) {
+			Ok(mut sigterm) => {
+				sigterm.recv().await;
+			}
+			Err(e) => {
+				eprintln!("Failed to listen for SIGTERM: {}", e);
+				std::future::pending::<()>().await;
+			}
+		}
+	};
+
+	#[cfg(not(unix))]
+	let terminate = std::future::pending::<()>();
+
+	tokio::select! {
+		_ = ctrl_c => {}
+		_ = terminate => {}
+	}
 }
 
 impl Default for Worker {
@@ -533,6 +942,9 @@ impl Default for Worker {
 			task_lock: None,
 			result_backend: None,
 			webhook_senders: Vec::new(),
+			metrics: None,
+			rate_limiter: RateLimiter::new(Arc::new(MemoryTaskLock::new())),
+			retry_strategy: None,
 		}
 	}
 }
@@ -592,6 +1004,9 @@ mod tests {
 			task_lock: None,
 			result_backend: None,
 			webhook_senders: Vec::new(),
+			metrics: None,
+			rate_limiter: RateLimiter::new(Arc::new(MemoryTaskLock::new())),
+			retry_strategy: None,
 		};
 
 		let handle = tokio::spawn(async move { worker.run(backend).await });
@@ -636,4 +1051,41 @@ mod tests {
 
 		assert!(worker.result_backend.is_some());
 	}
+
+	#[tokio::test]
+	async fn test_worker_records_metrics() {
+		let metrics = Arc::new(TaskMetrics::new());
+		let worker = Worker::new(WorkerConfig::default()).with_metrics(Arc::clone(&metrics));
+		let backend: Arc<dyn TaskBackend> = Arc::new(DummyBackend::new());
+
+		worker
+			.execute_task(TaskId::new(), backend, false)
+			.await
+			.unwrap();
+
+		let stats = metrics.task_stats("unknown_task").await.unwrap();
+		assert_eq!(stats.started, 1);
+		assert_eq!(stats.succeeded, 1);
+		assert_eq!(stats.durations.count(), 1);
+		let snapshot = metrics.snapshot().await;
+		assert_eq!(snapshot.task_counts.successful, 1);
+		assert_eq!(snapshot.task_counts.running, 0);
+	}
+
+	#[tokio::test]
+	async fn test_worker_releases_running_gauge_on_retry() {
+		let metrics = Arc::new(TaskMetrics::new());
+		let worker = Worker::new(WorkerConfig::default())
+			.with_registry(Arc::new(TaskRegistry::new()))
+			.with_metrics(Arc::clone(&metrics));
+		let backend: Arc<dyn TaskBackend> = Arc::new(DummyBackend::new());
+
+		let result = worker.execute_task(TaskId::new(), backend, true).await;
+
+		assert!(result.is_err());
+		let stats = metrics.task_stats("unknown_task").await.unwrap();
+		assert_eq!(stats.started, 1);
+		assert_eq!(stats.retried, 1);
+		assert_eq!(metrics.snapshot().await.task_counts.running, 0);
+	}
 }
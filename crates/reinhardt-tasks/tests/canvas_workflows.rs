@@ -0,0 +1,276 @@
+//! Canvas workflow tests
+//!
+//! Tests chains with result piping, parallel groups, chords, and failure
+//! handling for workflows executed through a task registry.
+
+use async_trait::async_trait;
+use reinhardt_tasks::canvas::{CanvasExecutor, Signature, chain, chord, group};
+use reinhardt_tasks::{
+	MemoryResultBackend, ResultBackend, Task, TaskError, TaskExecutor, TaskFactory, TaskId,
+	TaskRegistry, TaskResult, TaskStatus,
+};
+use serde_json::{Value, json};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Barrier;
+
+/// Arithmetic task configured from its JSON arguments
+struct MathTask {
+	id: TaskId,
+	op: String,
+	args: Value,
+	barrier: Option<Arc<Barrier>>,
+}
+
+impl Task for MathTask {
+	fn id(&self) -> TaskId {
+		self.id
+	}
+
+	fn name(&self) -> &str {
+		&self.op
+	}
+}
+
+#[async_trait]
+impl TaskExecutor for MathTask {
+	async fn execute(&self) -> TaskResult<()> {
+		self.execute_with_result().await.map(|_| ())
+	}
+
+	async fn execute_with_result(&self) -> TaskResult<Option<Value>> {
+		let parent = &self.args["parent_result"];
+		let value = match self.op.as_str() {
+			"number" => self.args["value"].clone(),
+			"add" => json!(parent.as_i64().unwrap_or(0) + self.args["n"].as_i64().unwrap()),
+			"sum" => json!(
+				parent
+					.as_array()
+					.unwrap()
+					.iter()
+					.map(|v| v.as_i64().unwrap())
+					.sum::<i64>()
+			),
+			"fail" => return Err(TaskError::ExecutionFailed("boom".to_string())),
+			"wait" => {
+				self.barrier.as_ref().unwrap().wait().await;
+				json!(true)
+			}
+			other => panic!("unknown op {}", other),
+		};
+		Ok(Some(value))
+	}
+}
+
+struct MathFactory {
+	op: &'static str,
+	barrier: Option<Arc<Barrier>>,
+	calls: Arc<Mutex<Vec<Value>>>,
+}
+
+#[async_trait]
+impl TaskFactory for MathFactory {
+	async fn create(&self, data: &str) -> TaskResult<Box<dyn TaskExecutor>> {
+		let args: Value =
+			serde_json::from_str(data).map_err(|e| TaskError::SerializationError(e.to_string()))?;
+		self.calls.lock().unwrap().push(args.clone());
+		Ok(Box::new(MathTask {
+			id: TaskId::new(),
+			op: self.op.to_string(),
+			args,
+			barrier: self.barrier.clone(),
+		}))
+	}
+}
+
+struct Fixture {
+	executor: CanvasExecutor,
+	calls: Arc<Mutex<Vec<Value>>>,
+}
+
+async fn fixture(barrier: Option<Arc<Barrier>>) -> Fixture {
+	let registry = Arc::new(TaskRegistry::new());
+	let calls = Arc::new(Mutex::new(Vec::new()));
+	for op in ["number", "add", "sum", "fail", "wait"] {
+		registry
+			.register(
+				op.to_string(),
+				Arc::new(MathFactory {
+					op,
+					barrier: barrier.clone(),
+					calls: Arc::clone(&calls),
+				}),
+			)
+			.await;
+	}
+	Fixture {
+		executor: CanvasExecutor::new(registry, Arc::new(MemoryResultBackend::new())),
+		calls,
+	}
+}
+
+fn number(value: i64) -> Signature {
+	Signature::new("number", json!({ "value": value }))
+}
+
+fn add(n: i64) -> Signature {
+	Signature::new("add", json!({ "n": n }))
+}
+
+/// Test: each chain step receives the previous result
+#[tokio::test]
+async fn test_chain_pipes_results() {
+	let fx = fixture(None).await;
+
+	let result = fx
+		.executor
+		.apply(&chain([number(1).into(), add(2).into(), add(3).into()]))
+		.await
+		.unwrap();
+
+	assert_eq!(result, json!(6));
+	let calls = fx.calls.lock().unwrap();
+	assert_eq!(calls[1], json!({ "n": 2, "parent_result": 1 }));
+	assert_eq!(calls[2], json!({ "n": 3, "parent_result": 3 }));
+}
+
+/// Test: immutable signatures ignore the previous result
+#[tokio::test]
+async fn test_chain_immutable_signature() {
+	let fx = fixture(None).await;
+
+	let result = fx
+		.executor
+		.apply(&chain([number(10).into(), add(1).immutable().into()]))
+		.await
+		.unwrap();
+
+	assert_eq!(result, json!(1));
+}
+
+/// Test: group members run concurrently and results keep member order
+#[tokio::test]
+async fn test_group_runs_in_parallel() {
+	let barrier = Arc::new(Barrier::new(3));
+	let fx = fixture(Some(barrier)).await;
+	let wait = || Signature::new("wait", json!({})).into();
+
+	let result = tokio::time::timeout(
+		Duration::from_secs(5),
+		fx.executor.apply(&group([wait(), wait(), wait()])),
+	)
+	.await
+	.expect("group members should run concurrently")
+	.unwrap();
+
+	assert_eq!(result, json!([true, true, true]));
+}
+
+/// Test: the chord callback receives all header results in order
+#[tokio::test]
+async fn test_chord_collects_results() {
+	let fx = fixture(None).await;
+
+	let workflow = chord(
+		[
+			number(1).into(),
+			chain([number(2).into(), add(10).into()]),
+			number(3).into(),
+		],
+		Signature::new("sum", json!({})),
+	);
+	let result = fx.executor.apply(&workflow).await.unwrap();
+
+	assert_eq!(result, json!(16));
+	let calls = fx.calls.lock().unwrap();
+	assert_eq!(
+		calls.last().unwrap(),
+		&json!({ "parent_result": [1, 12, 3] })
+	);
+}
+
+/// Test: a chain can continue from a group's results
+#[tokio::test]
+async fn test_chain_after_group() {
+	let fx = fixture(None).await;
+
+	let workflow = chain([
+		number(5).into(),
+		group([add(1).into(), add(2).into()]),
+		Signature::new("sum", json!({})).into(),
+	]);
+	let result = fx.executor.apply(&workflow).await.unwrap();
+
+	assert_eq!(result, json!(13));
+}
+
+/// Test: a failing header member skips the chord callback
+#[tokio::test]
+async fn test_chord_failure_skips_callback() {
+	let fx = fixture(None).await;
+
+	let workflow = chord(
+		[number(1).into(), Signature::new("fail", json!({})).into()],
+		Signature::new("sum", json!({})),
+	);
+	let error = fx.executor.apply(&workflow).await.unwrap_err();
+
+	assert!(error.to_string().contains("boom"));
+	let calls = fx.calls.lock().unwrap();
+	assert_eq!(calls.len(), 2);
+}
+
+/// Test: task results and failures are stored in the result backend
+#[tokio::test]
+async fn test_results_are_stored() {
+	let registry = Arc::new(TaskRegistry::new());
+	let ids = Arc::new(Mutex::new(Vec::new()));
+
+	struct RecordingFactory {
+		inner: MathFactory,
+		ids: Arc<Mutex<Vec<TaskId>>>,
+	}
+
+	#[async_trait]
+	impl TaskFactory for RecordingFactory {
+		async fn create(&self, data: &str) -> TaskResult<Box<dyn TaskExecutor>> {
+			let task = self.inner.create(data).await?;
+			self.ids.lock().unwrap().push(task.id());
+			Ok(task)
+		}
+	}
+
+	for op in ["number", "fail"] {
+		registry
+			.register(
+				op.to_string(),
+				Arc::new(RecordingFactory {
+					inner: MathFactory {
+						op,
+						barrier: None,
+						calls: Arc::default(),
+					},
+					ids: Arc::clone(&ids),
+				}),
+			)
+			.await;
+	}
+	let results = Arc::new(MemoryResultBackend::new());
+	let executor = CanvasExecutor::new(registry, Arc::clone(&results) as Arc<dyn ResultBackend>);
+
+	let outcome = executor
+		.apply(&chain([
+			number(7).into(),
+			Signature::new("fail", json!({})).into(),
+		]))
+		.await;
+
+	assert!(outcome.is_err());
+	let ids = ids.lock().unwrap().clone();
+	let first = results.get_result(ids[0]).await.unwrap().unwrap();
+	let second = results.get_result(ids[1]).await.unwrap().unwrap();
+	assert_eq!(first.status(), TaskStatus::Success);
+	assert_eq!(first.result(), Some("7"));
+	assert_eq!(second.status(), TaskStatus::Failure);
+	assert!(second.error().unwrap().contains("boom"));
+}
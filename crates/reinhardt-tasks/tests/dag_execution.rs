@@ -3,7 +3,64 @@
 //! Tests DAG task dependencies, topological sorting, parallel execution,
 //! failure handling, and cycle detection.
 
-use reinhardt_tasks::{TaskDAG, TaskId, TaskNodeStatus};
+use async_trait::async_trait;
+use reinhardt_tasks::{
+	FailurePolicy, Task, TaskDAG, TaskError, TaskExecutor, TaskId, TaskNodeStatus, TaskResult,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Barrier;
+
+/// Test task whose behavior is configured per test
+struct StepTask {
+	id: TaskId,
+	after: Vec<TaskId>,
+	outcome: Outcome,
+}
+
+enum Outcome {
+	Succeed,
+	Fail,
+	Panic,
+	WaitFor(Arc<Barrier>),
+}
+
+fn step(after: &[TaskId], outcome: Outcome) -> Arc<dyn TaskExecutor> {
+	Arc::new(StepTask {
+		id: TaskId::new(),
+		after: after.to_vec(),
+		outcome,
+	})
+}
+
+impl Task for StepTask {
+	fn id(&self) -> TaskId {
+		self.id
+	}
+
+	fn name(&self) -> &str {
+		"step"
+	}
+
+	fn dependencies(&self) -> Vec<TaskId> {
+		self.after.clone()
+	}
+}
+
+#[async_trait]
+impl TaskExecutor for StepTask {
+	async fn execute(&self) -> TaskResult<()> {
+		match &self.outcome {
+			Outcome::Succeed => Ok(()),
+			Outcome::Fail => Err(TaskError::ExecutionFailed("step failed".to_string())),
+			Outcome::Panic => panic!("step panicked"),
+			Outcome::WaitFor(barrier) => {
+				barrier.wait().await;
+				Ok(())
+			}
+		}
+	}
+}
 
 /// Test: Basic DAG creation and task addition
 #[test]
@@ -253,3 +310,94 @@ fn test_parallel_execution_readiness() {
 	assert!(ready.contains(&task_c));
 	assert!(ready.contains(&task_d));
 }
+
+/// Test: Independent branches of a diamond run concurrently
+#[tokio::test]
+async fn test_execute_runs_independent_branches_concurrently() {
+	// Both branches must be running at the same time to pass the barrier
+	let barrier = Arc::new(Barrier::new(2));
+	let a = step(&[], Outcome::Succeed);
+	let b = step(&[a.id()], Outcome::WaitFor(Arc::clone(&barrier)));
+	let c = step(&[a.id()], Outcome::WaitFor(barrier));
+	let d = step(&[b.id(), c.id()], Outcome::Succeed);
+	let tasks = vec![a.clone(), b, c, d.clone()];
+	let mut dag = TaskDAG::from_tasks(&tasks).unwrap();
+
+	let report = tokio::time::timeout(Duration::from_secs(5), dag.execute(&tasks))
+		.await
+		.expect("branches did not run concurrently")
+		.unwrap();
+
+	assert!(report.is_success());
+	assert_eq!(report.completed.len(), 4);
+	assert_eq!(report.completed.first(), Some(&a.id()));
+	assert_eq!(report.completed.last(), Some(&d.id()));
+}
+
+/// Test: SkipDependents skips downstream tasks but runs unrelated branches
+#[tokio::test]
+async fn test_execute_skip_dependents_policy() {
+	let a = step(&[], Outcome::Fail);
+	let b = step(&[a.id()], Outcome::Succeed);
+	let c = step(&[b.id()], Outcome::Succeed);
+	let unrelated = step(&[], Outcome::Succeed);
+	let tasks = vec![a.clone(), b.clone(), c.clone(), unrelated.clone()];
+	let mut dag = TaskDAG::from_tasks(&tasks).unwrap();
+
+	let report = dag.execute(&tasks).await.unwrap();
+
+	assert!(!report.is_success());
+	assert_eq!(report.completed, vec![unrelated.id()]);
+	assert!(report.failed.contains_key(&a.id()));
+	assert_eq!(report.skipped, vec![b.id(), c.id()]);
+	assert_eq!(
+		dag.get_task(c.id()).unwrap().status(),
+		TaskNodeStatus::Skipped
+	);
+}
+
+/// Test: FailFast skips every task that has not started
+#[tokio::test]
+async fn test_execute_fail_fast_policy() {
+	let a = step(&[], Outcome::Fail);
+	let b = step(&[a.id()], Outcome::Succeed);
+	let tasks = vec![a.clone(), b.clone()];
+	let mut dag = TaskDAG::from_tasks(&tasks)
+		.unwrap()
+		.with_failure_policy(FailurePolicy::FailFast);
+
+	let report = dag.execute(&tasks).await.unwrap();
+
+	assert!(report.completed.is_empty());
+	assert_eq!(report.failed.len(), 1);
+	assert_eq!(report.skipped, vec![b.id()]);
+}
+
+/// Test: ContinueDependents runs dependents of failed tasks
+#[tokio::test]
+async fn test_execute_continue_dependents_policy() {
+	let a = step(&[], Outcome::Panic);
+	let b = step(&[a.id()], Outcome::Succeed);
+	let tasks = vec![a.clone(), b.clone()];
+	let mut dag = TaskDAG::from_tasks(&tasks)
+		.unwrap()
+		.with_failure_policy(FailurePolicy::ContinueDependents);
+
+	let report = dag.execute(&tasks).await.unwrap();
+
+	assert_eq!(report.completed, vec![b.id()]);
+	assert!(report.failed[&a.id()].contains("panicked"));
+	assert!(report.skipped.is_empty());
+}
+
+/// Test: Executing a DAG without an executor for each task fails
+#[tokio::test]
+async fn test_execute_requires_executor_for_every_task() {
+	let a = step(&[], Outcome::Succeed);
+	let mut dag = TaskDAG::from_tasks(std::slice::from_ref(&a)).unwrap();
+	dag.add_task(TaskId::new()).unwrap();
+
+	let result = dag.execute(&[a]).await;
+
+	assert!(matches!(result, Err(TaskError::TaskNotFound(_))));
+}
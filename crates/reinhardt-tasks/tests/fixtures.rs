@@ -0,0 +1,172 @@
+//! Shared test fixtures for reinhardt-tasks tests
+//!
+//! Provides an in-memory [`TaskBackend`] that worker, scheduler and queue
+//! tests can seed with tasks and inspect afterwards.
+
+// Allow dead code in test fixtures module: not every test file uses every
+// helper of the shared backend.
+#![allow(dead_code)]
+// Allow unreachable_pub: items are accessed by other test files through
+// mod fixtures.
+#![allow(unreachable_pub)]
+
+use async_trait::async_trait;
+use reinhardt_tasks::{SerializedTask, Task, TaskBackend, TaskExecutionError, TaskId, TaskStatus};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// In-memory FIFO backend recording every status change
+///
+/// Tasks are seeded with [`MemoryBackend::push`] and dequeued in order.
+/// Tasks submitted through [`TaskBackend::enqueue`] are queued too, with
+/// empty task data, and their names are available from
+/// [`MemoryBackend::enqueued`].
+pub struct MemoryBackend {
+	pending: Mutex<VecDeque<TaskId>>,
+	data: Mutex<HashMap<TaskId, SerializedTask>>,
+	history: Mutex<HashMap<TaskId, Vec<TaskStatus>>>,
+	enqueued: Mutex<Vec<String>>,
+	supports_requeue: bool,
+}
+
+impl Default for MemoryBackend {
+	fn default() -> Self {
+		Self {
+			pending: Mutex::new(VecDeque::new()),
+			data: Mutex::new(HashMap::new()),
+			history: Mutex::new(HashMap::new()),
+			enqueued: Mutex::new(Vec::new()),
+			supports_requeue: true,
+		}
+	}
+}
+
+impl MemoryBackend {
+	/// Create an empty backend
+	pub fn new() -> Arc<Self> {
+		Arc::new(Self::default())
+	}
+
+	/// Create a backend holding `count` `name` tasks with the same data
+	pub fn with_tasks(name: &str, data: &str, count: usize) -> Arc<Self> {
+		let backend = Self::new();
+		for _ in 0..count {
+			backend.push(name, data);
+		}
+		backend
+	}
+
+	/// Create an empty backend whose `requeue` is unsupported
+	pub fn without_requeue() -> Arc<Self> {
+		Arc::new(Self {
+			supports_requeue: false,
+			..Self::default()
+		})
+	}
+
+	/// Queue a `name` task with the given data and return its id
+	pub fn push(&self, name: &str, data: &str) -> TaskId {
+		let task_id = TaskId::new();
+		self.data.lock().unwrap().insert(
+			task_id,
+			SerializedTask::new(name.to_string(), data.to_string()),
+		);
+		self.pending.lock().unwrap().push_back(task_id);
+		task_id
+	}
+
+	/// Ids of the tasks still waiting to be dequeued
+	pub fn pending(&self) -> Vec<TaskId> {
+		self.pending.lock().unwrap().iter().copied().collect()
+	}
+
+	/// Every status a task was set to, oldest first
+	pub fn history(&self, task_id: TaskId) -> Vec<TaskStatus> {
+		self.history
+			.lock()
+			.unwrap()
+			.get(&task_id)
+			.cloned()
+			.unwrap_or_default()
+	}
+
+	/// The last status a task was set to
+	pub fn status(&self, task_id: TaskId) -> Option<TaskStatus> {
+		self.history(task_id).last().copied()
+	}
+
+	/// Number of tasks that ended in success or failure
+	pub fn finished(&self) -> usize {
+		self.history
+			.lock()
+			.unwrap()
+			.values()
+			.filter(|history| {
+				matches!(
+					history.last(),
+					Some(TaskStatus::Success | TaskStatus::Failure)
+				)
+			})
+			.count()
+	}
+
+	/// Names of the tasks submitted through `enqueue`
+	pub fn enqueued(&self) -> Vec<String> {
+		self.enqueued.lock().unwrap().clone()
+	}
+}
+
+#[async_trait]
+impl TaskBackend for MemoryBackend {
+	async fn enqueue(&self, task: Box<dyn Task>) -> Result<TaskId, TaskExecutionError> {
+		self.enqueued.lock().unwrap().push(task.name().to_string());
+		self.data.lock().unwrap().insert(
+			task.id(),
+			SerializedTask::new(task.name().to_string(), "{}".to_string()),
+		);
+		self.pending.lock().unwrap().push_back(task.id());
+		Ok(task.id())
+	}
+
+	async fn dequeue(&self) -> Result<Option<TaskId>, TaskExecutionError> {
+		Ok(self.pending.lock().unwrap().pop_front())
+	}
+
+	async fn get_status(&self, task_id: TaskId) -> Result<TaskStatus, TaskExecutionError> {
+		Ok(self.status(task_id).unwrap_or(TaskStatus::Pending))
+	}
+
+	async fn update_status(
+		&self,
+		task_id: TaskId,
+		status: TaskStatus,
+	) -> Result<(), TaskExecutionError> {
+		self.history
+			.lock()
+			.unwrap()
+			.entry(task_id)
+			.or_default()
+			.push(status);
+		Ok(())
+	}
+
+	async fn get_task_data(
+		&self,
+		task_id: TaskId,
+	) -> Result<Option<SerializedTask>, TaskExecutionError> {
+		Ok(self.data.lock().unwrap().get(&task_id).cloned())
+	}
+
+	async fn requeue(&self, task_id: TaskId) -> Result<bool, TaskExecutionError> {
+		if !self.supports_requeue {
+			return Ok(false);
+		}
+		self.update_status(task_id, TaskStatus::Pending).await?;
+		self.pending.lock().unwrap().push_back(task_id);
+		Ok(true)
+	}
+
+	fn backend_name(&self) -> &str {
+		"memory"
+	}
+}
@@ -0,0 +1,171 @@
+//! Worker graceful shutdown tests
+//!
+//! Tests that stopping a worker lets in-flight tasks finish within the grace
+//! period, and re-enqueues or fails tasks that are still running after it.
+
+mod fixtures;
+
+use async_trait::async_trait;
+use fixtures::MemoryBackend;
+use reinhardt_tasks::{
+	MemoryResultBackend, QueueSpec, ResultBackend, Task, TaskBackend, TaskExecutor, TaskFactory,
+	TaskId, TaskRegistry, TaskResult, TaskStatus, WeightedQueueSet, WeightingStrategy, Worker,
+	WorkerConfig,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Backend holding one `sleep` task per duration, in milliseconds
+fn sleep_backend(durations: &[u64], supports_requeue: bool) -> (Arc<MemoryBackend>, Vec<TaskId>) {
+	let backend = if supports_requeue {
+		MemoryBackend::new()
+	} else {
+		MemoryBackend::without_requeue()
+	};
+	let ids = durations
+		.iter()
+		.map(|ms| backend.push("sleep", &ms.to_string()))
+		.collect();
+	(backend, ids)
+}
+
+struct SleepTask {
+	duration: Duration,
+	started: Arc<AtomicUsize>,
+}
+
+impl Task for SleepTask {
+	fn id(&self) -> TaskId {
+		TaskId::new()
+	}
+
+	fn name(&self) -> &str {
+		"sleep"
+	}
+}
+
+#[async_trait]
+impl TaskExecutor for SleepTask {
+	async fn execute(&self) -> TaskResult<()> {
+		self.started.fetch_add(1, Ordering::SeqCst);
+		tokio::time::sleep(self.duration).await;
+		Ok(())
+	}
+}
+
+struct SleepFactory(Arc<AtomicUsize>);
+
+#[async_trait]
+impl TaskFactory for SleepFactory {
+	async fn create(&self, data: &str) -> TaskResult<Box<dyn TaskExecutor>> {
+		Ok(Box::new(SleepTask {
+			duration: Duration::from_millis(data.parse().unwrap()),
+			started: Arc::clone(&self.0),
+		}))
+	}
+}
+
+async fn worker(grace_period: Duration, started: &Arc<AtomicUsize>) -> Worker {
+	let registry = Arc::new(TaskRegistry::new());
+	registry
+		.register(
+			"sleep".to_string(),
+			Arc::new(SleepFactory(Arc::clone(started))),
+		)
+		.await;
+	Worker::new(
+		WorkerConfig::new("shutdown".to_string())
+			.with_concurrency(2)
+			.with_poll_interval(Duration::from_millis(5))
+			.with_shutdown_grace_period(grace_period),
+	)
+	.with_registry(registry)
+}
+
+async fn wait_until_started(started: &AtomicUsize, count: usize) {
+	tokio::time::timeout(Duration::from_secs(5), async {
+		while started.load(Ordering::SeqCst) < count {
+			tokio::time::sleep(Duration::from_millis(5)).await;
+		}
+	})
+	.await
+	.expect("tasks should start");
+}
+
+/// Test: a task running at shutdown finishes within the grace period and no
+/// new task is fetched
+#[tokio::test]
+async fn test_in_flight_task_finishes_within_grace_period() {
+	let started = Arc::new(AtomicUsize::new(0));
+	let (backend, ids) = sleep_backend(&[200, 10], true);
+	let worker = Arc::new(worker(Duration::from_secs(5), &started).await);
+	let handle = {
+		let worker = Arc::clone(&worker);
+		let backend = Arc::clone(&backend) as Arc<dyn TaskBackend>;
+		tokio::spawn(async move { worker.run(backend).await.unwrap() })
+	};
+
+	wait_until_started(&started, 1).await;
+	worker.stop().await;
+	handle.await.unwrap();
+
+	assert_eq!(backend.status(ids[0]), Some(TaskStatus::Success));
+	assert_eq!(backend.pending(), vec![ids[1]]);
+	assert_eq!(started.load(Ordering::SeqCst), 1);
+}
+
+/// Test: a task outliving the grace period is put back on the queue
+#[tokio::test]
+async fn test_task_past_grace_period_is_requeued() {
+	let started = Arc::new(AtomicUsize::new(0));
+	let (backend, ids) = sleep_backend(&[10_000], true);
+	let worker = Arc::new(worker(Duration::from_millis(50), &started).await);
+	let handle = {
+		let worker = Arc::clone(&worker);
+		let backend = Arc::clone(&backend) as Arc<dyn TaskBackend>;
+		tokio::spawn(async move { worker.run(backend).await.unwrap() })
+	};
+
+	wait_until_started(&started, 1).await;
+	worker.stop().await;
+	tokio::time::timeout(Duration::from_secs(2), handle)
+		.await
+		.expect("worker should stop after the grace period")
+		.unwrap();
+
+	assert_eq!(backend.status(ids[0]), Some(TaskStatus::Pending));
+	assert_eq!(backend.pending(), vec![ids[0]]);
+}
+
+/// Test: queue workers finish short tasks and fail long ones when the backend
+/// cannot re-enqueue
+#[tokio::test]
+async fn test_queue_worker_marks_unrequeueable_tasks_failed() {
+	let started = Arc::new(AtomicUsize::new(0));
+	let (backend, ids) = sleep_backend(&[10_000, 100], false);
+	let results = Arc::new(MemoryResultBackend::new());
+	let worker = Arc::new(
+		worker(Duration::from_millis(300), &started)
+			.await
+			.with_result_backend(Arc::clone(&results) as Arc<dyn ResultBackend>),
+	);
+	let queues = WeightedQueueSet::new(WeightingStrategy::RoundRobin).with_queue(QueueSpec::new(
+		"default",
+		Arc::clone(&backend) as Arc<dyn TaskBackend>,
+	));
+	let handle = tokio::spawn(Arc::clone(&worker).run_queues(Arc::new(queues)));
+
+	wait_until_started(&started, 2).await;
+	worker.stop().await;
+	tokio::time::timeout(Duration::from_secs(2), handle)
+		.await
+		.expect("worker should stop after the grace period")
+		.unwrap()
+		.unwrap();
+
+	assert_eq!(backend.status(ids[0]), Some(TaskStatus::Failure));
+	assert_eq!(backend.status(ids[1]), Some(TaskStatus::Success));
+	let interrupted = results.get_result(ids[0]).await.unwrap().unwrap();
+	assert!(interrupted.error().unwrap().contains("interrupted"));
+}
@@ -62,25 +62,22 @@ fn test_queue_config_clone() {
 /// Test: TaskQueue creation
 #[test]
 fn test_task_queue_new() {
-	let queue = TaskQueue::new();
 	// Queue creation should succeed (no panic)
-	assert_eq!(std::mem::size_of_val(&queue), 0); // TaskQueue is a unit struct
+	let _queue = TaskQueue::new();
 }
 
 /// Test: TaskQueue with_config
 #[test]
 fn test_task_queue_with_config() {
 	let config = QueueConfig::new("custom_queue".to_string());
-	let queue = TaskQueue::with_config(config);
 	// Queue creation with config should succeed
-	assert_eq!(std::mem::size_of_val(&queue), 0);
+	let _queue = TaskQueue::with_config(config);
 }
 
 /// Test: TaskQueue default
 #[test]
 fn test_task_queue_default() {
-	let queue = TaskQueue;
-	assert_eq!(std::mem::size_of_val(&queue), 0);
+	let _queue = TaskQueue::default();
 }
 
 /// Test: TaskQueue enqueue returns valid TaskId
@@ -0,0 +1,122 @@
+//! Per-task rate limiting tests
+//!
+//! Tests that workers sharing a task lock respect the rate limit a task was
+//! registered with.
+
+mod fixtures;
+
+use async_trait::async_trait;
+use fixtures::MemoryBackend;
+use reinhardt_tasks::{
+	MemoryTaskLock, Task, TaskBackend, TaskExecutor, TaskFactory, TaskId, TaskOptions,
+	TaskRegistry, TaskResult, Worker, WorkerConfig,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Records the second (rate limit window) each execution started in
+struct ApiCall {
+	starts: Arc<Mutex<Vec<u64>>>,
+}
+
+impl Task for ApiCall {
+	fn id(&self) -> TaskId {
+		TaskId::new()
+	}
+
+	fn name(&self) -> &str {
+		"call_api"
+	}
+}
+
+#[async_trait]
+impl TaskExecutor for ApiCall {
+	async fn execute(&self) -> TaskResult<()> {
+		let second = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.unwrap()
+			.as_secs();
+		self.starts.lock().unwrap().push(second);
+		Ok(())
+	}
+}
+
+struct ApiCallFactory {
+	starts: Arc<Mutex<Vec<u64>>>,
+}
+
+#[async_trait]
+impl TaskFactory for ApiCallFactory {
+	async fn create(&self, _data: &str) -> TaskResult<Box<dyn TaskExecutor>> {
+		Ok(Box::new(ApiCall {
+			starts: Arc::clone(&self.starts),
+		}))
+	}
+}
+
+/// Test: two workers sharing a lock run at most `limit` tasks per window
+#[tokio::test]
+async fn test_rate_limit_is_shared_between_workers() {
+	let starts = Arc::new(Mutex::new(Vec::new()));
+	let registry = Arc::new(TaskRegistry::new());
+	registry
+		.register_with_options(
+			"call_api".to_string(),
+			Arc::new(ApiCallFactory {
+				starts: Arc::clone(&starts),
+			}),
+			TaskOptions::new().rate_limit("2/s").unwrap(),
+		)
+		.await;
+	let backend = MemoryBackend::with_tasks("call_api", "{}", 5);
+	let lock = Arc::new(MemoryTaskLock::new());
+
+	let workers: Vec<_> = (0..2)
+		.map(|i| {
+			Arc::new(
+				Worker::new(
+					WorkerConfig::new(format!("worker-{}", i))
+						.with_poll_interval(Duration::from_millis(5)),
+				)
+				.with_registry(Arc::clone(&registry))
+				.with_lock(Arc::clone(&lock) as _),
+			)
+		})
+		.collect();
+	let handles: Vec<_> = workers
+		.iter()
+		.map(|worker| {
+			let worker = Arc::clone(worker);
+			let backend = Arc::clone(&backend) as Arc<dyn TaskBackend>;
+			tokio::spawn(async move { worker.run(backend).await.unwrap() })
+		})
+		.collect();
+
+	tokio::time::timeout(Duration::from_secs(10), async {
+		while backend.finished() < 5 {
+			tokio::time::sleep(Duration::from_millis(20)).await;
+		}
+	})
+	.await
+	.expect("all tasks should eventually run");
+	for worker in &workers {
+		worker.stop().await;
+	}
+	for handle in handles {
+		handle.await.unwrap();
+	}
+
+	let starts = starts.lock().unwrap();
+	assert_eq!(starts.len(), 5);
+	let mut per_window: HashMap<u64, usize> = HashMap::new();
+	for second in starts.iter() {
+		*per_window.entry(*second).or_default() += 1;
+	}
+	assert!(
+		per_window.values().all(|count| *count <= 2),
+		"executions per second: {:?}",
+		per_window
+	);
+	assert!(per_window.len() >= 3);
+}
@@ -244,3 +244,39 @@ async fn test_empty_queue_dequeue(
 	let result = backend.dequeue().await.unwrap();
 	assert_eq!(result, None);
 }
+
+/// Test: Redis schedule store persists schedules and grants exclusive leader locks
+#[rstest]
+#[tokio::test]
+async fn test_redis_schedule_store(
+	#[future] redis_container: (ContainerAsync<GenericImage>, u16, String),
+) {
+	use reinhardt_tasks::{PersistentSchedule, RedisScheduleStore, ScheduleKind, ScheduleStore};
+	use std::time::Duration;
+
+	let (_container, _port, url) = redis_container.await;
+	let store = RedisScheduleStore::new(&url).await.unwrap();
+	let ttl = Duration::from_secs(30);
+
+	store
+		.save(PersistentSchedule::new(
+			"report",
+			"report_task",
+			ScheduleKind::Interval { seconds: 60 },
+		))
+		.await
+		.unwrap();
+	let at = chrono::Utc::now();
+	store.mark_run("report", at).await.unwrap();
+
+	let reopened = RedisScheduleStore::new(&url).await.unwrap();
+	assert_eq!(
+		reopened.get("report").await.unwrap().unwrap().last_run_at,
+		Some(at)
+	);
+	assert!(store.try_lock("report", "a", ttl).await.unwrap());
+	assert!(!reopened.try_lock("report", "b", ttl).await.unwrap());
+	store.unlock("report", "a").await.unwrap();
+	assert!(reopened.try_lock("report", "b", ttl).await.unwrap());
+	assert!(store.remove("report").await.unwrap());
+}
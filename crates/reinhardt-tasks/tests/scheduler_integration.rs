@@ -2,10 +2,14 @@
 //!
 //! Tests CronSchedule, Schedule trait, and Scheduler implementations.
 
+mod fixtures;
+
 use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
+use fixtures::MemoryBackend;
 use reinhardt_tasks::{
-	Task, TaskExecutor, TaskId, TaskResult,
+	MemoryScheduleStore, PersistentSchedule, ScheduleKind, ScheduleStore, Task, TaskExecutor,
+	TaskId, TaskResult,
 	scheduler::{CronSchedule, Schedule, Scheduler},
 };
 use std::sync::{Arc, Mutex};
@@ -100,11 +104,9 @@ fn test_cron_schedule_as_trait() {
 #[test]
 fn test_scheduler_new() {
 	let scheduler = Scheduler::new();
-	// Scheduler creation should succeed (no panic)
-	assert_eq!(
-		std::mem::size_of_val(&scheduler),
-		std::mem::size_of::<Vec<()>>()
-	);
+	// Scheduler creation should succeed without a persistent store
+	assert!(scheduler.store().is_none());
+	assert!(scheduler.instance_id().is_none());
 }
 
 /// Test: Scheduler add_task
@@ -202,3 +204,150 @@ fn test_cron_schedule_edge_cases() {
 	let schedule = CronSchedule::new("invalid cron expression".to_string());
 	assert!(schedule.next_run().is_none());
 }
+
+/// Interval schedule that became due a while ago
+fn overdue_schedule(name: &str) -> PersistentSchedule {
+	let mut schedule = PersistentSchedule::new(
+		name,
+		format!("{}_task", name),
+		ScheduleKind::Interval { seconds: 60 },
+	);
+	schedule.created_at -= Duration::minutes(5);
+	schedule
+}
+
+/// Test: Only one of several schedulers sharing a store enqueues a run
+#[tokio::test]
+async fn test_persistent_schedule_enqueued_once_across_instances() {
+	let store: Arc<dyn ScheduleStore> = Arc::new(MemoryScheduleStore::new());
+	let backend = MemoryBackend::new();
+	let beats: Vec<Scheduler> = (0..4)
+		.map(|i| {
+			Scheduler::new()
+				.with_store(Arc::clone(&store), backend.clone())
+				.with_instance_id(format!("beat-{}", i))
+		})
+		.collect();
+	store.save(overdue_schedule("report")).await.unwrap();
+
+	let (a, b, c, d) = tokio::join!(
+		beats[0].tick(),
+		beats[1].tick(),
+		beats[2].tick(),
+		beats[3].tick()
+	);
+
+	let enqueued: usize = [a, b, c, d].into_iter().map(|r| r.unwrap().len()).sum();
+	assert_eq!(enqueued, 1);
+	assert_eq!(backend.enqueued(), vec!["report_task".to_string()]);
+	assert!(
+		store
+			.get("report")
+			.await
+			.unwrap()
+			.unwrap()
+			.last_run_at
+			.is_some()
+	);
+}
+
+/// Test: A schedule locked by another instance is skipped
+#[tokio::test]
+async fn test_persistent_schedule_respects_foreign_lock() {
+	let store: Arc<dyn ScheduleStore> = Arc::new(MemoryScheduleStore::new());
+	let backend = MemoryBackend::new();
+	let scheduler = Scheduler::new()
+		.with_store(Arc::clone(&store), backend.clone())
+		.with_instance_id("beat-1");
+	store.save(overdue_schedule("report")).await.unwrap();
+	store
+		.try_lock("report", "beat-2", std::time::Duration::from_secs(30))
+		.await
+		.unwrap();
+
+	let enqueued = scheduler.tick().await.unwrap();
+
+	assert!(enqueued.is_empty());
+	assert!(backend.enqueued().is_empty());
+}
+
+/// Test: Schedules can be added, disabled and removed at runtime
+#[tokio::test]
+async fn test_persistent_schedule_runtime_edits() {
+	let backend = MemoryBackend::new();
+	let scheduler =
+		Scheduler::new().with_store(Arc::new(MemoryScheduleStore::new()), backend.clone());
+
+	let mut schedule = overdue_schedule("sync");
+	schedule.enabled = false;
+	scheduler.save_schedule(schedule.clone()).await.unwrap();
+	assert!(scheduler.tick().await.unwrap().is_empty());
+
+	schedule.enabled = true;
+	scheduler.save_schedule(schedule).await.unwrap();
+	assert_eq!(scheduler.tick().await.unwrap().len(), 1);
+
+	assert!(scheduler.remove_schedule("sync").await.unwrap());
+	assert!(scheduler.store().unwrap().list().await.unwrap().is_empty());
+}
+
+/// Test: Persistent schedule operations require a configured store
+#[tokio::test]
+async fn test_scheduler_without_store_rejects_persistent_operations() {
+	let scheduler = Scheduler::new();
+
+	assert!(scheduler.tick().await.is_err());
+	assert!(
+		scheduler
+			.save_schedule(overdue_schedule("report"))
+			.await
+			.is_err()
+	);
+}
+
+/// Test: Schedules and their last run survive a restart of the SQLite store
+#[cfg(feature = "database-backend")]
+#[tokio::test]
+async fn test_sqlite_schedules_survive_restart() {
+	use reinhardt_tasks::SqliteScheduleStore;
+
+	let dir = tempfile::tempdir().unwrap();
+	let url = format!("sqlite://{}", dir.path().join("beat.db").display());
+	let backend = MemoryBackend::new();
+
+	{
+		let store = Arc::new(SqliteScheduleStore::new(&url).await.unwrap());
+		let scheduler = Scheduler::new().with_store(store, backend.clone());
+		scheduler
+			.save_schedule(overdue_schedule("report"))
+			.await
+			.unwrap();
+		assert_eq!(scheduler.tick().await.unwrap().len(), 1);
+	}
+
+	let store = Arc::new(SqliteScheduleStore::new(&url).await.unwrap());
+	let restored = store.get("report").await.unwrap().unwrap();
+	let scheduler = Scheduler::new().with_store(store, backend.clone());
+
+	assert!(restored.last_run_at.is_some());
+	assert!(scheduler.tick().await.unwrap().is_empty());
+	assert_eq!(backend.enqueued().len(), 1);
+}
+
+/// Test: SQLite leader lock is exclusive between owners
+#[cfg(feature = "database-backend")]
+#[tokio::test]
+async fn test_sqlite_schedule_lock_is_exclusive() {
+	use reinhardt_tasks::SqliteScheduleStore;
+
+	let dir = tempfile::tempdir().unwrap();
+	let url = format!("sqlite://{}", dir.path().join("beat.db").display());
+	let store = SqliteScheduleStore::new(&url).await.unwrap();
+	let ttl = std::time::Duration::from_secs(30);
+
+	assert!(store.try_lock("report", "a", ttl).await.unwrap());
+	assert!(store.try_lock("report", "a", ttl).await.unwrap());
+	assert!(!store.try_lock("report", "b", ttl).await.unwrap());
+	store.unlock("report", "a").await.unwrap();
+	assert!(store.try_lock("report", "b", ttl).await.unwrap());
+}
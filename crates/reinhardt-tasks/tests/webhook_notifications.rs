@@ -0,0 +1,210 @@
+//! Webhook notification tests
+//!
+//! Tests that workers send signed webhook notifications for retried, failed
+//! and successful task executions.
+
+mod fixtures;
+
+use async_trait::async_trait;
+use fixtures::MemoryBackend;
+use mockito::Matcher;
+use reinhardt_tasks::{
+	RetryStrategy, Task, TaskBackend, TaskError, TaskExecutor, TaskFactory, TaskId, TaskMetrics,
+	TaskRegistry, TaskResult, TaskStatus, WebhookConfig, WebhookTaskStatus, Worker, WorkerConfig,
+};
+use serde_json::json;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+/// Fails until it has been attempted `failures + 1` times
+struct FlakyTask {
+	attempts: Arc<AtomicU32>,
+	failures: u32,
+}
+
+impl Task for FlakyTask {
+	fn id(&self) -> TaskId {
+		TaskId::new()
+	}
+
+	fn name(&self) -> &str {
+		"flaky"
+	}
+}
+
+#[async_trait]
+impl TaskExecutor for FlakyTask {
+	async fn execute(&self) -> TaskResult<()> {
+		if self.attempts.fetch_add(1, Ordering::SeqCst) < self.failures {
+			return Err(TaskError::ExecutionFailed(
+				"upstream unavailable".to_string(),
+			));
+		}
+		Ok(())
+	}
+}
+
+struct FlakyFactory {
+	attempts: Arc<AtomicU32>,
+	failures: u32,
+}
+
+#[async_trait]
+impl TaskFactory for FlakyFactory {
+	async fn create(&self, _data: &str) -> TaskResult<Box<dyn TaskExecutor>> {
+		Ok(Box::new(FlakyTask {
+			attempts: Arc::clone(&self.attempts),
+			failures: self.failures,
+		}))
+	}
+}
+
+async fn run_until_finished(worker: Arc<Worker>, backend: Arc<MemoryBackend>, task_id: TaskId) {
+	let handle = {
+		let worker = Arc::clone(&worker);
+		let backend = Arc::clone(&backend) as Arc<dyn TaskBackend>;
+		tokio::spawn(async move { worker.run(backend).await.unwrap() })
+	};
+	tokio::time::timeout(Duration::from_secs(5), async {
+		while !matches!(
+			backend.history(task_id).last(),
+			Some(TaskStatus::Success | TaskStatus::Failure)
+		) {
+			tokio::time::sleep(Duration::from_millis(10)).await;
+		}
+	})
+	.await
+	.expect("task should finish");
+	worker.stop().await;
+	handle.await.unwrap();
+}
+
+async fn wait_for(mocks: &[&mockito::Mock]) {
+	tokio::time::timeout(Duration::from_secs(5), async {
+		loop {
+			let mut matched = true;
+			for mock in mocks {
+				matched &= mock.matched_async().await;
+			}
+			if matched {
+				break;
+			}
+			tokio::time::sleep(Duration::from_millis(10)).await;
+		}
+	})
+	.await
+	.expect("webhooks should be delivered");
+}
+
+async fn worker(
+	failures: u32,
+	max_retries: u32,
+	webhook: WebhookConfig,
+) -> (Arc<Worker>, Arc<TaskMetrics>) {
+	let registry = Arc::new(TaskRegistry::new());
+	let attempts = Arc::new(AtomicU32::new(0));
+	let metrics = Arc::new(TaskMetrics::new());
+	registry
+		.register(
+			"flaky".to_string(),
+			Arc::new(FlakyFactory { attempts, failures }),
+		)
+		.await;
+
+	let mut config =
+		WorkerConfig::new("webhooks".to_string()).with_poll_interval(Duration::from_millis(5));
+	config.webhook_configs.push(webhook);
+	let worker = Worker::new(config)
+		.with_registry(registry)
+		.with_metrics(Arc::clone(&metrics))
+		.with_retry_strategy(
+			RetryStrategy::fixed_delay(Duration::from_millis(10)).with_max_retries(max_retries),
+		);
+	(Arc::new(worker), metrics)
+}
+
+/// Test: retried attempts notify retry events before the final success
+#[tokio::test]
+async fn test_retry_events_precede_success() {
+	let mut server = mockito::Server::new_async().await;
+	let retry = server
+		.mock("POST", "/hooks")
+		.match_body(Matcher::PartialJson(json!({
+			"task_name": "flaky",
+			"status": "retry",
+			"error": "Task execution failed: upstream unavailable",
+		})))
+		.with_status(200)
+		.expect(2)
+		.create_async()
+		.await;
+	let success = server
+		.mock("POST", "/hooks")
+		.match_body(Matcher::PartialJson(json!({ "status": "success" })))
+		.with_status(200)
+		.expect(1)
+		.create_async()
+		.await;
+
+	let (worker, metrics) = worker(
+		2,
+		3,
+		WebhookConfig {
+			url: format!("{}/hooks", server.url()),
+			..Default::default()
+		},
+	)
+	.await;
+	let backend = MemoryBackend::new();
+	let task_id = backend.push("flaky", "{}");
+	run_until_finished(worker, Arc::clone(&backend), task_id).await;
+	wait_for(&[&retry, &success]).await;
+
+	retry.assert_async().await;
+	success.assert_async().await;
+	assert_eq!(
+		backend.history(task_id),
+		vec![TaskStatus::Retry, TaskStatus::Retry, TaskStatus::Success]
+	);
+	let stats = metrics.task_stats("flaky").await.unwrap();
+	assert_eq!(stats.retried, 2);
+	assert_eq!(stats.succeeded, 1);
+	assert_eq!(stats.failed, 0);
+}
+
+/// Test: a task out of retries sends one signed failure event
+#[tokio::test]
+async fn test_exhausted_retries_send_signed_failure() {
+	let mut server = mockito::Server::new_async().await;
+	let failed = server
+		.mock("POST", "/hooks")
+		.match_header("X-Webhook-Signature", Matcher::Regex("^sha256=".into()))
+		.match_body(Matcher::PartialJson(json!({ "status": "failed" })))
+		.with_status(200)
+		.expect(1)
+		.create_async()
+		.await;
+
+	let (worker, _) = worker(
+		5,
+		1,
+		WebhookConfig {
+			url: format!("{}/hooks", server.url()),
+			secret: Some("shared-secret".to_string()),
+			events: vec![WebhookTaskStatus::Failed],
+			..Default::default()
+		},
+	)
+	.await;
+	let backend = MemoryBackend::new();
+	let task_id = backend.push("flaky", "{}");
+	run_until_finished(worker, Arc::clone(&backend), task_id).await;
+	wait_for(&[&failed]).await;
+
+	failed.assert_async().await;
+	assert_eq!(
+		backend.history(task_id),
+		vec![TaskStatus::Retry, TaskStatus::Failure]
+	);
+}
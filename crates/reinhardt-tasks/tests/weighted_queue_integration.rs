@@ -0,0 +1,193 @@
+//! Weighted queue tests
+//!
+//! Tests polling order across several named queues, per-queue concurrency
+//! limits, and workers running tasks from a weighted queue set.
+
+mod fixtures;
+
+use async_trait::async_trait;
+use fixtures::MemoryBackend;
+use reinhardt_tasks::{
+	QueueSpec, Task, TaskBackend, TaskExecutor, TaskFactory, TaskId, TaskRegistry, TaskResult,
+	WeightedQueueSet, WeightingStrategy, Worker, WorkerConfig,
+};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Tracks how many tasks of each queue run at once
+#[derive(Default)]
+struct ConcurrencyTracker {
+	running: Mutex<HashMap<String, usize>>,
+	peak: Mutex<HashMap<String, usize>>,
+	total_peak: AtomicUsize,
+}
+
+struct TrackedTask {
+	id: TaskId,
+	queue: String,
+	tracker: Arc<ConcurrencyTracker>,
+}
+
+impl Task for TrackedTask {
+	fn id(&self) -> TaskId {
+		self.id
+	}
+
+	fn name(&self) -> &str {
+		"tracked"
+	}
+}
+
+#[async_trait]
+impl TaskExecutor for TrackedTask {
+	async fn execute(&self) -> TaskResult<()> {
+		{
+			let mut running = self.tracker.running.lock().unwrap();
+			let count = running.entry(self.queue.clone()).or_default();
+			*count += 1;
+			let mut peak = self.tracker.peak.lock().unwrap();
+			let queue_peak = peak.entry(self.queue.clone()).or_default();
+			*queue_peak = (*queue_peak).max(*count);
+			self.tracker
+				.total_peak
+				.fetch_max(running.values().sum(), Ordering::SeqCst);
+		}
+		tokio::time::sleep(Duration::from_millis(30)).await;
+		*self
+			.tracker
+			.running
+			.lock()
+			.unwrap()
+			.get_mut(&self.queue)
+			.unwrap() -= 1;
+		Ok(())
+	}
+}
+
+struct TrackedFactory(Arc<ConcurrencyTracker>);
+
+#[async_trait]
+impl TaskFactory for TrackedFactory {
+	async fn create(&self, data: &str) -> TaskResult<Box<dyn TaskExecutor>> {
+		Ok(Box::new(TrackedTask {
+			id: TaskId::new(),
+			queue: data.to_string(),
+			tracker: Arc::clone(&self.0),
+		}))
+	}
+}
+
+/// Test: the weighted strategy dequeues from queues in proportion to weight
+#[tokio::test]
+async fn test_weighted_dequeue_proportions() {
+	let high = MemoryBackend::with_tasks("tracked", "high", 100);
+	let low = MemoryBackend::with_tasks("tracked", "low", 100);
+	let queues = WeightedQueueSet::new(WeightingStrategy::Weighted)
+		.with_queue(QueueSpec::new("high", high).with_weight(4))
+		.with_queue(QueueSpec::new("low", low).with_weight(1));
+
+	let mut counts: HashMap<String, usize> = HashMap::new();
+	for _ in 0..50 {
+		let dequeued = queues.dequeue().await.unwrap().unwrap();
+		*counts.entry(dequeued.queue).or_default() += 1;
+	}
+
+	assert_eq!(counts["high"], 40);
+	assert_eq!(counts["low"], 10);
+}
+
+/// Test: empty queues fall through to the next queue
+#[tokio::test]
+async fn test_empty_preferred_queue_falls_back() {
+	let queues = WeightedQueueSet::new(WeightingStrategy::StrictPriority)
+		.with_queue(
+			QueueSpec::new("high", MemoryBackend::with_tasks("tracked", "high", 0)).with_weight(10),
+		)
+		.with_queue(QueueSpec::new(
+			"low",
+			MemoryBackend::with_tasks("tracked", "low", 1),
+		));
+
+	let dequeued = queues.dequeue().await.unwrap().unwrap();
+
+	assert_eq!(dequeued.queue, "low");
+	assert!(queues.dequeue().await.unwrap().is_none());
+}
+
+/// Test: a saturated queue is skipped until a permit is released
+#[tokio::test]
+async fn test_concurrency_limit_skips_saturated_queue() {
+	let queues = WeightedQueueSet::new(WeightingStrategy::StrictPriority)
+		.with_queue(
+			QueueSpec::new("bulk", MemoryBackend::with_tasks("tracked", "bulk", 5))
+				.with_weight(10)
+				.with_max_concurrency(1),
+		)
+		.with_queue(QueueSpec::new(
+			"interactive",
+			MemoryBackend::with_tasks("tracked", "interactive", 5),
+		));
+
+	let first = queues.dequeue().await.unwrap().unwrap();
+	let second = queues.dequeue().await.unwrap().unwrap();
+	assert_eq!(first.queue, "bulk");
+	assert_eq!(second.queue, "interactive");
+	assert_eq!(queues.in_flight("bulk"), Some(1));
+
+	drop(first);
+	let third = queues.dequeue().await.unwrap().unwrap();
+	assert_eq!(third.queue, "bulk");
+}
+
+/// Test: bulk jobs cannot occupy every worker slot
+#[tokio::test]
+async fn test_worker_respects_per_queue_limits() {
+	let tracker = Arc::new(ConcurrencyTracker::default());
+	let registry = Arc::new(TaskRegistry::new());
+	registry
+		.register(
+			"tracked".to_string(),
+			Arc::new(TrackedFactory(Arc::clone(&tracker))),
+		)
+		.await;
+
+	let bulk = MemoryBackend::with_tasks("tracked", "bulk", 12);
+	let interactive = MemoryBackend::with_tasks("tracked", "interactive", 4);
+	let queues = WeightedQueueSet::new(WeightingStrategy::Weighted)
+		.with_queue(
+			QueueSpec::new("bulk", Arc::clone(&bulk) as Arc<dyn TaskBackend>)
+				.with_weight(5)
+				.with_max_concurrency(2),
+		)
+		.with_queue(QueueSpec::new(
+			"interactive",
+			Arc::clone(&interactive) as Arc<dyn TaskBackend>,
+		));
+
+	let worker = Arc::new(
+		Worker::new(
+			WorkerConfig::new("fair".to_string())
+				.with_concurrency(4)
+				.with_poll_interval(Duration::from_millis(5)),
+		)
+		.with_registry(registry),
+	);
+	let handle = tokio::spawn(Arc::clone(&worker).run_queues(Arc::new(queues)));
+
+	tokio::time::timeout(Duration::from_secs(5), async {
+		while bulk.finished() + interactive.finished() < 16 {
+			tokio::time::sleep(Duration::from_millis(10)).await;
+		}
+	})
+	.await
+	.expect("all tasks should finish");
+	worker.stop().await;
+	handle.await.unwrap().unwrap();
+
+	let peak = tracker.peak.lock().unwrap();
+	assert_eq!(peak["bulk"], 2);
+	assert!(peak["interactive"] >= 1);
+	assert!(tracker.total_peak.load(Ordering::SeqCst) <= 4);
+}
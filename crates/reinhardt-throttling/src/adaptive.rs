@@ -2,12 +2,21 @@
 //!
 //! Dynamically adjusts rate limits based on system load, error rates,
 //! and other performance metrics.
-
-use super::backend::ThrottleBackend;
+//!
+//! [`AdaptiveThrottle`] lowers its rate while the measured stress is above
+//! the configured threshold and raises it again once stress falls below the
+//! recovery threshold, holding the rate in between so it does not flap.
+//! Load is reported with [`AdaptiveThrottle::update_metrics`] or measured by
+//! a [`LoadTracker`] fed with the latency and outcome of handled requests.
+//! The throttle can also wrap another throttle, which is only consulted for
+//! requests the adaptive limit admits.
+
+use super::backend::{ThrottleBackend, check_fixed_window};
 use super::time_provider::{SystemTimeProvider, TimeProvider};
-use super::{Throttle, ThrottleError, ThrottleResult};
+use super::{RateLimitInfo, Throttle, ThrottleError, ThrottleResult};
 use async_trait::async_trait;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
+use std::collections::VecDeque;
 use std::sync::Arc;
 use tokio::time::{Duration, Instant};
 
@@ -61,11 +70,20 @@ pub struct AdaptiveConfig {
 	pub adjustment_speed: f64,
 	/// Stress threshold for rate decrease (0.0 - 1.0)
 	pub stress_threshold: f64,
+	/// Stress below which the rate increases again (0.0 - 1.0)
+	///
+	/// Stress between this and `stress_threshold` keeps the current rate.
+	pub recovery_threshold: f64,
+	/// Minimum time between two rate adjustments
+	pub adjustment_interval: Duration,
 }
 
 impl AdaptiveConfig {
 	/// Creates a new adaptive configuration
 	///
+	/// The recovery threshold defaults to 80% of `stress_threshold` and the
+	/// adjustment interval to 5 seconds.
+	///
 	/// # Examples
 	///
 	/// ```
@@ -92,8 +110,31 @@ impl AdaptiveConfig {
 			initial_rate,
 			adjustment_speed,
 			stress_threshold,
+			recovery_threshold: stress_threshold * DEFAULT_RECOVERY_RATIO,
+			adjustment_interval: DEFAULT_ADJUSTMENT_INTERVAL,
 		}
 	}
+
+	/// Set the stress below which the rate increases again
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_throttling::adaptive::AdaptiveConfig;
+	///
+	/// let config = AdaptiveConfig::default().with_recovery_threshold(0.3);
+	/// assert_eq!(config.recovery_threshold, 0.3);
+	/// ```
+	pub fn with_recovery_threshold(mut self, threshold: f64) -> Self {
+		self.recovery_threshold = threshold;
+		self
+	}
+
+	/// Set the minimum time between two rate adjustments
+	pub fn with_adjustment_interval(mut self, interval: Duration) -> Self {
+		self.adjustment_interval = interval;
+		self
+	}
 }
 
 impl Default for AdaptiveConfig {
@@ -115,6 +156,123 @@ impl Default for AdaptiveConfig {
 			initial_rate: (100, 60),
 			adjustment_speed: 0.1,
 			stress_threshold: 0.7,
+			recovery_threshold: 0.7 * DEFAULT_RECOVERY_RATIO,
+			adjustment_interval: DEFAULT_ADJUSTMENT_INTERVAL,
+		}
+	}
+}
+
+/// Fraction of the stress threshold below which the rate recovers by default
+const DEFAULT_RECOVERY_RATIO: f64 = 0.8;
+
+/// Default minimum time between two rate adjustments
+const DEFAULT_ADJUSTMENT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Most samples a [`LoadTracker`] keeps, however busy the window
+const MAX_LOAD_SAMPLES: usize = 10_000;
+
+#[derive(Debug, Clone, Copy)]
+struct LoadSample {
+	at: Instant,
+	latency: Duration,
+	error: bool,
+}
+
+/// Rolling measurement of request latency and error rate
+///
+/// Record every handled request with [`record`](Self::record), e.g. from
+/// `ThrottleMiddleware::with_load_tracker` in `reinhardt-middleware`. An
+/// [`AdaptiveThrottle`] built with [`AdaptiveThrottle::with_load_tracker`]
+/// reads its [`LoadMetrics`] whenever its rate is due for adjustment.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_throttling::adaptive::LoadTracker;
+/// use std::time::Duration;
+///
+/// let tracker = LoadTracker::new(Duration::from_secs(30));
+/// tracker.record(Duration::from_millis(200), false);
+/// tracker.record(Duration::from_millis(400), true);
+///
+/// let metrics = tracker.metrics();
+/// assert_eq!(metrics.error_rate, 0.5);
+/// assert_eq!(metrics.avg_response_time, 300.0);
+/// ```
+pub struct LoadTracker<T: TimeProvider = SystemTimeProvider> {
+	window: Duration,
+	samples: Mutex<VecDeque<LoadSample>>,
+	cpu_usage: RwLock<f64>,
+	time_provider: Arc<T>,
+}
+
+impl LoadTracker<SystemTimeProvider> {
+	/// Creates a tracker averaging requests over the last `window`
+	pub fn new(window: Duration) -> Self {
+		Self::with_time_provider(window, Arc::new(SystemTimeProvider::new()))
+	}
+}
+
+impl<T: TimeProvider> LoadTracker<T> {
+	/// Creates a tracker with a custom time provider
+	pub fn with_time_provider(window: Duration, time_provider: Arc<T>) -> Self {
+		Self {
+			window,
+			samples: Mutex::new(VecDeque::new()),
+			cpu_usage: RwLock::new(0.0),
+			time_provider,
+		}
+	}
+
+	/// Record the latency of a handled request and whether it failed
+	pub fn record(&self, latency: Duration, error: bool) {
+		let now = self.time_provider.now();
+		let mut samples = self.samples.lock();
+		self.prune(&mut samples, now);
+		if samples.len() >= MAX_LOAD_SAMPLES {
+			samples.pop_front();
+		}
+		samples.push_back(LoadSample {
+			at: now,
+			latency,
+			error,
+		});
+	}
+
+	/// Report the current CPU usage (0.0 - 1.0)
+	///
+	/// The tracker cannot measure CPU usage itself; it reports the last
+	/// value set here.
+	pub fn set_cpu_usage(&self, usage: f64) {
+		*self.cpu_usage.write() = usage.clamp(0.0, 1.0);
+	}
+
+	/// Load measured over the window
+	///
+	/// Without recent requests, the error rate and response time are zero.
+	pub fn metrics(&self) -> LoadMetrics {
+		let mut samples = self.samples.lock();
+		self.prune(&mut samples, self.time_provider.now());
+		let cpu_usage = *self.cpu_usage.read();
+		if samples.is_empty() {
+			return LoadMetrics::new(0.0, 0.0, cpu_usage);
+		}
+
+		let count = samples.len() as f64;
+		let errors = samples.iter().filter(|sample| sample.error).count() as f64;
+		let total_ms: f64 = samples
+			.iter()
+			.map(|sample| sample.latency.as_secs_f64() * 1000.0)
+			.sum();
+		LoadMetrics::new(errors / count, total_ms / count, cpu_usage)
+	}
+
+	fn prune(&self, samples: &mut VecDeque<LoadSample>, now: Instant) {
+		while samples
+			.front()
+			.is_some_and(|sample| now.duration_since(sample.at) > self.window)
+		{
+			samples.pop_front();
 		}
 	}
 }
@@ -129,6 +287,14 @@ struct AdaptiveState {
 
 /// Adaptive rate limiting throttle
 ///
+/// The rate starts at `initial_rate` and is adjusted at most once per
+/// `adjustment_interval`: down while stress exceeds `stress_threshold`, up
+/// while it is below `recovery_threshold`, and held in between.
+///
+/// With [`with_inner`](Self::with_inner) the throttle guards another
+/// throttle. The adaptive limit is checked first, so requests it rejects do
+/// not use up the wrapped throttle's quota.
+///
 /// # Examples
 ///
 /// ```
@@ -147,6 +313,8 @@ pub struct AdaptiveThrottle<B: ThrottleBackend, T: TimeProvider = SystemTimeProv
 	config: AdaptiveConfig,
 	state: Arc<RwLock<AdaptiveState>>,
 	time_provider: Arc<T>,
+	inner: Option<Arc<dyn Throttle>>,
+	load_tracker: Option<Arc<LoadTracker<T>>>,
 }
 
 impl<B: ThrottleBackend> AdaptiveThrottle<B, SystemTimeProvider> {
@@ -175,6 +343,8 @@ impl<B: ThrottleBackend> AdaptiveThrottle<B, SystemTimeProvider> {
 			config,
 			state: Arc::new(RwLock::new(initial_state)),
 			time_provider: Arc::new(SystemTimeProvider::new()),
+			inner: None,
+			load_tracker: None,
 		}
 	}
 }
@@ -197,9 +367,58 @@ impl<B: ThrottleBackend, T: TimeProvider> AdaptiveThrottle<B, T> {
 			config,
 			state: Arc::new(RwLock::new(initial_state)),
 			time_provider,
+			inner: None,
+			load_tracker: None,
 		}
 	}
 
+	/// Guard another throttle with the adaptive limit
+	///
+	/// Requests must pass the adaptive limit first and then `inner`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_throttling::adaptive::{AdaptiveConfig, AdaptiveThrottle};
+	/// use reinhardt_throttling::{AnonRateThrottle, MemoryBackend, Throttle};
+	/// use std::sync::Arc;
+	///
+	/// # tokio_test::block_on(async {
+	/// let config = AdaptiveConfig::new((1, 60), (10, 60), (1, 60), 0.1, 0.7);
+	/// let throttle = AdaptiveThrottle::new(Arc::new(MemoryBackend::new()), config)
+	///     .with_inner(Arc::new(AnonRateThrottle::new(5, 60)));
+	///
+	/// assert!(throttle.allow_request("client").await.unwrap());
+	/// assert!(!throttle.allow_request("client").await.unwrap());
+	/// # });
+	/// ```
+	pub fn with_inner(mut self, inner: Arc<dyn Throttle>) -> Self {
+		self.inner = Some(inner);
+		self
+	}
+
+	/// Measure load with `tracker` instead of explicit metric updates
+	///
+	/// The tracker's metrics are read whenever the rate is due for
+	/// adjustment while requests are checked.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_throttling::adaptive::{AdaptiveConfig, AdaptiveThrottle, LoadTracker};
+	/// use reinhardt_throttling::MemoryBackend;
+	/// use std::sync::Arc;
+	/// use std::time::Duration;
+	///
+	/// let tracker = Arc::new(LoadTracker::new(Duration::from_secs(30)));
+	/// let throttle = AdaptiveThrottle::new(Arc::new(MemoryBackend::new()), AdaptiveConfig::default())
+	///     .with_load_tracker(Arc::clone(&tracker));
+	/// ```
+	pub fn with_load_tracker(mut self, tracker: Arc<LoadTracker<T>>) -> Self {
+		self.load_tracker = Some(tracker);
+		self
+	}
+
 	/// Update system load metrics
 	///
 	/// # Examples
@@ -219,6 +438,10 @@ impl<B: ThrottleBackend, T: TimeProvider> AdaptiveThrottle<B, T> {
 	/// # });
 	/// ```
 	pub async fn update_metrics(&self, metrics: LoadMetrics) {
+		self.record_metrics(metrics);
+	}
+
+	fn record_metrics(&self, metrics: LoadMetrics) {
 		let mut state = self.state.write();
 
 		// Add to history (keep last 10 metrics)
@@ -229,12 +452,27 @@ impl<B: ThrottleBackend, T: TimeProvider> AdaptiveThrottle<B, T> {
 
 		// Adjust rate if enough time has passed
 		let now = self.time_provider.now();
-		if now.duration_since(state.last_adjustment) > Duration::from_secs(5) {
+		if now.duration_since(state.last_adjustment) > self.config.adjustment_interval {
 			self.adjust_rate(&mut state, metrics);
 			state.last_adjustment = now;
 		}
 	}
 
+	/// Read the load tracker if the rate is due for adjustment
+	fn refresh_load(&self) {
+		let Some(tracker) = &self.load_tracker else {
+			return;
+		};
+		let due = self
+			.time_provider
+			.now()
+			.duration_since(self.state.read().last_adjustment)
+			> self.config.adjustment_interval;
+		if due {
+			self.record_metrics(tracker.metrics());
+		}
+	}
+
 	/// Adjust the rate based on system stress
 	fn adjust_rate(&self, state: &mut AdaptiveState, metrics: LoadMetrics) {
 		let stress = metrics.calculate_stress();
@@ -244,10 +482,13 @@ impl<B: ThrottleBackend, T: TimeProvider> AdaptiveThrottle<B, T> {
 			// Decrease rate when under stress
 			let decrease_factor = 1.0 - self.config.adjustment_speed;
 			((current_requests as f64) * decrease_factor) as usize
-		} else {
+		} else if stress < self.config.recovery_threshold {
 			// Increase rate when system is healthy
 			let increase_factor = 1.0 + self.config.adjustment_speed;
 			((current_requests as f64) * increase_factor) as usize
+		} else {
+			// Hold the rate between the thresholds to avoid flapping
+			current_requests
 		};
 
 		// Clamp to min/max bounds
@@ -283,15 +524,32 @@ impl<B: ThrottleBackend, T: TimeProvider> AdaptiveThrottle<B, T> {
 #[async_trait]
 impl<B: ThrottleBackend, T: TimeProvider> Throttle for AdaptiveThrottle<B, T> {
 	async fn allow_request(&self, key: &str) -> ThrottleResult<bool> {
+		self.refresh_load();
 		let (rate, period) = self.get_current_rate();
 
+		let Some(inner) = &self.inner else {
+			let count = self
+				.backend
+				.increment(key, period)
+				.await
+				.map_err(ThrottleError::ThrottleError)?;
+			return Ok(count <= rate);
+		};
+
+		// Check the adaptive limit before the wrapped throttle counts the request
 		let count = self
 			.backend
+			.get_count(key)
+			.await
+			.map_err(ThrottleError::ThrottleError)?;
+		if count >= rate || !inner.allow_request(key).await? {
+			return Ok(false);
+		}
+		self.backend
 			.increment(key, period)
 			.await
 			.map_err(ThrottleError::ThrottleError)?;
-
-		Ok(count <= rate)
+		Ok(true)
 	}
 
 	async fn wait_time(&self, key: &str) -> ThrottleResult<Option<u64>> {
@@ -303,16 +561,60 @@ impl<B: ThrottleBackend, T: TimeProvider> Throttle for AdaptiveThrottle<B, T> {
 			.await
 			.map_err(ThrottleError::ThrottleError)?;
 
-		if count > rate {
-			Ok(Some(period))
-		} else {
-			Ok(None)
+		// With a wrapped throttle, reaching the rate already blocks requests
+		let limited = match &self.inner {
+			Some(_) => count >= rate,
+			None => count > rate,
+		};
+		if limited {
+			return Ok(Some(period));
+		}
+		match &self.inner {
+			Some(inner) => inner.wait_time(key).await,
+			None => Ok(None),
 		}
 	}
 
 	fn get_rate(&self) -> (usize, u64) {
 		self.get_current_rate()
 	}
+
+	async fn check(&self, key: &str) -> ThrottleResult<RateLimitInfo> {
+		self.refresh_load();
+		let (rate, period) = self.get_current_rate();
+
+		let Some(inner) = &self.inner else {
+			return check_fixed_window(self.backend.as_ref(), key, rate, period).await;
+		};
+
+		let quota = self
+			.backend
+			.quota(key, rate, period)
+			.await
+			.map_err(ThrottleError::ThrottleError)?;
+		if quota.remaining == 0 {
+			return Ok(RateLimitInfo {
+				allowed: false,
+				limit: rate,
+				remaining: 0,
+				reset: quota.reset,
+				retry_after: Some(quota.reset),
+			});
+		}
+
+		let mut info = inner.check(key).await?;
+		if info.allowed {
+			self.backend
+				.increment(key, period)
+				.await
+				.map_err(ThrottleError::ThrottleError)?;
+			if rate < info.limit {
+				info.limit = rate;
+			}
+			info.remaining = info.remaining.min(quota.remaining - 1);
+		}
+		Ok(info)
+	}
 }
 
 #[cfg(test)]
@@ -356,6 +658,23 @@ mod tests {
 		assert!(!throttle.allow_request("test_key").await.unwrap());
 	}
 
+	#[tokio::test]
+	async fn test_adaptive_throttle_check_uses_current_rate() {
+		let backend = Arc::new(MemoryBackend::new());
+		let config = AdaptiveConfig::new((1, 60), (10, 60), (2, 60), 0.1, 0.7);
+		let throttle = AdaptiveThrottle::new(backend, config);
+
+		let info = throttle.check("client").await.unwrap();
+		assert!(info.allowed);
+		assert_eq!((info.limit, info.remaining), (2, 1));
+
+		throttle.check("client").await.unwrap();
+		let info = throttle.check("client").await.unwrap();
+		assert!(!info.allowed);
+		assert_eq!(info.remaining, 0);
+		assert_eq!(info.retry_after, Some(info.reset));
+	}
+
 	#[tokio::test]
 	async fn test_adaptive_throttle_metrics_update() {
 		use tokio::time::Instant;
@@ -452,5 +771,124 @@ mod tests {
 		assert_eq!(config.min_rate, (10, 60));
 		assert_eq!(config.max_rate, (1000, 60));
 		assert_eq!(config.initial_rate, (100, 60));
+		assert!((config.recovery_threshold - 0.56).abs() < 1e-9);
+		assert_eq!(config.adjustment_interval, Duration::from_secs(5));
+	}
+
+	type MockBackend = MemoryBackend<MockTimeProvider>;
+
+	fn mock_throttle(
+		config: AdaptiveConfig,
+	) -> (
+		AdaptiveThrottle<MockBackend, MockTimeProvider>,
+		Arc<MockTimeProvider>,
+	) {
+		let time_provider = Arc::new(MockTimeProvider::new(Instant::now()));
+		let backend = Arc::new(MemoryBackend::with_time_provider(time_provider.clone()));
+		let throttle = AdaptiveThrottle::with_time_provider(backend, config, time_provider.clone());
+		(throttle, time_provider)
+	}
+
+	#[tokio::test]
+	async fn test_adaptive_throttle_holds_rate_between_thresholds() {
+		let config = AdaptiveConfig::new((10, 60), (100, 60), (50, 60), 0.2, 0.7)
+			.with_recovery_threshold(0.3);
+		let (throttle, time) = mock_throttle(config);
+
+		// Stress of 0.5 is below the decrease threshold but above recovery
+		let elevated = LoadMetrics::new(0.5, 500.0, 0.5);
+		for _ in 0..3 {
+			time.advance(std::time::Duration::from_secs(6));
+			throttle.update_metrics(elevated).await;
+		}
+
+		assert_eq!(throttle.get_current_rate(), (50, 60));
+	}
+
+	/// Throttle counting how often it was consulted
+	struct CountingThrottle {
+		calls: std::sync::atomic::AtomicUsize,
+	}
+
+	#[async_trait]
+	impl Throttle for CountingThrottle {
+		async fn allow_request(&self, _key: &str) -> ThrottleResult<bool> {
+			self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+			Ok(true)
+		}
+
+		async fn wait_time(&self, _key: &str) -> ThrottleResult<Option<u64>> {
+			Ok(None)
+		}
+
+		fn get_rate(&self) -> (usize, u64) {
+			(100, 60)
+		}
+	}
+
+	#[tokio::test]
+	async fn test_adaptive_throttle_rejects_before_inner_quota() {
+		let inner = Arc::new(CountingThrottle {
+			calls: std::sync::atomic::AtomicUsize::new(0),
+		});
+		let config = AdaptiveConfig::new((1, 60), (10, 60), (2, 60), 0.1, 0.7);
+		let (throttle, _) = mock_throttle(config);
+		let throttle = throttle.with_inner(inner.clone());
+
+		assert!(throttle.allow_request("client").await.unwrap());
+		assert!(throttle.check("client").await.unwrap().allowed);
+		assert!(!throttle.allow_request("client").await.unwrap());
+		let info = throttle.check("client").await.unwrap();
+
+		assert!(!info.allowed);
+		assert_eq!(info.limit, 2);
+		assert_eq!(throttle.wait_time("client").await.unwrap(), Some(60));
+		assert_eq!(inner.calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+	}
+
+	#[tokio::test]
+	async fn test_load_tracker_drives_rate() {
+		let config = AdaptiveConfig::new((10, 60), (100, 60), (50, 60), 0.2, 0.7);
+		let (throttle, time) = mock_throttle(config);
+		let tracker = Arc::new(LoadTracker::with_time_provider(
+			Duration::from_secs(30),
+			time.clone(),
+		));
+		tracker.set_cpu_usage(0.9);
+		let throttle = throttle.with_load_tracker(tracker.clone());
+
+		for _ in 0..10 {
+			tracker.record(Duration::from_millis(1500), true);
+		}
+		time.advance(std::time::Duration::from_secs(6));
+		throttle.allow_request("client").await.unwrap();
+
+		assert_eq!(throttle.get_current_rate(), (40, 60));
+
+		// Old samples leave the window and the rate recovers
+		tracker.set_cpu_usage(0.0);
+		time.advance(std::time::Duration::from_secs(31));
+		throttle.allow_request("client").await.unwrap();
+		assert_eq!(throttle.get_current_rate(), (48, 60));
+	}
+
+	#[test]
+	fn test_load_tracker_metrics_window() {
+		let time = Arc::new(MockTimeProvider::new(Instant::now()));
+		let tracker = LoadTracker::with_time_provider(Duration::from_secs(10), time.clone());
+		assert_eq!(tracker.metrics().error_rate, 0.0);
+
+		tracker.record(Duration::from_millis(100), true);
+		time.advance(std::time::Duration::from_secs(6));
+		tracker.record(Duration::from_millis(300), false);
+
+		let metrics = tracker.metrics();
+		assert_eq!(metrics.error_rate, 0.5);
+		assert_eq!(metrics.avg_response_time, 200.0);
+
+		time.advance(std::time::Duration::from_secs(6));
+		let metrics = tracker.metrics();
+		assert_eq!(metrics.error_rate, 0.0);
+		assert_eq!(metrics.avg_response_time, 300.0);
 	}
 }
@@ -0,0 +1,572 @@
+//! Time-window analytics for throttling decisions
+//!
+//! Every allowed or blocked request can be recorded per scope and identity
+//! into an [`AnalyticsBackend`]. The backend answers the questions operators
+//! ask when tuning limits: who is being blocked the most, and how the block
+//! rate evolves over time.
+//!
+//! # Examples
+//!
+//! ```
+//! use reinhardt_throttling::analytics::{RecordingThrottle, ThrottleAnalytics};
+//! use reinhardt_throttling::{AnonRateThrottle, Throttle};
+//! use std::time::Duration;
+//!
+//! # tokio_test::block_on(async {
+//! let analytics = ThrottleAnalytics::memory();
+//! let throttle = RecordingThrottle::new(AnonRateThrottle::new(1, 60), analytics.clone(), "anon");
+//!
+//! throttle.allow_request("10.0.0.1").await.unwrap();
+//! throttle.allow_request("10.0.0.1").await.unwrap();
+//!
+//! let offenders = analytics.top_offenders(None, Duration::from_secs(3600), 10).await.unwrap();
+//! assert_eq!(offenders[0].identity, "10.0.0.1");
+//! assert_eq!(offenders[0].stats.blocked, 1);
+//! # });
+//! ```
+
+use super::{RateLimitInfo, Throttle, ThrottleResult};
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::RwLock;
+
+/// A recorded throttling decision
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThrottleEvent {
+	/// Throttle scope, e.g. `"anon"` or `"uploads"`
+	pub scope: String,
+	/// Client the decision applies to (user id, IP address, ...)
+	pub identity: String,
+	/// Whether the request was allowed
+	pub allowed: bool,
+	/// Unix timestamp in seconds
+	pub timestamp: u64,
+}
+
+impl ThrottleEvent {
+	/// Creates an event timestamped now
+	pub fn new(scope: impl Into<String>, identity: impl Into<String>, allowed: bool) -> Self {
+		Self {
+			scope: scope.into(),
+			identity: identity.into(),
+			allowed,
+			timestamp: unix_now(),
+		}
+	}
+
+	/// Set the event timestamp
+	pub fn at(mut self, timestamp: u64) -> Self {
+		self.timestamp = timestamp;
+		self
+	}
+}
+
+/// Allowed and blocked request counts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ThrottleStats {
+	/// Number of allowed requests
+	pub allowed: u64,
+	/// Number of blocked requests
+	pub blocked: u64,
+}
+
+impl ThrottleStats {
+	/// Total number of requests
+	pub fn total(&self) -> u64 {
+		self.allowed + self.blocked
+	}
+
+	/// Fraction of requests that were blocked (0.0 - 1.0)
+	pub fn block_rate(&self) -> f64 {
+		match self.total() {
+			0 => 0.0,
+			total => self.blocked as f64 / total as f64,
+		}
+	}
+
+	fn add(&mut self, other: &ThrottleStats) {
+		self.allowed += other.allowed;
+		self.blocked += other.blocked;
+	}
+}
+
+/// An identity ranked by blocked requests
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Offender {
+	/// Throttle scope
+	pub scope: String,
+	/// Blocked client
+	pub identity: String,
+	/// Counts for the identity in the scope
+	pub stats: ThrottleStats,
+}
+
+/// Counts over one time interval
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatsBucket {
+	/// Unix timestamp in seconds at which the interval starts
+	pub start: u64,
+	/// Counts within the interval
+	pub stats: ThrottleStats,
+}
+
+/// Storage for throttling analytics
+///
+/// Time ranges are Unix timestamps in seconds, `from` inclusive and `to`
+/// exclusive. A `scope` of `None` covers every scope.
+#[async_trait]
+pub trait AnalyticsBackend: Send + Sync {
+	/// Record a throttling decision
+	async fn record(&self, event: ThrottleEvent) -> ThrottleResult<()>;
+
+	/// Identities with the most blocked requests, most blocked first
+	async fn top_offenders(
+		&self,
+		scope: Option<&str>,
+		from: u64,
+		to: u64,
+		limit: usize,
+	) -> ThrottleResult<Vec<Offender>>;
+
+	/// Counts grouped into consecutive intervals of `bucket_secs`
+	///
+	/// Intervals without any request are omitted.
+	async fn stats_over_time(
+		&self,
+		scope: Option<&str>,
+		from: u64,
+		to: u64,
+		bucket_secs: u64,
+	) -> ThrottleResult<Vec<StatsBucket>>;
+}
+
+type SlotKey = (String, String);
+
+/// In-memory analytics backend
+///
+/// Events are aggregated into slots of `resolution` seconds, and slots older
+/// than the retention period are discarded as new events arrive.
+pub struct MemoryAnalyticsBackend {
+	resolution: u64,
+	retention: Duration,
+	slots: RwLock<BTreeMap<u64, HashMap<SlotKey, ThrottleStats>>>,
+}
+
+impl MemoryAnalyticsBackend {
+	/// Creates a backend with one-minute resolution and a day of retention
+	pub fn new() -> Self {
+		Self {
+			resolution: 60,
+			retention: Duration::from_secs(86_400),
+			slots: RwLock::new(BTreeMap::new()),
+		}
+	}
+
+	/// Set the granularity, in seconds, at which events are aggregated
+	pub fn with_resolution(mut self, seconds: u64) -> Self {
+		self.resolution = seconds.max(1);
+		self
+	}
+
+	/// Set how long events are kept
+	pub fn with_retention(mut self, retention: Duration) -> Self {
+		self.retention = retention;
+		self
+	}
+
+	fn slot(&self, timestamp: u64) -> u64 {
+		timestamp - timestamp % self.resolution
+	}
+}
+
+impl Default for MemoryAnalyticsBackend {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+fn matches_scope(filter: Option<&str>, scope: &str) -> bool {
+	filter.is_none_or(|filter| filter == scope)
+}
+
+#[async_trait]
+impl AnalyticsBackend for MemoryAnalyticsBackend {
+	async fn record(&self, event: ThrottleEvent) -> ThrottleResult<()> {
+		let mut slots = self.slots.write().await;
+		let stats = slots
+			.entry(self.slot(event.timestamp))
+			.or_default()
+			.entry((event.scope, event.identity))
+			.or_default();
+		if event.allowed {
+			stats.allowed += 1;
+		} else {
+			stats.blocked += 1;
+		}
+
+		let cutoff = event.timestamp.saturating_sub(self.retention.as_secs());
+		*slots = slots.split_off(&self.slot(cutoff));
+		Ok(())
+	}
+
+	async fn top_offenders(
+		&self,
+		scope: Option<&str>,
+		from: u64,
+		to: u64,
+		limit: usize,
+	) -> ThrottleResult<Vec<Offender>> {
+		if from >= to {
+			return Ok(Vec::new());
+		}
+		let slots = self.slots.read().await;
+		let mut totals: HashMap<&SlotKey, ThrottleStats> = HashMap::new();
+		for entries in slots.range(self.slot(from)..to).map(|(_, entries)| entries) {
+			for (key, stats) in entries {
+				if matches_scope(scope, &key.0) {
+					totals.entry(key).or_default().add(stats);
+				}
+			}
+		}
+
+		let mut offenders: Vec<Offender> = totals
+			.into_iter()
+			.filter(|(_, stats)| stats.blocked > 0)
+			.map(|((scope, identity), stats)| Offender {
+				scope: scope.clone(),
+				identity: identity.clone(),
+				stats,
+			})
+			.collect();
+		offenders.sort_by(|a, b| {
+			b.stats
+				.blocked
+				.cmp(&a.stats.blocked)
+				.then_with(|| a.scope.cmp(&b.scope))
+				.then_with(|| a.identity.cmp(&b.identity))
+		});
+		offenders.truncate(limit);
+		Ok(offenders)
+	}
+
+	async fn stats_over_time(
+		&self,
+		scope: Option<&str>,
+		from: u64,
+		to: u64,
+		bucket_secs: u64,
+	) -> ThrottleResult<Vec<StatsBucket>> {
+		if from >= to {
+			return Ok(Vec::new());
+		}
+		let bucket_secs = bucket_secs.max(1);
+		let slots = self.slots.read().await;
+		let mut buckets: BTreeMap<u64, ThrottleStats> = BTreeMap::new();
+		for (slot, entries) in slots.range(self.slot(from)..to) {
+			let start = slot - slot % bucket_secs;
+			let bucket = buckets.entry(start).or_default();
+			for ((event_scope, _), stats) in entries {
+				if matches_scope(scope, event_scope) {
+					bucket.add(stats);
+				}
+			}
+		}
+		Ok(buckets
+			.into_iter()
+			.filter(|(_, stats)| stats.total() > 0)
+			.map(|(start, stats)| StatsBucket { start, stats })
+			.collect())
+	}
+}
+
+/// Handle for recording and querying throttling analytics
+///
+/// Queries cover a window ending now; use the [backend](Self::backend)
+/// directly for explicit time ranges.
+#[derive(Clone)]
+pub struct ThrottleAnalytics {
+	backend: Arc<dyn AnalyticsBackend>,
+}
+
+impl ThrottleAnalytics {
+	/// Creates analytics stored in `backend`
+	pub fn new(backend: Arc<dyn AnalyticsBackend>) -> Self {
+		Self { backend }
+	}
+
+	/// Creates analytics stored in a [`MemoryAnalyticsBackend`]
+	pub fn memory() -> Self {
+		Self::new(Arc::new(MemoryAnalyticsBackend::new()))
+	}
+
+	/// The backend analytics are stored in
+	pub fn backend(&self) -> &Arc<dyn AnalyticsBackend> {
+		&self.backend
+	}
+
+	/// Record a throttling decision made now
+	pub async fn record(&self, scope: &str, identity: &str, allowed: bool) -> ThrottleResult<()> {
+		self.backend
+			.record(ThrottleEvent::new(scope, identity, allowed))
+			.await
+	}
+
+	/// Identities with the most blocked requests over the last `window`
+	pub async fn top_offenders(
+		&self,
+		scope: Option<&str>,
+		window: Duration,
+		limit: usize,
+	) -> ThrottleResult<Vec<Offender>> {
+		let (from, to) = window_range(window);
+		self.backend.top_offenders(scope, from, to, limit).await
+	}
+
+	/// Counts per interval of `bucket` over the last `window`
+	pub async fn stats_over_time(
+		&self,
+		scope: Option<&str>,
+		window: Duration,
+		bucket: Duration,
+	) -> ThrottleResult<Vec<StatsBucket>> {
+		let (from, to) = window_range(window);
+		self.backend
+			.stats_over_time(scope, from, to, bucket.as_secs())
+			.await
+	}
+}
+
+fn unix_now() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|elapsed| elapsed.as_secs())
+		.unwrap_or(0)
+}
+
+fn window_range(window: Duration) -> (u64, u64) {
+	let to = unix_now() + 1;
+	(to.saturating_sub(window.as_secs()), to)
+}
+
+/// Throttle wrapper recording each decision into analytics
+///
+/// The key passed to the throttle is recorded as the identity. Failing to
+/// record a decision is logged and never changes the decision itself.
+pub struct RecordingThrottle<Th: Throttle> {
+	inner: Th,
+	analytics: ThrottleAnalytics,
+	scope: String,
+}
+
+impl<Th: Throttle> RecordingThrottle<Th> {
+	/// Wraps `inner`, recording its decisions under `scope`
+	pub fn new(inner: Th, analytics: ThrottleAnalytics, scope: impl Into<String>) -> Self {
+		Self {
+			inner,
+			analytics,
+			scope: scope.into(),
+		}
+	}
+
+	/// The wrapped throttle
+	pub fn inner(&self) -> &Th {
+		&self.inner
+	}
+
+	async fn record(&self, key: &str, allowed: bool) {
+		if let Err(e) = self.analytics.record(&self.scope, key, allowed).await {
+			tracing::warn!(
+				scope = %self.scope,
+				error = %e,
+				"Failed to record throttling decision"
+			);
+		}
+	}
+}
+
+#[async_trait]
+impl<Th: Throttle> Throttle for RecordingThrottle<Th> {
+	async fn allow_request(&self, key: &str) -> ThrottleResult<bool> {
+		let allowed = self.inner.allow_request(key).await?;
+		self.record(key, allowed).await;
+		Ok(allowed)
+	}
+
+	async fn wait_time(&self, key: &str) -> ThrottleResult<Option<u64>> {
+		self.inner.wait_time(key).await
+	}
+
+	fn get_rate(&self) -> (usize, u64) {
+		self.inner.get_rate()
+	}
+
+	async fn check(&self, key: &str) -> ThrottleResult<RateLimitInfo> {
+		let info = self.inner.check(key).await?;
+		self.record(key, info.allowed).await;
+		Ok(info)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	async fn record(
+		backend: &MemoryAnalyticsBackend,
+		scope: &str,
+		identity: &str,
+		blocked: bool,
+		at: u64,
+	) {
+		backend
+			.record(ThrottleEvent::new(scope, identity, !blocked).at(at))
+			.await
+			.unwrap();
+	}
+
+	#[tokio::test]
+	async fn test_top_offenders() {
+		let backend = MemoryAnalyticsBackend::new();
+		record(&backend, "anon", "a", true, 1_000).await;
+		record(&backend, "anon", "a", true, 1_010).await;
+		record(&backend, "anon", "b", true, 1_020).await;
+		record(&backend, "anon", "c", false, 1_030).await;
+		record(&backend, "user", "a", true, 1_040).await;
+
+		let all = backend.top_offenders(None, 0, 2_000, 10).await.unwrap();
+		assert_eq!(all.len(), 3);
+		assert_eq!(
+			(all[0].scope.as_str(), all[0].identity.as_str()),
+			("anon", "a")
+		);
+		assert_eq!(all[0].stats.blocked, 2);
+
+		let user = backend
+			.top_offenders(Some("user"), 0, 2_000, 10)
+			.await
+			.unwrap();
+		assert_eq!(user.len(), 1);
+
+		let limited = backend.top_offenders(None, 0, 2_000, 1).await.unwrap();
+		assert_eq!(limited.len(), 1);
+	}
+
+	#[tokio::test]
+	async fn test_stats_over_time() {
+		let backend = MemoryAnalyticsBackend::new().with_resolution(60);
+		record(&backend, "anon", "a", false, 0).await;
+		record(&backend, "anon", "a", true, 30).await;
+		record(&backend, "anon", "a", true, 90).await;
+		record(&backend, "anon", "a", false, 400).await;
+
+		let buckets = backend.stats_over_time(None, 0, 600, 300).await.unwrap();
+
+		assert_eq!(buckets.len(), 2);
+		assert_eq!(buckets[0].start, 0);
+		assert_eq!(
+			buckets[0].stats,
+			ThrottleStats {
+				allowed: 1,
+				blocked: 2
+			}
+		);
+		assert!((buckets[0].stats.block_rate() - 2.0 / 3.0).abs() < 1e-9);
+		assert_eq!(buckets[1].start, 300);
+		assert_eq!(buckets[1].stats.block_rate(), 0.0);
+
+		// The range excludes earlier slots
+		let later = backend.stats_over_time(None, 300, 600, 300).await.unwrap();
+		assert_eq!(later.len(), 1);
+	}
+
+	#[tokio::test]
+	async fn test_retention() {
+		let backend = MemoryAnalyticsBackend::new().with_retention(Duration::from_secs(120));
+		record(&backend, "anon", "a", true, 0).await;
+		record(&backend, "anon", "b", true, 600).await;
+
+		let offenders = backend.top_offenders(None, 0, 1_000, 10).await.unwrap();
+		assert_eq!(offenders.len(), 1);
+		assert_eq!(offenders[0].identity, "b");
+	}
+
+	#[tokio::test]
+	async fn test_recording_throttle_records_check() {
+		let analytics = ThrottleAnalytics::memory();
+		let throttle = RecordingThrottle::new(
+			crate::AnonRateThrottle::new(1, 60),
+			analytics.clone(),
+			"anon",
+		);
+
+		assert!(throttle.check("client").await.unwrap().allowed);
+		assert!(!throttle.check("client").await.unwrap().allowed);
+
+		let buckets = analytics
+			.stats_over_time(
+				Some("anon"),
+				Duration::from_secs(60),
+				Duration::from_secs(60),
+			)
+			.await
+			.unwrap();
+		let total = buckets
+			.iter()
+			.fold(ThrottleStats::default(), |mut total, bucket| {
+				total.add(&bucket.stats);
+				total
+			});
+		assert_eq!(
+			total,
+			ThrottleStats {
+				allowed: 1,
+				blocked: 1
+			}
+		);
+	}
+
+	struct FailingBackend;
+
+	#[async_trait]
+	impl AnalyticsBackend for FailingBackend {
+		async fn record(&self, _event: ThrottleEvent) -> ThrottleResult<()> {
+			Err(crate::ThrottleError::ThrottleError(
+				"analytics unavailable".into(),
+			))
+		}
+
+		async fn top_offenders(
+			&self,
+			_scope: Option<&str>,
+			_from: u64,
+			_to: u64,
+			_limit: usize,
+		) -> ThrottleResult<Vec<Offender>> {
+			Ok(Vec::new())
+		}
+
+		async fn stats_over_time(
+			&self,
+			_scope: Option<&str>,
+			_from: u64,
+			_to: u64,
+			_bucket_secs: u64,
+		) -> ThrottleResult<Vec<StatsBucket>> {
+			Ok(Vec::new())
+		}
+	}
+
+	#[tokio::test]
+	async fn test_recording_throttle_ignores_analytics_errors() {
+		let throttle = RecordingThrottle::new(
+			crate::AnonRateThrottle::new(1, 60),
+			ThrottleAnalytics::new(Arc::new(FailingBackend)),
+			"anon",
+		);
+
+		assert!(throttle.allow_request("client").await.unwrap());
+		assert!(!throttle.check("client").await.unwrap().allowed);
+	}
+}
@@ -1,5 +1,5 @@
-use super::backend::{MemoryBackend, ThrottleBackend};
-use super::{Throttle, ThrottleResult};
+use super::backend::{MemoryBackend, ThrottleBackend, check_fixed_window};
+use super::{RateLimitInfo, Throttle, ThrottleResult};
 use async_trait::async_trait;
 
 pub struct AnonRateThrottle<B: ThrottleBackend = MemoryBackend> {
@@ -90,6 +90,10 @@ impl<B: ThrottleBackend> Throttle for AnonRateThrottle<B> {
 	fn get_rate(&self) -> (usize, u64) {
 		(self.rate, self.window_secs)
 	}
+	async fn check(&self, key: &str) -> ThrottleResult<RateLimitInfo> {
+		let key = format!("throttle:anon:{}", key);
+		check_fixed_window(&self.backend, &key, self.rate, self.window_secs).await
+	}
 }
 
 #[cfg(test)]
@@ -138,6 +142,22 @@ mod tests {
 		assert!(throttle.allow_request(ip2).await.unwrap());
 	}
 
+	#[tokio::test]
+	async fn test_check_reports_remaining_quota() {
+		let throttle = AnonRateThrottle::new(2, 60);
+
+		let info = throttle.check("10.0.0.1").await.unwrap();
+		assert!(info.allowed);
+		assert_eq!((info.limit, info.remaining, info.reset), (2, 1, 60));
+		assert_eq!(info.retry_after, None);
+
+		throttle.check("10.0.0.1").await.unwrap();
+		let info = throttle.check("10.0.0.1").await.unwrap();
+		assert!(!info.allowed);
+		assert_eq!(info.remaining, 0);
+		assert_eq!(info.retry_after, Some(60));
+	}
+
 	#[tokio::test]
 	async fn test_accepts_request_under_limit() {
 		let throttle = AnonRateThrottle::new(1, 86400); // 1 per day
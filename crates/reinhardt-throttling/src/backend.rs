@@ -1,11 +1,21 @@
-use super::ThrottleError;
+use super::throttle::RateLimitInfo;
 use super::time_provider::{SystemTimeProvider, TimeProvider};
+use super::{ThrottleError, ThrottleResult};
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tokio::time::{Duration, Instant};
 
+/// Remaining quota of a fixed-window counter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quota {
+	/// Requests left in the current window
+	pub remaining: usize,
+	/// Seconds until the window resets
+	pub reset: u64,
+}
+
 #[async_trait]
 pub trait ThrottleBackend: Send + Sync {
 	async fn increment(&self, key: &str, window: u64) -> Result<usize, String>;
@@ -27,6 +37,43 @@ pub trait ThrottleBackend: Send + Sync {
 		// Default implementation returns None (not implemented)
 		Ok(None)
 	}
+
+	/// Remaining quota of a counter allowing `limit` requests per `window` seconds
+	///
+	/// The default implementation cannot tell when the window started and
+	/// reports a full window until reset.
+	async fn quota(&self, key: &str, limit: usize, window: u64) -> Result<Quota, String> {
+		let count = self.get_count(key).await?;
+		Ok(Quota {
+			remaining: limit.saturating_sub(count),
+			reset: window,
+		})
+	}
+}
+
+/// Count a request in a fixed window and report the resulting quota
+pub(crate) async fn check_fixed_window<B: ThrottleBackend + ?Sized>(
+	backend: &B,
+	key: &str,
+	limit: usize,
+	window: u64,
+) -> ThrottleResult<RateLimitInfo> {
+	let count = backend
+		.increment(key, window)
+		.await
+		.map_err(ThrottleError::ThrottleError)?;
+	let quota = backend
+		.quota(key, limit, window)
+		.await
+		.map_err(ThrottleError::ThrottleError)?;
+	let allowed = count <= limit;
+	Ok(RateLimitInfo {
+		allowed,
+		limit,
+		remaining: quota.remaining,
+		reset: quota.reset,
+		retry_after: (!allowed).then_some(quota.reset),
+	})
 }
 
 #[derive(Clone)]
@@ -88,6 +135,25 @@ impl<T: TimeProvider> ThrottleBackend for MemoryBackend<T> {
 		let storage = self.storage.read().await;
 		Ok(storage.get(key).map(|(count, _)| *count).unwrap_or(0))
 	}
+	async fn quota(&self, key: &str, limit: usize, window: u64) -> Result<Quota, String> {
+		let storage = self.storage.read().await;
+		let window = Duration::from_secs(window);
+		let elapsed = storage
+			.get(key)
+			.map(|(count, start)| (*count, self.time_provider.now().duration_since(*start)))
+			.filter(|(_, elapsed)| *elapsed <= window);
+		Ok(match elapsed {
+			Some((count, elapsed)) => Quota {
+				remaining: limit.saturating_sub(count),
+				reset: (window - elapsed).as_secs_f64().ceil() as u64,
+			},
+			// No request in the current window
+			None => Quota {
+				remaining: limit,
+				reset: 0,
+			},
+		})
+	}
 }
 
 #[cfg(feature = "redis-backend")]
@@ -141,6 +207,20 @@ impl ThrottleBackend for RedisThrottleBackend {
 			.map_err(|e| e.to_string())?;
 		conn.get(key).await.map_err(|e| e.to_string())
 	}
+	async fn quota(&self, key: &str, limit: usize, _window: u64) -> Result<Quota, String> {
+		use redis::AsyncCommands;
+		let mut conn = self
+			.client
+			.get_multiplexed_async_connection()
+			.await
+			.map_err(|e| e.to_string())?;
+		let count: Option<usize> = conn.get(key).await.map_err(|e| e.to_string())?;
+		let ttl: i64 = conn.ttl(key).await.map_err(|e| e.to_string())?;
+		Ok(Quota {
+			remaining: limit.saturating_sub(count.unwrap_or(0)),
+			reset: ttl.max(0) as u64,
+		})
+	}
 }
 
 #[cfg(test)]
@@ -204,6 +284,37 @@ mod tests {
 		assert_eq!(count2, 1);
 	}
 
+	#[tokio::test]
+	async fn test_memory_backend_quota() {
+		use crate::time_provider::MockTimeProvider;
+
+		let time = Arc::new(MockTimeProvider::new(Instant::now()));
+		let backend = MemoryBackend::with_time_provider(time.clone());
+
+		assert_eq!(
+			backend.quota("key", 3, 60).await.unwrap(),
+			Quota {
+				remaining: 3,
+				reset: 0
+			}
+		);
+
+		backend.increment("key", 60).await.unwrap();
+		backend.increment("key", 60).await.unwrap();
+		time.advance(Duration::from_secs(15));
+		assert_eq!(
+			backend.quota("key", 3, 60).await.unwrap(),
+			Quota {
+				remaining: 1,
+				reset: 45
+			}
+		);
+
+		// An expired window has its full quota again
+		time.advance(Duration::from_secs(50));
+		assert_eq!(backend.quota("key", 3, 60).await.unwrap().remaining, 3);
+	}
+
 	#[tokio::test]
 	async fn test_memory_backend_default() {
 		let backend = MemoryBackend::default();
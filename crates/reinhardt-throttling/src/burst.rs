@@ -1,7 +1,7 @@
 //! Burst rate throttling
 
 use super::backend::ThrottleBackend;
-use super::{Throttle, ThrottleError, ThrottleResult};
+use super::{RateLimitInfo, Throttle, ThrottleError, ThrottleResult};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -108,4 +108,93 @@ impl<B: ThrottleBackend> Throttle for BurstRateThrottle<B> {
 	fn get_rate(&self) -> (usize, u64) {
 		(self.sustained_rate, self.sustained_duration.as_secs())
 	}
+
+	/// Report the quota of the more restrictive window
+	async fn check(&self, key: &str) -> ThrottleResult<RateLimitInfo> {
+		let backend = self.backend.lock().await;
+		let windows = [
+			(
+				format!("burst:{}", key),
+				self.burst_rate,
+				self.burst_duration,
+			),
+			(
+				format!("sustained:{}", key),
+				self.sustained_rate,
+				self.sustained_duration,
+			),
+		];
+
+		let mut allowed = true;
+		for (window_key, rate, _) in &windows {
+			let count = backend
+				.get_count(window_key)
+				.await
+				.map_err(ThrottleError::ThrottleError)?;
+			allowed &= count < *rate;
+		}
+		if allowed {
+			for (window_key, _, duration) in &windows {
+				backend.increment_duration(window_key, *duration).await?;
+			}
+		}
+
+		let mut info: Option<RateLimitInfo> = None;
+		for (window_key, rate, duration) in &windows {
+			let quota = backend
+				.quota(window_key, *rate, duration.as_secs())
+				.await
+				.map_err(ThrottleError::ThrottleError)?;
+			let exhausted = quota.remaining == 0;
+			let window = RateLimitInfo {
+				allowed,
+				limit: *rate,
+				remaining: quota.remaining,
+				reset: quota.reset,
+				retry_after: (!allowed && exhausted).then_some(quota.reset),
+			};
+			info = match info {
+				// An exhausted window blocks until the later of the resets
+				Some(current)
+					if current.remaining < window.remaining
+						|| (current.remaining == window.remaining
+							&& current.reset >= window.reset) =>
+				{
+					Some(current)
+				}
+				_ => Some(window),
+			};
+		}
+		Ok(info.unwrap_or_else(RateLimitInfo::unlimited))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::backend::MemoryBackend;
+	use std::time::Duration;
+
+	#[tokio::test]
+	async fn test_burst_check_reports_the_exhausted_window() {
+		let backend = Arc::new(Mutex::new(MemoryBackend::new()));
+		let throttle = BurstRateThrottle::new(
+			backend,
+			2,
+			10,
+			Duration::from_secs(1),
+			Duration::from_secs(60),
+		);
+
+		let info = throttle.check("client").await.unwrap();
+		assert!(info.allowed);
+		assert_eq!((info.limit, info.remaining), (2, 1));
+
+		throttle.check("client").await.unwrap();
+		let info = throttle.check("client").await.unwrap();
+		assert!(!info.allowed);
+		assert_eq!((info.limit, info.remaining), (2, 0));
+		assert_eq!(info.retry_after, Some(info.reset));
+		assert!(info.reset <= 1);
+	}
 }
@@ -0,0 +1,158 @@
+//! Concurrency-based throttling
+//!
+//! Limits how many requests of a key are in flight at the same time rather
+//! than how many arrive per window, which suits expensive endpoints such as
+//! report generation. A [`ConcurrencyPermit`] is acquired when a request
+//! starts and releases its slot when dropped.
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Limits simultaneous in-flight requests per key
+///
+/// Keys of the form `"scope:identifier"` use the limit of their scope if one
+/// was added, and the default limit otherwise.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_throttling::ConcurrencyThrottle;
+///
+/// let throttle = ConcurrencyThrottle::new(10).add_scope("reports", 1);
+///
+/// let permit = throttle.try_acquire("reports:user1").unwrap();
+/// assert!(throttle.try_acquire("reports:user1").is_none());
+/// assert!(throttle.try_acquire("reports:user2").is_some());
+///
+/// drop(permit);
+/// assert!(throttle.try_acquire("reports:user1").is_some());
+/// ```
+#[derive(Clone)]
+pub struct ConcurrencyThrottle {
+	max_concurrent: usize,
+	scopes: HashMap<String, usize>,
+	in_flight: Arc<Mutex<HashMap<String, usize>>>,
+}
+
+impl ConcurrencyThrottle {
+	/// Creates a throttle allowing `max_concurrent` in-flight requests per key
+	pub fn new(max_concurrent: usize) -> Self {
+		Self {
+			max_concurrent,
+			scopes: HashMap::new(),
+			in_flight: Arc::new(Mutex::new(HashMap::new())),
+		}
+	}
+
+	/// Set the limit for keys in a scope
+	pub fn add_scope(mut self, scope: impl Into<String>, max_concurrent: usize) -> Self {
+		self.scopes.insert(scope.into(), max_concurrent);
+		self
+	}
+
+	/// Maximum number of in-flight requests for a key
+	pub fn limit(&self, key: &str) -> usize {
+		key.split_once(':')
+			.and_then(|(scope, _)| self.scopes.get(scope))
+			.copied()
+			.unwrap_or(self.max_concurrent)
+	}
+
+	/// Number of requests of a key currently in flight
+	pub fn in_flight(&self, key: &str) -> usize {
+		self.in_flight.lock().get(key).copied().unwrap_or(0)
+	}
+
+	/// Take a slot for a request, or `None` if the key is at its limit
+	///
+	/// The slot is released when the returned permit is dropped.
+	pub fn try_acquire(&self, key: &str) -> Option<ConcurrencyPermit> {
+		let limit = self.limit(key);
+		let mut in_flight = self.in_flight.lock();
+		let count = in_flight.entry(key.to_string()).or_insert(0);
+		if *count >= limit {
+			if *count == 0 {
+				in_flight.remove(key);
+			}
+			return None;
+		}
+		*count += 1;
+		Some(ConcurrencyPermit {
+			key: key.to_string(),
+			in_flight: self.in_flight.clone(),
+		})
+	}
+}
+
+/// A slot held by an in-flight request
+///
+/// Dropping the permit releases the slot, including when the request fails
+/// or its future is cancelled.
+pub struct ConcurrencyPermit {
+	key: String,
+	in_flight: Arc<Mutex<HashMap<String, usize>>>,
+}
+
+impl ConcurrencyPermit {
+	/// Key the slot was acquired for
+	pub fn key(&self) -> &str {
+		&self.key
+	}
+}
+
+impl Drop for ConcurrencyPermit {
+	fn drop(&mut self) {
+		let mut in_flight = self.in_flight.lock();
+		if let Some(count) = in_flight.get_mut(&self.key) {
+			*count = count.saturating_sub(1);
+			if *count == 0 {
+				in_flight.remove(&self.key);
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_limits_in_flight_requests_per_key() {
+		let throttle = ConcurrencyThrottle::new(2);
+
+		let first = throttle.try_acquire("user1").unwrap();
+		let _second = throttle.try_acquire("user1").unwrap();
+		assert!(throttle.try_acquire("user1").is_none());
+		assert_eq!(throttle.in_flight("user1"), 2);
+
+		// Other keys are independent
+		assert!(throttle.try_acquire("user2").is_some());
+
+		drop(first);
+		assert_eq!(throttle.in_flight("user1"), 1);
+		assert!(throttle.try_acquire("user1").is_some());
+	}
+
+	#[test]
+	fn test_scope_limits() {
+		let throttle = ConcurrencyThrottle::new(5).add_scope("reports", 1);
+
+		assert_eq!(throttle.limit("reports:user1"), 1);
+		assert_eq!(throttle.limit("api:user1"), 5);
+		assert_eq!(throttle.limit("user1"), 5);
+
+		let _permit = throttle.try_acquire("reports:user1").unwrap();
+		assert!(throttle.try_acquire("reports:user1").is_none());
+	}
+
+	#[test]
+	fn test_released_slots_are_cleaned_up() {
+		let throttle = ConcurrencyThrottle::new(1).add_scope("disabled", 0);
+
+		drop(throttle.try_acquire("user1").unwrap());
+		assert!(throttle.try_acquire("disabled:user1").is_none());
+
+		assert!(throttle.in_flight.lock().is_empty());
+	}
+}
@@ -2,11 +2,37 @@
 //!
 //! Allows different rate limits based on the geographical location of the client,
 //! determined by their IP address.
-
-use super::backend::ThrottleBackend;
-use super::{Throttle, ThrottleError, ThrottleResult};
+//!
+//! Rules are checked from the most to the least specific: IP networks first,
+//! then the country resolved by a [`GeoIpProvider`], then the default rate.
+//! Networks and countries can also be put on an allow list, which bypasses
+//! throttling, or a deny list, which blocks every request.
+//!
+//! # Examples
+//!
+//! ```
+//! use reinhardt_throttling::geo::{GeoDecision, GeoRateConfig};
+//! use std::collections::HashMap;
+//!
+//! let mut config = GeoRateConfig::new(HashMap::new(), (50, 60));
+//! config.add_country_rate("JP", 200, 60);
+//! config.deny_country("XX");
+//! config.allow_network("10.0.0.0/8".parse().unwrap());
+//! config.add_network_rate("203.0.113.0/24".parse().unwrap(), 5, 60);
+//!
+//! let ip = |s: &str| s.parse().unwrap();
+//! assert_eq!(config.decide(ip("10.1.2.3"), Some("XX")), GeoDecision::Allow);
+//! assert_eq!(config.decide(ip("203.0.113.9"), Some("JP")), GeoDecision::Limit(5, 60));
+//! assert_eq!(config.decide(ip("198.51.100.1"), Some("JP")), GeoDecision::Limit(200, 60));
+//! assert_eq!(config.decide(ip("198.51.100.1"), Some("XX")), GeoDecision::Deny);
+//! assert_eq!(config.decide(ip("198.51.100.1"), None), GeoDecision::Limit(50, 60));
+//! ```
+
+use super::backend::{ThrottleBackend, check_fixed_window};
+use super::{RateLimitInfo, Throttle, ThrottleError, ThrottleResult};
 use async_trait::async_trait;
-use std::collections::HashMap;
+use ipnet::IpNet;
+use std::collections::{HashMap, HashSet};
 use std::net::IpAddr;
 use std::sync::Arc;
 
@@ -20,6 +46,27 @@ pub struct GeoRateConfig {
 	pub country_rates: HashMap<String, (usize, u64)>,
 	/// Default rate for countries not specified
 	pub default_rate: (usize, u64),
+	/// Rate for IP networks, taking precedence over country rates
+	pub network_rates: Vec<(IpNet, (usize, u64))>,
+	/// Networks that are never throttled
+	pub allowed_networks: Vec<IpNet>,
+	/// Networks whose requests are always rejected
+	pub denied_networks: Vec<IpNet>,
+	/// Countries that are never throttled
+	pub allowed_countries: HashSet<String>,
+	/// Countries whose requests are always rejected
+	pub denied_countries: HashSet<String>,
+}
+
+/// How a client is treated by the geo rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeoDecision {
+	/// Not throttled
+	Allow,
+	/// Always rejected
+	Deny,
+	/// Limited to `rate` requests per `period` seconds
+	Limit(usize, u64),
 }
 
 impl GeoRateConfig {
@@ -41,6 +88,11 @@ impl GeoRateConfig {
 		Self {
 			country_rates,
 			default_rate,
+			network_rates: Vec::new(),
+			allowed_networks: Vec::new(),
+			denied_networks: Vec::new(),
+			allowed_countries: HashSet::new(),
+			denied_countries: HashSet::new(),
 		}
 	}
 
@@ -57,10 +109,132 @@ impl GeoRateConfig {
 			.copied()
 			.unwrap_or(self.default_rate)
 	}
+
+	/// Add a rate limit for an IP network
+	///
+	/// When networks overlap, the one with the longest prefix applies.
+	pub fn add_network_rate(&mut self, network: IpNet, rate: usize, period: u64) {
+		self.network_rates.push((network, (rate, period)));
+	}
+
+	/// Never throttle requests from a network
+	pub fn allow_network(&mut self, network: IpNet) {
+		self.allowed_networks.push(network);
+	}
+
+	/// Reject every request from a network
+	pub fn deny_network(&mut self, network: IpNet) {
+		self.denied_networks.push(network);
+	}
+
+	/// Never throttle requests from a country
+	pub fn allow_country(&mut self, country_code: &str) {
+		self.allowed_countries.insert(country_code.to_string());
+	}
+
+	/// Reject every request from a country
+	pub fn deny_country(&mut self, country_code: &str) {
+		self.denied_countries.insert(country_code.to_string());
+	}
+
+	/// Decide how a client is treated
+	///
+	/// Network rules are checked before country rules; within each, the deny
+	/// list comes before the allow list and the allow list before rates.
+	pub fn decide(&self, ip: IpAddr, country_code: Option<&str>) -> GeoDecision {
+		if self.denied_networks.iter().any(|net| net.contains(&ip)) {
+			return GeoDecision::Deny;
+		}
+		if self.allowed_networks.iter().any(|net| net.contains(&ip)) {
+			return GeoDecision::Allow;
+		}
+		if let Some((_, (rate, period))) = self
+			.network_rates
+			.iter()
+			.filter(|(net, _)| net.contains(&ip))
+			.max_by_key(|(net, _)| net.prefix_len())
+		{
+			return GeoDecision::Limit(*rate, *period);
+		}
+
+		let Some(country_code) = country_code else {
+			let (rate, period) = self.default_rate;
+			return GeoDecision::Limit(rate, period);
+		};
+		if self.denied_countries.contains(country_code) {
+			return GeoDecision::Deny;
+		}
+		if self.allowed_countries.contains(country_code) {
+			return GeoDecision::Allow;
+		}
+		let (rate, period) = self.get_rate(country_code);
+		GeoDecision::Limit(rate, period)
+	}
+}
+
+/// Resolves the country of an IP address
+///
+/// Implemented for closures, so a lookup table or an external service can be
+/// plugged in without a GeoIP database.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_throttling::geo::GeoIpProvider;
+/// use std::net::IpAddr;
+///
+/// let provider = |ip: IpAddr| ip.is_loopback().then(|| "JP".to_string());
+/// assert_eq!(provider.country_code("127.0.0.1".parse().unwrap()), Some("JP".to_string()));
+/// ```
+pub trait GeoIpProvider: Send + Sync {
+	/// ISO 3166-1 alpha-2 code of the country, if known
+	fn country_code(&self, ip: IpAddr) -> Option<String>;
+}
+
+impl<F> GeoIpProvider for F
+where
+	F: Fn(IpAddr) -> Option<String> + Send + Sync,
+{
+	fn country_code(&self, ip: IpAddr) -> Option<String> {
+		self(ip)
+	}
+}
+
+/// [`GeoIpProvider`] backed by a MaxMind GeoIP2/GeoLite2 country database
+#[cfg(feature = "geo-limiting")]
+pub struct MaxMindGeoIp {
+	reader: maxminddb::Reader<Vec<u8>>,
+}
+
+#[cfg(feature = "geo-limiting")]
+impl MaxMindGeoIp {
+	/// Open a database file
+	pub fn open(path: &str) -> Result<Self, String> {
+		let reader = maxminddb::Reader::open_readfile(path)
+			.map_err(|e| format!("Failed to open GeoIP database: {}", e))?;
+		Ok(Self { reader })
+	}
+}
+
+#[cfg(feature = "geo-limiting")]
+impl GeoIpProvider for MaxMindGeoIp {
+	fn country_code(&self, ip: IpAddr) -> Option<String> {
+		let country: geoip2::Country = self.reader.lookup(ip).ok()??;
+		country
+			.country
+			.and_then(|c| c.iso_code)
+			.map(|s| s.to_string())
+	}
 }
 
 /// Geo-based rate limiting throttle
 ///
+/// Keys are client IP addresses, either bare or prefixed with `ip:`. The
+/// rules are only as reliable as the address, so key requests by the peer
+/// address or the address reported by trusted proxies, as the throttle
+/// middleware of `reinhardt-middleware` does by default, never by a raw
+/// `X-Forwarded-For` header a client can set.
+///
 /// # Examples
 ///
 /// ```
@@ -82,13 +256,12 @@ impl GeoRateConfig {
 pub struct GeoRateThrottle<B: ThrottleBackend> {
 	backend: Arc<B>,
 	config: GeoRateConfig,
-	#[cfg(feature = "geo-limiting")]
-	geoip_reader: Option<Arc<maxminddb::Reader<Vec<u8>>>>,
+	provider: Option<Arc<dyn GeoIpProvider>>,
 }
 
 impl<B: ThrottleBackend> GeoRateThrottle<B> {
 	/// Creates a new geo-based throttle without GeoIP database
-	/// This will always use the default rate
+	/// Only network rules and the default rate apply
 	///
 	/// # Examples
 	///
@@ -106,8 +279,39 @@ impl<B: ThrottleBackend> GeoRateThrottle<B> {
 		Self {
 			backend,
 			config,
-			#[cfg(feature = "geo-limiting")]
-			geoip_reader: None,
+			provider: None,
+		}
+	}
+
+	/// Creates a new geo-based throttle resolving countries with `provider`
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_throttling::geo::{GeoRateConfig, GeoRateThrottle};
+	/// use reinhardt_throttling::{MemoryBackend, Throttle};
+	/// use std::collections::HashMap;
+	/// use std::net::IpAddr;
+	/// use std::sync::Arc;
+	///
+	/// # tokio_test::block_on(async {
+	/// let mut config = GeoRateConfig::new(HashMap::new(), (50, 60));
+	/// config.deny_country("XX");
+	/// let provider = Arc::new(|_ip: IpAddr| Some("XX".to_string()));
+	/// let throttle = GeoRateThrottle::with_provider(Arc::new(MemoryBackend::new()), config, provider);
+	///
+	/// assert!(!throttle.allow_request("ip:198.51.100.1").await.unwrap());
+	/// # });
+	/// ```
+	pub fn with_provider(
+		backend: Arc<B>,
+		config: GeoRateConfig,
+		provider: Arc<dyn GeoIpProvider>,
+	) -> Self {
+		Self {
+			backend,
+			config,
+			provider: Some(provider),
 		}
 	}
 
@@ -137,58 +341,39 @@ impl<B: ThrottleBackend> GeoRateThrottle<B> {
 		config: GeoRateConfig,
 		geoip_db_path: &str,
 	) -> Result<Self, String> {
-		let reader = maxminddb::Reader::open_readfile(geoip_db_path)
-			.map_err(|e| format!("Failed to open GeoIP database: {}", e))?;
-
-		Ok(Self {
-			backend,
-			config,
-			geoip_reader: Some(Arc::new(reader)),
-		})
+		let provider = MaxMindGeoIp::open(geoip_db_path)?;
+		Ok(Self::with_provider(backend, config, Arc::new(provider)))
 	}
 
-	#[cfg(feature = "geo-limiting")]
-	/// Get country code from IP address
-	fn get_country_code(&self, ip: IpAddr) -> Option<String> {
-		let reader = self.geoip_reader.as_ref()?;
-
-		let country: geoip2::Country = reader.lookup(ip).ok()??;
-		country
-			.country
-			.and_then(|c| c.iso_code)
-			.map(|s| s.to_string())
-	}
-
-	#[cfg(not(feature = "geo-limiting"))]
-	/// Get country code from IP address (stub for non-geo builds)
-	fn get_country_code(&self, _ip: IpAddr) -> Option<String> {
-		None
-	}
-
-	/// Extract IP address from key (expects format "ip:xxx.xxx.xxx.xxx")
+	/// Extract IP address from key ("xxx.xxx.xxx.xxx" or "ip:xxx.xxx.xxx.xxx")
 	fn extract_ip(&self, key: &str) -> Option<IpAddr> {
-		if let Some(ip_str) = key.strip_prefix("ip:") {
-			ip_str.parse().ok()
-		} else {
-			None
-		}
+		key.strip_prefix("ip:").unwrap_or(key).parse().ok()
 	}
 
-	/// Get rate limit for the given key
-	async fn get_rate_for_key(&self, key: &str) -> (usize, u64) {
-		if let Some(ip) = self.extract_ip(key)
-			&& let Some(country_code) = self.get_country_code(ip)
-		{
-			return self.config.get_rate(&country_code);
-		}
-		self.config.default_rate
+	/// Decide how the client of the given key is treated
+	///
+	/// Keys without an IP address get the default rate.
+	pub fn decide(&self, key: &str) -> GeoDecision {
+		let Some(ip) = self.extract_ip(key) else {
+			let (rate, period) = self.config.default_rate;
+			return GeoDecision::Limit(rate, period);
+		};
+		let country_code = self
+			.provider
+			.as_ref()
+			.and_then(|provider| provider.country_code(ip));
+		self.config.decide(ip, country_code.as_deref())
 	}
 }
 
 #[async_trait]
 impl<B: ThrottleBackend> Throttle for GeoRateThrottle<B> {
 	async fn allow_request(&self, key: &str) -> ThrottleResult<bool> {
-		let (rate, period) = self.get_rate_for_key(key).await;
+		let (rate, period) = match self.decide(key) {
+			GeoDecision::Allow => return Ok(true),
+			GeoDecision::Deny => return Ok(false),
+			GeoDecision::Limit(rate, period) => (rate, period),
+		};
 
 		let count = self
 			.backend
@@ -200,7 +385,11 @@ impl<B: ThrottleBackend> Throttle for GeoRateThrottle<B> {
 	}
 
 	async fn wait_time(&self, key: &str) -> ThrottleResult<Option<u64>> {
-		let (rate, period) = self.get_rate_for_key(key).await;
+		// Waiting does not lift a deny rule
+		let (rate, period) = match self.decide(key) {
+			GeoDecision::Allow | GeoDecision::Deny => return Ok(None),
+			GeoDecision::Limit(rate, period) => (rate, period),
+		};
 
 		let count = self
 			.backend
@@ -218,6 +407,19 @@ impl<B: ThrottleBackend> Throttle for GeoRateThrottle<B> {
 	fn get_rate(&self) -> (usize, u64) {
 		self.config.default_rate
 	}
+
+	async fn check(&self, key: &str) -> ThrottleResult<RateLimitInfo> {
+		match self.decide(key) {
+			GeoDecision::Allow => Ok(RateLimitInfo::unlimited()),
+			GeoDecision::Deny => Ok(RateLimitInfo {
+				allowed: false,
+				..RateLimitInfo::unlimited()
+			}),
+			GeoDecision::Limit(rate, period) => {
+				check_fixed_window(self.backend.as_ref(), key, rate, period).await
+			}
+		}
+	}
 }
 
 #[cfg(test)]
@@ -261,6 +463,9 @@ mod tests {
 		assert!(ip.is_some());
 		assert_eq!(ip.unwrap().to_string(), "192.168.1.1");
 
+		let bare = throttle.extract_ip("2001:db8::1");
+		assert_eq!(bare, Some("2001:db8::1".parse().unwrap()));
+
 		let no_ip = throttle.extract_ip("user:123");
 		assert!(no_ip.is_none());
 	}
@@ -278,6 +483,76 @@ mod tests {
 		assert_eq!(config.get_rate("UK"), (50, 60));
 	}
 
+	#[tokio::test]
+	async fn test_network_rules_take_precedence_over_countries() {
+		let mut config = GeoRateConfig::new(HashMap::new(), (50, 60));
+		config.add_country_rate("US", 100, 60);
+		config.deny_country("US");
+		config.add_network_rate("10.0.0.0/8".parse().unwrap(), 10, 60);
+		config.add_network_rate("10.1.0.0/16".parse().unwrap(), 1, 60);
+		config.deny_network("10.1.2.0/24".parse().unwrap());
+		config.allow_network("10.1.2.3/32".parse().unwrap());
+
+		let ip = |s: &str| s.parse::<IpAddr>().unwrap();
+		assert_eq!(
+			config.decide(ip("10.9.9.9"), Some("US")),
+			GeoDecision::Limit(10, 60)
+		);
+		assert_eq!(
+			config.decide(ip("10.1.9.9"), Some("US")),
+			GeoDecision::Limit(1, 60)
+		);
+		// Deny list wins over allow list
+		assert_eq!(config.decide(ip("10.1.2.3"), None), GeoDecision::Deny);
+		assert_eq!(
+			config.decide(ip("192.0.2.1"), Some("US")),
+			GeoDecision::Deny
+		);
+		assert_eq!(
+			config.decide("::1".parse().unwrap(), Some("JP")),
+			GeoDecision::Limit(50, 60)
+		);
+	}
+
+	#[tokio::test]
+	async fn test_geo_rate_throttle_with_provider() {
+		let mut config = GeoRateConfig::new(HashMap::new(), (100, 60));
+		config.add_country_rate("JP", 1, 60);
+		config.allow_country("US");
+		config.deny_country("XX");
+		let provider = |ip: IpAddr| match ip.to_string().as_str() {
+			"192.0.2.1" => Some("JP".to_string()),
+			"192.0.2.2" => Some("US".to_string()),
+			"192.0.2.3" => Some("XX".to_string()),
+			_ => None,
+		};
+		let throttle = GeoRateThrottle::with_provider(
+			Arc::new(MemoryBackend::new()),
+			config,
+			Arc::new(provider),
+		);
+
+		assert!(throttle.allow_request("ip:192.0.2.1").await.unwrap());
+		assert!(!throttle.allow_request("ip:192.0.2.1").await.unwrap());
+		assert_eq!(throttle.wait_time("ip:192.0.2.1").await.unwrap(), Some(60));
+
+		for _ in 0..3 {
+			assert!(throttle.allow_request("ip:192.0.2.2").await.unwrap());
+		}
+		assert_eq!(
+			throttle.check("ip:192.0.2.2").await.unwrap(),
+			RateLimitInfo::unlimited()
+		);
+
+		let denied = throttle.check("ip:192.0.2.3").await.unwrap();
+		assert!(!denied.allowed);
+		assert!(denied.headers().is_empty());
+		assert_eq!(throttle.wait_time("ip:192.0.2.3").await.unwrap(), None);
+
+		let info = throttle.check("ip:192.0.2.9").await.unwrap();
+		assert_eq!((info.limit, info.remaining), (100, 99));
+	}
+
 	#[tokio::test]
 	async fn test_geo_rate_throttle_get_rate() {
 		let backend = Arc::new(MemoryBackend::new());
@@ -2,11 +2,15 @@
 //!
 //! The leaky bucket algorithm processes requests at a constant rate,
 //! smoothing out bursts. Requests that exceed the bucket's capacity are rejected.
+//! Each key passed to [`Throttle::allow_request`] gets its own bucket.
+//! Buckets are kept in memory by the throttle itself and dropped once they
+//! have fully drained, so an empty bucket costs nothing.
 
 use super::backend::ThrottleBackend;
 use super::time_provider::{SystemTimeProvider, TimeProvider};
-use super::{Throttle, ThrottleResult};
+use super::{RateLimitInfo, Throttle, ThrottleResult};
 use async_trait::async_trait;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tokio::time::Instant;
@@ -79,7 +83,10 @@ impl LeakyBucketConfig {
 	}
 }
 
-/// Bucket state for tracking requests
+/// Number of buckets above which drained buckets are swept
+const MIN_SWEEP_THRESHOLD: usize = 1024;
+
+/// Bucket state for tracking requests of one key
 #[derive(Debug, Clone)]
 struct BucketState {
 	/// Current number of requests in the bucket
@@ -88,6 +95,23 @@ struct BucketState {
 	last_leak: Instant,
 }
 
+/// Buckets of all keys with a non-empty level
+#[derive(Debug)]
+struct Buckets {
+	states: HashMap<String, BucketState>,
+	/// Size at which drained buckets of idle keys are swept
+	sweep_at: usize,
+}
+
+impl Default for Buckets {
+	fn default() -> Self {
+		Self {
+			states: HashMap::new(),
+			sweep_at: MIN_SWEEP_THRESHOLD,
+		}
+	}
+}
+
 /// Leaky bucket throttle implementation
 ///
 /// # Examples
@@ -104,6 +128,8 @@ struct BucketState {
 ///
 /// // Requests are processed at constant rate
 /// assert!(throttle.allow_request("user_123").await.unwrap());
+/// assert!(throttle.level("user_123").await > 0.0);
+/// assert_eq!(throttle.level("user_456").await, 0.0);
 /// # });
 /// ```
 pub struct LeakyBucketThrottle<B: ThrottleBackend, T: TimeProvider = SystemTimeProvider> {
@@ -113,7 +139,7 @@ pub struct LeakyBucketThrottle<B: ThrottleBackend, T: TimeProvider = SystemTimeP
 	backend: Arc<B>,
 	config: LeakyBucketConfig,
 	time_provider: Arc<T>,
-	state: Arc<RwLock<BucketState>>,
+	buckets: Arc<RwLock<Buckets>>,
 }
 
 impl<B: ThrottleBackend> LeakyBucketThrottle<B, SystemTimeProvider> {
@@ -131,18 +157,7 @@ impl<B: ThrottleBackend> LeakyBucketThrottle<B, SystemTimeProvider> {
 	/// let throttle = LeakyBucketThrottle::new("api_key".to_string(), backend, config);
 	/// ```
 	pub fn new(key: String, backend: Arc<B>, config: LeakyBucketConfig) -> Self {
-		let initial_state = BucketState {
-			level: 0.0,
-			last_leak: SystemTimeProvider::new().now(),
-		};
-
-		Self {
-			key,
-			backend,
-			config,
-			time_provider: Arc::new(SystemTimeProvider::new()),
-			state: Arc::new(RwLock::new(initial_state)),
-		}
+		Self::with_time_provider(key, backend, config, Arc::new(SystemTimeProvider::new()))
 	}
 }
 
@@ -154,20 +169,20 @@ impl<B: ThrottleBackend, T: TimeProvider> LeakyBucketThrottle<B, T> {
 		config: LeakyBucketConfig,
 		time_provider: Arc<T>,
 	) -> Self {
-		let initial_state = BucketState {
-			level: 0.0,
-			last_leak: time_provider.now(),
-		};
-
 		Self {
 			key,
 			backend,
 			config,
 			time_provider,
-			state: Arc::new(RwLock::new(initial_state)),
+			buckets: Arc::new(RwLock::new(Buckets::default())),
 		}
 	}
 
+	/// Returns the bucket configuration
+	pub fn config(&self) -> &LeakyBucketConfig {
+		&self.config
+	}
+
 	/// Leak requests from the bucket based on elapsed time
 	fn leak_bucket(&self, state: &mut BucketState) {
 		let now = self.time_provider.now();
@@ -182,31 +197,65 @@ impl<B: ThrottleBackend, T: TimeProvider> LeakyBucketThrottle<B, T> {
 		state.last_leak = now;
 	}
 
-	/// Get current bucket level
-	pub async fn level(&self) -> f64 {
-		let mut state = self.state.write().await;
-		self.leak_bucket(&mut state);
-		state.level
+	/// Leak the bucket of a key, dropping it once it has drained
+	fn leak_key(&self, buckets: &mut Buckets, key: &str) -> Option<f64> {
+		let state = buckets.states.get_mut(key)?;
+		self.leak_bucket(state);
+		let level = state.level;
+		if level <= 0.0 {
+			buckets.states.remove(key);
+		}
+		Some(level)
 	}
 
-	/// Reset the bucket to empty
-	pub async fn reset(&self) {
-		let mut state = self.state.write().await;
-		state.level = 0.0;
-		state.last_leak = self.time_provider.now();
+	/// Drop the drained buckets of all keys
+	fn sweep(&self, buckets: &mut Buckets) {
+		buckets.states.retain(|_, state| {
+			self.leak_bucket(state);
+			state.level > 0.0
+		});
+		buckets.sweep_at = (buckets.states.len() * 2).max(MIN_SWEEP_THRESHOLD);
+	}
+
+	/// Get the current bucket level for a key
+	pub async fn level(&self, key: &str) -> f64 {
+		let mut buckets = self.buckets.write().await;
+		self.leak_key(&mut buckets, key).unwrap_or(0.0)
+	}
+
+	/// Number of keys with a non-empty bucket
+	pub async fn tracked_keys(&self) -> usize {
+		let mut buckets = self.buckets.write().await;
+		self.sweep(&mut buckets);
+		buckets.states.len()
+	}
+
+	/// Reset the bucket of a key to empty
+	pub async fn reset(&self, key: &str) {
+		self.buckets.write().await.states.remove(key);
 	}
 }
 
 #[async_trait]
 impl<B: ThrottleBackend, T: TimeProvider> Throttle for LeakyBucketThrottle<B, T> {
-	async fn allow_request(&self, _key: &str) -> ThrottleResult<bool> {
-		let mut state = self.state.write().await;
+	async fn allow_request(&self, key: &str) -> ThrottleResult<bool> {
+		let mut buckets = self.buckets.write().await;
+		if buckets.states.len() >= buckets.sweep_at && !buckets.states.contains_key(key) {
+			self.sweep(&mut buckets);
+		}
+		let state = buckets
+			.states
+			.entry(key.to_string())
+			.or_insert_with(|| BucketState {
+				level: 0.0,
+				last_leak: self.time_provider.now(),
+			});
 
 		// Leak requests first
-		self.leak_bucket(&mut state);
+		self.leak_bucket(state);
 
-		// Check if there's room in the bucket
-		if state.level < self.config.capacity as f64 {
+		// Check if there is room for a whole request in the bucket
+		if state.level + 1.0 <= self.config.capacity as f64 {
 			state.level += 1.0;
 			Ok(true)
 		} else {
@@ -214,16 +263,19 @@ impl<B: ThrottleBackend, T: TimeProvider> Throttle for LeakyBucketThrottle<B, T>
 		}
 	}
 
-	async fn wait_time(&self, _key: &str) -> ThrottleResult<Option<u64>> {
-		let state = self.state.read().await;
+	async fn wait_time(&self, key: &str) -> ThrottleResult<Option<u64>> {
+		let mut buckets = self.buckets.write().await;
+		let Some(level) = self.leak_key(&mut buckets, key) else {
+			return Ok(None);
+		};
 
-		if state.level < self.config.capacity as f64 {
+		if level + 1.0 <= self.config.capacity as f64 {
 			return Ok(None);
 		}
 
 		// Calculate time until space is available
 		// Need to wait until level drops below capacity
-		let excess = state.level - (self.config.capacity as f64 - 1.0);
+		let excess = level - (self.config.capacity as f64 - 1.0);
 		let wait_secs = (excess / self.config.leak_rate).ceil();
 
 		Ok(Some(wait_secs as u64))
@@ -232,6 +284,20 @@ impl<B: ThrottleBackend, T: TimeProvider> Throttle for LeakyBucketThrottle<B, T>
 	fn get_rate(&self) -> (usize, u64) {
 		(self.config.leak_rate as usize, 1)
 	}
+
+	async fn check(&self, key: &str) -> ThrottleResult<RateLimitInfo> {
+		let allowed = self.allow_request(key).await?;
+		let retry_after = self.wait_time(key).await?;
+		let level = self.level(key).await;
+		let capacity = self.config.capacity;
+		Ok(RateLimitInfo {
+			allowed,
+			limit: capacity,
+			remaining: (capacity as f64 - level).max(0.0).floor() as usize,
+			reset: (level / self.config.leak_rate).ceil() as u64,
+			retry_after: if allowed { None } else { retry_after },
+		})
+	}
 }
 
 #[cfg(test)]
@@ -334,17 +400,17 @@ mod tests {
 		);
 
 		// Initial level should be 0
-		assert_eq!(throttle.level().await, 0.0);
+		assert_eq!(throttle.level("user").await, 0.0);
 
 		// Add 5 requests
 		for _ in 0..5 {
 			throttle.allow_request("user").await.unwrap();
 		}
-		assert_eq!(throttle.level().await, 5.0);
+		assert_eq!(throttle.level("user").await, 5.0);
 
 		// Advance time by 1 second (2 requests leak)
 		time_provider.advance(std::time::Duration::from_secs(1));
-		assert_eq!(throttle.level().await, 3.0);
+		assert_eq!(throttle.level("user").await, 3.0);
 	}
 
 	#[tokio::test]
@@ -364,11 +430,11 @@ mod tests {
 		for _ in 0..10 {
 			throttle.allow_request("user").await.unwrap();
 		}
-		assert!(throttle.level().await > 0.0);
+		assert!(throttle.level("user").await > 0.0);
 
 		// Reset
-		throttle.reset().await;
-		assert_eq!(throttle.level().await, 0.0);
+		throttle.reset("user").await;
+		assert_eq!(throttle.level("user").await, 0.0);
 	}
 
 	#[tokio::test]
@@ -395,6 +461,101 @@ mod tests {
 		assert!(wait.unwrap() > 0);
 	}
 
+	#[tokio::test]
+	async fn test_leaky_bucket_keys_are_independent() {
+		use tokio::time::Instant;
+		let time_provider = Arc::new(MockTimeProvider::new(Instant::now()));
+		let backend = Arc::new(MemoryBackend::with_time_provider(time_provider.clone()));
+		let config = LeakyBucketConfig::new(2, 1.0);
+		let throttle = LeakyBucketThrottle::with_time_provider(
+			"test".to_string(),
+			backend,
+			config,
+			time_provider.clone(),
+		);
+
+		assert!(throttle.allow_request("alice").await.unwrap());
+		assert!(throttle.allow_request("alice").await.unwrap());
+		assert!(!throttle.allow_request("alice").await.unwrap());
+
+		// Another key has its own, empty bucket
+		assert!(throttle.allow_request("bob").await.unwrap());
+		assert_eq!(throttle.wait_time("bob").await.unwrap(), None);
+		assert_eq!(throttle.wait_time("alice").await.unwrap(), Some(1));
+
+		// Wait time reflects leakage since the last request
+		time_provider.advance(std::time::Duration::from_secs(1));
+		assert_eq!(throttle.wait_time("alice").await.unwrap(), None);
+		assert!(throttle.allow_request("alice").await.unwrap());
+	}
+
+	#[tokio::test]
+	async fn test_leaky_bucket_check() {
+		use tokio::time::Instant;
+		let time_provider = Arc::new(MockTimeProvider::new(Instant::now()));
+		let backend = Arc::new(MemoryBackend::with_time_provider(time_provider.clone()));
+		let config = LeakyBucketConfig::new(2, 0.5);
+		let throttle = LeakyBucketThrottle::with_time_provider(
+			"test".to_string(),
+			backend,
+			config,
+			time_provider,
+		);
+
+		let info = throttle.check("user").await.unwrap();
+		assert_eq!(
+			info,
+			RateLimitInfo {
+				allowed: true,
+				limit: 2,
+				remaining: 1,
+				reset: 2,
+				retry_after: None,
+			}
+		);
+
+		throttle.check("user").await.unwrap();
+		let info = throttle.check("user").await.unwrap();
+		assert!(!info.allowed);
+		assert_eq!(info.remaining, 0);
+		assert_eq!(info.reset, 4);
+		assert_eq!(info.retry_after, Some(2));
+	}
+
+	#[tokio::test]
+	async fn test_leaky_bucket_drops_drained_buckets() {
+		use tokio::time::Instant;
+		let time_provider = Arc::new(MockTimeProvider::new(Instant::now()));
+		let backend = Arc::new(MemoryBackend::with_time_provider(time_provider.clone()));
+		let config = LeakyBucketConfig::new(5, 1.0);
+		let throttle = LeakyBucketThrottle::with_time_provider(
+			"test".to_string(),
+			backend,
+			config,
+			time_provider.clone(),
+		);
+
+		// Many one-off clients
+		for i in 0..MIN_SWEEP_THRESHOLD {
+			throttle.allow_request(&format!("ip-{}", i)).await.unwrap();
+		}
+		assert_eq!(
+			throttle.buckets.read().await.states.len(),
+			MIN_SWEEP_THRESHOLD
+		);
+
+		// Once drained, idle buckets are swept when a new key arrives
+		time_provider.advance(std::time::Duration::from_secs(1));
+		throttle.allow_request("new-client").await.unwrap();
+		assert_eq!(throttle.buckets.read().await.states.len(), 1);
+		assert_eq!(throttle.tracked_keys().await, 1);
+
+		// Reading a drained bucket drops it as well
+		time_provider.advance(std::time::Duration::from_secs(1));
+		assert_eq!(throttle.level("new-client").await, 0.0);
+		assert!(throttle.buckets.read().await.states.is_empty());
+	}
+
 	#[test]
 	fn test_leaky_bucket_config_per_second() {
 		let config = LeakyBucketConfig::per_second(10.0, 20);
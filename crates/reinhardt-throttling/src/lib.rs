@@ -10,8 +10,17 @@
 //! - **Token Bucket**: Allows burst traffic while maintaining average rate
 //! - **Leaky Bucket**: Smooths burst traffic by processing at constant rate
 //! - **Adaptive Throttling**: Dynamically adjusts rates based on system load
-//! - **Geo-based Limiting**: Different rates per geographic region
+//! - **Geo-based Limiting**: Different rates per country or IP network, with allow/deny lists
 //! - **Time-of-day Limiting**: Different rates for peak/off-peak hours
+//! - **Concurrency Limiting**: Caps simultaneous in-flight requests per key
+//! - **Analytics**: Records allowed/blocked requests per scope and identity
+//!
+//! ## Response Headers
+//!
+//! [`Throttle::check`] returns a [`RateLimitInfo`] whose
+//! [`headers`](RateLimitInfo::headers) are the `Retry-After` and draft
+//! `RateLimit-Limit`/`RateLimit-Remaining`/`RateLimit-Reset` headers for
+//! the response.
 //!
 //! ## Backends
 //!
@@ -19,9 +28,11 @@
 //! - **Redis**: Distributed rate limiting with Redis (feature: `redis-backend`)
 
 pub mod adaptive;
+pub mod analytics;
 pub mod anon;
 pub mod backend;
 pub mod burst;
+pub mod concurrent;
 pub mod geo;
 pub mod leaky_bucket;
 pub mod scoped;
@@ -32,14 +43,22 @@ pub mod time_provider;
 pub mod token_bucket;
 pub mod user;
 
-pub use adaptive::{AdaptiveConfig, AdaptiveThrottle, LoadMetrics};
+pub use adaptive::{AdaptiveConfig, AdaptiveThrottle, LoadMetrics, LoadTracker};
+pub use analytics::{
+	AnalyticsBackend, MemoryAnalyticsBackend, Offender, RecordingThrottle, StatsBucket,
+	ThrottleAnalytics, ThrottleEvent, ThrottleStats,
+};
 pub use anon::AnonRateThrottle;
-pub use backend::{MemoryBackend, ThrottleBackend};
+pub use backend::{MemoryBackend, Quota, ThrottleBackend};
 pub use burst::BurstRateThrottle;
-pub use geo::{GeoRateConfig, GeoRateThrottle};
+pub use concurrent::{ConcurrencyPermit, ConcurrencyThrottle};
+pub use geo::{GeoDecision, GeoIpProvider, GeoRateConfig, GeoRateThrottle};
 pub use leaky_bucket::{LeakyBucketConfig, LeakyBucketThrottle};
 pub use scoped::ScopedRateThrottle;
-pub use throttle::{Throttle, ThrottleError, ThrottleResult};
+pub use throttle::{
+	RATELIMIT_LIMIT, RATELIMIT_REMAINING, RATELIMIT_RESET, RETRY_AFTER, RateLimitInfo, Throttle,
+	ThrottleError, ThrottleResult,
+};
 pub use tiered::{Tier, TieredRateThrottle};
 pub use time_of_day::{TimeOfDayConfig, TimeOfDayThrottle, TimeRange};
 pub use time_provider::{MockTimeProvider, SystemTimeProvider, TimeProvider};
@@ -48,3 +67,6 @@ pub use user::UserRateThrottle;
 
 #[cfg(feature = "redis-backend")]
 pub use backend::RedisThrottleBackend;
+
+#[cfg(feature = "geo-limiting")]
+pub use geo::MaxMindGeoIp;
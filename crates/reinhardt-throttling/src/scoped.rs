@@ -1,10 +1,19 @@
-use super::backend::{MemoryBackend, ThrottleBackend};
-use super::{Throttle, ThrottleResult};
+use super::backend::{MemoryBackend, ThrottleBackend, check_fixed_window};
+use super::leaky_bucket::{LeakyBucketConfig, LeakyBucketThrottle};
+use super::{RateLimitInfo, Throttle, ThrottleResult};
 use async_trait::async_trait;
 use std::collections::HashMap;
+use std::sync::Arc;
 
+/// Throttles requests per scope, keyed as `"scope:identifier"`
+///
+/// Scopes added with [`add_scope`](Self::add_scope) use a fixed window
+/// counted in the backend. Other algorithms, such as a leaky bucket, can be
+/// selected per scope with [`add_leaky_bucket_scope`](Self::add_leaky_bucket_scope)
+/// or [`add_throttle_scope`](Self::add_throttle_scope).
 pub struct ScopedRateThrottle<B: ThrottleBackend = MemoryBackend> {
 	pub scopes: HashMap<String, (usize, u64)>,
+	throttles: HashMap<String, Box<dyn Throttle>>,
 	backend: B,
 }
 
@@ -22,6 +31,7 @@ impl ScopedRateThrottle<MemoryBackend> {
 	pub fn new() -> Self {
 		Self {
 			scopes: HashMap::new(),
+			throttles: HashMap::new(),
 			backend: MemoryBackend::new(),
 		}
 	}
@@ -48,6 +58,7 @@ impl<B: ThrottleBackend> ScopedRateThrottle<B> {
 	pub fn with_backend(backend: B) -> Self {
 		Self {
 			scopes: HashMap::new(),
+			throttles: HashMap::new(),
 			backend,
 		}
 	}
@@ -67,7 +78,54 @@ impl<B: ThrottleBackend> ScopedRateThrottle<B> {
 	/// assert_eq!(throttle.scopes.get("upload"), Some(&(10, 60)));
 	/// ```
 	pub fn add_scope(mut self, scope: impl Into<String>, rate: usize, window: u64) -> Self {
-		self.scopes.insert(scope.into(), (rate, window));
+		let scope = scope.into();
+		self.throttles.remove(&scope);
+		self.scopes.insert(scope, (rate, window));
+		self
+	}
+
+	/// Add a scope limited by a leaky bucket
+	///
+	/// Each identifier in the scope gets its own bucket.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_throttling::{LeakyBucketConfig, ScopedRateThrottle, Throttle};
+	///
+	/// # tokio_test::block_on(async {
+	/// let throttle = ScopedRateThrottle::new()
+	///     .add_scope("api", 100, 60)
+	///     .add_leaky_bucket_scope("upstream", LeakyBucketConfig::per_minute(1.0, 2));
+	///
+	/// assert!(throttle.allow_request("upstream:user1").await.unwrap());
+	/// assert!(throttle.allow_request("upstream:user1").await.unwrap());
+	/// assert!(!throttle.allow_request("upstream:user1").await.unwrap());
+	/// assert!(throttle.allow_request("upstream:user2").await.unwrap());
+	/// # });
+	/// ```
+	pub fn add_leaky_bucket_scope(
+		self,
+		scope: impl Into<String>,
+		config: LeakyBucketConfig,
+	) -> Self {
+		let scope = scope.into();
+		let throttle =
+			LeakyBucketThrottle::new(scope.clone(), Arc::new(MemoryBackend::new()), config);
+		self.add_throttle_scope(scope, throttle)
+	}
+
+	/// Add a scope limited by any throttle
+	///
+	/// The throttle is called with the identifier part of the key.
+	pub fn add_throttle_scope(
+		mut self,
+		scope: impl Into<String>,
+		throttle: impl Throttle + 'static,
+	) -> Self {
+		let scope = scope.into();
+		self.scopes.remove(&scope);
+		self.throttles.insert(scope, Box::new(throttle));
 		self
 	}
 }
@@ -80,6 +138,9 @@ impl<B: ThrottleBackend> Throttle for ScopedRateThrottle<B> {
 			return Ok(true);
 		}
 		let (scope, identifier) = (parts[0], parts[1]);
+		if let Some(throttle) = self.throttles.get(scope) {
+			return throttle.allow_request(identifier).await;
+		}
 		if let Some(&(rate, window)) = self.scopes.get(scope) {
 			let key = format!("throttle:scope:{}:{}", scope, identifier);
 			let count = self
@@ -98,6 +159,9 @@ impl<B: ThrottleBackend> Throttle for ScopedRateThrottle<B> {
 			return Ok(None);
 		}
 		let (scope, identifier) = (parts[0], parts[1]);
+		if let Some(throttle) = self.throttles.get(scope) {
+			return throttle.wait_time(identifier).await;
+		}
 		if let Some(&(rate, window)) = self.scopes.get(scope) {
 			let key = format!("throttle:scope:{}:{}", scope, identifier);
 			let count = self
@@ -117,6 +181,21 @@ impl<B: ThrottleBackend> Throttle for ScopedRateThrottle<B> {
 	fn get_rate(&self) -> (usize, u64) {
 		(0, 0)
 	}
+	async fn check(&self, scope_key: &str) -> ThrottleResult<RateLimitInfo> {
+		let Some((scope, identifier)) = scope_key.split_once(':') else {
+			return Ok(RateLimitInfo::unlimited());
+		};
+		if let Some(throttle) = self.throttles.get(scope) {
+			return throttle.check(identifier).await;
+		}
+		match self.scopes.get(scope) {
+			Some(&(rate, window)) => {
+				let key = format!("throttle:scope:{}:{}", scope, identifier);
+				check_fixed_window(&self.backend, &key, rate, window).await
+			}
+			None => Ok(RateLimitInfo::unlimited()),
+		}
+	}
 }
 
 #[cfg(test)]
@@ -222,4 +301,74 @@ mod tests {
 		// user:456 should still have capacity
 		assert!(throttle.allow_request("user:456").await.unwrap());
 	}
+
+	#[tokio::test]
+	async fn test_leaky_bucket_scope() {
+		use crate::time_provider::MockTimeProvider;
+		use std::time::Duration;
+		use tokio::time::Instant;
+
+		let mock_time = Arc::new(MockTimeProvider::new(Instant::now()));
+		let leaky = LeakyBucketThrottle::with_time_provider(
+			"upstream".to_string(),
+			Arc::new(MemoryBackend::with_time_provider(mock_time.clone())),
+			LeakyBucketConfig::per_second(2.0, 4),
+			mock_time.clone(),
+		);
+		let throttle = ScopedRateThrottle::new()
+			.add_scope("api", 1, 60)
+			.add_throttle_scope("upstream", leaky);
+
+		for _ in 0..4 {
+			assert!(throttle.allow_request("upstream:user1").await.unwrap());
+		}
+		assert!(!throttle.allow_request("upstream:user1").await.unwrap());
+		assert_eq!(throttle.wait_time("upstream:user1").await.unwrap(), Some(1));
+
+		// The bucket drains at a constant rate instead of resetting per window
+		mock_time.advance(Duration::from_secs(1));
+		assert!(throttle.allow_request("upstream:user1").await.unwrap());
+		assert!(throttle.allow_request("upstream:user1").await.unwrap());
+		assert!(!throttle.allow_request("upstream:user1").await.unwrap());
+
+		// Fixed-window scopes are unaffected
+		assert!(throttle.allow_request("api:user1").await.unwrap());
+		assert!(!throttle.allow_request("api:user1").await.unwrap());
+	}
+
+	#[tokio::test]
+	async fn test_check_reports_scope_quota() {
+		let throttle = ScopedRateThrottle::new()
+			.add_scope("api", 2, 60)
+			.add_leaky_bucket_scope("upstream", LeakyBucketConfig::per_minute(60.0, 3));
+
+		let info = throttle.check("api:user1").await.unwrap();
+		assert!(info.allowed);
+		assert_eq!((info.limit, info.remaining, info.reset), (2, 1, 60));
+
+		throttle.check("api:user1").await.unwrap();
+		let info = throttle.check("api:user1").await.unwrap();
+		assert!(!info.allowed);
+		assert_eq!(info.remaining, 0);
+		assert_eq!(info.retry_after, Some(60));
+
+		let info = throttle.check("upstream:user1").await.unwrap();
+		assert!(info.allowed);
+		assert_eq!((info.limit, info.remaining), (3, 2));
+
+		assert!(!throttle.check("other:user1").await.unwrap().is_limited());
+	}
+
+	#[tokio::test]
+	async fn test_scope_algorithm_can_be_replaced() {
+		let throttle = ScopedRateThrottle::new()
+			.add_scope("x", 1, 60)
+			.add_leaky_bucket_scope("x", LeakyBucketConfig::per_minute(1.0, 3));
+
+		assert!(!throttle.scopes.contains_key("x"));
+		for _ in 0..3 {
+			assert!(throttle.allow_request("x:req1").await.unwrap());
+		}
+		assert!(!throttle.allow_request("x:req1").await.unwrap());
+	}
 }
@@ -11,6 +11,90 @@ pub enum ThrottleError {
 
 pub type ThrottleResult<T> = Result<T, ThrottleError>;
 
+/// `Retry-After` response header
+pub const RETRY_AFTER: &str = "Retry-After";
+/// `RateLimit-Limit` response header (IETF draft)
+pub const RATELIMIT_LIMIT: &str = "RateLimit-Limit";
+/// `RateLimit-Remaining` response header (IETF draft)
+pub const RATELIMIT_REMAINING: &str = "RateLimit-Remaining";
+/// `RateLimit-Reset` response header (IETF draft)
+pub const RATELIMIT_RESET: &str = "RateLimit-Reset";
+
+/// Outcome of a throttle check, with the quota to report to the client
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitInfo {
+	/// Whether the request is allowed
+	pub allowed: bool,
+	/// Maximum number of requests in the quota; `0` if the key is not limited
+	pub limit: usize,
+	/// Requests left in the quota
+	pub remaining: usize,
+	/// Seconds until the quota is fully available again
+	pub reset: u64,
+	/// Seconds to wait before retrying, set when the request is throttled
+	pub retry_after: Option<u64>,
+}
+
+impl RateLimitInfo {
+	/// Info for a key that is not limited
+	pub fn unlimited() -> Self {
+		Self {
+			allowed: true,
+			limit: 0,
+			remaining: 0,
+			reset: 0,
+			retry_after: None,
+		}
+	}
+
+	/// Whether the key is subject to a limit
+	pub fn is_limited(&self) -> bool {
+		self.limit > 0
+	}
+
+	/// Response headers describing the quota
+	///
+	/// `Retry-After` is only included for throttled requests, and no headers
+	/// are produced for keys that are not limited.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_throttling::RateLimitInfo;
+	///
+	/// let info = RateLimitInfo {
+	///     allowed: false,
+	///     limit: 100,
+	///     remaining: 0,
+	///     reset: 30,
+	///     retry_after: Some(30),
+	/// };
+	/// assert_eq!(
+	///     info.headers(),
+	///     vec![
+	///         ("RateLimit-Limit", "100".to_string()),
+	///         ("RateLimit-Remaining", "0".to_string()),
+	///         ("RateLimit-Reset", "30".to_string()),
+	///         ("Retry-After", "30".to_string()),
+	///     ]
+	/// );
+	/// ```
+	pub fn headers(&self) -> Vec<(&'static str, String)> {
+		if !self.is_limited() {
+			return Vec::new();
+		}
+		let mut headers = vec![
+			(RATELIMIT_LIMIT, self.limit.to_string()),
+			(RATELIMIT_REMAINING, self.remaining.to_string()),
+			(RATELIMIT_RESET, self.reset.to_string()),
+		];
+		if let Some(retry_after) = self.retry_after {
+			headers.push((RETRY_AFTER, retry_after.to_string()));
+		}
+		headers
+	}
+}
+
 #[async_trait]
 pub trait Throttle: Send + Sync {
 	async fn allow_request(&self, key: &str) -> ThrottleResult<bool>;
@@ -20,6 +104,35 @@ pub trait Throttle: Send + Sync {
 	}
 
 	fn get_rate(&self) -> (usize, u64);
+
+	/// Count a request and report the quota left for the key
+	///
+	/// The default implementation builds on [`allow_request`](Self::allow_request),
+	/// [`wait_time`](Self::wait_time) and [`get_rate`](Self::get_rate); as it
+	/// cannot see the remaining quota, it reports none left once throttled
+	/// and the full rate otherwise. Throttles backed by a counter override it
+	/// with the backend's exact figures.
+	async fn check(&self, key: &str) -> ThrottleResult<RateLimitInfo> {
+		let allowed = self.allow_request(key).await?;
+		let (limit, window) = self.get_rate();
+		if allowed {
+			return Ok(RateLimitInfo {
+				allowed,
+				limit,
+				remaining: limit,
+				reset: 0,
+				retry_after: None,
+			});
+		}
+		let retry_after = self.wait_time(key).await?.unwrap_or(window);
+		Ok(RateLimitInfo {
+			allowed,
+			limit,
+			remaining: 0,
+			reset: retry_after,
+			retry_after: Some(retry_after),
+		})
+	}
 }
 
 #[cfg(test)]
@@ -54,4 +167,26 @@ mod tests {
 			ThrottleError::RateLimitExceeded
 		));
 	}
+
+	#[tokio::test]
+	async fn test_default_check_reports_wait_time_when_throttled() {
+		struct Deny;
+
+		#[async_trait]
+		impl Throttle for Deny {
+			async fn allow_request(&self, _key: &str) -> ThrottleResult<bool> {
+				Ok(false)
+			}
+
+			fn get_rate(&self) -> (usize, u64) {
+				(10, 60)
+			}
+		}
+
+		let info = Deny.check("test_key").await.unwrap();
+		assert!(!info.allowed);
+		assert_eq!(info.retry_after, Some(60));
+		assert_eq!(info.headers().len(), 4);
+		assert!(RateLimitInfo::unlimited().headers().is_empty());
+	}
 }
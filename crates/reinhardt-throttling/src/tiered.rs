@@ -1,7 +1,7 @@
 //! Tiered rate throttling based on user level
 
 use super::backend::ThrottleBackend;
-use super::{Throttle, ThrottleError, ThrottleResult};
+use super::{RateLimitInfo, Throttle, ThrottleError, ThrottleResult};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
@@ -133,19 +133,20 @@ impl<B: ThrottleBackend> TieredRateThrottle<B> {
 	pub fn get_tier(&self, tier_name: &str) -> &Tier {
 		self.tiers.get(tier_name).unwrap_or(&self.default_tier)
 	}
+
+	/// Tier of a key in the `tier:user_id` format
+	fn tier_of(&self, key: &str) -> &Tier {
+		match key.split_once(':') {
+			Some((tier_name, _)) => self.get_tier(tier_name),
+			None => &self.default_tier,
+		}
+	}
 }
 
 #[async_trait::async_trait]
 impl<B: ThrottleBackend> Throttle for TieredRateThrottle<B> {
 	async fn allow_request(&self, key: &str) -> ThrottleResult<bool> {
-		// Extract tier from key (format: "tier:user_id")
-		let parts: Vec<&str> = key.split(':').collect();
-		let tier = if parts.len() > 1 {
-			self.get_tier(parts[0])
-		} else {
-			&self.default_tier
-		};
-
+		let tier = self.tier_of(key);
 		let backend = self.backend.lock().await;
 		let count = backend
 			.get_count(key)
@@ -171,4 +172,55 @@ impl<B: ThrottleBackend> Throttle for TieredRateThrottle<B> {
 	fn get_rate(&self) -> (usize, u64) {
 		(self.default_tier.rate, self.default_tier.duration.as_secs())
 	}
+
+	async fn check(&self, key: &str) -> ThrottleResult<RateLimitInfo> {
+		let tier = self.tier_of(key);
+		let window = tier.duration.as_secs();
+		let backend = self.backend.lock().await;
+		let count = backend
+			.get_count(key)
+			.await
+			.map_err(ThrottleError::ThrottleError)?;
+
+		let allowed = count < tier.rate;
+		if allowed {
+			backend.increment_duration(key, tier.duration).await?;
+		}
+		let quota = backend
+			.quota(key, tier.rate, window)
+			.await
+			.map_err(ThrottleError::ThrottleError)?;
+		Ok(RateLimitInfo {
+			allowed,
+			limit: tier.rate,
+			remaining: quota.remaining,
+			reset: quota.reset,
+			retry_after: (!allowed).then_some(quota.reset),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::backend::MemoryBackend;
+	use std::time::Duration;
+
+	#[tokio::test]
+	async fn test_tiered_check_uses_the_tier_of_the_key() {
+		let backend = Arc::new(Mutex::new(MemoryBackend::new()));
+		let mut throttle =
+			TieredRateThrottle::new(backend, Tier::new("free", 1, Duration::from_secs(60)));
+		throttle.add_tier(Tier::new("premium", 5, Duration::from_secs(60)));
+
+		let info = throttle.check("premium:alice").await.unwrap();
+		assert!(info.allowed);
+		assert_eq!((info.limit, info.remaining), (5, 4));
+
+		throttle.check("free:bob").await.unwrap();
+		let info = throttle.check("free:bob").await.unwrap();
+		assert!(!info.allowed);
+		assert_eq!((info.limit, info.remaining), (1, 0));
+		assert_eq!(info.retry_after, Some(info.reset));
+	}
 }
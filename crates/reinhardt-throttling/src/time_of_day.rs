@@ -3,9 +3,9 @@
 //! Allows different rate limits based on the time of day, enabling peak/off-peak
 //! rate differentiation.
 
-use super::backend::ThrottleBackend;
+use super::backend::{ThrottleBackend, check_fixed_window};
 use super::time_provider::{SystemTimeProvider, TimeProvider};
-use super::{Throttle, ThrottleError, ThrottleResult};
+use super::{RateLimitInfo, Throttle, ThrottleError, ThrottleResult};
 use async_trait::async_trait;
 use std::sync::Arc;
 
@@ -222,6 +222,11 @@ impl<B: ThrottleBackend, T: TimeProvider> Throttle for TimeOfDayThrottle<B, T> {
 		// Return peak rate as default
 		self.config.peak_rate
 	}
+
+	async fn check(&self, key: &str) -> ThrottleResult<RateLimitInfo> {
+		let (rate, period) = self.get_current_rate().await;
+		check_fixed_window(self.backend.as_ref(), key, rate, period).await
+	}
 }
 
 #[cfg(test)]
@@ -306,6 +311,25 @@ mod tests {
 		assert!(!throttle.allow_request("test_key").await.unwrap());
 	}
 
+	#[tokio::test]
+	async fn test_time_of_day_throttle_check_uses_current_rate() {
+		let backend = Arc::new(MemoryBackend::new());
+		let config = TimeOfDayConfig::new(TimeRange::new(9, 17), (5, 60), (10, 60));
+		let throttle = TimeOfDayThrottle::new(backend, config);
+		let (limit, _) = throttle.get_current_rate().await;
+
+		let info = throttle.check("test_key").await.unwrap();
+		assert!(info.allowed);
+		assert_eq!((info.limit, info.remaining), (limit, limit - 1));
+
+		for _ in 1..limit {
+			throttle.check("test_key").await.unwrap();
+		}
+		let info = throttle.check("test_key").await.unwrap();
+		assert!(!info.allowed);
+		assert_eq!(info.retry_after, Some(info.reset));
+	}
+
 	#[tokio::test]
 	async fn test_time_of_day_throttle_get_rate() {
 		let backend = Arc::new(MemoryBackend::new());
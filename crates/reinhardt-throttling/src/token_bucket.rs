@@ -6,7 +6,7 @@
 
 use super::backend::ThrottleBackend;
 use super::time_provider::{SystemTimeProvider, TimeProvider};
-use super::{Throttle, ThrottleResult};
+use super::{RateLimitInfo, Throttle, ThrottleResult};
 use async_trait::async_trait;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -316,6 +316,20 @@ impl<B: ThrottleBackend, T: TimeProvider> TokenBucket<B, T> {
 	}
 
 	/// Reset the bucket to full capacity
+	/// Seconds until the bucket holds `tokens` tokens again
+	fn seconds_until(&self, state: &BucketState, tokens: usize) -> u64 {
+		if state.tokens >= tokens {
+			return 0;
+		}
+		let intervals = (tokens - state.tokens).div_ceil(self.config.refill_rate.max(1)) as u64;
+		let elapsed = self
+			.time_provider
+			.now()
+			.duration_since(state.last_refill)
+			.as_secs();
+		(intervals * self.config.refill_interval).saturating_sub(elapsed)
+	}
+
 	pub async fn reset(&self) {
 		let mut state = self.state.write().await;
 		state.tokens = self.config.capacity;
@@ -352,6 +366,24 @@ impl<B: ThrottleBackend, T: TimeProvider> Throttle for TokenBucket<B, T> {
 	fn get_rate(&self) -> (usize, u64) {
 		(self.config.refill_rate, self.config.refill_interval)
 	}
+
+	async fn check(&self, _key: &str) -> ThrottleResult<RateLimitInfo> {
+		let per_request = self.config.tokens_per_request.max(1);
+		let mut state = self.state.write().await;
+		self.refill_tokens(&mut state);
+
+		let allowed = state.tokens >= per_request;
+		if allowed {
+			state.tokens -= per_request;
+		}
+		Ok(RateLimitInfo {
+			allowed,
+			limit: self.config.capacity / per_request,
+			remaining: state.tokens / per_request,
+			reset: self.seconds_until(&state, self.config.capacity),
+			retry_after: (!allowed).then(|| self.seconds_until(&state, per_request)),
+		})
+	}
 }
 
 #[cfg(test)]
@@ -360,6 +392,39 @@ mod tests {
 	use crate::backend::MemoryBackend;
 	use crate::time_provider::MockTimeProvider;
 
+	#[tokio::test]
+	async fn test_token_bucket_check_reports_tokens() {
+		let time_provider = Arc::new(MockTimeProvider::new(Instant::now()));
+		let backend = Arc::new(MemoryBackend::new());
+		let config = TokenBucketConfig::new(2, 1, 10, 1);
+		let throttle = TokenBucket::with_time_provider(
+			"test".to_string(),
+			backend,
+			config,
+			time_provider.clone(),
+		);
+
+		let info = throttle.check("user").await.unwrap();
+		assert_eq!(
+			info,
+			RateLimitInfo {
+				allowed: true,
+				limit: 2,
+				remaining: 1,
+				reset: 10,
+				retry_after: None,
+			}
+		);
+
+		throttle.check("user").await.unwrap();
+		time_provider.advance(Duration::from_secs(4));
+		let info = throttle.check("user").await.unwrap();
+		assert!(!info.allowed);
+		assert_eq!(info.remaining, 0);
+		assert_eq!(info.reset, 16);
+		assert_eq!(info.retry_after, Some(6));
+	}
+
 	#[tokio::test]
 	async fn test_token_bucket_basic() {
 		let backend = Arc::new(MemoryBackend::new());
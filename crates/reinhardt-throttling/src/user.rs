@@ -1,5 +1,5 @@
-use super::backend::{MemoryBackend, ThrottleBackend};
-use super::{Throttle, ThrottleResult};
+use super::backend::{MemoryBackend, ThrottleBackend, check_fixed_window};
+use super::{RateLimitInfo, Throttle, ThrottleResult};
 use async_trait::async_trait;
 
 pub struct UserRateThrottle<B: ThrottleBackend = MemoryBackend> {
@@ -90,6 +90,10 @@ impl<B: ThrottleBackend> Throttle for UserRateThrottle<B> {
 	fn get_rate(&self) -> (usize, u64) {
 		(self.rate, self.window_secs)
 	}
+	async fn check(&self, user_id: &str) -> ThrottleResult<RateLimitInfo> {
+		let key = format!("throttle:user:{}", user_id);
+		check_fixed_window(&self.backend, &key, self.rate, self.window_secs).await
+	}
 }
 
 #[cfg(test)]
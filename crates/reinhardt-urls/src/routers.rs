@@ -102,6 +102,10 @@
 //!
 //! let router = group.build();
 //! ```
+//!
+//! Routers implementing [`Router`], such as [`DefaultRouter`], group routes with
+//! [`Router::group`]; group middleware run after the router's own
+//! [`with_middleware`](DefaultRouter::with_middleware) and before the route's.
 
 pub mod cache;
 #[cfg(feature = "client-router")]
@@ -120,6 +124,7 @@ pub mod router;
 pub mod script_prefix;
 pub mod server_router;
 pub mod simple;
+mod trie;
 pub mod unified_router;
 pub mod visualization;
 
@@ -128,8 +133,9 @@ pub use reinhardt_routers_macros::path;
 
 pub use cache::RouteCache;
 pub use converters::{
-	Converter, ConverterError, ConverterResult, DateConverter, FloatConverter, IntegerConverter,
-	PathConverter, SlugConverter, UuidConverter,
+	Converter, ConverterError, ConverterResult, CustomConverter, DateConverter, FloatConverter,
+	IntegerConverter, PathConverter, RegisteredConverter, SlugConverter, UuidConverter,
+	get_converter, register_converter,
 };
 pub use helpers::{IncludedRouter, include_routes, path, re_path};
 pub use pattern::{MatchingMode, PathMatcher, PathPattern, RadixRouter, RadixRouterError};
@@ -150,14 +156,14 @@ pub use reverse::{
 	reverse_with_aho_corasick,
 };
 pub use route::Route;
-pub use route_group::{RouteGroup, RouteInfo};
+pub use route_group::{RouteGroup, RouteInfo, RouterGroup};
 pub use router::{DefaultRouter, Router};
 pub use script_prefix::{clear_script_prefix, get_script_prefix, set_script_prefix};
 pub use simple::SimpleRouter;
 // Server router (full HTTP routing implementation)
 pub use server_router::{
-	FunctionHandler, ServerRouter, clear_router, get_router, is_router_registered, register_router,
-	register_router_arc,
+	FunctionHandler, ServerRouter, TrailingSlash, clear_router, get_router, is_router_registered,
+	register_router, register_router_arc,
 };
 
 // Unified router (closure-based API combining server and client routers)
@@ -8,6 +8,11 @@
 //! - `PathConverter`: Validates and converts path parameters (with security checks)
 //! - `FloatConverter`: Validates and converts floating-point parameters
 //!
+//! Custom converters with a parse/serialize pair can be registered under a
+//! name with [`register_converter`] and used in patterns as `{<name:param>}`;
+//! [`PathPattern`](super::PathPattern) then matches and reverses the parameter
+//! with the converter.
+//!
 //! # Examples
 //!
 //! ```
@@ -31,7 +36,10 @@
 
 use chrono::NaiveDate;
 use regex::Regex;
-use std::sync::OnceLock;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
 use thiserror::Error;
 
 /// Error type for converter validation failures
@@ -558,6 +566,254 @@ impl Converter for FloatConverter {
 	}
 }
 
+type ParseFn<T> = Arc<dyn Fn(&str) -> ConverterResult<T> + Send + Sync>;
+type SerializeFn<T> = Arc<dyn Fn(&T) -> String + Send + Sync>;
+
+/// Converter built from a regex and a parse/serialize pair
+///
+/// The regex selects the path segment when matching; `parse` then decides
+/// whether the segment is valid and produces the value, and `serialize`
+/// turns a value back into a segment when reversing.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use reinhardt_urls::routers::converters::{Converter, ConverterError, CustomConverter};
+///
+/// let day = CustomConverter::new(
+///     r"[0-9]{4}-[0-9]{2}-[0-9]{2}",
+///     |s| {
+///         NaiveDate::parse_from_str(s, "%Y-%m-%d")
+///             .map_err(|e| ConverterError::InvalidFormat(e.to_string()))
+///     },
+///     |d: &NaiveDate| d.format("%Y-%m-%d").to_string(),
+/// );
+///
+/// assert!(day.validate("2024-02-29"));
+/// assert!(!day.validate("2023-02-29"));
+/// assert_eq!(day.serialize(&NaiveDate::from_ymd_opt(2024, 1, 5).unwrap()), "2024-01-05");
+/// ```
+pub struct CustomConverter<T> {
+	pattern: String,
+	parse: ParseFn<T>,
+	serialize: SerializeFn<T>,
+}
+
+impl<T> Clone for CustomConverter<T> {
+	fn clone(&self) -> Self {
+		Self {
+			pattern: self.pattern.clone(),
+			parse: self.parse.clone(),
+			serialize: self.serialize.clone(),
+		}
+	}
+}
+
+impl<T> CustomConverter<T> {
+	/// Create a converter matching `pattern` with the given parse/serialize pair
+	pub fn new<P, S>(pattern: impl Into<String>, parse: P, serialize: S) -> Self
+	where
+		P: Fn(&str) -> ConverterResult<T> + Send + Sync + 'static,
+		S: Fn(&T) -> String + Send + Sync + 'static,
+	{
+		Self {
+			pattern: pattern.into(),
+			parse: Arc::new(parse),
+			serialize: Arc::new(serialize),
+		}
+	}
+
+	/// Turn a value into a path segment
+	pub fn serialize(&self, value: &T) -> String {
+		(self.serialize)(value)
+	}
+}
+
+impl<T> Converter for CustomConverter<T> {
+	type Output = T;
+
+	fn validate(&self, value: &str) -> bool {
+		(self.parse)(value).is_ok()
+	}
+
+	fn convert(&self, value: &str) -> ConverterResult<T> {
+		(self.parse)(value)
+	}
+
+	fn pattern(&self) -> &str {
+		&self.pattern
+	}
+}
+
+/// Type-erased view of a [`CustomConverter`] held by the registry
+trait AnyConverter: Send + Sync {
+	fn pattern(&self) -> &str;
+	fn validate(&self, value: &str) -> bool;
+	fn parse_any(&self, value: &str) -> ConverterResult<Box<dyn Any>>;
+	fn serialize_any(&self, value: &dyn Any) -> Option<String>;
+}
+
+impl<T: 'static> AnyConverter for CustomConverter<T> {
+	fn pattern(&self) -> &str {
+		&self.pattern
+	}
+
+	fn validate(&self, value: &str) -> bool {
+		Converter::validate(self, value)
+	}
+
+	fn parse_any(&self, value: &str) -> ConverterResult<Box<dyn Any>> {
+		(self.parse)(value).map(|value| Box::new(value) as Box<dyn Any>)
+	}
+
+	fn serialize_any(&self, value: &dyn Any) -> Option<String> {
+		value.downcast_ref::<T>().map(|value| self.serialize(value))
+	}
+}
+
+/// A converter registered under a name with [`register_converter`]
+#[derive(Clone)]
+pub struct RegisteredConverter {
+	name: String,
+	inner: Arc<dyn AnyConverter>,
+}
+
+impl RegisteredConverter {
+	/// Name used in `{<name:param>}` patterns
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Regex selecting the path segment
+	pub fn pattern(&self) -> &str {
+		self.inner.pattern()
+	}
+
+	/// Whether a path segment parses
+	pub fn validate(&self, value: &str) -> bool {
+		self.inner.validate(value)
+	}
+
+	/// Parse a path segment into the converter's value type
+	///
+	/// # Errors
+	///
+	/// Returns `ConverterError::InvalidFormat` if the segment does not parse
+	/// or the converter does not produce a `T`.
+	pub fn parse<T: 'static>(&self, value: &str) -> ConverterResult<T> {
+		self.inner
+			.parse_any(value)?
+			.downcast::<T>()
+			.map(|value| *value)
+			.map_err(|_| self.type_mismatch::<T>())
+	}
+
+	/// Turn a value into a path segment
+	///
+	/// # Errors
+	///
+	/// Returns `ConverterError::InvalidFormat` if the converter does not
+	/// accept a `T`.
+	pub fn serialize<T: 'static>(&self, value: &T) -> ConverterResult<String> {
+		self.inner
+			.serialize_any(value)
+			.ok_or_else(|| self.type_mismatch::<T>())
+	}
+
+	fn type_mismatch<T>(&self) -> ConverterError {
+		ConverterError::InvalidFormat(format!(
+			"converter '{}' does not handle values of type {}",
+			self.name,
+			std::any::type_name::<T>()
+		))
+	}
+}
+
+/// Incremented on every registration, so cached patterns can be reparsed
+static REGISTRY_VERSION: AtomicUsize = AtomicUsize::new(0);
+
+/// Current version of the converter registry
+pub(crate) fn registry_version() -> usize {
+	REGISTRY_VERSION.load(Ordering::Acquire)
+}
+
+fn converter_registry() -> &'static RwLock<HashMap<String, RegisteredConverter>> {
+	static REGISTRY: OnceLock<RwLock<HashMap<String, RegisteredConverter>>> = OnceLock::new();
+	REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Register a converter for `{<name:param>}` path segments
+///
+/// Registering a name again replaces the previous converter, and a name of
+/// a built-in type specifier (such as `int`) overrides the built-in. Patterns
+/// pick up converters when they are created, so register converters before
+/// building routes. With the `path!` macro, declare the name as
+/// `path!("...", converters = [name])`.
+///
+/// # Errors
+///
+/// Returns `ConverterError::InvalidFormat` if `name` is not a lowercase
+/// identifier.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_urls::routers::PathPattern;
+/// use reinhardt_urls::routers::converters::{ConverterError, CustomConverter, register_converter};
+///
+/// register_converter(
+///     "hex",
+///     CustomConverter::new(
+///         "[0-9a-f]+",
+///         |s| u64::from_str_radix(s, 16).map_err(|e| ConverterError::InvalidFormat(e.to_string())),
+///         |n: &u64| format!("{:x}", n),
+///     ),
+/// )
+/// .unwrap();
+///
+/// let pattern = PathPattern::new("/colors/{<hex:rgb>}/").unwrap();
+/// assert!(pattern.is_match("/colors/ff8800/"));
+/// assert!(!pattern.is_match("/colors/orange/"));
+/// ```
+pub fn register_converter<T: 'static>(
+	name: impl Into<String>,
+	converter: CustomConverter<T>,
+) -> ConverterResult<()> {
+	let name = name.into();
+	let mut chars = name.chars();
+	let valid = chars
+		.next()
+		.is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+		&& chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+	if !valid {
+		return Err(ConverterError::InvalidFormat(format!(
+			"invalid converter name '{}'",
+			name
+		)));
+	}
+
+	let converter = RegisteredConverter {
+		name: name.clone(),
+		inner: Arc::new(converter),
+	};
+	converter_registry()
+		.write()
+		.unwrap_or_else(|e| e.into_inner())
+		.insert(name, converter);
+	REGISTRY_VERSION.fetch_add(1, Ordering::AcqRel);
+	Ok(())
+}
+
+/// Look up a registered converter by name
+pub fn get_converter(name: &str) -> Option<RegisteredConverter> {
+	converter_registry()
+		.read()
+		.unwrap_or_else(|e| e.into_inner())
+		.get(name)
+		.cloned()
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -840,4 +1096,83 @@ mod tests {
 		assert_eq!(path_conv.pattern(), r"[^/\0]+(?:/[^/\0]+)*");
 		assert_eq!(float_conv.pattern(), r"-?\d+\.?\d*");
 	}
+
+	fn register_day(name: &str) {
+		register_converter(
+			name,
+			CustomConverter::new(
+				r"[0-9]{8}",
+				|s| {
+					NaiveDate::parse_from_str(s, "%Y%m%d")
+						.map_err(|e| ConverterError::InvalidFormat(e.to_string()))
+				},
+				|d: &NaiveDate| d.format("%Y%m%d").to_string(),
+			),
+		)
+		.unwrap();
+	}
+
+	#[test]
+	fn test_registered_converter_matches_and_reverses() {
+		use crate::routers::PathPattern;
+		use std::collections::HashMap;
+
+		register_day("compact_day");
+		let pattern = PathPattern::new("/archive/{<compact_day:day>}/").unwrap();
+
+		let params = pattern.extract_params("/archive/20240229/").unwrap();
+		assert_eq!(params["day"], "20240229");
+		let day: NaiveDate = pattern
+			.converter("day")
+			.unwrap()
+			.parse(&params["day"])
+			.unwrap();
+		assert_eq!(day, NaiveDate::from_ymd_opt(2024, 2, 29).unwrap());
+
+		// Matches the regex but does not parse
+		assert!(!pattern.is_match("/archive/20230229/"));
+
+		let valid = HashMap::from([("day".to_string(), "20240101".to_string())]);
+		assert_eq!(pattern.reverse(&valid).unwrap(), "/archive/20240101/");
+		let invalid = HashMap::from([("day".to_string(), "20241301".to_string())]);
+		assert!(pattern.reverse(&invalid).is_err());
+	}
+
+	#[test]
+	fn test_registered_converter_typed_values() {
+		register_day("typed_day");
+		let converter = get_converter("typed_day").unwrap();
+
+		let day = NaiveDate::from_ymd_opt(2024, 1, 5).unwrap();
+		assert_eq!(converter.serialize(&day).unwrap(), "20240105");
+		assert!(converter.serialize(&5u32).is_err());
+		assert!(converter.parse::<u32>("20240105").is_err());
+
+		assert!(get_converter("missing_day").is_none());
+		assert!(register_day_named("Bad-Name").is_err());
+	}
+
+	fn register_day_named(name: &str) -> ConverterResult<()> {
+		register_converter(
+			name,
+			CustomConverter::new(r"[0-9]+", |_| Ok(()), |_: &()| String::new()),
+		)
+	}
+
+	#[test]
+	fn test_url_reverser_with_registered_converter() {
+		use crate::routers::UrlReverser;
+
+		register_day("reverse_day");
+		let mut reverser = UrlReverser::new();
+		reverser.register_path("archive", "/archive/{<reverse_day:day>}/");
+
+		assert_eq!(
+			reverser
+				.reverse_with("archive", &[("day", "20240105")])
+				.unwrap(),
+			"/archive/20240105/"
+		);
+		assert!(reverser.reverse_with("archive", &[("day", "jan")]).is_err());
+	}
 }
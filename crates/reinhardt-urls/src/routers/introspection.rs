@@ -28,6 +28,7 @@
 //! assert_eq!(api_routes.len(), 1);
 //! ```
 
+use crate::routers::Route;
 use crate::routers::namespace::Namespace;
 use hyper::Method;
 use serde::{Deserialize, Serialize};
@@ -54,6 +55,14 @@ pub struct RouteInfo {
 	/// Parameter names extracted from the path
 	pub params: Vec<String>,
 
+	/// Type name of the handler serving this route
+	#[serde(default)]
+	pub handler: Option<String>,
+
+	/// Type names of the middleware applied to this route, outermost first
+	#[serde(default)]
+	pub middleware: Vec<String>,
+
 	/// Additional metadata
 	pub metadata: HashMap<String, String>,
 }
@@ -116,10 +125,92 @@ impl RouteInfo {
 			namespace,
 			route_name,
 			params,
+			handler: None,
+			middleware: Vec::new(),
 			metadata: HashMap::new(),
 		}
 	}
 
+	/// Describe a registered [`Route`]
+	///
+	/// The route accepts every method, so `methods` is left empty.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_urls::routers::Route;
+	/// use reinhardt_urls::routers::introspection::RouteInfo;
+	/// use reinhardt_middleware::LoggingMiddleware;
+	/// use std::sync::Arc;
+	///
+	/// # use async_trait::async_trait;
+	/// # use reinhardt_http::{Handler, Request, Response, Result};
+	/// # struct UserDetail;
+	/// # #[async_trait]
+	/// # impl Handler for UserDetail {
+	/// #     async fn handle(&self, _req: Request) -> Result<Response> {
+	/// #         Ok(Response::ok())
+	/// #     }
+	/// # }
+	/// let route = Route::from_handler("/users/{id}/", UserDetail)
+	///     .with_name("detail")
+	///     .with_namespace("users")
+	///     .with_middleware(Arc::new(LoggingMiddleware::new()));
+	///
+	/// let info = RouteInfo::from_route(&route);
+	/// assert_eq!(info.name.as_deref(), Some("users:detail"));
+	/// assert!(info.handler.unwrap().ends_with("UserDetail"));
+	/// assert!(info.middleware[0].ends_with("LoggingMiddleware"));
+	/// ```
+	pub fn from_route(route: &Route) -> Self {
+		let mut info = Self::new(&route.path, Vec::new(), route.full_name());
+		// Keep the namespace of unnamed routes
+		if info.namespace.is_none() && route.name.is_none() {
+			info.namespace = route.namespace.clone();
+		}
+		info.handler = Some(route.handler().type_name().to_string());
+		info.middleware = route
+			.middleware
+			.iter()
+			.map(|m| m.type_name().to_string())
+			.collect();
+		info
+	}
+
+	/// Set the handler type name
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_urls::routers::introspection::RouteInfo;
+	/// use hyper::Method;
+	///
+	/// let info = RouteInfo::new("/users/", vec![Method::GET], None::<String>)
+	///     .with_handler("app::views::UserList");
+	/// assert_eq!(info.handler.as_deref(), Some("app::views::UserList"));
+	/// ```
+	pub fn with_handler(mut self, handler: impl Into<String>) -> Self {
+		self.handler = Some(handler.into());
+		self
+	}
+
+	/// Set the middleware type names, outermost first
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_urls::routers::introspection::RouteInfo;
+	/// use hyper::Method;
+	///
+	/// let info = RouteInfo::new("/users/", vec![Method::GET], None::<String>)
+	///     .with_middleware(vec!["LoggingMiddleware".to_string()]);
+	/// assert_eq!(info.middleware, vec!["LoggingMiddleware"]);
+	/// ```
+	pub fn with_middleware(mut self, middleware: Vec<String>) -> Self {
+		self.middleware = middleware;
+		self
+	}
+
 	/// Add metadata to this route
 	///
 	/// # Examples
@@ -227,17 +318,37 @@ impl RouteInspector {
 		name: Option<impl Into<String>>,
 		metadata: Option<HashMap<String, String>>,
 	) {
-		let path = path.into();
-		let mut route = RouteInfo::new(&path, methods, name);
+		let mut route = RouteInfo::new(path, methods, name);
 
 		if let Some(meta) = metadata {
 			route.metadata = meta;
 		}
 
+		self.add_route_info(route);
+	}
+
+	/// Add a fully described route to the inspector
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_urls::routers::introspection::{RouteInfo, RouteInspector};
+	/// use hyper::Method;
+	///
+	/// let mut inspector = RouteInspector::new();
+	/// inspector.add_route_info(
+	///     RouteInfo::new("/users/", vec![Method::GET], Some("users:list"))
+	///         .with_handler("app::views::UserList"),
+	/// );
+	///
+	/// let route = inspector.find_by_name("users:list").unwrap();
+	/// assert_eq!(route.handler.as_deref(), Some("app::views::UserList"));
+	/// ```
+	pub fn add_route_info(&mut self, route: RouteInfo) {
 		let index = self.routes.len();
 
 		// Index by path
-		self.path_index.insert(path.clone(), index);
+		self.path_index.insert(route.path.clone(), index);
 
 		// Index by name
 		if let Some(ref name) = route.name {
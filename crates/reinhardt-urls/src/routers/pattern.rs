@@ -1,7 +1,10 @@
+use super::converters::{RegisteredConverter, get_converter};
+use super::trie::RouteTrie;
 use aho_corasick::AhoCorasick;
 use matchit::Router as MatchitRouter;
 use regex::Regex;
 use std::collections::HashMap;
+use std::sync::OnceLock;
 
 /// Convert a type specifier to its corresponding regex pattern
 ///
@@ -23,6 +26,10 @@ use std::collections::HashMap;
 /// | `bool` | `true\|false\|1\|0` | Boolean literals |
 /// | `email` | Email regex | Email format |
 /// | `date` | `[0-9]{4}-[0-9]{2}-[0-9]{2}` | ISO 8601 date |
+///
+/// Converters registered with
+/// [`register_converter`](super::converters::register_converter) take
+/// precedence over this table.
 fn type_spec_to_regex(type_spec: &str) -> &'static str {
 	match type_spec {
 		// Basic types (legacy)
@@ -59,6 +66,10 @@ pub struct PathPattern {
 	/// Pre-built Aho-Corasick automaton for efficient URL reversal
 	/// This is constructed once during pattern creation for O(n+m+z) reversal
 	aho_corasick: Option<AhoCorasick>,
+	/// Registered converters of typed parameters, by parameter name
+	converters: Vec<(String, RegisteredConverter)>,
+	/// Whether a parameter may match across `/`
+	spans_segments: bool,
 }
 
 /// Parse result containing regex, param names, and normalized pattern for URL reversal
@@ -68,6 +79,8 @@ struct ParsePatternResult {
 	/// Pattern normalized to `{name}` format for URL reversal
 	/// e.g., "/users/{<int:id>}/" -> "/users/{id}/"
 	normalized_pattern: String,
+	converters: Vec<(String, RegisteredConverter)>,
+	spans_segments: bool,
 }
 
 impl PathPattern {
@@ -113,6 +126,8 @@ impl PathPattern {
 			regex,
 			param_names: parse_result.param_names,
 			aho_corasick,
+			converters: parse_result.converters,
+			spans_segments: parse_result.spans_segments,
 		})
 	}
 
@@ -120,6 +135,8 @@ impl PathPattern {
 		let mut regex_str = String::from("^");
 		let mut param_names = Vec::new();
 		let mut normalized_pattern = String::new();
+		let mut converters = Vec::new();
+		let mut spans_segments = false;
 		let mut chars = pattern.chars().peekable();
 
 		while let Some(ch) = chars.next() {
@@ -140,30 +157,43 @@ impl PathPattern {
 					}
 
 					// Check for typed parameter syntax: {<type:name>}
-					let (param_name, regex_pattern) =
-						if param_content.starts_with('<') && param_content.ends_with('>') {
-							// Parse {<type:name>}
-							let inner = &param_content[1..param_content.len() - 1]; // Remove < >
-							if let Some(colon_pos) = inner.find(':') {
-								let type_spec = &inner[..colon_pos];
-								let name = &inner[colon_pos + 1..];
-								if name.is_empty() {
-									return Err(format!(
-										"Empty parameter name in typed parameter: {{<{}:>}}",
-										type_spec
-									));
-								}
-								(name.to_string(), type_spec_to_regex(type_spec))
-							} else {
+					let (param_name, regex_pattern) = if param_content.starts_with('<')
+						&& param_content.ends_with('>')
+					{
+						// Parse {<type:name>}
+						let inner = &param_content[1..param_content.len() - 1]; // Remove < >
+						if let Some(colon_pos) = inner.find(':') {
+							let type_spec = &inner[..colon_pos];
+							let name = &inner[colon_pos + 1..];
+							if name.is_empty() {
 								return Err(format!(
-									"Invalid typed parameter syntax: {{<{}>}}. Expected {{<type:name>}}",
-									inner
+									"Empty parameter name in typed parameter: {{<{}:>}}",
+									type_spec
 								));
 							}
+							spans_segments |= type_spec == "path";
+							match get_converter(type_spec) {
+								Some(converter) => {
+									let regex = converter.pattern().to_string();
+									converters.push((name.to_string(), converter));
+									// Converter regexes are opaque, so assume they may match `/`
+									spans_segments = true;
+									(name.to_string(), regex)
+								}
+								None => {
+									(name.to_string(), type_spec_to_regex(type_spec).to_string())
+								}
+							}
 						} else {
-							// Simple {name} parameter - use default [^/]+
-							(param_content, "[^/]+")
-						};
+							return Err(format!(
+								"Invalid typed parameter syntax: {{<{}>}}. Expected {{<type:name>}}",
+								inner
+							));
+						}
+					} else {
+						// Simple {name} parameter - use default [^/]+
+						(param_content, "[^/]+".to_string())
+					};
 
 					param_names.push(param_name.clone());
 					regex_str.push_str(&format!("(?P<{}>{})", param_name, regex_pattern));
@@ -187,8 +217,20 @@ impl PathPattern {
 			regex_str,
 			param_names,
 			normalized_pattern,
+			converters,
+			spans_segments,
 		})
 	}
+
+	/// Pattern with typed parameters normalized to `{name}`
+	pub(crate) fn normalized_pattern(&self) -> &str {
+		&self.normalized_pattern
+	}
+
+	/// Whether a parameter may match across `/`
+	pub(crate) fn spans_segments(&self) -> bool {
+		self.spans_segments
+	}
 	/// Get the original pattern string
 	///
 	/// # Examples
@@ -228,11 +270,28 @@ impl PathPattern {
 	/// assert!(!pattern.is_match("/users/"));
 	/// ```
 	pub fn is_match(&self, path: &str) -> bool {
-		self.regex.is_match(path)
+		if self.converters.is_empty() {
+			self.regex.is_match(path)
+		} else {
+			self.extract_params(path).is_some()
+		}
+	}
+
+	/// Get the registered converter of a typed parameter
+	///
+	/// Returns `None` for untyped parameters and built-in type specifiers.
+	pub fn converter(&self, param_name: &str) -> Option<&RegisteredConverter> {
+		self.converters
+			.iter()
+			.find(|(name, _)| name == param_name)
+			.map(|(_, converter)| converter)
 	}
 
 	/// Match a path and extract parameters
 	///
+	/// Parameters with a registered converter must also parse, otherwise the
+	/// path does not match.
+	///
 	/// # Examples
 	///
 	/// ```
@@ -243,15 +302,22 @@ impl PathPattern {
 	/// assert_eq!(params.get("id"), Some(&"123".to_string()));
 	/// ```
 	pub fn extract_params(&self, path: &str) -> Option<HashMap<String, String>> {
-		self.regex.captures(path).map(|captures| {
-			let mut params = HashMap::new();
-			for name in self.param_names() {
-				if let Some(value) = captures.name(name) {
-					params.insert(name.clone(), value.as_str().to_string());
-				}
+		let captures = self.regex.captures(path)?;
+		let mut params = HashMap::new();
+		for name in self.param_names() {
+			if let Some(value) = captures.name(name) {
+				params.insert(name.clone(), value.as_str().to_string());
 			}
-			params
-		})
+		}
+		for (name, converter) in &self.converters {
+			if !params
+				.get(name)
+				.is_some_and(|value| converter.validate(value))
+			{
+				return None;
+			}
+		}
+		Some(params)
 	}
 
 	/// Reverse URL pattern with parameters using Aho-Corasick algorithm
@@ -269,7 +335,8 @@ impl PathPattern {
 	/// # Returns
 	///
 	/// * `Ok(String)` - Reversed URL with parameters substituted
-	/// * `Err(String)` - If required parameters are missing
+	/// * `Err(String)` - If required parameters are missing, or a value is
+	///   rejected by the parameter's registered converter
 	///
 	/// # Examples
 	///
@@ -293,6 +360,17 @@ impl PathPattern {
 				return Err(format!("Missing required parameter: {}", param_name));
 			}
 		}
+		for (name, converter) in &self.converters {
+			let value = &params[name];
+			if !converter.validate(value) {
+				return Err(format!(
+					"Invalid value for parameter {}: '{}' is not a valid {}",
+					name,
+					value,
+					converter.name()
+				));
+			}
+		}
 
 		// If no parameters, return normalized pattern as-is
 		if self.param_names.is_empty() {
@@ -334,16 +412,26 @@ pub enum MatchingMode {
 	Linear,
 	/// Radix Tree O(m) matching using matchit (recommended for >100 routes)
 	RadixTree,
+	/// Segment trie compiled from the patterns, confirmed with each pattern's
+	/// regex
+	///
+	/// Gives the same results as `Linear`, including typed parameters and
+	/// registration order, while only testing the patterns whose segments fit
+	/// the path.
+	Trie,
 }
 
 /// Path matcher - uses composition to match paths
 ///
-/// Supports two matching modes:
+/// Supports three matching modes:
 /// - **Linear** (default): O(n) search through patterns, suitable for <100 routes
 /// - **RadixTree**: O(m) matching using radix tree, recommended for >100 routes
+/// - **Trie**: segment trie with linear-mode semantics, for large route sets
 pub struct PathMatcher {
 	patterns: Vec<(PathPattern, String)>, // (pattern, handler_id)
 	radix_router: Option<RadixRouter>,
+	/// Compiled on first match and discarded when a pattern is added
+	trie: OnceLock<RouteTrie>,
 	mode: MatchingMode,
 }
 
@@ -362,6 +450,7 @@ impl PathMatcher {
 		Self {
 			patterns: Vec::new(),
 			radix_router: None,
+			trie: OnceLock::new(),
 			mode: MatchingMode::Linear,
 		}
 	}
@@ -383,6 +472,7 @@ impl PathMatcher {
 			} else {
 				None
 			},
+			trie: OnceLock::new(),
 			mode,
 		}
 	}
@@ -427,6 +517,35 @@ impl PathMatcher {
 	pub fn mode(&self) -> MatchingMode {
 		self.mode
 	}
+
+	/// Compile the route trie now rather than on the first match
+	///
+	/// Call once all patterns are registered, e.g. at startup, to keep the
+	/// cost off the first request. Does nothing outside `Trie` mode.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_urls::routers::{MatchingMode, PathMatcher, PathPattern};
+	///
+	/// let mut matcher = PathMatcher::with_mode(MatchingMode::Trie);
+	/// matcher.add_pattern(PathPattern::new("/users/{<int:id>}/").unwrap(), "detail".to_string());
+	/// matcher.add_pattern(PathPattern::new("/users/{slug}/").unwrap(), "by_slug".to_string());
+	/// matcher.compile();
+	///
+	/// assert_eq!(matcher.match_path("/users/42/").unwrap().0, "detail");
+	/// assert_eq!(matcher.match_path("/users/alice/").unwrap().0, "by_slug");
+	/// ```
+	pub fn compile(&self) {
+		if self.mode == MatchingMode::Trie {
+			self.compiled_trie();
+		}
+	}
+
+	fn compiled_trie(&self) -> &RouteTrie {
+		self.trie
+			.get_or_init(|| RouteTrie::build(self.patterns.iter().map(|(pattern, _)| pattern)))
+	}
 	/// Add a pattern to the matcher
 	///
 	/// If radix tree mode is enabled, also adds to the radix router.
@@ -446,6 +565,7 @@ impl PathMatcher {
 	pub fn add_pattern(&mut self, pattern: PathPattern, handler_id: String) {
 		let pattern_str = pattern.pattern().to_string();
 		self.patterns.push((pattern, handler_id.clone()));
+		self.trie = OnceLock::new();
 
 		// If radix tree mode is enabled, also add to radix router
 		if let Some(ref mut radix_router) = self.radix_router {
@@ -497,21 +617,27 @@ impl PathMatcher {
 				// Use linear O(n) matching
 				self.match_path_linear(path)
 			}
+			MatchingMode::Trie => self.match_path_trie(path),
 		}
 	}
 
+	/// Trie matching, confirming candidates in registration order
+	fn match_path_trie(&self, path: &str) -> Option<(String, HashMap<String, String>)> {
+		self.compiled_trie()
+			.candidates(path)
+			.into_iter()
+			.find_map(|index| {
+				let (pattern, handler_id) = &self.patterns[index];
+				pattern
+					.extract_params(path)
+					.map(|params| (handler_id.clone(), params))
+			})
+	}
+
 	/// Linear pattern matching (O(n))
 	fn match_path_linear(&self, path: &str) -> Option<(String, HashMap<String, String>)> {
 		for (pattern, handler_id) in &self.patterns {
-			if let Some(captures) = pattern.regex.captures(path) {
-				let mut params = HashMap::new();
-
-				for name in pattern.param_names() {
-					if let Some(value) = captures.name(name) {
-						params.insert(name.clone(), value.as_str().to_string());
-					}
-				}
-
+			if let Some(params) = pattern.extract_params(path) {
 				return Some((handler_id.clone(), params));
 			}
 		}
@@ -1052,4 +1178,53 @@ mod tests {
 			assert!(linear_result.is_some());
 		}
 	}
+
+	#[test]
+	fn test_path_matcher_linear_vs_trie() {
+		let patterns = [
+			"/users/",
+			"/users/{<int:id>}/",
+			"/users/{slug}/",
+			"/users/me/",
+			"/files/{<path:rest>}",
+			"/export/{id}.json",
+		];
+		let mut linear_matcher = PathMatcher::new();
+		let mut trie_matcher = PathMatcher::with_mode(MatchingMode::Trie);
+		for (i, pattern) in patterns.iter().enumerate() {
+			let pattern = PathPattern::new(*pattern).unwrap();
+			linear_matcher.add_pattern(pattern.clone(), format!("handler_{}", i));
+			trie_matcher.add_pattern(pattern, format!("handler_{}", i));
+		}
+
+		for path in [
+			"/users/",
+			"/users/42/",
+			"/users/me/",
+			"/files/a/b/c.txt",
+			"/export/7.json",
+			"/export/7.xml",
+			"/missing/",
+		] {
+			assert_eq!(
+				linear_matcher.match_path(path),
+				trie_matcher.match_path(path)
+			);
+		}
+		assert_eq!(
+			trie_matcher.match_path("/users/me/").unwrap().0,
+			"handler_2"
+		);
+	}
+
+	#[test]
+	fn test_path_matcher_trie_rebuilt_on_registration() {
+		let mut matcher = PathMatcher::with_mode(MatchingMode::Trie);
+		matcher.add_pattern(PathPattern::new("/users/").unwrap(), "list".to_string());
+		matcher.compile();
+		assert!(matcher.match_path("/posts/").is_none());
+
+		matcher.add_pattern(PathPattern::new("/posts/").unwrap(), "posts".to_string());
+		assert_eq!(matcher.match_path("/posts/").unwrap().0, "posts");
+	}
 }
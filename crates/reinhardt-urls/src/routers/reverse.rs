@@ -4,15 +4,40 @@
 /// This module provides both string-based (runtime) and type-safe (compile-time)
 /// URL reversal mechanisms.
 // use crate::path;
+use super::converters::registry_version;
 use super::{PathPattern, Route};
 use aho_corasick::AhoCorasick;
 use reinhardt_core::exception::{Error, Result};
 use std::collections::HashMap;
 use std::marker::PhantomData;
+use std::sync::{Arc, OnceLock, RwLock};
 
 pub type ReverseError = Error;
 pub type ReverseResult<T> = Result<T>;
 
+/// Parsed pattern with the converter registry version it was parsed against
+type CachedPattern = (usize, Arc<PathPattern>);
+
+/// Parse a static pattern once, reparsing only after converters change
+fn static_pattern(pattern: &'static str) -> ReverseResult<Arc<PathPattern>> {
+	static CACHE: OnceLock<RwLock<HashMap<&'static str, CachedPattern>>> = OnceLock::new();
+	let cache = CACHE.get_or_init(|| RwLock::new(HashMap::new()));
+
+	let version = registry_version();
+	if let Some((parsed_at, parsed)) = cache.read().unwrap().get(pattern)
+		&& *parsed_at == version
+	{
+		return Ok(parsed.clone());
+	}
+
+	let parsed = Arc::new(PathPattern::new(pattern).map_err(ReverseError::Validation)?);
+	cache
+		.write()
+		.unwrap()
+		.insert(pattern, (version, parsed.clone()));
+	Ok(parsed)
+}
+
 /// Optimized URL parameter substitution using Aho-Corasick algorithm
 ///
 /// This function uses Aho-Corasick for multi-pattern matching, allowing
@@ -189,22 +214,31 @@ pub fn reverse_single_pass(pattern: &str, params: &HashMap<String, String>) -> S
 pub struct UrlReverser {
 	/// Map of route names (including namespace) to routes
 	routes: HashMap<String, Route>,
+	/// Patterns compiled at registration, by route name
+	patterns: HashMap<String, std::result::Result<PathPattern, String>>,
 }
 
 impl UrlReverser {
 	pub fn new() -> Self {
 		Self {
 			routes: HashMap::new(),
+			patterns: HashMap::new(),
 		}
 	}
 
 	/// Register a route for reverse lookup
 	pub fn register(&mut self, route: Route) {
 		if let Some(full_name) = route.full_name() {
-			self.routes.insert(full_name, route);
+			self.insert(full_name, route);
 		}
 	}
 
+	fn insert(&mut self, name: String, route: Route) {
+		self.patterns
+			.insert(name.clone(), PathPattern::new(&route.path));
+		self.routes.insert(name, route);
+	}
+
 	/// Register a route by name and path (without handler)
 	///
 	/// This is used for hierarchical routers where we only need the name-to-path mapping
@@ -261,7 +295,7 @@ impl UrlReverser {
 			route
 		};
 
-		self.routes.insert(name.to_string(), route);
+		self.insert(name.to_string(), route);
 	}
 
 	/// Reverse a URL name to a path with parameters
@@ -303,13 +337,11 @@ impl UrlReverser {
 	/// assert_eq!(url, "/users/123/");
 	/// ```
 	pub fn reverse(&self, name: &str, params: &HashMap<String, String>) -> ReverseResult<String> {
-		let route = self
-			.routes
+		let pattern = self
+			.patterns
 			.get(name)
-			.ok_or_else(|| Error::NotFound(name.to_string()))?;
-
-		// Parse the path pattern to find parameters
-		let pattern = PathPattern::new(&route.path)
+			.ok_or_else(|| Error::NotFound(name.to_string()))?
+			.as_ref()
 			.map_err(|e| Error::Validation(format!("pattern: {}", e)))?;
 
 		// Validate all required parameters are present before substitution
@@ -319,8 +351,9 @@ impl UrlReverser {
 			}
 		}
 
-		// Use single-pass substitution algorithm: O(n+m) instead of O(n×m×p)
-		Ok(reverse_single_pass(&route.path, params))
+		// Substitute into the normalized pattern so typed parameters such as
+		// `{<int:id>}` are replaced and registered converters validate values
+		pattern.reverse(params).map_err(Error::Validation)
 	}
 
 	/// Reverse a URL name to a path with positional parameters
@@ -504,13 +537,14 @@ pub fn reverse_typed_with_params<U: UrlPatternWithParams>(
 		}
 	}
 
-	// Convert &str HashMap to String HashMap for single-pass algorithm
 	let string_params: HashMap<String, String> = params
 		.iter()
 		.map(|(k, v)| (k.to_string(), v.to_string()))
 		.collect();
 
-	Ok(reverse_single_pass(U::PATTERN, &string_params))
+	static_pattern(U::PATTERN)?
+		.reverse(&string_params)
+		.map_err(ReverseError::Validation)
 }
 
 /// Type-safe URL parameter builder
@@ -542,6 +576,7 @@ pub fn reverse_typed_with_params<U: UrlPatternWithParams>(
 pub struct UrlParams<U: UrlPatternWithParams> {
 	_phantom: PhantomData<U>,
 	params: HashMap<String, String>,
+	error: Option<ReverseError>,
 }
 
 impl<U: UrlPatternWithParams> UrlParams<U> {
@@ -550,7 +585,63 @@ impl<U: UrlPatternWithParams> UrlParams<U> {
 		Self {
 			_phantom: PhantomData,
 			params: HashMap::new(),
+			error: None,
+		}
+	}
+
+	/// Add a parameter serialized by its registered converter
+	///
+	/// The parameter must use a converter registered with
+	/// [`register_converter`](super::converters::register_converter) that
+	/// handles values of type `T`; otherwise [`build`](Self::build) fails.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// use reinhardt_urls::routers::converters::{ConverterError, CustomConverter, register_converter};
+	/// use reinhardt_urls::routers::reverse::{UrlParams, UrlPattern, UrlPatternWithParams};
+	///
+	/// register_converter(
+	///     "octal",
+	///     CustomConverter::new(
+	///         "[0-7]+",
+	///         |s| u32::from_str_radix(s, 8).map_err(|e| ConverterError::InvalidFormat(e.to_string())),
+	///         |n: &u32| format!("{:o}", n),
+	///     ),
+	/// )
+	/// .unwrap();
+	///
+	/// pub struct ModeUrl;
+	/// impl UrlPattern for ModeUrl {
+	///     const NAME: &'static str = "mode";
+	///     const PATTERN: &'static str = "/modes/{<octal:mode>}/";
+	/// }
+	/// impl UrlPatternWithParams for ModeUrl {
+	///     const PARAMS: &'static [&'static str] = &["mode"];
+	/// }
+	///
+	/// let url = UrlParams::<ModeUrl>::new().value("mode", &0o755u32).build().unwrap();
+	/// assert_eq!(url, "/modes/755/");
+	/// ```
+	pub fn value<T: 'static>(mut self, name: impl Into<String>, value: &T) -> Self {
+		let name = name.into();
+		let serialized = static_pattern(U::PATTERN).and_then(|pattern| {
+			let converter = pattern.converter(&name).ok_or_else(|| {
+				ReverseError::Validation(format!("parameter {} has no registered converter", name))
+			})?;
+			converter
+				.serialize(value)
+				.map_err(|e| ReverseError::Validation(e.to_string()))
+		});
+		match serialized {
+			Ok(serialized) => {
+				self.params.insert(name, serialized);
+			}
+			Err(e) => {
+				self.error.get_or_insert(e);
+			}
 		}
+		self
 	}
 
 	/// Add a parameter (note: parameter name is not compile-time checked currently,
@@ -562,6 +653,9 @@ impl<U: UrlPatternWithParams> UrlParams<U> {
 
 	/// Build the URL string, checking that all required parameters are present
 	pub fn build(self) -> ReverseResult<String> {
+		if let Some(error) = self.error {
+			return Err(error);
+		}
 		let params_ref: HashMap<&str, &str> = self
 			.params
 			.iter()
@@ -1190,4 +1284,31 @@ mod tests {
 		println!("  Single-pass: {:?}", single_pass_duration);
 		println!("  Aho-Corasick: {:?}", aho_corasick_duration);
 	}
+
+	#[test]
+	fn test_static_pattern_parsed_once_per_converter_version() {
+		use crate::routers::converters::{ConverterError, CustomConverter, register_converter};
+
+		let pattern = "/cached/{<cached_digit:n>}/";
+		let first = static_pattern(pattern).unwrap();
+		assert!(Arc::ptr_eq(&first, &static_pattern(pattern).unwrap()));
+		assert!(first.converter("n").is_none());
+
+		register_converter(
+			"cached_digit",
+			CustomConverter::new(
+				"[0-9]",
+				|s| {
+					s.parse::<u8>()
+						.map_err(|e| ConverterError::InvalidFormat(e.to_string()))
+				},
+				|n: &u8| n.to_string(),
+			),
+		)
+		.unwrap();
+
+		let reparsed = static_pattern(pattern).unwrap();
+		assert!(!Arc::ptr_eq(&first, &reparsed));
+		assert!(reparsed.converter("n").is_some());
+	}
 }
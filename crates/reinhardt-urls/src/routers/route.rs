@@ -1,4 +1,4 @@
-use reinhardt_http::Handler;
+use reinhardt_http::{Handler, MiddlewareChain, Request, Response, Result};
 use reinhardt_middleware::Middleware;
 use std::sync::Arc;
 
@@ -328,13 +328,34 @@ impl Route {
 	pub fn handler_arc(&self) -> Arc<dyn Handler> {
 		Arc::clone(&self.handler)
 	}
+
+	/// Handle a request through the route's middleware stack
+	///
+	/// `outer` middleware, such as the router's global middleware, run
+	/// before the route's own, which run in the order they were added.
+	pub async fn dispatch(
+		&self,
+		request: Request,
+		outer: &[Arc<dyn Middleware>],
+	) -> Result<Response> {
+		if outer.is_empty() && self.middleware.is_empty() {
+			return self.handler.handle(request).await;
+		}
+
+		let chain = outer
+			.iter()
+			.chain(&self.middleware)
+			.fold(MiddlewareChain::new(self.handler_arc()), |chain, mw| {
+				chain.with_middleware(mw.clone())
+			});
+		chain.handle(request).await
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
 	use async_trait::async_trait;
-	use reinhardt_http::{Request, Response, Result};
 
 	struct DummyHandler;
 
@@ -2,8 +2,9 @@
 //!
 //! Provides functionality to group multiple routes and apply middleware to the entire group.
 
-use crate::routers::ServerRouter;
+use crate::routers::{Route, Router, ServerRouter};
 use reinhardt_middleware::Middleware;
+use std::sync::Arc;
 
 /// Route information tuple: (path, name, namespace, methods)
 pub type RouteInfo = Vec<(String, Option<String>, Option<String>, Vec<hyper::Method>)>;
@@ -459,12 +460,130 @@ impl Default for RouteGroup {
 	}
 }
 
+/// Routes of a [`Router`] sharing a prefix and middleware
+///
+/// Created with [`Router::group`]. Routes added through the group get the
+/// group prefix prepended to their path and the group middleware prepended
+/// to their middleware stack. Nested groups inherit both, so a request runs
+/// router middleware first, then group middleware from the outermost group
+/// inwards, then the route's own.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_urls::routers::{DefaultRouter, Router, path};
+/// use reinhardt_middleware::LoggingMiddleware;
+/// # use async_trait::async_trait;
+/// # use reinhardt_http::{Handler, Request, Response, Result};
+/// # use std::sync::Arc;
+/// # struct DummyHandler;
+/// # #[async_trait]
+/// # impl Handler for DummyHandler {
+/// #     async fn handle(&self, _req: Request) -> Result<Response> {
+/// #         Ok(Response::ok())
+/// #     }
+/// # }
+/// let handler = Arc::new(DummyHandler);
+/// let mut router = DefaultRouter::new();
+///
+/// let mut api = router.group("/api").with_middleware(LoggingMiddleware::new());
+/// api.add_route(path("/users/", handler.clone()));
+/// api.group("/admin")
+///     .with_middleware(LoggingMiddleware::new())
+///     .add_route(path("/stats/", handler));
+///
+/// let routes = router.get_routes();
+/// assert_eq!(routes[0].path, "/api/users/");
+/// assert_eq!(routes[0].middleware.len(), 1);
+/// assert_eq!(routes[1].path, "/api/admin/stats/");
+/// assert_eq!(routes[1].middleware.len(), 2);
+/// ```
+pub struct RouterGroup<'a, R: Router> {
+	router: &'a mut R,
+	prefix: String,
+	namespace: Option<String>,
+	middleware: Vec<Arc<dyn Middleware>>,
+}
+
+impl<'a, R: Router> RouterGroup<'a, R> {
+	/// Create a group adding routes to `router` under `prefix`
+	pub fn new(router: &'a mut R, prefix: &str) -> Self {
+		Self {
+			router,
+			prefix: prefix.trim_end_matches('/').to_string(),
+			namespace: None,
+			middleware: Vec::new(),
+		}
+	}
+
+	/// Add middleware applied to every route of the group
+	pub fn with_middleware<M: Middleware + 'static>(mut self, middleware: M) -> Self {
+		self.middleware.push(Arc::new(middleware));
+		self
+	}
+
+	/// Set the namespace of routes added without one
+	pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+		self.namespace = Some(namespace.into());
+		self
+	}
+
+	/// Path prefix of the group, including the prefixes of parent groups
+	pub fn prefix(&self) -> &str {
+		&self.prefix
+	}
+
+	/// Middleware of the group, including those of parent groups
+	pub fn middleware(&self) -> &[Arc<dyn Middleware>] {
+		&self.middleware
+	}
+
+	/// Add a route to the router through the group
+	pub fn add_route(&mut self, mut route: Route) -> &mut Self {
+		route.path = if route.path.starts_with('/') {
+			format!("{}{}", self.prefix, route.path)
+		} else {
+			format!("{}/{}", self.prefix, route.path)
+		};
+		if route.namespace.is_none() {
+			route.namespace = self.namespace.clone();
+		}
+		route.middleware = self
+			.middleware
+			.iter()
+			.cloned()
+			.chain(route.middleware)
+			.collect();
+		self.router.add_route(route);
+		self
+	}
+
+	/// Create a nested group inheriting this group's prefix, namespace and middleware
+	pub fn group(&mut self, prefix: &str) -> RouterGroup<'_, R> {
+		let prefix = if prefix.starts_with('/') {
+			format!("{}{}", self.prefix, prefix)
+		} else {
+			format!("{}/{}", self.prefix, prefix)
+		};
+		RouterGroup {
+			router: &mut *self.router,
+			prefix: prefix.trim_end_matches('/').to_string(),
+			namespace: self.namespace.clone(),
+			middleware: self.middleware.clone(),
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use hyper::Method;
-	use reinhardt_http::{Request, Response, Result};
+	use crate::routers::{DefaultRouter, SimpleRouter, path};
+	use async_trait::async_trait;
+	use bytes::Bytes;
+	use hyper::{HeaderMap, Method, Uri, Version};
+	use reinhardt_http::{Handler, Request, Response, Result};
 	use reinhardt_middleware::LoggingMiddleware;
+	use std::sync::Mutex;
 
 	async fn test_handler(_req: Request) -> Result<Response> {
 		Ok(Response::ok())
@@ -518,6 +637,87 @@ mod tests {
 		assert_eq!(router.children_count(), 1);
 	}
 
+	#[derive(Clone, Default)]
+	struct Recorder(Arc<Mutex<Vec<&'static str>>>);
+
+	struct Record(&'static str, Recorder);
+
+	#[async_trait]
+	impl Middleware for Record {
+		async fn process(&self, request: Request, next: Arc<dyn Handler>) -> Result<Response> {
+			self.1.0.lock().unwrap().push(self.0);
+			next.handle(request).await
+		}
+	}
+
+	struct Ok200;
+
+	#[async_trait]
+	impl Handler for Ok200 {
+		async fn handle(&self, _req: Request) -> Result<Response> {
+			Ok(Response::ok())
+		}
+	}
+
+	fn request(uri: &'static str) -> Request {
+		Request::builder()
+			.method(Method::GET)
+			.uri(Uri::from_static(uri))
+			.version(Version::HTTP_11)
+			.headers(HeaderMap::new())
+			.body(Bytes::new())
+			.build()
+			.unwrap()
+	}
+
+	#[tokio::test]
+	async fn test_router_group_middleware_order() {
+		let recorder = Recorder::default();
+		let mut router = DefaultRouter::new().with_middleware(Record("global", recorder.clone()));
+		{
+			let mut api = router
+				.group("/api/")
+				.with_middleware(Record("api", recorder.clone()));
+			api.add_route(path("/health/", Arc::new(Ok200)));
+			api.group("v1")
+				.with_middleware(Record("v1", recorder.clone()))
+				.add_route(
+					path("/users/", Arc::new(Ok200))
+						.with_middleware(Arc::new(Record("route", recorder.clone()))),
+				);
+		}
+
+		let response = router.route(request("/api/v1/users/")).await.unwrap();
+		assert_eq!(response.status, 200);
+		assert_eq!(
+			*recorder.0.lock().unwrap(),
+			vec!["global", "api", "v1", "route"]
+		);
+
+		recorder.0.lock().unwrap().clear();
+		router.route(request("/api/health/")).await.unwrap();
+		assert_eq!(*recorder.0.lock().unwrap(), vec!["global", "api"]);
+	}
+
+	#[test]
+	fn test_router_group_namespace() {
+		let mut router = SimpleRouter::new();
+		router
+			.group("/users")
+			.with_namespace("users")
+			.add_route(path("/", Arc::new(Ok200)).with_name("list"))
+			.add_route(
+				path("/{id}/", Arc::new(Ok200))
+					.with_name("detail")
+					.with_namespace("people"),
+			);
+
+		let routes = router.get_routes();
+		assert_eq!(routes[0].path, "/users/");
+		assert_eq!(routes[0].full_name(), Some("users:list".to_string()));
+		assert_eq!(routes[1].full_name(), Some("people:detail".to_string()));
+	}
+
 	#[test]
 	fn test_route_group_multiple_middleware() {
 		let group = RouteGroup::new()
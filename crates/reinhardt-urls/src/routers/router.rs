@@ -1,6 +1,8 @@
-use super::{PathMatcher, PathPattern, Route};
+use super::introspection::{RouteInfo, RouteInspector};
+use super::{MatchingMode, PathMatcher, PathPattern, Route, RouterGroup};
 use async_trait::async_trait;
 use reinhardt_http::{Handler, Request, Response, Result};
+use reinhardt_middleware::Middleware;
 use reinhardt_views::viewsets::ViewSet;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -15,15 +17,33 @@ pub trait Router: Send + Sync {
 	/// Handle a request (similar to Handler::handle)
 	fn route(&self, request: Request)
 	-> impl std::future::Future<Output = Result<Response>> + Send;
+
+	/// Register routes under a common prefix and middleware
+	///
+	/// See [`RouterGroup`].
+	fn group(&mut self, prefix: &str) -> RouterGroup<'_, Self>
+	where
+		Self: Sized,
+	{
+		RouterGroup::new(self, prefix)
+	}
 }
 
 /// Default router implementation
 /// Similar to Django REST Framework's DefaultRouter and Django's URLResolver
+///
+/// Paths are matched with a segment trie compiled from the registered
+/// routes (see [`MatchingMode::Trie`]), so lookup cost does not grow with
+/// the number of routes.
 pub struct DefaultRouter {
 	routes: Vec<Route>,
 	matcher: PathMatcher,
+	/// Index of the route registered under each handler id
+	route_index: HashMap<String, usize>,
 	/// URL reverser for name-to-URL resolution
 	reverser: super::reverse::UrlReverser,
+	/// Middleware applied to every route, before route-level middleware
+	middleware: Vec<Arc<dyn Middleware>>,
 }
 
 impl DefaultRouter {
@@ -40,10 +60,36 @@ impl DefaultRouter {
 	pub fn new() -> Self {
 		Self {
 			routes: Vec::new(),
-			matcher: PathMatcher::new(),
+			matcher: PathMatcher::with_mode(MatchingMode::Trie),
+			route_index: HashMap::new(),
 			reverser: super::reverse::UrlReverser::new(),
+			middleware: Vec::new(),
 		}
 	}
+
+	/// Add middleware applied to every route of this router
+	///
+	/// Router middleware run before the middleware of the matched route.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_urls::routers::DefaultRouter;
+	/// use reinhardt_middleware::LoggingMiddleware;
+	///
+	/// let router = DefaultRouter::new()
+	///     .with_middleware(LoggingMiddleware::new());
+	/// assert_eq!(router.middleware().len(), 1);
+	/// ```
+	pub fn with_middleware<M: Middleware + 'static>(mut self, middleware: M) -> Self {
+		self.middleware.push(Arc::new(middleware));
+		self
+	}
+
+	/// Middleware applied to every route of this router
+	pub fn middleware(&self) -> &[Arc<dyn Middleware>] {
+		&self.middleware
+	}
 	/// Get a reference to the URL reverser
 	/// This allows for URL name resolution (reverse routing)
 	///
@@ -322,6 +368,85 @@ impl DefaultRouter {
 		&self.routes
 	}
 
+	/// Describe the registered routes for debugging and documentation
+	///
+	/// Each route lists its handler type and the middleware it runs through,
+	/// router middleware first. Render the result with
+	/// [`RouteVisualizer`](super::visualization::RouteVisualizer).
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_urls::routers::{DefaultRouter, Router, path};
+	/// use reinhardt_middleware::LoggingMiddleware;
+	/// use reinhardt_http::Handler;
+	/// use std::sync::Arc;
+	///
+	/// # use async_trait::async_trait;
+	/// # use reinhardt_http::{Request, Response, Result};
+	/// # struct UserList;
+	/// # #[async_trait]
+	/// # impl Handler for UserList {
+	/// #     async fn handle(&self, _req: Request) -> Result<Response> {
+	/// #         Ok(Response::ok())
+	/// #     }
+	/// # }
+	/// let mut router = DefaultRouter::new().with_middleware(LoggingMiddleware::new());
+	/// router.add_route(path("/users/", Arc::new(UserList)).with_name("list"));
+	///
+	/// let inspector = router.inspect();
+	/// let route = inspector.find_by_name("list").unwrap();
+	/// assert!(route.handler.as_deref().unwrap().ends_with("UserList"));
+	/// assert_eq!(route.middleware.len(), 1);
+	/// ```
+	pub fn inspect(&self) -> RouteInspector {
+		let router_middleware: Vec<String> = self
+			.middleware
+			.iter()
+			.map(|m| m.type_name().to_string())
+			.collect();
+
+		let mut inspector = RouteInspector::new();
+		for route in &self.routes {
+			let mut info = RouteInfo::from_route(route);
+			info.middleware
+				.splice(0..0, router_middleware.iter().cloned());
+			inspector.add_route_info(info);
+		}
+		inspector
+	}
+
+	/// Compile the route matcher ahead of the first request
+	///
+	/// The matcher is otherwise compiled lazily on the first request after a
+	/// route is registered. Call this once all routes are added, e.g. at
+	/// startup.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_urls::routers::{DefaultRouter, Router, path};
+	/// use reinhardt_http::Handler;
+	/// use std::sync::Arc;
+	///
+	/// # use async_trait::async_trait;
+	/// # use reinhardt_http::{Request, Response, Result};
+	/// # struct DummyHandler;
+	/// # #[async_trait]
+	/// # impl Handler for DummyHandler {
+	/// #     async fn handle(&self, _req: Request) -> Result<Response> {
+	/// #         Ok(Response::ok())
+	/// #     }
+	/// # }
+	/// let handler = Arc::new(DummyHandler);
+	/// let mut router = DefaultRouter::new();
+	/// router.add_route(path("/users/{id}/", handler));
+	/// router.compile();
+	/// ```
+	pub fn compile(&self) {
+		self.matcher.compile();
+	}
+
 	/// Find routes that match a namespace pattern
 	/// Used for namespace-based versioning
 	///
@@ -443,6 +568,10 @@ impl Router for DefaultRouter {
 			.unwrap_or_else(|| format!("route_{}", self.routes.len()));
 
 		self.matcher.add_pattern(pattern, handler_id.clone());
+		// The first route registered under an id handles its matches
+		self.route_index
+			.entry(handler_id)
+			.or_insert(self.routes.len());
 
 		// Register route for reverse lookup if it has a name
 		if route.full_name().is_some() || route.name.is_some() {
@@ -506,38 +635,15 @@ impl Router for DefaultRouter {
 	async fn route(&self, mut request: Request) -> Result<Response> {
 		let path = request.path().to_string();
 
-		if let Some((handler_id, params)) = self.matcher.match_path(&path) {
-			// Find the route by name or full_name
-			let route = self.routes.iter().find(|r| {
-				// Check if route name matches
-				if let Some(name) = &r.name
-					&& name == &handler_id
-				{
-					return true;
-				}
-				// Check if full_name matches
-				if let Some(full_name) = r.full_name()
-					&& full_name == handler_id
-				{
-					return true;
-				}
-				false
-			});
-
-			if let Some(route) = route {
-				// Add path parameters to request
-				request.path_params = params;
-				return route.handler().handle(request).await;
-			}
-
-			// If handler_id is in format "route_N", try to get route by index
-			if handler_id.starts_with("route_")
-				&& let Ok(index) = handler_id.strip_prefix("route_").unwrap().parse::<usize>()
-				&& let Some(route) = self.routes.get(index)
-			{
-				request.path_params = params;
-				return route.handler().handle(request).await;
-			}
+		if let Some((handler_id, params)) = self.matcher.match_path(&path)
+			&& let Some(route) = self
+				.route_index
+				.get(&handler_id)
+				.and_then(|&index| self.routes.get(index))
+		{
+			// Add path parameters to request
+			request.path_params = params;
+			return route.dispatch(request, &self.middleware).await;
 		}
 
 		Err(reinhardt_core::exception::Error::NotFound(format!(
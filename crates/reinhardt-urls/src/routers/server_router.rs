@@ -23,18 +23,20 @@
 //! - Routes are compiled lazily on first access (thread-safe with RwLock)
 //! - Parameters are extracted directly from matchit's Params
 
+use super::introspection::{self, RouteInspector};
 use super::{Route, UrlReverser};
 use async_trait::async_trait;
 use hyper::Method;
 use matchit::Router as MatchitRouter;
 use reinhardt_core::endpoint::EndpointInfo;
 use reinhardt_di::InjectionContext;
-use reinhardt_http::{Error, Handler, MiddlewareChain, Request, Response, Result};
+use reinhardt_http::{ApiVersion, Error, Handler, MiddlewareChain, Request, Response, Result};
 use reinhardt_middleware::Middleware;
 use reinhardt_views::viewsets::{Action, ViewSet};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 
+pub use self::canonical::TrailingSlash;
 pub use self::global::{
 	clear_router, get_router, is_router_registered, register_router, register_router_arc,
 };
@@ -43,6 +45,7 @@ pub use self::matching::{extract_params, path_matches};
 
 pub(crate) use self::handlers::ViewSetHandler;
 
+mod canonical;
 pub mod global;
 mod handlers;
 mod matching;
@@ -74,6 +77,9 @@ pub(crate) struct RouteMatch {
 
 	/// DI context
 	pub di_context: Option<Arc<InjectionContext>>,
+
+	/// API version requested through a versioned mount
+	pub version: Option<String>,
 }
 
 /// Unified router with hierarchical routing support
@@ -139,6 +145,9 @@ pub struct ServerRouter {
 	/// Child routers
 	children: Vec<ServerRouter>,
 
+	/// Versioned mounts in registration order, with their index in `children`
+	versions: Vec<(String, usize)>,
+
 	/// DI context
 	di_context: Option<Arc<InjectionContext>>,
 
@@ -171,6 +180,12 @@ pub struct ServerRouter {
 
 	/// Flag indicating if routes have been compiled (uses RwLock for thread-safety)
 	routes_compiled: RwLock<bool>,
+
+	/// Trailing slash handling, applied by the router serving the request
+	trailing_slash: TrailingSlash,
+
+	/// Redirect mixed-case paths to a matching lowercase path
+	lowercase_paths: bool,
 }
 
 /// Function-based route
@@ -256,6 +271,7 @@ impl ServerRouter {
 			functions: Vec::new(),
 			views: Vec::new(),
 			children: Vec::new(),
+			versions: Vec::new(),
 			di_context: None,
 			middleware: Vec::new(),
 			reverser: UrlReverser::new(),
@@ -267,6 +283,8 @@ impl ServerRouter {
 			head_router: RwLock::new(MatchitRouter::new()),
 			options_router: RwLock::new(MatchitRouter::new()),
 			routes_compiled: RwLock::new(false),
+			trailing_slash: TrailingSlash::default(),
+			lowercase_paths: false,
 		}
 	}
 
@@ -319,6 +337,66 @@ impl ServerRouter {
 		self
 	}
 
+	/// Set how paths differing from a route only by a trailing slash are
+	/// handled
+	///
+	/// Applies when this router serves the request directly; the setting of
+	/// mounted children is ignored.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_urls::routers::{ServerRouter, TrailingSlash};
+	/// use reinhardt_http::{Handler, Request, Response};
+	/// use hyper::{Method, StatusCode};
+	///
+	/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+	/// let router = ServerRouter::new()
+	///     .with_trailing_slash(TrailingSlash::Redirect)
+	///     .function("/users/", Method::GET, |_req| async { Ok(Response::ok()) });
+	///
+	/// let request = Request::builder().uri("/users?page=2").build().unwrap();
+	/// let response = router.handle(request).await.unwrap();
+	/// assert_eq!(response.status, StatusCode::MOVED_PERMANENTLY);
+	/// assert_eq!(response.headers["location"], "/users/?page=2");
+	/// # });
+	/// ```
+	pub fn with_trailing_slash(mut self, trailing_slash: TrailingSlash) -> Self {
+		self.trailing_slash = trailing_slash;
+		self
+	}
+
+	/// Get the trailing slash handling of this router
+	pub fn trailing_slash(&self) -> TrailingSlash {
+		self.trailing_slash
+	}
+
+	/// Redirect paths with uppercase letters to their lowercase form when
+	/// only the lowercase form matches a route
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_urls::routers::ServerRouter;
+	/// use reinhardt_http::{Handler, Request, Response};
+	/// use hyper::{Method, StatusCode};
+	///
+	/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+	/// let router = ServerRouter::new()
+	///     .with_lowercase_paths(true)
+	///     .function("/users/", Method::GET, |_req| async { Ok(Response::ok()) });
+	///
+	/// let request = Request::builder().uri("/Users/").build().unwrap();
+	/// let response = router.handle(request).await.unwrap();
+	/// assert_eq!(response.status, StatusCode::MOVED_PERMANENTLY);
+	/// assert_eq!(response.headers["location"], "/users/");
+	/// # });
+	/// ```
+	pub fn with_lowercase_paths(mut self, enabled: bool) -> Self {
+		self.lowercase_paths = enabled;
+		self
+	}
+
 	/// Add middleware to this router
 	///
 	/// # Examples
@@ -409,6 +487,102 @@ impl ServerRouter {
 		self.children.push(child);
 	}
 
+	/// Mount a router for an API version at `/{version}/`
+	///
+	/// Paths under a version that its router does not define fall back to the
+	/// routers of previously registered versions, newest first. The requested
+	/// version is stored in the request extensions as [`ApiVersion`] for both
+	/// direct and fallback matches. The version router's namespace defaults to
+	/// the version name.
+	///
+	/// # Panics
+	///
+	/// Panics if `version` is empty or has already been mounted on this router.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use reinhardt_urls::routers::ServerRouter;
+	/// use hyper::Method;
+	/// # use reinhardt_http::{Request, Response, Result};
+	///
+	/// # async fn list_users(_req: Request) -> Result<Response> {
+	/// #     Ok(Response::ok())
+	/// # }
+	/// # async fn list_orders(_req: Request) -> Result<Response> {
+	/// #     Ok(Response::ok())
+	/// # }
+	/// let v1 = ServerRouter::new()
+	///     .function("/users/", Method::GET, list_users)
+	///     .function("/orders/", Method::GET, list_orders);
+	/// // Only redefines /users/; /v2/orders/ is served by v1
+	/// let v2 = ServerRouter::new().function("/users/", Method::GET, list_users);
+	///
+	/// let router = ServerRouter::new()
+	///     .with_prefix("/api")
+	///     .version("v1", v1)
+	///     .version("v2", v2);
+	///
+	/// assert_eq!(router.versions(), vec!["v1", "v2"]);
+	/// ```
+	pub fn version(mut self, version: &str, mut router: ServerRouter) -> Self {
+		let version = version.trim_matches('/');
+		if version.is_empty() {
+			panic!(
+				"API version cannot be an empty string. \
+				 Use a name such as 'v1' instead."
+			);
+		}
+		if self.versions.iter().any(|(mounted, _)| mounted == version) {
+			panic!(
+				"API version '{}' is already mounted on this router. \
+				 Each version can only be registered once.",
+				version
+			);
+		}
+
+		router.prefix = format!("/{}/", version);
+		if router.namespace.is_none() {
+			router.namespace = Some(version.to_string());
+		}
+		if router.di_context.is_none() {
+			router.di_context = self.di_context.clone();
+		}
+
+		self.versions
+			.push((version.to_string(), self.children.len()));
+		self.children.push(router);
+		self
+	}
+
+	/// Get the mounted API versions in registration order
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use reinhardt_urls::routers::ServerRouter;
+	///
+	/// let router = ServerRouter::new().version("v1", ServerRouter::new());
+	/// assert_eq!(router.versions(), vec!["v1"]);
+	/// ```
+	pub fn versions(&self) -> Vec<&str> {
+		self.versions
+			.iter()
+			.map(|(version, _)| version.as_str())
+			.collect()
+	}
+
+	/// Child routers that are not versioned mounts
+	///
+	/// Versioned mounts are resolved through [`Self::resolve_versioned`] only.
+	fn unversioned_children(&self) -> impl Iterator<Item = &ServerRouter> {
+		self.children
+			.iter()
+			.enumerate()
+			.filter(|(index, _)| !self.versions.iter().any(|(_, mount)| mount == index))
+			.map(|(_, child)| child)
+	}
+
 	/// Add multiple child routers at once
 	///
 	/// # Examples
@@ -892,6 +1066,20 @@ impl ServerRouter {
 		self
 	}
 
+	/// Matchit router holding the routes of `method`
+	fn method_router(&self, method: &Method) -> &RwLock<MatchitRouter<RouteHandler>> {
+		match *method {
+			Method::GET => &self.get_router,
+			Method::POST => &self.post_router,
+			Method::PUT => &self.put_router,
+			Method::DELETE => &self.delete_router,
+			Method::PATCH => &self.patch_router,
+			Method::HEAD => &self.head_router,
+			Method::OPTIONS => &self.options_router,
+			_ => &self.get_router,
+		}
+	}
+
 	/// Compile all routes into matchit routers
 	///
 	/// This should be called after all routes have been registered.
@@ -914,17 +1102,8 @@ impl ServerRouter {
 			};
 
 			// matchit uses {name} format which matches our pattern
-			let router_lock = match func_route.method {
-				Method::GET => &self.get_router,
-				Method::POST => &self.post_router,
-				Method::PUT => &self.put_router,
-				Method::DELETE => &self.delete_router,
-				Method::PATCH => &self.patch_router,
-				Method::HEAD => &self.head_router,
-				Method::OPTIONS => &self.options_router,
-				_ => &self.get_router,
-			};
-			let _ = router_lock
+			let _ = self
+				.method_router(&func_route.method)
 				.write()
 				.expect("RwLock poisoned")
 				.insert(&func_route.path, route_handler);
@@ -982,82 +1161,21 @@ impl ServerRouter {
 				format!("{}/{}", self.prefix, prefix.trim_start_matches('/'))
 			};
 
-			// Collection route: GET /prefix/ (list), POST /prefix/ (create)
-			let collection_path = format!("{}/", base_path.trim_end_matches('/'));
-
-			// List action (GET)
-			let list_handler = RouteHandler {
-				handler: Arc::new(ViewSetHandler {
-					viewset: viewset.clone(),
-					action: Action::list(),
-				}),
-				middleware: Vec::new(),
-			};
-			let _ = self
-				.get_router
-				.write()
-				.expect("RwLock poisoned")
-				.insert(&collection_path, list_handler);
-
-			// Create action (POST)
-			let create_handler = RouteHandler {
-				handler: Arc::new(ViewSetHandler {
-					viewset: viewset.clone(),
-					action: Action::create(),
-				}),
-				middleware: Vec::new(),
-			};
-			let _ = self
-				.post_router
-				.write()
-				.expect("RwLock poisoned")
-				.insert(&collection_path, create_handler);
-
-			// Detail routes: GET/PUT/DELETE /prefix/{id}/
-			let lookup_field = viewset.get_lookup_field();
-			let detail_path = format!("{}/{{{}}}/", base_path.trim_end_matches('/'), lookup_field);
-
-			// Retrieve action (GET)
-			let retrieve_handler = RouteHandler {
-				handler: Arc::new(ViewSetHandler {
-					viewset: viewset.clone(),
-					action: Action::retrieve(),
-				}),
-				middleware: Vec::new(),
-			};
-			let _ = self
-				.get_router
-				.write()
-				.expect("RwLock poisoned")
-				.insert(&detail_path, retrieve_handler);
-
-			// Update action (PUT)
-			let update_handler = RouteHandler {
-				handler: Arc::new(ViewSetHandler {
-					viewset: viewset.clone(),
-					action: Action::update(),
-				}),
-				middleware: Vec::new(),
-			};
-			let _ = self
-				.put_router
-				.write()
-				.expect("RwLock poisoned")
-				.insert(&detail_path, update_handler);
-
-			// Destroy action (DELETE)
-			let destroy_handler = RouteHandler {
-				handler: Arc::new(ViewSetHandler {
-					viewset: viewset.clone(),
-					action: Action::destroy(),
-				}),
-				middleware: Vec::new(),
-			};
-			let _ = self
-				.delete_router
-				.write()
-				.expect("RwLock poisoned")
-				.insert(&detail_path, destroy_handler);
+			// Register a route for each action the ViewSet implements
+			for (path, method, action) in viewset_routes(&base_path, viewset.as_ref()) {
+				let handler = RouteHandler {
+					handler: Arc::new(ViewSetHandler {
+						viewset: viewset.clone(),
+						action,
+					}),
+					middleware: Vec::new(),
+				};
+				let _ = self
+					.method_router(&method)
+					.write()
+					.expect("RwLock poisoned")
+					.insert(&path, handler);
+			}
 		}
 
 		// Mark routes as compiled
@@ -1148,24 +1266,15 @@ impl ServerRouter {
 			));
 		}
 
-		// Collect ViewSet routes
-		for prefix in self.viewsets.keys() {
+		// Collect ViewSet routes (same paths as compile_routes registers)
+		for (prefix, viewset) in &self.viewsets {
 			let base_path = if self.prefix.is_empty() {
-				format!("/{}", prefix)
+				format!("/{}", prefix.trim_start_matches('/'))
 			} else {
-				format!("{}/{}", self.prefix, prefix)
+				format!("{}/{}", self.prefix, prefix.trim_start_matches('/'))
 			};
 
-			// ViewSets generate standard CRUD routes
-			let viewset_routes = vec![
-				(format!("{}/", base_path), vec![Method::GET, Method::POST]),
-				(
-					format!("{}/<id>/", base_path),
-					vec![Method::GET, Method::PUT, Method::DELETE],
-				),
-			];
-
-			for (path, methods) in viewset_routes {
+			for (path, methods) in viewset_route_methods(&base_path, viewset.as_ref()) {
 				routes.push((
 					path,
 					None,                   // ViewSet routes don't have individual names
@@ -1208,6 +1317,130 @@ impl ServerRouter {
 		routes
 	}
 
+	/// Describe every route of this router and its children
+	///
+	/// Unlike [`get_all_routes`](Self::get_all_routes), each entry carries the
+	/// full route name, the handler type and the middleware applied to the
+	/// route (router middleware first, then route middleware).
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_urls::routers::ServerRouter;
+	/// use reinhardt_middleware::LoggingMiddleware;
+	/// use hyper::Method;
+	/// # use reinhardt_http::{Request, Response, Result};
+	///
+	/// async fn list_users(_req: Request) -> Result<Response> {
+	///     Ok(Response::ok())
+	/// }
+	///
+	/// let users = ServerRouter::new()
+	///     .with_namespace("users")
+	///     .function_named("/", Method::GET, "list", list_users);
+	/// let router = ServerRouter::new()
+	///     .with_prefix("/api")
+	///     .with_middleware(LoggingMiddleware::new())
+	///     .mount("/users/", users);
+	///
+	/// let inspector = router.inspect();
+	/// let route = inspector.find_by_name("users:list").unwrap();
+	/// assert_eq!(route.path, "/api/users/");
+	/// assert!(route.handler.as_deref().unwrap().ends_with("list_users"));
+	/// assert!(route.middleware[0].ends_with("LoggingMiddleware"));
+	/// ```
+	pub fn inspect(&self) -> RouteInspector {
+		let mut inspector = RouteInspector::new();
+		self.collect_route_info("", None, &[], &mut inspector);
+		inspector
+	}
+
+	fn collect_route_info(
+		&self,
+		parent_prefix: &str,
+		parent_namespace: Option<&str>,
+		parent_middleware: &[String],
+		inspector: &mut RouteInspector,
+	) {
+		let prefix = join_paths(parent_prefix, &self.prefix);
+		let namespace = self.get_full_namespace(parent_namespace);
+		let mut middleware = parent_middleware.to_vec();
+		middleware.extend(self.middleware.iter().map(|m| m.type_name().to_string()));
+
+		let describe = |path: &str,
+		                methods: Vec<Method>,
+		                name: Option<&String>,
+		                namespace: Option<String>,
+		                handler: String,
+		                route_middleware: &[Arc<dyn Middleware>]| {
+			let full_name = match (&namespace, name) {
+				(Some(ns), Some(name)) => Some(format!("{}:{}", ns, name)),
+				(None, Some(name)) => Some(name.clone()),
+				_ => None,
+			};
+			let mut info =
+				introspection::RouteInfo::new(join_paths(&prefix, path), methods, full_name);
+			if name.is_none() {
+				info.namespace = namespace;
+			}
+			let mut middleware = middleware.clone();
+			middleware.extend(route_middleware.iter().map(|m| m.type_name().to_string()));
+			info.with_handler(handler).with_middleware(middleware)
+		};
+
+		for route in &self.routes {
+			inspector.add_route_info(describe(
+				&route.path,
+				Vec::new(),
+				route.name.as_ref(),
+				route.namespace.clone().or_else(|| namespace.clone()),
+				route.handler().type_name().to_string(),
+				&route.middleware,
+			));
+		}
+
+		for func_route in &self.functions {
+			inspector.add_route_info(describe(
+				&func_route.path,
+				vec![func_route.method.clone()],
+				func_route.name.as_ref(),
+				namespace.clone(),
+				func_route.handler.type_name().to_string(),
+				&func_route.middleware,
+			));
+		}
+
+		for view_route in &self.views {
+			inspector.add_route_info(describe(
+				&view_route.path,
+				Vec::new(),
+				view_route.name.as_ref(),
+				namespace.clone(),
+				view_route.handler.type_name().to_string(),
+				&view_route.middleware,
+			));
+		}
+
+		for (viewset_prefix, viewset) in &self.viewsets {
+			let base_path = format!("/{}", viewset_prefix.trim_matches('/'));
+			let handler = format!("ViewSet({})", viewset.get_basename());
+			for (path, methods) in viewset_route_methods(&base_path, viewset.as_ref()) {
+				inspector.add_route_info(describe(
+					&path,
+					methods,
+					None,
+					namespace.clone(),
+					handler.clone(),
+					&[],
+				));
+			}
+		}
+
+		for child in &self.children {
+			child.collect_route_info(&prefix, namespace.as_deref(), &middleware, inspector);
+		}
+	}
+
 	/// Get the fully qualified namespace for this router
 	///
 	/// Returns the complete namespace chain from root to this router.
@@ -1374,7 +1607,7 @@ impl ServerRouter {
 	/// 1. Check prefix match
 	/// 2. Try child routers first (depth-first search)
 	/// 3. Try own routes
-	fn resolve(&self, path: &str, method: &Method) -> Option<RouteMatch> {
+	fn resolve(&self, path: &str, method: &Method, toggle_slash: bool) -> Option<RouteMatch> {
 		// 1. Check prefix
 		let remaining_path = if !self.prefix.is_empty() {
 			let stripped = path.strip_prefix(&self.prefix)?;
@@ -1384,17 +1617,81 @@ impl ServerRouter {
 			path
 		};
 
-		// 2. Try child routers first
-		for child in &self.children {
-			if let Some(route_match) =
-				child.resolve_internal(remaining_path, method, &self.middleware, &self.di_context)
-			{
+		// 2. Try versioned mounts, falling back to earlier versions
+		if let Some(route_match) = self.resolve_versioned(
+			remaining_path,
+			method,
+			toggle_slash,
+			&self.middleware,
+			&self.di_context,
+		) {
+			return Some(route_match);
+		}
+
+		// 3. Try child routers
+		for child in self.unversioned_children() {
+			if let Some(route_match) = child.resolve_internal(
+				remaining_path,
+				method,
+				toggle_slash,
+				&self.middleware,
+				&self.di_context,
+			) {
 				return Some(route_match);
 			}
 		}
 
-		// 3. Try own routes
-		self.match_own_routes(remaining_path, method)
+		// 4. Try own routes
+		self.match_own_routes(remaining_path, method, toggle_slash)
+	}
+
+	/// Resolve a path under a versioned mount
+	///
+	/// Tries the requested version's router, then earlier versions newest
+	/// first, and tags the match with the requested version.
+	fn resolve_versioned(
+		&self,
+		path: &str,
+		method: &Method,
+		toggle_slash: bool,
+		parent_middleware: &[Arc<dyn Middleware>],
+		parent_di: &Option<Arc<InjectionContext>>,
+	) -> Option<RouteMatch> {
+		let (requested, candidates) = self.version_candidates(path)?;
+		candidates.into_iter().find_map(|(router, candidate)| {
+			let mut route_match = router.resolve_internal(
+				&candidate,
+				method,
+				toggle_slash,
+				parent_middleware,
+				parent_di,
+			)?;
+			route_match.version = Some(requested.to_string());
+			Some(route_match)
+		})
+	}
+
+	/// Find the version requested by `path` and the routers that may serve it
+	///
+	/// Returns the requested version with its own router and every earlier
+	/// version's router, newest first, each paired with `path` rewritten to
+	/// that router's prefix.
+	fn version_candidates(&self, path: &str) -> Option<(&str, Vec<(&ServerRouter, String)>)> {
+		let position = self.versions.iter().position(|(version, _)| {
+			path.strip_prefix('/')
+				.and_then(|p| p.strip_prefix(version.as_str()))
+				.is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+		})?;
+		let (requested, _) = &self.versions[position];
+		let rest = &path[requested.len() + 1..];
+		let rest = rest.strip_prefix('/').unwrap_or(rest);
+
+		let candidates = self.versions[..=position]
+			.iter()
+			.rev()
+			.map(|(version, index)| (&self.children[*index], format!("/{}/{}", version, rest)))
+			.collect();
+		Some((requested.as_str(), candidates))
 	}
 
 	/// Internal route resolution with middleware and DI context inheritance
@@ -1402,6 +1699,7 @@ impl ServerRouter {
 		&self,
 		path: &str,
 		method: &Method,
+		toggle_slash: bool,
 		parent_middleware: &[Arc<dyn Middleware>],
 		parent_di: &Option<Arc<InjectionContext>>,
 	) -> Option<RouteMatch> {
@@ -1422,24 +1720,51 @@ impl ServerRouter {
 		// Inherit DI context
 		let di_context = self.di_context.clone().or_else(|| parent_di.clone());
 
+		// Try versioned mounts, falling back to earlier versions
+		if let Some(route_match) = self.resolve_versioned(
+			remaining_path,
+			method,
+			toggle_slash,
+			&middleware_stack,
+			&di_context,
+		) {
+			return Some(route_match);
+		}
+
 		// Try child routers
-		for child in &self.children {
-			if let Some(route_match) =
-				child.resolve_internal(remaining_path, method, &middleware_stack, &di_context)
-			{
+		for child in self.unversioned_children() {
+			if let Some(route_match) = child.resolve_internal(
+				remaining_path,
+				method,
+				toggle_slash,
+				&middleware_stack,
+				&di_context,
+			) {
 				return Some(route_match);
 			}
 		}
 
 		// Try own routes
-		self.match_own_routes_with_context(remaining_path, method, middleware_stack, di_context)
+		self.match_own_routes_with_context(
+			remaining_path,
+			method,
+			toggle_slash,
+			middleware_stack,
+			di_context,
+		)
 	}
 
 	/// Match routes in this router (without context)
-	fn match_own_routes(&self, path: &str, method: &Method) -> Option<RouteMatch> {
+	fn match_own_routes(
+		&self,
+		path: &str,
+		method: &Method,
+		toggle_slash: bool,
+	) -> Option<RouteMatch> {
 		self.match_own_routes_with_context(
 			path,
 			method,
+			toggle_slash,
 			self.middleware.clone(),
 			self.di_context.clone(),
 		)
@@ -1453,6 +1778,7 @@ impl ServerRouter {
 		&self,
 		path: &str,
 		method: &Method,
+		toggle_slash: bool,
 		middleware_stack: Vec<Arc<dyn Middleware>>,
 		di_context: Option<Arc<InjectionContext>>,
 	) -> Option<RouteMatch> {
@@ -1469,22 +1795,13 @@ impl ServerRouter {
 		};
 
 		// Use matchit to find matching route - O(m) complexity
-		let router_lock = match *method {
-			Method::GET => &self.get_router,
-			Method::POST => &self.post_router,
-			Method::PUT => &self.put_router,
-			Method::DELETE => &self.delete_router,
-			Method::PATCH => &self.patch_router,
-			Method::HEAD => &self.head_router,
-			Method::OPTIONS => &self.options_router,
-			_ => &self.get_router,
-		};
-
-		let router = router_lock.read().expect("RwLock poisoned");
+		let router = self.method_router(method).read().expect("RwLock poisoned");
 
 		// Try matching with the original path first
 		// If that fails, try with trailing slash toggled (Django-style APPEND_SLASH behavior)
-		let paths_to_try = if search_path.ends_with('/') {
+		let paths_to_try = if !toggle_slash {
+			vec![search_path.clone()]
+		} else if search_path.ends_with('/') {
 			// Path has trailing slash, try without if not found
 			let without_slash = search_path.trim_end_matches('/').to_string();
 			let without_slash = if without_slash.is_empty() {
@@ -1518,6 +1835,7 @@ impl ServerRouter {
 					params,
 					middleware_stack: combined_middleware,
 					di_context,
+					version: None,
 				});
 			}
 		}
@@ -1525,11 +1843,40 @@ impl ServerRouter {
 		None
 	}
 
+	/// Find the canonical form of a path that matched no route
+	///
+	/// Candidates are the path with its trailing slash toggled (in
+	/// `TrailingSlash::Redirect` mode) and its lowercase form (when lowercase
+	/// paths are enabled); the first one that matches a route is returned.
+	fn canonical_path(&self, path: &str, method: &Method) -> Option<String> {
+		let redirect_slash = self.trailing_slash == TrailingSlash::Redirect;
+		let toggle_slash = self.trailing_slash == TrailingSlash::Lenient;
+
+		let mut candidates = Vec::new();
+		if redirect_slash {
+			candidates.extend(canonical::toggle_trailing_slash(path));
+		}
+		if self.lowercase_paths && path.chars().any(|c| c.is_uppercase()) {
+			let lowercase = path.to_lowercase();
+			let toggled = if redirect_slash {
+				canonical::toggle_trailing_slash(&lowercase)
+			} else {
+				None
+			};
+			candidates.push(lowercase);
+			candidates.extend(toggled);
+		}
+
+		candidates
+			.into_iter()
+			.find(|candidate| self.resolve(candidate, method, toggle_slash).is_some())
+	}
+
 	/// Check if a path exists in any HTTP method's router
 	///
 	/// This is used to determine whether to return 404 (path not found)
 	/// or 405 (method not allowed) when a route doesn't match.
-	fn path_exists_for_any_method(&self, path: &str) -> bool {
+	fn path_exists_for_any_method(&self, path: &str, toggle_slash: bool) -> bool {
 		self.compile_routes();
 
 		// Apply prefix stripping logic (same as resolve method)
@@ -1556,7 +1903,9 @@ impl ServerRouter {
 		};
 
 		// Build paths to try with trailing slash toggled (Django-style APPEND_SLASH)
-		let paths_to_try = if search_path.ends_with('/') {
+		let paths_to_try = if !toggle_slash {
+			vec![search_path.clone()]
+		} else if search_path.ends_with('/') {
 			let without_slash = search_path.trim_end_matches('/').to_string();
 			let without_slash = if without_slash.is_empty() {
 				"/".to_string()
@@ -1588,18 +1937,65 @@ impl ServerRouter {
 		}
 
 		// Also check children routers with remaining path
-		for child in &self.children {
+		for child in self.unversioned_children() {
 			for try_path in &paths_to_try {
-				if child.path_exists_for_any_method(try_path) {
+				if child.path_exists_for_any_method(try_path, toggle_slash) {
 					return true;
 				}
 			}
 		}
 
+		// And the earlier versions a versioned path falls back to
+		if let Some((_, candidates)) = self.version_candidates(&search_path) {
+			return candidates.iter().any(|(router, candidate)| {
+				router.path_exists_for_any_method(candidate, toggle_slash)
+			});
+		}
+
 		false
 	}
 }
 
+/// Path, method and action of each standard action a ViewSet implements
+fn viewset_routes(base_path: &str, viewset: &dyn ViewSet) -> Vec<(String, Method, Action)> {
+	let base_path = base_path.trim_end_matches('/');
+	viewset
+		.get_actions()
+		.into_iter()
+		.filter_map(|action| {
+			let method = action.method()?;
+			let path = if action.detail {
+				format!("{}/{{{}}}/", base_path, viewset.get_lookup_field())
+			} else {
+				format!("{}/", base_path)
+			};
+			Some((path, method, action))
+		})
+		.collect()
+}
+
+/// ViewSet routes grouped by path, collection route first
+fn viewset_route_methods(base_path: &str, viewset: &dyn ViewSet) -> Vec<(String, Vec<Method>)> {
+	let mut grouped: Vec<(String, Vec<Method>)> = Vec::new();
+	for (path, method, _) in viewset_routes(base_path, viewset) {
+		match grouped.iter_mut().find(|(existing, _)| *existing == path) {
+			Some((_, methods)) => methods.push(method),
+			None => grouped.push((path, vec![method])),
+		}
+	}
+	grouped.sort_by_key(|(path, _)| path.contains('{'));
+	grouped
+}
+
+/// Join two path fragments without doubling the `/` between them
+fn join_paths(prefix: &str, path: &str) -> String {
+	match (prefix.ends_with('/'), path.starts_with('/')) {
+		(true, true) => format!("{}{}", prefix, &path[1..]),
+		(false, false) if !prefix.is_empty() && !path.is_empty() => format!("{}/{}", prefix, path),
+		_ => format!("{}{}", prefix, path),
+	}
+}
+
 impl Default for ServerRouter {
 	fn default() -> Self {
 		Self::new()
@@ -1614,12 +2010,21 @@ impl Handler for ServerRouter {
 		let method = &req.method;
 
 		// Resolve route with HTTP method for matchit routing
-		let route_match = match self.resolve(path, method) {
+		let toggle_slash = self.trailing_slash == TrailingSlash::Lenient;
+		let route_match = match self.resolve(path, method, toggle_slash) {
 			Some(m) => m,
 			None => {
+				if let Some(location) = self.canonical_path(path, method) {
+					let location = match req.uri.query() {
+						Some(query) => format!("{}?{}", location, query),
+						None => location,
+					};
+					return Ok(canonical::redirect(method, &location));
+				}
+
 				// Route not found for this method
 				// Check if path exists for any other method to determine 404 vs 405
-				if self.path_exists_for_any_method(path) {
+				if self.path_exists_for_any_method(path, toggle_slash) {
 					return Err(Error::MethodNotAllowed(format!(
 						"Method {} not allowed for {}",
 						method, path
@@ -1640,6 +2045,11 @@ impl Handler for ServerRouter {
 			req.set_di_context(di_ctx.clone());
 		}
 
+		// Expose the version requested through a versioned mount
+		if let Some(version) = route_match.version {
+			req.extensions.insert(ApiVersion(version));
+		}
+
 		// Apply middleware stack using MiddlewareChain
 		if route_match.middleware_stack.is_empty() {
 			// No middleware, execute handler directly
@@ -1886,7 +2296,7 @@ mod tests {
 		// Test matching performance (should be O(m) where m = path length)
 		let start = Instant::now();
 		for _ in 0..10000 {
-			let result = router.match_own_routes("/api/resource500/action", &Method::GET);
+			let result = router.match_own_routes("/api/resource500/action", &Method::GET, true);
 			assert!(result.is_some());
 		}
 		let elapsed = start.elapsed();
@@ -1900,6 +2310,231 @@ mod tests {
 		);
 	}
 
+	async fn canonical_test_response(
+		router: &ServerRouter,
+		method: Method,
+		uri: &str,
+	) -> Result<Response> {
+		let request = Request::builder().method(method).uri(uri).build().unwrap();
+		router.handle(request).await
+	}
+
+	#[tokio::test]
+	async fn test_version_fallback_exposes_requested_version() {
+		async fn v1_handler(req: Request) -> Result<Response> {
+			let version = req.extensions.get::<ApiVersion>().unwrap();
+			Ok(Response::ok().with_body(format!("v1 handler, {}", version)))
+		}
+		async fn v2_handler(req: Request) -> Result<Response> {
+			let version = req.extensions.get::<ApiVersion>().unwrap();
+			Ok(Response::ok().with_body(format!("v2 handler, {}", version)))
+		}
+
+		let v1 = ServerRouter::new()
+			.function("/users/", Method::GET, v1_handler)
+			.function("/orders/", Method::GET, v1_handler);
+		let v2 = ServerRouter::new()
+			.function("/users/", Method::GET, v2_handler)
+			.function("/reports/", Method::GET, v2_handler);
+		let router = ServerRouter::new()
+			.with_prefix("/api")
+			.version("v1", v1)
+			.version("v2", v2);
+
+		let cases = [
+			("/api/v1/users/", "v1 handler, v1"),
+			("/api/v2/users/", "v2 handler, v2"),
+			("/api/v2/orders/", "v1 handler, v2"),
+		];
+		for (uri, body) in cases {
+			let response = canonical_test_response(&router, Method::GET, uri)
+				.await
+				.unwrap();
+			assert_eq!(response.body, body, "{}", uri);
+		}
+
+		// Later versions are never used as a fallback
+		let result = canonical_test_response(&router, Method::GET, "/api/v1/reports/").await;
+		assert!(matches!(result, Err(Error::NotFound(_))));
+
+		// Method checks consider fallback routes
+		let result = canonical_test_response(&router, Method::POST, "/api/v2/orders/").await;
+		assert!(matches!(result, Err(Error::MethodNotAllowed(_))));
+	}
+
+	#[tokio::test]
+	async fn test_version_falls_back_to_newest_earlier_version() {
+		async fn v1_handler(_req: Request) -> Result<Response> {
+			Ok(Response::ok().with_body("v1"))
+		}
+		async fn v2_handler(_req: Request) -> Result<Response> {
+			Ok(Response::ok().with_body("v2"))
+		}
+		async fn v3_handler(_req: Request) -> Result<Response> {
+			Ok(Response::ok().with_body("v3"))
+		}
+
+		let v1 = ServerRouter::new()
+			.function("/users/", Method::GET, v1_handler)
+			.function("/orders/", Method::GET, v1_handler);
+		let v2 = ServerRouter::new().function("/users/", Method::GET, v2_handler);
+		let v3 = ServerRouter::new().function("/reports/", Method::GET, v3_handler);
+		let router = ServerRouter::new()
+			.version("v1", v1)
+			.version("v2", v2)
+			.version("v3", v3);
+
+		let cases = [
+			// The newest earlier version defining the path wins
+			("/v3/users/", "v2"),
+			// Versions without the path are skipped
+			("/v3/orders/", "v1"),
+			("/v3/reports/", "v3"),
+		];
+		for (uri, body) in cases {
+			let response = canonical_test_response(&router, Method::GET, uri)
+				.await
+				.unwrap();
+			assert_eq!(response.body, body, "{}", uri);
+		}
+		let route_match = router.resolve("/v3/orders/", &Method::GET, false).unwrap();
+		assert_eq!(route_match.version.as_deref(), Some("v3"));
+	}
+
+	#[tokio::test]
+	async fn test_version_without_match_is_not_found() {
+		async fn dummy_handler(_req: Request) -> Result<Response> {
+			Ok(Response::ok())
+		}
+
+		let v1 = ServerRouter::new().function("/users/", Method::GET, dummy_handler);
+		let v2 = ServerRouter::new().function("/orders/", Method::GET, dummy_handler);
+		let router = ServerRouter::new()
+			.with_prefix("/api")
+			.version("v1", v1)
+			.version("v2", v2);
+
+		for uri in ["/api/v2/missing/", "/api/v3/users/", "/api/users/"] {
+			let result = canonical_test_response(&router, Method::GET, uri).await;
+			assert!(matches!(result, Err(Error::NotFound(_))), "{}", uri);
+		}
+	}
+
+	#[test]
+	fn test_empty_version_panics() {
+		for version in ["", "/", "//"] {
+			let result = std::panic::catch_unwind(|| {
+				ServerRouter::new().version(version, ServerRouter::new())
+			});
+			assert!(result.is_err(), "{:?}", version);
+		}
+	}
+
+	#[test]
+	#[should_panic(expected = "API version 'v1' is already mounted")]
+	fn test_duplicate_version_panics() {
+		let _ = ServerRouter::new()
+			.version("v1", ServerRouter::new())
+			.version("/v1/", ServerRouter::new());
+	}
+
+	#[tokio::test]
+	async fn test_trailing_slash_redirect() {
+		async fn dummy_handler(_req: Request) -> Result<Response> {
+			Ok(Response::ok())
+		}
+
+		let router = ServerRouter::new()
+			.with_trailing_slash(TrailingSlash::Redirect)
+			.function("/users/", Method::GET, dummy_handler)
+			.function("/users/", Method::POST, dummy_handler)
+			.function("/health", Method::GET, dummy_handler);
+
+		let response = canonical_test_response(&router, Method::GET, "/users/")
+			.await
+			.unwrap();
+		assert_eq!(response.status, hyper::StatusCode::OK);
+
+		let response = canonical_test_response(&router, Method::GET, "/users?page=2")
+			.await
+			.unwrap();
+		assert_eq!(response.status, hyper::StatusCode::MOVED_PERMANENTLY);
+		assert_eq!(response.headers["location"], "/users/?page=2");
+
+		let response = canonical_test_response(&router, Method::POST, "/users")
+			.await
+			.unwrap();
+		assert_eq!(response.status, hyper::StatusCode::PERMANENT_REDIRECT);
+
+		let response = canonical_test_response(&router, Method::GET, "/health/")
+			.await
+			.unwrap();
+		assert_eq!(response.status, hyper::StatusCode::MOVED_PERMANENTLY);
+		assert_eq!(response.headers["location"], "/health");
+	}
+
+	#[tokio::test]
+	async fn test_trailing_slash_redirect_is_not_protocol_relative() {
+		async fn dummy_handler(_req: Request) -> Result<Response> {
+			Ok(Response::ok())
+		}
+
+		let router = ServerRouter::new()
+			.with_trailing_slash(TrailingSlash::Redirect)
+			.function("//evil.com/", Method::GET, dummy_handler);
+
+		// The Location must not be read as `http://evil.com/`
+		let response = canonical_test_response(&router, Method::GET, "//evil.com")
+			.await
+			.unwrap();
+		assert_eq!(response.status, hyper::StatusCode::MOVED_PERMANENTLY);
+		assert_eq!(response.headers["location"], "/%2Fevil.com/");
+	}
+
+	#[tokio::test]
+	async fn test_trailing_slash_strict_and_lenient() {
+		async fn dummy_handler(_req: Request) -> Result<Response> {
+			Ok(Response::ok())
+		}
+
+		let strict = ServerRouter::new()
+			.with_trailing_slash(TrailingSlash::Strict)
+			.function("/users/", Method::GET, dummy_handler);
+		let result = canonical_test_response(&strict, Method::GET, "/users").await;
+		assert!(matches!(result, Err(Error::NotFound(_))));
+
+		let lenient = ServerRouter::new().function("/users/", Method::GET, dummy_handler);
+		let response = canonical_test_response(&lenient, Method::GET, "/users")
+			.await
+			.unwrap();
+		assert_eq!(response.status, hyper::StatusCode::OK);
+	}
+
+	#[tokio::test]
+	async fn test_lowercase_path_redirect() {
+		async fn dummy_handler(_req: Request) -> Result<Response> {
+			Ok(Response::ok())
+		}
+
+		let router = ServerRouter::new()
+			.with_trailing_slash(TrailingSlash::Redirect)
+			.with_lowercase_paths(true)
+			.function("/users/", Method::GET, dummy_handler)
+			.function("/files/{name}/", Method::GET, dummy_handler);
+
+		let response = canonical_test_response(&router, Method::GET, "/Users")
+			.await
+			.unwrap();
+		assert_eq!(response.status, hyper::StatusCode::MOVED_PERMANENTLY);
+		assert_eq!(response.headers["location"], "/users/");
+
+		// Mixed-case paths that already match are served as-is
+		let response = canonical_test_response(&router, Method::GET, "/files/README/")
+			.await
+			.unwrap();
+		assert_eq!(response.status, hyper::StatusCode::OK);
+	}
+
 	#[tokio::test]
 	async fn test_route_matching_correctness() {
 		use hyper::Method;
@@ -1921,23 +2556,23 @@ mod tests {
 		router.compile_routes();
 
 		// Test exact path matching
-		let result = router.match_own_routes("/users/123", &Method::GET);
+		let result = router.match_own_routes("/users/123", &Method::GET, true);
 		assert!(result.is_some());
 		assert_eq!(result.unwrap().params.get("id"), Some(&"123".to_string()));
 
 		// Test nested path matching
-		let result = router.match_own_routes("/users/456/posts", &Method::GET);
+		let result = router.match_own_routes("/users/456/posts", &Method::GET, true);
 		assert!(result.is_some());
 		assert_eq!(result.unwrap().params.get("id"), Some(&"456".to_string()));
 
 		// Test multiple parameters
-		let result = router.match_own_routes("/posts/789/comments/101", &Method::GET);
+		let result = router.match_own_routes("/posts/789/comments/101", &Method::GET, true);
 		let params = result.unwrap().params;
 		assert_eq!(params.get("post_id"), Some(&"789".to_string()));
 		assert_eq!(params.get("comment_id"), Some(&"101".to_string()));
 
 		// Test non-matching route
-		let result = router.match_own_routes("/nonexistent", &Method::GET);
+		let result = router.match_own_routes("/nonexistent", &Method::GET, true);
 		assert!(result.is_none());
 	}
 
@@ -1960,15 +2595,60 @@ mod tests {
 		router.compile_routes();
 
 		// Test GET method
-		let result = router.match_own_routes("/users", &Method::GET);
+		let result = router.match_own_routes("/users", &Method::GET, true);
 		assert!(result.is_some());
 
 		// Test POST method
-		let result = router.match_own_routes("/users", &Method::POST);
+		let result = router.match_own_routes("/users", &Method::POST, true);
 		assert!(result.is_some());
 
 		// Test unsupported method
-		let result = router.match_own_routes("/users", &Method::DELETE);
+		let result = router.match_own_routes("/users", &Method::DELETE, true);
 		assert!(result.is_none());
 	}
+
+	#[test]
+	fn test_viewset_routes_follow_implemented_actions() {
+		use reinhardt_views::viewsets::{ModelViewSet, ReadOnlyModelViewSet};
+
+		let router = ServerRouter::new()
+			.viewset("users", ReadOnlyModelViewSet::<(), ()>::new("users"))
+			.viewset("posts", ModelViewSet::<(), ()>::new("posts"));
+		router.compile_routes();
+
+		let mut routes = router.get_all_routes();
+		routes.sort_by(|a, b| a.0.cmp(&b.0));
+		let methods: Vec<(&str, &[Method])> = routes
+			.iter()
+			.map(|(path, _, _, methods)| (path.as_str(), methods.as_slice()))
+			.collect();
+		assert_eq!(
+			methods,
+			vec![
+				("/posts/", &[Method::GET, Method::POST][..]),
+				(
+					"/posts/{id}/",
+					&[Method::GET, Method::PUT, Method::PATCH, Method::DELETE][..]
+				),
+				("/users/", &[Method::GET][..]),
+				("/users/{id}/", &[Method::GET][..]),
+			]
+		);
+
+		assert!(
+			router
+				.match_own_routes("/users/1/", &Method::GET, true)
+				.is_some()
+		);
+		assert!(
+			router
+				.match_own_routes("/users/1/", &Method::PUT, true)
+				.is_none()
+		);
+		assert!(
+			router
+				.match_own_routes("/posts/1/", &Method::PATCH, true)
+				.is_some()
+		);
+	}
 }
@@ -0,0 +1,97 @@
+//! Canonical URL redirects (trailing slash and lowercase paths)
+
+use hyper::{Method, StatusCode};
+use reinhardt_http::Response;
+
+/// How [`ServerRouter`](super::ServerRouter) treats a path that differs from
+/// a route only by its trailing slash
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_urls::routers::{ServerRouter, TrailingSlash};
+///
+/// // Django-style APPEND_SLASH: `/users` redirects to `/users/`
+/// let router = ServerRouter::new().with_trailing_slash(TrailingSlash::Redirect);
+/// assert_eq!(router.trailing_slash(), TrailingSlash::Redirect);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrailingSlash {
+	/// Serve the route under either form
+	#[default]
+	Lenient,
+	/// Redirect to the form the route was registered with
+	Redirect,
+	/// Only the registered form matches
+	Strict,
+}
+
+/// Toggle the trailing slash of a path, `None` for the root path
+pub(super) fn toggle_trailing_slash(path: &str) -> Option<String> {
+	if path == "/" || path.is_empty() {
+		None
+	} else if let Some(stripped) = path.strip_suffix('/') {
+		Some(stripped.to_string())
+	} else {
+		Some(format!("{}/", path))
+	}
+}
+
+/// Escape a leading `//` so the path cannot be read as a protocol-relative URL
+///
+/// Mirrors Django's `escape_leading_slashes`: `//evil.com/x` becomes
+/// `/%2Fevil.com/x`, which browsers resolve against the current host.
+pub(super) fn escape_leading_slashes(path: &str) -> String {
+	match path.strip_prefix("//") {
+		Some(rest) => format!("/%2F{}", rest),
+		None => path.to_string(),
+	}
+}
+
+/// Permanent redirect to the path `location`
+///
+/// Safe methods get `301 Moved Permanently`; others get
+/// `308 Permanent Redirect` so clients resend the same method and body.
+pub(super) fn redirect(method: &Method, location: &str) -> Response {
+	let status = if *method == Method::GET || *method == Method::HEAD {
+		StatusCode::MOVED_PERMANENTLY
+	} else {
+		StatusCode::PERMANENT_REDIRECT
+	};
+	Response::new(status).with_header("Location", &escape_leading_slashes(location))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_toggle_trailing_slash() {
+		assert_eq!(toggle_trailing_slash("/users"), Some("/users/".to_string()));
+		assert_eq!(toggle_trailing_slash("/users/"), Some("/users".to_string()));
+		assert_eq!(toggle_trailing_slash("/"), None);
+	}
+
+	#[test]
+	fn test_escape_leading_slashes() {
+		assert_eq!(escape_leading_slashes("//evil.com/x"), "/%2Fevil.com/x");
+		assert_eq!(escape_leading_slashes("///evil.com"), "/%2F/evil.com");
+		assert_eq!(escape_leading_slashes("/users/"), "/users/");
+		assert_eq!(
+			redirect(&Method::GET, "//evil.com/x/").headers["location"],
+			"/%2Fevil.com/x/"
+		);
+	}
+
+	#[test]
+	fn test_redirect_status_depends_on_method() {
+		assert_eq!(
+			redirect(&Method::GET, "/users/").status,
+			StatusCode::MOVED_PERMANENTLY
+		);
+		assert_eq!(
+			redirect(&Method::POST, "/users/").status,
+			StatusCode::PERMANENT_REDIRECT
+		);
+	}
+}
@@ -36,4 +36,8 @@ where
 	async fn handle(&self, req: Request) -> Result<Response> {
 		(self.func)(req).await
 	}
+
+	fn type_name(&self) -> &'static str {
+		std::any::type_name::<F>()
+	}
 }
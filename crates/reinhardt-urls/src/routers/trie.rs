@@ -0,0 +1,149 @@
+//! Segment trie used by [`PathMatcher`](super::PathMatcher) in
+//! [`MatchingMode::Trie`](super::MatchingMode::Trie).
+//!
+//! Patterns are split on `/` into static segments and parameter segments.
+//! Looking up a path walks the trie and yields the patterns whose segments
+//! fit, which are then confirmed with the pattern's own regex in
+//! registration order. Matching therefore gives the same result as linear
+//! matching while only testing a handful of patterns.
+//!
+//! Patterns whose parameters may span several segments (the `path` type and
+//! registered converters) cannot be placed in the trie; they are kept aside
+//! and always considered.
+
+use super::PathPattern;
+use std::collections::HashMap;
+
+#[derive(Default)]
+struct Node {
+	statics: HashMap<String, Node>,
+	param: Option<Box<Node>>,
+	/// Patterns ending at this node, by registration index
+	terminal: Vec<usize>,
+}
+
+/// Compiled trie over a set of patterns
+#[derive(Default)]
+pub(crate) struct RouteTrie {
+	root: Node,
+	/// Patterns that are not segment aligned, by registration index
+	fallback: Vec<usize>,
+}
+
+impl RouteTrie {
+	/// Compile patterns, identified by their position in `patterns`
+	pub(crate) fn build<'a>(patterns: impl IntoIterator<Item = &'a PathPattern>) -> Self {
+		let mut trie = Self::default();
+		for (index, pattern) in patterns.into_iter().enumerate() {
+			trie.insert(index, pattern);
+		}
+		trie
+	}
+
+	fn insert(&mut self, index: usize, pattern: &PathPattern) {
+		if pattern.spans_segments() {
+			self.fallback.push(index);
+			return;
+		}
+
+		let mut node = &mut self.root;
+		for segment in pattern.normalized_pattern().split('/') {
+			node = if segment.contains('{') {
+				node.param.get_or_insert_with(Default::default)
+			} else {
+				node.statics.entry(segment.to_string()).or_default()
+			};
+		}
+		node.terminal.push(index);
+	}
+
+	/// Indexes of the patterns that may match `path`, in registration order
+	pub(crate) fn candidates(&self, path: &str) -> Vec<usize> {
+		let segments: Vec<&str> = path.split('/').collect();
+		let mut candidates = self.fallback.clone();
+		collect(&self.root, &segments, &mut candidates);
+		candidates.sort_unstable();
+		candidates.dedup();
+		candidates
+	}
+}
+
+/// Walk the trie one segment at a time
+///
+/// The walk keeps the set of nodes reached so far instead of recursing into
+/// every static/parameter combination. Each node sits at a single depth, so
+/// it is visited at most once and the cost is bounded by the size of the
+/// trie rather than by the number of ways the path can be split.
+fn collect(root: &Node, segments: &[&str], candidates: &mut Vec<usize>) {
+	let mut frontier = vec![root];
+	for segment in segments {
+		let mut next = Vec::new();
+		for node in frontier {
+			if let Some(child) = node.statics.get(*segment) {
+				next.push(child);
+			}
+			// Parameters never match an empty segment
+			if let Some(child) = &node.param
+				&& !segment.is_empty()
+			{
+				next.push(child.as_ref());
+			}
+		}
+		if next.is_empty() {
+			return;
+		}
+		frontier = next;
+	}
+	for node in frontier {
+		candidates.extend_from_slice(&node.terminal);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn trie(patterns: &[&str]) -> RouteTrie {
+		let patterns: Vec<PathPattern> = patterns
+			.iter()
+			.map(|p| PathPattern::new(*p).unwrap())
+			.collect();
+		RouteTrie::build(&patterns)
+	}
+
+	#[test]
+	fn test_candidates_follow_static_and_param_segments() {
+		let trie = trie(&[
+			"/users/",
+			"/users/{id}/",
+			"/users/me/",
+			"/posts/{id}/",
+			"/files/{<path:rest>}",
+			"/users/{id}.json",
+		]);
+
+		assert_eq!(trie.candidates("/users/"), vec![0, 4]);
+		assert_eq!(trie.candidates("/users/me/"), vec![1, 2, 4]);
+		assert_eq!(trie.candidates("/users/42.json"), vec![4, 5]);
+		assert_eq!(trie.candidates("/unknown/"), vec![4]);
+	}
+
+	#[test]
+	fn test_candidates_with_static_and_param_at_every_depth() {
+		// Each depth offers both a static and a parameter branch
+		let depth = 24;
+		let patterns: Vec<String> = (0..depth)
+			.map(|i| {
+				let segments: Vec<&str> = (0..depth)
+					.map(|j| if j == i { "{p}" } else { "a" })
+					.collect();
+				format!("/{}/", segments.join("/"))
+			})
+			.collect();
+		let patterns: Vec<&str> = patterns.iter().map(String::as_str).collect();
+		let trie = trie(&patterns);
+
+		let path = format!("/{}/", vec!["a"; depth].join("/"));
+		assert_eq!(trie.candidates(&path), (0..depth).collect::<Vec<_>>());
+	}
+}
@@ -155,6 +155,13 @@ impl UnifiedRouter {
 		self.server
 	}
 
+	/// Returns every server route as (full_path, name, namespace, methods).
+	///
+	/// Delegates to [`ServerRouter::get_all_routes`].
+	pub fn get_all_routes(&self) -> crate::routers::RouteInfo {
+		self.server.get_all_routes()
+	}
+
 	/// Consumes the router and returns the client router.
 	pub fn into_client(self) -> ClientRouter {
 		self.client
@@ -239,6 +246,14 @@ impl UnifiedRouter {
 		self.mount(prefix, child.server)
 	}
 
+	/// Mount a server router for an API version.
+	///
+	/// This is a convenience method that delegates to [`ServerRouter::version`].
+	pub fn version(mut self, version: &str, router: ServerRouter) -> Self {
+		self.server = self.server.version(version, router);
+		self
+	}
+
 	/// Register an endpoint on server router.
 	///
 	/// This is a convenience method that delegates to [`ServerRouter::endpoint`].
@@ -342,6 +357,13 @@ impl UnifiedRouter {
 		self.server
 	}
 
+	/// Returns every server route as (full_path, name, namespace, methods).
+	///
+	/// Delegates to [`ServerRouter::get_all_routes`].
+	pub fn get_all_routes(&self) -> crate::routers::RouteInfo {
+		self.server.get_all_routes()
+	}
+
 	/// Registers server router globally.
 	pub fn register_globally(self) {
 		crate::routers::register_router(self.server);
@@ -386,6 +408,12 @@ impl UnifiedRouter {
 		self.mount(prefix, child.server)
 	}
 
+	/// Mount a server router for an API version.
+	pub fn version(mut self, version: &str, router: ServerRouter) -> Self {
+		self.server = self.server.version(version, router);
+		self
+	}
+
 	/// Register an endpoint on server router.
 	pub fn endpoint<F, E>(mut self, f: F) -> Self
 	where
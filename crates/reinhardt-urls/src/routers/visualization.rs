@@ -1,7 +1,7 @@
 //! Route visualization for documentation and debugging
 //!
 //! This module provides utilities for visualizing route structures in various formats,
-//! including ASCII art trees and DOT (Graphviz) format.
+//! including ASCII art trees, DOT (Graphviz), Mermaid and JSON.
 //!
 //! # Examples
 //!
@@ -35,8 +35,14 @@ pub enum VisualizationFormat {
 	/// Markdown table
 	Markdown,
 
-	/// Plain text list
+	/// Plain text list, with handler and middleware when known
 	List,
+
+	/// JSON array of [`RouteInfo`]
+	Json,
+
+	/// Mermaid flowchart
+	Mermaid,
 }
 
 /// Route visualizer
@@ -108,6 +114,8 @@ impl RouteVisualizer {
 			VisualizationFormat::Dot => self.render_dot(),
 			VisualizationFormat::Markdown => self.render_markdown(),
 			VisualizationFormat::List => self.render_list(),
+			VisualizationFormat::Json => self.render_json(),
+			VisualizationFormat::Mermaid => self.render_mermaid(),
 		}
 	}
 
@@ -286,11 +294,78 @@ impl RouteVisualizer {
 				output.push_str(&format!(" ({})", name));
 			}
 
+			if let Some(ref handler) = route.handler {
+				output.push_str(&format!(" -> {}", handler));
+			}
+
+			if !route.middleware.is_empty() {
+				output.push_str(&format!(" via {}", route.middleware.join(", ")));
+			}
+
 			output.push('\n');
 		}
 
 		output
 	}
+
+	/// Render as pretty-printed JSON
+	fn render_json(&self) -> String {
+		serde_json::to_string_pretty(&self.routes).expect("RouteInfo is always serializable")
+	}
+
+	/// Render as a Mermaid flowchart grouped by namespace
+	fn render_mermaid(&self) -> String {
+		let mut output = String::new();
+		output.push_str("flowchart LR\n");
+		output.push_str("  root[\"Routes\"]\n");
+
+		let mut namespaces: Vec<&String> = self
+			.routes
+			.iter()
+			.filter_map(|route| route.namespace.as_ref())
+			.collect::<HashSet<_>>()
+			.into_iter()
+			.collect();
+		namespaces.sort();
+
+		let mut namespace_nodes: HashMap<&String, String> = HashMap::new();
+		for (i, ns) in namespaces.into_iter().enumerate() {
+			let node = format!("ns{}", i);
+			output.push_str(&format!("  {}[\"{}\"]\n", node, mermaid_escape(ns)));
+			output.push_str(&format!("  root --> {}\n", node));
+			namespace_nodes.insert(ns, node);
+		}
+
+		for (i, route) in self.routes.iter().enumerate() {
+			let methods = if route.methods.is_empty() {
+				"ALL".to_string()
+			} else {
+				route.methods.join(", ")
+			};
+			let mut label = format!("{}<br/>{}", mermaid_escape(&route.path), methods);
+			if let Some(ref handler) = route.handler {
+				label.push_str(&format!("<br/>{}", mermaid_escape(handler)));
+			}
+
+			let parent = route
+				.namespace
+				.as_ref()
+				.and_then(|ns| namespace_nodes.get(ns))
+				.map(String::as_str)
+				.unwrap_or("root");
+			output.push_str(&format!("  route{}[\"{}\"]\n", i, label));
+			output.push_str(&format!("  {} --> route{}\n", parent, i));
+		}
+
+		output
+	}
+}
+
+/// Escape characters that end a quoted Mermaid label or are read as markup
+fn mermaid_escape(text: &str) -> String {
+	text.replace('"', "#quot;")
+		.replace('<', "#lt;")
+		.replace('>', "#gt;")
 }
 
 /// Namespace tree structure
@@ -420,6 +495,41 @@ mod tests {
 		assert!(dot.contains("/users/"));
 	}
 
+	#[test]
+	fn test_visualizer_json_format() {
+		let mut inspector = RouteInspector::new();
+		inspector.add_route_info(
+			RouteInfo::new("/users/", vec![Method::GET], Some("users:list"))
+				.with_handler("app::UserList")
+				.with_middleware(vec!["app::Auth".to_string()]),
+		);
+
+		let visualizer = RouteVisualizer::from_inspector(&inspector);
+		let json = visualizer.render(VisualizationFormat::Json);
+		let routes: Vec<RouteInfo> = serde_json::from_str(&json).unwrap();
+
+		assert_eq!(routes.len(), 1);
+		assert_eq!(routes[0].handler.as_deref(), Some("app::UserList"));
+		assert_eq!(routes[0].middleware, vec!["app::Auth"]);
+	}
+
+	#[test]
+	fn test_visualizer_mermaid_format() {
+		let mut inspector = RouteInspector::new();
+		inspector.add_route("/users/", vec![Method::GET], Some("api:users:list"), None);
+		inspector.add_route("/health/", vec![], None::<String>, None);
+
+		let visualizer = RouteVisualizer::from_inspector(&inspector);
+		let mermaid = visualizer.render(VisualizationFormat::Mermaid);
+
+		assert!(mermaid.starts_with("flowchart LR\n"));
+		assert!(mermaid.contains("ns0[\"api:users\"]"));
+		assert!(mermaid.contains("route0[\"/users/<br/>GET\"]"));
+		assert!(mermaid.contains("ns0 --> route0"));
+		assert!(mermaid.contains("root --> route1"));
+		assert!(mermaid.contains("/health/<br/>ALL"));
+	}
+
 	#[test]
 	fn test_visualizer_empty() {
 		let inspector = RouteInspector::new();
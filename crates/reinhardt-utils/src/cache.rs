@@ -14,8 +14,12 @@
 //! - **HybridCache**: Multi-tier caching (memory + distributed)
 //! - **RedisSentinelCache**: Redis Sentinel support (requires redis-sentinel feature)
 //! - **Pub/Sub**: Cache invalidation via Redis channels (requires redis-backend feature)
+//! - **Near Cache**: In-process LRU in front of RedisCache, kept fresh via Pub/Sub
 //! - **Cache Warming**: Pre-populate cache on startup
 //! - **Cache Tags**: Tag-based invalidation for related entries
+//! - **Metrics**: Per-pattern hit/miss/latency statistics with Prometheus export
+//! - **Memoization**: `#[cached]` caches async function results keyed by their arguments
+//! - **Compression**: gzip/zstd/lz4 compression of large values for distributed backends
 //! - TTL support for automatic expiration
 //! - Async-first API
 //!
@@ -87,6 +91,7 @@
 //!
 
 mod cache_trait;
+mod compression;
 mod entry;
 mod in_memory;
 mod key_builder;
@@ -94,6 +99,8 @@ mod layered;
 mod statistics;
 
 pub mod file_backend;
+pub mod memoize;
+pub mod metrics;
 pub mod tags;
 pub mod warming;
 
@@ -111,15 +118,25 @@ pub mod redis_sentinel;
 #[cfg(feature = "redis-backend")]
 pub mod pubsub;
 
+#[cfg(feature = "redis-backend")]
+pub mod near_cache;
+
 // Re-export exception types
 pub use reinhardt_core::exception::Result;
 
 // Re-export core items
-pub use cache_trait::Cache;
+pub use cache_trait::{Cache, namespace_version_key};
+pub use compression::{
+	CacheCompression, CompressionAlgorithm, DEFAULT_COMPRESSION_THRESHOLD, decode_value,
+	decompress, encode_value,
+};
 pub use in_memory::{CleanupStrategy, InMemoryCache};
 pub use key_builder::CacheKeyBuilder;
 pub use layered::LayeredCacheStore;
-pub use statistics::{CacheEntryInfo, CacheStatistics};
+pub use memoize::Memoize;
+pub use metrics::{CacheMetrics, InstrumentedCache, OTHER_PATTERN};
+pub use reinhardt_core::macros::cached;
+pub use statistics::{CacheEntryInfo, CacheStatistics, PatternStatistics};
 
 #[cfg(feature = "redis-backend")]
 pub use redis_backend::RedisCache;
@@ -127,7 +144,7 @@ pub use redis_backend::RedisCache;
 #[cfg(feature = "memcached-backend")]
 pub use memcached::{MemcachedCache, MemcachedConfig};
 
-pub use hybrid::HybridCache;
+pub use hybrid::{HybridCache, WriteStrategy};
 
 #[cfg(feature = "redis-sentinel")]
 pub use redis_sentinel::{RedisSentinelCache, RedisSentinelConfig};
@@ -135,6 +152,9 @@ pub use redis_sentinel::{RedisSentinelCache, RedisSentinelConfig};
 #[cfg(feature = "redis-backend")]
 pub use pubsub::{CacheInvalidationChannel, CacheInvalidationMessage, CacheInvalidationSubscriber};
 
+#[cfg(feature = "redis-backend")]
+pub use near_cache::{DEFAULT_NEAR_CACHE_TTL, NearCache};
+
 // Re-export file backend
 pub use file_backend::FileCache;
 
@@ -29,6 +29,10 @@ pub trait Cache: Send + Sync {
 	async fn clear(&self) -> Result<()>;
 
 	/// Get multiple values at once
+	///
+	/// Missing and expired keys are absent from the result. The default
+	/// implementation issues one `get` per key; backends override it with a
+	/// single round trip where possible.
 	async fn get_many<T>(&self, keys: &[&str]) -> Result<HashMap<String, T>>
 	where
 		T: for<'de> Deserialize<'de> + Serialize + Send + Sync,
@@ -43,6 +47,8 @@ pub trait Cache: Send + Sync {
 	}
 
 	/// Set multiple values at once
+	///
+	/// The default implementation issues one `set` per key.
 	async fn set_many<T>(&self, values: HashMap<String, T>, ttl: Option<Duration>) -> Result<()>
 	where
 		T: Serialize + Send + Sync,
@@ -54,6 +60,8 @@ pub trait Cache: Send + Sync {
 	}
 
 	/// Delete multiple keys at once
+	///
+	/// The default implementation issues one `delete` per key.
 	async fn delete_many(&self, keys: &[&str]) -> Result<()> {
 		for key in keys {
 			self.delete(key).await?;
@@ -73,4 +81,36 @@ pub trait Cache: Send + Sync {
 	async fn decr(&self, key: &str, delta: i64) -> Result<i64> {
 		self.incr(key, -delta).await
 	}
+
+	/// Get the current version of a key namespace
+	///
+	/// Namespaces start at version 1. The version counter is stored in the
+	/// cache itself under [`namespace_version_key`].
+	async fn namespace_version(&self, namespace: &str) -> Result<u64> {
+		let bumps: Option<i64> = self.get(&namespace_version_key(namespace)).await?;
+		Ok(1 + bumps.unwrap_or(0).max(0) as u64)
+	}
+
+	/// Move a namespace to its next version and return it
+	///
+	/// Keys built for earlier versions are no longer read, which invalidates
+	/// the whole namespace without enumerating its keys. The stale entries
+	/// are left to expire through their TTL.
+	async fn incr_version(&self, namespace: &str) -> Result<u64> {
+		let bumps = self.incr(&namespace_version_key(namespace), 1).await?;
+		Ok(1 + bumps.max(0) as u64)
+	}
+}
+
+/// Key under which the version counter of `namespace` is stored
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_utils::cache::namespace_version_key;
+///
+/// assert_eq!(namespace_version_key("users"), "users:__version__");
+/// ```
+pub fn namespace_version_key(namespace: &str) -> String {
+	format!("{}:__version__", namespace)
 }
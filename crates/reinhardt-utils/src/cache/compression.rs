@@ -0,0 +1,274 @@
+//! Compression of serialized cache values
+//!
+//! Values whose serialized form exceeds a size threshold are compressed
+//! before being sent to the backend. Compressed payloads start with a short
+//! header naming the algorithm; serialized JSON never starts with a NUL byte,
+//! so compressed and uncompressed entries can coexist under the same backend
+//! and are told apart on read.
+//!
+//! # Examples
+//!
+//! ```
+//! use reinhardt_utils::cache::{CacheCompression, CompressionAlgorithm, decompress};
+//!
+//! let compression = CacheCompression::new(CompressionAlgorithm::Gzip).with_threshold(16);
+//! let data = "<li>rendered row</li>".repeat(20).into_bytes();
+//!
+//! let stored = compression.compress(&data).unwrap();
+//! assert!(stored.len() < data.len());
+//! assert_eq!(&*decompress(&stored).unwrap(), &data[..]);
+//! ```
+
+use reinhardt_core::exception::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::io::{Read, Write};
+
+/// Marker prepended to compressed values, followed by the algorithm id
+const MAGIC: &[u8; 3] = b"\0RC";
+const HEADER_LEN: usize = MAGIC.len() + 1;
+
+/// Default size above which values are compressed, in bytes
+pub const DEFAULT_COMPRESSION_THRESHOLD: usize = 1024;
+
+/// Compression algorithm for cache values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionAlgorithm {
+	/// gzip (always available)
+	Gzip,
+	/// Zstandard (requires cache-zstd feature)
+	#[cfg(feature = "cache-zstd")]
+	Zstd,
+	/// LZ4 block format (requires cache-lz4 feature)
+	#[cfg(feature = "cache-lz4")]
+	Lz4,
+}
+
+impl CompressionAlgorithm {
+	fn id(self) -> u8 {
+		match self {
+			Self::Gzip => 1,
+			#[cfg(feature = "cache-zstd")]
+			Self::Zstd => 2,
+			#[cfg(feature = "cache-lz4")]
+			Self::Lz4 => 3,
+		}
+	}
+
+	fn from_id(id: u8) -> Result<Self> {
+		match id {
+			1 => Ok(Self::Gzip),
+			#[cfg(feature = "cache-zstd")]
+			2 => Ok(Self::Zstd),
+			#[cfg(feature = "cache-lz4")]
+			3 => Ok(Self::Lz4),
+			_ => Err(Error::Serialization(format!(
+				"Unsupported cache compression algorithm id: {}",
+				id
+			))),
+		}
+	}
+}
+
+/// Compression settings for a cache backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheCompression {
+	algorithm: CompressionAlgorithm,
+	threshold: usize,
+	level: Option<u32>,
+}
+
+impl CacheCompression {
+	/// Compress values larger than [`DEFAULT_COMPRESSION_THRESHOLD`] with `algorithm`
+	pub fn new(algorithm: CompressionAlgorithm) -> Self {
+		Self {
+			algorithm,
+			threshold: DEFAULT_COMPRESSION_THRESHOLD,
+			level: None,
+		}
+	}
+
+	/// Only compress values whose serialized size exceeds `bytes`
+	pub fn with_threshold(mut self, bytes: usize) -> Self {
+		self.threshold = bytes;
+		self
+	}
+
+	/// Set the compression level (algorithm specific; ignored by LZ4)
+	pub fn with_level(mut self, level: u32) -> Self {
+		self.level = Some(level);
+		self
+	}
+
+	/// Get the compression algorithm
+	pub fn algorithm(&self) -> CompressionAlgorithm {
+		self.algorithm
+	}
+
+	/// Get the size threshold in bytes
+	pub fn threshold(&self) -> usize {
+		self.threshold
+	}
+
+	/// Compress `data` if it exceeds the threshold
+	///
+	/// Data at or below the threshold, or that does not shrink when
+	/// compressed, is returned unchanged without a header.
+	pub fn compress(&self, data: &[u8]) -> Result<Vec<u8>> {
+		if data.len() <= self.threshold {
+			return Ok(data.to_vec());
+		}
+
+		let mut out = Vec::with_capacity(data.len() / 2);
+		out.extend_from_slice(MAGIC);
+		out.push(self.algorithm.id());
+
+		match self.algorithm {
+			CompressionAlgorithm::Gzip => {
+				let level = self
+					.level
+					.map_or(flate2::Compression::default(), flate2::Compression::new);
+				let mut encoder = flate2::write::GzEncoder::new(out, level);
+				encoder.write_all(data).map_err(compression_error)?;
+				out = encoder.finish().map_err(compression_error)?;
+			}
+			#[cfg(feature = "cache-zstd")]
+			CompressionAlgorithm::Zstd => {
+				let level = self.level.map_or(0, |level| level as i32);
+				let compressed = zstd::bulk::compress(data, level).map_err(compression_error)?;
+				out.extend_from_slice(&compressed);
+			}
+			#[cfg(feature = "cache-lz4")]
+			CompressionAlgorithm::Lz4 => {
+				out.extend_from_slice(&lz4_flex::compress_prepend_size(data));
+			}
+		}
+
+		if out.len() >= data.len() {
+			return Ok(data.to_vec());
+		}
+		Ok(out)
+	}
+}
+
+/// Decompress a stored value, passing uncompressed values through unchanged
+pub fn decompress(data: &[u8]) -> Result<Cow<'_, [u8]>> {
+	if data.len() < HEADER_LEN || !data.starts_with(MAGIC) {
+		return Ok(Cow::Borrowed(data));
+	}
+
+	let payload = &data[HEADER_LEN..];
+	let decompressed = match CompressionAlgorithm::from_id(data[MAGIC.len()])? {
+		CompressionAlgorithm::Gzip => {
+			let mut out = Vec::new();
+			flate2::read::GzDecoder::new(payload)
+				.read_to_end(&mut out)
+				.map_err(compression_error)?;
+			out
+		}
+		#[cfg(feature = "cache-zstd")]
+		CompressionAlgorithm::Zstd => {
+			let mut out = Vec::new();
+			zstd::stream::read::Decoder::new(payload)
+				.and_then(|mut decoder| decoder.read_to_end(&mut out))
+				.map_err(compression_error)?;
+			out
+		}
+		#[cfg(feature = "cache-lz4")]
+		CompressionAlgorithm::Lz4 => {
+			lz4_flex::decompress_size_prepended(payload).map_err(compression_error)?
+		}
+	};
+
+	Ok(Cow::Owned(decompressed))
+}
+
+/// Serialize a value for storage, compressing it when configured
+///
+/// Custom [`Cache`](super::Cache) backends can use this with [`decode_value`]
+/// to share the on-wire format of the built-in backends.
+pub fn encode_value<T>(value: &T, compression: Option<&CacheCompression>) -> Result<Vec<u8>>
+where
+	T: Serialize + ?Sized,
+{
+	let serialized = serde_json::to_vec(value).map_err(|e| Error::Serialization(e.to_string()))?;
+	match compression {
+		Some(compression) => compression.compress(&serialized),
+		None => Ok(serialized),
+	}
+}
+
+/// Deserialize a stored value, compressed or not
+pub fn decode_value<T>(data: &[u8]) -> Result<T>
+where
+	T: for<'de> Deserialize<'de>,
+{
+	let data = decompress(data)?;
+	serde_json::from_slice(&data).map_err(|e| Error::Serialization(e.to_string()))
+}
+
+fn compression_error(e: impl std::fmt::Display) -> Error {
+	Error::Serialization(format!("Cache compression error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+
+	fn fragment() -> String {
+		"<div class=\"card\">rendered fragment</div>".repeat(100)
+	}
+
+	#[rstest]
+	#[case(CompressionAlgorithm::Gzip)]
+	#[cfg_attr(feature = "cache-zstd", case(CompressionAlgorithm::Zstd))]
+	#[cfg_attr(feature = "cache-lz4", case(CompressionAlgorithm::Lz4))]
+	fn test_round_trip(#[case] algorithm: CompressionAlgorithm) {
+		let compression = CacheCompression::new(algorithm);
+		let value = fragment();
+
+		let stored = encode_value(&value, Some(&compression)).unwrap();
+		let decoded: String = decode_value(&stored).unwrap();
+
+		assert!(stored.starts_with(MAGIC));
+		assert!(stored.len() < value.len());
+		assert_eq!(decoded, value);
+	}
+
+	#[rstest]
+	fn test_below_threshold_is_stored_raw() {
+		let compression = CacheCompression::new(CompressionAlgorithm::Gzip);
+
+		let stored = encode_value(&"short", Some(&compression)).unwrap();
+
+		assert_eq!(stored, b"\"short\"");
+	}
+
+	#[rstest]
+	fn test_uncompressed_values_still_decode() {
+		let stored = encode_value(&fragment(), None).unwrap();
+
+		let decoded: String = decode_value(&stored).unwrap();
+
+		assert_eq!(decoded, fragment());
+	}
+
+	#[rstest]
+	fn test_incompressible_data_is_stored_raw() {
+		let compression = CacheCompression::new(CompressionAlgorithm::Gzip).with_threshold(0);
+		let data: Vec<u8> = (0..64u8).map(|i| i.wrapping_mul(151)).collect();
+
+		let stored = compression.compress(&data).unwrap();
+
+		assert_eq!(stored, data);
+	}
+
+	#[rstest]
+	fn test_unknown_algorithm_is_rejected() {
+		let mut stored = MAGIC.to_vec();
+		stored.extend_from_slice(&[0xff, 1, 2, 3]);
+
+		assert!(decompress(&stored).is_err());
+	}
+}
@@ -287,6 +287,100 @@ impl Cache for FileCache {
 
 		Ok(())
 	}
+
+	async fn get_many<T>(&self, keys: &[&str]) -> Result<HashMap<String, T>>
+	where
+		T: for<'de> Deserialize<'de> + Serialize + Send + Sync,
+	{
+		let mut results = HashMap::with_capacity(keys.len());
+		let mut expired = Vec::new();
+
+		for key in keys {
+			let path = self.get_file_path(key);
+			let data = match fs::read(&path).await {
+				Ok(data) => data,
+				Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+				Err(e) => {
+					return Err(Error::Internal(format!("Failed to read cache file: {}", e)));
+				}
+			};
+
+			let stored: StoredEntry =
+				serde_json::from_slice(&data).map_err(|e| Error::Serialization(e.to_string()))?;
+
+			if stored.entry.is_expired() {
+				let _ = fs::remove_file(&path).await;
+				expired.push(*key);
+				continue;
+			}
+
+			let value = serde_json::from_slice(&stored.entry.value)
+				.map_err(|e| Error::Serialization(e.to_string()))?;
+			results.insert(key.to_string(), value);
+		}
+
+		// Update the index once for all expired entries
+		if !expired.is_empty() {
+			let mut index = self.index.write().await;
+			for key in expired {
+				index.remove(key);
+			}
+		}
+
+		Ok(results)
+	}
+
+	async fn set_many<T>(&self, values: HashMap<String, T>, ttl: Option<Duration>) -> Result<()>
+	where
+		T: Serialize + Send + Sync,
+	{
+		let ttl = ttl.or(self.default_ttl);
+		let mut written = Vec::with_capacity(values.len());
+
+		for (key, value) in values {
+			let serialized =
+				serde_json::to_vec(&value).map_err(|e| Error::Serialization(e.to_string()))?;
+			let path = self.get_file_path(&key);
+			let stored = StoredEntry {
+				key,
+				entry: CacheEntry::new(serialized, ttl),
+			};
+			let data =
+				serde_json::to_vec(&stored).map_err(|e| Error::Serialization(e.to_string()))?;
+
+			fs::write(&path, data)
+				.await
+				.map_err(|e| Error::Internal(format!("Failed to write cache file: {}", e)))?;
+			written.push((stored.key, path));
+		}
+
+		let mut index = self.index.write().await;
+		index.extend(written);
+
+		Ok(())
+	}
+
+	async fn delete_many(&self, keys: &[&str]) -> Result<()> {
+		for key in keys {
+			match fs::remove_file(self.get_file_path(key)).await {
+				Ok(()) => {}
+				Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+				Err(e) => {
+					return Err(Error::Internal(format!(
+						"Failed to delete cache file: {}",
+						e
+					)));
+				}
+			}
+		}
+
+		let mut index = self.index.write().await;
+		for key in keys {
+			index.remove(*key);
+		}
+
+		Ok(())
+	}
 }
 
 #[cfg(test)]
@@ -419,4 +513,27 @@ mod tests {
 		assert!(!cache.has_key("key1").await.unwrap());
 		assert!(!cache.has_key("key2").await.unwrap());
 	}
+
+	#[tokio::test]
+	async fn test_file_cache_many() {
+		let cache = create_test_cache("many").await;
+
+		// Set many
+		let mut values = HashMap::new();
+		values.insert("key1".to_string(), "value1".to_string());
+		values.insert("key2".to_string(), "value2".to_string());
+		cache.set_many(values, None).await.unwrap();
+
+		// Get many
+		let results: HashMap<String, String> =
+			cache.get_many(&["key1", "key2", "key3"]).await.unwrap();
+		assert_eq!(results.len(), 2);
+		assert_eq!(results.get("key1"), Some(&"value1".to_string()));
+		assert_eq!(results.get("key2"), Some(&"value2".to_string()));
+
+		// Delete many, including a missing key
+		cache.delete_many(&["key1", "key2", "key3"]).await.unwrap();
+		assert!(!cache.has_key("key1").await.unwrap());
+		assert!(!cache.has_key("key2").await.unwrap());
+	}
 }
@@ -8,7 +8,10 @@
 //! - **L1 cache**: Fast in-memory cache for frequently accessed data
 //! - **L2 cache**: Distributed cache (Redis/Memcached) for shared data
 //! - **Automatic promotion**: L2 hits are promoted to L1 for faster subsequent access
-//! - **Write-through**: Writes update both L1 and L2 simultaneously
+//! - **Write strategies**: Write-through (default), write-behind with a flush
+//!   queue, or read-repair (see [`WriteStrategy`])
+//! - **Invalidation**: Local L1 entries can be dropped across instances via
+//!   the Redis pub/sub channel (requires redis-backend feature)
 //!
 //! # Examples
 //!
@@ -41,12 +44,66 @@
 //! ```
 
 use super::Cache;
+#[cfg(feature = "redis-backend")]
+use super::pubsub::{
+	CacheInvalidationChannel, CacheInvalidationMessage, CacheInvalidationSubscriber,
+};
 use async_trait::async_trait;
-use reinhardt_core::exception::Result;
+use reinhardt_core::exception::{Error, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// How [`HybridCache`] propagates writes to its tiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WriteStrategy {
+	/// Write L1 and L2 before returning
+	#[default]
+	WriteThrough,
+	/// Write L1 immediately and queue L2 writes until [`HybridCache::flush`]
+	///
+	/// The queue is flushed automatically once it holds `max_pending` keys.
+	/// Repeated writes to a queued key are coalesced, so only the latest
+	/// value reaches L2. The TTL counts from the original write: L2 gets
+	/// the time left at flush, and entries that expired while queued are
+	/// deleted instead. Call `flush` before shutdown: queued writes are
+	/// lost if the cache is dropped.
+	WriteBehind {
+		/// Number of queued keys that triggers an inline flush
+		max_pending: usize,
+	},
+	/// Write L2 only and drop the L1 entry
+	///
+	/// The next read misses L1 and repairs it from L2, so L1 never holds a
+	/// value that L2 has not accepted.
+	ReadRepair,
+}
+
+/// A queued L2 operation for write-behind mode
+#[derive(Debug, Clone)]
+enum PendingWrite {
+	Set {
+		value: serde_json::Value,
+		expires_at: Option<Instant>,
+	},
+	Delete,
+}
+
+impl PendingWrite {
+	fn set(value: serde_json::Value, ttl: Option<Duration>) -> Self {
+		Self::Set {
+			value,
+			expires_at: ttl.map(|ttl| Instant::now() + ttl),
+		}
+	}
+
+	/// Whether this is a set whose TTL ran out while queued
+	fn is_expired(&self) -> bool {
+		matches!(self, Self::Set { expires_at: Some(expires_at), .. } if *expires_at <= Instant::now())
+	}
+}
 
 /// Hybrid cache with two-level caching strategy
 ///
@@ -65,6 +122,10 @@ where
 {
 	l1: Arc<L1>,
 	l2: Arc<L2>,
+	strategy: WriteStrategy,
+	pending: Arc<Mutex<HashMap<String, PendingWrite>>>,
+	#[cfg(feature = "redis-backend")]
+	invalidation: Option<Arc<CacheInvalidationChannel>>,
 }
 
 impl<L1, L2> HybridCache<L1, L2>
@@ -90,9 +151,45 @@ where
 		Self {
 			l1: Arc::new(l1),
 			l2: Arc::new(l2),
+			strategy: WriteStrategy::default(),
+			pending: Arc::new(Mutex::new(HashMap::new())),
+			#[cfg(feature = "redis-backend")]
+			invalidation: None,
 		}
 	}
 
+	/// Set the write strategy
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_utils::cache::{HybridCache, InMemoryCache, WriteStrategy};
+	///
+	/// let cache = HybridCache::new(InMemoryCache::new(), InMemoryCache::new())
+	///     .with_write_strategy(WriteStrategy::WriteBehind { max_pending: 100 });
+	/// assert_eq!(cache.write_strategy(), WriteStrategy::WriteBehind { max_pending: 100 });
+	/// ```
+	pub fn with_write_strategy(mut self, strategy: WriteStrategy) -> Self {
+		self.strategy = strategy;
+		self
+	}
+
+	/// Get the configured write strategy
+	pub fn write_strategy(&self) -> WriteStrategy {
+		self.strategy
+	}
+
+	/// Publish L1 invalidations for every write through `channel`
+	///
+	/// Other instances sharing the same L2 should run
+	/// [`start_invalidation_listener`](Self::start_invalidation_listener)
+	/// so their L1 entries are dropped when this instance writes.
+	#[cfg(feature = "redis-backend")]
+	pub fn with_invalidation_channel(mut self, channel: CacheInvalidationChannel) -> Self {
+		self.invalidation = Some(Arc::new(channel));
+		self
+	}
+
 	/// Get a reference to the L1 cache
 	///
 	/// # Examples
@@ -143,6 +240,175 @@ where
 	{
 		self.l1.set(key, value, ttl).await
 	}
+
+	/// Number of keys waiting to be written to L2 in write-behind mode
+	pub async fn pending_writes(&self) -> usize {
+		self.pending.lock().await.len()
+	}
+
+	/// Write all queued operations to L2
+	///
+	/// If an L2 write fails, the failed and remaining operations stay queued
+	/// (unless a newer write for the same key arrived meanwhile) and the
+	/// error is returned.
+	pub async fn flush(&self) -> Result<()> {
+		let queued: Vec<(String, PendingWrite)> = self.pending.lock().await.drain().collect();
+		let mut queued = queued.into_iter();
+
+		while let Some((key, op)) = queued.next() {
+			let result = match &op {
+				PendingWrite::Set { value, expires_at } => match expires_at {
+					None => self.l2.set(&key, value, None).await,
+					Some(expires_at) => {
+						match expires_at.checked_duration_since(Instant::now()) {
+							Some(remaining) if !remaining.is_zero() => {
+								self.l2.set(&key, value, Some(remaining)).await
+							}
+							// Expired while queued: L2 must not keep an older value either
+							_ => self.l2.delete(&key).await,
+						}
+					}
+				},
+				PendingWrite::Delete => self.l2.delete(&key).await,
+			};
+			if let Err(e) = result {
+				let mut pending = self.pending.lock().await;
+				for (key, op) in std::iter::once((key, op)).chain(queued) {
+					pending.entry(key).or_insert(op);
+				}
+				return Err(e);
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Start flushing the write-behind queue periodically
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_utils::cache::{HybridCache, InMemoryCache, WriteStrategy};
+	/// use std::time::Duration;
+	///
+	/// # async fn example() {
+	/// let cache = HybridCache::new(InMemoryCache::new(), InMemoryCache::new())
+	///     .with_write_strategy(WriteStrategy::WriteBehind { max_pending: 1000 });
+	///
+	/// // Push queued writes to L2 every second
+	/// cache.start_auto_flush(Duration::from_secs(1));
+	/// # }
+	/// ```
+	pub fn start_auto_flush(&self, interval: Duration)
+	where
+		L1: 'static,
+		L2: 'static,
+	{
+		let cache = self.clone();
+		tokio::spawn(async move {
+			let mut interval_timer = tokio::time::interval(interval);
+			loop {
+				interval_timer.tick().await;
+				if let Err(e) = cache.flush().await {
+					tracing::warn!("Hybrid cache flush failed: {}", e);
+				}
+			}
+		});
+	}
+
+	/// Drop local L1 entries named by an invalidation message
+	///
+	/// L1 backends cannot match patterns, so pattern invalidations clear the
+	/// whole L1 tier.
+	#[cfg(feature = "redis-backend")]
+	pub async fn apply_invalidation(&self, message: &CacheInvalidationMessage) -> Result<()> {
+		match message {
+			CacheInvalidationMessage::InvalidateKey { key } => self.l1.delete(key).await,
+			CacheInvalidationMessage::InvalidatePattern { .. }
+			| CacheInvalidationMessage::ClearAll => self.l1.clear().await,
+		}
+	}
+
+	/// Apply invalidation messages from `subscriber` to L1 in the background
+	///
+	/// Messages published by this instance's invalidation channel are
+	/// skipped, so a write does not evict the L1 entry it just stored.
+	#[cfg(feature = "redis-backend")]
+	pub fn start_invalidation_listener(&self, mut subscriber: CacheInvalidationSubscriber)
+	where
+		L1: 'static,
+		L2: 'static,
+	{
+		if let Some(channel) = &self.invalidation {
+			subscriber.ignore_origin(channel.node_id());
+		}
+		let cache = self.clone();
+		tokio::spawn(async move {
+			loop {
+				match subscriber.next_message().await {
+					Ok(Some(message)) => {
+						if let Err(e) = cache.apply_invalidation(&message).await {
+							tracing::warn!("Hybrid cache invalidation failed: {}", e);
+						}
+					}
+					Ok(None) => break,
+					Err(e) => tracing::warn!("Hybrid cache invalidation message dropped: {}", e),
+				}
+			}
+		});
+	}
+
+	/// Queue write-behind operations, flushing once the queue is full
+	async fn enqueue<I>(&self, ops: I, max_pending: usize) -> Result<()>
+	where
+		I: IntoIterator<Item = (String, PendingWrite)>,
+	{
+		let len = {
+			let mut pending = self.pending.lock().await;
+			pending.extend(ops);
+			pending.len()
+		};
+		if len >= max_pending {
+			self.flush().await?;
+		}
+		Ok(())
+	}
+
+	/// Look up a key in the write-behind queue
+	///
+	/// Returns `Some(None)` for a queued delete and `None` if the key is not queued.
+	async fn pending_value<T>(&self, key: &str) -> Result<Option<Option<T>>>
+	where
+		T: for<'de> Deserialize<'de>,
+	{
+		match self.pending.lock().await.get(key) {
+			Some(op) if op.is_expired() => Ok(Some(None)),
+			Some(PendingWrite::Set { value, .. }) => {
+				let value = serde_json::from_value(value.clone())
+					.map_err(|e| Error::Serialization(e.to_string()))?;
+				Ok(Some(Some(value)))
+			}
+			Some(PendingWrite::Delete) => Ok(Some(None)),
+			None => Ok(None),
+		}
+	}
+
+	#[cfg_attr(not(feature = "redis-backend"), allow(unused_variables))]
+	async fn publish_invalidation(&self, key: &str) -> Result<()> {
+		#[cfg(feature = "redis-backend")]
+		if let Some(channel) = &self.invalidation {
+			channel.invalidate(key).await?;
+		}
+		Ok(())
+	}
+
+	async fn publish_clear(&self) -> Result<()> {
+		#[cfg(feature = "redis-backend")]
+		if let Some(channel) = &self.invalidation {
+			channel.clear_all().await?;
+		}
+		Ok(())
+	}
 }
 
 #[async_trait]
@@ -160,6 +426,11 @@ where
 			return Ok(Some(value));
 		}
 
+		// Queued writes are newer than anything in L2
+		if let Some(value) = self.pending_value::<T>(key).await? {
+			return Ok(value);
+		}
+
 		// Try L2 (slow path)
 		if let Some(value) = self.l2.get::<T>(key).await? {
 			// Promote to L1 for faster subsequent access
@@ -175,17 +446,41 @@ where
 	where
 		T: Serialize + Send + Sync,
 	{
-		// Write-through: update both L1 and L2
-		self.l1.set(key, value, ttl).await?;
-		self.l2.set(key, value, ttl).await?;
-		Ok(())
+		match self.strategy {
+			WriteStrategy::WriteThrough => {
+				self.l1.set(key, value, ttl).await?;
+				self.l2.set(key, value, ttl).await?;
+			}
+			WriteStrategy::WriteBehind { max_pending } => {
+				self.l1.set(key, value, ttl).await?;
+				let value =
+					serde_json::to_value(value).map_err(|e| Error::Serialization(e.to_string()))?;
+				self.enqueue(
+					[(key.to_string(), PendingWrite::set(value, ttl))],
+					max_pending,
+				)
+				.await?;
+			}
+			WriteStrategy::ReadRepair => {
+				self.l2.set(key, value, ttl).await?;
+				self.l1.delete(key).await?;
+			}
+		}
+		self.publish_invalidation(key).await
 	}
 
 	async fn delete(&self, key: &str) -> Result<()> {
-		// Delete from both caches
 		self.l1.delete(key).await?;
-		self.l2.delete(key).await?;
-		Ok(())
+		match self.strategy {
+			WriteStrategy::WriteBehind { max_pending } => {
+				self.enqueue([(key.to_string(), PendingWrite::Delete)], max_pending)
+					.await?;
+			}
+			WriteStrategy::WriteThrough | WriteStrategy::ReadRepair => {
+				self.l2.delete(key).await?;
+			}
+		}
+		self.publish_invalidation(key).await
 	}
 
 	async fn has_key(&self, key: &str) -> Result<bool> {
@@ -194,15 +489,20 @@ where
 			return Ok(true);
 		}
 
+		if let Some(op) = self.pending.lock().await.get(key) {
+			return Ok(matches!(op, PendingWrite::Set { .. }) && !op.is_expired());
+		}
+
 		// Check L2 (slow path)
 		self.l2.has_key(key).await
 	}
 
 	async fn clear(&self) -> Result<()> {
-		// Clear both caches
+		// Queued writes would resurrect cleared keys
+		self.pending.lock().await.clear();
 		self.l1.clear().await?;
 		self.l2.clear().await?;
-		Ok(())
+		self.publish_clear().await
 	}
 
 	async fn get_many<T>(&self, keys: &[&str]) -> Result<HashMap<String, T>>
@@ -215,20 +515,30 @@ where
 		let l1_results = self.l1.get_many::<T>(keys).await?;
 		results.extend(l1_results);
 
-		// Find keys not in L1
-		let missing_keys: Vec<&str> = keys
-			.iter()
-			.filter(|k| !results.contains_key(**k))
-			.copied()
-			.collect();
+		// Find keys not in L1, answering queued writes from the queue
+		let mut missing_keys = Vec::new();
+		for key in keys {
+			if results.contains_key(*key) {
+				continue;
+			}
+			match self.pending_value::<T>(key).await? {
+				Some(Some(value)) => {
+					results.insert(key.to_string(), value);
+				}
+				Some(None) => {}
+				None => missing_keys.push(*key),
+			}
+		}
 
 		if !missing_keys.is_empty() {
 			// Try L2 for missing keys
 			let l2_results = self.l2.get_many::<T>(&missing_keys).await?;
 
 			// Promote L2 results to L1 for faster subsequent access
-			for (key, value) in &l2_results {
-				self.l1.set(key, value, None).await?;
+			if !l2_results.is_empty() {
+				let promoted: HashMap<String, &T> =
+					l2_results.iter().map(|(k, v)| (k.clone(), v)).collect();
+				self.l1.set_many(promoted, None).await?;
 			}
 
 			results.extend(l2_results);
@@ -241,22 +551,56 @@ where
 	where
 		T: Serialize + Send + Sync,
 	{
-		// Write-through: update both L1 and L2
-		for (key, value) in values.iter() {
-			self.l1.set(key, value, ttl).await?;
-			self.l2.set(key, value, ttl).await?;
+		let keys: Vec<&str> = values.keys().map(String::as_str).collect();
+		let borrowed: HashMap<String, &T> = values.iter().map(|(k, v)| (k.clone(), v)).collect();
+		match self.strategy {
+			WriteStrategy::WriteThrough => {
+				// Update both L1 and L2 with one batch each
+				self.l1.set_many(borrowed.clone(), ttl).await?;
+				self.l2.set_many(borrowed, ttl).await?;
+			}
+			WriteStrategy::WriteBehind { max_pending } => {
+				self.l1.set_many(borrowed, ttl).await?;
+				let mut queued = Vec::with_capacity(values.len());
+				for (key, value) in &values {
+					let value = serde_json::to_value(value)
+						.map_err(|e| Error::Serialization(e.to_string()))?;
+					queued.push((key.clone(), PendingWrite::set(value, ttl)));
+				}
+				self.enqueue(queued, max_pending).await?;
+			}
+			WriteStrategy::ReadRepair => {
+				self.l2.set_many(borrowed, ttl).await?;
+				self.l1.delete_many(&keys).await?;
+			}
+		}
+		for key in keys {
+			self.publish_invalidation(key).await?;
 		}
 		Ok(())
 	}
 
 	async fn delete_many(&self, keys: &[&str]) -> Result<()> {
-		// Delete from both caches
 		self.l1.delete_many(keys).await?;
-		self.l2.delete_many(keys).await?;
+		match self.strategy {
+			WriteStrategy::WriteBehind { max_pending } => {
+				let ops = keys.iter().map(|k| (k.to_string(), PendingWrite::Delete));
+				self.enqueue(ops, max_pending).await?;
+			}
+			WriteStrategy::WriteThrough | WriteStrategy::ReadRepair => {
+				self.l2.delete_many(keys).await?;
+			}
+		}
+		for key in keys {
+			self.publish_invalidation(key).await?;
+		}
 		Ok(())
 	}
 
 	async fn incr(&self, key: &str, delta: i64) -> Result<i64> {
+		// Counters live in L2, so queued writes must land first
+		self.flush().await?;
+
 		// Increment in L2 (source of truth)
 		let result = self.l2.incr(key, delta).await?;
 
@@ -267,6 +611,9 @@ where
 	}
 
 	async fn decr(&self, key: &str, delta: i64) -> Result<i64> {
+		// Counters live in L2, so queued writes must land first
+		self.flush().await?;
+
 		// Decrement in L2 (source of truth)
 		let result = self.l2.decr(key, delta).await?;
 
@@ -531,4 +878,164 @@ mod tests {
 		let l1_value: Option<String> = cache.l1().get("key1").await.unwrap();
 		assert_eq!(l1_value, Some("value1".to_string()));
 	}
+
+	#[tokio::test]
+	async fn test_hybrid_cache_write_behind_queues_l2() {
+		let l1 = InMemoryCache::new();
+		let l2 = InMemoryCache::new();
+		let cache = HybridCache::new(l1.clone(), l2.clone())
+			.with_write_strategy(WriteStrategy::WriteBehind { max_pending: 10 });
+
+		cache.set("key1", &"value1", None).await.unwrap();
+		cache.set("key1", &"value2", None).await.unwrap();
+
+		// L1 is written immediately, L2 waits for a flush
+		let l1_value: Option<String> = l1.get("key1").await.unwrap();
+		let l2_value: Option<String> = l2.get("key1").await.unwrap();
+		assert_eq!(l1_value, Some("value2".to_string()));
+		assert_eq!(l2_value, None);
+		assert_eq!(cache.pending_writes().await, 1);
+
+		cache.flush().await.unwrap();
+
+		let l2_value: Option<String> = l2.get("key1").await.unwrap();
+		assert_eq!(l2_value, Some("value2".to_string()));
+		assert_eq!(cache.pending_writes().await, 0);
+	}
+
+	#[tokio::test]
+	async fn test_hybrid_cache_write_behind_flushes_when_full() {
+		let l1 = InMemoryCache::new();
+		let l2 = InMemoryCache::new();
+		let cache = HybridCache::new(l1, l2.clone())
+			.with_write_strategy(WriteStrategy::WriteBehind { max_pending: 2 });
+
+		cache.set("key1", &1, None).await.unwrap();
+		assert!(!l2.has_key("key1").await.unwrap());
+
+		cache.set("key2", &2, None).await.unwrap();
+
+		assert!(l2.has_key("key1").await.unwrap());
+		assert!(l2.has_key("key2").await.unwrap());
+		assert_eq!(cache.pending_writes().await, 0);
+	}
+
+	#[tokio::test]
+	async fn test_hybrid_cache_write_behind_reads_queue() {
+		let l1 = InMemoryCache::new();
+		let l2 = InMemoryCache::new();
+		let cache = HybridCache::new(l1.clone(), l2.clone())
+			.with_write_strategy(WriteStrategy::WriteBehind { max_pending: 10 });
+		l2.set("stale", &"old", None).await.unwrap();
+
+		cache.set("fresh", &"new", None).await.unwrap();
+		cache.delete("stale").await.unwrap();
+		l1.clear().await.unwrap();
+
+		// Queued operations win over L2 even after L1 loses the entry
+		let fresh: Option<String> = cache.get("fresh").await.unwrap();
+		let stale: Option<String> = cache.get("stale").await.unwrap();
+		assert_eq!(fresh, Some("new".to_string()));
+		assert_eq!(stale, None);
+		assert!(!cache.has_key("stale").await.unwrap());
+
+		cache.flush().await.unwrap();
+		assert!(!l2.has_key("stale").await.unwrap());
+	}
+
+	#[tokio::test]
+	async fn test_hybrid_cache_write_behind_keeps_original_ttl() {
+		let l1 = InMemoryCache::new();
+		let l2 = InMemoryCache::new();
+		let cache = HybridCache::new(l1, l2.clone())
+			.with_write_strategy(WriteStrategy::WriteBehind { max_pending: 10 });
+		l2.set("short", &"old", None).await.unwrap();
+
+		cache
+			.set("short", &"new", Some(Duration::from_millis(50)))
+			.await
+			.unwrap();
+		cache
+			.set("long", &"new", Some(Duration::from_millis(300)))
+			.await
+			.unwrap();
+		tokio::time::sleep(Duration::from_millis(100)).await;
+
+		// Expired while queued, so L2's older value is not served either
+		assert!(!cache.has_key("short").await.unwrap());
+		let short: Option<String> = cache.get("short").await.unwrap();
+		assert_eq!(short, None);
+		cache.flush().await.unwrap();
+
+		assert!(!l2.has_key("short").await.unwrap());
+		assert!(l2.has_key("long").await.unwrap());
+		// Only the remaining ~200ms reach L2, not the full 300ms
+		tokio::time::sleep(Duration::from_millis(250)).await;
+		assert!(!l2.has_key("long").await.unwrap());
+	}
+
+	#[tokio::test]
+	async fn test_hybrid_cache_clear_discards_queue() {
+		let l1 = InMemoryCache::new();
+		let l2 = InMemoryCache::new();
+		let cache = HybridCache::new(l1, l2.clone())
+			.with_write_strategy(WriteStrategy::WriteBehind { max_pending: 10 });
+
+		cache.set("key1", &"value1", None).await.unwrap();
+		cache.clear().await.unwrap();
+		cache.flush().await.unwrap();
+
+		assert!(!l2.has_key("key1").await.unwrap());
+	}
+
+	#[tokio::test]
+	async fn test_hybrid_cache_read_repair() {
+		let l1 = InMemoryCache::new();
+		let l2 = InMemoryCache::new();
+		let cache =
+			HybridCache::new(l1.clone(), l2.clone()).with_write_strategy(WriteStrategy::ReadRepair);
+		l1.set("key1", &"stale", None).await.unwrap();
+
+		cache.set("key1", &"value1", None).await.unwrap();
+
+		// Writes skip L1 and drop any stale copy
+		assert!(!l1.has_key("key1").await.unwrap());
+		let l2_value: Option<String> = l2.get("key1").await.unwrap();
+		assert_eq!(l2_value, Some("value1".to_string()));
+
+		// Reads repair L1 from L2
+		let value: Option<String> = cache.get("key1").await.unwrap();
+		assert_eq!(value, Some("value1".to_string()));
+		let l1_value: Option<String> = l1.get("key1").await.unwrap();
+		assert_eq!(l1_value, Some("value1".to_string()));
+	}
+
+	#[cfg(feature = "redis-backend")]
+	#[tokio::test]
+	async fn test_hybrid_cache_apply_invalidation() {
+		let l1 = InMemoryCache::new();
+		let l2 = InMemoryCache::new();
+		let cache = HybridCache::new(l1.clone(), l2.clone());
+		cache.set("key1", &"value1", None).await.unwrap();
+		cache.set("key2", &"value2", None).await.unwrap();
+
+		cache
+			.apply_invalidation(&CacheInvalidationMessage::InvalidateKey {
+				key: "key1".to_string(),
+			})
+			.await
+			.unwrap();
+
+		assert!(!l1.has_key("key1").await.unwrap());
+		assert!(l1.has_key("key2").await.unwrap());
+		assert!(l2.has_key("key1").await.unwrap());
+
+		cache
+			.apply_invalidation(&CacheInvalidationMessage::ClearAll)
+			.await
+			.unwrap();
+
+		assert!(!l1.has_key("key2").await.unwrap());
+		assert!(l2.has_key("key2").await.unwrap());
+	}
 }
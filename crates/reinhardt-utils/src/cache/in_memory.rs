@@ -263,6 +263,7 @@ impl InMemoryCache {
 			total_requests: hits + misses,
 			entry_count,
 			memory_usage,
+			patterns: Default::default(),
 		}
 	}
 
@@ -618,6 +619,94 @@ impl Cache for InMemoryCache {
 		}
 		Ok(())
 	}
+
+	async fn get_many<T>(&self, keys: &[&str]) -> Result<HashMap<String, T>>
+	where
+		T: for<'de> Deserialize<'de> + Serialize + Send + Sync,
+	{
+		let raw = match self.cleanup_strategy {
+			CleanupStrategy::Naive => {
+				let mut store = self.store.write().await;
+				let mut raw = HashMap::with_capacity(keys.len());
+				for key in keys {
+					if let Some(entry) = store.get_mut(*key)
+						&& !entry.is_expired()
+					{
+						entry.touch();
+						raw.insert(key.to_string(), entry.value.clone());
+					}
+				}
+				raw
+			}
+			CleanupStrategy::Layered => match self.layered_store {
+				Some(ref layered_store) => layered_store.get_many(keys).await,
+				None => HashMap::new(),
+			},
+		};
+
+		let hits = raw.len() as u64;
+		self.hits.fetch_add(hits, Ordering::Relaxed);
+		self.misses
+			.fetch_add(keys.len() as u64 - hits, Ordering::Relaxed);
+
+		raw.into_iter()
+			.map(|(key, data)| {
+				serde_json::from_slice(&data)
+					.map(|value| (key, value))
+					.map_err(|e| Error::Serialization(e.to_string()))
+			})
+			.collect()
+	}
+
+	async fn set_many<T>(&self, values: HashMap<String, T>, ttl: Option<Duration>) -> Result<()>
+	where
+		T: Serialize + Send + Sync,
+	{
+		// Serialize everything before taking the lock
+		let serialized = values
+			.into_iter()
+			.map(|(key, value)| {
+				serde_json::to_vec(&value)
+					.map(|data| (key, data))
+					.map_err(|e| Error::Serialization(e.to_string()))
+			})
+			.collect::<Result<Vec<_>>>()?;
+
+		let ttl = ttl.or(self.default_ttl);
+
+		match self.cleanup_strategy {
+			CleanupStrategy::Naive => {
+				let mut store = self.store.write().await;
+				for (key, data) in serialized {
+					store.insert(key, CacheEntry::new(data, ttl));
+				}
+			}
+			CleanupStrategy::Layered => {
+				if let Some(ref layered_store) = self.layered_store {
+					layered_store.set_many(serialized, ttl).await;
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	async fn delete_many(&self, keys: &[&str]) -> Result<()> {
+		match self.cleanup_strategy {
+			CleanupStrategy::Naive => {
+				let mut store = self.store.write().await;
+				for key in keys {
+					store.remove(*key);
+				}
+			}
+			CleanupStrategy::Layered => {
+				if let Some(ref layered_store) = self.layered_store {
+					layered_store.delete_many(keys).await;
+				}
+			}
+		}
+		Ok(())
+	}
 }
 
 #[cfg(test)]
@@ -693,6 +782,42 @@ mod tests {
 		assert!(!cache.has_key("key2").await.unwrap());
 	}
 
+	#[tokio::test]
+	async fn test_in_memory_cache_many_layered() {
+		let cache = InMemoryCache::with_layered_cleanup();
+
+		let mut values = HashMap::new();
+		values.insert("key1".to_string(), 1);
+		values.insert("key2".to_string(), 2);
+		cache
+			.set_many(values, Some(Duration::from_secs(60)))
+			.await
+			.unwrap();
+
+		let results: HashMap<String, i32> =
+			cache.get_many(&["key1", "key2", "key3"]).await.unwrap();
+		assert_eq!(
+			results,
+			HashMap::from([("key1".to_string(), 1), ("key2".to_string(), 2)])
+		);
+
+		cache.delete_many(&["key1"]).await.unwrap();
+		assert!(!cache.has_key("key1").await.unwrap());
+		assert!(cache.has_key("key2").await.unwrap());
+	}
+
+	#[tokio::test]
+	async fn test_in_memory_cache_get_many_statistics() {
+		let cache = InMemoryCache::new();
+		cache.set("key1", &"value1", None).await.unwrap();
+
+		let _: HashMap<String, String> = cache.get_many(&["key1", "key2", "key3"]).await.unwrap();
+
+		let stats = cache.get_statistics().await;
+		assert_eq!(stats.hits, 1);
+		assert_eq!(stats.misses, 2);
+	}
+
 	#[tokio::test]
 	async fn test_in_memory_cache_incr_decr() {
 		let cache = InMemoryCache::new();
@@ -1,10 +1,20 @@
 //! Cache key builder for generating cache keys
 
+use super::cache_trait::Cache;
+use reinhardt_core::exception::Result;
+
 /// Cache key builder for generating cache keys
+///
+/// Keys have the form `prefix:version:key`, or `prefix:version:namespace:key`
+/// when a namespace is set. Keys can additionally carry a version counter
+/// stored in the cache (see [`build_versioned`](Self::build_versioned)), so a
+/// whole namespace, or the un-namespaced keys of the prefix, are invalidated
+/// with a single [`incr_version`](Self::incr_version).
 #[derive(Clone)]
 pub struct CacheKeyBuilder {
 	prefix: String,
 	version: u32,
+	namespace: Option<String>,
 }
 
 impl CacheKeyBuilder {
@@ -22,6 +32,7 @@ impl CacheKeyBuilder {
 		Self {
 			prefix: prefix.into(),
 			version: 1,
+			namespace: None,
 		}
 	}
 	/// Set the version for cache key namespacing
@@ -38,6 +49,26 @@ impl CacheKeyBuilder {
 		self.version = version;
 		self
 	}
+	/// Group keys under a namespace
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_utils::cache::CacheKeyBuilder;
+	///
+	/// let builder = CacheKeyBuilder::new("myapp").with_namespace("users");
+	/// assert_eq!(builder.build("42"), "myapp:1:users:42");
+	/// ```
+	pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+		self.namespace = Some(namespace.into());
+		self
+	}
+
+	/// Get the namespace, if any
+	pub fn namespace(&self) -> Option<&str> {
+		self.namespace.as_deref()
+	}
+
 	/// Build a cache key with prefix and version
 	///
 	/// # Examples
@@ -50,7 +81,10 @@ impl CacheKeyBuilder {
 	/// assert_eq!(key, "app:3:user:123");
 	/// ```
 	pub fn build(&self, key: &str) -> String {
-		format!("{}:{}:{}", self.prefix, self.version, key)
+		match &self.namespace {
+			Some(namespace) => format!("{}:{}:{}:{}", self.prefix, self.version, namespace, key),
+			None => format!("{}:{}:{}", self.prefix, self.version, key),
+		}
 	}
 	/// Build multiple cache keys at once
 	///
@@ -66,6 +100,55 @@ impl CacheKeyBuilder {
 	pub fn build_many(&self, keys: &[&str]) -> Vec<String> {
 		keys.iter().map(|k| self.build(k)).collect()
 	}
+
+	/// Build a cache key that includes the namespace's current version
+	///
+	/// Without a namespace the global version of the prefix is used instead.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_utils::cache::{CacheKeyBuilder, InMemoryCache};
+	///
+	/// # async fn example() -> reinhardt_core::exception::Result<()> {
+	/// let cache = InMemoryCache::new();
+	/// let users = CacheKeyBuilder::new("myapp").with_namespace("users");
+	///
+	/// assert_eq!(users.build_versioned(&cache, "42").await?, "myapp:1:users:1:42");
+	///
+	/// // Every key in the namespace moves to version 2
+	/// users.incr_version(&cache).await?;
+	/// assert_eq!(users.build_versioned(&cache, "42").await?, "myapp:1:users:2:42");
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub async fn build_versioned<C>(&self, cache: &C, key: &str) -> Result<String>
+	where
+		C: Cache + ?Sized,
+	{
+		let namespace_version = cache.namespace_version(&self.version_namespace()).await?;
+		Ok(self.build(&format!("{}:{}", namespace_version, key)))
+	}
+
+	/// Invalidate every key built by [`build_versioned`](Self::build_versioned)
+	///
+	/// Returns the new version. Without a namespace the global version of
+	/// the prefix is bumped, invalidating the versioned keys built without a
+	/// namespace.
+	pub async fn incr_version<C>(&self, cache: &C) -> Result<u64>
+	where
+		C: Cache + ?Sized,
+	{
+		cache.incr_version(&self.version_namespace()).await
+	}
+
+	/// Namespace name used for the version counter, scoped by prefix and version
+	fn version_namespace(&self) -> String {
+		match &self.namespace {
+			Some(namespace) => format!("{}:{}:{}", self.prefix, self.version, namespace),
+			None => format!("{}:{}", self.prefix, self.version),
+		}
+	}
 }
 
 impl Default for CacheKeyBuilder {
@@ -77,6 +160,7 @@ impl Default for CacheKeyBuilder {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::cache::InMemoryCache;
 
 	#[tokio::test]
 	async fn test_cache_key_builder() {
@@ -87,4 +171,44 @@ mod tests {
 		let keys = builder.build_many(&["key1", "key2"]);
 		assert_eq!(keys, vec!["myapp:2:key1", "myapp:2:key2"]);
 	}
+
+	#[tokio::test]
+	async fn test_cache_key_builder_namespace_versioning() {
+		let cache = InMemoryCache::new();
+		let users = CacheKeyBuilder::new("myapp").with_namespace("users");
+		let posts = CacheKeyBuilder::new("myapp").with_namespace("posts");
+
+		let user_key = users.build_versioned(&cache, "1").await.unwrap();
+		let post_key = posts.build_versioned(&cache, "1").await.unwrap();
+		cache.set(&user_key, &"alice", None).await.unwrap();
+		cache.set(&post_key, &"hello", None).await.unwrap();
+
+		assert_eq!(users.incr_version(&cache).await.unwrap(), 2);
+
+		// Only the bumped namespace is invalidated
+		let user_key = users.build_versioned(&cache, "1").await.unwrap();
+		let post_key = posts.build_versioned(&cache, "1").await.unwrap();
+		let user: Option<String> = cache.get(&user_key).await.unwrap();
+		let post: Option<String> = cache.get(&post_key).await.unwrap();
+		assert_eq!(user_key, "myapp:1:users:2:1");
+		assert_eq!(user, None);
+		assert_eq!(post, Some("hello".to_string()));
+	}
+
+	#[tokio::test]
+	async fn test_cache_key_builder_without_namespace() {
+		let cache = InMemoryCache::new();
+		let builder = CacheKeyBuilder::new("myapp");
+
+		let key = builder.build_versioned(&cache, "key").await.unwrap();
+		cache.set(&key, &"value", None).await.unwrap();
+		assert_eq!(key, "myapp:1:1:key");
+
+		assert_eq!(builder.incr_version(&cache).await.unwrap(), 2);
+
+		let key = builder.build_versioned(&cache, "key").await.unwrap();
+		let value: Option<String> = cache.get(&key).await.unwrap();
+		assert_eq!(key, "myapp:1:2:key");
+		assert_eq!(value, None);
+	}
 }
@@ -162,6 +162,68 @@ impl LayeredCacheStore {
 		store.insert(key, entry);
 	}
 
+	/// Get multiple values under a single lock acquisition
+	///
+	/// Missing and expired keys are absent from the result. Expired entries
+	/// are deleted on access.
+	///
+	/// Time complexity: O(k) for k keys
+	pub async fn get_many(&self, keys: &[&str]) -> HashMap<String, Vec<u8>> {
+		let mut store = self.store.write().await;
+		let mut results = HashMap::with_capacity(keys.len());
+
+		for key in keys {
+			let Some(entry) = store.get_mut(*key) else {
+				continue;
+			};
+			if entry.is_expired() {
+				store.remove(*key);
+				continue;
+			}
+			entry.touch();
+			results.insert(key.to_string(), entry.value.clone());
+		}
+		results
+	}
+
+	/// Set multiple values under a single lock acquisition
+	///
+	/// Time complexity: O(k) for k entries
+	pub async fn set_many(
+		&self,
+		entries: impl IntoIterator<Item = (String, Vec<u8>)>,
+		ttl: Option<std::time::Duration>,
+	) {
+		let mut store = self.store.write().await;
+		let mut ttl_index = self.ttl_index.write().await;
+
+		for (key, value) in entries {
+			let entry = CacheEntry::new(value, ttl);
+			if let Some(expires_at) = entry.expires_at {
+				let timestamp = expires_at
+					.duration_since(SystemTime::UNIX_EPOCH)
+					.ok()
+					.map(|d| d.as_secs())
+					.unwrap_or(0);
+				ttl_index
+					.entry(timestamp)
+					.or_insert_with(Vec::new)
+					.push(key.clone());
+			}
+			store.insert(key, entry);
+		}
+	}
+
+	/// Delete multiple keys under a single lock acquisition
+	///
+	/// Time complexity: O(k) for k keys
+	pub async fn delete_many(&self, keys: &[&str]) {
+		let mut store = self.store.write().await;
+		for key in keys {
+			store.remove(*key);
+		}
+	}
+
 	/// Delete a key from the cache
 	///
 	/// Time complexity: O(1)
@@ -69,10 +69,12 @@
 
 use super::Result;
 use super::cache_trait::Cache;
+use super::compression::{CacheCompression, decode_value, encode_value};
 use async_trait::async_trait;
 use memcache_async::ascii::Protocol;
 use reinhardt_core::exception::Error;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::time::Duration;
 use tokio::net::TcpStream;
 use tokio::sync::Mutex;
@@ -132,6 +134,7 @@ impl Default for MemcachedConfig {
 /// Memcached-based cache backend with multi-server support.
 pub struct MemcachedCache {
 	servers: Vec<Mutex<MemcachedProtocol>>,
+	compression: Option<CacheCompression>,
 }
 
 impl MemcachedCache {
@@ -174,7 +177,19 @@ impl MemcachedCache {
 			}));
 		}
 
-		Ok(Self { servers: protocols })
+		Ok(Self {
+			servers: protocols,
+			compression: None,
+		})
+	}
+
+	/// Compress large values before storing them
+	///
+	/// Entries written without compression remain readable. Memcached's
+	/// default 1 MiB item limit applies to the compressed size.
+	pub fn with_compression(mut self, compression: CacheCompression) -> Self {
+		self.compression = Some(compression);
+		self
 	}
 
 	/// Helper method to connect to a single Memcached server.
@@ -208,6 +223,18 @@ impl MemcachedCache {
 		&self.servers[index % self.servers.len()]
 	}
 
+	/// Group keys by the server they hash to.
+	fn group_keys_by_server<'a>(&self, keys: &[&'a str]) -> HashMap<usize, Vec<&'a str>> {
+		let mut groups: HashMap<usize, Vec<&'a str>> = HashMap::new();
+		for key in keys {
+			groups
+				.entry(self.get_server_index_for_key(key))
+				.or_default()
+				.push(key);
+		}
+		groups
+	}
+
 	/// Create a new Memcached cache from URL.
 	pub async fn from_url(url: &str) -> Result<Self> {
 		let config = MemcachedConfig {
@@ -241,9 +268,7 @@ impl Cache for MemcachedCache {
 						return Ok(None);
 					}
 
-					let deserialized: T = serde_json::from_slice(&value).map_err(|e| {
-						Error::Serialization(format!("Failed to deserialize value: {}", e))
-					})?;
+					let deserialized: T = decode_value(&value)?;
 					return Ok(Some(deserialized));
 				}
 				Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
@@ -267,8 +292,7 @@ impl Cache for MemcachedCache {
 	where
 		T: Serialize + Send + Sync,
 	{
-		let serialized = serde_json::to_vec(value)
-			.map_err(|e| Error::Serialization(format!("Failed to serialize value: {}", e)))?;
+		let serialized = encode_value(value, self.compression.as_ref())?;
 
 		let expiration = ttl.map(|d| d.as_secs() as u32).unwrap_or(0);
 		let start_index = self.get_server_index_for_key(key);
@@ -384,6 +408,105 @@ impl Cache for MemcachedCache {
 				.unwrap_or_else(|| Error::Http("Failed to clear cache on all servers".to_string())))
 		}
 	}
+
+	async fn get_many<T>(&self, keys: &[&str]) -> Result<HashMap<String, T>>
+	where
+		T: for<'de> Deserialize<'de> + Serialize + Send + Sync,
+	{
+		let mut results = HashMap::with_capacity(keys.len());
+
+		// One multi-key `get` per server
+		for (index, server_keys) in self.group_keys_by_server(keys) {
+			let fetched = {
+				let mut protocol = self.get_server(index).lock().await;
+				protocol.get_multi(&server_keys).await
+			};
+
+			match fetched {
+				Ok(values) => {
+					for (key, value) in values {
+						// Deleted keys are stored as empty values
+						if value.is_empty() {
+							continue;
+						}
+						let deserialized: T = decode_value(&value)?;
+						results.insert(key, deserialized);
+					}
+				}
+				Err(e) => {
+					eprintln!(
+						"Warning: Get_many operation failed on server {}, falling back to single gets: {}",
+						index, e
+					);
+					for key in server_keys {
+						if let Some(value) = self.get(key).await? {
+							results.insert(key.to_string(), value);
+						}
+					}
+				}
+			}
+		}
+
+		Ok(results)
+	}
+
+	async fn set_many<T>(&self, values: HashMap<String, T>, ttl: Option<Duration>) -> Result<()>
+	where
+		T: Serialize + Send + Sync,
+	{
+		let keys: Vec<&str> = values.keys().map(String::as_str).collect();
+		let expiration = ttl.map(|d| d.as_secs() as u32).unwrap_or(0);
+
+		// Lock each server once for all of its keys
+		for (index, server_keys) in self.group_keys_by_server(&keys) {
+			let mut failed = Vec::new();
+			{
+				let mut protocol = self.get_server(index).lock().await;
+				for key in server_keys {
+					let serialized = encode_value(&values[key], self.compression.as_ref())?;
+					if let Err(e) = protocol.set(key, &serialized, expiration).await {
+						eprintln!(
+							"Warning: Set operation failed on server {}, trying next: {}",
+							index, e
+						);
+						failed.push(key);
+					}
+				}
+			}
+
+			for key in failed {
+				self.set(key, &values[key], ttl).await?;
+			}
+		}
+
+		Ok(())
+	}
+
+	async fn delete_many(&self, keys: &[&str]) -> Result<()> {
+		// Lock each server once for all of its keys
+		for (index, server_keys) in self.group_keys_by_server(keys) {
+			let mut failed = Vec::new();
+			{
+				let mut protocol = self.get_server(index).lock().await;
+				for key in server_keys {
+					// Same immediate-expiration workaround as `delete`
+					if let Err(e) = protocol.set(key, &[], 1).await {
+						eprintln!(
+							"Warning: Delete operation failed on server {}, trying next: {}",
+							index, e
+						);
+						failed.push(key);
+					}
+				}
+			}
+
+			for key in failed {
+				self.delete(key).await?;
+			}
+		}
+
+		Ok(())
+	}
 }
 
 #[cfg(test)]
@@ -433,6 +556,41 @@ mod tests {
 		assert!(!exists);
 	}
 
+	#[tokio::test]
+	async fn test_memcached_batch_operations() {
+		let (_container, url) = reinhardt_test::containers::start_memcached().await;
+		let cache = MemcachedCache::from_url(&url)
+			.await
+			.expect("Failed to connect to Memcached");
+
+		let values = HashMap::from([
+			("batch_key1".to_string(), "value1".to_string()),
+			("batch_key2".to_string(), "value2".to_string()),
+		]);
+		cache
+			.set_many(values, Some(Duration::from_secs(60)))
+			.await
+			.expect("Failed to set many");
+
+		let results: HashMap<String, String> = cache
+			.get_many(&["batch_key1", "batch_key2", "batch_key3"])
+			.await
+			.expect("Failed to get many");
+		assert_eq!(results.len(), 2);
+		assert_eq!(results.get("batch_key1"), Some(&"value1".to_string()));
+
+		cache
+			.delete_many(&["batch_key1", "batch_key2"])
+			.await
+			.expect("Failed to delete many");
+
+		let results: HashMap<String, String> = cache
+			.get_many(&["batch_key1", "batch_key2"])
+			.await
+			.expect("Failed to get many after delete");
+		assert!(results.is_empty());
+	}
+
 	#[tokio::test]
 	async fn test_multiple_servers_connection() {
 		// Start 3 Memcached containers
@@ -0,0 +1,261 @@
+//! Function result memoization
+//!
+//! [`Memoize`] caches the return value of an async computation under a key
+//! derived from its arguments. It is the runtime behind the `#[cached]`
+//! attribute macro, but can also be used directly.
+//!
+//! Cache failures never fail the wrapped call: when the backend cannot be
+//! read or written the value is simply recomputed.
+
+use super::cache_trait::Cache;
+use reinhardt_core::exception::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::Duration;
+
+type Predicate<R> = Box<dyn Fn(&R) -> bool + Send + Sync>;
+
+/// Memoization settings for a single function
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_utils::cache::{InMemoryCache, Memoize};
+/// use std::time::Duration;
+///
+/// # async fn example() {
+/// let cache = InMemoryCache::new();
+/// let memo = Memoize::<Vec<u32>>::new("app:squares")
+///     .with_ttl(Duration::from_secs(60))
+///     .unless(|squares| squares.is_empty());
+///
+/// let n = 4u32;
+/// let key = memo.key(&n).unwrap();
+/// let squares = memo
+///     .get_or_compute(&cache, Some(&key), async move { (1..=n).map(|i| i * i).collect() })
+///     .await;
+/// assert_eq!(squares, vec![1, 4, 9, 16]);
+/// # }
+/// ```
+pub struct Memoize<R> {
+	prefix: String,
+	ttl: Option<Duration>,
+	unless: Option<Predicate<R>>,
+}
+
+impl<R> Memoize<R>
+where
+	R: for<'de> Deserialize<'de> + Serialize + Send + Sync,
+{
+	/// Create memoization settings storing entries under `prefix`
+	pub fn new(prefix: impl Into<String>) -> Self {
+		Self {
+			prefix: prefix.into(),
+			ttl: None,
+			unless: None,
+		}
+	}
+
+	/// Expire cached results after `ttl`
+	pub fn with_ttl(mut self, ttl: Duration) -> Self {
+		self.ttl = Some(ttl);
+		self
+	}
+
+	/// Skip caching results for which `predicate` returns `true`
+	pub fn unless<F>(mut self, predicate: F) -> Self
+	where
+		F: Fn(&R) -> bool + Send + Sync + 'static,
+	{
+		self.unless = Some(Box::new(predicate));
+		self
+	}
+
+	/// Key prefix
+	pub fn prefix(&self) -> &str {
+		&self.prefix
+	}
+
+	/// Build the cache key for a set of arguments
+	///
+	/// Arguments are serialized to JSON and hashed, so the key has a fixed
+	/// length regardless of argument size: `{prefix}:{md5 hex}`.
+	pub fn key<A>(&self, args: &A) -> Result<String>
+	where
+		A: Serialize + ?Sized,
+	{
+		use md5::{Digest, Md5};
+
+		let encoded = serde_json::to_vec(args)
+			.map_err(|e| Error::Serialization(format!("Failed to serialize arguments: {}", e)))?;
+		Ok(format!(
+			"{}:{}",
+			self.prefix,
+			hex::encode(Md5::digest(&encoded))
+		))
+	}
+
+	/// Return the cached value for `key`, or await `compute` and cache its output
+	///
+	/// When `key` is `None` (the arguments could not be serialized) the
+	/// cache is bypassed entirely.
+	pub async fn get_or_compute<C, F>(&self, cache: &C, key: Option<&str>, compute: F) -> R
+	where
+		C: Cache + ?Sized,
+		F: Future<Output = R>,
+	{
+		let Some(key) = key else {
+			return compute.await;
+		};
+
+		match cache.get::<R>(key).await {
+			Ok(Some(value)) => return value,
+			Ok(None) => {}
+			Err(e) => tracing::warn!("Memoized lookup of {} failed: {}", key, e),
+		}
+
+		let value = compute.await;
+		self.store(cache, key, &value).await;
+		value
+	}
+
+	/// Like [`get_or_compute`](Self::get_or_compute) for fallible computations
+	///
+	/// Only `Ok` values are cached; errors are returned to the caller and the
+	/// next call computes again.
+	pub async fn try_get_or_compute<C, F, E>(
+		&self,
+		cache: &C,
+		key: Option<&str>,
+		compute: F,
+	) -> std::result::Result<R, E>
+	where
+		C: Cache + ?Sized,
+		F: Future<Output = std::result::Result<R, E>>,
+	{
+		let Some(key) = key else {
+			return compute.await;
+		};
+
+		match cache.get::<R>(key).await {
+			Ok(Some(value)) => return Ok(value),
+			Ok(None) => {}
+			Err(e) => tracing::warn!("Memoized lookup of {} failed: {}", key, e),
+		}
+
+		let value = compute.await?;
+		self.store(cache, key, &value).await;
+		Ok(value)
+	}
+
+	/// Drop the cached result for a set of arguments
+	pub async fn invalidate<C, A>(&self, cache: &C, args: &A) -> Result<()>
+	where
+		C: Cache + ?Sized,
+		A: Serialize + ?Sized,
+	{
+		cache.delete(&self.key(args)?).await
+	}
+
+	async fn store<C>(&self, cache: &C, key: &str, value: &R)
+	where
+		C: Cache + ?Sized,
+	{
+		if self.unless.as_ref().is_some_and(|skip| skip(value)) {
+			return;
+		}
+		if let Err(e) = cache.set(key, value, self.ttl).await {
+			tracing::warn!("Memoized store of {} failed: {}", key, e);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cache::InMemoryCache;
+	use rstest::rstest;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_get_or_compute_caches_result() {
+		let cache = InMemoryCache::new();
+		let memo = Memoize::<String>::new("test");
+		let calls = AtomicUsize::new(0);
+		let key = memo.key(&("a", 1)).unwrap();
+
+		for _ in 0..3 {
+			let value = memo
+				.get_or_compute(&cache, Some(&key), async {
+					calls.fetch_add(1, Ordering::SeqCst);
+					"computed".to_string()
+				})
+				.await;
+			assert_eq!(value, "computed");
+		}
+
+		assert_eq!(calls.load(Ordering::SeqCst), 1);
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_unless_skips_caching() {
+		let cache = InMemoryCache::new();
+		let memo = Memoize::<Vec<i32>>::new("test").unless(|v| v.is_empty());
+		let key = memo.key(&1).unwrap();
+
+		memo.get_or_compute(&cache, Some(&key), async { Vec::new() })
+			.await;
+
+		assert!(!cache.has_key(&key).await.unwrap());
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_try_get_or_compute_skips_errors() {
+		let cache = InMemoryCache::new();
+		let memo = Memoize::<u32>::new("test");
+		let key = memo.key(&()).unwrap();
+
+		let failed: std::result::Result<u32, &str> = memo
+			.try_get_or_compute(&cache, Some(&key), async { Err("boom") })
+			.await;
+		let ok: std::result::Result<u32, &str> = memo
+			.try_get_or_compute(&cache, Some(&key), async { Ok(7) })
+			.await;
+		let cached: std::result::Result<u32, &str> = memo
+			.try_get_or_compute(&cache, Some(&key), async { Ok(8) })
+			.await;
+
+		assert_eq!(failed, Err("boom"));
+		assert_eq!(ok, Ok(7));
+		assert_eq!(cached, Ok(7));
+	}
+
+	#[rstest]
+	fn test_key_depends_on_prefix_and_args() {
+		let users = Memoize::<u32>::new("users");
+		let posts = Memoize::<u32>::new("posts");
+
+		let key = users.key(&(1, "x")).unwrap();
+
+		assert!(key.starts_with("users:"));
+		assert_eq!(key, users.key(&(1, "x")).unwrap());
+		assert_ne!(key, users.key(&(2, "x")).unwrap());
+		assert_ne!(key, posts.key(&(1, "x")).unwrap());
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_invalidate() {
+		let cache = InMemoryCache::new();
+		let memo = Memoize::<u32>::new("test");
+		let key = memo.key(&5).unwrap();
+		memo.get_or_compute(&cache, Some(&key), async { 1 }).await;
+
+		memo.invalidate(&cache, &5).await.unwrap();
+
+		assert!(!cache.has_key(&key).await.unwrap());
+	}
+}
@@ -0,0 +1,468 @@
+//! Per-pattern cache metrics
+//!
+//! [`CacheMetrics`] aggregates hits, misses, writes and latency by key
+//! prefix, and [`InstrumentedCache`] records them for any [`Cache`] backend.
+//! The collected numbers can be exported in Prometheus text format.
+//!
+//! # Examples
+//!
+//! ```
+//! use reinhardt_utils::cache::{Cache, CacheMetrics, InMemoryCache, InstrumentedCache};
+//! use std::sync::Arc;
+//!
+//! # async fn example() -> reinhardt_core::exception::Result<()> {
+//! let metrics = Arc::new(CacheMetrics::new().with_pattern("views:user"));
+//! let cache = InstrumentedCache::new(InMemoryCache::new(), metrics.clone());
+//!
+//! cache.set("views:user:1", &"<html>", None).await?;
+//! let _: Option<String> = cache.get("views:user:1").await?; // hit
+//! let _: Option<String> = cache.get("views:user:2").await?; // miss
+//! let _: Option<String> = cache.get("session:abc").await?; // miss, pattern "session"
+//!
+//! let stats = metrics.statistics();
+//! assert_eq!(stats.patterns["views:user"].hits, 1);
+//! assert_eq!(stats.patterns["views:user"].misses, 1);
+//! assert_eq!(stats.patterns["session"].misses, 1);
+//! assert!(metrics.export_prometheus().contains("cache_hits_total{pattern=\"views:user\"} 1"));
+//! # Ok(())
+//! # }
+//! ```
+
+use super::cache_trait::Cache;
+use super::statistics::{CacheStatistics, PatternStatistics};
+use async_trait::async_trait;
+use reinhardt_core::exception::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Pattern label for keys that have no prefix at the configured depth
+pub const OTHER_PATTERN: &str = "_other";
+
+/// Cache metrics aggregated by key pattern
+///
+/// A key's pattern is the longest registered pattern it starts with, or
+/// otherwise its first `prefix_depth` `:`-separated segments (default 1).
+/// Keys with no separator at that depth are grouped under [`OTHER_PATTERN`]
+/// to keep the number of patterns bounded.
+#[derive(Debug)]
+pub struct CacheMetrics {
+	patterns: Vec<String>,
+	prefix_depth: usize,
+	stats: Mutex<HashMap<String, PatternStatistics>>,
+}
+
+impl CacheMetrics {
+	/// Create metrics grouping keys by their first segment
+	pub fn new() -> Self {
+		Self {
+			patterns: Vec::new(),
+			prefix_depth: 1,
+			stats: Mutex::new(HashMap::new()),
+		}
+	}
+
+	/// Group keys by their first `depth` `:`-separated segments
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_utils::cache::CacheMetrics;
+	///
+	/// let metrics = CacheMetrics::new().with_prefix_depth(2);
+	/// assert_eq!(metrics.pattern_for("views:users:42"), "views:users");
+	/// assert_eq!(metrics.pattern_for("views"), "_other");
+	/// ```
+	pub fn with_prefix_depth(mut self, depth: usize) -> Self {
+		self.prefix_depth = depth.max(1);
+		self
+	}
+
+	/// Register an explicit key prefix to aggregate under
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_utils::cache::CacheMetrics;
+	///
+	/// let metrics = CacheMetrics::new().with_pattern("views:user");
+	/// assert_eq!(metrics.pattern_for("views:user:42"), "views:user");
+	/// assert_eq!(metrics.pattern_for("views:post:1"), "views");
+	/// ```
+	pub fn with_pattern(mut self, prefix: impl Into<String>) -> Self {
+		self.patterns.push(prefix.into());
+		// Longest prefix first so the most specific pattern wins
+		self.patterns.sort_by_key(|p| std::cmp::Reverse(p.len()));
+		self
+	}
+
+	/// Resolve the pattern a key is aggregated under
+	pub fn pattern_for(&self, key: &str) -> String {
+		if let Some(pattern) = self.patterns.iter().find(|p| key.starts_with(p.as_str())) {
+			return pattern.clone();
+		}
+
+		match key.match_indices(':').nth(self.prefix_depth - 1) {
+			Some((end, _)) => key[..end].to_string(),
+			None => OTHER_PATTERN.to_string(),
+		}
+	}
+
+	/// Record a read that found a value
+	pub fn record_hit(&self, key: &str, latency: Duration) {
+		self.record(key, latency, |stats| stats.hits += 1);
+	}
+
+	/// Record a read that found nothing
+	pub fn record_miss(&self, key: &str, latency: Duration) {
+		self.record(key, latency, |stats| stats.misses += 1);
+	}
+
+	/// Record a write or delete
+	pub fn record_write(&self, key: &str, latency: Duration) {
+		self.record(key, latency, |stats| stats.writes += 1);
+	}
+
+	fn record(&self, key: &str, latency: Duration, update: impl FnOnce(&mut PatternStatistics)) {
+		let pattern = self.pattern_for(key);
+		let mut stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+		let entry = stats.entry(pattern).or_default();
+		update(entry);
+		entry.operations += 1;
+		entry.total_latency += latency;
+	}
+
+	/// Get statistics for a single pattern
+	pub fn pattern(&self, pattern: &str) -> Option<PatternStatistics> {
+		self.stats
+			.lock()
+			.unwrap_or_else(|e| e.into_inner())
+			.get(pattern)
+			.cloned()
+	}
+
+	/// Get totals and per-pattern statistics
+	///
+	/// `entry_count` and `memory_usage` are left at zero; they are properties
+	/// of the backend, not of the recorded traffic.
+	pub fn statistics(&self) -> CacheStatistics {
+		let patterns = self.stats.lock().unwrap_or_else(|e| e.into_inner()).clone();
+		let hits = patterns.values().map(|s| s.hits).sum();
+		let misses = patterns.values().map(|s| s.misses).sum();
+
+		CacheStatistics {
+			hits,
+			misses,
+			total_requests: hits + misses,
+			patterns,
+			..Default::default()
+		}
+	}
+
+	/// Clear all recorded statistics
+	pub fn reset(&self) {
+		self.stats.lock().unwrap_or_else(|e| e.into_inner()).clear();
+	}
+
+	/// Export per-pattern metrics in Prometheus text format
+	pub fn export_prometheus(&self) -> String {
+		let mut patterns: Vec<(String, PatternStatistics)> = self
+			.stats
+			.lock()
+			.unwrap_or_else(|e| e.into_inner())
+			.iter()
+			.map(|(pattern, stats)| (escape_label(pattern), stats.clone()))
+			.collect();
+		patterns.sort_by(|a, b| a.0.cmp(&b.0));
+
+		let mut output = String::new();
+		write_counter(
+			&mut output,
+			"cache_hits_total",
+			"Cache reads that found a value",
+			&patterns,
+			|s| s.hits,
+		);
+		write_counter(
+			&mut output,
+			"cache_misses_total",
+			"Cache reads that found nothing",
+			&patterns,
+			|s| s.misses,
+		);
+		write_counter(
+			&mut output,
+			"cache_writes_total",
+			"Cache writes and deletes",
+			&patterns,
+			|s| s.writes,
+		);
+
+		let _ = writeln!(
+			output,
+			"# HELP cache_operation_duration_seconds Time spent in cache operations"
+		);
+		let _ = writeln!(output, "# TYPE cache_operation_duration_seconds summary");
+		for (pattern, stats) in &patterns {
+			let _ = writeln!(
+				output,
+				"cache_operation_duration_seconds_sum{{pattern=\"{}\"}} {:.6}",
+				pattern,
+				stats.total_latency.as_secs_f64()
+			);
+			let _ = writeln!(
+				output,
+				"cache_operation_duration_seconds_count{{pattern=\"{}\"}} {}",
+				pattern, stats.operations
+			);
+		}
+
+		output
+	}
+}
+
+impl Default for CacheMetrics {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+fn write_counter(
+	output: &mut String,
+	name: &str,
+	help: &str,
+	patterns: &[(String, PatternStatistics)],
+	value: impl Fn(&PatternStatistics) -> u64,
+) {
+	let _ = writeln!(output, "# HELP {} {}", name, help);
+	let _ = writeln!(output, "# TYPE {} counter", name);
+	for (pattern, stats) in patterns {
+		let _ = writeln!(
+			output,
+			"{}{{pattern=\"{}\"}} {}",
+			name,
+			pattern,
+			value(stats)
+		);
+	}
+}
+
+fn escape_label(value: &str) -> String {
+	value
+		.replace('\\', "\\\\")
+		.replace('"', "\\\"")
+		.replace('\n', "\\n")
+}
+
+/// Cache wrapper that records [`CacheMetrics`] for every operation
+///
+/// Reads count as hits or misses, writes and deletes as writes. `has_key`,
+/// `clear` and counter operations pass through unrecorded.
+pub struct InstrumentedCache<C> {
+	inner: Arc<C>,
+	metrics: Arc<CacheMetrics>,
+}
+
+impl<C: Cache> InstrumentedCache<C> {
+	/// Wrap `inner`, recording into `metrics`
+	pub fn new(inner: C, metrics: Arc<CacheMetrics>) -> Self {
+		Self::from_arc(Arc::new(inner), metrics)
+	}
+
+	/// Wrap a shared cache, recording into `metrics`
+	pub fn from_arc(inner: Arc<C>, metrics: Arc<CacheMetrics>) -> Self {
+		Self { inner, metrics }
+	}
+
+	/// Get the wrapped cache
+	pub fn inner(&self) -> &C {
+		&self.inner
+	}
+
+	/// Get the metrics being recorded
+	pub fn metrics(&self) -> &Arc<CacheMetrics> {
+		&self.metrics
+	}
+}
+
+impl<C> Clone for InstrumentedCache<C> {
+	fn clone(&self) -> Self {
+		Self {
+			inner: Arc::clone(&self.inner),
+			metrics: Arc::clone(&self.metrics),
+		}
+	}
+}
+
+#[async_trait]
+impl<C: Cache> Cache for InstrumentedCache<C> {
+	async fn get<T>(&self, key: &str) -> Result<Option<T>>
+	where
+		T: for<'de> Deserialize<'de> + Serialize + Send + Sync,
+	{
+		let start = Instant::now();
+		let value = self.inner.get::<T>(key).await?;
+		match value {
+			Some(_) => self.metrics.record_hit(key, start.elapsed()),
+			None => self.metrics.record_miss(key, start.elapsed()),
+		}
+		Ok(value)
+	}
+
+	async fn set<T>(&self, key: &str, value: &T, ttl: Option<Duration>) -> Result<()>
+	where
+		T: Serialize + Send + Sync,
+	{
+		let start = Instant::now();
+		self.inner.set(key, value, ttl).await?;
+		self.metrics.record_write(key, start.elapsed());
+		Ok(())
+	}
+
+	async fn delete(&self, key: &str) -> Result<()> {
+		let start = Instant::now();
+		self.inner.delete(key).await?;
+		self.metrics.record_write(key, start.elapsed());
+		Ok(())
+	}
+
+	async fn has_key(&self, key: &str) -> Result<bool> {
+		self.inner.has_key(key).await
+	}
+
+	async fn clear(&self) -> Result<()> {
+		self.inner.clear().await
+	}
+
+	async fn get_many<T>(&self, keys: &[&str]) -> Result<HashMap<String, T>>
+	where
+		T: for<'de> Deserialize<'de> + Serialize + Send + Sync,
+	{
+		let start = Instant::now();
+		let values = self.inner.get_many::<T>(keys).await?;
+		// Spread the batch latency evenly over its keys
+		let latency = start.elapsed().div_f64(keys.len().max(1) as f64);
+		for key in keys {
+			if values.contains_key(*key) {
+				self.metrics.record_hit(key, latency);
+			} else {
+				self.metrics.record_miss(key, latency);
+			}
+		}
+		Ok(values)
+	}
+
+	async fn set_many<T>(&self, values: HashMap<String, T>, ttl: Option<Duration>) -> Result<()>
+	where
+		T: Serialize + Send + Sync,
+	{
+		let keys: Vec<String> = values.keys().cloned().collect();
+		let start = Instant::now();
+		self.inner.set_many(values, ttl).await?;
+		let latency = start.elapsed().div_f64(keys.len().max(1) as f64);
+		for key in &keys {
+			self.metrics.record_write(key, latency);
+		}
+		Ok(())
+	}
+
+	async fn delete_many(&self, keys: &[&str]) -> Result<()> {
+		let start = Instant::now();
+		self.inner.delete_many(keys).await?;
+		let latency = start.elapsed().div_f64(keys.len().max(1) as f64);
+		for key in keys {
+			self.metrics.record_write(key, latency);
+		}
+		Ok(())
+	}
+
+	async fn incr(&self, key: &str, delta: i64) -> Result<i64> {
+		self.inner.incr(key, delta).await
+	}
+
+	async fn decr(&self, key: &str, delta: i64) -> Result<i64> {
+		self.inner.decr(key, delta).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cache::InMemoryCache;
+	use rstest::rstest;
+
+	#[rstest]
+	#[case("views:users:1", "views")]
+	#[case("views", OTHER_PATTERN)]
+	#[case("", OTHER_PATTERN)]
+	fn test_pattern_for_default_depth(#[case] key: &str, #[case] expected: &str) {
+		let metrics = CacheMetrics::new();
+
+		assert_eq!(metrics.pattern_for(key), expected);
+	}
+
+	#[rstest]
+	fn test_pattern_for_prefers_longest_registered_pattern() {
+		let metrics = CacheMetrics::new()
+			.with_pattern("views:")
+			.with_pattern("views:users:");
+
+		assert_eq!(metrics.pattern_for("views:users:1"), "views:users:");
+		assert_eq!(metrics.pattern_for("views:posts:1"), "views:");
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_instrumented_cache_records_per_pattern() {
+		let metrics = Arc::new(CacheMetrics::new());
+		let cache = InstrumentedCache::new(InMemoryCache::new(), metrics.clone());
+
+		cache.set("users:1", &"alice", None).await.unwrap();
+		let _: Option<String> = cache.get("users:1").await.unwrap();
+		let _: Option<String> = cache.get("users:2").await.unwrap();
+		let _: HashMap<String, String> = cache.get_many(&["posts:1", "users:1"]).await.unwrap();
+		cache.delete("posts:1").await.unwrap();
+
+		let users = metrics.pattern("users").unwrap();
+		let posts = metrics.pattern("posts").unwrap();
+		assert_eq!((users.hits, users.misses, users.writes), (2, 1, 1));
+		assert_eq!((posts.hits, posts.misses, posts.writes), (0, 1, 1));
+		assert_eq!(users.operations, 4);
+
+		let stats = metrics.statistics();
+		assert_eq!(stats.hits, 2);
+		assert_eq!(stats.misses, 2);
+		assert_eq!(stats.total_requests, 4);
+	}
+
+	#[rstest]
+	fn test_export_prometheus() {
+		let metrics = CacheMetrics::new().with_pattern("say\"hi");
+		metrics.record_hit("views:1", Duration::from_millis(2));
+		metrics.record_miss("views:2", Duration::from_millis(2));
+		metrics.record_write("say\"hi:1", Duration::ZERO);
+
+		let output = metrics.export_prometheus();
+
+		assert!(output.contains("# TYPE cache_hits_total counter"));
+		assert!(output.contains("cache_hits_total{pattern=\"views\"} 1"));
+		assert!(output.contains("cache_misses_total{pattern=\"views\"} 1"));
+		assert!(output.contains("cache_writes_total{pattern=\"say\\\"hi\"} 1"));
+		assert!(
+			output.contains("cache_operation_duration_seconds_sum{pattern=\"views\"} 0.004000")
+		);
+		assert!(output.contains("cache_operation_duration_seconds_count{pattern=\"views\"} 2"));
+	}
+
+	#[rstest]
+	fn test_reset() {
+		let metrics = CacheMetrics::new();
+		metrics.record_hit("views:1", Duration::ZERO);
+
+		metrics.reset();
+
+		assert!(metrics.statistics().patterns.is_empty());
+	}
+}
@@ -0,0 +1,379 @@
+//! In-process near cache for distributed backends
+//!
+//! A [`NearCache`] keeps recently read values in local memory so hot keys
+//! are served without a network round trip. Entries are bounded both by
+//! count (least recently used entries are evicted first) and by age, and
+//! are dropped when a [`CacheInvalidationMessage`] names them, so instances
+//! sharing a backend do not serve each other's stale data.
+//!
+//! Values read from the backend are stored with
+//! [`insert_if_unchanged`](NearCache::insert_if_unchanged), which never lets
+//! an entry outlive its backend TTL and skips the insert if an invalidation
+//! arrived while the value was being read.
+//!
+//! # Examples
+//!
+//! ```
+//! use reinhardt_utils::cache::{CacheInvalidationMessage, NearCache};
+//! use std::time::Duration;
+//!
+//! let near = NearCache::new(1_000).with_ttl(Duration::from_secs(5));
+//! near.insert("user:1", b"\"alice\"".to_vec());
+//! assert!(near.get("user:1").is_some());
+//!
+//! near.apply(&CacheInvalidationMessage::InvalidateKey { key: "user:1".to_string() });
+//! assert!(near.get("user:1").is_none());
+//! ```
+
+use super::pubsub::CacheInvalidationMessage;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Default lifetime of near cache entries
+pub const DEFAULT_NEAR_CACHE_TTL: Duration = Duration::from_secs(60);
+
+struct NearEntry {
+	data: Vec<u8>,
+	expires_at: Instant,
+	last_used: u64,
+}
+
+#[derive(Default)]
+struct NearState {
+	entries: HashMap<String, NearEntry>,
+	/// Recency index: access tick -> key, oldest first
+	recency: BTreeMap<u64, String>,
+	tick: u64,
+	/// Bumped by every invalidation
+	generation: u64,
+}
+
+impl NearState {
+	fn next_tick(&mut self) -> u64 {
+		self.tick += 1;
+		self.tick
+	}
+
+	fn remove(&mut self, key: &str) {
+		if let Some(entry) = self.entries.remove(key) {
+			self.recency.remove(&entry.last_used);
+		}
+	}
+
+	fn clear(&mut self) {
+		self.entries.clear();
+		self.recency.clear();
+	}
+
+	fn store(&mut self, capacity: usize, key: &str, data: Vec<u8>, ttl: Duration) {
+		self.remove(key);
+		while self.entries.len() >= capacity {
+			let Some((_, oldest)) = self.recency.pop_first() else {
+				break;
+			};
+			self.entries.remove(&oldest);
+		}
+
+		let tick = self.next_tick();
+		self.recency.insert(tick, key.to_string());
+		self.entries.insert(
+			key.to_string(),
+			NearEntry {
+				data,
+				expires_at: Instant::now() + ttl,
+				last_used: tick,
+			},
+		);
+	}
+}
+
+/// Bounded in-process LRU of serialized cache values
+///
+/// Values are stored exactly as the backend returned them, so decoding
+/// (including decompression) happens on each read as it would for a
+/// backend hit.
+pub struct NearCache {
+	capacity: usize,
+	ttl: Duration,
+	state: Mutex<NearState>,
+}
+
+impl NearCache {
+	/// Create a near cache holding at most `capacity` entries
+	pub fn new(capacity: usize) -> Self {
+		Self {
+			capacity,
+			ttl: DEFAULT_NEAR_CACHE_TTL,
+			state: Mutex::new(NearState::default()),
+		}
+	}
+
+	/// Set how long entries may be served locally
+	///
+	/// This bounds staleness if an invalidation message is lost.
+	pub fn with_ttl(mut self, ttl: Duration) -> Self {
+		self.ttl = ttl;
+		self
+	}
+
+	/// Maximum number of entries
+	pub fn capacity(&self) -> usize {
+		self.capacity
+	}
+
+	/// Lifetime of entries
+	pub fn ttl(&self) -> Duration {
+		self.ttl
+	}
+
+	/// Number of entries currently held, including expired ones not yet evicted
+	pub fn len(&self) -> usize {
+		self.state.lock().unwrap().entries.len()
+	}
+
+	/// Check whether the near cache is empty
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Get the stored bytes for `key`, marking it as recently used
+	pub fn get(&self, key: &str) -> Option<Vec<u8>> {
+		let mut state = self.state.lock().unwrap();
+		if state.entries.get(key)?.expires_at <= Instant::now() {
+			state.remove(key);
+			return None;
+		}
+
+		let tick = state.next_tick();
+		let entry = state.entries.get_mut(key)?;
+		let previous = std::mem::replace(&mut entry.last_used, tick);
+		let data = entry.data.clone();
+		state.recency.remove(&previous);
+		state.recency.insert(tick, key.to_string());
+		Some(data)
+	}
+
+	/// Store bytes for `key`, evicting the least recently used entry when full
+	pub fn insert(&self, key: &str, data: Vec<u8>) {
+		if self.capacity == 0 {
+			return;
+		}
+		self.state
+			.lock()
+			.unwrap()
+			.store(self.capacity, key, data, self.ttl);
+	}
+
+	/// Current invalidation generation
+	///
+	/// Take it before reading a value from the backend and pass it to
+	/// [`insert_if_unchanged`](Self::insert_if_unchanged).
+	pub fn generation(&self) -> u64 {
+		self.state.lock().unwrap().generation
+	}
+
+	/// Store bytes read from the backend
+	///
+	/// The entry expires after the near cache TTL or `remote_ttl`, the time
+	/// the value has left in the backend, whichever comes first. Nothing is
+	/// stored if an invalidation happened since `generation` was taken, as
+	/// the value read may already be stale. Returns whether it was stored.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_utils::cache::NearCache;
+	/// use std::time::Duration;
+	///
+	/// let near = NearCache::new(10);
+	/// let generation = near.generation();
+	/// // Another instance writes the key while we read it
+	/// near.remove("user:1");
+	///
+	/// assert!(!near.insert_if_unchanged("user:1", vec![1], generation, None));
+	/// assert!(near.insert_if_unchanged("user:1", vec![1], near.generation(), Some(Duration::from_secs(1))));
+	/// ```
+	pub fn insert_if_unchanged(
+		&self,
+		key: &str,
+		data: Vec<u8>,
+		generation: u64,
+		remote_ttl: Option<Duration>,
+	) -> bool {
+		if self.capacity == 0 {
+			return false;
+		}
+		let ttl = remote_ttl.map_or(self.ttl, |remote_ttl| remote_ttl.min(self.ttl));
+		let mut state = self.state.lock().unwrap();
+		if state.generation != generation || ttl.is_zero() {
+			return false;
+		}
+		state.store(self.capacity, key, data, ttl);
+		true
+	}
+
+	/// Drop the entry for `key`
+	pub fn remove(&self, key: &str) {
+		let mut state = self.state.lock().unwrap();
+		state.generation += 1;
+		state.remove(key);
+	}
+
+	/// Drop every entry whose key matches a Redis-style glob `pattern`
+	///
+	/// An invalid pattern clears the whole near cache, since dropping too
+	/// much only costs a backend read.
+	pub fn remove_matching(&self, pattern: &str) {
+		let mut state = self.state.lock().unwrap();
+		state.generation += 1;
+		let Ok(pattern) = glob::Pattern::new(pattern) else {
+			state.clear();
+			return;
+		};
+
+		let matching: Vec<String> = state
+			.entries
+			.keys()
+			.filter(|key| pattern.matches(key))
+			.cloned()
+			.collect();
+		for key in matching {
+			state.remove(&key);
+		}
+	}
+
+	/// Drop all entries
+	pub fn clear(&self) {
+		let mut state = self.state.lock().unwrap();
+		state.generation += 1;
+		state.clear();
+	}
+
+	/// Drop the entries named by an invalidation message
+	pub fn apply(&self, message: &CacheInvalidationMessage) {
+		match message {
+			CacheInvalidationMessage::InvalidateKey { key } => self.remove(key),
+			CacheInvalidationMessage::InvalidatePattern { pattern } => {
+				self.remove_matching(pattern)
+			}
+			CacheInvalidationMessage::ClearAll => self.clear(),
+		}
+	}
+}
+
+impl std::fmt::Debug for NearCache {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("NearCache")
+			.field("capacity", &self.capacity)
+			.field("ttl", &self.ttl)
+			.field("len", &self.len())
+			.finish()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+
+	#[rstest]
+	fn test_evicts_least_recently_used() {
+		let near = NearCache::new(2);
+		near.insert("a", vec![1]);
+		near.insert("b", vec![2]);
+
+		// Touch "a" so "b" becomes the eviction candidate
+		assert_eq!(near.get("a"), Some(vec![1]));
+		near.insert("c", vec![3]);
+
+		assert_eq!(near.len(), 2);
+		assert_eq!(near.get("a"), Some(vec![1]));
+		assert_eq!(near.get("b"), None);
+		assert_eq!(near.get("c"), Some(vec![3]));
+	}
+
+	#[rstest]
+	fn test_entries_expire() {
+		let near = NearCache::new(10).with_ttl(Duration::from_millis(20));
+		near.insert("a", vec![1]);
+
+		std::thread::sleep(Duration::from_millis(40));
+
+		assert_eq!(near.get("a"), None);
+		assert!(near.is_empty());
+	}
+
+	#[rstest]
+	fn test_reinsert_replaces_value() {
+		let near = NearCache::new(2);
+		near.insert("a", vec![1]);
+		near.insert("a", vec![2]);
+
+		assert_eq!(near.len(), 1);
+		assert_eq!(near.get("a"), Some(vec![2]));
+	}
+
+	#[rstest]
+	#[case(CacheInvalidationMessage::InvalidateKey { key: "user:1".to_string() }, &["user:2", "post:1"])]
+	#[case(CacheInvalidationMessage::InvalidatePattern { pattern: "user:*".to_string() }, &["post:1"])]
+	#[case(CacheInvalidationMessage::InvalidatePattern { pattern: "[".to_string() }, &[])]
+	#[case(CacheInvalidationMessage::ClearAll, &[])]
+	fn test_apply_invalidation(
+		#[case] message: CacheInvalidationMessage,
+		#[case] remaining: &[&str],
+	) {
+		let near = NearCache::new(10);
+		for key in ["user:1", "user:2", "post:1"] {
+			near.insert(key, vec![0]);
+		}
+
+		near.apply(&message);
+
+		let mut kept: Vec<&str> = ["user:1", "user:2", "post:1"]
+			.into_iter()
+			.filter(|key| near.get(key).is_some())
+			.collect();
+		kept.sort();
+		let mut expected = remaining.to_vec();
+		expected.sort();
+		assert_eq!(kept, expected);
+	}
+
+	#[rstest]
+	fn test_insert_is_capped_at_remote_ttl() {
+		let near = NearCache::new(10).with_ttl(Duration::from_secs(60));
+
+		assert!(near.insert_if_unchanged(
+			"a",
+			vec![1],
+			near.generation(),
+			Some(Duration::from_millis(20))
+		));
+		std::thread::sleep(Duration::from_millis(40));
+
+		assert_eq!(near.get("a"), None);
+	}
+
+	#[rstest]
+	#[case(CacheInvalidationMessage::InvalidateKey { key: "other".to_string() })]
+	#[case(CacheInvalidationMessage::InvalidatePattern { pattern: "other:*".to_string() })]
+	#[case(CacheInvalidationMessage::ClearAll)]
+	fn test_invalidation_during_read_skips_insert(#[case] message: CacheInvalidationMessage) {
+		let near = NearCache::new(10);
+		let generation = near.generation();
+
+		near.apply(&message);
+
+		assert!(!near.insert_if_unchanged("a", vec![1], generation, None));
+		assert!(near.is_empty());
+	}
+
+	#[rstest]
+	fn test_zero_capacity_stores_nothing() {
+		let near = NearCache::new(0);
+		near.insert("a", vec![1]);
+
+		assert!(near.is_empty());
+	}
+}
@@ -44,10 +44,41 @@ pub enum CacheInvalidationMessage {
 	ClearAll,
 }
 
+/// Published payload, naming the node that sent the message
+#[derive(Serialize, Deserialize)]
+struct InvalidationEnvelope {
+	origin: String,
+	message: CacheInvalidationMessage,
+}
+
+/// Payloads accepted from the channel, including bare messages from older publishers
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum InvalidationPayload {
+	Envelope(InvalidationEnvelope),
+	Bare(CacheInvalidationMessage),
+}
+
+impl InvalidationPayload {
+	fn decode(payload: &str) -> Result<(Option<String>, CacheInvalidationMessage)> {
+		let payload: Self = serde_json::from_str(payload)
+			.map_err(|e| Error::Serialization(format!("Deserialization error: {}", e)))?;
+		Ok(match payload {
+			Self::Envelope(envelope) => (Some(envelope.origin), envelope.message),
+			Self::Bare(message) => (None, message),
+		})
+	}
+}
+
 /// Cache invalidation pub/sub channel.
+///
+/// Each channel has a node id that is sent with its messages, so a node can
+/// skip its own invalidations with
+/// [`CacheInvalidationSubscriber::ignore_origin`].
 pub struct CacheInvalidationChannel {
 	client: Client,
 	channel_name: String,
+	node_id: String,
 }
 
 impl CacheInvalidationChannel {
@@ -59,6 +90,7 @@ impl CacheInvalidationChannel {
 		Ok(Self {
 			client,
 			channel_name: "cache:invalidation".to_string(),
+			node_id: uuid::Uuid::new_v4().to_string(),
 		})
 	}
 
@@ -70,9 +102,21 @@ impl CacheInvalidationChannel {
 		Ok(Self {
 			client,
 			channel_name,
+			node_id: uuid::Uuid::new_v4().to_string(),
 		})
 	}
 
+	/// Set the node id sent with published messages (random by default).
+	pub fn with_node_id(mut self, node_id: impl Into<String>) -> Self {
+		self.node_id = node_id.into();
+		self
+	}
+
+	/// Get the node id sent with published messages.
+	pub fn node_id(&self) -> &str {
+		&self.node_id
+	}
+
 	/// Publish a cache invalidation message for a specific key.
 	pub async fn invalidate(&self, key: &str) -> Result<()> {
 		let msg = CacheInvalidationMessage::InvalidateKey {
@@ -105,7 +149,11 @@ impl CacheInvalidationChannel {
 			.await
 			.map_err(|e| Error::Http(format!("Redis error: {}", e)))?;
 
-		let json = serde_json::to_string(&message)
+		let envelope = InvalidationEnvelope {
+			origin: self.node_id.clone(),
+			message,
+		};
+		let json = serde_json::to_string(&envelope)
 			.map_err(|e| Error::Serialization(format!("Serialization error: {}", e)))?;
 
 		let _: () = conn
@@ -131,6 +179,7 @@ impl CacheInvalidationChannel {
 
 		Ok(CacheInvalidationSubscriber {
 			pubsub: Arc::new(Mutex::new(pubsub)),
+			ignored_origin: None,
 		})
 	}
 }
@@ -138,25 +187,50 @@ impl CacheInvalidationChannel {
 /// Subscriber for cache invalidation messages.
 pub struct CacheInvalidationSubscriber {
 	pubsub: Arc<Mutex<PubSub>>,
+	ignored_origin: Option<String>,
 }
 
 impl CacheInvalidationSubscriber {
+	/// Skip messages published by the node with this id.
+	pub fn ignore_origin(&mut self, node_id: impl Into<String>) {
+		self.ignored_origin = Some(node_id.into());
+	}
+
 	/// Get the next invalidation message.
 	pub async fn next_message(&mut self) -> Result<Option<CacheInvalidationMessage>> {
 		let mut pubsub = self.pubsub.lock().await;
+		let mut messages = pubsub.on_message();
 
-		match pubsub.on_message().next().await {
-			Some(msg) => {
-				let payload: String = msg
-					.get_payload()
-					.map_err(|e| Error::Http(format!("Redis error: {}", e)))?;
+		while let Some(msg) = messages.next().await {
+			let payload: String = msg
+				.get_payload()
+				.map_err(|e| Error::Http(format!("Redis error: {}", e)))?;
 
-				let message: CacheInvalidationMessage = serde_json::from_str(&payload)
-					.map_err(|e| Error::Serialization(format!("Deserialization error: {}", e)))?;
-
-				Ok(Some(message))
+			let (origin, message) = InvalidationPayload::decode(&payload)?;
+			if origin.is_some() && origin == self.ignored_origin {
+				continue;
 			}
-			None => Ok(None),
+			return Ok(Some(message));
 		}
+		Ok(None)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_decode_payload_with_and_without_origin() {
+		let (origin, message) = InvalidationPayload::decode(
+			r#"{"origin":"node-1","message":{"InvalidateKey":{"key":"a"}}}"#,
+		)
+		.unwrap();
+		assert_eq!(origin.as_deref(), Some("node-1"));
+		assert!(matches!(message, CacheInvalidationMessage::InvalidateKey { key } if key == "a"));
+
+		let (origin, message) = InvalidationPayload::decode(r#""ClearAll""#).unwrap();
+		assert_eq!(origin, None);
+		assert!(matches!(message, CacheInvalidationMessage::ClearAll));
 	}
 }
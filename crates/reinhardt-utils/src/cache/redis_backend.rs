@@ -3,11 +3,15 @@
 //! Provides a Redis-backed cache implementation with connection pooling.
 
 use super::Cache;
+use super::compression::{CacheCompression, decode_value, encode_value};
+use super::near_cache::NearCache;
+use super::pubsub::{CacheInvalidationChannel, CacheInvalidationSubscriber};
 use async_trait::async_trait;
 use deadpool_redis::{Config as PoolConfig, Pool, Runtime};
 use redis::AsyncCommands;
 use reinhardt_core::exception::{Error, Result};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use std::time::Duration;
 
 /// Redis cache backend with connection pooling
@@ -19,6 +23,9 @@ pub struct RedisCache {
 	pool: Pool,
 	default_ttl: Option<Duration>,
 	key_prefix: String,
+	compression: Option<CacheCompression>,
+	near: Option<Arc<NearCache>>,
+	invalidation: Option<Arc<CacheInvalidationChannel>>,
 }
 
 impl RedisCache {
@@ -49,6 +56,9 @@ impl RedisCache {
 			pool,
 			default_ttl: None,
 			key_prefix: String::new(),
+			compression: None,
+			near: None,
+			invalidation: None,
 		})
 	}
 
@@ -77,6 +87,9 @@ impl RedisCache {
 			pool,
 			default_ttl: None,
 			key_prefix: String::new(),
+			compression: None,
+			near: None,
+			invalidation: None,
 		})
 	}
 
@@ -125,6 +138,143 @@ impl RedisCache {
 		self
 	}
 
+	/// Compress large values before storing them
+	///
+	/// Entries written without compression remain readable, so this can be
+	/// enabled on a cache that already holds data.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use reinhardt_utils::cache::{CacheCompression, CompressionAlgorithm, RedisCache};
+	///
+	/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+	/// let cache = RedisCache::new("redis://localhost:6379")
+	///     .await?
+	///     .with_compression(CacheCompression::new(CompressionAlgorithm::Gzip).with_threshold(4096));
+	/// // Values larger than 4 KiB are gzip-compressed
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn with_compression(mut self, compression: CacheCompression) -> Self {
+		self.compression = Some(compression);
+		self
+	}
+
+	/// Serve hot keys from an in-process near cache
+	///
+	/// Values read from Redis are kept locally, never longer than their
+	/// remaining TTL in Redis, and dropped when this instance writes them.
+	/// To see writes made by other instances, also
+	/// configure [`with_invalidation_channel`](Self::with_invalidation_channel)
+	/// and run [`start_near_cache_listener`](Self::start_near_cache_listener);
+	/// the near cache TTL bounds staleness if a message is missed.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use reinhardt_utils::cache::{CacheInvalidationChannel, NearCache, RedisCache};
+	/// use std::time::Duration;
+	///
+	/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+	/// let channel = CacheInvalidationChannel::new("redis://localhost:6379").await?;
+	/// let subscriber = channel.subscribe().await?;
+	///
+	/// let cache = RedisCache::new("redis://localhost:6379")
+	///     .await?
+	///     .with_near_cache(NearCache::new(10_000).with_ttl(Duration::from_secs(30)))
+	///     .with_invalidation_channel(channel);
+	/// cache.start_near_cache_listener(subscriber);
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn with_near_cache(mut self, near: NearCache) -> Self {
+		self.near = Some(Arc::new(near));
+		self
+	}
+
+	/// Publish an invalidation message for every write through `channel`
+	///
+	/// Messages name the prefixed key, so caches with different key
+	/// prefixes can share a channel without evicting each other's entries.
+	pub fn with_invalidation_channel(mut self, channel: CacheInvalidationChannel) -> Self {
+		self.invalidation = Some(Arc::new(channel));
+		self
+	}
+
+	/// Get the near cache, if enabled
+	pub fn near_cache(&self) -> Option<&NearCache> {
+		self.near.as_deref()
+	}
+
+	/// Apply invalidation messages from `subscriber` to the near cache in the background
+	///
+	/// Does nothing if no near cache is configured.
+	pub fn start_near_cache_listener(&self, mut subscriber: CacheInvalidationSubscriber) {
+		let Some(near) = self.near.clone() else {
+			return;
+		};
+		// This instance already dropped the keys it wrote
+		if let Some(channel) = &self.invalidation {
+			subscriber.ignore_origin(channel.node_id());
+		}
+		tokio::spawn(async move {
+			loop {
+				match subscriber.next_message().await {
+					Ok(Some(message)) => near.apply(&message),
+					Ok(None) => break,
+					Err(e) => tracing::warn!("Near cache invalidation message dropped: {}", e),
+				}
+			}
+		});
+	}
+
+	/// Drop written keys from the near cache and tell other instances to do the same
+	async fn invalidate_near(&self, keys: &[&str]) -> Result<()> {
+		if self.near.is_none() && self.invalidation.is_none() {
+			return Ok(());
+		}
+		let full_keys: Vec<String> = keys.iter().map(|key| self.build_key(key)).collect();
+		if let Some(near) = &self.near {
+			for full_key in &full_keys {
+				near.remove(full_key);
+			}
+		}
+		if let Some(channel) = &self.invalidation {
+			for full_key in &full_keys {
+				channel.invalidate(full_key).await?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Read values and their remaining TTL in one round trip, for the near cache
+	async fn get_with_ttl(
+		conn: &mut deadpool_redis::Connection,
+		full_keys: &[String],
+	) -> Result<Vec<(Option<Vec<u8>>, Option<Duration>)>> {
+		let mut pipe = redis::pipe();
+		for full_key in full_keys {
+			pipe.get(full_key).pttl(full_key);
+		}
+		let replies: Vec<redis::Value> = pipe
+			.query_async(&mut **conn)
+			.await
+			.map_err(|e| Error::Http(format!("Failed to get value from Redis: {}", e)))?;
+
+		replies
+			.chunks(2)
+			.map(|reply| {
+				let value: Option<Vec<u8>> = redis::from_redis_value(&reply[0])
+					.map_err(|e| Error::Http(format!("Failed to get value from Redis: {}", e)))?;
+				// PTTL is negative for keys without expiry
+				let ttl: i64 = redis::from_redis_value(&reply[1])
+					.map_err(|e| Error::Http(format!("Failed to get TTL from Redis: {}", e)))?;
+				Ok((value, u64::try_from(ttl).ok().map(Duration::from_millis)))
+			})
+			.collect()
+	}
+
 	/// Build the full key with prefix
 	fn build_key(&self, key: &str) -> String {
 		if self.key_prefix.is_empty() {
@@ -142,23 +292,40 @@ impl Cache for RedisCache {
 		T: for<'de> Deserialize<'de> + Send,
 	{
 		let full_key = self.build_key(key);
+		if let Some(bytes) = self.near.as_ref().and_then(|near| near.get(&full_key)) {
+			return decode_value(&bytes).map(Some);
+		}
+
 		let mut conn = self
 			.pool
 			.get()
 			.await
 			.map_err(|e| Error::Http(format!("Failed to get connection from pool: {}", e)))?;
 
+		if let Some(near) = &self.near {
+			let generation = near.generation();
+			let (value, remote_ttl) =
+				Self::get_with_ttl(&mut conn, std::slice::from_ref(&full_key))
+					.await?
+					.pop()
+					.unwrap_or_default();
+			return match value {
+				Some(bytes) => {
+					let deserialized = decode_value(&bytes)?;
+					near.insert_if_unchanged(&full_key, bytes, generation, remote_ttl);
+					Ok(Some(deserialized))
+				}
+				None => Ok(None),
+			};
+		}
+
 		let value: Option<Vec<u8>> = conn
 			.get(&full_key)
 			.await
 			.map_err(|e| Error::Http(format!("Failed to get value from Redis: {}", e)))?;
 
 		match value {
-			Some(bytes) => {
-				let deserialized = serde_json::from_slice(&bytes)
-					.map_err(|e| Error::Serialization(e.to_string()))?;
-				Ok(Some(deserialized))
-			}
+			Some(bytes) => Ok(Some(decode_value(&bytes)?)),
 			None => Ok(None),
 		}
 	}
@@ -168,8 +335,7 @@ impl Cache for RedisCache {
 		T: Serialize + Send + Sync,
 	{
 		let full_key = self.build_key(key);
-		let serialized =
-			serde_json::to_vec(value).map_err(|e| Error::Serialization(e.to_string()))?;
+		let serialized = encode_value(value, self.compression.as_ref())?;
 		let mut conn = self
 			.pool
 			.get()
@@ -179,9 +345,10 @@ impl Cache for RedisCache {
 		let effective_ttl = ttl.or(self.default_ttl);
 
 		if let Some(ttl_duration) = effective_ttl {
-			let seconds = ttl_duration.as_secs();
+			// Millisecond precision, so sub-second TTLs are not rounded to zero
+			let millis = ttl_duration.as_millis().max(1) as u64;
 			let _: () = conn
-				.set_ex(&full_key, serialized, seconds)
+				.pset_ex(&full_key, serialized, millis)
 				.await
 				.map_err(|e| Error::Http(format!("Failed to set value in Redis: {}", e)))?;
 		} else {
@@ -191,7 +358,7 @@ impl Cache for RedisCache {
 				.map_err(|e| Error::Http(format!("Failed to set value in Redis: {}", e)))?;
 		}
 
-		Ok(())
+		self.invalidate_near(&[key]).await
 	}
 
 	async fn delete(&self, key: &str) -> Result<()> {
@@ -207,11 +374,19 @@ impl Cache for RedisCache {
 			.await
 			.map_err(|e| Error::Http(format!("Failed to delete value from Redis: {}", e)))?;
 
-		Ok(())
+		self.invalidate_near(&[key]).await
 	}
 
 	async fn has_key(&self, key: &str) -> Result<bool> {
 		let full_key = self.build_key(key);
+		if self
+			.near
+			.as_ref()
+			.is_some_and(|near| near.get(&full_key).is_some())
+		{
+			return Ok(true);
+		}
+
 		let mut conn = self
 			.pool
 			.get()
@@ -256,6 +431,23 @@ impl Cache for RedisCache {
 			}
 		}
 
+		if self.key_prefix.is_empty() {
+			if let Some(near) = &self.near {
+				near.clear();
+			}
+			if let Some(channel) = &self.invalidation {
+				channel.clear_all().await?;
+			}
+		} else {
+			// Leave the entries of other prefixes alone
+			let pattern = format!("{}:*", self.key_prefix);
+			if let Some(near) = &self.near {
+				near.remove_matching(&pattern);
+			}
+			if let Some(channel) = &self.invalidation {
+				channel.invalidate_pattern(&pattern).await?;
+			}
+		}
 		Ok(())
 	}
 
@@ -263,23 +455,52 @@ impl Cache for RedisCache {
 	where
 		T: for<'de> Deserialize<'de> + Send,
 	{
-		let full_keys: Vec<String> = keys.iter().map(|k| self.build_key(k)).collect();
+		let mut results = std::collections::HashMap::new();
+		let mut missing = Vec::with_capacity(keys.len());
+		for key in keys {
+			let full_key = self.build_key(key);
+			match self.near.as_ref().and_then(|near| near.get(&full_key)) {
+				Some(bytes) => {
+					results.insert(key.to_string(), decode_value(&bytes)?);
+				}
+				None => missing.push((*key, full_key)),
+			}
+		}
+		let (keys, full_keys): (Vec<&str>, Vec<String>) = missing.into_iter().unzip();
+
+		if keys.is_empty() {
+			return Ok(results);
+		}
+
 		let mut conn = self
 			.pool
 			.get()
 			.await
 			.map_err(|e| Error::Http(format!("Failed to get connection from pool: {}", e)))?;
 
-		let values: Vec<Option<Vec<u8>>> = conn
-			.get(&full_keys)
+		if let Some(near) = &self.near {
+			let generation = near.generation();
+			let values = Self::get_with_ttl(&mut conn, &full_keys).await?;
+			for ((key, full_key), (value, remote_ttl)) in keys.iter().zip(&full_keys).zip(values) {
+				if let Some(bytes) = value {
+					let deserialized: T = decode_value(&bytes)?;
+					near.insert_if_unchanged(full_key, bytes, generation, remote_ttl);
+					results.insert(key.to_string(), deserialized);
+				}
+			}
+			return Ok(results);
+		}
+
+		// Explicit MGET so that a single key still yields a list reply
+		let values: Vec<Option<Vec<u8>>> = redis::cmd("MGET")
+			.arg(&full_keys)
+			.query_async(&mut *conn)
 			.await
 			.map_err(|e| Error::Http(format!("Failed to get multiple values from Redis: {}", e)))?;
 
-		let mut results = std::collections::HashMap::new();
 		for (i, value_opt) in values.into_iter().enumerate() {
 			if let Some(bytes) = value_opt {
-				let deserialized: T = serde_json::from_slice(&bytes)
-					.map_err(|e| Error::Serialization(e.to_string()))?;
+				let deserialized: T = decode_value(&bytes)?;
 				results.insert(keys[i].to_string(), deserialized);
 			}
 		}
@@ -295,6 +516,10 @@ impl Cache for RedisCache {
 	where
 		T: Serialize + Send + Sync,
 	{
+		if values.is_empty() {
+			return Ok(());
+		}
+
 		let mut conn = self
 			.pool
 			.get()
@@ -302,29 +527,35 @@ impl Cache for RedisCache {
 			.map_err(|e| Error::Http(format!("Failed to get connection from pool: {}", e)))?;
 		let effective_ttl = ttl.or(self.default_ttl);
 
+		// Send all writes in a single pipeline round trip
+		let mut pipe = redis::pipe();
 		for (key, value) in values.iter() {
 			let full_key = self.build_key(key);
-			let serialized =
-				serde_json::to_vec(value).map_err(|e| Error::Serialization(e.to_string()))?;
+			let serialized = encode_value(value, self.compression.as_ref())?;
 
-			if let Some(ttl_duration) = effective_ttl {
-				let seconds = ttl_duration.as_secs();
-				let _: () = conn
-					.set_ex(&full_key, serialized, seconds)
-					.await
-					.map_err(|e| Error::Http(format!("Failed to set value in Redis: {}", e)))?;
-			} else {
-				let _: () = conn
-					.set(&full_key, serialized)
-					.await
-					.map_err(|e| Error::Http(format!("Failed to set value in Redis: {}", e)))?;
+			match effective_ttl {
+				Some(ttl_duration) => {
+					pipe.pset_ex(full_key, serialized, ttl_duration.as_millis().max(1) as u64)
+				}
+				None => pipe.set(full_key, serialized),
 			}
+			.ignore();
 		}
 
-		Ok(())
+		let _: () = pipe
+			.query_async(&mut *conn)
+			.await
+			.map_err(|e| Error::Http(format!("Failed to set multiple values in Redis: {}", e)))?;
+
+		let keys: Vec<&str> = values.keys().map(String::as_str).collect();
+		self.invalidate_near(&keys).await
 	}
 
 	async fn delete_many(&self, keys: &[&str]) -> Result<()> {
+		if keys.is_empty() {
+			return Ok(());
+		}
+
 		let full_keys: Vec<String> = keys.iter().map(|k| self.build_key(k)).collect();
 		let mut conn = self
 			.pool
@@ -339,7 +570,7 @@ impl Cache for RedisCache {
 			))
 		})?;
 
-		Ok(())
+		self.invalidate_near(keys).await
 	}
 
 	async fn incr(&self, key: &str, delta: i64) -> Result<i64> {
@@ -355,6 +586,7 @@ impl Cache for RedisCache {
 			.await
 			.map_err(|e| Error::Http(format!("Failed to increment value in Redis: {}", e)))?;
 
+		self.invalidate_near(&[key]).await?;
 		Ok(result)
 	}
 
@@ -371,6 +603,7 @@ impl Cache for RedisCache {
 			.await
 			.map_err(|e| Error::Http(format!("Failed to decrement value in Redis: {}", e)))?;
 
+		self.invalidate_near(&[key]).await?;
 		Ok(result)
 	}
 }
@@ -550,4 +783,104 @@ mod tests {
 		let value: Option<String> = cache.get("user:1").await.unwrap();
 		assert_eq!(value, None);
 	}
+
+	#[tokio::test]
+	async fn test_redis_cache_compression() {
+		let redis = RedisContainer::new().await;
+		let plain = RedisCache::new(redis.connection_url())
+			.await
+			.unwrap()
+			.with_key_prefix("test");
+		let compressed = plain.clone().with_compression(
+			CacheCompression::new(crate::cache::CompressionAlgorithm::Gzip).with_threshold(64),
+		);
+		let fragment = "<p>rendered</p>".repeat(200);
+
+		plain.set("raw", &fragment, None).await.unwrap();
+		compressed.set("packed", &fragment, None).await.unwrap();
+
+		// Both caches read both formats
+		let raw: Option<String> = compressed.get("raw").await.unwrap();
+		let packed: Option<String> = plain.get("packed").await.unwrap();
+		assert_eq!(raw.as_deref(), Some(fragment.as_str()));
+		assert_eq!(packed.as_deref(), Some(fragment.as_str()));
+
+		// The compressed entry takes less space in Redis
+		let mut conn = plain.pool().get().await.unwrap();
+		let raw_len: usize = conn.strlen("test:raw").await.unwrap();
+		let packed_len: usize = conn.strlen("test:packed").await.unwrap();
+		assert!(packed_len < raw_len);
+	}
+
+	#[tokio::test]
+	async fn test_redis_cache_near_cache_invalidation() {
+		let redis = RedisContainer::new().await;
+		let url = redis.connection_url();
+		let reader_channel = CacheInvalidationChannel::new(&url).await.unwrap();
+		let subscriber = reader_channel.subscribe().await.unwrap();
+		let reader = RedisCache::new(&url)
+			.await
+			.unwrap()
+			.with_key_prefix("test")
+			.with_near_cache(NearCache::new(100))
+			.with_invalidation_channel(reader_channel);
+		reader.start_near_cache_listener(subscriber);
+		let writer = RedisCache::new(&url)
+			.await
+			.unwrap()
+			.with_key_prefix("test")
+			.with_invalidation_channel(CacheInvalidationChannel::new(&url).await.unwrap());
+
+		writer.set("hot", &"v1", None).await.unwrap();
+		let first: Option<String> = reader.get("hot").await.unwrap();
+		assert_eq!(first.as_deref(), Some("v1"));
+		assert_eq!(reader.near_cache().unwrap().len(), 1);
+
+		// A write from another instance evicts the local copy
+		writer.set("hot", &"v2", None).await.unwrap();
+		tokio::time::sleep(Duration::from_millis(200)).await;
+		assert!(reader.near_cache().unwrap().is_empty());
+
+		let second: Option<String> = reader.get("hot").await.unwrap();
+		assert_eq!(second.as_deref(), Some("v2"));
+	}
+
+	#[tokio::test]
+	async fn test_redis_cache_near_cache_respects_prefix_and_remote_ttl() {
+		let redis = RedisContainer::new().await;
+		let url = redis.connection_url();
+		let channel = CacheInvalidationChannel::new(&url).await.unwrap();
+		let subscriber = channel.subscribe().await.unwrap();
+		let reader = RedisCache::new(&url)
+			.await
+			.unwrap()
+			.with_key_prefix("a")
+			.with_near_cache(NearCache::new(100).with_ttl(Duration::from_secs(60)));
+		reader.start_near_cache_listener(subscriber);
+		let other = RedisCache::new(&url)
+			.await
+			.unwrap()
+			.with_key_prefix("b")
+			.with_invalidation_channel(channel);
+
+		reader.set("hot", &"v1", None).await.unwrap();
+		reader
+			.set("short", &"v1", Some(Duration::from_secs(1)))
+			.await
+			.unwrap();
+		let _: Option<String> = reader.get("hot").await.unwrap();
+		let _: Option<String> = reader.get("short").await.unwrap();
+		assert_eq!(reader.near_cache().unwrap().len(), 2);
+
+		// The same key under another prefix is a different entry
+		other.set("hot", &"other", None).await.unwrap();
+		tokio::time::sleep(Duration::from_millis(200)).await;
+		let hot: Option<String> = reader.get("hot").await.unwrap();
+		assert_eq!(hot.as_deref(), Some("v1"));
+
+		// The local copy expires with the Redis key
+		tokio::time::sleep(Duration::from_millis(1200)).await;
+		let short: Option<String> = reader.get("short").await.unwrap();
+		assert_eq!(short, None);
+	}
 }
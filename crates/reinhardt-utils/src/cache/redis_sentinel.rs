@@ -29,6 +29,7 @@
 //! ```
 
 use super::cache_trait::Cache;
+use super::compression::{CacheCompression, decode_value, encode_value};
 use async_trait::async_trait;
 use redis::{AsyncCommands, sentinel::Sentinel};
 use reinhardt_core::exception::{Error, Result};
@@ -56,6 +57,7 @@ pub struct RedisSentinelConfig {
 pub struct RedisSentinelCache {
 	sentinel: Arc<RwLock<Sentinel>>,
 	master_name: String,
+	compression: Option<CacheCompression>,
 }
 
 impl RedisSentinelCache {
@@ -69,9 +71,18 @@ impl RedisSentinelCache {
 		Ok(Self {
 			sentinel: Arc::new(RwLock::new(sentinel)),
 			master_name: config.master_name,
+			compression: None,
 		})
 	}
 
+	/// Compress large values before storing them
+	///
+	/// Entries written without compression remain readable.
+	pub fn with_compression(mut self, compression: CacheCompression) -> Self {
+		self.compression = Some(compression);
+		self
+	}
+
 	/// Get a connection to the current master.
 	async fn get_connection(&self) -> Result<redis::aio::MultiplexedConnection> {
 		let mut sentinel = self.sentinel.write().await;
@@ -102,8 +113,7 @@ impl Cache for RedisSentinelCache {
 
 		match value {
 			Some(bytes) => {
-				let deserialized = serde_json::from_slice(&bytes)
-					.map_err(|e| Error::Serialization(e.to_string()))?;
+				let deserialized = decode_value(&bytes)?;
 				Ok(Some(deserialized))
 			}
 			None => Ok(None),
@@ -115,8 +125,7 @@ impl Cache for RedisSentinelCache {
 		T: Serialize + Send + Sync,
 	{
 		let mut conn = self.get_connection().await?;
-		let serialized =
-			serde_json::to_vec(value).map_err(|e| Error::Serialization(e.to_string()))?;
+		let serialized = encode_value(value, self.compression.as_ref())?;
 
 		if let Some(ttl_duration) = ttl {
 			let seconds = ttl_duration.as_secs();
@@ -165,18 +174,23 @@ impl Cache for RedisSentinelCache {
 	where
 		T: for<'de> Deserialize<'de> + Send,
 	{
+		if keys.is_empty() {
+			return Ok(HashMap::new());
+		}
+
 		let mut conn = self.get_connection().await?;
 
-		let values: Vec<Option<Vec<u8>>> = conn
-			.get(keys)
+		// Explicit MGET so that a single key still yields a list reply
+		let values: Vec<Option<Vec<u8>>> = redis::cmd("MGET")
+			.arg(keys)
+			.query_async(&mut conn)
 			.await
 			.map_err(|e| Error::Http(format!("Failed to get multiple values from Redis: {}", e)))?;
 
 		let mut results = HashMap::new();
 		for (i, value_opt) in values.into_iter().enumerate() {
 			if let Some(bytes) = value_opt {
-				let deserialized: T = serde_json::from_slice(&bytes)
-					.map_err(|e| Error::Serialization(e.to_string()))?;
+				let deserialized: T = decode_value(&bytes)?;
 				results.insert(keys[i].to_string(), deserialized);
 			}
 		}
@@ -188,30 +202,37 @@ impl Cache for RedisSentinelCache {
 	where
 		T: Serialize + Send + Sync,
 	{
+		if values.is_empty() {
+			return Ok(());
+		}
+
 		let mut conn = self.get_connection().await?;
 
+		// Send all writes in a single pipeline round trip
+		let mut pipe = redis::pipe();
 		for (key, value) in values.iter() {
-			let serialized =
-				serde_json::to_vec(value).map_err(|e| Error::Serialization(e.to_string()))?;
-
-			if let Some(ttl_duration) = ttl {
-				let seconds = ttl_duration.as_secs();
-				let _: () = conn
-					.set_ex(key, serialized, seconds)
-					.await
-					.map_err(|e| Error::Http(format!("Failed to set value in Redis: {}", e)))?;
-			} else {
-				let _: () = conn
-					.set(key, serialized)
-					.await
-					.map_err(|e| Error::Http(format!("Failed to set value in Redis: {}", e)))?;
+			let serialized = encode_value(value, self.compression.as_ref())?;
+
+			match ttl {
+				Some(ttl_duration) => pipe.set_ex(key, serialized, ttl_duration.as_secs()),
+				None => pipe.set(key, serialized),
 			}
+			.ignore();
 		}
 
+		let _: () = pipe
+			.query_async(&mut conn)
+			.await
+			.map_err(|e| Error::Http(format!("Failed to set multiple values in Redis: {}", e)))?;
+
 		Ok(())
 	}
 
 	async fn delete_many(&self, keys: &[&str]) -> Result<()> {
+		if keys.is_empty() {
+			return Ok(());
+		}
+
 		let mut conn = self.get_connection().await?;
 		let _: () = conn.del(keys).await.map_err(|e| {
 			Error::Http(format!(
@@ -1,5 +1,8 @@
 //! Cache statistics and entry information
 
+use std::collections::HashMap;
+use std::time::Duration;
+
 /// Cache entry information for inspection
 #[derive(Debug, Clone)]
 pub struct CacheEntryInfo {
@@ -26,6 +29,8 @@ pub struct CacheStatistics {
 	pub entry_count: u64,
 	/// Approximate memory usage in bytes
 	pub memory_usage: u64,
+	/// Statistics per key pattern (populated by [`CacheMetrics`](super::CacheMetrics))
+	pub patterns: HashMap<String, PatternStatistics>,
 }
 
 impl CacheStatistics {
@@ -74,6 +79,56 @@ impl CacheStatistics {
 	}
 }
 
+/// Statistics for keys sharing a prefix
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PatternStatistics {
+	/// Number of reads that found a value
+	pub hits: u64,
+	/// Number of reads that found nothing
+	pub misses: u64,
+	/// Number of writes and deletes
+	pub writes: u64,
+	/// Number of timed operations (reads and writes)
+	pub operations: u64,
+	/// Total time spent in timed operations
+	pub total_latency: Duration,
+}
+
+impl PatternStatistics {
+	/// Calculate hit rate over reads (0.0 to 1.0)
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_utils::cache::PatternStatistics;
+	///
+	/// let stats = PatternStatistics {
+	///     hits: 3,
+	///     misses: 1,
+	///     ..Default::default()
+	/// };
+	///
+	/// assert_eq!(stats.hit_rate(), 0.75);
+	/// ```
+	pub fn hit_rate(&self) -> f64 {
+		let reads = self.hits + self.misses;
+		if reads == 0 {
+			0.0
+		} else {
+			self.hits as f64 / reads as f64
+		}
+	}
+
+	/// Average latency of timed operations
+	pub fn average_latency(&self) -> Duration {
+		if self.operations == 0 {
+			Duration::ZERO
+		} else {
+			self.total_latency.div_f64(self.operations as f64)
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -84,4 +139,19 @@ mod tests {
 		assert_eq!(stats.hit_rate(), 0.0);
 		assert_eq!(stats.miss_rate(), 0.0);
 	}
+
+	#[tokio::test]
+	async fn test_pattern_statistics_average_latency() {
+		let stats = PatternStatistics {
+			operations: 4,
+			total_latency: Duration::from_millis(10),
+			..Default::default()
+		};
+
+		assert_eq!(stats.average_latency(), Duration::from_micros(2500));
+		assert_eq!(
+			PatternStatistics::default().average_latency(),
+			Duration::ZERO
+		);
+	}
 }
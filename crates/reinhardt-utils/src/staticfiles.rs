@@ -3,6 +3,7 @@
 pub mod caching;
 pub mod cdn;
 pub mod checks;
+pub mod collect;
 pub mod dependency_resolver;
 pub mod handler;
 pub mod health;
@@ -20,6 +21,10 @@ pub mod dev_server;
 pub use caching::{CacheControlConfig, CacheControlMiddleware, CacheDirective, CachePolicy};
 pub use cdn::{CdnConfig, CdnInvalidationRequest, CdnProvider, CdnPurgeHelper, CdnUrlGenerator};
 pub use checks::{CheckLevel, CheckMessage, check_static_files_config};
+pub use collect::{
+	CollectReport, CollectedFile, CollectedFiles, CompressionPostProcessor, HashingPostProcessor,
+	PostProcessor, SourceMapPostProcessor, StaticCollector,
+};
 pub use dependency_resolver::DependencyGraph;
 pub use handler::{StaticError, StaticFile, StaticFileHandler, StaticResult};
 pub use health::{
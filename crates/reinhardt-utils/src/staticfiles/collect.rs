@@ -0,0 +1,977 @@
+//! Static file collection
+//!
+//! [`StaticCollector`] gathers the files of every configured finder into a
+//! [`Storage`], the way Django's `collectstatic` does:
+//!
+//! 1. Files are listed from the finders in order. When several finders
+//!    provide the same path, the first one wins. Contents stay on disk until
+//!    a step needs them, so large assets are never held in memory as a set.
+//! 2. The collected set is handed to each [`PostProcessor`] in registration
+//!    order. Processors may rewrite files and add new ones.
+//! 3. The resulting files are saved to the storage, unless running in dry-run
+//!    mode, in which case the report lists what would have been written.
+//!
+//! Built-in post-processors:
+//!
+//! - [`SourceMapPostProcessor`]: links scripts and stylesheets to their source
+//!   maps; register it before hashing
+//! - [`HashingPostProcessor`]: content-hashed copies and manifest through
+//!   [`ManifestStaticFilesStorage`], with CSS and source map references
+//!   rewritten
+//! - [`CompressionPostProcessor`]: gzip/brotli pre-compressed variants
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use reinhardt_utils::staticfiles::collect::{
+//!     CompressionPostProcessor, HashingPostProcessor, SourceMapPostProcessor, StaticCollector,
+//! };
+//! use reinhardt_utils::staticfiles::processing::compress::CompressionConfig;
+//! use reinhardt_utils::staticfiles::{FileSystemStorage, ManifestStaticFilesStorage, StaticFilesFinder};
+//! use std::path::PathBuf;
+//! use std::sync::Arc;
+//!
+//! # async fn example() -> std::io::Result<()> {
+//! let manifest = Arc::new(ManifestStaticFilesStorage::new("staticfiles", "/static/"));
+//! let report = StaticCollector::new(Arc::new(FileSystemStorage::new("staticfiles", "/static/")))
+//!     .with_finder(StaticFilesFinder::new(vec![PathBuf::from("static")]))
+//!     .with_post_processor(SourceMapPostProcessor::new())
+//!     .with_post_processor(HashingPostProcessor::new(manifest))
+//!     .with_post_processor(CompressionPostProcessor::new(CompressionConfig::default()))
+//!     .with_dry_run(true)
+//!     .collect()
+//!     .await?;
+//!
+//! println!("{} files would be written", report.written.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use super::processing::Processor;
+use super::processing::compress::{BrotliCompressor, CompressionConfig, GzipCompressor};
+use super::storage::{ManifestStaticFilesStorage, StaticFilesFinder, Storage};
+use async_trait::async_trait;
+use regex::{Captures, Regex};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashSet};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
+
+/// A file of the collected set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollectedFile {
+	/// File the content is read from, `None` for files added by post-processors
+	pub source: Option<PathBuf>,
+	/// Content replacing the source, set once a post-processor rewrote the file
+	pub content: Option<Vec<u8>>,
+}
+
+impl CollectedFile {
+	/// Whether the file is still the unmodified source file
+	pub fn is_unmodified_source(&self) -> bool {
+		self.content.is_none() && self.source.is_some()
+	}
+
+	/// Read the content, from memory or from the source file
+	pub async fn read(&self) -> io::Result<Cow<'_, [u8]>> {
+		match (&self.content, &self.source) {
+			(Some(content), _) => Ok(Cow::Borrowed(content)),
+			(None, Some(source)) => tokio::fs::read(source).await.map(Cow::Owned),
+			(None, None) => Ok(Cow::Borrowed(&[])),
+		}
+	}
+}
+
+/// Files gathered by a [`StaticCollector`], keyed by their storage name
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CollectedFiles {
+	files: BTreeMap<String, CollectedFile>,
+	paths: BTreeMap<String, String>,
+}
+
+impl CollectedFiles {
+	/// Create an empty set
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Add or replace a file generated by a post-processor
+	pub fn add(&mut self, name: impl Into<String>, content: Vec<u8>) {
+		self.files.insert(
+			name.into(),
+			CollectedFile {
+				source: None,
+				content: Some(content),
+			},
+		);
+	}
+
+	/// Add or replace a file whose content is read from `source` when needed
+	pub fn add_from_source(&mut self, name: impl Into<String>, source: PathBuf) {
+		self.files.insert(
+			name.into(),
+			CollectedFile {
+				source: Some(source),
+				content: None,
+			},
+		);
+	}
+
+	/// Add `to` as a copy of `from` without reading its content, returning
+	/// whether `from` exists
+	pub fn copy(&mut self, from: &str, to: impl Into<String>) -> bool {
+		match self.files.get(from).cloned() {
+			Some(file) => {
+				self.files.insert(to.into(), file);
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// Get a file by name
+	pub fn get(&self, name: &str) -> Option<&CollectedFile> {
+		self.files.get(name)
+	}
+
+	/// Read the content of a file
+	pub async fn read(&self, name: &str) -> io::Result<Option<Cow<'_, [u8]>>> {
+		match self.files.get(name) {
+			Some(file) => file.read().await.map(Some),
+			None => Ok(None),
+		}
+	}
+
+	/// Replace the content of an existing file, returning whether it exists
+	pub fn set_content(&mut self, name: &str, content: Vec<u8>) -> bool {
+		match self.files.get_mut(name) {
+			Some(file) => {
+				file.content = Some(content);
+				true
+			}
+			None => false,
+		}
+	}
+
+	/// Remove a file
+	pub fn remove(&mut self, name: &str) -> Option<CollectedFile> {
+		self.files.remove(name)
+	}
+
+	/// Whether a file exists
+	pub fn contains(&self, name: &str) -> bool {
+		self.files.contains_key(name)
+	}
+
+	/// Names of all files, sorted
+	pub fn names(&self) -> Vec<String> {
+		self.files.keys().cloned().collect()
+	}
+
+	/// Iterate over the files, sorted by name
+	pub fn iter(&self) -> impl Iterator<Item = (&String, &CollectedFile)> {
+		self.files.iter()
+	}
+
+	/// Number of files
+	pub fn len(&self) -> usize {
+		self.files.len()
+	}
+
+	/// Whether the set is empty
+	pub fn is_empty(&self) -> bool {
+		self.files.is_empty()
+	}
+
+	/// Record the name under which `name` is served (e.g. its hashed copy)
+	pub fn set_path(&mut self, name: impl Into<String>, served_name: impl Into<String>) {
+		self.paths.insert(name.into(), served_name.into());
+	}
+
+	/// Name under which `name` is served, if a post-processor recorded one
+	pub fn path(&self, name: &str) -> Option<&str> {
+		self.paths.get(name).map(String::as_str)
+	}
+
+	/// All recorded (name, served name) pairs
+	pub fn paths(&self) -> &BTreeMap<String, String> {
+		&self.paths
+	}
+}
+
+/// A step run on the collected files before they are saved
+#[async_trait]
+pub trait PostProcessor: Send + Sync {
+	/// Name used in logs and error messages
+	fn name(&self) -> &str;
+
+	/// Transform the collected files in place
+	async fn post_process(&self, files: &mut CollectedFiles) -> io::Result<()>;
+}
+
+/// Outcome of a collection run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CollectReport {
+	/// Whether nothing was written
+	pub dry_run: bool,
+	/// Files found by the finders and collected
+	pub found: Vec<String>,
+	/// Files provided by several finders (only the first one is collected)
+	pub duplicates: Vec<String>,
+	/// Files skipped because they are hidden or match an ignore pattern
+	pub ignored: Vec<String>,
+	/// Files saved to the storage (or that would be saved in dry-run mode)
+	pub written: Vec<String>,
+	/// Served names recorded by post-processors
+	pub paths: BTreeMap<String, String>,
+}
+
+/// Gathers static files from finders into a storage
+pub struct StaticCollector {
+	storage: Arc<dyn Storage>,
+	finders: Vec<StaticFilesFinder>,
+	post_processors: Vec<Box<dyn PostProcessor>>,
+	ignore_patterns: Vec<glob::Pattern>,
+	dry_run: bool,
+}
+
+impl StaticCollector {
+	/// Create a collector writing to `storage`
+	pub fn new(storage: Arc<dyn Storage>) -> Self {
+		Self {
+			storage,
+			finders: Vec::new(),
+			post_processors: Vec::new(),
+			ignore_patterns: Vec::new(),
+			dry_run: false,
+		}
+	}
+
+	/// Add a finder; earlier finders take precedence
+	pub fn with_finder(mut self, finder: StaticFilesFinder) -> Self {
+		self.finders.push(finder);
+		self
+	}
+
+	/// Add several finders; earlier finders take precedence
+	pub fn with_finders(mut self, finders: impl IntoIterator<Item = StaticFilesFinder>) -> Self {
+		self.finders.extend(finders);
+		self
+	}
+
+	/// Add a post-processor, run after the ones already registered
+	pub fn with_post_processor(mut self, processor: impl PostProcessor + 'static) -> Self {
+		self.post_processors.push(Box::new(processor));
+		self
+	}
+
+	/// Skip files whose path or file name matches one of the glob patterns
+	///
+	/// Invalid patterns are ignored.
+	pub fn with_ignore_patterns(mut self, patterns: Vec<impl AsRef<str>>) -> Self {
+		self.ignore_patterns.extend(
+			patterns
+				.iter()
+				.filter_map(|p| glob::Pattern::new(p.as_ref()).ok()),
+		);
+		self
+	}
+
+	/// Run every step without writing to the storage
+	pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+		self.dry_run = dry_run;
+		self
+	}
+
+	fn is_ignored(&self, name: &str) -> bool {
+		let file_name = Path::new(name)
+			.file_name()
+			.and_then(|n| n.to_str())
+			.unwrap_or(name);
+		file_name.starts_with('.')
+			|| self
+				.ignore_patterns
+				.iter()
+				.any(|p| p.matches(name) || p.matches(file_name))
+	}
+
+	/// Read the files of all finders without post-processing them
+	pub async fn gather(&self) -> io::Result<(CollectedFiles, CollectReport)> {
+		let mut files = CollectedFiles::new();
+		let mut report = CollectReport {
+			dry_run: self.dry_run,
+			..Default::default()
+		};
+		let mut seen = HashSet::new();
+
+		for finder in &self.finders {
+			let mut names = finder.find_all();
+			// Normalize Windows separators so names match storage paths
+			for name in &mut names {
+				*name = name.replace('\\', "/");
+			}
+			names.sort();
+
+			for name in names {
+				if self.is_ignored(&name) {
+					report.ignored.push(name);
+					continue;
+				}
+				if !seen.insert(name.clone()) {
+					report.duplicates.push(name);
+					continue;
+				}
+
+				let source = finder.find(&name)?;
+				files.add_from_source(name.clone(), source);
+				report.found.push(name);
+			}
+		}
+
+		Ok((files, report))
+	}
+
+	/// Gather and post-process the static files without saving them
+	///
+	/// For callers that write the files themselves, e.g. to link unchanged
+	/// files instead of copying them.
+	pub async fn process(&self) -> io::Result<(CollectedFiles, CollectReport)> {
+		let (mut files, mut report) = self.gather().await?;
+
+		for processor in &self.post_processors {
+			processor.post_process(&mut files).await.map_err(|e| {
+				io::Error::new(e.kind(), format!("{} failed: {}", processor.name(), e))
+			})?;
+		}
+		report.paths = files.paths().clone();
+
+		Ok((files, report))
+	}
+
+	/// Gather, post-process and save the static files
+	pub async fn collect(&self) -> io::Result<CollectReport> {
+		let (files, mut report) = self.process().await?;
+
+		for (name, file) in files.iter() {
+			if !self.dry_run {
+				self.storage.save(name, &file.read().await?).await?;
+			}
+			report.written.push(name.clone());
+		}
+
+		Ok(report)
+	}
+}
+
+/// Directory part of a storage name, including the trailing slash
+fn dir_prefix(name: &str) -> &str {
+	name.rfind('/').map_or("", |i| &name[..=i])
+}
+
+/// File name part of a storage name
+fn base_name(name: &str) -> &str {
+	name.rfind('/').map_or(name, |i| &name[i + 1..])
+}
+
+/// Resolve a reference found in `from` to a storage name
+///
+/// Returns `None` for URLs that do not point to a collected file (absolute
+/// URLs, data URIs, fragments, paths escaping the storage root).
+fn resolve_reference(from: &str, reference: &str) -> Option<String> {
+	if reference.is_empty()
+		|| reference.starts_with('#')
+		|| reference.starts_with('/')
+		|| reference.starts_with("data:")
+		|| reference.contains("://")
+	{
+		return None;
+	}
+
+	let mut parts: Vec<&str> = dir_prefix(from)
+		.split('/')
+		.filter(|p| !p.is_empty())
+		.collect();
+	for segment in reference.split('/') {
+		match segment {
+			"" | "." => {}
+			".." => {
+				parts.pop()?;
+			}
+			segment => parts.push(segment),
+		}
+	}
+	Some(parts.join("/"))
+}
+
+/// Replace a reference by the served name of its target, keeping the relative
+/// directory, query string and fragment
+fn rewrite_reference(
+	from: &str,
+	reference: &str,
+	paths: &BTreeMap<String, String>,
+) -> Option<String> {
+	let split = reference.find(['?', '#']).unwrap_or(reference.len());
+	let (path, suffix) = reference.split_at(split);
+	let served = paths.get(&resolve_reference(from, path)?)?;
+	Some(format!(
+		"{}{}{}",
+		dir_prefix(path),
+		base_name(served),
+		suffix
+	))
+}
+
+static CSS_URL: LazyLock<Regex> =
+	LazyLock::new(|| Regex::new(r#"url\(\s*(['"]?)([^'")]+)(['"]?)\s*\)"#).unwrap());
+static CSS_IMPORT: LazyLock<Regex> =
+	LazyLock::new(|| Regex::new(r#"@import\s*(['"])([^'"]+)(['"])"#).unwrap());
+static SOURCE_MAPPING_URL: LazyLock<Regex> =
+	LazyLock::new(|| Regex::new(r"(?m)(//|/\*)# sourceMappingURL=(\S+?)(\s*\*/|[ \t]*$)").unwrap());
+
+/// Rewrite the `sourceMappingURL` comment of a script or stylesheet
+fn rewrite_source_map(name: &str, content: &[u8], paths: &BTreeMap<String, String>) -> Vec<u8> {
+	let Ok(text) = std::str::from_utf8(content) else {
+		return content.to_vec();
+	};
+	SOURCE_MAPPING_URL
+		.replace_all(text, |caps: &Captures| {
+			match rewrite_reference(name, &caps[2], paths) {
+				Some(reference) => {
+					format!("{}# sourceMappingURL={}{}", &caps[1], reference, &caps[3])
+				}
+				None => caps[0].to_string(),
+			}
+		})
+		.into_owned()
+		.into_bytes()
+}
+
+/// Whether references in the file are rewritten to hashed names
+fn has_references(name: &str) -> bool {
+	name.ends_with(".css") || name.ends_with(".js")
+}
+
+/// Rewrite the references of a stylesheet or script to hashed names
+fn rewrite_references(name: &str, content: &[u8], paths: &BTreeMap<String, String>) -> Vec<u8> {
+	if name.ends_with(".css") {
+		rewrite_source_map(name, &rewrite_css(name, content, paths), paths)
+	} else if name.ends_with(".js") {
+		rewrite_source_map(name, content, paths)
+	} else {
+		content.to_vec()
+	}
+}
+
+/// Rewrite `url()` and `@import` references of a stylesheet
+fn rewrite_css(name: &str, content: &[u8], paths: &BTreeMap<String, String>) -> Vec<u8> {
+	let Ok(css) = std::str::from_utf8(content) else {
+		return content.to_vec();
+	};
+
+	let css = CSS_URL.replace_all(css, |caps: &Captures| {
+		match rewrite_reference(name, &caps[2], paths) {
+			Some(reference) => format!("url({}{}{})", &caps[1], reference, &caps[3]),
+			None => caps[0].to_string(),
+		}
+	});
+	let css = CSS_IMPORT.replace_all(&css, |caps: &Captures| {
+		match rewrite_reference(name, &caps[2], paths) {
+			Some(reference) => format!("@import {}{}{}", &caps[1], reference, &caps[3]),
+			None => caps[0].to_string(),
+		}
+	});
+	css.into_owned().into_bytes()
+}
+
+/// Adds content-hashed copies of every file and the manifest
+///
+/// Hashed names are computed by the wrapped [`ManifestStaticFilesStorage`],
+/// which also receives the resulting mapping so its `url()` serves hashed
+/// names. Original files are kept. References in stylesheets and
+/// `sourceMappingURL` comments of scripts and stylesheets are rewritten to the
+/// hashed names before the referencing file is hashed, so every hash matches
+/// the content served under it. Since a stylesheet's hash depends on the
+/// stylesheets it imports, hashing is repeated until stable.
+///
+/// Only stylesheets and scripts are held in memory; other files are hashed
+/// one at a time and their hashed copies still point to the source file.
+pub struct HashingPostProcessor {
+	storage: Arc<ManifestStaticFilesStorage>,
+	max_passes: usize,
+}
+
+impl HashingPostProcessor {
+	/// Create a hashing post-processor
+	pub fn new(storage: Arc<ManifestStaticFilesStorage>) -> Self {
+		Self {
+			storage,
+			max_passes: 5,
+		}
+	}
+
+	/// Set the maximum number of passes over stylesheets (defaults to 5)
+	pub fn with_max_passes(mut self, max_passes: usize) -> Self {
+		self.max_passes = max_passes.max(1);
+		self
+	}
+
+	/// Storage computing the hashed names
+	pub fn storage(&self) -> &Arc<ManifestStaticFilesStorage> {
+		&self.storage
+	}
+}
+
+#[async_trait]
+impl PostProcessor for HashingPostProcessor {
+	fn name(&self) -> &str {
+		"HashingPostProcessor"
+	}
+
+	async fn post_process(&self, files: &mut CollectedFiles) -> io::Result<()> {
+		let mut paths = BTreeMap::new();
+		let mut sources = BTreeMap::new();
+		for name in files.names() {
+			let Some(content) = files.read(&name).await? else {
+				continue;
+			};
+			if has_references(&name) {
+				sources.insert(name, content.into_owned());
+			} else {
+				paths.insert(name.clone(), self.storage.get_hashed_name(&name, &content));
+			}
+		}
+
+		// Scripts only reference source maps, which are hashed by now
+		for (name, content) in sources.iter().filter(|(name, _)| name.ends_with(".js")) {
+			let hashed = self
+				.storage
+				.get_hashed_name(name, &rewrite_references(name, content, &paths));
+			paths.insert(name.clone(), hashed);
+		}
+
+		let stylesheets: Vec<_> = sources
+			.iter()
+			.filter(|(name, _)| name.ends_with(".css"))
+			.collect();
+		let mut stable = stylesheets.is_empty();
+		for _ in 0..self.max_passes {
+			let mut changed = false;
+			for (name, content) in &stylesheets {
+				let hashed = self
+					.storage
+					.get_hashed_name(name, &rewrite_references(name, content, &paths));
+				if paths.get(*name) != Some(&hashed) {
+					paths.insert((*name).clone(), hashed);
+					changed = true;
+				}
+			}
+			if !changed {
+				stable = true;
+				break;
+			}
+		}
+		if !stable {
+			return Err(io::Error::other(format!(
+				"stylesheet references did not stabilize after {} passes",
+				self.max_passes
+			)));
+		}
+
+		for (name, hashed) in &paths {
+			match sources.get(name) {
+				Some(content) => {
+					files.add(hashed.clone(), rewrite_references(name, content, &paths))
+				}
+				None => {
+					files.copy(name, hashed.clone());
+				}
+			}
+			files.set_path(name.clone(), hashed.clone());
+			self.storage
+				.insert_hashed_path(name.clone(), hashed.clone());
+		}
+		files.add(
+			self.storage.manifest_name.clone(),
+			self.storage.manifest_json()?.into_bytes(),
+		);
+
+		Ok(())
+	}
+}
+
+/// Links scripts and stylesheets to their source maps
+///
+/// A script or stylesheet without a `sourceMappingURL` comment gets one when
+/// a `<name>.map` file sits next to it, and the `file` of every referenced
+/// map is set to the file referencing it. Register it before
+/// [`HashingPostProcessor`], which then rewrites the comments to the hashed
+/// maps as part of the content it hashes.
+#[derive(Debug, Default)]
+pub struct SourceMapPostProcessor;
+
+impl SourceMapPostProcessor {
+	/// Create a source map post-processor
+	pub fn new() -> Self {
+		Self
+	}
+}
+
+#[async_trait]
+impl PostProcessor for SourceMapPostProcessor {
+	fn name(&self) -> &str {
+		"SourceMapPostProcessor"
+	}
+
+	async fn post_process(&self, files: &mut CollectedFiles) -> io::Result<()> {
+		for name in files.names() {
+			if !has_references(&name) {
+				continue;
+			}
+			let Some(content) = files.read(&name).await? else {
+				continue;
+			};
+			let Ok(text) = std::str::from_utf8(&content) else {
+				continue;
+			};
+
+			let references: Vec<String> = SOURCE_MAPPING_URL
+				.captures_iter(text)
+				.map(|caps| caps[2].to_string())
+				.collect();
+			let sibling = format!("{}.map", name);
+			let map_names: Vec<String> = if references.is_empty() {
+				if !files.contains(&sibling) {
+					continue;
+				}
+				let comment = if name.ends_with(".css") {
+					format!("\n/*# sourceMappingURL={} */\n", base_name(&sibling))
+				} else {
+					format!("\n//# sourceMappingURL={}\n", base_name(&sibling))
+				};
+				let linked = format!("{}{}", text.trim_end(), comment).into_bytes();
+				files.set_content(&name, linked);
+				vec![sibling]
+			} else {
+				references
+					.iter()
+					.filter_map(|reference| {
+						let path = reference.split(['?', '#']).next().unwrap_or(reference);
+						resolve_reference(&name, path)
+					})
+					.collect()
+			};
+
+			for map_name in map_names {
+				let Some(map) = files.read(&map_name).await? else {
+					continue;
+				};
+				let mut map: serde_json::Value =
+					serde_json::from_slice(&map).map_err(io::Error::other)?;
+				let Some(object) = map.as_object_mut() else {
+					continue;
+				};
+				object.insert(
+					"file".to_string(),
+					serde_json::Value::String(base_name(&name).to_string()),
+				);
+				let map = serde_json::to_vec(&map).map_err(io::Error::other)?;
+				files.set_content(&map_name, map);
+			}
+		}
+
+		Ok(())
+	}
+}
+
+/// Adds `.gz` and `.br` pre-compressed variants of eligible files
+///
+/// Eligibility (extension and minimum size) and levels come from the
+/// [`CompressionConfig`].
+pub struct CompressionPostProcessor {
+	config: CompressionConfig,
+}
+
+impl CompressionPostProcessor {
+	/// Create a compression post-processor
+	pub fn new(config: CompressionConfig) -> Self {
+		Self { config }
+	}
+}
+
+#[async_trait]
+impl PostProcessor for CompressionPostProcessor {
+	fn name(&self) -> &str {
+		"CompressionPostProcessor"
+	}
+
+	async fn post_process(&self, files: &mut CollectedFiles) -> io::Result<()> {
+		let gzip = GzipCompressor::with_level(self.config.gzip_level);
+		let brotli = BrotliCompressor::with_settings(self.config.brotli_quality, 22);
+
+		for name in files.names() {
+			let path = Path::new(&name);
+			let Some(content) = files.read(&name).await? else {
+				continue;
+			};
+			if !self.config.should_compress(path, content.len()) {
+				continue;
+			}
+			let content = content.into_owned();
+
+			if self.config.gzip {
+				let compressed = gzip.process(&content, path).await?;
+				files.add(format!("{}.gz", name), compressed);
+			}
+			if self.config.brotli {
+				let compressed = brotli.process(&content, path).await?;
+				files.add(format!("{}.br", name), compressed);
+			}
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::staticfiles::MemoryStorage;
+	use rstest::rstest;
+	use std::fs;
+	use tempfile::TempDir;
+
+	fn source_dir(files: &[(&str, &str)]) -> TempDir {
+		let dir = TempDir::new().unwrap();
+		for (name, content) in files {
+			let path = dir.path().join(name);
+			fs::create_dir_all(path.parent().unwrap()).unwrap();
+			fs::write(path, content).unwrap();
+		}
+		dir
+	}
+
+	fn finder(dir: &TempDir) -> StaticFilesFinder {
+		StaticFilesFinder::new(vec![dir.path().to_path_buf()])
+	}
+
+	#[rstest]
+	#[case("css/app.css", "../img/logo.png", Some("img/logo.png"))]
+	#[case("css/app.css", "./fonts/a.woff", Some("css/fonts/a.woff"))]
+	#[case("app.css", "../logo.png", None)]
+	#[case("css/app.css", "https://cdn.example.com/x.png", None)]
+	#[case("css/app.css", "data:image/png;base64,AAAA", None)]
+	#[case("css/app.css", "/static/logo.png", None)]
+	fn test_resolve_reference(
+		#[case] from: &str,
+		#[case] reference: &str,
+		#[case] expected: Option<&str>,
+	) {
+		assert_eq!(resolve_reference(from, reference).as_deref(), expected);
+	}
+
+	#[rstest]
+	fn test_rewrite_css_keeps_relative_dir_and_suffix() {
+		let paths = BTreeMap::from([
+			("img/logo.png".to_string(), "img/logo.abc.png".to_string()),
+			("css/base.css".to_string(), "css/base.def.css".to_string()),
+		]);
+		let css = r#"@import "base.css"; a { background: url('../img/logo.png?v=1#x') } b { background: url(missing.png) }"#;
+
+		let rewritten =
+			String::from_utf8(rewrite_css("css/app.css", css.as_bytes(), &paths)).unwrap();
+
+		assert_eq!(
+			rewritten,
+			r#"@import "base.def.css"; a { background: url('../img/logo.abc.png?v=1#x') } b { background: url(missing.png) }"#
+		);
+	}
+
+	#[tokio::test]
+	async fn test_first_finder_wins_and_ignored_files_are_skipped() {
+		let app = source_dir(&[("css/app.css", "app"), (".hidden", "x"), ("notes.txt", "x")]);
+		let other = source_dir(&[("css/app.css", "other"), ("js/app.js", "js")]);
+		let storage = Arc::new(MemoryStorage::default());
+
+		let report = StaticCollector::new(storage.clone())
+			.with_finders([finder(&app), finder(&other)])
+			.with_ignore_patterns(vec!["*.txt"])
+			.collect()
+			.await
+			.unwrap();
+
+		assert_eq!(report.found, vec!["css/app.css", "js/app.js"]);
+		assert_eq!(report.duplicates, vec!["css/app.css"]);
+		assert_eq!(report.ignored.len(), 2);
+		assert_eq!(storage.open("css/app.css").await.unwrap(), b"app");
+		assert!(storage.exists("js/app.js"));
+	}
+
+	#[tokio::test]
+	async fn test_dry_run_writes_nothing() {
+		let app = source_dir(&[("js/app.js", "console.log(1)")]);
+		let storage = Arc::new(MemoryStorage::default());
+		let manifest = Arc::new(ManifestStaticFilesStorage::new(app.path(), "/static/"));
+
+		let report = StaticCollector::new(storage.clone())
+			.with_finder(finder(&app))
+			.with_post_processor(HashingPostProcessor::new(manifest))
+			.with_dry_run(true)
+			.collect()
+			.await
+			.unwrap();
+
+		assert!(report.dry_run);
+		// Original, hashed copy and manifest
+		assert_eq!(report.written.len(), 3);
+		assert!(report.written.contains(&"staticfiles.json".to_string()));
+		assert!(!storage.exists("js/app.js"));
+	}
+
+	#[tokio::test]
+	async fn test_hashing_rewrites_stylesheets_and_records_manifest() {
+		let app = source_dir(&[
+			("img/logo.png", "png"),
+			("css/base.css", "body { background: url(../img/logo.png) }"),
+			("css/app.css", "@import 'base.css';"),
+		]);
+		let storage = Arc::new(MemoryStorage::default());
+		let manifest = Arc::new(ManifestStaticFilesStorage::new(app.path(), "/static/"));
+
+		let report = StaticCollector::new(storage.clone())
+			.with_finder(finder(&app))
+			.with_post_processor(HashingPostProcessor::new(manifest.clone()))
+			.collect()
+			.await
+			.unwrap();
+
+		let logo = &report.paths["img/logo.png"];
+		let base = &report.paths["css/base.css"];
+		let app_css = &report.paths["css/app.css"];
+		let base_content = String::from_utf8(storage.open(base).await.unwrap()).unwrap();
+		let app_content = String::from_utf8(storage.open(app_css).await.unwrap()).unwrap();
+
+		assert_eq!(
+			base_content,
+			format!("body {{ background: url(../img/{}) }}", base_name(logo))
+		);
+		assert_eq!(app_content, format!("@import '{}';", base_name(base)));
+		assert_eq!(
+			manifest.get_hashed_path("css/app.css").as_ref(),
+			Some(app_css)
+		);
+		assert_eq!(manifest.url("img/logo.png"), format!("/static/{}", logo));
+		let saved_manifest: serde_json::Value =
+			serde_json::from_slice(&storage.open("staticfiles.json").await.unwrap()).unwrap();
+		assert_eq!(saved_manifest["paths"]["css/base.css"], base.as_str());
+		// Originals are kept
+		assert!(storage.exists("css/app.css"));
+	}
+
+	#[tokio::test]
+	async fn test_source_maps_follow_hashed_names() {
+		let app = source_dir(&[
+			("js/app.js", "run();\n//# sourceMappingURL=app.js.map"),
+			(
+				"js/app.js.map",
+				r#"{"version":3,"file":"bundle.js","sources":[],"names":[],"mappings":""}"#,
+			),
+			("css/site.css", "body {}"),
+			("css/site.css.map", r#"{"version":3,"sources":[]}"#),
+		]);
+		let storage = Arc::new(MemoryStorage::default());
+		let manifest = Arc::new(ManifestStaticFilesStorage::new(app.path(), "/static/"));
+
+		let report = StaticCollector::new(storage.clone())
+			.with_finder(finder(&app))
+			.with_post_processor(SourceMapPostProcessor::new())
+			.with_post_processor(HashingPostProcessor::new(manifest.clone()))
+			.collect()
+			.await
+			.unwrap();
+
+		let js = &report.paths["js/app.js"];
+		let map = &report.paths["js/app.js.map"];
+		let js_content = storage.open(js).await.unwrap();
+		let map_content = storage.open(map).await.unwrap();
+		let map_json: serde_json::Value = serde_json::from_slice(&map_content).unwrap();
+
+		assert_eq!(
+			String::from_utf8(js_content.clone()).unwrap(),
+			format!("run();\n//# sourceMappingURL={}", base_name(map))
+		);
+		assert_eq!(map_json["file"], "app.js");
+		// Hashes match the content served under the hashed names
+		assert_eq!(*js, manifest.get_hashed_name("js/app.js", &js_content));
+		assert_eq!(
+			*map,
+			manifest.get_hashed_name("js/app.js.map", &map_content)
+		);
+		// The original script still points to the original map
+		assert_eq!(
+			storage.open("js/app.js").await.unwrap(),
+			b"run();\n//# sourceMappingURL=app.js.map"
+		);
+
+		// A stylesheet is linked to the map next to it
+		let css_map = &report.paths["css/site.css.map"];
+		let css =
+			String::from_utf8(storage.open(&report.paths["css/site.css"]).await.unwrap()).unwrap();
+		assert_eq!(
+			css,
+			format!(
+				"body {{}}\n/*# sourceMappingURL={} */\n",
+				base_name(css_map)
+			)
+		);
+	}
+
+	#[tokio::test]
+	async fn test_unmodified_files_are_read_from_their_source() {
+		let app = source_dir(&[("img/logo.png", "png")]);
+		let storage = Arc::new(MemoryStorage::default());
+		let manifest = Arc::new(ManifestStaticFilesStorage::new(app.path(), "/static/"));
+		let collector = StaticCollector::new(storage)
+			.with_finder(finder(&app))
+			.with_post_processor(HashingPostProcessor::new(manifest));
+
+		let (files, report) = collector.process().await.unwrap();
+
+		let hashed = files.get(&report.paths["img/logo.png"]).unwrap();
+		assert!(hashed.is_unmodified_source());
+		assert_eq!(hashed.source, Some(app.path().join("img/logo.png")));
+		assert_eq!(hashed.read().await.unwrap().as_ref(), b"png");
+	}
+
+	#[tokio::test]
+	async fn test_compression_adds_variants_of_eligible_files() {
+		let large = "a".repeat(2048);
+		let app = source_dir(&[
+			("js/app.js", &large),
+			("js/small.js", "x"),
+			("img/a.png", &large),
+		]);
+		let storage = Arc::new(MemoryStorage::default());
+
+		let report = StaticCollector::new(storage.clone())
+			.with_finder(finder(&app))
+			.with_post_processor(CompressionPostProcessor::new(CompressionConfig::default()))
+			.collect()
+			.await
+			.unwrap();
+
+		assert!(report.written.contains(&"js/app.js.gz".to_string()));
+		assert!(report.written.contains(&"js/app.js.br".to_string()));
+		assert!(!storage.exists("js/small.js.gz"));
+		assert!(!storage.exists("img/a.png.gz"));
+
+		let mut decoded = String::new();
+		let gz = storage.open("js/app.js.gz").await.unwrap();
+		io::Read::read_to_string(
+			&mut flate2::read::GzDecoder::new(gz.as_slice()),
+			&mut decoded,
+		)
+		.unwrap();
+		assert_eq!(decoded, large);
+	}
+}
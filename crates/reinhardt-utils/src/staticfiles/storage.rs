@@ -442,7 +442,8 @@ impl ManifestStaticFilesStorage {
 		format!("{:x}", hasher.finish())
 	}
 
-	fn get_hashed_name(&self, name: &str, content: &[u8]) -> String {
+	/// Name under which `content` is stored, with a content hash before the extension
+	pub fn get_hashed_name(&self, name: &str, content: &[u8]) -> String {
 		let hash = Self::hash_content(content);
 		let hash_short = &hash[..12];
 
@@ -521,22 +522,30 @@ impl ManifestStaticFilesStorage {
 	}
 
 	async fn save_manifest(&self) -> io::Result<()> {
-		let (manifest_path, manifest_json) = {
-			let hashed_files = self.hashed_files.read().unwrap();
-			let manifest_path = self.normalize_path(&self.manifest_name);
+		let manifest_path = self.normalize_path(&self.manifest_name);
+		let manifest_json = self.manifest_json()?;
 
-			// Create manifest with "paths" key to match Django's manifest structure
-			let manifest_data = serde_json::json!({
-				"paths": *hashed_files
-			});
+		tokio::fs::write(manifest_path, manifest_json).await
+	}
 
-			let manifest_json =
-				serde_json::to_string_pretty(&manifest_data).map_err(io::Error::other)?;
+	/// Serialize the current mapping in the manifest format
+	pub fn manifest_json(&self) -> io::Result<String> {
+		let hashed_files = self.hashed_files.read().unwrap();
 
-			(manifest_path, manifest_json)
-		};
+		// Create manifest with "paths" key to match Django's manifest structure
+		let manifest_data = serde_json::json!({
+			"paths": *hashed_files
+		});
 
-		tokio::fs::write(manifest_path, manifest_json).await
+		serde_json::to_string_pretty(&manifest_data).map_err(io::Error::other)
+	}
+
+	/// Record the hashed name of a file stored by another component
+	pub fn insert_hashed_path(&self, name: impl Into<String>, hashed_name: impl Into<String>) {
+		self.hashed_files
+			.write()
+			.unwrap()
+			.insert(name.into(), hashed_name.into());
 	}
 
 	/// Load manifest from disk
@@ -0,0 +1,122 @@
+//! `#[cached]` memoization integration tests
+
+use reinhardt_utils::cache::{Cache, InMemoryCache, Memoize, cached};
+use serde::Serialize;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+static CACHE: LazyLock<InMemoryCache> = LazyLock::new(InMemoryCache::new);
+
+static SQUARE_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[cached(cache = CACHE, prefix = "square")]
+async fn square(n: u64) -> u64 {
+	SQUARE_CALLS.fetch_add(1, Ordering::SeqCst);
+	n * n
+}
+
+static LOOKUP_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[cached(cache = CACHE, unless = |users| users.is_empty())]
+async fn lookup(name: &str, limit: usize) -> Vec<String> {
+	LOOKUP_CALLS.fetch_add(1, Ordering::SeqCst);
+	if name == "nobody" {
+		return Vec::new();
+	}
+	(0..limit).map(|i| format!("{}{}", name, i)).collect()
+}
+
+static PARSE_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[cached(cache = CACHE, prefix = "parse", ttl = 60)]
+async fn parse(input: String) -> Result<i32, std::num::ParseIntError> {
+	PARSE_CALLS.fetch_add(1, Ordering::SeqCst);
+	let value = input.trim().parse::<i32>()?;
+	Ok(value * 2)
+}
+
+static REPOSITORY_CACHE: LazyLock<InMemoryCache> = LazyLock::new(InMemoryCache::new);
+
+static TITLE_LOADS: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Serialize)]
+struct Repository {
+	tenant: String,
+}
+
+impl Repository {
+	#[cached(prefix = "repo:title", cache = REPOSITORY_CACHE)]
+	async fn title(&self, id: u32) -> String {
+		TITLE_LOADS.fetch_add(1, Ordering::SeqCst);
+		format!("{} title {}", self.tenant, id)
+	}
+}
+
+/// Test: repeated calls with the same arguments run the body once
+#[tokio::test]
+async fn test_cached_same_arguments() {
+	assert_eq!(square(3).await, 9);
+	assert_eq!(square(3).await, 9);
+	assert_eq!(square(4).await, 16);
+
+	assert_eq!(SQUARE_CALLS.load(Ordering::SeqCst), 2);
+}
+
+/// Test: results matching `unless` are recomputed every call
+#[tokio::test]
+async fn test_cached_unless() {
+	assert_eq!(lookup("ann", 2).await, vec!["ann0", "ann1"]);
+	assert_eq!(lookup("ann", 2).await, vec!["ann0", "ann1"]);
+	assert!(lookup("nobody", 2).await.is_empty());
+	assert!(lookup("nobody", 2).await.is_empty());
+
+	assert_eq!(LOOKUP_CALLS.load(Ordering::SeqCst), 3);
+}
+
+/// Test: errors are returned but not cached
+#[tokio::test]
+async fn test_cached_result_errors_not_cached() {
+	assert!(parse("x".to_string()).await.is_err());
+	assert!(parse("x".to_string()).await.is_err());
+	assert_eq!(parse("21".to_string()).await.unwrap(), 42);
+	assert_eq!(parse("21".to_string()).await.unwrap(), 42);
+
+	assert_eq!(PARSE_CALLS.load(Ordering::SeqCst), 3);
+}
+
+/// Test: methods key their results by the receiver as well as the arguments
+#[tokio::test]
+async fn test_cached_method_keys_receiver() {
+	let acme = Repository {
+		tenant: "acme".to_string(),
+	};
+	let globex = Repository {
+		tenant: "globex".to_string(),
+	};
+
+	assert_eq!(acme.title(1).await, "acme title 1");
+	assert_eq!(acme.title(1).await, "acme title 1");
+	assert_eq!(globex.title(1).await, "globex title 1");
+
+	assert_eq!(TITLE_LOADS.load(Ordering::SeqCst), 2);
+	let key = Memoize::<String>::new("repo:title")
+		.key(&(&&acme, &1u32))
+		.unwrap();
+	let stored: Option<String> = REPOSITORY_CACHE.get(&key).await.unwrap();
+	assert_eq!(stored.as_deref(), Some("acme title 1"));
+}
+
+/// Test: ttl expires cached results
+#[tokio::test]
+async fn test_memoize_ttl_expiry() {
+	let cache = InMemoryCache::new();
+	let memo = Memoize::<u32>::new("ttl").with_ttl(Duration::from_millis(50));
+	let key = memo.key(&()).unwrap();
+
+	memo.get_or_compute(&cache, Some(&key), async { 1 }).await;
+	tokio::time::sleep(Duration::from_millis(100)).await;
+	let value = memo.get_or_compute(&cache, Some(&key), async { 2 }).await;
+
+	assert_eq!(value, 2);
+}
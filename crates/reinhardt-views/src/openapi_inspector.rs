@@ -3,7 +3,10 @@
 //! This module provides functionality to extract OpenAPI schema information from ViewSets,
 //! including paths, operations, parameters, and request/response schemas.
 
-use crate::viewsets::{ActionMetadata, ViewSet};
+use crate::viewsets::{
+	ActionMetadata, FilterConfig, FilterableViewSet, OrderingConfig, PaginatedViewSet,
+	PaginationConfig, ViewSet,
+};
 use hyper::Method;
 use reinhardt_rest::openapi::{
 	Operation, Parameter, PathItem, RefOr, RequestBody, Response, /* Responses, */ Schema,
@@ -11,10 +14,12 @@ use reinhardt_rest::openapi::{
 use std::collections::HashMap;
 use utoipa::openapi::ContentBuilder;
 use utoipa::openapi::path::ParameterIn;
-use utoipa::openapi::path::{HttpMethod, OperationBuilder, ParameterBuilder, PathItemBuilder};
+use utoipa::openapi::path::{
+	HttpMethod, OperationBuilder, ParameterBuilder, ParameterStyle, PathItemBuilder,
+};
 use utoipa::openapi::request_body::RequestBodyBuilder;
 use utoipa::openapi::response::ResponseBuilder;
-use utoipa::openapi::schema::{ObjectBuilder, SchemaType, Type};
+use utoipa::openapi::schema::{ArrayBuilder, ObjectBuilder, SchemaType, Type};
 
 /// Inspects ViewSets to extract schema information
 ///
@@ -137,6 +142,120 @@ impl ViewSetInspector {
 		paths
 	}
 
+	/// Extract path items, documenting the list endpoint's query parameters
+	///
+	/// Same as [`extract_paths`](Self::extract_paths), but the list operation
+	/// also carries the pagination, filter, search and ordering parameters
+	/// configured on the ViewSet (see [`list_query_parameters`](Self::list_query_parameters)).
+	///
+	/// # Example
+	///
+	/// ```rust,no_run
+	/// # use reinhardt_views::openapi_inspector::ViewSetInspector;
+	/// # use reinhardt_views::viewsets::{ModelViewSet, OrderingConfig};
+	/// #
+	/// # #[derive(Debug, Clone)]
+	/// # struct User { id: i64, username: String }
+	/// # #[derive(Debug, Clone)]
+	/// # struct UserSerializer;
+	/// #
+	/// let viewset = ModelViewSet::<User, UserSerializer>::new("users")
+	///     .with_ordering(OrderingConfig::new().with_ordering_fields(vec!["username"]));
+	/// let inspector = ViewSetInspector::new();
+	/// let paths = inspector.extract_paths_with_query_params(&viewset, "/api/users");
+	/// ```
+	pub fn extract_paths_with_query_params<V>(
+		&self,
+		viewset: &V,
+		base_path: &str,
+	) -> HashMap<String, PathItem>
+	where
+		V: ViewSet + PaginatedViewSet + FilterableViewSet,
+	{
+		let mut paths = self.extract_paths(viewset, base_path);
+
+		let parameters = self.list_query_parameters(
+			viewset.get_pagination_config().as_ref(),
+			viewset.get_filter_config().as_ref(),
+			viewset.get_ordering_config().as_ref(),
+		);
+		let collection_path = format!("{}/", base_path.trim_end_matches('/'));
+		if let Some(list) = paths
+			.get_mut(&collection_path)
+			.and_then(|item| item.get.as_mut())
+		{
+			list.parameters
+				.get_or_insert_with(Vec::new)
+				.extend(parameters);
+		}
+
+		paths
+	}
+
+	/// Build the query parameters accepted by a list endpoint
+	///
+	/// Pagination contributes its page, limit/offset or cursor parameters,
+	/// each filterable field becomes an exact-match parameter, search fields
+	/// add a `search` parameter, and ordering fields add an `ordering`
+	/// parameter whose allowed values include the descending (`-field`) forms.
+	///
+	/// # Example
+	///
+	/// ```rust
+	/// # use reinhardt_views::openapi_inspector::ViewSetInspector;
+	/// # use reinhardt_views::viewsets::{FilterConfig, PaginationConfig};
+	/// let inspector = ViewSetInspector::new();
+	/// let params = inspector.list_query_parameters(
+	///     Some(&PaginationConfig::page_number(20, Some(100))),
+	///     Some(&FilterConfig::new().with_filterable_fields(vec!["status"])),
+	///     None,
+	/// );
+	///
+	/// let names: Vec<_> = params.iter().map(|p| p.name.as_str()).collect();
+	/// assert_eq!(names, vec!["page", "status"]);
+	/// ```
+	pub fn list_query_parameters(
+		&self,
+		pagination: Option<&PaginationConfig>,
+		filters: Option<&FilterConfig>,
+		ordering: Option<&OrderingConfig>,
+	) -> Vec<Parameter> {
+		let mut parameters = Vec::new();
+
+		if let Some(pagination) = pagination {
+			parameters.extend(self.create_pagination_parameters(pagination));
+		}
+
+		if let Some(filters) = filters {
+			for field in &filters.filterable_fields {
+				parameters.push(Self::create_query_parameter(
+					field,
+					format!("Filter by exact {}", field),
+					Self::create_string_schema(),
+				));
+			}
+
+			if !filters.search_fields.is_empty() {
+				parameters.push(Self::create_query_parameter(
+					"search",
+					format!(
+						"Search term matched against {}",
+						filters.search_fields.join(", ")
+					),
+					Self::create_string_schema(),
+				));
+			}
+		}
+
+		if let Some(ordering) = ordering
+			&& !ordering.ordering_fields.is_empty()
+		{
+			parameters.push(Self::create_ordering_parameter(ordering));
+		}
+
+		parameters
+	}
+
 	/// Extract CRUD operations for standard ViewSet actions
 	fn extract_crud_paths<V: ViewSet>(
 		&self,
@@ -624,6 +743,93 @@ impl ViewSetInspector {
 			.build()
 	}
 
+	fn create_pagination_parameters(&self, pagination: &PaginationConfig) -> Vec<Parameter> {
+		let (default_size, max_size) = match pagination {
+			PaginationConfig::PageNumber {
+				page_size,
+				max_page_size,
+			} => (*page_size, *max_page_size),
+			PaginationConfig::LimitOffset {
+				default_limit,
+				max_limit,
+			} => (*default_limit, *max_limit),
+			PaginationConfig::Cursor { page_size, .. } => (*page_size, None),
+			PaginationConfig::None => return Vec::new(),
+		};
+
+		pagination
+			.schema_parameters()
+			.into_iter()
+			.filter(|param| param.location == "query")
+			.map(|param| {
+				let schema = match (param.schema_type.as_str(), param.name.as_str()) {
+					("integer", "offset") => ObjectBuilder::new()
+						.schema_type(SchemaType::Type(Type::Integer))
+						.minimum(Some(0)),
+					("integer", "page") => ObjectBuilder::new()
+						.schema_type(SchemaType::Type(Type::Integer))
+						.minimum(Some(1))
+						.default(Some(1.into())),
+					("integer", _) => ObjectBuilder::new()
+						.schema_type(SchemaType::Type(Type::Integer))
+						.minimum(Some(1))
+						.maximum(max_size)
+						.default(Some(default_size.into())),
+					_ => ObjectBuilder::new().schema_type(SchemaType::Type(Type::String)),
+				};
+
+				ParameterBuilder::new()
+					.name(param.name)
+					.parameter_in(ParameterIn::Query)
+					.required(if param.required {
+						utoipa::openapi::Required::True
+					} else {
+						utoipa::openapi::Required::False
+					})
+					.schema(Some(Schema::Object(schema.build())))
+					.description(Some(param.description))
+					.build()
+			})
+			.collect()
+	}
+
+	fn create_ordering_parameter(ordering: &OrderingConfig) -> Parameter {
+		let allowed = ordering
+			.ordering_fields
+			.iter()
+			.flat_map(|field| [field.clone(), format!("-{}", field)]);
+		let items = ObjectBuilder::new()
+			.schema_type(SchemaType::Type(Type::String))
+			.enum_values(Some(allowed));
+
+		let mut schema = ArrayBuilder::new().items(items);
+		if !ordering.default_ordering.is_empty() {
+			schema = schema.default(Some(ordering.default_ordering.clone().into()));
+		}
+
+		ParameterBuilder::new()
+			.name("ordering")
+			.parameter_in(ParameterIn::Query)
+			.required(utoipa::openapi::Required::False)
+			.schema(Some(Schema::Array(schema.build())))
+			.style(Some(ParameterStyle::Form))
+			.explode(Some(false))
+			.description(Some(
+				"Comma-separated fields to order by; prefix a field with '-' for descending order",
+			))
+			.build()
+	}
+
+	fn create_query_parameter(name: &str, description: String, schema: Schema) -> Parameter {
+		ParameterBuilder::new()
+			.name(name)
+			.parameter_in(ParameterIn::Query)
+			.required(utoipa::openapi::Required::False)
+			.schema(Some(schema))
+			.description(Some(description))
+			.build()
+	}
+
 	fn create_request_body(&self, description: &str) -> RequestBody {
 		let content = ContentBuilder::new()
 			.schema(Some(Self::create_object_schema()))
@@ -655,6 +861,14 @@ impl ViewSetInspector {
 		)
 	}
 
+	fn create_string_schema() -> Schema {
+		Schema::Object(
+			ObjectBuilder::new()
+				.schema_type(SchemaType::Type(Type::String))
+				.build(),
+		)
+	}
+
 	fn create_array_schema() -> Schema {
 		use utoipa::openapi::schema::Array;
 		Schema::Array(Array::new(Self::create_object_schema()))
@@ -872,4 +1086,86 @@ mod tests {
 			_ => panic!("Expected Object schema"),
 		}
 	}
+
+	fn parameter<'a>(params: &'a [Parameter], name: &str) -> &'a Parameter {
+		params
+			.iter()
+			.find(|param| param.name == name)
+			.unwrap_or_else(|| panic!("missing parameter {name}"))
+	}
+
+	fn schema_json(param: &Parameter) -> serde_json::Value {
+		serde_json::to_value(param.schema.as_ref().unwrap()).unwrap()
+	}
+
+	#[test]
+	fn test_list_query_parameters_for_limit_offset_pagination() {
+		let inspector = ViewSetInspector::new();
+		let params = inspector.list_query_parameters(
+			Some(&PaginationConfig::limit_offset(25, Some(500))),
+			None,
+			None,
+		);
+
+		assert_eq!(params.len(), 2);
+		let limit = schema_json(parameter(&params, "limit"));
+		assert_eq!(limit["type"], "integer");
+		assert_eq!(limit["default"], 25);
+		assert_eq!(limit["maximum"], 500.0);
+		assert_eq!(schema_json(parameter(&params, "offset"))["minimum"], 0.0);
+		assert!(parameter(&params, "offset").parameter_in == ParameterIn::Query);
+	}
+
+	#[test]
+	fn test_list_query_parameters_for_filters_and_ordering() {
+		let inspector = ViewSetInspector::new();
+		let filters = FilterConfig::new()
+			.with_filterable_fields(vec!["status"])
+			.with_search_fields(vec!["name", "email"]);
+		let ordering = OrderingConfig::new()
+			.with_ordering_fields(vec!["created_at", "name"])
+			.with_default_ordering(vec!["-created_at"]);
+
+		let params = inspector.list_query_parameters(
+			Some(&PaginationConfig::none()),
+			Some(&filters),
+			Some(&ordering),
+		);
+
+		let names: Vec<_> = params.iter().map(|param| param.name.as_str()).collect();
+		assert_eq!(names, vec!["status", "search", "ordering"]);
+		assert_eq!(schema_json(parameter(&params, "status"))["type"], "string");
+
+		let ordering = parameter(&params, "ordering");
+		assert_eq!(ordering.explode, Some(false));
+		let schema = schema_json(ordering);
+		assert_eq!(schema["type"], "array");
+		assert_eq!(
+			schema["items"]["enum"],
+			serde_json::json!(["created_at", "-created_at", "name", "-name"])
+		);
+		assert_eq!(schema["default"], serde_json::json!(["-created_at"]));
+	}
+
+	#[test]
+	fn test_extract_paths_with_query_params_only_affects_list() {
+		let viewset = ModelViewSet::<TestModel, TestSerializer>::new("users")
+			.with_pagination(PaginationConfig::cursor(20, "id"))
+			.with_filters(FilterConfig::new().with_filterable_fields(vec!["is_active"]));
+		let inspector = ViewSetInspector::new();
+		let paths = inspector.extract_paths_with_query_params(&viewset, "/api/users");
+
+		let list = paths["/api/users/"].get.as_ref().unwrap();
+		let params = list.parameters.as_ref().unwrap();
+		let names: Vec<_> = params.iter().map(|param| param.name.as_str()).collect();
+		assert_eq!(names, vec!["cursor", "page_size", "is_active"]);
+		assert!(
+			paths["/api/users/"]
+				.post
+				.as_ref()
+				.unwrap()
+				.parameters
+				.is_none()
+		);
+	}
 }
@@ -1,3 +1,5 @@
+use hyper::Method;
+
 /// Action type for ViewSet operations
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ActionType {
@@ -74,6 +76,30 @@ impl Action {
 		}
 	}
 
+	/// HTTP method a standard action is routed on
+	///
+	/// Returns `None` for custom actions, which declare their own methods.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use hyper::Method;
+	/// use reinhardt_views::viewsets::Action;
+	///
+	/// assert_eq!(Action::partial_update().method(), Some(Method::PATCH));
+	/// assert_eq!(Action::custom("publish", true).method(), None);
+	/// ```
+	pub fn method(&self) -> Option<Method> {
+		match self.action_type {
+			ActionType::List | ActionType::Retrieve => Some(Method::GET),
+			ActionType::Create => Some(Method::POST),
+			ActionType::Update => Some(Method::PUT),
+			ActionType::PartialUpdate => Some(Method::PATCH),
+			ActionType::Destroy => Some(Method::DELETE),
+			ActionType::Custom(_) => None,
+		}
+	}
+
 	/// Create an Action from a string name
 	/// Maps standard action names to their corresponding ActionType
 	///
@@ -5,6 +5,7 @@
 use async_trait::async_trait;
 use reinhardt_core::pagination::{
 	CursorPagination, LimitOffsetPagination, PageNumberPagination, PaginatedResponse, Paginator,
+	SchemaParameter,
 };
 use reinhardt_http::{Request, Result};
 use serde::Serialize;
@@ -101,6 +102,61 @@ impl PaginationConfig {
 	pub fn none() -> Self {
 		Self::None
 	}
+
+	/// Query parameters accepted by this pagination style
+	///
+	/// These are the parameters read by
+	/// [`PaginatedViewSet::paginate_queryset`], for documenting list endpoints.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_views::viewsets::PaginationConfig;
+	///
+	/// let names: Vec<String> = PaginationConfig::limit_offset(25, Some(500))
+	///     .schema_parameters()
+	///     .into_iter()
+	///     .map(|param| param.name)
+	///     .collect();
+	/// assert_eq!(names, vec!["limit", "offset"]);
+	/// ```
+	pub fn schema_parameters(&self) -> Vec<SchemaParameter> {
+		match self {
+			Self::PageNumber {
+				page_size,
+				max_page_size,
+			} => {
+				Paginator::get_schema_parameters(&page_number_paginator(*page_size, *max_page_size))
+			}
+			Self::LimitOffset {
+				default_limit,
+				max_limit,
+			} => Paginator::get_schema_parameters(&limit_offset_paginator(
+				*default_limit,
+				*max_limit,
+			)),
+			Self::Cursor { page_size, .. } => {
+				Paginator::get_schema_parameters(&CursorPagination::new().page_size(*page_size))
+			}
+			Self::None => Vec::new(),
+		}
+	}
+}
+
+fn page_number_paginator(page_size: usize, max_page_size: Option<usize>) -> PageNumberPagination {
+	let mut paginator = PageNumberPagination::new().page_size(page_size);
+	if let Some(max) = max_page_size {
+		paginator = paginator.max_page_size(max);
+	}
+	paginator
+}
+
+fn limit_offset_paginator(default_limit: usize, max_limit: Option<usize>) -> LimitOffsetPagination {
+	let mut paginator = LimitOffsetPagination::new().default_limit(default_limit);
+	if let Some(max) = max_limit {
+		paginator = paginator.max_limit(max);
+	}
+	paginator
 }
 
 /// Trait for ViewSets that support pagination
@@ -135,23 +191,19 @@ pub trait PaginatedViewSet: Send + Sync {
 			PaginationConfig::PageNumber {
 				page_size,
 				max_page_size,
-			} => {
-				let mut paginator = PageNumberPagination::new().page_size(page_size);
-				if let Some(max) = max_page_size {
-					paginator = paginator.max_page_size(max);
-				}
-				paginator.paginate(&items, Some(query_string), base_url)
-			}
+			} => page_number_paginator(page_size, max_page_size).paginate(
+				&items,
+				Some(query_string),
+				base_url,
+			),
 			PaginationConfig::LimitOffset {
 				default_limit,
 				max_limit,
-			} => {
-				let mut paginator = LimitOffsetPagination::new().default_limit(default_limit);
-				if let Some(max) = max_limit {
-					paginator = paginator.max_limit(max);
-				}
-				paginator.paginate(&items, Some(query_string), base_url)
-			}
+			} => limit_offset_paginator(default_limit, max_limit).paginate(
+				&items,
+				Some(query_string),
+				base_url,
+			),
 			PaginationConfig::Cursor {
 				page_size,
 				ordering_field: _,
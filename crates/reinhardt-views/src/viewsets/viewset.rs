@@ -22,6 +22,20 @@ pub trait ViewSet: Send + Sync {
 		"id"
 	}
 
+	/// Standard actions this ViewSet implements
+	///
+	/// Routers only register routes for these actions. Defaults to list,
+	/// create, retrieve, update and destroy.
+	fn get_actions(&self) -> Vec<Action> {
+		vec![
+			Action::list(),
+			Action::create(),
+			Action::retrieve(),
+			Action::update(),
+			Action::destroy(),
+		]
+	}
+
 	/// Dispatch request to appropriate action
 	async fn dispatch(&self, request: Request, action: Action) -> Result<Response>;
 
@@ -356,6 +370,17 @@ where
 		&self.lookup_field
 	}
 
+	fn get_actions(&self) -> Vec<Action> {
+		vec![
+			Action::list(),
+			Action::create(),
+			Action::retrieve(),
+			Action::update(),
+			Action::partial_update(),
+			Action::destroy(),
+		]
+	}
+
 	async fn dispatch(&self, request: Request, action: Action) -> Result<Response> {
 		// Route to appropriate handler based on HTTP method and action
 		match (request.method.clone(), action.detail) {
@@ -576,6 +601,10 @@ where
 		&self.lookup_field
 	}
 
+	fn get_actions(&self) -> Vec<Action> {
+		vec![Action::list(), Action::retrieve()]
+	}
+
 	async fn dispatch(&self, request: Request, action: Action) -> Result<Response> {
 		match (request.method.clone(), action.detail) {
 			(Method::GET, false) => {
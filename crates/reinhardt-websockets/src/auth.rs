@@ -4,6 +4,9 @@
 //! integrating with Reinhardt's auth system.
 
 use crate::connection::{WebSocketConnection, WebSocketError, WebSocketResult};
+use crate::middleware::{
+	ConnectionContext, ConnectionMiddleware, MiddlewareError, MiddlewareResult,
+};
 use async_trait::async_trait;
 use std::sync::Arc;
 
@@ -177,6 +180,131 @@ impl WebSocketAuthenticator for TokenAuthenticator {
 	}
 }
 
+/// Authenticator for WebSocket upgrade requests
+///
+/// Unlike [`WebSocketAuthenticator`], this runs before the connection is
+/// established and extracts credentials from the upgrade request itself
+/// (headers, cookies or query parameters). Use it with [`AuthMiddleware`].
+#[async_trait]
+pub trait UpgradeAuthenticator: Send + Sync {
+	/// Authenticate an upgrade request
+	///
+	/// Returns `Ok(None)` if the request carries no credentials this
+	/// authenticator understands, and an error if the credentials are invalid.
+	async fn authenticate_upgrade(
+		&self,
+		context: &ConnectionContext,
+	) -> AuthResult<Option<Box<dyn AuthUser>>>;
+}
+
+/// Query parameter checked for a token when no `Authorization` header is sent
+///
+/// Browsers cannot set headers on WebSocket upgrade requests, so clients
+/// pass the token in the URL instead (`/ws/chat?token=...`).
+pub const TOKEN_QUERY_PARAM: &str = "token";
+
+/// Get the token of an upgrade request from the `Authorization: Bearer`
+/// header or the [`TOKEN_QUERY_PARAM`] query parameter
+pub fn upgrade_token(context: &ConnectionContext) -> Option<&str> {
+	context
+		.bearer_token()
+		.or_else(|| context.query_param(TOKEN_QUERY_PARAM))
+}
+
+#[async_trait]
+impl UpgradeAuthenticator for TokenAuthenticator {
+	async fn authenticate_upgrade(
+		&self,
+		context: &ConnectionContext,
+	) -> AuthResult<Option<Box<dyn AuthUser>>> {
+		let Some(token) = upgrade_token(context) else {
+			return Ok(None);
+		};
+		self.tokens
+			.get(token)
+			.map(|user| Some(Box::new(user.clone()) as Box<dyn AuthUser>))
+			.ok_or(AuthError::InvalidCredentials)
+	}
+}
+
+/// Connection middleware authenticating upgrade requests
+///
+/// On success the user is stored in the [`ConnectionContext`], and
+/// [`MiddlewareChain::process_upgrade`](crate::middleware::MiddlewareChain::process_upgrade)
+/// returns the [`AuthenticatedConnection`]. Requests with invalid
+/// credentials are always rejected; requests without credentials are
+/// rejected unless anonymous connections are allowed.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_websockets::auth::{AuthMiddleware, SimpleAuthUser, TokenAuthenticator};
+/// use reinhardt_websockets::middleware::{ConnectionContext, MiddlewareChain};
+/// use reinhardt_websockets::WebSocketConnection;
+/// use tokio::sync::mpsc;
+/// use std::sync::Arc;
+///
+/// # tokio_test::block_on(async {
+/// let authenticator = TokenAuthenticator::new(vec![(
+///     "valid_token".to_string(),
+///     SimpleAuthUser::new("user_1".to_string(), "alice".to_string(), vec![]),
+/// )]);
+/// let mut chain = MiddlewareChain::new();
+/// chain.add_connection_middleware(Box::new(AuthMiddleware::new(Arc::new(authenticator))));
+///
+/// let (tx, _rx) = mpsc::unbounded_channel();
+/// let conn = Arc::new(WebSocketConnection::new("conn_1".to_string(), tx));
+/// let mut context = ConnectionContext::new("127.0.0.1".to_string())
+///     .with_query_string("token=valid_token");
+/// let auth_conn = chain.process_upgrade(&mut context, conn.clone()).await.unwrap();
+/// assert_eq!(auth_conn.unwrap().user().username(), "alice");
+///
+/// let mut anonymous = ConnectionContext::new("127.0.0.1".to_string());
+/// assert!(chain.process_upgrade(&mut anonymous, conn).await.is_err());
+/// # });
+/// ```
+pub struct AuthMiddleware {
+	authenticator: Arc<dyn UpgradeAuthenticator>,
+	allow_anonymous: bool,
+}
+
+impl AuthMiddleware {
+	/// Create a new authentication middleware
+	pub fn new(authenticator: Arc<dyn UpgradeAuthenticator>) -> Self {
+		Self {
+			authenticator,
+			allow_anonymous: false,
+		}
+	}
+
+	/// Accept upgrade requests that carry no credentials
+	pub fn allow_anonymous(mut self) -> Self {
+		self.allow_anonymous = true;
+		self
+	}
+}
+
+#[async_trait]
+impl ConnectionMiddleware for AuthMiddleware {
+	async fn on_connect(&self, context: &mut ConnectionContext) -> MiddlewareResult<()> {
+		match self.authenticator.authenticate_upgrade(context).await {
+			Ok(Some(user)) => {
+				context.set_user(user);
+				Ok(())
+			}
+			Ok(None) if self.allow_anonymous => Ok(()),
+			Ok(None) => Err(MiddlewareError::ConnectionRejected(
+				AuthError::MissingAuthentication.to_string(),
+			)),
+			Err(e) => Err(MiddlewareError::ConnectionRejected(e.to_string())),
+		}
+	}
+
+	async fn on_disconnect(&self, _connection: &Arc<WebSocketConnection>) -> MiddlewareResult<()> {
+		Ok(())
+	}
+}
+
 /// Authorization policy for WebSocket messages
 #[async_trait]
 pub trait AuthorizationPolicy: Send + Sync {
@@ -301,6 +429,21 @@ impl AuthenticatedConnection {
 		Self { connection, user }
 	}
 
+	/// Create an authenticated connection from the user authenticated by
+	/// [`AuthMiddleware`] during the upgrade
+	///
+	/// Takes the user out of the context; fails with
+	/// [`AuthError::MissingAuthentication`] if there is none.
+	pub fn from_context(
+		connection: Arc<WebSocketConnection>,
+		context: &mut ConnectionContext,
+	) -> AuthResult<Self> {
+		let user = context
+			.take_user()
+			.ok_or(AuthError::MissingAuthentication)?;
+		Ok(Self::new(connection, user))
+	}
+
 	/// Get the underlying WebSocket connection
 	pub fn connection(&self) -> &Arc<WebSocketConnection> {
 		&self.connection
@@ -330,6 +473,7 @@ impl AuthenticatedConnection {
 mod tests {
 	use super::*;
 	use crate::connection::Message;
+	use crate::middleware::MiddlewareChain;
 	use tokio::sync::mpsc;
 
 	#[test]
@@ -460,4 +604,82 @@ mod tests {
 
 		assert!(result.is_err());
 	}
+
+	fn token_middleware() -> AuthMiddleware {
+		let user = SimpleAuthUser::new("user_1".to_string(), "alice".to_string(), vec![]);
+		let authenticator = TokenAuthenticator::new(vec![("token123".to_string(), user)]);
+		AuthMiddleware::new(Arc::new(authenticator))
+	}
+
+	#[tokio::test]
+	async fn test_auth_middleware_accepts_bearer_header() {
+		let middleware = token_middleware();
+		let mut context = ConnectionContext::new("127.0.0.1".to_string())
+			.with_header("authorization".to_string(), "Bearer token123".to_string());
+
+		middleware.on_connect(&mut context).await.unwrap();
+
+		let (tx, _rx) = mpsc::unbounded_channel();
+		let conn = Arc::new(WebSocketConnection::new("conn_1".to_string(), tx));
+		let auth_conn = AuthenticatedConnection::from_context(conn, &mut context).unwrap();
+		assert_eq!(auth_conn.user().id(), "user_1");
+		assert!(context.user().is_none());
+	}
+
+	#[tokio::test]
+	async fn test_auth_middleware_rejects_invalid_token() {
+		let middleware = token_middleware().allow_anonymous();
+		let mut context =
+			ConnectionContext::new("127.0.0.1".to_string()).with_query_string("token=wrong");
+
+		let result = middleware.on_connect(&mut context).await;
+
+		assert!(matches!(
+			result,
+			Err(MiddlewareError::ConnectionRejected(_))
+		));
+		assert!(context.user().is_none());
+	}
+
+	#[tokio::test]
+	async fn test_process_upgrade_authenticates_connection() {
+		let mut chain = MiddlewareChain::new();
+		chain.add_connection_middleware(Box::new(token_middleware()));
+		let (tx, _rx) = mpsc::unbounded_channel();
+		let conn = Arc::new(WebSocketConnection::new("conn_1".to_string(), tx));
+
+		let mut context =
+			ConnectionContext::new("127.0.0.1".to_string()).with_query_string("token=token123");
+		let auth_conn = chain
+			.process_upgrade(&mut context, conn.clone())
+			.await
+			.unwrap()
+			.unwrap();
+		assert_eq!(auth_conn.user().username(), "alice");
+		assert_eq!(auth_conn.connection().id(), "conn_1");
+
+		let mut context = ConnectionContext::new("127.0.0.1".to_string());
+		let result = chain.process_upgrade(&mut context, conn).await;
+		assert!(matches!(
+			result,
+			Err(MiddlewareError::ConnectionRejected(_))
+		));
+	}
+
+	#[tokio::test]
+	async fn test_auth_middleware_anonymous() {
+		let mut context = ConnectionContext::new("127.0.0.1".to_string());
+
+		assert!(token_middleware().on_connect(&mut context).await.is_err());
+		token_middleware()
+			.allow_anonymous()
+			.on_connect(&mut context)
+			.await
+			.unwrap();
+
+		let (tx, _rx) = mpsc::unbounded_channel();
+		let conn = Arc::new(WebSocketConnection::new("conn_1".to_string(), tx));
+		let result = AuthenticatedConnection::from_context(conn, &mut context);
+		assert!(matches!(result, Err(AuthError::MissingAuthentication)));
+	}
 }
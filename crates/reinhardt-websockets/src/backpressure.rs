@@ -0,0 +1,519 @@
+//! Outbound send queues with backpressure
+//!
+//! Connections created with [`WebSocketConnection::with_send_queue`] buffer
+//! outgoing messages in a bounded [`SendQueue`] instead of an unbounded
+//! channel. When a slow client lets the queue fill up, the configured
+//! [`OverflowPolicy`] decides what happens, so a large broadcast cannot grow
+//! the server's memory without limit.
+//!
+//! ## Usage Example
+//!
+//! ```
+//! use reinhardt_websockets::backpressure::{OverflowPolicy, SendQueueConfig};
+//! use reinhardt_websockets::{Message, WebSocketConnection};
+//!
+//! # tokio_test::block_on(async {
+//! let config = SendQueueConfig::default()
+//!     .with_max_messages(2)
+//!     .with_overflow_policy(OverflowPolicy::DropOldest);
+//! let (conn, mut rx) = WebSocketConnection::with_send_queue("user1".to_string(), config);
+//!
+//! for i in 0..3 {
+//!     conn.send_text(format!("update {}", i)).await.unwrap();
+//! }
+//!
+//! assert_eq!(rx.recv().await, Some(Message::text("update 1".to_string())));
+//! assert_eq!(rx.recv().await, Some(Message::text("update 2".to_string())));
+//! assert_eq!(rx.dropped(), 1);
+//! # });
+//! ```
+//!
+//! [`WebSocketConnection::with_send_queue`]: crate::connection::WebSocketConnection::with_send_queue
+
+use crate::connection::{Message, WebSocketError, WebSocketResult};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::Notify;
+
+/// Close code sent when a connection is closed because its queue overflowed
+pub const OVERFLOW_CLOSE_CODE: u16 = 1008;
+
+/// What to do when a message does not fit into a full send queue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+	/// Discard the oldest queued messages until the new one fits
+	#[default]
+	DropOldest,
+	/// Discard the queue and close the connection
+	Close,
+	/// Replace every queued text/binary message with the new one
+	///
+	/// Suited to streams of state snapshots, where only the latest
+	/// message matters to a client that has fallen behind.
+	Coalesce,
+}
+
+/// Send queue configuration
+#[derive(Debug, Clone)]
+pub struct SendQueueConfig {
+	/// Maximum number of queued messages (None for unlimited)
+	pub max_messages: Option<usize>,
+	/// Maximum total payload size of queued messages in bytes (None for unlimited)
+	pub max_bytes: Option<usize>,
+	/// Behavior when a limit would be exceeded
+	pub overflow_policy: OverflowPolicy,
+}
+
+impl Default for SendQueueConfig {
+	/// Creates a default send queue configuration.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::backpressure::{OverflowPolicy, SendQueueConfig};
+	///
+	/// let config = SendQueueConfig::default();
+	/// assert_eq!(config.max_messages, Some(1024));
+	/// assert_eq!(config.max_bytes, Some(16 * 1024 * 1024));
+	/// assert_eq!(config.overflow_policy, OverflowPolicy::DropOldest);
+	/// ```
+	fn default() -> Self {
+		Self {
+			max_messages: Some(1024),
+			max_bytes: Some(16 * 1024 * 1024),
+			overflow_policy: OverflowPolicy::DropOldest,
+		}
+	}
+}
+
+impl SendQueueConfig {
+	/// Set the maximum number of queued messages
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::backpressure::SendQueueConfig;
+	///
+	/// let config = SendQueueConfig::default().with_max_messages(64);
+	/// assert_eq!(config.max_messages, Some(64));
+	/// ```
+	pub fn with_max_messages(mut self, max: usize) -> Self {
+		self.max_messages = Some(max);
+		self
+	}
+
+	/// Set the maximum total payload size of queued messages
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::backpressure::SendQueueConfig;
+	///
+	/// let config = SendQueueConfig::default().with_max_bytes(1024 * 1024);
+	/// assert_eq!(config.max_bytes, Some(1024 * 1024));
+	/// ```
+	pub fn with_max_bytes(mut self, max: usize) -> Self {
+		self.max_bytes = Some(max);
+		self
+	}
+
+	/// Set the overflow policy
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::backpressure::{OverflowPolicy, SendQueueConfig};
+	///
+	/// let config = SendQueueConfig::default().with_overflow_policy(OverflowPolicy::Close);
+	/// assert_eq!(config.overflow_policy, OverflowPolicy::Close);
+	/// ```
+	pub fn with_overflow_policy(mut self, policy: OverflowPolicy) -> Self {
+		self.overflow_policy = policy;
+		self
+	}
+
+	fn fits(&self, messages: usize, bytes: usize) -> bool {
+		self.max_messages.is_none_or(|max| messages <= max)
+			&& self.max_bytes.is_none_or(|max| bytes <= max)
+	}
+}
+
+/// Payload size of a message as counted against the byte limit
+fn message_size(message: &Message) -> usize {
+	match message {
+		Message::Text { data } => data.len(),
+		Message::Binary { data } => data.len(),
+		Message::Close { reason, .. } => 2 + reason.len(),
+		Message::Ping | Message::Pong => 0,
+	}
+}
+
+fn is_data(message: &Message) -> bool {
+	matches!(message, Message::Text { .. } | Message::Binary { .. })
+}
+
+#[derive(Default)]
+struct QueueState {
+	messages: VecDeque<Message>,
+	bytes: usize,
+	dropped: u64,
+	closed: bool,
+}
+
+impl QueueState {
+	fn push_back(&mut self, message: Message) {
+		self.bytes += message_size(&message);
+		self.messages.push_back(message);
+	}
+
+	fn pop_front(&mut self) -> Option<Message> {
+		let message = self.messages.pop_front()?;
+		self.bytes -= message_size(&message);
+		Some(message)
+	}
+
+	/// Remove the oldest data message, keeping control frames in place
+	fn drop_oldest_data(&mut self) -> bool {
+		let Some(index) = self.messages.iter().position(is_data) else {
+			return false;
+		};
+		let message = self.messages.remove(index).expect("index is in bounds");
+		self.bytes -= message_size(&message);
+		self.dropped += 1;
+		true
+	}
+}
+
+/// Bounded outbound message queue shared by a connection and its writer
+pub struct SendQueue {
+	config: SendQueueConfig,
+	state: Mutex<QueueState>,
+	notify: Notify,
+}
+
+impl SendQueue {
+	/// Create a new send queue
+	pub fn new(config: SendQueueConfig) -> Self {
+		Self {
+			config,
+			state: Mutex::new(QueueState::default()),
+			notify: Notify::new(),
+		}
+	}
+
+	/// Get the send queue configuration
+	pub fn config(&self) -> &SendQueueConfig {
+		&self.config
+	}
+
+	fn state(&self) -> MutexGuard<'_, QueueState> {
+		self.state.lock().unwrap_or_else(|e| e.into_inner())
+	}
+
+	/// Enqueue a message, applying the overflow policy if it does not fit
+	///
+	/// Fails if the queue is closed, if the message alone exceeds the byte
+	/// limit, or if the overflow policy closed the queue. In the last case a
+	/// close frame with [`OVERFLOW_CLOSE_CODE`] replaces the queued messages.
+	pub fn push(&self, message: Message) -> WebSocketResult<()> {
+		let mut state = self.state();
+		if state.closed {
+			return Err(WebSocketError::Send("Connection closed".to_string()));
+		}
+
+		let size = message_size(&message);
+		if self
+			.config
+			.fits(state.messages.len() + 1, state.bytes + size)
+		{
+			state.push_back(message);
+			drop(state);
+			self.notify.notify_one();
+			return Ok(());
+		}
+		if !self.config.fits(1, size) {
+			state.dropped += 1;
+			return Err(WebSocketError::Send(
+				"Message exceeds send queue byte limit".to_string(),
+			));
+		}
+
+		match self.config.overflow_policy {
+			OverflowPolicy::DropOldest => {
+				while !self
+					.config
+					.fits(state.messages.len() + 1, state.bytes + size)
+				{
+					if !state.drop_oldest_data() {
+						state.dropped += 1;
+						return Err(WebSocketError::Send("Send queue is full".to_string()));
+					}
+				}
+			}
+			OverflowPolicy::Coalesce => {
+				while state.drop_oldest_data() {}
+				if !self
+					.config
+					.fits(state.messages.len() + 1, state.bytes + size)
+				{
+					state.dropped += 1;
+					return Err(WebSocketError::Send("Send queue is full".to_string()));
+				}
+			}
+			OverflowPolicy::Close => {
+				state.dropped += state.messages.iter().filter(|m| is_data(m)).count() as u64 + 1;
+				state.messages.clear();
+				state.bytes = 0;
+				state.push_back(Message::Close {
+					code: OVERFLOW_CLOSE_CODE,
+					reason: "Send queue overflow".to_string(),
+				});
+				state.closed = true;
+				drop(state);
+				self.notify.notify_one();
+				return Err(WebSocketError::Send("Send queue overflow".to_string()));
+			}
+		}
+
+		state.push_back(message);
+		drop(state);
+		self.notify.notify_one();
+		Ok(())
+	}
+
+	/// Enqueue a final message regardless of the limits and close the queue
+	///
+	/// Messages already queued are still delivered before it.
+	pub fn close_with(&self, message: Message) -> WebSocketResult<()> {
+		let mut state = self.state();
+		if state.closed {
+			return Err(WebSocketError::Send("Connection closed".to_string()));
+		}
+		state.push_back(message);
+		state.closed = true;
+		drop(state);
+		self.notify.notify_one();
+		Ok(())
+	}
+
+	/// Close the queue without a final message
+	pub fn close(&self) {
+		self.state().closed = true;
+		self.notify.notify_one();
+	}
+
+	/// Check whether the queue is closed
+	pub fn is_closed(&self) -> bool {
+		self.state().closed
+	}
+
+	/// Get the number of queued messages
+	pub fn len(&self) -> usize {
+		self.state().messages.len()
+	}
+
+	/// Check whether the queue is empty
+	pub fn is_empty(&self) -> bool {
+		self.state().messages.is_empty()
+	}
+
+	/// Get the total payload size of queued messages in bytes
+	pub fn bytes(&self) -> usize {
+		self.state().bytes
+	}
+
+	/// Get the number of messages discarded because of the limits
+	pub fn dropped(&self) -> u64 {
+		self.state().dropped
+	}
+
+	/// Dequeue the next message without waiting
+	pub fn try_recv(&self) -> Option<Message> {
+		self.state().pop_front()
+	}
+
+	/// Dequeue the next message, waiting until one is available
+	///
+	/// Returns `None` once the queue is closed and drained.
+	pub async fn recv(&self) -> Option<Message> {
+		loop {
+			let notified = self.notify.notified();
+			{
+				let mut state = self.state();
+				if let Some(message) = state.pop_front() {
+					return Some(message);
+				}
+				if state.closed {
+					return None;
+				}
+			}
+			notified.await;
+		}
+	}
+}
+
+/// Receiving half of a connection's send queue
+///
+/// The task writing frames to the socket drains this receiver.
+pub struct SendQueueReceiver {
+	queue: Arc<SendQueue>,
+}
+
+impl SendQueueReceiver {
+	pub(crate) fn new(queue: Arc<SendQueue>) -> Self {
+		Self { queue }
+	}
+
+	/// Dequeue the next message, waiting until one is available
+	///
+	/// Returns `None` once the connection is closed (or dropped) and all
+	/// queued messages have been received.
+	pub async fn recv(&mut self) -> Option<Message> {
+		self.queue.recv().await
+	}
+
+	/// Dequeue the next message without waiting
+	pub fn try_recv(&mut self) -> Option<Message> {
+		self.queue.try_recv()
+	}
+
+	/// Get the number of queued messages
+	pub fn len(&self) -> usize {
+		self.queue.len()
+	}
+
+	/// Check whether the queue is empty
+	pub fn is_empty(&self) -> bool {
+		self.queue.is_empty()
+	}
+
+	/// Get the total payload size of queued messages in bytes
+	pub fn bytes(&self) -> usize {
+		self.queue.bytes()
+	}
+
+	/// Get the number of messages discarded because of the limits
+	pub fn dropped(&self) -> u64 {
+		self.queue.dropped()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+
+	fn text(data: &str) -> Message {
+		Message::text(data.to_string())
+	}
+
+	fn drain(queue: &SendQueue) -> Vec<Message> {
+		std::iter::from_fn(|| queue.try_recv()).collect()
+	}
+
+	#[test]
+	fn test_drop_oldest_keeps_newest_messages() {
+		let queue = SendQueue::new(SendQueueConfig::default().with_max_messages(2));
+
+		for data in ["a", "b", "c"] {
+			queue.push(text(data)).unwrap();
+		}
+
+		assert_eq!(drain(&queue), vec![text("b"), text("c")]);
+		assert_eq!(queue.dropped(), 1);
+		assert_eq!(queue.bytes(), 0);
+	}
+
+	#[test]
+	fn test_byte_limit_preserves_control_frames() {
+		let queue = SendQueue::new(
+			SendQueueConfig::default()
+				.with_max_bytes(8)
+				.with_overflow_policy(OverflowPolicy::DropOldest),
+		);
+
+		queue.push(text("1234")).unwrap();
+		queue.push(Message::Ping).unwrap();
+		queue.push(text("5678")).unwrap();
+		queue.push(text("abcd")).unwrap();
+
+		assert_eq!(queue.bytes(), 8);
+		assert_eq!(
+			drain(&queue),
+			vec![Message::Ping, text("5678"), text("abcd")]
+		);
+	}
+
+	#[test]
+	fn test_coalesce_replaces_queued_data() {
+		let queue = SendQueue::new(
+			SendQueueConfig::default()
+				.with_max_messages(3)
+				.with_overflow_policy(OverflowPolicy::Coalesce),
+		);
+
+		queue.push(text("state 1")).unwrap();
+		queue.push(Message::Ping).unwrap();
+		queue.push(text("state 2")).unwrap();
+		queue.push(text("state 3")).unwrap();
+
+		assert_eq!(drain(&queue), vec![Message::Ping, text("state 3")]);
+		assert_eq!(queue.dropped(), 2);
+	}
+
+	#[tokio::test]
+	async fn test_close_policy_closes_queue() {
+		let queue = SendQueue::new(
+			SendQueueConfig::default()
+				.with_max_messages(1)
+				.with_overflow_policy(OverflowPolicy::Close),
+		);
+
+		queue.push(text("a")).unwrap();
+		assert!(queue.push(text("b")).is_err());
+		assert!(queue.is_closed());
+		assert!(queue.push(text("c")).is_err());
+
+		assert_eq!(
+			queue.recv().await,
+			Some(Message::Close {
+				code: OVERFLOW_CLOSE_CODE,
+				reason: "Send queue overflow".to_string(),
+			})
+		);
+		assert_eq!(queue.recv().await, None);
+		assert_eq!(queue.dropped(), 2);
+	}
+
+	#[rstest]
+	#[case(OverflowPolicy::DropOldest)]
+	#[case(OverflowPolicy::Coalesce)]
+	#[case(OverflowPolicy::Close)]
+	fn test_oversized_message_is_rejected(#[case] policy: OverflowPolicy) {
+		let queue = SendQueue::new(
+			SendQueueConfig::default()
+				.with_max_bytes(4)
+				.with_overflow_policy(policy),
+		);
+
+		queue.push(text("ok")).unwrap();
+		assert!(queue.push(text("too large")).is_err());
+
+		assert!(!queue.is_closed());
+		assert_eq!(drain(&queue), vec![text("ok")]);
+	}
+
+	#[tokio::test]
+	async fn test_recv_waits_for_messages() {
+		let queue = Arc::new(SendQueue::new(SendQueueConfig::default()));
+		let receiver = tokio::spawn({
+			let queue = queue.clone();
+			async move { queue.recv().await }
+		});
+
+		tokio::task::yield_now().await;
+		queue.push(text("hello")).unwrap();
+
+		assert_eq!(receiver.await.unwrap(), Some(text("hello")));
+	}
+}
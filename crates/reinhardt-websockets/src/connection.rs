@@ -1,3 +1,6 @@
+use crate::backpressure::{SendQueue, SendQueueConfig, SendQueueReceiver};
+#[cfg(feature = "compression")]
+use crate::deflate::{DeflateParams, PerMessageDeflate};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tokio::sync::mpsc;
@@ -125,13 +128,51 @@ impl Message {
 	}
 }
 
+/// Outbound path of a connection
+enum Outbound {
+	Channel(mpsc::UnboundedSender<Message>),
+	Queue(QueueSender),
+}
+
+/// Sending half of a send queue; closes the queue when the connection is dropped
+struct QueueSender(Arc<SendQueue>);
+
+impl Drop for QueueSender {
+	fn drop(&mut self) {
+		self.0.close();
+	}
+}
+
+impl Outbound {
+	fn send(&self, message: Message) -> WebSocketResult<()> {
+		match self {
+			Outbound::Channel(tx) => tx
+				.send(message)
+				.map_err(|e| WebSocketError::Send(e.to_string())),
+			Outbound::Queue(queue) => queue.0.push(message),
+		}
+	}
+
+	fn close_with(&self, message: Message) -> WebSocketResult<()> {
+		match self {
+			Outbound::Channel(tx) => tx
+				.send(message)
+				.map_err(|e| WebSocketError::Send(e.to_string())),
+			Outbound::Queue(queue) => queue.0.close_with(message),
+		}
+	}
+}
+
 /// WebSocket connection
 pub struct WebSocketConnection {
 	id: String,
-	tx: mpsc::UnboundedSender<Message>,
+	outbound: Outbound,
 	closed: Arc<RwLock<bool>>,
 	/// Subprotocol (negotiated protocol during WebSocket handshake)
 	subprotocol: Option<String>,
+	/// permessage-deflate state (negotiated extension during WebSocket handshake)
+	#[cfg(feature = "compression")]
+	deflate: Option<Arc<std::sync::Mutex<PerMessageDeflate>>>,
 }
 
 impl WebSocketConnection {
@@ -150,12 +191,52 @@ impl WebSocketConnection {
 	pub fn new(id: String, tx: mpsc::UnboundedSender<Message>) -> Self {
 		Self {
 			id,
-			tx,
+			outbound: Outbound::Channel(tx),
 			closed: Arc::new(RwLock::new(false)),
 			subprotocol: None,
+			#[cfg(feature = "compression")]
+			deflate: None,
 		}
 	}
 
+	/// Creates a new WebSocket connection whose outgoing messages are buffered
+	/// in a bounded send queue.
+	///
+	/// The returned receiver is drained by the task writing to the socket.
+	/// See the [`backpressure`](crate::backpressure) module for the overflow policies.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::backpressure::{OverflowPolicy, SendQueueConfig};
+	/// use reinhardt_websockets::{WebSocketConnection, Message};
+	///
+	/// # tokio_test::block_on(async {
+	/// let config = SendQueueConfig::default()
+	///     .with_max_messages(1)
+	///     .with_overflow_policy(OverflowPolicy::Close);
+	/// let (conn, mut rx) = WebSocketConnection::with_send_queue("test".to_string(), config);
+	///
+	/// conn.send_text("first".to_string()).await.unwrap();
+	/// assert!(conn.send_text("second".to_string()).await.is_err());
+	/// assert!(conn.is_closed().await);
+	///
+	/// assert!(matches!(rx.recv().await, Some(Message::Close { code: 1008, .. })));
+	/// # });
+	/// ```
+	pub fn with_send_queue(id: String, config: SendQueueConfig) -> (Self, SendQueueReceiver) {
+		let queue = Arc::new(SendQueue::new(config));
+		let conn = Self {
+			id,
+			outbound: Outbound::Queue(QueueSender(queue.clone())),
+			closed: Arc::new(RwLock::new(false)),
+			subprotocol: None,
+			#[cfg(feature = "compression")]
+			deflate: None,
+		};
+		(conn, SendQueueReceiver::new(queue))
+	}
+
 	/// Creates a new WebSocket connection with the given ID, sender, and subprotocol.
 	///
 	/// # Examples
@@ -180,9 +261,11 @@ impl WebSocketConnection {
 	) -> Self {
 		Self {
 			id,
-			tx,
+			outbound: Outbound::Channel(tx),
 			closed: Arc::new(RwLock::new(false)),
 			subprotocol,
+			#[cfg(feature = "compression")]
+			deflate: None,
 		}
 	}
 
@@ -205,6 +288,87 @@ impl WebSocketConnection {
 	pub fn subprotocol(&self) -> Option<&str> {
 		self.subprotocol.as_deref()
 	}
+	/// Attaches the permessage-deflate extension negotiated during the handshake.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::WebSocketConnection;
+	/// use reinhardt_websockets::deflate::DeflateConfig;
+	/// use tokio::sync::mpsc;
+	///
+	/// let deflate = DeflateConfig::default()
+	///     .negotiate("permessage-deflate; client_max_window_bits")
+	///     .unwrap();
+	///
+	/// let (tx, _rx) = mpsc::unbounded_channel();
+	/// let conn = WebSocketConnection::new("test".to_string(), tx).with_deflate(deflate);
+	/// assert!(conn.deflate_params().is_some());
+	/// ```
+	#[cfg(feature = "compression")]
+	pub fn with_deflate(mut self, deflate: PerMessageDeflate) -> Self {
+		self.deflate = Some(Arc::new(std::sync::Mutex::new(deflate)));
+		self
+	}
+	/// Gets the negotiated permessage-deflate parameters, if the extension is in use.
+	#[cfg(feature = "compression")]
+	pub fn deflate_params(&self) -> Option<DeflateParams> {
+		let deflate = self.deflate.as_ref()?;
+		let deflate = deflate.lock().unwrap_or_else(|e| e.into_inner());
+		Some(*deflate.params())
+	}
+	/// Compresses an outgoing frame payload with permessage-deflate.
+	///
+	/// Returns `None` when the extension is not in use or the payload is
+	/// below the compression threshold; the payload is then sent as is.
+	/// Otherwise the returned bytes are sent in a frame with RSV1 set.
+	/// Payloads must be passed in the order their messages are sent.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::WebSocketConnection;
+	/// use reinhardt_websockets::deflate::DeflateConfig;
+	/// use tokio::sync::mpsc;
+	///
+	/// let config = DeflateConfig::default().with_threshold(64);
+	/// let deflate = config.negotiate("permessage-deflate").unwrap();
+	///
+	/// let (tx, _rx) = mpsc::unbounded_channel();
+	/// let conn = WebSocketConnection::new("test".to_string(), tx).with_deflate(deflate);
+	///
+	/// assert!(conn.compress_payload(b"small").unwrap().is_none());
+	/// let payload = b"{\"event\":\"tick\"}".repeat(10);
+	/// let compressed = conn.compress_payload(&payload).unwrap().unwrap();
+	/// assert!(compressed.len() < payload.len());
+	/// ```
+	#[cfg(feature = "compression")]
+	pub fn compress_payload(&self, payload: &[u8]) -> WebSocketResult<Option<Vec<u8>>> {
+		let Some(deflate) = &self.deflate else {
+			return Ok(None);
+		};
+		let mut deflate = deflate
+			.lock()
+			.map_err(|e| WebSocketError::Internal(e.to_string()))?;
+		if !deflate.should_compress(payload) {
+			return Ok(None);
+		}
+		deflate.compress(payload).map(Some)
+	}
+	/// Decompresses the payload of an incoming frame received with RSV1 set.
+	///
+	/// Fails if permessage-deflate was not negotiated for this connection.
+	/// Payloads must be passed in the order their messages are received.
+	#[cfg(feature = "compression")]
+	pub fn decompress_payload(&self, payload: &[u8]) -> WebSocketResult<Vec<u8>> {
+		let deflate = self.deflate.as_ref().ok_or_else(|| {
+			WebSocketError::Protocol("permessage-deflate was not negotiated".to_string())
+		})?;
+		deflate
+			.lock()
+			.map_err(|e| WebSocketError::Internal(e.to_string()))?
+			.decompress(payload)
+	}
 	/// Gets the connection ID.
 	///
 	/// # Examples
@@ -244,9 +408,14 @@ impl WebSocketConnection {
 			return Err(WebSocketError::Send("Connection closed".to_string()));
 		}
 
-		self.tx
-			.send(message)
-			.map_err(|e| WebSocketError::Send(e.to_string()))
+		let result = self.outbound.send(message);
+		if let Outbound::Queue(queue) = &self.outbound
+			&& queue.0.is_closed()
+		{
+			// The overflow policy closed the connection
+			*self.closed.write().await = true;
+		}
+		result
 	}
 	/// Sends a text message through the WebSocket connection.
 	///
@@ -348,13 +517,10 @@ impl WebSocketConnection {
 	/// ```
 	pub async fn close(&self) -> WebSocketResult<()> {
 		// First send the close message
-		let result = self
-			.tx
-			.send(Message::Close {
-				code: 1000,
-				reason: "Normal closure".to_string(),
-			})
-			.map_err(|e| WebSocketError::Send(e.to_string()));
+		let result = self.outbound.close_with(Message::Close {
+			code: 1000,
+			reason: "Normal closure".to_string(),
+		});
 
 		// Then mark as closed
 		*self.closed.write().await = true;
@@ -423,4 +589,34 @@ mod tests {
 			_ => panic!("Expected text message"),
 		}
 	}
+
+	#[cfg(feature = "compression")]
+	#[test]
+	fn test_connection_deflate_round_trip() {
+		use crate::deflate::DeflateConfig;
+
+		let config = DeflateConfig::default().with_threshold(16);
+		let (tx, _rx) = mpsc::unbounded_channel();
+		let conn = WebSocketConnection::new("test".to_string(), tx)
+			.with_deflate(config.negotiate("permessage-deflate").unwrap());
+		let mut peer = config.negotiate("permessage-deflate").unwrap();
+		let payload = br#"{"event":"update","value":1}"#.repeat(8);
+
+		let compressed = conn.compress_payload(&payload).unwrap().unwrap();
+		let received = peer.decompress(&compressed).unwrap();
+		let echoed = peer.compress(&received).unwrap();
+
+		assert_eq!(conn.decompress_payload(&echoed).unwrap(), payload);
+	}
+
+	#[cfg(feature = "compression")]
+	#[test]
+	fn test_connection_without_deflate_sends_payload_as_is() {
+		let (tx, _rx) = mpsc::unbounded_channel();
+		let conn = WebSocketConnection::new("test".to_string(), tx);
+
+		assert!(conn.deflate_params().is_none());
+		assert!(conn.compress_payload(b"payload").unwrap().is_none());
+		assert!(conn.decompress_payload(b"payload").is_err());
+	}
 }
@@ -41,6 +41,9 @@
 #[allow(unused_imports)]
 use crate::connection::{Message, WebSocketConnection, WebSocketError, WebSocketResult};
 use async_trait::async_trait;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
 use std::sync::Arc;
 
 #[cfg(feature = "di")]
@@ -517,6 +520,208 @@ impl WebSocketConsumer for JsonConsumer {
 	}
 }
 
+/// Consumer whose messages are serde types rather than raw frames
+///
+/// Wrap an implementation in [`TypedConsumerAdapter`] to use it wherever a
+/// [`WebSocketConsumer`] is expected. Incoming text and binary frames are
+/// decoded from JSON into `In` (typically an internally tagged enum, so
+/// that each variant maps to one kind of client message), and the reply
+/// returned by [`on_message`](Self::on_message) is encoded as JSON in a
+/// frame of the same kind as the request. Frames that fail to decode are
+/// answered with an [`ErrorFrame`] and do not reach the consumer.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_websockets::consumers::{
+///     ConsumerContext, TypedConsumer, TypedConsumerAdapter, WebSocketConsumer,
+/// };
+/// use reinhardt_websockets::{Message, WebSocketConnection, WebSocketResult};
+/// use async_trait::async_trait;
+/// use serde::{Deserialize, Serialize};
+/// use std::sync::Arc;
+/// use tokio::sync::mpsc;
+///
+/// #[derive(Deserialize)]
+/// #[serde(tag = "type", rename_all = "snake_case")]
+/// enum Request {
+///     Add { a: i64, b: i64 },
+///     Ping,
+/// }
+///
+/// #[derive(Serialize)]
+/// #[serde(tag = "type", rename_all = "snake_case")]
+/// enum Reply {
+///     Sum { value: i64 },
+///     Pong,
+/// }
+///
+/// struct Calculator;
+///
+/// #[async_trait]
+/// impl TypedConsumer<Request, Reply> for Calculator {
+///     async fn on_message(
+///         &self,
+///         _context: &mut ConsumerContext,
+///         message: Request,
+///     ) -> WebSocketResult<Option<Reply>> {
+///         Ok(Some(match message {
+///             Request::Add { a, b } => Reply::Sum { value: a + b },
+///             Request::Ping => Reply::Pong,
+///         }))
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let consumer = TypedConsumerAdapter::new(Calculator);
+/// let (tx, mut rx) = mpsc::unbounded_channel();
+/// let conn = Arc::new(WebSocketConnection::new("test".to_string(), tx));
+/// let mut context = ConsumerContext::new(conn);
+///
+/// let request = Message::text(r#"{"type":"add","a":2,"b":3}"#.to_string());
+/// consumer.on_message(&mut context, request).await.unwrap();
+///
+/// match rx.recv().await.unwrap() {
+///     Message::Text { data } => assert_eq!(data, r#"{"type":"sum","value":5}"#),
+///     _ => panic!("Expected text message"),
+/// }
+/// # });
+/// ```
+#[async_trait]
+pub trait TypedConsumer<In, Out>: Send + Sync
+where
+	In: DeserializeOwned + Send + 'static,
+	Out: Serialize + Send + 'static,
+{
+	/// Called when a WebSocket connection is established
+	async fn on_connect(&self, _context: &mut ConsumerContext) -> WebSocketResult<()> {
+		Ok(())
+	}
+
+	/// Called with each decoded message; the returned value, if any, is sent back
+	async fn on_message(
+		&self,
+		context: &mut ConsumerContext,
+		message: In,
+	) -> WebSocketResult<Option<Out>>;
+
+	/// Called when a WebSocket connection is closed
+	async fn on_disconnect(&self, _context: &mut ConsumerContext) -> WebSocketResult<()> {
+		Ok(())
+	}
+}
+
+/// Error sent to the client when a frame cannot be decoded
+///
+/// Serialized as `{"type":"error","code":"...","message":"..."}`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename = "error")]
+pub struct ErrorFrame {
+	/// Machine-readable error code
+	pub code: String,
+	/// Human-readable description
+	pub message: String,
+}
+
+impl ErrorFrame {
+	/// Code for frames whose payload does not match the message schema
+	pub const INVALID_MESSAGE: &'static str = "invalid_message";
+
+	/// Create a new error frame
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::consumers::ErrorFrame;
+	///
+	/// let frame = ErrorFrame::new(ErrorFrame::INVALID_MESSAGE, "missing field `a`");
+	/// assert_eq!(
+	///     serde_json::to_string(&frame).unwrap(),
+	///     r#"{"type":"error","code":"invalid_message","message":"missing field `a`"}"#
+	/// );
+	/// ```
+	pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+		Self {
+			code: code.into(),
+			message: message.into(),
+		}
+	}
+}
+
+/// Adapter running a [`TypedConsumer`] as a [`WebSocketConsumer`]
+pub struct TypedConsumerAdapter<C, In, Out> {
+	consumer: C,
+	_marker: PhantomData<fn(In) -> Out>,
+}
+
+impl<C, In, Out> TypedConsumerAdapter<C, In, Out>
+where
+	C: TypedConsumer<In, Out>,
+	In: DeserializeOwned + Send + 'static,
+	Out: Serialize + Send + 'static,
+{
+	/// Create a new adapter for a typed consumer
+	pub fn new(consumer: C) -> Self {
+		Self {
+			consumer,
+			_marker: PhantomData,
+		}
+	}
+
+	/// Get the wrapped consumer
+	pub fn consumer(&self) -> &C {
+		&self.consumer
+	}
+}
+
+#[async_trait]
+impl<C, In, Out> WebSocketConsumer for TypedConsumerAdapter<C, In, Out>
+where
+	C: TypedConsumer<In, Out>,
+	In: DeserializeOwned + Send + 'static,
+	Out: Serialize + Send + 'static,
+{
+	async fn on_connect(&self, context: &mut ConsumerContext) -> WebSocketResult<()> {
+		self.consumer.on_connect(context).await
+	}
+
+	async fn on_message(
+		&self,
+		context: &mut ConsumerContext,
+		message: Message,
+	) -> WebSocketResult<()> {
+		let (decoded, binary) = match &message {
+			Message::Text { data } => (serde_json::from_str::<In>(data), false),
+			Message::Binary { data } => (serde_json::from_slice::<In>(data), true),
+			_ => return Ok(()),
+		};
+
+		let decoded = match decoded {
+			Ok(decoded) => decoded,
+			Err(e) => {
+				let frame = ErrorFrame::new(ErrorFrame::INVALID_MESSAGE, e.to_string());
+				return context.connection.send_json(&frame).await;
+			}
+		};
+
+		let Some(reply) = self.consumer.on_message(context, decoded).await? else {
+			return Ok(());
+		};
+		let reply = if binary {
+			Message::binary(
+				serde_json::to_vec(&reply).map_err(|e| WebSocketError::Protocol(e.to_string()))?,
+			)
+		} else {
+			Message::json(&reply)?
+		};
+		context.connection.send(reply).await
+	}
+
+	async fn on_disconnect(&self, context: &mut ConsumerContext) -> WebSocketResult<()> {
+		self.consumer.on_disconnect(context).await
+	}
+}
+
 /// Consumer chain for composing multiple consumers
 ///
 /// # Examples
@@ -677,4 +882,93 @@ mod tests {
 
 		assert!(chain.on_connect(&mut context).await.is_ok());
 	}
+
+	#[derive(serde::Deserialize)]
+	#[serde(tag = "type", rename_all = "snake_case")]
+	enum CounterRequest {
+		Increment { by: u32 },
+		Reset,
+	}
+
+	#[derive(serde::Serialize)]
+	struct CounterReply {
+		value: u32,
+	}
+
+	struct Counter {
+		value: std::sync::Mutex<u32>,
+	}
+
+	#[async_trait]
+	impl TypedConsumer<CounterRequest, CounterReply> for Counter {
+		async fn on_message(
+			&self,
+			_context: &mut ConsumerContext,
+			message: CounterRequest,
+		) -> WebSocketResult<Option<CounterReply>> {
+			let mut value = self.value.lock().unwrap();
+			match message {
+				CounterRequest::Increment { by } => *value += by,
+				CounterRequest::Reset => {
+					*value = 0;
+					return Ok(None);
+				}
+			}
+			Ok(Some(CounterReply { value: *value }))
+		}
+	}
+
+	fn typed_consumer() -> TypedConsumerAdapter<Counter, CounterRequest, CounterReply> {
+		TypedConsumerAdapter::new(Counter {
+			value: std::sync::Mutex::new(0),
+		})
+	}
+
+	#[tokio::test]
+	async fn test_typed_consumer_replies_in_request_frame_kind() {
+		let consumer = typed_consumer();
+		let (tx, mut rx) = mpsc::unbounded_channel();
+		let conn = Arc::new(WebSocketConnection::new("test".to_string(), tx));
+		let mut context = ConsumerContext::new(conn);
+
+		let text = Message::text(r#"{"type":"increment","by":2}"#.to_string());
+		consumer.on_message(&mut context, text).await.unwrap();
+		let binary = Message::binary(br#"{"type":"increment","by":3}"#.to_vec());
+		consumer.on_message(&mut context, binary).await.unwrap();
+		let reset = Message::text(r#"{"type":"reset"}"#.to_string());
+		consumer.on_message(&mut context, reset).await.unwrap();
+
+		assert_eq!(
+			rx.try_recv().unwrap(),
+			Message::text(r#"{"value":2}"#.to_string())
+		);
+		assert_eq!(
+			rx.try_recv().unwrap(),
+			Message::binary(br#"{"value":5}"#.to_vec())
+		);
+		assert!(rx.try_recv().is_err());
+		assert_eq!(*consumer.consumer().value.lock().unwrap(), 0);
+	}
+
+	#[tokio::test]
+	async fn test_typed_consumer_sends_error_frame_on_decode_failure() {
+		let consumer = typed_consumer();
+		let (tx, mut rx) = mpsc::unbounded_channel();
+		let conn = Arc::new(WebSocketConnection::new("test".to_string(), tx));
+		let mut context = ConsumerContext::new(conn);
+
+		for payload in [
+			r#"{"type":"increment"}"#,
+			r#"{"type":"explode"}"#,
+			"not json",
+		] {
+			let message = Message::text(payload.to_string());
+			consumer.on_message(&mut context, message).await.unwrap();
+
+			let frame: ErrorFrame = rx.try_recv().unwrap().parse_json().unwrap();
+			assert_eq!(frame.code, ErrorFrame::INVALID_MESSAGE);
+			assert!(!frame.message.is_empty());
+		}
+		assert_eq!(*consumer.consumer().value.lock().unwrap(), 0);
+	}
 }
@@ -0,0 +1,746 @@
+//! Per-message deflate compression (RFC 7692)
+//!
+//! This module implements the `permessage-deflate` WebSocket extension:
+//! negotiating its parameters from the client's `Sec-WebSocket-Extensions`
+//! offer during the handshake, and compressing or decompressing message
+//! payloads once the extension is in use. [`DeflateMiddleware`] runs the
+//! negotiation as part of the connection middleware chain.
+//!
+//! The server always compresses with the full 32 KiB window, so offers that
+//! ask it to use a smaller one are declined.
+//!
+//! ## Usage Example
+//!
+//! ```
+//! use reinhardt_websockets::deflate::DeflateConfig;
+//!
+//! let config = DeflateConfig::default()
+//!     .with_client_max_window_bits(12)
+//!     .with_threshold(256);
+//!
+//! // Offer sent by the client in `Sec-WebSocket-Extensions`
+//! let offer = "permessage-deflate; client_max_window_bits";
+//! let mut deflate = config.negotiate(offer).expect("offer accepted");
+//!
+//! // Value for the `Sec-WebSocket-Extensions` response header
+//! assert_eq!(
+//!     deflate.response_header(),
+//!     "permessage-deflate; client_max_window_bits=12"
+//! );
+//!
+//! let payload = br#"{"event":"update","items":[1,2,3]}"#.repeat(20);
+//! let compressed = deflate.compress(&payload).unwrap();
+//! assert!(compressed.len() < payload.len());
+//! assert_eq!(deflate.decompress(&compressed).unwrap(), payload);
+//! ```
+
+use crate::connection::WebSocketConnection;
+use crate::middleware::{ConnectionContext, ConnectionMiddleware, MiddlewareResult};
+use crate::{WebSocketError, WebSocketResult};
+use async_trait::async_trait;
+use flate2::{Compress, Compression, Decompress, FlushCompress, FlushDecompress, Status};
+use std::sync::Arc;
+
+/// Extension token used in `Sec-WebSocket-Extensions`
+pub const EXTENSION_NAME: &str = "permessage-deflate";
+
+/// Smallest window clients are asked to compress with
+const MIN_WINDOW_BITS: u8 = 9;
+
+/// Largest LZ77 window allowed by RFC 7692
+const MAX_WINDOW_BITS: u8 = 15;
+
+/// Request header carrying the client's extension offers
+pub const EXTENSIONS_HEADER: &str = "Sec-WebSocket-Extensions";
+
+/// Empty stored block that ends every sync-flushed message
+const DEFLATE_TAIL: [u8; 4] = [0x00, 0x00, 0xff, 0xff];
+
+/// Server-side permessage-deflate configuration
+#[derive(Debug, Clone)]
+pub struct DeflateConfig {
+	/// Largest window (base-2 logarithm, 9-15) clients may compress with
+	pub client_max_window_bits: u8,
+	/// Reset the compressor after every outgoing message
+	pub server_no_context_takeover: bool,
+	/// Ask clients to reset their compressor after every message
+	pub client_no_context_takeover: bool,
+	/// Payloads smaller than this many bytes are sent uncompressed
+	pub threshold: usize,
+	/// Compression level (0-9)
+	pub level: u32,
+	/// Largest decompressed message size in bytes
+	pub max_message_size: usize,
+}
+
+impl Default for DeflateConfig {
+	/// Creates a default permessage-deflate configuration.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::deflate::DeflateConfig;
+	///
+	/// let config = DeflateConfig::default();
+	/// assert_eq!(config.client_max_window_bits, 15);
+	/// assert!(!config.server_no_context_takeover);
+	/// assert_eq!(config.threshold, 1024);
+	/// assert_eq!(config.level, 6);
+	/// assert_eq!(config.max_message_size, 16 * 1024 * 1024);
+	/// ```
+	fn default() -> Self {
+		Self {
+			client_max_window_bits: MAX_WINDOW_BITS,
+			server_no_context_takeover: false,
+			client_no_context_takeover: false,
+			threshold: 1024,
+			level: 6,
+			max_message_size: 16 * 1024 * 1024,
+		}
+	}
+}
+
+impl DeflateConfig {
+	/// Sets the largest window clients may compress with.
+	///
+	/// Values are clamped to 9-15. The limit is only sent to clients that
+	/// announce support for `client_max_window_bits`.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::deflate::DeflateConfig;
+	///
+	/// let config = DeflateConfig::default().with_client_max_window_bits(12);
+	/// assert_eq!(config.client_max_window_bits, 12);
+	/// ```
+	pub fn with_client_max_window_bits(mut self, bits: u8) -> Self {
+		self.client_max_window_bits = bits.clamp(MIN_WINDOW_BITS, MAX_WINDOW_BITS);
+		self
+	}
+
+	/// Sets whether the compressor is reset after every outgoing message.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::deflate::DeflateConfig;
+	///
+	/// let config = DeflateConfig::default().with_server_no_context_takeover(true);
+	/// assert!(config.server_no_context_takeover);
+	/// ```
+	pub fn with_server_no_context_takeover(mut self, enabled: bool) -> Self {
+		self.server_no_context_takeover = enabled;
+		self
+	}
+
+	/// Sets whether clients are asked to reset their compressor after every message.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::deflate::DeflateConfig;
+	///
+	/// let config = DeflateConfig::default().with_client_no_context_takeover(true);
+	/// assert!(config.client_no_context_takeover);
+	/// ```
+	pub fn with_client_no_context_takeover(mut self, enabled: bool) -> Self {
+		self.client_no_context_takeover = enabled;
+		self
+	}
+
+	/// Sets the payload size below which messages are sent uncompressed.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::deflate::DeflateConfig;
+	///
+	/// let config = DeflateConfig::default().with_threshold(128);
+	/// assert_eq!(config.threshold, 128);
+	/// ```
+	pub fn with_threshold(mut self, threshold: usize) -> Self {
+		self.threshold = threshold;
+		self
+	}
+
+	/// Sets the compression level (0-9, clamped).
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::deflate::DeflateConfig;
+	///
+	/// let config = DeflateConfig::default().with_level(9);
+	/// assert_eq!(config.level, 9);
+	/// ```
+	pub fn with_level(mut self, level: u32) -> Self {
+		self.level = level.min(9);
+		self
+	}
+
+	/// Sets the largest size a compressed message may inflate to.
+	///
+	/// Messages growing past the limit are rejected, which keeps small
+	/// compressed frames from expanding into huge allocations.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::deflate::DeflateConfig;
+	///
+	/// let config = DeflateConfig::default().with_max_message_size(64 * 1024);
+	/// assert_eq!(config.max_message_size, 64 * 1024);
+	/// ```
+	pub fn with_max_message_size(mut self, max_message_size: usize) -> Self {
+		self.max_message_size = max_message_size;
+		self
+	}
+
+	/// Negotiates the extension from a `Sec-WebSocket-Extensions` request header.
+	///
+	/// The first acceptable `permessage-deflate` offer is used. Offers with
+	/// unknown or duplicate parameters, or asking for a server window smaller
+	/// than 15 bits, are declined. Returns `None` when no offer is acceptable,
+	/// in which case the connection proceeds without compression.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::deflate::DeflateConfig;
+	///
+	/// let config = DeflateConfig::default();
+	///
+	/// let deflate = config
+	///     .negotiate("permessage-deflate; server_no_context_takeover")
+	///     .unwrap();
+	/// assert!(deflate.params().server_no_context_takeover);
+	///
+	/// assert!(config.negotiate("x-webkit-deflate-frame").is_none());
+	/// ```
+	pub fn negotiate(&self, offer: &str) -> Option<PerMessageDeflate> {
+		offer
+			.split(',')
+			.find_map(|extension| self.accept_offer(extension))
+			.map(|params| PerMessageDeflate::new(params, self))
+	}
+
+	fn accept_offer(&self, extension: &str) -> Option<DeflateParams> {
+		let mut parts = extension.split(';').map(str::trim);
+		if !parts.next()?.eq_ignore_ascii_case(EXTENSION_NAME) {
+			return None;
+		}
+
+		let mut params = DeflateParams {
+			server_no_context_takeover: self.server_no_context_takeover,
+			client_no_context_takeover: self.client_no_context_takeover,
+			client_max_window_bits: MAX_WINDOW_BITS,
+		};
+		let mut seen = Vec::new();
+
+		for part in parts {
+			let (name, value) = match part.split_once('=') {
+				Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+				None => (part, None),
+			};
+			if seen.contains(&name) {
+				return None;
+			}
+			seen.push(name);
+
+			match (name, value) {
+				("server_no_context_takeover", None) => params.server_no_context_takeover = true,
+				("client_no_context_takeover", None) => params.client_no_context_takeover = true,
+				// The compressor always uses the full window
+				("server_max_window_bits", Some(value)) => {
+					if parse_window_bits(value)? < MAX_WINDOW_BITS {
+						return None;
+					}
+				}
+				("client_max_window_bits", value) => {
+					let bits = match value {
+						Some(value) => parse_window_bits(value)?,
+						None => MAX_WINDOW_BITS,
+					};
+					params.client_max_window_bits = bits.min(self.client_max_window_bits);
+				}
+				_ => return None,
+			}
+		}
+
+		Some(params)
+	}
+}
+
+/// Parses a window size parameter, which RFC 7692 limits to 8-15
+fn parse_window_bits(value: &str) -> Option<u8> {
+	value
+		.parse::<u8>()
+		.ok()
+		.filter(|bits| (8..=MAX_WINDOW_BITS).contains(bits))
+}
+
+/// Negotiated permessage-deflate parameters
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeflateParams {
+	/// The server resets its compressor after every message
+	pub server_no_context_takeover: bool,
+	/// The client resets its compressor after every message
+	pub client_no_context_takeover: bool,
+	/// Window size the client compresses with
+	pub client_max_window_bits: u8,
+}
+
+impl DeflateParams {
+	/// Formats the parameters as a `Sec-WebSocket-Extensions` response value.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::deflate::DeflateParams;
+	///
+	/// let params = DeflateParams {
+	///     server_no_context_takeover: true,
+	///     client_no_context_takeover: false,
+	///     client_max_window_bits: 10,
+	/// };
+	/// assert_eq!(
+	///     params.to_header(),
+	///     "permessage-deflate; server_no_context_takeover; client_max_window_bits=10"
+	/// );
+	/// ```
+	pub fn to_header(&self) -> String {
+		let mut header = EXTENSION_NAME.to_string();
+		if self.server_no_context_takeover {
+			header.push_str("; server_no_context_takeover");
+		}
+		if self.client_no_context_takeover {
+			header.push_str("; client_no_context_takeover");
+		}
+		if self.client_max_window_bits < MAX_WINDOW_BITS {
+			header.push_str(&format!(
+				"; client_max_window_bits={}",
+				self.client_max_window_bits
+			));
+		}
+		header
+	}
+}
+
+/// Compression state of a connection using permessage-deflate
+///
+/// Payloads must be compressed and decompressed in the order messages are
+/// sent and received, since each message may refer back to earlier ones
+/// unless context takeover is disabled.
+pub struct PerMessageDeflate {
+	params: DeflateParams,
+	threshold: usize,
+	max_message_size: usize,
+	compressor: Compress,
+	decompressor: Decompress,
+}
+
+impl PerMessageDeflate {
+	/// Creates the compression state for negotiated parameters.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::deflate::{DeflateConfig, DeflateParams, PerMessageDeflate};
+	///
+	/// let params = DeflateParams {
+	///     server_no_context_takeover: false,
+	///     client_no_context_takeover: false,
+	///     client_max_window_bits: 15,
+	/// };
+	/// let deflate = PerMessageDeflate::new(params, &DeflateConfig::default());
+	/// assert_eq!(deflate.params(), &params);
+	/// ```
+	pub fn new(params: DeflateParams, config: &DeflateConfig) -> Self {
+		// Inflating with a larger window than the client used is always safe,
+		// so both directions use the full window
+		Self {
+			params,
+			threshold: config.threshold,
+			max_message_size: config.max_message_size,
+			compressor: Compress::new(Compression::new(config.level.min(9)), false),
+			decompressor: Decompress::new(false),
+		}
+	}
+
+	/// Gets the negotiated parameters.
+	pub fn params(&self) -> &DeflateParams {
+		&self.params
+	}
+
+	/// Gets the `Sec-WebSocket-Extensions` value for the handshake response.
+	pub fn response_header(&self) -> String {
+		self.params.to_header()
+	}
+
+	/// Checks whether a payload is large enough to be worth compressing.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::deflate::DeflateConfig;
+	///
+	/// let config = DeflateConfig::default().with_threshold(64);
+	/// let deflate = config.negotiate("permessage-deflate").unwrap();
+	///
+	/// assert!(!deflate.should_compress(b"ping"));
+	/// assert!(deflate.should_compress(&[b'a'; 64]));
+	/// ```
+	pub fn should_compress(&self, payload: &[u8]) -> bool {
+		payload.len() >= self.threshold
+	}
+
+	/// Compresses an outgoing message payload.
+	///
+	/// The result is the payload of a frame sent with the RSV1 bit set.
+	pub fn compress(&mut self, payload: &[u8]) -> WebSocketResult<Vec<u8>> {
+		let mut output = Vec::with_capacity(payload.len() / 2 + 64);
+		let start = self.compressor.total_in();
+
+		loop {
+			let consumed = (self.compressor.total_in() - start) as usize;
+			if output.capacity() - output.len() < 64 {
+				output.reserve(output.capacity().max(64));
+			}
+			self.compressor
+				.compress_vec(&payload[consumed..], &mut output, FlushCompress::Sync)
+				.map_err(|e| {
+					WebSocketError::Protocol(format!("Deflate compression failed: {}", e))
+				})?;
+
+			let consumed = (self.compressor.total_in() - start) as usize;
+			// A sync flush is complete once all input is consumed and the
+			// output buffer was not filled
+			if consumed == payload.len() && output.len() < output.capacity() {
+				break;
+			}
+		}
+
+		if output.ends_with(&DEFLATE_TAIL) {
+			output.truncate(output.len() - DEFLATE_TAIL.len());
+		}
+		if self.params.server_no_context_takeover {
+			self.compressor.reset();
+		}
+		Ok(output)
+	}
+
+	/// Decompresses the payload of an incoming message sent with RSV1 set.
+	///
+	/// Fails once the message inflates past the configured
+	/// [`max_message_size`](DeflateConfig::max_message_size); the connection
+	/// should then be closed, since the compression context is lost.
+	pub fn decompress(&mut self, payload: &[u8]) -> WebSocketResult<Vec<u8>> {
+		let mut input = Vec::with_capacity(payload.len() + DEFLATE_TAIL.len());
+		input.extend_from_slice(payload);
+		input.extend_from_slice(&DEFLATE_TAIL);
+
+		let limit = self.max_message_size;
+		let mut output = Vec::with_capacity((payload.len() * 4 + 64).min(limit + 1));
+		let start = self.decompressor.total_in();
+
+		loop {
+			let consumed = (self.decompressor.total_in() - start) as usize;
+			if output.len() > limit {
+				return Err(WebSocketError::Protocol(format!(
+					"Decompressed message exceeds {} bytes",
+					limit
+				)));
+			}
+			if output.capacity() - output.len() < 64 {
+				// Grow at most one byte past the limit so overflow is detected
+				// without allocating for the whole inflated message
+				let additional = output.capacity().max(64).min(limit + 1 - output.len());
+				output.reserve(additional.max(1));
+			}
+			let status = self
+				.decompressor
+				.decompress_vec(&input[consumed..], &mut output, FlushDecompress::Sync)
+				.map_err(|e| {
+					WebSocketError::Protocol(format!("Deflate decompression failed: {}", e))
+				})?;
+
+			let consumed = (self.decompressor.total_in() - start) as usize;
+			if status == Status::StreamEnd
+				|| (consumed == input.len() && output.len() < output.capacity())
+			{
+				break;
+			}
+		}
+
+		if self.params.client_no_context_takeover {
+			self.decompressor.reset(false);
+		}
+		Ok(output)
+	}
+}
+
+/// Connection middleware negotiating permessage-deflate from the upgrade request
+///
+/// Reads the client's offers from the `Sec-WebSocket-Extensions` header and
+/// stores the accepted extension in [`ConnectionContext::deflate`]. The
+/// server sends [`PerMessageDeflate::response_header`] back in the handshake
+/// response and attaches the extension to the connection with
+/// [`WebSocketConnection::with_deflate`]. Connections whose offers are all
+/// declined proceed uncompressed.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_websockets::WebSocketConnection;
+/// use reinhardt_websockets::deflate::{DeflateConfig, DeflateMiddleware};
+/// use reinhardt_websockets::middleware::{ConnectionContext, MiddlewareChain};
+/// use tokio::sync::mpsc;
+///
+/// # tokio_test::block_on(async {
+/// let mut chain = MiddlewareChain::new();
+/// chain.add_connection_middleware(Box::new(DeflateMiddleware::new(DeflateConfig::default())));
+///
+/// let mut context = ConnectionContext::new("127.0.0.1".to_string()).with_header(
+///     "Sec-WebSocket-Extensions".to_string(),
+///     "permessage-deflate; client_max_window_bits".to_string(),
+/// );
+/// chain.process_connect(&mut context).await.unwrap();
+///
+/// let deflate = context.deflate.take().unwrap();
+/// assert_eq!(deflate.response_header(), "permessage-deflate");
+///
+/// let (tx, _rx) = mpsc::unbounded_channel();
+/// let conn = WebSocketConnection::new("conn_1".to_string(), tx).with_deflate(deflate);
+/// assert!(conn.deflate_params().is_some());
+/// # });
+/// ```
+pub struct DeflateMiddleware {
+	config: DeflateConfig,
+}
+
+impl DeflateMiddleware {
+	/// Create a middleware negotiating with the given configuration
+	pub fn new(config: DeflateConfig) -> Self {
+		Self { config }
+	}
+}
+
+#[async_trait]
+impl ConnectionMiddleware for DeflateMiddleware {
+	async fn on_connect(&self, context: &mut ConnectionContext) -> MiddlewareResult<()> {
+		context.deflate = context
+			.header(EXTENSIONS_HEADER)
+			.and_then(|offer| self.config.negotiate(offer));
+		Ok(())
+	}
+
+	async fn on_disconnect(&self, _connection: &Arc<WebSocketConnection>) -> MiddlewareResult<()> {
+		Ok(())
+	}
+}
+
+impl std::fmt::Debug for PerMessageDeflate {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("PerMessageDeflate")
+			.field("params", &self.params)
+			.field("threshold", &self.threshold)
+			.field("max_message_size", &self.max_message_size)
+			.finish_non_exhaustive()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rstest::rstest;
+
+	fn json_payload() -> Vec<u8> {
+		br#"{"type":"update","id":42,"fields":{"status":"active","count":7}}"#.repeat(30)
+	}
+
+	#[rstest]
+	#[case("permessage-deflate", "permessage-deflate")]
+	#[case("permessage-deflate; client_max_window_bits", "permessage-deflate")]
+	#[case(
+		"permessage-deflate; client_max_window_bits=10",
+		"permessage-deflate; client_max_window_bits=10"
+	)]
+	#[case(
+		"permessage-deflate; server_max_window_bits=15; client_no_context_takeover",
+		"permessage-deflate; client_no_context_takeover"
+	)]
+	#[case(
+		"permessage-deflate; server_max_window_bits=12, permessage-deflate",
+		"permessage-deflate"
+	)]
+	#[case(
+		"x-webkit-deflate-frame, permessage-deflate; server_max_window_bits=\"15\"",
+		"permessage-deflate"
+	)]
+	fn test_negotiate_accepts_offer(#[case] offer: &str, #[case] expected: &str) {
+		let deflate = DeflateConfig::default().negotiate(offer).unwrap();
+		assert_eq!(deflate.response_header(), expected);
+	}
+
+	#[rstest]
+	#[case("")]
+	#[case("x-webkit-deflate-frame")]
+	#[case("permessage-deflate; unknown_param")]
+	#[case("permessage-deflate; server_no_context_takeover; server_no_context_takeover")]
+	#[case("permessage-deflate; server_max_window_bits=16")]
+	#[case("permessage-deflate; server_max_window_bits=10")]
+	#[case("permessage-deflate; server_max_window_bits")]
+	#[case("permessage-deflate; server_no_context_takeover=1")]
+	fn test_negotiate_declines_offer(#[case] offer: &str) {
+		assert!(DeflateConfig::default().negotiate(offer).is_none());
+	}
+
+	#[rstest]
+	fn test_configured_limits_are_applied() {
+		let config = DeflateConfig::default()
+			.with_client_max_window_bits(12)
+			.with_server_no_context_takeover(true);
+
+		let deflate = config
+			.negotiate("permessage-deflate; client_max_window_bits")
+			.unwrap();
+
+		assert_eq!(
+			deflate.params(),
+			&DeflateParams {
+				server_no_context_takeover: true,
+				client_no_context_takeover: false,
+				client_max_window_bits: 12,
+			}
+		);
+	}
+
+	#[rstest]
+	fn test_decompress_rfc_example() {
+		// "Hello" compressed in a single message, from RFC 7692 section 7.2.3.1
+		let mut deflate = DeflateConfig::default()
+			.negotiate("permessage-deflate")
+			.unwrap();
+
+		let payload = deflate
+			.decompress(&[0xf2, 0x48, 0xcd, 0xc9, 0xc9, 0x07, 0x00])
+			.unwrap();
+
+		assert_eq!(payload, b"Hello");
+	}
+
+	#[rstest]
+	#[case("permessage-deflate")]
+	#[case("permessage-deflate; server_no_context_takeover; client_no_context_takeover")]
+	#[case("permessage-deflate; client_max_window_bits=9")]
+	fn test_round_trip_over_several_messages(#[case] offer: &str) {
+		let config = DeflateConfig::default();
+		let mut server = config.negotiate(offer).unwrap();
+		// Acts as the peer decoding what the server sends
+		let mut peer = PerMessageDeflate::new(
+			DeflateParams {
+				client_no_context_takeover: server.params().server_no_context_takeover,
+				..*server.params()
+			},
+			&config,
+		);
+
+		for _ in 0..3 {
+			let payload = json_payload();
+			let compressed = server.compress(&payload).unwrap();
+
+			assert!(compressed.len() < payload.len() / 4);
+			assert!(!compressed.ends_with(&DEFLATE_TAIL));
+			assert_eq!(peer.decompress(&compressed).unwrap(), payload);
+		}
+	}
+
+	#[rstest]
+	fn test_context_takeover_shrinks_repeated_messages() {
+		let mut deflate = DeflateConfig::default()
+			.negotiate("permessage-deflate")
+			.unwrap();
+		let payload = json_payload();
+
+		let first = deflate.compress(&payload).unwrap();
+		let second = deflate.compress(&payload).unwrap();
+
+		assert!(second.len() < first.len());
+	}
+
+	#[rstest]
+	fn test_large_incompressible_payload_round_trips() {
+		let mut server = DeflateConfig::default()
+			.negotiate("permessage-deflate")
+			.unwrap();
+		let mut peer = DeflateConfig::default()
+			.negotiate("permessage-deflate")
+			.unwrap();
+		let payload: Vec<u8> = (0..200_000u32)
+			.map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+			.collect();
+
+		let compressed = server.compress(&payload).unwrap();
+
+		assert_eq!(peer.decompress(&compressed).unwrap(), payload);
+	}
+
+	#[rstest]
+	fn test_decompress_invalid_payload_fails() {
+		let mut deflate = DeflateConfig::default()
+			.negotiate("permessage-deflate")
+			.unwrap();
+
+		let result = deflate.decompress(&[0xff, 0xff, 0xff, 0xff]);
+
+		assert!(result.is_err());
+	}
+
+	#[rstest]
+	fn test_decompress_rejects_messages_over_limit() {
+		let config = DeflateConfig::default().with_max_message_size(4096);
+		let mut server = config.negotiate("permessage-deflate").unwrap();
+		let mut peer = DeflateConfig::default()
+			.negotiate("permessage-deflate")
+			.unwrap();
+		let bomb = peer.compress(&vec![0u8; 1024 * 1024]).unwrap();
+
+		let result = server.decompress(&bomb);
+
+		assert!(bomb.len() < 4096);
+		assert!(result.is_err());
+	}
+
+	#[rstest]
+	fn test_decompress_accepts_messages_at_limit() {
+		let config = DeflateConfig::default().with_max_message_size(4096);
+		let mut server = config.negotiate("permessage-deflate").unwrap();
+		let mut peer = DeflateConfig::default()
+			.negotiate("permessage-deflate")
+			.unwrap();
+		let compressed = peer.compress(&[b'a'; 4096]).unwrap();
+
+		assert_eq!(server.decompress(&compressed).unwrap(), vec![b'a'; 4096]);
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_middleware_negotiates_from_extensions_header() {
+		let middleware = DeflateMiddleware::new(DeflateConfig::default());
+		let mut offered = ConnectionContext::new("127.0.0.1".to_string()).with_header(
+			"sec-websocket-extensions".to_string(),
+			"permessage-deflate; client_no_context_takeover".to_string(),
+		);
+		let mut plain = ConnectionContext::new("127.0.0.1".to_string());
+
+		middleware.on_connect(&mut offered).await.unwrap();
+		middleware.on_connect(&mut plain).await.unwrap();
+
+		assert_eq!(
+			offered.deflate.unwrap().response_header(),
+			"permessage-deflate; client_no_context_takeover"
+		);
+		assert!(plain.deflate.is_none());
+	}
+}
@@ -1,7 +1,22 @@
 //! WebSocket handler
+//!
+//! Besides the [`WebSocketHandler`] trait, this module provides
+//! [`HeartbeatMonitor`], which keeps track of live connections, sends
+//! server-initiated pings and reaps connections that stop answering or go
+//! idle, so that dead peers do not linger in rooms. Its
+//! [`run`](HeartbeatMonitor::run) method is the receive loop of a
+//! connection: it feeds every incoming frame to the monitor before
+//! dispatching it to the handler.
 
 use crate::connection::Message;
+use crate::connection::WebSocketConnection;
 use crate::connection::WebSocketResult;
+use crate::room::RoomManager;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{Notify, RwLock, mpsc};
+use tokio::task::JoinHandle;
 
 /// WebSocket handler trait
 pub trait WebSocketHandler: Send + Sync {
@@ -17,3 +32,605 @@ pub trait WebSocketHandler: Send + Sync {
 	/// Handle connection close
 	fn on_disconnect(&self) -> impl std::future::Future<Output = WebSocketResult<()>> + Send;
 }
+
+/// Heartbeat configuration
+#[derive(Debug, Clone)]
+pub struct HeartbeatConfig {
+	/// Interval between server-initiated pings
+	pub ping_interval: Duration,
+	/// Time a peer has to answer a ping before it is considered dead
+	pub pong_timeout: Duration,
+	/// Time without incoming text or binary frames after which a connection
+	/// is closed (None to keep idle connections open as long as they answer pings)
+	pub idle_timeout: Option<Duration>,
+}
+
+impl Default for HeartbeatConfig {
+	/// Creates a default heartbeat configuration.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::handler::HeartbeatConfig;
+	/// use std::time::Duration;
+	///
+	/// let config = HeartbeatConfig::default();
+	/// assert_eq!(config.ping_interval, Duration::from_secs(30));
+	/// assert_eq!(config.pong_timeout, Duration::from_secs(10));
+	/// assert_eq!(config.idle_timeout, None);
+	/// ```
+	fn default() -> Self {
+		Self {
+			ping_interval: Duration::from_secs(30),
+			pong_timeout: Duration::from_secs(10),
+			idle_timeout: None,
+		}
+	}
+}
+
+impl HeartbeatConfig {
+	/// Set the interval between pings
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::handler::HeartbeatConfig;
+	/// use std::time::Duration;
+	///
+	/// let config = HeartbeatConfig::default().with_ping_interval(Duration::from_secs(15));
+	/// assert_eq!(config.ping_interval, Duration::from_secs(15));
+	/// ```
+	pub fn with_ping_interval(mut self, interval: Duration) -> Self {
+		self.ping_interval = interval;
+		self
+	}
+
+	/// Set how long a peer has to answer a ping
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::handler::HeartbeatConfig;
+	/// use std::time::Duration;
+	///
+	/// let config = HeartbeatConfig::default().with_pong_timeout(Duration::from_secs(5));
+	/// assert_eq!(config.pong_timeout, Duration::from_secs(5));
+	/// ```
+	pub fn with_pong_timeout(mut self, timeout: Duration) -> Self {
+		self.pong_timeout = timeout;
+		self
+	}
+
+	/// Set the idle timeout
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::handler::HeartbeatConfig;
+	/// use std::time::Duration;
+	///
+	/// let config = HeartbeatConfig::default().with_idle_timeout(Duration::from_secs(300));
+	/// assert_eq!(config.idle_timeout, Some(Duration::from_secs(300)));
+	/// ```
+	pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+		self.idle_timeout = Some(timeout);
+		self
+	}
+}
+
+/// Heartbeat state of a single connection
+struct HeartbeatEntry<H> {
+	connection: Arc<WebSocketConnection>,
+	handler: Arc<H>,
+	last_activity: Instant,
+	last_ping: Instant,
+	awaiting_pong: Option<Instant>,
+	/// Wakes the connection's receive loop once the connection is reaped
+	reaped: Arc<Notify>,
+}
+
+/// Ping scheduler and dead connection reaper
+///
+/// Connections are registered together with their handler, usually by
+/// running their receive loop with [`run`](Self::run), which reports every
+/// incoming frame through [`observe`](Self::observe). [`tick`](Self::tick)
+/// (or the task started by [`spawn`](Self::spawn)) sends pings when they are
+/// due and reaps every connection that either missed a pong or exceeded the
+/// idle timeout. Reaping closes the connection, removes it from all rooms of
+/// the attached [`RoomManager`] and fires the handler's
+/// [`on_disconnect`](WebSocketHandler::on_disconnect) hook.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_websockets::handler::{HeartbeatConfig, HeartbeatMonitor, WebSocketHandler};
+/// use reinhardt_websockets::room::RoomManager;
+/// use reinhardt_websockets::{Message, WebSocketConnection, WebSocketResult};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+/// use tokio::sync::mpsc;
+///
+/// struct Handler;
+///
+/// impl WebSocketHandler for Handler {
+///     async fn on_message(&self, _message: Message) -> WebSocketResult<()> {
+///         Ok(())
+///     }
+///     async fn on_connect(&self) -> WebSocketResult<()> {
+///         Ok(())
+///     }
+///     async fn on_disconnect(&self) -> WebSocketResult<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// # tokio_test::block_on(async {
+/// let rooms = Arc::new(RoomManager::new());
+/// let config = HeartbeatConfig::default().with_ping_interval(Duration::from_secs(15));
+/// let monitor = HeartbeatMonitor::new(config).with_room_manager(rooms);
+///
+/// let (tx, _rx) = mpsc::unbounded_channel();
+/// let conn = Arc::new(WebSocketConnection::new("user1".to_string(), tx));
+/// monitor.register(conn, Arc::new(Handler)).await;
+///
+/// monitor.observe("user1", &Message::Pong).await;
+/// assert!(monitor.tick().await.is_empty());
+/// assert_eq!(monitor.connection_count().await, 1);
+/// # });
+/// ```
+pub struct HeartbeatMonitor<H> {
+	config: HeartbeatConfig,
+	rooms: Option<Arc<RoomManager>>,
+	entries: RwLock<HashMap<String, HeartbeatEntry<H>>>,
+}
+
+impl<H: WebSocketHandler + 'static> HeartbeatMonitor<H> {
+	/// Create a new heartbeat monitor
+	pub fn new(config: HeartbeatConfig) -> Self {
+		Self {
+			config,
+			rooms: None,
+			entries: RwLock::new(HashMap::new()),
+		}
+	}
+
+	/// Remove reaped connections from the rooms of this manager
+	pub fn with_room_manager(mut self, rooms: Arc<RoomManager>) -> Self {
+		self.rooms = Some(rooms);
+		self
+	}
+
+	/// Get the heartbeat configuration
+	pub fn config(&self) -> &HeartbeatConfig {
+		&self.config
+	}
+
+	/// Start monitoring a connection
+	pub async fn register(&self, connection: Arc<WebSocketConnection>, handler: Arc<H>) {
+		self.register_entry(connection, handler).await;
+	}
+
+	async fn register_entry(
+		&self,
+		connection: Arc<WebSocketConnection>,
+		handler: Arc<H>,
+	) -> Arc<Notify> {
+		let now = Instant::now();
+		let reaped = Arc::new(Notify::new());
+		let entry = HeartbeatEntry {
+			connection: connection.clone(),
+			handler,
+			last_activity: now,
+			last_ping: now,
+			awaiting_pong: None,
+			reaped: reaped.clone(),
+		};
+		self.entries
+			.write()
+			.await
+			.insert(connection.id().to_string(), entry);
+		reaped
+	}
+
+	/// Run the receive loop of a connection
+	///
+	/// Registers the connection, calls the handler's
+	/// [`on_connect`](WebSocketHandler::on_connect) hook and then reads
+	/// `incoming` until the peer closes, the stream ends or the connection
+	/// is reaped. Every frame is passed to [`observe`](Self::observe), pings
+	/// are answered with a pong, and text and binary frames go to
+	/// [`on_message`](WebSocketHandler::on_message).
+	///
+	/// When the loop ends for any reason other than reaping, the connection
+	/// is closed, removed from the rooms of the attached [`RoomManager`] and
+	/// [`on_disconnect`](WebSocketHandler::on_disconnect) is called. A
+	/// handler error ends the loop the same way and is returned.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::handler::{HeartbeatConfig, HeartbeatMonitor, WebSocketHandler};
+	/// use reinhardt_websockets::{Message, WebSocketConnection, WebSocketResult};
+	/// use std::sync::Arc;
+	/// use tokio::sync::mpsc;
+	///
+	/// struct Echo(Arc<WebSocketConnection>);
+	///
+	/// impl WebSocketHandler for Echo {
+	///     async fn on_message(&self, message: Message) -> WebSocketResult<()> {
+	///         self.0.send(message).await
+	///     }
+	///     async fn on_connect(&self) -> WebSocketResult<()> {
+	///         Ok(())
+	///     }
+	///     async fn on_disconnect(&self) -> WebSocketResult<()> {
+	///         Ok(())
+	///     }
+	/// }
+	///
+	/// # tokio_test::block_on(async {
+	/// let monitor = HeartbeatMonitor::new(HeartbeatConfig::default());
+	/// let (out_tx, mut out_rx) = mpsc::unbounded_channel();
+	/// let conn = Arc::new(WebSocketConnection::new("user1".to_string(), out_tx));
+	///
+	/// let (in_tx, in_rx) = mpsc::unbounded_channel();
+	/// in_tx.send(Message::text("hello".to_string())).unwrap();
+	/// drop(in_tx);
+	///
+	/// monitor.run(conn.clone(), Arc::new(Echo(conn)), in_rx).await.unwrap();
+	/// assert_eq!(out_rx.recv().await.unwrap(), Message::text("hello".to_string()));
+	/// assert_eq!(monitor.connection_count().await, 0);
+	/// # });
+	/// ```
+	pub async fn run(
+		&self,
+		connection: Arc<WebSocketConnection>,
+		handler: Arc<H>,
+		mut incoming: mpsc::UnboundedReceiver<Message>,
+	) -> WebSocketResult<()> {
+		let connection_id = connection.id().to_string();
+		let reaped = self
+			.register_entry(connection.clone(), handler.clone())
+			.await;
+
+		let result = match handler.on_connect().await {
+			Ok(()) => loop {
+				let message = tokio::select! {
+					message = incoming.recv() => message,
+					// `tick` already closed the connection and ran the hooks
+					() = reaped.notified() => return Ok(()),
+				};
+				let Some(message) = message else {
+					break Ok(());
+				};
+
+				self.observe(&connection_id, &message).await;
+				let handled = match message {
+					Message::Text { .. } | Message::Binary { .. } => {
+						handler.on_message(message).await
+					}
+					Message::Ping => connection.send(Message::Pong).await,
+					Message::Pong => Ok(()),
+					Message::Close { .. } => break Ok(()),
+				};
+				if let Err(e) = handled {
+					break Err(e);
+				}
+			},
+			Err(e) => Err(e),
+		};
+
+		// Reaped while the last frame was being handled
+		if !self.unregister(&connection_id).await {
+			return result;
+		}
+		let _ = connection.close().await;
+		if let Some(rooms) = &self.rooms {
+			rooms.leave_all_rooms(&connection_id).await;
+		}
+		let disconnected = handler.on_disconnect().await;
+		result.and(disconnected)
+	}
+
+	/// Stop monitoring a connection without closing it
+	///
+	/// Returns `true` if the connection was being monitored.
+	pub async fn unregister(&self, connection_id: &str) -> bool {
+		self.entries.write().await.remove(connection_id).is_some()
+	}
+
+	/// Record an incoming frame
+	///
+	/// A pong answers the outstanding ping; text and binary frames count as
+	/// activity for the idle timeout.
+	pub async fn observe(&self, connection_id: &str, message: &Message) {
+		if let Some(entry) = self.entries.write().await.get_mut(connection_id) {
+			match message {
+				Message::Pong => entry.awaiting_pong = None,
+				Message::Text { .. } | Message::Binary { .. } => {
+					entry.last_activity = Instant::now();
+				}
+				Message::Ping | Message::Close { .. } => {}
+			}
+		}
+	}
+
+	/// Get the number of monitored connections
+	pub async fn connection_count(&self) -> usize {
+		self.entries.read().await.len()
+	}
+
+	/// Send due pings and reap dead or idle connections
+	///
+	/// Returns the IDs of the reaped connections.
+	pub async fn tick(&self) -> Vec<String> {
+		self.tick_at(Instant::now()).await
+	}
+
+	/// Run [`tick`](Self::tick) periodically on a background task
+	///
+	/// The task checks twice per ping interval (or pong timeout, whichever
+	/// is shorter) and runs until the returned handle is aborted.
+	pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+		let period = self.config.ping_interval.min(self.config.pong_timeout) / 2;
+		tokio::spawn(async move {
+			let mut interval = tokio::time::interval(period.max(Duration::from_millis(1)));
+			interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+			loop {
+				interval.tick().await;
+				self.tick().await;
+			}
+		})
+	}
+
+	async fn tick_at(&self, now: Instant) -> Vec<String> {
+		let mut reaped = Vec::new();
+		let mut pings = Vec::new();
+		{
+			let mut entries = self.entries.write().await;
+			entries.retain(|id, entry| {
+				let missed_pong = entry
+					.awaiting_pong
+					.is_some_and(|sent| now.duration_since(sent) >= self.config.pong_timeout);
+				let idle = self
+					.config
+					.idle_timeout
+					.is_some_and(|timeout| now.duration_since(entry.last_activity) >= timeout);
+
+				if missed_pong || idle {
+					reaped.push((
+						id.clone(),
+						entry.connection.clone(),
+						entry.handler.clone(),
+						entry.reaped.clone(),
+					));
+					return false;
+				}
+				if entry.awaiting_pong.is_none()
+					&& now.duration_since(entry.last_ping) >= self.config.ping_interval
+				{
+					entry.last_ping = now;
+					entry.awaiting_pong = Some(now);
+					pings.push(entry.connection.clone());
+				}
+				true
+			});
+		}
+
+		for connection in pings {
+			// A failed send means the peer is gone; the missing pong reaps it.
+			let _ = connection.send(Message::Ping).await;
+		}
+
+		let mut ids = Vec::with_capacity(reaped.len());
+		for (id, connection, handler, notify) in reaped {
+			let _ = connection.close().await;
+			if let Some(rooms) = &self.rooms {
+				rooms.leave_all_rooms(&id).await;
+			}
+			let _ = handler.on_disconnect().await;
+			notify.notify_one();
+			ids.push(id);
+		}
+		ids
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::atomic::{AtomicUsize, Ordering};
+	use tokio::sync::mpsc;
+
+	#[derive(Default)]
+	struct CountingHandler {
+		messages: AtomicUsize,
+		disconnects: AtomicUsize,
+	}
+
+	impl WebSocketHandler for CountingHandler {
+		async fn on_message(&self, _message: Message) -> WebSocketResult<()> {
+			self.messages.fetch_add(1, Ordering::SeqCst);
+			Ok(())
+		}
+
+		async fn on_connect(&self) -> WebSocketResult<()> {
+			Ok(())
+		}
+
+		async fn on_disconnect(&self) -> WebSocketResult<()> {
+			self.disconnects.fetch_add(1, Ordering::SeqCst);
+			Ok(())
+		}
+	}
+
+	fn config() -> HeartbeatConfig {
+		HeartbeatConfig::default()
+			.with_ping_interval(Duration::from_secs(30))
+			.with_pong_timeout(Duration::from_secs(10))
+	}
+
+	#[tokio::test]
+	async fn test_heartbeat_pings_and_keeps_responsive_connection() {
+		let monitor = HeartbeatMonitor::new(config());
+		let handler = Arc::new(CountingHandler::default());
+		let (tx, mut rx) = mpsc::unbounded_channel();
+		let conn = Arc::new(WebSocketConnection::new("user1".to_string(), tx));
+		monitor.register(conn.clone(), handler.clone()).await;
+		let start = Instant::now();
+
+		assert!(monitor.tick_at(start).await.is_empty());
+		assert!(rx.try_recv().is_err());
+
+		let reaped = monitor.tick_at(start + Duration::from_secs(31)).await;
+		assert!(reaped.is_empty());
+		assert_eq!(rx.try_recv().unwrap(), Message::Ping);
+
+		monitor.observe("user1", &Message::Pong).await;
+		let reaped = monitor.tick_at(start + Duration::from_secs(45)).await;
+		assert!(reaped.is_empty());
+		assert!(!conn.is_closed().await);
+		assert_eq!(handler.disconnects.load(Ordering::SeqCst), 0);
+	}
+
+	#[tokio::test]
+	async fn test_heartbeat_reaps_connection_missing_pong() {
+		let rooms = Arc::new(RoomManager::new());
+		rooms.create_room("chat".to_string()).await;
+		let monitor = HeartbeatMonitor::new(config()).with_room_manager(rooms.clone());
+		let handler = Arc::new(CountingHandler::default());
+		let (tx, mut rx) = mpsc::unbounded_channel();
+		let conn = Arc::new(WebSocketConnection::new("user1".to_string(), tx));
+		rooms
+			.join_room("chat".to_string(), conn.clone())
+			.await
+			.unwrap();
+		monitor.register(conn.clone(), handler.clone()).await;
+		let start = Instant::now();
+
+		monitor.tick_at(start + Duration::from_secs(30)).await;
+		assert_eq!(rx.try_recv().unwrap(), Message::Ping);
+
+		let reaped = monitor.tick_at(start + Duration::from_secs(40)).await;
+		assert_eq!(reaped, vec!["user1".to_string()]);
+		assert!(conn.is_closed().await);
+		assert!(matches!(rx.try_recv().unwrap(), Message::Close { .. }));
+		assert_eq!(rooms.get_room_size("chat").await, 0);
+		assert_eq!(handler.disconnects.load(Ordering::SeqCst), 1);
+		assert_eq!(monitor.connection_count().await, 0);
+	}
+
+	#[tokio::test]
+	async fn test_heartbeat_reaps_idle_connection() {
+		let monitor = HeartbeatMonitor::new(config().with_idle_timeout(Duration::from_secs(60)));
+		let handler = Arc::new(CountingHandler::default());
+		let (tx, _rx) = mpsc::unbounded_channel();
+		let conn = Arc::new(WebSocketConnection::new("user1".to_string(), tx));
+		monitor.register(conn.clone(), handler.clone()).await;
+		let start = Instant::now();
+
+		monitor.tick_at(start + Duration::from_secs(30)).await;
+		monitor.observe("user1", &Message::Pong).await;
+		assert!(
+			monitor
+				.tick_at(start + Duration::from_secs(59))
+				.await
+				.is_empty()
+		);
+
+		// Answering pings keeps the connection alive but does not count as activity
+		let reaped = monitor.tick_at(start + Duration::from_secs(60)).await;
+		assert_eq!(reaped, vec!["user1".to_string()]);
+		assert!(conn.is_closed().await);
+		assert_eq!(handler.disconnects.load(Ordering::SeqCst), 1);
+	}
+
+	#[tokio::test]
+	async fn test_heartbeat_unregister_stops_monitoring() {
+		let monitor = HeartbeatMonitor::new(config());
+		let handler = Arc::new(CountingHandler::default());
+		let (tx, _rx) = mpsc::unbounded_channel();
+		let conn = Arc::new(WebSocketConnection::new("user1".to_string(), tx));
+		monitor.register(conn.clone(), handler.clone()).await;
+
+		assert!(monitor.unregister("user1").await);
+		assert!(!monitor.unregister("user1").await);
+		let reaped = monitor
+			.tick_at(Instant::now() + Duration::from_secs(3600))
+			.await;
+		assert!(reaped.is_empty());
+		assert!(!conn.is_closed().await);
+		assert_eq!(handler.disconnects.load(Ordering::SeqCst), 0);
+	}
+
+	async fn wait_until_registered(monitor: &HeartbeatMonitor<CountingHandler>) {
+		while monitor.connection_count().await == 0 {
+			tokio::task::yield_now().await;
+		}
+	}
+
+	#[tokio::test]
+	async fn test_run_feeds_frames_to_monitor_and_handler() {
+		let monitor = Arc::new(HeartbeatMonitor::new(config()));
+		let handler = Arc::new(CountingHandler::default());
+		let (out_tx, mut out_rx) = mpsc::unbounded_channel();
+		let conn = Arc::new(WebSocketConnection::new("user1".to_string(), out_tx));
+		let (in_tx, in_rx) = mpsc::unbounded_channel();
+		let task = tokio::spawn({
+			let monitor = monitor.clone();
+			let handler = handler.clone();
+			async move { monitor.run(conn, handler, in_rx).await }
+		});
+		wait_until_registered(&monitor).await;
+		let start = Instant::now();
+
+		monitor.tick_at(start + Duration::from_secs(31)).await;
+		assert_eq!(out_rx.recv().await.unwrap(), Message::Ping);
+		in_tx.send(Message::Pong).unwrap();
+		in_tx.send(Message::Ping).unwrap();
+		in_tx.send(Message::text("hi".to_string())).unwrap();
+		assert_eq!(out_rx.recv().await.unwrap(), Message::Pong);
+		while handler.messages.load(Ordering::SeqCst) == 0 {
+			tokio::task::yield_now().await;
+		}
+
+		// The pong read by the loop answered the ping
+		let reaped = monitor.tick_at(start + Duration::from_secs(45)).await;
+		assert!(reaped.is_empty());
+
+		in_tx
+			.send(Message::Close {
+				code: 1000,
+				reason: String::new(),
+			})
+			.unwrap();
+		task.await.unwrap().unwrap();
+		assert_eq!(monitor.connection_count().await, 0);
+		assert_eq!(handler.disconnects.load(Ordering::SeqCst), 1);
+	}
+
+	#[tokio::test]
+	async fn test_run_ends_when_connection_is_reaped() {
+		let monitor = Arc::new(HeartbeatMonitor::new(config()));
+		let handler = Arc::new(CountingHandler::default());
+		let (out_tx, _out_rx) = mpsc::unbounded_channel();
+		let conn = Arc::new(WebSocketConnection::new("user1".to_string(), out_tx));
+		let (_in_tx, in_rx) = mpsc::unbounded_channel();
+		let task = tokio::spawn({
+			let monitor = monitor.clone();
+			let handler = handler.clone();
+			let conn = conn.clone();
+			async move { monitor.run(conn, handler, in_rx).await }
+		});
+		wait_until_registered(&monitor).await;
+		let start = Instant::now();
+
+		monitor.tick_at(start + Duration::from_secs(30)).await;
+		let reaped = monitor.tick_at(start + Duration::from_secs(40)).await;
+
+		assert_eq!(reaped, vec!["user1".to_string()]);
+		task.await.unwrap().unwrap();
+		assert!(conn.is_closed().await);
+		assert_eq!(handler.disconnects.load(Ordering::SeqCst), 1);
+	}
+}
@@ -3,5 +3,7 @@
 //! This module provides integration helpers for connecting WebSocket functionality
 //! with other parts of the Reinhardt framework.
 
+#[cfg(feature = "auth")]
+pub mod auth;
 #[cfg(feature = "pages-integration")]
 pub mod pages;
@@ -0,0 +1,255 @@
+//! Integration with reinhardt-auth
+//!
+//! Upgrade authenticators backed by reinhardt-auth, for use with
+//! [`AuthMiddleware`](crate::auth::AuthMiddleware):
+//!
+//! - [`JwtAuthenticator`] verifies a JWT sent as `Authorization: Bearer`
+//!   header or `token` query parameter
+//! - [`SessionAuthenticator`] resolves the session cookie set by
+//!   reinhardt-auth's login handler
+//!
+//! ## Usage Example
+//!
+//! ```
+//! use reinhardt_auth::{InMemorySessionStore, JwtAuth};
+//! use reinhardt_websockets::auth::AuthMiddleware;
+//! use reinhardt_websockets::integration::auth::{JwtAuthenticator, SessionAuthenticator};
+//! use reinhardt_websockets::middleware::MiddlewareChain;
+//! use std::sync::Arc;
+//!
+//! let mut chain = MiddlewareChain::new();
+//! chain.add_connection_middleware(Box::new(AuthMiddleware::new(Arc::new(
+//!     JwtAuthenticator::new(JwtAuth::new(b"secret")),
+//! ))));
+//!
+//! // Or, for browser clients sharing the HTTP session:
+//! let store = Arc::new(InMemorySessionStore::new());
+//! let authenticator = SessionAuthenticator::new(store);
+//! ```
+
+use crate::auth::{
+	AuthError, AuthResult, AuthUser, SimpleAuthUser, UpgradeAuthenticator, upgrade_token,
+};
+use crate::middleware::ConnectionContext;
+use async_trait::async_trait;
+use reinhardt_auth::{JwtAuth, SESSION_COOKIE_NAME, SESSION_KEY_USER_ID, SessionStore};
+use std::sync::Arc;
+
+/// Upgrade authenticator verifying JWTs issued by [`JwtAuth`]
+///
+/// The token is read from the `Authorization: Bearer` header, falling back
+/// to the `token` query parameter.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_auth::JwtAuth;
+/// use reinhardt_websockets::auth::UpgradeAuthenticator;
+/// use reinhardt_websockets::integration::auth::JwtAuthenticator;
+/// use reinhardt_websockets::middleware::ConnectionContext;
+///
+/// # tokio_test::block_on(async {
+/// let jwt = JwtAuth::new(b"secret");
+/// let token = jwt.generate_token("42".to_string(), "alice".to_string()).unwrap();
+/// let authenticator = JwtAuthenticator::new(jwt);
+///
+/// let context = ConnectionContext::new("127.0.0.1".to_string())
+///     .with_query_string(&format!("token={}", token));
+/// let user = authenticator.authenticate_upgrade(&context).await.unwrap().unwrap();
+/// assert_eq!(user.id(), "42");
+/// assert_eq!(user.username(), "alice");
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct JwtAuthenticator {
+	jwt: JwtAuth,
+}
+
+impl JwtAuthenticator {
+	/// Create a new JWT authenticator
+	pub fn new(jwt: JwtAuth) -> Self {
+		Self { jwt }
+	}
+}
+
+#[async_trait]
+impl UpgradeAuthenticator for JwtAuthenticator {
+	async fn authenticate_upgrade(
+		&self,
+		context: &ConnectionContext,
+	) -> AuthResult<Option<Box<dyn AuthUser>>> {
+		let Some(token) = upgrade_token(context) else {
+			return Ok(None);
+		};
+		let claims = self
+			.jwt
+			.decode(token)
+			.map_err(|_| AuthError::InvalidCredentials)?;
+		if claims.is_expired() {
+			return Err(AuthError::TokenExpired);
+		}
+
+		Ok(Some(Box::new(SimpleAuthUser::new(
+			claims.sub,
+			claims.username,
+			Vec::new(),
+		))))
+	}
+}
+
+/// Upgrade authenticator resolving reinhardt-auth session cookies
+///
+/// The session ID is read from the `sessionid` cookie and the user ID from
+/// the session data stored at login. The resulting user has no username of
+/// its own, so its ID is used in its place.
+///
+/// # Examples
+///
+/// ```
+/// use reinhardt_auth::{InMemorySessionStore, SESSION_KEY_USER_ID, Session, SessionStore};
+/// use reinhardt_websockets::auth::UpgradeAuthenticator;
+/// use reinhardt_websockets::integration::auth::SessionAuthenticator;
+/// use reinhardt_websockets::middleware::ConnectionContext;
+/// use std::sync::Arc;
+///
+/// # tokio_test::block_on(async {
+/// let store = Arc::new(InMemorySessionStore::new());
+/// let mut session = Session::new();
+/// session.set(SESSION_KEY_USER_ID, serde_json::json!("42"));
+/// store.save(&"abc".to_string(), &session).await;
+///
+/// let authenticator = SessionAuthenticator::new(store);
+/// let context = ConnectionContext::new("127.0.0.1".to_string())
+///     .with_header("Cookie".to_string(), "sessionid=abc".to_string());
+/// let user = authenticator.authenticate_upgrade(&context).await.unwrap().unwrap();
+/// assert_eq!(user.id(), "42");
+/// # });
+/// ```
+pub struct SessionAuthenticator<S> {
+	store: Arc<S>,
+	cookie_name: String,
+}
+
+impl<S: SessionStore> SessionAuthenticator<S> {
+	/// Create a new session authenticator
+	pub fn new(store: Arc<S>) -> Self {
+		Self {
+			store,
+			cookie_name: SESSION_COOKIE_NAME.to_string(),
+		}
+	}
+
+	/// Read the session ID from a cookie other than `sessionid`
+	pub fn with_cookie_name(mut self, name: impl Into<String>) -> Self {
+		self.cookie_name = name.into();
+		self
+	}
+}
+
+#[async_trait]
+impl<S: SessionStore> UpgradeAuthenticator for SessionAuthenticator<S> {
+	async fn authenticate_upgrade(
+		&self,
+		context: &ConnectionContext,
+	) -> AuthResult<Option<Box<dyn AuthUser>>> {
+		let Some(session_id) = context.cookie(&self.cookie_name) else {
+			return Ok(None);
+		};
+		let session = self
+			.store
+			.load(&session_id.to_string())
+			.await
+			.ok_or(AuthError::InvalidCredentials)?;
+		let user_id = match session.get(SESSION_KEY_USER_ID) {
+			Some(serde_json::Value::String(id)) => id.clone(),
+			Some(serde_json::Value::Number(id)) => id.to_string(),
+			_ => return Err(AuthError::InvalidCredentials),
+		};
+
+		Ok(Some(Box::new(SimpleAuthUser::new(
+			user_id.clone(),
+			user_id,
+			Vec::new(),
+		))))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::auth::{AuthMiddleware, TOKEN_QUERY_PARAM};
+	use crate::middleware::ConnectionMiddleware;
+	use chrono::Duration;
+	use reinhardt_auth::{Claims, InMemorySessionStore, Session};
+
+	#[tokio::test]
+	async fn test_jwt_authenticator_prefers_header() {
+		let jwt = JwtAuth::new(b"secret");
+		let token = jwt
+			.generate_token("1".to_string(), "alice".to_string())
+			.unwrap();
+		let authenticator = JwtAuthenticator::new(jwt);
+		let context = ConnectionContext::new("127.0.0.1".to_string())
+			.with_header("Authorization".to_string(), format!("Bearer {}", token))
+			.with_query_string("token=ignored");
+
+		let user = authenticator
+			.authenticate_upgrade(&context)
+			.await
+			.unwrap()
+			.unwrap();
+
+		assert_eq!(user.username(), "alice");
+	}
+
+	#[tokio::test]
+	async fn test_jwt_authenticator_rejects_bad_tokens() {
+		let jwt = JwtAuth::new(b"secret");
+		let expired = jwt
+			.encode(&Claims::new(
+				"1".to_string(),
+				"alice".to_string(),
+				Duration::hours(-2),
+			))
+			.unwrap();
+		let forged = JwtAuth::new(b"other")
+			.generate_token("1".to_string(), "alice".to_string())
+			.unwrap();
+		let authenticator = JwtAuthenticator::new(jwt);
+
+		for token in [expired, forged] {
+			let context = ConnectionContext::new("127.0.0.1".to_string())
+				.with_query_string(&format!("{}={}", TOKEN_QUERY_PARAM, token));
+			assert!(authenticator.authenticate_upgrade(&context).await.is_err());
+		}
+
+		let context = ConnectionContext::new("127.0.0.1".to_string());
+		assert!(
+			authenticator
+				.authenticate_upgrade(&context)
+				.await
+				.unwrap()
+				.is_none()
+		);
+	}
+
+	#[tokio::test]
+	async fn test_session_authenticator_with_middleware() {
+		let store = Arc::new(InMemorySessionStore::new());
+		let mut session = Session::new();
+		session.set(SESSION_KEY_USER_ID, serde_json::json!(7));
+		store.save(&"valid".to_string(), &session).await;
+		let middleware = AuthMiddleware::new(Arc::new(
+			SessionAuthenticator::new(store).with_cookie_name("ws_session"),
+		));
+
+		let mut context = ConnectionContext::new("127.0.0.1".to_string())
+			.with_header("Cookie".to_string(), "ws_session=valid".to_string());
+		middleware.on_connect(&mut context).await.unwrap();
+		assert_eq!(context.user().unwrap().id(), "7");
+
+		let mut context = ConnectionContext::new("127.0.0.1".to_string())
+			.with_header("Cookie".to_string(), "ws_session=unknown".to_string());
+		assert!(middleware.on_connect(&mut context).await.is_err());
+	}
+}
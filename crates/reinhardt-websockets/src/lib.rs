@@ -7,8 +7,12 @@
 //! ## Features
 //!
 //! - **Connection Management**: Robust WebSocket connection handling with lifecycle hooks
+//! - **Backpressure**: Bounded per-connection send queues with configurable overflow policies
+//! - **Heartbeats**: Server-initiated pings with dead and idle connection reaping
 //! - **Room-Based Messaging**: Group connections into rooms for targeted broadcasting
-//! - **Authentication & Authorization**: Token-based auth and permission-based authorization
+//! - **Presence Tracking**: Track who is online in each room with join/leave events
+//! - **Authentication & Authorization**: Token, JWT and session authentication of upgrade
+//!   requests, and permission-based authorization
 //! - **Rate Limiting**: Connection and message rate limiting to prevent abuse
 //! - **Middleware Integration**: Pre-processing and post-processing of connections and messages
 //! - **WebSocket Routing**: URL-based WebSocket endpoint registration
@@ -47,6 +51,11 @@
 //! reinhardt-websockets = { version = "0.1", features = ["compression"] }
 //! ```
 //!
+//! It also provides the `permessage-deflate` extension (RFC 7692) in the
+//! `deflate` module, negotiated from the `Sec-WebSocket-Extensions` header
+//! by `DeflateMiddleware` and attached to a connection with
+//! `WebSocketConnection::with_deflate`.
+//!
 //! ### Automatic Reconnection
 //!
 //! The `reconnection` module provides automatic reconnection with exponential backoff:
@@ -144,16 +153,21 @@
 //! in the WebSocket handshake, allowing the server to authenticate the connection.
 
 pub mod auth;
+pub mod backpressure;
 pub mod channels;
 #[cfg(feature = "compression")]
 pub mod compression;
 pub mod connection;
 pub mod consumers;
+#[cfg(feature = "compression")]
+pub mod deflate;
 pub mod handler;
-#[cfg(feature = "pages-integration")]
+#[cfg(any(feature = "auth", feature = "pages-integration"))]
 pub mod integration;
+pub mod live;
 pub mod metrics;
 pub mod middleware;
+pub mod presence;
 pub mod protocol;
 pub mod reconnection;
 #[cfg(feature = "redis-channel")]
@@ -163,9 +177,11 @@ pub mod routing;
 pub mod throttling;
 
 pub use auth::{
-	AuthError, AuthResult, AuthUser, AuthenticatedConnection, AuthorizationPolicy,
-	PermissionBasedPolicy, SimpleAuthUser, TokenAuthenticator, WebSocketAuthenticator,
+	AuthError, AuthMiddleware, AuthResult, AuthUser, AuthenticatedConnection, AuthorizationPolicy,
+	PermissionBasedPolicy, SimpleAuthUser, TokenAuthenticator, UpgradeAuthenticator,
+	WebSocketAuthenticator,
 };
+pub use backpressure::{OverflowPolicy, SendQueue, SendQueueConfig, SendQueueReceiver};
 pub use channels::{
 	ChannelError, ChannelLayer, ChannelLayerWrapper, ChannelMessage, ChannelResult,
 	InMemoryChannelLayer,
@@ -174,12 +190,17 @@ pub use channels::{
 pub use compression::{CompressionCodec, compress_message, decompress_message};
 pub use connection::{Message, WebSocketConnection, WebSocketError, WebSocketResult};
 pub use consumers::{
-	BroadcastConsumer, ConsumerChain, ConsumerContext, EchoConsumer, JsonConsumer,
-	WebSocketConsumer,
+	BroadcastConsumer, ConsumerChain, ConsumerContext, EchoConsumer, ErrorFrame, JsonConsumer,
+	TypedConsumer, TypedConsumerAdapter, WebSocketConsumer,
 };
-pub use handler::WebSocketHandler;
+#[cfg(feature = "compression")]
+pub use deflate::{DeflateConfig, DeflateMiddleware, DeflateParams, PerMessageDeflate};
+pub use handler::{HeartbeatConfig, HeartbeatMonitor, WebSocketHandler};
+#[cfg(feature = "auth")]
+pub use integration::auth::{JwtAuthenticator, SessionAuthenticator};
 #[cfg(feature = "pages-integration")]
 pub use integration::pages::{PagesAuthUser, PagesAuthenticator};
+pub use live::{LiveFrame, LiveRoomConsumer};
 #[cfg(feature = "metrics")]
 pub use metrics::MetricsExporter;
 pub use metrics::{MetricsCollector, MetricsSnapshot, PeriodicReporter, WebSocketMetrics};
@@ -188,6 +209,7 @@ pub use middleware::{
 	MessageMiddleware, MessageSizeLimitMiddleware, MiddlewareChain, MiddlewareError,
 	MiddlewareResult,
 };
+pub use presence::{PresenceError, PresenceEvent, PresenceInfo, PresenceResult, PresenceTracker};
 pub use protocol::default_websocket_config;
 pub use reconnection::{ReconnectionConfig, ReconnectionStrategy};
 #[cfg(feature = "redis-channel")]
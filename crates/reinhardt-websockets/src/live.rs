@@ -0,0 +1,268 @@
+//! Server side of live signals
+//!
+//! Clients created with `use_live_signal` in `reinhardt-pages` subscribe to a
+//! room by sending `{"type": "subscribe", "room": "<room>"}` each time their
+//! connection opens, and `{"type": "unsubscribe", "room": "<room>"}` before
+//! closing it. [`LiveRoomConsumer`] handles those frames by joining and
+//! leaving rooms of a [`RoomManager`], and [`LiveRoomConsumer::publish`] sends
+//! values to subscribers wrapped in the `{"room": ..., "payload": ...}`
+//! envelope the client expects.
+//!
+//! ## Usage Example
+//!
+//! ```
+//! use reinhardt_websockets::consumers::{ConsumerContext, WebSocketConsumer};
+//! use reinhardt_websockets::live::LiveRoomConsumer;
+//! use reinhardt_websockets::room::RoomManager;
+//! use reinhardt_websockets::{Message, WebSocketConnection};
+//! use std::sync::Arc;
+//! use tokio::sync::mpsc;
+//!
+//! # tokio_test::block_on(async {
+//! let consumer = LiveRoomConsumer::new(Arc::new(RoomManager::new()));
+//!
+//! let (tx, mut rx) = mpsc::unbounded_channel();
+//! let conn = Arc::new(WebSocketConnection::new("client_1".to_string(), tx));
+//! let mut context = ConsumerContext::new(conn);
+//!
+//! let subscribe = Message::text(r#"{"type":"subscribe","room":"dashboard"}"#.to_string());
+//! consumer.on_message(&mut context, subscribe).await.unwrap();
+//!
+//! consumer.publish("dashboard", &serde_json::json!({"active": 3})).await.unwrap();
+//! let received: serde_json::Value = rx.recv().await.unwrap().parse_json().unwrap();
+//! assert_eq!(received, serde_json::json!({"room": "dashboard", "payload": {"active": 3}}));
+//! # });
+//! ```
+
+use crate::connection::{Message, WebSocketError, WebSocketResult};
+use crate::consumers::{ConsumerContext, WebSocketConsumer};
+use crate::room::{RoomError, RoomManager};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Control frame sent by live signal clients
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum LiveFrame {
+	/// Start receiving values published to a room
+	Subscribe {
+		/// Room to join
+		room: String,
+	},
+	/// Stop receiving values published to a room
+	Unsubscribe {
+		/// Room to leave
+		room: String,
+	},
+}
+
+impl LiveFrame {
+	/// Parses a text frame, returning `None` for anything that is not a
+	/// subscription control frame
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::live::LiveFrame;
+	///
+	/// assert_eq!(
+	///     LiveFrame::parse(r#"{"type":"subscribe","room":"dash"}"#),
+	///     Some(LiveFrame::Subscribe { room: "dash".to_string() })
+	/// );
+	/// assert_eq!(LiveFrame::parse(r#"{"type":"chat","text":"hi"}"#), None);
+	/// ```
+	pub fn parse(text: &str) -> Option<Self> {
+		serde_json::from_str(text).ok()
+	}
+}
+
+/// Consumer joining and leaving rooms on live signal subscription frames
+///
+/// Rooms are created on first subscription. Messages other than
+/// subscription frames are ignored, so the consumer can be placed in a
+/// [`ConsumerChain`](crate::consumers::ConsumerChain) with application
+/// consumers. Disconnected clients leave every room they joined.
+pub struct LiveRoomConsumer {
+	rooms: Arc<RoomManager>,
+}
+
+impl LiveRoomConsumer {
+	/// Create a consumer managing subscriptions in `rooms`
+	pub fn new(rooms: Arc<RoomManager>) -> Self {
+		Self { rooms }
+	}
+
+	/// Get the rooms subscriptions are tracked in
+	pub fn rooms(&self) -> &Arc<RoomManager> {
+		&self.rooms
+	}
+
+	/// Send a value to every subscriber of `room`
+	///
+	/// Publishing to a room nobody has subscribed to is a no-op.
+	pub async fn publish<T: Serialize>(&self, room: &str, payload: &T) -> WebSocketResult<()> {
+		let envelope = serde_json::json!({ "room": room, "payload": payload });
+		let message = Message::json(&envelope)?;
+		match self.rooms.broadcast_to_room(room, message).await {
+			Ok(()) | Err(RoomError::RoomNotFound(_)) => Ok(()),
+			Err(RoomError::WebSocket(e)) => Err(e),
+			Err(e) => Err(WebSocketError::Send(e.to_string())),
+		}
+	}
+}
+
+#[async_trait]
+impl WebSocketConsumer for LiveRoomConsumer {
+	async fn on_connect(&self, _context: &mut ConsumerContext) -> WebSocketResult<()> {
+		Ok(())
+	}
+
+	async fn on_message(
+		&self,
+		context: &mut ConsumerContext,
+		message: Message,
+	) -> WebSocketResult<()> {
+		let Message::Text { data } = &message else {
+			return Ok(());
+		};
+		let client_id = context.connection.id().to_string();
+
+		match LiveFrame::parse(data) {
+			Some(LiveFrame::Subscribe { room }) => {
+				let room = self.rooms.get_or_create_room(room).await;
+				// Clients re-send the subscription after every reconnect
+				match room.join(client_id, context.connection.clone()).await {
+					Ok(()) | Err(RoomError::ClientAlreadyExists(_)) => Ok(()),
+					Err(e) => Err(WebSocketError::Connection(e.to_string())),
+				}
+			}
+			Some(LiveFrame::Unsubscribe { room }) => {
+				match self.rooms.leave_room(&room, &client_id).await {
+					Ok(())
+					| Err(RoomError::RoomNotFound(_))
+					| Err(RoomError::ClientNotFound(_)) => Ok(()),
+					Err(e) => Err(WebSocketError::Connection(e.to_string())),
+				}
+			}
+			None => Ok(()),
+		}
+	}
+
+	async fn on_disconnect(&self, context: &mut ConsumerContext) -> WebSocketResult<()> {
+		self.rooms.leave_all_rooms(context.connection.id()).await;
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::WebSocketConnection;
+	use rstest::rstest;
+	use tokio::sync::mpsc;
+
+	fn client(id: &str) -> (ConsumerContext, mpsc::UnboundedReceiver<Message>) {
+		let (tx, rx) = mpsc::unbounded_channel();
+		let conn = Arc::new(WebSocketConnection::new(id.to_string(), tx));
+		(ConsumerContext::new(conn), rx)
+	}
+
+	fn frame(frame: &LiveFrame) -> Message {
+		Message::text(serde_json::to_string(frame).unwrap())
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_subscribe_is_idempotent() {
+		let consumer = LiveRoomConsumer::new(Arc::new(RoomManager::new()));
+		let (mut context, _rx) = client("c1");
+		let subscribe = LiveFrame::Subscribe {
+			room: "dash".to_string(),
+		};
+
+		consumer
+			.on_message(&mut context, frame(&subscribe))
+			.await
+			.unwrap();
+		consumer
+			.on_message(&mut context, frame(&subscribe))
+			.await
+			.unwrap();
+
+		assert_eq!(consumer.rooms().get_room_size("dash").await, 1);
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_unsubscribe_stops_delivery() {
+		let consumer = LiveRoomConsumer::new(Arc::new(RoomManager::new()));
+		let (mut context, mut rx) = client("c1");
+		let room = "dash".to_string();
+
+		consumer
+			.on_message(
+				&mut context,
+				frame(&LiveFrame::Subscribe { room: room.clone() }),
+			)
+			.await
+			.unwrap();
+		consumer
+			.on_message(
+				&mut context,
+				frame(&LiveFrame::Unsubscribe { room: room.clone() }),
+			)
+			.await
+			.unwrap();
+		consumer.publish(&room, &1).await.unwrap();
+
+		assert!(rx.try_recv().is_err());
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_unknown_frames_and_rooms_are_ignored() {
+		let consumer = LiveRoomConsumer::new(Arc::new(RoomManager::new()));
+		let (mut context, _rx) = client("c1");
+
+		consumer
+			.on_message(&mut context, Message::text("not json".to_string()))
+			.await
+			.unwrap();
+		consumer
+			.on_message(
+				&mut context,
+				frame(&LiveFrame::Unsubscribe {
+					room: "missing".to_string(),
+				}),
+			)
+			.await
+			.unwrap();
+		consumer.publish("missing", &1).await.unwrap();
+
+		assert_eq!(consumer.rooms().room_count().await, 0);
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_disconnect_leaves_all_rooms() {
+		let consumer = LiveRoomConsumer::new(Arc::new(RoomManager::new()));
+		let (mut context, _rx) = client("c1");
+		for room in ["a", "b"] {
+			consumer
+				.on_message(
+					&mut context,
+					frame(&LiveFrame::Subscribe {
+						room: room.to_string(),
+					}),
+				)
+				.await
+				.unwrap();
+		}
+
+		consumer.on_disconnect(&mut context).await.unwrap();
+
+		assert_eq!(consumer.rooms().get_room_size("a").await, 0);
+		assert_eq!(consumer.rooms().get_room_size("b").await, 0);
+	}
+}
@@ -3,6 +3,7 @@
 //! This module provides middleware support for WebSocket connections,
 //! allowing pre-processing and post-processing of connections and messages.
 
+use crate::auth::{AuthUser, AuthenticatedConnection};
 use crate::connection::{Message, WebSocketConnection};
 use async_trait::async_trait;
 use std::sync::Arc;
@@ -29,6 +30,13 @@ pub struct ConnectionContext {
 	pub headers: std::collections::HashMap<String, String>,
 	/// Custom metadata
 	pub metadata: std::collections::HashMap<String, String>,
+	/// Query parameters of the upgrade request
+	query: std::collections::HashMap<String, String>,
+	/// User authenticated during the upgrade
+	user: Option<Box<dyn AuthUser>>,
+	/// permessage-deflate negotiated during the upgrade (set by `DeflateMiddleware`)
+	#[cfg(feature = "compression")]
+	pub deflate: Option<crate::deflate::PerMessageDeflate>,
 }
 
 impl ConnectionContext {
@@ -47,6 +55,10 @@ impl ConnectionContext {
 			ip,
 			headers: std::collections::HashMap::new(),
 			metadata: std::collections::HashMap::new(),
+			query: std::collections::HashMap::new(),
+			user: None,
+			#[cfg(feature = "compression")]
+			deflate: None,
 		}
 	}
 
@@ -61,6 +73,108 @@ impl ConnectionContext {
 		self.metadata.insert(key, value);
 		self
 	}
+
+	/// Add the query parameters of an upgrade request URL
+	///
+	/// Malformed query strings are ignored.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::middleware::ConnectionContext;
+	///
+	/// let context = ConnectionContext::new("127.0.0.1".to_string())
+	///     .with_query_string("room=lobby&token=a%2Bb");
+	/// assert_eq!(context.query_param("room"), Some("lobby"));
+	/// assert_eq!(context.query_param("token"), Some("a+b"));
+	/// ```
+	pub fn with_query_string(mut self, query: &str) -> Self {
+		let query = query.strip_prefix('?').unwrap_or(query);
+		if let Ok(pairs) = serde_urlencoded::from_str::<Vec<(String, String)>>(query) {
+			self.query.extend(pairs);
+		}
+		self
+	}
+
+	/// Get the user authenticated during the upgrade, if any
+	pub fn user(&self) -> Option<&dyn AuthUser> {
+		self.user.as_deref()
+	}
+
+	/// Set the user authenticated during the upgrade
+	///
+	/// Called by [`AuthMiddleware`](crate::auth::AuthMiddleware) and by
+	/// custom authentication middleware.
+	pub fn set_user(&mut self, user: Box<dyn AuthUser>) {
+		self.user = Some(user);
+	}
+
+	/// Take the user authenticated during the upgrade out of the context
+	pub fn take_user(&mut self) -> Option<Box<dyn AuthUser>> {
+		self.user.take()
+	}
+
+	/// Get a header value, ignoring the case of the header name
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::middleware::ConnectionContext;
+	///
+	/// let context = ConnectionContext::new("127.0.0.1".to_string())
+	///     .with_header("Authorization".to_string(), "Bearer abc".to_string());
+	/// assert_eq!(context.header("authorization"), Some("Bearer abc"));
+	/// ```
+	pub fn header(&self, name: &str) -> Option<&str> {
+		self.headers
+			.iter()
+			.find(|(key, _)| key.eq_ignore_ascii_case(name))
+			.map(|(_, value)| value.as_str())
+	}
+
+	/// Get a query parameter of the upgrade request
+	pub fn query_param(&self, name: &str) -> Option<&str> {
+		self.query.get(name).map(String::as_str)
+	}
+
+	/// Get the token of an `Authorization: Bearer` header
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::middleware::ConnectionContext;
+	///
+	/// let context = ConnectionContext::new("127.0.0.1".to_string())
+	///     .with_header("Authorization".to_string(), "Bearer abc".to_string());
+	/// assert_eq!(context.bearer_token(), Some("abc"));
+	/// ```
+	pub fn bearer_token(&self) -> Option<&str> {
+		let value = self.header("authorization")?;
+		let (scheme, token) = value.split_once(' ')?;
+		scheme
+			.eq_ignore_ascii_case("bearer")
+			.then(|| token.trim())
+			.filter(|token| !token.is_empty())
+	}
+
+	/// Get a cookie sent with the upgrade request
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::middleware::ConnectionContext;
+	///
+	/// let context = ConnectionContext::new("127.0.0.1".to_string())
+	///     .with_header("Cookie".to_string(), "csrftoken=x; sessionid=abc".to_string());
+	/// assert_eq!(context.cookie("sessionid"), Some("abc"));
+	/// assert_eq!(context.cookie("missing"), None);
+	/// ```
+	pub fn cookie(&self, name: &str) -> Option<&str> {
+		self.header("cookie")?.split(';').find_map(|cookie| {
+			let (key, value) = cookie.split_once('=')?;
+			(key.trim() == name).then(|| value.trim())
+		})
+	}
 }
 
 /// WebSocket connection middleware trait
@@ -376,6 +490,53 @@ impl MiddlewareChain {
 		Ok(())
 	}
 
+	/// Process an upgrade request and authenticate its connection
+	///
+	/// Runs the connection middlewares, then wraps `connection` with the
+	/// user authenticated by [`AuthMiddleware`](crate::auth::AuthMiddleware).
+	/// Returns `None` if the connection was accepted anonymously.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::auth::{AuthMiddleware, SimpleAuthUser, TokenAuthenticator};
+	/// use reinhardt_websockets::middleware::{ConnectionContext, MiddlewareChain};
+	/// use reinhardt_websockets::WebSocketConnection;
+	/// use std::sync::Arc;
+	/// use tokio::sync::mpsc;
+	///
+	/// # tokio_test::block_on(async {
+	/// let authenticator = TokenAuthenticator::new(vec![(
+	///     "valid_token".to_string(),
+	///     SimpleAuthUser::new("user_1".to_string(), "alice".to_string(), vec![]),
+	/// )]);
+	/// let mut chain = MiddlewareChain::new();
+	/// chain.add_connection_middleware(Box::new(
+	///     AuthMiddleware::new(Arc::new(authenticator)).allow_anonymous(),
+	/// ));
+	///
+	/// let (tx, _rx) = mpsc::unbounded_channel();
+	/// let conn = Arc::new(WebSocketConnection::new("conn_1".to_string(), tx));
+	/// let mut context = ConnectionContext::new("127.0.0.1".to_string())
+	///     .with_header("Authorization".to_string(), "Bearer valid_token".to_string());
+	/// let auth_conn = chain.process_upgrade(&mut context, conn.clone()).await.unwrap();
+	/// assert_eq!(auth_conn.unwrap().user().id(), "user_1");
+	///
+	/// let mut anonymous = ConnectionContext::new("127.0.0.1".to_string());
+	/// assert!(chain.process_upgrade(&mut anonymous, conn).await.unwrap().is_none());
+	/// # });
+	/// ```
+	pub async fn process_upgrade(
+		&self,
+		context: &mut ConnectionContext,
+		connection: Arc<WebSocketConnection>,
+	) -> MiddlewareResult<Option<AuthenticatedConnection>> {
+		self.process_connect(context).await?;
+		Ok(context
+			.take_user()
+			.map(|user| AuthenticatedConnection::new(connection, user)))
+	}
+
 	/// Process disconnection through all middlewares
 	pub async fn process_disconnect(
 		&self,
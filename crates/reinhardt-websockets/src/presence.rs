@@ -0,0 +1,1025 @@
+//! Presence tracking for WebSocket rooms
+//!
+//! This module tracks which users are online in each room, along with
+//! per-user metadata such as a display name or status. Members of a room are
+//! notified when users join, leave, or update their metadata, and the
+//! tracker answers presence queries for a room or a user.
+//!
+//! A user is present in a room while at least one of their connections has
+//! joined it, so opening a second tab does not emit a second join event.
+//!
+//! Behind more than one server, the trackers share presence through a
+//! [`ChannelLayer`]: each one publishes its changes and applies the others'
+//! on [`PresenceTracker::sync`], so a user connected to any instance is
+//! present everywhere.
+//!
+//! ## Usage Example
+//!
+//! ```
+//! use reinhardt_websockets::presence::PresenceTracker;
+//! use reinhardt_websockets::room::RoomManager;
+//! use reinhardt_websockets::WebSocketConnection;
+//! use serde_json::json;
+//! use std::sync::Arc;
+//! use tokio::sync::mpsc;
+//!
+//! # tokio_test::block_on(async {
+//! let tracker = PresenceTracker::new(Arc::new(RoomManager::new()));
+//!
+//! let (tx, _rx) = mpsc::unbounded_channel();
+//! let conn = Arc::new(WebSocketConnection::new("conn_1".to_string(), tx));
+//!
+//! tracker
+//!     .join("chat", "alice", conn, json!({"name": "Alice"}))
+//!     .await
+//!     .unwrap();
+//!
+//! assert!(tracker.is_online("chat", "alice").await);
+//! let present = tracker.list("chat").await;
+//! assert_eq!(present[0].metadata["name"], "Alice");
+//! # });
+//! ```
+
+use crate::channels::{ChannelError, ChannelLayer, ChannelMessage};
+use crate::connection::{Message, WebSocketConnection, WebSocketError};
+use crate::room::{RoomError, RoomManager};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+
+/// Error types for presence operations
+#[derive(Debug, thiserror::Error)]
+pub enum PresenceError {
+	#[error("User not present: {0}")]
+	NotPresent(String),
+	#[error("Room error: {0}")]
+	Room(#[from] RoomError),
+	#[error("Channel error: {0}")]
+	Channel(#[from] ChannelError),
+	#[error("WebSocket error: {0}")]
+	WebSocket(#[from] WebSocketError),
+}
+
+pub type PresenceResult<T> = Result<T, PresenceError>;
+
+/// A user's presence in a room
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PresenceInfo {
+	/// User ID
+	pub user_id: String,
+	/// Metadata shared with other members (display name, status, ...)
+	pub metadata: Value,
+	/// When the user's first connection joined the room
+	pub joined_at: DateTime<Utc>,
+	/// Number of the user's connections in the room
+	pub connections: usize,
+}
+
+/// Presence change sent to room members
+///
+/// Events are delivered as JSON text messages tagged by `type`, for
+/// example `{"type":"presence_join","room":"chat","user":{...}}`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PresenceEvent {
+	/// A user came online in the room
+	PresenceJoin { room: String, user: PresenceInfo },
+	/// A user's metadata changed
+	PresenceUpdate { room: String, user: PresenceInfo },
+	/// A user's last connection left the room
+	PresenceLeave { room: String, user_id: String },
+}
+
+impl PresenceEvent {
+	/// Get the room the event belongs to
+	pub fn room(&self) -> &str {
+		match self {
+			Self::PresenceJoin { room, .. }
+			| Self::PresenceUpdate { room, .. }
+			| Self::PresenceLeave { room, .. } => room,
+		}
+	}
+
+	/// Get the user the event is about
+	pub fn user_id(&self) -> &str {
+		match self {
+			Self::PresenceJoin { user, .. } | Self::PresenceUpdate { user, .. } => &user.user_id,
+			Self::PresenceLeave { user_id, .. } => user_id,
+		}
+	}
+}
+
+/// Instance ID used when presence is not shared through a channel layer
+const LOCAL_INSTANCE: &str = "local";
+
+/// A connection keeping a user present: (instance ID, connection ID)
+type ConnectionKey = (String, String);
+
+/// Presence of one user, including the connections keeping them online
+struct PresenceEntry {
+	metadata: Value,
+	joined_at: DateTime<Utc>,
+	connections: BTreeSet<ConnectionKey>,
+}
+
+impl PresenceEntry {
+	fn info(&self, user_id: &str) -> PresenceInfo {
+		PresenceInfo {
+			user_id: user_id.to_string(),
+			metadata: self.metadata.clone(),
+			joined_at: self.joined_at,
+			connections: self.connections.len(),
+		}
+	}
+}
+
+/// Presence change exchanged between instances through the channel layer
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum SyncOp {
+	/// A connection of the sending instance joined a room
+	Join {
+		room: String,
+		user_id: String,
+		connection: String,
+		metadata: Value,
+		joined_at: DateTime<Utc>,
+	},
+	/// A connection of the sending instance left a room
+	Leave {
+		room: String,
+		user_id: String,
+		connection: String,
+	},
+	/// A user's metadata was replaced on the sending instance
+	Update {
+		room: String,
+		user_id: String,
+		metadata: Value,
+	},
+	/// The sending instance started following a room and needs its members
+	Snapshot { room: String },
+}
+
+impl SyncOp {
+	fn room(&self) -> &str {
+		match self {
+			Self::Join { room, .. }
+			| Self::Leave { room, .. }
+			| Self::Update { room, .. }
+			| Self::Snapshot { room } => room,
+		}
+	}
+}
+
+/// Tracks online users per room and notifies members of changes
+///
+/// Membership is kept in the underlying [`RoomManager`]: joining through
+/// the tracker adds the connection to the room (creating it if needed), and
+/// events are broadcast to the room's connections.
+///
+/// Behind more than one server, give every instance the same channel layer
+/// with [`with_channel_layer`](Self::with_channel_layer). Each instance then
+/// publishes its joins, leaves and updates to the `presence.<room>` group,
+/// and applies the changes of the other instances when
+/// [`sync`](Self::sync) runs, so presence queries and events cover the
+/// users connected to every instance.
+pub struct PresenceTracker {
+	rooms: Arc<RoomManager>,
+	channel_layer: Option<Arc<dyn ChannelLayer>>,
+	instance_id: String,
+	/// room ID -> user ID -> presence
+	presence: RwLock<HashMap<String, HashMap<String, PresenceEntry>>>,
+	/// local connection ID -> (room ID, user ID) memberships
+	connections: RwLock<HashMap<String, Vec<(String, String)>>>,
+	/// rooms whose `presence.<room>` group this instance is subscribed to
+	followed: RwLock<HashSet<String>>,
+}
+
+impl PresenceTracker {
+	/// Create a presence tracker for the rooms of a room manager
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::presence::PresenceTracker;
+	/// use reinhardt_websockets::room::RoomManager;
+	/// use std::sync::Arc;
+	///
+	/// # tokio_test::block_on(async {
+	/// let tracker = PresenceTracker::new(Arc::new(RoomManager::new()));
+	/// assert_eq!(tracker.count("lobby").await, 0);
+	/// # });
+	/// ```
+	pub fn new(rooms: Arc<RoomManager>) -> Self {
+		Self {
+			rooms,
+			channel_layer: None,
+			instance_id: LOCAL_INSTANCE.to_string(),
+			presence: RwLock::new(HashMap::new()),
+			connections: RwLock::new(HashMap::new()),
+			followed: RwLock::new(HashSet::new()),
+		}
+	}
+
+	/// Share presence with other instances through a channel layer
+	///
+	/// `instance_id` must be unique per application instance; it is the
+	/// channel this instance receives the other instances' changes on.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::presence::PresenceTracker;
+	/// use reinhardt_websockets::room::RoomManager;
+	/// use reinhardt_websockets::InMemoryChannelLayer;
+	/// use std::sync::Arc;
+	///
+	/// let tracker = PresenceTracker::new(Arc::new(RoomManager::new()))
+	///     .with_channel_layer(Arc::new(InMemoryChannelLayer::new()), "server-1");
+	/// assert_eq!(tracker.instance_id(), "server-1");
+	/// ```
+	pub fn with_channel_layer(
+		mut self,
+		layer: Arc<dyn ChannelLayer>,
+		instance_id: impl Into<String>,
+	) -> Self {
+		self.channel_layer = Some(layer);
+		self.instance_id = instance_id.into();
+		self
+	}
+
+	/// Get the ID this instance is known by to the other instances
+	pub fn instance_id(&self) -> &str {
+		&self.instance_id
+	}
+
+	/// Get the channel layer group presence changes for a room are published to
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::presence::PresenceTracker;
+	///
+	/// assert_eq!(PresenceTracker::group_name("chat"), "presence.chat");
+	/// ```
+	pub fn group_name(room_id: &str) -> String {
+		format!("presence.{}", room_id)
+	}
+
+	/// Add a user's connection to a room and mark the user as present
+	///
+	/// The connection joins the room, which is created if it does not
+	/// exist. If this is the user's first connection in the room, the other
+	/// members receive a [`PresenceEvent::PresenceJoin`]; otherwise the
+	/// existing presence is kept and `metadata` is ignored.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::presence::PresenceTracker;
+	/// use reinhardt_websockets::room::RoomManager;
+	/// use reinhardt_websockets::{Message, WebSocketConnection};
+	/// use serde_json::json;
+	/// use std::sync::Arc;
+	/// use tokio::sync::mpsc;
+	///
+	/// # tokio_test::block_on(async {
+	/// let tracker = PresenceTracker::new(Arc::new(RoomManager::new()));
+	///
+	/// let (alice_tx, mut alice_rx) = mpsc::unbounded_channel();
+	/// let alice = Arc::new(WebSocketConnection::new("a1".to_string(), alice_tx));
+	/// tracker.join("chat", "alice", alice, json!({})).await.unwrap();
+	///
+	/// let (bob_tx, _bob_rx) = mpsc::unbounded_channel();
+	/// let bob = Arc::new(WebSocketConnection::new("b1".to_string(), bob_tx));
+	/// tracker.join("chat", "bob", bob, json!({})).await.unwrap();
+	///
+	/// // Alice is told that Bob came online
+	/// match alice_rx.recv().await.unwrap() {
+	///     Message::Text { data } => assert!(data.contains("presence_join")),
+	///     _ => panic!("Expected text message"),
+	/// }
+	/// # });
+	/// ```
+	pub async fn join(
+		&self,
+		room_id: &str,
+		user_id: &str,
+		connection: Arc<WebSocketConnection>,
+		metadata: Value,
+	) -> PresenceResult<PresenceInfo> {
+		let connection_id = connection.id().to_string();
+		let room = self.rooms.get_or_create_room(room_id.to_string()).await;
+		room.join(connection_id.clone(), connection).await?;
+		self.follow(room_id).await?;
+
+		let key = (self.instance_id.clone(), connection_id.clone());
+		let (info, came_online) = self
+			.add_connection(room_id, user_id, key, metadata, Utc::now())
+			.await;
+
+		self.connections
+			.write()
+			.await
+			.entry(connection_id.clone())
+			.or_default()
+			.push((room_id.to_string(), user_id.to_string()));
+
+		if came_online {
+			let event = PresenceEvent::PresenceJoin {
+				room: room_id.to_string(),
+				user: info.clone(),
+			};
+			self.notify(&event, Some(&connection_id)).await?;
+		}
+		self.broadcast(SyncOp::Join {
+			room: room_id.to_string(),
+			user_id: user_id.to_string(),
+			connection: connection_id,
+			metadata: info.metadata.clone(),
+			joined_at: info.joined_at,
+		})
+		.await?;
+
+		Ok(info)
+	}
+
+	/// Remove a connection from a room
+	///
+	/// When it was the user's last connection in the room, the user is no
+	/// longer present and the remaining members receive a
+	/// [`PresenceEvent::PresenceLeave`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::presence::PresenceTracker;
+	/// use reinhardt_websockets::room::RoomManager;
+	/// use reinhardt_websockets::WebSocketConnection;
+	/// use serde_json::json;
+	/// use std::sync::Arc;
+	/// use tokio::sync::mpsc;
+	///
+	/// # tokio_test::block_on(async {
+	/// let tracker = PresenceTracker::new(Arc::new(RoomManager::new()));
+	/// let (tx, _rx) = mpsc::unbounded_channel();
+	/// let conn = Arc::new(WebSocketConnection::new("c1".to_string(), tx));
+	///
+	/// tracker.join("chat", "alice", conn, json!({})).await.unwrap();
+	/// tracker.leave("chat", "c1").await.unwrap();
+	///
+	/// assert!(!tracker.is_online("chat", "alice").await);
+	/// # });
+	/// ```
+	pub async fn leave(&self, room_id: &str, connection_id: &str) -> PresenceResult<()> {
+		let user_id = {
+			let mut connections = self.connections.write().await;
+			let memberships = connections
+				.get_mut(connection_id)
+				.ok_or_else(|| PresenceError::NotPresent(connection_id.to_string()))?;
+			let position = memberships
+				.iter()
+				.position(|(room, _)| room == room_id)
+				.ok_or_else(|| PresenceError::NotPresent(connection_id.to_string()))?;
+			let (_, user_id) = memberships.remove(position);
+			if memberships.is_empty() {
+				connections.remove(connection_id);
+			}
+			user_id
+		};
+
+		if let Some(room) = self.rooms.get_room(room_id).await {
+			// The room may already have dropped a connection that failed
+			match room.leave(connection_id).await {
+				Ok(()) | Err(RoomError::ClientNotFound(_)) => {}
+				Err(e) => return Err(e.into()),
+			}
+		}
+
+		let key = (self.instance_id.clone(), connection_id.to_string());
+		if self.remove_connection(room_id, &user_id, &key).await {
+			let event = PresenceEvent::PresenceLeave {
+				room: room_id.to_string(),
+				user_id: user_id.clone(),
+			};
+			self.notify(&event, None).await?;
+		}
+		self.broadcast(SyncOp::Leave {
+			room: room_id.to_string(),
+			user_id,
+			connection: connection_id.to_string(),
+		})
+		.await
+	}
+
+	/// Remove a connection from every room it joined
+	///
+	/// Call this when a connection closes.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::presence::PresenceTracker;
+	/// use reinhardt_websockets::room::RoomManager;
+	/// use reinhardt_websockets::WebSocketConnection;
+	/// use serde_json::json;
+	/// use std::sync::Arc;
+	/// use tokio::sync::mpsc;
+	///
+	/// # tokio_test::block_on(async {
+	/// let tracker = PresenceTracker::new(Arc::new(RoomManager::new()));
+	/// let (tx, _rx) = mpsc::unbounded_channel();
+	/// let conn = Arc::new(WebSocketConnection::new("c1".to_string(), tx));
+	///
+	/// tracker.join("chat", "alice", conn.clone(), json!({})).await.unwrap();
+	/// tracker.join("games", "alice", conn, json!({})).await.unwrap();
+	///
+	/// tracker.disconnect("c1").await.unwrap();
+	/// assert!(tracker.rooms_for("alice").await.is_empty());
+	/// # });
+	/// ```
+	pub async fn disconnect(&self, connection_id: &str) -> PresenceResult<()> {
+		let rooms: Vec<String> = self
+			.connections
+			.read()
+			.await
+			.get(connection_id)
+			.map(|memberships| memberships.iter().map(|(room, _)| room.clone()).collect())
+			.unwrap_or_default();
+
+		for room_id in rooms {
+			self.leave(&room_id, connection_id).await?;
+		}
+		Ok(())
+	}
+
+	/// Replace a present user's metadata and notify the room
+	///
+	/// Members receive a [`PresenceEvent::PresenceUpdate`].
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::presence::PresenceTracker;
+	/// use reinhardt_websockets::room::RoomManager;
+	/// use reinhardt_websockets::WebSocketConnection;
+	/// use serde_json::json;
+	/// use std::sync::Arc;
+	/// use tokio::sync::mpsc;
+	///
+	/// # tokio_test::block_on(async {
+	/// let tracker = PresenceTracker::new(Arc::new(RoomManager::new()));
+	/// let (tx, _rx) = mpsc::unbounded_channel();
+	/// let conn = Arc::new(WebSocketConnection::new("c1".to_string(), tx));
+	///
+	/// tracker.join("chat", "alice", conn, json!({"status": "online"})).await.unwrap();
+	/// tracker
+	///     .update_metadata("chat", "alice", json!({"status": "away"}))
+	///     .await
+	///     .unwrap();
+	///
+	/// let alice = tracker.get("chat", "alice").await.unwrap();
+	/// assert_eq!(alice.metadata["status"], "away");
+	/// # });
+	/// ```
+	pub async fn update_metadata(
+		&self,
+		room_id: &str,
+		user_id: &str,
+		metadata: Value,
+	) -> PresenceResult<PresenceInfo> {
+		let info = self
+			.replace_metadata(room_id, user_id, metadata.clone())
+			.await
+			.ok_or_else(|| PresenceError::NotPresent(user_id.to_string()))?;
+
+		let event = PresenceEvent::PresenceUpdate {
+			room: room_id.to_string(),
+			user: info.clone(),
+		};
+		self.notify(&event, None).await?;
+		self.broadcast(SyncOp::Update {
+			room: room_id.to_string(),
+			user_id: user_id.to_string(),
+			metadata,
+		})
+		.await?;
+
+		Ok(info)
+	}
+
+	/// Apply the presence changes published by other instances
+	///
+	/// Drains this instance's channel on the channel layer, updating the
+	/// presence of remote users and notifying local room members of their
+	/// joins, leaves and updates. Returns the number of changes applied.
+	/// Without a channel layer this does nothing.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::presence::PresenceTracker;
+	/// use reinhardt_websockets::room::RoomManager;
+	/// use reinhardt_websockets::{ChannelLayer, InMemoryChannelLayer, WebSocketConnection};
+	/// use serde_json::json;
+	/// use std::sync::Arc;
+	/// use tokio::sync::mpsc;
+	///
+	/// # tokio_test::block_on(async {
+	/// let layer: Arc<dyn ChannelLayer> = Arc::new(InMemoryChannelLayer::new());
+	/// let server_1 = PresenceTracker::new(Arc::new(RoomManager::new()))
+	///     .with_channel_layer(layer.clone(), "server-1");
+	/// let server_2 = PresenceTracker::new(Arc::new(RoomManager::new()))
+	///     .with_channel_layer(layer, "server-2");
+	///
+	/// let (tx, _rx) = mpsc::unbounded_channel();
+	/// let bob = Arc::new(WebSocketConnection::new("b1".to_string(), tx));
+	/// server_2.join("chat", "bob", bob, json!({})).await.unwrap();
+	///
+	/// let (tx, _rx) = mpsc::unbounded_channel();
+	/// let alice = Arc::new(WebSocketConnection::new("a1".to_string(), tx));
+	/// server_1.join("chat", "alice", alice, json!({})).await.unwrap();
+	///
+	/// // Server 2 answers server 1's request for the room's members
+	/// server_2.sync().await.unwrap();
+	/// server_1.sync().await.unwrap();
+	/// assert!(server_1.is_online("chat", "bob").await);
+	/// assert!(server_2.is_online("chat", "alice").await);
+	/// # });
+	/// ```
+	pub async fn sync(&self) -> PresenceResult<usize> {
+		let Some(layer) = &self.channel_layer else {
+			return Ok(0);
+		};
+
+		let mut applied = 0;
+		while let Some(message) = layer.receive(&self.instance_id).await? {
+			// Group messages are delivered to their sender too
+			if message.sender() == self.instance_id {
+				continue;
+			}
+			let op: SyncOp = message.payload().parse_json()?;
+			self.apply(message.sender(), op).await?;
+			applied += 1;
+		}
+		Ok(applied)
+	}
+
+	/// Run [`sync`](Self::sync) every `period` on a background task
+	///
+	/// The task runs until the returned handle is aborted. Failed syncs are
+	/// retried on the next tick.
+	pub fn spawn_sync(self: Arc<Self>, period: Duration) -> JoinHandle<()> {
+		tokio::spawn(async move {
+			let mut interval = tokio::time::interval(period.max(Duration::from_millis(1)));
+			interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+			loop {
+				interval.tick().await;
+				let _ = self.sync().await;
+			}
+		})
+	}
+
+	/// List the users present in a room, ordered by user ID
+	pub async fn list(&self, room_id: &str) -> Vec<PresenceInfo> {
+		let presence = self.presence.read().await;
+		let mut users: Vec<PresenceInfo> = presence
+			.get(room_id)
+			.map(|users| {
+				users
+					.iter()
+					.map(|(user_id, entry)| entry.info(user_id))
+					.collect()
+			})
+			.unwrap_or_default();
+		users.sort_by(|a, b| a.user_id.cmp(&b.user_id));
+		users
+	}
+
+	/// Get a user's presence in a room
+	pub async fn get(&self, room_id: &str, user_id: &str) -> Option<PresenceInfo> {
+		let presence = self.presence.read().await;
+		presence
+			.get(room_id)
+			.and_then(|users| users.get(user_id))
+			.map(|entry| entry.info(user_id))
+	}
+
+	/// Check whether a user is present in a room
+	pub async fn is_online(&self, room_id: &str, user_id: &str) -> bool {
+		let presence = self.presence.read().await;
+		presence
+			.get(room_id)
+			.is_some_and(|users| users.contains_key(user_id))
+	}
+
+	/// Get the number of users present in a room
+	pub async fn count(&self, room_id: &str) -> usize {
+		let presence = self.presence.read().await;
+		presence.get(room_id).map_or(0, HashMap::len)
+	}
+
+	/// List the rooms a user is present in, sorted
+	pub async fn rooms_for(&self, user_id: &str) -> Vec<String> {
+		let presence = self.presence.read().await;
+		let mut rooms: Vec<String> = presence
+			.iter()
+			.filter(|(_, users)| users.contains_key(user_id))
+			.map(|(room_id, _)| room_id.clone())
+			.collect();
+		rooms.sort();
+		rooms
+	}
+
+	/// Apply a change published by `instance`
+	async fn apply(&self, instance: &str, op: SyncOp) -> PresenceResult<()> {
+		match op {
+			SyncOp::Join {
+				room,
+				user_id,
+				connection,
+				metadata,
+				joined_at,
+			} => {
+				let key = (instance.to_string(), connection);
+				let (info, came_online) = self
+					.add_connection(&room, &user_id, key, metadata, joined_at)
+					.await;
+				if came_online {
+					self.notify(&PresenceEvent::PresenceJoin { room, user: info }, None)
+						.await?;
+				}
+			}
+			SyncOp::Leave {
+				room,
+				user_id,
+				connection,
+			} => {
+				let key = (instance.to_string(), connection);
+				if self.remove_connection(&room, &user_id, &key).await {
+					self.notify(&PresenceEvent::PresenceLeave { room, user_id }, None)
+						.await?;
+				}
+			}
+			SyncOp::Update {
+				room,
+				user_id,
+				metadata,
+			} => {
+				if let Some(info) = self.replace_metadata(&room, &user_id, metadata).await {
+					self.notify(&PresenceEvent::PresenceUpdate { room, user: info }, None)
+						.await?;
+				}
+			}
+			SyncOp::Snapshot { room } => self.send_snapshot(instance, &room).await?,
+		}
+		Ok(())
+	}
+
+	/// Record a connection of a user, returning whether the user came online
+	async fn add_connection(
+		&self,
+		room_id: &str,
+		user_id: &str,
+		key: ConnectionKey,
+		metadata: Value,
+		joined_at: DateTime<Utc>,
+	) -> (PresenceInfo, bool) {
+		let mut presence = self.presence.write().await;
+		let users = presence.entry(room_id.to_string()).or_default();
+		let came_online = !users.contains_key(user_id);
+		let entry = users
+			.entry(user_id.to_string())
+			.or_insert_with(|| PresenceEntry {
+				metadata,
+				joined_at,
+				connections: BTreeSet::new(),
+			});
+		entry.joined_at = entry.joined_at.min(joined_at);
+		entry.connections.insert(key);
+		(entry.info(user_id), came_online)
+	}
+
+	/// Forget a connection of a user, returning whether the user went offline
+	async fn remove_connection(&self, room_id: &str, user_id: &str, key: &ConnectionKey) -> bool {
+		let mut presence = self.presence.write().await;
+		let Some(users) = presence.get_mut(room_id) else {
+			return false;
+		};
+		let went_offline = match users.get_mut(user_id) {
+			Some(entry) => entry.connections.remove(key) && entry.connections.is_empty(),
+			None => false,
+		};
+		if went_offline {
+			users.remove(user_id);
+		}
+		if users.is_empty() {
+			presence.remove(room_id);
+		}
+		went_offline
+	}
+
+	async fn replace_metadata(
+		&self,
+		room_id: &str,
+		user_id: &str,
+		metadata: Value,
+	) -> Option<PresenceInfo> {
+		let mut presence = self.presence.write().await;
+		let entry = presence.get_mut(room_id)?.get_mut(user_id)?;
+		entry.metadata = metadata;
+		Some(entry.info(user_id))
+	}
+
+	/// Subscribe to a room's changes and ask the other instances for its members
+	async fn follow(&self, room_id: &str) -> PresenceResult<()> {
+		let Some(layer) = &self.channel_layer else {
+			return Ok(());
+		};
+		if !self.followed.write().await.insert(room_id.to_string()) {
+			return Ok(());
+		}
+
+		layer
+			.group_add(&Self::group_name(room_id), &self.instance_id)
+			.await?;
+		self.broadcast(SyncOp::Snapshot {
+			room: room_id.to_string(),
+		})
+		.await
+	}
+
+	/// Send the local connections in a room to an instance that asked for them
+	async fn send_snapshot(&self, instance: &str, room_id: &str) -> PresenceResult<()> {
+		let Some(layer) = &self.channel_layer else {
+			return Ok(());
+		};
+
+		let ops: Vec<SyncOp> = {
+			let presence = self.presence.read().await;
+			let Some(users) = presence.get(room_id) else {
+				return Ok(());
+			};
+			users
+				.iter()
+				.flat_map(|(user_id, entry)| {
+					entry
+						.connections
+						.iter()
+						.filter(|(owner, _)| *owner == self.instance_id)
+						.map(|(_, connection)| SyncOp::Join {
+							room: room_id.to_string(),
+							user_id: user_id.clone(),
+							connection: connection.clone(),
+							metadata: entry.metadata.clone(),
+							joined_at: entry.joined_at,
+						})
+				})
+				.collect()
+		};
+
+		for op in ops {
+			let message = ChannelMessage::new(self.instance_id.clone(), Message::json(&op)?);
+			layer.send(instance, message).await?;
+		}
+		Ok(())
+	}
+
+	/// Publish a change to the other instances following the room
+	async fn broadcast(&self, op: SyncOp) -> PresenceResult<()> {
+		let Some(layer) = &self.channel_layer else {
+			return Ok(());
+		};
+
+		let message = ChannelMessage::new(self.instance_id.clone(), Message::json(&op)?);
+		match layer
+			.group_send(&Self::group_name(op.room()), message)
+			.await
+		{
+			// No instance follows the room
+			Ok(()) | Err(ChannelError::GroupNotFound(_)) => Ok(()),
+			Err(e) => Err(e.into()),
+		}
+	}
+
+	/// Deliver an event to the room's local connections
+	async fn notify(&self, event: &PresenceEvent, exclude: Option<&str>) -> PresenceResult<()> {
+		let message = Message::json(event)?;
+
+		if let Some(room) = self.rooms.get_room(event.room()).await {
+			for client_id in room.client_ids().await {
+				if Some(client_id.as_str()) == exclude {
+					continue;
+				}
+				// Members that disconnected meanwhile are cleaned up by `leave`
+				let _ = room.send_to(&client_id, message.clone()).await;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::channels::InMemoryChannelLayer;
+	use rstest::rstest;
+	use serde_json::json;
+	use tokio::sync::mpsc;
+
+	fn connection(id: &str) -> (Arc<WebSocketConnection>, mpsc::UnboundedReceiver<Message>) {
+		let (tx, rx) = mpsc::unbounded_channel();
+		(Arc::new(WebSocketConnection::new(id.to_string(), tx)), rx)
+	}
+
+	fn next_event(rx: &mut mpsc::UnboundedReceiver<Message>) -> PresenceEvent {
+		rx.try_recv().unwrap().parse_json().unwrap()
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_join_notifies_other_members() {
+		let tracker = PresenceTracker::new(Arc::new(RoomManager::new()));
+		let (alice, mut alice_rx) = connection("a1");
+		let (bob, mut bob_rx) = connection("b1");
+
+		tracker
+			.join("chat", "alice", alice, json!({"name": "Alice"}))
+			.await
+			.unwrap();
+		tracker
+			.join("chat", "bob", bob, json!({"name": "Bob"}))
+			.await
+			.unwrap();
+
+		match next_event(&mut alice_rx) {
+			PresenceEvent::PresenceJoin { room, user } => {
+				assert_eq!(room, "chat");
+				assert_eq!(user.user_id, "bob");
+				assert_eq!(user.metadata, json!({"name": "Bob"}));
+			}
+			other => panic!("Expected join event, got {:?}", other),
+		}
+		// The joining connection is not told about itself
+		assert!(bob_rx.try_recv().is_err());
+		assert_eq!(tracker.count("chat").await, 2);
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_user_stays_online_until_last_connection_leaves() {
+		let tracker = PresenceTracker::new(Arc::new(RoomManager::new()));
+		let (observer, mut observer_rx) = connection("o1");
+		let (tab1, _tab1_rx) = connection("t1");
+		let (tab2, _tab2_rx) = connection("t2");
+		tracker
+			.join("doc", "observer", observer, json!({}))
+			.await
+			.unwrap();
+
+		tracker.join("doc", "alice", tab1, json!({})).await.unwrap();
+		let info = tracker.join("doc", "alice", tab2, json!({})).await.unwrap();
+		assert_eq!(info.connections, 2);
+		assert!(matches!(
+			next_event(&mut observer_rx),
+			PresenceEvent::PresenceJoin { .. }
+		));
+		assert!(observer_rx.try_recv().is_err());
+
+		tracker.leave("doc", "t1").await.unwrap();
+		assert!(tracker.is_online("doc", "alice").await);
+		assert!(observer_rx.try_recv().is_err());
+
+		tracker.leave("doc", "t2").await.unwrap();
+		assert!(!tracker.is_online("doc", "alice").await);
+		assert_eq!(
+			next_event(&mut observer_rx),
+			PresenceEvent::PresenceLeave {
+				room: "doc".to_string(),
+				user_id: "alice".to_string(),
+			}
+		);
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_update_metadata_broadcasts_update() {
+		let tracker = PresenceTracker::new(Arc::new(RoomManager::new()));
+		let (alice, mut alice_rx) = connection("a1");
+		tracker
+			.join("chat", "alice", alice, json!({"status": "online"}))
+			.await
+			.unwrap();
+
+		tracker
+			.update_metadata("chat", "alice", json!({"status": "typing"}))
+			.await
+			.unwrap();
+
+		match next_event(&mut alice_rx) {
+			PresenceEvent::PresenceUpdate { user, .. } => {
+				assert_eq!(user.metadata["status"], "typing");
+			}
+			other => panic!("Expected update event, got {:?}", other),
+		}
+		let result = tracker.update_metadata("chat", "bob", json!({})).await;
+		assert!(matches!(result, Err(PresenceError::NotPresent(_))));
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_disconnect_leaves_every_room() {
+		let rooms = Arc::new(RoomManager::new());
+		let tracker = PresenceTracker::new(rooms.clone());
+		let (conn, _rx) = connection("c1");
+		tracker
+			.join("chat", "alice", conn.clone(), json!({}))
+			.await
+			.unwrap();
+		tracker
+			.join("games", "alice", conn, json!({}))
+			.await
+			.unwrap();
+		assert_eq!(tracker.rooms_for("alice").await, vec!["chat", "games"]);
+
+		tracker.disconnect("c1").await.unwrap();
+
+		assert!(tracker.rooms_for("alice").await.is_empty());
+		assert_eq!(rooms.get_room_size("chat").await, 0);
+		assert!(matches!(
+			tracker.leave("chat", "c1").await,
+			Err(PresenceError::NotPresent(_))
+		));
+	}
+
+	#[rstest]
+	#[tokio::test]
+	async fn test_presence_is_shared_through_channel_layer() {
+		let layer: Arc<dyn ChannelLayer> = Arc::new(InMemoryChannelLayer::new());
+		let server_1 = PresenceTracker::new(Arc::new(RoomManager::new()))
+			.with_channel_layer(layer.clone(), "server-1");
+		let server_2 = PresenceTracker::new(Arc::new(RoomManager::new()))
+			.with_channel_layer(layer, "server-2");
+		let (alice, mut alice_rx) = connection("c1");
+		let (bob, _bob_rx) = connection("c1");
+
+		server_1
+			.join("chat", "alice", alice, json!({"name": "Alice"}))
+			.await
+			.unwrap();
+		server_2
+			.join("chat", "bob", bob, json!({"name": "Bob"}))
+			.await
+			.unwrap();
+
+		// Server 1 learns of Bob; server 2 gets Alice from server 1's snapshot
+		assert_eq!(server_1.sync().await.unwrap(), 2);
+		assert_eq!(server_2.sync().await.unwrap(), 1);
+		for tracker in [&server_1, &server_2] {
+			let users: Vec<String> = tracker
+				.list("chat")
+				.await
+				.into_iter()
+				.map(|user| user.user_id)
+				.collect();
+			assert_eq!(users, vec!["alice", "bob"]);
+		}
+		match next_event(&mut alice_rx) {
+			PresenceEvent::PresenceJoin { user, .. } => assert_eq!(user.metadata["name"], "Bob"),
+			other => panic!("Expected join event, got {:?}", other),
+		}
+
+		server_2
+			.update_metadata("chat", "bob", json!({"name": "Robert"}))
+			.await
+			.unwrap();
+		server_1.sync().await.unwrap();
+		assert_eq!(
+			server_1.get("chat", "bob").await.unwrap().metadata["name"],
+			"Robert"
+		);
+		assert!(matches!(
+			next_event(&mut alice_rx),
+			PresenceEvent::PresenceUpdate { .. }
+		));
+
+		// Connection IDs only need to be unique per instance
+		server_2.leave("chat", "c1").await.unwrap();
+		server_1.sync().await.unwrap();
+		assert!(!server_1.is_online("chat", "bob").await);
+		assert!(server_1.is_online("chat", "alice").await);
+		assert_eq!(
+			next_event(&mut alice_rx),
+			PresenceEvent::PresenceLeave {
+				room: "chat".to_string(),
+				user_id: "bob".to_string(),
+			}
+		);
+	}
+}
@@ -760,6 +760,47 @@ impl RoomManager {
 		room.leave(user_id).await
 	}
 
+	/// Remove a client from every room it has joined
+	///
+	/// Returns the IDs of the rooms the client was removed from.
+	///
+	/// # Examples
+	///
+	/// ```
+	/// use reinhardt_websockets::room::RoomManager;
+	/// use reinhardt_websockets::WebSocketConnection;
+	/// use tokio::sync::mpsc;
+	/// use std::sync::Arc;
+	///
+	/// # tokio_test::block_on(async {
+	/// let manager = RoomManager::new();
+	/// manager.create_room("chat".to_string()).await;
+	/// manager.create_room("lobby".to_string()).await;
+	///
+	/// let (tx, _rx) = mpsc::unbounded_channel();
+	/// let conn = Arc::new(WebSocketConnection::new("user1".to_string(), tx));
+	///
+	/// manager.join_room("chat".to_string(), conn.clone()).await.unwrap();
+	/// manager.join_room("lobby".to_string(), conn).await.unwrap();
+	///
+	/// let mut left = manager.leave_all_rooms("user1").await;
+	/// left.sort();
+	/// assert_eq!(left, vec!["chat".to_string(), "lobby".to_string()]);
+	/// assert_eq!(manager.get_room_size("chat").await, 0);
+	/// # });
+	/// ```
+	pub async fn leave_all_rooms(&self, client_id: &str) -> Vec<String> {
+		let rooms: Vec<Arc<Room>> = self.rooms.read().await.values().cloned().collect();
+
+		let mut left = Vec::new();
+		for room in rooms {
+			if room.leave(client_id).await.is_ok() {
+				left.push(room.id().to_string());
+			}
+		}
+		left
+	}
+
 	/// Get the number of clients in a specific room
 	///
 	/// # Examples
@@ -991,6 +1032,37 @@ mod tests {
 		assert!(matches!(rx3.try_recv(), Ok(Message::Text { .. })));
 	}
 
+	#[tokio::test]
+	async fn test_room_broadcast_removes_overflowing_client() {
+		use crate::backpressure::{OverflowPolicy, SendQueueConfig};
+
+		let room = Room::new("backpressure_test".to_string());
+
+		let (tx, mut rx) = mpsc::unbounded_channel();
+		let fast = Arc::new(WebSocketConnection::new("fast".to_string(), tx));
+		let config = SendQueueConfig::default()
+			.with_max_messages(2)
+			.with_overflow_policy(OverflowPolicy::Close);
+		let (slow, _slow_rx) = WebSocketConnection::with_send_queue("slow".to_string(), config);
+		let slow = Arc::new(slow);
+
+		room.join("fast".to_string(), fast).await.unwrap();
+		room.join("slow".to_string(), slow.clone()).await.unwrap();
+
+		for i in 0..3 {
+			room.broadcast(Message::text(format!("tick {}", i)))
+				.await
+				.unwrap();
+		}
+
+		assert!(slow.is_closed().await);
+		assert!(!room.has_client("slow").await);
+		assert!(room.has_client("fast").await);
+		for _ in 0..3 {
+			assert!(matches!(rx.try_recv(), Ok(Message::Text { .. })));
+		}
+	}
+
 	#[tokio::test]
 	async fn test_room_send_to_specific_client() {
 		let room = Room::new("private_msg_test".to_string());
@@ -0,0 +1,93 @@
+//! Live signal end-to-end tests
+//!
+//! Drives `LiveRoomConsumer` with the frames produced by the
+//! `use_live_signal` client in reinhardt-pages and decodes what the server
+//! publishes with the client's decoder.
+
+#![cfg(feature = "pages-integration")]
+
+use reinhardt_pages::reactive::hooks::live::{
+	decode_live_payload, subscribe_frame, unsubscribe_frame,
+};
+use reinhardt_websockets::consumers::{ConsumerContext, WebSocketConsumer};
+use reinhardt_websockets::live::LiveRoomConsumer;
+use reinhardt_websockets::room::RoomManager;
+use reinhardt_websockets::{Message, WebSocketConnection};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Stats {
+	active: u32,
+}
+
+fn received_text(rx: &mut mpsc::UnboundedReceiver<Message>) -> Option<String> {
+	match rx.try_recv().ok()? {
+		Message::Text { data } => Some(data),
+		other => panic!("Expected text message, got {:?}", other),
+	}
+}
+
+/// Test: a subscribed client receives published values until it unsubscribes
+#[tokio::test]
+async fn test_live_signal_subscription_round_trip() {
+	let consumer = LiveRoomConsumer::new(Arc::new(RoomManager::new()));
+	let (tx, mut rx) = mpsc::unbounded_channel();
+	let conn = Arc::new(WebSocketConnection::new("client_1".to_string(), tx));
+	let mut context = ConsumerContext::new(conn);
+
+	consumer
+		.on_message(&mut context, Message::text(subscribe_frame("dash")))
+		.await
+		.unwrap();
+	consumer
+		.publish("dash", &Stats { active: 3 })
+		.await
+		.unwrap();
+	consumer
+		.publish("other", &Stats { active: 9 })
+		.await
+		.unwrap();
+
+	let text = received_text(&mut rx).unwrap();
+	assert_eq!(
+		decode_live_payload::<Stats>(&text, Some("dash")),
+		Ok(Some(Stats { active: 3 }))
+	);
+	assert!(received_text(&mut rx).is_none());
+
+	consumer
+		.on_message(&mut context, Message::text(unsubscribe_frame("dash")))
+		.await
+		.unwrap();
+	consumer
+		.publish("dash", &Stats { active: 4 })
+		.await
+		.unwrap();
+
+	assert!(received_text(&mut rx).is_none());
+}
+
+/// Test: re-subscribing after a reconnect does not duplicate deliveries
+#[tokio::test]
+async fn test_live_signal_resubscribe_after_reconnect() {
+	let consumer = LiveRoomConsumer::new(Arc::new(RoomManager::new()));
+	let (tx, mut rx) = mpsc::unbounded_channel();
+	let conn = Arc::new(WebSocketConnection::new("client_1".to_string(), tx));
+	let mut context = ConsumerContext::new(conn);
+
+	for _ in 0..2 {
+		consumer
+			.on_message(&mut context, Message::text(subscribe_frame("dash")))
+			.await
+			.unwrap();
+	}
+	consumer
+		.publish("dash", &Stats { active: 5 })
+		.await
+		.unwrap();
+
+	assert!(received_text(&mut rx).is_some());
+	assert!(received_text(&mut rx).is_none());
+}
@@ -158,6 +158,12 @@ pub mod linkme {
 	pub use linkme::*;
 }
 
+#[cfg(all(feature = "cache", not(target_arch = "wasm32")))]
+#[doc(hidden)]
+pub mod reinhardt_utils {
+	pub use reinhardt_utils::*;
+}
+
 #[cfg(all(feature = "database", not(target_arch = "wasm32")))]
 #[doc(hidden)]
 pub mod reinhardt_orm {
@@ -813,7 +819,7 @@ pub use reinhardt_db::migrations::{
 
 // Re-export cache (cache feature)
 #[cfg(all(feature = "cache", not(target_arch = "wasm32")))]
-pub use reinhardt_utils::cache::{Cache, CacheKeyBuilder, InMemoryCache};
+pub use reinhardt_utils::cache::{Cache, CacheKeyBuilder, InMemoryCache, cached};
 
 // Cache middleware is in reinhardt-middleware
 #[cfg(all(feature = "middleware", not(target_arch = "wasm32")))]
@@ -837,7 +843,7 @@ pub use reinhardt_auth::sessions::{
 	feature = "middleware",
 	not(target_arch = "wasm32")
 ))]
-pub use reinhardt_auth::sessions::{HttpSessionConfig, SameSite, SessionMiddleware};
+pub use reinhardt_auth::sessions::{HttpSessionConfig, LazySession, SameSite, SessionMiddleware};
 
 // Re-export contrib modules (contrib feature)
 // Note: reinhardt_contrib exports individual modules (auth, sessions, etc.)
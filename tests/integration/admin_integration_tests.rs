@@ -541,6 +541,7 @@ async fn test_export_import_server_functions_structure(
 		model_name.clone(),
 		ImportFormat::Json,
 		vec![], // Empty data
+		false,
 		site.clone(),
 		db.clone(),
 	)
@@ -1,6 +1,26 @@
-fn main() {
+use reinhardt_grpc::codegen::{CrudCodegen, ProtoModel, ProtoType};
+
+fn main() -> std::io::Result<()> {
 	// Declare custom cfg features to avoid "unexpected cfg" warnings
 	println!(
 		"cargo:rustc-check-cfg=cfg(feature, values(\"hot-reload\", \"caching\", \"source-maps\", \"image-optimization\", \"graphql\"))"
 	);
+
+	// Generate the CRUD service fixture so the scaffold rendered by
+	// `CrudCodegen` is compiled and exercised by the gRPC tests
+	CrudCodegen::new("crud_fixture")
+		.model(
+			ProtoModel::new("Article")
+				.primary_key("id", ProtoType::Int64)
+				.field("title", ProtoType::String)
+				.optional_field("summary", ProtoType::String)
+				.field("published_at", ProtoType::Timestamp),
+		)
+		.model(
+			ProtoModel::new("Category")
+				.with_plural("Categories")
+				.primary_key("slug", ProtoType::String)
+				.field("name", ProtoType::String),
+		)
+		.compile()
 }
@@ -29,6 +29,12 @@ pub mod migration_duplicate;
 pub mod migrations;
 pub mod validator_test_common;
 
+/// CRUD services generated by `reinhardt_grpc::codegen` in `build.rs`
+pub mod grpc_crud {
+	tonic::include_proto!("crud_fixture");
+	include!(concat!(env!("OUT_DIR"), "/crud_fixture.crud.rs"));
+}
+
 /// Test database setup using TestContainers
 ///
 /// This function uses the shared PostgreSQL container managed by reinhardt-test
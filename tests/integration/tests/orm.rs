@@ -30,3 +30,12 @@ mod proxy_advanced_features;
 
 #[path = "orm/proxy_orm_integration.rs"]
 mod proxy_orm_integration;
+
+#[path = "orm/row_streaming_integration.rs"]
+mod row_streaming_integration;
+
+#[path = "orm/schema_search_path_integration.rs"]
+mod schema_search_path_integration;
+
+#[path = "orm/transient_error_retry_integration.rs"]
+mod transient_error_retry_integration;
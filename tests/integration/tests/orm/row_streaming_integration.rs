@@ -0,0 +1,96 @@
+//! Row Streaming Integration Tests
+//!
+//! Tests `fetch_stream()` on the PostgreSQL backend, covering:
+//! - Rows spanning several cursor batches
+//! - Bound parameters in streamed queries
+//! - Dropping a stream before it is exhausted
+//!
+//! **Fixtures Used:**
+//! - postgres_container: PostgreSQL database container
+
+use futures::{StreamExt, TryStreamExt};
+use reinhardt_db::backends::connection::DatabaseConnection;
+use reinhardt_db::backends::dialect::PostgresBackend;
+use reinhardt_db::backends::types::QueryValue;
+use reinhardt_test::fixtures::postgres_container;
+use rstest::*;
+use sqlx::PgPool;
+use std::sync::Arc;
+use testcontainers::{ContainerAsync, GenericImage};
+
+async fn seed_numbers(pool: &PgPool, count: i64) {
+	sqlx::query("CREATE TABLE numbers (n BIGINT NOT NULL)")
+		.execute(pool)
+		.await
+		.expect("Failed to create table");
+	sqlx::query("INSERT INTO numbers (n) SELECT generate_series(1, $1)")
+		.bind(count)
+		.execute(pool)
+		.await
+		.expect("Failed to insert rows");
+}
+
+/// Test streaming rows across several cursor batches
+///
+/// **Test Intent**: Verify every row is yielded once and in order when the
+/// result set is larger than the cursor batch size
+///
+/// **Integration Point**: DatabaseConnection::fetch_stream → PostgreSQL cursor
+///
+/// **Not Intent**: MySQL/SQLite streaming
+#[rstest]
+#[tokio::test]
+async fn test_fetch_stream_spans_batches(
+	#[future] postgres_container: (ContainerAsync<GenericImage>, Arc<PgPool>, u16, String),
+) {
+	// Arrange
+	let (_container, pool, _port, _url) = postgres_container.await;
+	seed_numbers(&pool, 25).await;
+	let backend = PostgresBackend::new(pool.as_ref().clone()).with_stream_batch_size(10);
+	let conn = DatabaseConnection::new(Arc::new(backend));
+
+	// Act
+	let rows: Vec<i64> = conn
+		.fetch_stream("SELECT n FROM numbers WHERE n > $1 ORDER BY n", vec![QueryValue::Int(3)])
+		.map(|row| row.and_then(|row| row.get::<i64>("n")))
+		.try_collect()
+		.await
+		.expect("Failed to stream rows");
+
+	// Assert
+	assert_eq!(rows, (4..=25).collect::<Vec<i64>>());
+}
+
+/// Test dropping a stream before it is exhausted
+///
+/// **Test Intent**: Verify the cursor transaction is released so the pool
+/// stays usable after a partially consumed stream
+///
+/// **Integration Point**: fetch_stream → transaction rollback on drop
+///
+/// **Not Intent**: Cursor batching
+#[rstest]
+#[tokio::test]
+async fn test_fetch_stream_dropped_early(
+	#[future] postgres_container: (ContainerAsync<GenericImage>, Arc<PgPool>, u16, String),
+) {
+	// Arrange
+	let (_container, pool, _port, _url) = postgres_container.await;
+	seed_numbers(&pool, 100).await;
+	let backend = PostgresBackend::new(pool.as_ref().clone()).with_stream_batch_size(10);
+	let conn = DatabaseConnection::new(Arc::new(backend));
+
+	// Act
+	{
+		let mut stream = conn.fetch_stream("SELECT n FROM numbers ORDER BY n", vec![]);
+		let first = stream.try_next().await.expect("Failed to read row");
+		assert!(first.is_some());
+	}
+	let count = conn
+		.fetch_one("SELECT COUNT(*) AS count FROM numbers", vec![])
+		.await
+		.expect("Failed to query after dropped stream");
+
+	// Assert
+	assert_eq!(count.get::<i64>("count").unwrap(), 100);
+}
@@ -0,0 +1,83 @@
+//! Schema Search Path Integration Tests
+//!
+//! Tests schema-per-tenant support on PostgreSQL, covering:
+//! - Per-connection `search_path` configuration
+//! - Schema-qualified tables in the query builder
+//!
+//! **Fixtures Used:**
+//! - postgres_container: PostgreSQL database container
+
+use reinhardt_db::backends::connection::DatabaseConnection;
+use reinhardt_db::backends::types::QueryValue;
+use reinhardt_test::fixtures::postgres_container;
+use rstest::*;
+use sqlx::PgPool;
+use std::sync::Arc;
+use testcontainers::{ContainerAsync, GenericImage};
+
+async fn create_tenant(pool: &PgPool, schema: &str) {
+	sqlx::query(&format!("CREATE SCHEMA \"{}\"", schema))
+		.execute(pool)
+		.await
+		.expect("Failed to create schema");
+	sqlx::query(&format!(
+		"CREATE TABLE \"{}\".\"notes\" (id BIGINT PRIMARY KEY, body TEXT NOT NULL)",
+		schema
+	))
+	.execute(pool)
+	.await
+	.expect("Failed to create table");
+}
+
+/// Test resolving unqualified tables through the connection search path
+///
+/// **Test Intent**: Verify rows written through a tenant connection land in
+/// the tenant schema and are visible through schema-qualified queries
+///
+/// **Integration Point**: DatabaseConnection::connect_postgres_with_search_path
+/// → SelectBuilder::schema
+///
+/// **Not Intent**: Schema editor DDL generation
+#[rstest]
+#[tokio::test]
+async fn test_search_path_isolates_tenants(
+	#[future] postgres_container: (ContainerAsync<GenericImage>, Arc<PgPool>, u16, String),
+) {
+	// Arrange
+	let (_container, pool, _port, url) = postgres_container.await;
+	create_tenant(&pool, "tenant_a").await;
+	create_tenant(&pool, "tenant_b").await;
+	let tenant_a = DatabaseConnection::connect_postgres_with_search_path(&url, &["tenant_a"])
+		.await
+		.expect("Failed to connect");
+	let admin = DatabaseConnection::connect_postgres(&url)
+		.await
+		.expect("Failed to connect");
+
+	// Act
+	tenant_a
+		.execute(
+			"INSERT INTO notes (id, body) VALUES ($1, $2)",
+			vec![QueryValue::Int(1), QueryValue::String("hello".to_string())],
+		)
+		.await
+		.expect("Failed to insert");
+	let tenant_a_rows = admin
+		.select()
+		.from("notes")
+		.schema("tenant_a")
+		.fetch_all()
+		.await
+		.expect("Failed to query tenant_a");
+	let tenant_b_rows = admin
+		.select()
+		.from("notes")
+		.schema("tenant_b")
+		.fetch_all()
+		.await
+		.expect("Failed to query tenant_b");
+
+	// Assert
+	assert_eq!(tenant_a_rows.len(), 1);
+	assert!(tenant_b_rows.is_empty());
+}
@@ -0,0 +1,83 @@
+//! Transient Error Retry Integration Tests
+//!
+//! Tests transient-error classification and retry on PostgreSQL, covering:
+//! - Deadlocks surfaced as `DatabaseError::Deadlock`
+//! - Retrying a whole transaction with `RetryPolicy::run`
+//!
+//! **Fixtures Used:**
+//! - postgres_container: PostgreSQL database container
+
+use reinhardt_db::backends::DatabaseError;
+use reinhardt_db::backends::connection::DatabaseConnection;
+use reinhardt_db::backends::dialect::PostgresBackend;
+use reinhardt_db::backends::retry::RetryPolicy;
+use reinhardt_db::backends::types::QueryValue;
+use reinhardt_test::fixtures::postgres_container;
+use rstest::*;
+use sqlx::PgPool;
+use std::sync::Arc;
+use testcontainers::{ContainerAsync, GenericImage};
+
+async fn seed_accounts(pool: &PgPool) {
+	sqlx::query("CREATE TABLE accounts (id BIGINT PRIMARY KEY, balance BIGINT NOT NULL)")
+		.execute(pool)
+		.await
+		.expect("Failed to create table");
+	sqlx::query("INSERT INTO accounts (id, balance) VALUES (1, 100), (2, 100)")
+		.execute(pool)
+		.await
+		.expect("Failed to insert rows");
+}
+
+/// Lock `first` then `second` inside one transaction, pausing in between
+async fn transfer(conn: &DatabaseConnection, first: i64, second: i64) -> Result<(), DatabaseError> {
+	let update = "UPDATE accounts SET balance = balance - 1 WHERE id = $1";
+	let mut tx = conn.begin().await?;
+	tx.execute(update, vec![QueryValue::Int(first)]).await?;
+	tx.execute("SELECT pg_sleep(0.2)", vec![]).await?;
+	tx.execute(update, vec![QueryValue::Int(second)]).await?;
+	tx.commit().await
+}
+
+/// Test classifying and retrying a deadlock
+///
+/// **Test Intent**: Verify crossing lock orders raise `DatabaseError::Deadlock`
+/// and that retrying the aborted transaction lets both transfers commit
+///
+/// **Integration Point**: sqlx error → DatabaseError classification → RetryPolicy::run
+///
+/// **Not Intent**: MySQL/SQLite error codes
+#[rstest]
+#[tokio::test]
+async fn test_deadlock_is_classified_and_retried(
+	#[future] postgres_container: (ContainerAsync<GenericImage>, Arc<PgPool>, u16, String),
+) {
+	// Arrange
+	let (_container, pool, _port, _url) = postgres_container.await;
+	seed_accounts(&pool).await;
+	let conn = DatabaseConnection::new(Arc::new(PostgresBackend::new(pool.as_ref().clone())));
+
+	// Act
+	let (first, second) = tokio::join!(transfer(&conn, 1, 2), transfer(&conn, 2, 1));
+	let deadlocked = [&first, &second]
+		.iter()
+		.filter(|result| matches!(result, Err(DatabaseError::Deadlock(_))))
+		.count();
+	let policy = RetryPolicy::new(3);
+	let (retried_first, retried_second) = tokio::join!(
+		policy.run(|| transfer(&conn, 1, 2)),
+		policy.run(|| transfer(&conn, 2, 1))
+	);
+	let total = conn
+		.fetch_one("SELECT SUM(balance)::BIGINT AS total FROM accounts", vec![])
+		.await
+		.expect("Failed to sum balances");
+
+	// Assert
+	assert_eq!(deadlocked, 1);
+	assert!(first.is_ok() || second.is_ok());
+	assert!(retried_first.is_ok());
+	assert!(retried_second.is_ok());
+	// One transfer of the first round and both retried transfers committed
+	assert_eq!(total.get::<i64>("total").unwrap(), 194);
+}